@@ -0,0 +1,162 @@
+//! `plastic bundle`: bundling an entry module and the modules it imports.
+
+use plastic::syntax::{
+  bundle::{bundle, Format, Options},
+  codegen,
+};
+use std::{
+  fs,
+  path::{Path, PathBuf},
+};
+
+const USAGE: &str = "\
+usage: plastic bundle <entry> [options]
+
+options:
+  -o, --out-file <file>  write the bundle to a file instead of the standard output
+  --format <format>      `script` (default) or `iife`
+  --name <name>          the global variable the exports of the entry are assigned to, with `iife`
+  --compact              print the bundle without whitespace
+  --sourcemap            write a source map next to the output file, as `<file>.map`";
+
+/// The command line arguments of `plastic bundle`.
+#[derive(Debug, Default)]
+struct Args {
+  entry: Option<PathBuf>,
+  out_file: Option<PathBuf>,
+  format: Option<Format>,
+  name: Option<String>,
+  compact: bool,
+  sourcemap: bool,
+}
+
+impl Args {
+  /// Parses the arguments following the command.
+  fn parse(args: Vec<String>) -> Result<Self, String> {
+    let mut parsed = Self::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+      let mut value = |option: &str| {
+        args
+          .next()
+          .ok_or_else(|| format!("`{}` needs a value", option))
+      };
+      match arg.as_str() {
+        "-o" | "--out-file" => parsed.out_file = Some(value(&arg)?.into()),
+        "--format" => {
+          let format = value(&arg)?;
+          parsed.format =
+            Some(Format::from_name(&format).ok_or_else(|| format!("unknown format `{}`", format))?);
+        }
+        "--name" => parsed.name = Some(value(&arg)?),
+        "--compact" => parsed.compact = true,
+        "--sourcemap" => parsed.sourcemap = true,
+        option if option.starts_with('-') => return Err(format!("unknown option `{}`", option)),
+        entry if parsed.entry.is_none() => parsed.entry = Some(entry.into()),
+        extra => return Err(format!("unexpected argument `{}`", extra)),
+      }
+    }
+    if parsed.sourcemap && parsed.out_file.is_none() {
+      return Err("`--sourcemap` needs an output file".to_owned());
+    }
+    Ok(parsed)
+  }
+}
+
+/// Runs the command, returning the exit code.
+pub(crate) fn run(args: Vec<String>) -> i32 {
+  if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+    println!("{}", USAGE);
+    return 0;
+  }
+  let args = match Args::parse(args) {
+    Ok(Args { entry: None, .. }) => {
+      eprintln!("plastic bundle: missing the entry module\n\n{}", USAGE);
+      return 2;
+    }
+    Ok(args) => args,
+    Err(message) => {
+      eprintln!("plastic bundle: {}\n\n{}", message, USAGE);
+      return 2;
+    }
+  };
+
+  let mut options = Options::default().with_format(args.format.unwrap_or(Format::Script));
+  if let Some(name) = args.name {
+    options = options.with_name(name);
+  }
+  if args.compact {
+    options = options.with_output(codegen::Options::compact());
+  }
+  // The sources of the map are found from the directory it is written to.
+  let map_file = match &args.out_file {
+    Some(out_file) if args.sourcemap => {
+      let mut map_file = out_file.clone().into_os_string();
+      map_file.push(".map");
+      let map_file = PathBuf::from(map_file);
+      let map_dir = match map_file.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+      };
+      options = options.with_map_dir(map_dir);
+      Some(map_file)
+    }
+    _ => None,
+  };
+  let bundled = match bundle(args.entry.as_ref().expect("the entry is given"), &options) {
+    Ok(bundled) => bundled,
+    Err(err) => {
+      eprintln!("error: {}", err);
+      return 1;
+    }
+  };
+  for cycle in bundled.cycles() {
+    let modules: Vec<_> = cycle
+      .iter()
+      .map(|path| path.display().to_string())
+      .collect();
+    eprintln!("warning: circular imports: {}", modules.join(" -> "));
+  }
+
+  let out_file = match args.out_file {
+    Some(out_file) => out_file,
+    None => {
+      print!("{}", bundled.code());
+      return 0;
+    }
+  };
+  let mut code = bundled.code().to_owned();
+  if let Some(map_file) = map_file {
+    if let Err(code) = write(&map_file, &bundled.source_map().to_json()) {
+      return code;
+    }
+    if !code.ends_with('\n') {
+      code.push('\n');
+    }
+    let name = map_file.file_name().expect("the map file has a name");
+    code.push_str(&format!(
+      "//# sourceMappingURL={}\n",
+      name.to_string_lossy()
+    ));
+  }
+  match write(&out_file, &code) {
+    Ok(()) => 0,
+    Err(code) => code,
+  }
+}
+
+/// Writes a file, creating its directory if needed, returning the exit code on errors.
+fn write(path: &Path, contents: &str) -> Result<(), i32> {
+  let result = match path.parent() {
+    Some(parent) => fs::create_dir_all(parent).and_then(|()| fs::write(path, contents)),
+    None => fs::write(path, contents),
+  };
+  result.map_err(|err| {
+    eprintln!(
+      "error: {}: could not write the file: {}",
+      path.display(),
+      err
+    );
+    1
+  })
+}
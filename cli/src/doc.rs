@@ -0,0 +1,120 @@
+//! `plastic doc`: documenting the exported API of a module from its JSDoc comments.
+
+use plastic::syntax::{comments::CommentMap, jsdoc::ModuleDocs, parser::Parser};
+use std::{fs, path::PathBuf};
+
+const USAGE: &str = "\
+usage: plastic doc <module> [options]
+
+options:
+  -o, --out-file <file>  write the documentation to a file instead of the standard output
+  --format <format>      `markdown` (default) or `json`";
+
+/// The output format of the documentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+  Markdown,
+  Json,
+}
+
+/// The command line arguments of `plastic doc`.
+#[derive(Debug, Default)]
+struct Args {
+  module: Option<PathBuf>,
+  out_file: Option<PathBuf>,
+  format: Option<Format>,
+}
+
+impl Args {
+  /// Parses the arguments following the command.
+  fn parse(args: Vec<String>) -> Result<Self, String> {
+    let mut parsed = Self::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+      let mut value = |option: &str| {
+        args
+          .next()
+          .ok_or_else(|| format!("`{}` needs a value", option))
+      };
+      match arg.as_str() {
+        "-o" | "--out-file" => parsed.out_file = Some(value(&arg)?.into()),
+        "--format" => {
+          parsed.format = match value(&arg)?.as_str() {
+            "markdown" => Some(Format::Markdown),
+            "json" => Some(Format::Json),
+            format => return Err(format!("unknown format `{}`", format)),
+          }
+        }
+        option if option.starts_with('-') => return Err(format!("unknown option `{}`", option)),
+        module if parsed.module.is_none() => parsed.module = Some(module.into()),
+        extra => return Err(format!("unexpected argument `{}`", extra)),
+      }
+    }
+    Ok(parsed)
+  }
+}
+
+/// Runs the command, returning the exit code.
+pub(crate) fn run(args: Vec<String>) -> i32 {
+  if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+    println!("{}", USAGE);
+    return 0;
+  }
+  let args = match Args::parse(args) {
+    Ok(Args { module: None, .. }) => {
+      eprintln!("plastic doc: missing the module\n\n{}", USAGE);
+      return 2;
+    }
+    Ok(args) => args,
+    Err(message) => {
+      eprintln!("plastic doc: {}\n\n{}", message, USAGE);
+      return 2;
+    }
+  };
+
+  let path = args.module.expect("the module is given");
+  let source = match fs::read_to_string(&path) {
+    Ok(source) => source,
+    Err(err) => {
+      eprintln!(
+        "error: {}: could not read the file: {}",
+        path.display(),
+        err
+      );
+      return 1;
+    }
+  };
+  let module = match Parser::new(source.as_bytes()).parse_module() {
+    Ok(module) => module,
+    Err(err) => {
+      eprintln!("error: {}: {}", path.display(), err);
+      return 1;
+    }
+  };
+  let docs = ModuleDocs::new(&module, &CommentMap::module(&source, &module));
+  let mut out = match args.format.unwrap_or(Format::Markdown) {
+    Format::Markdown => docs.to_markdown(),
+    Format::Json => docs.to_json(),
+  };
+  if !out.ends_with('\n') {
+    out.push('\n');
+  }
+
+  match args.out_file {
+    Some(out_file) => match fs::write(&out_file, out) {
+      Ok(()) => 0,
+      Err(err) => {
+        eprintln!(
+          "error: {}: could not write the file: {}",
+          out_file.display(),
+          err
+        );
+        1
+      }
+    },
+    None => {
+      print!("{}", out);
+      0
+    }
+  }
+}
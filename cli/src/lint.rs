@@ -0,0 +1,224 @@
+//! `plastic lint`: checking files with the rules of the linter.
+
+use plastic::syntax::{
+  diagnostics::{Diagnostic, Format, Renderer, Severity},
+  lint::{apply_fixes, Config, Linter, Problem},
+  parser::ParseError,
+};
+use std::{
+  env, fs,
+  path::{Path, PathBuf},
+};
+
+const USAGE: &str = "\
+usage: plastic lint <file>... [options]
+
+options:
+  --fix              apply the fixes of the problems to the files
+  --format <format>  `human` (default) or `json`
+  --config <file>    read the rules from a file instead of the nearest `plastic.toml`
+  --script           parse the files as scripts instead of modules";
+
+/// The name of the configuration file looked for in the current directory and its ancestors.
+const CONFIG_FILE: &str = "plastic.toml";
+
+/// The number of times the fixes are applied to a file, as fixing a problem can reveal another.
+const FIX_PASSES: usize = 10;
+
+/// The command line arguments of `plastic lint`.
+#[derive(Debug, Default)]
+struct Args {
+  files: Vec<PathBuf>,
+  fix: bool,
+  format: Option<Format>,
+  config: Option<PathBuf>,
+  script: bool,
+}
+
+impl Args {
+  /// Parses the arguments following the command.
+  fn parse(args: Vec<String>) -> Result<Self, String> {
+    let mut parsed = Self::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+      let mut value = |option: &str| {
+        args
+          .next()
+          .ok_or_else(|| format!("`{}` needs a value", option))
+      };
+      match arg.as_str() {
+        "--fix" => parsed.fix = true,
+        "--format" => {
+          parsed.format = match value(&arg)?.as_str() {
+            "human" => Some(Format::Human),
+            "json" => Some(Format::Json),
+            format => return Err(format!("unknown format `{}`", format)),
+          }
+        }
+        "--config" => parsed.config = Some(value(&arg)?.into()),
+        "--script" => parsed.script = true,
+        option if option.starts_with('-') => return Err(format!("unknown option `{}`", option)),
+        file => parsed.files.push(file.into()),
+      }
+    }
+    Ok(parsed)
+  }
+}
+
+/// Runs the command, returning the exit code.
+pub(crate) fn run(args: Vec<String>) -> i32 {
+  if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+    println!("{}", USAGE);
+    return 0;
+  }
+  let args = match Args::parse(args) {
+    Ok(args) if args.files.is_empty() => {
+      eprintln!("plastic lint: missing the files to lint\n\n{}", USAGE);
+      return 2;
+    }
+    Ok(args) => args,
+    Err(message) => {
+      eprintln!("plastic lint: {}\n\n{}", message, USAGE);
+      return 2;
+    }
+  };
+
+  let config_file = args.config.clone().or_else(find_config);
+  let config = match &config_file {
+    Some(path) => match read(path).map(|text| Config::from_toml(&text)) {
+      Ok(Ok(config)) => config,
+      Ok(Err(err)) => {
+        eprintln!("error: {}: {}", path.display(), err);
+        return 1;
+      }
+      Err(code) => return code,
+    },
+    None => Config::new(),
+  };
+  let linter = Linter::new(config);
+  for (name, _) in linter.config().rules() {
+    if linter.rules().all(|rule| rule.name() != name) {
+      let path = config_file
+        .as_ref()
+        .expect("rules are configured in a file");
+      eprintln!("warning: {}: unknown rule `{}`", path.display(), name);
+    }
+  }
+
+  let format = args.format.unwrap_or(Format::Human);
+  let mut code = 0;
+  for path in &args.files {
+    let mut source = match read(path) {
+      Ok(source) => source,
+      Err(_) => {
+        code = 1;
+        continue;
+      }
+    };
+    let lint = |source: &str| {
+      if args.script {
+        linter.lint_script(source)
+      } else {
+        linter.lint_module(source)
+      }
+    };
+    let file_name = path.display().to_string();
+    let diagnostics = match lint(&source) {
+      Ok(mut problems) => {
+        if args.fix {
+          let fixed = match fix(&source, problems, &lint) {
+            Ok(fixed) => fixed,
+            Err(err) => {
+              // The fixes broke the syntax, so the file is left as it is.
+              eprintln!("error: {}: the fixes do not parse: {}", file_name, err);
+              code = 1;
+              continue;
+            }
+          };
+          if fixed.0 != source {
+            if let Err(err) = write(path, &fixed.0) {
+              code = err;
+              continue;
+            }
+            source = fixed.0;
+          }
+          problems = fixed.1;
+        }
+        problems.into_iter().map(Problem::into_diagnostic).collect()
+      }
+      Err(err) => vec![Diagnostic::from(err)],
+    };
+    if diagnostics
+      .iter()
+      .any(|diagnostic| diagnostic.severity() == Severity::Error)
+    {
+      code = 1;
+    }
+    let renderer = Renderer::new(&source)
+      .with_file_name(&file_name)
+      .with_format(format);
+    match format {
+      Format::Human => {
+        for diagnostic in &diagnostics {
+          println!("{}", renderer.render(diagnostic));
+        }
+      }
+      Format::Json => print!("{}", renderer.render_all(&diagnostics)),
+    }
+  }
+  code
+}
+
+/// Applies the fixes of the problems until there are no more, returning the fixed source and
+/// its problems.
+fn fix<F>(
+  source: &str,
+  mut problems: Vec<Problem>,
+  lint: &F,
+) -> Result<(String, Vec<Problem>), ParseError>
+where
+  F: Fn(&str) -> Result<Vec<Problem>, ParseError>,
+{
+  let mut source = source.to_owned();
+  for _ in 0..FIX_PASSES {
+    if problems.iter().all(|problem| problem.fix().is_none()) {
+      break;
+    }
+    source = apply_fixes(&source, problems.iter().filter_map(Problem::fix));
+    problems = lint(&source)?;
+  }
+  Ok((source, problems))
+}
+
+/// Looks for the configuration file in the current directory and its ancestors.
+fn find_config() -> Option<PathBuf> {
+  let dir = env::current_dir().ok()?;
+  dir
+    .ancestors()
+    .map(|dir| dir.join(CONFIG_FILE))
+    .find(|path| path.is_file())
+}
+
+/// Reads a file, returning the exit code on errors.
+fn read(path: &Path) -> Result<String, i32> {
+  fs::read_to_string(path).map_err(|err| {
+    eprintln!(
+      "error: {}: could not read the file: {}",
+      path.display(),
+      err
+    );
+    1
+  })
+}
+
+/// Writes a file, returning the exit code on errors.
+fn write(path: &Path, contents: &str) -> Result<(), i32> {
+  fs::write(path, contents).map_err(|err| {
+    eprintln!(
+      "error: {}: could not write the file: {}",
+      path.display(),
+      err
+    );
+    1
+  })
+}
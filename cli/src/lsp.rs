@@ -0,0 +1,36 @@
+//! The `plastic-lsp` language server, speaking the Language Server Protocol over the standard
+//! input and output.
+
+use plastic::syntax::lsp;
+use std::{env, io, process};
+
+const USAGE: &str = "\
+usage: plastic-lsp [--stdio]
+
+Serves the Language Server Protocol over the standard input and output.";
+
+fn main() {
+  for arg in env::args().skip(1) {
+    match arg.as_str() {
+      // Passed by some clients, as the standard input and output are the only transport.
+      "--stdio" => {}
+      "-h" | "--help" => {
+        println!("{}", USAGE);
+        return;
+      }
+      arg => {
+        eprintln!("plastic-lsp: unknown argument `{}`\n\n{}", arg, USAGE);
+        process::exit(2);
+      }
+    }
+  }
+  let stdin = io::stdin();
+  let code = match lsp::serve(stdin.lock(), io::stdout().lock()) {
+    Ok(code) => code,
+    Err(err) => {
+      eprintln!("plastic-lsp: {}", err);
+      1
+    }
+  };
+  process::exit(code);
+}
@@ -0,0 +1,37 @@
+//! The `plastic` command line tool.
+
+mod bundle;
+mod doc;
+mod lint;
+
+use std::{env, process};
+
+const USAGE: &str = "\
+usage: plastic <command> [options]
+
+commands:
+  bundle    join an entry module and the modules it imports into one script
+  doc       document the exported API of a module from its JSDoc comments
+  lint      check files with the rules of the linter, and fix their problems";
+
+fn main() {
+  let mut args = env::args().skip(1);
+  let code = match args.next().as_deref() {
+    Some("bundle") => bundle::run(args.collect()),
+    Some("doc") => doc::run(args.collect()),
+    Some("lint") => lint::run(args.collect()),
+    Some("-h") | Some("--help") => {
+      println!("{}", USAGE);
+      0
+    }
+    Some(command) => {
+      eprintln!("plastic: unknown command `{}`\n\n{}", command, USAGE);
+      2
+    }
+    None => {
+      eprintln!("{}", USAGE);
+      2
+    }
+  };
+  process::exit(code);
+}
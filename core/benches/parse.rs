@@ -0,0 +1,206 @@
+//! Measures the throughput of the lexer and the parser on a large script.
+//!
+//! Run with `cargo bench -p plastic --bench parse`, which reports the tokens lexed and the
+//! nodes parsed per second on a generated bundle of about 2 MB, and the heap allocations made
+//! by a run, which do not vary with the load of the machine. A path given after `--` is
+//! measured instead of the generated source:
+//!
+//! ```text
+//! cargo bench -p plastic --bench parse -- dist/bundle.js
+//! ```
+//!
+//! Syntax trees are allocated in the [arena](plastic::syntax::ast::arena). The `boxed-ast`
+//! feature allocates each of their nodes on the heap instead, so running both measures the
+//! arena against boxed nodes in the same tree:
+//!
+//! ```text
+//! cargo bench -p plastic --bench parse
+//! cargo bench -p plastic --bench parse --features boxed-ast
+//! ```
+
+use plastic::syntax::{
+  ast::{
+    visitor::{visit, Flow, NodePath, Path, Visitor},
+    Node,
+  },
+  lexer::Lexer,
+  parser::Parser,
+};
+use std::{
+  alloc::{GlobalAlloc, Layout, System},
+  env, fs,
+  sync::atomic::{AtomicUsize, Ordering},
+  time::{Duration, Instant},
+};
+
+/// The number of times each phase runs, the median run being reported.
+const RUNS: usize = 11;
+
+/// The number of modules in the generated bundle.
+const MODULES: usize = 2_000;
+
+/// The heap, counting the allocations made on it.
+struct Counting;
+
+/// The number of allocations made by [`Counting`].
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    System.alloc(layout)
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    System.dealloc(ptr, layout)
+  }
+
+  unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    System.realloc(ptr, layout, new_size)
+  }
+}
+
+#[global_allocator]
+static HEAP: Counting = Counting;
+
+fn main() {
+  let source = match env::args().skip(1).find(|arg| !arg.starts_with('-')) {
+    Some(path) => fs::read_to_string(&path).unwrap_or_else(|err| panic!("{}: {}", path, err)),
+    None => bundle(MODULES),
+  };
+
+  let (tokens, lex_allocations) = allocations(|| lex(&source));
+  let (nodes, parse_allocations) = allocations(|| parse(&source));
+  println!(
+    "source: {:.2} MB, {} tokens, {} nodes",
+    source.len() as f64 / 1e6,
+    tokens,
+    nodes
+  );
+
+  let lexing = median(|| {
+    assert_eq!(lex(&source), tokens);
+  });
+  report("lex", lexing, tokens, "tokens");
+  println!("lex    {:>9} allocations", lex_allocations);
+
+  let parsing = median(|| {
+    assert_eq!(parse(&source), nodes);
+  });
+  report("parse", parsing, nodes, "nodes");
+  report("parse", parsing, tokens, "tokens");
+  println!("parse  {:>9} allocations", parse_allocations);
+}
+
+/// Runs a phase once, returning its result and the number of allocations it made.
+fn allocations<T, F: FnOnce() -> T>(run: F) -> (T, usize) {
+  let start = ALLOCATIONS.load(Ordering::Relaxed);
+  let result = run();
+  (result, ALLOCATIONS.load(Ordering::Relaxed) - start)
+}
+
+/// Lexes a source, returning the number of tokens.
+fn lex(source: &str) -> usize {
+  let mut lexer = Lexer::new(source.as_bytes());
+  let mut tokens = 0;
+  while lexer.next().expect("failed to lex the source").is_some() {
+    tokens += 1;
+  }
+  tokens
+}
+
+/// Parses a source as a script, returning the number of nodes of the tree.
+fn parse(source: &str) -> usize {
+  let script = Parser::new(source.as_bytes())
+    .parse_script()
+    .expect("failed to parse the source");
+  let mut counter = Counter(0);
+  let _ = counter.visit_statements(script.items(), &mut Path::new());
+  counter.0
+}
+
+/// Counts the nodes of a tree.
+struct Counter(usize);
+
+impl<'ast> Visitor<'ast> for Counter {
+  fn visit_node(&mut self, node: &'ast Node, path: &mut NodePath<'ast>) -> Flow {
+    self.0 += 1;
+    visit::walk_node(self, node, path)
+  }
+}
+
+/// Runs a phase several times, returning the median duration.
+fn median<F: FnMut()>(mut run: F) -> Duration {
+  let mut durations: Vec<_> = (0..RUNS)
+    .map(|_| {
+      let start = Instant::now();
+      run();
+      start.elapsed()
+    })
+    .collect();
+  durations.sort();
+  durations[RUNS / 2]
+}
+
+/// Prints the throughput of a phase.
+fn report(phase: &str, duration: Duration, count: usize, unit: &str) {
+  println!(
+    "{:<6} {:>9.2} ms {:>8.2} M {}/s",
+    phase,
+    duration.as_secs_f64() * 1e3,
+    count as f64 / duration.as_secs_f64() / 1e6,
+    unit
+  );
+}
+
+/// Generates a script shaped like the output of a bundler: many small modules wrapped in
+/// functions, reusing common names and declaring names of their own.
+fn bundle(modules: usize) -> String {
+  let mut source = String::from("var __modules = {};\n");
+  for index in 0..modules {
+    source.push_str(&format!(
+      r#"__modules[{index}] = function (module, exports, require) {{
+  "use strict";
+  var helper_{index} = require({dependency});
+  function Widget_{index}(options, children) {{
+    this.options = options || {{}};
+    this.children = children === void 0 ? [] : children;
+    this.id = "widget-{index}";
+  }}
+  Widget_{index}.prototype.render = function (context) {{
+    var total = 0, items = this.children;
+    for (var i = 0; i < items.length; i++) {{
+      if (items[i] && typeof items[i].render === "function") {{
+        total += items[i].render(context) * {index} / 2;
+      }} else {{
+        total -= helper_{index}.measure(items[i], {{ depth: i, name: this.id }});
+      }}
+    }}
+    return total > 0 ? total : -1;
+  }};
+  class Store_{index} extends helper_{index}.Base {{
+    constructor(state) {{
+      super(state);
+      this.listeners = [];
+    }}
+    subscribe(listener) {{
+      this.listeners.push(listener);
+      return () => this.listeners.splice(this.listeners.indexOf(listener), 1);
+    }}
+    static create(...args) {{
+      const [state = {{ count: {index} }}] = args;
+      return new Store_{index}(state);
+    }}
+  }}
+  exports.Widget = Widget_{index};
+  exports.Store = Store_{index};
+  exports.version = "1.0.{index}";
+}};
+"#,
+      index = index,
+      dependency = index / 2,
+    ));
+  }
+  source
+}
@@ -0,0 +1,530 @@
+//! The arena the nodes of syntax trees are allocated in.
+//!
+//! A parse makes a great many small nodes. Rather than asking the heap for each of them, they
+//! are bump allocated: each thread has a current chunk of memory, and a node takes the next
+//! bytes of the chunk, a new chunk being started once it is full.
+//!
+//! Nodes hold their children, and the lists of their children, as [`ArenaBox`]es, which own
+//! their value as a `Box` does. A syntax tree has no lifetime, and its parts can be moved,
+//! cloned and replaced by the passes rewriting it. Each chunk counts the boxes in it that are
+//! alive, and it is freed when the last of them is dropped: the nodes of a parse are next to
+//! one another, and are freed a chunk at a time with their tree. A part kept after its tree is
+//! dropped keeps its chunk alive.
+//!
+//! ```
+//! use plastic::syntax::ast::arena::ArenaBox;
+//!
+//! let mut items = ArenaBox::<[u32]>::from(vec![1, 2, 3]);
+//! items[0] = 4;
+//! assert_eq!(*items, [4, 2, 3]);
+//! assert_eq!(ArenaBox::new(String::from("x")).into_inner(), "x");
+//! ```
+//!
+//! With the `boxed-ast` feature, each box is a heap allocation of its own, as a `Box` is, to
+//! measure the arena against it:
+//!
+//! ```text
+//! cargo bench -p plastic --bench parse
+//! cargo bench -p plastic --bench parse --features boxed-ast
+//! ```
+
+use std::{
+  alloc::Layout,
+  cmp::Ordering,
+  fmt, hash,
+  iter::FromIterator,
+  marker::PhantomData,
+  mem::ManuallyDrop,
+  ops::{Deref, DerefMut},
+  ptr::{self, NonNull},
+  slice,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// An owned value allocated in the arena.
+pub struct ArenaBox<T: ?Sized> {
+  ptr: NonNull<T>,
+  /// The box owns a `T`.
+  marker: PhantomData<T>,
+}
+
+impl<T> ArenaBox<T> {
+  /// Allocates a value in the arena.
+  pub fn new(value: T) -> Self {
+    let ptr = allocate::<T>(1);
+    // SAFETY: the memory is allocated for a `T`, and not initialized yet.
+    unsafe { ptr.as_ptr().write(value) };
+    Self {
+      ptr,
+      marker: PhantomData,
+    }
+  }
+
+  /// Moves the value out of the box.
+  pub fn into_inner(self) -> T {
+    let this = ManuallyDrop::new(self);
+    // SAFETY: the value is read once, and the box is not dropped, so the value is not either.
+    unsafe {
+      let value = this.ptr.as_ptr().read();
+      release(this.ptr.cast(), Layout::new::<T>());
+      value
+    }
+  }
+}
+
+impl<T> ArenaBox<[T]> {
+  /// Moves the values out of the box, into a vector.
+  pub fn into_vec(self) -> Vec<T> {
+    let this = ManuallyDrop::new(self);
+    let len = this.len();
+    let mut vec = Vec::with_capacity(len);
+    // SAFETY: the values are moved to the vector, and the box is not dropped, so they are not
+    // either.
+    unsafe {
+      ptr::copy_nonoverlapping(this.as_ptr(), vec.as_mut_ptr(), len);
+      vec.set_len(len);
+      release(this.ptr.cast(), Layout::for_value::<[T]>(&**this));
+    }
+    vec
+  }
+}
+
+impl<T: ?Sized> Drop for ArenaBox<T> {
+  fn drop(&mut self) {
+    // SAFETY: the box owns its value, which is dropped once.
+    unsafe {
+      let layout = Layout::for_value::<T>(self.ptr.as_ref());
+      ptr::drop_in_place(self.ptr.as_ptr());
+      release(self.ptr.cast(), layout);
+    }
+  }
+}
+
+impl<T: ?Sized> Deref for ArenaBox<T> {
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    // SAFETY: the box owns a valid value.
+    unsafe { self.ptr.as_ref() }
+  }
+}
+
+impl<T: ?Sized> DerefMut for ArenaBox<T> {
+  fn deref_mut(&mut self) -> &mut T {
+    // SAFETY: the box owns a valid value, borrowed mutably with the box.
+    unsafe { self.ptr.as_mut() }
+  }
+}
+
+impl<T: ?Sized> AsRef<T> for ArenaBox<T> {
+  fn as_ref(&self) -> &T {
+    self
+  }
+}
+
+impl<T: ?Sized> AsMut<T> for ArenaBox<T> {
+  fn as_mut(&mut self) -> &mut T {
+    self
+  }
+}
+
+impl<T: Clone> Clone for ArenaBox<T> {
+  fn clone(&self) -> Self {
+    Self::new((**self).clone())
+  }
+}
+
+impl<T: Clone> Clone for ArenaBox<[T]> {
+  fn clone(&self) -> Self {
+    self.iter().cloned().collect()
+  }
+}
+
+impl<T> Default for ArenaBox<[T]> {
+  fn default() -> Self {
+    Vec::new().into()
+  }
+}
+
+impl<T> From<Vec<T>> for ArenaBox<[T]> {
+  fn from(mut vec: Vec<T>) -> Self {
+    let len = vec.len();
+    let data = allocate::<T>(len);
+    // SAFETY: the values are moved out of the vector, which no longer holds them.
+    unsafe {
+      ptr::copy_nonoverlapping(vec.as_ptr(), data.as_ptr(), len);
+      vec.set_len(0);
+    }
+    Self {
+      ptr: NonNull::slice_from_raw_parts(data, len),
+      marker: PhantomData,
+    }
+  }
+}
+
+impl<T, const N: usize> From<[T; N]> for ArenaBox<[T]> {
+  fn from(values: [T; N]) -> Self {
+    Vec::from(values).into()
+  }
+}
+
+impl<T: Clone> From<&[T]> for ArenaBox<[T]> {
+  fn from(values: &[T]) -> Self {
+    values.iter().cloned().collect()
+  }
+}
+
+impl<T> From<Box<[T]>> for ArenaBox<[T]> {
+  fn from(values: Box<[T]>) -> Self {
+    values.into_vec().into()
+  }
+}
+
+impl<T> From<ArenaBox<[T]>> for Vec<T> {
+  fn from(values: ArenaBox<[T]>) -> Self {
+    values.into_vec()
+  }
+}
+
+impl<T> FromIterator<T> for ArenaBox<[T]> {
+  fn from_iter<I>(iter: I) -> Self
+  where
+    I: IntoIterator<Item = T>,
+  {
+    iter.into_iter().collect::<Vec<_>>().into()
+  }
+}
+
+impl<'a, T> IntoIterator for &'a ArenaBox<[T]> {
+  type Item = &'a T;
+  type IntoIter = slice::Iter<'a, T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<'a, T> IntoIterator for &'a mut ArenaBox<[T]> {
+  type Item = &'a mut T;
+  type IntoIter = slice::IterMut<'a, T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter_mut()
+  }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for ArenaBox<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt::Debug::fmt(&**self, f)
+  }
+}
+
+impl<T: ?Sized + PartialEq> PartialEq for ArenaBox<T> {
+  fn eq(&self, other: &Self) -> bool {
+    **self == **other
+  }
+}
+
+impl<T: ?Sized + Eq> Eq for ArenaBox<T> {}
+
+impl<T: ?Sized + PartialOrd> PartialOrd for ArenaBox<T> {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    (**self).partial_cmp(&**other)
+  }
+}
+
+impl<T: ?Sized + Ord> Ord for ArenaBox<T> {
+  fn cmp(&self, other: &Self) -> Ordering {
+    (**self).cmp(&**other)
+  }
+}
+
+impl<T: ?Sized + hash::Hash> hash::Hash for ArenaBox<T> {
+  fn hash<H: hash::Hasher>(&self, state: &mut H) {
+    (**self).hash(state)
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<T: ?Sized + Serialize> Serialize for ArenaBox<T> {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    (**self).serialize(serializer)
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for ArenaBox<T> {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    T::deserialize(deserializer).map(Self::new)
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for ArenaBox<[T]> {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    Vec::deserialize(deserializer).map(Self::from)
+  }
+}
+
+/// Allocates the memory of `count` values of type `T`, which are not initialized.
+fn allocate<T>(count: usize) -> NonNull<T> {
+  let layout = Layout::array::<T>(count).expect("the values are too large to allocate");
+  if layout.size() == 0 {
+    return NonNull::dangling();
+  }
+  chunk::allocate(layout).cast()
+}
+
+/// Frees the memory of values once they are dropped or moved out.
+///
+/// # Safety
+///
+/// The memory must have been allocated with the same layout, and not be used afterwards.
+unsafe fn release(ptr: NonNull<u8>, layout: Layout) {
+  if layout.size() != 0 {
+    chunk::release(ptr, layout);
+  }
+}
+
+/// The chunks of the arena.
+#[cfg(not(feature = "boxed-ast"))]
+mod chunk {
+  use std::{
+    alloc::{self, Layout},
+    cell::Cell,
+    mem,
+    ptr::NonNull,
+  };
+
+  /// The size of a chunk, which is also its alignment, so that the header of the chunk of a
+  /// value is found from the address of the value.
+  const SIZE: usize = 64 * 1024;
+
+  /// The size of the largest value bump allocated, the larger ones getting a chunk of their own.
+  const LARGEST: usize = SIZE / 4;
+
+  /// The start of a chunk.
+  struct Header {
+    /// The number of values alive in the chunk, and one more while it is the current chunk of
+    /// its thread.
+    live: Cell<usize>,
+    /// The size of the chunk.
+    size: usize,
+  }
+
+  /// The current chunk of a thread, and the bytes of it that are used.
+  struct Bump {
+    chunk: Cell<Option<NonNull<Header>>>,
+    used: Cell<usize>,
+  }
+
+  impl Bump {
+    /// Allocates memory in the current chunk, starting a new one when it is full.
+    fn allocate(&self, layout: Layout) -> NonNull<u8> {
+      loop {
+        if let Some(chunk) = self.chunk.get() {
+          // Chunks are aligned to their size, so an offset is aligned as its address is.
+          let start = align(self.used.get(), layout.align());
+          if start + layout.size() <= SIZE {
+            self.used.set(start + layout.size());
+            // SAFETY: the chunk is alive while it is the current one, and the value fits in it.
+            unsafe {
+              let live = &chunk.as_ref().live;
+              live.set(live.get() + 1);
+              return NonNull::new_unchecked(chunk.as_ptr().cast::<u8>().add(start));
+            }
+          }
+        }
+        let chunk = new_chunk(SIZE);
+        if let Some(full) = self.chunk.replace(Some(chunk)) {
+          // SAFETY: the bump holds a count of its current chunk.
+          unsafe { unref(full) };
+        }
+        self.used.set(mem::size_of::<Header>());
+      }
+    }
+  }
+
+  impl Drop for Bump {
+    fn drop(&mut self) {
+      if let Some(chunk) = self.chunk.take() {
+        // SAFETY: the bump holds a count of its current chunk.
+        unsafe { unref(chunk) };
+      }
+    }
+  }
+
+  thread_local! {
+    static BUMP: Bump = const {
+      Bump {
+        chunk: Cell::new(None),
+        used: Cell::new(0),
+      }
+    };
+  }
+
+  /// Allocates memory for a value, which is not zero sized.
+  pub(super) fn allocate(layout: Layout) -> NonNull<u8> {
+    assert!(layout.align() < SIZE, "the values are too aligned to allocate");
+    if layout.size() <= LARGEST {
+      // The thread has no current chunk once it is being destroyed.
+      if let Ok(ptr) = BUMP.try_with(|bump| bump.allocate(layout)) {
+        return ptr;
+      }
+    }
+    let start = align(mem::size_of::<Header>(), layout.align());
+    let chunk = new_chunk(start + layout.size());
+    // SAFETY: the chunk is large enough for the value after its header.
+    unsafe { NonNull::new_unchecked(chunk.as_ptr().cast::<u8>().add(start)) }
+  }
+
+  /// Frees the memory of a value, freeing its chunk with the last value alive in it.
+  ///
+  /// # Safety
+  ///
+  /// The memory must have been allocated by [`allocate`], and not be used afterwards.
+  pub(super) unsafe fn release(ptr: NonNull<u8>, _layout: Layout) {
+    let offset = ptr.as_ptr() as usize % SIZE;
+    unref(NonNull::new_unchecked(
+      ptr.as_ptr().wrapping_sub(offset).cast::<Header>(),
+    ));
+  }
+
+  /// Allocates a chunk, counting one value alive in it.
+  fn new_chunk(size: usize) -> NonNull<Header> {
+    let layout = Layout::from_size_align(size, SIZE).expect("the chunk is too large to allocate");
+    // SAFETY: the layout is not zero sized, as it holds the header.
+    let chunk = unsafe { alloc::alloc(layout) }.cast::<Header>();
+    let chunk = NonNull::new(chunk).unwrap_or_else(|| alloc::handle_alloc_error(layout));
+    // SAFETY: the chunk is allocated, and aligned for its header.
+    unsafe {
+      chunk.as_ptr().write(Header {
+        live: Cell::new(1),
+        size,
+      })
+    };
+    chunk
+  }
+
+  /// Counts one less value alive in a chunk, freeing it when there are none left.
+  ///
+  /// # Safety
+  ///
+  /// The chunk must be alive, and the count must have been held by the caller.
+  unsafe fn unref(chunk: NonNull<Header>) {
+    let live = chunk.as_ref().live.get() - 1;
+    if live == 0 {
+      let size = chunk.as_ref().size;
+      alloc::dealloc(
+        chunk.as_ptr().cast(),
+        Layout::from_size_align_unchecked(size, SIZE),
+      );
+    } else {
+      chunk.as_ref().live.set(live);
+    }
+  }
+
+  /// Rounds an offset up to an alignment.
+  fn align(offset: usize, align: usize) -> usize {
+    (offset + align - 1) & !(align - 1)
+  }
+}
+
+/// The heap, allocating each value on its own as a `Box` does.
+#[cfg(feature = "boxed-ast")]
+mod chunk {
+  use std::{
+    alloc::{self, Layout},
+    ptr::NonNull,
+  };
+
+  /// Allocates memory for a value, which is not zero sized.
+  pub(super) fn allocate(layout: Layout) -> NonNull<u8> {
+    // SAFETY: the layout is not zero sized.
+    let ptr = unsafe { alloc::alloc(layout) };
+    NonNull::new(ptr).unwrap_or_else(|| alloc::handle_alloc_error(layout))
+  }
+
+  /// Frees the memory of a value.
+  ///
+  /// # Safety
+  ///
+  /// The memory must have been allocated by [`allocate`] with the layout, and not be used
+  /// afterwards.
+  pub(super) unsafe fn release(ptr: NonNull<u8>, layout: Layout) {
+    alloc::dealloc(ptr.as_ptr(), layout)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::ArenaBox;
+  use std::{cell::Cell, rc::Rc};
+
+  /// A value counting the values of its kind that are alive.
+  #[derive(Debug)]
+  struct Counted(Rc<Cell<usize>>, [u64; 4]);
+
+  impl Counted {
+    fn new(alive: &Rc<Cell<usize>>) -> Self {
+      alive.set(alive.get() + 1);
+      Self(alive.clone(), [7; 4])
+    }
+  }
+
+  impl Clone for Counted {
+    fn clone(&self) -> Self {
+      Self::new(&self.0)
+    }
+  }
+
+  impl Drop for Counted {
+    fn drop(&mut self) {
+      self.0.set(self.0.get() - 1);
+    }
+  }
+
+  #[test]
+  fn values_are_dropped_once() {
+    let alive = Rc::new(Cell::new(0));
+    let boxes: Vec<_> = (0..10_000)
+      .map(|_| ArenaBox::new(Counted::new(&alive)))
+      .collect();
+    let list: ArenaBox<[Counted]> = (0..3_000).map(|_| Counted::new(&alive)).collect();
+    let large: ArenaBox<[Counted]> = (0..1_000).map(|_| Counted::new(&alive)).collect();
+    assert_eq!(alive.get(), 14_000);
+    assert!(boxes.iter().all(|value| value.1 == [7; 4]));
+
+    let copies = list.clone();
+    assert_eq!(alive.get(), 17_000);
+    let moved = boxes.into_iter().map(ArenaBox::into_inner).collect::<Vec<_>>();
+    assert_eq!(alive.get(), 17_000);
+    drop(moved);
+    drop(large);
+    assert_eq!(alive.get(), 3_000 + 3_000);
+    let vec = copies.into_vec();
+    assert_eq!(vec.len(), 3_000);
+    drop((vec, list));
+    assert_eq!(alive.get(), 0);
+  }
+
+  #[test]
+  fn zero_sized_values() {
+    let empty: ArenaBox<[u8]> = ArenaBox::default();
+    assert!(empty.is_empty());
+    let units: ArenaBox<[()]> = vec![(); 5].into();
+    assert_eq!(units.len(), 5);
+    let () = ArenaBox::new(()).into_inner();
+  }
+}
@@ -1,7 +1,10 @@
-use gc::{Finalize, Trace};
 use std::fmt::{Display, Formatter, Result};
 
-#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
 /// A Javascript Constant
 pub enum Const {
   /// A UTF-8 string, such as `"Hello, world"`
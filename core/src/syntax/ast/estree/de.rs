@@ -0,0 +1,916 @@
+//! Conversion of ESTree values to the syntax tree.
+
+use super::{Program, Value, BINARY_OPERATORS, UNARY_OPERATORS};
+use crate::syntax::ast::{
+  arena::ArenaBox,
+  module::Module,
+  node::{
+    object::number_to_name, ArrayDecl, ArrowFunctionDecl, Assign, Await, BinOp, Block, Break, Call,
+    Case, Catch, Class, ClassElement, ClassElementName, ConditionalOp, Continue, Declaration,
+    DeclarationList, Directive, DoWhileLoop, ExportDecl, ExportSpecifier, Finally, ForInLoop,
+    ForLoop, ForOfLoop, FormalParameter, FunctionDecl, FunctionExpr, FunctionKind, GetConstField,
+    GetField, GetPrivateField, Identifier, If, ImportAttribute, ImportCall, ImportDecl,
+    ImportSpecifier, IterableLoopInitializer, Labelled, MethodDefinitionKind, New, Object,
+    OptionalChain, Pattern, PatternElement, PrivateIn, PropertyDefinition, PropertyName,
+    PropertyPattern, Return, Spread, StatementList, Switch, TaggedTemplate, TemplateElement,
+    TemplateLit, Throw, Try, UnaryOp, WhileLoop, With, Yield,
+  },
+  node::{ArrayPattern, ObjectPattern},
+  operator as op, Const, Node, NodeSpan, Sym,
+};
+
+type Result<T> = std::result::Result<T, String>;
+
+/// The parts of a function read from an ESTree function node.
+type FunctionParts = (
+  Option<Sym>,
+  Vec<FormalParameter>,
+  StatementList,
+  FunctionKind,
+);
+
+/// Converts ESTree values to the syntax tree.
+///
+/// Strictness is not part of ESTree, so the reader tracks it like the parser does, from the
+/// source type, `"use strict"` directives and class bodies.
+pub(super) struct Reader {
+  strict: bool,
+}
+
+impl Reader {
+  /// Creates a reader for a new program.
+  pub(super) fn new() -> Self {
+    Self { strict: false }
+  }
+
+  /// Converts a `Program` to a script or a module.
+  pub(super) fn program(&mut self, value: &Value) -> Result<Program> {
+    expect_type(value, "Program")?;
+    let items = array(value, "body")?;
+    match value.get("sourceType").and_then(Value::as_str) {
+      Some("module") => {
+        self.strict = true;
+        let body = StatementList::from(self.statements(items)?).with_strict(true);
+        Ok(Program::Module(Module::new(body)))
+      }
+      Some("script") | None => {
+        self.strict = has_use_strict(items);
+        let body = StatementList::from(self.statements(items)?).with_strict(self.strict);
+        Ok(Program::Script(body))
+      }
+      Some(other) => Err(format!("unknown source type `{}`", other)),
+    }
+  }
+
+  /// Converts a list of statements.
+  fn statements(&mut self, items: &[Value]) -> Result<Vec<Node>> {
+    items.iter().map(|item| self.statement(item)).collect()
+  }
+
+  /// Converts the body of a function, which is strict if its outer code or one of its
+  /// directives is.
+  fn function_body(&mut self, items: &[Value]) -> Result<StatementList> {
+    let outer = self.strict;
+    self.strict = outer || has_use_strict(items);
+    let body = self.statements(items);
+    let strict = self.strict;
+    self.strict = outer;
+    Ok(StatementList::from(body?).with_strict(strict))
+  }
+
+  /// Converts a `BlockStatement`.
+  fn block(&mut self, value: &Value) -> Result<Block> {
+    expect_type(value, "BlockStatement")?;
+    Ok(Block::from(self.statements(array(value, "body")?)?))
+  }
+
+  /// Converts a statement.
+  fn statement(&mut self, value: &Value) -> Result<Node> {
+    Ok(match node_type(value)? {
+      "ExpressionStatement" => match value.get("directive").and_then(Value::as_str) {
+        Some(directive) => directive_node(field(value, "expression")?, directive)?.into(),
+        None => self.expression(field(value, "expression")?)?,
+      },
+      "BlockStatement" => self.block(value)?.into(),
+      "EmptyStatement" => Node::empty(),
+      "DebuggerStatement" => Node::Debugger(NodeSpan::default()),
+      "VariableDeclaration" => self.declarations(value)?.into(),
+      "FunctionDeclaration" => {
+        let (name, parameters, body, kind) = self.function(value)?;
+        let name = name.ok_or("function declarations must have a name")?;
+        FunctionDecl::new(name, parameters, body)
+          .with_kind(kind)
+          .into()
+      }
+      "ClassDeclaration" => Node::ClassDecl(self.class(value)?),
+      "IfStatement" => If::new::<_, _, Node, _>(
+        self.expression(field(value, "test")?)?,
+        self.statement(field(value, "consequent")?)?,
+        self.optional(value, "alternate", Self::statement)?,
+      )
+      .into(),
+      "ForStatement" => ForLoop::new(
+        self.optional(value, "init", Self::for_init)?,
+        self.optional(value, "test", Self::expression)?,
+        self.optional(value, "update", Self::expression)?,
+        self.statement(field(value, "body")?)?,
+      )
+      .into(),
+      "ForInStatement" => ForInLoop::new(
+        self.loop_initializer(field(value, "left")?)?,
+        self.expression(field(value, "right")?)?,
+        self.statement(field(value, "body")?)?,
+      )
+      .into(),
+      "ForOfStatement" => {
+        let init = self.loop_initializer(field(value, "left")?)?;
+        let iterable = self.expression(field(value, "right")?)?;
+        let body = self.statement(field(value, "body")?)?;
+        if flag(value, "await") {
+          ForOfLoop::new_await(init, iterable, body).into()
+        } else {
+          ForOfLoop::new(init, iterable, body).into()
+        }
+      }
+      "WhileStatement" => WhileLoop::new(
+        self.expression(field(value, "test")?)?,
+        self.statement(field(value, "body")?)?,
+      )
+      .into(),
+      "WithStatement" => With::new(
+        self.expression(field(value, "object")?)?,
+        self.statement(field(value, "body")?)?,
+      )
+      .into(),
+      "DoWhileStatement" => DoWhileLoop::new(
+        self.statement(field(value, "body")?)?,
+        self.expression(field(value, "test")?)?,
+      )
+      .into(),
+      "LabeledStatement" => Labelled::new(
+        identifier_name(field(value, "label")?)?,
+        self.statement(field(value, "body")?)?,
+      )
+      .into(),
+      "BreakStatement" => Break::new::<_, Sym>(optional_name(value, "label")?).into(),
+      "ContinueStatement" => Continue::new::<_, Sym>(optional_name(value, "label")?).into(),
+      "ReturnStatement" => Return::new(self.optional(value, "argument", Self::expression)?).into(),
+      "ThrowStatement" => Throw::new(self.expression(field(value, "argument")?)?).into(),
+      "SwitchStatement" => {
+        let discriminant = self.expression(field(value, "discriminant")?)?;
+        let cases = array(value, "cases")?
+          .iter()
+          .map(|case| {
+            expect_type(case, "SwitchCase")?;
+            let test = self.optional(case, "test", Self::expression)?;
+            Ok(Case::new(
+              test,
+              self.statements(array(case, "consequent")?)?,
+            ))
+          })
+          .collect::<Result<Vec<_>>>()?;
+        Switch::new(discriminant, cases).into()
+      }
+      "TryStatement" => {
+        let block = self.block(field(value, "block")?)?;
+        let catch = match value.get("handler") {
+          Some(handler) => {
+            expect_type(handler, "CatchClause")?;
+            let parameter = self.optional(handler, "param", Self::pattern)?;
+            Some(Catch::new(parameter, self.block(field(handler, "body")?)?))
+          }
+          None => None,
+        };
+        let finally = match value.get("finalizer") {
+          Some(finalizer) => Some(Finally::from(self.block(finalizer)?)),
+          None => None,
+        };
+        Try::new(block, catch, finally).into()
+      }
+      "ImportDeclaration" => self.import(value)?.into(),
+      "ExportNamedDeclaration" | "ExportDefaultDeclaration" | "ExportAllDeclaration" => {
+        self.export(value)?.into()
+      }
+      kind => return Err(format!("unexpected `{}` in statement position", kind)),
+    })
+  }
+
+  /// Converts an expression.
+  fn expression(&mut self, value: &Value) -> Result<Node> {
+    Ok(match node_type(value)? {
+      "Identifier" => Identifier::from(identifier_name(value)?).into(),
+      "Literal" => literal(value)?.into(),
+      "ThisExpression" => Node::this(),
+      "Super" => Node::super_ref(),
+      "ArrayExpression" => {
+        let elements = array(value, "elements")?
+          .iter()
+          .map(|element| match element {
+            Value::Null => Ok(Node::empty()),
+            element => self.expression(element),
+          })
+          .collect::<Result<Vec<_>>>()?;
+        ArrayDecl::from(elements).into()
+      }
+      "ObjectExpression" => self.object(value)?.into(),
+      "FunctionExpression" => self.function_expr(value)?.into(),
+      "ArrowFunctionExpression" => self.arrow(value)?.into(),
+      "ClassExpression" => Node::ClassExpr(self.class(value)?),
+      "UnaryExpression" => {
+        let operator = string(value, "operator")?;
+        let op = UNARY_OPERATORS
+          .iter()
+          .find(|(name, _)| *name == operator)
+          .map(|(_, op)| *op)
+          .ok_or_else(|| format!("unknown unary operator `{}`", operator))?;
+        UnaryOp::new(op, self.expression(field(value, "argument")?)?).into()
+      }
+      "UpdateExpression" => {
+        let op = match (string(value, "operator")?, flag(value, "prefix")) {
+          ("++", true) => op::UnaryOp::IncrementPre,
+          ("++", false) => op::UnaryOp::IncrementPost,
+          ("--", true) => op::UnaryOp::DecrementPre,
+          ("--", false) => op::UnaryOp::DecrementPost,
+          (operator, _) => return Err(format!("unknown update operator `{}`", operator)),
+        };
+        UnaryOp::new(op, self.expression(field(value, "argument")?)?).into()
+      }
+      "BinaryExpression" => {
+        let left = field(value, "left")?;
+        if node_type(left)? == "PrivateIdentifier" && string(value, "operator")? == "in" {
+          PrivateIn::new(
+            identifier_name(left)?,
+            self.expression(field(value, "right")?)?,
+          )
+          .into()
+        } else {
+          self.binary(value)?.into()
+        }
+      }
+      "LogicalExpression" => self.binary(value)?.into(),
+      "AssignmentExpression" if string(value, "operator")? == "=" => Assign::new(
+        self.pattern(field(value, "left")?)?,
+        self.expression(field(value, "right")?)?,
+      )
+      .into(),
+      "AssignmentExpression" => self.binary(value)?.into(),
+      "ConditionalExpression" => ConditionalOp::new(
+        self.expression(field(value, "test")?)?,
+        self.expression(field(value, "consequent")?)?,
+        self.expression(field(value, "alternate")?)?,
+      )
+      .into(),
+      "CallExpression" => {
+        let callee = self.expression(field(value, "callee")?)?;
+        let args = self.expressions(array(value, "arguments")?)?;
+        if flag(value, "optional") {
+          Call::new_optional(callee, args).into()
+        } else {
+          Call::new(callee, args).into()
+        }
+      }
+      "NewExpression" => New::new(
+        self.expression(field(value, "callee")?)?,
+        self.expressions(array(value, "arguments")?)?,
+      )
+      .into(),
+      "MemberExpression" => self.member(value)?,
+      "ChainExpression" => OptionalChain::new(self.expression(field(value, "expression")?)?).into(),
+      "SequenceExpression" => {
+        let mut expressions = array(value, "expressions")?.iter();
+        let first = expressions
+          .next()
+          .ok_or("sequence expressions cannot be empty")?;
+        let mut sequence = self.expression(first)?;
+        for expr in expressions {
+          sequence = BinOp::new(op::BinOp::Comma, sequence, self.expression(expr)?).into();
+        }
+        sequence
+      }
+      "YieldExpression" => Yield::new(
+        self.optional(value, "argument", Self::expression)?,
+        flag(value, "delegate"),
+      )
+      .into(),
+      "AwaitExpression" => Await::new(self.expression(field(value, "argument")?)?).into(),
+      "ImportExpression" => ImportCall::new(
+        self.expression(field(value, "source")?)?,
+        self.optional(value, "options", Self::expression)?,
+      )
+      .into(),
+      "MetaProperty" => {
+        let meta = identifier_name(field(value, "meta")?)?;
+        let property = identifier_name(field(value, "property")?)?;
+        match (&*meta, &*property) {
+          ("import", "meta") => Node::import_meta(),
+          _ => return Err(format!("unsupported meta property `{}.{}`", meta, property)),
+        }
+      }
+      "SpreadElement" => Spread::new(self.expression(field(value, "argument")?)?).into(),
+      "TemplateLiteral" => self.template(value)?.into(),
+      "TaggedTemplateExpression" => TaggedTemplate::new(
+        self.expression(field(value, "tag")?)?,
+        self.template(field(value, "quasi")?)?,
+      )
+      .into(),
+      kind => return Err(format!("unexpected `{}` in expression position", kind)),
+    })
+  }
+
+  /// Converts a list of expressions, such as call arguments.
+  fn expressions(&mut self, values: &[Value]) -> Result<Vec<Node>> {
+    values.iter().map(|value| self.expression(value)).collect()
+  }
+
+  /// Converts an optional field, missing or `null` fields giving `None`.
+  fn optional<T, F>(&mut self, value: &Value, key: &str, convert: F) -> Result<Option<T>>
+  where
+    F: Fn(&mut Self, &Value) -> Result<T>,
+  {
+    value.get(key).map(|value| convert(self, value)).transpose()
+  }
+
+  /// Converts a binary, logical or compound assignment expression.
+  fn binary(&mut self, value: &Value) -> Result<BinOp> {
+    let kind = node_type(value)?;
+    let operator = string(value, "operator")?;
+    let op = BINARY_OPERATORS
+      .iter()
+      .find(|(name, op)| {
+        *name == operator
+          && match op {
+            op::BinOp::Log(_) => kind == "LogicalExpression",
+            op::BinOp::Assign(_) => kind == "AssignmentExpression",
+            _ => kind == "BinaryExpression",
+          }
+      })
+      .map(|(_, op)| *op)
+      .ok_or_else(|| format!("unknown operator `{}` in `{}`", operator, kind))?;
+    Ok(BinOp::new(
+      op,
+      self.expression(field(value, "left")?)?,
+      self.expression(field(value, "right")?)?,
+    ))
+  }
+
+  /// Converts a `MemberExpression`.
+  fn member(&mut self, value: &Value) -> Result<Node> {
+    let object = self.expression(field(value, "object")?)?;
+    let property = field(value, "property")?;
+    let optional = flag(value, "optional");
+    Ok(if node_type(property)? == "PrivateIdentifier" {
+      let name = identifier_name(property)?;
+      if optional {
+        GetPrivateField::new_optional(object, name).into()
+      } else {
+        GetPrivateField::new(object, name).into()
+      }
+    } else if flag(value, "computed") {
+      let field = self.expression(property)?;
+      if optional {
+        GetField::new_optional(object, field).into()
+      } else {
+        GetField::new(object, field).into()
+      }
+    } else {
+      let name = identifier_name(property)?;
+      if optional {
+        GetConstField::new_optional(object, name).into()
+      } else {
+        GetConstField::new(object, name).into()
+      }
+    })
+  }
+
+  /// Converts a `var`, `let` or `const` declaration.
+  fn declarations(&mut self, value: &Value) -> Result<DeclarationList> {
+    let declarations = array(value, "declarations")?
+      .iter()
+      .map(|decl| {
+        expect_type(decl, "VariableDeclarator")?;
+        Ok(Declaration::new(
+          self.pattern(field(decl, "id")?)?,
+          self.optional(decl, "init", Self::expression)?,
+        ))
+      })
+      .collect::<Result<ArenaBox<[_]>>>()?;
+    match string(value, "kind")? {
+      "var" => Ok(DeclarationList::Var(declarations)),
+      "let" => Ok(DeclarationList::Let(declarations)),
+      "const" => Ok(DeclarationList::Const(declarations)),
+      kind => Err(format!("unknown declaration kind `{}`", kind)),
+    }
+  }
+
+  /// Converts the initializer of a `for` loop, a declaration or an expression.
+  fn for_init(&mut self, value: &Value) -> Result<Node> {
+    if node_type(value)? == "VariableDeclaration" {
+      Ok(self.declarations(value)?.into())
+    } else {
+      self.expression(value)
+    }
+  }
+
+  /// Converts the left hand side of a `for ... in` or `for ... of` loop.
+  fn loop_initializer(&mut self, value: &Value) -> Result<IterableLoopInitializer> {
+    if node_type(value)? != "VariableDeclaration" {
+      return Ok(IterableLoopInitializer::Pattern(self.pattern(value)?));
+    }
+    let target = match array(value, "declarations")? {
+      [decl] => self.pattern(field(decl, "id")?)?,
+      _ => return Err("loop declarations must have a single declarator".into()),
+    };
+    match string(value, "kind")? {
+      "var" => Ok(IterableLoopInitializer::Var(target)),
+      "let" => Ok(IterableLoopInitializer::Let(target)),
+      "const" => Ok(IterableLoopInitializer::Const(target)),
+      kind => Err(format!("unknown declaration kind `{}`", kind)),
+    }
+  }
+
+  /// Converts a binding or assignment pattern.
+  fn pattern(&mut self, value: &Value) -> Result<Pattern> {
+    Ok(match node_type(value)? {
+      "Identifier" => Identifier::from(identifier_name(value)?).into(),
+      "MemberExpression" => Pattern::Member(ArenaBox::new(self.member(value)?)),
+      "ObjectPattern" => {
+        let mut properties = Vec::new();
+        let mut rest = None;
+        for property in array(value, "properties")? {
+          if rest.is_some() {
+            return Err("the rest element must be the last property".into());
+          }
+          if node_type(property)? == "RestElement" {
+            rest = Some(self.pattern(field(property, "argument")?)?);
+            continue;
+          }
+          expect_type(property, "Property")?;
+          let computed = flag(property, "computed");
+          let element = self.pattern_element(field(property, "value")?)?;
+          properties.push(match element.target() {
+            Pattern::Identifier(ident) if flag(property, "shorthand") && !computed => {
+              PropertyPattern::shorthand(ident.name(), element.default().cloned())
+            }
+            _ => {
+              let key = self.property_name(field(property, "key")?, computed)?;
+              PropertyPattern::new(key, element)
+            }
+          });
+        }
+        ObjectPattern::new(properties, rest).into()
+      }
+      "ArrayPattern" => {
+        let mut elements = Vec::new();
+        let mut rest = None;
+        for element in array(value, "elements")? {
+          if rest.is_some() {
+            return Err("the rest element must be the last element".into());
+          }
+          match element {
+            Value::Null => elements.push(None),
+            element if node_type(element)? == "RestElement" => {
+              rest = Some(self.pattern(field(element, "argument")?)?);
+            }
+            element => elements.push(Some(self.pattern_element(element)?)),
+          }
+        }
+        ArrayPattern::new(elements, rest).into()
+      }
+      kind => return Err(format!("unexpected `{}` in pattern position", kind)),
+    })
+  }
+
+  /// Converts a pattern with an optional default value.
+  fn pattern_element(&mut self, value: &Value) -> Result<PatternElement> {
+    if node_type(value)? == "AssignmentPattern" {
+      Ok(PatternElement::new(
+        self.pattern(field(value, "left")?)?,
+        Some(self.expression(field(value, "right")?)?),
+      ))
+    } else {
+      Ok(PatternElement::new(self.pattern(value)?, None))
+    }
+  }
+
+  /// Converts the parameters of a function.
+  fn parameters(&mut self, values: &[Value]) -> Result<Vec<FormalParameter>> {
+    values
+      .iter()
+      .map(|value| {
+        if node_type(value)? == "RestElement" {
+          let target = self.pattern(field(value, "argument")?)?;
+          Ok(FormalParameter::new(Declaration::new(target, None), true))
+        } else {
+          let element = self.pattern_element(value)?;
+          let (target, default) = (element.target().clone(), element.default().cloned());
+          Ok(FormalParameter::new(
+            Declaration::new(target, default),
+            false,
+          ))
+        }
+      })
+      .collect()
+  }
+
+  /// Converts the parts of a function declaration or expression.
+  fn function(&mut self, value: &Value) -> Result<FunctionParts> {
+    let name = optional_name(value, "id")?;
+    let kind = FunctionKind::new(flag(value, "async"), flag(value, "generator"));
+    let parameters = self.parameters(array(value, "params")?)?;
+    let body = field(value, "body")?;
+    expect_type(body, "BlockStatement")?;
+    let body = self.function_body(array(body, "body")?)?;
+    Ok((name, parameters, body, kind))
+  }
+
+  /// Converts a `FunctionExpression`, also used for methods.
+  fn function_expr(&mut self, value: &Value) -> Result<FunctionExpr> {
+    expect_type(value, "FunctionExpression")?;
+    let (name, parameters, body, kind) = self.function(value)?;
+    Ok(FunctionExpr::new(name, parameters, body).with_kind(kind))
+  }
+
+  /// Converts an `ArrowFunctionExpression`.
+  fn arrow(&mut self, value: &Value) -> Result<ArrowFunctionDecl> {
+    let parameters = self.parameters(array(value, "params")?)?;
+    let body = field(value, "body")?;
+    let body = if node_type(body)? == "BlockStatement" {
+      self.function_body(array(body, "body")?)?
+    } else {
+      let expr = self.expression(body)?;
+      StatementList::from(vec![Return::new(Some(expr)).into()]).with_strict(self.strict)
+    };
+    if flag(value, "async") {
+      Ok(ArrowFunctionDecl::new_async(parameters, body))
+    } else {
+      Ok(ArrowFunctionDecl::new(parameters, body))
+    }
+  }
+
+  /// Converts a class declaration or expression, whose code is always strict.
+  fn class(&mut self, value: &Value) -> Result<Class> {
+    let outer = self.strict;
+    self.strict = true;
+    let class = self.class_parts(value);
+    self.strict = outer;
+    class
+  }
+
+  /// Converts the parts of a class, in strict mode code.
+  fn class_parts(&mut self, value: &Value) -> Result<Class> {
+    let name = optional_name(value, "id")?;
+    let super_ref = self.optional(value, "superClass", Self::expression)?;
+    let body = field(value, "body")?;
+    expect_type(body, "ClassBody")?;
+
+    let mut constructor = None;
+    let mut elements = Vec::new();
+    for element in array(body, "body")? {
+      match node_type(element)? {
+        "MethodDefinition" if string(element, "kind")? == "constructor" => {
+          constructor = Some(self.function_expr(field(element, "value")?)?);
+        }
+        "MethodDefinition" => {
+          let kind = match string(element, "kind")? {
+            "method" => MethodDefinitionKind::Ordinary,
+            "get" => MethodDefinitionKind::Get,
+            "set" => MethodDefinitionKind::Set,
+            kind => return Err(format!("unknown method kind `{}`", kind)),
+          };
+          elements.push(ClassElement::Method {
+            name: self.class_element_name(element)?,
+            kind,
+            function: self.function_expr(field(element, "value")?)?,
+            is_static: flag(element, "static"),
+            span: NodeSpan::default(),
+          });
+        }
+        "PropertyDefinition" => elements.push(ClassElement::Field {
+          name: self.class_element_name(element)?,
+          init: self.optional(element, "value", Self::expression)?,
+          is_static: flag(element, "static"),
+          span: NodeSpan::default(),
+        }),
+        "StaticBlock" => {
+          let body = self.statements(array(element, "body")?)?;
+          elements.push(ClassElement::StaticBlock(
+            StatementList::from(body),
+            NodeSpan::default(),
+          ));
+        }
+        kind => return Err(format!("unexpected `{}` in a class body", kind)),
+      }
+    }
+    Ok(Class::new(name, super_ref, constructor, elements))
+  }
+
+  /// Converts the key of a class element.
+  fn class_element_name(&mut self, element: &Value) -> Result<ClassElementName> {
+    let key = field(element, "key")?;
+    if node_type(key)? == "PrivateIdentifier" {
+      Ok(ClassElementName::PrivateName(identifier_name(key)?))
+    } else {
+      Ok(self.property_name(key, flag(element, "computed"))?.into())
+    }
+  }
+
+  /// Converts the key of a property.
+  fn property_name(&mut self, key: &Value, computed: bool) -> Result<PropertyName> {
+    if computed {
+      return Ok(PropertyName::Computed(self.expression(key)?));
+    }
+    match node_type(key)? {
+      "Identifier" => Ok(PropertyName::Literal(identifier_name(key)?)),
+      "Literal" => match key.get("value") {
+        Some(Value::String(name)) => Ok(PropertyName::Literal(name.as_ref().into())),
+        Some(Value::Number(number)) => Ok(PropertyName::Literal(number_to_name(*number).into())),
+        _ => Err("property names must be strings or numbers".into()),
+      },
+      kind => Err(format!("unexpected `{}` as a property name", kind)),
+    }
+  }
+
+  /// Converts an `ObjectExpression`.
+  fn object(&mut self, value: &Value) -> Result<Object> {
+    let properties = array(value, "properties")?
+      .iter()
+      .map(|property| {
+        if node_type(property)? == "SpreadElement" {
+          let argument = self.expression(field(property, "argument")?)?;
+          return Ok(PropertyDefinition::spread_object(argument));
+        }
+        expect_type(property, "Property")?;
+        let computed = flag(property, "computed");
+        let name = self.property_name(field(property, "key")?, computed)?;
+        let property_value = field(property, "value")?;
+        let method_kind = match string(property, "kind")? {
+          "get" => Some(MethodDefinitionKind::Get),
+          "set" => Some(MethodDefinitionKind::Set),
+          "init" if flag(property, "method") => Some(MethodDefinitionKind::Ordinary),
+          "init" => None,
+          kind => return Err(format!("unknown property kind `{}`", kind)),
+        };
+        Ok(match method_kind {
+          Some(kind) => {
+            PropertyDefinition::method_definition(kind, name, self.function_expr(property_value)?)
+          }
+          None if flag(property, "shorthand") && !computed => {
+            PropertyDefinition::identifier_reference(identifier_name(property_value)?)
+          }
+          None => PropertyDefinition::property(name, self.expression(property_value)?),
+        })
+      })
+      .collect::<Result<Vec<_>>>()?;
+    Ok(Object::from(properties))
+  }
+
+  /// Converts a `TemplateLiteral`.
+  fn template(&mut self, value: &Value) -> Result<TemplateLit> {
+    expect_type(value, "TemplateLiteral")?;
+    let quasis = array(value, "quasis")?
+      .iter()
+      .map(|quasi| {
+        expect_type(quasi, "TemplateElement")?;
+        let value = field(quasi, "value")?;
+        let cooked = value.get("cooked").and_then(Value::as_str);
+        Ok(TemplateElement::new(cooked, string(value, "raw")?))
+      })
+      .collect::<Result<Vec<_>>>()?;
+    let exprs = self.expressions(array(value, "expressions")?)?;
+    if quasis.len() != exprs.len() + 1 {
+      return Err("template literals have one more quasi than expressions".into());
+    }
+    Ok(TemplateLit::new(quasis, exprs))
+  }
+
+  /// Converts an `ImportDeclaration`.
+  fn import(&mut self, value: &Value) -> Result<ImportDecl> {
+    let specifiers = array(value, "specifiers")?
+      .iter()
+      .map(|specifier| {
+        let local = Identifier::new(identifier_name(field(specifier, "local")?)?);
+        match node_type(specifier)? {
+          "ImportDefaultSpecifier" => Ok(ImportSpecifier::Default(local)),
+          "ImportNamespaceSpecifier" => Ok(ImportSpecifier::Namespace(local)),
+          "ImportSpecifier" => Ok(ImportSpecifier::named(
+            module_export_name(field(specifier, "imported")?)?,
+            local,
+          )),
+          kind => Err(format!("unexpected `{}` in an import declaration", kind)),
+        }
+      })
+      .collect::<Result<Vec<_>>>()?;
+    Ok(ImportDecl::new(
+      specifiers,
+      string_literal(field(value, "source")?)?,
+      attributes(value)?,
+    ))
+  }
+
+  /// Converts an `ExportNamedDeclaration`, `ExportDefaultDeclaration` or
+  /// `ExportAllDeclaration`.
+  fn export(&mut self, value: &Value) -> Result<ExportDecl> {
+    match node_type(value)? {
+      "ExportNamedDeclaration" => {
+        if let Some(decl) = value.get("declaration") {
+          return Ok(ExportDecl::declaration(self.statement(decl)?));
+        }
+        let specifiers = array(value, "specifiers")?
+          .iter()
+          .map(|specifier| {
+            expect_type(specifier, "ExportSpecifier")?;
+            Ok(ExportSpecifier::new(
+              module_export_name(field(specifier, "local")?)?,
+              module_export_name(field(specifier, "exported")?)?,
+            ))
+          })
+          .collect::<Result<Vec<_>>>()?;
+        Ok(ExportDecl::Named {
+          specifiers: specifiers.into(),
+          source: value.get("source").map(string_literal).transpose()?,
+          attributes: attributes(value)?.into(),
+        })
+      }
+      "ExportAllDeclaration" => Ok(ExportDecl::All {
+        alias: value.get("exported").map(module_export_name).transpose()?,
+        source: string_literal(field(value, "source")?)?,
+        attributes: attributes(value)?.into(),
+      }),
+      _ => {
+        let decl = field(value, "declaration")?;
+        match node_type(decl)? {
+          "FunctionDeclaration" => {
+            let (name, parameters, body, kind) = self.function(decl)?;
+            Ok(ExportDecl::DefaultFunction(
+              FunctionExpr::new(name, parameters, body).with_kind(kind),
+            ))
+          }
+          "ClassDeclaration" => Ok(ExportDecl::DefaultClass(self.class(decl)?)),
+          _ => Ok(ExportDecl::DefaultExpression(ArenaBox::new(
+            self.expression(decl)?,
+          ))),
+        }
+      }
+    }
+  }
+}
+
+/// Gets the type of a node.
+fn node_type(value: &Value) -> Result<&str> {
+  value
+    .get("type")
+    .and_then(Value::as_str)
+    .ok_or_else(|| "expected an ESTree node".to_owned())
+}
+
+/// Checks the type of a node.
+fn expect_type(value: &Value, expected: &str) -> Result<()> {
+  match node_type(value)? {
+    kind if kind == expected => Ok(()),
+    kind => Err(format!("expected `{}`, found `{}`", expected, kind)),
+  }
+}
+
+/// Gets a required field of a node.
+fn field<'v>(value: &'v Value, key: &str) -> Result<&'v Value> {
+  value.get(key).ok_or_else(|| {
+    format!(
+      "missing `{}` in `{}`",
+      key,
+      node_type(value).unwrap_or("node")
+    )
+  })
+}
+
+/// Gets a required array field of a node.
+fn array<'v>(value: &'v Value, key: &str) -> Result<&'v [Value]> {
+  match field(value, key)? {
+    Value::Array(values) => Ok(values),
+    _ => Err(format!("`{}` must be an array", key)),
+  }
+}
+
+/// Gets a required string field of a node.
+fn string<'v>(value: &'v Value, key: &str) -> Result<&'v str> {
+  field(value, key)?
+    .as_str()
+    .ok_or_else(|| format!("`{}` must be a string", key))
+}
+
+/// Gets a boolean field of a node, missing fields being `false`.
+fn flag(value: &Value, key: &str) -> bool {
+  matches!(value.get(key), Some(Value::Bool(true)))
+}
+
+/// Gets the name of an `Identifier` or a `PrivateIdentifier`.
+fn identifier_name(value: &Value) -> Result<Sym> {
+  match node_type(value)? {
+    "Identifier" | "PrivateIdentifier" => Ok(string(value, "name")?.into()),
+    kind => Err(format!("expected an identifier, found `{}`", kind)),
+  }
+}
+
+/// Gets the name of an optional `Identifier` field, such as a label.
+fn optional_name(value: &Value, key: &str) -> Result<Option<Sym>> {
+  value.get(key).map(identifier_name).transpose()
+}
+
+/// Gets the value of a string `Literal`.
+fn string_literal(value: &Value) -> Result<Box<str>> {
+  expect_type(value, "Literal")?;
+  match value.get("value") {
+    Some(Value::String(s)) => Ok(s.clone()),
+    _ => Err("expected a string literal".into()),
+  }
+}
+
+/// Gets an imported or exported name, an identifier or a string literal.
+fn module_export_name(value: &Value) -> Result<Sym> {
+  match node_type(value)? {
+    "Literal" => string_literal(value).map(Sym::from),
+    _ => identifier_name(value),
+  }
+}
+
+/// Gets the import attributes of an import or export declaration, which older tools omit.
+fn attributes(value: &Value) -> Result<Vec<ImportAttribute>> {
+  let attributes = match value.get("attributes") {
+    Some(Value::Array(attributes)) => attributes,
+    Some(_) => return Err("`attributes` must be an array".into()),
+    None => return Ok(Vec::new()),
+  };
+  attributes
+    .iter()
+    .map(|attribute| {
+      expect_type(attribute, "ImportAttribute")?;
+      Ok(ImportAttribute::new(
+        module_export_name(field(attribute, "key")?)?,
+        string_literal(field(attribute, "value")?)?,
+      ))
+    })
+    .collect()
+}
+
+/// Converts a `Literal`.
+fn literal(value: &Value) -> Result<Const> {
+  if value.get("bigint").is_some() {
+    return Err("BigInt literals are not supported".into());
+  }
+  if let Some(regex) = value.get("regex") {
+    let flags = string(regex, "flags")?;
+    if let Some(flag) = flags.chars().find(|flag| !matches!(flag, 'g' | 'i')) {
+      return Err(format!("unsupported regular expression flag `{}`", flag));
+    }
+    return Ok(Const::RegExp(
+      string(regex, "pattern")?.to_owned(),
+      flags.contains('g'),
+      flags.contains('i'),
+    ));
+  }
+  match value.get("value") {
+    Some(Value::String(s)) => Ok(Const::String(s.clone())),
+    Some(Value::Bool(b)) => Ok(Const::Bool(*b)),
+    Some(Value::Number(number)) => Ok(number_literal(*number)),
+    // Numbers too large for JSON, such as `1e999`, only have their raw form.
+    None => match value.get("raw").and_then(Value::as_str).map(str::parse) {
+      Some(Ok(number)) => Ok(number_literal(number)),
+      _ => Ok(Const::Null),
+    },
+    Some(_) => Err("unsupported literal value".into()),
+  }
+}
+
+/// Converts a number, stored as an integer if it is one, as the lexer does.
+fn number_literal(number: f64) -> Const {
+  let int = number as i32;
+  #[allow(clippy::float_cmp)]
+  if f64::from(int) == number && !(number == 0.0 && number.is_sign_negative()) {
+    Const::Int(int)
+  } else {
+    Const::Num(number)
+  }
+}
+
+/// Converts the string literal of a directive, whose source text between the quotes is
+/// `directive`.
+fn directive_node(literal: &Value, directive: &str) -> Result<Directive> {
+  let value = match literal.get("value") {
+    Some(Value::String(value)) => value,
+    _ => return Err("directives must be string literals".into()),
+  };
+  let raw = match literal.get("raw").and_then(Value::as_str) {
+    Some(raw) => raw.to_owned(),
+    // A double quote in the directive is not escaped if it was written with single quotes.
+    None if directive.contains('"') => format!("'{}'", directive),
+    None => format!("\"{}\"", directive),
+  };
+  Ok(Directive::new(value.as_ref(), raw))
+}
+
+/// Returns `true` if the directive prologue of a body contains `"use strict"`.
+fn has_use_strict(items: &[Value]) -> bool {
+  items
+    .iter()
+    .map_while(|item| item.get("directive").and_then(Value::as_str))
+    .any(|directive| directive == "use strict")
+}
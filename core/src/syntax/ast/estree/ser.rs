@@ -0,0 +1,934 @@
+//! Conversion of the syntax tree to ESTree values.
+
+use super::{Value, BINARY_OPERATORS, UNARY_OPERATORS};
+use crate::syntax::{
+  ast::{
+    node::{
+      object::name_to_number, ArrowFunctionDecl, BinOp, Block, Class, ClassElement,
+      ClassElementName, DeclarationList, ExportDecl, FormalParameter, FunctionExpr, FunctionKind,
+      ImportAttribute, ImportDecl, ImportSpecifier, IterableLoopInitializer, MethodDefinitionKind,
+      Object, Pattern, PatternElement, PropertyDefinition, PropertyName, StatementList,
+      TemplateLit, UnaryOp,
+    },
+    operator as op, Const, Node, Position, Span, Sym,
+  },
+  lexer::is_identifier_name,
+};
+
+type Result<T> = std::result::Result<T, String>;
+
+/// Converts the syntax tree to ESTree values.
+pub(super) struct Writer<'a> {
+  source: Option<LineIndex<'a>>,
+}
+
+impl<'a> Writer<'a> {
+  /// Creates a writer, computing the `loc` and `range` fields from the source if given.
+  pub(super) fn new(source: Option<&'a str>) -> Self {
+    Self {
+      source: source.map(LineIndex::new),
+    }
+  }
+
+  /// Converts the body of a script or a module to a `Program`.
+  pub(super) fn program(&self, body: &StatementList, source_type: &str) -> Result<Value> {
+    let mut program = Value::node("Program");
+    if let Some(index) = &self.source {
+      program = index.locate(program, index.full_span());
+    }
+    Ok(
+      program
+        .with("body", self.statements(body.items())?)
+        .with("sourceType", source_type),
+    )
+  }
+
+  /// Converts a list of statements.
+  fn statements(&self, items: &[Node]) -> Result<Vec<Value>> {
+    items.iter().map(|item| self.statement(item)).collect()
+  }
+
+  /// Converts a block of statements to a `BlockStatement`.
+  fn block(&self, items: &[Node]) -> Result<Value> {
+    Ok(Value::node("BlockStatement").with("body", self.statements(items)?))
+  }
+
+  /// Adds the `loc` and `range` fields of a node, if its span and the source are known and
+  /// the node does not have them yet.
+  fn located(&self, value: Value, span: Option<Span>) -> Value {
+    match (&self.source, span) {
+      (Some(index), Some(span)) if value.get("loc").is_none() => index.locate(value, span),
+      _ => value,
+    }
+  }
+
+  /// Converts the name of a function or a class, or a label, to an `Identifier`.
+  fn name(&self, name: Option<&str>, span: Option<Span>) -> Value {
+    match name {
+      Some(name) => self.located(identifier(name), span),
+      None => Value::Null,
+    }
+  }
+
+  /// Converts a node in statement position, wrapping expressions in an `ExpressionStatement`.
+  fn statement(&self, node: &Node) -> Result<Value> {
+    let value = match node {
+      Node::Block(block) => self.block(block.statements())?,
+      Node::Break(brk) => {
+        Value::node("BreakStatement").with("label", self.name(brk.label(), brk.label_span()))
+      }
+      Node::ClassDecl(class) => self.class("ClassDeclaration", class)?,
+      Node::Continue(cont) => {
+        Value::node("ContinueStatement").with("label", self.name(cont.label(), cont.label_span()))
+      }
+      Node::Debugger(_) => Value::node("DebuggerStatement"),
+      Node::DeclarationList(list, _) => self.declarations(list)?,
+      Node::Directive(directive) => {
+        let raw = directive.raw();
+        let literal = string_literal(directive.value()).with("raw", raw);
+        Value::node("ExpressionStatement")
+          .with("expression", self.located(literal, directive.span()))
+          .with("directive", &raw[1..raw.len() - 1])
+      }
+      Node::DoWhileLoop(do_while) => Value::node("DoWhileStatement")
+        .with("body", self.statement(do_while.body())?)
+        .with("test", self.expression(do_while.cond())?),
+      Node::Empty(_) => Value::node("EmptyStatement"),
+      Node::Export(export, _) => self.export(export)?,
+      Node::ForInLoop(for_in) => Value::node("ForInStatement")
+        .with("left", self.loop_initializer(for_in.init())?)
+        .with("right", self.expression(for_in.expr())?)
+        .with("body", self.statement(for_in.body())?),
+      Node::ForLoop(for_loop) => Value::node("ForStatement")
+        .with("init", self.optional(for_loop.init(), Self::for_init)?)
+        .with(
+          "test",
+          self.optional(for_loop.condition(), Self::expression)?,
+        )
+        .with(
+          "update",
+          self.optional(for_loop.final_expr(), Self::expression)?,
+        )
+        .with("body", self.statement(for_loop.body())?),
+      Node::ForOfLoop(for_of) => Value::node("ForOfStatement")
+        .with("await", for_of.is_await())
+        .with("left", self.loop_initializer(for_of.init())?)
+        .with("right", self.expression(for_of.iterable())?)
+        .with("body", self.statement(for_of.body())?),
+      Node::FunctionDecl(decl) => self.function(
+        "FunctionDeclaration",
+        self.name(Some(decl.name()), decl.name_span()),
+        decl.parameters(),
+        decl.body(),
+        decl.kind(),
+      )?,
+      Node::If(if_smt) => Value::node("IfStatement")
+        .with("test", self.expression(if_smt.cond())?)
+        .with("consequent", self.statement(if_smt.body())?)
+        .with(
+          "alternate",
+          self.optional(if_smt.else_node(), Self::statement)?,
+        ),
+      Node::Import(import) => self.import(import)?,
+      Node::Labelled(labelled) => Value::node("LabeledStatement")
+        .with(
+          "label",
+          self.name(Some(labelled.label()), labelled.label_span()),
+        )
+        .with("body", self.statement(labelled.statement())?),
+      Node::Return(ret) => Value::node("ReturnStatement")
+        .with("argument", self.optional(ret.expr(), Self::expression)?),
+      Node::Switch(switch) => {
+        let cases = switch
+          .cases()
+          .iter()
+          .map(|case| {
+            Ok(
+              self.located(
+                Value::node("SwitchCase")
+                  .with("test", self.optional(case.condition(), Self::expression)?)
+                  .with("consequent", self.statements(case.body())?),
+                case.span(),
+              ),
+            )
+          })
+          .collect::<Result<Vec<_>>>()?;
+        Value::node("SwitchStatement")
+          .with("discriminant", self.expression(switch.val())?)
+          .with("cases", cases)
+      }
+      Node::Throw(throw) => {
+        Value::node("ThrowStatement").with("argument", self.expression(throw.expr())?)
+      }
+      Node::Try(try_node) => {
+        let block = |block: &Block| -> Result<Value> {
+          Ok(self.located(self.block(block.statements())?, block.span()))
+        };
+        let handler = match try_node.catch() {
+          Some(catch) => self.located(
+            Value::node("CatchClause")
+              .with("param", self.optional(catch.parameter(), Self::pattern)?)
+              .with("body", block(catch.block())?),
+            catch.span(),
+          ),
+          None => Value::Null,
+        };
+        let finalizer = match try_node.finally() {
+          Some(finally) => block(finally)?,
+          None => Value::Null,
+        };
+        Value::node("TryStatement")
+          .with("block", block(try_node.block())?)
+          .with("handler", handler)
+          .with("finalizer", finalizer)
+      }
+      Node::WhileLoop(while_loop) => Value::node("WhileStatement")
+        .with("test", self.expression(while_loop.cond())?)
+        .with("body", self.statement(while_loop.body())?),
+      Node::With(with) => Value::node("WithStatement")
+        .with("object", self.expression(with.object())?)
+        .with("body", self.statement(with.body())?),
+      expr => Value::node("ExpressionStatement").with("expression", self.expression(expr)?),
+    };
+    Ok(self.located(value, node.span()))
+  }
+
+  /// Converts a node in expression position.
+  fn expression(&self, node: &Node) -> Result<Value> {
+    let value = match node {
+      Node::ArrayDecl(array) => {
+        let elements = array
+          .elements()
+          .iter()
+          .map(|element| match element {
+            Node::Empty(_) => Ok(Value::Null),
+            element => self.expression(element),
+          })
+          .collect::<Result<Vec<_>>>()?;
+        Value::node("ArrayExpression").with("elements", elements)
+      }
+      Node::ArrowFunctionDecl(arrow) => self.arrow(arrow)?,
+      Node::Assign(assign) => Value::node("AssignmentExpression")
+        .with("operator", "=")
+        .with("left", self.pattern(assign.lhs())?)
+        .with("right", self.expression(assign.rhs())?),
+      Node::Await(await_expr) => {
+        Value::node("AwaitExpression").with("argument", self.expression(await_expr.expr())?)
+      }
+      Node::BinOp(bin_op) => self.binary(bin_op)?,
+      Node::Call(call) => Value::node("CallExpression")
+        .with("callee", self.expression(call.expr())?)
+        .with("arguments", self.expressions(call.args())?)
+        .with("optional", call.optional()),
+      Node::ClassExpr(class) => self.class("ClassExpression", class)?,
+      Node::ConditionalOp(cond) => Value::node("ConditionalExpression")
+        .with("test", self.expression(cond.cond())?)
+        .with("consequent", self.expression(cond.if_true())?)
+        .with("alternate", self.expression(cond.if_false())?),
+      Node::Const(value, _) => literal(value)?,
+      Node::FunctionExpr(function) => self.function_expr(function)?,
+      Node::GetConstField(field) => Value::node("MemberExpression")
+        .with("object", self.expression(field.obj())?)
+        .with("property", identifier(field.field()))
+        .with("computed", false)
+        .with("optional", field.optional()),
+      Node::GetField(field) => Value::node("MemberExpression")
+        .with("object", self.expression(field.obj())?)
+        .with("property", self.expression(field.field())?)
+        .with("computed", true)
+        .with("optional", field.optional()),
+      Node::GetPrivateField(field) => Value::node("MemberExpression")
+        .with("object", self.expression(field.obj())?)
+        .with("property", private_identifier(field.field()))
+        .with("computed", false)
+        .with("optional", field.optional()),
+      Node::Identifier(ident) => identifier(ident.name()),
+      Node::ImportCall(call) => Value::node("ImportExpression")
+        .with("source", self.expression(call.specifier())?)
+        .with("options", self.optional(call.options(), Self::expression)?),
+      Node::ImportMeta(_) => Value::node("MetaProperty")
+        .with("meta", identifier("import"))
+        .with("property", identifier("meta")),
+      Node::New(new) => Value::node("NewExpression")
+        .with("callee", self.expression(new.expr())?)
+        .with("arguments", self.expressions(new.args())?),
+      Node::Object(object) => self.object(object)?,
+      Node::OptionalChain(chain) => {
+        Value::node("ChainExpression").with("expression", self.expression(chain.expr())?)
+      }
+      Node::PrivateIn(private_in) => Value::node("BinaryExpression")
+        .with("operator", "in")
+        .with("left", private_identifier(private_in.name()))
+        .with("right", self.expression(private_in.target())?),
+      Node::Spread(spread) => {
+        Value::node("SpreadElement").with("argument", self.expression(spread.val())?)
+      }
+      Node::Super(_) => Value::node("Super"),
+      Node::TaggedTemplate(tagged) => Value::node("TaggedTemplateExpression")
+        .with("tag", self.expression(tagged.tag())?)
+        .with("quasi", self.template(tagged.template())?),
+      Node::TemplateLit(template) => self.template(template)?,
+      Node::This(_) => Value::node("ThisExpression"),
+      Node::UnaryOp(unary) => self.unary(unary)?,
+      Node::Yield(yield_expr) => Value::node("YieldExpression")
+        .with(
+          "argument",
+          self.optional(yield_expr.expr(), Self::expression)?,
+        )
+        .with("delegate", yield_expr.delegate()),
+      Node::Error(_) => return Err("syntax error nodes have no ESTree form".into()),
+      _ => return Err("found a statement in expression position".into()),
+    };
+    Ok(self.located(value, node.span()))
+  }
+
+  /// Converts a list of expressions, such as call arguments.
+  fn expressions(&self, nodes: &[Node]) -> Result<Vec<Value>> {
+    nodes.iter().map(|node| self.expression(node)).collect()
+  }
+
+  /// Converts an optional node, giving `null` if there is none.
+  fn optional<T, F>(&self, node: Option<T>, convert: F) -> Result<Value>
+  where
+    F: Fn(&Self, T) -> Result<Value>,
+  {
+    node.map_or(Ok(Value::Null), |node| convert(self, node))
+  }
+
+  /// Converts a binary operation to a binary, logical, assignment or sequence expression.
+  fn binary(&self, bin_op: &BinOp) -> Result<Value> {
+    if bin_op.op() == op::BinOp::Comma {
+      // The comma operator is left associative, `a, b, c` is a single sequence.
+      let mut expressions = vec![self.expression(bin_op.rhs())?];
+      let mut lhs = bin_op.lhs();
+      while let Node::BinOp(inner) = lhs {
+        if inner.op() != op::BinOp::Comma {
+          break;
+        }
+        expressions.push(self.expression(inner.rhs())?);
+        lhs = inner.lhs();
+      }
+      expressions.push(self.expression(lhs)?);
+      expressions.reverse();
+      return Ok(Value::node("SequenceExpression").with("expressions", expressions));
+    }
+
+    let kind = match bin_op.op() {
+      op::BinOp::Log(_) => "LogicalExpression",
+      op::BinOp::Assign(_) => "AssignmentExpression",
+      _ => "BinaryExpression",
+    };
+    let operator = BINARY_OPERATORS
+      .iter()
+      .find(|(_, op)| *op == bin_op.op())
+      .map(|(operator, _)| *operator)
+      .expect("every binary operator has an ESTree form");
+    Ok(
+      Value::node(kind)
+        .with("operator", operator)
+        .with("left", self.expression(bin_op.lhs())?)
+        .with("right", self.expression(bin_op.rhs())?),
+    )
+  }
+
+  /// Converts a unary operation to a unary or update expression.
+  fn unary(&self, unary: &UnaryOp) -> Result<Value> {
+    let (kind, operator, prefix) = match unary.op() {
+      op::UnaryOp::IncrementPre => ("UpdateExpression", "++", true),
+      op::UnaryOp::IncrementPost => ("UpdateExpression", "++", false),
+      op::UnaryOp::DecrementPre => ("UpdateExpression", "--", true),
+      op::UnaryOp::DecrementPost => ("UpdateExpression", "--", false),
+      unary_op => {
+        let operator = UNARY_OPERATORS
+          .iter()
+          .find(|(_, op)| *op == unary_op)
+          .map(|(operator, _)| *operator)
+          .expect("every unary operator has an ESTree form");
+        ("UnaryExpression", operator, true)
+      }
+    };
+    Ok(
+      Value::node(kind)
+        .with("operator", operator)
+        .with("prefix", prefix)
+        .with("argument", self.expression(unary.target())?),
+    )
+  }
+
+  /// Converts a `var`, `let` or `const` declaration.
+  fn declarations(&self, list: &DeclarationList) -> Result<Value> {
+    let declarations = list
+      .as_ref()
+      .iter()
+      .map(|decl| {
+        Ok(
+          self.located(
+            Value::node("VariableDeclarator")
+              .with("id", self.pattern(decl.target())?)
+              .with("init", self.optional(decl.init(), Self::expression)?),
+            decl.span(),
+          ),
+        )
+      })
+      .collect::<Result<Vec<_>>>()?;
+    Ok(
+      Value::node("VariableDeclaration")
+        .with("declarations", declarations)
+        .with("kind", list.kind()),
+    )
+  }
+
+  /// Converts the initializer of a `for` loop, a declaration or an expression.
+  fn for_init(&self, init: &Node) -> Result<Value> {
+    match init {
+      Node::DeclarationList(list, span) => Ok(self.located(self.declarations(list)?, span.get())),
+      expr => self.expression(expr),
+    }
+  }
+
+  /// Converts the left hand side of a `for ... in` or `for ... of` loop.
+  fn loop_initializer(&self, init: &IterableLoopInitializer) -> Result<Value> {
+    let kind = match init {
+      IterableLoopInitializer::Var(_) => "var",
+      IterableLoopInitializer::Let(_) => "let",
+      IterableLoopInitializer::Const(_) => "const",
+      IterableLoopInitializer::Pattern(pattern) => return self.pattern(pattern),
+    };
+    let declarator = Value::node("VariableDeclarator")
+      .with("id", self.pattern(init.pattern())?)
+      .with("init", Value::Null);
+    Ok(
+      Value::node("VariableDeclaration")
+        .with("declarations", vec![declarator])
+        .with("kind", kind),
+    )
+  }
+
+  /// Converts a binding or assignment pattern.
+  fn pattern(&self, pattern: &Pattern) -> Result<Value> {
+    let value = match pattern {
+      Pattern::Identifier(ident) => identifier(ident.name()),
+      Pattern::Object(object) => {
+        let mut properties = object
+          .properties()
+          .iter()
+          .map(|property| {
+            let (key, computed) = self.property_name(property.key())?;
+            Ok(
+              Value::node("Property")
+                .with("key", key)
+                .with("value", self.pattern_element(property.value())?)
+                .with("kind", "init")
+                .with("method", false)
+                .with("shorthand", property.is_shorthand())
+                .with("computed", computed),
+            )
+          })
+          .collect::<Result<Vec<_>>>()?;
+        if let Some(rest) = object.rest() {
+          properties.push(self.rest(rest)?);
+        }
+        Value::node("ObjectPattern").with("properties", properties)
+      }
+      Pattern::Array(array) => {
+        let mut elements = array
+          .elements()
+          .iter()
+          .map(|element| self.optional(element.as_ref(), Self::pattern_element))
+          .collect::<Result<Vec<_>>>()?;
+        if let Some(rest) = array.rest() {
+          elements.push(self.rest(rest)?);
+        }
+        Value::node("ArrayPattern").with("elements", elements)
+      }
+      Pattern::Member(member) => self.expression(member)?,
+    };
+    Ok(self.located(value, pattern.span()))
+  }
+
+  /// Converts a pattern with an optional default value.
+  fn pattern_element(&self, element: &PatternElement) -> Result<Value> {
+    self.with_default(element.target(), element.default())
+  }
+
+  /// Converts a pattern, wrapped in an `AssignmentPattern` if it has a default value.
+  fn with_default(&self, target: &Pattern, default: Option<&Node>) -> Result<Value> {
+    let target = self.pattern(target)?;
+    Ok(match default {
+      Some(default) => Value::node("AssignmentPattern")
+        .with("left", target)
+        .with("right", self.expression(default)?),
+      None => target,
+    })
+  }
+
+  /// Converts the target of a rest element, such as `...rest`.
+  fn rest(&self, target: &Pattern) -> Result<Value> {
+    Ok(Value::node("RestElement").with("argument", self.pattern(target)?))
+  }
+
+  /// Converts the parameters of a function.
+  fn parameters(&self, parameters: &[FormalParameter]) -> Result<Vec<Value>> {
+    parameters
+      .iter()
+      .map(|param| {
+        let value = if param.is_rest_param() {
+          self.rest(param.target())?
+        } else {
+          self.with_default(param.target(), param.init())?
+        };
+        Ok(self.located(value, param.span()))
+      })
+      .collect()
+  }
+
+  /// Converts a function declaration or expression.
+  fn function(
+    &self,
+    kind: &str,
+    id: Value,
+    parameters: &[FormalParameter],
+    body: &[Node],
+    function_kind: FunctionKind,
+  ) -> Result<Value> {
+    let (is_async, is_generator) = match function_kind {
+      FunctionKind::Ordinary => (false, false),
+      FunctionKind::Generator => (false, true),
+      FunctionKind::Async => (true, false),
+      FunctionKind::AsyncGenerator => (true, true),
+    };
+    Ok(
+      Value::node(kind)
+        .with("id", id)
+        .with("expression", false)
+        .with("generator", is_generator)
+        .with("async", is_async)
+        .with("params", self.parameters(parameters)?)
+        .with("body", self.block(body)?),
+    )
+  }
+
+  /// Converts a function expression, also used for methods.
+  fn function_expr(&self, function: &FunctionExpr) -> Result<Value> {
+    let value = self.function(
+      "FunctionExpression",
+      self.name(function.name().map(Sym::as_str), function.name_span()),
+      function.parameters(),
+      function.body(),
+      function.kind(),
+    )?;
+    Ok(self.located(value, function.span()))
+  }
+
+  /// Converts an arrow function.
+  ///
+  /// The syntax tree does not tell `() => { return a; }` from `() => a`, both are written with
+  /// an expression body.
+  fn arrow(&self, arrow: &ArrowFunctionDecl) -> Result<Value> {
+    let (expression, body) = match arrow.body() {
+      [Node::Return(ret)] if ret.expr().is_some() => {
+        (true, self.expression(ret.expr().expect("checked above"))?)
+      }
+      body => (false, self.block(body)?),
+    };
+    Ok(
+      Value::node("ArrowFunctionExpression")
+        .with("id", Value::Null)
+        .with("expression", expression)
+        .with("generator", false)
+        .with("async", arrow.is_async())
+        .with("params", self.parameters(arrow.params())?)
+        .with("body", body),
+    )
+  }
+
+  /// Converts a class declaration or expression.
+  fn class(&self, kind: &str, class: &Class) -> Result<Value> {
+    let mut body = Vec::with_capacity(class.elements().len() + 1);
+    if let Some(constructor) = class.constructor() {
+      let value = Value::node("MethodDefinition")
+        .with("key", identifier("constructor"))
+        .with("value", self.function_expr(constructor)?)
+        .with("kind", "constructor")
+        .with("computed", false)
+        .with("static", false);
+      body.push(self.located(value, class.constructor_span()));
+    }
+    for element in class.elements() {
+      let value = match element {
+        ClassElement::Method {
+          name,
+          kind,
+          function,
+          is_static,
+          ..
+        } => {
+          let (key, computed) = self.class_element_name(name)?;
+          let kind = match kind {
+            MethodDefinitionKind::Get => "get",
+            MethodDefinitionKind::Set => "set",
+            MethodDefinitionKind::Ordinary => "method",
+          };
+          Value::node("MethodDefinition")
+            .with("key", key)
+            .with("value", self.function_expr(function)?)
+            .with("kind", kind)
+            .with("computed", computed)
+            .with("static", *is_static)
+        }
+        ClassElement::Field {
+          name,
+          init,
+          is_static,
+          ..
+        } => {
+          let (key, computed) = self.class_element_name(name)?;
+          Value::node("PropertyDefinition")
+            .with("key", key)
+            .with("value", self.optional(init.as_ref(), Self::expression)?)
+            .with("computed", computed)
+            .with("static", *is_static)
+        }
+        ClassElement::StaticBlock(block, _) => {
+          Value::node("StaticBlock").with("body", self.statements(block.items())?)
+        }
+      };
+      body.push(self.located(value, element.span()));
+    }
+    let value = Value::node(kind)
+      .with(
+        "id",
+        self.name(class.name().map(Sym::as_str), class.name_span()),
+      )
+      .with(
+        "superClass",
+        self.optional(class.super_ref(), Self::expression)?,
+      )
+      .with("body", Value::node("ClassBody").with("body", body));
+    Ok(self.located(value, class.span()))
+  }
+
+  /// Converts the name of a class element, telling whether it is computed.
+  fn class_element_name(&self, name: &ClassElementName) -> Result<(Value, bool)> {
+    match name {
+      ClassElementName::PropertyName(name) => self.property_name(name),
+      ClassElementName::PrivateName(name) => Ok((private_identifier(name), false)),
+    }
+  }
+
+  /// Converts a property name, telling whether it is computed.
+  fn property_name(&self, name: &PropertyName) -> Result<(Value, bool)> {
+    Ok(match name {
+      PropertyName::Literal(name) if is_identifier_name(name) => (identifier(name), false),
+      PropertyName::Literal(name) => match name_to_number(name) {
+        Some(number) => (Value::node("Literal").with("value", number), false),
+        None => (string_literal(name), false),
+      },
+      PropertyName::Computed(node) => (self.expression(node)?, true),
+    })
+  }
+
+  /// Converts an object literal.
+  fn object(&self, object: &Object) -> Result<Value> {
+    let properties = object
+      .properties()
+      .iter()
+      .map(|property| {
+        let value = match property {
+          PropertyDefinition::IdentifierReference(ident, _) => {
+            let name = self.located(identifier(ident.name()), ident.span());
+            Value::node("Property")
+              .with("key", name.clone())
+              .with("value", name)
+              .with("kind", "init")
+              .with("method", false)
+              .with("shorthand", true)
+              .with("computed", false)
+          }
+          PropertyDefinition::Property(name, value, _) => {
+            let (key, computed) = self.property_name(name)?;
+            Value::node("Property")
+              .with("key", key)
+              .with("value", self.expression(value)?)
+              .with("kind", "init")
+              .with("method", false)
+              .with("shorthand", false)
+              .with("computed", computed)
+          }
+          PropertyDefinition::MethodDefinition(kind, name, function, _) => {
+            let (key, computed) = self.property_name(name)?;
+            let kind = match kind {
+              MethodDefinitionKind::Get => "get",
+              MethodDefinitionKind::Set => "set",
+              MethodDefinitionKind::Ordinary => "init",
+            };
+            Value::node("Property")
+              .with("key", key)
+              .with("value", self.function_expr(function)?)
+              .with("kind", kind)
+              .with("method", kind == "init")
+              .with("shorthand", false)
+              .with("computed", computed)
+          }
+          PropertyDefinition::SpreadObject(node, _) => {
+            Value::node("SpreadElement").with("argument", self.expression(node)?)
+          }
+        };
+        Ok(self.located(value, property.span()))
+      })
+      .collect::<Result<Vec<_>>>()?;
+    Ok(Value::node("ObjectExpression").with("properties", properties))
+  }
+
+  /// Converts a template literal.
+  fn template(&self, template: &TemplateLit) -> Result<Value> {
+    let quasis = template.quasis();
+    let quasis: Vec<_> = quasis
+      .iter()
+      .enumerate()
+      .map(|(index, quasi)| {
+        let value = Value::Object(Vec::new())
+          .with("raw", quasi.raw())
+          .with("cooked", quasi.cooked());
+        Value::node("TemplateElement")
+          .with("value", value)
+          .with("tail", index + 1 == quasis.len())
+      })
+      .collect();
+    Ok(
+      Value::node("TemplateLiteral")
+        .with("quasis", quasis)
+        .with("expressions", self.expressions(template.exprs())?),
+    )
+  }
+
+  /// Converts an `import` declaration.
+  fn import(&self, import: &ImportDecl) -> Result<Value> {
+    let specifiers: Vec<_> = import
+      .specifiers()
+      .iter()
+      .map(|specifier| {
+        let local = specifier.local_identifier();
+        let local = self.located(identifier(local.name()), local.span());
+        match specifier {
+          ImportSpecifier::Default(_) => Value::node("ImportDefaultSpecifier").with("local", local),
+          ImportSpecifier::Namespace(_) => {
+            Value::node("ImportNamespaceSpecifier").with("local", local)
+          }
+          ImportSpecifier::Named { imported, .. } => Value::node("ImportSpecifier")
+            .with("imported", module_export_name(imported))
+            .with("local", local),
+        }
+      })
+      .collect();
+    Ok(
+      Value::node("ImportDeclaration")
+        .with("specifiers", specifiers)
+        .with("source", string_literal(import.source()))
+        .with("attributes", attributes(import.attributes())),
+    )
+  }
+
+  /// Converts an `export` declaration.
+  fn export(&self, export: &ExportDecl) -> Result<Value> {
+    Ok(match export {
+      ExportDecl::Named {
+        specifiers,
+        source,
+        attributes: attrs,
+      } => {
+        let specifiers: Vec<_> = specifiers
+          .iter()
+          .map(|specifier| {
+            Value::node("ExportSpecifier")
+              .with("local", module_export_name(specifier.local()))
+              .with("exported", module_export_name(specifier.exported()))
+          })
+          .collect();
+        Value::node("ExportNamedDeclaration")
+          .with("declaration", Value::Null)
+          .with("specifiers", specifiers)
+          .with("source", source.as_deref().map(string_literal))
+          .with("attributes", attributes(attrs))
+      }
+      ExportDecl::All {
+        alias,
+        source,
+        attributes: attrs,
+      } => Value::node("ExportAllDeclaration")
+        .with("exported", alias.as_deref().map(module_export_name))
+        .with("source", string_literal(source))
+        .with("attributes", attributes(attrs)),
+      ExportDecl::Declaration(decl) => Value::node("ExportNamedDeclaration")
+        .with("declaration", self.statement(decl)?)
+        .with("specifiers", Vec::<Value>::new())
+        .with("source", Value::Null)
+        .with("attributes", Vec::<Value>::new()),
+      ExportDecl::DefaultFunction(function) => Value::node("ExportDefaultDeclaration").with(
+        "declaration",
+        self.function(
+          "FunctionDeclaration",
+          self.name(function.name().map(Sym::as_str), function.name_span()),
+          function.parameters(),
+          function.body(),
+          function.kind(),
+        )?,
+      ),
+      ExportDecl::DefaultClass(class) => Value::node("ExportDefaultDeclaration")
+        .with("declaration", self.class("ClassDeclaration", class)?),
+      ExportDecl::DefaultExpression(expr) => {
+        Value::node("ExportDefaultDeclaration").with("declaration", self.expression(expr)?)
+      }
+    })
+  }
+}
+
+/// Creates an `Identifier`.
+fn identifier(name: &str) -> Value {
+  Value::node("Identifier").with("name", name)
+}
+
+/// Creates a `PrivateIdentifier`, from a name without its leading `#`.
+fn private_identifier(name: &str) -> Value {
+  Value::node("PrivateIdentifier").with("name", name)
+}
+
+/// Creates a string `Literal`.
+fn string_literal(value: &str) -> Value {
+  Value::node("Literal").with("value", value)
+}
+
+/// Creates the name of an imported or exported binding, a string literal if it is not a valid
+/// identifier, as in `export { a as "a b" }`.
+fn module_export_name(name: &str) -> Value {
+  if is_identifier_name(name) {
+    identifier(name)
+  } else {
+    string_literal(name)
+  }
+}
+
+/// Converts import attributes, such as `with { type: "json" }`.
+fn attributes(attributes: &[ImportAttribute]) -> Vec<Value> {
+  attributes
+    .iter()
+    .map(|attribute| {
+      Value::node("ImportAttribute")
+        .with("key", module_export_name(attribute.key()))
+        .with("value", string_literal(attribute.value()))
+    })
+    .collect()
+}
+
+/// Converts a constant to a `Literal`.
+fn literal(value: &Const) -> Result<Value> {
+  let literal = Value::node("Literal");
+  Ok(match value {
+    Const::String(s) => literal.with("value", &**s),
+    Const::Num(num) if num.is_nan() => return Err("NaN has no literal form".into()),
+    Const::Num(num) if *num == f64::INFINITY => {
+      literal.with("value", Value::Null).with("raw", "1e999")
+    }
+    Const::Num(num) if num.is_infinite() => {
+      return Err("negative infinity has no literal form".into())
+    }
+    Const::Num(num) => literal.with("value", *num),
+    Const::Int(num) => literal.with("value", f64::from(*num)),
+    Const::Bool(b) => literal.with("value", *b),
+    Const::Null => literal.with("value", Value::Null),
+    Const::Undefined => identifier("undefined"),
+    Const::RegExp(pattern, global, ignore_case) => {
+      let mut flags = String::new();
+      if *global {
+        flags.push('g');
+      }
+      if *ignore_case {
+        flags.push('i');
+      }
+      let regex = Value::Object(vec![
+        ("pattern".into(), pattern.as_str().into()),
+        ("flags".into(), flags.as_str().into()),
+      ]);
+      literal.with("value", Value::Null).with("regex", regex)
+    }
+  })
+}
+
+/// Computes the `loc` and `range` fields of nodes from positions in the source.
+struct LineIndex<'a> {
+  /// The source lines, with the UTF-16 offset of their start.
+  lines: Vec<(usize, &'a str)>,
+}
+
+impl<'a> LineIndex<'a> {
+  /// Splits the source into lines, with the same line terminators as the lexer.
+  fn new(source: &'a str) -> Self {
+    let mut lines = Vec::new();
+    let (mut start, mut offset, mut line_offset) = (0, 0, 0);
+    let mut chars = source.char_indices().peekable();
+    while let Some((i, ch)) = chars.next() {
+      offset += ch.len_utf16();
+      let end = match ch {
+        '\r' => match chars.peek() {
+          Some((_, '\n')) => {
+            chars.next();
+            offset += 1;
+            i + 2
+          }
+          _ => i + 1,
+        },
+        '\n' | '\u{2028}' | '\u{2029}' => i + ch.len_utf8(),
+        _ => continue,
+      };
+      lines.push((line_offset, &source[start..i]));
+      start = end;
+      line_offset = offset;
+    }
+    lines.push((line_offset, &source[start..]));
+    Self { lines }
+  }
+
+  /// Gets the span of the whole source.
+  fn full_span(&self) -> Span {
+    let (_, last) = self.lines.last().expect("there is always a line");
+    let end = Position::new(self.lines.len() as u32, last.chars().count() as u32 + 1);
+    Span::new(Position::new(1, 1), end)
+  }
+
+  /// Gets the UTF-16 column and offset of a position.
+  fn offset(&self, position: Position) -> (usize, usize) {
+    let (line_offset, text) = self
+      .lines
+      .get(position.line_number() as usize - 1)
+      .copied()
+      .unwrap_or((0, ""));
+    let column = text
+      .chars()
+      .take(position.column_number() as usize - 1)
+      .map(char::len_utf16)
+      .sum::<usize>();
+    (column, line_offset + column)
+  }
+
+  /// Adds the `loc` and `range` fields of a node.
+  fn locate(&self, node: Value, span: Span) -> Value {
+    let (start_column, start) = self.offset(span.start());
+    let (end_column, end) = self.offset(span.end());
+    let position = |line: u32, column: usize| {
+      Value::Object(vec![
+        ("line".into(), f64::from(line).into()),
+        ("column".into(), (column as f64).into()),
+      ])
+    };
+    let loc = Value::Object(vec![
+      (
+        "start".into(),
+        position(span.start().line_number(), start_column),
+      ),
+      ("end".into(), position(span.end().line_number(), end_column)),
+    ]);
+    node
+      .with("loc", loc)
+      .with("range", vec![(start as f64).into(), (end as f64).into()])
+  }
+}
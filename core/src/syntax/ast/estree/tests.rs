@@ -0,0 +1,296 @@
+//! Tests for the ESTree conversion.
+
+use super::{Estree, Program};
+use crate::syntax::parser::Parser;
+use serde_json::{json, Value};
+
+/// Parses a script and serializes it.
+#[track_caller]
+fn script_json(js: &str) -> Value {
+  let ast = Parser::new(js.as_bytes())
+    .parse_script()
+    .expect("failed to parse the script");
+  serde_json::to_value(Estree::script(&ast)).expect("failed to serialize the script")
+}
+
+/// Gets the first statement of a serialized program.
+#[track_caller]
+fn first_statement(js: &str) -> Value {
+  script_json(js)["body"][0].clone()
+}
+
+/// Checks that a script is read back from its JSON form unchanged.
+#[track_caller]
+fn check_script_round_trip(js: &str) {
+  let ast = Parser::new(js.as_bytes())
+    .parse_script()
+    .expect("failed to parse the script");
+  let json = serde_json::to_string(&Estree::script(&ast)).expect("failed to serialize");
+  let program: Program = serde_json::from_str(&json).expect("failed to deserialize");
+  assert_eq!(program, Program::Script(ast), "round trip of `{}`", js);
+}
+
+/// Checks that a module is read back from its JSON form unchanged.
+#[track_caller]
+fn check_module_round_trip(js: &str) {
+  let module = Parser::new(js.as_bytes())
+    .parse_module()
+    .expect("failed to parse the module");
+  let json = serde_json::to_string(&Estree::module(&module)).expect("failed to serialize");
+  let program: Program = serde_json::from_str(&json).expect("failed to deserialize");
+  assert_eq!(program, Program::Module(module), "round trip of `{}`", js);
+}
+
+#[test]
+fn program() {
+  assert_eq!(
+    script_json("a;"),
+    json!({
+      "type": "Program",
+      "sourceType": "script",
+      "body": [{
+        "type": "ExpressionStatement",
+        "expression": { "type": "Identifier", "name": "a" },
+      }],
+    })
+  );
+}
+
+#[test]
+fn location() {
+  let js = "let é = 1;\nf(é);";
+  let ast = Parser::new(js.as_bytes()).parse_script().unwrap();
+  let json = serde_json::to_value(Estree::script(&ast).with_source(js)).unwrap();
+  assert_eq!(json["range"], json!([0, 16]));
+  assert_eq!(
+    json["loc"],
+    json!({
+      "start": { "line": 1, "column": 0 },
+      "end": { "line": 2, "column": 5 },
+    })
+  );
+  assert_eq!(json["body"][0]["declarations"][0]["range"], json!([4, 9]));
+  assert_eq!(json["body"][1]["range"], json!([11, 15]));
+  let argument = &json["body"][1]["expression"]["arguments"][0];
+  assert_eq!(argument["range"], json!([13, 14]));
+  assert_eq!(
+    argument["loc"],
+    json!({
+      "start": { "line": 2, "column": 2 },
+      "end": { "line": 2, "column": 3 },
+    })
+  );
+
+  // Names of functions and classes, and labels, have their own location.
+  let js = "function f() {}\nl: for (;;) break l;\nnew class C {};";
+  let ast = Parser::new(js.as_bytes()).parse_script().unwrap();
+  let json = serde_json::to_value(Estree::script(&ast).with_source(js)).unwrap();
+  assert_eq!(json["body"][0]["id"]["range"], json!([9, 10]));
+  assert_eq!(json["body"][1]["label"]["range"], json!([16, 17]));
+  assert_eq!(
+    json["body"][1]["body"]["body"]["label"]["range"],
+    json!([34, 35])
+  );
+  assert_eq!(
+    json["body"][2]["expression"]["callee"]["id"]["range"],
+    json!([47, 48])
+  );
+}
+
+#[test]
+fn expressions() {
+  assert_eq!(
+    first_statement("a.b?.[c]();")["expression"],
+    json!({
+      "type": "ChainExpression",
+      "expression": {
+        "type": "CallExpression",
+        "callee": {
+          "type": "MemberExpression",
+          "object": {
+            "type": "MemberExpression",
+            "object": { "type": "Identifier", "name": "a" },
+            "property": { "type": "Identifier", "name": "b" },
+            "computed": false,
+            "optional": false,
+          },
+          "property": { "type": "Identifier", "name": "c" },
+          "computed": true,
+          "optional": true,
+        },
+        "arguments": [],
+        "optional": false,
+      },
+    })
+  );
+  assert_eq!(
+    first_statement("a, b, c;")["expression"]["type"],
+    "SequenceExpression"
+  );
+  assert_eq!(
+    first_statement("a ?? b;")["expression"]["type"],
+    "LogicalExpression"
+  );
+  assert_eq!(first_statement("a **= 2;")["expression"]["operator"], "**=");
+}
+
+#[test]
+fn literals() {
+  let json = script_json("'a'; 1; 1.5; true; null; 1e999;");
+  let literal = |i: usize| json["body"][i]["expression"].clone();
+  assert_eq!(literal(0)["value"], "a");
+  assert_eq!(json["body"][0]["directive"], "a");
+  assert_eq!(literal(1), json!({ "type": "Literal", "value": 1 }));
+  assert_eq!(literal(2)["value"], 1.5);
+  assert_eq!(literal(3)["value"], true);
+  assert_eq!(literal(4)["value"], Value::Null);
+  assert_eq!(literal(5)["raw"], "1e999");
+}
+
+#[test]
+fn templates() {
+  let template = first_statement("`a${b}\\n`;")["expression"].clone();
+  assert_eq!(template["type"], "TemplateLiteral");
+  assert_eq!(
+    template["quasis"][1],
+    json!({ "type": "TemplateElement", "value": { "raw": "\\n", "cooked": "\n" }, "tail": true })
+  );
+  assert_eq!(template["expressions"][0]["name"], "b");
+
+  let tagged = first_statement("f`\\u`;")["expression"].clone();
+  assert_eq!(tagged["type"], "TaggedTemplateExpression");
+  assert_eq!(tagged["tag"]["name"], "f");
+  assert_eq!(tagged["quasi"]["quasis"][0]["value"]["cooked"], Value::Null);
+}
+
+#[test]
+fn directives() {
+  let json = script_json("'use strict'; f('not a directive');");
+  assert_eq!(json["body"][0]["directive"], "use strict");
+  assert_eq!(json["body"][1].get("directive"), None);
+
+  // The directive is the source text, and a parenthesized string is not one.
+  let json = script_json("'use\\x20strict'; ('a');");
+  assert_eq!(json["body"][0]["directive"], "use\\x20strict");
+  assert_eq!(json["body"][0]["expression"]["value"], "use strict");
+  assert_eq!(json["body"][1].get("directive"), None);
+  check_script_round_trip("'use\\x20strict'; \"it's\"; ('a');");
+}
+
+#[test]
+fn classes() {
+  let class = first_statement("class A { #x = 1; constructor() {} static { } get [k]() {} }");
+  let body = &class["body"]["body"];
+  assert_eq!(body[0]["kind"], "constructor");
+  assert_eq!(body[1]["type"], "PropertyDefinition");
+  assert_eq!(
+    body[1]["key"],
+    json!({ "type": "PrivateIdentifier", "name": "x" })
+  );
+  assert_eq!(body[2]["type"], "StaticBlock");
+  assert_eq!(body[3]["kind"], "get");
+  assert_eq!(body[3]["computed"], true);
+}
+
+#[test]
+fn module() {
+  let js = "import a, { b as c } from 'm' with { type: 'json' }; export * as ns from 'n';";
+  let module = Parser::new(js.as_bytes()).parse_module().unwrap();
+  let json = serde_json::to_value(Estree::module(&module)).unwrap();
+  assert_eq!(json["sourceType"], "module");
+  let import = &json["body"][0];
+  assert_eq!(import["specifiers"][0]["type"], "ImportDefaultSpecifier");
+  assert_eq!(import["specifiers"][1]["imported"]["name"], "b");
+  assert_eq!(import["attributes"][0]["value"]["value"], "json");
+  assert_eq!(json["body"][1]["type"], "ExportAllDeclaration");
+  assert_eq!(json["body"][1]["exported"]["name"], "ns");
+}
+
+#[test]
+fn script_round_trip() {
+  check_script_round_trip(
+    r#"
+    "use strict";
+    var a = 1, b = -0, c = 2.5, d = 1e999, e = "s", f = null;
+    let [h, , ...i] = [1, , 3];
+    const { j, k: [l] = [], 'm-n': o, 1: p, ...q } = {};
+    function* gen(x, y = 1, ...z) { yield x; yield* z; }
+    async function run() { await gen(); for await (const v of w) {} }
+    const arrow = async (x) => x + 1, block = () => { return; };
+    label: for (let i = 0; i < 10; i++) { if (i) continue label; else break; }
+    for (var key in obj) {}
+    for (x.y of list) {}
+    while (a) do b--; while (c);
+    switch (a) { case 1: b(); default: }
+    try { throw new Error("e"); } catch ({ message }) {} finally { debugger; }
+    class A extends B {
+      static #count = 0;
+      constructor() { super(); }
+      static { A.#count++; }
+      get value() { return #count in this; }
+      [Symbol.iterator]() {}
+    }
+    x = a ? b : c, y ??= z, o = { a, b: 1, [c]: 2, get d() {}, set d(v) {}, e() {}, ...f };
+    typeof a, void 0, delete a.b, !a, ~a, -a, +a, ++a, a++;
+    a?.b.c?.(d)?.[e];
+    (function named() {}), import("m"), [...a];
+    `a${b}c${`d`}`, tag`\unicode${e}`;
+    "#,
+  );
+}
+
+#[test]
+fn function_strictness_round_trip() {
+  check_script_round_trip("function f() { 'use strict'; return () => 1; } () => 2;");
+  check_script_round_trip("class A { m() { return () => this; } }");
+}
+
+#[test]
+fn module_round_trip() {
+  check_module_round_trip(
+    r#"
+    import def, * as ns from "a";
+    import { b, c as d, "e f" as g } from "b" with { type: "json" };
+    export const h = import.meta.url;
+    export { h as i, def };
+    export * from "c";
+    export * as j from "d";
+    export { k } from "e";
+    export default function () {}
+    "#,
+  );
+  check_module_round_trip("export default class {}");
+  check_module_round_trip("export default 1 + 2;");
+  check_module_round_trip("export async function f() { await 1; }");
+}
+
+#[test]
+fn error_nodes() {
+  let recovered = Parser::new("let a = ;".as_bytes()).parse_script_with_recovery();
+  let error = serde_json::to_string(&Estree::script(recovered.ast())).unwrap_err();
+  assert!(error.to_string().contains("syntax error"), "{}", error);
+}
+
+#[test]
+fn invalid_input() {
+  let read = |json: Value| {
+    serde_json::from_value::<Program>(json)
+      .unwrap_err()
+      .to_string()
+  };
+  assert_eq!(
+    read(json!({ "type": "Script", "body": [] })),
+    "expected `Program`, found `Script`"
+  );
+  assert_eq!(
+    read(json!({ "type": "Program", "body": [{ "type": "Identifier", "name": "a" }] })),
+    "unexpected `Identifier` in statement position"
+  );
+  assert_eq!(
+    read(json!({
+      "type": "Program",
+      "body": [{ "type": "ExpressionStatement", "expression": { "type": "TemplateElement" } }],
+    })),
+    "unexpected `TemplateElement` in expression position"
+  );
+}
@@ -0,0 +1,211 @@
+//! A JSON-like value, the intermediate form of ESTree trees.
+
+use serde::{
+  de::{self, MapAccess, SeqAccess, Visitor},
+  ser::{SerializeMap, SerializeSeq},
+  Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::fmt;
+
+/// Numbers up to this magnitude are written as integers, as JavaScript does.
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// A value of an ESTree tree.
+///
+/// Object keys keep their insertion order, so that `type` always comes first.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Value {
+  Null,
+  Bool(bool),
+  Number(f64),
+  String(Box<str>),
+  Array(Vec<Value>),
+  Object(Vec<(Box<str>, Value)>),
+}
+
+impl Value {
+  /// Creates a node object of the given type.
+  pub(super) fn node(kind: &str) -> Self {
+    Self::Object(vec![("type".into(), kind.into())])
+  }
+
+  /// Adds a field to an object.
+  pub(super) fn with<V>(mut self, key: &str, value: V) -> Self
+  where
+    V: Into<Self>,
+  {
+    if let Self::Object(fields) = &mut self {
+      fields.push((key.into(), value.into()));
+    }
+    self
+  }
+
+  /// Gets a field of an object, treating `null` like a missing field.
+  pub(super) fn get(&self, key: &str) -> Option<&Self> {
+    match self {
+      Self::Object(fields) => fields
+        .iter()
+        .find(|(name, _)| &**name == key)
+        .map(|(_, value)| value)
+        .filter(|value| **value != Self::Null),
+      _ => None,
+    }
+  }
+
+  /// Gets the string, if this is one.
+  pub(super) fn as_str(&self) -> Option<&str> {
+    match self {
+      Self::String(s) => Some(s),
+      _ => None,
+    }
+  }
+}
+
+impl From<&str> for Value {
+  fn from(value: &str) -> Self {
+    Self::String(value.into())
+  }
+}
+
+impl From<bool> for Value {
+  fn from(value: bool) -> Self {
+    Self::Bool(value)
+  }
+}
+
+impl From<f64> for Value {
+  fn from(value: f64) -> Self {
+    Self::Number(value)
+  }
+}
+
+impl From<Vec<Value>> for Value {
+  fn from(values: Vec<Value>) -> Self {
+    Self::Array(values)
+  }
+}
+
+impl<T> From<Option<T>> for Value
+where
+  T: Into<Value>,
+{
+  fn from(value: Option<T>) -> Self {
+    value.map_or(Self::Null, Into::into)
+  }
+}
+
+impl Serialize for Value {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    match self {
+      Self::Null => serializer.serialize_unit(),
+      Self::Bool(b) => serializer.serialize_bool(*b),
+      // `-0` stays a float, as an integer would lose its sign.
+      Self::Number(n)
+        if n.fract() == 0.0
+          && n.abs() <= MAX_SAFE_INTEGER
+          && !(*n == 0.0 && n.is_sign_negative()) =>
+      {
+        serializer.serialize_i64(*n as i64)
+      }
+      Self::Number(n) => serializer.serialize_f64(*n),
+      Self::String(s) => serializer.serialize_str(s),
+      Self::Array(values) => {
+        let mut seq = serializer.serialize_seq(Some(values.len()))?;
+        for value in values {
+          seq.serialize_element(value)?;
+        }
+        seq.end()
+      }
+      Self::Object(fields) => {
+        let mut map = serializer.serialize_map(Some(fields.len()))?;
+        for (key, value) in fields {
+          map.serialize_entry(&**key, value)?;
+        }
+        map.end()
+      }
+    }
+  }
+}
+
+impl<'de> Deserialize<'de> for Value {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    deserializer.deserialize_any(ValueVisitor)
+  }
+}
+
+/// Builds a [`Value`] from any self-describing format.
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+  type Value = Value;
+
+  fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("an ESTree value")
+  }
+
+  fn visit_unit<E>(self) -> Result<Value, E> {
+    Ok(Value::Null)
+  }
+
+  fn visit_none<E>(self) -> Result<Value, E> {
+    Ok(Value::Null)
+  }
+
+  fn visit_some<D>(self, deserializer: D) -> Result<Value, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    Value::deserialize(deserializer)
+  }
+
+  fn visit_bool<E>(self, value: bool) -> Result<Value, E> {
+    Ok(Value::Bool(value))
+  }
+
+  fn visit_i64<E>(self, value: i64) -> Result<Value, E> {
+    Ok(Value::Number(value as f64))
+  }
+
+  fn visit_u64<E>(self, value: u64) -> Result<Value, E> {
+    Ok(Value::Number(value as f64))
+  }
+
+  fn visit_f64<E>(self, value: f64) -> Result<Value, E> {
+    Ok(Value::Number(value))
+  }
+
+  fn visit_str<E>(self, value: &str) -> Result<Value, E>
+  where
+    E: de::Error,
+  {
+    Ok(Value::String(value.into()))
+  }
+
+  fn visit_seq<A>(self, mut seq: A) -> Result<Value, A::Error>
+  where
+    A: SeqAccess<'de>,
+  {
+    let mut values = Vec::new();
+    while let Some(value) = seq.next_element()? {
+      values.push(value);
+    }
+    Ok(Value::Array(values))
+  }
+
+  fn visit_map<A>(self, mut map: A) -> Result<Value, A::Error>
+  where
+    A: MapAccess<'de>,
+  {
+    let mut fields = Vec::new();
+    while let Some((key, value)) = map.next_entry::<String, Value>()? {
+      fields.push((key.into_boxed_str(), value));
+    }
+    Ok(Value::Object(fields))
+  }
+}
@@ -0,0 +1,59 @@
+//! Ids of the nodes that name bindings.
+//!
+//! Identifiers, function and class declarations and expressions, and import and export
+//! specifiers each get an id when they are created. A clone keeps the id of its original, so
+//! what is recorded by id for a syntax tree, such as the declarations and references of a
+//! [`ScopeTree`](crate::syntax::scope::ScopeTree), still holds once the tree is moved or
+//! cloned:
+//!
+//! ```
+//! use plastic::syntax::ast::node::Identifier;
+//!
+//! let count = Identifier::new("count");
+//! let copy = count.clone();
+//! assert_eq!(copy.id(), count.id());
+//! assert_ne!(Identifier::new("count").id(), count.id());
+//! ```
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The id of a node of the syntax tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(usize);
+
+impl NodeId {
+  /// Creates an id that no other node has.
+  pub fn new() -> Self {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    Self(NEXT.fetch_add(1, Ordering::Relaxed))
+  }
+}
+
+impl Default for NodeId {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// The id of a node, as a field of the node.
+///
+/// Comparing nodes ignores their ids, as it ignores their spans, so trees are equal when they
+/// have the same structure, whatever nodes they were cloned from.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Id(NodeId);
+
+impl Id {
+  /// Gets the id.
+  #[inline]
+  pub(crate) fn get(self) -> NodeId {
+    self.0
+  }
+}
+
+impl PartialEq for Id {
+  fn eq(&self, _: &Self) -> bool {
+    true
+  }
+}
+
+impl Eq for Id {}
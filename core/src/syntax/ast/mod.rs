@@ -1,12 +1,23 @@
+pub mod arena;
 pub mod constant;
+#[cfg(feature = "serde")]
+pub mod estree;
+pub mod id;
 pub mod position;
 pub mod punctuator;
 pub mod keyword;
+pub mod module;
+pub mod node;
 pub mod operator;
+pub mod sym;
+pub mod visitor;
 
 pub use self::{
   keyword::Keyword,
   constant::Const,
-  position::{Position, Span},
+  id::NodeId,
+  node::Node,
+  position::{NodeSpan, Position, Span},
   punctuator::Punctuator,
+  sym::Sym,
 };
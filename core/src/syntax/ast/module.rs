@@ -0,0 +1,314 @@
+//! Parsed modules and the summary of their imports and exports.
+//!
+//! The summary follows the import and export entry records of the specification, so that
+//! loaders and bundlers can link modules together without walking the syntax tree.
+//!
+//! More information:
+//!  - [ECMAScript specification][spec]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-source-text-module-records
+
+use super::{
+  node::{ExportDecl, ImportSpecifier, Node, StatementList},
+  Sym,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The local name used for default exported expressions and anonymous declarations.
+pub const DEFAULT_LOCAL_NAME: &str = "*default*";
+
+/// A parsed module, its body together with the summary of its imports and exports.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Module {
+  body: StatementList,
+  summary: ModuleSummary,
+}
+
+impl Module {
+  /// Creates a new module from its body, computing the summary of its imports and exports.
+  pub fn new<B>(body: B) -> Self
+  where
+    B: Into<StatementList>,
+  {
+    let body = body.into();
+    let summary = ModuleSummary::new(&body);
+    Self { body, summary }
+  }
+
+  /// Gets the statements of the module.
+  pub fn body(&self) -> &StatementList {
+    &self.body
+  }
+
+  /// Gets the summary of the imports and exports of the module.
+  pub fn summary(&self) -> &ModuleSummary {
+    &self.summary
+  }
+
+  /// Consumes the module, returning its statements.
+  pub fn into_body(self) -> StatementList {
+    self.body
+  }
+}
+
+/// The name imported from another module.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ImportName {
+  /// A single exported name, `default` for default imports.
+  Name(Sym),
+  /// The module namespace object, as in `import * as ns` or `export * as ns`.
+  Namespace,
+}
+
+/// A binding imported from another module.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#importentry-record
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImportEntry {
+  module_request: Box<str>,
+  import_name: ImportName,
+  local_name: Sym,
+}
+
+impl ImportEntry {
+  /// Gets the module specifier the binding is imported from.
+  pub fn module_request(&self) -> &str {
+    &self.module_request
+  }
+
+  /// Gets the name imported from the module.
+  pub fn import_name(&self) -> &ImportName {
+    &self.import_name
+  }
+
+  /// Gets the name of the local binding.
+  pub fn local_name(&self) -> &str {
+    &self.local_name
+  }
+}
+
+/// A name exported by a module, or a star re-export.
+///
+/// Local bindings which are themselves imported are reported as indirect exports, so following
+/// `Indirect` entries is enough to find where a binding is defined.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#exportentry-record
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExportEntry {
+  /// A binding declared in the module, such as `export const a = 1;` or `export { a as b };`.
+  Local {
+    /// The name visible to importing modules.
+    export_name: Sym,
+    /// The name of the local binding, `*default*` for default exported expressions.
+    local_name: Sym,
+  },
+  /// A name re-exported from another module, such as `export { a as b } from "mod";` or
+  /// `export * as ns from "mod";`.
+  Indirect {
+    /// The name visible to importing modules.
+    export_name: Sym,
+    /// The module specifier the name is re-exported from.
+    module_request: Box<str>,
+    /// The name imported from that module.
+    import_name: ImportName,
+  },
+  /// All the names of another module, `export * from "mod";`.
+  Star {
+    /// The module specifier the names are re-exported from.
+    module_request: Box<str>,
+  },
+}
+
+impl ExportEntry {
+  /// Gets the name visible to importing modules, star re-exports have none.
+  pub fn export_name(&self) -> Option<&str> {
+    match self {
+      Self::Local { export_name, .. } | Self::Indirect { export_name, .. } => Some(export_name),
+      Self::Star { .. } => None,
+    }
+  }
+}
+
+/// The imports and exports of a module.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ModuleSummary {
+  requested_modules: Vec<Box<str>>,
+  imports: Vec<ImportEntry>,
+  exports: Vec<ExportEntry>,
+}
+
+impl ModuleSummary {
+  /// Computes the summary of the top level declarations of a module.
+  pub fn new(body: &StatementList) -> Self {
+    let mut summary = Self::default();
+
+    for item in body.items() {
+      match item {
+        Node::Import(decl) => {
+          summary.request(decl.source());
+          for specifier in decl.specifiers() {
+            let import_name = match specifier {
+              ImportSpecifier::Default(_) => ImportName::Name("default".into()),
+              ImportSpecifier::Namespace(_) => ImportName::Namespace,
+              ImportSpecifier::Named { imported, .. } => ImportName::Name(imported.clone()),
+            };
+            summary.imports.push(ImportEntry {
+              module_request: decl.source().into(),
+              import_name,
+              local_name: specifier.local().into(),
+            });
+          }
+        }
+        Node::Export(decl, _) => {
+          if let Some(source) = decl.source() {
+            summary.request(source);
+          }
+          summary.add_export(decl);
+        }
+        _ => {}
+      }
+    }
+
+    // Exporting an imported binding is the same as re-exporting it, unless it is a namespace
+    // object, which only exists in the importing module.
+    let imports = &summary.imports;
+    for entry in &mut summary.exports {
+      if let ExportEntry::Local {
+        export_name,
+        local_name,
+      } = entry
+      {
+        let import = imports
+          .iter()
+          .find(|import| import.local_name == *local_name);
+        if let Some(import) = import.filter(|import| import.import_name != ImportName::Namespace) {
+          *entry = ExportEntry::Indirect {
+            export_name: export_name.clone(),
+            module_request: import.module_request.clone(),
+            import_name: import.import_name.clone(),
+          };
+        }
+      }
+    }
+
+    summary
+  }
+
+  /// Gets the module specifiers requested by the module, in source order and without
+  /// duplicates.
+  pub fn requested_modules(&self) -> &[Box<str>] {
+    &self.requested_modules
+  }
+
+  /// Gets the bindings imported by the module.
+  pub fn imports(&self) -> &[ImportEntry] {
+    &self.imports
+  }
+
+  /// Gets the names exported by the module.
+  pub fn exports(&self) -> &[ExportEntry] {
+    &self.exports
+  }
+
+  /// Returns an iterator over the names explicitly exported by the module, star re-exports are
+  /// not expanded.
+  pub fn exported_names(&self) -> impl Iterator<Item = &str> {
+    self.exports.iter().filter_map(ExportEntry::export_name)
+  }
+
+  /// Records a requested module specifier.
+  fn request(&mut self, source: &str) {
+    if !self.requested_modules.iter().any(|m| m.as_ref() == source) {
+      self.requested_modules.push(source.into());
+    }
+  }
+
+  /// Records the entries of a single export declaration.
+  fn add_export(&mut self, decl: &ExportDecl) {
+    match decl {
+      ExportDecl::Named {
+        specifiers,
+        source: None,
+        ..
+      } => {
+        for specifier in specifiers.iter() {
+          self.export_local(specifier.exported(), specifier.local());
+        }
+      }
+      ExportDecl::Named {
+        specifiers,
+        source: Some(source),
+        ..
+      } => {
+        for specifier in specifiers.iter() {
+          self.exports.push(ExportEntry::Indirect {
+            export_name: specifier.exported().into(),
+            module_request: source.clone(),
+            import_name: ImportName::Name(specifier.local().into()),
+          });
+        }
+      }
+      ExportDecl::All {
+        alias: Some(alias),
+        source,
+        ..
+      } => self.exports.push(ExportEntry::Indirect {
+        export_name: alias.clone(),
+        module_request: source.clone(),
+        import_name: ImportName::Namespace,
+      }),
+      ExportDecl::All {
+        alias: None,
+        source,
+        ..
+      } => self.exports.push(ExportEntry::Star {
+        module_request: source.clone(),
+      }),
+      ExportDecl::Declaration(node) => match node.as_ref() {
+        Node::DeclarationList(list, _) => {
+          for declaration in list.as_ref() {
+            for name in declaration.target().bound_names() {
+              self.export_local(name, name);
+            }
+          }
+        }
+        Node::FunctionDecl(function) => self.export_local(function.name(), function.name()),
+        Node::ClassDecl(class) => {
+          let name = class.name().map_or(DEFAULT_LOCAL_NAME, Sym::as_str);
+          self.export_local(name, name);
+        }
+        _ => {}
+      },
+      ExportDecl::DefaultFunction(function) => self.export_local(
+        "default",
+        function.name().map_or(DEFAULT_LOCAL_NAME, Sym::as_str),
+      ),
+      ExportDecl::DefaultClass(class) => self.export_local(
+        "default",
+        class.name().map_or(DEFAULT_LOCAL_NAME, Sym::as_str),
+      ),
+      ExportDecl::DefaultExpression(_) => self.export_local("default", DEFAULT_LOCAL_NAME),
+    }
+  }
+
+  /// Records a local export entry.
+  fn export_local(&mut self, export_name: &str, local_name: &str) {
+    self.exports.push(ExportEntry::Local {
+      export_name: export_name.into(),
+      local_name: local_name.into(),
+    });
+  }
+}
@@ -0,0 +1,63 @@
+//! Array declaration node.
+
+use super::Node;
+use crate::syntax::ast::{arena::ArenaBox, NodeSpan};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// An array is an ordered collection of data (either primitive or object depending upon the
+/// language).
+///
+/// Holes (elisions) are represented by `Node::Empty`, and spread elements by `Node::Spread`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ArrayLiteral
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArrayDecl {
+  arr: ArenaBox<[Node]>,
+  span: NodeSpan,
+}
+
+impl ArrayDecl {
+  /// Gets the elements of the array literal.
+  pub fn elements(&self) -> &[Node] {
+    &self.arr
+  }
+
+  /// Mutable version of [`elements`](Self::elements).
+  pub fn elements_mut(&mut self) -> &mut [Node] {
+    &mut self.arr
+  }
+}
+
+spanned!(ArrayDecl);
+
+impl AsRef<[Node]> for ArrayDecl {
+  fn as_ref(&self) -> &[Node] {
+    &self.arr
+  }
+}
+
+impl<T> From<T> for ArrayDecl
+where
+  T: Into<ArenaBox<[Node]>>,
+{
+  fn from(decl: T) -> Self {
+    Self {
+      arr: decl.into(),
+      span: NodeSpan::default(),
+    }
+  }
+}
+
+impl From<ArrayDecl> for Node {
+  fn from(arr: ArrayDecl) -> Self {
+    Self::ArrayDecl(arr)
+  }
+}
@@ -0,0 +1,54 @@
+//! Await expression node.
+
+use super::Node;
+use crate::syntax::ast::{arena::ArenaBox, NodeSpan};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The `await` operator is used to wait for a promise, it can only be used inside an async
+/// function or at the top level of a module.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-AwaitExpression
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/await
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Await {
+  expr: ArenaBox<Node>,
+  span: NodeSpan,
+}
+
+impl Await {
+  /// Creates a new `Await` AST node.
+  pub fn new<E>(expr: E) -> Self
+  where
+    E: Into<Node>,
+  {
+    Self {
+      expr: ArenaBox::new(expr.into()),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the awaited expression.
+  pub fn expr(&self) -> &Node {
+    &self.expr
+  }
+
+  /// Mutable version of [`expr`](Self::expr).
+  pub fn expr_mut(&mut self) -> &mut Node {
+    &mut self.expr
+  }
+}
+
+spanned!(Await);
+
+impl From<Await> for Node {
+  fn from(await_expr: Await) -> Self {
+    Self::Await(await_expr)
+  }
+}
@@ -0,0 +1,60 @@
+//! Block AST node.
+
+use super::{Node, StatementList};
+use crate::syntax::ast::NodeSpan;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A `block` statement (or compound statement in other languages) is used to group zero or
+/// more statements.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-BlockStatement
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/block
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Block {
+  statements: StatementList,
+  span: NodeSpan,
+}
+
+impl Block {
+  /// Gets the list of statements in this block.
+  pub fn statements(&self) -> &[Node] {
+    self.statements.items()
+  }
+
+  /// Mutable version of [`statements`](Self::statements).
+  pub fn statements_mut(&mut self) -> &mut [Node] {
+    self.statements.items_mut()
+  }
+
+  /// Gets the statement list of the block, to add or remove statements.
+  pub fn statement_list_mut(&mut self) -> &mut StatementList {
+    &mut self.statements
+  }
+}
+
+spanned!(Block);
+
+impl<T> From<T> for Block
+where
+  T: Into<StatementList>,
+{
+  fn from(list: T) -> Self {
+    Self {
+      statements: list.into(),
+      span: NodeSpan::default(),
+    }
+  }
+}
+
+impl From<Block> for Node {
+  fn from(block: Block) -> Self {
+    Self::Block(block)
+  }
+}
@@ -0,0 +1,223 @@
+//! Call, `new` and optional chain nodes.
+
+use super::Node;
+use crate::syntax::ast::{arena::ArenaBox, NodeSpan};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Calling the function actually performs the specified actions with the indicated parameters.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-CallExpression
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Guide/Functions#Calling_functions
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Call {
+  expr: ArenaBox<Node>,
+  args: ArenaBox<[Node]>,
+  optional: bool,
+  pure: bool,
+  span: NodeSpan,
+}
+
+impl Call {
+  /// Creates a new `Call` AST node.
+  pub fn new<E, A>(expr: E, args: A) -> Self
+  where
+    E: Into<Node>,
+    A: Into<ArenaBox<[Node]>>,
+  {
+    Self {
+      expr: ArenaBox::new(expr.into()),
+      args: args.into(),
+      optional: false,
+      pure: false,
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Creates an optional (`f?.(args)`) `Call` AST node.
+  pub fn new_optional<E, A>(expr: E, args: A) -> Self
+  where
+    E: Into<Node>,
+    A: Into<ArenaBox<[Node]>>,
+  {
+    Self {
+      expr: ArenaBox::new(expr.into()),
+      args: args.into(),
+      optional: true,
+      pure: false,
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the name of the function call.
+  pub fn expr(&self) -> &Node {
+    &self.expr
+  }
+
+  /// Mutable version of [`expr`](Self::expr).
+  pub fn expr_mut(&mut self) -> &mut Node {
+    &mut self.expr
+  }
+
+  /// Retrieves the arguments passed to the function.
+  pub fn args(&self) -> &[Node] {
+    &self.args
+  }
+
+  /// Mutable version of [`args`](Self::args).
+  pub fn args_mut(&mut self) -> &mut [Node] {
+    &mut self.args
+  }
+
+  /// Returns `true` if the call was written with `?.`.
+  pub fn optional(&self) -> bool {
+    self.optional
+  }
+
+  /// Returns `true` if the call is annotated as free of side effects, with `/*#__PURE__*/`.
+  pub fn pure(&self) -> bool {
+    self.pure
+  }
+
+  /// Sets whether the call is annotated as free of side effects.
+  pub fn set_pure(&mut self, pure: bool) {
+    self.pure = pure;
+  }
+}
+
+spanned!(Call);
+
+impl From<Call> for Node {
+  fn from(call: Call) -> Self {
+    Self::Call(call)
+  }
+}
+
+/// The `new` operator lets developers create an instance of a user-defined object type or of
+/// one of the built-in object types that has a constructor function.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-NewExpression
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/new
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct New {
+  expr: ArenaBox<Node>,
+  args: ArenaBox<[Node]>,
+  pure: bool,
+  span: NodeSpan,
+}
+
+impl New {
+  /// Creates a new `New` AST node.
+  #[allow(clippy::self_named_constructors)]
+  pub fn new<E, A>(expr: E, args: A) -> Self
+  where
+    E: Into<Node>,
+    A: Into<ArenaBox<[Node]>>,
+  {
+    Self {
+      expr: ArenaBox::new(expr.into()),
+      args: args.into(),
+      pure: false,
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the name of the function call.
+  pub fn expr(&self) -> &Node {
+    &self.expr
+  }
+
+  /// Mutable version of [`expr`](Self::expr).
+  pub fn expr_mut(&mut self) -> &mut Node {
+    &mut self.expr
+  }
+
+  /// Retrieves the arguments passed to the function.
+  pub fn args(&self) -> &[Node] {
+    &self.args
+  }
+
+  /// Mutable version of [`args`](Self::args).
+  pub fn args_mut(&mut self) -> &mut [Node] {
+    &mut self.args
+  }
+
+  /// Returns `true` if the construction is annotated as free of side effects, with
+  /// `/*#__PURE__*/`.
+  pub fn pure(&self) -> bool {
+    self.pure
+  }
+
+  /// Sets whether the construction is annotated as free of side effects.
+  pub fn set_pure(&mut self, pure: bool) {
+    self.pure = pure;
+  }
+}
+
+spanned!(New);
+
+impl From<New> for Node {
+  fn from(new: New) -> Self {
+    Self::New(new)
+  }
+}
+
+/// An optional chain, such as `a?.b.c()`.
+///
+/// The chain wraps the whole member/call expression so that when any `?.` link finds a
+/// `null` or `undefined` base, the entire chain short-circuits to `undefined`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-OptionalExpression
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Optional_chaining
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OptionalChain {
+  expr: ArenaBox<Node>,
+  span: NodeSpan,
+}
+
+impl OptionalChain {
+  /// Creates a new `OptionalChain` AST node.
+  pub fn new<E>(expr: E) -> Self
+  where
+    E: Into<Node>,
+  {
+    Self {
+      expr: ArenaBox::new(expr.into()),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the member or call expression containing the optional links.
+  pub fn expr(&self) -> &Node {
+    &self.expr
+  }
+
+  /// Mutable version of [`expr`](Self::expr).
+  pub fn expr_mut(&mut self) -> &mut Node {
+    &mut self.expr
+  }
+}
+
+spanned!(OptionalChain);
+
+impl From<OptionalChain> for Node {
+  fn from(chain: OptionalChain) -> Self {
+    Self::OptionalChain(chain)
+  }
+}
@@ -0,0 +1,277 @@
+//! Class declaration and expression nodes.
+
+use super::{FunctionExpr, MethodDefinitionKind, Node, PropertyName, StatementList};
+use crate::syntax::ast::{arena::ArenaBox, id::Id, NodeId, NodeSpan, Span, Sym};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A class definition, shared by class declarations and class expressions.
+///
+/// The constructor is stored apart from the other elements, since it becomes the class
+/// function itself instead of a member.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-class-definitions
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Classes
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Class {
+  name: Option<Sym>,
+  super_ref: Option<ArenaBox<Node>>,
+  constructor: Option<ArenaBox<FunctionExpr>>,
+  elements: ArenaBox<[ClassElement]>,
+  span: NodeSpan,
+  name_span: NodeSpan,
+  constructor_span: NodeSpan,
+  #[cfg_attr(feature = "serde", serde(skip))]
+  id: Id,
+}
+
+impl Class {
+  /// Creates a new class definition.
+  pub fn new<N, S, E>(
+    name: N,
+    super_ref: Option<S>,
+    constructor: Option<FunctionExpr>,
+    elements: E,
+  ) -> Self
+  where
+    N: Into<Option<Sym>>,
+    S: Into<Node>,
+    E: Into<ArenaBox<[ClassElement]>>,
+  {
+    Self {
+      name: name.into(),
+      super_ref: super_ref.map(|s| ArenaBox::new(s.into())),
+      constructor: constructor.map(ArenaBox::new),
+      elements: elements.into(),
+      span: NodeSpan::default(),
+      name_span: NodeSpan::default(),
+      constructor_span: NodeSpan::default(),
+      id: Id::default(),
+    }
+  }
+
+  /// Gets the name of the class, if it has one.
+  pub fn name(&self) -> Option<&Sym> {
+    self.name.as_ref()
+  }
+
+  /// Mutable version of [`name`](Self::name).
+  pub fn name_mut(&mut self) -> Option<&mut Sym> {
+    self.name.as_mut()
+  }
+
+  /// Gets the id of the class, which declares its name, if any.
+  pub fn id(&self) -> NodeId {
+    self.id.get()
+  }
+
+  /// Gets the expression in the `extends` clause, if any.
+  pub fn super_ref(&self) -> Option<&Node> {
+    self.super_ref.as_deref()
+  }
+
+  /// Mutable version of [`super_ref`](Self::super_ref).
+  pub fn super_ref_mut(&mut self) -> Option<&mut Node> {
+    self.super_ref.as_deref_mut()
+  }
+
+  /// Gets the explicit constructor of the class, if any.
+  pub fn constructor(&self) -> Option<&FunctionExpr> {
+    self.constructor.as_deref()
+  }
+
+  /// Mutable version of [`constructor`](Self::constructor).
+  pub fn constructor_mut(&mut self) -> Option<&mut FunctionExpr> {
+    self.constructor.as_deref_mut()
+  }
+
+  /// Gets the elements of the class body, in source order.
+  pub fn elements(&self) -> &[ClassElement] {
+    &self.elements
+  }
+
+  /// Mutable version of [`elements`](Self::elements).
+  pub fn elements_mut(&mut self) -> &mut [ClassElement] {
+    &mut self.elements
+  }
+}
+
+spanned!(Class);
+spanned!(Class, "the name", name_span, set_name_span, with_name_span);
+spanned!(
+  Class,
+  "the constructor, its modifiers included",
+  constructor_span,
+  set_constructor_span,
+  with_constructor_span
+);
+
+/// The name of a class element, either a regular property name or a private name.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ClassElementName
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClassElementName {
+  /// A public name, such as `a`, `"a"` or `[a]`.
+  PropertyName(PropertyName),
+  /// A private name, such as `#a`. The name is stored without the leading `#`.
+  PrivateName(Sym),
+}
+
+impl ClassElementName {
+  /// Gets the private name, if this is one.
+  pub fn private_name(&self) -> Option<&str> {
+    match self {
+      Self::PrivateName(name) => Some(name),
+      Self::PropertyName(_) => None,
+    }
+  }
+}
+
+impl From<PropertyName> for ClassElementName {
+  fn from(name: PropertyName) -> Self {
+    Self::PropertyName(name)
+  }
+}
+
+/// A single element of a class body.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ClassElement
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Classes#class_body
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClassElement {
+  /// A method, getter or setter, such as `get #a() {}` or `static b() {}`.
+  Method {
+    name: ClassElementName,
+    kind: MethodDefinitionKind,
+    function: FunctionExpr,
+    is_static: bool,
+    span: NodeSpan,
+  },
+  /// A field definition, such as `a = 1;` or `static #b;`.
+  Field {
+    name: ClassElementName,
+    init: Option<Node>,
+    is_static: bool,
+    span: NodeSpan,
+  },
+  /// A static initialization block, such as `static { init(); }`.
+  StaticBlock(StatementList, NodeSpan),
+}
+
+impl ClassElement {
+  /// Returns `true` if the element belongs to the class itself instead of its instances.
+  pub fn is_static(&self) -> bool {
+    match self {
+      Self::Method { is_static, .. } | Self::Field { is_static, .. } => *is_static,
+      Self::StaticBlock(..) => true,
+    }
+  }
+
+  /// Gets the name of the element, static blocks have none.
+  pub fn name(&self) -> Option<&ClassElementName> {
+    match self {
+      Self::Method { name, .. } | Self::Field { name, .. } => Some(name),
+      Self::StaticBlock(..) => None,
+    }
+  }
+
+  /// Gets the span of the source the element was parsed from, its modifiers included, `None`
+  /// if it was not parsed.
+  pub fn span(&self) -> Option<Span> {
+    match self {
+      Self::Method { span, .. } | Self::Field { span, .. } | Self::StaticBlock(_, span) => {
+        span.get()
+      }
+    }
+  }
+
+  /// Sets the span of the source the element was parsed from.
+  pub fn set_span<S>(&mut self, span: S)
+  where
+    S: Into<NodeSpan>,
+  {
+    match self {
+      Self::Method { span: old, .. }
+      | Self::Field { span: old, .. }
+      | Self::StaticBlock(_, old) => *old = span.into(),
+    }
+  }
+
+  /// Builder version of [`set_span`](Self::set_span).
+  pub fn with_span<S>(mut self, span: S) -> Self
+  where
+    S: Into<NodeSpan>,
+  {
+    self.set_span(span);
+    self
+  }
+}
+
+/// An ergonomic brand check for private names, such as `#a in obj`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-RelationalExpression
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/in
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrivateIn {
+  name: Sym,
+  target: ArenaBox<Node>,
+  span: NodeSpan,
+}
+
+impl PrivateIn {
+  /// Creates a new `PrivateIn` AST node.
+  pub fn new<N, T>(name: N, target: T) -> Self
+  where
+    N: Into<Sym>,
+    T: Into<Node>,
+  {
+    Self {
+      name: name.into(),
+      target: ArenaBox::new(target.into()),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the private name, without the leading `#`.
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Gets the object being checked.
+  pub fn target(&self) -> &Node {
+    &self.target
+  }
+
+  /// Mutable version of [`target`](Self::target).
+  pub fn target_mut(&mut self) -> &mut Node {
+    &mut self.target
+  }
+}
+
+spanned!(PrivateIn);
+
+impl From<PrivateIn> for Node {
+  fn from(private_in: PrivateIn) -> Self {
+    Self::PrivateIn(private_in)
+  }
+}
@@ -0,0 +1,156 @@
+//! Conditional nodes.
+
+use super::Node;
+use crate::syntax::ast::{arena::ArenaBox, NodeSpan};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The `if` statement executes a statement if a specified condition is [`truthy`][truthy]. If
+/// the condition is [`falsy`][falsy], another statement can be executed.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-IfStatement
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/if...else
+/// [truthy]: https://developer.mozilla.org/en-US/docs/Glossary/truthy
+/// [falsy]: https://developer.mozilla.org/en-US/docs/Glossary/falsy
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct If {
+  cond: ArenaBox<Node>,
+  body: ArenaBox<Node>,
+  else_node: Option<ArenaBox<Node>>,
+  span: NodeSpan,
+}
+
+impl If {
+  /// Creates an `If` AST node.
+  pub fn new<C, B, E, OE>(condition: C, body: B, else_node: OE) -> Self
+  where
+    C: Into<Node>,
+    B: Into<Node>,
+    E: Into<Node>,
+    OE: Into<Option<E>>,
+  {
+    Self {
+      cond: ArenaBox::new(condition.into()),
+      body: ArenaBox::new(body.into()),
+      else_node: else_node.into().map(E::into).map(ArenaBox::new),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the condition of the `if` statement.
+  pub fn cond(&self) -> &Node {
+    &self.cond
+  }
+
+  /// Mutable version of [`cond`](Self::cond).
+  pub fn cond_mut(&mut self) -> &mut Node {
+    &mut self.cond
+  }
+
+  /// Gets the body to execute if the condition is true.
+  pub fn body(&self) -> &Node {
+    &self.body
+  }
+
+  /// Mutable version of [`body`](Self::body).
+  pub fn body_mut(&mut self) -> &mut Node {
+    &mut self.body
+  }
+
+  /// Gets the `else` node, if it has one.
+  pub fn else_node(&self) -> Option<&Node> {
+    self.else_node.as_ref().map(ArenaBox::as_ref)
+  }
+
+  /// Mutable version of [`else_node`](Self::else_node).
+  pub fn else_node_mut(&mut self) -> Option<&mut Node> {
+    self.else_node.as_mut().map(ArenaBox::as_mut)
+  }
+}
+
+spanned!(If);
+
+impl From<If> for Node {
+  fn from(if_stm: If) -> Node {
+    Self::If(if_stm)
+  }
+}
+
+/// The `conditional` (ternary) operator is the only JavaScript operator that takes three
+/// operands.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ConditionalExpression
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Conditional_Operator
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConditionalOp {
+  condition: ArenaBox<Node>,
+  if_true: ArenaBox<Node>,
+  if_false: ArenaBox<Node>,
+  span: NodeSpan,
+}
+
+impl ConditionalOp {
+  /// Creates a `ConditionalOp` AST node.
+  pub fn new<C, T, F>(condition: C, if_true: T, if_false: F) -> Self
+  where
+    C: Into<Node>,
+    T: Into<Node>,
+    F: Into<Node>,
+  {
+    Self {
+      condition: ArenaBox::new(condition.into()),
+      if_true: ArenaBox::new(if_true.into()),
+      if_false: ArenaBox::new(if_false.into()),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the condition.
+  pub fn cond(&self) -> &Node {
+    &self.condition
+  }
+
+  /// Mutable version of [`cond`](Self::cond).
+  pub fn cond_mut(&mut self) -> &mut Node {
+    &mut self.condition
+  }
+
+  /// Gets the expression evaluated when the condition is truthy.
+  pub fn if_true(&self) -> &Node {
+    &self.if_true
+  }
+
+  /// Mutable version of [`if_true`](Self::if_true).
+  pub fn if_true_mut(&mut self) -> &mut Node {
+    &mut self.if_true
+  }
+
+  /// Gets the expression evaluated when the condition is falsy.
+  pub fn if_false(&self) -> &Node {
+    &self.if_false
+  }
+
+  /// Mutable version of [`if_false`](Self::if_false).
+  pub fn if_false_mut(&mut self) -> &mut Node {
+    &mut self.if_false
+  }
+}
+
+spanned!(ConditionalOp);
+
+impl From<ConditionalOp> for Node {
+  fn from(cond_op: ConditionalOp) -> Node {
+    Self::ConditionalOp(cond_op)
+  }
+}
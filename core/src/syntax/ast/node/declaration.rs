@@ -0,0 +1,528 @@
+//! Declaration nodes.
+
+use super::{Node, Pattern, StatementList};
+use crate::syntax::ast::{arena::ArenaBox, id::Id, NodeId, NodeSpan, Sym};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A list of `var`, `let` or `const` declarations, such as `let a = 1, [b, c] = d`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-LexicalDeclaration
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/let
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum DeclarationList {
+  /// The `const` keyword creates a read-only reference to a value.
+  Const(ArenaBox<[Declaration]>),
+  /// The `let` statement declares a block scope local variable.
+  Let(ArenaBox<[Declaration]>),
+  /// The `var` statement declares a function scoped variable.
+  Var(ArenaBox<[Declaration]>),
+}
+
+impl DeclarationList {
+  /// Gets the keyword used to introduce the declarations.
+  pub fn kind(&self) -> &'static str {
+    match self {
+      Self::Const(_) => "const",
+      Self::Let(_) => "let",
+      Self::Var(_) => "var",
+    }
+  }
+}
+
+impl AsRef<[Declaration]> for DeclarationList {
+  fn as_ref(&self) -> &[Declaration] {
+    match self {
+      Self::Const(list) | Self::Let(list) | Self::Var(list) => list,
+    }
+  }
+}
+
+impl AsMut<[Declaration]> for DeclarationList {
+  fn as_mut(&mut self) -> &mut [Declaration] {
+    match self {
+      Self::Const(list) | Self::Let(list) | Self::Var(list) => list,
+    }
+  }
+}
+
+impl From<DeclarationList> for Node {
+  fn from(list: DeclarationList) -> Self {
+    Self::DeclarationList(list, NodeSpan::default())
+  }
+}
+
+/// A single declaration, a binding pattern with an optional initializer.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Declaration {
+  target: Pattern,
+  init: Option<Node>,
+  span: NodeSpan,
+}
+
+impl Declaration {
+  /// Creates a new declaration.
+  pub fn new<P>(target: P, init: Option<Node>) -> Self
+  where
+    P: Into<Pattern>,
+  {
+    Self {
+      target: target.into(),
+      init,
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the binding target of the declaration.
+  pub fn target(&self) -> &Pattern {
+    &self.target
+  }
+
+  /// Mutable version of [`target`](Self::target).
+  pub fn target_mut(&mut self) -> &mut Pattern {
+    &mut self.target
+  }
+
+  /// Gets the initializer of the declaration, if any.
+  pub fn init(&self) -> Option<&Node> {
+    self.init.as_ref()
+  }
+
+  /// Mutable version of [`init`](Self::init).
+  pub fn init_mut(&mut self) -> Option<&mut Node> {
+    self.init.as_mut()
+  }
+}
+
+spanned!(Declaration);
+
+/// A formal parameter of a function, such as `a`, `{b} = {}` or `...rest`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-FormalParameter
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Glossary/Parameter
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormalParameter {
+  declaration: Declaration,
+  is_rest_param: bool,
+  span: NodeSpan,
+}
+
+impl FormalParameter {
+  /// Creates a new formal parameter.
+  pub fn new(declaration: Declaration, is_rest_param: bool) -> Self {
+    Self {
+      declaration,
+      is_rest_param,
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the binding pattern of the parameter.
+  pub fn target(&self) -> &Pattern {
+    self.declaration.target()
+  }
+
+  /// Mutable version of [`target`](Self::target).
+  pub fn target_mut(&mut self) -> &mut Pattern {
+    self.declaration.target_mut()
+  }
+
+  /// Gets the declaration of the parameter, its pattern with its default value.
+  pub fn declaration(&self) -> &Declaration {
+    &self.declaration
+  }
+
+  /// Mutable version of [`declaration`](Self::declaration).
+  pub fn declaration_mut(&mut self) -> &mut Declaration {
+    &mut self.declaration
+  }
+
+  /// Gets the default value of the parameter, if any.
+  pub fn init(&self) -> Option<&Node> {
+    self.declaration.init()
+  }
+
+  /// Mutable version of [`init`](Self::init).
+  pub fn init_mut(&mut self) -> Option<&mut Node> {
+    self.declaration.init_mut()
+  }
+
+  /// Returns `true` if this is a rest parameter (`...args`).
+  pub fn is_rest_param(&self) -> bool {
+    self.is_rest_param
+  }
+}
+
+spanned!(FormalParameter);
+
+/// The kind of a function, telling whether it is a generator, an async function, or both.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-function-definitions
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Copy, Default)]
+pub enum FunctionKind {
+  /// A regular function, such as `function f() {}`.
+  #[default]
+  Ordinary,
+  /// A generator function, such as `function* f() {}`.
+  Generator,
+  /// An async function, such as `async function f() {}`.
+  Async,
+  /// An async generator function, such as `async function* f() {}`.
+  AsyncGenerator,
+}
+
+impl FunctionKind {
+  /// Gets the function kind from its `async` and `*` modifiers.
+  pub fn new(is_async: bool, is_generator: bool) -> Self {
+    match (is_async, is_generator) {
+      (false, false) => Self::Ordinary,
+      (false, true) => Self::Generator,
+      (true, false) => Self::Async,
+      (true, true) => Self::AsyncGenerator,
+    }
+  }
+
+  /// Returns `true` for async functions and async generators.
+  pub fn is_async(self) -> bool {
+    matches!(self, Self::Async | Self::AsyncGenerator)
+  }
+
+  /// Returns `true` for generators and async generators.
+  pub fn is_generator(self) -> bool {
+    matches!(self, Self::Generator | Self::AsyncGenerator)
+  }
+}
+
+/// The `function` declaration (function statement) defines a function with the specified
+/// parameters.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-FunctionDeclaration
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/function
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FunctionDecl {
+  name: Sym,
+  parameters: ArenaBox<[FormalParameter]>,
+  body: StatementList,
+  kind: FunctionKind,
+  span: NodeSpan,
+  name_span: NodeSpan,
+  #[cfg_attr(feature = "serde", serde(skip))]
+  id: Id,
+}
+
+impl FunctionDecl {
+  /// Creates a new function declaration.
+  pub fn new<N, P, B>(name: N, parameters: P, body: B) -> Self
+  where
+    N: Into<Sym>,
+    P: Into<ArenaBox<[FormalParameter]>>,
+    B: Into<StatementList>,
+  {
+    Self {
+      name: name.into(),
+      parameters: parameters.into(),
+      body: body.into(),
+      kind: FunctionKind::Ordinary,
+      span: NodeSpan::default(),
+      name_span: NodeSpan::default(),
+      id: Id::default(),
+    }
+  }
+
+  /// Sets the kind of the function declaration, such as `async` or generator.
+  pub fn with_kind(mut self, kind: FunctionKind) -> Self {
+    self.kind = kind;
+    self
+  }
+
+  /// Gets the name of the function declaration.
+  pub fn name(&self) -> &Sym {
+    &self.name
+  }
+
+  /// Mutable version of [`name`](Self::name).
+  pub fn name_mut(&mut self) -> &mut Sym {
+    &mut self.name
+  }
+
+  /// Gets the id of the function declaration, which declares its name.
+  pub fn id(&self) -> NodeId {
+    self.id.get()
+  }
+
+  /// Gets the list of parameters of the function declaration.
+  pub fn parameters(&self) -> &[FormalParameter] {
+    &self.parameters
+  }
+
+  /// Mutable version of [`parameters`](Self::parameters).
+  pub fn parameters_mut(&mut self) -> &mut [FormalParameter] {
+    &mut self.parameters
+  }
+
+  /// Gets the body of the function declaration.
+  pub fn body(&self) -> &[Node] {
+    self.body.items()
+  }
+
+  /// Mutable version of [`body`](Self::body).
+  pub fn body_mut(&mut self) -> &mut [Node] {
+    self.body.items_mut()
+  }
+
+  /// Gets the statement list of the body, to add or remove statements.
+  pub fn body_list_mut(&mut self) -> &mut StatementList {
+    &mut self.body
+  }
+
+  /// Returns `true` if the body of the function declaration is strict mode code.
+  pub fn is_strict(&self) -> bool {
+    self.body.strict()
+  }
+
+  /// Gets the kind of the function declaration.
+  pub fn kind(&self) -> FunctionKind {
+    self.kind
+  }
+}
+
+spanned!(FunctionDecl);
+spanned!(
+  FunctionDecl,
+  "the name",
+  name_span,
+  set_name_span,
+  with_name_span
+);
+
+impl From<FunctionDecl> for Node {
+  fn from(decl: FunctionDecl) -> Self {
+    Self::FunctionDecl(decl)
+  }
+}
+
+/// The `function` expression defines a function with the specified parameters.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-FunctionExpression
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/function
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FunctionExpr {
+  name: Option<Sym>,
+  parameters: ArenaBox<[FormalParameter]>,
+  body: StatementList,
+  kind: FunctionKind,
+  span: NodeSpan,
+  name_span: NodeSpan,
+  #[cfg_attr(feature = "serde", serde(skip))]
+  id: Id,
+}
+
+impl FunctionExpr {
+  /// Creates a new function expression.
+  pub fn new<N, P, B>(name: N, parameters: P, body: B) -> Self
+  where
+    N: Into<Option<Sym>>,
+    P: Into<ArenaBox<[FormalParameter]>>,
+    B: Into<StatementList>,
+  {
+    Self {
+      name: name.into(),
+      parameters: parameters.into(),
+      body: body.into(),
+      kind: FunctionKind::Ordinary,
+      span: NodeSpan::default(),
+      name_span: NodeSpan::default(),
+      id: Id::default(),
+    }
+  }
+
+  /// Sets the kind of the function expression, such as `async` or generator.
+  pub fn with_kind(mut self, kind: FunctionKind) -> Self {
+    self.kind = kind;
+    self
+  }
+
+  /// Gets the name of the function expression, if it has one.
+  pub fn name(&self) -> Option<&Sym> {
+    self.name.as_ref()
+  }
+
+  /// Mutable version of [`name`](Self::name).
+  pub fn name_mut(&mut self) -> Option<&mut Sym> {
+    self.name.as_mut()
+  }
+
+  /// Gets the id of the function expression, which declares its name, if any.
+  pub fn id(&self) -> NodeId {
+    self.id.get()
+  }
+
+  /// Gets the list of parameters of the function expression.
+  pub fn parameters(&self) -> &[FormalParameter] {
+    &self.parameters
+  }
+
+  /// Mutable version of [`parameters`](Self::parameters).
+  pub fn parameters_mut(&mut self) -> &mut [FormalParameter] {
+    &mut self.parameters
+  }
+
+  /// Gets the body of the function expression.
+  pub fn body(&self) -> &[Node] {
+    self.body.items()
+  }
+
+  /// Mutable version of [`body`](Self::body).
+  pub fn body_mut(&mut self) -> &mut [Node] {
+    self.body.items_mut()
+  }
+
+  /// Gets the statement list of the body, to add or remove statements.
+  pub fn body_list_mut(&mut self) -> &mut StatementList {
+    &mut self.body
+  }
+
+  /// Returns `true` if the body of the function expression is strict mode code.
+  pub fn is_strict(&self) -> bool {
+    self.body.strict()
+  }
+
+  /// Gets the kind of the function expression.
+  pub fn kind(&self) -> FunctionKind {
+    self.kind
+  }
+}
+
+spanned!(FunctionExpr);
+spanned!(
+  FunctionExpr,
+  "the name",
+  name_span,
+  set_name_span,
+  with_name_span
+);
+
+impl From<FunctionExpr> for Node {
+  fn from(expr: FunctionExpr) -> Self {
+    Self::FunctionExpr(expr)
+  }
+}
+
+/// An arrow function expression is a syntactically compact alternative to a regular function
+/// expression.
+///
+/// A concise body (`a => a + 1`) is stored as a body with a single `return` statement.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ArrowFunction
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Functions/Arrow_functions
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArrowFunctionDecl {
+  params: ArenaBox<[FormalParameter]>,
+  body: StatementList,
+  is_async: bool,
+  span: NodeSpan,
+}
+
+impl ArrowFunctionDecl {
+  /// Creates a new `ArrowFunctionDecl` AST node.
+  pub fn new<P, B>(params: P, body: B) -> Self
+  where
+    P: Into<ArenaBox<[FormalParameter]>>,
+    B: Into<StatementList>,
+  {
+    Self {
+      params: params.into(),
+      body: body.into(),
+      is_async: false,
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Creates a new async `ArrowFunctionDecl` AST node, such as `async a => await a`.
+  pub fn new_async<P, B>(params: P, body: B) -> Self
+  where
+    P: Into<ArenaBox<[FormalParameter]>>,
+    B: Into<StatementList>,
+  {
+    Self {
+      params: params.into(),
+      body: body.into(),
+      is_async: true,
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the list of parameters of the arrow function.
+  pub fn params(&self) -> &[FormalParameter] {
+    &self.params
+  }
+
+  /// Mutable version of [`params`](Self::params).
+  pub fn params_mut(&mut self) -> &mut [FormalParameter] {
+    &mut self.params
+  }
+
+  /// Gets the body of the arrow function.
+  pub fn body(&self) -> &[Node] {
+    self.body.items()
+  }
+
+  /// Mutable version of [`body`](Self::body).
+  pub fn body_mut(&mut self) -> &mut [Node] {
+    self.body.items_mut()
+  }
+
+  /// Gets the statement list of the body, to add or remove statements.
+  pub fn body_list_mut(&mut self) -> &mut StatementList {
+    &mut self.body
+  }
+
+  /// Returns `true` if the body of the arrow function is strict mode code.
+  pub fn is_strict(&self) -> bool {
+    self.body.strict()
+  }
+
+  /// Returns `true` if this is an async arrow function.
+  pub fn is_async(&self) -> bool {
+    self.is_async
+  }
+}
+
+spanned!(ArrowFunctionDecl);
+
+impl From<ArrowFunctionDecl> for Node {
+  fn from(decl: ArrowFunctionDecl) -> Self {
+    Self::ArrowFunctionDecl(decl)
+  }
+}
@@ -0,0 +1,65 @@
+//! Directive node.
+
+use super::Node;
+use crate::syntax::ast::NodeSpan;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A directive is a string literal statement of the directive prologue at the start of a
+/// script, a module or a function body, such as `"use strict";`.
+///
+/// The source text of the literal is kept, since a directive is only recognised when written
+/// exactly: `"use\x20strict"` is not a `"use strict"` directive.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-directive-prologues-and-the-use-strict-directive
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Directive {
+  value: Box<str>,
+  raw: Box<str>,
+  span: NodeSpan,
+}
+
+impl Directive {
+  /// Creates a new `Directive` AST node from the value of its string literal and its source
+  /// text, with the quotes.
+  pub fn new<V, R>(value: V, raw: R) -> Self
+  where
+    V: Into<Box<str>>,
+    R: Into<Box<str>>,
+  {
+    Self {
+      value: value.into(),
+      raw: raw.into(),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the value of the string literal.
+  pub fn value(&self) -> &str {
+    &self.value
+  }
+
+  /// Gets the source text of the string literal, with its quotes and escape sequences.
+  pub fn raw(&self) -> &str {
+    &self.raw
+  }
+
+  /// Returns `true` if this is a `"use strict"` directive, written without escape sequences or
+  /// line continuations.
+  pub fn is_use_strict(&self) -> bool {
+    matches!(&*self.raw, "\"use strict\"" | "'use strict'")
+  }
+}
+
+spanned!(Directive);
+
+impl From<Directive> for Node {
+  fn from(directive: Directive) -> Self {
+    Self::Directive(directive)
+  }
+}
@@ -0,0 +1,245 @@
+//! Property access nodes.
+
+use super::Node;
+use crate::syntax::ast::{arena::ArenaBox, NodeSpan, Sym};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// This property accessor provides access to an object's properties by using the
+/// [dot notation][mdn].
+///
+/// When `optional` is set the access was written as `obj?.field` and is part of an
+/// `OptionalChain`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-property-accessors
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Property_accessors#Dot_notation
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct GetConstField {
+  obj: ArenaBox<Node>,
+  field: Sym,
+  optional: bool,
+  span: NodeSpan,
+}
+
+impl GetConstField {
+  /// Creates a `GetConstField` AST node.
+  pub fn new<V, L>(value: V, label: L) -> Self
+  where
+    V: Into<Node>,
+    L: Into<Sym>,
+  {
+    Self {
+      obj: ArenaBox::new(value.into()),
+      field: label.into(),
+      optional: false,
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Creates an optional (`obj?.field`) `GetConstField` AST node.
+  pub fn new_optional<V, L>(value: V, label: L) -> Self
+  where
+    V: Into<Node>,
+    L: Into<Sym>,
+  {
+    Self {
+      obj: ArenaBox::new(value.into()),
+      field: label.into(),
+      optional: true,
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the original object from where to get the field from.
+  pub fn obj(&self) -> &Node {
+    &self.obj
+  }
+
+  /// Mutable version of [`obj`](Self::obj).
+  pub fn obj_mut(&mut self) -> &mut Node {
+    &mut self.obj
+  }
+
+  /// Gets the name of the field to retrieve.
+  pub fn field(&self) -> &str {
+    &self.field
+  }
+
+  /// Returns `true` if the access was written with `?.`.
+  pub fn optional(&self) -> bool {
+    self.optional
+  }
+}
+
+spanned!(GetConstField);
+
+impl From<GetConstField> for Node {
+  fn from(get_const_field: GetConstField) -> Self {
+    Self::GetConstField(get_const_field)
+  }
+}
+
+/// This property accessor provides access to an object's properties by using the
+/// [bracket notation][mdn].
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-property-accessors
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Property_accessors#Bracket_notation
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct GetField {
+  obj: ArenaBox<Node>,
+  field: ArenaBox<Node>,
+  optional: bool,
+  span: NodeSpan,
+}
+
+impl GetField {
+  /// Creates a `GetField` AST node.
+  pub fn new<V, F>(value: V, field: F) -> Self
+  where
+    V: Into<Node>,
+    F: Into<Node>,
+  {
+    Self {
+      obj: ArenaBox::new(value.into()),
+      field: ArenaBox::new(field.into()),
+      optional: false,
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Creates an optional (`obj?.[field]`) `GetField` AST node.
+  pub fn new_optional<V, F>(value: V, field: F) -> Self
+  where
+    V: Into<Node>,
+    F: Into<Node>,
+  {
+    Self {
+      obj: ArenaBox::new(value.into()),
+      field: ArenaBox::new(field.into()),
+      optional: true,
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the original object from where to get the field from.
+  pub fn obj(&self) -> &Node {
+    &self.obj
+  }
+
+  /// Mutable version of [`obj`](Self::obj).
+  pub fn obj_mut(&mut self) -> &mut Node {
+    &mut self.obj
+  }
+
+  /// Gets the expression that evaluates to the name of the field.
+  pub fn field(&self) -> &Node {
+    &self.field
+  }
+
+  /// Mutable version of [`field`](Self::field).
+  pub fn field_mut(&mut self) -> &mut Node {
+    &mut self.field
+  }
+
+  /// Returns `true` if the access was written with `?.`.
+  pub fn optional(&self) -> bool {
+    self.optional
+  }
+}
+
+spanned!(GetField);
+
+impl From<GetField> for Node {
+  fn from(get_field: GetField) -> Self {
+    Self::GetField(get_field)
+  }
+}
+
+/// This property accessor provides access to a private member of a class instance, such as
+/// `this.#count`.
+///
+/// The stored name does not include the leading `#`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-MemberExpression
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Classes/Private_class_fields
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct GetPrivateField {
+  obj: ArenaBox<Node>,
+  field: Sym,
+  optional: bool,
+  span: NodeSpan,
+}
+
+impl GetPrivateField {
+  /// Creates a `GetPrivateField` AST node.
+  pub fn new<V, L>(value: V, field: L) -> Self
+  where
+    V: Into<Node>,
+    L: Into<Sym>,
+  {
+    Self {
+      obj: ArenaBox::new(value.into()),
+      field: field.into(),
+      optional: false,
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Creates an optional (`obj?.#field`) `GetPrivateField` AST node.
+  pub fn new_optional<V, L>(value: V, field: L) -> Self
+  where
+    V: Into<Node>,
+    L: Into<Sym>,
+  {
+    Self {
+      obj: ArenaBox::new(value.into()),
+      field: field.into(),
+      optional: true,
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the object the private member is read from.
+  pub fn obj(&self) -> &Node {
+    &self.obj
+  }
+
+  /// Mutable version of [`obj`](Self::obj).
+  pub fn obj_mut(&mut self) -> &mut Node {
+    &mut self.obj
+  }
+
+  /// Gets the private name, without the leading `#`.
+  pub fn field(&self) -> &str {
+    &self.field
+  }
+
+  /// Returns `true` if the access is optional (`obj?.#field`).
+  pub fn optional(&self) -> bool {
+    self.optional
+  }
+}
+
+spanned!(GetPrivateField);
+
+impl From<GetPrivateField> for Node {
+  fn from(get_private_field: GetPrivateField) -> Self {
+    Self::GetPrivateField(get_private_field)
+  }
+}
@@ -0,0 +1,82 @@
+//! Local identifier node.
+
+use crate::syntax::ast::{id::Id, node::Node, NodeId, NodeSpan, Sym};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// An `identifier` is a sequence of characters in the code that identifies a variable,
+/// function, or property.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-Identifier
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Glossary/Identifier
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Identifier {
+  ident: Sym,
+  span: NodeSpan,
+  #[cfg_attr(feature = "serde", serde(skip))]
+  id: Id,
+}
+
+impl Identifier {
+  /// Creates a new identifier.
+  pub fn new<I>(ident: I) -> Self
+  where
+    I: Into<Sym>,
+  {
+    Self {
+      ident: ident.into(),
+      span: NodeSpan::default(),
+      id: Id::default(),
+    }
+  }
+
+  /// Gets the name of the identifier.
+  pub fn name(&self) -> &Sym {
+    &self.ident
+  }
+
+  /// Gets the id of the identifier.
+  pub fn id(&self) -> NodeId {
+    self.id.get()
+  }
+}
+
+spanned!(Identifier);
+
+impl fmt::Display for Identifier {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt::Display::fmt(&self.ident, f)
+  }
+}
+
+impl AsRef<str> for Identifier {
+  fn as_ref(&self) -> &str {
+    &self.ident
+  }
+}
+
+impl<T> From<T> for Identifier
+where
+  T: Into<Sym>,
+{
+  fn from(stm: T) -> Self {
+    Self {
+      ident: stm.into(),
+      span: NodeSpan::default(),
+      id: Id::default(),
+    }
+  }
+}
+
+impl From<Identifier> for Node {
+  fn from(local: Identifier) -> Self {
+    Self::Identifier(local)
+  }
+}
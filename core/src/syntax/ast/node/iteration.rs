@@ -0,0 +1,504 @@
+//! Iteration nodes.
+
+use super::{Node, Pattern};
+use crate::syntax::ast::{arena::ArenaBox, NodeSpan, Sym};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The `for` statement creates a loop that consists of three optional expressions.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ForDeclaration
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/for
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ForLoop {
+  init: Option<ArenaBox<Node>>,
+  condition: Option<ArenaBox<Node>>,
+  final_expr: Option<ArenaBox<Node>>,
+  body: ArenaBox<Node>,
+  span: NodeSpan,
+}
+
+impl ForLoop {
+  /// Creates a new for loop AST node.
+  pub fn new<B>(init: Option<Node>, condition: Option<Node>, final_expr: Option<Node>, body: B) -> Self
+  where
+    B: Into<Node>,
+  {
+    Self {
+      init: init.map(ArenaBox::new),
+      condition: condition.map(ArenaBox::new),
+      final_expr: final_expr.map(ArenaBox::new),
+      body: ArenaBox::new(body.into()),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the initialization node.
+  pub fn init(&self) -> Option<&Node> {
+    self.init.as_ref().map(ArenaBox::as_ref)
+  }
+
+  /// Mutable version of [`init`](Self::init).
+  pub fn init_mut(&mut self) -> Option<&mut Node> {
+    self.init.as_mut().map(ArenaBox::as_mut)
+  }
+
+  /// Gets the loop condition node.
+  pub fn condition(&self) -> Option<&Node> {
+    self.condition.as_ref().map(ArenaBox::as_ref)
+  }
+
+  /// Mutable version of [`condition`](Self::condition).
+  pub fn condition_mut(&mut self) -> Option<&mut Node> {
+    self.condition.as_mut().map(ArenaBox::as_mut)
+  }
+
+  /// Gets the final expression node.
+  pub fn final_expr(&self) -> Option<&Node> {
+    self.final_expr.as_ref().map(ArenaBox::as_ref)
+  }
+
+  /// Mutable version of [`final_expr`](Self::final_expr).
+  pub fn final_expr_mut(&mut self) -> Option<&mut Node> {
+    self.final_expr.as_mut().map(ArenaBox::as_mut)
+  }
+
+  /// Gets the body of the for loop.
+  pub fn body(&self) -> &Node {
+    &self.body
+  }
+
+  /// Mutable version of [`body`](Self::body).
+  pub fn body_mut(&mut self) -> &mut Node {
+    &mut self.body
+  }
+}
+
+spanned!(ForLoop);
+
+impl From<ForLoop> for Node {
+  fn from(for_loop: ForLoop) -> Self {
+    Self::ForLoop(for_loop)
+  }
+}
+
+/// The left hand side of a `for ... in` or `for ... of` loop.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ForInOfStatement
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum IterableLoopInitializer {
+  /// `for (var x of y)`
+  Var(Pattern),
+  /// `for (let x of y)`
+  Let(Pattern),
+  /// `for (const x of y)`
+  Const(Pattern),
+  /// An assignment target without declaration, `for (x.y of z)` or `for ([a, b] of c)`.
+  Pattern(Pattern),
+}
+
+impl IterableLoopInitializer {
+  /// Gets the pattern the loop assigns to on each iteration.
+  pub fn pattern(&self) -> &Pattern {
+    match self {
+      Self::Var(pattern) | Self::Let(pattern) | Self::Const(pattern) | Self::Pattern(pattern) => {
+        pattern
+      }
+    }
+  }
+
+  /// Mutable version of [`pattern`](Self::pattern).
+  pub fn pattern_mut(&mut self) -> &mut Pattern {
+    match self {
+      Self::Var(pattern) | Self::Let(pattern) | Self::Const(pattern) | Self::Pattern(pattern) => {
+        pattern
+      }
+    }
+  }
+}
+
+/// The `for...in` statement iterates over all enumerable string properties of an object.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ForInOfStatement
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/for...in
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ForInLoop {
+  init: IterableLoopInitializer,
+  expr: ArenaBox<Node>,
+  body: ArenaBox<Node>,
+  span: NodeSpan,
+}
+
+impl ForInLoop {
+  /// Creates a new `ForInLoop` AST node.
+  pub fn new<E, B>(init: IterableLoopInitializer, expr: E, body: B) -> Self
+  where
+    E: Into<Node>,
+    B: Into<Node>,
+  {
+    Self {
+      init,
+      expr: ArenaBox::new(expr.into()),
+      body: ArenaBox::new(body.into()),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the loop variable.
+  pub fn init(&self) -> &IterableLoopInitializer {
+    &self.init
+  }
+
+  /// Mutable version of [`init`](Self::init).
+  pub fn init_mut(&mut self) -> &mut IterableLoopInitializer {
+    &mut self.init
+  }
+
+  /// Gets the object whose properties are iterated.
+  pub fn expr(&self) -> &Node {
+    &self.expr
+  }
+
+  /// Mutable version of [`expr`](Self::expr).
+  pub fn expr_mut(&mut self) -> &mut Node {
+    &mut self.expr
+  }
+
+  /// Gets the body of the loop.
+  pub fn body(&self) -> &Node {
+    &self.body
+  }
+
+  /// Mutable version of [`body`](Self::body).
+  pub fn body_mut(&mut self) -> &mut Node {
+    &mut self.body
+  }
+}
+
+spanned!(ForInLoop);
+
+impl From<ForInLoop> for Node {
+  fn from(for_in: ForInLoop) -> Self {
+    Self::ForInLoop(for_in)
+  }
+}
+
+/// The `for...of` statement creates a loop iterating over iterable objects.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ForInOfStatement
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/for...of
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ForOfLoop {
+  init: IterableLoopInitializer,
+  iterable: ArenaBox<Node>,
+  body: ArenaBox<Node>,
+  is_await: bool,
+  span: NodeSpan,
+}
+
+impl ForOfLoop {
+  /// Creates a new `ForOfLoop` AST node.
+  pub fn new<I, B>(init: IterableLoopInitializer, iterable: I, body: B) -> Self
+  where
+    I: Into<Node>,
+    B: Into<Node>,
+  {
+    Self {
+      init,
+      iterable: ArenaBox::new(iterable.into()),
+      body: ArenaBox::new(body.into()),
+      is_await: false,
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Creates a new `for await...of` AST node, iterating over an async iterable.
+  pub fn new_await<I, B>(init: IterableLoopInitializer, iterable: I, body: B) -> Self
+  where
+    I: Into<Node>,
+    B: Into<Node>,
+  {
+    Self {
+      init,
+      iterable: ArenaBox::new(iterable.into()),
+      body: ArenaBox::new(body.into()),
+      is_await: true,
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the loop variable.
+  pub fn init(&self) -> &IterableLoopInitializer {
+    &self.init
+  }
+
+  /// Mutable version of [`init`](Self::init).
+  pub fn init_mut(&mut self) -> &mut IterableLoopInitializer {
+    &mut self.init
+  }
+
+  /// Gets the iterated expression.
+  pub fn iterable(&self) -> &Node {
+    &self.iterable
+  }
+
+  /// Mutable version of [`iterable`](Self::iterable).
+  pub fn iterable_mut(&mut self) -> &mut Node {
+    &mut self.iterable
+  }
+
+  /// Gets the body of the loop.
+  pub fn body(&self) -> &Node {
+    &self.body
+  }
+
+  /// Mutable version of [`body`](Self::body).
+  pub fn body_mut(&mut self) -> &mut Node {
+    &mut self.body
+  }
+
+  /// Returns `true` if this is a `for await...of` loop.
+  pub fn is_await(&self) -> bool {
+    self.is_await
+  }
+}
+
+spanned!(ForOfLoop);
+
+impl From<ForOfLoop> for Node {
+  fn from(for_of: ForOfLoop) -> Self {
+    Self::ForOfLoop(for_of)
+  }
+}
+
+/// The `while` statement creates a loop that executes a specified statement as long as the
+/// test condition evaluates to true.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-grammar-notation-WhileStatement
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/while
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct WhileLoop {
+  cond: ArenaBox<Node>,
+  body: ArenaBox<Node>,
+  span: NodeSpan,
+}
+
+impl WhileLoop {
+  /// Creates a `WhileLoop` AST node.
+  pub fn new<C, B>(condition: C, body: B) -> Self
+  where
+    C: Into<Node>,
+    B: Into<Node>,
+  {
+    Self {
+      cond: ArenaBox::new(condition.into()),
+      body: ArenaBox::new(body.into()),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the condition of the while loop.
+  pub fn cond(&self) -> &Node {
+    &self.cond
+  }
+
+  /// Mutable version of [`cond`](Self::cond).
+  pub fn cond_mut(&mut self) -> &mut Node {
+    &mut self.cond
+  }
+
+  /// Gets the body of the while loop.
+  pub fn body(&self) -> &Node {
+    &self.body
+  }
+
+  /// Mutable version of [`body`](Self::body).
+  pub fn body_mut(&mut self) -> &mut Node {
+    &mut self.body
+  }
+}
+
+spanned!(WhileLoop);
+
+impl From<WhileLoop> for Node {
+  fn from(while_loop: WhileLoop) -> Self {
+    Self::WhileLoop(while_loop)
+  }
+}
+
+/// The `do...while` statement creates a loop that executes a specified statement until the test
+/// condition evaluates to false.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-do-while-statement
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/do...while
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DoWhileLoop {
+  body: ArenaBox<Node>,
+  cond: ArenaBox<Node>,
+  span: NodeSpan,
+}
+
+impl DoWhileLoop {
+  /// Creates a `DoWhileLoop` AST node.
+  pub fn new<B, C>(body: B, condition: C) -> Self
+  where
+    B: Into<Node>,
+    C: Into<Node>,
+  {
+    Self {
+      body: ArenaBox::new(body.into()),
+      cond: ArenaBox::new(condition.into()),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the body of the loop.
+  pub fn body(&self) -> &Node {
+    &self.body
+  }
+
+  /// Mutable version of [`body`](Self::body).
+  pub fn body_mut(&mut self) -> &mut Node {
+    &mut self.body
+  }
+
+  /// Gets the condition of the loop.
+  pub fn cond(&self) -> &Node {
+    &self.cond
+  }
+
+  /// Mutable version of [`cond`](Self::cond).
+  pub fn cond_mut(&mut self) -> &mut Node {
+    &mut self.cond
+  }
+}
+
+spanned!(DoWhileLoop);
+
+impl From<DoWhileLoop> for Node {
+  fn from(do_while: DoWhileLoop) -> Self {
+    Self::DoWhileLoop(do_while)
+  }
+}
+
+/// The `break` statement terminates the current loop, switch, or label statement and transfers
+/// program control to the statement following the terminated statement.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-BreakStatement
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/break
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Break {
+  label: Option<Sym>,
+  span: NodeSpan,
+  label_span: NodeSpan,
+}
+
+impl Break {
+  /// Creates a `Break` AST node.
+  pub fn new<OL, L>(label: OL) -> Self
+  where
+    L: Into<Sym>,
+    OL: Into<Option<L>>,
+  {
+    Self {
+      label: label.into().map(L::into),
+      span: NodeSpan::default(),
+      label_span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the label of the break statement, if any.
+  pub fn label(&self) -> Option<&str> {
+    self.label.as_deref()
+  }
+}
+
+spanned!(Break);
+spanned!(Break, "the label", label_span, set_label_span, with_label_span);
+
+impl From<Break> for Node {
+  fn from(break_smt: Break) -> Node {
+    Self::Break(break_smt)
+  }
+}
+
+/// The `continue` statement terminates execution of the statements in the current iteration of
+/// the current or labeled loop, and continues execution of the loop with the next iteration.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ContinueStatement
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/continue
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Continue {
+  label: Option<Sym>,
+  span: NodeSpan,
+  label_span: NodeSpan,
+}
+
+impl Continue {
+  /// Creates a `Continue` AST node.
+  pub fn new<OL, L>(label: OL) -> Self
+  where
+    L: Into<Sym>,
+    OL: Into<Option<L>>,
+  {
+    Self {
+      label: label.into().map(L::into),
+      span: NodeSpan::default(),
+      label_span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the label of the continue statement, if any.
+  pub fn label(&self) -> Option<&str> {
+    self.label.as_deref()
+  }
+}
+
+spanned!(Continue);
+spanned!(Continue, "the label", label_span, set_label_span, with_label_span);
+
+impl From<Continue> for Node {
+  fn from(cont: Continue) -> Node {
+    Self::Continue(cont)
+  }
+}
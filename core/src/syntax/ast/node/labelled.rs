@@ -0,0 +1,70 @@
+//! Labelled statement node.
+
+use super::Node;
+use crate::syntax::ast::{arena::ArenaBox, NodeSpan, Sym};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A labelled statement can be used with `break` or `continue` statements.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-LabelledStatement
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/label
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Labelled {
+  label: Sym,
+  statement: ArenaBox<Node>,
+  span: NodeSpan,
+  label_span: NodeSpan,
+}
+
+impl Labelled {
+  /// Creates a new `Labelled` AST node.
+  pub fn new<L, S>(label: L, statement: S) -> Self
+  where
+    L: Into<Sym>,
+    S: Into<Node>,
+  {
+    Self {
+      label: label.into(),
+      statement: ArenaBox::new(statement.into()),
+      span: NodeSpan::default(),
+      label_span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the label name.
+  pub fn label(&self) -> &str {
+    &self.label
+  }
+
+  /// Gets the labelled statement.
+  pub fn statement(&self) -> &Node {
+    &self.statement
+  }
+
+  /// Mutable version of [`statement`](Self::statement).
+  pub fn statement_mut(&mut self) -> &mut Node {
+    &mut self.statement
+  }
+}
+
+spanned!(Labelled);
+spanned!(
+  Labelled,
+  "the label",
+  label_span,
+  set_label_span,
+  with_label_span
+);
+
+impl From<Labelled> for Node {
+  fn from(labelled: Labelled) -> Self {
+    Self::Labelled(labelled)
+  }
+}
@@ -0,0 +1,400 @@
+//! This module implements the `Node` structure, which composes the AST.
+
+/// Implements the span accessors of a node type that stores its span in a `span` field.
+macro_rules! spanned {
+  ($ty:ident) => {
+    impl $ty {
+      /// Gets the span of the source the node was parsed from, `None` if it was not parsed.
+      #[inline]
+      pub fn span(&self) -> Option<crate::syntax::ast::Span> {
+        self.span.get()
+      }
+
+      /// Sets the span of the source the node was parsed from.
+      #[inline]
+      pub fn set_span<S>(&mut self, span: S)
+      where
+        S: Into<crate::syntax::ast::NodeSpan>,
+      {
+        self.span = span.into();
+      }
+
+      /// Builder version of [`set_span`](Self::set_span).
+      #[inline]
+      pub fn with_span<S>(mut self, span: S) -> Self
+      where
+        S: Into<crate::syntax::ast::NodeSpan>,
+      {
+        self.set_span(span);
+        self
+      }
+    }
+  };
+  ($ty:ident, $what:literal, $get:ident, $set:ident, $with:ident) => {
+    impl $ty {
+      #[doc = concat!(
+        "Gets the span of ", $what, ", `None` if it was not parsed or there is none."
+      )]
+      #[inline]
+      pub fn $get(&self) -> Option<crate::syntax::ast::Span> {
+        self.$get.get()
+      }
+
+      #[doc = concat!("Sets the span of ", $what, ".")]
+      #[inline]
+      pub fn $set<S>(&mut self, span: S)
+      where
+        S: Into<crate::syntax::ast::NodeSpan>,
+      {
+        self.$get = span.into();
+      }
+
+      #[doc = concat!(
+        "Builder version of [`", stringify!($set), "`](Self::", stringify!($set), ")."
+      )]
+      #[inline]
+      pub fn $with<S>(mut self, span: S) -> Self
+      where
+        S: Into<crate::syntax::ast::NodeSpan>,
+      {
+        self.$set(span);
+        self
+      }
+    }
+  };
+}
+
+pub mod array;
+pub mod await_expr;
+pub mod block;
+pub mod call;
+pub mod class;
+pub mod conditional;
+pub mod declaration;
+pub mod directive;
+pub mod field;
+pub mod identifier;
+pub mod iteration;
+pub mod labelled;
+pub mod module;
+pub mod object;
+pub mod operator;
+pub mod pattern;
+pub mod return_smt;
+pub mod spread;
+pub mod statement_list;
+pub mod switch;
+pub mod template;
+pub mod throw;
+pub mod try_node;
+pub mod with;
+pub mod yield_expr;
+
+pub use self::{
+  array::ArrayDecl,
+  await_expr::Await,
+  block::Block,
+  call::{Call, New, OptionalChain},
+  class::{Class, ClassElement, ClassElementName, PrivateIn},
+  conditional::{ConditionalOp, If},
+  declaration::{
+    ArrowFunctionDecl, Declaration, DeclarationList, FormalParameter, FunctionDecl, FunctionExpr,
+    FunctionKind,
+  },
+  directive::Directive,
+  field::{GetConstField, GetField, GetPrivateField},
+  identifier::Identifier,
+  iteration::{
+    Break, Continue, DoWhileLoop, ForInLoop, ForLoop, ForOfLoop, IterableLoopInitializer,
+    WhileLoop,
+  },
+  labelled::Labelled,
+  module::{
+    ExportDecl, ExportSpecifier, ImportAttribute, ImportCall, ImportDecl, ImportSpecifier,
+  },
+  object::{MethodDefinitionKind, Object, PropertyDefinition, PropertyName},
+  operator::{Assign, BinOp, UnaryOp},
+  pattern::{ArrayPattern, ObjectPattern, Pattern, PatternElement, PropertyPattern},
+  return_smt::Return,
+  spread::Spread,
+  statement_list::StatementList,
+  switch::{Case, Switch},
+  template::{TaggedTemplate, TemplateElement, TemplateLit},
+  throw::Throw,
+  try_node::{Catch, Finally, Try},
+  with::With,
+  yield_expr::Yield,
+};
+use super::{Const, NodeSpan, Span};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A single node of the JavaScript abstract syntax tree.
+///
+/// Expressions and statements share this type, an expression statement is simply the expression
+/// node placed in a `StatementList`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Node {
+  /// An array literal, such as `[1, , ...a]`.
+  ArrayDecl(ArrayDecl),
+  /// An arrow function, such as `(a, b) => a + b`.
+  ArrowFunctionDecl(ArrowFunctionDecl),
+  /// A plain assignment, such as `a = b` or `[a, b] = [b, a]`.
+  Assign(Assign),
+  /// A binary or compound assignment operation, such as `a + b` or `a += b`.
+  BinOp(BinOp),
+  /// An `await` expression, such as `await a`.
+  Await(Await),
+  /// A block statement, such as `{ a; b; }`.
+  Block(Block),
+  /// A `break` statement.
+  Break(Break),
+  /// A function call, such as `f(a, b)`.
+  Call(Call),
+  /// A class declaration, such as `class A extends B {}`.
+  ClassDecl(Class),
+  /// A class expression, such as `(class {})`.
+  ClassExpr(Class),
+  /// A conditional (ternary) expression, such as `a ? b : c`.
+  ConditionalOp(ConditionalOp),
+  /// A literal value.
+  Const(Const, NodeSpan),
+  /// A `continue` statement.
+  Continue(Continue),
+  /// A `debugger` statement.
+  Debugger(NodeSpan),
+  /// A `var`, `let` or `const` declaration.
+  DeclarationList(DeclarationList, NodeSpan),
+  /// A directive of a directive prologue, such as `"use strict";`.
+  Directive(Directive),
+  /// A `do ... while` loop.
+  DoWhileLoop(DoWhileLoop),
+  /// An empty statement (`;`) or an array hole.
+  Empty(NodeSpan),
+  /// A statement or an expression that could not be parsed.
+  ///
+  /// Only produced when parsing with error recovery.
+  Error(NodeSpan),
+  /// An `export` declaration.
+  Export(ExportDecl, NodeSpan),
+  /// A `for ... in` loop.
+  ForInLoop(ForInLoop),
+  /// A C-style `for` loop.
+  ForLoop(ForLoop),
+  /// A `for ... of` loop.
+  ForOfLoop(ForOfLoop),
+  /// A function declaration.
+  FunctionDecl(FunctionDecl),
+  /// A function expression.
+  FunctionExpr(FunctionExpr),
+  /// A static property access, such as `a.b`.
+  GetConstField(GetConstField),
+  /// A computed property access, such as `a[b]`.
+  GetField(GetField),
+  /// A private member access, such as `this.#a`.
+  GetPrivateField(GetPrivateField),
+  /// An identifier reference.
+  Identifier(Identifier),
+  /// An `if` statement.
+  If(If),
+  /// An `import` declaration.
+  Import(ImportDecl),
+  /// A dynamic import, such as `import("./a.js")`.
+  ImportCall(ImportCall),
+  /// The `import.meta` meta property.
+  ImportMeta(NodeSpan),
+  /// A labelled statement, such as `outer: for (;;) {}`.
+  Labelled(Labelled),
+  /// A `new` expression.
+  New(New),
+  /// An object literal.
+  Object(Object),
+  /// An optional chain, such as `a?.b.c`.
+  OptionalChain(OptionalChain),
+  /// A private brand check, such as `#a in obj`.
+  PrivateIn(PrivateIn),
+  /// A `return` statement.
+  Return(Return),
+  /// A spread element, such as `...a`.
+  Spread(Spread),
+  /// The `super` keyword, only valid as `super(...)`, `super.a` or `super[a]`.
+  Super(NodeSpan),
+  /// A `switch` statement.
+  Switch(Switch),
+  /// A tagged template, such as `` tag`a${b}` ``.
+  TaggedTemplate(TaggedTemplate),
+  /// A template literal, such as `` `a${b}` ``.
+  TemplateLit(TemplateLit),
+  /// The `this` keyword.
+  This(NodeSpan),
+  /// A `throw` statement.
+  Throw(Throw),
+  /// A `try` statement.
+  Try(Try),
+  /// A unary or update operation, such as `!a` or `a++`.
+  UnaryOp(UnaryOp),
+  /// A `while` loop.
+  WhileLoop(WhileLoop),
+  /// A `with` statement.
+  With(With),
+  /// A `yield` or `yield*` expression.
+  Yield(Yield),
+}
+
+impl Node {
+  /// Creates a `This` AST node.
+  pub fn this() -> Self {
+    Self::This(NodeSpan::default())
+  }
+
+  /// Creates an `ImportMeta` AST node.
+  pub fn import_meta() -> Self {
+    Self::ImportMeta(NodeSpan::default())
+  }
+
+  /// Creates a `Super` AST node.
+  pub fn super_ref() -> Self {
+    Self::Super(NodeSpan::default())
+  }
+
+  /// Creates an `Empty` AST node.
+  pub fn empty() -> Self {
+    Self::Empty(NodeSpan::default())
+  }
+
+  /// Creates an `Error` AST node.
+  pub fn error() -> Self {
+    Self::Error(NodeSpan::default())
+  }
+
+  /// Gets the span of the source the node was parsed from, `None` if it was not parsed.
+  pub fn span(&self) -> Option<Span> {
+    match self {
+      Self::ArrayDecl(node) => node.span(),
+      Self::ArrowFunctionDecl(node) => node.span(),
+      Self::Assign(node) => node.span(),
+      Self::BinOp(node) => node.span(),
+      Self::Await(node) => node.span(),
+      Self::Block(node) => node.span(),
+      Self::Break(node) => node.span(),
+      Self::Call(node) => node.span(),
+      Self::ClassDecl(node) => node.span(),
+      Self::ClassExpr(node) => node.span(),
+      Self::ConditionalOp(node) => node.span(),
+      Self::Continue(node) => node.span(),
+      Self::Directive(node) => node.span(),
+      Self::DoWhileLoop(node) => node.span(),
+      Self::ForInLoop(node) => node.span(),
+      Self::ForLoop(node) => node.span(),
+      Self::ForOfLoop(node) => node.span(),
+      Self::FunctionDecl(node) => node.span(),
+      Self::FunctionExpr(node) => node.span(),
+      Self::GetConstField(node) => node.span(),
+      Self::GetField(node) => node.span(),
+      Self::GetPrivateField(node) => node.span(),
+      Self::Identifier(node) => node.span(),
+      Self::If(node) => node.span(),
+      Self::Import(node) => node.span(),
+      Self::ImportCall(node) => node.span(),
+      Self::Labelled(node) => node.span(),
+      Self::New(node) => node.span(),
+      Self::Object(node) => node.span(),
+      Self::OptionalChain(node) => node.span(),
+      Self::PrivateIn(node) => node.span(),
+      Self::Return(node) => node.span(),
+      Self::Spread(node) => node.span(),
+      Self::Switch(node) => node.span(),
+      Self::TaggedTemplate(node) => node.span(),
+      Self::TemplateLit(node) => node.span(),
+      Self::Throw(node) => node.span(),
+      Self::Try(node) => node.span(),
+      Self::UnaryOp(node) => node.span(),
+      Self::WhileLoop(node) => node.span(),
+      Self::With(node) => node.span(),
+      Self::Yield(node) => node.span(),
+      Self::Const(_, span) | Self::DeclarationList(_, span) | Self::Export(_, span) => span.get(),
+      Self::Debugger(span)
+      | Self::Empty(span)
+      | Self::Error(span)
+      | Self::ImportMeta(span)
+      | Self::Super(span)
+      | Self::This(span) => span.get(),
+    }
+  }
+
+  /// Sets the span of the source the node was parsed from.
+  pub fn set_span<S>(&mut self, span: S)
+  where
+    S: Into<NodeSpan>,
+  {
+    let span = span.into();
+    match self {
+      Self::ArrayDecl(node) => node.set_span(span),
+      Self::ArrowFunctionDecl(node) => node.set_span(span),
+      Self::Assign(node) => node.set_span(span),
+      Self::BinOp(node) => node.set_span(span),
+      Self::Await(node) => node.set_span(span),
+      Self::Block(node) => node.set_span(span),
+      Self::Break(node) => node.set_span(span),
+      Self::Call(node) => node.set_span(span),
+      Self::ClassDecl(node) => node.set_span(span),
+      Self::ClassExpr(node) => node.set_span(span),
+      Self::ConditionalOp(node) => node.set_span(span),
+      Self::Continue(node) => node.set_span(span),
+      Self::Directive(node) => node.set_span(span),
+      Self::DoWhileLoop(node) => node.set_span(span),
+      Self::ForInLoop(node) => node.set_span(span),
+      Self::ForLoop(node) => node.set_span(span),
+      Self::ForOfLoop(node) => node.set_span(span),
+      Self::FunctionDecl(node) => node.set_span(span),
+      Self::FunctionExpr(node) => node.set_span(span),
+      Self::GetConstField(node) => node.set_span(span),
+      Self::GetField(node) => node.set_span(span),
+      Self::GetPrivateField(node) => node.set_span(span),
+      Self::Identifier(node) => node.set_span(span),
+      Self::If(node) => node.set_span(span),
+      Self::Import(node) => node.set_span(span),
+      Self::ImportCall(node) => node.set_span(span),
+      Self::Labelled(node) => node.set_span(span),
+      Self::New(node) => node.set_span(span),
+      Self::Object(node) => node.set_span(span),
+      Self::OptionalChain(node) => node.set_span(span),
+      Self::PrivateIn(node) => node.set_span(span),
+      Self::Return(node) => node.set_span(span),
+      Self::Spread(node) => node.set_span(span),
+      Self::Switch(node) => node.set_span(span),
+      Self::TaggedTemplate(node) => node.set_span(span),
+      Self::TemplateLit(node) => node.set_span(span),
+      Self::Throw(node) => node.set_span(span),
+      Self::Try(node) => node.set_span(span),
+      Self::UnaryOp(node) => node.set_span(span),
+      Self::WhileLoop(node) => node.set_span(span),
+      Self::With(node) => node.set_span(span),
+      Self::Yield(node) => node.set_span(span),
+      Self::Const(_, slot) | Self::DeclarationList(_, slot) | Self::Export(_, slot) => *slot = span,
+      Self::Debugger(slot)
+      | Self::Empty(slot)
+      | Self::Error(slot)
+      | Self::ImportMeta(slot)
+      | Self::Super(slot)
+      | Self::This(slot) => *slot = span,
+    }
+  }
+
+  /// Builder version of [`set_span`](Self::set_span).
+  pub fn with_span<S>(mut self, span: S) -> Self
+  where
+    S: Into<NodeSpan>,
+  {
+    self.set_span(span);
+    self
+  }
+}
+
+impl From<Const> for Node {
+  fn from(c: Const) -> Self {
+    Self::Const(c, NodeSpan::default())
+  }
+}
@@ -0,0 +1,361 @@
+//! Module related nodes: import and export declarations, `import()` and `import.meta`.
+
+use super::{Class, FunctionExpr, Identifier, Node};
+use crate::syntax::ast::{arena::ArenaBox, id::Id, NodeId, NodeSpan, Sym};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// An import attribute, such as `type: "json"` in `import a from "./a.json" with { type: "json" }`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-WithClause
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/import/with
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportAttribute {
+  key: Box<str>,
+  value: Box<str>,
+}
+
+impl ImportAttribute {
+  /// Creates a new import attribute.
+  pub fn new<K, V>(key: K, value: V) -> Self
+  where
+    K: Into<Box<str>>,
+    V: Into<Box<str>>,
+  {
+    Self {
+      key: key.into(),
+      value: value.into(),
+    }
+  }
+
+  /// Gets the key of the attribute.
+  pub fn key(&self) -> &str {
+    &self.key
+  }
+
+  /// Gets the value of the attribute.
+  pub fn value(&self) -> &str {
+    &self.value
+  }
+}
+
+/// A single binding created by an import declaration.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImportSpecifier {
+  /// The default export of the module, `import a from "mod"`.
+  Default(Identifier),
+  /// The module namespace object, `import * as ns from "mod"`.
+  Namespace(Identifier),
+  /// A named export of the module, `import { a as b } from "mod"`.
+  Named {
+    /// The name exported by the imported module.
+    imported: Sym,
+    /// The local binding.
+    local: Identifier,
+  },
+}
+
+impl ImportSpecifier {
+  /// Creates a named import specifier.
+  pub fn named<I, L>(imported: I, local: L) -> Self
+  where
+    I: Into<Sym>,
+    L: Into<Identifier>,
+  {
+    Self::Named {
+      imported: imported.into(),
+      local: local.into(),
+    }
+  }
+
+  /// Gets the name of the local binding created by this specifier.
+  pub fn local(&self) -> &Sym {
+    self.local_identifier().name()
+  }
+
+  /// Gets the identifier of the local binding created by this specifier.
+  pub fn local_identifier(&self) -> &Identifier {
+    match self {
+      Self::Default(local) | Self::Namespace(local) | Self::Named { local, .. } => local,
+    }
+  }
+
+  /// Gets the id of the specifier, the id of its local binding.
+  pub fn id(&self) -> NodeId {
+    self.local_identifier().id()
+  }
+}
+
+/// The `import` declaration binds names exported by another module.
+///
+/// A declaration without specifiers, such as `import "mod";`, only evaluates the module.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ImportDeclaration
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/import
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportDecl {
+  specifiers: ArenaBox<[ImportSpecifier]>,
+  source: Box<str>,
+  attributes: ArenaBox<[ImportAttribute]>,
+  span: NodeSpan,
+}
+
+impl ImportDecl {
+  /// Creates a new import declaration.
+  pub fn new<S, M, A>(specifiers: S, source: M, attributes: A) -> Self
+  where
+    S: Into<ArenaBox<[ImportSpecifier]>>,
+    M: Into<Box<str>>,
+    A: Into<ArenaBox<[ImportAttribute]>>,
+  {
+    Self {
+      specifiers: specifiers.into(),
+      source: source.into(),
+      attributes: attributes.into(),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the bindings created by the declaration.
+  pub fn specifiers(&self) -> &[ImportSpecifier] {
+    &self.specifiers
+  }
+
+  /// Mutable version of [`specifiers`](Self::specifiers).
+  pub fn specifiers_mut(&mut self) -> &mut [ImportSpecifier] {
+    &mut self.specifiers
+  }
+
+  /// Gets the module specifier string.
+  pub fn source(&self) -> &str {
+    &self.source
+  }
+
+  /// Gets the import attributes of the declaration.
+  pub fn attributes(&self) -> &[ImportAttribute] {
+    &self.attributes
+  }
+
+  /// Mutable version of [`attributes`](Self::attributes).
+  pub fn attributes_mut(&mut self) -> &mut [ImportAttribute] {
+    &mut self.attributes
+  }
+}
+
+spanned!(ImportDecl);
+
+impl From<ImportDecl> for Node {
+  fn from(decl: ImportDecl) -> Self {
+    Self::Import(decl)
+  }
+}
+
+/// A single name in an export list, such as `a as b` in `export { a as b }`.
+///
+/// For re-exports, `local` is the name exported by the source module.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExportSpecifier {
+  local: Sym,
+  exported: Sym,
+  local_span: NodeSpan,
+  #[cfg_attr(feature = "serde", serde(skip))]
+  id: Id,
+}
+
+impl ExportSpecifier {
+  /// Creates a new export specifier.
+  pub fn new<L, E>(local: L, exported: E) -> Self
+  where
+    L: Into<Sym>,
+    E: Into<Sym>,
+  {
+    Self {
+      local: local.into(),
+      exported: exported.into(),
+      local_span: NodeSpan::default(),
+      id: Id::default(),
+    }
+  }
+
+  /// Gets the local name, or the imported name for re-exports.
+  pub fn local(&self) -> &Sym {
+    &self.local
+  }
+
+  /// Gets the id of the specifier, which refers to its local name.
+  pub fn id(&self) -> NodeId {
+    self.id.get()
+  }
+
+  /// Gets the name visible to importing modules.
+  pub fn exported(&self) -> &str {
+    &self.exported
+  }
+}
+
+spanned!(
+  ExportSpecifier,
+  "the local name",
+  local_span,
+  set_local_span,
+  with_local_span
+);
+
+/// The `export` declaration makes bindings of the module available to other modules.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ExportDeclaration
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/export
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExportDecl {
+  /// An export list, such as `export { a, b as c };` or `export { a } from "mod";`.
+  Named {
+    /// The exported names.
+    specifiers: ArenaBox<[ExportSpecifier]>,
+    /// The module the names are re-exported from, if any.
+    source: Option<Box<str>>,
+    /// The import attributes of the re-export.
+    attributes: ArenaBox<[ImportAttribute]>,
+  },
+  /// A star re-export, such as `export * from "mod";` or `export * as ns from "mod";`.
+  All {
+    /// The name the namespace object is exported as, if any.
+    alias: Option<Sym>,
+    /// The module the names are re-exported from.
+    source: Box<str>,
+    /// The import attributes of the re-export.
+    attributes: ArenaBox<[ImportAttribute]>,
+  },
+  /// An exported variable, function or class declaration, such as `export const a = 1;`.
+  Declaration(ArenaBox<Node>),
+  /// A default exported function declaration, whose name is optional.
+  DefaultFunction(FunctionExpr),
+  /// A default exported class declaration, whose name is optional.
+  DefaultClass(Class),
+  /// A default exported expression, such as `export default a + b;`.
+  DefaultExpression(ArenaBox<Node>),
+}
+
+impl ExportDecl {
+  /// Creates an export list, optionally re-exported from another module.
+  pub fn named<S>(
+    specifiers: S,
+    source: Option<Box<str>>,
+    attributes: ArenaBox<[ImportAttribute]>,
+  ) -> Self
+  where
+    S: Into<ArenaBox<[ExportSpecifier]>>,
+  {
+    Self::Named {
+      specifiers: specifiers.into(),
+      source,
+      attributes,
+    }
+  }
+
+  /// Creates an exported declaration.
+  pub fn declaration<D>(declaration: D) -> Self
+  where
+    D: Into<Node>,
+  {
+    Self::Declaration(ArenaBox::new(declaration.into()))
+  }
+
+  /// Creates a default exported expression.
+  pub fn default_expression<E>(expr: E) -> Self
+  where
+    E: Into<Node>,
+  {
+    Self::DefaultExpression(ArenaBox::new(expr.into()))
+  }
+
+  /// Gets the module specifier string of a re-export.
+  pub fn source(&self) -> Option<&str> {
+    match self {
+      Self::Named { source, .. } => source.as_deref(),
+      Self::All { source, .. } => Some(source),
+      _ => None,
+    }
+  }
+}
+
+impl From<ExportDecl> for Node {
+  fn from(decl: ExportDecl) -> Self {
+    Self::Export(decl, NodeSpan::default())
+  }
+}
+
+/// A dynamic import, such as `import("./a.js")` or `import(a, { with: { type: "json" } })`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ImportCall
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/import
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportCall {
+  specifier: ArenaBox<Node>,
+  options: Option<ArenaBox<Node>>,
+  span: NodeSpan,
+}
+
+impl ImportCall {
+  /// Creates a new dynamic import.
+  pub fn new<S>(specifier: S, options: Option<Node>) -> Self
+  where
+    S: Into<Node>,
+  {
+    Self {
+      specifier: ArenaBox::new(specifier.into()),
+      options: options.map(ArenaBox::new),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the expression evaluating to the module specifier.
+  pub fn specifier(&self) -> &Node {
+    &self.specifier
+  }
+
+  /// Mutable version of [`specifier`](Self::specifier).
+  pub fn specifier_mut(&mut self) -> &mut Node {
+    &mut self.specifier
+  }
+
+  /// Gets the options argument, if any.
+  pub fn options(&self) -> Option<&Node> {
+    self.options.as_deref()
+  }
+
+  /// Mutable version of [`options`](Self::options).
+  pub fn options_mut(&mut self) -> Option<&mut Node> {
+    self.options.as_deref_mut()
+  }
+}
+
+spanned!(ImportCall);
+
+impl From<ImportCall> for Node {
+  fn from(call: ImportCall) -> Self {
+    Self::ImportCall(call)
+  }
+}
@@ -0,0 +1,232 @@
+//! Object literal node.
+
+use super::{FunctionExpr, Identifier, Node};
+use crate::syntax::ast::{arena::ArenaBox, NodeSpan, Span, Sym};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Objects in JavaScript may be defined as an unordered collection of related data, of
+/// primitive or reference types, in the form of “key: value” pairs.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ObjectLiteral
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Object_initializer
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Object {
+  properties: ArenaBox<[PropertyDefinition]>,
+  span: NodeSpan,
+}
+
+impl Object {
+  /// Gets the property definitions of the object literal.
+  pub fn properties(&self) -> &[PropertyDefinition] {
+    &self.properties
+  }
+
+  /// Mutable version of [`properties`](Self::properties).
+  pub fn properties_mut(&mut self) -> &mut [PropertyDefinition] {
+    &mut self.properties
+  }
+}
+
+spanned!(Object);
+
+impl<T> From<T> for Object
+where
+  T: Into<ArenaBox<[PropertyDefinition]>>,
+{
+  fn from(props: T) -> Self {
+    Self {
+      properties: props.into(),
+      span: NodeSpan::default(),
+    }
+  }
+}
+
+impl From<Object> for Node {
+  fn from(obj: Object) -> Self {
+    Self::Object(obj)
+  }
+}
+
+/// The name of a property, in an object literal, a pattern or a class.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-PropertyName
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyName {
+  /// An identifier, string or numeric name, stored as its string value.
+  Literal(Sym),
+  /// A computed name, such as `[key]`.
+  Computed(Node),
+}
+
+impl PropertyName {
+  /// Gets the literal name, or `None` if the name is computed.
+  pub fn literal(&self) -> Option<&str> {
+    match self {
+      Self::Literal(name) => Some(name),
+      Self::Computed(_) => None,
+    }
+  }
+}
+
+impl From<&str> for PropertyName {
+  fn from(name: &str) -> Self {
+    Self::Literal(name.into())
+  }
+}
+
+impl From<Box<str>> for PropertyName {
+  fn from(name: Box<str>) -> Self {
+    Self::Literal(name.into())
+  }
+}
+
+impl From<Sym> for PropertyName {
+  fn from(name: Sym) -> Self {
+    Self::Literal(name)
+  }
+}
+
+impl From<Node> for PropertyName {
+  fn from(node: Node) -> Self {
+    Self::Computed(node)
+  }
+}
+
+/// Formats a number the way the parser stores numeric property names.
+pub(crate) fn number_to_name(value: f64) -> String {
+  let int = value as i32;
+  #[allow(clippy::float_cmp)]
+  if f64::from(int) == value {
+    int.to_string()
+  } else {
+    value.to_string()
+  }
+}
+
+/// Gets the number a property name was written as, if it is a numeric property name.
+pub(crate) fn name_to_number(name: &str) -> Option<f64> {
+  name
+    .parse::<f64>()
+    .ok()
+    .filter(|value| value.is_finite() && number_to_name(*value) == name)
+}
+
+/// A JavaScript property is a member of an object that associates a key with a value.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-PropertyDefinition
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Glossary/property/JavaScript
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyDefinition {
+  /// Puts a variable into an object, such as `{ a }`.
+  IdentifierReference(Identifier, NodeSpan),
+  /// Binds a property name to a JavaScript value, such as `{ a: 1 }`.
+  Property(PropertyName, Node, NodeSpan),
+  /// A method definition, getter or setter, such as `{ get a() {} }`.
+  MethodDefinition(MethodDefinitionKind, PropertyName, FunctionExpr, NodeSpan),
+  /// Copies the own enumerable properties of an expression, such as `{ ...a }`.
+  SpreadObject(Node, NodeSpan),
+}
+
+impl PropertyDefinition {
+  /// Creates an `IdentifierReference` property definition.
+  pub fn identifier_reference<I>(ident: I) -> Self
+  where
+    I: Into<Identifier>,
+  {
+    Self::IdentifierReference(ident.into(), NodeSpan::default())
+  }
+
+  /// Creates a `Property` definition.
+  pub fn property<N, V>(name: N, value: V) -> Self
+  where
+    N: Into<PropertyName>,
+    V: Into<Node>,
+  {
+    Self::Property(name.into(), value.into(), NodeSpan::default())
+  }
+
+  /// Creates a `MethodDefinition`.
+  pub fn method_definition<N>(kind: MethodDefinitionKind, name: N, body: FunctionExpr) -> Self
+  where
+    N: Into<PropertyName>,
+  {
+    Self::MethodDefinition(kind, name.into(), body, NodeSpan::default())
+  }
+
+  /// Creates a `SpreadObject`.
+  pub fn spread_object<O>(obj: O) -> Self
+  where
+    O: Into<Node>,
+  {
+    Self::SpreadObject(obj.into(), NodeSpan::default())
+  }
+
+  /// Gets the span of the source the property was parsed from, `None` if it was not parsed.
+  pub fn span(&self) -> Option<Span> {
+    match self {
+      Self::IdentifierReference(_, span)
+      | Self::Property(_, _, span)
+      | Self::MethodDefinition(_, _, _, span)
+      | Self::SpreadObject(_, span) => span.get(),
+    }
+  }
+
+  /// Sets the span of the source the property was parsed from.
+  pub fn set_span<S>(&mut self, span: S)
+  where
+    S: Into<NodeSpan>,
+  {
+    match self {
+      Self::IdentifierReference(_, old)
+      | Self::Property(_, _, old)
+      | Self::MethodDefinition(_, _, _, old)
+      | Self::SpreadObject(_, old) => *old = span.into(),
+    }
+  }
+
+  /// Builder version of [`set_span`](Self::set_span).
+  pub fn with_span<S>(mut self, span: S) -> Self
+  where
+    S: Into<NodeSpan>,
+  {
+    self.set_span(span);
+    self
+  }
+}
+
+/// Method definition kinds.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-MethodDefinition
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Functions/Method_definitions
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Copy)]
+pub enum MethodDefinitionKind {
+  /// The `get` syntax binds an object property to a function that will be called when that
+  /// property is looked up.
+  Get,
+  /// The `set` syntax binds an object property to a function to be called when there is an
+  /// attempt to set that property.
+  Set,
+  /// Starting with ECMAScript 2015, a shorter syntax for method definitions is available.
+  Ordinary,
+}
@@ -0,0 +1,188 @@
+//! Operator nodes.
+
+use super::{Node, Pattern};
+use crate::syntax::ast::{arena::ArenaBox, operator, NodeSpan};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// An assignment operator assigns a value to its left operand based on the value of its right
+/// operand.
+///
+/// The left hand side is a `Pattern`, so this covers both simple assignments (`a = b`,
+/// `a.b = c`) and destructuring assignments (`[a, b] = [b, a]`).
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-AssignmentExpression
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Assignment_Operators
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Assign {
+  lhs: ArenaBox<Pattern>,
+  rhs: ArenaBox<Node>,
+  span: NodeSpan,
+}
+
+impl Assign {
+  /// Creates an `Assign` AST node.
+  pub fn new<L, R>(lhs: L, rhs: R) -> Self
+  where
+    L: Into<Pattern>,
+    R: Into<Node>,
+  {
+    Self {
+      lhs: ArenaBox::new(lhs.into()),
+      rhs: ArenaBox::new(rhs.into()),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the left hand side of the assignment operation.
+  pub fn lhs(&self) -> &Pattern {
+    &self.lhs
+  }
+
+  /// Mutable version of [`lhs`](Self::lhs).
+  pub fn lhs_mut(&mut self) -> &mut Pattern {
+    &mut self.lhs
+  }
+
+  /// Gets the right hand side of the assignment operation.
+  pub fn rhs(&self) -> &Node {
+    &self.rhs
+  }
+
+  /// Mutable version of [`rhs`](Self::rhs).
+  pub fn rhs_mut(&mut self) -> &mut Node {
+    &mut self.rhs
+  }
+}
+
+spanned!(Assign);
+
+impl From<Assign> for Node {
+  fn from(op: Assign) -> Self {
+    Self::Assign(op)
+  }
+}
+
+/// Binary operators requires two operands, one before the operator and one after the operator.
+///
+/// More information:
+///  - [MDN Documentation][mdn]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators#Operators
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BinOp {
+  op: operator::BinOp,
+  lhs: ArenaBox<Node>,
+  rhs: ArenaBox<Node>,
+  span: NodeSpan,
+}
+
+impl BinOp {
+  /// Creates a `BinOp` AST node.
+  pub fn new<O, L, R>(op: O, lhs: L, rhs: R) -> Self
+  where
+    O: Into<operator::BinOp>,
+    L: Into<Node>,
+    R: Into<Node>,
+  {
+    Self {
+      op: op.into(),
+      lhs: ArenaBox::new(lhs.into()),
+      rhs: ArenaBox::new(rhs.into()),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the binary operation of the node.
+  pub fn op(&self) -> operator::BinOp {
+    self.op
+  }
+
+  /// Gets the left hand side of the binary operation.
+  pub fn lhs(&self) -> &Node {
+    &self.lhs
+  }
+
+  /// Mutable version of [`lhs`](Self::lhs).
+  pub fn lhs_mut(&mut self) -> &mut Node {
+    &mut self.lhs
+  }
+
+  /// Gets the right hand side of the binary operation.
+  pub fn rhs(&self) -> &Node {
+    &self.rhs
+  }
+
+  /// Mutable version of [`rhs`](Self::rhs).
+  pub fn rhs_mut(&mut self) -> &mut Node {
+    &mut self.rhs
+  }
+}
+
+spanned!(BinOp);
+
+impl From<BinOp> for Node {
+  fn from(op: BinOp) -> Self {
+    Self::BinOp(op)
+  }
+}
+
+/// A unary operation is an operation with only one operand.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-UnaryExpression
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators#Unary_operators
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnaryOp {
+  op: operator::UnaryOp,
+  target: ArenaBox<Node>,
+  span: NodeSpan,
+}
+
+impl UnaryOp {
+  /// Creates a new `UnaryOp` AST node.
+  pub fn new<V>(op: operator::UnaryOp, target: V) -> Self
+  where
+    V: Into<Node>,
+  {
+    Self {
+      op,
+      target: ArenaBox::new(target.into()),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the unary operation of the node.
+  pub fn op(&self) -> operator::UnaryOp {
+    self.op
+  }
+
+  /// Gets the target of this unary operator.
+  pub fn target(&self) -> &Node {
+    self.target.as_ref()
+  }
+
+  /// Mutable version of [`target`](Self::target).
+  pub fn target_mut(&mut self) -> &mut Node {
+    self.target.as_mut()
+  }
+}
+
+spanned!(UnaryOp);
+
+impl From<UnaryOp> for Node {
+  fn from(op: UnaryOp) -> Self {
+    Self::UnaryOp(op)
+  }
+}
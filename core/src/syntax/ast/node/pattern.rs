@@ -0,0 +1,337 @@
+//! Destructuring pattern nodes.
+//!
+//! Patterns are used on the left hand side of declarations (`let {a} = b`), as function and
+//! catch parameters, in `for ... in`/`for ... of` heads and as destructuring assignment
+//! targets (`[a, b] = [b, a]`).
+
+use super::{Identifier, Node, PropertyName};
+use crate::syntax::ast::{arena::ArenaBox, NodeSpan, Span, Sym};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A binding or assignment target.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-BindingPattern
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Destructuring_assignment
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Pattern {
+  /// A single name, such as `a`.
+  Identifier(Identifier),
+  /// An object pattern, such as `{ a, b: [c], ...d }`.
+  Object(ObjectPattern),
+  /// An array pattern, such as `[a, , b = 1, ...c]`.
+  Array(ArrayPattern),
+  /// A property access, such as `a.b` or `a[0]`.
+  ///
+  /// Only valid in assignment patterns, binding patterns can only introduce names.
+  Member(ArenaBox<Node>),
+}
+
+impl Pattern {
+  /// Returns `true` if the pattern only binds names, which is what declarations, parameters and
+  /// catch clauses require.
+  pub fn is_binding(&self) -> bool {
+    match self {
+      Self::Identifier(_) => true,
+      Self::Member(_) => false,
+      Self::Object(obj) => {
+        obj.properties().iter().all(|p| p.value().target().is_binding())
+          && obj.rest().is_none_or(|r| matches!(r, Self::Identifier(_)))
+      }
+      Self::Array(arr) => {
+        arr.elements().iter().flatten().all(|e| e.target().is_binding())
+          && arr.rest().is_none_or(Self::is_binding)
+      }
+    }
+  }
+
+  /// Gets the span of the source the pattern was parsed from, `None` if it was not parsed.
+  pub fn span(&self) -> Option<Span> {
+    match self {
+      Self::Identifier(ident) => ident.span(),
+      Self::Object(obj) => obj.span(),
+      Self::Array(arr) => arr.span(),
+      Self::Member(node) => node.span(),
+    }
+  }
+
+  /// Sets the span of the source the pattern was parsed from.
+  pub fn set_span<S>(&mut self, span: S)
+  where
+    S: Into<NodeSpan>,
+  {
+    match self {
+      Self::Identifier(ident) => ident.set_span(span),
+      Self::Object(obj) => obj.set_span(span),
+      Self::Array(arr) => arr.set_span(span),
+      Self::Member(node) => node.set_span(span),
+    }
+  }
+
+  /// Collects the names introduced by this pattern, in source order.
+  ///
+  /// More information:
+  ///  - [ECMAScript reference][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#sec-static-semantics-boundnames
+  pub fn bound_names(&self) -> Vec<&Sym> {
+    self
+      .bound_identifiers()
+      .into_iter()
+      .map(Identifier::name)
+      .collect()
+  }
+
+  /// Collects the identifiers introduced by this pattern, in source order.
+  pub fn bound_identifiers(&self) -> Vec<&Identifier> {
+    let mut names = Vec::new();
+    self.collect_bound_names(&mut names);
+    names
+  }
+
+  fn collect_bound_names<'a>(&'a self, names: &mut Vec<&'a Identifier>) {
+    match self {
+      Self::Identifier(ident) => names.push(ident),
+      Self::Member(_) => {}
+      Self::Object(obj) => {
+        for prop in obj.properties() {
+          prop.value().target().collect_bound_names(names);
+        }
+        if let Some(rest) = obj.rest() {
+          rest.collect_bound_names(names);
+        }
+      }
+      Self::Array(arr) => {
+        for elem in arr.elements().iter().flatten() {
+          elem.target().collect_bound_names(names);
+        }
+        if let Some(rest) = arr.rest() {
+          rest.collect_bound_names(names);
+        }
+      }
+    }
+  }
+}
+
+impl From<Identifier> for Pattern {
+  fn from(ident: Identifier) -> Self {
+    Self::Identifier(ident)
+  }
+}
+
+impl From<ObjectPattern> for Pattern {
+  fn from(obj: ObjectPattern) -> Self {
+    Self::Object(obj)
+  }
+}
+
+impl From<ArrayPattern> for Pattern {
+  fn from(arr: ArrayPattern) -> Self {
+    Self::Array(arr)
+  }
+}
+
+/// A pattern target together with its optional default value, such as `b = 1` in `[a, b = 1]`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PatternElement {
+  target: Pattern,
+  default: Option<Node>,
+}
+
+impl PatternElement {
+  /// Creates a new pattern element.
+  pub fn new<P>(target: P, default: Option<Node>) -> Self
+  where
+    P: Into<Pattern>,
+  {
+    Self {
+      target: target.into(),
+      default,
+    }
+  }
+
+  /// Gets the target of the element.
+  pub fn target(&self) -> &Pattern {
+    &self.target
+  }
+
+  /// Mutable version of [`target`](Self::target).
+  pub fn target_mut(&mut self) -> &mut Pattern {
+    &mut self.target
+  }
+
+  /// Gets the default value used when the matched value is `undefined`.
+  pub fn default(&self) -> Option<&Node> {
+    self.default.as_ref()
+  }
+
+  /// Mutable version of [`default`](Self::default).
+  pub fn default_mut(&mut self) -> Option<&mut Node> {
+    self.default.as_mut()
+  }
+}
+
+/// An object destructuring pattern.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ObjectBindingPattern
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObjectPattern {
+  properties: ArenaBox<[PropertyPattern]>,
+  rest: Option<ArenaBox<Pattern>>,
+  span: NodeSpan,
+}
+
+impl ObjectPattern {
+  /// Creates a new object pattern.
+  pub fn new<P>(properties: P, rest: Option<Pattern>) -> Self
+  where
+    P: Into<ArenaBox<[PropertyPattern]>>,
+  {
+    Self {
+      properties: properties.into(),
+      rest: rest.map(ArenaBox::new),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the destructured properties.
+  pub fn properties(&self) -> &[PropertyPattern] {
+    &self.properties
+  }
+
+  /// Mutable version of [`properties`](Self::properties).
+  pub fn properties_mut(&mut self) -> &mut [PropertyPattern] {
+    &mut self.properties
+  }
+
+  /// Gets the target receiving the remaining properties (`...rest`), if any.
+  pub fn rest(&self) -> Option<&Pattern> {
+    self.rest.as_ref().map(ArenaBox::as_ref)
+  }
+
+  /// Mutable version of [`rest`](Self::rest).
+  pub fn rest_mut(&mut self) -> Option<&mut Pattern> {
+    self.rest.as_mut().map(ArenaBox::as_mut)
+  }
+}
+
+spanned!(ObjectPattern);
+
+/// A single property of an object pattern, such as `a: [b] = []` or the shorthand `a = 1`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PropertyPattern {
+  key: PropertyName,
+  value: PatternElement,
+  shorthand: bool,
+}
+
+impl PropertyPattern {
+  /// Creates a new `key: value` property pattern.
+  pub fn new(key: PropertyName, value: PatternElement) -> Self {
+    Self {
+      key,
+      value,
+      shorthand: false,
+    }
+  }
+
+  /// Creates a shorthand property pattern, such as `a` or `a = 1`.
+  pub fn shorthand<I>(name: I, default: Option<Node>) -> Self
+  where
+    I: Into<Identifier>,
+  {
+    let name = name.into();
+    Self {
+      key: PropertyName::Literal(name.name().clone()),
+      value: PatternElement::new(name, default),
+      shorthand: true,
+    }
+  }
+
+  /// Gets the name of the property being destructured.
+  pub fn key(&self) -> &PropertyName {
+    &self.key
+  }
+
+  /// Mutable version of [`key`](Self::key).
+  pub fn key_mut(&mut self) -> &mut PropertyName {
+    &mut self.key
+  }
+
+  /// Gets the target (and default value) the property is destructured into.
+  pub fn value(&self) -> &PatternElement {
+    &self.value
+  }
+
+  /// Mutable version of [`value`](Self::value).
+  pub fn value_mut(&mut self) -> &mut PatternElement {
+    &mut self.value
+  }
+
+  /// Returns `true` if the property was written in shorthand form.
+  pub fn is_shorthand(&self) -> bool {
+    self.shorthand
+  }
+}
+
+/// An array destructuring pattern.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ArrayBindingPattern
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArrayPattern {
+  elements: ArenaBox<[Option<PatternElement>]>,
+  rest: Option<ArenaBox<Pattern>>,
+  span: NodeSpan,
+}
+
+impl ArrayPattern {
+  /// Creates a new array pattern, `None` elements are elisions (holes).
+  pub fn new<E>(elements: E, rest: Option<Pattern>) -> Self
+  where
+    E: Into<ArenaBox<[Option<PatternElement>]>>,
+  {
+    Self {
+      elements: elements.into(),
+      rest: rest.map(ArenaBox::new),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the elements of the pattern, `None` marks an elision.
+  pub fn elements(&self) -> &[Option<PatternElement>] {
+    &self.elements
+  }
+
+  /// Mutable version of [`elements`](Self::elements).
+  pub fn elements_mut(&mut self) -> &mut [Option<PatternElement>] {
+    &mut self.elements
+  }
+
+  /// Gets the target receiving the remaining elements (`...rest`), if any.
+  pub fn rest(&self) -> Option<&Pattern> {
+    self.rest.as_ref().map(ArenaBox::as_ref)
+  }
+
+  /// Mutable version of [`rest`](Self::rest).
+  pub fn rest_mut(&mut self) -> Option<&mut Pattern> {
+    self.rest.as_mut().map(ArenaBox::as_mut)
+  }
+}
+
+spanned!(ArrayPattern);
@@ -0,0 +1,54 @@
+//! Return statement node.
+
+use super::Node;
+use crate::syntax::ast::{arena::ArenaBox, NodeSpan};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The `return` statement ends function execution and specifies a value to be returned to the
+/// function caller.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ReturnStatement
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/return
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Return {
+  expr: Option<ArenaBox<Node>>,
+  span: NodeSpan,
+}
+
+impl Return {
+  /// Creates a new `Return` AST node.
+  pub fn new<E>(expr: Option<E>) -> Self
+  where
+    E: Into<Node>,
+  {
+    Self {
+      expr: expr.map(|e| ArenaBox::new(e.into())),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the returned expression, if any.
+  pub fn expr(&self) -> Option<&Node> {
+    self.expr.as_ref().map(ArenaBox::as_ref)
+  }
+
+  /// Mutable version of [`expr`](Self::expr).
+  pub fn expr_mut(&mut self) -> Option<&mut Node> {
+    self.expr.as_mut().map(ArenaBox::as_mut)
+  }
+}
+
+spanned!(Return);
+
+impl From<Return> for Node {
+  fn from(return_smt: Return) -> Self {
+    Self::Return(return_smt)
+  }
+}
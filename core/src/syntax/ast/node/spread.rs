@@ -0,0 +1,54 @@
+//! Spread element node.
+
+use super::Node;
+use crate::syntax::ast::{arena::ArenaBox, NodeSpan};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The `spread` syntax allows an iterable to be expanded in places where zero or more
+/// arguments, elements or properties are expected.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-SpreadElement
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Spread_syntax
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spread {
+  val: ArenaBox<Node>,
+  span: NodeSpan,
+}
+
+impl Spread {
+  /// Creates a new spread element.
+  pub fn new<V>(val: V) -> Self
+  where
+    V: Into<Node>,
+  {
+    Self {
+      val: ArenaBox::new(val.into()),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the expression being spread.
+  pub fn val(&self) -> &Node {
+    &self.val
+  }
+
+  /// Mutable version of [`val`](Self::val).
+  pub fn val_mut(&mut self) -> &mut Node {
+    &mut self.val
+  }
+}
+
+spanned!(Spread);
+
+impl From<Spread> for Node {
+  fn from(spread: Spread) -> Self {
+    Self::Spread(spread)
+  }
+}
@@ -0,0 +1,68 @@
+//! Statement list node.
+
+use super::Node;
+use crate::syntax::ast::arena::ArenaBox;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// List of statements.
+///
+/// Similar to `Node::Block` but without the braces.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-StatementList
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatementList {
+  items: ArenaBox<[Node]>,
+  strict: bool,
+}
+
+impl StatementList {
+  /// Gets the list of items.
+  pub fn items(&self) -> &[Node] {
+    &self.items
+  }
+
+  /// Mutable version of [`items`](Self::items).
+  pub fn items_mut(&mut self) -> &mut [Node] {
+    &mut self.items
+  }
+
+  /// Replaces the items with the ones returned by `f`, which can add or remove items.
+  pub fn replace_items<F>(&mut self, f: F)
+  where
+    F: FnOnce(Vec<Node>) -> Vec<Node>,
+  {
+    let items = std::mem::take(&mut self.items);
+    self.items = f(items.into_vec()).into();
+  }
+
+  /// Returns `true` if the statements are strict mode code.
+  ///
+  /// This is only recorded for the body of a script, a module or a function.
+  pub fn strict(&self) -> bool {
+    self.strict
+  }
+
+  /// Sets whether the statements are strict mode code.
+  pub fn with_strict(mut self, strict: bool) -> Self {
+    self.strict = strict;
+    self
+  }
+}
+
+impl<T> From<T> for StatementList
+where
+  T: Into<ArenaBox<[Node]>>,
+{
+  fn from(stm: T) -> Self {
+    Self {
+      items: stm.into(),
+      strict: false,
+    }
+  }
+}
@@ -0,0 +1,120 @@
+//! Switch node.
+
+use super::{Node, StatementList};
+use crate::syntax::ast::{arena::ArenaBox, NodeSpan};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A single `case` (or `default`) clause of a `switch` statement.
+///
+/// The `default` clause is the one without a condition. Clauses are kept in source order
+/// since that decides fall-through.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Case {
+  condition: Option<Node>,
+  body: StatementList,
+  span: NodeSpan,
+}
+
+impl Case {
+  /// Creates a `Case` clause, `None` as the condition creates the `default` clause.
+  pub fn new<B>(condition: Option<Node>, body: B) -> Self
+  where
+    B: Into<StatementList>,
+  {
+    Self {
+      condition,
+      body: body.into(),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the condition of the clause, `None` for `default`.
+  pub fn condition(&self) -> Option<&Node> {
+    self.condition.as_ref()
+  }
+
+  /// Mutable version of [`condition`](Self::condition).
+  pub fn condition_mut(&mut self) -> Option<&mut Node> {
+    self.condition.as_mut()
+  }
+
+  /// Gets the statements of the clause.
+  pub fn body(&self) -> &[Node] {
+    self.body.items()
+  }
+
+  /// Mutable version of [`body`](Self::body).
+  pub fn body_mut(&mut self) -> &mut [Node] {
+    self.body.items_mut()
+  }
+
+  /// Gets the statement list of the body, to add or remove statements.
+  pub fn body_list_mut(&mut self) -> &mut StatementList {
+    &mut self.body
+  }
+}
+
+spanned!(Case);
+
+/// The `switch` statement evaluates an expression, matching the expression's value to a case
+/// clause, and executes statements associated with that case.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-SwitchStatement
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/switch
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Switch {
+  val: ArenaBox<Node>,
+  cases: ArenaBox<[Case]>,
+  span: NodeSpan,
+}
+
+impl Switch {
+  /// Creates a `Switch` AST node.
+  pub fn new<V, C>(val: V, cases: C) -> Self
+  where
+    V: Into<Node>,
+    C: Into<ArenaBox<[Case]>>,
+  {
+    Self {
+      val: ArenaBox::new(val.into()),
+      cases: cases.into(),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the value to switch.
+  pub fn val(&self) -> &Node {
+    &self.val
+  }
+
+  /// Mutable version of [`val`](Self::val).
+  pub fn val_mut(&mut self) -> &mut Node {
+    &mut self.val
+  }
+
+  /// Gets the list of clauses, in source order.
+  pub fn cases(&self) -> &[Case] {
+    &self.cases
+  }
+
+  /// Mutable version of [`cases`](Self::cases).
+  pub fn cases_mut(&mut self) -> &mut [Case] {
+    &mut self.cases
+  }
+}
+
+spanned!(Switch);
+
+impl From<Switch> for Node {
+  fn from(switch: Switch) -> Self {
+    Self::Switch(switch)
+  }
+}
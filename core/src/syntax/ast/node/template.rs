@@ -0,0 +1,168 @@
+//! Template literal nodes.
+
+use super::Node;
+use crate::syntax::ast::{arena::ArenaBox, NodeSpan};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A template literal, such as `` `a${b}c` ``.
+///
+/// The strings around the substitutions are its quasis, there is always one more quasi than
+/// there are substitutions.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-TemplateLiteral
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Template_literals
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TemplateLit {
+  quasis: ArenaBox<[TemplateElement]>,
+  exprs: ArenaBox<[Node]>,
+  span: NodeSpan,
+}
+
+impl TemplateLit {
+  /// Creates a `TemplateLit` AST node.
+  ///
+  /// # Panics
+  ///
+  /// Panics if there is not exactly one more quasi than there are substitutions.
+  pub fn new<Q, E>(quasis: Q, exprs: E) -> Self
+  where
+    Q: Into<ArenaBox<[TemplateElement]>>,
+    E: Into<ArenaBox<[Node]>>,
+  {
+    let quasis = quasis.into();
+    let exprs = exprs.into();
+    assert_eq!(
+      quasis.len(),
+      exprs.len() + 1,
+      "a template literal has one more quasi than substitutions"
+    );
+    Self {
+      quasis,
+      exprs,
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the strings of the template, in source order.
+  pub fn quasis(&self) -> &[TemplateElement] {
+    &self.quasis
+  }
+
+  /// Gets the substitutions of the template, in source order.
+  pub fn exprs(&self) -> &[Node] {
+    &self.exprs
+  }
+
+  /// Mutable version of [`exprs`](Self::exprs).
+  pub fn exprs_mut(&mut self) -> &mut [Node] {
+    &mut self.exprs
+  }
+}
+
+spanned!(TemplateLit);
+
+impl From<TemplateLit> for Node {
+  fn from(template: TemplateLit) -> Self {
+    Self::TemplateLit(template)
+  }
+}
+
+/// A string of a template literal, between its backticks and substitutions.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TemplateElement {
+  cooked: Option<Box<str>>,
+  raw: Box<str>,
+}
+
+impl TemplateElement {
+  /// Creates a template string from its cooked value and its raw source text.
+  pub fn new<C, R>(cooked: Option<C>, raw: R) -> Self
+  where
+    C: Into<Box<str>>,
+    R: Into<Box<str>>,
+  {
+    Self {
+      cooked: cooked.map(Into::into),
+      raw: raw.into(),
+    }
+  }
+
+  /// Gets the value of the string, with its escape sequences interpreted.
+  ///
+  /// `None` if it has an invalid escape sequence, which is only allowed in tagged templates.
+  pub fn cooked(&self) -> Option<&str> {
+    self.cooked.as_deref()
+  }
+
+  /// Gets the source text of the string, with line terminators normalized to `\n`.
+  pub fn raw(&self) -> &str {
+    &self.raw
+  }
+}
+
+/// A tagged template, such as `` tag`a${b}c` ``, which calls the tag with the strings and the
+/// values of the substitutions.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-tagged-templates
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Template_literals#tagged_templates
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaggedTemplate {
+  tag: ArenaBox<Node>,
+  template: TemplateLit,
+  span: NodeSpan,
+}
+
+impl TaggedTemplate {
+  /// Creates a `TaggedTemplate` AST node.
+  pub fn new<T>(tag: T, template: TemplateLit) -> Self
+  where
+    T: Into<Node>,
+  {
+    Self {
+      tag: ArenaBox::new(tag.into()),
+      template,
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the function called with the template.
+  pub fn tag(&self) -> &Node {
+    &self.tag
+  }
+
+  /// Mutable version of [`tag`](Self::tag).
+  pub fn tag_mut(&mut self) -> &mut Node {
+    &mut self.tag
+  }
+
+  /// Gets the template literal.
+  pub fn template(&self) -> &TemplateLit {
+    &self.template
+  }
+
+  /// Mutable version of [`template`](Self::template).
+  pub fn template_mut(&mut self) -> &mut TemplateLit {
+    &mut self.template
+  }
+}
+
+spanned!(TaggedTemplate);
+
+impl From<TaggedTemplate> for Node {
+  fn from(tagged: TaggedTemplate) -> Self {
+    Self::TaggedTemplate(tagged)
+  }
+}
@@ -0,0 +1,53 @@
+//! Throw statement node.
+
+use super::Node;
+use crate::syntax::ast::{arena::ArenaBox, NodeSpan};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The `throw` statement throws a user-defined exception.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ThrowStatement
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/throw
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Throw {
+  expr: ArenaBox<Node>,
+  span: NodeSpan,
+}
+
+impl Throw {
+  /// Creates a new `Throw` AST node.
+  pub fn new<E>(expr: E) -> Self
+  where
+    E: Into<Node>,
+  {
+    Self {
+      expr: ArenaBox::new(expr.into()),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the thrown expression.
+  pub fn expr(&self) -> &Node {
+    &self.expr
+  }
+
+  /// Mutable version of [`expr`](Self::expr).
+  pub fn expr_mut(&mut self) -> &mut Node {
+    &mut self.expr
+  }
+}
+
+spanned!(Throw);
+
+impl From<Throw> for Node {
+  fn from(throw: Throw) -> Self {
+    Self::Throw(throw)
+  }
+}
@@ -0,0 +1,162 @@
+//! Try statement nodes.
+
+use super::{Block, Node, Pattern};
+use crate::syntax::ast::{arena::ArenaBox, NodeSpan};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The `try...catch` statement marks a block of statements to try and specifies a response
+/// should an exception be thrown.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-TryStatement
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/try...catch
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Try {
+  block: Block,
+  catch: Option<ArenaBox<Catch>>,
+  finally: Option<ArenaBox<Finally>>,
+  span: NodeSpan,
+}
+
+impl Try {
+  /// Creates a new `Try` AST node.
+  pub fn new<B>(block: B, catch: Option<Catch>, finally: Option<Finally>) -> Self
+  where
+    B: Into<Block>,
+  {
+    assert!(
+      catch.is_some() || finally.is_some(),
+      "one of catch or finally must be pressent"
+    );
+
+    Self {
+      block: block.into(),
+      catch: catch.map(ArenaBox::new),
+      finally: finally.map(ArenaBox::new),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the `try` block.
+  pub fn block(&self) -> &Block {
+    &self.block
+  }
+
+  /// Mutable version of [`block`](Self::block).
+  pub fn block_mut(&mut self) -> &mut Block {
+    &mut self.block
+  }
+
+  /// Gets the `catch` clause, if any.
+  pub fn catch(&self) -> Option<&Catch> {
+    self.catch.as_deref()
+  }
+
+  /// Mutable version of [`catch`](Self::catch).
+  pub fn catch_mut(&mut self) -> Option<&mut Catch> {
+    self.catch.as_deref_mut()
+  }
+
+  /// Gets the `finally` block, if any.
+  pub fn finally(&self) -> Option<&Block> {
+    self.finally.as_deref().map(Finally::block)
+  }
+
+  /// Mutable version of [`finally`](Self::finally).
+  pub fn finally_mut(&mut self) -> Option<&mut Block> {
+    self
+      .finally
+      .as_deref_mut()
+      .map(|finally| &mut finally.block)
+  }
+}
+
+spanned!(Try);
+
+impl From<Try> for Node {
+  fn from(try_catch: Try) -> Self {
+    Self::Try(try_catch)
+  }
+}
+
+/// Catch block.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Catch {
+  parameter: Option<Pattern>,
+  block: Block,
+  span: NodeSpan,
+}
+
+impl Catch {
+  /// Creates a new catch block, the parameter is optional (`catch {}`).
+  pub fn new<P, B>(parameter: Option<P>, block: B) -> Self
+  where
+    P: Into<Pattern>,
+    B: Into<Block>,
+  {
+    Self {
+      parameter: parameter.map(P::into),
+      block: block.into(),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the binding of the caught exception, if any.
+  pub fn parameter(&self) -> Option<&Pattern> {
+    self.parameter.as_ref()
+  }
+
+  /// Mutable version of [`parameter`](Self::parameter).
+  pub fn parameter_mut(&mut self) -> Option<&mut Pattern> {
+    self.parameter.as_mut()
+  }
+
+  /// Retrieves the catch execution block.
+  pub fn block(&self) -> &Block {
+    &self.block
+  }
+
+  /// Mutable version of [`block`](Self::block).
+  pub fn block_mut(&mut self) -> &mut Block {
+    &mut self.block
+  }
+}
+
+spanned!(Catch);
+
+/// Finally block.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Finally {
+  block: Block,
+}
+
+impl Finally {
+  /// Gets the finally block.
+  pub fn block(&self) -> &Block {
+    &self.block
+  }
+
+  /// Mutable version of [`block`](Self::block).
+  pub fn block_mut(&mut self) -> &mut Block {
+    &mut self.block
+  }
+}
+
+impl<T> From<T> for Finally
+where
+  T: Into<Block>,
+{
+  fn from(block: T) -> Self {
+    Self {
+      block: block.into(),
+    }
+  }
+}
@@ -0,0 +1,69 @@
+//! With statement node.
+
+use super::Node;
+use crate::syntax::ast::{arena::ArenaBox, NodeSpan};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The `with` statement extends the scope chain of a statement with the properties of an
+/// object.
+///
+/// It is only allowed in sloppy mode code.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-WithStatement
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/with
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct With {
+  object: ArenaBox<Node>,
+  body: ArenaBox<Node>,
+  span: NodeSpan,
+}
+
+impl With {
+  /// Creates a `With` AST node.
+  pub fn new<O, B>(object: O, body: B) -> Self
+  where
+    O: Into<Node>,
+    B: Into<Node>,
+  {
+    Self {
+      object: ArenaBox::new(object.into()),
+      body: ArenaBox::new(body.into()),
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the object whose properties are in scope.
+  pub fn object(&self) -> &Node {
+    &self.object
+  }
+
+  /// Mutable version of [`object`](Self::object).
+  pub fn object_mut(&mut self) -> &mut Node {
+    &mut self.object
+  }
+
+  /// Gets the body of the `with` statement.
+  pub fn body(&self) -> &Node {
+    &self.body
+  }
+
+  /// Mutable version of [`body`](Self::body).
+  pub fn body_mut(&mut self) -> &mut Node {
+    &mut self.body
+  }
+}
+
+spanned!(With);
+
+impl From<With> for Node {
+  fn from(with: With) -> Self {
+    Self::With(with)
+  }
+}
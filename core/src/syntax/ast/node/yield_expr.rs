@@ -0,0 +1,61 @@
+//! Yield expression node.
+
+use super::Node;
+use crate::syntax::ast::{arena::ArenaBox, NodeSpan};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The `yield` keyword is used to pause and resume a generator function, `yield*` delegates to
+/// another iterable.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-YieldExpression
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/yield
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Yield {
+  expr: Option<ArenaBox<Node>>,
+  delegate: bool,
+  span: NodeSpan,
+}
+
+impl Yield {
+  /// Creates a new `Yield` AST node.
+  pub fn new<E>(expr: Option<E>, delegate: bool) -> Self
+  where
+    E: Into<Node>,
+  {
+    Self {
+      expr: expr.map(|e| ArenaBox::new(e.into())),
+      delegate,
+      span: NodeSpan::default(),
+    }
+  }
+
+  /// Gets the yielded expression, if any.
+  pub fn expr(&self) -> Option<&Node> {
+    self.expr.as_ref().map(ArenaBox::as_ref)
+  }
+
+  /// Mutable version of [`expr`](Self::expr).
+  pub fn expr_mut(&mut self) -> Option<&mut Node> {
+    self.expr.as_mut().map(ArenaBox::as_mut)
+  }
+
+  /// Returns `true` if this is a delegating `yield*` expression.
+  pub fn delegate(&self) -> bool {
+    self.delegate
+  }
+}
+
+spanned!(Yield);
+
+impl From<Yield> for Node {
+  fn from(yield_expr: Yield) -> Self {
+    Self::Yield(yield_expr)
+  }
+}
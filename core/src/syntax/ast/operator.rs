@@ -1,11 +1,10 @@
-use gc::{Trace, Finalize, unsafe_empty_trace};
 use std::fmt::{Display, Formatter, Result};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Clone, Copy, Finalize, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum NumOp {
   /// `a + b`
   Add,
@@ -38,12 +37,89 @@ impl Display for NumOp {
   }
 }
 
-unsafe impl Trace for NumOp {
-  unsafe_empty_trace!();
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UnaryOp {
+  /// `++a`
+  IncrementPre,
+  /// `a++`
+  IncrementPost,
+  /// `--a`
+  DecrementPre,
+  /// `a--`
+  DecrementPost,
+  /// `-a`
+  Minus,
+  /// `+a`
+  Plus,
+  /// `!a`
+  Not,
+  /// `~a`
+  Tilde,
+  /// `typeof a`
+  TypeOf,
+  /// `delete a`
+  Delete,
+  /// `void a`
+  Void,
+}
+
+impl Display for UnaryOp {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    write!(
+      f,
+      "{}",
+      match *self {
+        Self::IncrementPost | Self::IncrementPre => "++",
+        Self::DecrementPost | Self::DecrementPre => "--",
+        Self::Plus => "+",
+        Self::Minus => "-",
+        Self::Not => "!",
+        Self::Tilde => "~",
+        Self::Delete => "delete",
+        Self::TypeOf => "typeof",
+        Self::Void => "void",
+      }
+    )
+  }
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Clone, Copy, Finalize, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BitOp {
+  /// `a & b`
+  And,
+  /// `a | b`
+  Or,
+  /// `a ^ b`
+  Xor,
+  /// `a << b`
+  Shl,
+  /// `a >> b`
+  Shr,
+  /// `a >>> b`
+  UShr,
+}
+
+impl Display for BitOp {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    write!(
+      f,
+      "{}",
+      match *self {
+        Self::And => "&",
+        Self::Or => "|",
+        Self::Xor => "^",
+        Self::Shl => "<<",
+        Self::Shr => ">>",
+        Self::UShr => ">>>",
+      }
+    )
+  }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum CompOp {
   /// `a == b`
   Equal,
@@ -63,7 +139,7 @@ pub enum CompOp {
   LessThanOrEqual,
   /// `a in b`
   In,
-  /// `a instanceOf b`
+  /// `a instanceof b`
   InstanceOf,
 }
 
@@ -79,26 +155,24 @@ impl Display for CompOp {
         Self::StrictNotEqual => "!==",
         Self::GreaterThan => ">",
         Self::GreaterThanOrEqual => ">=",
-        Self::LessThan => "<=",
+        Self::LessThan => "<",
         Self::LessThanOrEqual => "<=",
         Self::In => "in",
-        Self::InstanceOf => "instanceOf"
+        Self::InstanceOf => "instanceof",
       }
     )
   }
 }
 
-unsafe impl Trace for CompOp {
-  unsafe_empty_trace!();
-}
-
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Clone, Copy, Finalize, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum LogOp {
   /// `a && b`
   And,
   /// `a || b`
   Or,
+  /// `a ?? b`
+  Coalesce,
 }
 
 impl Display for LogOp {
@@ -109,21 +183,84 @@ impl Display for LogOp {
       match &self {
         Self::And => "&&",
         Self::Or => "||",
+        Self::Coalesce => "??",
       }
     )
   }
 }
 
-unsafe impl Trace for LogOp {
-  unsafe_empty_trace!();
+/// A compound assignment operator, such as `a += b`.
+///
+/// Plain assignment (`a = b`) is represented by the `Assign` node instead.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AssignOp {
+  /// `a += b`
+  Add,
+  /// `a -= b`
+  Sub,
+  /// `a *= b`
+  Mul,
+  /// `a **= b`
+  Exp,
+  /// `a /= b`
+  Div,
+  /// `a %= b`
+  Mod,
+  /// `a &= b`
+  And,
+  /// `a |= b`
+  Or,
+  /// `a ^= b`
+  Xor,
+  /// `a <<= b`
+  Shl,
+  /// `a >>= b`
+  Shr,
+  /// `a >>>= b`
+  Ushr,
+  /// `a &&= b`
+  BoolAnd,
+  /// `a ||= b`
+  BoolOr,
+  /// `a ??= b`
+  Coalesce,
+}
+
+impl Display for AssignOp {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    write!(
+      f,
+      "{}",
+      match *self {
+        Self::Add => "+=",
+        Self::Sub => "-=",
+        Self::Mul => "*=",
+        Self::Exp => "**=",
+        Self::Div => "/=",
+        Self::Mod => "%=",
+        Self::And => "&=",
+        Self::Or => "|=",
+        Self::Xor => "^=",
+        Self::Shl => "<<=",
+        Self::Shr => ">>=",
+        Self::Ushr => ">>>=",
+        Self::BoolAnd => "&&=",
+        Self::BoolOr => "||=",
+        Self::Coalesce => "??=",
+      }
+    )
+  }
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Clone, Copy, Finalize, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum BinOp {
   Num(NumOp),
+  Bit(BitOp),
   Comp(CompOp),
   Log(LogOp),
+  Assign(AssignOp),
   Comma,
 }
 
@@ -133,6 +270,18 @@ impl From<NumOp> for BinOp {
   }
 }
 
+impl From<BitOp> for BinOp {
+  fn from(op: BitOp) -> Self {
+    Self::Bit(op)
+  }
+}
+
+impl From<AssignOp> for BinOp {
+  fn from(op: AssignOp) -> Self {
+    Self::Assign(op)
+  }
+}
+
 impl From<CompOp> for BinOp {
   fn from(op: CompOp) -> Self {
     Self::Comp(op)
@@ -152,14 +301,13 @@ impl Display for BinOp {
       "{}",
       match *self {
         Self::Num(ref op) => op.to_string(),
+        Self::Bit(ref op) => op.to_string(),
         Self::Comp(ref op) => op.to_string(),
         Self::Log(ref op) => op.to_string(),
+        Self::Assign(ref op) => op.to_string(),
         Self::Comma => ",".to_string(),
       }
     )
   }
 }
 
-unsafe impl Trace for BinOp {
-  unsafe_empty_trace!();
-}
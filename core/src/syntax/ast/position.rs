@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 /// Stores both the column number and line number.
 ///
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Position {
   /// Line number.
   line_number: NonZeroU32,
@@ -46,7 +46,7 @@ impl fmt::Display for Position {
 ///
 /// Stroes a start position and an end positon.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Span {
   start: Position,
   end: Position,
@@ -109,6 +109,43 @@ impl fmt::Display for Span {
   }
 }
 
+/// The span of the source a node of the syntax tree was parsed from.
+///
+/// Nodes created after parsing, by a transform or by hand, have no span. Comparing nodes
+/// ignores their spans, so trees are equal when they have the same structure, wherever they
+/// come from.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NodeSpan(Option<Span>);
+
+impl NodeSpan {
+  /// Gets the span, `None` if the node was not parsed.
+  #[inline]
+  pub fn get(self) -> Option<Span> {
+    self.0
+  }
+}
+
+impl PartialEq for NodeSpan {
+  fn eq(&self, _: &Self) -> bool {
+    true
+  }
+}
+
+impl Eq for NodeSpan {}
+
+impl From<Span> for NodeSpan {
+  fn from(span: Span) -> Self {
+    Self(Some(span))
+  }
+}
+
+impl From<Option<Span>> for NodeSpan {
+  fn from(span: Option<Span>) -> Self {
+    Self(span)
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::{Position, Span};
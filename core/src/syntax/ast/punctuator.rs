@@ -5,7 +5,7 @@
 //!
 //! [spec]: https://tc39.es/ecma262/#prod-Punctuator
 
-use crate::syntax::ast::operator::{BinOp, CompOp, LogOp, NumOp};
+use crate::syntax::ast::operator::{AssignOp, BinOp, BitOp, CompOp, LogOp, NumOp};
 use std::{
   convert::TryInto,
   fmt::{Display, Error, Formatter},
@@ -35,6 +35,12 @@ pub enum Punctuator {
   AssignAdd,
   /// `&=`
   AssignAnd,
+  /// `&&=`
+  AssignBoolAnd,
+  /// `||=`
+  AssignBoolOr,
+  /// `??=`
+  AssignCoalesce,
   /// `/=`
   AssignDiv,
   /// `<<=`
@@ -65,6 +71,8 @@ pub enum Punctuator {
   CloseBracket,
   /// `)`
   CloseParen,
+  /// `??`
+  Coalesce,
   /// `:`
   Colon,
   /// `,`
@@ -105,6 +113,8 @@ pub enum Punctuator {
   OpenBracket,
   /// `(`
   OpenParen,
+  /// `?.`
+  Optional,
   /// `|`
   Or,
   /// `**`
@@ -135,28 +145,33 @@ impl Punctuator {
   /// If there is no match, `None` will be returned.
   pub fn as_binop(self) -> Option<BinOp> {
     match self {
-      // Self::AssignAdd => Some(BinOp::Assign(AssignOp::Add)),
-      // Self::AssignAnd => Some(BinOp::Assign(AssignOp::And)),
-      // Self::AssignDiv => Some(BinOp::Assign(AssignOp::Div)),
-      // Self::AssignLeftSh => Some(BinOp::Assign(AssignOp::Shl)),
-      // Self::AssignMod => Some(BinOp::Assign(AssignOp::Mod)),
-      // Self::AssignMul => Some(BinOp::Assign(AssignOp::Mul)),
-      // Self::AssignOr => Some(BinOp::Assign(AssignOp::Or)),
-      // Self::AssignPow => Some(BinOp::Assign(AssignOp::Exp)),
-      // Self::AssignRightSh => Some(BinOp::Assign(AssignOp::Shr)),
-      // Self::AssignSub => Some(BinOp::Assign(AssignOp::Sub)),
-      // Self::AssignURightSh => Some(BinOp::Assign(AssignOp::Ushr)),
-      // Self::AssignXor => Some(BinOp::Assign(AssignOp::Xor)),
+      Self::AssignAdd => Some(BinOp::Assign(AssignOp::Add)),
+      Self::AssignAnd => Some(BinOp::Assign(AssignOp::And)),
+      Self::AssignBoolAnd => Some(BinOp::Assign(AssignOp::BoolAnd)),
+      Self::AssignBoolOr => Some(BinOp::Assign(AssignOp::BoolOr)),
+      Self::AssignCoalesce => Some(BinOp::Assign(AssignOp::Coalesce)),
+      Self::AssignDiv => Some(BinOp::Assign(AssignOp::Div)),
+      Self::AssignLeftSh => Some(BinOp::Assign(AssignOp::Shl)),
+      Self::AssignMod => Some(BinOp::Assign(AssignOp::Mod)),
+      Self::AssignMul => Some(BinOp::Assign(AssignOp::Mul)),
+      Self::AssignOr => Some(BinOp::Assign(AssignOp::Or)),
+      Self::AssignPow => Some(BinOp::Assign(AssignOp::Exp)),
+      Self::AssignRightSh => Some(BinOp::Assign(AssignOp::Shr)),
+      Self::AssignSub => Some(BinOp::Assign(AssignOp::Sub)),
+      Self::AssignURightSh => Some(BinOp::Assign(AssignOp::Ushr)),
+      Self::AssignXor => Some(BinOp::Assign(AssignOp::Xor)),
       Self::Add => Some(BinOp::Num(NumOp::Add)),
       Self::Sub => Some(BinOp::Num(NumOp::Sub)),
       Self::Mul => Some(BinOp::Num(NumOp::Mul)),
       Self::Div => Some(BinOp::Num(NumOp::Div)),
       Self::Mod => Some(BinOp::Num(NumOp::Mod)),
-      // Self::And => Some(BinOp::Bit(BitOp::And)),
-      // Self::Or => Some(BinOp::Bit(BitOp::Or)),
-      // Self::Xor => Some(BinOp::Bit(BitOp::Xor)),
+      Self::Exp => Some(BinOp::Num(NumOp::Exp)),
+      Self::And => Some(BinOp::Bit(BitOp::And)),
+      Self::Or => Some(BinOp::Bit(BitOp::Or)),
+      Self::Xor => Some(BinOp::Bit(BitOp::Xor)),
       Self::BoolAnd => Some(BinOp::Log(LogOp::And)),
       Self::BoolOr => Some(BinOp::Log(LogOp::Or)),
+      Self::Coalesce => Some(BinOp::Log(LogOp::Coalesce)),
       Self::Eq => Some(BinOp::Comp(CompOp::Equal)),
       Self::NotEq => Some(BinOp::Comp(CompOp::NotEqual)),
       Self::StrictEq => Some(BinOp::Comp(CompOp::StrictEqual)),
@@ -165,9 +180,9 @@ impl Punctuator {
       Self::GreaterThan => Some(BinOp::Comp(CompOp::GreaterThan)),
       Self::GreaterThanOrEq => Some(BinOp::Comp(CompOp::GreaterThanOrEqual)),
       Self::LessThanOrEq => Some(BinOp::Comp(CompOp::LessThanOrEqual)),
-      // Self::LeftSh => Some(BinOp::Bit(BitOp::Shl)),
-      // Self::RightSh => Some(BinOp::Bit(BitOp::Shr)),
-      // Self::URightSh => Some(BinOp::Bit(BitOp::UShr)),
+      Self::LeftSh => Some(BinOp::Bit(BitOp::Shl)),
+      Self::RightSh => Some(BinOp::Bit(BitOp::Shr)),
+      Self::URightSh => Some(BinOp::Bit(BitOp::UShr)),
       Self::Comma => Some(BinOp::Comma),
       _ => None,
     }
@@ -195,6 +210,9 @@ impl Display for Punctuator {
         Self::Assign => "=",
         Self::AssignAdd => "+=",
         Self::AssignAnd => "&=",
+        Self::AssignBoolAnd => "&&=",
+        Self::AssignBoolOr => "||=",
+        Self::AssignCoalesce => "??=",
         Self::AssignDiv => "/=",
         Self::AssignLeftSh => "<<=",
         Self::AssignMod => "%=",
@@ -210,6 +228,7 @@ impl Display for Punctuator {
         Self::CloseBlock => "}",
         Self::CloseBracket => "]",
         Self::CloseParen => ")",
+        Self::Coalesce => "??",
         Self::Colon => ":",
         Self::Comma => ",",
         Self::Dec => "--",
@@ -230,6 +249,7 @@ impl Display for Punctuator {
         Self::OpenBlock => "{",
         Self::OpenBracket => "[",
         Self::OpenParen => "(",
+        Self::Optional => "?.",
         Self::Or => "|",
         Self::Exp => "**",
         Self::Question => "?",
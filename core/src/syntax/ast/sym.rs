@@ -0,0 +1,267 @@
+//! Interned names.
+//!
+//! The lexer interns the names of identifiers as it reads them, so every occurrence of a name in
+//! a source shares a single allocation, which the tokens and the syntax tree hold as [`Sym`]s:
+//!
+//! ```
+//! use plastic::syntax::ast::sym::Interner;
+//!
+//! let mut interner = Interner::new();
+//! let first = interner.intern("total");
+//! let second = interner.intern("total");
+//! assert_eq!(first, "total");
+//! assert!(first.ptr_eq(&second));
+//! assert_eq!(interner.len(), 1);
+//! ```
+//!
+//! A symbol keeps its name alive on its own, so it can outlive the interner, and symbols made
+//! without an interner, such as the names given to new nodes, are equal to the interned ones
+//! with the same text.
+//!
+//! The nodes themselves are allocated in the [arena](super::arena), the names being shared
+//! between trees rather than owned by one.
+
+use std::{
+  borrow::Borrow,
+  cmp::Ordering,
+  collections::HashSet,
+  convert::TryInto,
+  fmt,
+  hash::{self, BuildHasherDefault, Hasher},
+  ops::Deref,
+  rc::Rc,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// An interned name, cheap to clone and to compare.
+#[derive(Clone, Eq)]
+pub struct Sym(Rc<str>);
+
+impl Sym {
+  /// Gets the text of the name.
+  pub fn as_str(&self) -> &str {
+    &self.0
+  }
+
+  /// Returns `true` if both symbols share the same allocation, as the symbols of a name
+  /// interned by the same interner do.
+  pub fn ptr_eq(&self, other: &Self) -> bool {
+    Rc::ptr_eq(&self.0, &other.0)
+  }
+}
+
+impl Deref for Sym {
+  type Target = str;
+
+  fn deref(&self) -> &str {
+    &self.0
+  }
+}
+
+impl AsRef<str> for Sym {
+  fn as_ref(&self) -> &str {
+    &self.0
+  }
+}
+
+impl Borrow<str> for Sym {
+  fn borrow(&self) -> &str {
+    &self.0
+  }
+}
+
+impl PartialEq for Sym {
+  fn eq(&self, other: &Self) -> bool {
+    self.ptr_eq(other) || self.0 == other.0
+  }
+}
+
+impl PartialEq<str> for Sym {
+  fn eq(&self, other: &str) -> bool {
+    &*self.0 == other
+  }
+}
+
+impl PartialEq<&str> for Sym {
+  fn eq(&self, other: &&str) -> bool {
+    &*self.0 == *other
+  }
+}
+
+impl PartialEq<Sym> for str {
+  fn eq(&self, other: &Sym) -> bool {
+    self == &*other.0
+  }
+}
+
+impl PartialEq<Sym> for &str {
+  fn eq(&self, other: &Sym) -> bool {
+    *self == &*other.0
+  }
+}
+
+impl PartialOrd for Sym {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for Sym {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.0.cmp(&other.0)
+  }
+}
+
+impl hash::Hash for Sym {
+  fn hash<H: hash::Hasher>(&self, state: &mut H) {
+    self.0.hash(state)
+  }
+}
+
+impl fmt::Debug for Sym {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt::Debug::fmt(&*self.0, f)
+  }
+}
+
+impl fmt::Display for Sym {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt::Display::fmt(&*self.0, f)
+  }
+}
+
+impl From<&str> for Sym {
+  fn from(name: &str) -> Self {
+    Self(name.into())
+  }
+}
+
+impl From<&String> for Sym {
+  fn from(name: &String) -> Self {
+    Self(name.as_str().into())
+  }
+}
+
+impl From<String> for Sym {
+  fn from(name: String) -> Self {
+    Self(name.into())
+  }
+}
+
+impl From<Box<str>> for Sym {
+  fn from(name: Box<str>) -> Self {
+    Self(name.into())
+  }
+}
+
+impl From<&Sym> for Sym {
+  fn from(sym: &Sym) -> Self {
+    sym.clone()
+  }
+}
+
+impl From<Sym> for Box<str> {
+  fn from(sym: Sym) -> Self {
+    sym.as_str().into()
+  }
+}
+
+impl From<&Sym> for Box<str> {
+  fn from(sym: &Sym) -> Self {
+    sym.as_str().into()
+  }
+}
+
+impl From<Sym> for String {
+  fn from(sym: Sym) -> Self {
+    sym.as_str().to_owned()
+  }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Sym {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serializer.serialize_str(&self.0)
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Sym {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    String::deserialize(deserializer).map(Self::from)
+  }
+}
+
+/// The names interned while parsing a source.
+#[derive(Debug, Default)]
+pub struct Interner {
+  names: HashSet<Rc<str>, BuildHasherDefault<NameHasher>>,
+}
+
+impl Interner {
+  /// Creates an empty interner.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Gets the symbol of a name, allocating it the first time the name is seen.
+  pub fn intern(&mut self, name: &str) -> Sym {
+    if let Some(interned) = self.names.get(name) {
+      return Sym(interned.clone());
+    }
+    let interned: Rc<str> = name.into();
+    self.names.insert(interned.clone());
+    Sym(interned)
+  }
+
+  /// Gets the number of distinct names interned.
+  pub fn len(&self) -> usize {
+    self.names.len()
+  }
+
+  /// Returns `true` if no name was interned.
+  pub fn is_empty(&self) -> bool {
+    self.names.is_empty()
+  }
+}
+
+/// A hasher for short names, much faster than the default one, which resists collisions forged
+/// by an attacker: a source can only slow down its own parse.
+#[derive(Debug, Default)]
+struct NameHasher(u64);
+
+impl NameHasher {
+  fn add(&mut self, word: u64) {
+    self.0 = (self.0.rotate_left(5) ^ word).wrapping_mul(0x51_7c_c1_b7_27_22_0a_95);
+  }
+}
+
+impl Hasher for NameHasher {
+  fn write(&mut self, bytes: &[u8]) {
+    let mut chunks = bytes.chunks_exact(8);
+    for chunk in &mut chunks {
+      self.add(u64::from_le_bytes(
+        chunk.try_into().expect("chunks have 8 bytes"),
+      ));
+    }
+    for &byte in chunks.remainder() {
+      self.add(u64::from(byte));
+    }
+  }
+
+  fn write_u8(&mut self, byte: u8) {
+    self.add(u64::from(byte));
+  }
+
+  fn finish(&self) -> u64 {
+    self.0
+  }
+}
@@ -0,0 +1,393 @@
+//! Traversal of the syntax tree.
+//!
+//! [`Visitor`] walks a tree by shared reference and [`VisitorMut`] by mutable reference. Both
+//! have a `visit_*` method for each kind of node, whose default implementation calls the
+//! matching `walk_*` function of [`visit`] or [`visit_mut`] to visit the children. Overriding a
+//! `visit_*` method and calling its `walk_*` function from it runs code around the children,
+//! not calling it skips them.
+//!
+//! The `enter` and `exit` hooks are called for every [`Node`] and for the parts of the tree that
+//! are not nodes but can hold them, like declarations and patterns (see [`NodeRef`]). Returning
+//! [`Control::SkipChildren`] from `enter` skips the children of the node, and returning
+//! [`Control::Stop`] from either hook ends the traversal, which then returns
+//! [`ControlFlow::Break`].
+//!
+//! Every method also gets the [`Path`] from the root to the node: references to the ancestors
+//! for a `Visitor`, and their [`NodeKind`] for a `VisitorMut`, as the ancestors are already
+//! borrowed mutably. The path of the `visit_*` method of a [`Node`] variant, such as
+//! `visit_call`, ends with the node itself.
+//!
+//! ```
+//! use plastic::syntax::{
+//!   ast::{
+//!     node::Call,
+//!     visitor::{visit, Flow, NodePath, Path, Visitor},
+//!   },
+//!   parser::Parser,
+//! };
+//!
+//! /// Counts the function calls of a script.
+//! struct CallCounter(usize);
+//!
+//! impl<'ast> Visitor<'ast> for CallCounter {
+//!   fn visit_call(&mut self, call: &'ast Call, path: &mut NodePath<'ast>) -> Flow {
+//!     self.0 += 1;
+//!     visit::walk_call(self, call, path)
+//!   }
+//! }
+//!
+//! let script = Parser::new("f(g(1), h);".as_bytes()).parse_script().unwrap();
+//! let mut counter = CallCounter(0);
+//! counter.visit_statements(script.items(), &mut Path::new());
+//! assert_eq!(counter.0, 2);
+//! ```
+
+pub mod visit;
+pub mod visit_mut;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::{visit::Visitor, visit_mut::VisitorMut};
+pub use std::ops::ControlFlow;
+
+use super::node::{
+  Case, Catch, ClassElement, Declaration, ExportSpecifier, FormalParameter, ImportSpecifier, Node,
+  Pattern, PropertyDefinition,
+};
+
+/// The result of a traversal, [`ControlFlow::Break`] if it was stopped.
+pub type Flow = ControlFlow<()>;
+
+/// The path given to a [`Visitor`], with references to the ancestors.
+pub type NodePath<'ast> = Path<NodeRef<'ast>>;
+
+/// The path given to a [`VisitorMut`], with the kinds of the ancestors.
+pub type KindPath = Path<NodeKind>;
+
+/// What the traversal does after an `enter` or `exit` hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+  /// Goes on with the traversal.
+  Continue,
+  /// Skips the children of the node, its `exit` hook is still called.
+  ///
+  /// Returned from an `exit` hook, this is the same as `Continue`.
+  SkipChildren,
+  /// Ends the traversal.
+  Stop,
+}
+
+/// The ancestors of the node being visited, from the root.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Path<T> {
+  ancestors: Vec<T>,
+}
+
+impl<T> Path<T> {
+  /// Creates the path of a root, with no ancestors.
+  pub fn new() -> Self {
+    Self {
+      ancestors: Vec::new(),
+    }
+  }
+
+  /// Gets the parent of the node being visited, `None` at the root.
+  pub fn parent(&self) -> Option<&T> {
+    self.ancestors.last()
+  }
+
+  /// Iterates over the ancestors of the node being visited, from its parent to the root.
+  pub fn ancestors(&self) -> impl Iterator<Item = &T> {
+    self.ancestors.iter().rev()
+  }
+
+  /// Gets the number of ancestors of the node being visited.
+  pub fn depth(&self) -> usize {
+    self.ancestors.len()
+  }
+
+  /// Gets the ancestors of the node being visited, from the root to its parent.
+  pub fn as_slice(&self) -> &[T] {
+    &self.ancestors
+  }
+
+  /// Enters a node, making it the parent of the nodes visited next.
+  fn push(&mut self, ancestor: T) {
+    self.ancestors.push(ancestor);
+  }
+
+  /// Leaves the last node entered.
+  fn pop(&mut self) {
+    self.ancestors.pop();
+  }
+}
+
+impl<T> Default for Path<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// A reference to a part of the tree given to the hooks of a [`Visitor`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NodeRef<'ast> {
+  /// An expression or a statement.
+  Node(&'ast Node),
+  /// A declaration of a `var`, `let` or `const` statement.
+  Declaration(&'ast Declaration),
+  /// A function parameter.
+  FormalParameter(&'ast FormalParameter),
+  /// A binding or assignment pattern.
+  Pattern(&'ast Pattern),
+  /// A property of an object literal.
+  PropertyDefinition(&'ast PropertyDefinition),
+  /// An element of a class body.
+  ClassElement(&'ast ClassElement),
+  /// A clause of a `switch` statement.
+  Case(&'ast Case),
+  /// The `catch` clause of a `try` statement.
+  Catch(&'ast Catch),
+  /// A name imported by an `import` declaration.
+  ImportSpecifier(&'ast ImportSpecifier),
+  /// A name exported by an `export` list.
+  ExportSpecifier(&'ast ExportSpecifier),
+}
+
+impl NodeRef<'_> {
+  /// Gets the kind of the node.
+  pub fn kind(&self) -> NodeKind {
+    match self {
+      Self::Node(node) => NodeKind::of(node),
+      Self::Declaration(_) => NodeKind::Declaration,
+      Self::FormalParameter(_) => NodeKind::FormalParameter,
+      Self::Pattern(_) => NodeKind::Pattern,
+      Self::PropertyDefinition(_) => NodeKind::PropertyDefinition,
+      Self::ClassElement(_) => NodeKind::ClassElement,
+      Self::Case(_) => NodeKind::Case,
+      Self::Catch(_) => NodeKind::Catch,
+      Self::ImportSpecifier(_) => NodeKind::ImportSpecifier,
+      Self::ExportSpecifier(_) => NodeKind::ExportSpecifier,
+    }
+  }
+}
+
+/// A mutable reference to a part of the tree given to the hooks of a [`VisitorMut`].
+///
+/// The hooks can replace the node, the traversal then goes on with the new one.
+#[derive(Debug, PartialEq)]
+pub enum NodeMut<'ast> {
+  /// An expression or a statement.
+  Node(&'ast mut Node),
+  /// A declaration of a `var`, `let` or `const` statement.
+  Declaration(&'ast mut Declaration),
+  /// A function parameter.
+  FormalParameter(&'ast mut FormalParameter),
+  /// A binding or assignment pattern.
+  Pattern(&'ast mut Pattern),
+  /// A property of an object literal.
+  PropertyDefinition(&'ast mut PropertyDefinition),
+  /// An element of a class body.
+  ClassElement(&'ast mut ClassElement),
+  /// A clause of a `switch` statement.
+  Case(&'ast mut Case),
+  /// The `catch` clause of a `try` statement.
+  Catch(&'ast mut Catch),
+  /// A name imported by an `import` declaration.
+  ImportSpecifier(&'ast mut ImportSpecifier),
+  /// A name exported by an `export` list.
+  ExportSpecifier(&'ast mut ExportSpecifier),
+}
+
+impl NodeMut<'_> {
+  /// Gets the kind of the node.
+  pub fn kind(&self) -> NodeKind {
+    match self {
+      Self::Node(node) => NodeKind::of(node),
+      Self::Declaration(_) => NodeKind::Declaration,
+      Self::FormalParameter(_) => NodeKind::FormalParameter,
+      Self::Pattern(_) => NodeKind::Pattern,
+      Self::PropertyDefinition(_) => NodeKind::PropertyDefinition,
+      Self::ClassElement(_) => NodeKind::ClassElement,
+      Self::Case(_) => NodeKind::Case,
+      Self::Catch(_) => NodeKind::Catch,
+      Self::ImportSpecifier(_) => NodeKind::ImportSpecifier,
+      Self::ExportSpecifier(_) => NodeKind::ExportSpecifier,
+    }
+  }
+}
+
+/// Implements the conversions of a part of the tree to a `NodeRef` and a `NodeMut`.
+macro_rules! node_ref {
+  ($($ty:ident),*) => {
+    $(
+      impl<'ast> From<&'ast $ty> for NodeRef<'ast> {
+        fn from(node: &'ast $ty) -> Self {
+          Self::$ty(node)
+        }
+      }
+
+      impl<'ast> From<&'ast mut $ty> for NodeMut<'ast> {
+        fn from(node: &'ast mut $ty) -> Self {
+          Self::$ty(node)
+        }
+      }
+    )*
+  };
+}
+
+node_ref!(
+  Node,
+  Declaration,
+  FormalParameter,
+  Pattern,
+  PropertyDefinition,
+  ClassElement,
+  Case,
+  Catch,
+  ImportSpecifier,
+  ExportSpecifier
+);
+
+/// The kind of a part of the tree: the variant of a [`Node`], or the type of the other parts of
+/// the tree given to the hooks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+  ArrayDecl,
+  ArrowFunctionDecl,
+  Assign,
+  BinOp,
+  Await,
+  Block,
+  Break,
+  Call,
+  ClassDecl,
+  ClassExpr,
+  ConditionalOp,
+  Const,
+  Continue,
+  Debugger,
+  DeclarationList,
+  Directive,
+  DoWhileLoop,
+  Empty,
+  Error,
+  Export,
+  ForInLoop,
+  ForLoop,
+  ForOfLoop,
+  FunctionDecl,
+  FunctionExpr,
+  GetConstField,
+  GetField,
+  GetPrivateField,
+  Identifier,
+  If,
+  Import,
+  ImportCall,
+  ImportMeta,
+  Labelled,
+  New,
+  Object,
+  OptionalChain,
+  PrivateIn,
+  Return,
+  Spread,
+  Super,
+  Switch,
+  TaggedTemplate,
+  TemplateLit,
+  This,
+  Throw,
+  Try,
+  UnaryOp,
+  WhileLoop,
+  With,
+  Yield,
+  Declaration,
+  FormalParameter,
+  Pattern,
+  PropertyDefinition,
+  ClassElement,
+  Case,
+  Catch,
+  ImportSpecifier,
+  ExportSpecifier,
+}
+
+impl NodeKind {
+  /// Gets the kind of a node.
+  pub fn of(node: &Node) -> Self {
+    match node {
+      Node::ArrayDecl(_) => Self::ArrayDecl,
+      Node::ArrowFunctionDecl(_) => Self::ArrowFunctionDecl,
+      Node::Assign(_) => Self::Assign,
+      Node::BinOp(_) => Self::BinOp,
+      Node::Await(_) => Self::Await,
+      Node::Block(_) => Self::Block,
+      Node::Break(_) => Self::Break,
+      Node::Call(_) => Self::Call,
+      Node::ClassDecl(_) => Self::ClassDecl,
+      Node::ClassExpr(_) => Self::ClassExpr,
+      Node::ConditionalOp(_) => Self::ConditionalOp,
+      Node::Const(_, _) => Self::Const,
+      Node::Continue(_) => Self::Continue,
+      Node::Debugger(_) => Self::Debugger,
+      Node::DeclarationList(_, _) => Self::DeclarationList,
+      Node::Directive(_) => Self::Directive,
+      Node::DoWhileLoop(_) => Self::DoWhileLoop,
+      Node::Empty(_) => Self::Empty,
+      Node::Error(_) => Self::Error,
+      Node::Export(_, _) => Self::Export,
+      Node::ForInLoop(_) => Self::ForInLoop,
+      Node::ForLoop(_) => Self::ForLoop,
+      Node::ForOfLoop(_) => Self::ForOfLoop,
+      Node::FunctionDecl(_) => Self::FunctionDecl,
+      Node::FunctionExpr(_) => Self::FunctionExpr,
+      Node::GetConstField(_) => Self::GetConstField,
+      Node::GetField(_) => Self::GetField,
+      Node::GetPrivateField(_) => Self::GetPrivateField,
+      Node::Identifier(_) => Self::Identifier,
+      Node::If(_) => Self::If,
+      Node::Import(_) => Self::Import,
+      Node::ImportCall(_) => Self::ImportCall,
+      Node::ImportMeta(_) => Self::ImportMeta,
+      Node::Labelled(_) => Self::Labelled,
+      Node::New(_) => Self::New,
+      Node::Object(_) => Self::Object,
+      Node::OptionalChain(_) => Self::OptionalChain,
+      Node::PrivateIn(_) => Self::PrivateIn,
+      Node::Return(_) => Self::Return,
+      Node::Spread(_) => Self::Spread,
+      Node::Super(_) => Self::Super,
+      Node::Switch(_) => Self::Switch,
+      Node::TaggedTemplate(_) => Self::TaggedTemplate,
+      Node::TemplateLit(_) => Self::TemplateLit,
+      Node::This(_) => Self::This,
+      Node::Throw(_) => Self::Throw,
+      Node::Try(_) => Self::Try,
+      Node::UnaryOp(_) => Self::UnaryOp,
+      Node::WhileLoop(_) => Self::WhileLoop,
+      Node::With(_) => Self::With,
+      Node::Yield(_) => Self::Yield,
+    }
+  }
+
+  /// Returns `true` for the kinds of functions, whose bodies have their own scope.
+  pub fn is_function(self) -> bool {
+    matches!(
+      self,
+      Self::ArrowFunctionDecl | Self::FunctionDecl | Self::FunctionExpr
+    )
+  }
+}
+
+impl Control {
+  /// Gets the result of an `exit` hook, or of an `enter` hook that skipped the children.
+  fn after_exit(self) -> Flow {
+    match self {
+      Self::Stop => Flow::Break(()),
+      Self::Continue | Self::SkipChildren => Flow::Continue(()),
+    }
+  }
+}
@@ -0,0 +1,376 @@
+//! Tests for the traversal of the syntax tree.
+
+use super::{
+  visit, visit_mut, Control, Flow, KindPath, NodeKind, NodeMut, NodePath, NodeRef, Path, Visitor,
+  VisitorMut,
+};
+use crate::syntax::{
+  ast::{
+    node::{FunctionExpr, Identifier, StatementList},
+    Const, Node,
+  },
+  parser::Parser,
+};
+
+/// Parses a script.
+#[track_caller]
+fn parse(js: &str) -> StatementList {
+  Parser::new(js.as_bytes())
+    .parse_script()
+    .expect("failed to parse the script")
+}
+
+/// Records the names of the identifiers, in the order they are visited.
+#[derive(Default)]
+struct Identifiers(Vec<String>);
+
+impl<'ast> Visitor<'ast> for Identifiers {
+  fn visit_identifier(&mut self, ident: &'ast Identifier, _path: &mut NodePath<'ast>) -> Flow {
+    self.0.push(ident.name().to_string());
+    Flow::Continue(())
+  }
+}
+
+/// Collects the identifiers of a script.
+fn identifiers(js: &str) -> Vec<String> {
+  let script = parse(js);
+  let mut visitor = Identifiers::default();
+  assert_eq!(
+    visitor.visit_statements(script.items(), &mut Path::new()),
+    Flow::Continue(())
+  );
+  visitor.0
+}
+
+#[test]
+fn source_order() {
+  assert_eq!(
+    identifiers(
+      "let { a, b: [c = d] } = e; \
+       function f(g, ...h) { return i ? j(k) : l[m]; } \
+       for (n of o) p; \
+       try { q } catch (r) { s } finally { t }"
+    ),
+    vec!["a", "c", "d", "e", "g", "h", "i", "j", "k", "l", "m", "n", "o", "p", "q", "r", "s", "t"]
+  );
+  assert_eq!(
+    identifiers(
+      "class A extends B { [c] = d; static { e } get #f() { g } } \
+       ({ h, i: j, [k]: l, m() { n }, ...o }); \
+       switch (p) { case q: r; default: s }"
+    ),
+    vec!["B", "c", "d", "e", "g", "j", "k", "l", "n", "o", "p", "q", "r", "s"]
+  );
+}
+
+/// Records the `enter` and `exit` hooks with the kind of the node and its depth.
+#[derive(Default)]
+struct Hooks(Vec<String>);
+
+impl<'ast> Visitor<'ast> for Hooks {
+  fn enter(&mut self, node: NodeRef<'ast>, path: &NodePath<'ast>) -> Control {
+    self.0.push(format!(
+      "{}enter {:?}",
+      "  ".repeat(path.depth()),
+      node.kind()
+    ));
+    Control::Continue
+  }
+
+  fn exit(&mut self, node: NodeRef<'ast>, path: &NodePath<'ast>) -> Control {
+    self.0.push(format!(
+      "{}exit {:?}",
+      "  ".repeat(path.depth()),
+      node.kind()
+    ));
+    Control::Continue
+  }
+}
+
+#[test]
+fn hooks() {
+  let script = parse("let a = f(1);");
+  let mut hooks = Hooks::default();
+  assert_eq!(
+    hooks.visit_statements(script.items(), &mut Path::new()),
+    Flow::Continue(())
+  );
+  assert_eq!(
+    hooks.0,
+    vec![
+      "enter DeclarationList",
+      "  enter Declaration",
+      "    enter Pattern",
+      "    exit Pattern",
+      "    enter Call",
+      "      enter Identifier",
+      "      exit Identifier",
+      "      enter Const",
+      "      exit Const",
+      "    exit Call",
+      "  exit Declaration",
+      "exit DeclarationList",
+    ]
+  );
+}
+
+/// Checks the ancestors of the identifiers.
+struct Parents;
+
+impl<'ast> Visitor<'ast> for Parents {
+  fn visit_identifier(&mut self, ident: &'ast Identifier, path: &mut NodePath<'ast>) -> Flow {
+    let kinds: Vec<_> = path.ancestors().map(NodeRef::kind).collect();
+    match ident.name().as_str() {
+      "a" => assert_eq!(
+        kinds,
+        [
+          NodeKind::Pattern,
+          NodeKind::FormalParameter,
+          NodeKind::FunctionDecl
+        ]
+      ),
+      "b" => {
+        // The identifier node itself is the last entry of the path.
+        assert_eq!(
+          kinds,
+          [
+            NodeKind::Identifier,
+            NodeKind::Return,
+            NodeKind::FunctionDecl
+          ]
+        );
+        assert!(matches!(
+          path.parent(),
+          Some(NodeRef::Node(Node::Identifier(_)))
+        ));
+      }
+      name => panic!("unexpected identifier `{}`", name),
+    }
+    Flow::Continue(())
+  }
+}
+
+#[test]
+fn path() {
+  let script = parse("function f(a) { return b; }");
+  let mut path = Path::new();
+  assert_eq!(
+    Parents.visit_statements(script.items(), &mut path),
+    Flow::Continue(())
+  );
+  assert_eq!(path.depth(), 0);
+}
+
+/// Skips the bodies of functions, in the `enter` hook.
+#[derive(Default)]
+struct SkipFunctions(Identifiers, usize);
+
+impl<'ast> Visitor<'ast> for SkipFunctions {
+  fn enter(&mut self, node: NodeRef<'ast>, _path: &NodePath<'ast>) -> Control {
+    if node.kind().is_function() {
+      Control::SkipChildren
+    } else {
+      Control::Continue
+    }
+  }
+
+  fn exit(&mut self, node: NodeRef<'ast>, _path: &NodePath<'ast>) -> Control {
+    if node.kind().is_function() {
+      self.1 += 1;
+    }
+    Control::Continue
+  }
+
+  fn visit_identifier(&mut self, ident: &'ast Identifier, path: &mut NodePath<'ast>) -> Flow {
+    self.0.visit_identifier(ident, path)
+  }
+}
+
+#[test]
+fn skip_children() {
+  let script = parse("a; function f() { b; } (() => c)(d);");
+  let mut visitor = SkipFunctions::default();
+  assert_eq!(
+    visitor.visit_statements(script.items(), &mut Path::new()),
+    Flow::Continue(())
+  );
+  assert_eq!((visitor.0).0, vec!["a", "d"]);
+  assert_eq!(visitor.1, 2);
+}
+
+/// Skips the bodies of functions expressions, by not walking them.
+#[derive(Default)]
+struct SkipFunctionExprs(Identifiers);
+
+impl<'ast> Visitor<'ast> for SkipFunctionExprs {
+  fn visit_function_expr(&mut self, _expr: &'ast FunctionExpr, _path: &mut NodePath<'ast>) -> Flow {
+    Flow::Continue(())
+  }
+
+  fn visit_identifier(&mut self, ident: &'ast Identifier, path: &mut NodePath<'ast>) -> Flow {
+    self.0.visit_identifier(ident, path)
+  }
+}
+
+#[test]
+fn skip_by_not_walking() {
+  let script = parse("a(function () { b; }, { c() { d; } }, e);");
+  let mut visitor = SkipFunctionExprs::default();
+  assert_eq!(
+    visitor.visit_statements(script.items(), &mut Path::new()),
+    Flow::Continue(())
+  );
+  assert_eq!((visitor.0).0, vec!["a", "e"]);
+}
+
+/// Stops at the first call.
+#[derive(Default)]
+struct FirstCall(Identifiers);
+
+impl<'ast> Visitor<'ast> for FirstCall {
+  fn enter(&mut self, node: NodeRef<'ast>, _path: &NodePath<'ast>) -> Control {
+    match node {
+      NodeRef::Node(Node::Call(_)) => Control::Stop,
+      _ => Control::Continue,
+    }
+  }
+
+  fn visit_identifier(&mut self, ident: &'ast Identifier, path: &mut NodePath<'ast>) -> Flow {
+    self.0.visit_identifier(ident, path)
+  }
+}
+
+#[test]
+fn stop() {
+  let script = parse("a; if (b) { c(d); } e;");
+  let mut visitor = FirstCall::default();
+  let mut path = Path::new();
+  assert_eq!(
+    visitor.visit_statements(script.items(), &mut path),
+    Flow::Break(())
+  );
+  assert_eq!((visitor.0).0, vec!["a", "b"]);
+  assert_eq!(path.depth(), 0);
+}
+
+/// Renames every identifier, references and bindings.
+struct Rename;
+
+impl VisitorMut for Rename {
+  fn visit_identifier(&mut self, ident: &mut Identifier, _path: &mut KindPath) -> Flow {
+    *ident = Identifier::from(format!("_{}", ident.name()));
+    Flow::Continue(())
+  }
+}
+
+#[test]
+fn rename() {
+  let mut script = parse("let [a, { k: b }] = c; function f(d = e) { return a + d; }");
+  assert_eq!(
+    Rename.visit_statements(script.items_mut(), &mut Path::new()),
+    Flow::Continue(())
+  );
+  assert_eq!(
+    script,
+    parse("let [_a, { k: _b }] = _c; function f(_d = _e) { return _a + _d; }")
+  );
+}
+
+/// Replaces the `undefined` identifier by `void 0`, in the `enter` hook.
+#[derive(Default)]
+struct ReplaceUndefined(Vec<Vec<NodeKind>>);
+
+impl VisitorMut for ReplaceUndefined {
+  fn enter(&mut self, node: NodeMut<'_>, path: &KindPath) -> Control {
+    if let NodeMut::Node(node) = node {
+      if matches!(node, Node::Identifier(ident) if ident.name() == "undefined") {
+        *node = parse("void 0;").items()[0].clone();
+        self.0.push(path.as_slice().to_vec());
+      }
+    }
+    Control::Continue
+  }
+
+  fn visit_const(&mut self, value: &mut Const, _path: &mut KindPath) -> Flow {
+    if let Const::Int(0) = value {
+      *value = Const::Int(1);
+    }
+    Flow::Continue(())
+  }
+}
+
+#[test]
+fn replace_in_hook() {
+  let mut script = parse("f(undefined);");
+  let mut visitor = ReplaceUndefined::default();
+  assert_eq!(
+    visitor.visit_statements(script.items_mut(), &mut Path::new()),
+    Flow::Continue(())
+  );
+  // The children of the new node are visited.
+  assert_eq!(script, parse("f(void 1);"));
+  assert_eq!(visitor.0, vec![vec![NodeKind::Call]]);
+}
+
+/// Records the kinds of the nodes entered, with either traversal.
+#[derive(Default)]
+struct Kinds(Vec<(usize, NodeKind)>);
+
+impl<'ast> Visitor<'ast> for Kinds {
+  fn enter(&mut self, node: NodeRef<'ast>, path: &NodePath<'ast>) -> Control {
+    self.0.push((path.depth(), node.kind()));
+    Control::Continue
+  }
+}
+
+impl VisitorMut for Kinds {
+  fn enter(&mut self, node: NodeMut<'_>, path: &KindPath) -> Control {
+    self.0.push((path.depth(), node.kind()));
+    Control::Continue
+  }
+}
+
+#[test]
+fn same_traversal() {
+  let mut script = parse(
+    r#"
+    "use strict";
+    var a = [1, , ...b], { c, d: [e] = [], ...f } = g;
+    async function* h(i, j = 1, ...k) { yield await i; yield* k; }
+    const l = async (m) => m?.n.o?.(p)?.[q], r = () => { return; };
+    label: for (let s = 0; s < 10; s++) { if (s) continue label; else break; }
+    for (var t in u) {} for (v.w of x) {} while (y) do z--; while (a);
+    switch (a) { case 1: b(); default: }
+    try { throw new Error("e"); } catch ({ message }) {} finally { debugger; }
+    class A extends B {
+      static #c = 0;
+      constructor() { super(); }
+      static { A.#c++; }
+      get d() { return #c in this; }
+      [Symbol.iterator]() {}
+    }
+    x = a ? b : c, y ??= z, o = { a, b: 1, [c]: 2, get d() {}, set d(v) {}, e() {}, ...f };
+    typeof a, void 0, delete a.b, !a, ~a, -a, +a, ++a, a++, this, import("m");
+    "#,
+  );
+  let mut shared = Kinds::default();
+  assert_eq!(
+    Visitor::visit_statements(&mut shared, script.items(), &mut Path::new()),
+    Flow::Continue(())
+  );
+  let mut mutable = Kinds::default();
+  assert_eq!(
+    VisitorMut::visit_statements(&mut mutable, script.items_mut(), &mut Path::new()),
+    Flow::Continue(())
+  );
+  assert!(shared.0.len() > 150, "{} nodes", shared.0.len());
+  assert_eq!(shared.0, mutable.0);
+
+  // The `walk_*` functions are the default methods.
+  let mut walked = Kinds::default();
+  assert_eq!(
+    visit::walk_statements(&mut walked, script.items(), &mut Path::new()),
+    visit_mut::walk_statements(&mut Kinds::default(), script.items_mut(), &mut Path::new())
+  );
+  assert_eq!(walked.0, shared.0);
+}
@@ -0,0 +1,1255 @@
+//! Traversal of the syntax tree by shared reference.
+//!
+//! Each `walk_*` function visits the children of a node with the visitor, it is the default
+//! implementation of the matching [`Visitor`] method.
+
+use super::{Control, Flow, NodePath, NodeRef};
+use crate::syntax::ast::{
+  node::{
+    ArrayDecl, ArrayPattern, ArrowFunctionDecl, Assign, Await, BinOp, Block, Break, Call, Case,
+    Catch, Class, ClassElement, ClassElementName, ConditionalOp, Continue, Declaration,
+    DeclarationList, DoWhileLoop, ExportDecl, ExportSpecifier, ForInLoop, ForLoop, ForOfLoop,
+    FormalParameter, FunctionDecl, FunctionExpr, GetConstField, GetField, GetPrivateField,
+    Identifier, If, ImportCall, ImportDecl, ImportSpecifier, IterableLoopInitializer, Labelled,
+    New, Object, ObjectPattern, OptionalChain, Pattern, PatternElement, PrivateIn,
+    PropertyDefinition, PropertyName, PropertyPattern, Return, Spread, Switch, TaggedTemplate,
+    TemplateLit, Throw, Try, UnaryOp, WhileLoop, With, Yield,
+  },
+  Const, Node,
+};
+
+/// A traversal of the syntax tree by shared reference.
+///
+/// See the [module documentation](super) for how the methods fit together.
+pub trait Visitor<'ast> {
+  /// Called before visiting the children of a node, see [`NodeRef`] for the parts of the tree
+  /// this is called for.
+  fn enter(&mut self, _node: NodeRef<'ast>, _path: &NodePath<'ast>) -> Control {
+    Control::Continue
+  }
+
+  /// Called after visiting the children of a node.
+  fn exit(&mut self, _node: NodeRef<'ast>, _path: &NodePath<'ast>) -> Control {
+    Control::Continue
+  }
+
+  /// Visits a list of statements, such as a script or a function body.
+  fn visit_statements(&mut self, statements: &'ast [Node], path: &mut NodePath<'ast>) -> Flow {
+    walk_statements(self, statements, path)
+  }
+
+  /// Visits an expression or a statement.
+  fn visit_node(&mut self, node: &'ast Node, path: &mut NodePath<'ast>) -> Flow {
+    walk_node(self, node, path)
+  }
+
+  /// Visits an array literal.
+  fn visit_array_decl(&mut self, array: &'ast ArrayDecl, path: &mut NodePath<'ast>) -> Flow {
+    walk_array_decl(self, array, path)
+  }
+
+  /// Visits an arrow function.
+  fn visit_arrow_function_decl(
+    &mut self,
+    arrow: &'ast ArrowFunctionDecl,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    walk_arrow_function_decl(self, arrow, path)
+  }
+
+  /// Visits a plain assignment.
+  fn visit_assign(&mut self, assign: &'ast Assign, path: &mut NodePath<'ast>) -> Flow {
+    walk_assign(self, assign, path)
+  }
+
+  /// Visits a binary or compound assignment operation.
+  fn visit_bin_op(&mut self, op: &'ast BinOp, path: &mut NodePath<'ast>) -> Flow {
+    walk_bin_op(self, op, path)
+  }
+
+  /// Visits an `await` expression.
+  fn visit_await(&mut self, expr: &'ast Await, path: &mut NodePath<'ast>) -> Flow {
+    walk_await(self, expr, path)
+  }
+
+  /// Visits a block statement, or the block of a `try` statement.
+  fn visit_block(&mut self, block: &'ast Block, path: &mut NodePath<'ast>) -> Flow {
+    walk_block(self, block, path)
+  }
+
+  /// Visits a `break` statement.
+  fn visit_break(&mut self, _stmt: &'ast Break, _path: &mut NodePath<'ast>) -> Flow {
+    Flow::Continue(())
+  }
+
+  /// Visits a function call.
+  fn visit_call(&mut self, call: &'ast Call, path: &mut NodePath<'ast>) -> Flow {
+    walk_call(self, call, path)
+  }
+
+  /// Visits a class declaration or expression.
+  fn visit_class(&mut self, class: &'ast Class, path: &mut NodePath<'ast>) -> Flow {
+    walk_class(self, class, path)
+  }
+
+  /// Visits an element of a class body.
+  fn visit_class_element(
+    &mut self,
+    element: &'ast ClassElement,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    walk_class_element(self, element, path)
+  }
+
+  /// Visits a conditional expression.
+  fn visit_conditional_op(&mut self, op: &'ast ConditionalOp, path: &mut NodePath<'ast>) -> Flow {
+    walk_conditional_op(self, op, path)
+  }
+
+  /// Visits a literal value.
+  fn visit_const(&mut self, _value: &'ast Const, _path: &mut NodePath<'ast>) -> Flow {
+    Flow::Continue(())
+  }
+
+  /// Visits a `continue` statement.
+  fn visit_continue(&mut self, _stmt: &'ast Continue, _path: &mut NodePath<'ast>) -> Flow {
+    Flow::Continue(())
+  }
+
+  /// Visits a `var`, `let` or `const` statement.
+  fn visit_declaration_list(
+    &mut self,
+    list: &'ast DeclarationList,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    walk_declaration_list(self, list, path)
+  }
+
+  /// Visits a single declaration of a `var`, `let` or `const` statement.
+  fn visit_declaration(&mut self, decl: &'ast Declaration, path: &mut NodePath<'ast>) -> Flow {
+    walk_declaration(self, decl, path)
+  }
+
+  /// Visits a `do ... while` loop.
+  fn visit_do_while_loop(&mut self, stmt: &'ast DoWhileLoop, path: &mut NodePath<'ast>) -> Flow {
+    walk_do_while_loop(self, stmt, path)
+  }
+
+  /// Visits an `export` declaration.
+  fn visit_export_decl(&mut self, decl: &'ast ExportDecl, path: &mut NodePath<'ast>) -> Flow {
+    walk_export_decl(self, decl, path)
+  }
+
+  /// Visits a name exported by an `export` list.
+  fn visit_export_specifier(
+    &mut self,
+    specifier: &'ast ExportSpecifier,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    walk_export_specifier(self, specifier, path)
+  }
+
+  /// Visits a `for ... in` loop.
+  fn visit_for_in_loop(&mut self, stmt: &'ast ForInLoop, path: &mut NodePath<'ast>) -> Flow {
+    walk_for_in_loop(self, stmt, path)
+  }
+
+  /// Visits a C-style `for` loop.
+  fn visit_for_loop(&mut self, stmt: &'ast ForLoop, path: &mut NodePath<'ast>) -> Flow {
+    walk_for_loop(self, stmt, path)
+  }
+
+  /// Visits a `for ... of` loop.
+  fn visit_for_of_loop(&mut self, stmt: &'ast ForOfLoop, path: &mut NodePath<'ast>) -> Flow {
+    walk_for_of_loop(self, stmt, path)
+  }
+
+  /// Visits a function parameter.
+  fn visit_formal_parameter(
+    &mut self,
+    parameter: &'ast FormalParameter,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    walk_formal_parameter(self, parameter, path)
+  }
+
+  /// Visits a function declaration.
+  fn visit_function_decl(&mut self, decl: &'ast FunctionDecl, path: &mut NodePath<'ast>) -> Flow {
+    walk_function_decl(self, decl, path)
+  }
+
+  /// Visits a function expression, a method, or a default exported function.
+  fn visit_function_expr(&mut self, expr: &'ast FunctionExpr, path: &mut NodePath<'ast>) -> Flow {
+    walk_function_expr(self, expr, path)
+  }
+
+  /// Visits a static property access.
+  fn visit_get_const_field(
+    &mut self,
+    access: &'ast GetConstField,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    walk_get_const_field(self, access, path)
+  }
+
+  /// Visits a computed property access.
+  fn visit_get_field(&mut self, access: &'ast GetField, path: &mut NodePath<'ast>) -> Flow {
+    walk_get_field(self, access, path)
+  }
+
+  /// Visits a private member access.
+  fn visit_get_private_field(
+    &mut self,
+    access: &'ast GetPrivateField,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    walk_get_private_field(self, access, path)
+  }
+
+  /// Visits an identifier, a reference or a binding in a pattern.
+  fn visit_identifier(&mut self, _ident: &'ast Identifier, _path: &mut NodePath<'ast>) -> Flow {
+    Flow::Continue(())
+  }
+
+  /// Visits an `if` statement.
+  fn visit_if(&mut self, stmt: &'ast If, path: &mut NodePath<'ast>) -> Flow {
+    walk_if(self, stmt, path)
+  }
+
+  /// Visits an `import` declaration.
+  fn visit_import_decl(&mut self, decl: &'ast ImportDecl, path: &mut NodePath<'ast>) -> Flow {
+    walk_import_decl(self, decl, path)
+  }
+
+  /// Visits a name imported by an `import` declaration.
+  fn visit_import_specifier(
+    &mut self,
+    specifier: &'ast ImportSpecifier,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    walk_import_specifier(self, specifier, path)
+  }
+
+  /// Visits a dynamic import.
+  fn visit_import_call(&mut self, call: &'ast ImportCall, path: &mut NodePath<'ast>) -> Flow {
+    walk_import_call(self, call, path)
+  }
+
+  /// Visits the left hand side of a `for ... in` or `for ... of` loop.
+  fn visit_iterable_loop_initializer(
+    &mut self,
+    init: &'ast IterableLoopInitializer,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    walk_iterable_loop_initializer(self, init, path)
+  }
+
+  /// Visits a labelled statement.
+  fn visit_labelled(&mut self, stmt: &'ast Labelled, path: &mut NodePath<'ast>) -> Flow {
+    walk_labelled(self, stmt, path)
+  }
+
+  /// Visits a `new` expression.
+  fn visit_new(&mut self, expr: &'ast New, path: &mut NodePath<'ast>) -> Flow {
+    walk_new(self, expr, path)
+  }
+
+  /// Visits an object literal.
+  fn visit_object(&mut self, object: &'ast Object, path: &mut NodePath<'ast>) -> Flow {
+    walk_object(self, object, path)
+  }
+
+  /// Visits a property of an object literal.
+  fn visit_property_definition(
+    &mut self,
+    property: &'ast PropertyDefinition,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    walk_property_definition(self, property, path)
+  }
+
+  /// Visits the name of a property, a method or a class element.
+  fn visit_property_name(&mut self, name: &'ast PropertyName, path: &mut NodePath<'ast>) -> Flow {
+    walk_property_name(self, name, path)
+  }
+
+  /// Visits an optional chain.
+  fn visit_optional_chain(
+    &mut self,
+    chain: &'ast OptionalChain,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    walk_optional_chain(self, chain, path)
+  }
+
+  /// Visits a private brand check.
+  fn visit_private_in(&mut self, expr: &'ast PrivateIn, path: &mut NodePath<'ast>) -> Flow {
+    walk_private_in(self, expr, path)
+  }
+
+  /// Visits a `return` statement.
+  fn visit_return(&mut self, stmt: &'ast Return, path: &mut NodePath<'ast>) -> Flow {
+    walk_return(self, stmt, path)
+  }
+
+  /// Visits a spread element.
+  fn visit_spread(&mut self, spread: &'ast Spread, path: &mut NodePath<'ast>) -> Flow {
+    walk_spread(self, spread, path)
+  }
+
+  /// Visits a `switch` statement.
+  fn visit_switch(&mut self, stmt: &'ast Switch, path: &mut NodePath<'ast>) -> Flow {
+    walk_switch(self, stmt, path)
+  }
+
+  /// Visits a tagged template.
+  fn visit_tagged_template(
+    &mut self,
+    expr: &'ast TaggedTemplate,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    walk_tagged_template(self, expr, path)
+  }
+
+  /// Visits a template literal.
+  fn visit_template_lit(&mut self, template: &'ast TemplateLit, path: &mut NodePath<'ast>) -> Flow {
+    walk_template_lit(self, template, path)
+  }
+
+  /// Visits a clause of a `switch` statement.
+  fn visit_case(&mut self, case: &'ast Case, path: &mut NodePath<'ast>) -> Flow {
+    walk_case(self, case, path)
+  }
+
+  /// Visits a `throw` statement.
+  fn visit_throw(&mut self, stmt: &'ast Throw, path: &mut NodePath<'ast>) -> Flow {
+    walk_throw(self, stmt, path)
+  }
+
+  /// Visits a `try` statement.
+  fn visit_try(&mut self, stmt: &'ast Try, path: &mut NodePath<'ast>) -> Flow {
+    walk_try(self, stmt, path)
+  }
+
+  /// Visits the `catch` clause of a `try` statement.
+  fn visit_catch(&mut self, catch: &'ast Catch, path: &mut NodePath<'ast>) -> Flow {
+    walk_catch(self, catch, path)
+  }
+
+  /// Visits a unary or update operation.
+  fn visit_unary_op(&mut self, op: &'ast UnaryOp, path: &mut NodePath<'ast>) -> Flow {
+    walk_unary_op(self, op, path)
+  }
+
+  /// Visits a `while` loop.
+  fn visit_while_loop(&mut self, stmt: &'ast WhileLoop, path: &mut NodePath<'ast>) -> Flow {
+    walk_while_loop(self, stmt, path)
+  }
+
+  /// Visits a `with` statement.
+  fn visit_with(&mut self, stmt: &'ast With, path: &mut NodePath<'ast>) -> Flow {
+    walk_with(self, stmt, path)
+  }
+
+  /// Visits a `yield` expression.
+  fn visit_yield(&mut self, expr: &'ast Yield, path: &mut NodePath<'ast>) -> Flow {
+    walk_yield(self, expr, path)
+  }
+
+  /// Visits a binding or assignment pattern.
+  fn visit_pattern(&mut self, pattern: &'ast Pattern, path: &mut NodePath<'ast>) -> Flow {
+    walk_pattern(self, pattern, path)
+  }
+
+  /// Visits a pattern with an optional default value.
+  fn visit_pattern_element(
+    &mut self,
+    element: &'ast PatternElement,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    walk_pattern_element(self, element, path)
+  }
+
+  /// Visits an object pattern.
+  fn visit_object_pattern(
+    &mut self,
+    pattern: &'ast ObjectPattern,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    walk_object_pattern(self, pattern, path)
+  }
+
+  /// Visits a property of an object pattern.
+  fn visit_property_pattern(
+    &mut self,
+    property: &'ast PropertyPattern,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    walk_property_pattern(self, property, path)
+  }
+
+  /// Visits an array pattern.
+  fn visit_array_pattern(
+    &mut self,
+    pattern: &'ast ArrayPattern,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    walk_array_pattern(self, pattern, path)
+  }
+}
+
+/// Calls the hooks around the children of a part of the tree.
+fn hooked<'ast, V, F>(
+  visitor: &mut V,
+  node: NodeRef<'ast>,
+  path: &mut NodePath<'ast>,
+  children: F,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+  F: FnOnce(&mut V, &mut NodePath<'ast>) -> Flow,
+{
+  match visitor.enter(node, path) {
+    Control::Stop => return Flow::Break(()),
+    Control::SkipChildren => {}
+    Control::Continue => {
+      path.push(node);
+      let flow = children(visitor, path);
+      path.pop();
+      flow?;
+    }
+  }
+  visitor.exit(node, path).after_exit()
+}
+
+/// Visits the nodes of a list, stopping at the first that stops the traversal.
+fn each<'ast, V, T>(
+  visitor: &mut V,
+  items: &'ast [T],
+  path: &mut NodePath<'ast>,
+  mut visit: impl FnMut(&mut V, &'ast T, &mut NodePath<'ast>) -> Flow,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  for item in items {
+    visit(visitor, item, path)?;
+  }
+  Flow::Continue(())
+}
+
+/// Visits an optional node.
+fn optional<'ast, V>(visitor: &mut V, node: Option<&'ast Node>, path: &mut NodePath<'ast>) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  match node {
+    Some(node) => visitor.visit_node(node, path),
+    None => Flow::Continue(()),
+  }
+}
+
+/// Visits the statements of a list.
+pub fn walk_statements<'ast, V>(
+  visitor: &mut V,
+  statements: &'ast [Node],
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  each(visitor, statements, path, V::visit_node)
+}
+
+/// Calls the hooks around a node, and visits its children with the method of its variant.
+pub fn walk_node<'ast, V>(visitor: &mut V, node: &'ast Node, path: &mut NodePath<'ast>) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  hooked(
+    visitor,
+    NodeRef::Node(node),
+    path,
+    |visitor, path| match node {
+      Node::ArrayDecl(array) => visitor.visit_array_decl(array, path),
+      Node::ArrowFunctionDecl(arrow) => visitor.visit_arrow_function_decl(arrow, path),
+      Node::Assign(assign) => visitor.visit_assign(assign, path),
+      Node::BinOp(op) => visitor.visit_bin_op(op, path),
+      Node::Await(expr) => visitor.visit_await(expr, path),
+      Node::Block(block) => visitor.visit_block(block, path),
+      Node::Break(stmt) => visitor.visit_break(stmt, path),
+      Node::Call(call) => visitor.visit_call(call, path),
+      Node::ClassDecl(class) | Node::ClassExpr(class) => visitor.visit_class(class, path),
+      Node::ConditionalOp(op) => visitor.visit_conditional_op(op, path),
+      Node::Const(value, _) => visitor.visit_const(value, path),
+      Node::Continue(stmt) => visitor.visit_continue(stmt, path),
+      Node::DeclarationList(list, _) => visitor.visit_declaration_list(list, path),
+      Node::DoWhileLoop(stmt) => visitor.visit_do_while_loop(stmt, path),
+      Node::Export(decl, _) => visitor.visit_export_decl(decl, path),
+      Node::ForInLoop(stmt) => visitor.visit_for_in_loop(stmt, path),
+      Node::ForLoop(stmt) => visitor.visit_for_loop(stmt, path),
+      Node::ForOfLoop(stmt) => visitor.visit_for_of_loop(stmt, path),
+      Node::FunctionDecl(decl) => visitor.visit_function_decl(decl, path),
+      Node::FunctionExpr(expr) => visitor.visit_function_expr(expr, path),
+      Node::GetConstField(access) => visitor.visit_get_const_field(access, path),
+      Node::GetField(access) => visitor.visit_get_field(access, path),
+      Node::GetPrivateField(access) => visitor.visit_get_private_field(access, path),
+      Node::Identifier(ident) => visitor.visit_identifier(ident, path),
+      Node::If(stmt) => visitor.visit_if(stmt, path),
+      Node::Import(decl) => visitor.visit_import_decl(decl, path),
+      Node::ImportCall(call) => visitor.visit_import_call(call, path),
+      Node::Labelled(stmt) => visitor.visit_labelled(stmt, path),
+      Node::New(expr) => visitor.visit_new(expr, path),
+      Node::Object(object) => visitor.visit_object(object, path),
+      Node::OptionalChain(chain) => visitor.visit_optional_chain(chain, path),
+      Node::PrivateIn(expr) => visitor.visit_private_in(expr, path),
+      Node::Return(stmt) => visitor.visit_return(stmt, path),
+      Node::Spread(spread) => visitor.visit_spread(spread, path),
+      Node::Switch(stmt) => visitor.visit_switch(stmt, path),
+      Node::TaggedTemplate(expr) => visitor.visit_tagged_template(expr, path),
+      Node::TemplateLit(template) => visitor.visit_template_lit(template, path),
+      Node::Throw(stmt) => visitor.visit_throw(stmt, path),
+      Node::Try(stmt) => visitor.visit_try(stmt, path),
+      Node::UnaryOp(op) => visitor.visit_unary_op(op, path),
+      Node::WhileLoop(stmt) => visitor.visit_while_loop(stmt, path),
+      Node::With(stmt) => visitor.visit_with(stmt, path),
+      Node::Yield(expr) => visitor.visit_yield(expr, path),
+      Node::Debugger(_)
+      | Node::Directive(_)
+      | Node::Empty(_)
+      | Node::Error(_)
+      | Node::ImportMeta(_)
+      | Node::Super(_)
+      | Node::This(_) => Flow::Continue(()),
+    },
+  )
+}
+
+/// Visits the elements of an array literal.
+pub fn walk_array_decl<'ast, V>(
+  visitor: &mut V,
+  array: &'ast ArrayDecl,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  each(visitor, array.elements(), path, V::visit_node)
+}
+
+/// Visits the parameters and the body of an arrow function.
+pub fn walk_arrow_function_decl<'ast, V>(
+  visitor: &mut V,
+  arrow: &'ast ArrowFunctionDecl,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  each(visitor, arrow.params(), path, V::visit_formal_parameter)?;
+  visitor.visit_statements(arrow.body(), path)
+}
+
+/// Visits the target and the value of an assignment.
+pub fn walk_assign<'ast, V>(
+  visitor: &mut V,
+  assign: &'ast Assign,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_pattern(assign.lhs(), path)?;
+  visitor.visit_node(assign.rhs(), path)
+}
+
+/// Visits the operands of a binary operation.
+pub fn walk_bin_op<'ast, V>(visitor: &mut V, op: &'ast BinOp, path: &mut NodePath<'ast>) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_node(op.lhs(), path)?;
+  visitor.visit_node(op.rhs(), path)
+}
+
+/// Visits the operand of an `await` expression.
+pub fn walk_await<'ast, V>(visitor: &mut V, expr: &'ast Await, path: &mut NodePath<'ast>) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_node(expr.expr(), path)
+}
+
+/// Visits the statements of a block.
+pub fn walk_block<'ast, V>(visitor: &mut V, block: &'ast Block, path: &mut NodePath<'ast>) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_statements(block.statements(), path)
+}
+
+/// Visits the callee and the arguments of a call.
+pub fn walk_call<'ast, V>(visitor: &mut V, call: &'ast Call, path: &mut NodePath<'ast>) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_node(call.expr(), path)?;
+  each(visitor, call.args(), path, V::visit_node)
+}
+
+/// Visits the superclass, the constructor and the elements of a class.
+pub fn walk_class<'ast, V>(visitor: &mut V, class: &'ast Class, path: &mut NodePath<'ast>) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  optional(visitor, class.super_ref(), path)?;
+  if let Some(constructor) = class.constructor() {
+    visitor.visit_function_expr(constructor, path)?;
+  }
+  each(visitor, class.elements(), path, V::visit_class_element)
+}
+
+/// Calls the hooks around a class element, and visits its name and its value.
+pub fn walk_class_element<'ast, V>(
+  visitor: &mut V,
+  element: &'ast ClassElement,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  hooked(
+    visitor,
+    NodeRef::ClassElement(element),
+    path,
+    |visitor, path| {
+      if let Some(ClassElementName::PropertyName(name)) = element.name() {
+        visitor.visit_property_name(name, path)?;
+      }
+      match element {
+        ClassElement::Method { function, .. } => visitor.visit_function_expr(function, path),
+        ClassElement::Field { init, .. } => optional(visitor, init.as_ref(), path),
+        ClassElement::StaticBlock(body, _) => visitor.visit_statements(body.items(), path),
+      }
+    },
+  )
+}
+
+/// Visits the condition and the branches of a conditional expression.
+pub fn walk_conditional_op<'ast, V>(
+  visitor: &mut V,
+  op: &'ast ConditionalOp,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_node(op.cond(), path)?;
+  visitor.visit_node(op.if_true(), path)?;
+  visitor.visit_node(op.if_false(), path)
+}
+
+/// Visits the declarations of a `var`, `let` or `const` statement.
+pub fn walk_declaration_list<'ast, V>(
+  visitor: &mut V,
+  list: &'ast DeclarationList,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  each(visitor, list.as_ref(), path, V::visit_declaration)
+}
+
+/// Calls the hooks around a declaration, and visits its target and its initializer.
+pub fn walk_declaration<'ast, V>(
+  visitor: &mut V,
+  decl: &'ast Declaration,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  hooked(
+    visitor,
+    NodeRef::Declaration(decl),
+    path,
+    |visitor, path| {
+      visitor.visit_pattern(decl.target(), path)?;
+      optional(visitor, decl.init(), path)
+    },
+  )
+}
+
+/// Visits the body and the condition of a `do ... while` loop.
+pub fn walk_do_while_loop<'ast, V>(
+  visitor: &mut V,
+  stmt: &'ast DoWhileLoop,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_node(stmt.body(), path)?;
+  visitor.visit_node(stmt.cond(), path)
+}
+
+/// Visits the specifiers or the declaration of an `export` declaration.
+pub fn walk_export_decl<'ast, V>(
+  visitor: &mut V,
+  decl: &'ast ExportDecl,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  match decl {
+    ExportDecl::Named { specifiers, .. } => {
+      each(visitor, specifiers, path, V::visit_export_specifier)
+    }
+    ExportDecl::All { .. } => Flow::Continue(()),
+    ExportDecl::Declaration(node) | ExportDecl::DefaultExpression(node) => {
+      visitor.visit_node(node, path)
+    }
+    ExportDecl::DefaultFunction(function) => visitor.visit_function_expr(function, path),
+    ExportDecl::DefaultClass(class) => visitor.visit_class(class, path),
+  }
+}
+
+/// Calls the hooks around an export specifier.
+pub fn walk_export_specifier<'ast, V>(
+  visitor: &mut V,
+  specifier: &'ast ExportSpecifier,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  hooked(
+    visitor,
+    NodeRef::ExportSpecifier(specifier),
+    path,
+    |_, _| Flow::Continue(()),
+  )
+}
+
+/// Visits the target, the object and the body of a `for ... in` loop.
+pub fn walk_for_in_loop<'ast, V>(
+  visitor: &mut V,
+  stmt: &'ast ForInLoop,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_iterable_loop_initializer(stmt.init(), path)?;
+  visitor.visit_node(stmt.expr(), path)?;
+  visitor.visit_node(stmt.body(), path)
+}
+
+/// Visits the initializer, the condition, the update and the body of a `for` loop.
+pub fn walk_for_loop<'ast, V>(
+  visitor: &mut V,
+  stmt: &'ast ForLoop,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  optional(visitor, stmt.init(), path)?;
+  optional(visitor, stmt.condition(), path)?;
+  optional(visitor, stmt.final_expr(), path)?;
+  visitor.visit_node(stmt.body(), path)
+}
+
+/// Visits the target, the iterable and the body of a `for ... of` loop.
+pub fn walk_for_of_loop<'ast, V>(
+  visitor: &mut V,
+  stmt: &'ast ForOfLoop,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_iterable_loop_initializer(stmt.init(), path)?;
+  visitor.visit_node(stmt.iterable(), path)?;
+  visitor.visit_node(stmt.body(), path)
+}
+
+/// Calls the hooks around a parameter, and visits its target and its default value.
+pub fn walk_formal_parameter<'ast, V>(
+  visitor: &mut V,
+  parameter: &'ast FormalParameter,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  hooked(
+    visitor,
+    NodeRef::FormalParameter(parameter),
+    path,
+    |visitor, path| {
+      visitor.visit_pattern(parameter.target(), path)?;
+      optional(visitor, parameter.init(), path)
+    },
+  )
+}
+
+/// Visits the parameters and the body of a function declaration.
+pub fn walk_function_decl<'ast, V>(
+  visitor: &mut V,
+  decl: &'ast FunctionDecl,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  each(visitor, decl.parameters(), path, V::visit_formal_parameter)?;
+  visitor.visit_statements(decl.body(), path)
+}
+
+/// Visits the parameters and the body of a function expression.
+pub fn walk_function_expr<'ast, V>(
+  visitor: &mut V,
+  expr: &'ast FunctionExpr,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  each(visitor, expr.parameters(), path, V::visit_formal_parameter)?;
+  visitor.visit_statements(expr.body(), path)
+}
+
+/// Visits the object of a static property access.
+pub fn walk_get_const_field<'ast, V>(
+  visitor: &mut V,
+  access: &'ast GetConstField,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_node(access.obj(), path)
+}
+
+/// Visits the object and the key of a computed property access.
+pub fn walk_get_field<'ast, V>(
+  visitor: &mut V,
+  access: &'ast GetField,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_node(access.obj(), path)?;
+  visitor.visit_node(access.field(), path)
+}
+
+/// Visits the object of a private member access.
+pub fn walk_get_private_field<'ast, V>(
+  visitor: &mut V,
+  access: &'ast GetPrivateField,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_node(access.obj(), path)
+}
+
+/// Visits the condition and the branches of an `if` statement.
+pub fn walk_if<'ast, V>(visitor: &mut V, stmt: &'ast If, path: &mut NodePath<'ast>) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_node(stmt.cond(), path)?;
+  visitor.visit_node(stmt.body(), path)?;
+  optional(visitor, stmt.else_node(), path)
+}
+
+/// Visits the specifiers of an `import` declaration.
+pub fn walk_import_decl<'ast, V>(
+  visitor: &mut V,
+  decl: &'ast ImportDecl,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  each(visitor, decl.specifiers(), path, V::visit_import_specifier)
+}
+
+/// Calls the hooks around an import specifier.
+pub fn walk_import_specifier<'ast, V>(
+  visitor: &mut V,
+  specifier: &'ast ImportSpecifier,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  hooked(
+    visitor,
+    NodeRef::ImportSpecifier(specifier),
+    path,
+    |_, _| Flow::Continue(()),
+  )
+}
+
+/// Visits the specifier and the options of a dynamic import.
+pub fn walk_import_call<'ast, V>(
+  visitor: &mut V,
+  call: &'ast ImportCall,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_node(call.specifier(), path)?;
+  optional(visitor, call.options(), path)
+}
+
+/// Visits the pattern of the left hand side of a loop.
+pub fn walk_iterable_loop_initializer<'ast, V>(
+  visitor: &mut V,
+  init: &'ast IterableLoopInitializer,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_pattern(init.pattern(), path)
+}
+
+/// Visits the statement of a labelled statement.
+pub fn walk_labelled<'ast, V>(
+  visitor: &mut V,
+  stmt: &'ast Labelled,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_node(stmt.statement(), path)
+}
+
+/// Visits the constructor and the arguments of a `new` expression.
+pub fn walk_new<'ast, V>(visitor: &mut V, expr: &'ast New, path: &mut NodePath<'ast>) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_node(expr.expr(), path)?;
+  each(visitor, expr.args(), path, V::visit_node)
+}
+
+/// Visits the properties of an object literal.
+pub fn walk_object<'ast, V>(
+  visitor: &mut V,
+  object: &'ast Object,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  each(
+    visitor,
+    object.properties(),
+    path,
+    V::visit_property_definition,
+  )
+}
+
+/// Calls the hooks around a property of an object literal, and visits its name and its value.
+pub fn walk_property_definition<'ast, V>(
+  visitor: &mut V,
+  property: &'ast PropertyDefinition,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  hooked(
+    visitor,
+    NodeRef::PropertyDefinition(property),
+    path,
+    |visitor, path| match property {
+      PropertyDefinition::IdentifierReference(_, _) => Flow::Continue(()),
+      PropertyDefinition::Property(name, value, _) => {
+        visitor.visit_property_name(name, path)?;
+        visitor.visit_node(value, path)
+      }
+      PropertyDefinition::MethodDefinition(_, name, function, _) => {
+        visitor.visit_property_name(name, path)?;
+        visitor.visit_function_expr(function, path)
+      }
+      PropertyDefinition::SpreadObject(value, _) => visitor.visit_node(value, path),
+    },
+  )
+}
+
+/// Visits the expression of a computed property name.
+pub fn walk_property_name<'ast, V>(
+  visitor: &mut V,
+  name: &'ast PropertyName,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  match name {
+    PropertyName::Literal(_) => Flow::Continue(()),
+    PropertyName::Computed(expr) => visitor.visit_node(expr, path),
+  }
+}
+
+/// Visits the expression of an optional chain.
+pub fn walk_optional_chain<'ast, V>(
+  visitor: &mut V,
+  chain: &'ast OptionalChain,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_node(chain.expr(), path)
+}
+
+/// Visits the object of a private brand check.
+pub fn walk_private_in<'ast, V>(
+  visitor: &mut V,
+  expr: &'ast PrivateIn,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_node(expr.target(), path)
+}
+
+/// Visits the value of a `return` statement.
+pub fn walk_return<'ast, V>(visitor: &mut V, stmt: &'ast Return, path: &mut NodePath<'ast>) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  optional(visitor, stmt.expr(), path)
+}
+
+/// Visits the operand of a spread element.
+pub fn walk_spread<'ast, V>(
+  visitor: &mut V,
+  spread: &'ast Spread,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_node(spread.val(), path)
+}
+
+/// Visits the discriminant and the clauses of a `switch` statement.
+pub fn walk_switch<'ast, V>(visitor: &mut V, stmt: &'ast Switch, path: &mut NodePath<'ast>) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_node(stmt.val(), path)?;
+  each(visitor, stmt.cases(), path, V::visit_case)
+}
+
+/// Visits the tag and the substitutions of a tagged template.
+pub fn walk_tagged_template<'ast, V>(
+  visitor: &mut V,
+  expr: &'ast TaggedTemplate,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_node(expr.tag(), path)?;
+  visitor.visit_template_lit(expr.template(), path)
+}
+
+/// Visits the substitutions of a template literal.
+pub fn walk_template_lit<'ast, V>(
+  visitor: &mut V,
+  template: &'ast TemplateLit,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  each(visitor, template.exprs(), path, V::visit_node)
+}
+
+/// Calls the hooks around a `switch` clause, and visits its test and its statements.
+pub fn walk_case<'ast, V>(visitor: &mut V, case: &'ast Case, path: &mut NodePath<'ast>) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  hooked(visitor, NodeRef::Case(case), path, |visitor, path| {
+    optional(visitor, case.condition(), path)?;
+    visitor.visit_statements(case.body(), path)
+  })
+}
+
+/// Visits the value of a `throw` statement.
+pub fn walk_throw<'ast, V>(visitor: &mut V, stmt: &'ast Throw, path: &mut NodePath<'ast>) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_node(stmt.expr(), path)
+}
+
+/// Visits the blocks and the `catch` clause of a `try` statement.
+pub fn walk_try<'ast, V>(visitor: &mut V, stmt: &'ast Try, path: &mut NodePath<'ast>) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_block(stmt.block(), path)?;
+  if let Some(catch) = stmt.catch() {
+    visitor.visit_catch(catch, path)?;
+  }
+  match stmt.finally() {
+    Some(finally) => visitor.visit_block(finally, path),
+    None => Flow::Continue(()),
+  }
+}
+
+/// Calls the hooks around a `catch` clause, and visits its parameter and its block.
+pub fn walk_catch<'ast, V>(visitor: &mut V, catch: &'ast Catch, path: &mut NodePath<'ast>) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  hooked(visitor, NodeRef::Catch(catch), path, |visitor, path| {
+    if let Some(parameter) = catch.parameter() {
+      visitor.visit_pattern(parameter, path)?;
+    }
+    visitor.visit_block(catch.block(), path)
+  })
+}
+
+/// Visits the operand of a unary operation.
+pub fn walk_unary_op<'ast, V>(visitor: &mut V, op: &'ast UnaryOp, path: &mut NodePath<'ast>) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_node(op.target(), path)
+}
+
+/// Visits the condition and the body of a `while` loop.
+pub fn walk_while_loop<'ast, V>(
+  visitor: &mut V,
+  stmt: &'ast WhileLoop,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_node(stmt.cond(), path)?;
+  visitor.visit_node(stmt.body(), path)
+}
+
+/// Visits the object and the body of a `with` statement.
+pub fn walk_with<'ast, V>(visitor: &mut V, stmt: &'ast With, path: &mut NodePath<'ast>) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_node(stmt.object(), path)?;
+  visitor.visit_node(stmt.body(), path)
+}
+
+/// Visits the operand of a `yield` expression.
+pub fn walk_yield<'ast, V>(visitor: &mut V, expr: &'ast Yield, path: &mut NodePath<'ast>) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  optional(visitor, expr.expr(), path)
+}
+
+/// Calls the hooks around a pattern, and visits its parts.
+pub fn walk_pattern<'ast, V>(
+  visitor: &mut V,
+  pattern: &'ast Pattern,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  hooked(
+    visitor,
+    NodeRef::Pattern(pattern),
+    path,
+    |visitor, path| match pattern {
+      Pattern::Identifier(ident) => visitor.visit_identifier(ident, path),
+      Pattern::Object(object) => visitor.visit_object_pattern(object, path),
+      Pattern::Array(array) => visitor.visit_array_pattern(array, path),
+      Pattern::Member(member) => visitor.visit_node(member, path),
+    },
+  )
+}
+
+/// Visits the target and the default value of a pattern element.
+pub fn walk_pattern_element<'ast, V>(
+  visitor: &mut V,
+  element: &'ast PatternElement,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_pattern(element.target(), path)?;
+  optional(visitor, element.default(), path)
+}
+
+/// Visits the properties and the rest element of an object pattern.
+pub fn walk_object_pattern<'ast, V>(
+  visitor: &mut V,
+  pattern: &'ast ObjectPattern,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  each(
+    visitor,
+    pattern.properties(),
+    path,
+    V::visit_property_pattern,
+  )?;
+  match pattern.rest() {
+    Some(rest) => visitor.visit_pattern(rest, path),
+    None => Flow::Continue(()),
+  }
+}
+
+/// Visits the key and the value of a property of an object pattern.
+pub fn walk_property_pattern<'ast, V>(
+  visitor: &mut V,
+  property: &'ast PropertyPattern,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  visitor.visit_property_name(property.key(), path)?;
+  visitor.visit_pattern_element(property.value(), path)
+}
+
+/// Visits the elements and the rest element of an array pattern.
+pub fn walk_array_pattern<'ast, V>(
+  visitor: &mut V,
+  pattern: &'ast ArrayPattern,
+  path: &mut NodePath<'ast>,
+) -> Flow
+where
+  V: Visitor<'ast> + ?Sized,
+{
+  for element in pattern.elements().iter().flatten() {
+    visitor.visit_pattern_element(element, path)?;
+  }
+  match pattern.rest() {
+    Some(rest) => visitor.visit_pattern(rest, path),
+    None => Flow::Continue(()),
+  }
+}
@@ -0,0 +1,1124 @@
+//! Traversal of the syntax tree by mutable reference.
+//!
+//! Each `walk_*` function visits the children of a node with the visitor, it is the default
+//! implementation of the matching [`VisitorMut`] method.
+
+use super::{Control, Flow, KindPath, NodeMut};
+use crate::syntax::ast::{
+  node::{
+    ArrayDecl, ArrayPattern, ArrowFunctionDecl, Assign, Await, BinOp, Block, Break, Call, Case,
+    Catch, Class, ClassElement, ClassElementName, ConditionalOp, Continue, Declaration,
+    DeclarationList, DoWhileLoop, ExportDecl, ExportSpecifier, ForInLoop, ForLoop, ForOfLoop,
+    FormalParameter, FunctionDecl, FunctionExpr, GetConstField, GetField, GetPrivateField,
+    Identifier, If, ImportCall, ImportDecl, ImportSpecifier, IterableLoopInitializer, Labelled,
+    New, Object, ObjectPattern, OptionalChain, Pattern, PatternElement, PrivateIn,
+    PropertyDefinition, PropertyName, PropertyPattern, Return, Spread, Switch, TaggedTemplate,
+    TemplateLit, Throw, Try, UnaryOp, WhileLoop, With, Yield,
+  },
+  Const, Node,
+};
+
+/// A traversal of the syntax tree by mutable reference.
+///
+/// See the [module documentation](super) for how the methods fit together.
+pub trait VisitorMut {
+  /// Called before visiting the children of a node, see [`NodeMut`] for the parts of the tree
+  /// this is called for.
+  fn enter(&mut self, _node: NodeMut<'_>, _path: &KindPath) -> Control {
+    Control::Continue
+  }
+
+  /// Called after visiting the children of a node.
+  fn exit(&mut self, _node: NodeMut<'_>, _path: &KindPath) -> Control {
+    Control::Continue
+  }
+
+  /// Visits a list of statements, such as a script or a function body.
+  fn visit_statements(&mut self, statements: &mut [Node], path: &mut KindPath) -> Flow {
+    walk_statements(self, statements, path)
+  }
+
+  /// Visits an expression or a statement.
+  fn visit_node(&mut self, node: &mut Node, path: &mut KindPath) -> Flow {
+    walk_node(self, node, path)
+  }
+
+  /// Visits an array literal.
+  fn visit_array_decl(&mut self, array: &mut ArrayDecl, path: &mut KindPath) -> Flow {
+    walk_array_decl(self, array, path)
+  }
+
+  /// Visits an arrow function.
+  fn visit_arrow_function_decl(
+    &mut self,
+    arrow: &mut ArrowFunctionDecl,
+    path: &mut KindPath,
+  ) -> Flow {
+    walk_arrow_function_decl(self, arrow, path)
+  }
+
+  /// Visits a plain assignment.
+  fn visit_assign(&mut self, assign: &mut Assign, path: &mut KindPath) -> Flow {
+    walk_assign(self, assign, path)
+  }
+
+  /// Visits a binary or compound assignment operation.
+  fn visit_bin_op(&mut self, op: &mut BinOp, path: &mut KindPath) -> Flow {
+    walk_bin_op(self, op, path)
+  }
+
+  /// Visits an `await` expression.
+  fn visit_await(&mut self, expr: &mut Await, path: &mut KindPath) -> Flow {
+    walk_await(self, expr, path)
+  }
+
+  /// Visits a block statement, or the block of a `try` statement.
+  fn visit_block(&mut self, block: &mut Block, path: &mut KindPath) -> Flow {
+    walk_block(self, block, path)
+  }
+
+  /// Visits a `break` statement.
+  fn visit_break(&mut self, _stmt: &mut Break, _path: &mut KindPath) -> Flow {
+    Flow::Continue(())
+  }
+
+  /// Visits a function call.
+  fn visit_call(&mut self, call: &mut Call, path: &mut KindPath) -> Flow {
+    walk_call(self, call, path)
+  }
+
+  /// Visits a class declaration or expression.
+  fn visit_class(&mut self, class: &mut Class, path: &mut KindPath) -> Flow {
+    walk_class(self, class, path)
+  }
+
+  /// Visits an element of a class body.
+  fn visit_class_element(&mut self, element: &mut ClassElement, path: &mut KindPath) -> Flow {
+    walk_class_element(self, element, path)
+  }
+
+  /// Visits a conditional expression.
+  fn visit_conditional_op(&mut self, op: &mut ConditionalOp, path: &mut KindPath) -> Flow {
+    walk_conditional_op(self, op, path)
+  }
+
+  /// Visits a literal value.
+  fn visit_const(&mut self, _value: &mut Const, _path: &mut KindPath) -> Flow {
+    Flow::Continue(())
+  }
+
+  /// Visits a `continue` statement.
+  fn visit_continue(&mut self, _stmt: &mut Continue, _path: &mut KindPath) -> Flow {
+    Flow::Continue(())
+  }
+
+  /// Visits a `var`, `let` or `const` statement.
+  fn visit_declaration_list(&mut self, list: &mut DeclarationList, path: &mut KindPath) -> Flow {
+    walk_declaration_list(self, list, path)
+  }
+
+  /// Visits a single declaration of a `var`, `let` or `const` statement.
+  fn visit_declaration(&mut self, decl: &mut Declaration, path: &mut KindPath) -> Flow {
+    walk_declaration(self, decl, path)
+  }
+
+  /// Visits a `do ... while` loop.
+  fn visit_do_while_loop(&mut self, stmt: &mut DoWhileLoop, path: &mut KindPath) -> Flow {
+    walk_do_while_loop(self, stmt, path)
+  }
+
+  /// Visits an `export` declaration.
+  fn visit_export_decl(&mut self, decl: &mut ExportDecl, path: &mut KindPath) -> Flow {
+    walk_export_decl(self, decl, path)
+  }
+
+  /// Visits a name exported by an `export` list.
+  fn visit_export_specifier(
+    &mut self,
+    specifier: &mut ExportSpecifier,
+    path: &mut KindPath,
+  ) -> Flow {
+    walk_export_specifier(self, specifier, path)
+  }
+
+  /// Visits a `for ... in` loop.
+  fn visit_for_in_loop(&mut self, stmt: &mut ForInLoop, path: &mut KindPath) -> Flow {
+    walk_for_in_loop(self, stmt, path)
+  }
+
+  /// Visits a C-style `for` loop.
+  fn visit_for_loop(&mut self, stmt: &mut ForLoop, path: &mut KindPath) -> Flow {
+    walk_for_loop(self, stmt, path)
+  }
+
+  /// Visits a `for ... of` loop.
+  fn visit_for_of_loop(&mut self, stmt: &mut ForOfLoop, path: &mut KindPath) -> Flow {
+    walk_for_of_loop(self, stmt, path)
+  }
+
+  /// Visits a function parameter.
+  fn visit_formal_parameter(
+    &mut self,
+    parameter: &mut FormalParameter,
+    path: &mut KindPath,
+  ) -> Flow {
+    walk_formal_parameter(self, parameter, path)
+  }
+
+  /// Visits a function declaration.
+  fn visit_function_decl(&mut self, decl: &mut FunctionDecl, path: &mut KindPath) -> Flow {
+    walk_function_decl(self, decl, path)
+  }
+
+  /// Visits a function expression, a method, or a default exported function.
+  fn visit_function_expr(&mut self, expr: &mut FunctionExpr, path: &mut KindPath) -> Flow {
+    walk_function_expr(self, expr, path)
+  }
+
+  /// Visits a static property access.
+  fn visit_get_const_field(&mut self, access: &mut GetConstField, path: &mut KindPath) -> Flow {
+    walk_get_const_field(self, access, path)
+  }
+
+  /// Visits a computed property access.
+  fn visit_get_field(&mut self, access: &mut GetField, path: &mut KindPath) -> Flow {
+    walk_get_field(self, access, path)
+  }
+
+  /// Visits a private member access.
+  fn visit_get_private_field(&mut self, access: &mut GetPrivateField, path: &mut KindPath) -> Flow {
+    walk_get_private_field(self, access, path)
+  }
+
+  /// Visits an identifier, a reference or a binding in a pattern.
+  fn visit_identifier(&mut self, _ident: &mut Identifier, _path: &mut KindPath) -> Flow {
+    Flow::Continue(())
+  }
+
+  /// Visits an `if` statement.
+  fn visit_if(&mut self, stmt: &mut If, path: &mut KindPath) -> Flow {
+    walk_if(self, stmt, path)
+  }
+
+  /// Visits an `import` declaration.
+  fn visit_import_decl(&mut self, decl: &mut ImportDecl, path: &mut KindPath) -> Flow {
+    walk_import_decl(self, decl, path)
+  }
+
+  /// Visits a name imported by an `import` declaration.
+  fn visit_import_specifier(
+    &mut self,
+    specifier: &mut ImportSpecifier,
+    path: &mut KindPath,
+  ) -> Flow {
+    walk_import_specifier(self, specifier, path)
+  }
+
+  /// Visits a dynamic import.
+  fn visit_import_call(&mut self, call: &mut ImportCall, path: &mut KindPath) -> Flow {
+    walk_import_call(self, call, path)
+  }
+
+  /// Visits the left hand side of a `for ... in` or `for ... of` loop.
+  fn visit_iterable_loop_initializer(
+    &mut self,
+    init: &mut IterableLoopInitializer,
+    path: &mut KindPath,
+  ) -> Flow {
+    walk_iterable_loop_initializer(self, init, path)
+  }
+
+  /// Visits a labelled statement.
+  fn visit_labelled(&mut self, stmt: &mut Labelled, path: &mut KindPath) -> Flow {
+    walk_labelled(self, stmt, path)
+  }
+
+  /// Visits a `new` expression.
+  fn visit_new(&mut self, expr: &mut New, path: &mut KindPath) -> Flow {
+    walk_new(self, expr, path)
+  }
+
+  /// Visits an object literal.
+  fn visit_object(&mut self, object: &mut Object, path: &mut KindPath) -> Flow {
+    walk_object(self, object, path)
+  }
+
+  /// Visits a property of an object literal.
+  fn visit_property_definition(
+    &mut self,
+    property: &mut PropertyDefinition,
+    path: &mut KindPath,
+  ) -> Flow {
+    walk_property_definition(self, property, path)
+  }
+
+  /// Visits the name of a property, a method or a class element.
+  fn visit_property_name(&mut self, name: &mut PropertyName, path: &mut KindPath) -> Flow {
+    walk_property_name(self, name, path)
+  }
+
+  /// Visits an optional chain.
+  fn visit_optional_chain(&mut self, chain: &mut OptionalChain, path: &mut KindPath) -> Flow {
+    walk_optional_chain(self, chain, path)
+  }
+
+  /// Visits a private brand check.
+  fn visit_private_in(&mut self, expr: &mut PrivateIn, path: &mut KindPath) -> Flow {
+    walk_private_in(self, expr, path)
+  }
+
+  /// Visits a `return` statement.
+  fn visit_return(&mut self, stmt: &mut Return, path: &mut KindPath) -> Flow {
+    walk_return(self, stmt, path)
+  }
+
+  /// Visits a spread element.
+  fn visit_spread(&mut self, spread: &mut Spread, path: &mut KindPath) -> Flow {
+    walk_spread(self, spread, path)
+  }
+
+  /// Visits a `switch` statement.
+  fn visit_switch(&mut self, stmt: &mut Switch, path: &mut KindPath) -> Flow {
+    walk_switch(self, stmt, path)
+  }
+
+  /// Visits a tagged template.
+  fn visit_tagged_template(&mut self, expr: &mut TaggedTemplate, path: &mut KindPath) -> Flow {
+    walk_tagged_template(self, expr, path)
+  }
+
+  /// Visits a template literal.
+  fn visit_template_lit(&mut self, template: &mut TemplateLit, path: &mut KindPath) -> Flow {
+    walk_template_lit(self, template, path)
+  }
+
+  /// Visits a clause of a `switch` statement.
+  fn visit_case(&mut self, case: &mut Case, path: &mut KindPath) -> Flow {
+    walk_case(self, case, path)
+  }
+
+  /// Visits a `throw` statement.
+  fn visit_throw(&mut self, stmt: &mut Throw, path: &mut KindPath) -> Flow {
+    walk_throw(self, stmt, path)
+  }
+
+  /// Visits a `try` statement.
+  fn visit_try(&mut self, stmt: &mut Try, path: &mut KindPath) -> Flow {
+    walk_try(self, stmt, path)
+  }
+
+  /// Visits the `catch` clause of a `try` statement.
+  fn visit_catch(&mut self, catch: &mut Catch, path: &mut KindPath) -> Flow {
+    walk_catch(self, catch, path)
+  }
+
+  /// Visits a unary or update operation.
+  fn visit_unary_op(&mut self, op: &mut UnaryOp, path: &mut KindPath) -> Flow {
+    walk_unary_op(self, op, path)
+  }
+
+  /// Visits a `while` loop.
+  fn visit_while_loop(&mut self, stmt: &mut WhileLoop, path: &mut KindPath) -> Flow {
+    walk_while_loop(self, stmt, path)
+  }
+
+  /// Visits a `with` statement.
+  fn visit_with(&mut self, stmt: &mut With, path: &mut KindPath) -> Flow {
+    walk_with(self, stmt, path)
+  }
+
+  /// Visits a `yield` expression.
+  fn visit_yield(&mut self, expr: &mut Yield, path: &mut KindPath) -> Flow {
+    walk_yield(self, expr, path)
+  }
+
+  /// Visits a binding or assignment pattern.
+  fn visit_pattern(&mut self, pattern: &mut Pattern, path: &mut KindPath) -> Flow {
+    walk_pattern(self, pattern, path)
+  }
+
+  /// Visits a pattern with an optional default value.
+  fn visit_pattern_element(&mut self, element: &mut PatternElement, path: &mut KindPath) -> Flow {
+    walk_pattern_element(self, element, path)
+  }
+
+  /// Visits an object pattern.
+  fn visit_object_pattern(&mut self, pattern: &mut ObjectPattern, path: &mut KindPath) -> Flow {
+    walk_object_pattern(self, pattern, path)
+  }
+
+  /// Visits a property of an object pattern.
+  fn visit_property_pattern(
+    &mut self,
+    property: &mut PropertyPattern,
+    path: &mut KindPath,
+  ) -> Flow {
+    walk_property_pattern(self, property, path)
+  }
+
+  /// Visits an array pattern.
+  fn visit_array_pattern(&mut self, pattern: &mut ArrayPattern, path: &mut KindPath) -> Flow {
+    walk_array_pattern(self, pattern, path)
+  }
+}
+
+/// Calls the hooks around the children of a part of the tree.
+///
+/// The `enter` hook can replace the node, so its kind is only known once it returns.
+fn hooked<V, T, F>(visitor: &mut V, node: &mut T, path: &mut KindPath, children: F) -> Flow
+where
+  V: VisitorMut + ?Sized,
+  F: FnOnce(&mut V, &mut T, &mut KindPath) -> Flow,
+  for<'a> NodeMut<'a>: From<&'a mut T>,
+{
+  match visitor.enter(NodeMut::from(&mut *node), path) {
+    Control::Stop => return Flow::Break(()),
+    Control::SkipChildren => {}
+    Control::Continue => {
+      path.push(NodeMut::from(&mut *node).kind());
+      let flow = children(visitor, node, path);
+      path.pop();
+      flow?;
+    }
+  }
+  visitor.exit(NodeMut::from(node), path).after_exit()
+}
+
+/// Visits the nodes of a list, stopping at the first that stops the traversal.
+fn each<V, T>(
+  visitor: &mut V,
+  items: &mut [T],
+  path: &mut KindPath,
+  mut visit: impl FnMut(&mut V, &mut T, &mut KindPath) -> Flow,
+) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  for item in items {
+    visit(visitor, item, path)?;
+  }
+  Flow::Continue(())
+}
+
+/// Visits an optional node.
+fn optional<V>(visitor: &mut V, node: Option<&mut Node>, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  match node {
+    Some(node) => visitor.visit_node(node, path),
+    None => Flow::Continue(()),
+  }
+}
+
+/// Visits the statements of a list.
+pub fn walk_statements<V>(visitor: &mut V, statements: &mut [Node], path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  each(visitor, statements, path, V::visit_node)
+}
+
+/// Calls the hooks around a node, and visits its children with the method of its variant.
+pub fn walk_node<V>(visitor: &mut V, node: &mut Node, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  hooked(visitor, node, path, |visitor, node, path| match node {
+    Node::ArrayDecl(array) => visitor.visit_array_decl(array, path),
+    Node::ArrowFunctionDecl(arrow) => visitor.visit_arrow_function_decl(arrow, path),
+    Node::Assign(assign) => visitor.visit_assign(assign, path),
+    Node::BinOp(op) => visitor.visit_bin_op(op, path),
+    Node::Await(expr) => visitor.visit_await(expr, path),
+    Node::Block(block) => visitor.visit_block(block, path),
+    Node::Break(stmt) => visitor.visit_break(stmt, path),
+    Node::Call(call) => visitor.visit_call(call, path),
+    Node::ClassDecl(class) | Node::ClassExpr(class) => visitor.visit_class(class, path),
+    Node::ConditionalOp(op) => visitor.visit_conditional_op(op, path),
+    Node::Const(value, _) => visitor.visit_const(value, path),
+    Node::Continue(stmt) => visitor.visit_continue(stmt, path),
+    Node::DeclarationList(list, _) => visitor.visit_declaration_list(list, path),
+    Node::DoWhileLoop(stmt) => visitor.visit_do_while_loop(stmt, path),
+    Node::Export(decl, _) => visitor.visit_export_decl(decl, path),
+    Node::ForInLoop(stmt) => visitor.visit_for_in_loop(stmt, path),
+    Node::ForLoop(stmt) => visitor.visit_for_loop(stmt, path),
+    Node::ForOfLoop(stmt) => visitor.visit_for_of_loop(stmt, path),
+    Node::FunctionDecl(decl) => visitor.visit_function_decl(decl, path),
+    Node::FunctionExpr(expr) => visitor.visit_function_expr(expr, path),
+    Node::GetConstField(access) => visitor.visit_get_const_field(access, path),
+    Node::GetField(access) => visitor.visit_get_field(access, path),
+    Node::GetPrivateField(access) => visitor.visit_get_private_field(access, path),
+    Node::Identifier(ident) => visitor.visit_identifier(ident, path),
+    Node::If(stmt) => visitor.visit_if(stmt, path),
+    Node::Import(decl) => visitor.visit_import_decl(decl, path),
+    Node::ImportCall(call) => visitor.visit_import_call(call, path),
+    Node::Labelled(stmt) => visitor.visit_labelled(stmt, path),
+    Node::New(expr) => visitor.visit_new(expr, path),
+    Node::Object(object) => visitor.visit_object(object, path),
+    Node::OptionalChain(chain) => visitor.visit_optional_chain(chain, path),
+    Node::PrivateIn(expr) => visitor.visit_private_in(expr, path),
+    Node::Return(stmt) => visitor.visit_return(stmt, path),
+    Node::Spread(spread) => visitor.visit_spread(spread, path),
+    Node::Switch(stmt) => visitor.visit_switch(stmt, path),
+    Node::TaggedTemplate(expr) => visitor.visit_tagged_template(expr, path),
+    Node::TemplateLit(template) => visitor.visit_template_lit(template, path),
+    Node::Throw(stmt) => visitor.visit_throw(stmt, path),
+    Node::Try(stmt) => visitor.visit_try(stmt, path),
+    Node::UnaryOp(op) => visitor.visit_unary_op(op, path),
+    Node::WhileLoop(stmt) => visitor.visit_while_loop(stmt, path),
+    Node::With(stmt) => visitor.visit_with(stmt, path),
+    Node::Yield(expr) => visitor.visit_yield(expr, path),
+    Node::Debugger(_)
+    | Node::Directive(_)
+    | Node::Empty(_)
+    | Node::Error(_)
+    | Node::ImportMeta(_)
+    | Node::Super(_)
+    | Node::This(_) => Flow::Continue(()),
+  })
+}
+
+/// Visits the elements of an array literal.
+pub fn walk_array_decl<V>(visitor: &mut V, array: &mut ArrayDecl, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  each(visitor, array.elements_mut(), path, V::visit_node)
+}
+
+/// Visits the parameters and the body of an arrow function.
+pub fn walk_arrow_function_decl<V>(
+  visitor: &mut V,
+  arrow: &mut ArrowFunctionDecl,
+  path: &mut KindPath,
+) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  each(visitor, arrow.params_mut(), path, V::visit_formal_parameter)?;
+  visitor.visit_statements(arrow.body_mut(), path)
+}
+
+/// Visits the target and the value of an assignment.
+pub fn walk_assign<V>(visitor: &mut V, assign: &mut Assign, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_pattern(assign.lhs_mut(), path)?;
+  visitor.visit_node(assign.rhs_mut(), path)
+}
+
+/// Visits the operands of a binary operation.
+pub fn walk_bin_op<V>(visitor: &mut V, op: &mut BinOp, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_node(op.lhs_mut(), path)?;
+  visitor.visit_node(op.rhs_mut(), path)
+}
+
+/// Visits the operand of an `await` expression.
+pub fn walk_await<V>(visitor: &mut V, expr: &mut Await, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_node(expr.expr_mut(), path)
+}
+
+/// Visits the statements of a block.
+pub fn walk_block<V>(visitor: &mut V, block: &mut Block, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_statements(block.statements_mut(), path)
+}
+
+/// Visits the callee and the arguments of a call.
+pub fn walk_call<V>(visitor: &mut V, call: &mut Call, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_node(call.expr_mut(), path)?;
+  each(visitor, call.args_mut(), path, V::visit_node)
+}
+
+/// Visits the superclass, the constructor and the elements of a class.
+pub fn walk_class<V>(visitor: &mut V, class: &mut Class, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  optional(visitor, class.super_ref_mut(), path)?;
+  if let Some(constructor) = class.constructor_mut() {
+    visitor.visit_function_expr(constructor, path)?;
+  }
+  each(visitor, class.elements_mut(), path, V::visit_class_element)
+}
+
+/// Calls the hooks around a class element, and visits its name and its value.
+pub fn walk_class_element<V>(
+  visitor: &mut V,
+  element: &mut ClassElement,
+  path: &mut KindPath,
+) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  hooked(
+    visitor,
+    element,
+    path,
+    |visitor, element, path| match element {
+      ClassElement::Method { name, function, .. } => {
+        if let ClassElementName::PropertyName(name) = name {
+          visitor.visit_property_name(name, path)?;
+        }
+        visitor.visit_function_expr(function, path)
+      }
+      ClassElement::Field { name, init, .. } => {
+        if let ClassElementName::PropertyName(name) = name {
+          visitor.visit_property_name(name, path)?;
+        }
+        optional(visitor, init.as_mut(), path)
+      }
+      ClassElement::StaticBlock(body, _) => visitor.visit_statements(body.items_mut(), path),
+    },
+  )
+}
+
+/// Visits the condition and the branches of a conditional expression.
+pub fn walk_conditional_op<V>(visitor: &mut V, op: &mut ConditionalOp, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_node(op.cond_mut(), path)?;
+  visitor.visit_node(op.if_true_mut(), path)?;
+  visitor.visit_node(op.if_false_mut(), path)
+}
+
+/// Visits the declarations of a `var`, `let` or `const` statement.
+pub fn walk_declaration_list<V>(
+  visitor: &mut V,
+  list: &mut DeclarationList,
+  path: &mut KindPath,
+) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  each(visitor, list.as_mut(), path, V::visit_declaration)
+}
+
+/// Calls the hooks around a declaration, and visits its target and its initializer.
+pub fn walk_declaration<V>(visitor: &mut V, decl: &mut Declaration, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  hooked(visitor, decl, path, |visitor, decl, path| {
+    visitor.visit_pattern(decl.target_mut(), path)?;
+    optional(visitor, decl.init_mut(), path)
+  })
+}
+
+/// Visits the body and the condition of a `do ... while` loop.
+pub fn walk_do_while_loop<V>(visitor: &mut V, stmt: &mut DoWhileLoop, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_node(stmt.body_mut(), path)?;
+  visitor.visit_node(stmt.cond_mut(), path)
+}
+
+/// Visits the specifiers or the declaration of an `export` declaration.
+pub fn walk_export_decl<V>(visitor: &mut V, decl: &mut ExportDecl, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  match decl {
+    ExportDecl::Named { specifiers, .. } => {
+      each(visitor, specifiers, path, V::visit_export_specifier)
+    }
+    ExportDecl::All { .. } => Flow::Continue(()),
+    ExportDecl::Declaration(node) | ExportDecl::DefaultExpression(node) => {
+      visitor.visit_node(node, path)
+    }
+    ExportDecl::DefaultFunction(function) => visitor.visit_function_expr(function, path),
+    ExportDecl::DefaultClass(class) => visitor.visit_class(class, path),
+  }
+}
+
+/// Calls the hooks around an export specifier.
+pub fn walk_export_specifier<V>(
+  visitor: &mut V,
+  specifier: &mut ExportSpecifier,
+  path: &mut KindPath,
+) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  hooked(visitor, specifier, path, |_, _, _| Flow::Continue(()))
+}
+
+/// Visits the target, the object and the body of a `for ... in` loop.
+pub fn walk_for_in_loop<V>(visitor: &mut V, stmt: &mut ForInLoop, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_iterable_loop_initializer(stmt.init_mut(), path)?;
+  visitor.visit_node(stmt.expr_mut(), path)?;
+  visitor.visit_node(stmt.body_mut(), path)
+}
+
+/// Visits the initializer, the condition, the update and the body of a `for` loop.
+pub fn walk_for_loop<V>(visitor: &mut V, stmt: &mut ForLoop, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  optional(visitor, stmt.init_mut(), path)?;
+  optional(visitor, stmt.condition_mut(), path)?;
+  optional(visitor, stmt.final_expr_mut(), path)?;
+  visitor.visit_node(stmt.body_mut(), path)
+}
+
+/// Visits the target, the iterable and the body of a `for ... of` loop.
+pub fn walk_for_of_loop<V>(visitor: &mut V, stmt: &mut ForOfLoop, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_iterable_loop_initializer(stmt.init_mut(), path)?;
+  visitor.visit_node(stmt.iterable_mut(), path)?;
+  visitor.visit_node(stmt.body_mut(), path)
+}
+
+/// Calls the hooks around a parameter, and visits its target and its default value.
+pub fn walk_formal_parameter<V>(
+  visitor: &mut V,
+  parameter: &mut FormalParameter,
+  path: &mut KindPath,
+) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  hooked(visitor, parameter, path, |visitor, parameter, path| {
+    visitor.visit_pattern(parameter.target_mut(), path)?;
+    optional(visitor, parameter.init_mut(), path)
+  })
+}
+
+/// Visits the parameters and the body of a function declaration.
+pub fn walk_function_decl<V>(visitor: &mut V, decl: &mut FunctionDecl, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  each(
+    visitor,
+    decl.parameters_mut(),
+    path,
+    V::visit_formal_parameter,
+  )?;
+  visitor.visit_statements(decl.body_mut(), path)
+}
+
+/// Visits the parameters and the body of a function expression.
+pub fn walk_function_expr<V>(visitor: &mut V, expr: &mut FunctionExpr, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  each(
+    visitor,
+    expr.parameters_mut(),
+    path,
+    V::visit_formal_parameter,
+  )?;
+  visitor.visit_statements(expr.body_mut(), path)
+}
+
+/// Visits the object of a static property access.
+pub fn walk_get_const_field<V>(
+  visitor: &mut V,
+  access: &mut GetConstField,
+  path: &mut KindPath,
+) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_node(access.obj_mut(), path)
+}
+
+/// Visits the object and the key of a computed property access.
+pub fn walk_get_field<V>(visitor: &mut V, access: &mut GetField, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_node(access.obj_mut(), path)?;
+  visitor.visit_node(access.field_mut(), path)
+}
+
+/// Visits the object of a private member access.
+pub fn walk_get_private_field<V>(
+  visitor: &mut V,
+  access: &mut GetPrivateField,
+  path: &mut KindPath,
+) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_node(access.obj_mut(), path)
+}
+
+/// Visits the condition and the branches of an `if` statement.
+pub fn walk_if<V>(visitor: &mut V, stmt: &mut If, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_node(stmt.cond_mut(), path)?;
+  visitor.visit_node(stmt.body_mut(), path)?;
+  optional(visitor, stmt.else_node_mut(), path)
+}
+
+/// Visits the specifiers of an `import` declaration.
+pub fn walk_import_decl<V>(visitor: &mut V, decl: &mut ImportDecl, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  each(
+    visitor,
+    decl.specifiers_mut(),
+    path,
+    V::visit_import_specifier,
+  )
+}
+
+/// Calls the hooks around an import specifier.
+pub fn walk_import_specifier<V>(
+  visitor: &mut V,
+  specifier: &mut ImportSpecifier,
+  path: &mut KindPath,
+) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  hooked(visitor, specifier, path, |_, _, _| Flow::Continue(()))
+}
+
+/// Visits the specifier and the options of a dynamic import.
+pub fn walk_import_call<V>(visitor: &mut V, call: &mut ImportCall, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_node(call.specifier_mut(), path)?;
+  optional(visitor, call.options_mut(), path)
+}
+
+/// Visits the pattern of the left hand side of a loop.
+pub fn walk_iterable_loop_initializer<V>(
+  visitor: &mut V,
+  init: &mut IterableLoopInitializer,
+  path: &mut KindPath,
+) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_pattern(init.pattern_mut(), path)
+}
+
+/// Visits the statement of a labelled statement.
+pub fn walk_labelled<V>(visitor: &mut V, stmt: &mut Labelled, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_node(stmt.statement_mut(), path)
+}
+
+/// Visits the constructor and the arguments of a `new` expression.
+pub fn walk_new<V>(visitor: &mut V, expr: &mut New, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_node(expr.expr_mut(), path)?;
+  each(visitor, expr.args_mut(), path, V::visit_node)
+}
+
+/// Visits the properties of an object literal.
+pub fn walk_object<V>(visitor: &mut V, object: &mut Object, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  each(
+    visitor,
+    object.properties_mut(),
+    path,
+    V::visit_property_definition,
+  )
+}
+
+/// Calls the hooks around a property of an object literal, and visits its name and its value.
+pub fn walk_property_definition<V>(
+  visitor: &mut V,
+  property: &mut PropertyDefinition,
+  path: &mut KindPath,
+) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  hooked(
+    visitor,
+    property,
+    path,
+    |visitor, property, path| match property {
+      PropertyDefinition::IdentifierReference(_, _) => Flow::Continue(()),
+      PropertyDefinition::Property(name, value, _) => {
+        visitor.visit_property_name(name, path)?;
+        visitor.visit_node(value, path)
+      }
+      PropertyDefinition::MethodDefinition(_, name, function, _) => {
+        visitor.visit_property_name(name, path)?;
+        visitor.visit_function_expr(function, path)
+      }
+      PropertyDefinition::SpreadObject(value, _) => visitor.visit_node(value, path),
+    },
+  )
+}
+
+/// Visits the expression of a computed property name.
+pub fn walk_property_name<V>(visitor: &mut V, name: &mut PropertyName, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  match name {
+    PropertyName::Literal(_) => Flow::Continue(()),
+    PropertyName::Computed(expr) => visitor.visit_node(expr, path),
+  }
+}
+
+/// Visits the expression of an optional chain.
+pub fn walk_optional_chain<V>(
+  visitor: &mut V,
+  chain: &mut OptionalChain,
+  path: &mut KindPath,
+) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_node(chain.expr_mut(), path)
+}
+
+/// Visits the object of a private brand check.
+pub fn walk_private_in<V>(visitor: &mut V, expr: &mut PrivateIn, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_node(expr.target_mut(), path)
+}
+
+/// Visits the value of a `return` statement.
+pub fn walk_return<V>(visitor: &mut V, stmt: &mut Return, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  optional(visitor, stmt.expr_mut(), path)
+}
+
+/// Visits the operand of a spread element.
+pub fn walk_spread<V>(visitor: &mut V, spread: &mut Spread, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_node(spread.val_mut(), path)
+}
+
+/// Visits the discriminant and the clauses of a `switch` statement.
+pub fn walk_switch<V>(visitor: &mut V, stmt: &mut Switch, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_node(stmt.val_mut(), path)?;
+  each(visitor, stmt.cases_mut(), path, V::visit_case)
+}
+
+/// Visits the tag and the substitutions of a tagged template.
+pub fn walk_tagged_template<V>(
+  visitor: &mut V,
+  expr: &mut TaggedTemplate,
+  path: &mut KindPath,
+) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_node(expr.tag_mut(), path)?;
+  visitor.visit_template_lit(expr.template_mut(), path)
+}
+
+/// Visits the substitutions of a template literal.
+pub fn walk_template_lit<V>(
+  visitor: &mut V,
+  template: &mut TemplateLit,
+  path: &mut KindPath,
+) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  each(visitor, template.exprs_mut(), path, V::visit_node)
+}
+
+/// Calls the hooks around a `switch` clause, and visits its test and its statements.
+pub fn walk_case<V>(visitor: &mut V, case: &mut Case, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  hooked(visitor, case, path, |visitor, case, path| {
+    optional(visitor, case.condition_mut(), path)?;
+    visitor.visit_statements(case.body_mut(), path)
+  })
+}
+
+/// Visits the value of a `throw` statement.
+pub fn walk_throw<V>(visitor: &mut V, stmt: &mut Throw, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_node(stmt.expr_mut(), path)
+}
+
+/// Visits the blocks and the `catch` clause of a `try` statement.
+pub fn walk_try<V>(visitor: &mut V, stmt: &mut Try, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_block(stmt.block_mut(), path)?;
+  if let Some(catch) = stmt.catch_mut() {
+    visitor.visit_catch(catch, path)?;
+  }
+  match stmt.finally_mut() {
+    Some(finally) => visitor.visit_block(finally, path),
+    None => Flow::Continue(()),
+  }
+}
+
+/// Calls the hooks around a `catch` clause, and visits its parameter and its block.
+pub fn walk_catch<V>(visitor: &mut V, catch: &mut Catch, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  hooked(visitor, catch, path, |visitor, catch, path| {
+    if let Some(parameter) = catch.parameter_mut() {
+      visitor.visit_pattern(parameter, path)?;
+    }
+    visitor.visit_block(catch.block_mut(), path)
+  })
+}
+
+/// Visits the operand of a unary operation.
+pub fn walk_unary_op<V>(visitor: &mut V, op: &mut UnaryOp, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_node(op.target_mut(), path)
+}
+
+/// Visits the condition and the body of a `while` loop.
+pub fn walk_while_loop<V>(visitor: &mut V, stmt: &mut WhileLoop, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_node(stmt.cond_mut(), path)?;
+  visitor.visit_node(stmt.body_mut(), path)
+}
+
+/// Visits the object and the body of a `with` statement.
+pub fn walk_with<V>(visitor: &mut V, stmt: &mut With, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_node(stmt.object_mut(), path)?;
+  visitor.visit_node(stmt.body_mut(), path)
+}
+
+/// Visits the operand of a `yield` expression.
+pub fn walk_yield<V>(visitor: &mut V, expr: &mut Yield, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  optional(visitor, expr.expr_mut(), path)
+}
+
+/// Calls the hooks around a pattern, and visits its parts.
+pub fn walk_pattern<V>(visitor: &mut V, pattern: &mut Pattern, path: &mut KindPath) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  hooked(
+    visitor,
+    pattern,
+    path,
+    |visitor, pattern, path| match pattern {
+      Pattern::Identifier(ident) => visitor.visit_identifier(ident, path),
+      Pattern::Object(object) => visitor.visit_object_pattern(object, path),
+      Pattern::Array(array) => visitor.visit_array_pattern(array, path),
+      Pattern::Member(member) => visitor.visit_node(member, path),
+    },
+  )
+}
+
+/// Visits the target and the default value of a pattern element.
+pub fn walk_pattern_element<V>(
+  visitor: &mut V,
+  element: &mut PatternElement,
+  path: &mut KindPath,
+) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_pattern(element.target_mut(), path)?;
+  optional(visitor, element.default_mut(), path)
+}
+
+/// Visits the properties and the rest element of an object pattern.
+pub fn walk_object_pattern<V>(
+  visitor: &mut V,
+  pattern: &mut ObjectPattern,
+  path: &mut KindPath,
+) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  each(
+    visitor,
+    pattern.properties_mut(),
+    path,
+    V::visit_property_pattern,
+  )?;
+  match pattern.rest_mut() {
+    Some(rest) => visitor.visit_pattern(rest, path),
+    None => Flow::Continue(()),
+  }
+}
+
+/// Visits the key and the value of a property of an object pattern.
+pub fn walk_property_pattern<V>(
+  visitor: &mut V,
+  property: &mut PropertyPattern,
+  path: &mut KindPath,
+) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  visitor.visit_property_name(property.key_mut(), path)?;
+  visitor.visit_pattern_element(property.value_mut(), path)
+}
+
+/// Visits the elements and the rest element of an array pattern.
+pub fn walk_array_pattern<V>(
+  visitor: &mut V,
+  pattern: &mut ArrayPattern,
+  path: &mut KindPath,
+) -> Flow
+where
+  V: VisitorMut + ?Sized,
+{
+  for element in pattern.elements_mut().iter_mut().flatten() {
+    visitor.visit_pattern_element(element, path)?;
+  }
+  match pattern.rest_mut() {
+    Some(rest) => visitor.visit_pattern(rest, path),
+    None => Flow::Continue(()),
+  }
+}
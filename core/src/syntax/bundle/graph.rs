@@ -0,0 +1,100 @@
+//! Loading the modules an entry imports, directly or not.
+
+use super::{resolve::resolve, BundleError};
+use crate::syntax::{ast::module::Module, parser::Parser};
+use std::{
+  fs,
+  path::{Path, PathBuf},
+};
+
+/// A module read from a file.
+#[derive(Debug)]
+pub(super) struct Loaded {
+  pub(super) path: PathBuf,
+  pub(super) source: String,
+  pub(super) module: Module,
+  /// Whether the module is written in TypeScript.
+  pub(super) typescript: bool,
+  /// The modules its specifiers resolve to.
+  pub(super) dependencies: Vec<(Box<str>, usize)>,
+}
+
+impl Loaded {
+  /// Gets the module a specifier of the module resolves to.
+  pub(super) fn dependency(&self, specifier: &str) -> usize {
+    self
+      .dependencies
+      .iter()
+      .find(|(name, _)| name.as_ref() == specifier)
+      .map(|(_, module)| *module)
+      .expect("the specifiers of a loaded module are resolved")
+  }
+}
+
+/// The modules of a program, the entry being the first one.
+#[derive(Debug, Default)]
+pub(super) struct Graph {
+  pub(super) modules: Vec<Loaded>,
+  /// The modules in the order they run: each one after the modules it imports, unless they
+  /// import it back.
+  pub(super) order: Vec<usize>,
+  /// The cycles of imports, each one from the module imported back to the module importing it.
+  pub(super) cycles: Vec<Vec<usize>>,
+}
+
+impl Graph {
+  /// Loads an entry and the modules it imports.
+  pub(super) fn load(entry: &Path) -> Result<Self, BundleError> {
+    let entry = canonicalize(entry)?;
+    let mut graph = Self::default();
+    let _ = graph.visit(entry, &mut Vec::new())?;
+    Ok(graph)
+  }
+
+  /// Loads a module and its dependencies if it was not yet, `stack` being the modules being
+  /// loaded, which import it.
+  fn visit(&mut self, path: PathBuf, stack: &mut Vec<usize>) -> Result<usize, BundleError> {
+    if let Some(index) = self.modules.iter().position(|module| module.path == path) {
+      if let Some(start) = stack.iter().position(|&module| module == index) {
+        self.cycles.push(stack[start..].to_vec());
+      }
+      return Ok(index);
+    }
+
+    let source = fs::read_to_string(&path).map_err(|err| BundleError::Io(path.clone(), err))?;
+    let typescript = matches!(
+      path.extension().and_then(|extension| extension.to_str()),
+      Some("ts") | Some("mts")
+    );
+    let module = Parser::new(source.as_bytes())
+      .with_typescript(typescript)
+      .parse_module()
+      .map_err(|err| BundleError::Parse(path.clone(), err))?;
+    let specifiers = module.summary().requested_modules().to_vec();
+    let index = self.modules.len();
+    self.modules.push(Loaded {
+      path,
+      source,
+      module,
+      typescript,
+      dependencies: Vec::new(),
+    });
+
+    stack.push(index);
+    for specifier in specifiers {
+      let resolved = resolve(&self.modules[index].path, &specifier)?;
+      let dependency = self.visit(canonicalize(&resolved)?, stack)?;
+      self.modules[index]
+        .dependencies
+        .push((specifier, dependency));
+    }
+    stack.pop();
+    self.order.push(index);
+    Ok(index)
+  }
+}
+
+/// Gets the absolute path of a file, so that a module imported by several paths loads once.
+fn canonicalize(path: &Path) -> Result<PathBuf, BundleError> {
+  fs::canonicalize(path).map_err(|err| BundleError::Io(path.to_path_buf(), err))
+}
@@ -0,0 +1,623 @@
+//! Scope hoisting: joining the top levels of the modules into one.
+//!
+//! The top level bindings of every module are declared in the same scope, so each one gets a
+//! name that no other top level binding, global or inner binding along its uses takes: it
+//! keeps its own name when it can, and is renamed to `name$1`, `name$2` and so on otherwise.
+//! Imported bindings are replaced by the bindings they import, and module namespace objects
+//! are built from getters reading the bindings of their module.
+
+use super::{graph::Graph, BundleError};
+use crate::syntax::{
+  ast::{
+    module::{ExportEntry, ImportName, Module, ModuleSummary, DEFAULT_LOCAL_NAME},
+    node::{
+      Class, Declaration, DeclarationList, Directive, ExportDecl, FunctionDecl, FunctionExpr,
+      Identifier, Node, PropertyDefinition, PropertyName, StatementList,
+    },
+    visitor::{visit_mut, Flow, KindPath, Path, VisitorMut},
+    Keyword, NodeId, Sym,
+  },
+  json,
+  lexer::{is_identifier_name, STRICT_FORBIDDEN_IDENTIFIERS},
+  parser::Parser,
+  scope::{BindingId, BindingKind, ScopeId, ScopeTree},
+};
+use std::{
+  collections::{HashMap, HashSet},
+  mem,
+};
+
+/// What an import refers to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Target {
+  /// A top level binding of a module, by its name in the module, `*default*` for the default
+  /// exported expression or anonymous declaration.
+  Binding(usize, Box<str>),
+  /// The namespace object of a module.
+  Namespace(usize),
+}
+
+/// Statements of the bundle, printed one after the other.
+#[derive(Debug)]
+pub(super) struct Piece {
+  /// The module the statements come from, `None` for the statements added by the bundler.
+  pub(super) module: Option<usize>,
+  pub(super) items: Vec<Node>,
+  /// The names of the module given to other bindings, with the names of their source.
+  pub(super) renamed: HashMap<Box<str>, Box<str>>,
+}
+
+impl Piece {
+  /// Creates a piece of statements added by the bundler.
+  fn added(items: Vec<Node>) -> Self {
+    Self {
+      module: None,
+      items,
+      renamed: HashMap::new(),
+    }
+  }
+}
+
+/// The modules of a graph, hoisted into one scope.
+#[derive(Debug)]
+pub(super) struct Linked {
+  /// The statements of the bundle, in the order they run.
+  pub(super) pieces: Vec<Piece>,
+  /// The names exported by the entry, with the bindings they refer to.
+  pub(super) exports: Vec<(Box<str>, Box<str>)>,
+}
+
+/// Links the modules of a graph.
+pub(super) fn link(graph: &mut Graph) -> Result<Linked, BundleError> {
+  let mut linker = Linker::new(graph);
+  let imports = linker.imports()?;
+  linker.assign_names(&imports);
+
+  let use_strict = Directive::new("use strict", "\"use strict\"");
+  let mut pieces = vec![Piece::added(vec![use_strict.into()])];
+  let namespaces = linker.namespaces();
+  if !namespaces.is_empty() {
+    pieces.push(Piece::added(namespaces));
+  }
+  for &module in &linker.graph.order {
+    let body = mem::replace(
+      &mut linker.graph.modules[module].module,
+      Module::new(Vec::new()),
+    )
+    .into_body();
+    pieces.push(linker.rewrite(module, body, &imports));
+  }
+
+  let exports = linker
+    .export_names(0, &mut Vec::new())
+    .into_iter()
+    .filter_map(|name| {
+      let target = linker.export(0, &name, &mut Vec::new())?;
+      Some((name, linker.names.get(&target)?.clone()))
+    })
+    .collect();
+  Ok(Linked { pieces, exports })
+}
+
+/// Links the modules of a graph.
+struct Linker<'g> {
+  graph: &'g mut Graph,
+  trees: Vec<ScopeTree>,
+  summaries: Vec<ModuleSummary>,
+  /// The names of the globals the modules refer to, which no binding can take.
+  globals: HashSet<Box<str>>,
+  /// The names given to the bindings and namespace objects.
+  names: HashMap<Target, Box<str>>,
+  taken: HashSet<Box<str>>,
+}
+
+impl<'g> Linker<'g> {
+  fn new(graph: &'g mut Graph) -> Self {
+    let trees: Vec<_> = graph
+      .modules
+      .iter()
+      .map(|loaded| ScopeTree::module(&loaded.module))
+      .collect();
+    let summaries = graph
+      .modules
+      .iter()
+      .map(|loaded| loaded.module.summary().clone())
+      .collect();
+    let mut globals: HashSet<Box<str>> = trees
+      .iter()
+      .flat_map(|tree| tree.globals().map(|(_, reference)| reference.name().into()))
+      .collect();
+    // Namespace objects are frozen.
+    globals.insert("Object".into());
+    Self {
+      graph,
+      trees,
+      summaries,
+      globals,
+      names: HashMap::new(),
+      taken: HashSet::new(),
+    }
+  }
+
+  /// Finds the binding or the namespace object a module exports under a name, `None` if it
+  /// does not, or if the name is ambiguous or circular.
+  fn export(
+    &self,
+    module: usize,
+    name: &str,
+    visited: &mut Vec<(usize, Box<str>)>,
+  ) -> Option<Target> {
+    if visited.iter().any(|(m, n)| *m == module && **n == *name) {
+      return None;
+    }
+    visited.push((module, name.into()));
+    let loaded = &self.graph.modules[module];
+    let summary = &self.summaries[module];
+    for entry in summary.exports() {
+      match entry {
+        ExportEntry::Local {
+          export_name,
+          local_name,
+        } if **export_name == *name => {
+          // Imported namespace objects are exported as local bindings.
+          let import = summary
+            .imports()
+            .iter()
+            .find(|import| import.local_name() == local_name.as_ref());
+          return match import {
+            Some(import) => {
+              let dependency = loaded.dependency(import.module_request());
+              match import.import_name() {
+                ImportName::Namespace => Some(Target::Namespace(dependency)),
+                ImportName::Name(imported) => self.export(dependency, imported, visited),
+              }
+            }
+            None => Some(Target::Binding(module, local_name.as_str().into())),
+          };
+        }
+        ExportEntry::Indirect {
+          export_name,
+          module_request,
+          import_name,
+        } if **export_name == *name => {
+          let dependency = loaded.dependency(module_request);
+          return match import_name {
+            ImportName::Namespace => Some(Target::Namespace(dependency)),
+            ImportName::Name(imported) => self.export(dependency, imported, visited),
+          };
+        }
+        _ => {}
+      }
+    }
+    // Star re-exports never include the default export.
+    if name == "default" {
+      return None;
+    }
+    summary.exports().iter().find_map(|entry| match entry {
+      ExportEntry::Star { module_request } => {
+        self.export(loaded.dependency(module_request), name, visited)
+      }
+      _ => None,
+    })
+  }
+
+  /// Lists the names a module exports, following star re-exports.
+  fn export_names(&self, module: usize, visited: &mut Vec<usize>) -> Vec<Box<str>> {
+    if visited.contains(&module) {
+      return Vec::new();
+    }
+    visited.push(module);
+    let mut names: Vec<Box<str>> = self.summaries[module]
+      .exported_names()
+      .map(Box::from)
+      .collect();
+    for entry in self.summaries[module].exports() {
+      if let ExportEntry::Star { module_request } = entry {
+        let dependency = self.graph.modules[module].dependency(module_request);
+        for name in self.export_names(dependency, visited) {
+          if &*name != "default" && !names.contains(&name) {
+            names.push(name);
+          }
+        }
+      }
+    }
+    names
+  }
+
+  /// Finds what the imported bindings of every module refer to.
+  fn imports(&self) -> Result<Vec<HashMap<BindingId, Target>>, BundleError> {
+    let mut imports = Vec::new();
+    for (module, loaded) in self.graph.modules.iter().enumerate() {
+      let tree = &self.trees[module];
+      let mut targets = HashMap::new();
+      for import in self.summaries[module].imports() {
+        let dependency = loaded.dependency(import.module_request());
+        let binding = tree.scope(tree.root()).binding(import.local_name());
+        let target = match import.import_name() {
+          ImportName::Namespace => Target::Namespace(dependency),
+          ImportName::Name(name) => match self.export(dependency, name, &mut Vec::new()) {
+            Some(target) => target,
+            // Imports only used as types may be types, which are not exported once stripped.
+            None
+              if loaded.typescript
+                && binding.is_none_or(|binding| tree.binding(binding).references().is_empty()) =>
+            {
+              continue
+            }
+            None => {
+              return Err(BundleError::MissingExport {
+                name: name.as_str().into(),
+                module: self.graph.modules[dependency].path.clone(),
+                from: loaded.path.clone(),
+              })
+            }
+          },
+        };
+        if let Some(binding) = binding {
+          targets.insert(binding, target);
+        }
+      }
+      imports.push(targets);
+    }
+    Ok(imports)
+  }
+
+  /// Gives names to the top level bindings and to the namespace objects. The entry comes
+  /// first, so that its bindings keep their names if they can, then the modules in the order
+  /// they run.
+  fn assign_names(&mut self, imports: &[HashMap<BindingId, Target>]) {
+    // The scopes the bindings are used from, which must not have other bindings of their new
+    // names.
+    let mut uses: HashMap<Target, Vec<(usize, ScopeId)>> = HashMap::new();
+    for (module, tree) in self.trees.iter().enumerate() {
+      for &id in tree.scope(tree.root()).bindings() {
+        let binding = tree.binding(id);
+        let target = match imports[module].get(&id) {
+          Some(target) => target.clone(),
+          None if binding.kind() == BindingKind::Import => continue,
+          None => Target::Binding(module, binding.name().into()),
+        };
+        let scopes = binding
+          .references()
+          .iter()
+          .map(|&reference| (module, tree.reference(reference).scope()));
+        uses.entry(target).or_default().extend(scopes);
+      }
+    }
+
+    let mut namespaces: Vec<usize> = imports
+      .iter()
+      .flat_map(|targets| targets.values())
+      .chain(uses.keys())
+      .filter_map(|target| match target {
+        Target::Namespace(module) => Some(*module),
+        Target::Binding(..) => None,
+      })
+      .collect();
+    for name in self.export_names(0, &mut Vec::new()) {
+      if let Some(Target::Namespace(module)) = self.export(0, &name, &mut Vec::new()) {
+        namespaces.push(module);
+      }
+    }
+
+    let mut order = vec![0];
+    order.extend(
+      self
+        .graph
+        .order
+        .iter()
+        .copied()
+        .filter(|&module| module != 0),
+    );
+    for module in order {
+      let tree = &self.trees[module];
+      let mut targets: Vec<(Target, String)> = tree
+        .scope(tree.root())
+        .bindings()
+        .iter()
+        .map(|&id| tree.binding(id))
+        .filter(|binding| binding.kind() != BindingKind::Import)
+        .map(|binding| {
+          (
+            Target::Binding(module, binding.name().into()),
+            binding.name().to_owned(),
+          )
+        })
+        .collect();
+      let stem = self.stem(module);
+      if self.has_anonymous_default(module) {
+        targets.push((
+          Target::Binding(module, DEFAULT_LOCAL_NAME.into()),
+          format!("{}_default", stem),
+        ));
+      }
+      if namespaces.contains(&module) {
+        targets.push((Target::Namespace(module), stem));
+      }
+      for (target, base) in targets {
+        let scopes = uses.get(&target).map_or(&[][..], Vec::as_slice);
+        let name = self.available_name(&base, scopes);
+        self.taken.insert(name.clone());
+        self.names.insert(target, name);
+      }
+    }
+  }
+
+  /// Finds the first of `base`, `base$1`, `base$2` and so on that no other binding takes
+  /// along its uses.
+  fn available_name(&self, base: &str, scopes: &[(usize, ScopeId)]) -> Box<str> {
+    (0..)
+      .map(|suffix| match suffix {
+        0 => Box::from(base),
+        suffix => format!("{}${}", base, suffix).into(),
+      })
+      .find(|name| {
+        !self.taken.contains(name)
+          && !self.globals.contains(name)
+          && !is_reserved(name)
+          && !scopes
+            .iter()
+            .any(|&(module, scope)| self.is_shadowed(module, scope, name))
+      })
+      .expect("there are names left")
+  }
+
+  /// Returns `true` if a name is bound between a scope and the top level of its module.
+  fn is_shadowed(&self, module: usize, mut scope: ScopeId, name: &str) -> bool {
+    let tree = &self.trees[module];
+    while scope != tree.root() {
+      let current = tree.scope(scope);
+      if current.binding(name).is_some() {
+        return true;
+      }
+      scope = current.parent().expect("inner scopes have a parent scope");
+    }
+    false
+  }
+
+  /// Returns `true` if a module has a default exported expression or anonymous declaration,
+  /// which needs a name.
+  fn has_anonymous_default(&self, module: usize) -> bool {
+    self.summaries[module].exports().iter().any(|entry| {
+      matches!(
+        entry,
+        ExportEntry::Local { local_name, .. } if &**local_name == DEFAULT_LOCAL_NAME
+      )
+    })
+  }
+
+  /// Gets a name for a module after its file, `utils` for `src/utils.js` and `pkg` for
+  /// `pkg/index.js`.
+  fn stem(&self, module: usize) -> String {
+    let path = &self.graph.modules[module].path;
+    let stem = match path.file_stem().and_then(|stem| stem.to_str()) {
+      Some("index") => path
+        .parent()
+        .and_then(|parent| parent.file_name())
+        .and_then(|name| name.to_str()),
+      stem => stem,
+    };
+    let mut name: String = stem
+      .unwrap_or("module")
+      .chars()
+      .map(|ch| {
+        if ch.is_alphanumeric() || ch == '_' || ch == '$' {
+          ch
+        } else {
+          '_'
+        }
+      })
+      .collect();
+    if !name.starts_with(|ch: char| ch.is_alphabetic() || ch == '_' || ch == '$') {
+      name.insert(0, '_');
+    }
+    name
+  }
+
+  /// Builds the namespace objects of the modules imported as a whole, as in
+  /// `var utils = Object.freeze({ __proto__: null, get add() { return add; } });`.
+  fn namespaces(&self) -> Vec<Node> {
+    let mut namespaces: Vec<(&Box<str>, usize)> = self
+      .names
+      .iter()
+      .filter_map(|(target, name)| match target {
+        Target::Namespace(module) => Some((name, *module)),
+        Target::Binding(..) => None,
+      })
+      .collect();
+    namespaces.sort_by_key(|&(_, module)| module);
+
+    let mut source = String::new();
+    for (name, module) in namespaces {
+      source.push_str("var ");
+      source.push_str(name);
+      source.push_str(" = Object.freeze({ __proto__: null");
+      for export in self.export_names(module, &mut Vec::new()) {
+        let binding = self
+          .export(module, &export, &mut Vec::new())
+          .and_then(|target| self.names.get(&target));
+        let binding = match binding {
+          Some(binding) => binding,
+          None => continue,
+        };
+        source.push_str(", get ");
+        if is_identifier_name(&export) {
+          source.push_str(&export);
+        } else {
+          json::write_string(&export, &mut source);
+        }
+        source.push_str("() { return ");
+        source.push_str(binding);
+        source.push_str("; }");
+      }
+      source.push_str(" });\n");
+    }
+    Parser::new(source.as_bytes())
+      .parse_script()
+      .expect("namespace objects are valid scripts")
+      .items()
+      .to_vec()
+  }
+
+  /// Rewrites the body of a module for the shared scope: renames its bindings, replaces its
+  /// imports by the bindings they import and removes its import and export declarations.
+  fn rewrite(
+    &self,
+    module: usize,
+    mut body: StatementList,
+    imports: &[HashMap<BindingId, Target>],
+  ) -> Piece {
+    let tree = &self.trees[module];
+    let mut names = HashMap::new();
+    for &id in tree.scope(tree.root()).bindings() {
+      let target = match imports[module].get(&id) {
+        Some(target) => target.clone(),
+        None => Target::Binding(module, tree.binding(id).name().into()),
+      };
+      if let Some(name) = self.names.get(&target) {
+        names.insert(id, name.clone());
+      }
+    }
+
+    let mut renamer = Renamer {
+      tree,
+      names: &names,
+      renamed: HashMap::new(),
+    };
+    let _ = renamer.visit_statements(body.items_mut(), &mut Path::new());
+
+    let default_name = || self.names[&Target::Binding(module, DEFAULT_LOCAL_NAME.into())].clone();
+    // Directives are given once for the whole bundle.
+    let directives = body
+      .items()
+      .iter()
+      .take_while(|item| matches!(item, Node::Directive(_)))
+      .count();
+    let mut items = Vec::new();
+    for item in &body.items()[directives..] {
+      let decl = match item {
+        Node::Import(_) => continue,
+        Node::Export(decl, _) => decl,
+        item => {
+          items.push(item.clone());
+          continue;
+        }
+      };
+      match decl {
+        ExportDecl::Named { .. } | ExportDecl::All { .. } => {}
+        ExportDecl::Declaration(declaration) => items.push((**declaration).clone()),
+        ExportDecl::DefaultFunction(function) => {
+          let name = function.name().map_or_else(default_name, Box::from);
+          items.push(function_declaration(name, function).into());
+        }
+        ExportDecl::DefaultClass(class) => {
+          let name = class.name().map_or_else(default_name, Box::from);
+          let class = Class::new(
+            Sym::from(name),
+            class.super_ref().cloned(),
+            class.constructor().cloned(),
+            class.elements().to_vec(),
+          );
+          items.push(Node::ClassDecl(class));
+        }
+        ExportDecl::DefaultExpression(expr) => {
+          let declaration =
+            Declaration::new(Identifier::new(default_name()), Some((**expr).clone()));
+          items.push(DeclarationList::Var(vec![declaration].into()).into());
+        }
+      }
+    }
+
+    Piece {
+      module: Some(module),
+      items,
+      renamed: renamer.renamed,
+    }
+  }
+}
+
+/// Turns a default exported function into a declaration named `name`.
+fn function_declaration(name: Box<str>, function: &FunctionExpr) -> FunctionDecl {
+  let body = StatementList::from(function.body().to_vec()).with_strict(function.is_strict());
+  FunctionDecl::new(name, function.parameters().to_vec(), body).with_kind(function.kind())
+}
+
+/// Returns `true` if a name can not be used for a binding.
+fn is_reserved(name: &str) -> bool {
+  name.parse::<Keyword>().is_ok()
+    || STRICT_FORBIDDEN_IDENTIFIERS.contains(&name)
+    || matches!(name, "undefined" | "NaN" | "Infinity")
+}
+
+/// Renames the top level bindings of a module and its imported bindings, by the ids of the
+/// nodes naming them.
+struct Renamer<'a> {
+  tree: &'a ScopeTree,
+  names: &'a HashMap<BindingId, Box<str>>,
+  /// The names of the source, by the names they were replaced with.
+  renamed: HashMap<Box<str>, Box<str>>,
+}
+
+impl Renamer<'_> {
+  /// Gets the new name of a name of the syntax tree, if it changes.
+  fn rename(&mut self, name: &Sym, id: NodeId) -> Option<Box<str>> {
+    let binding = self.tree.resolve_at(id)?;
+    let renamed = self.names.get(&binding)?;
+    if **renamed == *name {
+      return None;
+    }
+    self
+      .renamed
+      .entry(renamed.clone())
+      .or_insert_with(|| name.into());
+    Some(renamed.clone())
+  }
+}
+
+impl VisitorMut for Renamer<'_> {
+  fn visit_identifier(&mut self, ident: &mut Identifier, _path: &mut KindPath) -> Flow {
+    if let Some(renamed) = self.rename(ident.name(), ident.id()) {
+      *ident = Identifier::new(renamed);
+    }
+    Flow::Continue(())
+  }
+
+  fn visit_property_definition(
+    &mut self,
+    property: &mut PropertyDefinition,
+    path: &mut KindPath,
+  ) -> Flow {
+    if let PropertyDefinition::IdentifierReference(ident, span) = property {
+      if let Some(renamed) = self.rename(ident.name(), ident.id()) {
+        *property = PropertyDefinition::Property(
+          PropertyName::Literal(ident.name().clone()),
+          Identifier::new(renamed).into(),
+          *span,
+        );
+      }
+      return Flow::Continue(());
+    }
+    visit_mut::walk_property_definition(self, property, path)
+  }
+
+  fn visit_function_decl(&mut self, decl: &mut FunctionDecl, path: &mut KindPath) -> Flow {
+    if let Some(renamed) = self.rename(decl.name(), decl.id()) {
+      *decl.name_mut() = renamed.into();
+    }
+    visit_mut::walk_function_decl(self, decl, path)
+  }
+
+  fn visit_function_expr(&mut self, expr: &mut FunctionExpr, path: &mut KindPath) -> Flow {
+    if let Some(renamed) = expr.name().and_then(|name| self.rename(name, expr.id())) {
+      *expr.name_mut().expect("the function has a name") = renamed.into();
+    }
+    visit_mut::walk_function_expr(self, expr, path)
+  }
+
+  fn visit_class(&mut self, class: &mut Class, path: &mut KindPath) -> Flow {
+    if let Some(renamed) = class.name().and_then(|name| self.rename(name, class.id())) {
+      *class.name_mut().expect("the class has a name") = renamed.into();
+    }
+    visit_mut::walk_class(self, class, path)
+  }
+}
@@ -0,0 +1,140 @@
+//! The source map of a bundle.
+//!
+//! Each piece of the bundle is mapped to the source of its module by its tokens, see
+//! [`TokenMatcher`]. Renamed bindings are matched by the names they have in their source, and
+//! the import and export declarations removed from the bundle are left out of the source.
+
+use super::link::Piece;
+use crate::syntax::{
+  ast::{Keyword, Punctuator},
+  lexer::{
+    token::{Token, TokenKind},
+    Lexer,
+  },
+  sourcemap::{
+    tokens::{is_mapped, tokens, TokenMatcher},
+    SourceMap, SourceMapBuilder,
+  },
+};
+use std::iter;
+
+/// Maps a bundle to the sources of its modules, given by name with their text.
+///
+/// `printed` is each piece of the bundle printed alone, which has the same tokens as in the
+/// bundle. The tokens before the first piece belong to the wrapper of the bundle.
+pub(super) fn source_map(
+  code: &str,
+  pieces: &[Piece],
+  printed: &[String],
+  sources: &[(String, &str)],
+) -> SourceMap {
+  let mut builder = SourceMapBuilder::new();
+  let indices: Vec<u32> = sources
+    .iter()
+    .map(|(name, source)| builder.add_source(name, Some(source)))
+    .collect();
+  let mut matchers: Vec<TokenMatcher> = sources
+    .iter()
+    .map(|(_, source)| TokenMatcher::from_tokens(module_tokens(source)))
+    .collect();
+
+  let output = tokens(code);
+  let counts: Vec<usize> = printed.iter().map(|piece| tokens(piece).len()).collect();
+  let wrapper = output.len().saturating_sub(counts.iter().sum());
+  let owners = iter::repeat_n(None, wrapper).chain(
+    pieces
+      .iter()
+      .zip(counts)
+      .flat_map(|(piece, count)| iter::repeat_n(Some(piece), count)),
+  );
+
+  for (token, owner) in output.iter().zip(owners) {
+    let (piece, module) = match owner.and_then(|piece| Some((piece, piece.module?))) {
+      Some(owner) => owner,
+      None => {
+        builder.add_unmapped(token.span().start());
+        continue;
+      }
+    };
+    let kind = match token.kind() {
+      TokenKind::Identifier(name) => match piece.renamed.get(name.as_str()) {
+        Some(original) => TokenKind::Identifier(original.as_ref().into()),
+        None => token.kind().clone(),
+      },
+      kind => kind.clone(),
+    };
+    match matchers[module].find(&kind) {
+      Some(matched) => {
+        let name = match matched.kind() {
+          TokenKind::Identifier(name) => Some(&**name),
+          _ => None,
+        };
+        builder.add_span(token.span(), indices[module], matched.span(), name);
+      }
+      None => builder.add_unmapped(token.span().start()),
+    }
+  }
+  builder.build()
+}
+
+/// Lexes the names and the literals of a module, except for the ones of its `import`
+/// declarations and of its `export` lists.
+fn module_tokens(source: &str) -> Vec<Token> {
+  let mut lexer = Lexer::new(source.as_bytes());
+  let mut all = Vec::new();
+  while let Ok(Some(token)) = lexer.next() {
+    if token.kind() == &TokenKind::EOF {
+      break;
+    }
+    all.push(token);
+  }
+
+  let mut tokens = Vec::new();
+  let mut index = 0;
+  while let Some(token) = all.get(index) {
+    let removed = match (token.kind(), all.get(index + 1).map(Token::kind)) {
+      // `import()` calls and `import.meta` stay.
+      (
+        TokenKind::Keyword(Keyword::Import),
+        Some(TokenKind::Punctuator(Punctuator::OpenParen))
+        | Some(TokenKind::Punctuator(Punctuator::Dot)),
+      ) => false,
+      (TokenKind::Keyword(Keyword::Import), _) => true,
+      (
+        TokenKind::Keyword(Keyword::Export),
+        Some(TokenKind::Punctuator(Punctuator::OpenBlock))
+        | Some(TokenKind::Punctuator(Punctuator::Mul)),
+      ) => true,
+      _ => false,
+    };
+    if removed {
+      index = declaration_end(&all, index);
+      continue;
+    }
+    if is_mapped(token.kind()) {
+      tokens.push(token.clone());
+    }
+    index += 1;
+  }
+  tokens
+}
+
+/// Finds the end of the import or export declaration starting at `start`: after its module
+/// specifier, the closing brace of an export list without one, or a semicolon.
+fn declaration_end(tokens: &[Token], start: usize) -> usize {
+  let is_from = |index: usize| matches!(tokens[index].kind(), TokenKind::Identifier(name) if name.as_ref() == "from");
+  for index in start + 1..tokens.len() {
+    match tokens[index].kind() {
+      TokenKind::StringLiteral(_) if index == start + 1 || is_from(index - 1) => return index + 1,
+      TokenKind::Punctuator(Punctuator::CloseBlock)
+        if tokens[start].kind() == &TokenKind::Keyword(Keyword::Export)
+          && !(index + 1 < tokens.len() && is_from(index + 1)) =>
+      {
+        return index + 1
+      }
+      TokenKind::Punctuator(Punctuator::Semicolon) => return index + 1,
+      _ => {}
+    }
+  }
+  tokens.len()
+}
@@ -0,0 +1,403 @@
+//! Bundling: joining an entry module and the modules it imports into one script.
+//!
+//! [`bundle`] reads the entry from the file system and follows its `import` and `export ...
+//! from` declarations, resolving specifiers as Node.js does: relative paths, with or without
+//! their extension, and packages of `node_modules` directories entered through the `exports`,
+//! `module` or `main` fields of their `package.json` file. TypeScript files are stripped of
+//! their types.
+//!
+//! The modules are concatenated in the order they run, after the modules they import, with
+//! their top level bindings hoisted into a single scope. Bindings of different modules with the
+//! same name are renamed apart, imported bindings are replaced by the bindings they import, and
+//! namespace imports become frozen objects reading the bindings of their module:
+//!
+//! ```
+//! use plastic::syntax::bundle::{bundle, Options};
+//! use std::fs;
+//!
+//! let dir = std::env::temp_dir().join(format!("plastic-bundle-doc-{}", std::process::id()));
+//! fs::create_dir_all(&dir).unwrap();
+//! fs::write(dir.join("main.js"), "import { add } from './math';\nconst one = 1;\nconsole.log(add(one, 2));\n").unwrap();
+//! fs::write(dir.join("math.js"), "const one = 1;\nexport function add(a, b) { return a + b + one - one; }\n").unwrap();
+//!
+//! let bundled = bundle(dir.join("main.js"), &Options::default()).unwrap();
+//! assert_eq!(
+//!   bundled.code(),
+//!   "\"use strict\";\nconst one$1 = 1;\n\nfunction add(a, b) {\n  return a + b + one$1 - one$1;\n}\n\n\
+//!    const one = 1;\nconsole.log(add(one, 2));\n"
+//! );
+//! assert!(bundled.cycles().is_empty());
+//! fs::remove_dir_all(&dir).unwrap();
+//! ```
+//!
+//! The bundle is a script, or with [`Format::Iife`] a function called right away, which can
+//! return the exports of the entry. Its [source map](crate::syntax::sourcemap) traces the names
+//! and literals of the bundle back to the files of the modules.
+//!
+//! Modules importing each other in a cycle are reported by [`Bundle::cycles`], and run as they
+//! would as modules: the module imported back runs first, and reads the bindings of the module
+//! importing it before they are initialized. Dynamic `import()` calls are left as they are, and
+//! their modules are not bundled.
+
+mod graph;
+mod link;
+mod map;
+mod resolve;
+#[cfg(test)]
+mod tests;
+
+use self::graph::Graph;
+use crate::syntax::{
+  ast::node::{
+    Call, Declaration, DeclarationList, FormalParameter, FunctionExpr, Identifier, Node, Object,
+    PropertyDefinition, PropertyName, Return, StatementList,
+  },
+  codegen::{self, Generator},
+  parser::ParseError,
+  sourcemap::SourceMap,
+};
+use std::{
+  error::Error as StdError,
+  fmt, fs, io,
+  path::{Component, Path, PathBuf},
+};
+
+/// The shape of a bundle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+  /// A script, whose top level bindings are global.
+  Script,
+  /// A function expression called right away, `(function () { ... })();`, whose bindings stay
+  /// inside of it.
+  Iife,
+}
+
+impl Format {
+  /// Gets the name of the format, as given on the command line.
+  pub fn name(self) -> &'static str {
+    match self {
+      Self::Script => "script",
+      Self::Iife => "iife",
+    }
+  }
+
+  /// Gets a format by name.
+  pub fn from_name(name: &str) -> Option<Self> {
+    match name {
+      "script" => Some(Self::Script),
+      "iife" => Some(Self::Iife),
+      _ => None,
+    }
+  }
+}
+
+impl fmt::Display for Format {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.name())
+  }
+}
+
+/// The options of [`bundle`].
+#[derive(Debug, Clone)]
+pub struct Options {
+  format: Format,
+  name: Option<Box<str>>,
+  output: codegen::Options,
+  map_dir: Option<PathBuf>,
+}
+
+impl Options {
+  /// Sets the shape of the bundle, [`Format::Script`] by default.
+  pub fn with_format(mut self, format: Format) -> Self {
+    self.format = format;
+    self
+  }
+
+  /// Sets the global variable the exports of the entry are assigned to, as an object, in the
+  /// [`Format::Iife`] format. Without one, the exports are left out.
+  pub fn with_name<N>(mut self, name: N) -> Self
+  where
+    N: Into<Box<str>>,
+  {
+    self.name = Some(name.into());
+    self
+  }
+
+  /// Sets the options the bundle is printed with.
+  pub fn with_output(mut self, output: codegen::Options) -> Self {
+    self.output = output;
+    self
+  }
+
+  /// Sets the directory the source map is written to. The sources of the map are named by
+  /// their paths relative to it, and by default relative to the directory of the entry.
+  pub fn with_map_dir<D>(mut self, map_dir: D) -> Self
+  where
+    D: Into<PathBuf>,
+  {
+    self.map_dir = Some(map_dir.into());
+    self
+  }
+
+  /// Gets the shape of the bundle.
+  pub fn format(&self) -> Format {
+    self.format
+  }
+
+  /// Gets the global variable the exports of the entry are assigned to.
+  pub fn name(&self) -> Option<&str> {
+    self.name.as_deref()
+  }
+
+  /// Gets the options the bundle is printed with.
+  pub fn output(&self) -> &codegen::Options {
+    &self.output
+  }
+
+  /// Gets the directory the source map is written to, if it was set.
+  pub fn map_dir(&self) -> Option<&Path> {
+    self.map_dir.as_deref()
+  }
+}
+
+impl Default for Options {
+  fn default() -> Self {
+    Self {
+      format: Format::Script,
+      name: None,
+      output: codegen::Options::default(),
+      map_dir: None,
+    }
+  }
+}
+
+/// An error preventing a program from being bundled.
+#[derive(Debug)]
+pub enum BundleError {
+  /// A file could not be read.
+  Io(PathBuf, io::Error),
+  /// A module could not be parsed.
+  Parse(PathBuf, ParseError),
+  /// A `package.json` file is not valid JSON.
+  Manifest {
+    /// The path of the file.
+    path: PathBuf,
+    /// What is wrong.
+    message: &'static str,
+    /// The byte offset of the error in the file.
+    offset: usize,
+  },
+  /// An import specifier matches no file.
+  Unresolved {
+    /// The specifier.
+    specifier: Box<str>,
+    /// The path of the importing module.
+    from: PathBuf,
+  },
+  /// A name is imported from a module which does not export it.
+  MissingExport {
+    /// The imported name.
+    name: Box<str>,
+    /// The path of the imported module.
+    module: PathBuf,
+    /// The path of the importing module.
+    from: PathBuf,
+  },
+}
+
+impl fmt::Display for BundleError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Io(path, err) => write!(f, "{}: could not read the file: {}", path.display(), err),
+      Self::Parse(path, err) => {
+        write!(f, "{}: could not parse the module: {}", path.display(), err)
+      }
+      Self::Manifest {
+        path,
+        message,
+        offset,
+      } => write!(
+        f,
+        "{}: invalid JSON at byte {}: {}",
+        path.display(),
+        offset,
+        message
+      ),
+      Self::Unresolved { specifier, from } => {
+        write!(f, "{}: could not resolve `{}`", from.display(), specifier)
+      }
+      Self::MissingExport { name, module, from } => write!(
+        f,
+        "{}: `{}` is not exported by {}",
+        from.display(),
+        name,
+        module.display()
+      ),
+    }
+  }
+}
+
+impl StdError for BundleError {
+  fn source(&self) -> Option<&(dyn StdError + 'static)> {
+    match self {
+      Self::Io(_, err) => Some(err),
+      Self::Parse(_, err) => Some(err),
+      _ => None,
+    }
+  }
+}
+
+/// A bundled program, with its source map.
+#[derive(Debug, Clone)]
+pub struct Bundle {
+  code: String,
+  map: SourceMap,
+  modules: Vec<PathBuf>,
+  cycles: Vec<Vec<PathBuf>>,
+}
+
+impl Bundle {
+  /// Gets the code of the bundle.
+  pub fn code(&self) -> &str {
+    &self.code
+  }
+
+  /// Consumes the bundle, returning its code.
+  pub fn into_code(self) -> String {
+    self.code
+  }
+
+  /// Gets the source map from the bundle to the files of its modules, named relative to the
+  /// directory of the entry.
+  pub fn source_map(&self) -> &SourceMap {
+    &self.map
+  }
+
+  /// Gets the paths of the bundled modules, in the order they run.
+  pub fn modules(&self) -> &[PathBuf] {
+    &self.modules
+  }
+
+  /// Gets the cycles of imports, each one listed from the module imported back to the module
+  /// importing it.
+  pub fn cycles(&self) -> &[Vec<PathBuf>] {
+    &self.cycles
+  }
+}
+
+/// Bundles the module at `entry` with the modules it imports.
+pub fn bundle<P>(entry: P, options: &Options) -> Result<Bundle, BundleError>
+where
+  P: AsRef<Path>,
+{
+  let mut graph = Graph::load(entry.as_ref())?;
+  let linked = link::link(&mut graph)?;
+
+  let generator = Generator::new(options.output.clone());
+  let printed: Vec<String> = linked
+    .pieces
+    .iter()
+    .map(|piece| generator.script(&StatementList::from(piece.items.clone())))
+    .collect();
+  let mut items: Vec<Node> = linked
+    .pieces
+    .iter()
+    .flat_map(|piece| piece.items.iter().cloned())
+    .collect();
+  let code = match (options.format, &options.name) {
+    (Format::Script, _) => generator.script(&StatementList::from(items)),
+    (Format::Iife, name) => {
+      if name.is_some() && !linked.exports.is_empty() {
+        let properties: Vec<PropertyDefinition> = linked
+          .exports
+          .iter()
+          .map(|(name, binding)| {
+            PropertyDefinition::property(
+              PropertyName::Literal(name.as_ref().into()),
+              Identifier::from(binding.as_ref()),
+            )
+          })
+          .collect();
+        items.push(Return::new(Some(Object::from(properties))).into());
+      }
+      let function = FunctionExpr::new(None, Vec::<FormalParameter>::new(), items);
+      let call = Node::from(Call::new(function, Vec::<Node>::new()));
+      let statement = match name {
+        Some(name) => {
+          let declaration = Declaration::new(Identifier::from(name.as_ref()), Some(call));
+          DeclarationList::Var(vec![declaration].into()).into()
+        }
+        None => call,
+      };
+      generator.script(&StatementList::from(vec![statement]))
+    }
+  };
+
+  let root = match &options.map_dir {
+    Some(map_dir) => canonical_dir(map_dir),
+    None => graph.modules[0]
+      .path
+      .parent()
+      .map(Path::to_path_buf)
+      .unwrap_or_default(),
+  };
+  let sources: Vec<(String, &str)> = graph
+    .modules
+    .iter()
+    .map(|loaded| (relative(&root, &loaded.path), loaded.source.as_str()))
+    .collect();
+  let map = map::source_map(&code, &linked.pieces, &printed, &sources);
+
+  let path = |module: &usize| graph.modules[*module].path.clone();
+  Ok(Bundle {
+    code,
+    map,
+    modules: graph.order.iter().map(path).collect(),
+    cycles: graph
+      .cycles
+      .iter()
+      .map(|cycle| cycle.iter().map(path).collect())
+      .collect(),
+  })
+}
+
+/// Gets a directory as an absolute path without symbolic links, as the paths of the modules
+/// are, for as much of it as exists.
+fn canonical_dir(dir: &Path) -> PathBuf {
+  if let Ok(path) = fs::canonicalize(dir) {
+    return path;
+  }
+  match (dir.parent(), dir.file_name()) {
+    (Some(parent), Some(name)) if parent.as_os_str().is_empty() => {
+      canonical_dir(Path::new(".")).join(name)
+    }
+    (Some(parent), Some(name)) => canonical_dir(parent).join(name),
+    _ => dir.to_path_buf(),
+  }
+}
+
+/// Gets the path of a file relative to a directory, both absolute, with `/` separators. The
+/// path stays absolute if they have no common root, as on different drives.
+fn relative(dir: &Path, path: &Path) -> String {
+  let dir: Vec<Component<'_>> = dir.components().collect();
+  let path: Vec<Component<'_>> = path.components().collect();
+  let common = dir
+    .iter()
+    .zip(&path)
+    .take_while(|(dir, path)| dir == path)
+    .count();
+  let parts: Vec<String> = if common == 0 {
+    vec![path
+      .iter()
+      .collect::<PathBuf>()
+      .to_string_lossy()
+      .into_owned()]
+  } else {
+    let up = dir[common..].iter().map(|_| "..".to_owned());
+    let down = path[common..]
+      .iter()
+      .map(|component| component.as_os_str().to_string_lossy().into_owned());
+    up.chain(down).collect()
+  };
+  parts.join("/").replace('\\', "/")
+}
@@ -0,0 +1,188 @@
+//! Resolution of import specifiers to files, following the rules of Node.js.
+//!
+//! Relative and absolute specifiers name a file, tried as it is and then with the extensions of
+//! [`EXTENSIONS`], or a directory, with a `package.json` file or an `index` file. Bare
+//! specifiers, such as `lodash` or `@scope/pkg/sub`, name a package found in the
+//! `node_modules` directories of the importing directory and of its parents. A package is
+//! entered through the `exports` field of its `package.json` file, then through its `module`
+//! and `main` fields.
+
+use super::BundleError;
+use crate::syntax::json::{self, Value};
+use std::{
+  fs,
+  path::{Path, PathBuf},
+};
+
+/// The extensions tried for files named without one.
+const EXTENSIONS: [&str; 3] = ["js", "mjs", "ts"];
+
+/// The conditions of the `exports` field a bundled module matches, in any order in the field.
+const CONDITIONS: [&str; 3] = ["import", "module", "default"];
+
+/// Resolves an import specifier of the module at `from` to a file.
+pub(super) fn resolve(from: &Path, specifier: &str) -> Result<PathBuf, BundleError> {
+  let directory = from.parent().unwrap_or_else(|| Path::new(""));
+  let found = if is_path(specifier) {
+    file_or_directory(&directory.join(specifier))?
+  } else {
+    package(directory, specifier)?
+  };
+  found.ok_or_else(|| BundleError::Unresolved {
+    specifier: specifier.into(),
+    from: from.to_path_buf(),
+  })
+}
+
+/// Returns `true` if a specifier is a relative or an absolute path rather than a package.
+fn is_path(specifier: &str) -> bool {
+  specifier == "."
+    || specifier == ".."
+    || specifier.starts_with("./")
+    || specifier.starts_with("../")
+    || Path::new(specifier).is_absolute()
+}
+
+/// Finds the file a path names, as a file or as a directory.
+fn file_or_directory(path: &Path) -> Result<Option<PathBuf>, BundleError> {
+  match file(path) {
+    Some(file) => Ok(Some(file)),
+    None if path.is_dir() => directory(path),
+    None => Ok(None),
+  }
+}
+
+/// Finds a file, as it is named or with one of the extensions.
+fn file(path: &Path) -> Option<PathBuf> {
+  if path.is_file() {
+    return Some(path.to_path_buf());
+  }
+  EXTENSIONS
+    .iter()
+    .map(|extension| {
+      let mut name = path.as_os_str().to_owned();
+      name.push(".");
+      name.push(extension);
+      PathBuf::from(name)
+    })
+    .find(|path| path.is_file())
+}
+
+/// Finds the `index` file of a directory.
+fn index(path: &Path) -> Option<PathBuf> {
+  EXTENSIONS
+    .iter()
+    .map(|extension| path.join(format!("index.{}", extension)))
+    .find(|path| path.is_file())
+}
+
+/// Finds the main file of a directory: the file named by the `module` or `main` field of its
+/// `package.json` file, or its `index` file.
+fn directory(path: &Path) -> Result<Option<PathBuf>, BundleError> {
+  let manifest = manifest(path)?;
+  let main = ["module", "main"].iter().find_map(|field| {
+    match manifest.as_ref().and_then(|manifest| manifest.get(field)) {
+      Some(Value::String(main)) => {
+        let main = path.join(main);
+        file(&main).or_else(|| index(&main))
+      }
+      _ => None,
+    }
+  });
+  Ok(main.or_else(|| index(path)))
+}
+
+/// Finds a file of a package imported from the directory `from`, `lodash/fp` being the file
+/// `fp` of the package `lodash`.
+fn package(from: &Path, specifier: &str) -> Result<Option<PathBuf>, BundleError> {
+  // Scoped package names have two segments.
+  let segments = if specifier.starts_with('@') { 2 } else { 1 };
+  let end = specifier
+    .match_indices('/')
+    .nth(segments - 1)
+    .map_or(specifier.len(), |(end, _)| end);
+  let (name, subpath) = specifier.split_at(end);
+
+  let root = match from
+    .ancestors()
+    .map(|ancestor| ancestor.join("node_modules").join(name))
+    .find(|root| root.is_dir())
+  {
+    Some(root) => root,
+    None => return Ok(None),
+  };
+
+  let manifest = manifest(&root)?;
+  if let Some(exports) = manifest
+    .as_ref()
+    .and_then(|manifest| manifest.get("exports"))
+  {
+    // Only the files listed in `exports` can be imported, exactly as they are named.
+    let target = exports_target(exports, &format!(".{}", subpath));
+    return Ok(
+      target
+        .map(|target| root.join(target.trim_start_matches("./")))
+        .filter(|target| target.is_file()),
+    );
+  }
+  match subpath.strip_prefix('/') {
+    Some(subpath) => file_or_directory(&root.join(subpath)),
+    None => directory(&root),
+  }
+}
+
+/// Finds the target of the subpath `key`, such as `.` or `./sub`, in the `exports` field of a
+/// package.
+///
+/// The field maps subpaths to targets, or is the target of `.` itself. Subpaths can be
+/// patterns, where `*` stands for any text that is put in place of the `*` of the target.
+fn exports_target(exports: &Value, key: &str) -> Option<String> {
+  let subpaths = match exports {
+    Value::Object(members) if members.iter().any(|(name, _)| name.starts_with('.')) => members,
+    _ if key == "." => return conditional_target(exports),
+    _ => return None,
+  };
+  if let Some((_, target)) = subpaths.iter().find(|(name, _)| name == key) {
+    return conditional_target(target);
+  }
+  // The pattern with the longest prefix wins.
+  subpaths
+    .iter()
+    .filter_map(|(name, target)| {
+      let (prefix, suffix) = name.split_once('*')?;
+      let matched = key.strip_prefix(prefix)?.strip_suffix(suffix)?;
+      Some((prefix.len(), target, matched))
+    })
+    .max_by_key(|(prefix, _, _)| *prefix)
+    .and_then(|(_, target, matched)| Some(conditional_target(target)?.replace('*', matched)))
+}
+
+/// Gets a target of the `exports` field: a path, the first of an array of targets that
+/// matches, or the target of the first of its conditions in [`CONDITIONS`].
+fn conditional_target(target: &Value) -> Option<String> {
+  match target {
+    Value::String(path) => Some(path.clone()),
+    Value::Array(targets) => targets.iter().find_map(conditional_target),
+    Value::Object(conditions) => conditions
+      .iter()
+      .filter(|(condition, _)| CONDITIONS.contains(&condition.as_str()))
+      .find_map(|(_, target)| conditional_target(target)),
+    _ => None,
+  }
+}
+
+/// Reads the `package.json` file of a directory, if it has one.
+fn manifest(directory: &Path) -> Result<Option<Value>, BundleError> {
+  let path = directory.join("package.json");
+  if !path.is_file() {
+    return Ok(None);
+  }
+  let text = fs::read_to_string(&path).map_err(|err| BundleError::Io(path.clone(), err))?;
+  json::parse(&text)
+    .map(Some)
+    .map_err(|(message, offset)| BundleError::Manifest {
+      path,
+      message,
+      offset,
+    })
+}
@@ -0,0 +1,423 @@
+use super::{bundle, Bundle, BundleError, Format, Options};
+use crate::syntax::{ast::Position, codegen};
+use std::{
+  fs,
+  path::{Path, PathBuf},
+};
+
+/// A directory of files, removed when dropped.
+struct Project {
+  root: PathBuf,
+}
+
+impl Project {
+  fn new(name: &str, files: &[(&str, &str)]) -> Self {
+    let root = std::env::temp_dir().join(format!("plastic-bundle-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    for (path, content) in files {
+      let path = root.join(path);
+      fs::create_dir_all(path.parent().unwrap()).unwrap();
+      fs::write(path, content).unwrap();
+    }
+    Self { root }
+  }
+
+  fn bundle(&self, entry: &str, options: &Options) -> Result<Bundle, BundleError> {
+    bundle(self.root.join(entry), options)
+  }
+
+  /// Bundles a script in the compact format.
+  fn compact(&self, entry: &str) -> String {
+    let options = Options::default().with_output(codegen::Options::compact());
+    self.bundle(entry, &options).unwrap().into_code()
+  }
+
+  fn path(&self, path: &str) -> PathBuf {
+    fs::canonicalize(self.root.join(path)).unwrap()
+  }
+}
+
+impl Drop for Project {
+  fn drop(&mut self) {
+    let _ = fs::remove_dir_all(&self.root);
+  }
+}
+
+#[test]
+fn hoisting() {
+  let project = Project::new(
+    "hoisting",
+    &[
+      (
+        "main.js",
+        "import { count as total, increment as inc } from './counter.js';\n\
+         let count = 0;\n\
+         inc();\n\
+         console.log(total, { total }, count);\n",
+      ),
+      (
+        "counter.js",
+        "\"use strict\";\nexport let count = 0;\nexport function increment() { count++; }\n",
+      ),
+    ],
+  );
+  assert_eq!(
+    project.compact("main.js"),
+    "\"use strict\";let count$1=0;function increment(){count$1++}let count=0;increment();\
+     console.log(count$1,{total:count$1},count)"
+  );
+}
+
+#[test]
+fn shadowing() {
+  // `a` can not be the name of the binding of `lib.js` in `f`, nor `log` anywhere.
+  let project = Project::new(
+    "shadowing",
+    &[
+      (
+        "main.js",
+        "import { a as b } from './lib.js';\nfunction f(a) { return a + b; }\nf(log);\n",
+      ),
+      (
+        "lib.js",
+        "export const a = 1;\nconst log = 2;\nexport { log as other };\n",
+      ),
+    ],
+  );
+  assert_eq!(
+    project.compact("main.js"),
+    "\"use strict\";const a$1=1;const log$1=2;function f(a){return a+a$1}f(log)"
+  );
+}
+
+#[test]
+fn default_exports() {
+  let project = Project::new(
+    "default",
+    &[
+      (
+        "main.js",
+        "import value from './value.js';\nimport make from './make.js';\n\
+         import Named from './named.js';\nconsole.log(value, make(), new Named());\n",
+      ),
+      ("value.js", "export default 6 * 7;\n"),
+      ("make.js", "export default function () { return 1; }\n"),
+      ("named.js", "export default class Named {}\n"),
+    ],
+  );
+  assert_eq!(
+    project.compact("main.js"),
+    "\"use strict\";var value_default=6*7;function make_default(){return 1}class Named{}\
+     console.log(value_default,make_default(),new Named())"
+  );
+}
+
+#[test]
+fn namespaces_and_re_exports() {
+  let project = Project::new(
+    "namespaces",
+    &[
+      (
+        "main.js",
+        "import * as utils from './utils/index.js';\nimport { twice } from './utils/index.js';\n\
+         console.log(utils.twice(utils.one), twice(1), utils);\n",
+      ),
+      (
+        "utils/index.js",
+        "export * from './math.js';\nexport { one as default, one as \"the one\" } from './math.js';\n",
+      ),
+      (
+        "utils/math.js",
+        "export const one = 1;\nexport function twice(x) { return x * 2; }\n",
+      ),
+    ],
+  );
+  assert_eq!(
+    project.compact("main.js"),
+    "\"use strict\";var utils=Object.freeze({__proto__:null,get default(){return one},\
+     get \"the one\"(){return one},get one(){return one},get twice(){return twice}});\
+     const one=1;function twice(x){return x*2}console.log(utils.twice(utils.one),twice(1),utils)"
+  );
+}
+
+#[test]
+fn node_modules() {
+  let project = Project::new(
+    "packages",
+    &[
+      (
+        "src/main.js",
+        "import a from 'main-field';\nimport b from 'module-field';\n\
+         import c from '@scope/exports';\nimport d from '@scope/exports/feature';\n\
+         import e from 'main-field/lib/extra';\nconsole.log(a, b, c, d, e);\n",
+      ),
+      (
+        "node_modules/main-field/package.json",
+        "{ \"main\": \"./lib/main\" }",
+      ),
+      (
+        "node_modules/main-field/lib/main.js",
+        "export default 'a';\n",
+      ),
+      (
+        "node_modules/main-field/lib/extra/index.js",
+        "export default 'e';\n",
+      ),
+      (
+        "node_modules/module-field/package.json",
+        "{ \"main\": \"cjs.js\", \"module\": \"esm.mjs\" }",
+      ),
+      ("node_modules/module-field/esm.mjs", "export default 'b';\n"),
+      (
+        "node_modules/@scope/exports/package.json",
+        "{\n  \"main\": \"nope.js\",\n  \"exports\": {\n    \
+         \".\": { \"require\": \"./nope.js\", \"import\": \"./dist/index.js\" },\n    \
+         \"./*\": \"./dist/features/*.js\"\n  }\n}",
+      ),
+      (
+        "node_modules/@scope/exports/dist/index.js",
+        "export default 'c';\n",
+      ),
+      (
+        "node_modules/@scope/exports/dist/features/feature.js",
+        "export default 'd';\n",
+      ),
+    ],
+  );
+  let bundled = project.bundle("src/main.js", &Options::default()).unwrap();
+  let modules: Vec<_> = ["main-field/lib/main.js", "module-field/esm.mjs"]
+    .iter()
+    .map(|path| project.path(&format!("node_modules/{}", path)))
+    .collect();
+  assert_eq!(&bundled.modules()[..2], &modules[..]);
+  assert_eq!(bundled.modules().len(), 6);
+  assert!(bundled.code().ends_with(
+    "var main_default = \"a\";\nvar esm_default = \"b\";\nvar dist_default = \"c\";\n\
+     var feature_default = \"d\";\nvar extra_default = \"e\";\nconsole.log(\n  main_default,\n  \
+     esm_default,\n  dist_default,\n  feature_default,\n  extra_default,\n);\n"
+  ));
+
+  let private = Project::new(
+    "private",
+    &[
+      ("main.js", "import '@scope/exports/dist/index.js';\n"),
+      (
+        "node_modules/@scope/exports/package.json",
+        "{ \"exports\": \"./dist/index.js\" }",
+      ),
+      ("node_modules/@scope/exports/dist/index.js", ""),
+    ],
+  );
+  let err = private.bundle("main.js", &Options::default()).unwrap_err();
+  assert!(matches!(
+    err,
+    BundleError::Unresolved { ref specifier, .. } if &**specifier == "@scope/exports/dist/index.js"
+  ));
+}
+
+#[test]
+fn typescript() {
+  let project = Project::new(
+    "typescript",
+    &[
+      (
+        "main.ts",
+        "import { Shape, area } from './shape';\nconst square: Shape = { side: 2 };\n\
+         console.log(area(square));\n",
+      ),
+      (
+        "shape.ts",
+        "export interface Shape { side: number }\n\
+         export function area(shape: Shape): number { return shape.side ** 2; }\n",
+      ),
+    ],
+  );
+  assert_eq!(
+    project.compact("main.ts"),
+    "\"use strict\";function area(shape){return shape.side**2}const square={side:2};\
+     console.log(area(square))"
+  );
+}
+
+#[test]
+fn cycles() {
+  let project = Project::new(
+    "cycles",
+    &[
+      (
+        "main.js",
+        "import { even } from './even.js';\nconsole.log(even(4));\n",
+      ),
+      (
+        "even.js",
+        "import { odd } from './odd.js';\n\
+         export function even(n) { return n === 0 || odd(n - 1); }\n",
+      ),
+      (
+        "odd.js",
+        "import { even } from './even.js';\n\
+         export function odd(n) { return n !== 0 && even(n - 1); }\n",
+      ),
+    ],
+  );
+  let bundled = project.bundle("main.js", &Options::default()).unwrap();
+  assert_eq!(
+    bundled.cycles(),
+    &[vec![project.path("even.js"), project.path("odd.js")]]
+  );
+  assert_eq!(
+    bundled.modules(),
+    &[
+      project.path("odd.js"),
+      project.path("even.js"),
+      project.path("main.js")
+    ]
+  );
+}
+
+#[test]
+fn iife() {
+  let project = Project::new(
+    "iife",
+    &[
+      (
+        "lib.js",
+        "import * as helpers from './helpers.js';\nexport const version = '1.0';\n\
+         export { helpers, version as \"v\" };\nexport default function greet() {}\n",
+      ),
+      ("helpers.js", "export const id = (x) => x;\n"),
+    ],
+  );
+  let compact = Options::default()
+    .with_format(Format::Iife)
+    .with_output(codegen::Options::compact());
+  assert_eq!(
+    project.bundle("lib.js", &compact).unwrap().code(),
+    "(function(){\"use strict\";var helpers=Object.freeze({__proto__:null,get id(){return id}});\
+     const id=x=>x;const version=\"1.0\";function greet(){}})()"
+  );
+  assert_eq!(
+    project
+      .bundle("lib.js", &compact.with_name("Lib"))
+      .unwrap()
+      .code(),
+    "var Lib=function(){\"use strict\";var helpers=Object.freeze({__proto__:null,get id(){return id}});\
+     const id=x=>x;const version=\"1.0\";function greet(){}return{version:version,helpers:helpers,\
+     v:version,default:greet}}()"
+  );
+}
+
+#[test]
+fn errors() {
+  let project = Project::new(
+    "errors",
+    &[
+      ("missing.js", "import { nope } from './lib.js';\n"),
+      ("unresolved.js", "import './nowhere.js';\n"),
+      ("invalid.js", "import './lib.js';\nlet let = 1;\n"),
+      ("manifest.js", "import 'broken';\n"),
+      ("lib.js", "export const yes = 1;\n"),
+      ("node_modules/broken/package.json", "{ \"main\": }"),
+    ],
+  );
+  let err = project
+    .bundle("missing.js", &Options::default())
+    .unwrap_err();
+  assert_eq!(
+    err.to_string(),
+    format!(
+      "{}: `nope` is not exported by {}",
+      project.path("missing.js").display(),
+      project.path("lib.js").display()
+    )
+  );
+  let err = project
+    .bundle("unresolved.js", &Options::default())
+    .unwrap_err();
+  assert_eq!(
+    err.to_string(),
+    format!(
+      "{}: could not resolve `./nowhere.js`",
+      project.path("unresolved.js").display()
+    )
+  );
+  let err = project
+    .bundle("invalid.js", &Options::default())
+    .unwrap_err();
+  assert!(matches!(err, BundleError::Parse(ref path, _) if *path == project.path("invalid.js")));
+  let err = project
+    .bundle("manifest.js", &Options::default())
+    .unwrap_err();
+  assert!(matches!(err, BundleError::Manifest { offset: 10, .. }));
+  let err = project
+    .bundle("nothing.js", &Options::default())
+    .unwrap_err();
+  assert!(
+    matches!(err, BundleError::Io(ref path, _) if path == Path::new(&project.root.join("nothing.js")))
+  );
+}
+
+#[test]
+fn source_map() {
+  let project = Project::new(
+    "map",
+    &[
+      (
+        "main.js",
+        "import { value } from './lib/value.js';\nconst value$1 = 1;\nlog(value);\n",
+      ),
+      ("lib/value.js", "export const value = 'forty-two';\n"),
+    ],
+  );
+  let bundled = project.bundle("main.js", &Options::default()).unwrap();
+  assert_eq!(
+    bundled.code(),
+    "\"use strict\";\nconst value = \"forty-two\";\nconst value$1 = 1;\nlog(value);\n"
+  );
+  let map = bundled.source_map();
+  assert_eq!(&*map.sources()[0], "main.js");
+  assert_eq!(&*map.sources()[1], "lib/value.js");
+
+  // `"use strict"` is added.
+  assert!(map.lookup(Position::new(1, 1)).is_none());
+  let literal = map.lookup(Position::new(2, 15)).unwrap();
+  assert_eq!(literal.source(), "lib/value.js");
+  assert_eq!(literal.position(), Position::new(1, 22));
+  // The imported binding is traced back to the import.
+  let reference = map.lookup(Position::new(4, 5)).unwrap();
+  assert_eq!(reference.source(), "main.js");
+  assert_eq!(reference.position(), Position::new(3, 5));
+  assert_eq!(reference.name(), Some("value"));
+}
+
+#[test]
+fn source_map_paths() {
+  let project = Project::new(
+    "map-paths",
+    &[
+      (
+        "src/main.js",
+        "import { value } from '../shared/value.js';\nlog(value);\n",
+      ),
+      ("shared/value.js", "export const value = 1;\n"),
+    ],
+  );
+  // The sources are relative to the directory of the entry by default, even outside of it.
+  let bundled = project.bundle("src/main.js", &Options::default()).unwrap();
+  let map = bundled.source_map();
+  assert_eq!(&*map.sources()[0], "main.js");
+  assert_eq!(&*map.sources()[1], "../shared/value.js");
+
+  // Or relative to the directory of the map, which may not exist yet.
+  let options = Options::default().with_map_dir(project.root.join("dist/maps"));
+  let bundled = project.bundle("src/main.js", &options).unwrap();
+  let map = bundled.source_map();
+  assert_eq!(&*map.sources()[0], "../../src/main.js");
+  assert_eq!(&*map.sources()[1], "../../shared/value.js");
+
+  let options = Options::default().with_map_dir(&project.root);
+  let bundled = project.bundle("src/main.js", &options).unwrap();
+  let map = bundled.source_map();
+  assert_eq!(&*map.sources()[0], "src/main.js");
+  assert_eq!(&*map.sources()[1], "shared/value.js");
+}
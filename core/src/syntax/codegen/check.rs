@@ -0,0 +1,214 @@
+//! Checking that source files are formatted, for continuous integration.
+
+use super::{format_module, format_script, Options};
+use crate::syntax::parser::ParseError;
+use std::{
+  error::Error as StdError,
+  fmt, fs, io,
+  path::{Path, PathBuf},
+};
+
+/// Whether a source text is a script or a module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceType {
+  /// A classic script.
+  Script,
+  /// An ES module.
+  Module,
+}
+
+impl SourceType {
+  /// Gets the source type implied by the extension of a file: `.mjs` files are modules and
+  /// `.cjs` files are scripts.
+  pub fn from_path<P>(path: P) -> Option<Self>
+  where
+    P: AsRef<Path>,
+  {
+    match path.as_ref().extension()?.to_str()? {
+      "mjs" => Some(Self::Module),
+      "cjs" => Some(Self::Script),
+      _ => None,
+    }
+  }
+}
+
+/// The first line where a source text differs from its formatted version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+  line: usize,
+  expected: Box<str>,
+  found: Box<str>,
+}
+
+impl Mismatch {
+  /// Gets the number of the line, starting at 1.
+  pub fn line(&self) -> usize {
+    self.line
+  }
+
+  /// Gets the formatted line.
+  pub fn expected(&self) -> &str {
+    &self.expected
+  }
+
+  /// Gets the line of the source text.
+  pub fn found(&self) -> &str {
+    &self.found
+  }
+}
+
+impl fmt::Display for Mismatch {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "line {}: expected `{}`, found `{}`",
+      self.line, self.expected, self.found
+    )
+  }
+}
+
+/// Checks whether a source text is formatted, its comments included, returning the first
+/// mismatch if it is not.
+pub fn check(
+  source: &str,
+  source_type: SourceType,
+  options: &Options,
+) -> Result<Option<Mismatch>, ParseError> {
+  let formatted = match source_type {
+    SourceType::Script => format_script(source, options)?,
+    SourceType::Module => format_module(source, options)?,
+  };
+  Ok(first_mismatch(source, &formatted))
+}
+
+/// Finds the first line where two texts differ.
+fn first_mismatch(source: &str, formatted: &str) -> Option<Mismatch> {
+  if source == formatted {
+    return None;
+  }
+  let mut found = source.split('\n');
+  let mut expected = formatted.split('\n');
+  let mut line = 1;
+  loop {
+    match (expected.next(), found.next()) {
+      (Some(expected), Some(found)) if expected == found => line += 1,
+      (expected, found) => {
+        return Some(Mismatch {
+          line,
+          expected: expected.unwrap_or_default().into(),
+          found: found.unwrap_or_default().into(),
+        })
+      }
+    }
+  }
+}
+
+/// An error preventing a file from being checked.
+#[derive(Debug)]
+pub enum CheckError {
+  /// The file could not be read.
+  Io(io::Error),
+  /// The file could not be parsed.
+  Parse(ParseError),
+}
+
+impl fmt::Display for CheckError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Io(err) => write!(f, "could not read the file: {}", err),
+      Self::Parse(err) => write!(f, "could not parse the file: {}", err),
+    }
+  }
+}
+
+impl StdError for CheckError {
+  fn source(&self) -> Option<&(dyn StdError + 'static)> {
+    match self {
+      Self::Io(err) => Some(err),
+      Self::Parse(err) => Some(err),
+    }
+  }
+}
+
+/// The result of checking the formatting of files.
+#[derive(Debug, Default)]
+pub struct Report {
+  checked: usize,
+  unformatted: Vec<(PathBuf, Mismatch)>,
+  errors: Vec<(PathBuf, CheckError)>,
+}
+
+impl Report {
+  /// Gets the number of files checked, including those with errors.
+  pub fn checked(&self) -> usize {
+    self.checked
+  }
+
+  /// Gets the files whose formatting differs, with their first mismatch.
+  pub fn unformatted(&self) -> &[(PathBuf, Mismatch)] {
+    &self.unformatted
+  }
+
+  /// Gets the files that could not be checked.
+  pub fn errors(&self) -> &[(PathBuf, CheckError)] {
+    &self.errors
+  }
+
+  /// Returns `true` if all the files are formatted.
+  pub fn is_ok(&self) -> bool {
+    self.unformatted.is_empty() && self.errors.is_empty()
+  }
+}
+
+/// Lists the unformatted files and the errors, one per line, as in
+/// `src/main.js:3: formatting differs, expected ...`.
+impl fmt::Display for Report {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (path, mismatch) in &self.unformatted {
+      writeln!(
+        f,
+        "{}:{}: formatting differs, expected `{}`, found `{}`",
+        path.display(),
+        mismatch.line,
+        mismatch.expected,
+        mismatch.found
+      )?;
+    }
+    for (path, err) in &self.errors {
+      writeln!(f, "{}: {}", path.display(), err)?;
+    }
+    Ok(())
+  }
+}
+
+/// Checks the formatting of files.
+///
+/// Files are parsed as modules or scripts according to their extension, see
+/// [`SourceType::from_path`]. Other files are parsed as modules, and as scripts if that fails.
+pub fn check_files<I, P>(paths: I, options: &Options) -> Report
+where
+  I: IntoIterator<Item = P>,
+  P: AsRef<Path>,
+{
+  let mut report = Report::default();
+  for path in paths {
+    let path = path.as_ref();
+    report.checked += 1;
+    let result = fs::read_to_string(path)
+      .map_err(CheckError::Io)
+      .and_then(|source| {
+        match SourceType::from_path(path) {
+          Some(source_type) => check(&source, source_type, options),
+          None => check(&source, SourceType::Module, options)
+            .or_else(|_| check(&source, SourceType::Script, options)),
+        }
+        .map_err(CheckError::Parse)
+      });
+    match result {
+      Ok(None) => {}
+      Ok(Some(mismatch)) => report.unformatted.push((path.to_owned(), mismatch)),
+      Err(err) => report.errors.push((path.to_owned(), err)),
+    }
+  }
+  report
+}
@@ -0,0 +1,266 @@
+//! Documents: the generated code before it is laid out on lines.
+//!
+//! The printer turns the syntax tree into a [`Doc`], where the places the code may be broken over
+//! several lines are marked with [`Doc::Line`] and [`Doc::SoftLine`]. The layout then prints each
+//! [`Doc::Group`] on a single line if it fits in the line width, and breaks all of its lines
+//! otherwise, as described in Philip Wadler's "A prettier printer".
+
+use std::borrow::Cow;
+
+/// The generated code, with the places it can be broken over several lines.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Doc {
+  /// Nothing.
+  Nil,
+  /// Text without new lines.
+  Text(Cow<'static, str>),
+  /// A sequence of documents.
+  Concat(Vec<Doc>),
+  /// A document whose broken lines are indented by one more level.
+  Indent(Box<Doc>),
+  /// A document printed on a single line if it fits, or with all of its lines broken.
+  Group(Box<Doc>),
+  /// A space, or a new line if the enclosing group is broken.
+  Line,
+  /// Nothing, or a new line if the enclosing group is broken.
+  SoftLine,
+  /// A new line, which breaks all the enclosing groups.
+  HardLine,
+  /// The first document if the enclosing group is broken, the second one otherwise.
+  IfBreak(Box<Doc>, Box<Doc>),
+  /// Text printed at the end of the line, before the next new line, such as a line comment
+  /// after an expression followed by a comma. It breaks all the enclosing groups.
+  LineSuffix(Cow<'static, str>),
+}
+
+impl Doc {
+  /// Creates a text document.
+  pub(super) fn text<T>(text: T) -> Self
+  where
+    T: Into<Cow<'static, str>>,
+  {
+    Self::Text(text.into())
+  }
+
+  /// Creates an indented document.
+  pub(super) fn indent(doc: Self) -> Self {
+    Self::Indent(Box::new(doc))
+  }
+
+  /// Creates a group.
+  pub(super) fn group(doc: Self) -> Self {
+    Self::Group(Box::new(doc))
+  }
+
+  /// Creates a document only printed if the enclosing group is broken.
+  pub(super) fn if_break(doc: Self) -> Self {
+    Self::IfBreak(Box::new(doc), Box::new(Self::Nil))
+  }
+
+  /// Joins documents with a separator.
+  pub(super) fn join<I>(docs: I, separator: &Self) -> Self
+  where
+    I: IntoIterator<Item = Self>,
+  {
+    let mut joined = Vec::new();
+    for doc in docs {
+      if !joined.is_empty() {
+        joined.push(separator.clone());
+      }
+      joined.push(doc);
+    }
+    Self::Concat(joined)
+  }
+
+  /// Gets the first character printed by the document, if it prints any.
+  pub(super) fn first_char(&self) -> Option<char> {
+    match self {
+      Self::Text(text) => text.chars().next(),
+      Self::Concat(docs) => docs.iter().find_map(Self::first_char),
+      Self::Indent(doc) | Self::Group(doc) => doc.first_char(),
+      Self::Nil
+      | Self::Line
+      | Self::SoftLine
+      | Self::HardLine
+      | Self::IfBreak(..)
+      | Self::LineSuffix(_) => None,
+    }
+  }
+}
+
+impl From<&'static str> for Doc {
+  fn from(text: &'static str) -> Self {
+    Self::text(text)
+  }
+}
+
+impl From<String> for Doc {
+  fn from(text: String) -> Self {
+    Self::text(text)
+  }
+}
+
+impl From<Vec<Doc>> for Doc {
+  fn from(docs: Vec<Doc>) -> Self {
+    Self::Concat(docs)
+  }
+}
+
+/// Whether the lines of a document are broken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+  Flat,
+  Break,
+}
+
+/// Lays out a document in the given line width, indenting lines with `indent`.
+pub(super) fn layout(doc: &Doc, width: usize, indent: &str) -> String {
+  let mut layout = Layout {
+    out: String::new(),
+    column: 0,
+    width,
+    indent,
+    suffix: Vec::new(),
+  };
+  layout.print(doc);
+  layout.flush(0);
+  layout.out
+}
+
+/// The state of the layout of a document.
+struct Layout<'a> {
+  out: String,
+  column: usize,
+  width: usize,
+  indent: &'a str,
+  /// The texts printed before the next new line, each on its own line after the first.
+  suffix: Vec<String>,
+}
+
+impl Layout<'_> {
+  /// Prints a document, keeping track of the pending documents in a stack.
+  fn print(&mut self, doc: &Doc) {
+    let mut stack = vec![(0, Mode::Break, doc)];
+    while let Some((level, mode, doc)) = stack.pop() {
+      match doc {
+        Doc::Nil => {}
+        Doc::Text(text) => self.text(text),
+        Doc::Concat(docs) => stack.extend(docs.iter().rev().map(|doc| (level, mode, doc))),
+        Doc::Indent(doc) => stack.push((level + 1, mode, doc)),
+        Doc::Group(doc) => {
+          let mode = match mode {
+            Mode::Flat => Mode::Flat,
+            Mode::Break if self.fits(doc, &stack) => Mode::Flat,
+            Mode::Break => Mode::Break,
+          };
+          stack.push((level, mode, doc));
+        }
+        Doc::Line | Doc::SoftLine if mode == Mode::Break => self.newline(level),
+        Doc::Line => self.text(" "),
+        Doc::SoftLine => {}
+        Doc::HardLine => self.newline(level),
+        Doc::IfBreak(broken, flat) => {
+          stack.push((level, mode, if mode == Mode::Break { broken } else { flat }))
+        }
+        Doc::LineSuffix(text) => self.suffix.push(text.to_string()),
+      }
+    }
+  }
+
+  /// Checks whether a group fits on the rest of the current line, followed by the pending
+  /// documents up to their next line break.
+  fn fits(&self, group: &Doc, pending: &[(usize, Mode, &Doc)]) -> bool {
+    let mut remaining = self.width.saturating_sub(self.column) as isize;
+    let mut stack = vec![(Mode::Flat, group)];
+    let mut pending = pending.iter().rev();
+    loop {
+      let (mode, doc) = match stack.pop() {
+        Some(next) => next,
+        None => match pending.next() {
+          Some(&(_, mode, doc)) => (mode, doc),
+          None => return true,
+        },
+      };
+      match doc {
+        Doc::Nil => {}
+        Doc::Text(text) => remaining -= text.chars().count() as isize,
+        Doc::Concat(docs) => stack.extend(docs.iter().rev().map(|doc| (mode, doc))),
+        Doc::Indent(doc) | Doc::Group(doc) => stack.push((mode, doc)),
+        Doc::Line | Doc::SoftLine if mode == Mode::Break => return true,
+        Doc::Line => remaining -= 1,
+        Doc::SoftLine => {}
+        // A hard line in the group itself forces it to break, and so does a line suffix, which
+        // ends the line.
+        Doc::HardLine | Doc::LineSuffix(_) => return mode == Mode::Break,
+        Doc::IfBreak(broken, flat) => {
+          stack.push((mode, if mode == Mode::Break { broken } else { flat }))
+        }
+      }
+      if remaining < 0 {
+        return false;
+      }
+    }
+  }
+
+  /// Prints text, separating it from the previous text with a space if the two would otherwise
+  /// be read as a single token, as in `a in b` or `a - -b`.
+  fn text(&mut self, text: &str) {
+    if let (Some(last), Some(first)) = (self.out.chars().next_back(), text.chars().next()) {
+      if tokens_merge(last, first) {
+        self.out.push(' ');
+        self.column += 1;
+      }
+    }
+    self.out.push_str(text);
+    self.column += text.chars().count();
+  }
+
+  /// Prints the pending line suffixes, the ones after the first on new lines at an indentation
+  /// level, since a line comment ends its line.
+  fn flush(&mut self, level: usize) {
+    for (index, text) in std::mem::take(&mut self.suffix).iter().enumerate() {
+      let blank = self
+        .out
+        .rsplit('\n')
+        .next()
+        .is_some_and(|line| line.trim().is_empty());
+      let text = if index == 0 && !blank {
+        text.as_str()
+      } else if index == 0 {
+        text.trim_start()
+      } else {
+        self.line_break(level);
+        text.trim_start()
+      };
+      self.out.push_str(text);
+      self.column += text.chars().count();
+    }
+  }
+
+  /// Starts a new line after the pending line suffixes.
+  fn newline(&mut self, level: usize) {
+    self.flush(level);
+    self.line_break(level);
+  }
+
+  /// Starts a new line, without trailing spaces on the previous one.
+  fn line_break(&mut self, level: usize) {
+    let trimmed = self.out.trim_end_matches([' ', '\t']).len();
+    self.out.truncate(trimmed);
+    self.out.push('\n');
+    for _ in 0..level {
+      self.out.push_str(self.indent);
+    }
+    self.column = level * self.indent.chars().count();
+  }
+}
+
+/// Checks whether two characters would be read as part of the same token if they were adjacent.
+fn tokens_merge(last: char, first: char) -> bool {
+  let word = |c: char| c.is_alphanumeric() || c == '_' || c == '$' || c == '\\';
+  (word(last) && word(first))
+    || matches!(
+      (last, first),
+      ('+', '+') | ('-', '-') | ('/', '/') | ('/', '*') | ('<', '!')
+    )
+}
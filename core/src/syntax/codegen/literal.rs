@@ -0,0 +1,69 @@
+//! Printing of string and numeric literals.
+
+use super::Quotes;
+use std::fmt::Write;
+
+/// Prints a string literal, escaping the characters that can not appear in it as is.
+///
+/// The preferred quotes are used unless the string contains more of them than of the other
+/// quotes, so that `"it's"` keeps its double quotes with [`Quotes::Single`].
+pub(super) fn string(value: &str, preferred: Quotes) -> String {
+  let singles = value.matches('\'').count();
+  let doubles = value.matches('"').count();
+  let quote = match preferred {
+    Quotes::Double if doubles > singles => '\'',
+    Quotes::Single if singles > doubles => '"',
+    Quotes::Double => '"',
+    Quotes::Single => '\'',
+  };
+
+  let mut out = String::with_capacity(value.len() + 2);
+  out.push(quote);
+  let mut chars = value.chars().peekable();
+  while let Some(ch) = chars.next() {
+    match ch {
+      '\\' => out.push_str("\\\\"),
+      '\n' => out.push_str("\\n"),
+      '\r' => out.push_str("\\r"),
+      '\t' => out.push_str("\\t"),
+      '\x08' => out.push_str("\\b"),
+      '\x0c' => out.push_str("\\f"),
+      // `\0` followed by a digit would be a legacy octal escape.
+      '\0' if !matches!(chars.peek(), Some(next) if next.is_ascii_digit()) => out.push_str("\\0"),
+      '\u{2028}' | '\u{2029}' => write!(out, "\\u{:04x}", u32::from(ch)).expect("infallible"),
+      ch if ch.is_control() && u32::from(ch) <= 0xff => {
+        write!(out, "\\x{:02x}", u32::from(ch)).expect("infallible")
+      }
+      ch if ch == quote => {
+        out.push('\\');
+        out.push(ch);
+      }
+      ch => out.push(ch),
+    }
+  }
+  out.push(quote);
+  out
+}
+
+/// Prints a number in its shortest form, such as `1e21` rather than `1000000000000000000000`.
+///
+/// Infinities are printed as `1e999`, which is too large to be represented, and `NaN` as the
+/// global of the same name.
+pub(super) fn number(value: f64) -> String {
+  if value.is_nan() {
+    return "NaN".to_owned();
+  }
+  if value.is_infinite() {
+    return if value > 0.0 { "1e999" } else { "-1e999" }.to_owned();
+  }
+  if value == 0.0 {
+    return if value.is_sign_negative() { "-0" } else { "0" }.to_owned();
+  }
+  let decimal = value.to_string();
+  let exponent = format!("{:e}", value);
+  if exponent.len() < decimal.len() {
+    exponent
+  } else {
+    decimal
+  }
+}
@@ -0,0 +1,341 @@
+//! Code generation: printing the syntax tree back as JavaScript source text.
+//!
+//! A [`Generator`] prints scripts, modules and expressions with the [`Options`] it was created
+//! with. The output only has the parentheses that the precedence of the operators requires, and
+//! re-parsing it gives back the same syntax tree.
+//!
+//! There are two styles of output:
+//!
+//!  - [`Options::pretty`], the default, prints one statement per line and breaks lists, calls
+//!    and operations that do not fit in the line width, much like Prettier. The indentation,
+//!    quotes, semicolons and trailing commas are configurable.
+//!  - [`Options::compact`] prints everything on a single line without optional spaces, as a
+//!    starting point for minification.
+//!
+//! ```
+//! use plastic::syntax::{codegen, parser::Parser};
+//!
+//! let ast = Parser::new("let a=[1,2]\nif(a)b()".as_bytes()).parse_script().unwrap();
+//! let generator = codegen::Generator::new(codegen::Options::pretty());
+//! assert_eq!(generator.script(&ast), "let a = [1, 2];\nif (a) b();\n");
+//! ```
+//!
+//! The syntax tree does not record comments, but a [`Comments`] implementation, such as a
+//! [`CommentMap`], can provide them for the parts of the tree, and they are printed along with
+//! them.
+//!
+//! [`format_script`] and [`format_module`] format source text, keeping all of its comments, and
+//! [`check_files`] reports the files whose formatting differs from the output, for continuous
+//! integration.
+
+mod check;
+mod doc;
+mod literal;
+mod precedence;
+mod printer;
+#[cfg(test)]
+mod tests;
+
+pub use self::check::{check, check_files, CheckError, Mismatch, Report, SourceType};
+
+use self::printer::Printer;
+use crate::syntax::{
+  ast::{module::Module, node::StatementList, Node, Span},
+  comments::CommentMap,
+  parser::{ParseError, Parser},
+};
+
+/// The indentation of pretty printed code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indent {
+  /// A number of spaces per level.
+  Spaces(u8),
+  /// A tab per level.
+  Tab,
+}
+
+/// The quotes of string literals.
+///
+/// The other quotes are used for strings that contain more of the preferred ones, so that they
+/// need fewer escapes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quotes {
+  /// `"double"` quotes.
+  Double,
+  /// `'single'` quotes.
+  Single,
+}
+
+/// The style of the generated code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+  compact: bool,
+  indent: Indent,
+  quotes: Quotes,
+  semicolons: bool,
+  trailing_commas: bool,
+  line_width: usize,
+}
+
+impl Options {
+  /// Creates options for pretty printed code: two spaces of indentation, double quotes,
+  /// semicolons, trailing commas in broken lists and a line width of 80 columns.
+  pub fn pretty() -> Self {
+    Self {
+      compact: false,
+      indent: Indent::Spaces(2),
+      quotes: Quotes::Double,
+      semicolons: true,
+      trailing_commas: true,
+      line_width: 80,
+    }
+  }
+
+  /// Creates options for code on a single line without optional spaces and semicolons.
+  ///
+  /// Only the quotes apply to compact code.
+  pub fn compact() -> Self {
+    Self {
+      compact: true,
+      ..Self::pretty()
+    }
+  }
+
+  /// Sets the indentation.
+  pub fn with_indent(mut self, indent: Indent) -> Self {
+    self.indent = indent;
+    self
+  }
+
+  /// Sets the preferred quotes.
+  pub fn with_quotes(mut self, quotes: Quotes) -> Self {
+    self.quotes = quotes;
+    self
+  }
+
+  /// Sets whether statements end with semicolons.
+  ///
+  /// Without semicolons, statements that would continue the previous one, such as those starting
+  /// with `(` or `[`, start with a semicolon instead.
+  pub fn with_semicolons(mut self, semicolons: bool) -> Self {
+    self.semicolons = semicolons;
+    self
+  }
+
+  /// Sets whether lists broken over several lines have a trailing comma.
+  pub fn with_trailing_commas(mut self, trailing_commas: bool) -> Self {
+    self.trailing_commas = trailing_commas;
+    self
+  }
+
+  /// Sets the line width, past which lists and operations are broken over several lines.
+  pub fn with_line_width(mut self, line_width: usize) -> Self {
+    self.line_width = line_width;
+    self
+  }
+
+  /// Returns `true` for compact code.
+  pub fn is_compact(&self) -> bool {
+    self.compact
+  }
+
+  /// Gets the indentation.
+  pub fn indent(&self) -> Indent {
+    self.indent
+  }
+
+  /// Gets the preferred quotes.
+  pub fn quotes(&self) -> Quotes {
+    self.quotes
+  }
+
+  /// Returns `true` if statements end with semicolons.
+  pub fn semicolons(&self) -> bool {
+    self.semicolons
+  }
+
+  /// Returns `true` if lists broken over several lines have a trailing comma.
+  pub fn trailing_commas(&self) -> bool {
+    self.trailing_commas
+  }
+
+  /// Gets the line width.
+  pub fn line_width(&self) -> usize {
+    self.line_width
+  }
+}
+
+impl Default for Options {
+  fn default() -> Self {
+    Self::pretty()
+  }
+}
+
+/// The kind of a comment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentKind {
+  /// A `// line` comment.
+  Line,
+  /// A `/* block */` comment.
+  Block,
+}
+
+/// A comment, printed along with the part of the syntax tree it is attached to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comment {
+  kind: CommentKind,
+  text: Box<str>,
+}
+
+impl Comment {
+  /// Creates a line comment, its text being everything after the `//`.
+  pub fn line<T>(text: T) -> Self
+  where
+    T: Into<Box<str>>,
+  {
+    Self {
+      kind: CommentKind::Line,
+      text: text.into(),
+    }
+  }
+
+  /// Creates a block comment, its text being everything between `/*` and `*/`.
+  pub fn block<T>(text: T) -> Self
+  where
+    T: Into<Box<str>>,
+  {
+    Self {
+      kind: CommentKind::Block,
+      text: text.into(),
+    }
+  }
+
+  /// Gets the kind of the comment.
+  pub fn kind(&self) -> CommentKind {
+    self.kind
+  }
+
+  /// Gets the text of the comment, without its delimiters.
+  pub fn text(&self) -> &str {
+    &self.text
+  }
+}
+
+/// The comments attached to the parts of a syntax tree, known by their spans.
+///
+/// The parts are the nodes, the elements of class bodies with the constructors of classes, the
+/// properties of object literals, the cases of `switch` statements, the blocks and `catch`
+/// clauses of `try` statements, the declarations and the formal parameters.
+pub trait Comments {
+  /// Gets the comments printed before a part: on the lines before a statement or a member, and
+  /// on the same line before an expression.
+  fn leading(&self, span: Span) -> &[Comment];
+
+  /// Gets the comments printed after a part, on the same line.
+  fn trailing(&self, _span: Span) -> &[Comment] {
+    &[]
+  }
+
+  /// Gets the comments printed inside a part, on their own lines after its content, such as
+  /// the comments of an empty block.
+  fn inner(&self, _span: Span) -> &[Comment] {
+    &[]
+  }
+
+  /// Gets the comments printed on their own lines after the last statement.
+  fn end(&self) -> &[Comment] {
+    &[]
+  }
+}
+
+/// Prints syntax trees as JavaScript source text.
+#[derive(Clone)]
+pub struct Generator<'a> {
+  options: Options,
+  comments: Option<&'a dyn Comments>,
+}
+
+impl<'a> Generator<'a> {
+  /// Creates a generator with the given options.
+  pub fn new(options: Options) -> Self {
+    Self {
+      options,
+      comments: None,
+    }
+  }
+
+  /// Sets the comments printed with the parts of the syntax tree.
+  pub fn with_comments(mut self, comments: &'a dyn Comments) -> Self {
+    self.comments = Some(comments);
+    self
+  }
+
+  /// Prints a script. Pretty printed code ends with a new line.
+  pub fn script(&self, script: &StatementList) -> String {
+    self.statements(script.items())
+  }
+
+  /// Prints a module. Pretty printed code ends with a new line.
+  pub fn module(&self, module: &Module) -> String {
+    self.statements(module.body().items())
+  }
+
+  /// Prints a single expression.
+  pub fn expression(&self, expr: &Node) -> String {
+    let doc = Printer::new(&self.options, self.comments).expression(expr);
+    self.layout(&doc)
+  }
+
+  /// Prints a list of statements.
+  fn statements(&self, items: &[Node]) -> String {
+    let doc = Printer::new(&self.options, self.comments).program(items);
+    let mut code = self.layout(&doc);
+    if !self.options.is_compact() && !code.is_empty() {
+      code.push('\n');
+    }
+    code
+  }
+
+  /// Lays out a document in the line width.
+  fn layout(&self, doc: &doc::Doc) -> String {
+    if self.options.is_compact() {
+      return doc::layout(doc, usize::MAX, "");
+    }
+    let indent = match self.options.indent {
+      Indent::Spaces(spaces) => " ".repeat(spaces.into()),
+      Indent::Tab => "\t".to_owned(),
+    };
+    doc::layout(doc, self.options.line_width, &indent)
+  }
+}
+
+impl std::fmt::Debug for Generator<'_> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("Generator")
+      .field("options", &self.options)
+      .field("comments", &self.comments.is_some())
+      .finish()
+  }
+}
+
+/// Formats the source text of a script, with its comments.
+pub fn format_script(source: &str, options: &Options) -> Result<String, ParseError> {
+  let script = Parser::new(source.as_bytes()).parse_script()?;
+  let comments = CommentMap::script(source, &script);
+  Ok(
+    Generator::new(options.clone())
+      .with_comments(&comments)
+      .script(&script),
+  )
+}
+
+/// Formats the source text of a module, with its comments.
+pub fn format_module(source: &str, options: &Options) -> Result<String, ParseError> {
+  let module = Parser::new(source.as_bytes()).parse_module()?;
+  let comments = CommentMap::module(source, &module);
+  Ok(
+    Generator::new(options.clone())
+      .with_comments(&comments)
+      .module(&module),
+  )
+}
@@ -0,0 +1,143 @@
+//! Operator precedence, to print the parentheses the syntax tree needs and no others.
+
+use crate::syntax::ast::{
+  operator::{BinOp, BitOp, CompOp, LogOp, NumOp, UnaryOp},
+  Const, Node,
+};
+
+/// How tightly an expression binds, from the loosest to the tightest.
+///
+/// An expression printed where a higher precedence is expected is wrapped in parentheses.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Operator_Precedence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) enum Precedence {
+  /// Any expression, such as the expression of an expression statement.
+  Lowest,
+  /// `a, b`
+  Comma,
+  /// `...a`
+  Spread,
+  /// Assignments, arrow functions and `yield`, the `AssignmentExpression` of the specification.
+  Assign,
+  /// `a ? b : c`
+  Conditional,
+  /// `a ?? b`
+  Coalesce,
+  /// `a || b`
+  LogicalOr,
+  /// `a && b`
+  LogicalAnd,
+  /// `a | b`
+  BitwiseOr,
+  /// `a ^ b`
+  BitwiseXor,
+  /// `a & b`
+  BitwiseAnd,
+  /// `a == b`, `a !== b`, ...
+  Equality,
+  /// `a < b`, `a in b`, `a instanceof b`, ...
+  Relational,
+  /// `a << b`, `a >> b` and `a >>> b`
+  Shift,
+  /// `a + b` and `a - b`
+  Additive,
+  /// `a * b`, `a / b` and `a % b`
+  Multiplicative,
+  /// `a ** b`
+  Exponent,
+  /// `!a`, `++a`, `await a`, ...
+  Prefix,
+  /// `a++` and `a--`
+  Postfix,
+  /// Calls, such as `a()` or `a?.b`, the `LeftHandSideExpression` of the specification.
+  Call,
+  /// Property accesses and `new a()`, the `MemberExpression` of the specification.
+  Member,
+  /// Identifiers, literals and other expressions that never need parentheses.
+  Primary,
+}
+
+impl Precedence {
+  /// Gets the next tighter precedence.
+  pub(super) fn next(self) -> Self {
+    use Precedence::*;
+    match self {
+      Lowest => Comma,
+      Comma => Spread,
+      Spread => Assign,
+      Assign => Conditional,
+      Conditional => Coalesce,
+      Coalesce => LogicalOr,
+      LogicalOr => LogicalAnd,
+      LogicalAnd => BitwiseOr,
+      BitwiseOr => BitwiseXor,
+      BitwiseXor => BitwiseAnd,
+      BitwiseAnd => Equality,
+      Equality => Relational,
+      Relational => Shift,
+      Shift => Additive,
+      Additive => Multiplicative,
+      Multiplicative => Exponent,
+      Exponent => Prefix,
+      Prefix => Postfix,
+      Postfix => Call,
+      Call => Member,
+      Member | Primary => Primary,
+    }
+  }
+
+  /// Gets the precedence of a binary operator.
+  pub(super) fn of_binary(op: BinOp) -> Self {
+    match op {
+      BinOp::Num(NumOp::Add) | BinOp::Num(NumOp::Sub) => Self::Additive,
+      BinOp::Num(NumOp::Mul) | BinOp::Num(NumOp::Div) | BinOp::Num(NumOp::Mod) => {
+        Self::Multiplicative
+      }
+      BinOp::Num(NumOp::Exp) => Self::Exponent,
+      BinOp::Bit(BitOp::And) => Self::BitwiseAnd,
+      BinOp::Bit(BitOp::Or) => Self::BitwiseOr,
+      BinOp::Bit(BitOp::Xor) => Self::BitwiseXor,
+      BinOp::Bit(_) => Self::Shift,
+      BinOp::Comp(CompOp::Equal)
+      | BinOp::Comp(CompOp::NotEqual)
+      | BinOp::Comp(CompOp::StrictEqual)
+      | BinOp::Comp(CompOp::StrictNotEqual) => Self::Equality,
+      BinOp::Comp(_) => Self::Relational,
+      BinOp::Log(LogOp::And) => Self::LogicalAnd,
+      BinOp::Log(LogOp::Or) => Self::LogicalOr,
+      BinOp::Log(LogOp::Coalesce) => Self::Coalesce,
+      BinOp::Assign(_) => Self::Assign,
+      BinOp::Comma => Self::Comma,
+    }
+  }
+
+  /// Gets the precedence of an expression.
+  pub(super) fn of(node: &Node) -> Self {
+    match node {
+      Node::BinOp(bin_op) => Self::of_binary(bin_op.op()),
+      Node::Assign(_) | Node::ArrowFunctionDecl(_) | Node::Yield(_) => Self::Assign,
+      Node::ConditionalOp(_) => Self::Conditional,
+      Node::PrivateIn(_) => Self::Relational,
+      Node::UnaryOp(unary_op) => match unary_op.op() {
+        UnaryOp::IncrementPost | UnaryOp::DecrementPost => Self::Postfix,
+        _ => Self::Prefix,
+      },
+      Node::Await(_) => Self::Prefix,
+      Node::Spread(_) => Self::Spread,
+      Node::Call(_) | Node::OptionalChain(_) | Node::ImportCall(_) => Self::Call,
+      Node::New(_)
+      | Node::GetConstField(_)
+      | Node::GetField(_)
+      | Node::GetPrivateField(_)
+      | Node::TaggedTemplate(_) => Self::Member,
+      // Negative numbers are printed with a minus sign.
+      Node::Const(Const::Int(int), _) if *int < 0 => Self::Prefix,
+      Node::Const(Const::Num(num), _) if num.is_sign_negative() && !num.is_nan() => Self::Prefix,
+      _ => Self::Primary,
+    }
+  }
+}
@@ -0,0 +1,1796 @@
+//! Conversion of the syntax tree to a [`Doc`].
+
+use super::{doc::Doc, literal, precedence::Precedence, Comment, CommentKind, Comments, Options};
+use crate::syntax::{
+  ast::{
+    node::{
+      object::name_to_number, ArrowFunctionDecl, Block, Case, Class, ClassElement,
+      ClassElementName, Declaration, DeclarationList, ExportDecl, FormalParameter, FunctionExpr,
+      FunctionKind, ImportAttribute, ImportDecl, ImportSpecifier, IterableLoopInitializer,
+      MethodDefinitionKind, Pattern, PatternElement, PropertyDefinition, PropertyName, Switch,
+      TemplateLit, Try,
+    },
+    operator::{BinOp, CompOp, LogOp, NumOp, UnaryOp},
+    Const, Node, Span, Sym,
+  },
+  lexer::is_identifier_name,
+};
+use std::{cell::RefCell, collections::HashSet};
+
+/// Where an expression starts, for the tokens that would be read differently there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Start {
+  /// Anywhere else.
+  Any,
+  /// An expression statement, which can not start with `{`, `function` or `class`.
+  Statement,
+  /// The body of an arrow function, which can not start with `{`.
+  ArrowBody,
+  /// A default exported expression, which can not start with `function` or `class`.
+  ExportDefault,
+}
+
+/// The context an expression is printed in.
+#[derive(Debug, Clone, Copy)]
+struct Context {
+  /// What the expression starts.
+  start: Start,
+  /// Whether the `in` operator would be read as part of a `for ... in` loop.
+  forbid_in: bool,
+}
+
+impl Context {
+  /// An expression in its own delimiters, such as a call argument.
+  const ANY: Self = Self {
+    start: Start::Any,
+    forbid_in: false,
+  };
+
+  /// Creates a context for an expression starting at `start`.
+  fn at(start: Start) -> Self {
+    Self {
+      start,
+      forbid_in: false,
+    }
+  }
+
+  /// The context of the operands that are not the first one of the expression.
+  fn rest(self) -> Self {
+    Self {
+      start: Start::Any,
+      ..self
+    }
+  }
+}
+
+/// A side of a part of the syntax tree, where its comments are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Side {
+  Leading,
+  Trailing,
+  Inner,
+}
+
+/// Converts the syntax tree to a [`Doc`].
+pub(super) struct Printer<'a> {
+  options: &'a Options,
+  comments: Option<&'a dyn Comments>,
+  /// The sides of the parts whose comments are printed, since a part can be printed as a
+  /// statement and as an expression, or share its span with the part it wraps.
+  printed: RefCell<HashSet<(Span, Side)>>,
+}
+
+impl<'a> Printer<'a> {
+  /// Creates a printer.
+  pub(super) fn new(options: &'a Options, comments: Option<&'a dyn Comments>) -> Self {
+    Self {
+      options,
+      comments,
+      printed: RefCell::default(),
+    }
+  }
+
+  /// Prints the statements of a script or a module, and the comments after them.
+  pub(super) fn program(&self, items: &[Node]) -> Doc {
+    let mut docs = vec![self.statements(items, true)];
+    if let Some(comments) = self.comments {
+      for (index, comment) in comments.end().iter().enumerate() {
+        if index > 0 || !items.is_empty() {
+          docs.push(self.hard_line());
+        }
+        docs.push(self.comment(comment));
+      }
+    }
+    Doc::Concat(docs)
+  }
+
+  /// Prints an expression on its own.
+  pub(super) fn expression(&self, expr: &Node) -> Doc {
+    self.expr(expr, Precedence::Lowest, Context::ANY)
+  }
+
+  /// A space, omitted in compact output.
+  fn space(&self) -> Doc {
+    if self.options.is_compact() {
+      Doc::Nil
+    } else {
+      Doc::text(" ")
+    }
+  }
+
+  /// A space or a new line, omitted in compact output.
+  fn line(&self) -> Doc {
+    if self.options.is_compact() {
+      Doc::Nil
+    } else {
+      Doc::Line
+    }
+  }
+
+  /// A new line, omitted in compact output.
+  fn hard_line(&self) -> Doc {
+    if self.options.is_compact() {
+      Doc::Nil
+    } else {
+      Doc::HardLine
+    }
+  }
+
+  /// Prints the statements of a script, a module or a block.
+  ///
+  /// In compact output, the semicolon of the last statement is omitted if `last` is `true`.
+  pub(super) fn statements(&self, items: &[Node], last: bool) -> Doc {
+    let compact = self.options.is_compact();
+    let mut docs = Vec::new();
+    let mut previous: Option<&Node> = None;
+    // Whether the previous statement would continue on the next line, its semicolon omitted.
+    let mut open = false;
+    // Whether the statements so far are directives, so a string literal would be one too.
+    let mut prologue = true;
+    for (i, item) in items.iter().enumerate() {
+      if let Some(previous) = previous {
+        docs.push(self.hard_line());
+        if !compact && blank_line_between(previous, item) {
+          docs.push(Doc::HardLine);
+        }
+      }
+      previous = Some(item);
+
+      docs.push(self.leading_lines(item.span()));
+      let trailing = self.take(item.span(), Side::Trailing);
+      let (doc, semicolon) = match item {
+        Node::Const(Const::String(_), _) if prologue => (self.parenthesized(item), true),
+        _ => self.statement(item),
+      };
+      prologue &= matches!(item, Node::Directive(_));
+      if open
+        && matches!(
+          doc.first_char(),
+          Some('(') | Some('[') | Some('`') | Some('+') | Some('-') | Some('/') | Some(';')
+        )
+      {
+        docs.push(Doc::text(";"));
+      }
+      docs.push(doc);
+      let print_semicolon = if compact {
+        semicolon && !(last && i + 1 == items.len())
+      } else {
+        semicolon && self.options.semicolons()
+      };
+      if print_semicolon {
+        docs.push(Doc::text(";"));
+      }
+      open = semicolon && !compact && !print_semicolon;
+      docs.push(self.trailing(trailing));
+    }
+    Doc::Concat(docs)
+  }
+
+  /// Takes the comments of a side of a part, unless they are printed already.
+  fn take(&self, span: Option<Span>, side: Side) -> &'a [Comment] {
+    let (comments, span) = match (self.comments, span) {
+      (Some(comments), Some(span)) => (comments, span),
+      _ => return &[],
+    };
+    let found = match side {
+      Side::Leading => comments.leading(span),
+      Side::Trailing => comments.trailing(span),
+      Side::Inner => comments.inner(span),
+    };
+    if found.is_empty() || !self.printed.borrow_mut().insert((span, side)) {
+      return &[];
+    }
+    found
+  }
+
+  /// Prints the leading comments of a statement or a member, each on its own line.
+  fn leading_lines(&self, span: Option<Span>) -> Doc {
+    Doc::Concat(
+      self
+        .take(span, Side::Leading)
+        .iter()
+        .flat_map(|comment| vec![self.comment(comment), self.hard_line()])
+        .collect(),
+    )
+  }
+
+  /// Prints the inner comments of a part, each on a new line.
+  fn inner_lines(&self, span: Option<Span>) -> Vec<Doc> {
+    self
+      .take(span, Side::Inner)
+      .iter()
+      .flat_map(|comment| vec![self.hard_line(), self.comment(comment)])
+      .collect()
+  }
+
+  /// Prints comments after a part on the same line. Line comments are printed at the end of the
+  /// line, after the commas and semicolons.
+  fn trailing(&self, comments: &[Comment]) -> Doc {
+    Doc::Concat(
+      comments
+        .iter()
+        .map(|comment| match comment.kind() {
+          CommentKind::Line if !self.options.is_compact() => {
+            Doc::LineSuffix(format!(" //{}", comment.text()).into())
+          }
+          _ => Doc::Concat(vec![self.space(), self.comment(comment)]),
+        })
+        .collect(),
+    )
+  }
+
+  /// Prints a part of the syntax tree with its comments on the same lines: the leading ones
+  /// before it, a new line following line comments, then its inner comments if it did not print
+  /// them and its trailing ones.
+  fn commented(&self, span: Option<Span>, doc: Doc) -> Doc {
+    self.commented_with(span, doc, false)
+  }
+
+  /// Prints an expression with its comments, leaving out the purity annotations of the calls
+  /// printing their own.
+  fn commented_node(&self, node: &Node, doc: Doc) -> Doc {
+    self.commented_with(node.span(), doc, starts_pure(node))
+  }
+
+  fn commented_with(&self, span: Option<Span>, doc: Doc, pure: bool) -> Doc {
+    let mut docs = Vec::new();
+    for comment in self.take(span, Side::Leading) {
+      if pure && is_pure_annotation(comment) {
+        continue;
+      }
+      // A line comment follows the line comments pending at the end of the line.
+      docs.push(match comment.kind() {
+        CommentKind::Line if !self.options.is_compact() => {
+          Doc::LineSuffix(format!(" //{}", comment.text()).into())
+        }
+        _ => self.comment(comment),
+      });
+      docs.push(match comment.kind() {
+        CommentKind::Line => self.hard_line(),
+        CommentKind::Block => self.space(),
+      });
+    }
+    docs.push(doc);
+    docs.push(self.trailing(self.take(span, Side::Inner)));
+    docs.push(self.trailing(self.take(span, Side::Trailing)));
+    Doc::Concat(docs)
+  }
+
+  /// Prints a member of a class body, with its leading comments on their own lines.
+  fn member(&self, span: Option<Span>, doc: Doc) -> Doc {
+    let leading = self.leading_lines(span);
+    let trailing = self.take(span, Side::Trailing);
+    Doc::Concat(vec![
+      leading,
+      self.commented(span, doc),
+      self.trailing(trailing),
+    ])
+  }
+
+  /// Returns `true` if a part of the syntax tree has comments.
+  fn has_comments(&self, span: Option<Span>) -> bool {
+    match (self.comments, span) {
+      (Some(comments), Some(span)) => {
+        !comments.leading(span).is_empty()
+          || !comments.trailing(span).is_empty()
+          || !comments.inner(span).is_empty()
+      }
+      _ => false,
+    }
+  }
+
+  /// Returns `true` if a line comment is printed before an expression, so that it would start
+  /// on a new line.
+  fn breaks_before(&self, node: &Node) -> bool {
+    match (self.comments, node.span()) {
+      (Some(comments), Some(span)) if !self.options.is_compact() => {
+        !self.printed.borrow().contains(&(span, Side::Leading))
+          && comments
+            .leading(span)
+            .iter()
+            .any(|comment| comment.kind() == CommentKind::Line)
+      }
+      _ => false,
+    }
+  }
+
+  /// Prints the operand of `return`, `throw` or `yield`, in parentheses if a line comment starts
+  /// it on a new line, which would end the statement.
+  fn operand(&self, node: &Node, precedence: Precedence, context: Context) -> Doc {
+    if self.breaks_before(node) {
+      self.parenthesized(node)
+    } else {
+      self.expr(node, precedence, context)
+    }
+  }
+
+  /// Prints a comment. Comments are always block comments in compact output.
+  fn comment(&self, comment: &Comment) -> Doc {
+    match comment.kind() {
+      CommentKind::Line if self.options.is_compact() => {
+        Doc::text(format!("/*{}*/", comment.text().replace("*/", "* /")))
+      }
+      CommentKind::Line => Doc::text(format!("//{}", comment.text())),
+      CommentKind::Block => {
+        let mut lines = comment.text().split('\n');
+        let mut docs = vec![Doc::text(format!("/*{}", lines.next().unwrap_or_default()))];
+        // The lines of documentation comments are aligned on their stars.
+        let lines: Vec<_> = lines.collect();
+        let aligned = lines.iter().all(|line| line.trim_start().starts_with('*'));
+        for line in lines {
+          docs.push(Doc::HardLine);
+          docs.push(if aligned {
+            Doc::text(format!(" {}", line.trim()))
+          } else {
+            Doc::text(line.to_owned())
+          });
+        }
+        docs.push(Doc::text("*/"));
+        Doc::Concat(docs)
+      }
+    }
+  }
+
+  /// Prints a block of statements, followed by the inner comments of the part it belongs to.
+  fn block(&self, items: &[Node], span: Option<Span>) -> Doc {
+    let inner = self.inner_lines(span);
+    if items.is_empty() && inner.is_empty() {
+      return Doc::text("{}");
+    }
+    let mut docs = Vec::new();
+    if !items.is_empty() {
+      docs.push(self.hard_line());
+      docs.push(self.statements(items, true));
+    }
+    docs.extend(inner);
+    Doc::Concat(vec![
+      Doc::text("{"),
+      Doc::indent(Doc::Concat(docs)),
+      self.hard_line(),
+      Doc::text("}"),
+    ])
+  }
+
+  /// Prints a block statement, or a block of a `try` statement.
+  fn block_statement(&self, block: &Block) -> Doc {
+    self.commented(block.span(), self.block(block.statements(), block.span()))
+  }
+
+  /// Prints the body of a control statement, such as the body of a loop, with its leading space.
+  fn body(&self, node: &Node) -> Doc {
+    match node {
+      Node::Block(block) => Doc::Concat(vec![self.space(), self.block_statement(block)]),
+      Node::Empty(_) if !self.has_comments(node.span()) => Doc::text(";"),
+      _ => {
+        let (doc, semicolon) = self.statement(node);
+        Doc::Concat(vec![
+          self.space(),
+          doc,
+          Doc::text(if semicolon { ";" } else { "" }),
+        ])
+      }
+    }
+  }
+
+  /// Prints a statement with its comments, telling whether it ends with a semicolon.
+  fn statement(&self, node: &Node) -> (Doc, bool) {
+    let (doc, semicolon) = self.statement_uncommented(node);
+    (self.commented_node(node, doc), semicolon)
+  }
+
+  /// Prints a statement, telling whether it ends with a semicolon.
+  fn statement_uncommented(&self, node: &Node) -> (Doc, bool) {
+    match node {
+      Node::Block(block) => (self.block_statement(block), false),
+      Node::DeclarationList(list, _) => (self.declaration_list(list, false), true),
+      Node::FunctionDecl(decl) => (
+        self.function(
+          Some(decl.name()),
+          decl.kind(),
+          decl.parameters(),
+          decl.body(),
+          decl.span(),
+        ),
+        false,
+      ),
+      Node::ClassDecl(class) => (self.class(class), false),
+      Node::If(if_smt) => {
+        let mut docs = vec![
+          Doc::text("if"),
+          self.space(),
+          Doc::text("("),
+          self.expr(if_smt.cond(), Precedence::Lowest, Context::ANY),
+          Doc::text(")"),
+        ];
+        match if_smt.else_node() {
+          Some(else_node) => {
+            let body = if_smt.body();
+            let braced = match body {
+              Node::Block(_) => {
+                docs.push(self.body(body));
+                true
+              }
+              // The `else` would belong to the nested `if` statement without braces.
+              body if has_dangling_if(body) => {
+                docs.push(self.space());
+                docs.push(self.block(std::slice::from_ref(body), None));
+                true
+              }
+              body => {
+                docs.push(self.body(body));
+                false
+              }
+            };
+            docs.push(if braced {
+              self.space()
+            } else {
+              self.hard_line()
+            });
+            docs.push(Doc::text("else"));
+            docs.push(self.body(else_node));
+          }
+          None => docs.push(self.body(if_smt.body())),
+        }
+        (Doc::Concat(docs), false)
+      }
+      Node::ForLoop(for_loop) => {
+        let init = match for_loop.init() {
+          Some(init @ Node::DeclarationList(list, _)) => {
+            self.commented(init.span(), self.declaration_list(list, true))
+          }
+          Some(init) => self.expr(
+            init,
+            Precedence::Lowest,
+            Context {
+              start: Start::Any,
+              forbid_in: true,
+            },
+          ),
+          None => Doc::Nil,
+        };
+        let part = |node: Option<&Node>| match node {
+          Some(node) => Doc::Concat(vec![
+            self.space(),
+            self.expr(node, Precedence::Lowest, Context::ANY),
+          ]),
+          None => Doc::Nil,
+        };
+        (
+          Doc::Concat(vec![
+            Doc::text("for"),
+            self.space(),
+            Doc::text("("),
+            init,
+            Doc::text(";"),
+            part(for_loop.condition()),
+            Doc::text(";"),
+            part(for_loop.final_expr()),
+            Doc::text(")"),
+            self.body(for_loop.body()),
+          ]),
+          false,
+        )
+      }
+      Node::ForInLoop(for_in) => (
+        self.for_in_of(
+          "for",
+          for_in.init(),
+          "in",
+          self.expr(for_in.expr(), Precedence::Lowest, Context::ANY),
+          for_in.body(),
+        ),
+        false,
+      ),
+      Node::ForOfLoop(for_of) => (
+        self.for_in_of(
+          if for_of.is_await() {
+            "for await"
+          } else {
+            "for"
+          },
+          for_of.init(),
+          "of",
+          self.expr(for_of.iterable(), Precedence::Assign, Context::ANY),
+          for_of.body(),
+        ),
+        false,
+      ),
+      Node::WhileLoop(while_loop) => (
+        Doc::Concat(vec![
+          Doc::text("while"),
+          self.space(),
+          Doc::text("("),
+          self.expr(while_loop.cond(), Precedence::Lowest, Context::ANY),
+          Doc::text(")"),
+          self.body(while_loop.body()),
+        ]),
+        false,
+      ),
+      Node::With(with) => (
+        Doc::Concat(vec![
+          Doc::text("with"),
+          self.space(),
+          Doc::text("("),
+          self.expr(with.object(), Precedence::Lowest, Context::ANY),
+          Doc::text(")"),
+          self.body(with.body()),
+        ]),
+        false,
+      ),
+      Node::DoWhileLoop(do_while) => (
+        Doc::Concat(vec![
+          Doc::text("do"),
+          self.body(do_while.body()),
+          if matches!(do_while.body(), Node::Block(_)) {
+            self.space()
+          } else {
+            self.hard_line()
+          },
+          Doc::text("while"),
+          self.space(),
+          Doc::text("("),
+          self.expr(do_while.cond(), Precedence::Lowest, Context::ANY),
+          Doc::text(")"),
+        ]),
+        true,
+      ),
+      Node::Labelled(labelled) => {
+        let (doc, semicolon) = self.statement(labelled.statement());
+        (
+          Doc::Concat(vec![
+            Doc::text(labelled.label().to_owned()),
+            Doc::text(":"),
+            self.space(),
+            doc,
+          ]),
+          semicolon,
+        )
+      }
+      Node::Switch(switch) => (
+        Doc::Concat(vec![
+          Doc::text("switch"),
+          self.space(),
+          Doc::text("("),
+          self.expr(switch.val(), Precedence::Lowest, Context::ANY),
+          Doc::text(")"),
+          self.space(),
+          self.switch_body(switch),
+        ]),
+        false,
+      ),
+      Node::Try(try_smt) => (self.try_statement(try_smt), false),
+      Node::Throw(throw) => (
+        Doc::Concat(vec![
+          Doc::text("throw"),
+          self.space(),
+          self.operand(throw.expr(), Precedence::Lowest, Context::ANY),
+        ]),
+        true,
+      ),
+      Node::Return(ret) => (
+        match ret.expr() {
+          Some(expr) => Doc::Concat(vec![
+            Doc::text("return"),
+            self.space(),
+            self.operand(expr, Precedence::Lowest, Context::ANY),
+          ]),
+          None => Doc::text("return"),
+        },
+        true,
+      ),
+      Node::Break(brk) => (self.jump("break", brk.label()), true),
+      Node::Continue(cont) => (self.jump("continue", cont.label()), true),
+      Node::Debugger(_) => (Doc::text("debugger"), true),
+      // Escape sequences and line continuations change what directive it is.
+      Node::Directive(directive) => (Doc::text(directive.raw().to_owned()), true),
+      Node::Empty(_) => (Doc::text(";"), false),
+      Node::Error(_) => (Doc::text("/* error */"), false),
+      Node::Import(import) => (self.import(import), true),
+      Node::Export(export, _) => self.export(export),
+      expr => (
+        self.expr(expr, Precedence::Lowest, Context::at(Start::Statement)),
+        true,
+      ),
+    }
+  }
+
+  /// Prints a `break` or `continue` statement.
+  fn jump(&self, keyword: &'static str, label: Option<&str>) -> Doc {
+    match label {
+      Some(label) => Doc::Concat(vec![
+        Doc::text(keyword),
+        Doc::text(" "),
+        Doc::text(label.to_owned()),
+      ]),
+      None => Doc::text(keyword),
+    }
+  }
+
+  /// Prints a `var`, `let` or `const` declaration, without its semicolon.
+  fn declaration_list(&self, list: &DeclarationList, forbid_in: bool) -> Doc {
+    let context = Context {
+      start: Start::Any,
+      forbid_in,
+    };
+    let declarations = Doc::join(
+      list
+        .as_ref()
+        .iter()
+        .map(|decl| self.declaration(decl, context)),
+      &Doc::Concat(vec![Doc::text(","), self.line()]),
+    );
+    // A single declaration is not indented, so that its initializer is only indented once.
+    let declarations = if list.as_ref().len() > 1 {
+      Doc::indent(declarations)
+    } else {
+      declarations
+    };
+    Doc::group(Doc::Concat(vec![
+      Doc::text(list.kind()),
+      Doc::text(" "),
+      declarations,
+    ]))
+  }
+
+  /// Prints a single declaration.
+  fn declaration(&self, decl: &Declaration, context: Context) -> Doc {
+    self.commented(
+      decl.span(),
+      self.with_default(
+        self.pattern(decl.target(), Context::ANY),
+        decl.init(),
+        context,
+      ),
+    )
+  }
+
+  /// Prints a binding followed by its initializer, if it has one.
+  fn with_default(&self, target: Doc, init: Option<&Node>, context: Context) -> Doc {
+    match init {
+      Some(init) => Doc::Concat(vec![
+        target,
+        self.space(),
+        Doc::text("="),
+        self.space(),
+        self.expr(init, Precedence::Assign, context.rest()),
+      ]),
+      None => target,
+    }
+  }
+
+  /// Prints a `for ... in` or `for ... of` loop.
+  fn for_in_of(
+    &self,
+    keyword: &'static str,
+    init: &IterableLoopInitializer,
+    operator: &'static str,
+    expr: Doc,
+    body: &Node,
+  ) -> Doc {
+    let init = match init {
+      IterableLoopInitializer::Var(pattern) => self.binding("var", pattern),
+      IterableLoopInitializer::Let(pattern) => self.binding("let", pattern),
+      IterableLoopInitializer::Const(pattern) => self.binding("const", pattern),
+      IterableLoopInitializer::Pattern(pattern) => self.pattern(
+        pattern,
+        Context {
+          start: Start::Any,
+          forbid_in: true,
+        },
+      ),
+    };
+    Doc::Concat(vec![
+      Doc::text(keyword),
+      self.space(),
+      Doc::text("("),
+      init,
+      Doc::text(" "),
+      Doc::text(operator),
+      Doc::text(" "),
+      expr,
+      Doc::text(")"),
+      self.body(body),
+    ])
+  }
+
+  /// Prints a binding pattern preceded by its declaration keyword.
+  fn binding(&self, keyword: &'static str, pattern: &Pattern) -> Doc {
+    Doc::Concat(vec![
+      Doc::text(keyword),
+      Doc::text(" "),
+      self.pattern(pattern, Context::ANY),
+    ])
+  }
+
+  /// Prints the cases of a `switch` statement in braces, followed by its inner comments.
+  fn switch_body(&self, switch: &Switch) -> Doc {
+    let mut docs: Vec<_> = switch
+      .cases()
+      .iter()
+      .enumerate()
+      .map(|(i, case)| {
+        Doc::Concat(vec![
+          self.hard_line(),
+          self.member(case.span(), self.case(case, i + 1 == switch.cases().len())),
+        ])
+      })
+      .collect();
+    docs.extend(self.inner_lines(switch.span()));
+    Doc::Concat(vec![
+      Doc::text("{"),
+      Doc::indent(Doc::Concat(docs)),
+      self.hard_line(),
+      Doc::text("}"),
+    ])
+  }
+
+  /// Prints a case of a `switch` statement.
+  fn case(&self, case: &Case, last: bool) -> Doc {
+    let label = match case.condition() {
+      Some(condition) => Doc::Concat(vec![
+        Doc::text("case"),
+        Doc::text(" "),
+        self.expr(condition, Precedence::Lowest, Context::ANY),
+        Doc::text(":"),
+      ]),
+      None => Doc::text("default:"),
+    };
+    match case.body() {
+      [] => label,
+      [Node::Block(block)] => Doc::Concat(vec![label, self.space(), self.block_statement(block)]),
+      body => Doc::Concat(vec![
+        label,
+        Doc::indent(Doc::Concat(vec![
+          self.hard_line(),
+          self.statements(body, last),
+        ])),
+      ]),
+    }
+  }
+
+  /// Prints a `try` statement.
+  fn try_statement(&self, try_smt: &Try) -> Doc {
+    let mut docs = vec![
+      Doc::text("try"),
+      self.space(),
+      self.block_statement(try_smt.block()),
+    ];
+    if let Some(catch) = try_smt.catch() {
+      let mut clause = vec![Doc::text("catch")];
+      if let Some(parameter) = catch.parameter() {
+        clause.push(self.space());
+        clause.push(Doc::text("("));
+        clause.push(self.pattern(parameter, Context::ANY));
+        clause.push(Doc::text(")"));
+      }
+      clause.push(self.space());
+      clause.push(self.block_statement(catch.block()));
+      docs.push(self.space());
+      docs.push(self.commented(catch.span(), Doc::Concat(clause)));
+    }
+    if let Some(finally) = try_smt.finally() {
+      docs.push(self.space());
+      docs.push(Doc::text("finally"));
+      docs.push(self.space());
+      docs.push(self.block_statement(finally));
+    }
+    Doc::Concat(docs)
+  }
+
+  /// Prints an `import` declaration, without its semicolon.
+  fn import(&self, import: &ImportDecl) -> Doc {
+    let source = Doc::text(literal::string(import.source(), self.options.quotes()));
+    if import.specifiers().is_empty() {
+      return Doc::Concat(vec![
+        Doc::text("import"),
+        self.space(),
+        source,
+        self.attributes(import.attributes()),
+      ]);
+    }
+
+    let mut clauses = Vec::new();
+    let mut named = Vec::new();
+    for specifier in import.specifiers() {
+      match specifier {
+        ImportSpecifier::Default(local) => clauses.push(Doc::text(local.to_string())),
+        ImportSpecifier::Namespace(local) => clauses.push(Doc::Concat(vec![
+          Doc::text("*"),
+          self.space(),
+          Doc::text("as "),
+          Doc::text(local.to_string()),
+        ])),
+        ImportSpecifier::Named { imported, local } if imported == local.name() => {
+          named.push(Doc::text(local.to_string()))
+        }
+        ImportSpecifier::Named { imported, local } => named.push(Doc::Concat(vec![
+          self.module_export_name(imported),
+          Doc::text(" as "),
+          Doc::text(local.to_string()),
+        ])),
+      }
+    }
+    if !named.is_empty() {
+      clauses.push(self.list("{", named, "}", true, true));
+    }
+    Doc::Concat(vec![
+      Doc::text("import "),
+      Doc::join(clauses, &Doc::Concat(vec![Doc::text(","), self.space()])),
+      self.space(),
+      Doc::text("from"),
+      self.space(),
+      source,
+      self.attributes(import.attributes()),
+    ])
+  }
+
+  /// Prints an `export` declaration, telling whether it ends with a semicolon.
+  fn export(&self, export: &ExportDecl) -> (Doc, bool) {
+    let quotes = self.options.quotes();
+    match export {
+      ExportDecl::Named {
+        specifiers,
+        source,
+        attributes,
+      } => {
+        let specifiers = specifiers
+          .iter()
+          .map(|specifier| {
+            if specifier.local() == specifier.exported() {
+              self.module_export_name(specifier.local())
+            } else {
+              Doc::Concat(vec![
+                self.module_export_name(specifier.local()),
+                Doc::text(" as "),
+                self.module_export_name(specifier.exported()),
+              ])
+            }
+          })
+          .collect();
+        let mut docs = vec![
+          Doc::text("export"),
+          self.space(),
+          self.list("{", specifiers, "}", true, true),
+        ];
+        if let Some(source) = source {
+          docs.push(self.space());
+          docs.push(Doc::text("from"));
+          docs.push(self.space());
+          docs.push(Doc::text(literal::string(source, quotes)));
+          docs.push(self.attributes(attributes));
+        }
+        (Doc::Concat(docs), true)
+      }
+      ExportDecl::All {
+        alias,
+        source,
+        attributes,
+      } => {
+        let mut docs = vec![Doc::text("export"), self.space(), Doc::text("*")];
+        if let Some(alias) = alias {
+          docs.push(self.space());
+          docs.push(Doc::text("as "));
+          docs.push(self.module_export_name(alias));
+        }
+        docs.push(self.space());
+        docs.push(Doc::text("from"));
+        docs.push(self.space());
+        docs.push(Doc::text(literal::string(source, quotes)));
+        docs.push(self.attributes(attributes));
+        (Doc::Concat(docs), true)
+      }
+      ExportDecl::Declaration(decl) => {
+        let (doc, semicolon) = self.statement(decl);
+        (Doc::Concat(vec![Doc::text("export "), doc]), semicolon)
+      }
+      ExportDecl::DefaultFunction(function) => (
+        Doc::Concat(vec![
+          Doc::text("export default "),
+          self.function(
+            function.name().map(Sym::as_str),
+            function.kind(),
+            function.parameters(),
+            function.body(),
+            function.span(),
+          ),
+        ]),
+        false,
+      ),
+      ExportDecl::DefaultClass(class) => (
+        Doc::Concat(vec![Doc::text("export default "), self.class(class)]),
+        false,
+      ),
+      ExportDecl::DefaultExpression(expr) => (
+        Doc::Concat(vec![
+          Doc::text("export default "),
+          self.expr(expr, Precedence::Assign, Context::at(Start::ExportDefault)),
+        ]),
+        true,
+      ),
+    }
+  }
+
+  /// Prints the name of an imported or exported binding, as a string if it is not an identifier.
+  fn module_export_name(&self, name: &str) -> Doc {
+    if is_identifier_name(name) {
+      Doc::text(name.to_owned())
+    } else {
+      Doc::text(literal::string(name, self.options.quotes()))
+    }
+  }
+
+  /// Prints the import attributes of a module request, with their leading space.
+  fn attributes(&self, attributes: &[ImportAttribute]) -> Doc {
+    if attributes.is_empty() {
+      return Doc::Nil;
+    }
+    let attributes = attributes
+      .iter()
+      .map(|attribute| {
+        Doc::Concat(vec![
+          self.module_export_name(attribute.key()),
+          Doc::text(":"),
+          self.space(),
+          Doc::text(literal::string(attribute.value(), self.options.quotes())),
+        ])
+      })
+      .collect();
+    Doc::Concat(vec![
+      self.space(),
+      Doc::text("with"),
+      self.space(),
+      self.list("{", attributes, "}", true, true),
+    ])
+  }
+
+  /// Prints a comma separated list between delimiters, with one item per line if it is too long.
+  ///
+  /// `spaced` lists, such as object literals, have spaces inside their delimiters on a single line.
+  fn list(
+    &self,
+    open: &'static str,
+    items: Vec<Doc>,
+    close: &'static str,
+    spaced: bool,
+    trailing_comma: bool,
+  ) -> Doc {
+    self.commented_list(open, items, None, close, spaced, trailing_comma)
+  }
+
+  /// Prints a comma separated list, followed by the inner comments of the part it belongs to,
+  /// on their own lines.
+  fn commented_list(
+    &self,
+    open: &'static str,
+    items: Vec<Doc>,
+    span: Option<Span>,
+    close: &'static str,
+    spaced: bool,
+    trailing_comma: bool,
+  ) -> Doc {
+    let inner = self.inner_lines(span);
+    if items.is_empty() && inner.is_empty() {
+      return Doc::Concat(vec![Doc::text(open), Doc::text(close)]);
+    }
+    if self.options.is_compact() {
+      return Doc::Concat(vec![
+        Doc::text(open),
+        Doc::join(items, &Doc::text(",")),
+        Doc::Concat(inner),
+        Doc::text(close),
+      ]);
+    }
+    let line = if spaced { Doc::Line } else { Doc::SoftLine };
+    let mut docs = Vec::new();
+    if !items.is_empty() {
+      docs.push(line.clone());
+      docs.push(Doc::join(
+        items,
+        &Doc::Concat(vec![Doc::text(","), Doc::Line]),
+      ));
+      docs.push(if trailing_comma && self.options.trailing_commas() {
+        Doc::if_break(Doc::text(","))
+      } else {
+        Doc::Nil
+      });
+    }
+    docs.extend(inner);
+    Doc::group(Doc::Concat(vec![
+      Doc::text(open),
+      Doc::indent(Doc::Concat(docs)),
+      line,
+      Doc::text(close),
+    ]))
+  }
+
+  /// Prints the arguments of a call, keeping the parentheses next to a last argument that spans
+  /// several lines, as in `it("works", () => { ... })`.
+  fn arguments(&self, args: &[Node]) -> Doc {
+    let docs: Vec<_> = args
+      .iter()
+      .map(|arg| self.expr(arg, Precedence::Spread, Context::ANY))
+      .collect();
+    let huggable = |arg: &Node| match arg {
+      Node::FunctionExpr(_) => true,
+      Node::ArrowFunctionDecl(arrow) => concise_body(arrow).is_none(),
+      Node::Object(object) => !object.properties().is_empty(),
+      Node::ArrayDecl(array) => !array.elements().is_empty(),
+      _ => false,
+    };
+    match args.split_last() {
+      Some((last, rest))
+        if !self.options.is_compact()
+          && huggable(last)
+          && !rest
+            .iter()
+            .any(|arg| huggable(arg) || matches!(arg, Node::ArrowFunctionDecl(_))) =>
+      {
+        Doc::Concat(vec![
+          Doc::text("("),
+          Doc::join(docs, &Doc::text(", ")),
+          Doc::text(")"),
+        ])
+      }
+      _ => {
+        let trailing_comma = !matches!(args.last(), Some(Node::Spread(_)));
+        self.list("(", docs, ")", false, trailing_comma)
+      }
+    }
+  }
+
+  /// Prints formal parameters.
+  fn parameters(&self, parameters: &[FormalParameter]) -> Doc {
+    let docs = parameters
+      .iter()
+      .map(|parameter| {
+        let target = self.pattern(parameter.target(), Context::ANY);
+        let doc = if parameter.is_rest_param() {
+          Doc::Concat(vec![Doc::text("..."), target])
+        } else {
+          self.with_default(target, parameter.init(), Context::ANY)
+        };
+        self.commented(parameter.span(), doc)
+      })
+      .collect();
+    let trailing_comma = !parameters
+      .last()
+      .is_some_and(FormalParameter::is_rest_param);
+    self.list("(", docs, ")", false, trailing_comma)
+  }
+
+  /// Prints a function declaration or expression, its span giving the inner comments of its
+  /// body.
+  fn function(
+    &self,
+    name: Option<&str>,
+    kind: FunctionKind,
+    parameters: &[FormalParameter],
+    body: &[Node],
+    span: Option<Span>,
+  ) -> Doc {
+    let mut docs = Vec::new();
+    if kind.is_async() {
+      docs.push(Doc::text("async "));
+    }
+    docs.push(Doc::text("function"));
+    if kind.is_generator() {
+      docs.push(Doc::text("*"));
+    }
+    match name {
+      Some(name) => {
+        docs.push(Doc::text(" "));
+        docs.push(Doc::text(name.to_owned()));
+      }
+      None => docs.push(self.space()),
+    }
+    docs.push(self.parameters(parameters));
+    docs.push(self.space());
+    docs.push(self.block(body, span));
+    Doc::Concat(docs)
+  }
+
+  /// Prints a method of an object literal or a class.
+  fn method(&self, kind: MethodDefinitionKind, name: Doc, function_kind: FunctionKind) -> Doc {
+    let mut docs = Vec::new();
+    match kind {
+      MethodDefinitionKind::Get => docs.push(Doc::text("get ")),
+      MethodDefinitionKind::Set => docs.push(Doc::text("set ")),
+      MethodDefinitionKind::Ordinary => {}
+    }
+    if function_kind.is_async() {
+      docs.push(Doc::text("async "));
+    }
+    if function_kind.is_generator() {
+      docs.push(Doc::text("*"));
+    }
+    docs.push(name);
+    Doc::Concat(docs)
+  }
+
+  /// Prints the parameters and the body of a method.
+  fn method_function(&self, function: &FunctionExpr) -> Doc {
+    self.commented(
+      function.span(),
+      Doc::Concat(vec![
+        self.parameters(function.parameters()),
+        self.space(),
+        self.block(function.body(), function.span()),
+      ]),
+    )
+  }
+
+  /// Prints a class declaration or expression.
+  fn class(&self, class: &Class) -> Doc {
+    let mut docs = vec![Doc::text("class")];
+    if let Some(name) = class.name() {
+      docs.push(Doc::text(" "));
+      docs.push(Doc::text(name.as_str().to_owned()));
+    }
+    if let Some(super_ref) = class.super_ref() {
+      docs.push(Doc::text(" extends "));
+      docs.push(self.expr(super_ref, Precedence::Call, Context::ANY));
+    }
+    docs.push(self.space());
+
+    let mut elements = Vec::new();
+    if let Some(constructor) = class.constructor() {
+      let doc = Doc::Concat(vec![
+        Doc::text("constructor"),
+        self.parameters(constructor.parameters()),
+        self.space(),
+        self.block(constructor.body(), constructor.span()),
+      ]);
+      elements.push(self.member(constructor.span(), doc));
+    }
+    let mut iter = class.elements().iter().peekable();
+    while let Some(element) = iter.next() {
+      let doc = self.class_element(element);
+      let semicolon = match element {
+        ClassElement::Field { name, init, .. } => {
+          self.options.is_compact()
+            || self.options.semicolons()
+            || field_needs_semicolon(name, init.is_none(), iter.peek().copied())
+        }
+        _ => false,
+      };
+      let doc = if semicolon {
+        Doc::Concat(vec![doc, Doc::text(";")])
+      } else {
+        doc
+      };
+      elements.push(self.member(element.span(), doc));
+    }
+    let mut body: Vec<_> = elements
+      .into_iter()
+      .flat_map(|element| vec![self.hard_line(), element])
+      .collect();
+    body.extend(self.inner_lines(class.span()));
+    if body.is_empty() {
+      docs.push(Doc::text("{}"));
+    } else {
+      docs.push(Doc::text("{"));
+      docs.push(Doc::indent(Doc::Concat(body)));
+      docs.push(self.hard_line());
+      docs.push(Doc::text("}"));
+    }
+    Doc::Concat(docs)
+  }
+
+  /// Prints an element of a class body, without its semicolon.
+  fn class_element(&self, element: &ClassElement) -> Doc {
+    let modifier = if element.is_static() {
+      Doc::text("static ")
+    } else {
+      Doc::Nil
+    };
+    match element {
+      ClassElement::Method {
+        name,
+        kind,
+        function,
+        ..
+      } => Doc::Concat(vec![
+        modifier,
+        self.method(*kind, self.class_element_name(name), function.kind()),
+        self.method_function(function),
+      ]),
+      ClassElement::Field { name, init, .. } => Doc::Concat(vec![
+        modifier,
+        self.with_default(self.class_element_name(name), init.as_ref(), Context::ANY),
+      ]),
+      ClassElement::StaticBlock(body, _) => Doc::Concat(vec![
+        Doc::text("static"),
+        self.space(),
+        self.block(body.items(), element.span()),
+      ]),
+    }
+  }
+
+  /// Prints the name of a class element.
+  fn class_element_name(&self, name: &ClassElementName) -> Doc {
+    match name {
+      ClassElementName::PropertyName(name) => self.property_name(name),
+      ClassElementName::PrivateName(name) => Doc::text(format!("#{}", name)),
+    }
+  }
+
+  /// Prints a property name, as an identifier, a number or a string if it is not computed.
+  fn property_name(&self, name: &PropertyName) -> Doc {
+    match name {
+      PropertyName::Literal(name) if is_identifier_name(name) => Doc::text(name.to_string()),
+      PropertyName::Literal(name) if name_to_number(name).is_some() => Doc::text(name.to_string()),
+      PropertyName::Literal(name) => Doc::text(literal::string(name, self.options.quotes())),
+      PropertyName::Computed(node) => Doc::Concat(vec![
+        Doc::text("["),
+        self.expr(node, Precedence::Assign, Context::ANY),
+        Doc::text("]"),
+      ]),
+    }
+  }
+
+  /// Prints a binding or assignment pattern.
+  fn pattern(&self, pattern: &Pattern, context: Context) -> Doc {
+    match pattern {
+      Pattern::Identifier(ident) => Doc::text(ident.name().as_str().to_owned()),
+      Pattern::Member(node) => self.expr(node, Precedence::Call, context),
+      Pattern::Object(object) => {
+        let mut properties: Vec<_> = object
+          .properties()
+          .iter()
+          .map(|property| {
+            let value = property.value();
+            match property.key() {
+              // The value of a shorthand property may have been renamed since it was parsed.
+              PropertyName::Literal(name)
+                if property.is_shorthand()
+                  && matches!(value.target(), Pattern::Identifier(ident) if ident.name() == &**name) =>
+              {
+                self.with_default(Doc::text(name.to_string()), value.default(), Context::ANY)
+              }
+              key => Doc::Concat(vec![
+                self.property_name(key),
+                Doc::text(":"),
+                self.space(),
+                self.pattern_element(value),
+              ]),
+            }
+          })
+          .collect();
+        if let Some(rest) = object.rest() {
+          properties.push(Doc::Concat(vec![
+            Doc::text("..."),
+            self.pattern(rest, Context::ANY),
+          ]));
+        }
+        self.list("{", properties, "}", true, object.rest().is_none())
+      }
+      Pattern::Array(array) => {
+        let mut elements: Vec<_> = array
+          .elements()
+          .iter()
+          .map(|element| match element {
+            Some(element) => self.pattern_element(element),
+            None => Doc::Nil,
+          })
+          .collect();
+        let hole = array.rest().is_none() && matches!(array.elements().last(), Some(None));
+        if let Some(rest) = array.rest() {
+          elements.push(Doc::Concat(vec![
+            Doc::text("..."),
+            self.pattern(rest, Context::ANY),
+          ]));
+        } else if hole {
+          // A trailing hole needs its own comma.
+          elements.push(Doc::Nil);
+        }
+        self.list("[", elements, "]", false, array.rest().is_none() && !hole)
+      }
+    }
+  }
+
+  /// Prints an element of a destructuring pattern.
+  fn pattern_element(&self, element: &PatternElement) -> Doc {
+    self.with_default(
+      self.pattern(element.target(), Context::ANY),
+      element.default(),
+      Context::ANY,
+    )
+  }
+
+  /// Prints an expression, in parentheses if it binds more loosely than `precedence`.
+  fn expr(&self, node: &Node, precedence: Precedence, context: Context) -> Doc {
+    let forbidden_in = context.forbid_in
+      && match node {
+        Node::BinOp(bin_op) => bin_op.op() == BinOp::Comp(CompOp::In),
+        Node::PrivateIn(_) => true,
+        _ => false,
+      };
+    if Precedence::of(node) < precedence || forbidden_in || starts_badly(node, context.start) {
+      self.parenthesized(node)
+    } else {
+      self.expr_unparenthesized(node, context)
+    }
+  }
+
+  /// Prints an expression in parentheses.
+  fn parenthesized(&self, node: &Node) -> Doc {
+    Doc::Concat(vec![
+      Doc::text("("),
+      self.expr_unparenthesized(node, Context::ANY),
+      Doc::text(")"),
+    ])
+  }
+
+  /// Prints the object of a property access or the callee of a call.
+  fn object(&self, node: &Node, precedence: Precedence, context: Context) -> Doc {
+    match node {
+      // `1.a` would be read as a number, and `(a?.b).c` as a longer optional chain.
+      Node::Const(Const::Int(_), _) | Node::Const(Const::Num(_), _) | Node::OptionalChain(_) => {
+        self.parenthesized(node)
+      }
+      _ => self.expr(node, precedence, context),
+    }
+  }
+
+  /// Prints an expression with its comments, without parentheses around it.
+  fn expr_unparenthesized(&self, node: &Node, context: Context) -> Doc {
+    let doc = self.expr_uncommented(node, context);
+    self.commented_node(node, doc)
+  }
+
+  /// Prints an expression without parentheses around it.
+  fn expr_uncommented(&self, node: &Node, context: Context) -> Doc {
+    let space = || self.space();
+    match node {
+      Node::Identifier(ident) => Doc::text(ident.name().as_str().to_owned()),
+      Node::Const(value, _) => self.constant(value),
+      Node::This(_) => Doc::text("this"),
+      Node::Super(_) => Doc::text("super"),
+      Node::ImportMeta(_) => Doc::text("import.meta"),
+      Node::ArrayDecl(array) => {
+        let mut elements: Vec<_> = array
+          .elements()
+          .iter()
+          .map(|element| match element {
+            Node::Empty(_) => self.commented_node(element, Doc::Nil),
+            element => self.expr(element, Precedence::Spread, Context::ANY),
+          })
+          .collect();
+        let hole = matches!(array.elements().last(), Some(Node::Empty(_)));
+        if hole {
+          // A trailing hole needs its own comma.
+          elements.push(Doc::Nil);
+        }
+        let trailing_comma = !hole && !matches!(array.elements().last(), Some(Node::Spread(_)));
+        self.commented_list("[", elements, node.span(), "]", false, trailing_comma)
+      }
+      Node::Object(object) => {
+        let properties = object
+          .properties()
+          .iter()
+          .map(|property| {
+            let doc = match property {
+              PropertyDefinition::IdentifierReference(ident, _) => {
+                Doc::text(ident.name().to_string())
+              }
+              PropertyDefinition::Property(name, value, _) => Doc::Concat(vec![
+                self.property_name(name),
+                Doc::text(":"),
+                space(),
+                self.expr(value, Precedence::Assign, Context::ANY),
+              ]),
+              PropertyDefinition::MethodDefinition(kind, name, function, _) => Doc::Concat(vec![
+                self.method(*kind, self.property_name(name), function.kind()),
+                self.method_function(function),
+              ]),
+              PropertyDefinition::SpreadObject(node, _) => Doc::Concat(vec![
+                Doc::text("..."),
+                self.expr(node, Precedence::Assign, Context::ANY),
+              ]),
+            };
+            self.commented(property.span(), doc)
+          })
+          .collect();
+        self.commented_list("{", properties, node.span(), "}", true, true)
+      }
+      Node::FunctionExpr(function) => self.function(
+        function.name().map(Sym::as_str),
+        function.kind(),
+        function.parameters(),
+        function.body(),
+        function.span(),
+      ),
+      Node::ArrowFunctionDecl(arrow) => self.arrow(arrow, context),
+      Node::ClassExpr(class) => self.class(class),
+      Node::Assign(assign) => Doc::Concat(vec![
+        self.pattern(assign.lhs(), context),
+        space(),
+        Doc::text("="),
+        space(),
+        self.expr(assign.rhs(), Precedence::Assign, context.rest()),
+      ]),
+      Node::BinOp(bin_op) => self.binary(bin_op.op(), bin_op.lhs(), bin_op.rhs(), context),
+      Node::UnaryOp(unary_op) => {
+        let target = unary_op.target();
+        match unary_op.op() {
+          UnaryOp::IncrementPost | UnaryOp::DecrementPost => Doc::Concat(vec![
+            self.expr(target, Precedence::Call, context),
+            Doc::text(unary_op.op().to_string()),
+          ]),
+          op @ UnaryOp::TypeOf | op @ UnaryOp::Void | op @ UnaryOp::Delete => Doc::Concat(vec![
+            Doc::text(op.to_string()),
+            space(),
+            self.expr(target, Precedence::Prefix, context.rest()),
+          ]),
+          op => Doc::Concat(vec![
+            Doc::text(op.to_string()),
+            self.expr(target, Precedence::Prefix, context.rest()),
+          ]),
+        }
+      }
+      Node::Await(await_expr) => Doc::Concat(vec![
+        Doc::text("await"),
+        space(),
+        self.expr(await_expr.expr(), Precedence::Prefix, context.rest()),
+      ]),
+      Node::Yield(yield_expr) => {
+        let keyword = Doc::text(if yield_expr.delegate() {
+          "yield*"
+        } else {
+          "yield"
+        });
+        match yield_expr.expr() {
+          Some(expr) => Doc::Concat(vec![
+            keyword,
+            space(),
+            self.operand(expr, Precedence::Assign, context.rest()),
+          ]),
+          None => keyword,
+        }
+      }
+      Node::ConditionalOp(cond) => {
+        let line = || self.line();
+        Doc::group(Doc::Concat(vec![
+          self.expr(cond.cond(), Precedence::Coalesce, context),
+          Doc::indent(Doc::Concat(vec![
+            line(),
+            Doc::text("?"),
+            space(),
+            self.expr(cond.if_true(), Precedence::Assign, Context::ANY),
+            line(),
+            Doc::text(":"),
+            space(),
+            self.expr(cond.if_false(), Precedence::Assign, context.rest()),
+          ])),
+        ]))
+      }
+      Node::Call(call) => Doc::Concat(vec![
+        pure_annotation(call.pure()),
+        self.object(call.expr(), Precedence::Call, context),
+        Doc::text(if call.optional() { "?." } else { "" }),
+        self.arguments(call.args()),
+      ]),
+      Node::New(new) => {
+        let target = new.expr();
+        Doc::Concat(vec![
+          pure_annotation(new.pure()),
+          Doc::text("new "),
+          if has_call(target) {
+            self.parenthesized(target)
+          } else {
+            self.object(target, Precedence::Member, Context::ANY)
+          },
+          self.arguments(new.args()),
+        ])
+      }
+      Node::OptionalChain(chain) => self.expr_unparenthesized(chain.expr(), context),
+      Node::GetConstField(get) => Doc::Concat(vec![
+        self.object(get.obj(), Precedence::Call, context),
+        Doc::text(if get.optional() { "?." } else { "." }),
+        Doc::text(get.field().to_owned()),
+      ]),
+      Node::GetField(get) => Doc::Concat(vec![
+        self.object(get.obj(), Precedence::Call, context),
+        Doc::text(if get.optional() { "?.[" } else { "[" }),
+        self.expr(get.field(), Precedence::Lowest, Context::ANY),
+        Doc::text("]"),
+      ]),
+      Node::GetPrivateField(get) => Doc::Concat(vec![
+        self.object(get.obj(), Precedence::Call, context),
+        Doc::text(if get.optional() { "?.#" } else { ".#" }),
+        Doc::text(get.field().to_owned()),
+      ]),
+      Node::PrivateIn(private_in) => Doc::Concat(vec![
+        Doc::text(format!("#{}", private_in.name())),
+        Doc::text(" in "),
+        self.expr(private_in.target(), Precedence::Shift, context.rest()),
+      ]),
+      Node::ImportCall(call) => {
+        let mut args = vec![self.expr(call.specifier(), Precedence::Assign, Context::ANY)];
+        if let Some(options) = call.options() {
+          args.push(self.expr(options, Precedence::Assign, Context::ANY));
+        }
+        Doc::Concat(vec![
+          Doc::text("import"),
+          self.list("(", args, ")", false, true),
+        ])
+      }
+      Node::Spread(spread) => Doc::Concat(vec![
+        Doc::text("..."),
+        self.expr(spread.val(), Precedence::Assign, Context::ANY),
+      ]),
+      Node::TemplateLit(template) => self.template(template),
+      Node::TaggedTemplate(tagged) => Doc::Concat(vec![
+        self.object(tagged.tag(), Precedence::Call, context),
+        self.commented(tagged.template().span(), self.template(tagged.template())),
+      ]),
+      // Statements only appear here in a malformed tree.
+      statement => self.statement_uncommented(statement).0,
+    }
+  }
+
+  /// Prints a template literal with the raw text of its strings.
+  ///
+  /// Each string is printed as one text with the delimiters around it, since `a${` would
+  /// otherwise be spaced as two words.
+  fn template(&self, template: &TemplateLit) -> Doc {
+    let mut docs = Vec::new();
+    for (index, quasi) in template.quasis().iter().enumerate() {
+      let open = if index == 0 { "`" } else { "}" };
+      match template.exprs().get(index) {
+        Some(expr) => {
+          docs.push(Doc::text(format!("{}{}${{", open, quasi.raw())));
+          docs.push(self.expr(expr, Precedence::Lowest, Context::ANY));
+        }
+        None => docs.push(Doc::text(format!("{}{}`", open, quasi.raw()))),
+      }
+    }
+    Doc::Concat(docs)
+  }
+
+  /// Prints an arrow function.
+  fn arrow(&self, arrow: &ArrowFunctionDecl, context: Context) -> Doc {
+    let mut docs = Vec::new();
+    if arrow.is_async() {
+      docs.push(Doc::text("async"));
+      docs.push(self.space());
+    }
+    match arrow.params() {
+      [param]
+        if self.options.is_compact()
+          && !param.is_rest_param()
+          && param.init().is_none()
+          && matches!(param.target(), Pattern::Identifier(_)) =>
+      {
+        docs.push(self.commented(param.span(), self.pattern(param.target(), Context::ANY)))
+      }
+      params => docs.push(self.parameters(params)),
+    }
+    docs.push(self.space());
+    docs.push(Doc::text("=>"));
+    docs.push(self.space());
+    match concise_body(arrow) {
+      // The comments of the `return` statement go around the expression.
+      Some(expr) => docs.push(self.commented_node(
+        &arrow.body()[0],
+        self.expr(
+          expr,
+          Precedence::Assign,
+          Context {
+            start: Start::ArrowBody,
+            ..context
+          },
+        ),
+      )),
+      None => docs.push(self.block(arrow.body(), arrow.span())),
+    }
+    Doc::Concat(docs)
+  }
+
+  /// Prints a binary operation.
+  fn binary(&self, op: BinOp, lhs: &Node, rhs: &Node, context: Context) -> Doc {
+    let precedence = Precedence::of_binary(op);
+    let (lhs_precedence, rhs_precedence) = match op {
+      BinOp::Assign(_) => (Precedence::Call, Precedence::Assign),
+      // `-a ** b` is a syntax error, and `**` is right associative.
+      BinOp::Num(NumOp::Exp) => (Precedence::Postfix, Precedence::Exponent),
+      _ => (precedence, precedence.next()),
+    };
+    // `??` can not be mixed with `&&` or `||` without parentheses.
+    let mixed = |node: &Node| {
+      op == BinOp::Log(LogOp::Coalesce)
+        && matches!(node, Node::BinOp(operand) if matches!(operand.op(), BinOp::Log(LogOp::And) | BinOp::Log(LogOp::Or)))
+    };
+    let lhs = if mixed(lhs) {
+      self.parenthesized(lhs)
+    } else {
+      self.expr(lhs, lhs_precedence, context)
+    };
+    let rhs = if mixed(rhs) {
+      self.parenthesized(rhs)
+    } else {
+      self.expr(rhs, rhs_precedence, context.rest())
+    };
+    if op == BinOp::Comma {
+      return Doc::Concat(vec![lhs, Doc::text(","), self.line(), rhs]);
+    }
+    Doc::group(Doc::Concat(vec![
+      lhs,
+      self.space(),
+      Doc::text(op.to_string()),
+      Doc::indent(Doc::Concat(vec![self.line(), rhs])),
+    ]))
+  }
+
+  /// Prints a literal.
+  fn constant(&self, value: &Const) -> Doc {
+    match value {
+      Const::String(string) => Doc::text(literal::string(string, self.options.quotes())),
+      Const::Num(num) => Doc::text(literal::number(*num)),
+      Const::Int(int) => Doc::text(int.to_string()),
+      Const::Bool(b) => Doc::text(b.to_string()),
+      Const::Null => Doc::text("null"),
+      Const::Undefined => Doc::text("undefined"),
+      Const::RegExp(pattern, global, ignore_case) => Doc::text(format!(
+        "/{}/{}{}",
+        pattern,
+        if *global { "g" } else { "" },
+        if *ignore_case { "i" } else { "" }
+      )),
+    }
+  }
+}
+
+/// Gets the expression of an arrow function whose body is a single `return` statement, which is
+/// printed without braces.
+fn concise_body(arrow: &ArrowFunctionDecl) -> Option<&Node> {
+  match arrow.body() {
+    [Node::Return(ret)] => ret.expr(),
+    _ => None,
+  }
+}
+
+/// Checks whether an expression can not be printed at `start` without parentheses, such as an
+/// object literal at the start of a statement, which would be read as a block.
+fn starts_badly(node: &Node, start: Start) -> bool {
+  let object_pattern =
+    matches!(node, Node::Assign(assign) if matches!(assign.lhs(), Pattern::Object(_)));
+  match start {
+    Start::Any => false,
+    Start::Statement => {
+      object_pattern
+        || matches!(
+          node,
+          Node::Object(_) | Node::FunctionExpr(_) | Node::ClassExpr(_)
+        )
+    }
+    Start::ArrowBody => object_pattern || matches!(node, Node::Object(_)),
+    Start::ExportDefault => matches!(node, Node::FunctionExpr(_) | Node::ClassExpr(_)),
+  }
+}
+
+/// Returns `true` for a `/*#__PURE__*/` or `/*@__PURE__*/` annotation.
+fn is_pure_annotation(comment: &Comment) -> bool {
+  comment.kind() == CommentKind::Block && matches!(comment.text().trim(), "#__PURE__" | "@__PURE__")
+}
+
+/// Returns `true` if an expression starts with a call or a `new` expression printing its purity
+/// annotation, as in `/*#__PURE__*/ f().g()`.
+fn starts_pure(node: &Node) -> bool {
+  match node {
+    Node::Call(call) => call.pure() || starts_pure(call.expr()),
+    Node::New(new) => new.pure(),
+    Node::GetConstField(field) => starts_pure(field.obj()),
+    Node::GetField(field) => starts_pure(field.obj()),
+    _ => false,
+  }
+}
+
+/// Prints the annotation of a call or a `new` expression free of side effects.
+fn pure_annotation(pure: bool) -> Doc {
+  if pure {
+    Doc::text("/*#__PURE__*/")
+  } else {
+    Doc::Nil
+  }
+}
+
+/// Checks whether the callee of `new` contains a call, which would end the `new` expression, as in
+/// `new (a().b)()`.
+fn has_call(node: &Node) -> bool {
+  match node {
+    Node::Call(_) | Node::OptionalChain(_) | Node::ImportCall(_) => true,
+    Node::GetConstField(get) => has_call(get.obj()),
+    Node::GetField(get) => has_call(get.obj()),
+    Node::GetPrivateField(get) => has_call(get.obj()),
+    Node::TaggedTemplate(tagged) => has_call(tagged.tag()),
+    _ => false,
+  }
+}
+
+/// Checks whether a statement ends with an `if` statement without `else`, which would take the
+/// `else` of an enclosing `if` statement.
+fn has_dangling_if(node: &Node) -> bool {
+  match node {
+    Node::If(if_smt) => match if_smt.else_node() {
+      Some(else_node) => has_dangling_if(else_node),
+      None => true,
+    },
+    Node::ForLoop(for_loop) => has_dangling_if(for_loop.body()),
+    Node::ForInLoop(for_in) => has_dangling_if(for_in.body()),
+    Node::ForOfLoop(for_of) => has_dangling_if(for_of.body()),
+    Node::WhileLoop(while_loop) => has_dangling_if(while_loop.body()),
+    Node::With(with) => has_dangling_if(with.body()),
+    Node::Labelled(labelled) => has_dangling_if(labelled.statement()),
+    _ => false,
+  }
+}
+
+/// Checks whether a class field needs a semicolon when semicolons are omitted, because the next
+/// element would otherwise continue it, as in `a = b` followed by `[c]() {}`.
+fn field_needs_semicolon(
+  name: &ClassElementName,
+  no_init: bool,
+  next: Option<&ClassElement>,
+) -> bool {
+  let modifier = no_init
+    && matches!(
+      name,
+      ClassElementName::PropertyName(PropertyName::Literal(name))
+        if matches!(&**name, "get" | "set" | "static" | "async" | "accessor")
+    );
+  let continued = match next {
+    Some(ClassElement::Method { name, function, .. }) => {
+      function.kind().is_generator()
+        || matches!(
+          name,
+          ClassElementName::PropertyName(PropertyName::Computed(_))
+        )
+    }
+    Some(ClassElement::Field { name, .. }) => {
+      matches!(
+        name,
+        ClassElementName::PropertyName(PropertyName::Computed(_))
+      )
+    }
+    _ => false,
+  };
+  modifier || continued
+}
+
+/// Checks whether a blank line separates two statements: declarations of functions and classes
+/// are set apart, and so are imports from the rest of a module.
+fn blank_line_between(previous: &Node, next: &Node) -> bool {
+  fn is_declaration(node: &Node) -> bool {
+    match node {
+      Node::FunctionDecl(_) | Node::ClassDecl(_) => true,
+      Node::Export(ExportDecl::DefaultFunction(_), _)
+      | Node::Export(ExportDecl::DefaultClass(_), _) => true,
+      Node::Export(ExportDecl::Declaration(decl), _) => is_declaration(decl),
+      _ => false,
+    }
+  }
+  is_declaration(previous)
+    || is_declaration(next)
+    || (matches!(previous, Node::Import(_)) && !matches!(next, Node::Import(_)))
+}
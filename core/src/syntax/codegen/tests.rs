@@ -0,0 +1,742 @@
+//! Tests for the code generator.
+
+use super::{
+  check, check_files, format_module, format_script, Comment, Comments, Generator, Indent, Options,
+  Quotes, SourceType,
+};
+use crate::syntax::{
+  ast::{
+    node::{
+      BinOp, Directive, FunctionDecl, GetConstField, Identifier, New, StatementList,
+      UnaryOp as UnaryNode,
+    },
+    operator::{NumOp, UnaryOp},
+    Const, Node, Position, Span,
+  },
+  comments::CommentMap,
+  parser::Parser,
+};
+
+/// Parses a script.
+#[track_caller]
+fn parse(js: &str) -> StatementList {
+  Parser::new(js.as_bytes())
+    .parse_script()
+    .unwrap_or_else(|err| panic!("failed to parse `{}`: {}", js, err))
+}
+
+/// Prints a script in compact form.
+fn compact(js: &str) -> String {
+  Generator::new(Options::compact()).script(&parse(js))
+}
+
+/// Prints a script in pretty form.
+fn pretty(js: &str) -> String {
+  Generator::new(Options::pretty()).script(&parse(js))
+}
+
+/// Scripts covering every kind of node.
+const SCRIPTS: &[&str] = &[
+  r#""use strict"; var a = 1, b; let [c, , d = 2, ...e] = f; const { g, h: [i], ...j } = k;"#,
+  "function f(a, b = 1, ...c) { return a + b * c; } function* g() { yield 1; yield* f(); }",
+  "async function f() { await a; for await (const b of c) d(b); } (async () => await a)();",
+  "const f = (a) => ({ a }), g = async (b) => { b(); }, h = () => (a, b), i = () => () => 1;",
+  "if (a) b(); else if (c) { d(); } else e(); if (a) { if (b) c(); } else d();",
+  "if (a) for (;;) if (b) c(); else d(); else e();",
+  "for (let i = 0, n = a.length; i < n; i++) {} for (;;) break; for (a in b) ; for (var [a, b] of c) {}",
+  "for (a.b in c) {} for (let a = (b in c); a;) {} for (const a = () => { b in c; };;) {}",
+  "label: for (;;) { continue label; } outer: { break outer; } while (a) a--; do a++; while (a < 10)",
+  "with (a) b(); with (a) { c(); } if (a) with (b) if (c) d(); else e(); else f();",
+  "if (a) function f() {} else function g() {} h: function h() {}",
+  "switch (a) { case 1: case 2: b(); break; case 3: { c(); } default: d(); }",
+  "try { a(); } catch (e) { b(e); } finally { c(); } try {} catch { d(); } try {} finally {}",
+  "throw new Error(`${a}`.length ? 'a' : \"b\");",
+  "a = b ? c : d ? e : f; (a ? b : c) ? d : e; a = (b, c); a += b -= c; a ??= b; a ||= b &&= c;",
+  "a = -(-b); a = - -1; a = +(+b); a = - --b; a = !(!b); typeof typeof a; void 0; delete a[b];",
+  "a = (1).toString() + 1.5.toFixed() + (-1).x + a.b.c[d](e)[f]?.g?.[h]?.(i);",
+  "new A(); new A.B(); new (a())(); new (a().b)(); new (a?.b)(); new new A()(); (new A()).b();",
+  "(a?.b).c; (a?.b)(); a?.b.c(); (a, b)(); (() => {})(); (function () {})(); (class {});",
+  "({}); ({ a } = b); [a, b] = [b, a]; ({ a: { b } } = c);",
+  "a = { a, b: 1, 'c d': 2, 3: 4, [e]: 5, get f() { return 1; }, set f(v) {}, g() {}, async *h() {}, ...i };",
+  "a = [1, , 2, ...b, ,]; a = [,]; a = []; a = [, ,]; a = {};",
+  "a = (b, c) => b ** c; a = (-1) ** 2; a = (-b) ** 2; a = b ** -c; a = (b ** c) ** d; a = b ** c ** d;",
+  "a = (b + c) * d; a = b + (c + d); a = b - (c - d); a = (b - c) - d; a = b * (c / d);",
+  "a = b || c && d; a = (b || c) && d; a = (b ?? c) || d; a = b ?? (c || d); a = (b && c) ?? d;",
+  "a = b in c; a = !(b in c); a = b instanceof C; a = b < c == d > e; a = (b, c) ? d : e;",
+  "a = b | c ^ d & e; a = (b | c) & d; a = b << c >> d >>> e; a = b % c;",
+  "class A extends B { constructor(a) { super(a); } static #a = 1; b = 2; #c; static { A.#a++; } get d() { return #c in this; } set d(v) {} static async *e() {} ['f']() {} [g] = 3; }",
+  "class A { get; set; static; async; a = 1; [b] = 2; *c() {} } const B = class extends (a, b) {};",
+  "a = function* () {}; a = async function b() {}; a = class C { static d() {} };",
+  "a = x => x, b = async x => x; debugger; ; a = this; import('a');",
+  "a = 'it\\'s'; a = \"say \\\"hi\\\"\"; a = '\\n\\t\\\\\\0\\x01'; a = '\\u2028'; a = 'é';",
+  "a = 0.5; a = 1e21; a = 1.5e-7; a = 0xff; a = 1e999; a = 2147483648;",
+  "a = `x`; a = b`c`;",
+  "a = `a${b}c${`d${e}`}\\n${{ f }}`; a = b.c`\\unicode`; a = b()`c${d}`; new (a`b`)(); new a`b`();",
+  "a = /*#__PURE__*/ f(); b = /*#__PURE__*/ new C(); c = /*@__PURE__*/ g()(h()).i();",
+];
+
+/// Checks that printing a script and parsing it back gives the same syntax tree, in pretty and
+/// compact form, and that pretty printing is idempotent.
+#[test]
+fn round_trip() {
+  for js in SCRIPTS {
+    let ast = Parser::new(js.as_bytes())
+      .parse_script()
+      .unwrap_or_else(|err| panic!("failed to parse `{}`: {}", js, err));
+    for options in &[
+      Options::pretty(),
+      Options::compact(),
+      Options::pretty().with_semicolons(false),
+      Options::pretty()
+        .with_line_width(20)
+        .with_quotes(Quotes::Single),
+    ] {
+      let code = Generator::new(options.clone()).script(&ast);
+      let reparsed = Parser::new(code.as_bytes())
+        .parse_script()
+        .unwrap_or_else(|err| panic!("failed to parse `{}` printed from `{}`: {}", code, js, err));
+      assert_eq!(reparsed, ast, "`{}` printed as `{}`", js, code);
+      assert_eq!(
+        Generator::new(options.clone()).script(&reparsed),
+        code,
+        "printing `{}` is not idempotent",
+        js
+      );
+    }
+  }
+}
+
+#[test]
+fn modules() {
+  let js = "import a, { b, c as d, 'e f' as g } from 'a'; import * as ns from 'b'; import 'c'; \
+            import j from 'j.json' with { type: 'json' }; let m = import.meta; \
+            export { a, d as e2, g as 'h i' }; export * from 'd'; export * as e from 'e'; \
+            export { x as y } from 'f'; export const f = 1; export function g2() {} \
+            export default class {}";
+  let module = Parser::new(js.as_bytes()).parse_module().unwrap();
+  let code = Generator::new(Options::pretty()).module(&module);
+  assert_eq!(
+    code,
+    "import a, { b, c as d, \"e f\" as g } from \"a\";\n\
+     import * as ns from \"b\";\n\
+     import \"c\";\n\
+     import j from \"j.json\" with { type: \"json\" };\n\
+     \n\
+     let m = import.meta;\n\
+     export { a, d as e2, g as \"h i\" };\n\
+     export * from \"d\";\n\
+     export * as e from \"e\";\n\
+     export { x as y } from \"f\";\n\
+     export const f = 1;\n\
+     \n\
+     export function g2() {}\n\
+     \n\
+     export default class {}\n"
+  );
+  assert_eq!(Parser::new(code.as_bytes()).parse_module().unwrap(), module);
+
+  for js in &[
+    "export default (function () {});",
+    "export default (class {});",
+    "export default function () {}",
+    "export default (a, b);",
+    "export default a = 1;",
+  ] {
+    let module = Parser::new(js.as_bytes()).parse_module().unwrap();
+    let code = Generator::new(Options::compact()).module(&module);
+    assert_eq!(
+      Parser::new(code.as_bytes()).parse_module().unwrap(),
+      module,
+      "{}",
+      code
+    );
+  }
+  assert_eq!(
+    format_module("export default (function(){})", &Options::pretty()).unwrap(),
+    "export default (function () {});\n"
+  );
+}
+
+#[test]
+fn parentheses() {
+  for (js, expected) in &[
+    ("(a + b) * c", "(a+b)*c"),
+    ("a + (b * c)", "a+b*c"),
+    ("(a + b) + c", "a+b+c"),
+    ("a + (b + c)", "a+(b+c)"),
+    ("a = (b = c)", "a=b=c"),
+    ("(a, b), c", "a,b,c"),
+    ("a, (b, c)", "a,(b,c)"),
+    ("(a ? b : c) ? d : e", "(a?b:c)?d:e"),
+    ("a ? (b, c) : d = e", "a?(b,c):d=e"),
+    ("(a || b) ?? c", "(a||b)??c"),
+    ("(-a) ** b", "(-a)**b"),
+    ("(a ** b) ** c", "(a**b)**c"),
+    ("a ** (b ** c)", "a**b**c"),
+    ("(a => a)(b)", "(a=>a)(b)"),
+    ("(function () {})()", "(function(){})()"),
+    ("({}).a", "({}).a"),
+    ("({} = a)", "({}=a)"),
+    ("(1).a", "(1).a"),
+    ("(1.5).a", "(1.5).a"),
+    ("a - -b", "a- -b"),
+    ("a + +b", "a+ +b"),
+    ("a - --b", "a- --b"),
+    ("a-- - b", "a-- -b"),
+    ("a < !b", "a< !b"),
+    ("typeof (a + b)", "typeof(a+b)"),
+    ("typeof a", "typeof a"),
+    ("a in b", "a in b"),
+    ("new (a.b())()", "new (a.b())()"),
+    ("new (a())", "new (a())()"),
+    ("new a.b()", "new a.b()"),
+    ("(new a)()", "new a()()"),
+    ("(a?.b).c", "(a?.b).c"),
+    ("a?.b.c", "a?.b.c"),
+    ("(x = 1) => ({ x })", "(x=1)=>({x})"),
+    ("async () => {}", "async()=>{}"),
+    ("(a, b) => (c, d)", "(a,b)=>(c,d)"),
+    ("!(a instanceof b)", "!(a instanceof b)"),
+    ("(yield) => 1", "yield=>1"),
+  ] {
+    assert_eq!(compact(js), *expected, "{}", js);
+  }
+}
+
+#[test]
+fn for_in_initializers() {
+  assert_eq!(
+    pretty("for (var a = (b in c); a;) {}"),
+    "for (var a = (b in c); a;) {}\n"
+  );
+  assert_eq!(pretty("for ((a in b); a;) {}"), "for ((a in b); a;) {}\n");
+  assert_eq!(
+    compact("for (let a = () => (b in c);;) {}"),
+    "for(let a=()=>(b in c);;){}"
+  );
+  assert_eq!(
+    compact("for (let a = [b in c];;) {}"),
+    "for(let a=[b in c];;){}"
+  );
+}
+
+#[test]
+fn dangling_else() {
+  assert_eq!(
+    pretty("if (a) { if (b) c(); } else d();"),
+    "if (a) {\n  if (b) c();\n} else d();\n"
+  );
+  assert_eq!(
+    compact("if (a) { for (;;) if (b) c(); } else d();"),
+    "if(a){for(;;)if(b)c();}else d();"
+  );
+  assert_eq!(compact("if (a) b(); else c();"), "if(a)b();else c();");
+}
+
+#[test]
+fn strings() {
+  let string = |value: &str, quotes: Quotes| {
+    Generator::new(Options::pretty().with_quotes(quotes))
+      .expression(&Node::from(Const::from(value)))
+  };
+  assert_eq!(string("a", Quotes::Double), "\"a\"");
+  assert_eq!(string("a", Quotes::Single), "'a'");
+  assert_eq!(string("it's", Quotes::Single), "\"it's\"");
+  assert_eq!(string("say \"hi\"", Quotes::Double), "'say \"hi\"'");
+  assert_eq!(string("'\"", Quotes::Double), "\"'\\\"\"");
+  assert_eq!(string("'\"", Quotes::Single), "'\\'\"'");
+  assert_eq!(
+    string("\\\n\r\t\u{8}\u{c}\u{b}\u{1f}", Quotes::Double),
+    "\"\\\\\\n\\r\\t\\b\\f\\x0b\\x1f\""
+  );
+  assert_eq!(string("\0a\u{0}1", Quotes::Double), "\"\\0a\\x001\"");
+  assert_eq!(
+    string("\u{2028}\u{2029}", Quotes::Double),
+    "\"\\u2028\\u2029\""
+  );
+  assert_eq!(string("é 😀", Quotes::Double), "\"é 😀\"");
+
+  // The escapes are read back by the lexer.
+  let value = "\\\n\r\t\u{8}\u{c}\u{b}\u{1f}\0a\u{0}1\u{2028}'\"é";
+  let code = string(value, Quotes::Double);
+  assert_eq!(
+    parse(&code).items(),
+    &[Node::from(Directive::new(value, code.clone()))][..],
+    "{}",
+    code
+  );
+}
+
+#[test]
+fn directives() {
+  // Directives are printed as written.
+  assert_eq!(
+    compact("'use strict'; \"use\\x20strict\"; 'a\"b'; f();"),
+    "'use strict';\"use\\x20strict\";'a\"b';f()"
+  );
+  // Other string statements are not printed as directives.
+  assert_eq!(
+    compact("('use strict'); f(); 'a';"),
+    "(\"use strict\");f();\"a\""
+  );
+  assert_eq!(
+    compact("function f() { 'use strict'; ('a'); }"),
+    "function f(){'use strict';(\"a\")}"
+  );
+  let script = StatementList::from(vec![Node::from(Const::from("use strict"))]);
+  assert_eq!(
+    Generator::new(Options::compact()).script(&script),
+    "(\"use strict\")"
+  );
+}
+
+#[test]
+fn numbers() {
+  let number =
+    |value: f64| Generator::new(Options::compact()).expression(&Const::from(value).into());
+  assert_eq!(number(0.5), "0.5");
+  assert_eq!(number(1e21), "1e21");
+  assert_eq!(number(1.5e-7), "1.5e-7");
+  assert_eq!(number(123456.0), "123456");
+  assert_eq!(number(1e100), "1e100");
+  assert_eq!(number(-0.0), "-0");
+  assert_eq!(number(f64::INFINITY), "1e999");
+  assert_eq!(number(f64::NAN), "NaN");
+
+  // Negative numbers are operands of a minus sign.
+  let neg = Node::from(Const::from(-1));
+  let member = Node::from(GetConstField::new(neg.clone(), "a"));
+  assert_eq!(
+    Generator::new(Options::compact()).expression(&member),
+    "(-1).a"
+  );
+  let power = Node::from(BinOp::new(NumOp::Exp, neg.clone(), Const::from(2)));
+  assert_eq!(
+    Generator::new(Options::compact()).expression(&power),
+    "(-1)**2"
+  );
+  let minus = Node::from(UnaryNode::new(UnaryOp::Minus, neg));
+  assert_eq!(
+    Generator::new(Options::compact()).expression(&minus),
+    "- -1"
+  );
+}
+
+#[test]
+fn pretty_output() {
+  let js = "import a from 'a'; let x = 1; function f(a, b) { if (a) { return b } else return; } \
+            class A extends B { constructor() { super() } m() { return this.#p } #p = 1 } \
+            const o = { a, 'b-c': [1, 2], d() {} }; switch (x) { case 1: f(); break; default: }";
+  let expected = r#"import a from "a";
+
+let x = 1;
+
+function f(a, b) {
+  if (a) {
+    return b;
+  } else return;
+}
+
+class A extends B {
+  constructor() {
+    super();
+  }
+  m() {
+    return this.#p;
+  }
+  #p = 1;
+}
+
+const o = { a, "b-c": [1, 2], d() {} };
+switch (x) {
+  case 1:
+    f();
+    break;
+  default:
+}
+"#;
+  assert_eq!(format_module(js, &Options::pretty()).unwrap(), expected);
+  assert_eq!(
+    format_module(expected, &Options::pretty()).unwrap(),
+    expected
+  );
+}
+
+#[test]
+fn line_width() {
+  let js = "call(argument, anotherArgument, yetAnotherArgument, [1, 2, 3], { key: value });";
+  assert_eq!(
+    format_script(js, &Options::pretty()).unwrap(),
+    format!("{}\n", js)
+  );
+  assert_eq!(
+    format_script(js, &Options::pretty().with_line_width(40)).unwrap(),
+    "call(\n  argument,\n  anotherArgument,\n  yetAnotherArgument,\n  [1, 2, 3],\n  { key: value },\n);\n"
+  );
+  assert_eq!(
+    format_script(
+      js,
+      &Options::pretty()
+        .with_line_width(40)
+        .with_trailing_commas(false)
+        .with_indent(Indent::Tab)
+    )
+    .unwrap(),
+    "call(\n\targument,\n\tanotherArgument,\n\tyetAnotherArgument,\n\t[1, 2, 3],\n\t{ key: value }\n);\n"
+  );
+  // Trailing commas are never printed after rest elements.
+  assert_eq!(
+    format_script(
+      "function f(aaaaaaaaaa, ...bbbbbbbbbb) {}",
+      &Options::pretty().with_line_width(20)
+    )
+    .unwrap(),
+    "function f(\n  aaaaaaaaaa,\n  ...bbbbbbbbbb\n) {}\n"
+  );
+  // A last function argument keeps the parentheses on its lines.
+  assert_eq!(
+    pretty("it('works', () => { expect(a).toBe(1); });"),
+    "it(\"works\", () => {\n  expect(a).toBe(1);\n});\n"
+  );
+  // Operations break after their operator.
+  assert_eq!(
+    format_script(
+      "const total = firstValue + secondValue + thirdValue;",
+      &Options::pretty().with_line_width(40)
+    )
+    .unwrap(),
+    "const total = firstValue + secondValue +\n  thirdValue;\n"
+  );
+  assert_eq!(
+    format_script(
+      "const value = condition ? someValue : otherValue;",
+      &Options::pretty().with_line_width(40)
+    )
+    .unwrap(),
+    "const value = condition\n  ? someValue\n  : otherValue;\n"
+  );
+}
+
+#[test]
+fn semicolons() {
+  let options = Options::pretty().with_semicolons(false);
+  let js = "let a = b; (c || d).e(); [f, g] = h; +i; -j; `k`; l; do m++; while (n); for (;;); o: ;";
+  let code = format_script(js, &options).unwrap();
+  assert_eq!(
+    code,
+    "let a = b\n;(c || d).e()\n;[f, g] = h\n;+i\n;-j\n;`k`\nl\ndo m++;\nwhile (n)\nfor (;;);\no: ;\n"
+  );
+  assert_eq!(parse(&code), parse(js));
+
+  let class = "class A { a = 1; ['b']() {} static; c() {} d; *e() {} f = 2; g = 3 }";
+  let code = format_script(class, &options).unwrap();
+  assert_eq!(
+    code,
+    "class A {\n  a = 1;\n  [\"b\"]() {}\n  static;\n  c() {}\n  d;\n  *e() {}\n  f = 2\n  g = 3\n}\n"
+  );
+  assert_eq!(parse(&code), parse(class));
+}
+
+/// Comments attached to function declarations by name.
+/// Comments attached to the parts starting on the given lines.
+struct LineComments(Vec<(u32, Vec<Comment>, Vec<Comment>)>);
+
+impl LineComments {
+  fn find(&self, span: Span) -> Option<&(u32, Vec<Comment>, Vec<Comment>)> {
+    let line = span.start().line_number();
+    self.0.iter().find(|(start, ..)| *start == line)
+  }
+}
+
+impl Comments for LineComments {
+  fn leading(&self, span: Span) -> &[Comment] {
+    self.find(span).map_or(&[], |(_, leading, _)| leading)
+  }
+
+  fn trailing(&self, span: Span) -> &[Comment] {
+    self.find(span).map_or(&[], |(_, _, trailing)| trailing)
+  }
+}
+
+/// Gets a span on its own line.
+fn line(line: u32) -> Span {
+  Span::new(Position::new(line, 1), Position::new(line, 2))
+}
+
+#[test]
+fn pure_annotations() {
+  assert_eq!(
+    compact("a = /*#__PURE__*/ f(1); b = /* @__PURE__ */ new C; c = /*#__PURE__*/ g().h();"),
+    "a=/*#__PURE__*/f(1);b=/*#__PURE__*/new C();c=/*#__PURE__*/g().h()"
+  );
+  // Other comments and annotations that do not precede a call are dropped.
+  assert_eq!(
+    compact("a = /* pure */ f(); b = /*#__PURE__*/ c; /*#__PURE__*/\nd();"),
+    "a=f();b=c;/*#__PURE__*/d()"
+  );
+}
+
+#[test]
+fn comments() {
+  let comments = LineComments(vec![
+    (
+      1,
+      vec![
+        Comment::line(" Adds one."),
+        Comment::block("*\n * @param {number} a\n "),
+      ],
+      vec![],
+    ),
+    (2, vec![], vec![Comment::line(" unused")]),
+  ]);
+  let script = StatementList::from(vec![
+    Node::from(
+      FunctionDecl::new(
+        Box::from("f"),
+        vec![],
+        StatementList::from(vec![Node::from(
+          FunctionDecl::new(Box::from("g"), vec![], StatementList::from(vec![])).with_span(line(2)),
+        )]),
+      )
+      .with_span(line(1)),
+    ),
+    Node::from(Identifier::from("f")),
+  ]);
+  let generator = Generator::new(Options::pretty()).with_comments(&comments);
+  assert_eq!(
+    generator.script(&script),
+    "// Adds one.\n/**\n * @param {number} a\n */\nfunction f() {\n  function g() {} // unused\n}\n\nf;\n"
+  );
+  let generator = Generator::new(Options::compact()).with_comments(&comments);
+  assert_eq!(
+    generator.script(&script),
+    "/* Adds one.*/ /**\n * @param {number} a\n */function f(){function g(){}/* unused*/}f"
+  );
+}
+
+/// Gets the comments of a source, sorted, as their kind and text, purity annotations being
+/// printed as `/*#__PURE__*/`.
+fn source_comments(js: &str) -> Vec<(bool, String)> {
+  let script = parse(js);
+  let mut comments: Vec<_> = CommentMap::script(js, &script)
+    .comments()
+    .iter()
+    .map(|comment| match comment.text().trim() {
+      "#__PURE__" | "@__PURE__" => (true, "#__PURE__".to_owned()),
+      text => (comment.is_block(), text.to_owned()),
+    })
+    .collect();
+  comments.sort();
+  comments
+}
+
+/// Formats a script, checking that it keeps all its comments and gives a script with the same
+/// syntax tree.
+#[track_caller]
+fn format_keeping_comments(js: &str, options: &Options) -> String {
+  let code =
+    format_script(js, options).unwrap_or_else(|err| panic!("failed to format `{}`: {}", js, err));
+  let reparsed = Parser::new(code.as_bytes())
+    .parse_script()
+    .unwrap_or_else(|err| {
+      panic!(
+        "failed to parse `{}` formatted from `{}`: {}",
+        code, js, err
+      )
+    });
+  assert_eq!(reparsed, parse(js), "`{}` formatted as `{}`", js, code);
+  // Compact code has no line comments.
+  let kinds = |comments: Vec<(bool, String)>| -> Vec<(bool, String)> {
+    let mut comments: Vec<_> = comments
+      .into_iter()
+      .map(|(block, text)| (block || options.is_compact(), text))
+      .collect();
+    comments.sort();
+    comments
+  };
+  assert_eq!(
+    kinds(source_comments(&code)),
+    kinds(source_comments(js)),
+    "`{}` formatted as `{}`",
+    js,
+    code
+  );
+  code
+}
+
+/// Checks that formatting a script keeps all its comments, and that pretty formatting is
+/// idempotent.
+///
+/// Compact code is on a single line, where comments after a comma trail the previous part.
+#[track_caller]
+fn assert_keeps_comments(js: &str) {
+  let code = format_keeping_comments(js, &Options::pretty());
+  assert_eq!(
+    format_script(&code, &Options::pretty()).unwrap(),
+    code,
+    "formatting `{}` is not idempotent",
+    js
+  );
+  format_keeping_comments(js, &Options::compact());
+}
+
+#[test]
+fn comments_in_expressions() {
+  for js in &[
+    "a = b + /* c */ c;",
+    "a = b /* b */ + c;",
+    "f(a, /* b */ b);",
+    "f(a /* a */, b);",
+    "f(/* a */);",
+    "x = // line\n  1;",
+    "function f() {\n  return ( // why\n    a\n  );\n}",
+    "a = (/* b */ b, c);",
+    "a = b ? /* c */ c : /* d */ d;",
+    "a = /* await */ f(/* x */ x => /* y */ y);",
+    "a = `${/* b */ b}`;",
+    "a = tag/* t */`b`;",
+    "a.b /* b */.c();",
+  ] {
+    assert_keeps_comments(js);
+  }
+}
+
+#[test]
+fn comments_around_members() {
+  for js in &[
+    "a = {\n  // first\n  b: 1, // one\n  /* c */ c: 2,\n  d /* d */: 3,\n  // last\n};",
+    "a = { b: /* b */ 1, [/* c */ c]: 2, d() /* d */ {}, ...e /* e */ };",
+    "a = [\n  // first\n  1, // one\n  /* two */ 2,\n  // last\n];",
+    "a = [1, /* hole */, 2];",
+    "class A {\n  // a\n  a = 1; // one\n  /* b */ b() {}\n  // last\n}",
+    "switch (a) {\n  // one\n  case 1: // case\n    b();\n  // default\n  default:\n    c(); // c\n}",
+    "function f(/* a */ a, b /* b */, // c\n  c) {}",
+    "var a = 1, // a\n  b = 2; // b",
+  ] {
+    assert_keeps_comments(js);
+  }
+}
+
+#[test]
+fn comments_in_empty_bodies() {
+  for js in &[
+    "function f() {\n  // empty\n}",
+    "if (a) {\n  /* empty */\n} else {\n  // empty\n}",
+    "class A {\n  // empty\n}",
+    "class A {\n  a() {\n    // empty\n  }\n}",
+    "a = {\n  // empty\n};",
+    "a = [\n  // empty\n];",
+    "a = { b() { /* empty */ } };",
+    "switch (a) {\n  // empty\n}",
+    "try {\n  // try\n} catch (e) {\n  // catch\n} finally {\n  // finally\n}",
+    "a = () => {\n  // empty\n};",
+    "f(/* none */);",
+  ] {
+    assert_keeps_comments(js);
+  }
+}
+
+#[test]
+fn comments_after_statements() {
+  for js in &[
+    "function f() {\n  a();\n  // after\n}",
+    "if (a) {\n  b(); /* b */\n  // after\n}",
+    "a();\n// end",
+    "a(); /* end */",
+    "// only",
+    "/* only */",
+    "// one\n/* two */\n",
+    "a();\n\n/* end */\n// end\n",
+  ] {
+    assert_keeps_comments(js);
+  }
+}
+
+#[test]
+fn comments_everywhere() {
+  // Comments between every two tokens.
+  // The comments inside patterns move around them, so formatting is not idempotent.
+  for js in SCRIPTS {
+    for options in &[Options::pretty(), Options::compact()] {
+      let block = js.replace(' ', " /*k*/ ");
+      format_keeping_comments(&block, options);
+      // New lines are not allowed everywhere, as in `async\nfunction` or in strings.
+      let line = js.replace(' ', " // k\n");
+      if !js.contains(&['"', '\'', '`'][..]) && Parser::new(line.as_bytes()).parse_script().is_ok()
+      {
+        format_keeping_comments(&line, options);
+      }
+    }
+  }
+}
+
+#[test]
+fn generated_trees() {
+  // Trees built by hand, which the parser would not produce.
+  let new = Node::from(New::new(Node::from(Identifier::from("A")), vec![]));
+  assert_eq!(
+    Generator::new(Options::pretty()).expression(&new),
+    "new A()"
+  );
+  let undefined = Node::from(Const::Undefined);
+  assert_eq!(
+    Generator::new(Options::pretty()).expression(&undefined),
+    "undefined"
+  );
+}
+
+#[test]
+fn check_sources() {
+  let options = Options::pretty();
+  assert_eq!(
+    check("let a = 1;\n", SourceType::Script, &options).unwrap(),
+    None
+  );
+  let mismatch = check("let a = 1;\nlet  b = 2;\n", SourceType::Script, &options)
+    .unwrap()
+    .expect("the source is not formatted");
+  assert_eq!(mismatch.line(), 2);
+  assert_eq!(mismatch.expected(), "let b = 2;");
+  assert_eq!(mismatch.found(), "let  b = 2;");
+  let mismatch = check("let a = 1;", SourceType::Script, &options)
+    .unwrap()
+    .unwrap();
+  // The source lacks the final new line.
+  assert_eq!((mismatch.line(), mismatch.found()), (2, ""));
+  assert!(check("let a = ;", SourceType::Script, &options).is_err());
+  assert!(check("import a from 'a';\n", SourceType::Script, &options).is_err());
+}
+
+#[test]
+fn check_directory() {
+  let dir = std::env::temp_dir().join(format!("plastic-codegen-{}", std::process::id()));
+  std::fs::create_dir_all(&dir).unwrap();
+  let files = [
+    ("formatted.js", "import a from \"a\";\n"),
+    ("unformatted.mjs", "export  const a = 1;\n"),
+    ("script.cjs", "var yield = 1;\n"),
+    ("invalid.js", "let = ;"),
+  ];
+  for (name, source) in &files {
+    std::fs::write(dir.join(name), source).unwrap();
+  }
+  let paths: Vec<_> = files.iter().map(|(name, _)| dir.join(name)).collect();
+  let report = check_files(&paths, &Options::pretty());
+  std::fs::remove_dir_all(&dir).unwrap();
+
+  assert!(!report.is_ok());
+  assert_eq!(report.checked(), 4);
+  assert_eq!(report.unformatted().len(), 1);
+  assert_eq!(report.unformatted()[0].0, dir.join("unformatted.mjs"));
+  assert_eq!(report.errors().len(), 1, "{}", report);
+  assert_eq!(report.errors()[0].0, dir.join("invalid.js"));
+  let text = report.to_string();
+  assert!(
+    text.starts_with(&format!(
+      "{}:1: formatting differs, expected `export const a = 1;`, found `export  const a = 1;`\n",
+      dir.join("unformatted.mjs").display()
+    )),
+    "{}",
+    text
+  );
+}
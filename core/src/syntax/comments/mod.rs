@@ -0,0 +1,571 @@
+//! Comments: finding them in the source, and attaching them to the parts of the syntax tree.
+//!
+//! The parser leaves comments out of the syntax tree, so a [`CommentMap`] lexes the source
+//! again to find them, and attaches each comment to a statement by where it is:
+//!
+//!  - A comment between two statements of a list is a trailing comment of the first one if it
+//!    starts on the line the first one ends on, and a leading comment of the second one
+//!    otherwise.
+//!  - A comment before the first statement of a list is a leading comment of that statement.
+//!  - A comment after the last statement of a list is a trailing comment of that statement if
+//!    it starts on the line the statement ends on.
+//!
+//! The elements of a class body, its constructor included, and the properties of an object
+//! literal get their comments the same way, as the statements of a list.
+//!
+//! The other comments, such as those inside expressions, are attached to the parts of the tree
+//! around them, the parts being those listed by [`codegen::Comments`]. In the innermost part
+//! containing the comment:
+//!
+//!  - A comment on its own line is a leading comment of the next part, if there is one.
+//!  - A comment at the end of a line is a trailing comment of the previous part, if there is
+//!    one, and a leading comment of the next part otherwise.
+//!  - A comment with code before and after it on its line is a leading comment of the next part
+//!    if a token, such as a comma, separates it from the previous part, and a trailing comment
+//!    of the previous part otherwise.
+//!  - A comment with no part to attach to is an inner comment of the part containing it, as
+//!    the comments of an empty block or on their own lines after the last statement of a block.
+//!    The comments after the last statement of the source are its end comments.
+//!
+//! Every comment is attached once, and always to the same part of the tree for the same source.
+//!
+//! A comment map can be given to the [code generator](codegen::Generator), to print all the
+//! comments along with their parts:
+//!
+//! ```
+//! use plastic::syntax::{codegen, comments::CommentMap, parser::Parser};
+//!
+//! let source = "// Greets.\nhello( /* you */ ) // first\n\n/* then */ bye( )\n// End.\n";
+//! let script = Parser::new(source.as_bytes()).parse_script().unwrap();
+//! let comments = CommentMap::script(source, &script);
+//! let generator = codegen::Generator::new(codegen::Options::pretty()).with_comments(&comments);
+//! assert_eq!(
+//!   generator.script(&script),
+//!   "// Greets.\nhello /* you */(); // first\n/* then */\nbye();\n// End.\n"
+//! );
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use crate::syntax::{
+  ast::{
+    module::Module,
+    node::{
+      Block, Case, Catch, Class, ClassElement, Declaration, FormalParameter, FunctionExpr, Object,
+      PropertyDefinition, StatementList, TemplateLit,
+    },
+    visitor::{visit, Flow, NodePath, Path, Visitor},
+    Node, Span,
+  },
+  codegen,
+  locate::{Locations, Source},
+  scope::ScopeTree,
+};
+use std::{collections::HashMap, fmt};
+
+/// A comment of a source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Comment<'a> {
+  span: Span,
+  text: &'a str,
+  block: bool,
+}
+
+impl<'a> Comment<'a> {
+  /// Creates a comment, its text being the source between its delimiters.
+  pub(crate) fn new(span: Span, text: &'a str, block: bool) -> Self {
+    Self { span, text, block }
+  }
+
+  /// Gets the span of the comment, delimiters included.
+  pub fn span(&self) -> Span {
+    self.span
+  }
+
+  /// Gets the text of the comment, without its delimiters.
+  pub fn text(&self) -> &'a str {
+    self.text
+  }
+
+  /// Returns `true` for a `/* */` comment, `false` for a `//` comment.
+  pub fn is_block(&self) -> bool {
+    self.block
+  }
+
+  /// Returns `true` for a `/** */` documentation comment.
+  ///
+  /// Comments starting with more stars, such as `/*** ***/` banners, are not documentation.
+  pub fn is_jsdoc(&self) -> bool {
+    self.block && self.text.starts_with('*') && !self.text.starts_with("**")
+  }
+
+  /// Converts the comment to a comment of the [code generator](codegen::Generator).
+  pub fn to_codegen(&self) -> codegen::Comment {
+    if self.block {
+      codegen::Comment::block(self.text)
+    } else {
+      codegen::Comment::line(self.text)
+    }
+  }
+}
+
+/// A part of the syntax tree comments are attached to, as listed by [`codegen::Comments`].
+pub trait Commented {
+  /// Gets the span of the source the part was parsed from, by which its comments are found.
+  fn span(&self) -> Option<Span>;
+}
+
+macro_rules! commented {
+  ($($part:ty),*) => {
+    $(
+      impl Commented for $part {
+        fn span(&self) -> Option<Span> {
+          <$part>::span(self)
+        }
+      }
+    )*
+  };
+}
+
+commented!(
+  Node,
+  ClassElement,
+  FunctionExpr,
+  PropertyDefinition,
+  Case,
+  Block,
+  Catch,
+  Declaration,
+  FormalParameter,
+  TemplateLit
+);
+
+/// The comments attached to one side of a part of the syntax tree.
+#[derive(Debug, Default)]
+struct Attached<'a> {
+  comments: Vec<Comment<'a>>,
+  /// The same comments, for the code generator.
+  printed: Vec<codegen::Comment>,
+}
+
+impl<'a> Attached<'a> {
+  fn push(&mut self, comment: Comment<'a>) {
+    self.printed.push(comment.to_codegen());
+    self.comments.push(comment);
+  }
+}
+
+/// The comments of a source, attached to the parts of its syntax tree.
+///
+/// The parts of the tree are known by their span, so the map applies to the syntax tree it was
+/// created with and to the parts of it which keep their spans, wherever they are moved. The parts
+/// created after parsing have no comments.
+pub struct CommentMap<'a> {
+  comments: Vec<Comment<'a>>,
+  /// The comments before each part, by the span of the part.
+  leading: HashMap<Span, Attached<'a>>,
+  /// The comments after each part on the same line, by the span of the part.
+  trailing: HashMap<Span, Attached<'a>>,
+  /// The comments inside each part with no part to attach to, by the span of the part.
+  inner: HashMap<Span, Attached<'a>>,
+  /// The comments after the last statement.
+  end: Attached<'a>,
+}
+
+impl<'a> CommentMap<'a> {
+  /// Attaches the comments of the source of a script to its statements.
+  pub fn script(source: &'a str, script: &StatementList) -> Self {
+    Self::new(source, script.items(), &ScopeTree::script(script))
+  }
+
+  /// Attaches the comments of the source of a module to its statements.
+  pub fn module(source: &'a str, module: &Module) -> Self {
+    Self::new(source, module.body().items(), &ScopeTree::module(module))
+  }
+
+  fn new(text: &'a str, statements: &[Node], scopes: &ScopeTree) -> Self {
+    let source = Source::new(text);
+    let locations = Locations::new(&source, statements, scopes);
+    let mut attacher = Attacher {
+      source: &source,
+      locations: &locations,
+      attached: vec![false; source.comments.len()],
+      leading: HashMap::new(),
+      trailing: HashMap::new(),
+      inner: HashMap::new(),
+      end: Attached::default(),
+    };
+    let _ = attacher.visit_statements(statements, &mut Path::new());
+    let mut parts = Parts::default();
+    let _ = parts.visit_statements(statements, &mut Path::new());
+    attacher.attach_rest(&parts.parts);
+    let Attacher {
+      leading,
+      trailing,
+      inner,
+      end,
+      ..
+    } = attacher;
+    Self {
+      comments: source.comments,
+      leading,
+      trailing,
+      inner,
+      end,
+    }
+  }
+
+  /// Gets all the comments of the source, in order.
+  pub fn comments(&self) -> &[Comment<'a>] {
+    &self.comments
+  }
+
+  /// Gets the comments before a part of the syntax tree, in order: on the lines before a
+  /// statement or a member.
+  pub fn leading<T: Commented>(&self, part: &T) -> &[Comment<'a>] {
+    find(&self.leading, part.span()).map_or(&[], |attached| &attached.comments)
+  }
+
+  /// Gets the comments after a part of the syntax tree on the same line, in order: at the end
+  /// of the line of a statement or a member.
+  pub fn trailing<T: Commented>(&self, part: &T) -> &[Comment<'a>] {
+    find(&self.trailing, part.span()).map_or(&[], |attached| &attached.comments)
+  }
+
+  /// Gets the comments inside a part of the syntax tree with no other part to attach to, in
+  /// order.
+  pub fn inner<T: Commented>(&self, part: &T) -> &[Comment<'a>] {
+    find(&self.inner, part.span()).map_or(&[], |attached| &attached.comments)
+  }
+
+  /// Gets the comments after the last statement, in order.
+  pub fn end(&self) -> &[Comment<'a>] {
+    &self.end.comments
+  }
+
+  /// Gets the documentation comment of a statement or a member: its last leading comment, if it
+  /// is a `/** */` comment.
+  pub fn jsdoc<T: Commented>(&self, part: &T) -> Option<Comment<'a>> {
+    self
+      .leading(part)
+      .last()
+      .filter(|comment| comment.is_jsdoc())
+      .copied()
+  }
+}
+
+/// Finds the comments attached to a side of a part.
+fn find<'m, 'a>(
+  side: &'m HashMap<Span, Attached<'a>>,
+  span: Option<Span>,
+) -> Option<&'m Attached<'a>> {
+  side.get(&span?)
+}
+
+impl codegen::Comments for CommentMap<'_> {
+  fn leading(&self, span: Span) -> &[codegen::Comment] {
+    find(&self.leading, Some(span)).map_or(&[], |attached| &attached.printed)
+  }
+
+  fn trailing(&self, span: Span) -> &[codegen::Comment] {
+    find(&self.trailing, Some(span)).map_or(&[], |attached| &attached.printed)
+  }
+
+  fn inner(&self, span: Span) -> &[codegen::Comment] {
+    find(&self.inner, Some(span)).map_or(&[], |attached| &attached.printed)
+  }
+
+  fn end(&self) -> &[codegen::Comment] {
+    &self.end.printed
+  }
+}
+
+impl fmt::Debug for CommentMap<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("CommentMap")
+      .field("comments", &self.comments)
+      .finish()
+  }
+}
+
+/// The visitor attaching the comments to the statements of each list, and to the members of
+/// each class body and object literal, then the other comments to the parts around them.
+struct Attacher<'s, 'a> {
+  source: &'s Source<'a>,
+  locations: &'s Locations,
+  /// Whether each comment is attached.
+  attached: Vec<bool>,
+  leading: HashMap<Span, Attached<'a>>,
+  trailing: HashMap<Span, Attached<'a>>,
+  inner: HashMap<Span, Attached<'a>>,
+  end: Attached<'a>,
+}
+
+impl<'a> Attacher<'_, 'a> {
+  /// Attaches the comments around the statements of a list.
+  fn attach_statements(&mut self, statements: &[Node]) {
+    let parts = statements
+      .iter()
+      .filter_map(|statement| Some((statement.span()?, self.locations.statement(statement)?)))
+      .collect();
+    self.attach(parts, Source::list_end);
+  }
+
+  /// Attaches the comments around the members of a class body or an object literal.
+  fn attach_members<I>(&mut self, members: I)
+  where
+    I: IntoIterator<Item = Option<Span>>,
+  {
+    let locations = self.locations;
+    let mut parts: Vec<_> = members
+      .into_iter()
+      .filter_map(|span| {
+        let span = span?;
+        Some((span, locations.member(span)?))
+      })
+      .collect();
+    parts.sort_by_key(|&(_, start)| start);
+    self.attach(parts, Source::members_end);
+  }
+
+  /// Attaches the comments around the parts of a list, by their key and their first token in
+  /// order, `end` finding the last token of the list from the first token of its last part.
+  fn attach(&mut self, parts: Vec<(Span, usize)>, end: fn(&Source<'a>, usize) -> usize) {
+    let tokens = &self.source.tokens;
+    let mut previous = None;
+    for &(key, start) in &parts {
+      let after = start.checked_sub(1).map(|index| tokens[index].span());
+      let before = tokens[start].span();
+      for index in self.between(after, Some(before)) {
+        let comment = self.source.comments[index];
+        let side = match (previous, after) {
+          (Some(previous), Some(after))
+            if comment.span.start().line_number() == after.end().line_number() =>
+          {
+            self.trailing.entry(previous).or_default()
+          }
+          _ => self.leading.entry(key).or_default(),
+        };
+        side.push(comment);
+        self.attached[index] = true;
+      }
+      previous = Some(key);
+    }
+
+    if let Some(&(key, start)) = parts.last() {
+      let end = end(self.source, start);
+      let after = tokens[end].span();
+      let before = tokens.get(end + 1).map(|token| token.span());
+      for index in self.between(Some(after), before) {
+        let comment = self.source.comments[index];
+        if comment.span.start().line_number() != after.end().line_number() {
+          break;
+        }
+        self.trailing.entry(key).or_default().push(comment);
+        self.attached[index] = true;
+      }
+    }
+  }
+
+  /// Attaches the comments which are not attached to statements or members to the parts around
+  /// them.
+  fn attach_rest(&mut self, parts: &[Part]) {
+    let tokens = &self.source.tokens;
+    for index in 0..self.source.comments.len() {
+      if self.attached[index] {
+        continue;
+      }
+      let comment = self.source.comments[index];
+      let span = comment.span;
+      // The innermost part containing the comment comes last, the parts being in pre-order.
+      let parent = parts
+        .iter()
+        .rposition(|part| part.span.start() <= span.start() && span.end() <= part.span.end());
+      let children = parts.iter().filter(|part| part.parent == parent);
+      let previous = children
+        .clone()
+        .filter(|part| part.span.end() <= span.start())
+        .max_by_key(|part| part.span.end());
+      let next = children
+        .filter(|part| part.span.start() >= span.end())
+        .min_by_key(|part| part.span.start());
+
+      let before = tokens.partition_point(|token| token.span().end() <= span.start());
+      let own_line =
+        before == 0 || tokens[before - 1].span().end().line_number() < span.start().line_number();
+      let line_end = tokens
+        .get(before)
+        .is_none_or(|token| token.span().start().line_number() > span.end().line_number());
+      // A token between the previous part and the comment, such as a comma, ends the part.
+      let separated = |previous: &Part| {
+        tokens[..before]
+          .last()
+          .is_some_and(|token| token.span().start() >= previous.span.end())
+      };
+      let side = match (previous, next) {
+        (_, Some(next)) if own_line => Some((&mut self.leading, next.span)),
+        (Some(previous), _) if !own_line && (line_end || next.is_none()) => {
+          Some((&mut self.trailing, previous.span))
+        }
+        (Some(previous), Some(_)) if !own_line && !separated(previous) => {
+          Some((&mut self.trailing, previous.span))
+        }
+        (_, Some(next)) => Some((&mut self.leading, next.span)),
+        _ => parent.map(|parent| (&mut self.inner, parts[parent].span)),
+      };
+      match side {
+        Some((side, key)) => side.entry(key).or_default().push(comment),
+        None => self.end.push(comment),
+      }
+      self.attached[index] = true;
+    }
+  }
+
+  /// Gets the comments between two tokens, which are not attached yet, the first or the second
+  /// token being the start or the end of the source when they are `None`.
+  fn between(&self, after: Option<Span>, before: Option<Span>) -> Vec<usize> {
+    let comments = &self.source.comments;
+    let first = after.map_or(0, |after| {
+      comments.partition_point(|comment| comment.span.start() < after.end())
+    });
+    let last = before.map_or(comments.len(), |before| {
+      comments.partition_point(|comment| comment.span.start() < before.start())
+    });
+    (first..last.max(first))
+      .filter(|&index| !self.attached[index])
+      .collect()
+  }
+}
+
+impl<'ast> Visitor<'ast> for Attacher<'_, '_> {
+  fn visit_statements(&mut self, statements: &'ast [Node], path: &mut NodePath<'ast>) -> Flow {
+    self.attach_statements(statements);
+    visit::walk_statements(self, statements, path)
+  }
+
+  fn visit_class(&mut self, class: &'ast Class, path: &mut NodePath<'ast>) -> Flow {
+    let constructor = class.constructor().map(FunctionExpr::span);
+    let elements = class.elements().iter().map(ClassElement::span);
+    self.attach_members(constructor.into_iter().chain(elements));
+    visit::walk_class(self, class, path)
+  }
+
+  fn visit_object(&mut self, object: &'ast Object, path: &mut NodePath<'ast>) -> Flow {
+    let properties = object.properties().iter().map(PropertyDefinition::span);
+    self.attach_members(properties);
+    visit::walk_object(self, object, path)
+  }
+}
+
+/// A part of the syntax tree, with the innermost part containing it.
+#[derive(Debug)]
+struct Part {
+  span: Span,
+  parent: Option<usize>,
+}
+
+/// The visitor listing the parts of the syntax tree in pre-order.
+#[derive(Debug, Default)]
+struct Parts {
+  parts: Vec<Part>,
+  /// The parts being visited.
+  stack: Vec<usize>,
+}
+
+impl Parts {
+  /// Enters a part, telling whether it is a new one, the parts sharing their span with the part
+  /// around them, such as a function expression and its node, being the same part.
+  fn enter(&mut self, span: Option<Span>) -> bool {
+    let span = match span {
+      Some(span) => span,
+      None => return false,
+    };
+    let parent = self.stack.last().copied();
+    if parent.is_some_and(|parent| self.parts[parent].span == span) {
+      return false;
+    }
+    self.stack.push(self.parts.len());
+    self.parts.push(Part { span, parent });
+    true
+  }
+
+  /// Visits a part.
+  fn part<F>(&mut self, span: Option<Span>, walk: F) -> Flow
+  where
+    F: FnOnce(&mut Self) -> Flow,
+  {
+    let entered = self.enter(span);
+    let flow = walk(self);
+    if entered {
+      self.stack.pop();
+    }
+    flow
+  }
+}
+
+impl<'ast> Visitor<'ast> for Parts {
+  fn visit_node(&mut self, node: &'ast Node, path: &mut NodePath<'ast>) -> Flow {
+    self.part(node.span(), |parts| visit::walk_node(parts, node, path))
+  }
+
+  fn visit_function_expr(&mut self, expr: &'ast FunctionExpr, path: &mut NodePath<'ast>) -> Flow {
+    self.part(expr.span(), |parts| {
+      visit::walk_function_expr(parts, expr, path)
+    })
+  }
+
+  fn visit_class_element(
+    &mut self,
+    element: &'ast ClassElement,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    self.part(element.span(), |parts| {
+      visit::walk_class_element(parts, element, path)
+    })
+  }
+
+  fn visit_property_definition(
+    &mut self,
+    property: &'ast PropertyDefinition,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    self.part(property.span(), |parts| {
+      visit::walk_property_definition(parts, property, path)
+    })
+  }
+
+  fn visit_case(&mut self, case: &'ast Case, path: &mut NodePath<'ast>) -> Flow {
+    self.part(case.span(), |parts| visit::walk_case(parts, case, path))
+  }
+
+  fn visit_block(&mut self, block: &'ast Block, path: &mut NodePath<'ast>) -> Flow {
+    self.part(block.span(), |parts| visit::walk_block(parts, block, path))
+  }
+
+  fn visit_catch(&mut self, catch: &'ast Catch, path: &mut NodePath<'ast>) -> Flow {
+    self.part(catch.span(), |parts| visit::walk_catch(parts, catch, path))
+  }
+
+  fn visit_declaration(&mut self, decl: &'ast Declaration, path: &mut NodePath<'ast>) -> Flow {
+    self.part(decl.span(), |parts| {
+      visit::walk_declaration(parts, decl, path)
+    })
+  }
+
+  fn visit_formal_parameter(
+    &mut self,
+    parameter: &'ast FormalParameter,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    self.part(parameter.span(), |parts| {
+      visit::walk_formal_parameter(parts, parameter, path)
+    })
+  }
+
+  fn visit_template_lit(&mut self, template: &'ast TemplateLit, path: &mut NodePath<'ast>) -> Flow {
+    self.part(template.span(), |parts| {
+      visit::walk_template_lit(parts, template, path)
+    })
+  }
+}
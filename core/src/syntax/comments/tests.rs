@@ -0,0 +1,182 @@
+//! Tests for the attachment of comments.
+
+use super::{CommentMap, Commented};
+use crate::syntax::{
+  ast::{
+    node::{FunctionDecl, StatementList},
+    Node,
+  },
+  codegen::{Generator, Options},
+  parser::Parser,
+};
+
+#[track_caller]
+fn parse(js: &str) -> StatementList {
+  Parser::new(js.as_bytes())
+    .parse_script()
+    .unwrap_or_else(|err| panic!("failed to parse `{}`: {}", js, err))
+}
+
+/// Gets the texts of comments.
+fn texts<'a>(comments: &[super::Comment<'a>]) -> Vec<&'a str> {
+  comments.iter().map(|comment| comment.text()).collect()
+}
+
+/// Gets the texts of the leading and trailing comments of a part of the syntax tree.
+fn attached<'a, T: Commented>(comments: &CommentMap<'a>, part: &T) -> (Vec<&'a str>, Vec<&'a str>) {
+  (
+    texts(comments.leading(part)),
+    texts(comments.trailing(part)),
+  )
+}
+
+#[test]
+fn statements() {
+  let js = "// a\n/* b */ one(); // c\n/* d */ /* e */\n\ntwo(); /* f */ // g\n// h\n";
+  let script = parse(js);
+  let comments = CommentMap::script(js, &script);
+  let items = script.items();
+  assert_eq!(
+    attached(&comments, &items[0]),
+    (vec![" a", " b "], vec![" c"])
+  );
+  assert_eq!(
+    attached(&comments, &items[1]),
+    (vec![" d ", " e "], vec![" f ", " g"])
+  );
+  // A comment on its own line after the last statement is an end comment.
+  assert_eq!(texts(comments.end()), [" h"]);
+  assert_eq!(comments.comments().len(), 8);
+}
+
+#[test]
+fn nested_lists() {
+  let js = "function f() { // a\n  one();\n  /* b */\n  if (x) {\n    two(); // c\n    \
+            // d\n  }\n} // e\nswitch (x) {\n  case 1: // f\n    three();\n  // g\n  \
+            default:\n    four(1 /* h */);\n}\n";
+  let script = parse(js);
+  let comments = CommentMap::script(js, &script);
+  let items = script.items();
+  let function = match &items[0] {
+    Node::FunctionDecl(function) => function,
+    _ => panic!("expected a function declaration"),
+  };
+  let body = FunctionDecl::body(function);
+  assert_eq!(attached(&comments, &body[0]), (vec![" a"], vec![]));
+  assert_eq!(attached(&comments, &body[1]), (vec![" b "], vec![]));
+  assert_eq!(attached(&comments, &items[0]), (vec![], vec![" e"]));
+
+  let switch = match &items[1] {
+    Node::Switch(switch) => switch,
+    _ => panic!("expected a switch statement"),
+  };
+  let first = &switch.cases()[0].body()[0];
+  assert_eq!(attached(&comments, first), (vec![" f"], vec![]));
+
+  // A comment after the last statement of a block is inner, one before a clause leads it, and
+  // one inside an expression follows the operand before it.
+  let block = match &body[1] {
+    Node::If(stmt) => stmt.body(),
+    _ => panic!("expected an if statement"),
+  };
+  assert_eq!(texts(comments.inner(block)), [" d"]);
+  assert_eq!(attached(&comments, &switch.cases()[1]), (vec![" g"], vec![]));
+  let argument = match &switch.cases()[1].body()[0] {
+    Node::Call(call) => &call.args()[0],
+    _ => panic!("expected a call"),
+  };
+  assert_eq!(attached(&comments, argument), (vec![], vec![" h "]));
+  assert!(comments.end().is_empty());
+}
+
+#[test]
+fn class_members() {
+  let js = "class A extends B {\n  /** Makes one. */\n  constructor() { super(); }\n  \
+            // The count.\n  static count = 0 // zero\n  /** Gets it. */\n  \
+            get value() { return 1; }\n  x\n  // Runs.\n  static { run(); }\n  \
+            /* last */ #y; // y\n}\n";
+  let script = parse(js);
+  let comments = CommentMap::script(js, &script);
+  let class = match &script.items()[0] {
+    Node::ClassDecl(class) => class,
+    _ => panic!("expected a class declaration"),
+  };
+  let constructor = class.constructor().expect("no constructor");
+  assert_eq!(
+    attached(&comments, constructor),
+    (vec!["* Makes one. "], vec![])
+  );
+  let elements = class.elements();
+  assert_eq!(
+    attached(&comments, &elements[0]),
+    (vec![" The count."], vec![" zero"])
+  );
+  assert_eq!(
+    comments.jsdoc(&elements[1]).map(|comment| comment.text()),
+    Some("* Gets it. ")
+  );
+  assert_eq!(attached(&comments, &elements[2]), (vec![], vec![]));
+  assert_eq!(attached(&comments, &elements[3]), (vec![" Runs."], vec![]));
+  assert_eq!(
+    attached(&comments, &elements[4]),
+    (vec![" last "], vec![" y"])
+  );
+  assert!(comments.end().is_empty());
+}
+
+#[test]
+fn object_properties() {
+  let js = "const o = {\n  // a\n  a: 1, // one\n  /** b */\n  b() {},\n  \
+            ...c, /* spread */\n  d\n  // e\n};\n";
+  let script = parse(js);
+  let comments = CommentMap::script(js, &script);
+  let init = match &script.items()[0] {
+    Node::DeclarationList(list, _) => list.as_ref()[0].init().expect("no initializer"),
+    _ => panic!("expected a declaration list"),
+  };
+  let object = match init {
+    Node::Object(object) => object,
+    _ => panic!("expected an object literal"),
+  };
+  let properties = object.properties();
+  assert_eq!(
+    attached(&comments, &properties[0]),
+    (vec![" a"], vec![" one"])
+  );
+  assert_eq!(attached(&comments, &properties[1]), (vec!["* b "], vec![]));
+  assert_eq!(
+    attached(&comments, &properties[2]),
+    (vec![], vec![" spread "])
+  );
+  assert_eq!(attached(&comments, &properties[3]), (vec![], vec![]));
+  assert_eq!(texts(comments.inner(init)), [" e"]);
+}
+
+#[test]
+fn jsdoc() {
+  let js = "/** Documented. */\nfunction f() {}\n/** Not the last. */\n// Last.\n\
+            function g() {}\n/*** Banner. ***/\nfunction h() {}\n";
+  let script = parse(js);
+  let comments = CommentMap::script(js, &script);
+  let items = script.items();
+  assert_eq!(
+    comments.jsdoc(&items[0]).map(|comment| comment.text()),
+    Some("* Documented. ")
+  );
+  assert_eq!(comments.jsdoc(&items[1]), None);
+  assert_eq!(comments.jsdoc(&items[2]), None);
+}
+
+#[test]
+fn printed() {
+  let js = "/**\n * Greets.\n */\nfunction greet(name) {\n  // Say hello.\n  \
+            log('hello', name) // loud\n}\n";
+  let script = parse(js);
+  let comments = CommentMap::script(js, &script);
+  let generator = Generator::new(Options::pretty()).with_comments(&comments);
+  assert_eq!(
+    generator.script(&script),
+    "/**\n * Greets.\n */\nfunction greet(name) {\n  // Say hello.\n  \
+     log(\"hello\", name); // loud\n}\n"
+  );
+}
@@ -0,0 +1,247 @@
+//! Diagnostics: errors and warnings about a source text, and their rendering.
+//!
+//! A [`Diagnostic`] has a severity, a stable code, a message, labels pointing at spans of the
+//! source and notes. Lexer and parser errors convert to diagnostics with these codes:
+//!
+//! | Code    | Error                                                |
+//! |---------|------------------------------------------------------|
+//! | `E0001` | invalid character, literal or escape sequence        |
+//! | `E0002` | the input could not be read                          |
+//! | `E0003` | unexpected token                                     |
+//! | `E0004` | expected token missing                               |
+//! | `E0005` | unexpected end of input                              |
+//! | `E0006` | other syntax error                                   |
+//! | `E0007` | early error, such as a duplicate declaration         |
+//!
+//! Diagnostics are turned into text by a [`Renderer`], either for humans, with the offending
+//! source lines, or as JSON for other tools.
+
+mod render;
+#[cfg(test)]
+mod tests;
+
+pub use self::render::{Format, Renderer};
+
+use crate::syntax::{
+  ast::Span,
+  lexer::Error as LexError,
+  parser::{error::expected_tokens, ParseError},
+};
+use std::fmt;
+
+/// How serious a diagnostic is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+  /// The source is invalid.
+  Error,
+  /// The source is valid, but probably not what was meant.
+  Warning,
+  /// Additional information.
+  Note,
+}
+
+impl Severity {
+  /// Gets the name of the severity, as shown in rendered diagnostics.
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::Error => "error",
+      Self::Warning => "warning",
+      Self::Note => "note",
+    }
+  }
+}
+
+impl fmt::Display for Severity {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+/// A span of the source, with an optional message, attached to a diagnostic.
+///
+/// Primary labels point at the cause of the diagnostic, secondary labels give context, such as
+/// a previous declaration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Label {
+  span: Span,
+  message: Option<Box<str>>,
+  primary: bool,
+}
+
+impl Label {
+  /// Creates a primary label.
+  pub fn primary(span: Span) -> Self {
+    Self {
+      span,
+      message: None,
+      primary: true,
+    }
+  }
+
+  /// Creates a secondary label.
+  pub fn secondary(span: Span) -> Self {
+    Self {
+      span,
+      message: None,
+      primary: false,
+    }
+  }
+
+  /// Sets the message shown next to the label.
+  pub fn with_message<M>(mut self, message: M) -> Self
+  where
+    M: Into<Box<str>>,
+  {
+    self.message = Some(message.into());
+    self
+  }
+
+  /// Gets the span of the label.
+  pub fn span(&self) -> Span {
+    self.span
+  }
+
+  /// Gets the message of the label, if it has one.
+  pub fn message(&self) -> Option<&str> {
+    self.message.as_deref()
+  }
+
+  /// Returns `true` for a primary label.
+  pub fn is_primary(&self) -> bool {
+    self.primary
+  }
+}
+
+/// An error or warning about a source text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+  severity: Severity,
+  code: Option<Box<str>>,
+  message: Box<str>,
+  labels: Vec<Label>,
+  notes: Vec<Box<str>>,
+}
+
+impl Diagnostic {
+  /// Creates a new diagnostic without code, labels or notes.
+  pub fn new<M>(severity: Severity, message: M) -> Self
+  where
+    M: Into<Box<str>>,
+  {
+    Self {
+      severity,
+      code: None,
+      message: message.into(),
+      labels: Vec::new(),
+      notes: Vec::new(),
+    }
+  }
+
+  /// Creates a new error.
+  pub fn error<M>(message: M) -> Self
+  where
+    M: Into<Box<str>>,
+  {
+    Self::new(Severity::Error, message)
+  }
+
+  /// Creates a new warning.
+  pub fn warning<M>(message: M) -> Self
+  where
+    M: Into<Box<str>>,
+  {
+    Self::new(Severity::Warning, message)
+  }
+
+  /// Sets the code of the diagnostic, such as `E0007`.
+  pub fn with_code<C>(mut self, code: C) -> Self
+  where
+    C: Into<Box<str>>,
+  {
+    self.code = Some(code.into());
+    self
+  }
+
+  /// Adds a label.
+  pub fn with_label(mut self, label: Label) -> Self {
+    self.labels.push(label);
+    self
+  }
+
+  /// Adds a note, shown after the source lines.
+  pub fn with_note<N>(mut self, note: N) -> Self
+  where
+    N: Into<Box<str>>,
+  {
+    self.notes.push(note.into());
+    self
+  }
+
+  /// Gets the severity of the diagnostic.
+  pub fn severity(&self) -> Severity {
+    self.severity
+  }
+
+  /// Gets the code of the diagnostic, if it has one.
+  pub fn code(&self) -> Option<&str> {
+    self.code.as_deref()
+  }
+
+  /// Gets the message of the diagnostic.
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+
+  /// Gets the labels of the diagnostic.
+  pub fn labels(&self) -> &[Label] {
+    &self.labels
+  }
+
+  /// Gets the notes of the diagnostic.
+  pub fn notes(&self) -> &[Box<str>] {
+    &self.notes
+  }
+
+  /// Gets the span of the first primary label, or of the first label if none is primary.
+  pub fn primary_span(&self) -> Option<Span> {
+    self
+      .labels
+      .iter()
+      .find(|label| label.primary)
+      .or_else(|| self.labels.first())
+      .map(Label::span)
+  }
+}
+
+impl From<&ParseError> for Diagnostic {
+  fn from(error: &ParseError) -> Self {
+    let diagnostic = Self::error(error.message());
+    match error {
+      ParseError::Lex {
+        err: LexError::Syntax(_, position),
+      } => diagnostic
+        .with_code("E0001")
+        .with_label(Label::primary(Span::from(*position))),
+      ParseError::Lex { .. } => diagnostic.with_code("E0002"),
+      ParseError::Unexpected { span, .. } => diagnostic
+        .with_code("E0003")
+        .with_label(Label::primary(*span)),
+      ParseError::Expected { expected, span, .. } => diagnostic.with_code("E0004").with_label(
+        Label::primary(*span).with_message(format!("expected {}", expected_tokens(expected))),
+      ),
+      ParseError::AbruptEnd => diagnostic.with_code("E0005"),
+      ParseError::General { position, .. } => diagnostic
+        .with_code("E0006")
+        .with_label(Label::primary(Span::from(*position))),
+      ParseError::Early { span, .. } => diagnostic
+        .with_code("E0007")
+        .with_label(Label::primary(*span)),
+    }
+  }
+}
+
+impl From<ParseError> for Diagnostic {
+  fn from(error: ParseError) -> Self {
+    Self::from(&error)
+  }
+}
@@ -0,0 +1,335 @@
+//! Rendering of diagnostics as text, for humans or as JSON.
+
+use super::{Diagnostic, Label, Severity};
+use crate::syntax::ast::Position;
+use std::fmt::Write;
+
+/// ANSI style of the severity and of the primary labels of errors.
+const RED: &str = "1;31";
+/// ANSI style of the severity and of the primary labels of warnings.
+const YELLOW: &str = "1;33";
+/// ANSI style of the severity and of the primary labels of notes.
+const CYAN: &str = "1;36";
+/// ANSI style of the line numbers and of the secondary labels.
+const BLUE: &str = "1;34";
+/// ANSI style of the message.
+const BOLD: &str = "1";
+
+/// Number of columns a tab is expanded to in source lines.
+const TAB_WIDTH: usize = 4;
+
+/// The output format of a [`Renderer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+  /// Text for humans, showing the source lines the labels point at.
+  Human,
+  /// A JSON object for each diagnostic, with the positions of its labels.
+  Json,
+}
+
+/// Renders diagnostics about a source text.
+///
+/// In the human format, a diagnostic looks like this:
+///
+/// ```text
+/// error[E0007]: identifier 'a' has already been declared
+///  --> main.js:1:12
+///   |
+/// 1 | let a; let a;
+///   |            ^
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Renderer<'a> {
+  source: &'a str,
+  file_name: Option<&'a str>,
+  color: bool,
+  format: Format,
+}
+
+impl<'a> Renderer<'a> {
+  /// Creates a renderer for diagnostics about `source`, in the human format without colours.
+  pub fn new(source: &'a str) -> Self {
+    Self {
+      source,
+      file_name: None,
+      color: false,
+      format: Format::Human,
+    }
+  }
+
+  /// Sets the file name shown with the positions.
+  pub fn with_file_name(mut self, file_name: &'a str) -> Self {
+    self.file_name = Some(file_name);
+    self
+  }
+
+  /// Sets whether the human format uses ANSI colours.
+  pub fn with_color(mut self, color: bool) -> Self {
+    self.color = color;
+    self
+  }
+
+  /// Sets the output format.
+  pub fn with_format(mut self, format: Format) -> Self {
+    self.format = format;
+    self
+  }
+
+  /// Renders a single diagnostic, ending with a new line.
+  pub fn render(&self, diagnostic: &Diagnostic) -> String {
+    match self.format {
+      Format::Human => self.render_human(diagnostic),
+      Format::Json => {
+        let mut out = self.render_json(diagnostic);
+        out.push('\n');
+        out
+      }
+    }
+  }
+
+  /// Renders a list of diagnostics.
+  ///
+  /// In the human format, they are separated by empty lines. In the JSON format, they are
+  /// rendered as a single array.
+  pub fn render_all(&self, diagnostics: &[Diagnostic]) -> String {
+    match self.format {
+      Format::Human => diagnostics
+        .iter()
+        .map(|diagnostic| self.render_human(diagnostic))
+        .collect::<Vec<_>>()
+        .join("\n"),
+      Format::Json => format!(
+        "[{}]\n",
+        diagnostics
+          .iter()
+          .map(|diagnostic| self.render_json(diagnostic))
+          .collect::<Vec<_>>()
+          .join(",")
+      ),
+    }
+  }
+
+  /// Renders a diagnostic in the human format.
+  fn render_human(&self, diagnostic: &Diagnostic) -> String {
+    let style = match diagnostic.severity() {
+      Severity::Error => RED,
+      Severity::Warning => YELLOW,
+      Severity::Note => CYAN,
+    };
+    let mut out = String::new();
+
+    let header = match diagnostic.code() {
+      Some(code) => format!("{}[{}]", diagnostic.severity(), code),
+      None => diagnostic.severity().to_string(),
+    };
+    out.push_str(&self.paint(&header, style));
+    out.push_str(&self.paint(&format!(": {}", diagnostic.message()), BOLD));
+    out.push('\n');
+
+    let mut labels: Vec<&Label> = diagnostic.labels().iter().collect();
+    labels.sort_by_key(|label| (label.span().start(), !label.is_primary()));
+    let width = labels
+      .iter()
+      .map(|label| label.span().start().line_number())
+      .max()
+      .unwrap_or(1)
+      .to_string()
+      .len();
+    let margin = " ".repeat(width);
+    let gutter = self.paint(&format!("{} |", margin), BLUE);
+
+    let location = match (self.file_name, diagnostic.primary_span()) {
+      (Some(name), Some(span)) => Some(format!("{}:{}", name, span.start())),
+      (None, Some(span)) => Some(span.start().to_string()),
+      (Some(name), None) => Some(name.to_owned()),
+      (None, None) => None,
+    };
+    if let Some(location) = location {
+      let _ = writeln!(out, "{}{} {}", margin, self.paint("-->", BLUE), location);
+    }
+
+    if !labels.is_empty() {
+      let lines = source_lines(self.source);
+      let _ = writeln!(out, "{}", gutter);
+
+      let mut previous = None;
+      for label in &labels {
+        let line_number = label.span().start().line_number();
+        let text = lines
+          .get(line_number as usize - 1)
+          .copied()
+          .unwrap_or_default();
+
+        // Each line is shown once, followed by the labels starting on it.
+        if previous != Some(line_number) {
+          if matches!(previous, Some(previous) if line_number > previous + 1) {
+            let _ = writeln!(out, "{}", self.paint("...", BLUE));
+          }
+          let number = self.paint(&format!("{:>width$} |", line_number, width = width), BLUE);
+          let line = format!("{} {}", number, expand_tabs(text));
+          let _ = writeln!(out, "{}", line.trim_end());
+          previous = Some(line_number);
+        }
+
+        let start = display_column(text, label.span().start());
+        let end = if label.span().end().line_number() == line_number {
+          display_column(text, label.span().end())
+        } else {
+          display_column(
+            text,
+            Position::new(line_number, text.chars().count() as u32 + 1),
+          )
+        };
+        let (mark, label_style) = if label.is_primary() {
+          ('^', style)
+        } else {
+          ('-', BLUE)
+        };
+        let mut underline = mark.to_string().repeat(end.saturating_sub(start).max(1));
+        if let Some(message) = label.message() {
+          underline.push(' ');
+          underline.push_str(message);
+        }
+        let _ = writeln!(
+          out,
+          "{} {}{}",
+          gutter,
+          " ".repeat(start - 1),
+          self.paint(&underline, label_style)
+        );
+      }
+    }
+
+    if !diagnostic.notes().is_empty() && !labels.is_empty() {
+      let _ = writeln!(out, "{}", gutter);
+    }
+    for note in diagnostic.notes() {
+      let _ = writeln!(out, "{} {} {}", margin, self.paint("= note:", BOLD), note);
+    }
+    out
+  }
+
+  /// Renders a diagnostic as a JSON object, without a trailing new line.
+  fn render_json(&self, diagnostic: &Diagnostic) -> String {
+    let mut out = String::new();
+    let _ = write!(
+      out,
+      "{{\"severity\":{},\"code\":{},\"message\":{},\"file\":{},\"labels\":[",
+      json_string(diagnostic.severity().as_str()),
+      diagnostic
+        .code()
+        .map_or_else(|| "null".to_owned(), json_string),
+      json_string(diagnostic.message()),
+      self
+        .file_name
+        .map_or_else(|| "null".to_owned(), json_string),
+    );
+    for (i, label) in diagnostic.labels().iter().enumerate() {
+      let span = label.span();
+      let _ = write!(
+        out,
+        "{}{{\"primary\":{},\"message\":{},\"start\":{},\"end\":{}}}",
+        if i == 0 { "" } else { "," },
+        label.is_primary(),
+        label
+          .message()
+          .map_or_else(|| "null".to_owned(), json_string),
+        json_position(span.start()),
+        json_position(span.end()),
+      );
+    }
+    out.push_str("],\"notes\":[");
+    let notes: Vec<_> = diagnostic
+      .notes()
+      .iter()
+      .map(|note| json_string(note))
+      .collect();
+    out.push_str(&notes.join(","));
+    out.push_str("]}");
+    out
+  }
+
+  /// Wraps the text in the given ANSI style, if colours are enabled.
+  fn paint(&self, text: &str, style: &str) -> String {
+    if self.color {
+      format!("\u{1b}[{}m{}\u{1b}[0m", style, text)
+    } else {
+      text.to_owned()
+    }
+  }
+}
+
+/// Splits the source into lines, with the same line terminators as the lexer.
+fn source_lines(source: &str) -> Vec<&str> {
+  let mut lines = Vec::new();
+  let mut start = 0;
+  let mut chars = source.char_indices().peekable();
+  while let Some((i, ch)) = chars.next() {
+    match ch {
+      '\r' => {
+        lines.push(&source[start..i]);
+        if let Some((_, '\n')) = chars.peek() {
+          chars.next();
+          start = i + 2;
+        } else {
+          start = i + 1;
+        }
+      }
+      '\n' | '\u{2028}' | '\u{2029}' => {
+        lines.push(&source[start..i]);
+        start = i + ch.len_utf8();
+      }
+      _ => {}
+    }
+  }
+  lines.push(&source[start..]);
+  lines
+}
+
+/// Replaces the tabs of a source line by spaces.
+fn expand_tabs(line: &str) -> String {
+  line.replace('\t', &" ".repeat(TAB_WIDTH))
+}
+
+/// Converts the column of a position to a column of the line once its tabs are expanded.
+///
+/// Columns past the end of the line, such as the end of the input, are one character wide.
+fn display_column(line: &str, position: Position) -> usize {
+  let column = position.column_number() as usize;
+  let before = line.chars().take(column - 1);
+  let count = before.clone().count();
+  let width: usize = before
+    .map(|ch| if ch == '\t' { TAB_WIDTH } else { 1 })
+    .sum();
+  width + (column - 1 - count) + 1
+}
+
+/// Formats a position as a JSON object.
+fn json_position(position: Position) -> String {
+  format!(
+    "{{\"line\":{},\"column\":{}}}",
+    position.line_number(),
+    position.column_number()
+  )
+}
+
+/// Formats a string as a JSON string literal.
+fn json_string(value: &str) -> String {
+  let mut out = String::with_capacity(value.len() + 2);
+  out.push('"');
+  for ch in value.chars() {
+    match ch {
+      '"' => out.push_str("\\\""),
+      '\\' => out.push_str("\\\\"),
+      '\n' => out.push_str("\\n"),
+      '\r' => out.push_str("\\r"),
+      '\t' => out.push_str("\\t"),
+      ch if (ch as u32) < 0x20 => {
+        let _ = write!(out, "\\u{:04x}", ch as u32);
+      }
+      ch => out.push(ch),
+    }
+  }
+  out.push('"');
+  out
+}
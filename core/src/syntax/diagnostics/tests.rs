@@ -0,0 +1,169 @@
+//! Tests for the diagnostics.
+
+use super::{Diagnostic, Format, Label, Renderer};
+use crate::syntax::{
+  ast::{Position, Span},
+  parser::Parser,
+};
+
+/// Creates a span on a single line.
+fn span(line: u32, start: u32, end: u32) -> Span {
+  Span::new(Position::new(line, start), Position::new(line, end))
+}
+
+/// Parses the source as a script and converts its error to a diagnostic.
+#[track_caller]
+fn parse_error(js: &str) -> Diagnostic {
+  Parser::new(js.as_bytes())
+    .parse_script()
+    .expect_err("the source should not parse")
+    .into()
+}
+
+#[test]
+fn early_error() {
+  let js = "let a; let a;";
+  let diagnostic = parse_error(js);
+  assert_eq!(diagnostic.code(), Some("E0007"));
+  assert_eq!(diagnostic.primary_span(), Some(span(1, 12, 13)));
+  assert_eq!(
+    Renderer::new(js)
+      .with_file_name("main.js")
+      .render(&diagnostic),
+    "error[E0007]: identifier 'a' has already been declared\n \
+     --> main.js:1:12\n  \
+     |\n\
+     1 | let a; let a;\n  \
+     |            ^\n"
+  );
+}
+
+#[test]
+fn expected_token() {
+  let js = "if (a { }";
+  let diagnostic = parse_error(js);
+  assert_eq!(diagnostic.code(), Some("E0004"));
+  assert_eq!(
+    Renderer::new(js).render(&diagnostic),
+    "error[E0004]: expected token ')', got '{' in if statement\n \
+     --> 1:7\n  \
+     |\n\
+     1 | if (a { }\n  \
+     |       ^ expected token ')'\n"
+  );
+}
+
+#[test]
+fn lexer_errors() {
+  let diagnostic = parse_error("a = 'x\\q';");
+  assert_eq!(diagnostic.code(), Some("E0001"));
+  assert_eq!(diagnostic.message(), "invalid escape sequence `\\q`");
+  assert_eq!(
+    diagnostic.primary_span().map(Span::start),
+    Some(Position::new(1, 7))
+  );
+
+  let diagnostic = parse_error("a = @;");
+  assert_eq!(diagnostic.code(), Some("E0001"));
+  assert_eq!(diagnostic.message(), "unexpected '@'");
+}
+
+#[test]
+fn abrupt_end() {
+  let diagnostic = parse_error("a = ");
+  assert_eq!(diagnostic.code(), Some("E0005"));
+  assert!(diagnostic.labels().is_empty());
+  assert_eq!(
+    Renderer::new("a = ").render(&diagnostic),
+    "error[E0005]: abrupt end\n"
+  );
+}
+
+#[test]
+fn secondary_labels_and_notes() {
+  let js = "let a = 1;\n\nfoo();\n\tlet a = 2;\n";
+  let diagnostic = Diagnostic::error("identifier 'a' has already been declared")
+    .with_code("E0007")
+    .with_label(Label::primary(span(4, 6, 7)).with_message("declared again here"))
+    .with_label(Label::secondary(span(1, 5, 6)).with_message("first declared here"))
+    .with_note("variables declared with `let` cannot be redeclared");
+  assert_eq!(
+    Renderer::new(js)
+      .with_file_name("main.js")
+      .render(&diagnostic),
+    "error[E0007]: identifier 'a' has already been declared\n \
+     --> main.js:4:6\n  \
+     |\n\
+     1 | let a = 1;\n  \
+     |     - first declared here\n\
+     ...\n\
+     4 |     let a = 2;\n  \
+     |         ^ declared again here\n  \
+     |\n  \
+     = note: variables declared with `let` cannot be redeclared\n"
+  );
+}
+
+#[test]
+fn multi_line_span() {
+  let js = "foo(1,\r\n  2);";
+  let diagnostic = Diagnostic::warning("call spans several lines").with_label(Label::primary(
+    Span::new(Position::new(1, 1), Position::new(2, 5)),
+  ));
+  assert_eq!(
+    Renderer::new(js).render(&diagnostic),
+    "warning: call spans several lines\n \
+     --> 1:1\n  \
+     |\n\
+     1 | foo(1,\n  \
+     | ^^^^^^\n"
+  );
+}
+
+#[test]
+fn color() {
+  let js = "a = #;";
+  let diagnostic = parse_error(js);
+  let rendered = Renderer::new(js).with_color(true).render(&diagnostic);
+  assert!(rendered.starts_with("\u{1b}[1;31merror[E0001]\u{1b}[0m"));
+  assert!(rendered.contains("\u{1b}[1;31m^\u{1b}[0m"));
+
+  let rendered = Renderer::new(js).with_color(false).render(&diagnostic);
+  assert!(!rendered.contains('\u{1b}'));
+}
+
+#[test]
+fn json() {
+  let js = "if (a { }";
+  let renderer = Renderer::new(js)
+    .with_file_name("dir\\\"main\".js")
+    .with_format(Format::Json);
+  let diagnostic = parse_error(js).with_note("line\nbreak");
+  let expected = "{\"severity\":\"error\",\"code\":\"E0004\",\
+                  \"message\":\"expected token ')', got '{' in if statement\",\
+                  \"file\":\"dir\\\\\\\"main\\\".js\",\
+                  \"labels\":[{\"primary\":true,\"message\":\"expected token ')'\",\
+                  \"start\":{\"line\":1,\"column\":7},\"end\":{\"line\":1,\"column\":8}}],\
+                  \"notes\":[\"line\\nbreak\"]}";
+  assert_eq!(renderer.render(&diagnostic), format!("{}\n", expected));
+
+  let warning = Diagnostic::warning("unused");
+  assert_eq!(
+    renderer.render_all(&[diagnostic, warning]),
+    format!(
+      "[{},{{\"severity\":\"warning\",\"code\":null,\"message\":\"unused\",\
+       \"file\":\"dir\\\\\\\"main\\\".js\",\"labels\":[],\"notes\":[]}}]\n",
+      expected
+    )
+  );
+  assert_eq!(renderer.render_all(&[]), "[]\n");
+}
+
+#[test]
+fn render_all_human() {
+  let diagnostics = [Diagnostic::error("first"), Diagnostic::warning("second")];
+  assert_eq!(
+    Renderer::new("").render_all(&diagnostics),
+    "error: first\n\nwarning: second\n"
+  );
+}
@@ -0,0 +1,42 @@
+//! Edits of a source: replacements of spans of its text, such as the fixes of the [linter]
+//! and the changes applied to an [incremental document].
+//!
+//! [linter]: crate::syntax::lint
+//! [incremental document]: crate::syntax::incremental::Document
+
+use crate::syntax::ast::Span;
+
+/// A replacement of a span of the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+  span: Span,
+  text: Box<str>,
+}
+
+impl Edit {
+  /// Creates an edit replacing a span with a text.
+  pub fn new<T>(span: Span, text: T) -> Self
+  where
+    T: Into<Box<str>>,
+  {
+    Self {
+      span,
+      text: text.into(),
+    }
+  }
+
+  /// Creates an edit removing a span.
+  pub fn delete(span: Span) -> Self {
+    Self::new(span, "")
+  }
+
+  /// Gets the replaced span.
+  pub fn span(&self) -> Span {
+    self.span
+  }
+
+  /// Gets the text replacing the span.
+  pub fn text(&self) -> &str {
+    &self.text
+  }
+}
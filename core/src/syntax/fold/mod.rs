@@ -0,0 +1,217 @@
+//! Constant folding: evaluating the expressions of a syntax tree that only depend on literals.
+//!
+//! Arithmetic, bitwise and comparison operators, string concatenation and `typeof` applied to
+//! literals are replaced by their result, following the conversions of the language: `NaN`,
+//! `-0`, strings converted to numbers and numbers printed as strings give the same results as
+//! at run time. Logical operators, conditionals and comma expressions whose outcome is known
+//! are replaced by the operand they evaluate to:
+//!
+//! ```
+//! use plastic::syntax::{
+//!   codegen::{Generator, Options},
+//!   fold,
+//!   parser::Parser,
+//! };
+//!
+//! let mut script = Parser::new(&b"x = 60 * 60 * 24; y = 'v' + 1.50; z = !0 && f();"[..])
+//!   .parse_script()
+//!   .unwrap();
+//! fold::fold_statements(script.items_mut());
+//! assert_eq!(
+//!   Generator::new(Options::pretty()).script(&script),
+//!   "x = 86400;\ny = \"v1.5\";\nz = f();\n"
+//! );
+//! ```
+//!
+//! Folding never changes what the code does. Expressions are left as they are when their result
+//! is not known exactly, such as `0.1 ** 3` which engines may round differently, or when it can
+//! not be written as a literal: `NaN` and `undefined` are globals that a binding may shadow.
+//! The operand a logical operator evaluates to is not taken out of a call, `typeof` or `delete`
+//! when it is a name or a property access, since `(0 || obj.method)()` calls the method with
+//! another `this` than `obj.method()`.
+
+#[cfg(test)]
+mod tests;
+mod value;
+
+pub use self::value::{number_to_string, string_to_number, Value};
+
+use crate::syntax::ast::{
+  node::{Call, TaggedTemplate, UnaryOp},
+  operator::{self, BinOp, LogOp},
+  visitor::{visit_mut, Flow, KindPath, Path, VisitorMut},
+  Const, Node,
+};
+use std::mem;
+
+/// Folds the constant expressions of statements, such as the items of a script or a module.
+pub fn fold_statements(statements: &mut [Node]) {
+  let _ = Folder::new().visit_statements(statements, &mut Path::new());
+}
+
+/// Folds the constant expressions of an expression.
+pub fn fold_expression(expr: &mut Node) {
+  let _ = Folder::new().visit_node(expr, &mut Path::new());
+}
+
+/// Evaluates an expression made of literals and operators, `None` if its value is not known.
+///
+/// The expression has no side effects when its value is known.
+pub fn evaluate(expr: &Node) -> Option<Value> {
+  match expr {
+    Node::Const(value, _) => Some(Value::from_const(value)),
+    Node::FunctionExpr(_) | Node::ArrowFunctionDecl(_) => Some(Value::Function),
+    Node::UnaryOp(expr) => value::unary(expr.op(), &evaluate(expr.target())?),
+    Node::BinOp(expr) => match expr.op() {
+      BinOp::Num(op) => value::numeric(op, &evaluate(expr.lhs())?, &evaluate(expr.rhs())?),
+      BinOp::Bit(op) => value::bitwise(op, &evaluate(expr.lhs())?, &evaluate(expr.rhs())?),
+      BinOp::Comp(op) => value::comparison(op, &evaluate(expr.lhs())?, &evaluate(expr.rhs())?),
+      BinOp::Log(op) => {
+        let lhs = evaluate(expr.lhs())?;
+        if short_circuits(op, &lhs) {
+          Some(lhs)
+        } else {
+          evaluate(expr.rhs())
+        }
+      }
+      BinOp::Comma => {
+        evaluate(expr.lhs())?;
+        evaluate(expr.rhs())
+      }
+      BinOp::Assign(_) => None,
+    },
+    Node::ConditionalOp(expr) => {
+      if evaluate(expr.cond())?.to_boolean() {
+        evaluate(expr.if_true())
+      } else {
+        evaluate(expr.if_false())
+      }
+    }
+    _ => None,
+  }
+}
+
+/// Returns `true` if a logical operator evaluates to its left hand side.
+fn short_circuits(op: LogOp, lhs: &Value) -> bool {
+  match op {
+    LogOp::And => !lhs.to_boolean(),
+    LogOp::Or => lhs.to_boolean(),
+    LogOp::Coalesce => !lhs.is_nullish(),
+  }
+}
+
+/// Where an expression is, restricting what it can be replaced by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+  Value,
+  /// The callee of a call, the tag of a tagged template, or the operand of `typeof` or
+  /// `delete`, where names and property accesses are not evaluated as values.
+  Reference,
+  /// An item of a statement list, where a string literal would be a directive.
+  Statement,
+}
+
+/// Folds the expressions of a tree, the operands before the operators.
+#[derive(Debug)]
+struct Folder {
+  /// The position of the next node visited.
+  position: Position,
+}
+
+impl Folder {
+  fn new() -> Self {
+    Self {
+      position: Position::Value,
+    }
+  }
+
+  /// Replaces an operator expression by its result, or by the operand it evaluates to.
+  fn fold(node: &mut Node, position: Position) {
+    if !matches!(
+      node,
+      Node::UnaryOp(_) | Node::BinOp(_) | Node::ConditionalOp(_)
+    ) {
+      return;
+    }
+    if let Some(value) = evaluate(node).and_then(|value| value.to_const()) {
+      if !(position == Position::Statement && matches!(value, Const::String(_))) {
+        *node = value.into();
+      }
+      return;
+    }
+
+    let operand = match node {
+      Node::BinOp(expr) => match expr.op() {
+        BinOp::Log(op) => match evaluate(expr.lhs()) {
+          Some(lhs) if short_circuits(op, &lhs) => expr.lhs_mut(),
+          Some(_) => expr.rhs_mut(),
+          None => return,
+        },
+        BinOp::Comma if evaluate(expr.lhs()).is_some() => expr.rhs_mut(),
+        _ => return,
+      },
+      Node::ConditionalOp(expr) => match evaluate(expr.cond()) {
+        Some(cond) if cond.to_boolean() => expr.if_true_mut(),
+        Some(_) => expr.if_false_mut(),
+        None => return,
+      },
+      _ => return,
+    };
+    let kept = match position {
+      Position::Value => true,
+      Position::Reference => !matches!(
+        operand,
+        Node::Identifier(_)
+          | Node::GetConstField(_)
+          | Node::GetField(_)
+          | Node::GetPrivateField(_)
+          | Node::OptionalChain(_)
+      ),
+      Position::Statement => !matches!(operand, Node::Const(Const::String(_), _)),
+    };
+    if kept {
+      *node = mem::replace(operand, Node::empty());
+    }
+  }
+}
+
+impl VisitorMut for Folder {
+  fn visit_statements(&mut self, statements: &mut [Node], path: &mut KindPath) -> Flow {
+    for statement in statements {
+      self.position = Position::Statement;
+      self.visit_node(statement, path)?;
+    }
+    Flow::Continue(())
+  }
+
+  fn visit_node(&mut self, node: &mut Node, path: &mut KindPath) -> Flow {
+    let position = mem::replace(&mut self.position, Position::Value);
+    visit_mut::walk_node(self, node, path)?;
+    Self::fold(node, position);
+    Flow::Continue(())
+  }
+
+  fn visit_call(&mut self, call: &mut Call, path: &mut KindPath) -> Flow {
+    self.position = Position::Reference;
+    self.visit_node(call.expr_mut(), path)?;
+    for arg in call.args_mut() {
+      self.visit_node(arg, path)?;
+    }
+    Flow::Continue(())
+  }
+
+  fn visit_tagged_template(&mut self, expr: &mut TaggedTemplate, path: &mut KindPath) -> Flow {
+    self.position = Position::Reference;
+    visit_mut::walk_tagged_template(self, expr, path)
+  }
+
+  fn visit_unary_op(&mut self, expr: &mut UnaryOp, path: &mut KindPath) -> Flow {
+    if matches!(
+      expr.op(),
+      operator::UnaryOp::TypeOf | operator::UnaryOp::Delete
+    ) {
+      self.position = Position::Reference;
+    }
+    visit_mut::walk_unary_op(self, expr, path)
+  }
+}
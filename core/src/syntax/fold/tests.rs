@@ -0,0 +1,426 @@
+//! Tests for constant folding.
+
+use super::{evaluate, fold_statements, number_to_string, string_to_number, Value};
+use crate::syntax::{
+  ast::{
+    node::{BinOp, UnaryOp},
+    operator, Const, Node,
+  },
+  codegen::{Generator, Options},
+  parser::Parser,
+};
+
+/// Folds a script, and prints it.
+#[track_caller]
+fn fold(js: &str) -> String {
+  let mut script = Parser::new(js.as_bytes())
+    .parse_script()
+    .unwrap_or_else(|err| panic!("failed to parse `{}`: {}", js, err));
+  fold_statements(script.items_mut());
+  Generator::new(Options::compact()).script(&script)
+}
+
+/// Returns `true` if two values are the same, telling `0` from `-0` and `NaN` from nothing.
+fn same(lhs: &Value, rhs: &Value) -> bool {
+  match (lhs, rhs) {
+    (Value::Number(lhs), Value::Number(rhs)) => {
+      lhs.to_bits() == rhs.to_bits() || (lhs.is_nan() && rhs.is_nan())
+    }
+    (lhs, rhs) => lhs == rhs,
+  }
+}
+
+/// Expressions, with their values as computed by a JavaScript engine.
+fn cases() -> Vec<(&'static str, Value)> {
+  vec![
+    ("1 + 2", Value::Number(3.0)),
+    ("0.1 + 0.2", Value::Number(0.30000000000000004)),
+    ("2147483647 + 1", Value::Number(2147483648.0)),
+    ("-2147483648 - 1", Value::Number(-2147483649.0)),
+    ("5 - 7.5", Value::Number(-2.5)),
+    ("6 * 7", Value::Number(42.0)),
+    ("1 / 0", Value::Number(f64::INFINITY)),
+    ("-1 / 0", Value::Number(f64::NEG_INFINITY)),
+    ("1 / -0", Value::Number(f64::NEG_INFINITY)),
+    ("0 / 0", Value::Number(f64::NAN)),
+    ("-0", Value::Number(-0.0)),
+    ("-(0)", Value::Number(-0.0)),
+    ("0 * -1", Value::Number(-0.0)),
+    ("-0 + 0", Value::Number(0.0)),
+    ("-0 - 0", Value::Number(-0.0)),
+    ("7 % 3", Value::Number(1.0)),
+    ("-7 % 3", Value::Number(-1.0)),
+    ("7 % -3", Value::Number(1.0)),
+    ("-1 % 1", Value::Number(-0.0)),
+    ("5.5 % 2", Value::Number(1.5)),
+    ("1 % 0", Value::Number(f64::NAN)),
+    ("2 ** 10", Value::Number(1024.0)),
+    ("2 ** 53", Value::Number(9007199254740992.0)),
+    ("3 ** 0", Value::Number(1.0)),
+    ("(0 / 0) ** 0", Value::Number(1.0)),
+    ("1 ** (0 / 0)", Value::Number(f64::NAN)),
+    ("(-2) ** 3", Value::Number(-8.0)),
+    ("0 ** 5", Value::Number(0.0)),
+    ("\"a\" + \"b\"", Value::String("ab".into())),
+    ("\"a\" + 1", Value::String("a1".into())),
+    ("1 + \"a\"", Value::String("1a".into())),
+    ("\"x\" + 1.5", Value::String("x1.5".into())),
+    ("\"x\" + -0", Value::String("x0".into())),
+    ("\"x\" + 1e21", Value::String("x1e+21".into())),
+    ("\"x\" + 1e-7", Value::String("x1e-7".into())),
+    (
+      "\"x\" + 1.2345678901234568e20",
+      Value::String("x123456789012345680000".into()),
+    ),
+    ("\"x\" + 0.000001", Value::String("x0.000001".into())),
+    ("\"x\" + 1.5e-10", Value::String("x1.5e-10".into())),
+    ("\"x\" + (1 / 0)", Value::String("xInfinity".into())),
+    ("\"x\" + (0 / 0)", Value::String("xNaN".into())),
+    ("\"x\" + null", Value::String("xnull".into())),
+    ("\"x\" + true", Value::String("xtrue".into())),
+    ("\"x\" + void 0", Value::String("xundefined".into())),
+    ("1 + 2 + \"3\"", Value::String("33".into())),
+    ("\"3\" + 1 + 2", Value::String("312".into())),
+    ("null + 1", Value::Number(1.0)),
+    ("true + true", Value::Number(2.0)),
+    ("void 0 + 1", Value::Number(f64::NAN)),
+    ("\"5\" - 2", Value::Number(3.0)),
+    ("\"5\" * \"2\"", Value::Number(10.0)),
+    ("\" 12 \" - 0", Value::Number(12.0)),
+    ("\"0x1f\" - 0", Value::Number(31.0)),
+    ("\"0b101\" * 1", Value::Number(5.0)),
+    ("\"0o17\" * 1", Value::Number(15.0)),
+    ("\"-0x10\" * 1", Value::Number(f64::NAN)),
+    ("\"1e3\" * 1", Value::Number(1000.0)),
+    ("\".5\" * 1", Value::Number(0.5)),
+    ("\"5.\" * 1", Value::Number(5.0)),
+    ("\"\" * 1", Value::Number(0.0)),
+    ("\"  \\n\\t \" * 1", Value::Number(0.0)),
+    ("\"Infinity\" * 1", Value::Number(f64::INFINITY)),
+    ("\"-Infinity\" * 1", Value::Number(f64::NEG_INFINITY)),
+    ("\"infinity\" * 1", Value::Number(f64::NAN)),
+    ("\"1_000\" * 1", Value::Number(f64::NAN)),
+    ("\"12px\" * 1", Value::Number(f64::NAN)),
+    ("+\"  -0  \"", Value::Number(-0.0)),
+    ("+true", Value::Number(1.0)),
+    ("+null", Value::Number(0.0)),
+    ("+\"3.25\"", Value::Number(3.25)),
+    ("+void 0", Value::Number(f64::NAN)),
+    ("-\"3\"", Value::Number(-3.0)),
+    ("~5", Value::Number(-6.0)),
+    ("~-1", Value::Number(0.0)),
+    ("~4294967296", Value::Number(-1.0)),
+    ("~2147483648", Value::Number(2147483647.0)),
+    ("~1.9", Value::Number(-2.0)),
+    ("5 & 3", Value::Number(1.0)),
+    ("5 | 3", Value::Number(7.0)),
+    ("5 ^ 3", Value::Number(6.0)),
+    ("1 << 31", Value::Number(-2147483648.0)),
+    ("1 << 32", Value::Number(1.0)),
+    ("-1 >> 1", Value::Number(-1.0)),
+    ("-1 >>> 0", Value::Number(4294967295.0)),
+    ("-1 >>> 28", Value::Number(15.0)),
+    ("2 ** 32 | 0", Value::Number(0.0)),
+    ("1.5 | 0", Value::Number(1.0)),
+    ("-1.5 | 0", Value::Number(-1.0)),
+    ("\"12\" | 0", Value::Number(12.0)),
+    ("1 == 1", Value::Bool(true)),
+    ("1 == \"1\"", Value::Bool(true)),
+    ("0 == \"\"", Value::Bool(true)),
+    ("0 == \"0\"", Value::Bool(true)),
+    ("\"\" == \"0\"", Value::Bool(false)),
+    ("null == void 0", Value::Bool(true)),
+    ("null == 0", Value::Bool(false)),
+    ("void 0 == 0", Value::Bool(false)),
+    ("null == false", Value::Bool(false)),
+    ("true == 1", Value::Bool(true)),
+    ("true == \"1\"", Value::Bool(true)),
+    ("false == \"\"", Value::Bool(true)),
+    ("(0 / 0) == (0 / 0)", Value::Bool(false)),
+    ("(0 / 0) != (0 / 0)", Value::Bool(true)),
+    ("1 === 1", Value::Bool(true)),
+    ("1 === \"1\"", Value::Bool(false)),
+    ("0 === -0", Value::Bool(true)),
+    ("null === null", Value::Bool(true)),
+    ("null === void 0", Value::Bool(false)),
+    ("\"a\" === \"a\"", Value::Bool(true)),
+    ("(0 / 0) === (0 / 0)", Value::Bool(false)),
+    ("1 !== 2", Value::Bool(true)),
+    ("1 < 2", Value::Bool(true)),
+    ("2 < 1", Value::Bool(false)),
+    ("\"10\" < \"9\"", Value::Bool(true)),
+    ("\"10\" < 9", Value::Bool(false)),
+    ("\"a\" < \"b\"", Value::Bool(true)),
+    ("\"B\" < \"a\"", Value::Bool(true)),
+    ("\"\u{ffff}\" < \"\u{1f600}\"", Value::Bool(false)),
+    ("1 < (0 / 0)", Value::Bool(false)),
+    ("1 >= (0 / 0)", Value::Bool(false)),
+    ("null >= 0", Value::Bool(true)),
+    ("null > 0", Value::Bool(false)),
+    ("void 0 < 1", Value::Bool(false)),
+    ("1 <= 1", Value::Bool(true)),
+    ("2 >= 3", Value::Bool(false)),
+    ("\"abc\" < \"abd\"", Value::Bool(true)),
+    ("!0", Value::Bool(true)),
+    ("!1", Value::Bool(false)),
+    ("!\"\"", Value::Bool(true)),
+    ("!\"a\"", Value::Bool(false)),
+    ("!null", Value::Bool(true)),
+    ("!void 0", Value::Bool(true)),
+    ("!(0 / 0)", Value::Bool(true)),
+    ("!-0", Value::Bool(true)),
+    ("!!\"0\"", Value::Bool(true)),
+    ("!function () {}", Value::Bool(false)),
+    ("typeof 1", Value::String("number".into())),
+    ("typeof \"a\"", Value::String("string".into())),
+    ("typeof true", Value::String("boolean".into())),
+    ("typeof null", Value::String("object".into())),
+    ("typeof void 0", Value::String("undefined".into())),
+    ("typeof function () {}", Value::String("function".into())),
+    ("typeof (() => 1)", Value::String("function".into())),
+    ("typeof typeof 1", Value::String("string".into())),
+    ("void 0", Value::Undefined),
+    ("void \"a\"", Value::Undefined),
+    ("1 && 2", Value::Number(2.0)),
+    ("0 && 2", Value::Number(0.0)),
+    ("\"\" || \"b\"", Value::String("b".into())),
+    ("\"a\" || \"b\"", Value::String("a".into())),
+    ("null ?? 5", Value::Number(5.0)),
+    ("0 ?? 5", Value::Number(0.0)),
+    ("void 0 ?? \"d\"", Value::String("d".into())),
+    ("false ?? 1", Value::Bool(false)),
+    ("1 ? \"y\" : \"n\"", Value::String("y".into())),
+    ("0 ? \"y\" : \"n\"", Value::String("n".into())),
+    ("\"\" ? 1 : 2", Value::Number(2.0)),
+    ("(1, 2)", Value::Number(2.0)),
+    ("(1, 2, \"three\")", Value::String("three".into())),
+    ("1 + 2 * 3 - 4 / 2", Value::Number(5.0)),
+    ("(1 + 2) * \"3\"", Value::Number(9.0)),
+    ("\"a\" + (1 + 2)", Value::String("a3".into())),
+    ("1 / 3", Value::Number(0.3333333333333333)),
+    ("100 / 3", Value::Number(33.333333333333336)),
+    ("-(2 ** 31)", Value::Number(-2147483648.0)),
+    ("2 ** 31 | 0", Value::Number(-2147483648.0)),
+    ("(0 / 0) + \"x\"", Value::String("NaNx".into())),
+    ("\"abc\" * 1 + \"x\"", Value::String("NaNx".into())),
+  ]
+}
+
+#[test]
+fn folded_values_match_evaluation() {
+  for (expr, expected) in cases() {
+    let js = format!("x = ({});", expr);
+    let mut script = Parser::new(js.as_bytes())
+      .parse_script()
+      .unwrap_or_else(|err| panic!("failed to parse `{}`: {}", js, err));
+    let rhs = |script: &crate::syntax::ast::node::StatementList| match &script.items()[0] {
+      Node::Assign(assign) => assign.rhs().clone(),
+      node => panic!("unexpected node {:?}", node),
+    };
+
+    let value = evaluate(&rhs(&script)).unwrap_or_else(|| panic!("`{}` is not evaluated", expr));
+    assert!(
+      same(&value, &expected),
+      "`{}` evaluates to {:?} rather than {:?}",
+      expr,
+      value,
+      expected
+    );
+
+    fold_statements(script.items_mut());
+    let folded = rhs(&script);
+    match (&folded, expected.to_const()) {
+      (Node::Const(value, _), Some(_)) => assert!(
+        same(&Value::from_const(value), &expected),
+        "`{}` is folded to {:?} rather than {:?}",
+        expr,
+        value,
+        expected
+      ),
+      (folded, Some(_)) => panic!("`{}` is not folded: {:?}", expr, folded),
+      // `NaN` and `undefined` are not written as literals, but the expression keeps its value.
+      (folded, None) => assert!(
+        matches!(evaluate(folded), Some(value) if same(&value, &expected)),
+        "`{}` is folded to {:?}",
+        expr,
+        folded
+      ),
+    }
+  }
+}
+
+#[test]
+fn integers() {
+  assert_eq!(Value::Number(3.0).to_const(), Some(Const::Int(3)));
+  assert_eq!(Value::Number(-5.0).to_const(), Some(Const::Int(-5)));
+  assert_eq!(
+    Value::Number(2147483648.0).to_const(),
+    Some(Const::Num(2147483648.0))
+  );
+  assert!(
+    matches!(Value::Number(-0.0).to_const(), Some(Const::Num(num)) if num.is_sign_negative())
+  );
+  assert_eq!(Value::Number(0.5).to_const(), Some(Const::Num(0.5)));
+  assert_eq!(Value::Number(f64::NAN).to_const(), None);
+  assert_eq!(Value::Undefined.to_const(), None);
+  assert_eq!(fold("x = 1.5 + 1.5;"), "x=3");
+}
+
+#[test]
+fn objects() {
+  // Regular expression literals create a new object each time they are evaluated.
+  let regexp = Node::from(Const::RegExp("a".to_owned(), false, false));
+  assert_eq!(evaluate(&regexp), Some(Value::Object));
+  let not = UnaryOp::new(operator::UnaryOp::Not, regexp.clone());
+  assert_eq!(evaluate(&not.into()), Some(Value::Bool(false)));
+  let type_of = UnaryOp::new(operator::UnaryOp::TypeOf, regexp.clone());
+  assert_eq!(
+    evaluate(&type_of.into()),
+    Some(Value::String("object".into()))
+  );
+  let equal = BinOp::new(operator::CompOp::StrictEqual, regexp.clone(), regexp);
+  assert_eq!(evaluate(&equal.into()), None);
+}
+
+#[test]
+fn number_strings() {
+  let cases = [
+    (0.0, "0"),
+    (-0.0, "0"),
+    (1.0, "1"),
+    (-1.5, "-1.5"),
+    (0.1, "0.1"),
+    (123456789.0, "123456789"),
+    (1e21, "1e+21"),
+    (1.5e21, "1.5e+21"),
+    (1e20, "100000000000000000000"),
+    (1e-6, "0.000001"),
+    (1e-7, "1e-7"),
+    (1.25e-7, "1.25e-7"),
+    (5e-324, "5e-324"),
+    (f64::MAX, "1.7976931348623157e+308"),
+    (f64::INFINITY, "Infinity"),
+    (f64::NEG_INFINITY, "-Infinity"),
+    (f64::NAN, "NaN"),
+  ];
+  for &(num, string) in &cases {
+    assert_eq!(number_to_string(num), string, "{:?}", num);
+  }
+}
+
+#[test]
+fn string_numbers() {
+  let cases = [
+    ("", Some(0.0)),
+    (" \u{feff}\u{2028}42\u{3000}", Some(42.0)),
+    ("-12.5e1", Some(-125.0)),
+    ("0x10", Some(16.0)),
+    ("0X1f", Some(31.0)),
+    ("0b11", Some(3.0)),
+    ("0o17", Some(15.0)),
+    ("+Infinity", Some(f64::INFINITY)),
+    ("1e", Some(f64::NAN)),
+    ("0x", Some(f64::NAN)),
+    ("-0x10", Some(f64::NAN)),
+    ("0x1g", Some(f64::NAN)),
+    ("inf", Some(f64::NAN)),
+    ("NaN", Some(f64::NAN)),
+    ("1 2", Some(f64::NAN)),
+    ("\u{85}1", Some(f64::NAN)),
+    ("0x20000000000001", Some(9007199254740992.0)),
+    // Too large to be rounded here.
+    ("0x10000000000000000", None),
+  ];
+  for &(string, expected) in &cases {
+    let num = string_to_number(string);
+    assert!(
+      match (num, expected) {
+        (Some(num), Some(expected)) =>
+          num.to_bits() == expected.to_bits() || num.is_nan() && expected.is_nan(),
+        (num, expected) => num == expected,
+      },
+      "{:?} is converted to {:?}",
+      string,
+      num
+    );
+  }
+  assert_eq!(
+    string_to_number("-0").map(f64::is_sign_negative),
+    Some(true)
+  );
+}
+
+#[test]
+fn operands_with_side_effects() {
+  assert_eq!(fold("x = f() + 1 + 2;"), "x=f()+1+2");
+  assert_eq!(fold("x = 1 + 2 + f();"), "x=3+f()");
+  assert_eq!(fold("x = y * 0;"), "x=y*0");
+  assert_eq!(fold("x = (f(), 1) + 1;"), "x=(f(),1)+1");
+  assert_eq!(fold("x = typeof y;"), "x=typeof y");
+  assert_eq!(fold("x = 'a' in 'b';"), "x=\"a\"in\"b\"");
+  assert_eq!(fold("x = [] + 1;"), "x=[]+1");
+}
+
+#[test]
+fn inexact_results() {
+  assert_eq!(fold("x = 0.1 ** 3;"), "x=0.1**3");
+  assert_eq!(fold("x = 2 ** -1;"), "x=2**-1");
+  assert_eq!(fold("x = 10 ** 20;"), "x=10**20");
+  // `NaN` and `undefined` are not written as the globals.
+  assert_eq!(fold("x = 0 / 0;"), "x=0/0");
+  assert_eq!(fold("x = void (1 + 2);"), "x=void 3");
+  assert_eq!(fold("x = 1 / 0;"), "x=1e999");
+  assert_eq!(fold("x = -(1 - 1);"), "x=-0");
+}
+
+#[test]
+fn short_circuits() {
+  assert_eq!(fold("x = 1 && y;"), "x=y");
+  assert_eq!(fold("x = 0 && y;"), "x=0");
+  assert_eq!(fold("x = 'a' || f();"), "x=\"a\"");
+  assert_eq!(fold("x = null ?? y;"), "x=y");
+  assert_eq!(fold("x = 0 ?? y;"), "x=0");
+  assert_eq!(fold("x = y && 1;"), "x=y&&1");
+  assert_eq!(fold("x = !0 ? a : b;"), "x=a");
+  assert_eq!(fold("x = typeof 1 == 'number' ? a : b;"), "x=a");
+  assert_eq!(fold("x = (1, 2, y);"), "x=y");
+  assert_eq!(fold("x = (y, 1);"), "x=(y,1)");
+  assert_eq!(fold("x = (0 / 0) || y;"), "x=y");
+  assert_eq!(fold("x = (function () {}) && y;"), "x=y");
+}
+
+#[test]
+fn references() {
+  // The callee keeps its `this`, `typeof` and `delete` their operand.
+  assert_eq!(fold("(1 && obj.method)();"), "(1&&obj.method)()");
+  assert_eq!(fold("(0 ? a : eval)('b');"), "(0?a:eval)(\"b\")");
+  assert_eq!(fold("(0, obj.method)();"), "(0,obj.method)()");
+  assert_eq!(fold("x = typeof (1 && y);"), "x=typeof(1&&y)");
+  assert_eq!(fold("x = delete (1 && y.z);"), "x=delete(1&&y.z)");
+  // Calls and literals have no reference to lose.
+  assert_eq!(fold("(1 && f())();"), "f()()");
+  assert_eq!(fold("(1 + 2)();"), "(3)()");
+  assert_eq!(fold("x = typeof (1 && 2);"), "x=\"number\"");
+  assert_eq!(fold("x = obj[1 && 'key'];"), "x=obj[\"key\"]");
+}
+
+#[test]
+fn directives() {
+  assert_eq!(
+    fold("function f() { 'use' + ' strict'; 1 && 'use strict'; return 'a' + 'b'; }"),
+    "function f(){\"use\"+\" strict\";1&&\"use strict\";return\"ab\"}"
+  );
+  assert_eq!(fold("1 + 2;"), "3");
+}
+
+#[test]
+fn nested_code() {
+  assert_eq!(
+    fold("function f(a = 2 * 3) { return () => [1 + 1, { k: -(-1) }]; }"),
+    "function f(a=6){return()=>[2,{k:1}]}"
+  );
+  assert_eq!(
+    fold("if (1 < 2) { x = 'p' + 'q'; } else { y = 2 ** 8; }"),
+    "if(true){x=\"pq\"}else{y=256}"
+  );
+}
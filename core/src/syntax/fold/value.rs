@@ -0,0 +1,415 @@
+//! The values of constant expressions, with the conversions and operators of the language.
+
+use crate::syntax::ast::{
+  operator::{BitOp, CompOp, NumOp, UnaryOp},
+  Const,
+};
+use std::cmp::Ordering;
+
+/// The value of a constant expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+  Undefined,
+  Null,
+  Bool(bool),
+  Number(f64),
+  String(Box<str>),
+  /// A new object other than a function, such as the one created by a regular expression
+  /// literal.
+  Object,
+  /// A new function, created by a function expression or an arrow function.
+  Function,
+}
+
+impl Value {
+  /// Gets the value of a literal.
+  pub fn from_const(value: &Const) -> Self {
+    match value {
+      Const::String(string) => Self::String(string.clone()),
+      Const::RegExp(..) => Self::Object,
+      Const::Num(num) => Self::Number(*num),
+      Const::Int(int) => Self::Number(f64::from(*int)),
+      Const::Bool(value) => Self::Bool(*value),
+      Const::Null => Self::Null,
+      Const::Undefined => Self::Undefined,
+    }
+  }
+
+  /// Converts the value to a literal printing as a primitive of the same value.
+  ///
+  /// This is `None` for objects, and for `undefined` and `NaN`, which are printed as globals
+  /// that a binding may shadow. Integers fitting in 32 bits become [`Const::Int`], other
+  /// numbers, such as `-0`, [`Const::Num`].
+  pub fn to_const(&self) -> Option<Const> {
+    match self {
+      Self::Null => Some(Const::Null),
+      Self::Bool(value) => Some(Const::Bool(*value)),
+      Self::String(string) => Some(Const::String(string.clone())),
+      Self::Number(num) if num.is_nan() => None,
+      Self::Number(num) => {
+        let int = *num as i32;
+        if f64::from(int) == *num && !(int == 0 && num.is_sign_negative()) {
+          Some(Const::Int(int))
+        } else {
+          Some(Const::Num(*num))
+        }
+      }
+      Self::Undefined | Self::Object | Self::Function => None,
+    }
+  }
+
+  /// Gets the result of `typeof`.
+  pub fn type_of(&self) -> &'static str {
+    match self {
+      Self::Undefined => "undefined",
+      Self::Null | Self::Object => "object",
+      Self::Bool(_) => "boolean",
+      Self::Number(_) => "number",
+      Self::String(_) => "string",
+      Self::Function => "function",
+    }
+  }
+
+  /// Converts the value to a boolean, as in `if` conditions.
+  pub fn to_boolean(&self) -> bool {
+    match self {
+      Self::Undefined | Self::Null => false,
+      Self::Bool(value) => *value,
+      Self::Number(num) => !(*num == 0.0 || num.is_nan()),
+      Self::String(string) => !string.is_empty(),
+      Self::Object | Self::Function => true,
+    }
+  }
+
+  /// Converts the value to a number, as in `+value`.
+  ///
+  /// This is `None` for objects, whose conversion may run code, and for hexadecimal, octal and
+  /// binary strings too long to be converted here.
+  pub fn to_number(&self) -> Option<f64> {
+    match self {
+      Self::Undefined => Some(f64::NAN),
+      Self::Null => Some(0.0),
+      Self::Bool(value) => Some(if *value { 1.0 } else { 0.0 }),
+      Self::Number(num) => Some(*num),
+      Self::String(string) => string_to_number(string),
+      Self::Object | Self::Function => None,
+    }
+  }
+
+  /// Converts the value to a string, as in `"" + value`.
+  ///
+  /// This is `None` for objects, whose conversion may run code.
+  pub fn to_js_string(&self) -> Option<Box<str>> {
+    Some(match self {
+      Self::Undefined => "undefined".into(),
+      Self::Null => "null".into(),
+      Self::Bool(value) => value.to_string().into(),
+      Self::Number(num) => number_to_string(*num).into(),
+      Self::String(string) => string.clone(),
+      Self::Object | Self::Function => return None,
+    })
+  }
+
+  /// Returns `true` if the value is `null` or `undefined`.
+  pub fn is_nullish(&self) -> bool {
+    matches!(self, Self::Undefined | Self::Null)
+  }
+
+  fn is_object(&self) -> bool {
+    matches!(self, Self::Object | Self::Function)
+  }
+
+  fn to_int32(&self) -> Option<i32> {
+    self.to_number().map(|num| to_uint32(num) as i32)
+  }
+
+  fn to_uint32(&self) -> Option<u32> {
+    self.to_number().map(to_uint32)
+  }
+}
+
+/// Applies a unary operator, `None` if the result is not known.
+pub(super) fn unary(op: UnaryOp, value: &Value) -> Option<Value> {
+  Some(match op {
+    UnaryOp::Minus => Value::Number(-value.to_number()?),
+    UnaryOp::Plus => Value::Number(value.to_number()?),
+    UnaryOp::Not => Value::Bool(!value.to_boolean()),
+    UnaryOp::Tilde => Value::Number(f64::from(!value.to_int32()?)),
+    UnaryOp::TypeOf => Value::String(value.type_of().into()),
+    UnaryOp::Void => Value::Undefined,
+    UnaryOp::IncrementPre
+    | UnaryOp::IncrementPost
+    | UnaryOp::DecrementPre
+    | UnaryOp::DecrementPost
+    | UnaryOp::Delete => return None,
+  })
+}
+
+/// Applies an arithmetic operator, `None` if the result is not known.
+pub(super) fn numeric(op: NumOp, lhs: &Value, rhs: &Value) -> Option<Value> {
+  if op == NumOp::Add {
+    if lhs.is_object() || rhs.is_object() {
+      return None;
+    }
+    if matches!(lhs, Value::String(_)) || matches!(rhs, Value::String(_)) {
+      let mut string = String::from(lhs.to_js_string()?);
+      string.push_str(&rhs.to_js_string()?);
+      return Some(Value::String(string.into()));
+    }
+  }
+  let (lhs, rhs) = (lhs.to_number()?, rhs.to_number()?);
+  Some(Value::Number(match op {
+    NumOp::Add => lhs + rhs,
+    NumOp::Sub => lhs - rhs,
+    NumOp::Mul => lhs * rhs,
+    NumOp::Div => lhs / rhs,
+    // The remainder of the language truncates like `fmod`, the sign is the one of `lhs`.
+    NumOp::Mod => lhs % rhs,
+    NumOp::Exp => power(lhs, rhs)?,
+  }))
+}
+
+/// Applies a bitwise operator, `None` if the result is not known.
+pub(super) fn bitwise(op: BitOp, lhs: &Value, rhs: &Value) -> Option<Value> {
+  let result = match op {
+    BitOp::And => f64::from(lhs.to_int32()? & rhs.to_int32()?),
+    BitOp::Or => f64::from(lhs.to_int32()? | rhs.to_int32()?),
+    BitOp::Xor => f64::from(lhs.to_int32()? ^ rhs.to_int32()?),
+    BitOp::Shl => f64::from(lhs.to_int32()?.wrapping_shl(rhs.to_uint32()?)),
+    BitOp::Shr => f64::from(lhs.to_int32()?.wrapping_shr(rhs.to_uint32()?)),
+    BitOp::UShr => f64::from(lhs.to_uint32()?.wrapping_shr(rhs.to_uint32()?)),
+  };
+  Some(Value::Number(result))
+}
+
+/// Applies a comparison operator, `None` if the result is not known.
+pub(super) fn comparison(op: CompOp, lhs: &Value, rhs: &Value) -> Option<Value> {
+  if lhs.is_object() || rhs.is_object() {
+    return None;
+  }
+  let result = match op {
+    CompOp::Equal => loosely_equal(lhs, rhs)?,
+    CompOp::NotEqual => !loosely_equal(lhs, rhs)?,
+    CompOp::StrictEqual => strictly_equal(lhs, rhs),
+    CompOp::StrictNotEqual => !strictly_equal(lhs, rhs),
+    CompOp::LessThan => compare(lhs, rhs)? == Some(Ordering::Less),
+    CompOp::GreaterThan => compare(lhs, rhs)? == Some(Ordering::Greater),
+    CompOp::LessThanOrEqual => matches!(
+      compare(lhs, rhs)?,
+      Some(Ordering::Less) | Some(Ordering::Equal)
+    ),
+    CompOp::GreaterThanOrEqual => matches!(
+      compare(lhs, rhs)?,
+      Some(Ordering::Greater) | Some(Ordering::Equal)
+    ),
+    // Both throw with primitive right hand sides.
+    CompOp::In | CompOp::InstanceOf => return None,
+  };
+  Some(Value::Bool(result))
+}
+
+/// Compares primitives with `===`.
+fn strictly_equal(lhs: &Value, rhs: &Value) -> bool {
+  match (lhs, rhs) {
+    // `NaN` is not equal to itself, and `0` is equal to `-0`.
+    (Value::Number(lhs), Value::Number(rhs)) => lhs == rhs,
+    (lhs, rhs) => lhs == rhs,
+  }
+}
+
+/// Compares primitives with `==`.
+fn loosely_equal(lhs: &Value, rhs: &Value) -> Option<bool> {
+  match (lhs, rhs) {
+    (lhs, rhs) if lhs.is_nullish() || rhs.is_nullish() => {
+      Some(lhs.is_nullish() && rhs.is_nullish())
+    }
+    (Value::String(lhs), Value::String(rhs)) => Some(lhs == rhs),
+    (Value::Bool(lhs), Value::Bool(rhs)) => Some(lhs == rhs),
+    // Other primitives of different types are compared as numbers.
+    (lhs, rhs) => Some(lhs.to_number()? == rhs.to_number()?),
+  }
+}
+
+/// Compares primitives with `<`, the inner `None` being the result of comparisons with `NaN`.
+fn compare(lhs: &Value, rhs: &Value) -> Option<Option<Ordering>> {
+  match (lhs, rhs) {
+    // Strings are compared by their UTF-16 code units, not by code points.
+    (Value::String(lhs), Value::String(rhs)) => {
+      Some(Some(lhs.encode_utf16().cmp(rhs.encode_utf16())))
+    }
+    (lhs, rhs) => Some(lhs.to_number()?.partial_cmp(&rhs.to_number()?)),
+  }
+}
+
+/// Raises a number to a power, `None` unless the result is exact.
+///
+/// Engines may approximate the other results differently from the standard library.
+fn power(base: f64, exponent: f64) -> Option<f64> {
+  if exponent.is_nan() || (base.abs() == 1.0 && exponent.is_infinite()) {
+    return Some(f64::NAN);
+  }
+  if exponent == 0.0 {
+    return Some(1.0);
+  }
+  if base.is_nan() {
+    return Some(f64::NAN);
+  }
+  if base.fract() != 0.0 || exponent.fract() != 0.0 || !(0.0..=1100.0).contains(&exponent) {
+    return None;
+  }
+  let mut result = 1.0_f64;
+  for _ in 0..exponent as u32 {
+    result *= base;
+    if result.abs() > EXACT_INTEGERS {
+      return None;
+    }
+  }
+  Some(result)
+}
+
+/// 2<sup>53</sup>, up to which all integers are numbers.
+const EXACT_INTEGERS: f64 = 9_007_199_254_740_992.0;
+
+/// Converts a number to an unsigned 32 bits integer, wrapping around.
+fn to_uint32(num: f64) -> u32 {
+  if !num.is_finite() {
+    return 0;
+  }
+  num.trunc().rem_euclid(4_294_967_296.0) as u32
+}
+
+/// Converts a number to a string, as `String(num)` does.
+///
+/// The digits are the fewest that convert back to the same number, written with an exponent
+/// when the number is at least `1e21` or less than `1e-6`.
+pub fn number_to_string(num: f64) -> String {
+  if num.is_nan() {
+    return "NaN".to_owned();
+  }
+  if num == 0.0 {
+    return "0".to_owned();
+  }
+  if num < 0.0 {
+    return format!("-{}", number_to_string(-num));
+  }
+  if num.is_infinite() {
+    return "Infinity".to_owned();
+  }
+
+  // The standard library prints the shortest digits that round trip, like `1.2345e3`.
+  let scientific = format!("{:e}", num);
+  let (mantissa, exponent) = scientific
+    .split_once('e')
+    .expect("scientific notation has an exponent");
+  let digits = mantissa.replace('.', "");
+  let k = digits.len() as i32;
+  let n = exponent.parse::<i32>().expect("the exponent is an integer") + 1;
+
+  if k <= n && n <= 21 {
+    format!("{}{}", digits, "0".repeat((n - k) as usize))
+  } else if 0 < n && n <= 21 {
+    let (int, fract) = digits.split_at(n as usize);
+    format!("{}.{}", int, fract)
+  } else if -6 < n && n <= 0 {
+    format!("0.{}{}", "0".repeat(-n as usize), digits)
+  } else {
+    let sign = if n > 0 { '+' } else { '-' };
+    let (first, rest) = digits.split_at(1);
+    let point = if rest.is_empty() { "" } else { "." };
+    format!("{}{}{}e{}{}", first, point, rest, sign, (n - 1).abs())
+  }
+}
+
+/// Converts a string to a number, as `Number(string)` does.
+///
+/// This is `None` for hexadecimal, octal and binary integers of more than 64 bits, whose
+/// rounding is not done here.
+pub fn string_to_number(string: &str) -> Option<f64> {
+  let string = string.trim_matches(is_whitespace);
+  if string.is_empty() {
+    return Some(0.0);
+  }
+
+  let bytes = string.as_bytes();
+  if bytes.len() > 2 && bytes[0] == b'0' {
+    let radix = match bytes[1] {
+      b'x' | b'X' => Some(16),
+      b'o' | b'O' => Some(8),
+      b'b' | b'B' => Some(2),
+      _ => None,
+    };
+    if let Some(radix) = radix {
+      let mut value: u64 = 0;
+      for &byte in &bytes[2..] {
+        let digit = match (byte as char).to_digit(radix) {
+          Some(digit) => digit,
+          None => return Some(f64::NAN),
+        };
+        value = value
+          .checked_mul(u64::from(radix))?
+          .checked_add(u64::from(digit))?;
+      }
+      // The conversion rounds to the nearest number, as the language does.
+      return Some(value as f64);
+    }
+  }
+
+  let (negative, unsigned) = match bytes[0] {
+    b'-' => (true, &string[1..]),
+    b'+' => (false, &string[1..]),
+    _ => (false, string),
+  };
+  let magnitude = if unsigned == "Infinity" {
+    f64::INFINITY
+  } else if is_decimal(unsigned) {
+    unsigned.parse().ok()?
+  } else {
+    return Some(f64::NAN);
+  };
+  Some(if negative { -magnitude } else { magnitude })
+}
+
+/// Returns `true` if a string is an unsigned decimal literal, such as `1.5e3`, `.5` or `1.`.
+///
+/// Numeric separators, `Infinity` spelled otherwise and other forms accepted by the standard
+/// library are not.
+fn is_decimal(string: &str) -> bool {
+  let bytes = string.as_bytes();
+  let digits = |start: usize| {
+    bytes[start..]
+      .iter()
+      .take_while(|byte| byte.is_ascii_digit())
+      .count()
+  };
+  let int = digits(0);
+  let mut end = int;
+  let mut fract = 0;
+  if bytes.get(end) == Some(&b'.') {
+    fract = digits(end + 1);
+    end += 1 + fract;
+  }
+  if int + fract == 0 {
+    return false;
+  }
+  if matches!(bytes.get(end), Some(b'e') | Some(b'E')) {
+    end += 1;
+    if matches!(bytes.get(end), Some(b'+') | Some(b'-')) {
+      end += 1;
+    }
+    let exponent = digits(end);
+    if exponent == 0 {
+      return false;
+    }
+    end += exponent;
+  }
+  end == bytes.len()
+}
+
+/// Returns `true` for the white space and line terminators trimmed by `Number(string)`.
+fn is_whitespace(ch: char) -> bool {
+  matches!(
+    ch,
+    '\t' | '\n' | '\u{b}' | '\u{c}' | '\r' | ' ' | '\u{a0}' | '\u{1680}' | '\u{2000}'
+      ..='\u{200a}' | '\u{2028}' | '\u{2029}' | '\u{202f}' | '\u{205f}' | '\u{3000}' | '\u{feff}'
+  )
+}
@@ -0,0 +1,1384 @@
+//! Incremental parsing: parsing again only the part of a source that an edit changed.
+//!
+//! A [`Document`] keeps the text of a script or a module together with its syntax tree. Each
+//! [`Edit`] applied to it parses again the smallest function body or top level statement whose
+//! text changed, and moves the rest of the tree over as it was:
+//!
+//! ```
+//! use plastic::syntax::{
+//!   ast::{Position, Span},
+//!   incremental::{Document, Reparse},
+//!   edit::Edit,
+//!   parser::Parser,
+//! };
+//!
+//! let mut document = Document::parse_script("let total = 0;\nfunction add(n) {\n  total += n;\n}\n")
+//!   .unwrap();
+//! let span = Span::new(Position::new(3, 12), Position::new(3, 13));
+//! let reparse = document.edit(&Edit::new(span, "n * 2")).unwrap();
+//! assert_eq!(
+//!   reparse,
+//!   Reparse::Function(Span::new(Position::new(2, 17), Position::new(4, 2)))
+//! );
+//! assert_eq!(
+//!   document.statements(),
+//!   &Parser::new(document.text().as_bytes()).parse_script().unwrap()
+//! );
+//! ```
+//!
+//! The document keeps the byte offsets of the function bodies and of the top level statements
+//! on the side, shifting the ones after an edit by the length it added or removed, and moves
+//! the spans of the nodes after an edit over the text it inserted. A function is found in the
+//! tree by the span of its body, where its own span ends. An edit is parsed again on its own
+//! when it is inside:
+//!
+//!  - The braces of the body of a function, a method, a class constructor or an arrow function,
+//!    outside of parameters and computed property names. The body is parsed inside a function
+//!    with the same name, parameters and kind, itself inside code like the code around it: a
+//!    method, a function, a field or a static block with the same meaning of `super`,
+//!    `arguments`, `yield` and `await` around an arrow function, and a class declaring the same
+//!    private names around code in a class. It is in strict mode code when the code around the
+//!    function is.
+//!  - A top level statement, after its first token and before its last one, when the token
+//!    after it can not continue it. The new statement must declare the same names, and neither
+//!    it nor the old one can be a directive, an import or an export.
+//!
+//! Any other edit, or an edit whose part does not parse on its own, parses the whole text
+//! again, so that the tree is always the one a fresh parse of the text gives.
+
+#[cfg(test)]
+mod tests;
+
+use crate::syntax::{
+  ast::{
+    module::Module,
+    node::{
+      ArrowFunctionDecl, Block, Class, ClassElement, ClassElementName, FormalParameter,
+      FunctionDecl, FunctionExpr, FunctionKind, Identifier, MethodDefinitionKind, Pattern,
+      PropertyDefinition, PropertyName, StatementList, TemplateLit,
+    },
+    visitor::{
+      visit, visit_mut, Control, Flow, KindPath, NodeMut, NodePath, Path, Visitor, VisitorMut,
+    },
+    Keyword, Node, Position, Punctuator, Span,
+  },
+  codegen::{Generator, Options},
+  edit::Edit,
+  lexer::TokenKind,
+  locate::{Lines, Locations, Source},
+  parser::{ParseError, Parser},
+  scope::{BindingKind, ScopeTree},
+};
+use std::{mem, ops::ControlFlow, slice};
+
+/// The directive put before the parts of a strict script parsed on their own.
+const USE_STRICT: &str = "\"use strict\";\n";
+
+/// The line width the parameters of functions are printed with, wide enough to keep them on a
+/// single line.
+const PARAMETERS_WIDTH: usize = 1000;
+
+/// What an edit parsed again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reparse {
+  /// The body of a function, with the span of its braces in the new text.
+  Function(Span),
+  /// The top level statement with the given index.
+  Statement(usize),
+  /// The whole text.
+  Full,
+}
+
+/// The syntax tree of a document.
+#[derive(Debug)]
+enum Tree {
+  Script(StatementList),
+  Module(Module),
+}
+
+impl Tree {
+  /// Parses a script or a module.
+  fn parse(text: &str, module: bool) -> Result<Self, ParseError> {
+    let mut parser = Parser::new(text.as_bytes());
+    if module {
+      Ok(Self::Module(parser.parse_module()?))
+    } else {
+      Ok(Self::Script(parser.parse_script()?))
+    }
+  }
+}
+
+/// A script or a module that is parsed again incrementally as it is edited.
+#[derive(Debug)]
+pub struct Document {
+  text: String,
+  lines: Lines,
+  tree: Tree,
+  regions: Vec<Region>,
+  up_to_date: bool,
+}
+
+impl Document {
+  /// Parses a script.
+  pub fn parse_script<T>(text: T) -> Result<Self, ParseError>
+  where
+    T: Into<String>,
+  {
+    Self::parse(text.into(), false)
+  }
+
+  /// Parses a module.
+  pub fn parse_module<T>(text: T) -> Result<Self, ParseError>
+  where
+    T: Into<String>,
+  {
+    Self::parse(text.into(), true)
+  }
+
+  fn parse(text: String, module: bool) -> Result<Self, ParseError> {
+    let tree = Tree::parse(&text, module)?;
+    let mut document = Self {
+      lines: Lines::new(&text),
+      text,
+      tree,
+      regions: Vec::new(),
+      up_to_date: true,
+    };
+    document.regions = document.find_regions();
+    Ok(document)
+  }
+
+  /// Gets the text.
+  pub fn text(&self) -> &str {
+    &self.text
+  }
+
+  /// Returns `true` if the document is a module.
+  pub fn is_module(&self) -> bool {
+    matches!(self.tree, Tree::Module(_))
+  }
+
+  /// Gets the statements of the script or of the module.
+  pub fn statements(&self) -> &StatementList {
+    match &self.tree {
+      Tree::Script(statements) => statements,
+      Tree::Module(module) => module.body(),
+    }
+  }
+
+  /// Gets the module, or `None` for a script.
+  pub fn module(&self) -> Option<&Module> {
+    match &self.tree {
+      Tree::Script(_) => None,
+      Tree::Module(module) => Some(module),
+    }
+  }
+
+  /// Returns `true` if the syntax tree is the one of the text, which is not the case after an
+  /// edit that left the text with a syntax error.
+  pub fn is_up_to_date(&self) -> bool {
+    self.up_to_date
+  }
+
+  /// Applies an edit to the text and parses again what it changed.
+  ///
+  /// The span of the edit is in the text before it. When the new text does not parse, the
+  /// error is returned and the syntax tree stays the last one that parsed, until an edit fixes
+  /// the error.
+  pub fn edit(&mut self, edit: &Edit) -> Result<Reparse, ParseError> {
+    let start = self.lines.offset(&self.text, edit.span().start());
+    let end = self.lines.offset(&self.text, edit.span().end()).max(start);
+    let change = Change {
+      start,
+      end,
+      delta: edit.text().len() as isize - (end - start) as isize,
+    };
+    let region = if self.up_to_date {
+      self.region(change)
+    } else {
+      None
+    };
+    // The function of a body is found before the edit, where its body ends.
+    let function = match region {
+      Some(Region {
+        target: Target::Body { .. },
+        end,
+        ..
+      }) => self
+        .function(self.lines.position(&self.text, end))
+        .map(|function| (function.head, function.tail)),
+      _ => None,
+    };
+    let (start_position, end_position) = (
+      self.lines.position(&self.text, start),
+      self.lines.position(&self.text, end),
+    );
+    self.text.replace_range(start..end, edit.text());
+    self.lines = Lines::new(&self.text);
+    let moved = Moved {
+      start: start_position,
+      end: end_position,
+      new_end: self.lines.position(&self.text, start + edit.text().len()),
+    };
+
+    let reparse = region.and_then(|region| match (region.target, function) {
+      (Target::Body { strict }, Some((head, tail))) => {
+        self.reparse_body(region, &head, &tail, strict, change, moved)
+      }
+      (Target::Body { .. }, None) => None,
+      (Target::Statement { index, .. }, _) => self.reparse_statement(region, index, change, moved),
+    });
+    if let Some(reparse) = reparse {
+      return Ok(reparse);
+    }
+
+    match Tree::parse(&self.text, self.is_module()) {
+      Ok(tree) => {
+        self.tree = tree;
+        self.regions = self.find_regions();
+        self.up_to_date = true;
+        Ok(Reparse::Full)
+      }
+      Err(err) => {
+        self.regions.clear();
+        self.up_to_date = false;
+        Err(err)
+      }
+    }
+  }
+
+  /// Finds the smallest region around a change which can be parsed again on its own.
+  ///
+  /// The regions are found in the tokens, so the text of a region is parsed again the first
+  /// time it is edited, to check that it is the text of its part of the tree.
+  fn region(&mut self, change: Change) -> Option<Region> {
+    let body = self
+      .regions
+      .iter()
+      .enumerate()
+      .filter(|(_, region)| matches!(region.target, Target::Body { .. }))
+      .filter(|(_, region)| region.contains(change))
+      .min_by_key(|(_, region)| region.end - region.start);
+    let statement = || {
+      self.regions.iter().enumerate().find(|(_, region)| {
+        matches!(region.target, Target::Statement { closed: true, .. }) && region.contains(change)
+      })
+    };
+    let (index, &region) = body.or_else(statement)?;
+    if region.checked {
+      return Some(region);
+    }
+    let checked = match region.target {
+      Target::Body { strict } => {
+        let function = self.function(self.lines.position(&self.text, region.end))?;
+        let (head, tail) = (&function.head, &function.tail);
+        let mut part = self.parse_body(head, tail, strict, region.start, region.end)?;
+        part
+          .take_body(region.end - region.start)
+          .as_ref()
+          .map(StatementList::items)
+          == Some(function.body)
+      }
+      Target::Statement { index, .. } => {
+        let part = self.parse_statement(region.start, region.end)?;
+        part.statements.items().last() == self.statements().items().get(index)
+      }
+    };
+    if !checked {
+      self.regions.remove(index);
+      return None;
+    }
+    self.regions[index].checked = true;
+    Some(self.regions[index])
+  }
+
+  /// Parses again the body of a function inside code like the code around it, the head and the
+  /// tail of the function going before and after the body.
+  fn reparse_body(
+    &mut self,
+    region: Region,
+    head: &str,
+    tail: &str,
+    strict: bool,
+    change: Change,
+    moved: Moved,
+  ) -> Option<Reparse> {
+    let end = change.shift(region.end);
+    let mut part = self.parse_body(head, tail, strict, region.start, end)?;
+    let regions = part.regions(self.is_module());
+    let mut body = part.take_body(end - region.start)?;
+    self.move_part(&part, region.start, body.items_mut());
+
+    let mut swap = SwapBody::new(
+      Span::new(
+        self.lines.position(&self.text, region.start),
+        self.lines.position(&self.text, end),
+      ),
+      body,
+    );
+    self.edit_statements(|statements| {
+      moved.visit(statements);
+      let _ = swap.visit_statements(statements, &mut Path::new());
+    });
+    if !swap.swapped {
+      return None;
+    }
+
+    let (start, offset, length) = (region.start, part.offset, end - region.start);
+    self.update_regions(region, change, regions, |inner| match inner.target {
+      Target::Body { .. } if inner.start == offset => Some(Region {
+        checked: true,
+        ..inner.moved(offset, start)
+      }),
+      Target::Body { .. } if inner.start > offset && inner.end <= offset + length => {
+        Some(inner.moved(offset, start))
+      }
+      _ => None,
+    });
+    Some(Reparse::Function(Span::new(
+      self.lines.position(&self.text, start),
+      self.lines.position(&self.text, end),
+    )))
+  }
+
+  /// Parses again a top level statement on its own.
+  fn reparse_statement(
+    &mut self,
+    region: Region,
+    index: usize,
+    change: Change,
+    moved: Moved,
+  ) -> Option<Reparse> {
+    let old = self.statements().items().get(index)?;
+    if !is_reparsable(old) {
+      return None;
+    }
+    let mut part = self.parse_statement(region.start, change.shift(region.end))?;
+    let new = part.statements.items().last()?;
+    let strict = self.is_module() || self.statements().strict();
+    if !is_reparsable(new) || declared(&part.tree) != declared(&self.declaration_tree(old, strict))
+    {
+      return None;
+    }
+
+    let regions = part.regions(self.is_module());
+    let mut new = None;
+    part.statements.replace_items(|mut items| {
+      new = items.pop();
+      items
+    });
+    let mut new = new?;
+    self.move_part(&part, region.start, slice::from_mut(&mut new));
+    self.edit_statements(|statements| {
+      moved.visit(statements);
+      statements[index] = new;
+    });
+
+    let (start, offset) = (region.start, part.offset);
+    self.update_regions(region, change, regions, |inner| match inner.target {
+      Target::Body { .. } if inner.start >= offset => Some(inner.moved(offset, start)),
+      Target::Statement { .. } if inner.start == offset => Some(Region {
+        target: region.target,
+        checked: true,
+        ..inner.moved(offset, start)
+      }),
+      _ => None,
+    });
+    Some(Reparse::Statement(index))
+  }
+
+  /// Finds a function by the end of its span, which is the end of its body.
+  fn function(&self, end: Position) -> Option<Function<'_>> {
+    let mut function = None;
+    let mut functions = Functions::new(false, |found: &Found<'_, '_>| {
+      if found.span.end() != end {
+        return ControlFlow::Continue(());
+      }
+      function = found.wrapper().map(|(head, tail)| Function {
+        head,
+        tail,
+        body: found.body,
+      });
+      ControlFlow::Break(())
+    });
+    let _ = functions.visit_statements(self.statements().items(), &mut Path::new());
+    function
+  }
+
+  /// Parses the text of the body of a function, between `start` and `end`, between the head
+  /// and the tail of a function like it.
+  fn parse_body(
+    &self,
+    head: &str,
+    tail: &str,
+    strict: bool,
+    start: usize,
+    end: usize,
+  ) -> Option<Part> {
+    let mut text = self.prefix(strict).to_owned();
+    text.push_str(head);
+    let offset = text.len();
+    text.push_str(&self.text[start..end]);
+    text.push_str(tail);
+    self.parse_part(text, offset)
+  }
+
+  /// Parses the text of a top level statement, between `start` and `end`, on its own.
+  fn parse_statement(&self, start: usize, end: usize) -> Option<Part> {
+    let strict = self.is_module() || self.statements().strict();
+    let mut text = self.prefix(strict).to_owned();
+    let offset = text.len();
+    text.push_str(&self.text[start..end]);
+    self.parse_part(text, offset)
+  }
+
+  /// Parses a part of the document on its own, with the same goal as the document, which must
+  /// be a single statement after the directive put before it.
+  fn parse_part(&self, text: String, offset: usize) -> Option<Part> {
+    let mut parser = Parser::new(text.as_bytes());
+    let (statements, tree) = if self.is_module() {
+      let module = parser.parse_module().ok()?;
+      let tree = ScopeTree::module(&module);
+      (module.into_body(), tree)
+    } else {
+      let statements = parser.parse_script().ok()?;
+      let tree = ScopeTree::script(&statements);
+      (statements, tree)
+    };
+    let directives = usize::from(!self.is_module() && text.starts_with(USE_STRICT));
+    if statements.items().len() != directives + 1 {
+      return None;
+    }
+    Some(Part {
+      text,
+      offset,
+      statements,
+      tree,
+    })
+  }
+
+  /// Moves the spans of statements parsed in a part to the text of the document, where the part
+  /// starts at `start`.
+  fn move_part(&self, part: &Part, start: usize, statements: &mut [Node]) {
+    let lines = Lines::new(&part.text);
+    let mut respan = Respan(|position, _| {
+      let offset = lines
+        .offset(&part.text, position)
+        .saturating_sub(part.offset);
+      self.lines.position(&self.text, start + offset)
+    });
+    let _ = respan.visit_statements(statements, &mut Path::new());
+  }
+
+  /// Gets the text put before the parts of the document parsed on their own.
+  fn prefix(&self, strict: bool) -> &'static str {
+    if strict && !self.is_module() {
+      USE_STRICT
+    } else {
+      ""
+    }
+  }
+
+  /// Analyzes the scopes of a statement on its own, to find what it declares.
+  fn declaration_tree(&self, statement: &Node, strict: bool) -> ScopeTree {
+    let statements = StatementList::from(vec![statement.clone()]).with_strict(strict);
+    if self.is_module() {
+      ScopeTree::module(&Module::new(statements))
+    } else {
+      ScopeTree::script(&statements)
+    }
+  }
+
+  /// Changes the top level statements.
+  fn edit_statements<F>(&mut self, f: F)
+  where
+    F: FnOnce(&mut [Node]),
+  {
+    match &mut self.tree {
+      Tree::Script(statements) => f(statements.items_mut()),
+      Tree::Module(module) => {
+        let mut body = mem::replace(module, Module::new(Vec::<Node>::new())).into_body();
+        f(body.items_mut());
+        *module = Module::new(body);
+      }
+    }
+  }
+
+  /// Moves the regions over a change which parsed again the given region, replacing the
+  /// regions inside of it with the ones of the part parsed on its own.
+  fn update_regions<F>(&mut self, region: Region, change: Change, inner: Vec<Region>, f: F)
+  where
+    F: FnMut(Region) -> Option<Region>,
+  {
+    self
+      .regions
+      .retain(|other| !(other.start >= region.start && other.end <= region.end));
+    for other in &mut self.regions {
+      if other.end <= change.start {
+        continue;
+      } else if other.start >= change.end {
+        other.start = change.shift(other.start);
+        other.inner_start = change.shift(other.inner_start);
+      }
+      other.inner_end = change.shift(other.inner_end);
+      other.end = change.shift(other.end);
+    }
+    self.regions.extend(inner.into_iter().filter_map(f));
+    self
+      .regions
+      .sort_by_key(|region| (region.start, region.end));
+  }
+
+  /// Finds the regions of the whole text.
+  fn find_regions(&self) -> Vec<Region> {
+    match &self.tree {
+      Tree::Script(statements) => regions(
+        &self.text,
+        statements,
+        &ScopeTree::script(statements),
+        false,
+      ),
+      Tree::Module(module) => regions(&self.text, module.body(), &ScopeTree::module(module), true),
+    }
+  }
+}
+
+/// The part of the text an edit replaced, in byte offsets of the text before it, with the
+/// length it added or removed.
+#[derive(Debug, Clone, Copy)]
+struct Change {
+  start: usize,
+  end: usize,
+  delta: isize,
+}
+
+impl Change {
+  /// Moves an offset after the change.
+  fn shift(self, offset: usize) -> usize {
+    (offset as isize + self.delta) as usize
+  }
+}
+
+/// Where an edit was in the text before it, and where the text it inserted ends in the text
+/// after it.
+#[derive(Debug, Clone, Copy)]
+struct Moved {
+  start: Position,
+  end: Position,
+  new_end: Position,
+}
+
+impl Moved {
+  /// Moves a position of the text before the edit to the text after it. A span ending at the
+  /// start of the edit ends before the text it inserted, and a span starting at its end starts
+  /// after it.
+  fn position(self, position: Position, end: bool) -> Position {
+    let after = if end {
+      position > self.start
+    } else {
+      position >= self.end
+    };
+    if !after {
+      position
+    } else if position < self.end {
+      self.new_end
+    } else if position.line_number() == self.end.line_number() {
+      Position::new(
+        self.new_end.line_number(),
+        self.new_end.column_number() + position.column_number() - self.end.column_number(),
+      )
+    } else {
+      Position::new(
+        position.line_number() - self.end.line_number() + self.new_end.line_number(),
+        position.column_number(),
+      )
+    }
+  }
+
+  /// Moves the spans of statements over the edit.
+  fn visit(self, statements: &mut [Node]) {
+    let mut respan = Respan(|position, end| self.position(position, end));
+    let _ = respan.visit_statements(statements, &mut Path::new());
+  }
+}
+
+/// What a region of the text is the text of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+  /// The body of a function, with whether the code around the function is strict.
+  Body { strict: bool },
+  /// A top level statement, with whether the token after it can not continue it.
+  Statement { index: usize, closed: bool },
+}
+
+/// A part of the text that can be parsed again on its own, in byte offsets.
+#[derive(Debug, Clone, Copy)]
+struct Region {
+  target: Target,
+  start: usize,
+  end: usize,
+  /// Where the edits parsed again with the region start: after the opening brace of a body,
+  /// or after the first token of a statement.
+  inner_start: usize,
+  /// Where the edits parsed again with the region end: before the closing brace of a body, or
+  /// before the last token of a statement.
+  inner_end: usize,
+  /// Whether the text of the region is known to be the one of its part of the tree.
+  checked: bool,
+}
+
+impl Region {
+  /// Returns `true` if a change is inside the region.
+  fn contains(&self, change: Change) -> bool {
+    self.inner_start <= change.start && change.end <= self.inner_end
+  }
+
+  /// Moves a region found in a part of the text parsed on its own, starting at `from` in the
+  /// part, to the document, where the part starts at `to`.
+  fn moved(self, from: usize, to: usize) -> Self {
+    let shift = |offset: usize| offset - from + to;
+    Self {
+      target: self.target,
+      start: shift(self.start),
+      end: shift(self.end),
+      inner_start: shift(self.inner_start),
+      inner_end: shift(self.inner_end),
+      checked: self.checked,
+    }
+  }
+}
+
+/// A part of the document parsed on its own.
+struct Part {
+  text: String,
+  /// Where the text of the document starts in the text of the part.
+  offset: usize,
+  statements: StatementList,
+  tree: ScopeTree,
+}
+
+impl Part {
+  /// Finds the regions of the part.
+  fn regions(&self, module: bool) -> Vec<Region> {
+    regions(&self.text, &self.statements, &self.tree, module)
+  }
+
+  /// Takes the body of the function a body of the given length was parsed as, out of the
+  /// statements of the part.
+  fn take_body(&mut self, length: usize) -> Option<StatementList> {
+    let lines = Lines::new(&self.text);
+    let mut swap = SwapBody::new(
+      Span::new(
+        lines.position(&self.text, self.offset),
+        lines.position(&self.text, self.offset + length),
+      ),
+      StatementList::from(Vec::new()),
+    );
+    let _ = swap.visit_statements(self.statements.items_mut(), &mut Path::new());
+    if swap.swapped {
+      Some(swap.body)
+    } else {
+      None
+    }
+  }
+}
+
+/// Returns `true` if a top level statement can be parsed again on its own: it is not a
+/// directive, whose text can change the strictness of the code, nor an import or an export,
+/// which other statements of the module refer to.
+fn is_reparsable(statement: &Node) -> bool {
+  !matches!(
+    statement,
+    Node::Directive(_) | Node::Import(_) | Node::Export(_, _)
+  )
+}
+
+/// Gets the names and kinds of the bindings of the root scope, sorted.
+fn declared(tree: &ScopeTree) -> Vec<(&str, BindingKind)> {
+  let mut declared = tree
+    .scope(tree.root())
+    .bindings()
+    .iter()
+    .map(|&binding| {
+      let binding = tree.binding(binding);
+      (binding.name(), binding.kind())
+    })
+    .collect::<Vec<_>>();
+  declared.sort_by_key(|&(name, _)| name);
+  declared
+}
+
+/// Finds the regions of a parsed text: its top level statements and the bodies of its
+/// functions.
+fn regions(text: &str, statements: &StatementList, tree: &ScopeTree, module: bool) -> Vec<Region> {
+  let source = Source::new(text);
+  let locations = Locations::new(&source, statements.items(), tree);
+  let mut regions = Vec::new();
+
+  // The statements are only known when each one is found.
+  let starts = statements
+    .items()
+    .iter()
+    .map(|statement| locations.statement(statement))
+    .collect::<Option<Vec<_>>>();
+  if let Some(starts) = starts {
+    for (index, &first) in starts.iter().enumerate() {
+      let last = match starts.get(index + 1) {
+        Some(&next) => next.saturating_sub(1),
+        None => source.tokens.len().saturating_sub(1),
+      };
+      if last < first {
+        continue;
+      }
+      let (first_token, last_token) = (&source.tokens[first], &source.tokens[last]);
+      let closed = !source
+        .tokens
+        .get(last + 1)
+        .is_some_and(|next| continues(next.kind()));
+      regions.push(Region {
+        target: Target::Statement { index, closed },
+        start: source.offset(first_token.span().start()),
+        end: source.offset(last_token.span().end()),
+        inner_start: source.offset(first_token.span().end()),
+        inner_end: source.offset(last_token.span().start()),
+        checked: false,
+      });
+    }
+  }
+
+  let mut functions = Functions::new(module || statements.strict(), |found: &Found<'_, '_>| {
+    regions.extend(body_region(&source, &locations, found.body, found.strict));
+    ControlFlow::Continue(())
+  });
+  let _ = functions.visit_statements(statements.items(), &mut Path::new());
+  regions.sort_by_key(|region| (region.start, region.end));
+  regions
+}
+
+/// Returns `true` if a token could continue a statement before it, as an operator does.
+fn continues(kind: &TokenKind) -> bool {
+  match kind {
+    TokenKind::Identifier(_)
+    | TokenKind::BooleanLiteral(_)
+    | TokenKind::NullLiteral
+    | TokenKind::NumericLiteral(_)
+    | TokenKind::StringLiteral(_) => false,
+    TokenKind::Keyword(keyword) => matches!(keyword, Keyword::In | Keyword::InstanceOf),
+    TokenKind::Punctuator(punctuator) => !matches!(
+      punctuator,
+      Punctuator::OpenBlock | Punctuator::Not | Punctuator::Neg
+    ),
+    _ => true,
+  }
+}
+
+/// Finds the braces around a body, which is not empty.
+fn body_region(
+  source: &Source<'_>,
+  locations: &Locations,
+  body: &[Node],
+  strict: bool,
+) -> Option<Region> {
+  let first = locations.statement(body.first()?)?;
+  let open = source.tokens.get(first.checked_sub(1)?)?;
+  let close = source.tokens.get(source.list_end(first) + 1)?;
+  let is = |kind: &TokenKind, punctuator| *kind == TokenKind::Punctuator(punctuator);
+  if !is(open.kind(), Punctuator::OpenBlock) || !is(close.kind(), Punctuator::CloseBlock) {
+    return None;
+  }
+  Some(Region {
+    target: Target::Body { strict },
+    start: source.offset(open.span().start()),
+    end: source.offset(close.span().end()),
+    inner_start: source.offset(open.span().end()),
+    inner_end: source.offset(close.span().start()),
+    checked: false,
+  })
+}
+
+/// The text that goes after a class body put around a body parsed again.
+const CLASS_END: &str = " })";
+
+/// What the code around a function is in, for its body to be parsed again inside code like it.
+#[derive(Debug)]
+enum Frame<'ast> {
+  /// The body of a class, with the private names it declares.
+  Class(Vec<&'ast str>),
+  /// A function other than a method or an arrow function.
+  Function(FunctionKind),
+  /// A method.
+  Method(FunctionKind),
+  /// A class constructor, with whether its class has a superclass.
+  Constructor(bool),
+  /// The initializer of a class field.
+  Field,
+  /// A static block.
+  StaticBlock,
+  /// Parameters or a computed property name, where the code is not in the body of the function
+  /// around it.
+  Opaque,
+}
+
+/// What a function with a body is.
+#[derive(Debug, Clone, Copy)]
+enum Shape<'ast> {
+  Declaration(&'ast FunctionDecl),
+  Expression(&'ast FunctionExpr),
+  Arrow(&'ast ArrowFunctionDecl),
+  Method(&'ast FunctionExpr, MethodDefinitionKind),
+  /// A class constructor, with whether its class has a superclass.
+  Constructor(&'ast FunctionExpr, bool),
+}
+
+/// A function found in a tree, with the code around it.
+struct Found<'a, 'ast> {
+  /// The span of the function, which ends at the end of its body.
+  span: Span,
+  body: &'ast [Node],
+  shape: Shape<'ast>,
+  /// What the code around the function is in, innermost last.
+  frames: &'a [Frame<'ast>],
+  /// Whether the code around the function is strict.
+  strict: bool,
+}
+
+impl Found<'_, '_> {
+  /// Gets the text going before the body of the function, from the start of a statement, and
+  /// after it, to parse it again inside a function like it, itself inside code like the code
+  /// around it. Returns `None` for an arrow function that is not in the body of a function, a
+  /// class field or a static block, nor at the top level.
+  fn wrapper(&self) -> Option<(String, String)> {
+    let print = |function: Node| {
+      let code =
+        Generator::new(Options::pretty().with_line_width(PARAMETERS_WIDTH)).expression(&function);
+      code.strip_suffix("{}").unwrap_or(&code).to_owned()
+    };
+    let mut private = self
+      .frames
+      .iter()
+      .flat_map(|frame| match frame {
+        Frame::Class(private) => private.as_slice(),
+        _ => &[],
+      })
+      .copied()
+      .collect::<Vec<_>>();
+    private.sort_unstable();
+    private.dedup();
+    let in_class = self
+      .frames
+      .iter()
+      .any(|frame| matches!(frame, Frame::Class(_)));
+    // A class declaring the private names, before one of its members.
+    let class = |derived: bool, member: &str| {
+      let mut head = String::from(if derived {
+        "(class extends Object { "
+      } else {
+        "(class { "
+      });
+      for name in &private {
+        head.push_str(&format!("#{}; ", name));
+      }
+      head.push_str(member);
+      head
+    };
+    // The name and the parameters of a method.
+    let method = |name: &str, function: &FunctionExpr| {
+      let code = print(
+        FunctionExpr::new(None, function.parameters(), Vec::<Node>::new())
+          .with_kind(function.kind())
+          .into(),
+      );
+      Some(format!("{}{}", name, &code[code.find('(')?..]))
+    };
+
+    let wrapper = match self.shape {
+      Shape::Method(function, kind) => {
+        let accessor = match kind {
+          MethodDefinitionKind::Get => "get ",
+          MethodDefinitionKind::Set => "set ",
+          MethodDefinitionKind::Ordinary => "",
+        };
+        let name = format!("{}{}m", accessor, modifiers(function.kind()));
+        (
+          class(false, &method(&name, function)?),
+          CLASS_END.to_owned(),
+        )
+      }
+      Shape::Constructor(function, derived) => (
+        class(derived, &method("constructor", function)?),
+        CLASS_END.to_owned(),
+      ),
+      Shape::Declaration(_) | Shape::Expression(_) => {
+        let function = match self.shape {
+          Shape::Declaration(decl) => FunctionExpr::new(
+            Some(decl.name().into()),
+            decl.parameters(),
+            Vec::<Node>::new(),
+          )
+          .with_kind(decl.kind()),
+          Shape::Expression(expr) => FunctionExpr::new(
+            expr.name().map(Into::into),
+            expr.parameters(),
+            Vec::<Node>::new(),
+          )
+          .with_kind(expr.kind()),
+          _ => unreachable!("the shape is a function"),
+        };
+        let code = print(function.into());
+        if in_class {
+          (
+            class(false, &format!("x = ({}", code)),
+            format!(");{}", CLASS_END),
+          )
+        } else {
+          (format!("({}", code), ")".to_owned())
+        }
+      }
+      Shape::Arrow(arrow) => {
+        let code = format!(
+          "({}",
+          print(if arrow.is_async() {
+            ArrowFunctionDecl::new_async(arrow.params(), Vec::<Node>::new()).into()
+          } else {
+            ArrowFunctionDecl::new(arrow.params(), Vec::<Node>::new()).into()
+          })
+        );
+        match self.frames.last() {
+          None => (code, ")".to_owned()),
+          Some(Frame::Field) => (
+            class(false, &format!("x = {}", code)),
+            format!(");{}", CLASS_END),
+          ),
+          Some(Frame::StaticBlock) => (
+            class(false, &format!("static {{ {}", code)),
+            format!("); }}{}", CLASS_END),
+          ),
+          Some(Frame::Function(kind)) => {
+            let function = format!(
+              "{}function{} () {{ return {}",
+              if kind.is_async() { "async " } else { "" },
+              if kind.is_generator() { "*" } else { "" },
+              code
+            );
+            if in_class {
+              (
+                class(false, &format!("x = {}", function)),
+                format!("); }};{}", CLASS_END),
+              )
+            } else {
+              (format!("({}", function), "); })".to_owned())
+            }
+          }
+          Some(Frame::Method(kind)) => (
+            class(
+              false,
+              &format!("{}m() {{ return {}", modifiers(*kind), code),
+            ),
+            format!("); }}{}", CLASS_END),
+          ),
+          Some(Frame::Constructor(derived)) => (
+            class(*derived, &format!("constructor() {{ return {}", code)),
+            format!("); }}{}", CLASS_END),
+          ),
+          Some(Frame::Class(_)) | Some(Frame::Opaque) => return None,
+        }
+      }
+    };
+    Some(wrapper)
+  }
+}
+
+/// Gets the modifiers of a method of a kind, such as `async *`.
+fn modifiers(kind: FunctionKind) -> &'static str {
+  match (kind.is_async(), kind.is_generator()) {
+    (false, false) => "",
+    (false, true) => "*",
+    (true, false) => "async ",
+    (true, true) => "async *",
+  }
+}
+
+/// The visitor finding the functions with a body, and what the code around them is in.
+struct Functions<'ast, F> {
+  frames: Vec<Frame<'ast>>,
+  /// Whether the code being visited is strict, innermost last.
+  strict: Vec<bool>,
+  found: F,
+}
+
+impl<'ast, F> Functions<'ast, F>
+where
+  F: FnMut(&Found<'_, 'ast>) -> Flow,
+{
+  /// Creates the visitor, with whether the top level is strict and a function called with each
+  /// function found, which can stop the visit.
+  fn new(strict: bool, found: F) -> Self {
+    Self {
+      frames: Vec::new(),
+      strict: vec![strict],
+      found,
+    }
+  }
+
+  /// Calls the function with a function found, and visits it inside a frame.
+  fn function(
+    &mut self,
+    span: Option<Span>,
+    shape: Shape<'ast>,
+    frame: Option<Frame<'ast>>,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    let (parameters, body, strict) = match shape {
+      Shape::Declaration(decl) => (decl.parameters(), decl.body(), decl.is_strict()),
+      Shape::Expression(expr) | Shape::Method(expr, _) | Shape::Constructor(expr, _) => {
+        (expr.parameters(), expr.body(), expr.is_strict())
+      }
+      Shape::Arrow(arrow) => (arrow.params(), arrow.body(), arrow.is_strict()),
+    };
+    if let Some(span) = span {
+      (self.found)(&Found {
+        span,
+        body,
+        shape,
+        frames: &self.frames,
+        strict: *self
+          .strict
+          .last()
+          .expect("the strictness of the top level is known"),
+      })?;
+    }
+    let framed = frame.is_some();
+    self.frames.extend(frame);
+    self.strict.push(strict);
+    let flow = parameters
+      .iter()
+      .try_for_each(|parameter| self.visit_formal_parameter(parameter, path));
+    let flow = if flow.is_break() {
+      flow
+    } else {
+      self.visit_statements(body, path)
+    };
+    self.strict.pop();
+    if framed {
+      self.frames.pop();
+    }
+    flow
+  }
+
+  /// Visits the parts of a class.
+  fn class(&mut self, class: &'ast Class, path: &mut NodePath<'ast>) -> Flow {
+    if let Some(super_ref) = class.super_ref() {
+      self.visit_node(super_ref, path)?;
+    }
+    let derived = class.super_ref().is_some();
+    let private = class
+      .elements()
+      .iter()
+      .filter_map(|element| element.name()?.private_name())
+      .collect();
+    self.framed(Frame::Class(private), |this| {
+      if let Some(constructor) = class.constructor() {
+        let shape = Shape::Constructor(constructor, derived);
+        let frame = Frame::Constructor(derived);
+        this.function(constructor.span(), shape, Some(frame), path)?;
+      }
+      class
+        .elements()
+        .iter()
+        .try_for_each(|element| this.visit_class_element(element, path))
+    })
+  }
+
+  /// Visits a part inside a frame.
+  fn framed(&mut self, frame: Frame<'ast>, visit: impl FnOnce(&mut Self) -> Flow) -> Flow {
+    self.frames.push(frame);
+    let flow = visit(self);
+    self.frames.pop();
+    flow
+  }
+}
+
+impl<'ast, F> Visitor<'ast> for Functions<'ast, F>
+where
+  F: FnMut(&Found<'_, 'ast>) -> Flow,
+{
+  fn visit_function_decl(&mut self, decl: &'ast FunctionDecl, path: &mut NodePath<'ast>) -> Flow {
+    let frame = Frame::Function(decl.kind());
+    self.function(decl.span(), Shape::Declaration(decl), Some(frame), path)
+  }
+
+  fn visit_function_expr(&mut self, expr: &'ast FunctionExpr, path: &mut NodePath<'ast>) -> Flow {
+    let frame = Frame::Function(expr.kind());
+    self.function(expr.span(), Shape::Expression(expr), Some(frame), path)
+  }
+
+  fn visit_arrow_function_decl(
+    &mut self,
+    arrow: &'ast ArrowFunctionDecl,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    // Arrow functions share `super`, `arguments`, `yield` and `await` with the code around
+    // them, which their body can not be parsed again without.
+    let span = match self.frames.last() {
+      Some(Frame::Class(_)) | Some(Frame::Opaque) => None,
+      _ => arrow.span(),
+    };
+    self.function(span, Shape::Arrow(arrow), None, path)
+  }
+
+  fn visit_class(&mut self, class: &'ast Class, path: &mut NodePath<'ast>) -> Flow {
+    // All the parts of a class are strict mode code.
+    self.strict.push(true);
+    let flow = self.class(class, path);
+    self.strict.pop();
+    flow
+  }
+
+  fn visit_class_element(
+    &mut self,
+    element: &'ast ClassElement,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    if let Some(ClassElementName::PropertyName(name)) = element.name() {
+      self.visit_property_name(name, path)?;
+    }
+    match element {
+      ClassElement::Method { kind, function, .. } => {
+        let frame = Frame::Method(function.kind());
+        self.function(
+          function.span(),
+          Shape::Method(function, *kind),
+          Some(frame),
+          path,
+        )
+      }
+      ClassElement::Field { init, .. } => self.framed(Frame::Field, |this| match init {
+        Some(init) => this.visit_node(init, path),
+        None => ControlFlow::Continue(()),
+      }),
+      ClassElement::StaticBlock(body, _) => self.framed(Frame::StaticBlock, |this| {
+        this.visit_statements(body.items(), path)
+      }),
+    }
+  }
+
+  fn visit_formal_parameter(
+    &mut self,
+    parameter: &'ast FormalParameter,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    self.framed(Frame::Opaque, |this| {
+      visit::walk_formal_parameter(this, parameter, path)
+    })
+  }
+
+  fn visit_property_name(&mut self, name: &'ast PropertyName, path: &mut NodePath<'ast>) -> Flow {
+    self.framed(Frame::Opaque, |this| {
+      visit::walk_property_name(this, name, path)
+    })
+  }
+}
+
+/// A function found by the end of its span.
+#[derive(Debug)]
+struct Function<'a> {
+  /// The text going before the body, up to its opening brace.
+  head: String,
+  /// The text going after the body.
+  tail: String,
+  body: &'a [Node],
+}
+
+/// The visitor swapping the body of a function, found by the span of its body, with another.
+struct SwapBody {
+  /// The span of the body, braces included.
+  span: Span,
+  body: StatementList,
+  swapped: bool,
+}
+
+impl SwapBody {
+  fn new(span: Span, body: StatementList) -> Self {
+    Self {
+      span,
+      body,
+      swapped: false,
+    }
+  }
+
+  /// Swaps a body, if it is the one of the function looked for: the function ends with the
+  /// body, and starts before it, which a function inside the body does not.
+  fn swap(&mut self, span: Option<Span>, body: &mut StatementList) -> Flow {
+    let found =
+      span.is_some_and(|span| span.end() == self.span.end() && span.start() < self.span.start());
+    if !found {
+      return ControlFlow::Continue(());
+    }
+    mem::swap(body, &mut self.body);
+    self.swapped = true;
+    ControlFlow::Break(())
+  }
+}
+
+impl VisitorMut for SwapBody {
+  fn visit_function_decl(&mut self, decl: &mut FunctionDecl, path: &mut KindPath) -> Flow {
+    self.swap(decl.span(), decl.body_list_mut())?;
+    visit_mut::walk_function_decl(self, decl, path)
+  }
+
+  fn visit_function_expr(&mut self, expr: &mut FunctionExpr, path: &mut KindPath) -> Flow {
+    self.swap(expr.span(), expr.body_list_mut())?;
+    visit_mut::walk_function_expr(self, expr, path)
+  }
+
+  fn visit_arrow_function_decl(
+    &mut self,
+    arrow: &mut ArrowFunctionDecl,
+    path: &mut KindPath,
+  ) -> Flow {
+    self.swap(arrow.span(), arrow.body_list_mut())?;
+    visit_mut::walk_arrow_function_decl(self, arrow, path)
+  }
+}
+
+/// The visitor moving the spans of a tree with a function, which is given each start and end
+/// of a span, and whether it is an end.
+struct Respan<F>(F);
+
+impl<F> Respan<F>
+where
+  F: FnMut(Position, bool) -> Position,
+{
+  /// Moves a span. An empty span at an insertion moves after it.
+  fn span(&mut self, span: Option<Span>) -> Option<Span> {
+    span.map(|span| {
+      let start = (self.0)(span.start(), false);
+      Span::new(start, (self.0)(span.end(), true).max(start))
+    })
+  }
+
+  /// Moves the span of the name or the label of a node, which is not a node of its own.
+  fn name(&mut self, node: &mut Node) {
+    match node {
+      Node::FunctionDecl(decl) => {
+        let span = self.span(decl.name_span());
+        decl.set_name_span(span);
+      }
+      Node::Labelled(labelled) => {
+        let span = self.span(labelled.label_span());
+        labelled.set_label_span(span);
+      }
+      Node::Break(brk) => {
+        let span = self.span(brk.label_span());
+        brk.set_label_span(span);
+      }
+      Node::Continue(cont) => {
+        let span = self.span(cont.label_span());
+        cont.set_label_span(span);
+      }
+      _ => {}
+    }
+  }
+}
+
+impl<F> VisitorMut for Respan<F>
+where
+  F: FnMut(Position, bool) -> Position,
+{
+  fn enter(&mut self, node: NodeMut<'_>, _path: &KindPath) -> Control {
+    match node {
+      // Blocks, classes, functions, identifiers and template literals are also found out of
+      // nodes, they are moved when they are visited, once.
+      NodeMut::Node(Node::Block(_))
+      | NodeMut::Node(Node::ClassDecl(_))
+      | NodeMut::Node(Node::ClassExpr(_))
+      | NodeMut::Node(Node::FunctionExpr(_))
+      | NodeMut::Node(Node::Identifier(_))
+      | NodeMut::Node(Node::TemplateLit(_)) => {}
+      NodeMut::Node(node) => {
+        let span = self.span(node.span());
+        node.set_span(span);
+        self.name(node);
+      }
+      NodeMut::Declaration(decl) => {
+        let span = self.span(decl.span());
+        decl.set_span(span);
+      }
+      // The walk does not visit the declaration of a parameter.
+      NodeMut::FormalParameter(param) => {
+        let span = self.span(param.span());
+        param.set_span(span);
+        let span = self.span(param.declaration().span());
+        param.declaration_mut().set_span(span);
+      }
+      NodeMut::Pattern(pattern @ Pattern::Object(_))
+      | NodeMut::Pattern(pattern @ Pattern::Array(_)) => {
+        let span = self.span(pattern.span());
+        pattern.set_span(span);
+      }
+      NodeMut::PropertyDefinition(property) => {
+        let span = self.span(property.span());
+        property.set_span(span);
+        // The walk does not visit the identifier of a shorthand property.
+        if let PropertyDefinition::IdentifierReference(ident, _) = property {
+          let span = self.span(ident.span());
+          ident.set_span(span);
+        }
+      }
+      NodeMut::ClassElement(element) => {
+        let span = self.span(element.span());
+        element.set_span(span);
+      }
+      NodeMut::Case(case) => {
+        let span = self.span(case.span());
+        case.set_span(span);
+      }
+      NodeMut::Catch(catch) => {
+        let span = self.span(catch.span());
+        catch.set_span(span);
+      }
+      NodeMut::ExportSpecifier(specifier) => {
+        let span = self.span(specifier.local_span());
+        specifier.set_local_span(span);
+      }
+      _ => {}
+    }
+    Control::Continue
+  }
+
+  fn visit_block(&mut self, block: &mut Block, path: &mut KindPath) -> Flow {
+    let span = self.span(block.span());
+    block.set_span(span);
+    visit_mut::walk_block(self, block, path)
+  }
+
+  fn visit_class(&mut self, class: &mut Class, path: &mut KindPath) -> Flow {
+    let span = self.span(class.span());
+    class.set_span(span);
+    let span = self.span(class.name_span());
+    class.set_name_span(span);
+    let span = self.span(class.constructor_span());
+    class.set_constructor_span(span);
+    visit_mut::walk_class(self, class, path)
+  }
+
+  fn visit_function_expr(&mut self, expr: &mut FunctionExpr, path: &mut KindPath) -> Flow {
+    let span = self.span(expr.span());
+    expr.set_span(span);
+    let span = self.span(expr.name_span());
+    expr.set_name_span(span);
+    visit_mut::walk_function_expr(self, expr, path)
+  }
+
+  fn visit_identifier(&mut self, ident: &mut Identifier, _path: &mut KindPath) -> Flow {
+    let span = self.span(ident.span());
+    ident.set_span(span);
+    ControlFlow::Continue(())
+  }
+
+  fn visit_template_lit(&mut self, template: &mut TemplateLit, path: &mut KindPath) -> Flow {
+    let span = self.span(template.span());
+    template.set_span(span);
+    visit_mut::walk_template_lit(self, template, path)
+  }
+}
@@ -0,0 +1,604 @@
+//! Tests for incremental parsing, each edit checking that the document is the same as a fresh
+//! parse of its text.
+
+use super::{Document, Reparse, Target};
+use crate::syntax::{
+  ast::{
+    node::{Class, FunctionExpr, Identifier, PropertyDefinition, StatementList, TemplateLit},
+    visitor::{visit, Control, Flow, NodePath, NodeRef, Path, Visitor},
+    Node, NodeId, Position, Span,
+  },
+  edit::Edit,
+  parser::Parser,
+};
+
+/// Parses a script or a module.
+#[track_caller]
+fn document(js: &str, module: bool) -> Document {
+  let document = if module {
+    Document::parse_module(js)
+  } else {
+    Document::parse_script(js)
+  };
+  document.unwrap_or_else(|err| panic!("failed to parse `{}`: {}", js, err))
+}
+
+/// Gets the regions of a document.
+fn regions(document: &Document) -> Vec<(usize, usize, usize, usize, Target)> {
+  document
+    .regions
+    .iter()
+    .map(|region| {
+      (
+        region.start,
+        region.end,
+        region.inner_start,
+        region.inner_end,
+        region.target,
+      )
+    })
+    .collect()
+}
+
+/// Checks that the tree of a document is the one of a fresh parse of its text.
+#[track_caller]
+fn check_tree(document: &Document) -> Document {
+  assert!(document.is_up_to_date());
+  let fresh = self::document(document.text(), document.is_module());
+  assert_eq!(
+    document.statements(),
+    fresh.statements(),
+    "the tree of `{}` is not the one of a fresh parse",
+    document.text()
+  );
+  assert_eq!(document.module(), fresh.module());
+  assert_eq!(
+    spans(document.statements()),
+    spans(fresh.statements()),
+    "the spans of `{}` are not the ones of a fresh parse",
+    document.text()
+  );
+  assert!(
+    printed(document.statements()) == printed(fresh.statements()),
+    "the tree of `{}` is not the one of a fresh parse, spans included",
+    document.text()
+  );
+  fresh
+}
+
+/// Prints a tree with the spans that comparisons ignore, and without the ids of its nodes,
+/// which differ from one parse to another.
+fn printed(statements: &StatementList) -> String {
+  let printed = format!("{:?}", statements);
+  let mut parts = printed.split("NodeId(");
+  let mut without_ids = parts.next().unwrap_or_default().to_owned();
+  for part in parts {
+    without_ids.push_str("NodeId(");
+    without_ids.push_str(part.trim_start_matches(|c: char| c.is_ascii_digit()));
+  }
+  without_ids
+}
+
+/// Gets the spans of the nodes of a tree and of the names in them, in the order they are
+/// visited.
+fn spans(statements: &StatementList) -> Vec<Option<Span>> {
+  struct Spans(Vec<Option<Span>>);
+
+  impl<'ast> Visitor<'ast> for Spans {
+    fn enter(&mut self, node: NodeRef<'ast>, _path: &NodePath<'ast>) -> Control {
+      match node {
+        NodeRef::Node(node) => {
+          self.0.push(node.span());
+          match node {
+            Node::FunctionDecl(decl) => self.0.push(decl.name_span()),
+            Node::Labelled(labelled) => self.0.push(labelled.label_span()),
+            Node::Break(brk) => self.0.push(brk.label_span()),
+            Node::Continue(cont) => self.0.push(cont.label_span()),
+            _ => {}
+          }
+        }
+        NodeRef::Declaration(decl) => self.0.push(decl.span()),
+        // The walk does not visit the declaration of a parameter.
+        NodeRef::FormalParameter(param) => {
+          self.0.push(param.span());
+          self.0.push(param.declaration().span());
+        }
+        NodeRef::Pattern(pattern) => self.0.push(pattern.span()),
+        NodeRef::PropertyDefinition(property) => {
+          self.0.push(property.span());
+          if let PropertyDefinition::IdentifierReference(ident, _) = property {
+            self.0.push(ident.span());
+          }
+        }
+        NodeRef::ClassElement(element) => self.0.push(element.span()),
+        NodeRef::Case(case) => self.0.push(case.span()),
+        NodeRef::Catch(catch) => self.0.push(catch.span()),
+        NodeRef::ExportSpecifier(specifier) => self.0.push(specifier.local_span()),
+        _ => {}
+      }
+      Control::Continue
+    }
+
+    fn visit_identifier(&mut self, ident: &'ast Identifier, _path: &mut NodePath<'ast>) -> Flow {
+      self.0.push(ident.span());
+      Flow::Continue(())
+    }
+
+    fn visit_function_expr(&mut self, expr: &'ast FunctionExpr, path: &mut NodePath<'ast>) -> Flow {
+      self.0.push(expr.span());
+      self.0.push(expr.name_span());
+      visit::walk_function_expr(self, expr, path)
+    }
+
+    fn visit_class(&mut self, class: &'ast Class, path: &mut NodePath<'ast>) -> Flow {
+      self.0.push(class.span());
+      self.0.push(class.name_span());
+      self.0.push(class.constructor_span());
+      visit::walk_class(self, class, path)
+    }
+
+    fn visit_template_lit(
+      &mut self,
+      template: &'ast TemplateLit,
+      path: &mut NodePath<'ast>,
+    ) -> Flow {
+      self.0.push(template.span());
+      visit::walk_template_lit(self, template, path)
+    }
+  }
+
+  let mut spans = Spans(Vec::new());
+  let _ = spans.visit_statements(statements.items(), &mut Path::new());
+  spans.0
+}
+
+/// Checks that a document is the same as a fresh parse of its text, down to where its
+/// functions and statements are.
+#[track_caller]
+fn check(document: &Document) {
+  let fresh = check_tree(document);
+  assert_eq!(
+    regions(document),
+    regions(&fresh),
+    "the regions of `{}` are not the ones of a fresh parse",
+    document.text()
+  );
+}
+
+/// Replaces the text between two positions, given as lines and columns, and checks the result.
+#[track_caller]
+fn edit(document: &mut Document, start: (u32, u32), end: (u32, u32), text: &str) -> Reparse {
+  let span = Span::new(Position::new(start.0, start.1), Position::new(end.0, end.1));
+  let reparse = document
+    .edit(&Edit::new(span, text))
+    .unwrap_or_else(|err| panic!("failed to parse `{}`: {}", document.text(), err));
+  check(document);
+  reparse
+}
+
+/// Gets the span of a body, from the positions of its braces.
+fn body(open: (u32, u32), close: (u32, u32)) -> Reparse {
+  Reparse::Function(Span::new(
+    Position::new(open.0, open.1),
+    Position::new(close.0, close.1 + 1),
+  ))
+}
+
+/// Gets the id of a function declaration, which a fresh parse of it does not keep.
+fn function_id(statement: &Node) -> NodeId {
+  match statement {
+    Node::FunctionDecl(function) => function.id(),
+    _ => panic!("expected a function declaration"),
+  }
+}
+
+#[test]
+fn function_bodies() {
+  let mut document = document(
+    "function outer(a) {\n  function inner(b = 1) {\n    return b;\n  }\n  return a;\n}\n\
+     function other() {\n  log();\n}\nconst f = async (x) => {\n  await x;\n};\n",
+    false,
+  );
+  check(&document);
+  let other = function_id(&document.statements().items()[1]);
+
+  assert_eq!(
+    edit(&mut document, (3, 12), (3, 13), "b * 2"),
+    body((2, 25), (4, 3))
+  );
+  assert_eq!(
+    edit(&mut document, (5, 10), (5, 11), "inner(a)"),
+    body((1, 19), (6, 1))
+  );
+  // An edit at the start of a body, then at its end.
+  assert_eq!(
+    edit(&mut document, (1, 20), (1, 20), " let c = 1;"),
+    body((1, 19), (6, 1))
+  );
+  assert_eq!(
+    edit(&mut document, (6, 1), (6, 1), "  c++;\n"),
+    body((1, 19), (7, 1))
+  );
+  assert_eq!(
+    edit(&mut document, (12, 3), (12, 10), "await f(x);\n  yield;"),
+    body((11, 24), (14, 1))
+  );
+  assert_eq!(
+    document.text(),
+    "function outer(a) { let c = 1;\n  function inner(b = 1) {\n    return b * 2;\n  }\n  \
+     return inner(a);\n  c++;\n}\nfunction other() {\n  log();\n}\nconst f = async (x) => {\n  \
+     await f(x);\n  yield;;\n};\n"
+  );
+  // The functions that were not edited are moved over.
+  assert_eq!(function_id(&document.statements().items()[1]), other);
+}
+
+#[test]
+fn class_bodies() {
+  let mut document = document(
+    "class A extends B {\n  #x = 1;\n  constructor(a) {\n    super(a);\n  }\n  get x() {\n    \
+     return this.#x;\n  }\n  static {\n    const f = () => {\n      return 1;\n    };\n  }\n  \
+     y = () => {\n    return super.y;\n  };\n  async *m() {\n    const g = function () {\n      \
+     return arguments;\n    };\n    yield await g;\n  }\n}\n",
+    false,
+  );
+  check(&document);
+
+  // Constructors, accessors and methods.
+  assert_eq!(
+    edit(&mut document, (4, 11), (4, 12), "a, 1"),
+    body((3, 18), (5, 3))
+  );
+  assert_eq!(
+    edit(&mut document, (7, 17), (7, 19), "#x + 1"),
+    body((6, 11), (8, 3))
+  );
+  assert_eq!(
+    edit(&mut document, (21, 17), (21, 18), "g()"),
+    body((17, 14), (22, 3))
+  );
+  // Arrow functions in static blocks and fields, and functions in methods.
+  assert_eq!(
+    edit(&mut document, (11, 14), (11, 15), "this.#x"),
+    body((10, 21), (12, 5))
+  );
+  assert_eq!(
+    edit(&mut document, (15, 18), (15, 19), "z"),
+    body((14, 13), (16, 3))
+  );
+  assert_eq!(
+    edit(&mut document, (19, 14), (19, 23), "arguments[0]"),
+    body((18, 27), (20, 5))
+  );
+}
+
+#[test]
+fn class_contexts() {
+  let mut document = document(
+    "class A extends B {\n  constructor() {\n    const f = () => {\n      return 1;\n    };\n  \
+     }\n  m() {\n    const g = function () {\n      return 2;\n    };\n    return () => {\n      \
+     return 3;\n    };\n  }\n}\n",
+    false,
+  );
+  check(&document);
+
+  // An arrow function calls the constructor of the superclass as the constructor around it.
+  assert_eq!(
+    edit(&mut document, (4, 14), (4, 15), "super()"),
+    body((3, 21), (5, 5))
+  );
+  // A function in a method can not use the superclass, but an arrow function can.
+  let span = Span::new(Position::new(9, 14), Position::new(9, 15));
+  assert!(document.edit(&Edit::new(span, "super.x")).is_err());
+  assert_eq!(edit(&mut document, (9, 14), (9, 21), "2"), Reparse::Full);
+  assert_eq!(
+    edit(&mut document, (12, 14), (12, 15), "super.x"),
+    body((11, 18), (13, 5))
+  );
+  let span = Span::new(Position::new(12, 14), Position::new(12, 21));
+  assert!(document.edit(&Edit::new(span, "super()")).is_err());
+  assert_eq!(edit(&mut document, (12, 14), (12, 21), "3"), Reparse::Full);
+  // Private names must be declared by a class around them.
+  let span = Span::new(Position::new(12, 14), Position::new(12, 15));
+  assert!(document.edit(&Edit::new(span, "this.#y")).is_err());
+  assert_eq!(edit(&mut document, (12, 14), (12, 21), "3"), Reparse::Full);
+  // Class bodies are strict mode code, where `yield` is reserved.
+  let span = Span::new(Position::new(4, 14), Position::new(4, 21));
+  assert!(document.edit(&Edit::new(span, "yield")).is_err());
+  assert_eq!(edit(&mut document, (4, 14), (4, 19), "1"), Reparse::Full);
+  // The function in the method is not async.
+  let span = Span::new(Position::new(9, 14), Position::new(9, 15));
+  assert!(document.edit(&Edit::new(span, "await g")).is_err());
+  assert_eq!(edit(&mut document, (9, 14), (9, 21), "2"), Reparse::Full);
+}
+
+#[test]
+fn names() {
+  let mut document = document(
+    "log(1);\nfunction f(a, { b } = {}) {\n  outer: for (;;) {\n    break outer;\n  }\n}\n\
+     const C = class D {};\nconst g = function h() {};\n",
+    false,
+  );
+  check(&document);
+
+  // The names, the labels and the parameters after an edit are moved over it.
+  assert_eq!(
+    edit(&mut document, (1, 5), (1, 6), "12345"),
+    Reparse::Statement(0)
+  );
+  assert_eq!(
+    edit(&mut document, (1, 5), (1, 10), "1,\n  2"),
+    Reparse::Statement(0)
+  );
+  assert_ne!(
+    edit(&mut document, (5, 5), (5, 5), "continue outer;\n    "),
+    Reparse::Full
+  );
+  assert_ne!(edit(&mut document, (8, 1), (8, 1), "\n"), Reparse::Full);
+}
+
+#[test]
+fn element_spans() {
+  let js =
+    "log(1);\nconst s = tag`a${x}b`;\nconst o = { a, b: 1, get c() {\n  return 2;\n}, ...d };\n\
+            class E {\n  constructor() {}\n  f = 1;\n  static {}\n}\n";
+  for module in [false, true] {
+    let js = if module {
+      format!("{}export {{ s, o as p }};\n", js)
+    } else {
+      js.to_owned()
+    };
+    let mut document = document(&js, module);
+    check(&document);
+
+    // The template of a tagged template, the properties, the class elements, the constructor
+    // and the exported names after an edit are moved over it.
+    assert_eq!(
+      edit(&mut document, (1, 5), (1, 6), "1,\n  2"),
+      Reparse::Statement(0)
+    );
+    assert_eq!(
+      edit(&mut document, (1, 7), (2, 3), ""),
+      Reparse::Statement(0)
+    );
+  }
+}
+
+#[test]
+fn statements() {
+  let mut document = document(
+    "let a = 1;\nif (a) {\n  log(a);\n}\nfunction f() { return a; }\nlet b = [a, 2];\n",
+    false,
+  );
+  check(&document);
+  let function = function_id(&document.statements().items()[2]);
+
+  assert_eq!(
+    edit(&mut document, (3, 7), (3, 8), "a + 1"),
+    Reparse::Statement(1)
+  );
+  assert_eq!(
+    edit(&mut document, (6, 10), (6, 13), ", 3, 4"),
+    Reparse::Statement(3)
+  );
+  // Declaring another name changes the scope of the script.
+  assert_eq!(edit(&mut document, (1, 5), (1, 6), "c"), Reparse::Full);
+  assert_eq!(edit(&mut document, (1, 5), (1, 6), "a"), Reparse::Full);
+  // So does declaring the same name twice, which is an error.
+  let span = Span::new(Position::new(6, 5), Position::new(6, 6));
+  assert!(document.edit(&Edit::new(span, "a")).is_err());
+  assert!(!document.is_up_to_date());
+  assert_eq!(edit(&mut document, (6, 5), (6, 6), "b"), Reparse::Full);
+  assert_eq!(
+    edit(&mut document, (2, 5), (2, 6), "a && b"),
+    Reparse::Statement(1)
+  );
+  assert_ne!(function_id(&document.statements().items()[2]), function);
+}
+
+#[test]
+fn boundaries() {
+  let mut document = document(
+    "if (a) {}\n[1, 2].map(f)\nfunction g() { return 1 }\n",
+    false,
+  );
+  check(&document);
+  // The array could continue the first statement if it ended with an expression.
+  assert_eq!(edit(&mut document, (1, 5), (1, 6), "b"), Reparse::Full);
+  assert_eq!(
+    edit(&mut document, (2, 12), (2, 13), "g"),
+    Reparse::Statement(1)
+  );
+  // An edit replacing the first token of a statement, or the braces of a body.
+  assert_eq!(edit(&mut document, (1, 1), (1, 3), "while"), Reparse::Full);
+  assert_eq!(
+    edit(&mut document, (3, 14), (3, 26), "{ return 2; }"),
+    Reparse::Full
+  );
+  assert_eq!(
+    edit(&mut document, (3, 16), (3, 25), "return 3;"),
+    body((3, 14), (3, 26))
+  );
+  // An edit closing the body early does not parse inside the function.
+  let span = Span::new(Position::new(3, 25), Position::new(3, 25));
+  assert!(document.edit(&Edit::new(span, " }")).is_err());
+  assert_eq!(edit(&mut document, (3, 25), (3, 27), ""), Reparse::Full);
+  let span = Span::new(Position::new(3, 16), Position::new(3, 16));
+  assert!(document
+    .edit(&Edit::new(span, "}) + (function() {"))
+    .is_err());
+}
+
+#[test]
+fn strictness() {
+  let mut document = document(
+    "'use strict';\nfunction f(a) {\n  return a;\n}\nlet b = f(1);\n",
+    false,
+  );
+  check(&document);
+  // Deleting a variable is not allowed in strict mode code, around the function or at the top level.
+  let span = Span::new(Position::new(3, 3), Position::new(3, 3));
+  assert!(document.edit(&Edit::new(span, "delete a;\n  ")).is_err());
+  assert_eq!(edit(&mut document, (3, 3), (4, 3), ""), Reparse::Full);
+  let span = Span::new(Position::new(5, 9), Position::new(5, 13));
+  assert!(document
+    .edit(&Edit::new(span, "function() { delete b; }"))
+    .is_err());
+  assert_eq!(edit(&mut document, (5, 9), (5, 33), "f(2)"), Reparse::Full);
+  assert_eq!(
+    edit(&mut document, (5, 11), (5, 12), "3"),
+    Reparse::Statement(2)
+  );
+  // Editing the directive makes the script sloppy.
+  assert_eq!(
+    edit(&mut document, (1, 2), (1, 12), "use sloppy"),
+    Reparse::Full
+  );
+  assert!(!document.statements().strict());
+  assert_eq!(
+    edit(&mut document, (3, 3), (3, 3), "delete a;\n  "),
+    body((2, 15), (5, 1))
+  );
+}
+
+#[test]
+fn modules() {
+  let mut document = document(
+    "import { x } from './x.js';\nexport function f() {\n  return x;\n}\nconst y = f();\n\
+     export { y };\n",
+    true,
+  );
+  check(&document);
+  assert_eq!(
+    edit(&mut document, (3, 10), (3, 11), "x + 1"),
+    body((2, 21), (4, 1))
+  );
+  assert_eq!(
+    edit(&mut document, (5, 11), (5, 14), "f() * 2"),
+    Reparse::Statement(2)
+  );
+  // Imports and exports are used by the other statements.
+  assert_eq!(edit(&mut document, (1, 10), (1, 11), "x, z"), Reparse::Full);
+  assert_eq!(
+    edit(&mut document, (6, 10), (6, 11), "y as z"),
+    Reparse::Full
+  );
+  // Modules are strict mode code.
+  let span = Span::new(Position::new(3, 3), Position::new(3, 3));
+  assert!(document.edit(&Edit::new(span, "delete x;\n  ")).is_err());
+  assert_eq!(edit(&mut document, (3, 3), (4, 3), ""), Reparse::Full);
+}
+
+/// A generator of pseudo random numbers, so that the test is the same on each run.
+struct Random(u64);
+
+impl Random {
+  /// Gets a number lower than `max`.
+  fn below(&mut self, max: usize) -> usize {
+    self.0 ^= self.0 << 13;
+    self.0 ^= self.0 >> 7;
+    self.0 ^= self.0 << 17;
+    (self.0 % max as u64) as usize
+  }
+}
+
+/// Gets the position of a byte offset in an ASCII text.
+fn position(text: &str, offset: usize) -> Position {
+  let before = &text[..offset];
+  let line = before.matches('\n').count() + 1;
+  let column = offset - before.rfind('\n').map_or(0, |index| index + 1) + 1;
+  Position::new(line as u32, column as u32)
+}
+
+#[test]
+fn random_edits() {
+  const INSERTED: &[&str] = &[
+    "x",
+    "1",
+    ";",
+    "\n",
+    " + y",
+    "(",
+    ")",
+    "{",
+    "}",
+    "[0]",
+    "let z = 2;",
+    "return;",
+    "yield",
+    "await",
+    "=>",
+    ",",
+    "function h() { return 1 }",
+    "'use strict';",
+    "() => {}",
+    "`t`",
+    "super.x",
+    "super()",
+    "this.#count",
+    "#other",
+    "arguments",
+  ];
+  let js = "'use strict';\nlet total = 0;\nfunction add(a, b = 1) {\n  const sum = a + b;\n  \
+            if (sum > 10) {\n    return sum;\n  }\n  return function () {\n    \
+            return [sum, total];\n  };\n}\nconst list = [1, 2, 3].map((n) => {\n  \
+            return add(n);\n});\nfunction* numbers() {\n  yield 1;\n}\nasync function main() {\n  \
+            await add(total);\n}\nclass Counter extends Object {\n  #count = 0;\n  static {\n    \
+            total++;\n  }\n  constructor() {\n    super();\n  }\n  get count() {\n    \
+            return this.#count;\n  }\n  increment = () => {\n    this.#count += 1;\n  };\n}\n\
+            const point = { total, x: 1, get y() {\n  return String.raw`a${total}b`;\n} };\n\
+            total += list.length;\n";
+
+  for module in [false, true] {
+    let mut document = document(js, module);
+    let mut random = Random(0x2545_f491_4f6c_dd1d);
+    let mut incremental = 0;
+    for _ in 0..400 {
+      let text = document.text().to_owned();
+      let start = random.below(text.len() + 1);
+      let end = (start + random.below(4)).min(text.len());
+      let inserted = if random.below(3) == 0 {
+        ""
+      } else {
+        INSERTED[random.below(INSERTED.len())]
+      };
+      let span = Span::new(position(&text, start), position(&text, end));
+
+      let mut expected = text.clone();
+      expected.replace_range(start..end, inserted);
+      let fresh = if module {
+        Parser::new(expected.as_bytes()).parse_module().map(|_| ())
+      } else {
+        Parser::new(expected.as_bytes()).parse_script().map(|_| ())
+      };
+      let result = document.edit(&Edit::new(span, inserted));
+      assert_eq!(document.text(), expected);
+      assert_eq!(
+        result.is_ok(),
+        fresh.is_ok(),
+        "the edit of `{}` into `{}` has a different result",
+        text,
+        expected
+      );
+      match result {
+        Ok(reparse) => {
+          check_tree(&document);
+          if reparse != Reparse::Full {
+            incremental += 1;
+          }
+        }
+        // Undoes the edit, which parses the whole text again.
+        Err(_) => {
+          let span = Span::new(
+            position(&expected, start),
+            position(&expected, start + inserted.len()),
+          );
+          let reparse = document.edit(&Edit::new(span, &text[start..end]));
+          assert!(matches!(reparse, Ok(Reparse::Full)));
+          check_tree(&document);
+        }
+      }
+    }
+    assert!(incremental > 50, "only {} incremental edits", incremental);
+  }
+}
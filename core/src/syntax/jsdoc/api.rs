@@ -0,0 +1,823 @@
+//! Documentation of the exported API of a module.
+
+use super::{JsDoc, Tag};
+use crate::syntax::{
+  ast::{
+    module::Module,
+    node::{
+      ArrowFunctionDecl, Class, ClassElement, ClassElementName, ExportDecl, FunctionExpr,
+      FunctionKind, MethodDefinitionKind, Object, PropertyDefinition, PropertyName,
+    },
+    Node, NodeSpan,
+  },
+  codegen::{Generator, Options},
+  comments::{CommentMap, Commented},
+  json,
+};
+use std::{collections::HashMap, fmt::Write};
+
+/// The line width of the signatures, wide enough to print them on a single line.
+const SIGNATURE_WIDTH: usize = 1000;
+
+/// What an exported name is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ItemKind {
+  /// A function declaration, or a variable initialized with a function.
+  Function,
+  /// A class declaration.
+  Class,
+  /// A variable declared with `var`, `let` or `const`.
+  Variable,
+  /// A default exported expression.
+  Value,
+  /// A name re-exported from another module.
+  ReExport,
+}
+
+impl ItemKind {
+  /// Gets the name of the kind in the JSON output.
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::Function => "function",
+      Self::Class => "class",
+      Self::Variable => "variable",
+      Self::Value => "value",
+      Self::ReExport => "re-export",
+    }
+  }
+}
+
+/// What a member of a class or an object literal is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MemberKind {
+  /// The constructor of a class.
+  Constructor,
+  /// A method, or a property of an object literal initialized with a function.
+  Method,
+  /// A getter.
+  Getter,
+  /// A setter.
+  Setter,
+  /// A field of a class.
+  Field,
+  /// A property of an object literal.
+  Property,
+}
+
+impl MemberKind {
+  /// Gets the name of the kind in the JSON output.
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::Constructor => "constructor",
+      Self::Method => "method",
+      Self::Getter => "getter",
+      Self::Setter => "setter",
+      Self::Field => "field",
+      Self::Property => "property",
+    }
+  }
+}
+
+/// A public member of an exported class or object literal, and its documentation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+  name: Box<str>,
+  kind: MemberKind,
+  is_static: bool,
+  signature: Box<str>,
+  doc: Option<JsDoc>,
+}
+
+impl Member {
+  /// Gets the name of the member, in brackets when it is computed, such as `[Symbol.iterator]`.
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Gets what the member is.
+  pub fn kind(&self) -> MemberKind {
+    self.kind
+  }
+
+  /// Returns `true` for a static member of a class.
+  pub fn is_static(&self) -> bool {
+    self.is_static
+  }
+
+  /// Gets the declaration of the member without its body, such as `static get size()`.
+  pub fn signature(&self) -> &str {
+    &self.signature
+  }
+
+  /// Gets the documentation comment of the member.
+  pub fn doc(&self) -> Option<&JsDoc> {
+    self.doc.as_ref()
+  }
+}
+
+/// An exported name and its documentation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Item {
+  name: Box<str>,
+  kind: ItemKind,
+  signature: Option<Box<str>>,
+  source: Option<Box<str>>,
+  doc: Option<JsDoc>,
+  members: Vec<Member>,
+}
+
+impl Item {
+  /// Gets the name importing modules use, `default` for the default export, or `*` for the
+  /// names of a star re-export.
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Gets what the name is.
+  pub fn kind(&self) -> ItemKind {
+    self.kind
+  }
+
+  /// Gets the declaration of the name without its body, such as `function add(a, b)`.
+  pub fn signature(&self) -> Option<&str> {
+    self.signature.as_deref()
+  }
+
+  /// Gets the module a re-exported name comes from.
+  pub fn source(&self) -> Option<&str> {
+    self.source.as_deref()
+  }
+
+  /// Gets the documentation comment of the name.
+  pub fn doc(&self) -> Option<&JsDoc> {
+    self.doc.as_ref()
+  }
+
+  /// Gets the public members of an exported class or object literal, the constructor first and
+  /// the others in order.
+  pub fn members(&self) -> &[Member] {
+    &self.members
+  }
+}
+
+/// The documentation of the exported API of a module, in the order of the exports.
+///
+/// The documentation of a name is the `/** */` comment before its `export` statement, or before
+/// its declaration if it is exported by an export list. The members of exported classes and
+/// object literals are documented by the comments before them.
+///
+/// ```
+/// use plastic::syntax::{comments::CommentMap, jsdoc::ModuleDocs, parser::Parser};
+///
+/// let source = "/** Adds two numbers. */\nexport function add(a, b) { return a + b; }\n";
+/// let module = Parser::new(source.as_bytes()).parse_module().unwrap();
+/// let docs = ModuleDocs::new(&module, &CommentMap::module(source, &module));
+/// assert_eq!(
+///   docs.to_markdown(),
+///   "## `add`\n\n```js\nfunction add(a, b)\n```\n\nAdds two numbers.\n"
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModuleDocs {
+  items: Vec<Item>,
+}
+
+impl ModuleDocs {
+  /// Documents the exports of a module, with the comments of its source.
+  pub fn new(module: &Module, comments: &CommentMap<'_>) -> Self {
+    let jsdoc = |statement: &Node| doc(comments, statement);
+
+    // The declarations of the top level, by name.
+    let mut locals = HashMap::new();
+    for statement in module.body().items() {
+      let declaration = match statement {
+        Node::Export(ExportDecl::Declaration(declaration), _) => declaration,
+        _ => statement,
+      };
+      for item in declared(declaration, comments) {
+        locals.insert(item.name.clone(), (item, statement));
+      }
+    }
+
+    let mut items = Vec::new();
+    for statement in module.body().items() {
+      let decl = match statement {
+        Node::Export(decl, _) => decl,
+        _ => continue,
+      };
+      let doc = jsdoc(statement);
+      match decl {
+        ExportDecl::Declaration(declaration) => {
+          for item in declared(declaration, comments) {
+            items.push(Item {
+              doc: doc.clone(),
+              ..item
+            });
+          }
+        }
+        ExportDecl::DefaultFunction(function) => items.push(Item {
+          name: "default".into(),
+          kind: ItemKind::Function,
+          signature: Some(function_signature(function)),
+          source: None,
+          doc,
+          members: Vec::new(),
+        }),
+        ExportDecl::DefaultClass(class) => items.push(Item {
+          name: "default".into(),
+          kind: ItemKind::Class,
+          signature: Some(class_signature(class)),
+          source: None,
+          doc,
+          members: class_members(class, comments),
+        }),
+        ExportDecl::DefaultExpression(expr) => {
+          let local = match &**expr {
+            Node::Identifier(ident) => locals.get(ident.name().as_str()),
+            _ => None,
+          };
+          items.push(match local {
+            Some((item, declaration)) => Item {
+              name: "default".into(),
+              doc: doc.or_else(|| jsdoc(declaration)),
+              ..item.clone()
+            },
+            None => Item {
+              name: "default".into(),
+              kind: ItemKind::Value,
+              signature: None,
+              source: None,
+              doc,
+              members: value_members(expr, comments),
+            },
+          });
+        }
+        ExportDecl::Named {
+          specifiers,
+          source: Some(source),
+          ..
+        } => {
+          for specifier in specifiers.iter() {
+            items.push(Item {
+              name: specifier.exported().into(),
+              kind: ItemKind::ReExport,
+              signature: None,
+              source: Some(source.clone()),
+              doc: doc.clone(),
+              members: Vec::new(),
+            });
+          }
+        }
+        ExportDecl::Named {
+          specifiers,
+          source: None,
+          ..
+        } => {
+          for specifier in specifiers.iter() {
+            let import = module
+              .summary()
+              .imports()
+              .iter()
+              .find(|import| import.local_name() == specifier.local());
+            let item = match (locals.get(specifier.local().as_str()), import) {
+              (Some((item, declaration)), _) => Item {
+                name: specifier.exported().into(),
+                doc: doc.clone().or_else(|| jsdoc(declaration)),
+                ..item.clone()
+              },
+              (None, Some(import)) => Item {
+                name: specifier.exported().into(),
+                kind: ItemKind::ReExport,
+                signature: None,
+                source: Some(import.module_request().into()),
+                doc: doc.clone(),
+                members: Vec::new(),
+              },
+              (None, None) => Item {
+                name: specifier.exported().into(),
+                kind: ItemKind::Variable,
+                signature: None,
+                source: None,
+                doc: doc.clone(),
+                members: Vec::new(),
+              },
+            };
+            items.push(item);
+          }
+        }
+        ExportDecl::All { alias, source, .. } => items.push(Item {
+          name: alias.as_deref().unwrap_or("*").into(),
+          kind: ItemKind::ReExport,
+          signature: None,
+          source: Some(source.clone()),
+          doc,
+          members: Vec::new(),
+        }),
+      }
+    }
+    Self { items }
+  }
+
+  /// Gets the exported names, in order.
+  pub fn items(&self) -> &[Item] {
+    &self.items
+  }
+
+  /// Writes the documentation as Markdown, a section for each exported name, and a subsection
+  /// for each of its members, named `Name#member` for the members of instances and
+  /// `Name.member` for the others.
+  pub fn to_markdown(&self) -> String {
+    let mut sections = Vec::new();
+    for item in &self.items {
+      let mut out = format!("## `{}`\n", item.name);
+      if let Some(signature) = &item.signature {
+        let _ = write!(out, "\n```js\n{}\n```\n", signature);
+      }
+      if let Some(source) = &item.source {
+        let _ = write!(out, "\nRe-exported from `{}`.\n", source);
+      }
+      if let Some(doc) = &item.doc {
+        markdown_doc(doc, &mut out);
+      }
+      for member in &item.members {
+        let separator = match (item.kind, member.is_static) {
+          (ItemKind::Class, false) => '#',
+          _ => '.',
+        };
+        let _ = write!(
+          out,
+          "\n### `{}{}{}`\n\n```js\n{}\n```\n",
+          item.name, separator, member.name, member.signature
+        );
+        if let Some(doc) = &member.doc {
+          markdown_doc(doc, &mut out);
+        }
+      }
+      sections.push(out);
+    }
+    sections.join("\n")
+  }
+
+  /// Writes the documentation as a JSON object, with an `items` array of the exported names.
+  pub fn to_json(&self) -> String {
+    let mut out = String::from("{\"items\":[");
+    for (index, item) in self.items.iter().enumerate() {
+      if index > 0 {
+        out.push(',');
+      }
+      out.push_str("{\"name\":");
+      json::write_string(&item.name, &mut out);
+      out.push_str(",\"kind\":");
+      json::write_string(item.kind.as_str(), &mut out);
+      out.push_str(",\"signature\":");
+      json_option(item.signature.as_deref(), &mut out);
+      out.push_str(",\"source\":");
+      json_option(item.source.as_deref(), &mut out);
+      out.push_str(",\"doc\":");
+      json_option_doc(item.doc.as_ref(), &mut out);
+      out.push_str(",\"members\":[");
+      for (index, member) in item.members.iter().enumerate() {
+        if index > 0 {
+          out.push(',');
+        }
+        out.push_str("{\"name\":");
+        json::write_string(&member.name, &mut out);
+        out.push_str(",\"kind\":");
+        json::write_string(member.kind.as_str(), &mut out);
+        let _ = write!(out, ",\"static\":{}", member.is_static);
+        out.push_str(",\"signature\":");
+        json::write_string(&member.signature, &mut out);
+        out.push_str(",\"doc\":");
+        json_option_doc(member.doc.as_ref(), &mut out);
+        out.push('}');
+      }
+      out.push_str("]}");
+    }
+    out.push_str("]}");
+    out
+  }
+}
+
+/// Gets the documentation comment of a statement or a member.
+fn doc<T: Commented>(comments: &CommentMap<'_>, part: &T) -> Option<JsDoc> {
+  comments
+    .jsdoc(part)
+    .map(|comment| JsDoc::parse(comment.text()))
+}
+
+/// Gets the names a declaration statement declares, with what they are, their signature and
+/// their members, without their documentation.
+fn declared(statement: &Node, comments: &CommentMap<'_>) -> Vec<Item> {
+  let item = |name: &str, kind, signature, members| Item {
+    name: name.into(),
+    kind,
+    signature: Some(signature),
+    source: None,
+    doc: None,
+    members,
+  };
+  match statement {
+    Node::FunctionDecl(decl) => {
+      let function = FunctionExpr::new(
+        Some(decl.name().into()),
+        decl.parameters(),
+        Vec::<Node>::new(),
+      )
+      .with_kind(decl.kind());
+      vec![item(
+        decl.name(),
+        ItemKind::Function,
+        function_signature(&function),
+        Vec::new(),
+      )]
+    }
+    Node::ClassDecl(class) => match class.name() {
+      Some(name) => vec![item(
+        name,
+        ItemKind::Class,
+        class_signature(class),
+        class_members(class, comments),
+      )],
+      None => Vec::new(),
+    },
+    Node::DeclarationList(list, _) => list
+      .as_ref()
+      .iter()
+      .flat_map(|declaration| {
+        let names = declaration.target().bound_names();
+        // A single name initialized with a function is documented as a function.
+        let function = match (&names[..], declaration.init()) {
+          (&[name], Some(Node::ArrowFunctionDecl(arrow))) => {
+            let arrow = if arrow.is_async() {
+              ArrowFunctionDecl::new_async(arrow.params(), Vec::<Node>::new())
+            } else {
+              ArrowFunctionDecl::new(arrow.params(), Vec::<Node>::new())
+            };
+            Some((name, print(&arrow.into())))
+          }
+          (&[name], Some(Node::FunctionExpr(function))) => {
+            Some((name, function_signature(function)))
+          }
+          _ => None,
+        };
+        let keyword = list.kind();
+        match function {
+          Some((name, function)) => vec![item(
+            name,
+            ItemKind::Function,
+            format!("{} {} = {}", keyword, name, function).into(),
+            Vec::new(),
+          )],
+          None => {
+            // A single name initialized with a class or an object literal has its members.
+            let members = match (&names[..], declaration.init()) {
+              (&[_], Some(init)) => value_members(init, comments),
+              _ => Vec::new(),
+            };
+            let mut members = Some(members);
+            names
+              .into_iter()
+              .map(|name| {
+                let signature = format!("{} {}", keyword, name).into();
+                let members = members.take().unwrap_or_default();
+                item(name, ItemKind::Variable, signature, members)
+              })
+              .collect()
+          }
+        }
+      })
+      .collect(),
+    _ => Vec::new(),
+  }
+}
+
+/// Gets the public members of a class, the constructor first, with their documentation.
+///
+/// Private members and static blocks are not part of the API.
+fn class_members(class: &Class, comments: &CommentMap<'_>) -> Vec<Member> {
+  let mut members = Vec::new();
+  if let Some(constructor) = class.constructor() {
+    let class = Class::new(
+      None,
+      None::<Node>,
+      Some(without_body(constructor)),
+      Vec::new(),
+    );
+    members.push(Member {
+      name: "constructor".into(),
+      kind: MemberKind::Constructor,
+      is_static: false,
+      signature: member_signature(class),
+      doc: doc(comments, constructor),
+    });
+  }
+  for element in class.elements() {
+    let (name, kind, is_static, element_signature) = match element {
+      ClassElement::Method {
+        name: ClassElementName::PropertyName(name),
+        kind,
+        function,
+        is_static,
+        ..
+      } => (
+        name,
+        method_kind(*kind),
+        *is_static,
+        method(name, *kind, function, *is_static),
+      ),
+      ClassElement::Field {
+        name: ClassElementName::PropertyName(name),
+        is_static,
+        ..
+      } => (name, MemberKind::Field, *is_static, field(name, *is_static)),
+      _ => continue,
+    };
+    members.push(Member {
+      name: member_name(name),
+      kind,
+      is_static,
+      signature: member_signature(Class::new(
+        None,
+        None::<Node>,
+        None,
+        vec![element_signature],
+      )),
+      doc: doc(comments, element),
+    });
+  }
+  members
+}
+
+/// Gets the properties of an object literal, with their documentation.
+///
+/// Spread properties have no name, and are left out.
+fn object_members(object: &Object, comments: &CommentMap<'_>) -> Vec<Member> {
+  let mut members = Vec::new();
+  for property in object.properties() {
+    let (name, kind, element) = match property {
+      PropertyDefinition::IdentifierReference(ident, _) => {
+        let name = PropertyName::Literal(ident.name().clone());
+        let element = field(&name, false);
+        (name, MemberKind::Property, element)
+      }
+      PropertyDefinition::Property(name, value, _) => {
+        let function = match value {
+          Node::FunctionExpr(function) => Some(without_body(function)),
+          Node::ArrowFunctionDecl(arrow) => {
+            let function = FunctionExpr::new(None, arrow.params(), Vec::<Node>::new());
+            Some(if arrow.is_async() {
+              function.with_kind(FunctionKind::Async)
+            } else {
+              function
+            })
+          }
+          _ => None,
+        };
+        match function {
+          Some(function) => {
+            let element = method(name, MethodDefinitionKind::Ordinary, &function, false);
+            (name.clone(), MemberKind::Method, element)
+          }
+          None => (name.clone(), MemberKind::Property, field(name, false)),
+        }
+      }
+      PropertyDefinition::MethodDefinition(kind, name, function, _) => {
+        let element = method(name, *kind, function, false);
+        (name.clone(), method_kind(*kind), element)
+      }
+      PropertyDefinition::SpreadObject(_, _) => continue,
+    };
+    members.push(Member {
+      name: member_name(&name),
+      kind,
+      is_static: false,
+      signature: member_signature(Class::new(None, None::<Node>, None, vec![element])),
+      doc: doc(comments, property),
+    });
+  }
+  members
+}
+
+/// Gets the members of the class or the object literal a name is initialized with.
+fn value_members(value: &Node, comments: &CommentMap<'_>) -> Vec<Member> {
+  match value {
+    Node::ClassExpr(class) => class_members(class, comments),
+    Node::Object(object) => object_members(object, comments),
+    _ => Vec::new(),
+  }
+}
+
+/// Gets what a method is by its kind.
+fn method_kind(kind: MethodDefinitionKind) -> MemberKind {
+  match kind {
+    MethodDefinitionKind::Get => MemberKind::Getter,
+    MethodDefinitionKind::Set => MemberKind::Setter,
+    MethodDefinitionKind::Ordinary => MemberKind::Method,
+  }
+}
+
+/// Creates a method without a body, to print its signature.
+fn method(
+  name: &PropertyName,
+  kind: MethodDefinitionKind,
+  function: &FunctionExpr,
+  is_static: bool,
+) -> ClassElement {
+  ClassElement::Method {
+    name: name.clone().into(),
+    kind,
+    function: without_body(function),
+    is_static,
+    span: NodeSpan::default(),
+  }
+}
+
+/// Creates a field without an initializer, to print its signature.
+fn field(name: &PropertyName, is_static: bool) -> ClassElement {
+  ClassElement::Field {
+    name: name.clone().into(),
+    init: None,
+    is_static,
+    span: NodeSpan::default(),
+  }
+}
+
+/// Gets the name of a member, printing a computed name in brackets.
+fn member_name(name: &PropertyName) -> Box<str> {
+  match name {
+    PropertyName::Literal(name) => name.as_str().into(),
+    PropertyName::Computed(expr) => format!("[{}]", print(expr)).into(),
+  }
+}
+
+/// Prints the only member of a class on a single line, without its empty body or its `;`.
+fn member_signature(class: Class) -> Box<str> {
+  let code = Generator::new(Options::pretty().with_line_width(SIGNATURE_WIDTH))
+    .expression(&Node::ClassExpr(class));
+  let line = code.lines().nth(1).unwrap_or_default().trim();
+  line
+    .strip_suffix(" {}")
+    .or_else(|| line.strip_suffix(';'))
+    .unwrap_or(line)
+    .into()
+}
+
+/// Copies a function without its body.
+fn without_body(function: &FunctionExpr) -> FunctionExpr {
+  FunctionExpr::new(
+    function.name().map(Into::into),
+    function.parameters(),
+    Vec::<Node>::new(),
+  )
+  .with_kind(function.kind())
+}
+
+/// Prints a function without its body.
+fn function_signature(function: &FunctionExpr) -> Box<str> {
+  print(&Node::FunctionExpr(without_body(function)))
+}
+
+/// Prints a class without its body.
+fn class_signature(class: &Class) -> Box<str> {
+  let class = Class::new(
+    class.name().map(Into::into),
+    class.super_ref().cloned(),
+    None,
+    Vec::new(),
+  );
+  print(&Node::ClassExpr(class))
+}
+
+/// Prints an expression on a single line, without the empty body at its end.
+fn print(expr: &Node) -> Box<str> {
+  let code = Generator::new(Options::pretty().with_line_width(SIGNATURE_WIDTH)).expression(expr);
+  code.strip_suffix(" {}").unwrap_or(&code).into()
+}
+
+/// Writes the description and the tags of a documentation comment as Markdown.
+fn markdown_doc(doc: &JsDoc, out: &mut String) {
+  if let Some(reason) = doc.deprecated() {
+    out.push_str("\n**Deprecated.**");
+    if !reason.is_empty() {
+      out.push(' ');
+      out.push_str(reason);
+    }
+    out.push('\n');
+  }
+  if !doc.description().is_empty() {
+    let _ = writeln!(out, "\n{}", doc.description());
+  }
+  if let Some(type_expr) = doc.type_expr() {
+    let _ = writeln!(out, "\n**Type:** `{}`", type_expr);
+  }
+  let params = doc.params().collect::<Vec<_>>();
+  if !params.is_empty() {
+    out.push_str("\n**Parameters:**\n\n");
+    for param in params {
+      let name = match (param.is_optional(), param.default_value()) {
+        (true, Some(default_value)) => format!("[{}={}]", param.name(), default_value),
+        (true, None) => format!("[{}]", param.name()),
+        (false, _) => param.name().to_owned(),
+      };
+      let _ = write!(out, "- `{}`", name);
+      if let Some(type_expr) = param.type_expr() {
+        let _ = write!(out, " (`{}`)", type_expr);
+      }
+      if !param.description().is_empty() {
+        let _ = write!(out, ": {}", param.description());
+      }
+      out.push('\n');
+    }
+  }
+  if let Some((type_expr, description)) = doc.returns() {
+    out.push_str("\n**Returns:**");
+    if let Some(type_expr) = type_expr {
+      let _ = write!(out, " `{}`", type_expr);
+    }
+    if !description.is_empty() {
+      out.push_str(if type_expr.is_some() { ": " } else { " " });
+      out.push_str(description);
+    }
+    out.push('\n');
+  }
+  for tag in doc.tags() {
+    if let Tag::Other { name, text } = tag {
+      if name[..] == *"example" {
+        let _ = writeln!(out, "\n**Example:**\n\n```js\n{}\n```", text);
+      } else if text.is_empty() {
+        let _ = writeln!(out, "\n**@{}**", name);
+      } else {
+        let _ = writeln!(out, "\n**@{}** {}", name, text);
+      }
+    }
+  }
+}
+
+/// Writes the description and the tags of a documentation comment as a JSON object.
+fn json_doc(doc: &JsDoc, out: &mut String) {
+  out.push_str("{\"description\":");
+  json::write_string(doc.description(), out);
+  out.push_str(",\"deprecated\":");
+  json_option(doc.deprecated(), out);
+  out.push_str(",\"type\":");
+  json_option(doc.type_expr(), out);
+  out.push_str(",\"params\":[");
+  for (index, param) in doc.params().enumerate() {
+    if index > 0 {
+      out.push(',');
+    }
+    out.push_str("{\"name\":");
+    json::write_string(param.name(), out);
+    out.push_str(",\"type\":");
+    json_option(param.type_expr(), out);
+    out.push_str(",\"description\":");
+    json::write_string(param.description(), out);
+    let _ = write!(out, ",\"optional\":{}", param.is_optional());
+    out.push_str(",\"default\":");
+    json_option(param.default_value(), out);
+    out.push('}');
+  }
+  out.push_str("],\"returns\":");
+  match doc.returns() {
+    Some((type_expr, description)) => {
+      out.push_str("{\"type\":");
+      json_option(type_expr, out);
+      out.push_str(",\"description\":");
+      json::write_string(description, out);
+      out.push('}');
+    }
+    None => out.push_str("null"),
+  }
+  out.push_str(",\"tags\":[");
+  let others = doc.tags().iter().filter_map(|tag| match tag {
+    Tag::Other { name, text } => Some((name, text)),
+    _ => None,
+  });
+  for (index, (name, text)) in others.enumerate() {
+    if index > 0 {
+      out.push(',');
+    }
+    out.push_str("{\"name\":");
+    json::write_string(name, out);
+    out.push_str(",\"text\":");
+    json::write_string(text, out);
+    out.push('}');
+  }
+  out.push_str("]}");
+}
+
+/// Writes a documentation comment, or `null`.
+fn json_option_doc(doc: Option<&JsDoc>, out: &mut String) {
+  match doc {
+    Some(doc) => json_doc(doc, out),
+    None => out.push_str("null"),
+  }
+}
+
+/// Writes a string, or `null`.
+fn json_option(value: Option<&str>, out: &mut String) {
+  match value {
+    Some(value) => json::write_string(value, out),
+    None => out.push_str("null"),
+  }
+}
@@ -0,0 +1,297 @@
+//! JSDoc: the structure of `/** */` documentation comments, and documentation generated from
+//! them.
+//!
+//! A [`JsDoc`] is the description of a documentation comment followed by its block tags, such
+//! as `@param` and `@returns`:
+//!
+//! ```
+//! use plastic::syntax::jsdoc::{JsDoc, Tag};
+//!
+//! let doc = JsDoc::parse(
+//!   "*\n * Adds two numbers.\n *\n * @param {number} a - The first number.\n \
+//!    * @param {number} [b=0] The second number.\n * @returns {number} The sum.\n ",
+//! );
+//! assert_eq!(doc.description(), "Adds two numbers.");
+//! let params = doc.params().collect::<Vec<_>>();
+//! assert_eq!(params[0].name(), "a");
+//! assert_eq!(params[0].type_expr(), Some("number"));
+//! assert_eq!(params[0].description(), "The first number.");
+//! assert!(params[1].is_optional());
+//! assert_eq!(params[1].default_value(), Some("0"));
+//! assert_eq!(
+//!   doc.tags()[2],
+//!   Tag::Returns {
+//!     type_expr: Some("number".into()),
+//!     description: "The sum.".into(),
+//!   }
+//! );
+//! ```
+//!
+//! [`ModuleDocs`] documents the exported API of a module, with the members of its classes and
+//! object literals, as Markdown or JSON.
+
+mod api;
+#[cfg(test)]
+mod tests;
+
+pub use self::api::{Item, ItemKind, Member, MemberKind, ModuleDocs};
+
+use crate::syntax::comments::Comment;
+
+/// A parsed documentation comment.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsDoc {
+  description: Box<str>,
+  tags: Vec<Tag>,
+}
+
+impl JsDoc {
+  /// Parses the text of a documentation comment, between its `/*` and `*/` delimiters.
+  ///
+  /// The leading stars of the lines, and the space after them, are not part of the text of the
+  /// description and of the tags. A line starting with `@` starts a tag, which goes on until
+  /// the next tag.
+  pub fn parse(text: &str) -> Self {
+    let lines = text
+      .split('\n')
+      .map(|line| {
+        let line = line.trim_end_matches('\r').trim_start();
+        let line = line.strip_prefix('*').unwrap_or(line);
+        line.strip_prefix(' ').unwrap_or(line).trim_end()
+      })
+      .collect::<Vec<_>>();
+
+    let mut doc = Self::default();
+    let mut description = Vec::new();
+    let mut tag: Option<Vec<&str>> = None;
+    for line in lines {
+      if line.starts_with('@') {
+        if let Some(lines) = tag.take() {
+          doc.tags.push(Tag::parse(&lines));
+        }
+        tag = Some(vec![line]);
+      } else if let Some(lines) = &mut tag {
+        lines.push(line);
+      } else {
+        description.push(line);
+      }
+    }
+    if let Some(lines) = tag {
+      doc.tags.push(Tag::parse(&lines));
+    }
+    doc.description = join(&description).into();
+    doc
+  }
+
+  /// Parses a documentation comment, or returns `None` if the comment is not one.
+  pub fn from_comment(comment: &Comment<'_>) -> Option<Self> {
+    if comment.is_jsdoc() {
+      Some(Self::parse(comment.text()))
+    } else {
+      None
+    }
+  }
+
+  /// Gets the description before the tags.
+  pub fn description(&self) -> &str {
+    &self.description
+  }
+
+  /// Gets the tags, in order.
+  pub fn tags(&self) -> &[Tag] {
+    &self.tags
+  }
+
+  /// Returns an iterator over the `@param` tags.
+  pub fn params(&self) -> impl Iterator<Item = &Param> {
+    self.tags.iter().filter_map(|tag| match tag {
+      Tag::Param(param) => Some(param),
+      _ => None,
+    })
+  }
+
+  /// Gets the `@returns` tag, as its type and description.
+  pub fn returns(&self) -> Option<(Option<&str>, &str)> {
+    self.tags.iter().find_map(|tag| match tag {
+      Tag::Returns {
+        type_expr,
+        description,
+      } => Some((type_expr.as_deref(), &description[..])),
+      _ => None,
+    })
+  }
+
+  /// Gets the type of the `@type` tag.
+  pub fn type_expr(&self) -> Option<&str> {
+    self.tags.iter().find_map(|tag| match tag {
+      Tag::Type(type_expr) => Some(&type_expr[..]),
+      _ => None,
+    })
+  }
+
+  /// Gets the explanation of the `@deprecated` tag, which may be empty.
+  pub fn deprecated(&self) -> Option<&str> {
+    self.tags.iter().find_map(|tag| match tag {
+      Tag::Deprecated(reason) => Some(&reason[..]),
+      _ => None,
+    })
+  }
+}
+
+/// A block tag of a documentation comment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Tag {
+  /// A parameter of a function, `@param {type} name description`, also written `@arg` or
+  /// `@argument`.
+  Param(Param),
+  /// The value a function returns, `@returns {type} description`, also written `@return`.
+  Returns {
+    type_expr: Option<Box<str>>,
+    description: Box<str>,
+  },
+  /// The type of a value, `@type {type}`.
+  Type(Box<str>),
+  /// A deprecation, `@deprecated explanation`.
+  Deprecated(Box<str>),
+  /// Any other tag, such as `@example` or `@since`, with its text.
+  Other { name: Box<str>, text: Box<str> },
+}
+
+impl Tag {
+  /// Parses a tag from its lines, the first one starting with `@`.
+  fn parse(lines: &[&str]) -> Self {
+    let text = join(lines);
+    let text = &text[1..];
+    let (name, rest) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+    let rest = rest.trim_start();
+    match name {
+      "param" | "arg" | "argument" => Self::Param(Param::parse(rest)),
+      "returns" | "return" => {
+        let (type_expr, description) = type_expr(rest);
+        Self::Returns {
+          type_expr: type_expr.map(Into::into),
+          description: description.into(),
+        }
+      }
+      "type" => {
+        let (type_expr, rest) = type_expr(rest);
+        Self::Type(type_expr.unwrap_or(rest).into())
+      }
+      "deprecated" => Self::Deprecated(rest.into()),
+      _ => Self::Other {
+        name: name.into(),
+        text: rest.into(),
+      },
+    }
+  }
+}
+
+/// A `@param` tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Param {
+  name: Box<str>,
+  type_expr: Option<Box<str>>,
+  description: Box<str>,
+  optional: bool,
+  default_value: Option<Box<str>>,
+}
+
+impl Param {
+  /// Parses the text of a `@param` tag after its name: `{type} name - description`, the name
+  /// being in brackets for optional parameters, such as `[name]` or `[name=default]`.
+  fn parse(text: &str) -> Self {
+    let (type_expr, rest) = type_expr(text);
+    let (name, optional, default_value, rest) = match rest.strip_prefix('[') {
+      Some(inner) => {
+        let end = closing(inner, '[', ']').unwrap_or(inner.len());
+        let (name, default_value) = match inner[..end].split_once('=') {
+          Some((name, default_value)) => (name.trim(), Some(default_value.trim().into())),
+          None => (inner[..end].trim(), None),
+        };
+        let rest = inner.get(end + 1..).unwrap_or_default();
+        (name, true, default_value, rest)
+      }
+      None => {
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        (&rest[..end], false, None, &rest[end..])
+      }
+    };
+    let rest = rest.trim_start();
+    let description = rest.strip_prefix("- ").unwrap_or(rest).trim_start();
+    Self {
+      name: name.into(),
+      type_expr: type_expr.map(Into::into),
+      description: description.into(),
+      optional,
+      default_value,
+    }
+  }
+
+  /// Gets the name of the parameter, such as `options` or `options.name`.
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Gets the type of the parameter, without its braces.
+  pub fn type_expr(&self) -> Option<&str> {
+    self.type_expr.as_deref()
+  }
+
+  /// Gets the description of the parameter.
+  pub fn description(&self) -> &str {
+    &self.description
+  }
+
+  /// Returns `true` if the parameter is optional, its name being in brackets.
+  pub fn is_optional(&self) -> bool {
+    self.optional
+  }
+
+  /// Gets the default value of an optional parameter, as written in the comment.
+  pub fn default_value(&self) -> Option<&str> {
+    self.default_value.as_deref()
+  }
+}
+
+/// Joins lines with line breaks, without the empty lines at the start and at the end.
+fn join(lines: &[&str]) -> String {
+  let start = lines
+    .iter()
+    .position(|line| !line.is_empty())
+    .unwrap_or(lines.len());
+  let end = lines
+    .iter()
+    .rposition(|line| !line.is_empty())
+    .map_or(start, |end| end + 1);
+  lines[start..end].join("\n")
+}
+
+/// Reads the `{type}` at the start of a text, returning the type without its braces and the
+/// rest of the text.
+fn type_expr(text: &str) -> (Option<&str>, &str) {
+  let inner = match text.strip_prefix('{') {
+    Some(inner) => inner,
+    None => return (None, text),
+  };
+  match closing(inner, '{', '}') {
+    Some(end) => (Some(inner[..end].trim()), inner[end + 1..].trim_start()),
+    None => (None, text),
+  }
+}
+
+/// Finds the delimiter closing a text, after the opening one, with the nested pairs of
+/// delimiters skipped.
+fn closing(text: &str, open: char, close: char) -> Option<usize> {
+  let mut depth = 0usize;
+  for (index, ch) in text.char_indices() {
+    if ch == open {
+      depth += 1;
+    } else if ch == close {
+      if depth == 0 {
+        return Some(index);
+      }
+      depth -= 1;
+    }
+  }
+  None
+}
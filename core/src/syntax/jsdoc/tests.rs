@@ -0,0 +1,249 @@
+//! Tests for JSDoc comments and the documentation of modules.
+
+use super::{ItemKind, JsDoc, MemberKind, ModuleDocs, Tag};
+use crate::syntax::{comments::CommentMap, parser::Parser};
+
+/// Documents a module.
+#[track_caller]
+fn docs(js: &str) -> ModuleDocs {
+  let module = Parser::new(js.as_bytes())
+    .parse_module()
+    .unwrap_or_else(|err| panic!("failed to parse `{}`: {}", js, err));
+  ModuleDocs::new(&module, &CommentMap::module(js, &module))
+}
+
+#[test]
+fn tags() {
+  let doc = JsDoc::parse(
+    "*\n   * Splits a text.\n   *\n   * Empty parts are kept.\n   * @param {string} text\n   \
+     * @param {{ by: string, limit?: number }} [options] - How to split,\n   *   \
+     with several lines.\n   * @param {number} [options.limit=Infinity] The maximum.\n   \
+     * @return {Array<string>}\n   * @deprecated\n   * @since 1.2\n   * @example\n   \
+     * split('a,b', { by: ',' });\n   *   // ['a', 'b']\n   ",
+  );
+  assert_eq!(doc.description(), "Splits a text.\n\nEmpty parts are kept.");
+  let params = doc
+    .params()
+    .map(|param| {
+      (
+        param.name(),
+        param.type_expr(),
+        param.description(),
+        param.is_optional(),
+        param.default_value(),
+      )
+    })
+    .collect::<Vec<_>>();
+  assert_eq!(
+    params,
+    [
+      ("text", Some("string"), "", false, None),
+      (
+        "options",
+        Some("{ by: string, limit?: number }"),
+        "How to split,\n  with several lines.",
+        true,
+        None
+      ),
+      (
+        "options.limit",
+        Some("number"),
+        "The maximum.",
+        true,
+        Some("Infinity")
+      ),
+    ]
+  );
+  assert_eq!(doc.returns(), Some((Some("Array<string>"), "")));
+  assert_eq!(doc.deprecated(), Some(""));
+  assert_eq!(doc.type_expr(), None);
+  assert_eq!(
+    doc.tags()[5..],
+    [
+      Tag::Other {
+        name: "since".into(),
+        text: "1.2".into(),
+      },
+      Tag::Other {
+        name: "example".into(),
+        text: "split('a,b', { by: ',' });\n  // ['a', 'b']".into(),
+      },
+    ]
+  );
+
+  let doc = JsDoc::parse("* @type {Map<string, number>} ");
+  assert_eq!(doc.description(), "");
+  assert_eq!(doc.type_expr(), Some("Map<string, number>"));
+  let doc = JsDoc::parse("* @deprecated Use `join` instead. ");
+  assert_eq!(doc.deprecated(), Some("Use `join` instead."));
+}
+
+#[test]
+fn exports() {
+  let docs = docs(
+    "import { helper } from './helper.js';\n\
+     /** Adds. */\nexport async function add(a, b = 1, ...rest) {}\n\
+     /** The base. */\nclass Base {}\n\
+     /** Shapes. */\nexport class Shape extends Base {}\n\
+     export const double = (x) => x * 2, [first, second] = [1, 2];\n\
+     /** Exported later. */\nlet counter = 0;\nexport { counter as count, helper, Base };\n\
+     export * as utils from './utils.js';\nexport default 42;\n",
+  );
+  let items = docs
+    .items()
+    .iter()
+    .map(|item| {
+      (
+        item.name(),
+        item.kind(),
+        item.signature(),
+        item.source(),
+        item.doc().map(JsDoc::description),
+      )
+    })
+    .collect::<Vec<_>>();
+  assert_eq!(
+    items,
+    [
+      (
+        "add",
+        ItemKind::Function,
+        Some("async function add(a, b = 1, ...rest)"),
+        None,
+        Some("Adds.")
+      ),
+      (
+        "Shape",
+        ItemKind::Class,
+        Some("class Shape extends Base"),
+        None,
+        Some("Shapes.")
+      ),
+      (
+        "double",
+        ItemKind::Function,
+        Some("const double = (x) =>"),
+        None,
+        None
+      ),
+      ("first", ItemKind::Variable, Some("const first"), None, None),
+      (
+        "second",
+        ItemKind::Variable,
+        Some("const second"),
+        None,
+        None
+      ),
+      (
+        "count",
+        ItemKind::Variable,
+        Some("let counter"),
+        None,
+        Some("Exported later.")
+      ),
+      (
+        "helper",
+        ItemKind::ReExport,
+        None,
+        Some("./helper.js"),
+        None
+      ),
+      (
+        "Base",
+        ItemKind::Class,
+        Some("class Base"),
+        None,
+        Some("The base.")
+      ),
+      ("utils", ItemKind::ReExport, None, Some("./utils.js"), None),
+      ("default", ItemKind::Value, None, None, None),
+    ]
+  );
+}
+
+#[test]
+fn markdown() {
+  let docs = docs(
+    "/**\n * Scales a value.\n *\n * @param {number} value - The value.\n \
+     * @param {number} [factor=2] The factor.\n * @returns {number} The scaled value.\n \
+     * @deprecated Use `multiply`.\n * @example\n * scale(2); // 4\n */\n\
+     export function scale(value, factor = 2) {}\nexport * from './more.js';\n",
+  );
+  assert_eq!(
+    docs.to_markdown(),
+    "## `scale`\n\n```js\nfunction scale(value, factor = 2)\n```\n\n\
+     **Deprecated.** Use `multiply`.\n\nScales a value.\n\n**Parameters:**\n\n\
+     - `value` (`number`): The value.\n- `[factor=2]` (`number`): The factor.\n\n\
+     **Returns:** `number`: The scaled value.\n\n**Example:**\n\n```js\nscale(2); // 4\n```\n\
+     \n## `*`\n\nRe-exported from `./more.js`.\n"
+  );
+}
+
+#[test]
+fn json() {
+  let docs = docs(
+    "/**\n * Says \"hi\".\n * @param {string} [name]\n * @since 2\n */\n\
+     export function hi(name) {}\nexport let count = 0;\n",
+  );
+  assert_eq!(
+    docs.to_json(),
+    "{\"items\":[{\"name\":\"hi\",\"kind\":\"function\",\"signature\":\"function hi(name)\",\
+     \"source\":null,\"doc\":{\"description\":\"Says \\\"hi\\\".\",\"deprecated\":null,\
+     \"type\":null,\"params\":[{\"name\":\"name\",\"type\":\"string\",\"description\":\"\",\
+     \"optional\":true,\"default\":null}],\"returns\":null,\
+     \"tags\":[{\"name\":\"since\",\"text\":\"2\"}]},\"members\":[]},\
+     {\"name\":\"count\",\"kind\":\"variable\",\"signature\":\"let count\",\"source\":null,\
+     \"doc\":null,\"members\":[]}]}"
+  );
+}
+
+#[test]
+fn members() {
+  let docs = docs(
+    "/** A counter. */\nexport class Counter {\n  /**\n   * Creates a counter.\n   \
+     * @param {number} [start=0]\n   */\n  constructor(start = 0) { this.count = start; }\n  \
+     /** The step. */\n  static step = 1;\n  /** Counts one more. */\n  \
+     increment() { this.count += Counter.step; }\n  get value() { return this.count; }\n  \
+     #secret = 1;\n  static { Counter.step = 2; }\n}\n\n/** The settings. */\n\
+     export const settings = {\n  /** Whether to log. */\n  verbose: false,\n  \
+     /** Formats a value. */\n  format: (value) => String(value),\n  ...defaults,\n};\n",
+  );
+  let members = docs.items()[0]
+    .members()
+    .iter()
+    .map(|member| (member.name(), member.kind(), member.is_static()))
+    .collect::<Vec<_>>();
+  assert_eq!(
+    members,
+    [
+      ("constructor", MemberKind::Constructor, false),
+      ("step", MemberKind::Field, true),
+      ("increment", MemberKind::Method, false),
+      ("value", MemberKind::Getter, false),
+    ]
+  );
+  assert_eq!(
+    docs.to_markdown(),
+    "## `Counter`\n\n```js\nclass Counter\n```\n\nA counter.\n\
+     \n### `Counter#constructor`\n\n```js\nconstructor(start = 0)\n```\n\n\
+     Creates a counter.\n\n**Parameters:**\n\n- `[start=0]` (`number`)\n\
+     \n### `Counter.step`\n\n```js\nstatic step\n```\n\nThe step.\n\
+     \n### `Counter#increment`\n\n```js\nincrement()\n```\n\nCounts one more.\n\
+     \n### `Counter#value`\n\n```js\nget value()\n```\n\
+     \n## `settings`\n\n```js\nconst settings\n```\n\nThe settings.\n\
+     \n### `settings.verbose`\n\n```js\nverbose\n```\n\nWhether to log.\n\
+     \n### `settings.format`\n\n```js\nformat(value)\n```\n\nFormats a value.\n"
+  );
+}
+
+#[test]
+fn members_json() {
+  let docs = docs("export default {\n  /** The origin. */\n  origin: [0, 0],\n};\n");
+  assert_eq!(
+    docs.to_json(),
+    "{\"items\":[{\"name\":\"default\",\"kind\":\"value\",\"signature\":null,\
+     \"source\":null,\"doc\":null,\"members\":[{\"name\":\"origin\",\"kind\":\"property\",\
+     \"static\":false,\"signature\":\"origin\",\"doc\":{\"description\":\"The origin.\",\
+     \"deprecated\":null,\"type\":null,\"params\":[],\"returns\":null,\"tags\":[]}}]}]}"
+  );
+}
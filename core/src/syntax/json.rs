@@ -0,0 +1,277 @@
+//! The little JSON the crate reads and writes, for source maps, `package.json` files, the
+//! language server protocol and the API documentation: a reader for whole documents and writers
+//! for strings and values.
+
+use std::fmt::Write;
+
+/// A JSON value.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+  Null,
+  Bool(bool),
+  Number(f64),
+  String(String),
+  Array(Vec<Value>),
+  /// The members of an object, in order.
+  Object(Vec<(String, Value)>),
+}
+
+impl Value {
+  /// Gets a member of an object.
+  pub(crate) fn get(&self, key: &str) -> Option<&Value> {
+    match self {
+      Self::Object(members) => members
+        .iter()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value),
+      _ => None,
+    }
+  }
+}
+
+/// Parses a JSON document, returning the error message and its byte offset if it is malformed.
+pub(crate) fn parse(text: &str) -> Result<Value, (&'static str, usize)> {
+  let mut reader = Reader {
+    text: text.as_bytes(),
+    offset: 0,
+  };
+  let value = reader.value()?;
+  reader.whitespace();
+  if reader.offset < reader.text.len() {
+    return Err(reader.error("unexpected text after the value"));
+  }
+  Ok(value)
+}
+
+/// Formats a string as a JSON string literal.
+pub(crate) fn write_string(value: &str, out: &mut String) {
+  out.push('"');
+  for ch in value.chars() {
+    match ch {
+      '"' => out.push_str("\\\""),
+      '\\' => out.push_str("\\\\"),
+      '\n' => out.push_str("\\n"),
+      '\r' => out.push_str("\\r"),
+      '\t' => out.push_str("\\t"),
+      ch if (ch as u32) < 0x20 || ch == '\u{2028}' || ch == '\u{2029}' => {
+        let _ = write!(out, "\\u{:04x}", ch as u32);
+      }
+      ch => out.push(ch),
+    }
+  }
+  out.push('"');
+}
+
+/// Formats a value as JSON text, without whitespace.
+pub(crate) fn write(value: &Value, out: &mut String) {
+  match value {
+    Value::Null => out.push_str("null"),
+    Value::Bool(value) => out.push_str(if *value { "true" } else { "false" }),
+    // Integers, which are most numbers, are written without a fraction.
+    Value::Number(value) if value.fract() == 0.0 && value.abs() < 1e15 => {
+      let _ = write!(out, "{}", *value as i64);
+    }
+    Value::Number(value) if value.is_finite() => {
+      let _ = write!(out, "{}", value);
+    }
+    Value::Number(_) => out.push_str("null"),
+    Value::String(value) => write_string(value, out),
+    Value::Array(elements) => {
+      out.push('[');
+      for (index, element) in elements.iter().enumerate() {
+        if index > 0 {
+          out.push(',');
+        }
+        write(element, out);
+      }
+      out.push(']');
+    }
+    Value::Object(members) => {
+      out.push('{');
+      for (index, (name, value)) in members.iter().enumerate() {
+        if index > 0 {
+          out.push(',');
+        }
+        write_string(name, out);
+        out.push(':');
+        write(value, out);
+      }
+      out.push('}');
+    }
+  }
+}
+
+/// Reads JSON values from bytes.
+struct Reader<'a> {
+  text: &'a [u8],
+  offset: usize,
+}
+
+impl Reader<'_> {
+  fn error(&self, message: &'static str) -> (&'static str, usize) {
+    (message, self.offset)
+  }
+
+  fn peek(&self) -> Option<u8> {
+    self.text.get(self.offset).copied()
+  }
+
+  fn whitespace(&mut self) {
+    while let Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r') = self.peek() {
+      self.offset += 1;
+    }
+  }
+
+  /// Consumes a keyword, such as `null`.
+  fn keyword(&mut self, keyword: &str, value: Value) -> Result<Value, (&'static str, usize)> {
+    if self.text[self.offset..].starts_with(keyword.as_bytes()) {
+      self.offset += keyword.len();
+      Ok(value)
+    } else {
+      Err(self.error("unexpected character"))
+    }
+  }
+
+  fn value(&mut self) -> Result<Value, (&'static str, usize)> {
+    self.whitespace();
+    match self.peek() {
+      Some(b'n') => self.keyword("null", Value::Null),
+      Some(b't') => self.keyword("true", Value::Bool(true)),
+      Some(b'f') => self.keyword("false", Value::Bool(false)),
+      Some(b'"') => self.string().map(Value::String),
+      Some(b'[') => {
+        self.offset += 1;
+        let mut elements = Vec::new();
+        self.whitespace();
+        if self.peek() == Some(b']') {
+          self.offset += 1;
+          return Ok(Value::Array(elements));
+        }
+        loop {
+          elements.push(self.value()?);
+          self.whitespace();
+          match self.peek() {
+            Some(b',') => self.offset += 1,
+            Some(b']') => {
+              self.offset += 1;
+              return Ok(Value::Array(elements));
+            }
+            _ => return Err(self.error("expected `,` or `]`")),
+          }
+        }
+      }
+      Some(b'{') => {
+        self.offset += 1;
+        let mut members = Vec::new();
+        self.whitespace();
+        if self.peek() == Some(b'}') {
+          self.offset += 1;
+          return Ok(Value::Object(members));
+        }
+        loop {
+          self.whitespace();
+          if self.peek() != Some(b'"') {
+            return Err(self.error("expected a member name"));
+          }
+          let name = self.string()?;
+          self.whitespace();
+          if self.peek() != Some(b':') {
+            return Err(self.error("expected `:`"));
+          }
+          self.offset += 1;
+          members.push((name, self.value()?));
+          self.whitespace();
+          match self.peek() {
+            Some(b',') => self.offset += 1,
+            Some(b'}') => {
+              self.offset += 1;
+              return Ok(Value::Object(members));
+            }
+            _ => return Err(self.error("expected `,` or `}`")),
+          }
+        }
+      }
+      Some(b'-') | Some(b'0'..=b'9') => self.number(),
+      Some(_) => Err(self.error("unexpected character")),
+      None => Err(self.error("unexpected end of input")),
+    }
+  }
+
+  fn number(&mut self) -> Result<Value, (&'static str, usize)> {
+    let start = self.offset;
+    while let Some(b'-') | Some(b'+') | Some(b'.') | Some(b'e') | Some(b'E') | Some(b'0'..=b'9') =
+      self.peek()
+    {
+      self.offset += 1;
+    }
+    std::str::from_utf8(&self.text[start..self.offset])
+      .ok()
+      .and_then(|number| number.parse().ok())
+      .map(Value::Number)
+      .ok_or(("invalid number", start))
+  }
+
+  fn string(&mut self) -> Result<String, (&'static str, usize)> {
+    // Skip the opening quote.
+    self.offset += 1;
+    let mut bytes = Vec::new();
+    loop {
+      match self.peek() {
+        Some(b'"') => {
+          self.offset += 1;
+          return String::from_utf8(bytes).map_err(|_| self.error("invalid UTF-8"));
+        }
+        Some(b'\\') => {
+          self.offset += 1;
+          let escaped = match self.peek() {
+            Some(b'"') => '"',
+            Some(b'\\') => '\\',
+            Some(b'/') => '/',
+            Some(b'b') => '\u{8}',
+            Some(b'f') => '\u{c}',
+            Some(b'n') => '\n',
+            Some(b'r') => '\r',
+            Some(b't') => '\t',
+            Some(b'u') => {
+              self.offset += 1;
+              let unit = self.hex()?;
+              let code = if (0xD800..0xDC00).contains(&unit)
+                && self.text[self.offset..].starts_with(b"\\u")
+              {
+                self.offset += 2;
+                let low = self.hex()?;
+                0x10000 + ((unit - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF)
+              } else {
+                unit
+              };
+              let ch = std::char::from_u32(code).unwrap_or('\u{FFFD}');
+              bytes.extend(ch.encode_utf8(&mut [0; 4]).as_bytes());
+              continue;
+            }
+            _ => return Err(self.error("invalid escape")),
+          };
+          self.offset += 1;
+          bytes.extend(escaped.encode_utf8(&mut [0; 4]).as_bytes());
+        }
+        Some(byte) if byte < 0x20 => return Err(self.error("control character in string")),
+        Some(byte) => {
+          self.offset += 1;
+          bytes.push(byte);
+        }
+        None => return Err(self.error("unterminated string")),
+      }
+    }
+  }
+
+  /// Reads the four hexadecimal digits of a `\u` escape.
+  fn hex(&mut self) -> Result<u32, (&'static str, usize)> {
+    let digits = self
+      .text
+      .get(self.offset..self.offset + 4)
+      .and_then(|digits| std::str::from_utf8(digits).ok())
+      .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+      .ok_or_else(|| self.error("invalid unicode escape"))?;
+    self.offset += 4;
+    Ok(digits)
+  }
+}
@@ -30,9 +30,23 @@ impl<R> Tokenizer<R> for SingleLineComment {
   }
 }
 
+/// The longest text of a block comment that may be a purity annotation.
+const ANNOTATION_LEN: usize = 16;
+
 /// Lexes a block comment.
 ///
-pub(super) struct MultiLineComment;
+/// Remembers whether the comment is a purity annotation, `/*#__PURE__*/` or `/*@__PURE__*/`.
+#[derive(Debug, Default)]
+pub(super) struct MultiLineComment {
+  pure: bool,
+}
+
+impl MultiLineComment {
+  /// Returns `true` if the lexed comment is a purity annotation.
+  pub(super) fn is_pure_annotation(&self) -> bool {
+    self.pure
+  }
+}
 
 impl<R> Tokenizer<R> for MultiLineComment{
   fn lex(&mut self, cursor: &mut Cursor<R>, start_pos: Position) -> Result<Token, Error>
@@ -40,6 +54,8 @@ impl<R> Tokenizer<R> for MultiLineComment{
       R: Read,
   {
     let mut new_line = false;
+    let mut text = String::new();
+    let mut len = 0;
     loop {
       if let Some(ch) = cursor.next_char()? {
         if ch == '*' && cursor.next_is('/') ? {
@@ -47,6 +63,10 @@ impl<R> Tokenizer<R> for MultiLineComment{
         } else if ch == '\n' {
           new_line = true
         }
+        len += 1;
+        if len <= ANNOTATION_LEN {
+          text.push(ch);
+        }
       } else {
         return Err(Error::syntax(
           "Unterminated multiline comment",
@@ -54,6 +74,7 @@ impl<R> Tokenizer<R> for MultiLineComment{
         ))
       }
     }
+    self.pure = len <= ANNOTATION_LEN && matches!(text.trim(), "#__PURE__" | "@__PURE__");
 
     Ok(Token::new(
       if new_line {
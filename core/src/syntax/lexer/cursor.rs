@@ -2,7 +2,10 @@
 //! 词法分析器的游标实现。处理输入的文档字节流。
 //!
 use crate::{syntax::ast::Position};
-use std::io::{self, Bytes, Read, Error};
+use std::{
+  collections::VecDeque,
+  io::{self, Bytes, Read, Error},
+};
 
 /// Cursor over the source code.
 #[derive(Debug)]
@@ -67,11 +70,18 @@ impl<R> Cursor<R>
     self.iter.peek_char()
   }
 
+  /// Peeks the character `skip` positions after the next one, without consuming anything.
+  #[inline]
+  pub(super) fn peek_n(&mut self, skip: usize) -> Result<Option<char>, Error> {
+    self.iter.peek_char_n(skip)
+  }
+
   #[inline]
   pub(super) fn next_is(&mut self, peek: char) -> io::Result<bool> {
     Ok(match self.peek() ? {
       Some(next) if next == peek => {
         let _ = self.iter.next_char();
+        self.next_column();
         true
       }
       _ => false,
@@ -144,14 +154,14 @@ impl<R> Cursor<R>
 #[derive(Debug)]
 struct InnerIter<R> {
   iter: Bytes<R>,
-  peeked_char: Option<Option<char>>,
+  peeked: VecDeque<Option<char>>,
 }
 
 impl<R> InnerIter<R> {
   fn new(iter: Bytes<R>) -> Self {
     Self {
       iter,
-      peeked_char: None,
+      peeked: VecDeque::new(),
     }
   }
 }
@@ -161,10 +171,15 @@ impl<R> InnerIter<R>
     R: Read,
 {
   fn next_char(&mut self) -> io::Result<Option<char>> {
-    if let Some(v) = self.peeked_char.take() {
+    if let Some(v) = self.peeked.pop_front() {
       return Ok(v)
     }
 
+    self.read_char()
+  }
+
+  fn read_char(&mut self) -> io::Result<Option<char>> {
+
     let first_byte = match self.iter.next().transpose()? {
       Some(b) => b,
       None => return Ok(None)
@@ -242,12 +257,14 @@ impl<R> InnerIter<R>
 
   #[inline]
   pub(super) fn peek_char(&mut self) -> Result<Option<char>, Error> {
-    if let Some(v) = self.peeked_char {
-      Ok(v)
-    } else {
-      let chr = self.next_char()?;
-      self.peeked_char = Some(chr);
-      Ok(chr)
+    self.peek_char_n(0)
+  }
+
+  pub(super) fn peek_char_n(&mut self, skip: usize) -> Result<Option<char>, Error> {
+    while self.peeked.len() <= skip {
+      let chr = self.read_char()?;
+      self.peeked.push_back(chr);
     }
+    Ok(self.peeked[skip])
   }
 }
@@ -1,13 +1,18 @@
 use super::{Cursor, Tokenizer, Error};
 use crate::{
   syntax::{
-    ast::{Position, Keyword, Span},
+    ast::{sym::Interner, Position, Span},
     lexer::{Token, TokenKind},
   }
 };
 use std::io::Read;
 
-const STRICT_FORBIDDEN_IDENTIFIERS: [&str; 11] = [
+/// Identifiers that can not be used as binding names in strict mode code.
+///
+/// Apart from `eval` and `arguments`, these are reserved words in strict mode code and can not
+/// be used as identifiers at all. Since some of them are also contextual keywords, such as
+/// `static` in class bodies, they are checked by the parser rather than the lexer.
+pub(crate) const STRICT_FORBIDDEN_IDENTIFIERS: [&str; 11] = [
     "eval",
     "arguments",
     "implements",
@@ -21,53 +26,49 @@ const STRICT_FORBIDDEN_IDENTIFIERS: [&str; 11] = [
     "yield",
 ];
 
+/// Returns `true` if the name can be written as an identifier, instead of a string literal.
+pub(crate) fn is_identifier_name(name: &str) -> bool {
+  let mut chars = name.chars();
+  matches!(chars.next(), Some(c) if c.is_alphabetic() || c == '_' || c == '$')
+    && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
 /// Identifier lexing.
 ///
-pub(super) struct Identifier {
+pub(super) struct Identifier<'i> {
   init: char,
+  interner: &'i mut Interner,
+  /// The buffer the name is read into, reused from one name to the next.
+  buf: &'i mut String,
 }
 
-impl Identifier {
-  pub(super) fn new(init: char) -> Self {
-    Self { init }
+impl<'i> Identifier<'i> {
+  pub(super) fn new(init: char, interner: &'i mut Interner, buf: &'i mut String) -> Self {
+    Self { init, interner, buf }
   }
 }
 
-impl<R> Tokenizer<R> for Identifier {
+impl<R> Tokenizer<R> for Identifier<'_> {
   fn lex(&mut self, cursor: &mut Cursor<R>, start_pos: Position) -> Result<Token, Error>
     where
       R: Read,
   {
-    let mut buf = self.init.to_string();
+    self.buf.clear();
+    self.buf.push(self.init);
 
-    cursor.take_while_pred(&mut buf, &|c: char| {
-      c.is_alphabetic() || c.is_digit(10) || c == '_'
+    cursor.take_while_pred(self.buf, &|c: char| {
+      c.is_alphabetic() || c.is_ascii_digit() || c == '_' || c == '$'
     })?;
 
-    let tk = match buf.as_str() {
+    let tk = match self.buf.as_str() {
       "true" => TokenKind::BooleanLiteral(true),
       "false" => TokenKind::BooleanLiteral(false),
       "null" => TokenKind::NullLiteral,
       slice => {
         if let Ok(keyword) = slice.parse() {
-          if cursor.strict_mode() && keyword == Keyword::With {
-            return Err(Error::Syntax(
-              "using 'with' statement not allow in strict mode".into(),
-              start_pos
-            ))
-          }
           TokenKind::Keyword(keyword)
         } else {
-          if cursor.strict_mode() && STRICT_FORBIDDEN_IDENTIFIERS.contains(&slice) {
-            return Err(Error::Syntax(
-              format!(
-                "using feature reserved keywod '{}' not allowed in strict mode",
-                slice
-              ).into(),
-              start_pos
-            ));
-          }
-          TokenKind::identifier(slice)
+          TokenKind::Identifier(self.interner.intern(slice))
         }
       }
     };
@@ -75,3 +76,51 @@ impl<R> Tokenizer<R> for Identifier {
     Ok(Token::new(tk, Span::new(start_pos, cursor.pos())))
   }
 }
+
+/// Private identifier lexing, such as `#field`.
+///
+/// Assumes the `#` is already consumed by the cursor.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-PrivateIdentifier
+pub(super) struct PrivateIdentifier<'i> {
+  interner: &'i mut Interner,
+  /// The buffer the name is read into, reused from one name to the next.
+  buf: &'i mut String,
+}
+
+impl<'i> PrivateIdentifier<'i> {
+  pub(super) fn new(interner: &'i mut Interner, buf: &'i mut String) -> Self {
+    Self { interner, buf }
+  }
+}
+
+impl<R> Tokenizer<R> for PrivateIdentifier<'_> {
+  fn lex(&mut self, cursor: &mut Cursor<R>, start_pos: Position) -> Result<Token, Error>
+    where
+      R: Read,
+  {
+    self.buf.clear();
+
+    match cursor.peek()? {
+      Some(c) if c.is_alphabetic() || c == '_' || c == '$' => {}
+      _ => {
+        return Err(Error::syntax(
+          "expected an identifier after '#'",
+          start_pos,
+        ))
+      }
+    }
+
+    cursor.take_while_pred(self.buf, &|c: char| {
+      c.is_alphabetic() || c.is_ascii_digit() || c == '_' || c == '$'
+    })?;
+
+    Ok(Token::new(
+      TokenKind::PrivateIdentifier(self.interner.intern(self.buf)),
+      Span::new(start_pos, cursor.pos()),
+    ))
+  }
+}
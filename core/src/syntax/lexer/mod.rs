@@ -6,6 +6,7 @@ mod cursor;
 mod error;
 mod string;
 mod operator;
+mod template;
 pub mod token;
 
 #[cfg(test)]
@@ -13,17 +14,19 @@ mod tests;
 
 use self::{
   cursor::Cursor,
-  identifier::Identifier,
+  identifier::{Identifier, PrivateIdentifier},
   spread::SpreadLiteral,
   comment::{SingleLineComment, MultiLineComment},
   string::{StringLiteral},
   number::NumberLiteral,
   operator::Operator,
+  template::TemplateLiteral,
 };
-use crate::syntax::ast::{Punctuator, Span};
+use crate::syntax::ast::{sym::Interner, Punctuator, Span};
 pub use crate::{syntax::ast::Position};
 pub use token::{Token, TokenKind};
 pub use error::Error;
+pub(crate) use identifier::{is_identifier_name, STRICT_FORBIDDEN_IDENTIFIERS};
 use std::io::Read;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -49,6 +52,24 @@ trait Tokenizer<R> {
 pub struct Lexer<R> {
   cursor: Cursor<R>,
   goal_symbol: InputElement,
+  /// Whether a purity annotation was lexed since the last significant token.
+  pure_annotation: bool,
+  /// The start positions of the tokens following a purity annotation, in source order.
+  annotated: Vec<Position>,
+  /// The start positions of the string literals with legacy octal escapes, in source order.
+  octal_escapes: Vec<Position>,
+  /// The start positions of the legacy octal numeric literals, in source order.
+  legacy_octals: Vec<Position>,
+  /// The start positions and source text of the string literals not written as their value
+  /// between double quotes, in source order.
+  raw_strings: Vec<(Position, Box<str>)>,
+  /// The braces and template substitutions open, `true` for a substitution, whose closing `}`
+  /// goes on with the template.
+  braces: Vec<bool>,
+  /// The names of the identifiers lexed so far.
+  interner: Interner,
+  /// The buffer the names of identifiers are read into.
+  name: String,
 }
 
 // TODO: Strict mode
@@ -76,6 +97,13 @@ impl<R> Lexer<R> {
     self.goal_symbol
   }
 
+  /// Sets whether the source being lexed is strict mode code, which forbids legacy octal
+  /// literals.
+  #[inline]
+  pub(crate) fn set_strict_mode(&mut self, strict_mode: bool) {
+    self.cursor.set_strict_mode(strict_mode)
+  }
+
   #[inline]
   pub fn new(reader: R) -> Self
     where R: Read
@@ -83,6 +111,14 @@ impl<R> Lexer<R> {
     Self {
       cursor: Cursor::new(reader),
       goal_symbol: Default::default(),
+      pure_annotation: false,
+      annotated: Vec::new(),
+      octal_escapes: Vec::new(),
+      legacy_octals: Vec::new(),
+      raw_strings: Vec::new(),
+      braces: Vec::new(),
+      interner: Interner::new(),
+      name: String::new(),
     }
   }
 
@@ -97,7 +133,12 @@ impl<R> Lexer<R> {
         }
         '*' => {
           self.cursor.next_char()?.expect("* toekn vanished");
-          MultiLineComment.lex(&mut self.cursor, start)
+          let mut comment = MultiLineComment::default();
+          let token = comment.lex(&mut self.cursor, start)?;
+          if comment.is_pure_annotation() {
+            self.pure_annotation = true;
+          }
+          Ok(token)
         }
         ch => {
           match self.get_goal() {
@@ -127,6 +168,20 @@ impl<R> Lexer<R> {
     }
   }
 
+  /// Lexes a part of a template literal, starting with a backtick if `head` or else with the
+  /// `}` closing a substitution.
+  fn lex_template(&mut self, head: bool, start: Position) -> Result<Token, Error>
+    where R: Read,
+  {
+    let token = TemplateLiteral::new(head).lex(&mut self.cursor, start)?;
+    if let TokenKind::TemplateLiteral(template) = token.kind() {
+      if !template.is_tail() {
+        self.braces.push(true);
+      }
+    }
+    Ok(token)
+  }
+
   /// Retrieves the next token from the lexer
   pub fn next(&mut self) -> Result<Option<Token>, Error>
     where
@@ -150,10 +205,31 @@ impl<R> Lexer<R> {
         TokenKind::LineTerminator,
         Span::new(start, self.cursor.pos()),
       )),
-      '"' | '\'' => StringLiteral::new(next_chr).lex(&mut self.cursor, start),
-      _ if next_chr.is_digit(10) => NumberLiteral::new(next_chr).lex(&mut self.cursor, start),
+      '"' | '\'' => {
+        let mut string = StringLiteral::new(next_chr);
+        let token = string.lex(&mut self.cursor, start)?;
+        if string.has_legacy_octal_escape() && self.octal_escapes.last() < Some(&start) {
+          self.octal_escapes.push(start);
+        }
+        if let TokenKind::StringLiteral(value) = token.kind() {
+          let raw = string.raw();
+          let plain = raw.starts_with('"') && raw[1..raw.len() - 1] == **value;
+          if !plain && self.raw_strings.last().map(|(position, _)| position) < Some(&start) {
+            self.raw_strings.push((start, raw.into()));
+          }
+        }
+        Ok(token)
+      }
+      _ if next_chr.is_digit(10) => {
+        let mut number = NumberLiteral::new(next_chr);
+        let token = number.lex(&mut self.cursor, start)?;
+        if number.has_legacy_octal() && self.legacy_octals.last() < Some(&start) {
+          self.legacy_octals.push(start);
+        }
+        Ok(token)
+      }
       _ if next_chr.is_alphabetic() || next_chr == '$' || next_chr == '_' => {
-        Identifier::new(next_chr).lex(&mut self.cursor, start)
+        Identifier::new(next_chr, &mut self.interner, &mut self.name).lex(&mut self.cursor, start)
       }
       ';' => Ok(Token::new(
         Punctuator::Semicolon.into(),
@@ -176,10 +252,15 @@ impl<R> Lexer<R> {
         Punctuator::Comma.into(),
         Span::new(start, self.cursor.pos())
       )),
-      '{' => Ok(Token::new(
-        Punctuator::OpenBlock.into(),
-        Span::new(start, self.cursor.pos())
-      )),
+      '`' => self.lex_template(true, start),
+      '{' => {
+        self.braces.push(false);
+        Ok(Token::new(
+          Punctuator::OpenBlock.into(),
+          Span::new(start, self.cursor.pos())
+        ))
+      }
+      '}' if self.braces.pop() == Some(true) => self.lex_template(false, start),
       '}' => Ok(Token::new(
         Punctuator::CloseBlock.into(),
         Span::new(start, self.cursor.pos())
@@ -192,29 +273,67 @@ impl<R> Lexer<R> {
         Punctuator::CloseBracket.into(),
         Span::new(start, self.cursor.pos())
       )),
-      '?' => Ok(Token::new(
-        Punctuator::Question.into(),
-        Span::new(start, self.cursor.pos())
-      )),
       '/' => self.lex_slash_token(start),
-      '=' | '*' | '+' | '-' | '%' | '|' | '&' | '^' | '<' | '>' | '!' | '~' => {
+      '#' => PrivateIdentifier::new(&mut self.interner, &mut self.name).lex(&mut self.cursor, start),
+      '=' | '*' | '+' | '-' | '%' | '|' | '&' | '^' | '<' | '>' | '!' | '~' | '?' => {
         Operator::new(next_chr).lex(&mut self.cursor, start)
       }
       _ => {
-        let details = format!(
-          "unexpected '{}' at line {}, column {}",
-          next_chr,
-          start.line_number(),
-          start.column_number()
-        );
+        let details = format!("unexpected '{}'", next_chr);
         Err(Error::syntax(details, start))
       }
     }?;
 
-    if token.kind() == &TokenKind::Comment {
-      self.next()
-    } else {
-      Ok(Some(token))
+    match token.kind() {
+      TokenKind::Comment => self.next(),
+      TokenKind::LineTerminator => Ok(Some(token)),
+      _ => {
+        if self.pure_annotation {
+          self.pure_annotation = false;
+          let start = token.span().start();
+          if self.annotated.last() < Some(&start) {
+            self.annotated.push(start);
+          }
+        }
+        Ok(Some(token))
+      }
+    }
+  }
+
+  /// Gets the position of the next character to lex, the end of the input once it has all
+  /// been lexed.
+  pub(crate) fn position(&self) -> Position {
+    self.cursor.pos()
+  }
+
+  /// Returns `true` if the token starting at the given position follows a purity annotation,
+  /// `/*#__PURE__*/` or `/*@__PURE__*/`.
+  pub(crate) fn is_pure_annotated(&self, position: Position) -> bool {
+    self.annotated.binary_search(&position).is_ok()
+  }
+
+  /// Returns `true` if the string literal starting at the given position contains a legacy
+  /// octal escape sequence, which is not allowed in strict mode code.
+  pub(crate) fn has_octal_escape(&self, position: Position) -> bool {
+    self.octal_escapes.binary_search(&position).is_ok()
+  }
+
+  /// Returns `true` if the numeric literal starting at the given position is a legacy octal
+  /// literal, or a decimal literal with a leading zero, which are not allowed in strict mode
+  /// code.
+  pub(crate) fn has_legacy_octal(&self, position: Position) -> bool {
+    self.legacy_octals.binary_search(&position).is_ok()
+  }
+
+  /// Gets the source text of the string literal starting at the given position, with its
+  /// quotes and escape sequences.
+  pub(crate) fn raw_string(&self, position: Position, value: &str) -> Box<str> {
+    match self
+      .raw_strings
+      .binary_search_by_key(&position, |(start, _)| *start)
+    {
+      Ok(index) => self.raw_strings[index].1.clone(),
+      Err(_) => format!("\"{}\"", value).into(),
     }
   }
 }
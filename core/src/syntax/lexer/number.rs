@@ -20,12 +20,23 @@ use std::{io::Read, str::FromStr};
 #[derive(Debug, Clone, Copy)]
 pub(super) struct NumberLiteral {
   init: char,
+  /// Whether the literal is a legacy octal literal, such as `010`, or a decimal literal with a
+  /// leading zero, such as `09`, which are not allowed in strict mode code.
+  legacy_octal: bool,
 }
 
 impl NumberLiteral {
   /// Creates a new string literal lexer.
   pub(super) fn new(init: char) -> Self {
-    Self { init }
+    Self {
+      init,
+      legacy_octal: false,
+    }
+  }
+
+  /// Returns `true` if the lexed literal is only allowed in sloppy mode code.
+  pub(super) fn has_legacy_octal(&self) -> bool {
+    self.legacy_octal
   }
 }
 
@@ -220,6 +231,7 @@ impl<R> Tokenizer<R> for NumberLiteral {
                 buf.push(cursor.next_char()?.expect("'0' character vanished"));
 
                 kind = NumericKind::Integer(8);
+                self.legacy_octal = true;
               }
             } else if ch.is_digit(10) {
               // Indicates a numerical digit comes after then 0 but it isn't an octal digit
@@ -232,6 +244,7 @@ impl<R> Tokenizer<R> for NumberLiteral {
                 ));
               } else {
                 buf.push(cursor.next_char()?.expect("Number digit vanished"));
+                self.legacy_octal = true;
               }
             } // Else indicates that the symbol is a non-number.
           }
@@ -8,7 +8,6 @@ macro_rules! vop {
       None => Err(Error::syntax("abrupt end - could not preview next value as part of the operator", $cursor.pos())),
       Some('=') => {
         $cursor.next_char()?.expect("= token vanished");
-        $cursor.next_column();
         $assign_op
       },
       Some(_) => $op,
@@ -19,12 +18,10 @@ macro_rules! vop {
       None => Err(Error::syntax("abrupt end - could not preview next value as part of the operator", $cursor.pos())),
       Some('=') => {
         $cursor.next_char()?.expect("= token vanished");
-        $cursor.next_column();
         $assign_op
       },
       $($case => {
         $cursor.next_char()?.expect("token vanished");
-        $cursor.next_column();
         $block
       })+,
       _ => $op
@@ -34,7 +31,6 @@ macro_rules! vop {
     match $cursor.peek().ok_or_else(|| Error::syntax("could not preview next value", $cursor.pos()))? {
         $($case => {
             $cursor.next_char()?;
-            $cursor.next_column();
             $block
         })+,
         _ => $op
@@ -100,11 +96,30 @@ impl<R> Tokenizer<R> for Operator {
         Ok(Punctuator::Mod)
       ),
       '|' => op!(cursor, start_pos, Ok(Punctuator::AssignOr), Ok(Punctuator::Or), {
-          Some('|') => Ok(Punctuator::BoolOr)
+          Some('|') => vop!(cursor, Ok(Punctuator::AssignBoolOr), Ok(Punctuator::BoolOr))
       }),
       '&' => op!(cursor, start_pos, Ok(Punctuator::AssignAnd), Ok(Punctuator::And), {
-          Some('&') => Ok(Punctuator::BoolAnd)
+          Some('&') => vop!(cursor, Ok(Punctuator::AssignBoolAnd), Ok(Punctuator::BoolAnd))
       }),
+      '?' => {
+        let punc = match cursor.peek()? {
+          Some('?') => {
+            cursor.next_char()?.expect("? token vanished");
+            if cursor.next_is('=')? {
+              Punctuator::AssignCoalesce
+            } else {
+              Punctuator::Coalesce
+            }
+          }
+          // `a?.5:b` is a conditional expression, not an optional chain.
+          Some('.') if !cursor.peek_n(1)?.is_some_and(|c| c.is_ascii_digit()) => {
+            cursor.next_char()?.expect(". token vanished");
+            Punctuator::Optional
+          }
+          _ => Punctuator::Question,
+        };
+        Ok(Token::new(punc.into(), Span::new(start_pos, cursor.pos())))
+      }
       '^' => op!(
         cursor,
         start_pos,
@@ -10,9 +10,14 @@ use std::{
   str,
 };
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub(super) struct StringLiteral {
   terminator: StringTerminator,
+  /// The source text of the literal, with its quotes and escape sequences.
+  raw: String,
+  /// Whether the literal contains a legacy octal escape sequence, such as `\07`, or one of the
+  /// non-octal decimal escapes `\8` and `\9`, which are not allowed in strict mode code.
+  legacy_octal: bool,
 }
 
 impl StringLiteral {
@@ -23,7 +28,20 @@ impl StringLiteral {
       _ => unreachable!(),
     };
 
-    Self { terminator }
+    Self {
+      terminator,
+      raw: init.to_string(),
+      legacy_octal: false,
+    }
+  }
+
+  /// Gets the source text of the lexed literal, with its quotes and escape sequences.
+  pub(super) fn raw(&self) -> &str {
+    &self.raw
+  }
+
+  pub(super) fn has_legacy_octal_escape(&self) -> bool {
+    self.legacy_octal
   }
 }
 
@@ -47,6 +65,7 @@ impl<R> Tokenizer<R> for StringLiteral {
           "unterminated string literal"
         ))
       })?;
+      self.raw.push(next_chr);
 
       match next_chr {
         '\'' if self.terminator == StringTerminator::SingleQuote => {
@@ -62,6 +81,7 @@ impl<R> Tokenizer<R> for StringLiteral {
               "unterminated escap sequence in string literal",
             ))
           })?;
+          self.raw.push(escape);
 
           if escape != '\n' {
             match escape {
@@ -70,12 +90,47 @@ impl<R> Tokenizer<R> for StringLiteral {
               't' => buf.push('\t' as u16),
               'b' => buf.push('\x08' as u16),
               'f' => buf.push('\x0c' as u16),
-              '0' => buf.push('\0' as u16),
+              '0' if !matches!(cursor.peek()?, Some('0'..='9')) => buf.push('\0' as u16),
+              '0'..='7' => {
+                if cursor.strict_mode() {
+                  return Err(Error::syntax(
+                    "octal escape sequences are not allowed in strict mode",
+                    next_chr_start,
+                  ));
+                }
+                self.legacy_octal = true;
+
+                // A leading digit from 0 to 3 takes up to two more octal digits, 4 to 7 one more.
+                let mut code_point = escape.to_digit(8).expect("not an octal digit");
+                let len = if code_point < 4 { 2 } else { 1 };
+                for _ in 0..len {
+                  match cursor.peek()?.and_then(|ch| ch.to_digit(8)) {
+                    Some(digit) => {
+                      let digit_chr = cursor.next_char()?.expect("octal digit vanished");
+                      self.raw.push(digit_chr);
+                      code_point = code_point * 8 + digit;
+                    }
+                    None => break,
+                  }
+                }
+                buf.push(code_point as u16);
+              }
+              '8' | '9' => {
+                if cursor.strict_mode() {
+                  return Err(Error::syntax(
+                    "\\8 and \\9 are not allowed in strict mode",
+                    next_chr_start,
+                  ));
+                }
+                self.legacy_octal = true;
+                buf.push(escape as u16);
+              }
               'x' => {
                 let mut code_point_utf8_bytes = [0u8; 2];
                 cursor.fill_bytes(&mut code_point_utf8_bytes)?;
                 let code_point_str = str::from_utf8(&code_point_utf8_bytes)
                   .expect("malformed Hexadecimal character escape sequence");
+                self.raw.push_str(code_point_str);
                 let code_point = u16::from_str_radix(&code_point_str, 16).map_err(|_| {
                   Error::syntax(
                     "invalid Hexadecima escape sequence",
@@ -86,63 +141,60 @@ impl<R> Tokenizer<R> for StringLiteral {
                 buf.push(code_point);
               },
               'u' => {
-                // Support \u{X..X}
-                if cursor.next_is('{')? {
-                  cursor.next_char()?.expect("{ character vanished");
-
+                let code_point = if cursor.next_is('{')? {
+                  // `\u{X..X}`, with any number of hexadecimal digits.
                   let mut code_point_str = String::with_capacity(6);
-                  cursor.take_until('}', &mut code_point_str)?;
-
-                  // Consume the '}'
-                  cursor.next_char()?.expect("} charater vanished");
-
-                  let code_point = u32::from_str_radix(&code_point_str, 16)
-                    .map_err(|_| {
-                      Error::syntax(
-                        "malformed Unicode character escape sequence",
-                        cursor.pos(),
-                      )
-                    })?;
-
-                  if code_point > 0x10_FFFF {
+                  cursor.take_while_pred(&mut code_point_str, &|ch: char| ch.is_ascii_hexdigit())?;
+                  if code_point_str.is_empty() || !cursor.next_is('}')? {
                     return Err(Error::syntax(
-                      "Unicode codepoint must not be greater then 0x10FFFF in escape sequence",
-                      cursor.pos()
+                      "malformed Unicode character escape sequence",
+                      cursor.pos(),
                     ));
-                  } else if code_point <= 65335 {
-                    buf.push(code_point as u16);
-                  } else {
-                    let cu1 = ((code_point - 65535) / 1024 + 0xD800) as u16;
-                    let cu2 = ((code_point - 65535) % 1024 + 0xD800) as u16;
-                    buf.push(cu1);
-                    buf.push(cu2);
+                  }
+                  self.raw.push('{');
+                  self.raw.push_str(&code_point_str);
+                  self.raw.push('}');
+
+                  match u32::from_str_radix(&code_point_str, 16) {
+                    Ok(code_point) if code_point <= 0x10_FFFF => code_point,
+                    _ => {
+                      return Err(Error::syntax(
+                        "Unicode codepoint must not be greater then 0x10FFFF in escape sequence",
+                        cursor.pos(),
+                      ))
+                    }
                   }
                 } else {
-                  let mut code_point_utf8_bytes = [0u8; 4];
-                  cursor.fill_bytes(&mut code_point_utf8_bytes)?;
-
-                  let code_point_str = str::from_utf8(&code_point_utf8_bytes)
-                    .expect("malformed Unicode character escape sequence");
-                  let code_point = u16::from_str_radix(code_point_str, 16)
-                    .map_err(|_| {
-                      Error::syntax(
-                        "incalid Unicode escape sequence",
-                      cursor.pos()
-                      )
-                    })?;
+                  // `\uXXXX`, with exactly four hexadecimal digits.
+                  let mut code_point = 0;
+                  for _ in 0..4 {
+                    match cursor.peek()?.and_then(|ch| ch.to_digit(16)) {
+                      Some(digit) => {
+                        let digit_chr = cursor.next_char()?.expect("hex digit vanished");
+                        self.raw.push(digit_chr);
+                        code_point = code_point * 16 + digit;
+                      }
+                      None => {
+                        return Err(Error::syntax(
+                          "invalid Unicode escape sequence",
+                          cursor.pos(),
+                        ))
+                      }
+                    }
+                  }
+                  code_point
+                };
 
-                    buf.push(code_point);
+                // Code points past the BMP take a surrogate pair, lone surrogates are kept.
+                match char::from_u32(code_point) {
+                  Some(ch) => buf.extend_from_slice(ch.encode_utf16(&mut [0u16; 2])),
+                  None => buf.push(code_point as u16),
                 }
               },
               '\'' | '"' | '\\' => buf.push(escape as u16),
               ch => {
-                let details = format!(
-                  "invalid escape sequence `{}` at line {}, column {}",
-                  next_chr_start.line_number(),
-                  next_chr_start.column_number(),
-                  ch
-                );
-                return Err(Error::syntax(details, cursor.pos()))
+                let details = format!("invalid escape sequence `\\{}`", ch);
+                return Err(Error::syntax(details, next_chr_start))
               }
             }
           }
@@ -0,0 +1,183 @@
+use super::{Cursor, Error, Tokenizer};
+use crate::syntax::{
+  ast::{Position, Span},
+  lexer::{token::TemplateString, Token, TokenKind},
+};
+use std::io::{self, ErrorKind, Read};
+
+/// Lexes a part of a template literal, from its opening backtick or the `}` closing a
+/// substitution, up to its closing backtick or the `${` opening the next substitution.
+///
+/// The raw text keeps the escape sequences, with line terminators normalized to `\n`. The
+/// cooked value is `None` if an escape sequence is invalid, which is only an error outside of
+/// tagged templates, so the parser reports it.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-template-literal-lexical-components
+#[derive(Debug, Clone, Copy)]
+pub(super) struct TemplateLiteral {
+  head: bool,
+}
+
+impl TemplateLiteral {
+  /// Creates the lexer of a template part, `head` if it starts with a backtick.
+  pub(super) fn new(head: bool) -> Self {
+    Self { head }
+  }
+}
+
+impl<R> Tokenizer<R> for TemplateLiteral {
+  fn lex(&mut self, cursor: &mut Cursor<R>, start_pos: Position) -> Result<Token, Error>
+  where
+    R: Read,
+  {
+    let mut raw = String::new();
+    let mut cooked = Some(Vec::new());
+    let tail = loop {
+      let next_chr = cursor.next_char()?.ok_or_else(|| {
+        Error::from(io::Error::new(
+          ErrorKind::UnexpectedEof,
+          "unterminated template literal",
+        ))
+      })?;
+      match next_chr {
+        '`' => break true,
+        '$' if cursor.peek()? == Some('{') => {
+          cursor.next_char()?.expect("{ character vanished");
+          break false;
+        }
+        '\\' => {
+          raw.push('\\');
+          let escaped = escape(cursor, &mut raw)?;
+          cooked = cooked.and_then(|mut cooked: Vec<u16>| {
+            cooked.extend(escaped?);
+            Some(cooked)
+          });
+        }
+        // `\r` and `\r\n` are read as a single `\r`.
+        '\r' => {
+          raw.push('\n');
+          if let Some(cooked) = &mut cooked {
+            cooked.push('\n' as u16);
+          }
+        }
+        ch => {
+          raw.push(ch);
+          if let Some(cooked) = &mut cooked {
+            let mut buf = [0u16; 2];
+            cooked.extend_from_slice(ch.encode_utf16(&mut buf));
+          }
+        }
+      }
+    };
+
+    let cooked = cooked.map(|cooked| String::from_utf16_lossy(&cooked));
+    Ok(Token::new(
+      TokenKind::template_literal(TemplateString::new(cooked, raw, self.head, tail)),
+      Span::new(start_pos, cursor.pos()),
+    ))
+  }
+}
+
+/// Lexes the escape sequence following a `\`, pushing its source text to `raw`.
+///
+/// Returns the code units it stands for, `None` if it is invalid. Only the characters making up
+/// a valid sequence are consumed, the others are lexed as the text of the template.
+fn escape<R>(cursor: &mut Cursor<R>, raw: &mut String) -> Result<Option<Vec<u16>>, Error>
+where
+  R: Read,
+{
+  let escape = cursor.next_char()?.ok_or_else(|| {
+    Error::from(io::Error::new(
+      ErrorKind::UnexpectedEof,
+      "unterminated escape sequence in template literal",
+    ))
+  })?;
+  let code_unit = match escape {
+    // A line continuation stands for nothing.
+    '\r' | '\n' | '\u{2028}' | '\u{2029}' => {
+      raw.push(if escape == '\r' { '\n' } else { escape });
+      return Ok(Some(Vec::new()));
+    }
+    'n' => '\n' as u16,
+    'r' => '\r' as u16,
+    't' => '\t' as u16,
+    'b' => 0x08,
+    'f' => 0x0c,
+    'v' => 0x0b,
+    '0' if !cursor.peek()?.is_some_and(|ch| ch.is_ascii_digit()) => 0,
+    '0'..='9' => {
+      raw.push(escape);
+      return Ok(None);
+    }
+    'x' => {
+      raw.push('x');
+      return Ok(hex_digits(cursor, raw, 2)?.map(|code_unit| vec![code_unit as u16]));
+    }
+    'u' => {
+      raw.push('u');
+      let code_point = if cursor.peek()? == Some('{') {
+        cursor.next_char()?.expect("{ character vanished");
+        raw.push('{');
+        let mut digits = String::new();
+        while let Some(ch) = cursor.peek()?.filter(char::is_ascii_hexdigit) {
+          cursor.next_char()?.expect("hexadecimal digit vanished");
+          digits.push(ch);
+        }
+        raw.push_str(&digits);
+        if digits.is_empty() || cursor.peek()? != Some('}') {
+          return Ok(None);
+        }
+        cursor.next_char()?.expect("} character vanished");
+        raw.push('}');
+        match u32::from_str_radix(&digits, 16) {
+          Ok(code_point) if code_point <= 0x10_FFFF => code_point,
+          _ => return Ok(None),
+        }
+      } else {
+        match hex_digits(cursor, raw, 4)? {
+          Some(code_unit) => code_unit,
+          None => return Ok(None),
+        }
+      };
+      let mut buf = [0u16; 2];
+      return Ok(Some(match char::from_u32(code_point) {
+        Some(ch) => ch.encode_utf16(&mut buf).to_vec(),
+        // A lone surrogate.
+        None => vec![code_point as u16],
+      }));
+    }
+    ch => {
+      raw.push(ch);
+      let mut buf = [0u16; 2];
+      return Ok(Some(ch.encode_utf16(&mut buf).to_vec()));
+    }
+  };
+  raw.push(escape);
+  Ok(Some(vec![code_unit]))
+}
+
+/// Lexes `count` hexadecimal digits, `None` if there are fewer.
+fn hex_digits<R>(
+  cursor: &mut Cursor<R>,
+  raw: &mut String,
+  count: usize,
+) -> Result<Option<u32>, Error>
+where
+  R: Read,
+{
+  let mut value = 0;
+  for _ in 0..count {
+    match cursor.peek()?.and_then(|ch| ch.to_digit(16)) {
+      Some(digit) => {
+        let ch = cursor.next_char()?.expect("hexadecimal digit vanished");
+        raw.push(ch);
+        value = value * 16 + digit;
+      }
+      None => return Ok(None),
+    }
+  }
+  Ok(Some(value))
+}
@@ -1,5 +1,5 @@
 use super::*;
-use super::token::Numeric;
+use super::token::{Numeric, TemplateString};
 use crate::syntax::ast::Keyword;
 
 fn span(start: (u32, u32), end: (u32, u32)) -> Span {
@@ -60,6 +60,100 @@ fn check_string() {
   ];
 
   expect_tokens(&mut lexer, &expected);
+  assert_eq!(&*lexer.raw_string(Position::new(1, 1), "aaa"), "'aaa'");
+  assert_eq!(&*lexer.raw_string(Position::new(1, 7), "bbb"), "\"bbb\"");
+
+  // The source text keeps the escape sequences and line continuations.
+  let raw = "\"a\\x20\\u0062\\\nc\"";
+  let mut lexer = Lexer::new(raw.as_bytes());
+  expect_tokens(&mut lexer, &[TokenKind::string_literal("a bc")]);
+  assert_eq!(&*lexer.raw_string(Position::new(1, 1), "a bc"), raw);
+}
+
+#[test]
+fn check_unicode_escapes() {
+  let s = r"'\u{41}\u0042' '\u{1F600}\u{0000000043}' '\uD83D\uDE00' '\u{D800}'";
+  let mut lexer = Lexer::new(s.as_bytes());
+
+  let expected = [
+    TokenKind::string_literal("AB"),
+    TokenKind::string_literal("\u{1F600}C"),
+    TokenKind::string_literal("\u{1F600}"),
+    TokenKind::string_literal(String::from_utf16_lossy(&[0xD800])),
+  ];
+
+  expect_tokens(&mut lexer, &expected);
+  assert_eq!(
+    &*lexer.raw_string(Position::new(1, 16), "\u{1F600}C"),
+    r"'\u{1F600}\u{0000000043}'"
+  );
+
+  for invalid in &[r"'\u{}'", r"'\u{110000}'", r"'\u{41'", r"'\u{4G}'", r"'\u41'"] {
+    let mut lexer = Lexer::new(invalid.as_bytes());
+    assert!(lexer.next().is_err(), "lexed {}", invalid);
+  }
+}
+
+#[test]
+fn check_legacy_octal_escapes() {
+  let s = r"'\101\0\08\477\8' '\0a'";
+  let mut lexer = Lexer::new(s.as_bytes());
+
+  let expected = [
+    TokenKind::string_literal("A\u{0}\u{0}8\u{27}7\u{38}"),
+    TokenKind::string_literal("\u{0}a"),
+  ];
+
+  expect_tokens(&mut lexer, &expected);
+  assert!(lexer.has_octal_escape(Position::new(1, 1)));
+  assert!(!lexer.has_octal_escape(Position::new(1, 19)));
+
+  let mut lexer = Lexer::new(r"'\07'".as_bytes());
+  lexer.set_strict_mode(true);
+  assert!(lexer.next().is_err());
+}
+
+#[test]
+fn check_template() {
+  let s = "`a${b}c${ {d} }\\$` `\\u{61}\r\n`";
+  let mut lexer = Lexer::new(s.as_bytes());
+
+  let expected = [
+    TokenKind::template_literal(TemplateString::new(Some("a"), "a", true, false)),
+    TokenKind::identifier("b"),
+    TokenKind::template_literal(TemplateString::new(Some("c"), "c", false, false)),
+    TokenKind::Punctuator(Punctuator::OpenBlock),
+    TokenKind::identifier("d"),
+    TokenKind::Punctuator(Punctuator::CloseBlock),
+    TokenKind::template_literal(TemplateString::new(Some("$"), "\\$", false, true)),
+    TokenKind::template_literal(TemplateString::new(Some("a\n"), "\\u{61}\n", true, true)),
+  ];
+
+  expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn check_template_invalid_escape() {
+  let s = "`\\unicode \\1`";
+  let mut lexer = Lexer::new(s.as_bytes());
+
+  let expected = [TokenKind::template_literal(TemplateString::new(
+    None::<&str>,
+    "\\unicode \\1",
+    true,
+    true,
+  ))];
+
+  expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn check_template_span() {
+  let mut lexer = Lexer::new(&b"`a\nb${x}`"[..]);
+
+  assert_eq!(lexer.next().unwrap().unwrap().span(), span((1, 1), (2, 4)));
+  assert_eq!(lexer.next().unwrap().unwrap().span(), span((2, 4), (2, 5)));
+  assert_eq!(lexer.next().unwrap().unwrap().span(), span((2, 5), (2, 7)));
 }
 
 #[test]
@@ -220,7 +314,7 @@ fn check_positions() {
 
 #[test]
 fn check_positions_codepoint() {
-  let s = r#"console.log("hello world\u{{2764}}"); // Test"#;
+  let s = r#"console.log("hello world\u{2764}"); // Test"#;
   let mut lexer = Lexer::new(s.as_bytes());
 
   // First column is 1 (none zero column)
@@ -236,16 +330,42 @@ fn check_positions_codepoint() {
   // Open parethesis token should start on column 12.
   assert_eq!(lexer.next().unwrap().unwrap().span(), span((1, 12), (1, 13)));
 
-  // String literal should start on column 31
-  assert_eq!(lexer.next().unwrap().unwrap().span(), span((1, 13), (1, 34)));
+  // String literal should start on column 13, and span the 21 characters of its source.
+  let string = lexer.next().unwrap().unwrap();
+  assert_eq!(string.span(), span((1, 13), (1, 34)));
+  assert_eq!(string.kind(), &TokenKind::string_literal("hello world\u{2764}"));
 
-  // Close parethesis token should start on column 26.
+  // Close parethesis token should start on column 34.
   assert_eq!(lexer.next().unwrap().unwrap().span(), span((1, 34), (1, 35)));
 
-  // Semi colon token should start on 27
+  // Semi colon token should start on 35
   assert_eq!(lexer.next().unwrap().unwrap().span(), span((1, 35), (1, 36)));
 }
 
+#[test]
+fn check_positions_punctuators() {
+  let s = "a == b !== c ?? [...d]";
+  let mut lexer = Lexer::new(s.as_bytes());
+  let spans = (0..10)
+    .map(|_| lexer.next().unwrap().unwrap().span())
+    .collect::<Vec<_>>();
+  assert_eq!(
+    spans,
+    [
+      span((1, 1), (1, 2)),
+      span((1, 3), (1, 5)),
+      span((1, 6), (1, 7)),
+      span((1, 8), (1, 11)),
+      span((1, 12), (1, 13)),
+      span((1, 14), (1, 16)),
+      span((1, 17), (1, 18)),
+      span((1, 18), (1, 21)),
+      span((1, 21), (1, 22)),
+      span((1, 22), (1, 23)),
+    ]
+  );
+}
+
 #[test]
 fn check_line_numbers() {
   let s = "x\ny\n";
@@ -379,3 +499,26 @@ fn non_english_str() {
 
     expect_tokens(&mut lexer, &expected);
 }
+
+#[test]
+fn private_identifier() {
+  let s = "this.#x #y";
+  let mut lexer = Lexer::new(s.as_bytes());
+
+  let expected = [
+    TokenKind::Keyword(Keyword::This),
+    TokenKind::Punctuator(Punctuator::Dot),
+    TokenKind::private_identifier("x"),
+    TokenKind::private_identifier("y"),
+  ];
+
+  expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn private_identifier_without_name() {
+  let s = "# x";
+  let mut lexer = Lexer::new(s.as_bytes());
+
+  assert!(lexer.next().is_err());
+}
@@ -1,5 +1,5 @@
 use std::fmt::{Display, Formatter, Result};
-use crate::syntax::ast::{Keyword, Span, Punctuator};
+use crate::syntax::ast::{Keyword, Span, Punctuator, Sym};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -60,6 +60,62 @@ impl From<i32> for Numeric {
   }
 }
 
+/// A part of a template literal: the whole template if it has no substitutions, or the text
+/// before, between or after its substitutions.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug)]
+pub struct TemplateString {
+  cooked: Option<Box<str>>,
+  raw: Box<str>,
+  head: bool,
+  tail: bool,
+}
+
+impl TemplateString {
+  /// Creates a template part from its cooked value, its raw text, and whether it starts with a
+  /// backtick rather than a `}` and ends with a backtick rather than a `${`.
+  pub fn new<C, R>(cooked: Option<C>, raw: R, head: bool, tail: bool) -> Self
+  where
+    C: Into<Box<str>>,
+    R: Into<Box<str>>,
+  {
+    Self {
+      cooked: cooked.map(Into::into),
+      raw: raw.into(),
+      head,
+      tail,
+    }
+  }
+
+  /// Gets the value of the text, `None` if it has an invalid escape sequence.
+  pub fn cooked(&self) -> Option<&str> {
+    self.cooked.as_deref()
+  }
+
+  /// Gets the text as written, with line terminators normalized to `\n`.
+  pub fn raw(&self) -> &str {
+    &self.raw
+  }
+
+  /// Returns `true` if the part starts the template, with a backtick.
+  pub fn is_head(&self) -> bool {
+    self.head
+  }
+
+  /// Returns `true` if the part ends the template, with a backtick.
+  pub fn is_tail(&self) -> bool {
+    self.tail
+  }
+}
+
+impl Display for TemplateString {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    let open = if self.head { "`" } else { "}" };
+    let close = if self.tail { "`" } else { "${" };
+    write!(f, "{}{}{}", open, self.raw, close)
+  }
+}
+
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, PartialEq, Debug)]
 pub enum TokenKind {
@@ -68,7 +124,9 @@ pub enum TokenKind {
   /// The end of the file, 文件结尾
   EOF,
   /// 变量、函数等标识符
-  Identifier(Box<str>),
+  Identifier(Sym),
+  /// A private class member name such as `#field`, stored without the leading `#`.
+  PrivateIdentifier(Sym),
   /// 关键字
   Keyword(Keyword),
   /// NullLiteral
@@ -79,7 +137,8 @@ pub enum TokenKind {
   Punctuator(Punctuator),
   /// String literal
   StringLiteral(Box<str>),
-  TemplateLiteral(Box<str>),
+  /// A part of a template literal, see [`TemplateString`].
+  TemplateLiteral(TemplateString),
   /// 一般表达式
   // RegularExpression(String),
   /// Indicates the end of a line (`\n`).
@@ -126,11 +185,19 @@ impl TokenKind {
   /// Creates an `Identifier` token type.
   pub fn identifier<I>(ident: I) -> Self
   where
-      I: Into<Box<str>>,
+      I: Into<Sym>,
   {
       Self::Identifier(ident.into())
   }
 
+  /// Creates a `PrivateIdentifier` token type.
+  pub fn private_identifier<I>(ident: I) -> Self
+  where
+      I: Into<Sym>,
+  {
+      Self::PrivateIdentifier(ident.into())
+  }
+
   /// Creates a `Keyword` token kind.
   pub fn keyword(keyword: Keyword) -> Self {
       Self::Keyword(keyword)
@@ -158,11 +225,8 @@ impl TokenKind {
   }
 
   /// Creates a `TemplateLiteral` token type.
-  pub fn template_literal<S>(lit: S) -> Self
-  where
-      S: Into<Box<str>>,
-  {
-      Self::TemplateLiteral(lit.into())
+  pub fn template_literal(lit: TemplateString) -> Self {
+      Self::TemplateLiteral(lit)
   }
 
   /// Creates a `RegularExpressionLiteral` token kind.
@@ -191,6 +255,7 @@ impl Display for TokenKind {
       TokenKind::BooleanLiteral(val) => write!(f, "{}", val),
       TokenKind::EOF => write!(f, "end of file"),
       TokenKind::Identifier(ref ident) => write!(f, "{}", ident),
+      TokenKind::PrivateIdentifier(ref ident) => write!(f, "#{}", ident),
       TokenKind::Keyword(word) => write!(f, "{}", word),
       TokenKind::NullLiteral => write!(f, "null"),
       TokenKind::NumericLiteral(Numeric::Rational(num)) => write!(f, "{}", num),
@@ -0,0 +1,233 @@
+//! The configuration of the linter, read from the `[lint]` table of a `plastic.toml` file.
+
+use super::toml::{self, Value};
+use crate::syntax::diagnostics::Severity;
+use std::{
+  collections::{BTreeMap, BTreeSet},
+  error::Error as StdError,
+  fmt,
+};
+
+/// A set of global variables a script can expect from where it runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Env {
+  /// The globals of web browsers, such as `window` and `document`.
+  Browser,
+  /// The globals of Node.js, such as `process` and `require`.
+  Node,
+}
+
+impl Env {
+  /// Gets an environment by its name in a configuration file.
+  pub fn from_name(name: &str) -> Option<Self> {
+    match name {
+      "browser" => Some(Self::Browser),
+      "node" => Some(Self::Node),
+      _ => None,
+    }
+  }
+
+  /// Gets the name of the environment in a configuration file.
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::Browser => "browser",
+      Self::Node => "node",
+    }
+  }
+}
+
+impl fmt::Display for Env {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+/// The configuration of the linter: the severity of its rules and the globals scripts can use.
+///
+/// A `plastic.toml` file configures the linter in its `lint` table, and ignores the other
+/// tables:
+///
+/// ```toml
+/// [lint]
+/// env = ["browser"]
+/// globals = ["jQuery"]
+///
+/// [lint.rules]
+/// eqeqeq = "error"
+/// no-var = "off"
+/// ```
+///
+/// A rule is `"off"`, `"warn"` or `"error"`, or `0`, `1` or `2`. The rules not in the file have
+/// their default severity.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Config {
+  /// The configured severity of rules, `None` for the rules turned off.
+  rules: BTreeMap<Box<str>, Option<Severity>>,
+  envs: BTreeSet<Env>,
+  globals: BTreeSet<Box<str>>,
+}
+
+impl Config {
+  /// Creates a configuration with the default severity of every rule and no environment.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Reads a configuration from the text of a `plastic.toml` file.
+  pub fn from_toml(text: &str) -> Result<Self, ConfigError> {
+    let entries =
+      toml::parse(text).map_err(|(message, line)| ConfigError::Toml { message, line })?;
+    let mut config = Self::new();
+    for entry in entries {
+      let line = entry.line;
+      match (&entry.table[..], &entry.key[..]) {
+        ([lint], "env") if lint == "lint" => {
+          for name in strings(&entry.value, "lint.env", line)? {
+            let env = Env::from_name(&name).ok_or(ConfigError::Env { name, line })?;
+            config.envs.insert(env);
+          }
+        }
+        ([lint], "globals") if lint == "lint" => {
+          for name in strings(&entry.value, "lint.globals", line)? {
+            config.globals.insert(name.into());
+          }
+        }
+        ([lint, rules], name) if lint == "lint" && rules == "rules" => {
+          let severity = match &entry.value {
+            Value::String(level) if level == "off" => None,
+            Value::String(level) if level == "warn" => Some(Severity::Warning),
+            Value::String(level) if level == "error" => Some(Severity::Error),
+            Value::Integer(0) => None,
+            Value::Integer(1) => Some(Severity::Warning),
+            Value::Integer(2) => Some(Severity::Error),
+            _ => {
+              return Err(ConfigError::Value {
+                key: format!("lint.rules.{}", name),
+                expected: "\"off\", \"warn\" or \"error\"",
+                line,
+              })
+            }
+          };
+          config.rules.insert(name.into(), severity);
+        }
+        ([lint, ..], key) if lint == "lint" => {
+          let mut path = entry.table.join(".");
+          path.push('.');
+          path.push_str(key);
+          return Err(ConfigError::Key { key: path, line });
+        }
+        _ => {}
+      }
+    }
+    Ok(config)
+  }
+
+  /// Sets the severity of a rule, `None` turning it off.
+  pub fn with_rule<N>(mut self, name: N, severity: Option<Severity>) -> Self
+  where
+    N: Into<Box<str>>,
+  {
+    self.rules.insert(name.into(), severity);
+    self
+  }
+
+  /// Adds a global variable scripts can use.
+  pub fn with_global<N>(mut self, name: N) -> Self
+  where
+    N: Into<Box<str>>,
+  {
+    self.globals.insert(name.into());
+    self
+  }
+
+  /// Adds the globals of an environment.
+  pub fn with_env(mut self, env: Env) -> Self {
+    self.envs.insert(env);
+    self
+  }
+
+  /// Gets the severity of a rule, or `default` if it is not configured.
+  pub fn severity(&self, rule: &str, default: Option<Severity>) -> Option<Severity> {
+    self.rules.get(rule).copied().unwrap_or(default)
+  }
+
+  /// Iterates over the configured rules and their severity, by name.
+  pub fn rules(&self) -> impl Iterator<Item = (&str, Option<Severity>)> {
+    self
+      .rules
+      .iter()
+      .map(|(name, severity)| (&name[..], *severity))
+  }
+
+  /// Iterates over the environments.
+  pub fn envs(&self) -> impl Iterator<Item = Env> + '_ {
+    self.envs.iter().copied()
+  }
+
+  /// Iterates over the added global variables.
+  pub fn globals(&self) -> impl Iterator<Item = &str> {
+    self.globals.iter().map(|name| &name[..])
+  }
+
+  /// Returns `true` if a global variable is added, by name or by an environment.
+  pub fn is_global(&self, name: &str) -> bool {
+    self.globals.contains(name) || self.envs.iter().any(|&env| super::globals::is_env(env, name))
+  }
+}
+
+/// Reads an array of strings.
+fn strings(value: &Value, key: &str, line: usize) -> Result<Vec<String>, ConfigError> {
+  let error = || ConfigError::Value {
+    key: key.to_owned(),
+    expected: "an array of strings",
+    line,
+  };
+  match value {
+    Value::Array(elements) => elements
+      .iter()
+      .map(|element| match element {
+        Value::String(string) => Ok(string.clone()),
+        _ => Err(error()),
+      })
+      .collect(),
+    _ => Err(error()),
+  }
+}
+
+/// An error reading a configuration file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+  /// The text is not valid TOML, or uses what the reader does not support.
+  Toml {
+    message: &'static str,
+    /// The line of the error, from 1.
+    line: usize,
+  },
+  /// A key of the `lint` table has a value of the wrong type.
+  Value {
+    key: String,
+    expected: &'static str,
+    line: usize,
+  },
+  /// A key of the `lint` table is unknown.
+  Key { key: String, line: usize },
+  /// An environment is unknown.
+  Env { name: String, line: usize },
+}
+
+impl fmt::Display for ConfigError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Toml { message, line } => write!(f, "invalid TOML on line {}: {}", line, message),
+      Self::Value {
+        key,
+        expected,
+        line,
+      } => write!(f, "`{}` must be {} (line {})", key, expected, line),
+      Self::Key { key, line } => write!(f, "unknown key `{}` (line {})", key, line),
+      Self::Env { name, line } => write!(f, "unknown environment `{}` (line {})", name, line),
+    }
+  }
+}
+
+impl StdError for ConfigError {}
@@ -0,0 +1,158 @@
+//! The global variables scripts can use without declaring them.
+
+use super::config::Env;
+
+/// The globals of the language, in every environment.
+const BUILTINS: &[&str] = &[
+  "AggregateError",
+  "Array",
+  "ArrayBuffer",
+  "Atomics",
+  "BigInt",
+  "BigInt64Array",
+  "BigUint64Array",
+  "Boolean",
+  "DataView",
+  "Date",
+  "Error",
+  "EvalError",
+  "FinalizationRegistry",
+  "Float32Array",
+  "Float64Array",
+  "Function",
+  "Infinity",
+  "Int16Array",
+  "Int32Array",
+  "Int8Array",
+  "Intl",
+  "JSON",
+  "Map",
+  "Math",
+  "NaN",
+  "Number",
+  "Object",
+  "Promise",
+  "Proxy",
+  "RangeError",
+  "ReferenceError",
+  "Reflect",
+  "RegExp",
+  "Set",
+  "SharedArrayBuffer",
+  "String",
+  "Symbol",
+  "SyntaxError",
+  "TypeError",
+  "URIError",
+  "Uint16Array",
+  "Uint32Array",
+  "Uint8Array",
+  "Uint8ClampedArray",
+  "WeakMap",
+  "WeakRef",
+  "WeakSet",
+  "decodeURI",
+  "decodeURIComponent",
+  "encodeURI",
+  "encodeURIComponent",
+  "escape",
+  "eval",
+  "globalThis",
+  "isFinite",
+  "isNaN",
+  "parseFloat",
+  "parseInt",
+  "undefined",
+  "unescape",
+];
+
+/// The globals shared by browsers and Node.js.
+const COMMON: &[&str] = &[
+  "AbortController",
+  "AbortSignal",
+  "Blob",
+  "BroadcastChannel",
+  "TextDecoder",
+  "TextEncoder",
+  "URL",
+  "URLSearchParams",
+  "WebAssembly",
+  "atob",
+  "btoa",
+  "clearInterval",
+  "clearTimeout",
+  "console",
+  "crypto",
+  "fetch",
+  "performance",
+  "queueMicrotask",
+  "setInterval",
+  "setTimeout",
+  "structuredClone",
+];
+
+/// The globals of web browsers.
+const BROWSER: &[&str] = &[
+  "CustomEvent",
+  "Document",
+  "Element",
+  "Event",
+  "EventTarget",
+  "File",
+  "FileReader",
+  "FormData",
+  "HTMLElement",
+  "Headers",
+  "Image",
+  "IntersectionObserver",
+  "MutationObserver",
+  "Node",
+  "Request",
+  "ResizeObserver",
+  "Response",
+  "WebSocket",
+  "Worker",
+  "XMLHttpRequest",
+  "alert",
+  "cancelAnimationFrame",
+  "confirm",
+  "document",
+  "history",
+  "localStorage",
+  "location",
+  "navigator",
+  "prompt",
+  "requestAnimationFrame",
+  "screen",
+  "self",
+  "sessionStorage",
+  "window",
+];
+
+/// The globals of Node.js, with those of CommonJS modules.
+const NODE: &[&str] = &[
+  "Buffer",
+  "__dirname",
+  "__filename",
+  "clearImmediate",
+  "exports",
+  "global",
+  "module",
+  "process",
+  "require",
+  "setImmediate",
+];
+
+/// Returns `true` if a name is a global of the language.
+pub(super) fn is_builtin(name: &str) -> bool {
+  BUILTINS.contains(&name)
+}
+
+/// Returns `true` if a name is a global of an environment.
+pub(super) fn is_env(env: Env, name: &str) -> bool {
+  let globals = match env {
+    Env::Browser => BROWSER,
+    Env::Node => NODE,
+  };
+  COMMON.contains(&name) || globals.contains(&name)
+}
@@ -0,0 +1,494 @@
+//! Linting: finding code that is valid but probably wrong, and fixing it.
+//!
+//! A [`Linter`] parses a script or a module, analyzes its scopes and runs each enabled [`Rule`]
+//! over it. Rules report [`Problem`]s, diagnostics with an optional [`Fix`] made of text edits
+//! that [`apply_fixes`] applies to the source:
+//!
+//! ```
+//! use plastic::syntax::lint::{apply_fixes, Config, Linter};
+//!
+//! let source = "var answer = 42;\nif (typeof answer == 'number') {\n  report(answer);\n}\n";
+//! let config = Config::new().with_global("report");
+//! let problems = Linter::new(config).lint_module(source).unwrap();
+//! let messages = problems
+//!   .iter()
+//!   .map(|problem| problem.diagnostic().message())
+//!   .collect::<Vec<_>>();
+//! assert_eq!(
+//!   messages,
+//!   [
+//!     "Unexpected var, use let or const instead.",
+//!     "Expected '===' and instead saw '=='."
+//!   ]
+//! );
+//!
+//! let fixed = apply_fixes(source, problems.iter().filter_map(|problem| problem.fix()));
+//! assert_eq!(
+//!   fixed,
+//!   "let answer = 42;\nif (typeof answer === 'number') {\n  report(answer);\n}\n"
+//! );
+//! ```
+//!
+//! The severity of each rule comes from its default, or from the [`Config`], usually read from
+//! a `plastic.toml` file. A comment turns rules off for the line after it:
+//!
+//! ```js
+//! // plastic-disable-next-line eqeqeq, no-undef -- compared with null and undefined
+//! if (value == null) {}
+//! ```
+//!
+//! Without rule names, every rule is turned off for that line. The text after `--` explains why.
+//!
+//! The starter rules are in [`rules`]. They point at keywords and operators, which have no span
+//! of their own in the syntax tree, so they find them in the tokens of the source, with the
+//! help of the [`Context`].
+
+mod config;
+mod globals;
+pub mod rules;
+#[cfg(test)]
+mod tests;
+mod toml;
+
+pub use self::config::{Config, ConfigError, Env};
+
+use crate::syntax::{
+  ast::{
+    node::{BinOp, Case, DeclarationList, ForInLoop, ForOfLoop, Node, PropertyDefinition},
+    Position, Span,
+  },
+  comments::Comment,
+  diagnostics::{Diagnostic, Severity},
+  edit::Edit,
+  lexer::Token,
+  locate::{Lines, Locations, Source},
+  parser::{ParseError, Parser},
+  scope::{BindingId, ReferenceId, ScopeTree},
+};
+use std::fmt;
+
+/// A check of the linter.
+///
+/// A rule looks at the syntax tree and the scopes through a [`Context`], and reports what it
+/// finds with [`Context::report`] or [`Context::report_with_fix`].
+pub trait Rule {
+  /// Gets the name of the rule, as used in configuration files and in disable comments.
+  fn name(&self) -> &'static str;
+
+  /// Gets the severity of the rule when it is not configured, `None` if it is off by default.
+  fn default_severity(&self) -> Option<Severity>;
+
+  /// Checks a script or a module.
+  fn check(&self, context: &mut Context<'_>);
+}
+
+/// A problem reported by a rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+  rule: &'static str,
+  diagnostic: Diagnostic,
+  fix: Option<Fix>,
+}
+
+impl Problem {
+  /// Gets the name of the rule that reported the problem.
+  pub fn rule(&self) -> &'static str {
+    self.rule
+  }
+
+  /// Gets the diagnostic describing the problem.
+  pub fn diagnostic(&self) -> &Diagnostic {
+    &self.diagnostic
+  }
+
+  /// Gets the fix of the problem, if the rule knows a safe one.
+  pub fn fix(&self) -> Option<&Fix> {
+    self.fix.as_ref()
+  }
+
+  /// Consumes the problem, returning its diagnostic.
+  pub fn into_diagnostic(self) -> Diagnostic {
+    self.diagnostic
+  }
+}
+
+/// A change of the source fixing a problem, made of edits applied together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+  message: Box<str>,
+  edits: Vec<Edit>,
+}
+
+impl Fix {
+  /// Creates a fix, with a message describing what it does.
+  pub fn new<M>(message: M, edits: Vec<Edit>) -> Self
+  where
+    M: Into<Box<str>>,
+  {
+    Self {
+      message: message.into(),
+      edits,
+    }
+  }
+
+  /// Gets the message describing the fix, such as "Replace '==' with '==='".
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+
+  /// Gets the edits of the fix.
+  pub fn edits(&self) -> &[Edit] {
+    &self.edits
+  }
+}
+
+/// Applies fixes to a source, returning the fixed text.
+///
+/// The fixes are applied in the order of their first edit. A fix with an edit overlapping an
+/// edit of a fix applied before it is left out, as a whole, so that running the linter again
+/// on the fixed text finds and fixes what is left.
+pub fn apply_fixes<'a, I>(source: &str, fixes: I) -> String
+where
+  I: IntoIterator<Item = &'a Fix>,
+{
+  let lines = Lines::new(source);
+  let range = |edit: &Edit| {
+    let start = lines.offset(source, edit.span().start());
+    (start, lines.offset(source, edit.span().end()).max(start))
+  };
+  let mut fixes = fixes
+    .into_iter()
+    .map(|fix| {
+      let mut edits = fix
+        .edits
+        .iter()
+        .map(|edit| (range(edit), edit.text()))
+        .collect::<Vec<_>>();
+      edits.sort_by_key(|&(range, _)| range);
+      edits
+    })
+    .filter(|edits| !edits.is_empty())
+    .collect::<Vec<_>>();
+  fixes.sort_by_key(|edits| edits[0].0);
+
+  let mut applied: Vec<((usize, usize), &str)> = Vec::new();
+  for edits in fixes {
+    let overlaps = |(start, end): (usize, usize)| {
+      applied.iter().any(|&((other_start, other_end), _)| {
+        start < other_end && other_start < end
+          || (start == end || other_start == other_end) && start == other_start
+      })
+    };
+    let conflicts = edits.windows(2).any(|pair| pair[1].0 .0 < pair[0].0 .1);
+    if !conflicts && !edits.iter().any(|&(range, _)| overlaps(range)) {
+      applied.extend(edits);
+    }
+  }
+
+  applied.sort_by_key(|&(range, _)| range);
+  let mut fixed = source.to_owned();
+  for ((start, end), text) in applied.into_iter().rev() {
+    fixed.replace_range(start..end, text);
+  }
+  fixed
+}
+
+/// What a rule sees of a script or a module, and where it reports problems.
+pub struct Context<'a> {
+  rule: &'static str,
+  severity: Severity,
+  source: &'a Source<'a>,
+  statements: &'a [Node],
+  module: bool,
+  scopes: &'a ScopeTree,
+  locations: &'a Locations,
+  config: &'a Config,
+  problems: &'a mut Vec<Problem>,
+}
+
+impl<'a> Context<'a> {
+  /// Gets the source text.
+  pub fn source(&self) -> &'a str {
+    self.source.text
+  }
+
+  /// Gets the statements of the script or the module.
+  pub fn statements(&self) -> &'a [Node] {
+    self.statements
+  }
+
+  /// Returns `true` when linting a module, `false` for a script.
+  pub fn is_module(&self) -> bool {
+    self.module
+  }
+
+  /// Gets the scopes of the script or the module.
+  pub fn scopes(&self) -> &'a ScopeTree {
+    self.scopes
+  }
+
+  /// Gets the configuration of the linter.
+  pub fn config(&self) -> &'a Config {
+    self.config
+  }
+
+  /// Gets the tokens of the source, without line terminators and comments.
+  pub fn tokens(&self) -> &'a [Token] {
+    &self.source.tokens
+  }
+
+  /// Gets the comments of the source, in order.
+  pub fn comments(&self) -> &'a [Comment<'a>] {
+    &self.source.comments
+  }
+
+  /// Gets the span of the first token of a statement.
+  pub fn statement_start(&self, node: &Node) -> Option<Span> {
+    self.token_span(self.locations.statement(node)?)
+  }
+
+  /// Gets the span of the `var`, `let` or `const` keyword of a declaration list.
+  pub fn declaration_keyword(&self, list: &DeclarationList) -> Option<Span> {
+    self.token_span(self.locations.declaration_keyword(list)?)
+  }
+
+  /// Gets the span of the `var`, `let` or `const` keyword in the head of a `for in` loop.
+  pub fn for_in_keyword(&self, stmt: &ForInLoop) -> Option<Span> {
+    self.token_span(self.locations.for_in_keyword(stmt)?)
+  }
+
+  /// Gets the span of the `var`, `let` or `const` keyword in the head of a `for of` loop.
+  pub fn for_of_keyword(&self, stmt: &ForOfLoop) -> Option<Span> {
+    self.token_span(self.locations.for_of_keyword(stmt)?)
+  }
+
+  /// Gets the span of the `case` or `default` keyword of a clause of a `switch` statement.
+  pub fn case_keyword(&self, case: &Case) -> Option<Span> {
+    self.token_span(self.locations.case(case)?)
+  }
+
+  /// Gets the span of the operator of an equality comparison, such as `==`.
+  pub fn equality_operator(&self, op: &BinOp) -> Option<Span> {
+    self.token_span(self.locations.operator(op)?)
+  }
+
+  /// Gets the span of the key of a property of an object literal, when it is not computed.
+  pub fn property_key(&self, property: &PropertyDefinition) -> Option<Span> {
+    self.token_span(self.locations.key(property)?)
+  }
+
+  /// Gets the span of the name of the first declaration of a binding.
+  pub fn binding_span(&self, binding: BindingId) -> Option<Span> {
+    self.locations.declaration(binding)
+  }
+
+  /// Gets the span of the name of a reference.
+  pub fn reference_span(&self, reference: ReferenceId) -> Option<Span> {
+    self.locations.reference(reference)
+  }
+
+  /// Gets the span of a token.
+  pub fn token_span(&self, index: usize) -> Option<Span> {
+    self.source.tokens.get(index).map(Token::span)
+  }
+
+  /// Gets the end of the statement list a statement is in: the end of the last token before
+  /// the brace closing the list, or of the last token of the source.
+  fn list_end(&self, node: &Node) -> Option<Position> {
+    let start = self.locations.statement(node)?;
+    let end = self.source.list_end(start);
+    Some(self.source.tokens[end].span().end())
+  }
+
+  /// Creates a diagnostic with the severity of the rule, and its name as the code.
+  pub fn diagnostic<M>(&self, message: M) -> Diagnostic
+  where
+    M: Into<Box<str>>,
+  {
+    Diagnostic::new(self.severity, message).with_code(self.rule)
+  }
+
+  /// Reports a problem.
+  pub fn report(&mut self, diagnostic: Diagnostic) {
+    self.problems.push(Problem {
+      rule: self.rule,
+      diagnostic,
+      fix: None,
+    });
+  }
+
+  /// Reports a problem with a fix.
+  pub fn report_with_fix(&mut self, diagnostic: Diagnostic, fix: Fix) {
+    self.problems.push(Problem {
+      rule: self.rule,
+      diagnostic,
+      fix: Some(fix),
+    });
+  }
+}
+
+impl fmt::Debug for Context<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Context")
+      .field("rule", &self.rule)
+      .field("severity", &self.severity)
+      .field("module", &self.module)
+      .finish()
+  }
+}
+
+/// Runs rules over scripts and modules.
+pub struct Linter {
+  config: Config,
+  rules: Vec<Box<dyn Rule>>,
+}
+
+impl Linter {
+  /// Creates a linter with the [starter rules](rules::starter).
+  pub fn new(config: Config) -> Self {
+    Self {
+      config,
+      rules: rules::starter(),
+    }
+  }
+
+  /// Adds a rule.
+  pub fn with_rule<R>(mut self, rule: R) -> Self
+  where
+    R: Rule + 'static,
+  {
+    self.rules.push(Box::new(rule));
+    self
+  }
+
+  /// Iterates over the rules, enabled or not.
+  pub fn rules(&self) -> impl Iterator<Item = &dyn Rule> {
+    self.rules.iter().map(|rule| &**rule)
+  }
+
+  /// Gets the configuration.
+  pub fn config(&self) -> &Config {
+    &self.config
+  }
+
+  /// Lints the source text of a script, returning the problems in source order.
+  pub fn lint_script(&self, source: &str) -> Result<Vec<Problem>, ParseError> {
+    let script = Parser::new(source.as_bytes()).parse_script()?;
+    let scopes = ScopeTree::script(&script);
+    Ok(self.lint(source, script.items(), &scopes, false))
+  }
+
+  /// Lints the source text of a module, returning the problems in source order.
+  pub fn lint_module(&self, source: &str) -> Result<Vec<Problem>, ParseError> {
+    let module = Parser::new(source.as_bytes()).parse_module()?;
+    let scopes = ScopeTree::module(&module);
+    Ok(self.lint(source, module.body().items(), &scopes, true))
+  }
+
+  fn lint(
+    &self,
+    text: &str,
+    statements: &[Node],
+    scopes: &ScopeTree,
+    module: bool,
+  ) -> Vec<Problem> {
+    let source = Source::new(text);
+    let locations = Locations::new(&source, statements, scopes);
+    let mut problems = Vec::new();
+    for rule in &self.rules {
+      let severity = match self.config.severity(rule.name(), rule.default_severity()) {
+        Some(severity) => severity,
+        None => continue,
+      };
+      rule.check(&mut Context {
+        rule: rule.name(),
+        severity,
+        source: &source,
+        statements,
+        module,
+        scopes,
+        locations: &locations,
+        config: &self.config,
+        problems: &mut problems,
+      });
+    }
+
+    let directives = source
+      .comments
+      .iter()
+      .filter_map(Directive::new)
+      .collect::<Vec<_>>();
+    problems.retain(|problem| {
+      let line = match problem.diagnostic.primary_span() {
+        Some(span) => span.start().line_number(),
+        None => return true,
+      };
+      !directives
+        .iter()
+        .any(|directive| directive.line == line && directive.disables(problem.rule))
+    });
+    problems.sort_by_key(|problem| {
+      problem
+        .diagnostic
+        .primary_span()
+        .map(|span| (span.start(), span.end()))
+    });
+    problems
+  }
+}
+
+/// Loads the starter rules, with no configuration.
+impl Default for Linter {
+  fn default() -> Self {
+    Self::new(Config::default())
+  }
+}
+
+impl fmt::Debug for Linter {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Linter")
+      .field("config", &self.config)
+      .field(
+        "rules",
+        &self
+          .rules
+          .iter()
+          .map(|rule| rule.name())
+          .collect::<Vec<_>>(),
+      )
+      .finish()
+  }
+}
+
+/// A `plastic-disable-next-line` comment.
+#[derive(Debug)]
+struct Directive<'a> {
+  /// The line the rules are turned off for.
+  line: u32,
+  /// The rules turned off, all of them if empty.
+  rules: Vec<&'a str>,
+}
+
+impl<'a> Directive<'a> {
+  const PREFIX: &'static str = "plastic-disable-next-line";
+
+  fn new(comment: &Comment<'a>) -> Option<Self> {
+    let text = comment.text().trim_start();
+    let rest = text.strip_prefix(Self::PREFIX)?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+      return None;
+    }
+    let rules = rest.split("--").next().unwrap_or_default();
+    Some(Self {
+      line: comment.span().end().line_number() + 1,
+      rules: rules
+        .split(|ch: char| ch == ',' || ch.is_whitespace())
+        .filter(|rule| !rule.is_empty())
+        .collect(),
+    })
+  }
+
+  fn disables(&self, rule: &str) -> bool {
+    self.rules.is_empty() || self.rules.contains(&rule)
+  }
+}
@@ -0,0 +1,91 @@
+//! `eqeqeq`: comparisons with `==` and `!=`, which convert their operands.
+
+use crate::syntax::{
+  ast::{
+    node::{BinOp, Node},
+    operator::{self, CompOp, UnaryOp},
+    visitor::{visit, Flow, NodePath, Path, Visitor},
+    Const,
+  },
+  diagnostics::{Label, Severity},
+  edit::Edit,
+  lint::{Context, Fix, Rule},
+};
+
+/// Requires `===` and `!==` instead of `==` and `!=`.
+///
+/// The fix replaces the operator when the operands always have the same type, so that the
+/// comparison gives the same result: when one of them is a `typeof` expression, or both are
+/// literals of the same type.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Eqeqeq;
+
+impl Rule for Eqeqeq {
+  fn name(&self) -> &'static str {
+    "eqeqeq"
+  }
+
+  fn default_severity(&self) -> Option<Severity> {
+    Some(Severity::Warning)
+  }
+
+  fn check(&self, context: &mut Context<'_>) {
+    let mut finder = Finder(Vec::new());
+    let _ = finder.visit_statements(context.statements(), &mut Path::new());
+    for (op, expected, actual) in finder.0 {
+      let span = match context.equality_operator(op) {
+        Some(span) => span,
+        None => continue,
+      };
+      let diagnostic = context
+        .diagnostic(format!(
+          "Expected '{}' and instead saw '{}'.",
+          expected, actual
+        ))
+        .with_label(Label::primary(span));
+      if same_type(op.lhs(), op.rhs()) {
+        let fix = Fix::new(
+          format!("Replace '{}' with '{}'", actual, expected),
+          vec![Edit::new(span, expected)],
+        );
+        context.report_with_fix(diagnostic, fix);
+      } else {
+        context.report(diagnostic);
+      }
+    }
+  }
+}
+
+/// Finds the comparisons with `==` and `!=`, with the operators to use instead.
+struct Finder<'ast>(Vec<(&'ast BinOp, &'static str, &'static str)>);
+
+impl<'ast> Visitor<'ast> for Finder<'ast> {
+  fn visit_bin_op(&mut self, op: &'ast BinOp, path: &mut NodePath<'ast>) -> Flow {
+    match op.op() {
+      operator::BinOp::Comp(CompOp::Equal) => self.0.push((op, "===", "==")),
+      operator::BinOp::Comp(CompOp::NotEqual) => self.0.push((op, "!==", "!=")),
+      _ => {}
+    }
+    visit::walk_bin_op(self, op, path)
+  }
+}
+
+/// Returns `true` if two operands always have the same type.
+fn same_type(lhs: &Node, rhs: &Node) -> bool {
+  let is_typeof = |node: &Node| matches!(node, Node::UnaryOp(op) if op.op() == UnaryOp::TypeOf);
+  if is_typeof(lhs) || is_typeof(rhs) {
+    return true;
+  }
+  match (lhs, rhs) {
+    (Node::Const(lhs, _), Node::Const(rhs, _)) => matches!(
+      (lhs, rhs),
+      (Const::String(_), Const::String(_))
+        | (Const::Num(_), Const::Num(_))
+        | (Const::Num(_), Const::Int(_))
+        | (Const::Int(_), Const::Num(_))
+        | (Const::Int(_), Const::Int(_))
+        | (Const::Bool(_), Const::Bool(_))
+    ),
+    _ => false,
+  }
+}
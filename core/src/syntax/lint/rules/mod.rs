@@ -0,0 +1,79 @@
+//! The starter rules of the linter.
+//!
+//! | Rule             | Default   | Fix                                                      |
+//! |------------------|-----------|----------------------------------------------------------|
+//! | `eqeqeq`         | warning   | `==` to `===` when both sides have the same type         |
+//! | `no-dupe-keys`   | error     |                                                          |
+//! | `no-fallthrough` | error     |                                                          |
+//! | `no-undef`       | error     |                                                          |
+//! | `no-unreachable` | error     |                                                          |
+//! | `no-unused-vars` | warning   |                                                          |
+//! | `no-var`         | warning   | `var` to `let` when the scoping stays the same           |
+//! | `prefer-const`   | warning   | `let` to `const` when no variable of the list is written |
+
+mod eqeqeq;
+mod no_dupe_keys;
+mod no_fallthrough;
+mod no_undef;
+mod no_unreachable;
+mod no_unused_vars;
+mod no_var;
+mod prefer_const;
+
+pub use self::{
+  eqeqeq::Eqeqeq, no_dupe_keys::NoDupeKeys, no_fallthrough::NoFallthrough, no_undef::NoUndef,
+  no_unreachable::NoUnreachable, no_unused_vars::NoUnusedVars, no_var::NoVar,
+  prefer_const::PreferConst,
+};
+
+use super::Rule;
+use crate::syntax::{
+  ast::node::{Node, Pattern},
+  scope::{BindingId, ScopeTree},
+};
+
+/// Creates the starter rules, in alphabetical order.
+pub fn starter() -> Vec<Box<dyn Rule>> {
+  vec![
+    Box::new(Eqeqeq),
+    Box::new(NoDupeKeys),
+    Box::new(NoFallthrough),
+    Box::new(NoUndef),
+    Box::new(NoUnreachable),
+    Box::new(NoUnusedVars),
+    Box::new(NoVar),
+    Box::new(PreferConst),
+  ]
+}
+
+/// Returns `true` if a statement always ends with a `return`, `throw`, `break` or `continue`,
+/// so that the statements after it never run.
+fn terminates(node: &Node) -> bool {
+  match node {
+    Node::Return(_) | Node::Throw(_) | Node::Break(_) | Node::Continue(_) => true,
+    Node::Block(block) => block.statements().iter().any(terminates),
+    Node::If(stmt) => stmt.else_node().is_some_and(|else_node| {
+      terminates(stmt.body()) && terminates(else_node)
+    }),
+    Node::Try(stmt) => {
+      let block = stmt.block().statements().iter().any(terminates);
+      let catch = stmt
+        .catch()
+        .is_none_or(|catch| catch.block().statements().iter().any(terminates));
+      let finally = stmt
+        .finally()
+        .is_some_and(|finally| finally.statements().iter().any(terminates));
+      block && catch || finally
+    }
+    _ => false,
+  }
+}
+
+/// Gets the bindings a pattern declares.
+fn bindings(pattern: &Pattern, scopes: &ScopeTree) -> Vec<BindingId> {
+  pattern
+    .bound_identifiers()
+    .into_iter()
+    .filter_map(|ident| scopes.declaration_at(ident.id()))
+    .collect()
+}
@@ -0,0 +1,101 @@
+//! `no-dupe-keys`: object literals defining a property twice.
+
+use crate::syntax::{
+  ast::{
+    node::{MethodDefinitionKind, Object, PropertyDefinition, PropertyName},
+    visitor::{visit, Flow, NodePath, Path, Visitor},
+  },
+  diagnostics::{Label, Severity},
+  lint::{Context, Rule},
+};
+use std::collections::HashMap;
+
+/// Reports the properties of an object literal with the name of a property before them, which
+/// they silently replace.
+///
+/// A getter and a setter with the same name define one property together, and are allowed.
+/// Computed keys are not checked.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoDupeKeys;
+
+impl Rule for NoDupeKeys {
+  fn name(&self) -> &'static str {
+    "no-dupe-keys"
+  }
+
+  fn default_severity(&self) -> Option<Severity> {
+    Some(Severity::Error)
+  }
+
+  fn check(&self, context: &mut Context<'_>) {
+    let mut finder = Finder(Vec::new());
+    let _ = finder.visit_statements(context.statements(), &mut Path::new());
+    for object in finder.0 {
+      // The first definition of each key, with whether it is a getter or a setter.
+      let mut keys: HashMap<&str, (&PropertyDefinition, Accessor)> = HashMap::new();
+      for property in object.properties() {
+        let (key, accessor) = match property {
+          PropertyDefinition::IdentifierReference(ident, _) => (&ident.name()[..], Accessor::None),
+          PropertyDefinition::Property(PropertyName::Literal(key), _, _) => (&key[..], Accessor::None),
+          PropertyDefinition::MethodDefinition(kind, PropertyName::Literal(key), _, _) => {
+            let accessor = match kind {
+              MethodDefinitionKind::Get => Accessor::Get,
+              MethodDefinitionKind::Set => Accessor::Set,
+              MethodDefinitionKind::Ordinary => Accessor::None,
+            };
+            (&key[..], accessor)
+          }
+          _ => continue,
+        };
+        let (first, first_accessor) = match keys.get(key) {
+          Some(&entry) => entry,
+          None => {
+            keys.insert(key, (property, accessor));
+            continue;
+          }
+        };
+        let pair = matches!(
+          (first_accessor, accessor),
+          (Accessor::Get, Accessor::Set) | (Accessor::Set, Accessor::Get)
+        );
+        if pair {
+          // A third definition of the key is a duplicate of the pair.
+          keys.insert(key, (first, Accessor::Pair));
+          continue;
+        }
+        let span = match context.property_key(property) {
+          Some(span) => span,
+          None => continue,
+        };
+        let mut diagnostic = context
+          .diagnostic(format!("Duplicate key '{}'.", key))
+          .with_label(Label::primary(span));
+        if let Some(first) = context.property_key(first) {
+          diagnostic =
+            diagnostic.with_label(Label::secondary(first).with_message("first defined here"));
+        }
+        context.report(diagnostic);
+      }
+    }
+  }
+}
+
+/// Whether a property is a getter or a setter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Accessor {
+  None,
+  Get,
+  Set,
+  /// A getter and a setter.
+  Pair,
+}
+
+/// Finds the object literals.
+struct Finder<'ast>(Vec<&'ast Object>);
+
+impl<'ast> Visitor<'ast> for Finder<'ast> {
+  fn visit_object(&mut self, object: &'ast Object, path: &mut NodePath<'ast>) -> Flow {
+    self.0.push(object);
+    visit::walk_object(self, object, path)
+  }
+}
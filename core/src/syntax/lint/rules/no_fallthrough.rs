@@ -0,0 +1,90 @@
+//! `no-fallthrough`: `switch` clauses running into the next one.
+
+use super::terminates;
+use crate::syntax::{
+  ast::{
+    node::Switch,
+    visitor::{visit, Flow, NodePath, Path, Visitor},
+  },
+  diagnostics::{Label, Severity},
+  lint::{Context, Rule},
+};
+
+/// Reports the clauses of a `switch` statement which have statements and go on with the next
+/// clause, unless a comment matching `falls through` or `fallthrough` is right before the next
+/// clause.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoFallthrough;
+
+impl Rule for NoFallthrough {
+  fn name(&self) -> &'static str {
+    "no-fallthrough"
+  }
+
+  fn default_severity(&self) -> Option<Severity> {
+    Some(Severity::Error)
+  }
+
+  fn check(&self, context: &mut Context<'_>) {
+    let mut finder = Finder(Vec::new());
+    let _ = finder.visit_statements(context.statements(), &mut Path::new());
+    for stmt in finder.0 {
+      for pair in stmt.cases().windows(2) {
+        let (case, next) = (&pair[0], &pair[1]);
+        if case.body().is_empty() || case.body().iter().any(terminates) {
+          continue;
+        }
+        let span = match context.case_keyword(next) {
+          Some(span) => span,
+          None => continue,
+        };
+        // The comment must be between the last token of the clause and the next one.
+        let tokens = context.tokens();
+        let index = tokens.partition_point(|token| token.span().start() < span.start());
+        let after = index
+          .checked_sub(1)
+          .map(|previous| tokens[previous].span().end());
+        let commented = context.comments().iter().any(|comment| {
+          after.is_none_or(|after| comment.span().start() >= after)
+            && comment.span().end() <= span.start()
+            && is_fallthrough_comment(comment.text())
+        });
+        if commented {
+          continue;
+        }
+        let keyword = if next.condition().is_some() {
+          "case"
+        } else {
+          "default"
+        };
+        let diagnostic = context
+          .diagnostic(format!("Expected a 'break' statement before '{}'.", keyword))
+          .with_label(Label::primary(span));
+        context.report(diagnostic);
+      }
+    }
+  }
+}
+
+/// Returns `true` if a comment matches `falls?\s?through`, ignoring case.
+fn is_fallthrough_comment(text: &str) -> bool {
+  let text = text.to_lowercase();
+  text.match_indices("fall").any(|(index, _)| {
+    let rest = &text[index + 4..];
+    let rest = rest.strip_prefix('s').unwrap_or(rest);
+    let rest = rest
+      .strip_prefix(char::is_whitespace)
+      .unwrap_or(rest);
+    rest.starts_with("through")
+  })
+}
+
+/// Finds the `switch` statements.
+struct Finder<'ast>(Vec<&'ast Switch>);
+
+impl<'ast> Visitor<'ast> for Finder<'ast> {
+  fn visit_switch(&mut self, stmt: &'ast Switch, path: &mut NodePath<'ast>) -> Flow {
+    self.0.push(stmt);
+    visit::walk_switch(self, stmt, path)
+  }
+}
@@ -0,0 +1,71 @@
+//! `no-undef`: references to variables declared nowhere.
+
+use crate::syntax::{
+  ast::{
+    node::{Node, UnaryOp},
+    operator,
+    visitor::{visit, Flow, NodePath, Path, Visitor},
+  },
+  diagnostics::{Label, Severity},
+  lint::{globals, Context, Rule},
+  scope::{ReferenceId, ScopeTree},
+};
+use std::collections::HashSet;
+
+/// Reports the references to globals that are neither built in nor configured.
+///
+/// The operand of `typeof`, which checks whether a global exists, is allowed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoUndef;
+
+impl Rule for NoUndef {
+  fn name(&self) -> &'static str {
+    "no-undef"
+  }
+
+  fn default_severity(&self) -> Option<Severity> {
+    Some(Severity::Error)
+  }
+
+  fn check(&self, context: &mut Context<'_>) {
+    let mut finder = TypeofFinder {
+      scopes: context.scopes(),
+      operands: HashSet::new(),
+    };
+    let _ = finder.visit_statements(context.statements(), &mut Path::new());
+
+    let undefined = context
+      .scopes()
+      .globals()
+      .filter(|(id, reference)| {
+        !finder.operands.contains(id)
+          && !globals::is_builtin(reference.name())
+          && !context.config().is_global(reference.name())
+      })
+      .filter_map(|(id, reference)| Some((context.reference_span(id)?, reference.name())))
+      .collect::<Vec<_>>();
+    for (span, name) in undefined {
+      let diagnostic = context
+        .diagnostic(format!("'{}' is not defined.", name))
+        .with_label(Label::primary(span));
+      context.report(diagnostic);
+    }
+  }
+}
+
+/// Finds the references that are the operand of `typeof`.
+struct TypeofFinder<'t> {
+  scopes: &'t ScopeTree,
+  operands: HashSet<ReferenceId>,
+}
+
+impl<'ast> Visitor<'ast> for TypeofFinder<'_> {
+  fn visit_unary_op(&mut self, op: &'ast UnaryOp, path: &mut NodePath<'ast>) -> Flow {
+    if let (operator::UnaryOp::TypeOf, Node::Identifier(ident)) = (op.op(), op.target()) {
+      if let Some(reference) = self.scopes.reference_at(ident.id()) {
+        self.operands.insert(reference);
+      }
+    }
+    visit::walk_unary_op(self, op, path)
+  }
+}
@@ -0,0 +1,72 @@
+//! `no-unreachable`: statements after a `return`, `throw`, `break` or `continue`.
+
+use super::terminates;
+use crate::syntax::{
+  ast::{
+    node::{DeclarationList, Node},
+    visitor::{visit, Flow, NodePath, Path, Visitor},
+    Span,
+  },
+  diagnostics::{Label, Severity},
+  lint::{Context, Rule},
+};
+
+/// Reports the statements that never run, once for each list of statements.
+///
+/// Function declarations and `var` declarations without a value still take effect, as they are
+/// hoisted, so they are not reported.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoUnreachable;
+
+impl Rule for NoUnreachable {
+  fn name(&self) -> &'static str {
+    "no-unreachable"
+  }
+
+  fn default_severity(&self) -> Option<Severity> {
+    Some(Severity::Error)
+  }
+
+  fn check(&self, context: &mut Context<'_>) {
+    let mut finder = Finder(Vec::new());
+    let _ = finder.visit_statements(context.statements(), &mut Path::new());
+    for node in finder.0 {
+      let span = match (context.statement_start(node), context.list_end(node)) {
+        (Some(start), Some(end)) => Span::new(start.start(), end),
+        _ => continue,
+      };
+      let diagnostic = context
+        .diagnostic("Unreachable code.")
+        .with_label(Label::primary(span));
+      context.report(diagnostic);
+    }
+  }
+}
+
+/// Finds the first unreachable statement of each list.
+struct Finder<'ast>(Vec<&'ast Node>);
+
+impl<'ast> Visitor<'ast> for Finder<'ast> {
+  fn visit_statements(&mut self, statements: &'ast [Node], path: &mut NodePath<'ast>) -> Flow {
+    if let Some(end) = statements.iter().position(terminates) {
+      let unreachable = statements[end + 1..]
+        .iter()
+        .find(|statement| !is_hoisted(statement));
+      if let Some(statement) = unreachable {
+        self.0.push(statement);
+      }
+    }
+    visit::walk_statements(self, statements, path)
+  }
+}
+
+/// Returns `true` for the statements which only declare hoisted names.
+fn is_hoisted(node: &Node) -> bool {
+  match node {
+    Node::FunctionDecl(_) | Node::Empty(_) => true,
+    Node::DeclarationList(DeclarationList::Var(decls), _) => {
+      decls.iter().all(|decl| decl.init().is_none())
+    }
+    _ => false,
+  }
+}
@@ -0,0 +1,140 @@
+//! `no-unused-vars`: variables, functions, classes, imports and parameters never read.
+
+use super::bindings;
+use crate::syntax::{
+  ast::{
+    node::{Class, Declaration, FunctionExpr, IterableLoopInitializer, Node},
+    visitor::{visit, Flow, NodePath, NodeRef, Path, Visitor},
+  },
+  diagnostics::{Label, Severity},
+  lint::{Context, Rule},
+  scope::{BindingId, BindingKind, ScopeTree},
+};
+use std::collections::HashSet;
+
+/// Reports the bindings that are never read.
+///
+/// Exported bindings, `catch` parameters and the names of function and class expressions are
+/// not reported, nor are the bindings of scopes with a direct `eval` call. Parameters are only
+/// reported after the last parameter that is read, as the ones before it can not be removed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoUnusedVars;
+
+impl Rule for NoUnusedVars {
+  fn name(&self) -> &'static str {
+    "no-unused-vars"
+  }
+
+  fn default_severity(&self) -> Option<Severity> {
+    Some(Severity::Warning)
+  }
+
+  fn check(&self, context: &mut Context<'_>) {
+    let scopes = context.scopes();
+    let mut finder = Finder {
+      scopes,
+      expression_names: HashSet::new(),
+      assigned: HashSet::new(),
+    };
+    let _ = finder.visit_statements(context.statements(), &mut Path::new());
+
+    let is_used = |binding: BindingId| {
+      scopes
+        .binding(binding)
+        .references()
+        .iter()
+        .any(|&reference| scopes.reference(reference).access().is_read())
+    };
+    let mut unused = Vec::new();
+    for (_, scope) in scopes.scopes() {
+      if scope.is_tainted() {
+        continue;
+      }
+      // The parameters before the last one that is read are needed to get to it.
+      let last_used_parameter = scope
+        .bindings()
+        .iter()
+        .rposition(|&binding| {
+          scopes.binding(binding).kind() == BindingKind::Parameter && is_used(binding)
+        });
+      for (index, &id) in scope.bindings().iter().enumerate() {
+        let binding = scopes.binding(id);
+        let reported = match binding.kind() {
+          BindingKind::Arguments | BindingKind::CatchParameter => false,
+          BindingKind::Parameter => last_used_parameter.is_none_or(|last| index > last),
+          _ => !binding.is_exported() && !finder.expression_names.contains(&id),
+        };
+        if reported && !is_used(id) {
+          unused.push(id);
+        }
+      }
+    }
+
+    for id in unused {
+      let span = match context.binding_span(id) {
+        Some(span) => span,
+        None => continue,
+      };
+      let binding = scopes.binding(id);
+      let assigned = finder.assigned.contains(&id)
+        || binding
+          .references()
+          .iter()
+          .any(|&reference| scopes.reference(reference).access().is_write());
+      let message = if assigned {
+        format!("'{}' is assigned a value but never used.", binding.name())
+      } else {
+        format!("'{}' is defined but never used.", binding.name())
+      };
+      let diagnostic = context
+        .diagnostic(message)
+        .with_label(Label::primary(span));
+      context.report(diagnostic);
+    }
+  }
+}
+
+/// Finds the names of function and class expressions, and the variables declared with a value.
+struct Finder<'t> {
+  scopes: &'t ScopeTree,
+  expression_names: HashSet<BindingId>,
+  assigned: HashSet<BindingId>,
+}
+
+impl<'ast> Visitor<'ast> for Finder<'_> {
+  fn visit_function_expr(&mut self, expr: &'ast FunctionExpr, path: &mut NodePath<'ast>) -> Flow {
+    if let Some(binding) = expr.name().and(self.scopes.declaration_at(expr.id())) {
+      self.expression_names.insert(binding);
+    }
+    visit::walk_function_expr(self, expr, path)
+  }
+
+  fn visit_class(&mut self, class: &'ast Class, path: &mut NodePath<'ast>) -> Flow {
+    if let Some(NodeRef::Node(Node::ClassExpr(_))) = path.parent() {
+      if let Some(binding) = class.name().and(self.scopes.declaration_at(class.id())) {
+        self.expression_names.insert(binding);
+      }
+    }
+    visit::walk_class(self, class, path)
+  }
+
+  fn visit_declaration(&mut self, decl: &'ast Declaration, path: &mut NodePath<'ast>) -> Flow {
+    if decl.init().is_some() {
+      self
+        .assigned
+        .extend(bindings(decl.target(), self.scopes));
+    }
+    visit::walk_declaration(self, decl, path)
+  }
+
+  fn visit_iterable_loop_initializer(
+    &mut self,
+    init: &'ast IterableLoopInitializer,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    self
+      .assigned
+      .extend(bindings(init.pattern(), self.scopes));
+    visit::walk_iterable_loop_initializer(self, init, path)
+  }
+}
@@ -0,0 +1,252 @@
+//! `no-var`: `var` declarations, scoped to functions instead of blocks.
+
+use super::bindings;
+use crate::syntax::{
+  ast::{
+    node::{
+      ArrowFunctionDecl, ClassElement, DeclarationList, ForInLoop, ForOfLoop, FunctionDecl,
+      FunctionExpr, Identifier, IterableLoopInitializer, Node,
+    },
+    visitor::{visit, Flow, NodePath, Path, Visitor},
+    NodeId, Span,
+  },
+  diagnostics::{Diagnostic, Label, Severity},
+  edit::Edit,
+  lint::{Context, Fix, Rule},
+  scope::{BindingId, ReferenceId, ScopeTree},
+};
+use std::collections::{HashMap, HashSet};
+
+/// Requires `let` or `const` instead of `var`.
+///
+/// The fix replaces `var` with `let` when the variables keep their meaning: the declaration is
+/// directly in the body of a function, of a static block or of a module, each variable is
+/// declared once, and no variable is used before its declaration or in it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoVar;
+
+impl Rule for NoVar {
+  fn name(&self) -> &'static str {
+    "no-var"
+  }
+
+  fn default_severity(&self) -> Option<Severity> {
+    Some(Severity::Warning)
+  }
+
+  fn check(&self, context: &mut Context<'_>) {
+    let scopes = context.scopes();
+    let mut finder = Finder {
+      scopes,
+      body: context.is_module(),
+      direct: HashSet::new(),
+      declarations: HashMap::new(),
+      lists: Vec::new(),
+      current: None,
+      loops: Vec::new(),
+    };
+    let _ = finder.visit_statements(context.statements(), &mut Path::new());
+
+    for list in &finder.lists {
+      let span = match context.declaration_keyword(list.node) {
+        Some(span) => span,
+        None => continue,
+      };
+      let fixable = first_span(list.node).is_some_and(|span| finder.direct.contains(&span))
+        && list.bindings.iter().all(|&binding| {
+          finder.declarations.get(&binding) == Some(&1)
+            && context.binding_span(binding).is_some_and(|declaration| {
+              is_after(context, scopes, binding, declaration, &list.references)
+            })
+        });
+      let diagnostic = unexpected_var(context, span);
+      if fixable {
+        let fix = Fix::new("Replace 'var' with 'let'", vec![Edit::new(span, "let")]);
+        context.report_with_fix(diagnostic, fix);
+      } else {
+        context.report(diagnostic);
+      }
+    }
+    for &stmt in &finder.loops {
+      let span = match stmt {
+        Loop::In(stmt) => context.for_in_keyword(stmt),
+        Loop::Of(stmt) => context.for_of_keyword(stmt),
+      };
+      if let Some(span) = span {
+        let diagnostic = unexpected_var(context, span);
+        context.report(diagnostic);
+      }
+    }
+  }
+}
+
+/// Creates the diagnostic of a `var` keyword.
+fn unexpected_var(context: &Context<'_>, span: Span) -> Diagnostic {
+  context
+    .diagnostic("Unexpected var, use let or const instead.")
+    .with_label(Label::primary(span))
+}
+
+/// Returns `true` if every reference to a binding comes after its declaration, and outside of
+/// the declaration list.
+fn is_after(
+  context: &Context<'_>,
+  scopes: &ScopeTree,
+  binding: BindingId,
+  declaration: Span,
+  inner: &HashSet<ReferenceId>,
+) -> bool {
+  scopes
+    .binding(binding)
+    .references()
+    .iter()
+    .all(|&reference| {
+      !inner.contains(&reference)
+        && context
+          .reference_span(reference)
+          .is_some_and(|span| span.start() > declaration.start())
+    })
+}
+
+/// Gets the span of the first declaration of a list.
+fn first_span(list: &DeclarationList) -> Option<Span> {
+  list.as_ref().first()?.span()
+}
+
+/// A `var` declaration list.
+struct List<'ast> {
+  node: &'ast DeclarationList,
+  bindings: Vec<BindingId>,
+  /// The references made in the list, by the values of its variables.
+  references: HashSet<ReferenceId>,
+}
+
+/// A `for in` or `for of` loop declaring its variable with `var`.
+#[derive(Clone, Copy)]
+enum Loop<'ast> {
+  In(&'ast ForInLoop),
+  Of(&'ast ForOfLoop),
+}
+
+/// Finds the `var` declarations, and counts the declarations of each binding.
+struct Finder<'ast, 't> {
+  scopes: &'t ScopeTree,
+  /// Whether the next statement list is the body of a function, of a static block or of a
+  /// module.
+  body: bool,
+  /// The `var` lists directly in such a body, by the span of their first declaration.
+  direct: HashSet<Span>,
+  declarations: HashMap<BindingId, usize>,
+  lists: Vec<List<'ast>>,
+  /// The index of the list being visited.
+  current: Option<usize>,
+  loops: Vec<Loop<'ast>>,
+}
+
+impl<'ast> Finder<'ast, '_> {
+  fn declare(&mut self, id: NodeId) {
+    if let Some(binding) = self.scopes.declaration_at(id) {
+      *self.declarations.entry(binding).or_default() += 1;
+    }
+  }
+
+  /// Visits the body of a function.
+  fn body(&mut self, body: &'ast [Node], path: &mut NodePath<'ast>) -> Flow {
+    self.body = true;
+    self.visit_statements(body, path)
+  }
+}
+
+impl<'ast> Visitor<'ast> for Finder<'ast, '_> {
+  fn visit_statements(&mut self, statements: &'ast [Node], path: &mut NodePath<'ast>) -> Flow {
+    if std::mem::take(&mut self.body) {
+      for statement in statements {
+        if let Node::DeclarationList(list @ DeclarationList::Var(_), _) = statement {
+          self.direct.extend(first_span(list));
+        }
+      }
+    }
+    visit::walk_statements(self, statements, path)
+  }
+
+  fn visit_function_decl(&mut self, decl: &'ast FunctionDecl, path: &mut NodePath<'ast>) -> Flow {
+    self.declare(decl.id());
+    for parameter in decl.parameters() {
+      self.visit_formal_parameter(parameter, path)?;
+    }
+    self.body(decl.body(), path)
+  }
+
+  fn visit_function_expr(&mut self, expr: &'ast FunctionExpr, path: &mut NodePath<'ast>) -> Flow {
+    for parameter in expr.parameters() {
+      self.visit_formal_parameter(parameter, path)?;
+    }
+    self.body(expr.body(), path)
+  }
+
+  fn visit_arrow_function_decl(
+    &mut self,
+    arrow: &'ast ArrowFunctionDecl,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    for parameter in arrow.params() {
+      self.visit_formal_parameter(parameter, path)?;
+    }
+    self.body(arrow.body(), path)
+  }
+
+  fn visit_class_element(
+    &mut self,
+    element: &'ast ClassElement,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    self.body = matches!(element, ClassElement::StaticBlock(_, _));
+    visit::walk_class_element(self, element, path)
+  }
+
+  fn visit_identifier(&mut self, ident: &'ast Identifier, _path: &mut NodePath<'ast>) -> Flow {
+    self.declare(ident.id());
+    if let (Some(current), Some(reference)) = (self.current, self.scopes.reference_at(ident.id())) {
+      self.lists[current].references.insert(reference);
+    }
+    Flow::Continue(())
+  }
+
+  fn visit_declaration_list(
+    &mut self,
+    list: &'ast DeclarationList,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    if !matches!(list, DeclarationList::Var(_)) {
+      return visit::walk_declaration_list(self, list, path);
+    }
+    let bindings = list
+      .as_ref()
+      .iter()
+      .flat_map(|decl| bindings(decl.target(), self.scopes))
+      .collect();
+    self.lists.push(List {
+      node: list,
+      bindings,
+      references: HashSet::new(),
+    });
+    let outer = self.current.replace(self.lists.len() - 1);
+    let flow = visit::walk_declaration_list(self, list, path);
+    self.current = outer;
+    flow
+  }
+
+  fn visit_for_in_loop(&mut self, stmt: &'ast ForInLoop, path: &mut NodePath<'ast>) -> Flow {
+    if let IterableLoopInitializer::Var(_) = stmt.init() {
+      self.loops.push(Loop::In(stmt));
+    }
+    visit::walk_for_in_loop(self, stmt, path)
+  }
+
+  fn visit_for_of_loop(&mut self, stmt: &'ast ForOfLoop, path: &mut NodePath<'ast>) -> Flow {
+    if let IterableLoopInitializer::Var(_) = stmt.init() {
+      self.loops.push(Loop::Of(stmt));
+    }
+    visit::walk_for_of_loop(self, stmt, path)
+  }
+}
@@ -0,0 +1,152 @@
+//! `prefer-const`: `let` variables that are never reassigned.
+
+use super::bindings;
+use crate::syntax::{
+  ast::{
+    node::{DeclarationList, ForInLoop, ForOfLoop, IterableLoopInitializer},
+    visitor::{visit, Flow, NodePath, Path, Visitor},
+    Span,
+  },
+  diagnostics::{Label, Severity},
+  edit::Edit,
+  lint::{Context, Fix, Rule},
+  scope::{BindingId, ScopeTree},
+};
+
+/// Requires `const` for the `let` variables that get a value in their declaration and are
+/// never written after it.
+///
+/// The fix replaces `let` with `const` when every variable of the declaration qualifies.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PreferConst;
+
+impl Rule for PreferConst {
+  fn name(&self) -> &'static str {
+    "prefer-const"
+  }
+
+  fn default_severity(&self) -> Option<Severity> {
+    Some(Severity::Warning)
+  }
+
+  fn check(&self, context: &mut Context<'_>) {
+    let scopes = context.scopes();
+    let mut finder = Finder {
+      scopes,
+      declarations: Vec::new(),
+    };
+    let _ = finder.visit_statements(context.statements(), &mut Path::new());
+
+    for declaration in finder.declarations {
+      let keyword = match declaration.site {
+        Site::List(list) => context.declaration_keyword(list),
+        Site::In(stmt) => context.for_in_keyword(stmt),
+        Site::Of(stmt) => context.for_of_keyword(stmt),
+      };
+      let constant = declaration
+        .bindings
+        .iter()
+        .filter(|&&(_, initialized)| initialized)
+        .map(|&(binding, _)| binding)
+        .filter(|&binding| {
+          !scopes
+            .binding(binding)
+            .references()
+            .iter()
+            .any(|&reference| scopes.reference(reference).access().is_write())
+        })
+        .filter_map(|binding| Some((binding, context.binding_span(binding)?)))
+        .collect::<Vec<(BindingId, Span)>>();
+      let fixable = constant.len() == declaration.bindings.len();
+      for (binding, span) in constant {
+        let diagnostic = context
+          .diagnostic(format!(
+            "'{}' is never reassigned. Use 'const' instead.",
+            scopes.binding(binding).name()
+          ))
+          .with_label(Label::primary(span));
+        match keyword {
+          Some(keyword) if fixable => {
+            let fix = Fix::new("Replace 'let' with 'const'", vec![Edit::new(keyword, "const")]);
+            context.report_with_fix(diagnostic, fix);
+          }
+          _ => context.report(diagnostic),
+        }
+      }
+    }
+  }
+}
+
+/// Where variables are declared with `let`.
+#[derive(Clone, Copy)]
+enum Site<'ast> {
+  List(&'ast DeclarationList),
+  In(&'ast ForInLoop),
+  Of(&'ast ForOfLoop),
+}
+
+/// The variables of a `let` declaration.
+struct Declaration<'ast> {
+  site: Site<'ast>,
+  /// The bindings, and whether the declaration gives them a value.
+  bindings: Vec<(BindingId, bool)>,
+}
+
+/// Finds the `let` declarations.
+struct Finder<'ast, 't> {
+  scopes: &'t ScopeTree,
+  declarations: Vec<Declaration<'ast>>,
+}
+
+impl<'ast> Visitor<'ast> for Finder<'ast, '_> {
+  fn visit_declaration_list(
+    &mut self,
+    list: &'ast DeclarationList,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    if let DeclarationList::Let(decls) = list {
+      let bindings = decls
+        .iter()
+        .flat_map(|decl| {
+          let initialized = decl.init().is_some();
+          bindings(decl.target(), self.scopes)
+            .into_iter()
+            .map(move |binding| (binding, initialized))
+        })
+        .collect();
+      self.declarations.push(Declaration {
+        site: Site::List(list),
+        bindings,
+      });
+    }
+    visit::walk_declaration_list(self, list, path)
+  }
+
+  fn visit_for_in_loop(&mut self, stmt: &'ast ForInLoop, path: &mut NodePath<'ast>) -> Flow {
+    if let IterableLoopInitializer::Let(pattern) = stmt.init() {
+      self.declarations.push(Declaration {
+        site: Site::In(stmt),
+        bindings: loop_bindings(bindings(pattern, self.scopes)),
+      });
+    }
+    visit::walk_for_in_loop(self, stmt, path)
+  }
+
+  fn visit_for_of_loop(&mut self, stmt: &'ast ForOfLoop, path: &mut NodePath<'ast>) -> Flow {
+    if let IterableLoopInitializer::Let(pattern) = stmt.init() {
+      self.declarations.push(Declaration {
+        site: Site::Of(stmt),
+        bindings: loop_bindings(bindings(pattern, self.scopes)),
+      });
+    }
+    visit::walk_for_of_loop(self, stmt, path)
+  }
+}
+
+/// Marks the bindings of a loop head as initialized, by each iteration.
+fn loop_bindings(bindings: Vec<BindingId>) -> Vec<(BindingId, bool)> {
+  bindings
+    .into_iter()
+    .map(|binding| (binding, true))
+    .collect()
+}
@@ -0,0 +1,347 @@
+//! Tests for the linter.
+
+use super::{apply_fixes, Config, ConfigError, Env, Fix, Linter, Problem};
+use crate::syntax::{
+  ast::{Position, Span},
+  diagnostics::Severity,
+  edit::Edit,
+};
+
+/// Lints a module with a configuration.
+#[track_caller]
+fn lint_with(config: Config, js: &str) -> Vec<Problem> {
+  Linter::new(config)
+    .lint_module(js)
+    .unwrap_or_else(|err| panic!("failed to parse `{}`: {}", js, err))
+}
+
+/// Lints a module, describing each problem as `line:column rule: message`.
+#[track_caller]
+fn lint(js: &str) -> Vec<String> {
+  lint_with(Config::new(), js)
+    .iter()
+    .map(|problem| {
+      let start = problem.diagnostic().primary_span().unwrap().start();
+      format!(
+        "{}:{} {}: {}",
+        start.line_number(),
+        start.column_number(),
+        problem.rule(),
+        problem.diagnostic().message()
+      )
+    })
+    .collect()
+}
+
+/// Lints a module, keeping the problems of a rule.
+#[track_caller]
+fn lint_rule(rule: &str, js: &str) -> Vec<String> {
+  lint(js)
+    .into_iter()
+    .filter(|problem| problem.contains(&format!(" {}: ", rule)))
+    .collect()
+}
+
+/// Lints a module, and applies the fixes of the problems.
+#[track_caller]
+fn fix(js: &str) -> String {
+  let problems = lint_with(Config::new(), js);
+  apply_fixes(js, problems.iter().filter_map(Problem::fix))
+}
+
+fn span(start: (u32, u32), end: (u32, u32)) -> Span {
+  Span::new(Position::new(start.0, start.1), Position::new(end.0, end.1))
+}
+
+#[test]
+fn no_unused_vars() {
+  assert_eq!(
+    lint_rule(
+      "no-unused-vars",
+      "let a = 1;\nfunction f(x, y, z) { return y; }\nimport { b } from 'm';\nf();\n"
+    ),
+    [
+      "1:5 no-unused-vars: 'a' is assigned a value but never used.",
+      "2:18 no-unused-vars: 'z' is defined but never used.",
+      "3:10 no-unused-vars: 'b' is defined but never used.",
+    ]
+  );
+  // Exports, catch parameters and the names of function and class expressions are not reported.
+  assert_eq!(
+    lint(
+      "export const c = 1;\nconst d = 2;\nexport { d };\ntry {} catch (err) {}\n\
+       export const e = function named() {}, k = class Named {};\n"
+    ),
+    Vec::<String>::new()
+  );
+}
+
+#[test]
+fn no_undef() {
+  assert_eq!(
+    lint("console.log(missing, Math.max(1, 2));\nif (typeof maybe === 'undefined') {}\n"),
+    [
+      "1:1 no-undef: 'console' is not defined.",
+      "1:13 no-undef: 'missing' is not defined.",
+    ]
+  );
+  let config = Config::new().with_env(Env::Browser).with_global("missing");
+  assert!(lint_with(config, "console.log(missing, window);").is_empty());
+  let config = Config::new().with_env(Env::Node);
+  let problems = lint_with(config, "require('fs'); document.title;");
+  assert_eq!(problems.len(), 1);
+  assert_eq!(
+    problems[0].diagnostic().message(),
+    "'document' is not defined."
+  );
+}
+
+#[test]
+fn eqeqeq() {
+  let js = "export const a = typeof x == 'string', b = 1 != 2, c = x == null;\n";
+  let problems = lint_with(Config::new().with_global("x"), js);
+  let messages = problems
+    .iter()
+    .map(|problem| problem.diagnostic().message())
+    .collect::<Vec<_>>();
+  assert_eq!(
+    messages,
+    [
+      "Expected '===' and instead saw '=='.",
+      "Expected '!==' and instead saw '!='.",
+      "Expected '===' and instead saw '=='."
+    ]
+  );
+  // Comparing with `null` also matches `undefined`, so it is not fixed.
+  assert_eq!(
+    problems[2].diagnostic().primary_span(),
+    Some(span((1, 58), (1, 60)))
+  );
+  assert!(problems[2].fix().is_none());
+  assert_eq!(
+    apply_fixes(js, problems.iter().filter_map(Problem::fix)),
+    "export const a = typeof x === 'string', b = 1 !== 2, c = x == null;\n"
+  );
+}
+
+#[test]
+fn no_var() {
+  assert_eq!(
+    fix("var a = 1;\nfunction f() {\n  var b = a;\n  return b;\n}\nf();\n"),
+    "let a = 1;\nfunction f() {\n  let b = a;\n  return b;\n}\nf();\n"
+  );
+  // Variables in blocks and loops, declared twice, or used before or in their declaration keep
+  // `var`.
+  let js = "function f() {\n  if (f) { var a = 1; }\n  for (var i = 0; i < 1; i++) {}\n  \
+            var b = 1; var b = 2;\n  g(c);\n  var c = 3;\n  var d = d || 4;\n  \
+            return [a, i, b, c, d];\n}\nfunction g() { return h; }\nvar h;\nf();\n";
+  let problems = lint(js);
+  assert_eq!(
+    problems
+      .iter()
+      .filter(|problem| problem.contains("no-var"))
+      .count(),
+    7
+  );
+  assert_eq!(fix(js), js);
+  // The global variables of a script are properties of the global object.
+  let problems = Linter::default().lint_script("var a = 1;").unwrap();
+  let problem = problems.iter().find(|problem| problem.rule() == "no-var");
+  assert!(problem.unwrap().fix().is_none());
+}
+
+#[test]
+fn prefer_const() {
+  assert_eq!(
+    lint_rule(
+      "prefer-const",
+      "let a = 1, b = 2;\nb++;\nlet [c, d] = [a, b];\nfor (let k in c) { d = k; }\n"
+    ),
+    [
+      "1:5 prefer-const: 'a' is never reassigned. Use 'const' instead.",
+      "3:6 prefer-const: 'c' is never reassigned. Use 'const' instead.",
+      "4:10 prefer-const: 'k' is never reassigned. Use 'const' instead.",
+    ]
+  );
+  assert_eq!(
+    fix("let a = 1, b = 2;\nlet c;\nc = 1;\nfor (let x of [a, b, c]) { a + x; }\n"),
+    "const a = 1, b = 2;\nlet c;\nc = 1;\nfor (const x of [a, b, c]) { a + x; }\n"
+  );
+}
+
+#[test]
+fn no_unreachable() {
+  let js = "export function f(x) {\n  switch (x) {\n    case 1:\n      return 1;\n      \
+            f(2);\n  }\n  throw x;\n  function g() {}\n  var h;\n  \
+            if (x) {\n    x = 1;\n  }\n  f(3);\n}\n";
+  let problems = lint_with(Config::new(), js);
+  let spans = problems
+    .iter()
+    .filter(|problem| problem.rule() == "no-unreachable")
+    .map(|problem| problem.diagnostic().primary_span().unwrap())
+    .collect::<Vec<_>>();
+  // Hoisted declarations are skipped, and the span goes to the end of the list.
+  assert_eq!(spans, [span((5, 7), (5, 12)), span((10, 3), (13, 8))]);
+}
+
+#[test]
+fn no_dupe_keys() {
+  let js = "export const o = {\n  a: 1,\n  'b': 2,\n  get c() { return 1; },\n  \
+            set c(v) {},\n  a() {},\n  b,\n  c: 3,\n  [a]: 4,\n};\n";
+  let problems = lint_with(Config::new().with_global("b"), js);
+  let labels = problems
+    .iter()
+    .filter(|problem| problem.rule() == "no-dupe-keys")
+    .map(|problem| {
+      let labels = problem.diagnostic().labels();
+      (
+        problem.diagnostic().message(),
+        labels[0].span().start().line_number(),
+        labels[1].span().start().line_number(),
+      )
+    })
+    .collect::<Vec<_>>();
+  assert_eq!(
+    labels,
+    [
+      ("Duplicate key 'a'.", 6, 2),
+      ("Duplicate key 'b'.", 7, 3),
+      ("Duplicate key 'c'.", 8, 4),
+    ]
+  );
+}
+
+#[test]
+fn no_fallthrough() {
+  assert_eq!(
+    lint(
+      "export function f(x) {\n  switch (x) {\n    case 0:\n    case 1:\n      f(1);\n    \
+       case 2:\n      f(2);\n      // falls through\n    case 3:\n      if (x) {\n        \
+       break;\n      }\n    default:\n      f(3);\n  }\n}\n"
+    ),
+    [
+      "6:5 no-fallthrough: Expected a 'break' statement before 'case'.",
+      "13:5 no-fallthrough: Expected a 'break' statement before 'default'.",
+    ]
+  );
+}
+
+#[test]
+fn statements_without_semicolons() {
+  // The statements are found after line breaks, blocks and labels.
+  let js = "let a = 1\nlet b = a\nif (b) b++\nelse { let c = b; use(c) }\n\
+            loop: for (;;) { break loop }\nconst o = { k: a, a }\nclass C {\n  \
+            static { let s = 1; use(s) }\n  m() { let t = 2; return t }\n}\nuse(o, C)\n";
+  assert_eq!(
+    lint_with(Config::new().with_global("use"), js)
+      .iter()
+      .map(|problem| problem.diagnostic().primary_span().unwrap())
+      .collect::<Vec<_>>(),
+    [
+      span((1, 5), (1, 6)),
+      span((4, 12), (4, 13)),
+      span((8, 16), (8, 17)),
+      span((9, 13), (9, 14)),
+    ]
+  );
+}
+
+#[test]
+fn disable_comments() {
+  let js = "// plastic-disable-next-line no-undef, eqeqeq -- legacy code\nif (x == y) {}\n\
+            /* plastic-disable-next-line */\nvar a = b;\nz;\n";
+  assert_eq!(lint(js), ["5:1 no-undef: 'z' is not defined."]);
+}
+
+#[test]
+fn severities() {
+  let config = Config::new()
+    .with_rule("no-undef", Some(Severity::Warning))
+    .with_rule("no-var", None);
+  let problems = lint_with(config, "var a = b;\nexport { a };\n");
+  assert_eq!(problems.len(), 1);
+  assert_eq!(problems[0].rule(), "no-undef");
+  assert_eq!(problems[0].diagnostic().severity(), Severity::Warning);
+  assert_eq!(problems[0].diagnostic().code(), Some("no-undef"));
+}
+
+#[test]
+fn config_from_toml() {
+  let config = Config::from_toml(
+    "# The linter.\n[package]\nname = 'app'\n\n[lint]\nenv = [\"browser\",\n  \"node\"]\n\
+     globals = ['jQuery']  # From a script tag.\n\n[lint.rules]\neqeqeq = \"error\"\n\
+     no-var = 'off'\n\"no-undef\" = 1\n",
+  )
+  .unwrap();
+  assert_eq!(
+    config,
+    Config::new()
+      .with_env(Env::Browser)
+      .with_env(Env::Node)
+      .with_global("jQuery")
+      .with_rule("eqeqeq", Some(Severity::Error))
+      .with_rule("no-var", None)
+      .with_rule("no-undef", Some(Severity::Warning))
+  );
+  assert_eq!(
+    config.severity("prefer-const", Some(Severity::Warning)),
+    Some(Severity::Warning)
+  );
+  assert_eq!(config.severity("no-var", Some(Severity::Warning)), None);
+  assert!(config.is_global("process"));
+
+  let error = |toml: &str| Config::from_toml(toml).unwrap_err().to_string();
+  assert_eq!(
+    error("[lint.rules]\neqeqeq = \"always\"\n"),
+    "`lint.rules.eqeqeq` must be \"off\", \"warn\" or \"error\" (line 2)"
+  );
+  assert_eq!(
+    error("[lint]\nenvs = []\n"),
+    "unknown key `lint.envs` (line 2)"
+  );
+  assert_eq!(
+    error("[lint]\nenv = ['deno']\n"),
+    "unknown environment `deno` (line 2)"
+  );
+  assert_eq!(
+    error("[lint]\nglobals = 'jQuery'\n"),
+    "`lint.globals` must be an array of strings (line 2)"
+  );
+  assert_eq!(
+    error("[lint.rules]\neqeqeq = 'off'\neqeqeq = 'warn'\n"),
+    "invalid TOML on line 3: duplicate key"
+  );
+  assert_eq!(error("[lint\n"), "invalid TOML on line 1: expected `]`");
+  assert!(matches!(
+    Config::from_toml("a = \"unterminated\n"),
+    Err(ConfigError::Toml { line: 1, .. })
+  ));
+}
+
+#[test]
+fn overlapping_fixes() {
+  let source = "aaa bbb ccc";
+  let fixes = [
+    Fix::new("second", vec![Edit::new(span((1, 5), (1, 8)), "B")]),
+    Fix::new(
+      "first",
+      vec![
+        Edit::new(span((1, 1), (1, 4)), "A"),
+        Edit::new(span((1, 9), (1, 12)), "C"),
+      ],
+    ),
+    Fix::new("overlapping", vec![Edit::new(span((1, 2), (1, 6)), "X")]),
+    Fix::new("insertion", vec![Edit::new(span((1, 12), (1, 12)), "!")]),
+  ];
+  assert_eq!(apply_fixes(source, &fixes), "A B C!");
+  assert_eq!(
+    apply_fixes(
+      "é = 1;\n",
+      &[Fix::new(
+        "rename",
+        vec![Edit::new(span((1, 1), (1, 2)), "e")]
+      )]
+    ),
+    "e = 1;\n"
+  );
+}
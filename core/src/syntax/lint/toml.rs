@@ -0,0 +1,300 @@
+//! The little TOML a configuration file needs: tables, strings, integers, booleans and arrays.
+//!
+//! Inline tables, floats, dates and multi-line strings are not supported.
+
+/// A TOML value.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Value {
+  String(String),
+  Integer(i64),
+  Bool(bool),
+  Array(Vec<Value>),
+}
+
+/// A key and its value, with the names of the tables it is in.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Entry {
+  /// The table of the key, such as `["lint", "rules"]`, with the dotted parts of the key.
+  pub(super) table: Vec<String>,
+  pub(super) key: String,
+  pub(super) value: Value,
+  /// The line of the key, from 1.
+  pub(super) line: usize,
+}
+
+/// Parses a TOML document into its entries, in order, returning the error message and its line
+/// if it is malformed.
+pub(super) fn parse(text: &str) -> Result<Vec<Entry>, (&'static str, usize)> {
+  let mut reader = Reader {
+    text: text.as_bytes(),
+    offset: 0,
+    line: 1,
+  };
+  let mut entries: Vec<Entry> = Vec::new();
+  let mut tables: Vec<Vec<String>> = Vec::new();
+  let mut table = Vec::new();
+  loop {
+    reader.blank();
+    match reader.peek() {
+      None => return Ok(entries),
+      Some(b'[') => {
+        reader.offset += 1;
+        if reader.peek() == Some(b'[') {
+          return Err(reader.error("arrays of tables are not supported"));
+        }
+        let name = reader.key()?;
+        reader.spaces();
+        if reader.peek() != Some(b']') {
+          return Err(reader.error("expected `]`"));
+        }
+        reader.offset += 1;
+        if tables.contains(&name) {
+          return Err(reader.error("duplicate table"));
+        }
+        tables.push(name.clone());
+        table = name;
+      }
+      Some(_) => {
+        let line = reader.line;
+        let mut key = reader.key()?;
+        reader.spaces();
+        if reader.peek() != Some(b'=') {
+          return Err(reader.error("expected `=`"));
+        }
+        reader.offset += 1;
+        let value = reader.value()?;
+        let name = key.pop().unwrap_or_default();
+        let mut path = table.clone();
+        path.extend(key);
+        if entries
+          .iter()
+          .any(|entry| entry.table == path && entry.key == name)
+        {
+          return Err(("duplicate key", line));
+        }
+        entries.push(Entry {
+          table: path,
+          key: name,
+          value,
+          line,
+        });
+      }
+    }
+    reader.end_of_line()?;
+  }
+}
+
+/// Reads TOML values from bytes.
+struct Reader<'a> {
+  text: &'a [u8],
+  offset: usize,
+  line: usize,
+}
+
+impl Reader<'_> {
+  fn error(&self, message: &'static str) -> (&'static str, usize) {
+    (message, self.line)
+  }
+
+  fn peek(&self) -> Option<u8> {
+    self.text.get(self.offset).copied()
+  }
+
+  /// Skips spaces and tabs.
+  fn spaces(&mut self) {
+    while let Some(b' ') | Some(b'\t') = self.peek() {
+      self.offset += 1;
+    }
+  }
+
+  /// Skips a comment, up to the end of its line.
+  fn comment(&mut self) {
+    if self.peek() == Some(b'#') {
+      while !matches!(self.peek(), None | Some(b'\n')) {
+        self.offset += 1;
+      }
+    }
+  }
+
+  /// Skips whitespace, line breaks and comments.
+  fn blank(&mut self) {
+    loop {
+      self.spaces();
+      self.comment();
+      match self.peek() {
+        Some(b'\n') => {
+          self.offset += 1;
+          self.line += 1;
+        }
+        Some(b'\r') => self.offset += 1,
+        _ => return,
+      }
+    }
+  }
+
+  /// Expects the end of a line after a table header or a value, or the end of the text.
+  fn end_of_line(&mut self) -> Result<(), (&'static str, usize)> {
+    self.spaces();
+    self.comment();
+    match self.peek() {
+      None | Some(b'\n') | Some(b'\r') => Ok(()),
+      Some(_) => Err(self.error("expected the end of the line")),
+    }
+  }
+
+  /// Reads a key, made of bare or quoted parts separated by dots.
+  fn key(&mut self) -> Result<Vec<String>, (&'static str, usize)> {
+    let mut parts = Vec::new();
+    loop {
+      self.spaces();
+      let part = match self.peek() {
+        Some(b'"') => self.basic_string()?,
+        Some(b'\'') => self.literal_string()?,
+        _ => {
+          let start = self.offset;
+          while let Some(b'A'..=b'Z') | Some(b'a'..=b'z') | Some(b'0'..=b'9') | Some(b'_')
+          | Some(b'-') = self.peek()
+          {
+            self.offset += 1;
+          }
+          if start == self.offset {
+            return Err(self.error("expected a key"));
+          }
+          String::from_utf8_lossy(&self.text[start..self.offset]).into_owned()
+        }
+      };
+      parts.push(part);
+      self.spaces();
+      if self.peek() != Some(b'.') {
+        return Ok(parts);
+      }
+      self.offset += 1;
+    }
+  }
+
+  fn value(&mut self) -> Result<Value, (&'static str, usize)> {
+    self.spaces();
+    match self.peek() {
+      Some(b'"') => self.basic_string().map(Value::String),
+      Some(b'\'') => self.literal_string().map(Value::String),
+      Some(b'[') => {
+        self.offset += 1;
+        let mut elements = Vec::new();
+        loop {
+          self.blank();
+          if self.peek() == Some(b']') {
+            self.offset += 1;
+            return Ok(Value::Array(elements));
+          }
+          elements.push(self.value()?);
+          self.blank();
+          match self.peek() {
+            Some(b',') => self.offset += 1,
+            Some(b']') => {
+              self.offset += 1;
+              return Ok(Value::Array(elements));
+            }
+            _ => return Err(self.error("expected `,` or `]`")),
+          }
+        }
+      }
+      Some(b't') if self.text[self.offset..].starts_with(b"true") => {
+        self.offset += 4;
+        Ok(Value::Bool(true))
+      }
+      Some(b'f') if self.text[self.offset..].starts_with(b"false") => {
+        self.offset += 5;
+        Ok(Value::Bool(false))
+      }
+      Some(b'+') | Some(b'-') | Some(b'0'..=b'9') => self.integer(),
+      Some(b'\n') | Some(b'\r') | None => Err(self.error("expected a value")),
+      Some(_) => Err(self.error("unsupported value")),
+    }
+  }
+
+  fn integer(&mut self) -> Result<Value, (&'static str, usize)> {
+    let start = self.offset;
+    while let Some(b'+') | Some(b'-') | Some(b'_') | Some(b'0'..=b'9') = self.peek() {
+      self.offset += 1;
+    }
+    let digits = self.text[start..self.offset]
+      .iter()
+      .filter(|&&byte| byte != b'_')
+      .map(|&byte| byte as char)
+      .collect::<String>();
+    digits
+      .parse()
+      .map(Value::Integer)
+      .map_err(|_| self.error("invalid integer"))
+  }
+
+  /// Reads a `"` string, with escape sequences.
+  fn basic_string(&mut self) -> Result<String, (&'static str, usize)> {
+    // Skip the opening quote.
+    self.offset += 1;
+    let mut bytes = Vec::new();
+    loop {
+      match self.peek() {
+        Some(b'"') => {
+          self.offset += 1;
+          return String::from_utf8(bytes).map_err(|_| self.error("invalid UTF-8"));
+        }
+        Some(b'\\') => {
+          self.offset += 1;
+          let escaped = match self.peek() {
+            Some(b'"') => '"',
+            Some(b'\\') => '\\',
+            Some(b'b') => '\u{8}',
+            Some(b'f') => '\u{c}',
+            Some(b'n') => '\n',
+            Some(b'r') => '\r',
+            Some(b't') => '\t',
+            Some(b'u') | Some(b'U') => {
+              let length = if self.peek() == Some(b'u') { 4 } else { 8 };
+              self.offset += 1;
+              let digits = self
+                .text
+                .get(self.offset..self.offset + length)
+                .and_then(|digits| std::str::from_utf8(digits).ok())
+                .ok_or_else(|| self.error("invalid escape sequence"))?;
+              let ch = u32::from_str_radix(digits, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or_else(|| self.error("invalid escape sequence"))?;
+              self.offset += length - 1;
+              ch
+            }
+            _ => return Err(self.error("invalid escape sequence")),
+          };
+          self.offset += 1;
+          let mut buffer = [0; 4];
+          bytes.extend_from_slice(escaped.encode_utf8(&mut buffer).as_bytes());
+        }
+        Some(b'\n') | None => return Err(self.error("unterminated string")),
+        Some(byte) => {
+          self.offset += 1;
+          bytes.push(byte);
+        }
+      }
+    }
+  }
+
+  /// Reads a `'` string, without escape sequences.
+  fn literal_string(&mut self) -> Result<String, (&'static str, usize)> {
+    // Skip the opening quote.
+    self.offset += 1;
+    let start = self.offset;
+    loop {
+      match self.peek() {
+        Some(b'\'') => {
+          let string = String::from_utf8(self.text[start..self.offset].to_vec())
+            .map_err(|_| self.error("invalid UTF-8"));
+          self.offset += 1;
+          return string;
+        }
+        Some(b'\n') | None => return Err(self.error("unterminated string")),
+        Some(_) => self.offset += 1,
+      }
+    }
+  }
+}
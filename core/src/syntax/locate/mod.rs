@@ -0,0 +1,391 @@
+//! Finding the syntax tree in the tokens of its source.
+//!
+//! The linter points at keywords, operators and names, which have no span of their own in the
+//! syntax tree, and comments are attached to the tokens around statements and members, so the
+//! parts of the tree are found in the tokens of the source from the spans the parser gave them:
+//!
+//!  - A statement, an element of a class body, a property of an object literal and a clause of
+//!    a `switch` statement start at the first token of their span. An expression statement
+//!    starts at the parentheses opened before its expression, if any, and the constructor of a
+//!    class at the first token of the span the class keeps for it.
+//!  - The `var`, `let` or `const` keyword of a declaration is the token before the first name
+//!    it declares.
+//!  - The operator of an equality comparison is the first token after its left-hand side and
+//!    the parentheses closing it.
+//!  - The key of a property follows its `async`, `get` or `set` modifier and its `*`.
+//!  - The names of bindings and references are the spans of their identifiers.
+//!
+//! The parts are known by their span, so a part created after parsing, which has none, is not
+//! found.
+
+mod source;
+
+pub(crate) use self::source::{Lines, Source};
+
+use crate::syntax::{
+  ast::{
+    node::{
+      ArrowFunctionDecl, BinOp, Case, Class, DeclarationList, ExportDecl, ExportSpecifier,
+      ForInLoop, ForOfLoop, FunctionDecl, FunctionExpr, Identifier, ImportSpecifier,
+      IterableLoopInitializer, MethodDefinitionKind, Node, Object, PropertyDefinition,
+      PropertyName,
+    },
+    operator::{self, CompOp},
+    visitor::{visit, Flow, NodePath, NodeRef, Path, Visitor},
+    Keyword, NodeId, Position, Punctuator, Span,
+  },
+  lexer::{Token, TokenKind},
+  scope::{BindingId, ReferenceId, ScopeTree},
+};
+use std::{collections::HashMap, ptr};
+
+/// The tokens the parts of a syntax tree start at, by the span of the parts.
+#[derive(Debug, Default)]
+pub(crate) struct Locations {
+  statements: HashMap<Span, usize>,
+  /// The `var`, `let` or `const` keywords of declaration lists, by the span of their first
+  /// declaration, and of `for in` and `for of` loops.
+  keywords: HashMap<Span, usize>,
+  cases: HashMap<Span, usize>,
+  operators: HashMap<Span, usize>,
+  keys: HashMap<Span, usize>,
+  /// The elements of class bodies, and the properties of object literals. A constructor is
+  /// found by the span of its function.
+  members: HashMap<Span, usize>,
+  /// The first declaration of each binding.
+  declarations: HashMap<BindingId, Span>,
+  references: HashMap<ReferenceId, Span>,
+}
+
+impl Locations {
+  /// Finds the statements and the names of a script or a module in its source.
+  pub(crate) fn new(source: &Source<'_>, statements: &[Node], tree: &ScopeTree) -> Self {
+    let mut locator = Locator {
+      tokens: &source.tokens,
+      tree,
+      locations: Locations::default(),
+    };
+    let _ = locator.visit_statements(statements, &mut Path::new());
+    locator.locations
+  }
+
+  /// Gets the first token of a statement.
+  pub(crate) fn statement(&self, node: &Node) -> Option<usize> {
+    self.statements.get(&node.span()?).copied()
+  }
+
+  /// Gets the `var`, `let` or `const` keyword of a declaration list.
+  pub(crate) fn declaration_keyword(&self, list: &DeclarationList) -> Option<usize> {
+    self.keywords.get(&list.as_ref().first()?.span()?).copied()
+  }
+
+  /// Gets the `var`, `let` or `const` keyword declaring the variable of a `for in` loop.
+  pub(crate) fn for_in_keyword(&self, stmt: &ForInLoop) -> Option<usize> {
+    self.keywords.get(&stmt.span()?).copied()
+  }
+
+  /// Gets the `var`, `let` or `const` keyword declaring the variable of a `for of` loop.
+  pub(crate) fn for_of_keyword(&self, stmt: &ForOfLoop) -> Option<usize> {
+    self.keywords.get(&stmt.span()?).copied()
+  }
+
+  /// Gets the `case` or `default` keyword of a clause.
+  pub(crate) fn case(&self, case: &Case) -> Option<usize> {
+    self.cases.get(&case.span()?).copied()
+  }
+
+  /// Gets the operator of an equality comparison.
+  pub(crate) fn operator(&self, op: &BinOp) -> Option<usize> {
+    self.operators.get(&op.span()?).copied()
+  }
+
+  /// Gets the key of a property of an object literal, when it is a literal name.
+  pub(crate) fn key(&self, property: &PropertyDefinition) -> Option<usize> {
+    self.keys.get(&property.span()?).copied()
+  }
+
+  /// Gets the first token of the element of a class body, of the constructor of a class, or of
+  /// the property of an object literal with a span.
+  pub(crate) fn member(&self, span: Span) -> Option<usize> {
+    self.members.get(&span).copied()
+  }
+
+  /// Gets the span of the first declaration of a binding.
+  pub(crate) fn declaration(&self, binding: BindingId) -> Option<Span> {
+    self.declarations.get(&binding).copied()
+  }
+
+  /// Gets the span of a reference.
+  pub(crate) fn reference(&self, reference: ReferenceId) -> Option<Span> {
+    self.references.get(&reference).copied()
+  }
+}
+
+/// The visitor finding the syntax tree in the tokens.
+#[derive(Debug)]
+struct Locator<'s, 't> {
+  tokens: &'s [Token],
+  tree: &'t ScopeTree,
+  locations: Locations,
+}
+
+impl Locator<'_, '_> {
+  /// Gets the kind of a token, `None` past the end.
+  fn kind(&self, index: usize) -> Option<&TokenKind> {
+    self.tokens.get(index).map(Token::kind)
+  }
+
+  /// Gets the token at a position, or the first one after it.
+  fn token_at(&self, position: Position) -> usize {
+    self
+      .tokens
+      .partition_point(|token| token.span().start() < position)
+  }
+
+  /// Finds the first token of a statement.
+  fn statement(&mut self, node: &Node) {
+    let span = match node.span() {
+      Some(span) => span,
+      None => return,
+    };
+    // No statement follows an opening parenthesis, so those before a statement start it.
+    let mut start = self.token_at(span.start());
+    while start > 0 && self.kind(start - 1) == Some(&TokenKind::Punctuator(Punctuator::OpenParen)) {
+      start -= 1;
+    }
+    self.locations.statements.insert(span, start);
+  }
+
+  /// Finds the token of a name declaring a binding or referring to one.
+  fn name(&mut self, id: NodeId, span: Option<Span>) {
+    let span = match span {
+      Some(span) => span,
+      None => return,
+    };
+    if let Some(binding) = self.tree.declaration_at(id) {
+      let first = self.locations.declarations.entry(binding).or_insert(span);
+      if span.start() < first.start() {
+        *first = span;
+      }
+    }
+    if let Some(reference) = self.tree.reference_at(id) {
+      self.locations.references.insert(reference, span);
+    }
+  }
+
+  /// Finds the `var`, `let` or `const` keyword before the first name of a declaration.
+  fn keyword(&mut self, key: Span, declared: Option<Span>) {
+    let index = match declared.and_then(|declared| self.token_at(declared.start()).checked_sub(1)) {
+      Some(index) => index,
+      None => return,
+    };
+    if matches!(
+      self.kind(index),
+      Some(TokenKind::Keyword(Keyword::Var))
+        | Some(TokenKind::Keyword(Keyword::Let))
+        | Some(TokenKind::Keyword(Keyword::Const))
+    ) {
+      self.locations.keywords.insert(key, index);
+    }
+  }
+
+  /// Finds the keyword declaring the variable of a `for in` or `for of` loop.
+  fn loop_keyword(&mut self, key: Option<Span>, init: &IterableLoopInitializer) {
+    if let (Some(key), false) = (key, matches!(init, IterableLoopInitializer::Pattern(_))) {
+      self.keyword(key, init.pattern().span());
+    }
+  }
+
+  /// Returns `true` if a `return` statement starts with its keyword, `false` for the concise
+  /// body of an arrow function.
+  fn is_return_keyword(&self, span: Option<Span>) -> bool {
+    span.is_some_and(|span| {
+      self.kind(self.token_at(span.start())) == Some(&TokenKind::Keyword(Keyword::Return))
+    })
+  }
+
+  /// Finds the first token of a member of a class body or an object literal.
+  fn member(&mut self, key: Option<Span>, start: Option<Span>) -> Option<usize> {
+    let (key, start) = (key?, start?);
+    let index = self.token_at(start.start());
+    self.locations.members.insert(key, index);
+    Some(index)
+  }
+}
+
+impl<'ast> Visitor<'ast> for Locator<'_, '_> {
+  fn visit_statements(&mut self, statements: &'ast [Node], path: &mut NodePath<'ast>) -> Flow {
+    for statement in statements {
+      self.statement(statement);
+    }
+    visit::walk_statements(self, statements, path)
+  }
+
+  fn visit_node(&mut self, node: &'ast Node, path: &mut NodePath<'ast>) -> Flow {
+    if let Some(NodeRef::Node(parent)) = path.parent() {
+      if is_body(parent, node) {
+        self.statement(node);
+      }
+    }
+    visit::walk_node(self, node, path)
+  }
+
+  fn visit_identifier(&mut self, ident: &'ast Identifier, _path: &mut NodePath<'ast>) -> Flow {
+    self.name(ident.id(), ident.span());
+    Flow::Continue(())
+  }
+
+  fn visit_function_decl(&mut self, decl: &'ast FunctionDecl, path: &mut NodePath<'ast>) -> Flow {
+    self.name(decl.id(), decl.name_span());
+    visit::walk_function_decl(self, decl, path)
+  }
+
+  fn visit_function_expr(&mut self, expr: &'ast FunctionExpr, path: &mut NodePath<'ast>) -> Flow {
+    self.name(expr.id(), expr.name_span());
+    visit::walk_function_expr(self, expr, path)
+  }
+
+  fn visit_arrow_function_decl(
+    &mut self,
+    arrow: &'ast ArrowFunctionDecl,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    for parameter in arrow.params() {
+      self.visit_formal_parameter(parameter, path)?;
+    }
+    match arrow.body() {
+      // A concise body is a `return` statement which is not in the source.
+      [Node::Return(stmt)] if !self.is_return_keyword(stmt.span()) => match stmt.expr() {
+        Some(expr) => self.visit_node(expr, path),
+        None => Flow::Continue(()),
+      },
+      body => self.visit_statements(body, path),
+    }
+  }
+
+  fn visit_class(&mut self, class: &'ast Class, path: &mut NodePath<'ast>) -> Flow {
+    self.name(class.id(), class.name_span());
+    if let Some(constructor) = class.constructor() {
+      self.member(constructor.span(), class.constructor_span());
+    }
+    for element in class.elements() {
+      self.member(element.span(), element.span());
+    }
+    visit::walk_class(self, class, path)
+  }
+
+  fn visit_object(&mut self, object: &'ast Object, path: &mut NodePath<'ast>) -> Flow {
+    for property in object.properties() {
+      let start = match self.member(property.span(), property.span()) {
+        Some(start) => start,
+        None => continue,
+      };
+      let key = match property {
+        PropertyDefinition::IdentifierReference(..)
+        | PropertyDefinition::Property(PropertyName::Literal(_), ..) => Some(start),
+        PropertyDefinition::MethodDefinition(kind, PropertyName::Literal(_), function, _) => {
+          let modifiers = usize::from(*kind != MethodDefinitionKind::Ordinary)
+            + usize::from(function.kind().is_async())
+            + usize::from(function.kind().is_generator());
+          Some(start + modifiers)
+        }
+        _ => None,
+      };
+      if let (Some(span), Some(key)) = (property.span(), key) {
+        self.locations.keys.insert(span, key);
+      }
+    }
+    visit::walk_object(self, object, path)
+  }
+
+  fn visit_bin_op(&mut self, op: &'ast BinOp, path: &mut NodePath<'ast>) -> Flow {
+    let punctuator = match op.op() {
+      operator::BinOp::Comp(CompOp::Equal) => Some(Punctuator::Eq),
+      operator::BinOp::Comp(CompOp::NotEqual) => Some(Punctuator::NotEq),
+      operator::BinOp::Comp(CompOp::StrictEqual) => Some(Punctuator::StrictEq),
+      operator::BinOp::Comp(CompOp::StrictNotEqual) => Some(Punctuator::StrictNotEq),
+      _ => None,
+    };
+    if let (Some(punctuator), Some(span), Some(lhs)) = (punctuator, op.span(), op.lhs().span()) {
+      let mut index = self.token_at(lhs.end());
+      while self.kind(index) == Some(&TokenKind::Punctuator(Punctuator::CloseParen)) {
+        index += 1;
+      }
+      if self.kind(index) == Some(&TokenKind::Punctuator(punctuator)) {
+        self.locations.operators.insert(span, index);
+      }
+    }
+    visit::walk_bin_op(self, op, path)
+  }
+
+  fn visit_declaration_list(
+    &mut self,
+    list: &'ast DeclarationList,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    if let Some(first) = list.as_ref().first().and_then(|decl| decl.span()) {
+      self.keyword(first, Some(first));
+    }
+    visit::walk_declaration_list(self, list, path)
+  }
+
+  fn visit_for_in_loop(&mut self, stmt: &'ast ForInLoop, path: &mut NodePath<'ast>) -> Flow {
+    self.loop_keyword(stmt.span(), stmt.init());
+    visit::walk_for_in_loop(self, stmt, path)
+  }
+
+  fn visit_for_of_loop(&mut self, stmt: &'ast ForOfLoop, path: &mut NodePath<'ast>) -> Flow {
+    self.loop_keyword(stmt.span(), stmt.init());
+    visit::walk_for_of_loop(self, stmt, path)
+  }
+
+  fn visit_case(&mut self, case: &'ast Case, path: &mut NodePath<'ast>) -> Flow {
+    if let Some(span) = case.span() {
+      let index = self.token_at(span.start());
+      self.locations.cases.insert(span, index);
+    }
+    visit::walk_case(self, case, path)
+  }
+
+  fn visit_export_decl(&mut self, decl: &'ast ExportDecl, path: &mut NodePath<'ast>) -> Flow {
+    if let ExportDecl::Declaration(declaration) = decl {
+      self.statement(declaration);
+    }
+    visit::walk_export_decl(self, decl, path)
+  }
+
+  fn visit_export_specifier(
+    &mut self,
+    specifier: &'ast ExportSpecifier,
+    _path: &mut NodePath<'ast>,
+  ) -> Flow {
+    self.name(specifier.id(), specifier.local_span());
+    Flow::Continue(())
+  }
+
+  fn visit_import_specifier(
+    &mut self,
+    specifier: &'ast ImportSpecifier,
+    _path: &mut NodePath<'ast>,
+  ) -> Flow {
+    let local = specifier.local_identifier();
+    self.name(local.id(), local.span());
+    Flow::Continue(())
+  }
+}
+
+/// Returns `true` if a node is the body of a statement, and not in a statement list.
+fn is_body(parent: &Node, node: &Node) -> bool {
+  let is = |body: &Node| ptr::eq(body, node);
+  match parent {
+    Node::If(stmt) => is(stmt.body()) || stmt.else_node().is_some_and(is),
+    Node::WhileLoop(stmt) => is(stmt.body()),
+    Node::With(stmt) => is(stmt.body()),
+    Node::DoWhileLoop(stmt) => is(stmt.body()),
+    Node::ForLoop(stmt) => is(stmt.body()),
+    Node::ForInLoop(stmt) => is(stmt.body()),
+    Node::ForOfLoop(stmt) => is(stmt.body()),
+    Node::Labelled(stmt) => is(stmt.statement()),
+    _ => false,
+  }
+}
@@ -0,0 +1,175 @@
+//! The text of a source: its tokens, its comments and the byte offsets of its positions.
+
+use crate::syntax::{
+  ast::{Keyword, Position, Punctuator, Span},
+  comments::Comment,
+  lexer::{Lexer, Token, TokenKind},
+};
+
+/// The byte offsets at which the lines of a text start, to convert positions to offsets.
+#[derive(Debug, Clone)]
+pub(crate) struct Lines {
+  starts: Vec<usize>,
+}
+
+impl Lines {
+  /// Finds the lines of a text, ending with the same line terminators as in the lexer.
+  pub(crate) fn new(text: &str) -> Self {
+    let mut starts = vec![0];
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, ch)) = chars.next() {
+      match ch {
+        '\r' => match chars.peek() {
+          Some((_, '\n')) => {
+            chars.next();
+            starts.push(index + 2);
+          }
+          _ => starts.push(index + 1),
+        },
+        '\n' | '\u{2028}' | '\u{2029}' => starts.push(index + ch.len_utf8()),
+        _ => {}
+      }
+    }
+    Self { starts }
+  }
+
+  /// Gets the byte offset of a position, or the length of the text past its end.
+  pub(crate) fn offset(&self, text: &str, position: Position) -> usize {
+    let line = position.line_number() as usize;
+    let start = match self.starts.get(line - 1) {
+      Some(&start) => start,
+      None => return text.len(),
+    };
+    let end = self.starts.get(line).copied().unwrap_or(text.len());
+    text[start..end]
+      .char_indices()
+      .nth(position.column_number() as usize - 1)
+      .map_or(end, |(index, _)| start + index)
+  }
+
+  /// Gets the position of a byte offset.
+  pub(crate) fn position(&self, text: &str, offset: usize) -> Position {
+    let line = self.starts.partition_point(|&start| start <= offset);
+    let start = self.starts[line - 1];
+    let column = text[start..offset].chars().count() + 1;
+    Position::new(line as u32, column as u32)
+  }
+}
+
+/// A parsed source, lexed again to find its tokens and its comments.
+#[derive(Debug)]
+pub(crate) struct Source<'a> {
+  pub(crate) text: &'a str,
+  pub(crate) lines: Lines,
+  /// The significant tokens, without line terminators and comments.
+  pub(crate) tokens: Vec<Token>,
+  pub(crate) comments: Vec<Comment<'a>>,
+}
+
+impl<'a> Source<'a> {
+  /// Lexes a source, stopping at the first error.
+  pub(crate) fn new(text: &'a str) -> Self {
+    let mut source = Self {
+      text,
+      lines: Lines::new(text),
+      tokens: Vec::new(),
+      comments: Vec::new(),
+    };
+    let mut lexer = Lexer::new(text.as_bytes());
+    // The comments are in the text left between the tokens, or are line terminators when they
+    // span several lines.
+    let mut end = 0;
+    while let Ok(Some(token)) = lexer.next() {
+      let start = source.offset(token.span().start());
+      source.comments_in(end, start);
+      end = source.offset(token.span().end());
+      match token.kind() {
+        TokenKind::EOF => break,
+        TokenKind::LineTerminator => source.comments_in(start, end),
+        _ => source.tokens.push(token),
+      }
+    }
+    source.comments_in(end, text.len());
+    source
+  }
+
+  /// Gets the byte offset of a position.
+  pub(crate) fn offset(&self, position: Position) -> usize {
+    self.lines.offset(self.text, position)
+  }
+
+  /// Gets the last token of the statement list a token is in: the last token before the brace
+  /// closing the list, before the next clause of a `switch` statement, or of the source.
+  pub(crate) fn list_end(&self, start: usize) -> usize {
+    self.end(start, true)
+  }
+
+  /// Gets the last token of the members of a class body or an object literal a token is in: the
+  /// last token before the brace closing them.
+  pub(crate) fn members_end(&self, start: usize) -> usize {
+    self.end(start, false)
+  }
+
+  /// Gets the last token before the bracket closing the group a token is in, or before the next
+  /// clause of a `switch` statement when `clauses` is `true`.
+  fn end(&self, start: usize, clauses: bool) -> usize {
+    let mut depth = 0usize;
+    let mut end = start;
+    for (index, token) in self.tokens.iter().enumerate().skip(start) {
+      match token.kind() {
+        TokenKind::Punctuator(Punctuator::OpenBlock)
+        | TokenKind::Punctuator(Punctuator::OpenParen)
+        | TokenKind::Punctuator(Punctuator::OpenBracket) => depth += 1,
+        TokenKind::Punctuator(Punctuator::CloseBlock)
+        | TokenKind::Punctuator(Punctuator::CloseParen)
+        | TokenKind::Punctuator(Punctuator::CloseBracket) => {
+          if depth == 0 {
+            break;
+          }
+          depth -= 1;
+        }
+        TokenKind::TemplateLiteral(template) => match (template.is_head(), template.is_tail()) {
+          (true, false) => depth += 1,
+          (false, true) => depth = depth.saturating_sub(1),
+          _ => {}
+        },
+        // A `case` or `default` keyword ends the statements of the clause before it.
+        TokenKind::Keyword(Keyword::Case) | TokenKind::Keyword(Keyword::Default)
+          if clauses && depth == 0 && index > start =>
+        {
+          break
+        }
+        _ => {}
+      }
+      end = index;
+    }
+    end
+  }
+
+  /// Finds the comments between two byte offsets, where there is nothing but whitespace and
+  /// comments.
+  fn comments_in(&mut self, mut start: usize, end: usize) {
+    while start < end {
+      let rest = &self.text[start..end];
+      let (length, block) = if rest.starts_with("//") {
+        let length = rest
+          .find(['\r', '\n', '\u{2028}', '\u{2029}'])
+          .unwrap_or(rest.len());
+        (length, false)
+      } else if let Some(body) = rest.strip_prefix("/*") {
+        let length = body.find("*/").map_or(rest.len(), |index| index + 4);
+        (length, true)
+      } else {
+        start += rest.chars().next().map_or(1, char::len_utf8);
+        continue;
+      };
+      let text = &rest[2..if block { length.max(4) - 2 } else { length }];
+      let span = Span::new(
+        self.lines.position(self.text, start),
+        self.lines.position(self.text, start + length),
+      );
+      self.comments.push(Comment::new(span, text, block));
+      start += length;
+    }
+  }
+}
@@ -0,0 +1,175 @@
+//! The documents open in the editor, and the positions of the protocol.
+
+use crate::syntax::{
+  ast::{node::StatementList, Position, Span},
+  edit::Edit,
+  incremental,
+  locate::Lines,
+  parser::{ParseError, Parser},
+};
+
+/// A position of the protocol: the line, and the UTF-16 code units before the position on the
+/// line, both counted from zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Point {
+  pub(super) line: u32,
+  pub(super) character: u32,
+}
+
+/// The text of a document open in the editor, and its syntax tree.
+///
+/// The lines are broken as the lexer breaks them, so `\u{2028}` and `\u{2029}` end lines as
+/// well as `\n`, `\r\n` and `\r`.
+///
+/// Once the text parses, the changes are applied to an [incremental document](incremental),
+/// which parses again only what they changed. While the text has syntax errors, it is parsed
+/// with error recovery.
+#[derive(Debug)]
+pub(super) struct Document {
+  text: String,
+  lines: Lines,
+  module: bool,
+  /// The text parsed incrementally, since it first parsed.
+  parsed: Option<incremental::Document>,
+  /// The statements parsed with error recovery and the syntax errors, when the text does not
+  /// parse.
+  recovered: Option<(StatementList, Box<[ParseError]>)>,
+}
+
+impl Document {
+  /// Creates a document parsed as a module, or as a script.
+  pub(super) fn new(text: String, module: bool) -> Self {
+    let mut document = Self {
+      lines: Lines::new(&text),
+      text,
+      module,
+      parsed: None,
+      recovered: None,
+    };
+    document.parse();
+    document
+  }
+
+  /// Gets the text.
+  pub(super) fn text(&self) -> &str {
+    &self.text
+  }
+
+  /// Returns `true` if the document is parsed as a module.
+  pub(super) fn is_module(&self) -> bool {
+    self.module
+  }
+
+  /// Gets the syntax tree, when the text parses.
+  pub(super) fn tree(&self) -> Option<&incremental::Document> {
+    self.parsed.as_ref().filter(|parsed| parsed.is_up_to_date())
+  }
+
+  /// Gets the statements, recovered from the syntax errors if there are any.
+  pub(super) fn statements(&self) -> &StatementList {
+    match (&self.recovered, &self.parsed) {
+      (Some((statements, _)), _) => statements,
+      (None, Some(parsed)) => parsed.statements(),
+      (None, None) => unreachable!("a document is parsed when its text changes"),
+    }
+  }
+
+  /// Gets the syntax errors.
+  pub(super) fn errors(&self) -> &[ParseError] {
+    self.recovered.as_ref().map_or(&[], |(_, errors)| errors)
+  }
+
+  /// Replaces the text between two points, or the whole text without a range, and parses it
+  /// again.
+  pub(super) fn change(&mut self, range: Option<(Point, Point)>, text: &str) {
+    let (start, end) = match range {
+      Some((start, end)) => {
+        let start = self.offset(start);
+        (start, self.offset(end).max(start))
+      }
+      None => (0, self.text.len()),
+    };
+    if let Some(parsed) = &mut self.parsed {
+      let span = Span::new(
+        self.lines.position(&self.text, start),
+        self.lines.position(&self.text, end),
+      );
+      // A syntax error is recovered from below.
+      let _ = parsed.edit(&Edit::new(span, text));
+    }
+    self.text.replace_range(start..end, text);
+    self.lines = Lines::new(&self.text);
+    self.parse();
+  }
+
+  /// Parses the text once it changed: incrementally once it parsed, and with error recovery
+  /// while it does not parse.
+  fn parse(&mut self) {
+    if self.parsed.is_none() {
+      let parsed = if self.module {
+        incremental::Document::parse_module(self.text.as_str())
+      } else {
+        incremental::Document::parse_script(self.text.as_str())
+      };
+      self.parsed = parsed.ok();
+    }
+    self.recovered = None;
+    if self.tree().is_some() {
+      return;
+    }
+    let mut parser = Parser::new(self.text.as_bytes());
+    self.recovered = Some(if self.module {
+      let (module, errors) = parser.parse_module_with_recovery().into_parts();
+      (module.into_body(), errors)
+    } else {
+      parser.parse_script_with_recovery().into_parts()
+    });
+  }
+
+  /// Gets the byte offset of a point, which is the end of its line when the point is past it.
+  pub(super) fn offset(&self, point: Point) -> usize {
+    let start = self
+      .lines
+      .offset(&self.text, Position::new(point.line + 1, 1));
+    let mut units = 0;
+    for (index, ch) in self.text[start..].char_indices() {
+      if units >= point.character as usize || matches!(ch, '\r' | '\n' | '\u{2028}' | '\u{2029}') {
+        return start + index;
+      }
+      units += ch.len_utf16();
+    }
+    self.text.len()
+  }
+
+  /// Gets the point at a byte offset.
+  pub(super) fn point_at(&self, offset: usize) -> Point {
+    let position = self.lines.position(&self.text, offset);
+    let start = self
+      .lines
+      .offset(&self.text, Position::new(position.line_number(), 1));
+    Point {
+      line: position.line_number() - 1,
+      character: self.text[start..offset].encode_utf16().count() as u32,
+    }
+  }
+
+  /// Gets the point of a position of the parser.
+  pub(super) fn point(&self, position: Position) -> Point {
+    self.point_at(self.position_offset(position))
+  }
+
+  /// Gets the position of the parser at a point.
+  pub(super) fn position(&self, point: Point) -> Position {
+    self.lines.position(&self.text, self.offset(point))
+  }
+
+  /// Gets the byte offset of a position of the parser.
+  pub(super) fn position_offset(&self, position: Position) -> usize {
+    self.lines.offset(&self.text, position)
+  }
+
+  /// Gets the point at the end of the text.
+  pub(super) fn end(&self) -> Point {
+    self.point_at(self.text.len())
+  }
+}
@@ -0,0 +1,418 @@
+//! The features of the server, read from the syntax tree and the scopes of a document.
+
+use super::{
+  document::{Document, Point},
+  number, object, string,
+};
+use crate::syntax::{
+  ast::{Keyword, Punctuator, Span},
+  codegen::{Generator, Indent, Options},
+  comments::{Comment, CommentMap},
+  diagnostics::Diagnostic,
+  json::Value,
+  lexer::TokenKind,
+  locate::{Locations, Source},
+  scope::{BindingId, BindingKind, ScopeTree},
+};
+use std::collections::BTreeMap;
+
+/// The types of the semantic tokens, in the order of the legend given to the client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenType {
+  Keyword,
+  Variable,
+  Property,
+  Function,
+  Class,
+  Parameter,
+  String,
+  Number,
+  Operator,
+  Comment,
+}
+
+/// The names of the types of the semantic tokens, indexed by [`TokenType`].
+pub(super) const TOKEN_TYPES: &[&str] = &[
+  "keyword",
+  "variable",
+  "property",
+  "function",
+  "class",
+  "parameter",
+  "string",
+  "number",
+  "operator",
+  "comment",
+];
+
+/// A parsed document, with its scopes and the spans of its names.
+pub(super) struct Analysis<'a> {
+  document: &'a Document,
+  source: Source<'a>,
+  scopes: ScopeTree,
+  locations: Locations,
+}
+
+impl<'a> Analysis<'a> {
+  /// Analyzes the syntax tree of a document.
+  pub(super) fn new(document: &'a Document) -> Self {
+    let source = Source::new(document.text());
+    let statements = document.statements().items();
+    let scopes = ScopeTree::statements(statements, document.is_module());
+    let locations = Locations::new(&source, statements, &scopes);
+    Self {
+      document,
+      source,
+      scopes,
+      locations,
+    }
+  }
+
+  /// Gets the syntax errors, as the diagnostics of the protocol.
+  pub(super) fn diagnostics(&self) -> Value {
+    let diagnostics = self
+      .document
+      .errors()
+      .iter()
+      .map(|error| {
+        let diagnostic = Diagnostic::from(error);
+        // Errors without a position, such as an abrupt end, are at the end of the text.
+        let range = match diagnostic.primary_span() {
+          Some(span) => self.range(span),
+          None => {
+            let end = self.document.end();
+            range(end, end)
+          }
+        };
+        let mut members = vec![("range", range), ("severity", number(1))];
+        if let Some(code) = diagnostic.code() {
+          members.push(("code", string(code)));
+        }
+        members.push(("source", string("plastic")));
+        members.push(("message", string(diagnostic.message())));
+        object(members)
+      })
+      .collect();
+    Value::Array(diagnostics)
+  }
+
+  /// Gets the top level declarations.
+  pub(super) fn symbols(&self) -> Value {
+    let mut symbols: Vec<(Span, Value)> = self
+      .scopes
+      .scope(self.scopes.root())
+      .bindings()
+      .iter()
+      .filter_map(|&id| {
+        let binding = self.scopes.binding(id);
+        let span = self.locations.declaration(id)?;
+        let kind = match binding.kind() {
+          BindingKind::Function => 12,
+          BindingKind::Class => 5,
+          BindingKind::Const => 14,
+          BindingKind::Var | BindingKind::Let | BindingKind::Import => 13,
+          _ => return None,
+        };
+        let range = self.range(span);
+        let symbol = object(vec![
+          ("name", string(binding.name())),
+          ("detail", string(keyword(binding.kind()))),
+          ("kind", number(kind)),
+          ("range", range.clone()),
+          ("selectionRange", range),
+        ]);
+        Some((span, symbol))
+      })
+      .collect();
+    symbols.sort_by_key(|(span, _)| span.start());
+    Value::Array(symbols.into_iter().map(|(_, symbol)| symbol).collect())
+  }
+
+  /// Gets the declaration of the name at a point.
+  pub(super) fn definition(&self, uri: &str, point: Point) -> Value {
+    self
+      .binding_at(point)
+      .and_then(|(binding, _)| self.locations.declaration(binding))
+      .map_or(Value::Null, |span| self.location(uri, span))
+  }
+
+  /// Gets the uses of the binding of the name at a point, and its declaration if asked for.
+  pub(super) fn references(&self, uri: &str, point: Point, declaration: bool) -> Value {
+    let binding = match self.binding_at(point) {
+      Some((binding, _)) => binding,
+      None => return Value::Null,
+    };
+    let mut spans: Vec<Span> = self
+      .scopes
+      .binding(binding)
+      .references()
+      .iter()
+      .filter_map(|&reference| self.locations.reference(reference))
+      .collect();
+    if declaration {
+      spans.extend(self.locations.declaration(binding));
+    }
+    spans.sort_by_key(|span| span.start());
+    spans.dedup();
+    Value::Array(
+      spans
+        .into_iter()
+        .map(|span| self.location(uri, span))
+        .collect(),
+    )
+  }
+
+  /// Gets the kind of declaration of the name at a point.
+  pub(super) fn hover(&self, point: Point) -> Value {
+    let (id, span) = match self.binding_at(point) {
+      Some(found) => found,
+      None => return Value::Null,
+    };
+    let binding = self.scopes.binding(id);
+    let declaration = match binding.kind() {
+      BindingKind::Parameter => format!("(parameter) {}", binding.name()),
+      BindingKind::CatchParameter => format!("(catch parameter) {}", binding.name()),
+      BindingKind::Import => format!("(import) {}", binding.name()),
+      BindingKind::Arguments => format!("(implicit) {}", binding.name()),
+      kind if binding.is_exported() => format!("export {} {}", keyword(kind), binding.name()),
+      kind => format!("{} {}", keyword(kind), binding.name()),
+    };
+    object(vec![
+      (
+        "contents",
+        object(vec![
+          ("kind", string("markdown")),
+          ("value", string(format!("```js\n{}\n```", declaration))),
+        ]),
+      ),
+      ("range", self.range(span)),
+    ])
+  }
+
+  /// Gets the semantic tokens: the tokens classified by the lexer, with the names classified
+  /// by the kind of their binding, and the comments.
+  pub(super) fn semantic_tokens(&self) -> Value {
+    let mut names = BTreeMap::new();
+    for (id, binding) in self.scopes.bindings() {
+      let kind = match binding.kind() {
+        BindingKind::Function => TokenType::Function,
+        BindingKind::Class => TokenType::Class,
+        BindingKind::Parameter | BindingKind::CatchParameter => TokenType::Parameter,
+        _ => continue,
+      };
+      let references = binding
+        .references()
+        .iter()
+        .filter_map(|&reference| self.locations.reference(reference));
+      for span in self.locations.declaration(id).into_iter().chain(references) {
+        names.insert(span.start(), kind);
+      }
+    }
+
+    let mut tokens = Vec::new();
+    let mut after_dot = false;
+    for token in &self.source.tokens {
+      let kind = match token.kind() {
+        TokenKind::Keyword(_) | TokenKind::BooleanLiteral(_) | TokenKind::NullLiteral => {
+          Some(TokenType::Keyword)
+        }
+        TokenKind::Identifier(_) if after_dot => Some(TokenType::Property),
+        TokenKind::Identifier(_) => Some(
+          names
+            .get(&token.span().start())
+            .copied()
+            .unwrap_or(TokenType::Variable),
+        ),
+        TokenKind::PrivateIdentifier(_) => Some(TokenType::Property),
+        TokenKind::StringLiteral(_) | TokenKind::TemplateLiteral(_) => Some(TokenType::String),
+        TokenKind::NumericLiteral(_) => Some(TokenType::Number),
+        TokenKind::Punctuator(punctuator) if is_operator(*punctuator) => Some(TokenType::Operator),
+        _ => None,
+      };
+      after_dot = matches!(
+        token.kind(),
+        TokenKind::Punctuator(Punctuator::Dot) | TokenKind::Punctuator(Punctuator::Optional)
+      );
+      tokens.extend(kind.map(|kind| (token.span(), kind)));
+    }
+    tokens.extend(
+      self
+        .source
+        .comments
+        .iter()
+        .map(|comment| (comment.span(), TokenType::Comment)),
+    );
+    tokens.sort_by_key(|(span, _)| span.start());
+
+    // Each token is encoded as five numbers relative to the token before it, and tokens
+    // spanning several lines, such as templates and block comments, are split into lines.
+    let mut data = Vec::new();
+    let mut last = Point {
+      line: 0,
+      character: 0,
+    };
+    for (span, kind) in tokens {
+      let start = self.document.position_offset(span.start());
+      let end = self.document.position_offset(span.end());
+      let mut offset = start;
+      for line in self.document.text()[start..end.max(start)].split_inclusive(is_line_break) {
+        let text = line.trim_end_matches(is_line_break);
+        let point = self.document.point_at(offset);
+        offset += line.len();
+        if text.is_empty() {
+          continue;
+        }
+        let character = if point.line == last.line {
+          point.character - last.character
+        } else {
+          point.character
+        };
+        data.push(number(point.line - last.line));
+        data.push(number(character));
+        data.push(number(text.encode_utf16().count() as u32));
+        data.push(number(kind as u32));
+        data.push(number(0));
+        last = point;
+      }
+    }
+    object(vec![("data", Value::Array(data))])
+  }
+
+  /// Finds the binding of the name at a point, and the span of the name.
+  fn binding_at(&self, point: Point) -> Option<(BindingId, Span)> {
+    let position = self.document.position(point);
+    let reference = self.scopes.references().find_map(|(id, reference)| {
+      let span = self.locations.reference(id)?;
+      Some((reference.binding()?, span)).filter(|_| span.contains(position))
+    });
+    reference.or_else(|| {
+      self.scopes.bindings().find_map(|(id, _)| {
+        let span = self.locations.declaration(id)?;
+        Some((id, span)).filter(|_| span.contains(position))
+      })
+    })
+  }
+
+  /// Converts a span to a range of the protocol.
+  fn range(&self, span: Span) -> Value {
+    range(
+      self.document.point(span.start()),
+      self.document.point(span.end()),
+    )
+  }
+
+  /// Converts a span of the document to a location of the protocol.
+  fn location(&self, uri: &str, span: Span) -> Value {
+    object(vec![("uri", string(uri)), ("range", self.range(span))])
+  }
+}
+
+/// Formats a document with the code generator, returning the edits to make, or `None` if the
+/// document does not parse or if the formatted text would lose some of its comments.
+pub(super) fn format(document: &Document, options: &Value) -> Option<Value> {
+  let text = document.text();
+  let tree = document.tree()?;
+  let tab_size = match options.get("tabSize") {
+    Some(Value::Number(size)) => size.clamp(1.0, 16.0) as u8,
+    _ => 2,
+  };
+  let indent = match options.get("insertSpaces") {
+    Some(Value::Bool(false)) => Indent::Tab,
+    _ => Indent::Spaces(tab_size),
+  };
+  let options = Options::pretty().with_indent(indent);
+  let (formatted, comments) = match tree.module() {
+    Some(module) => {
+      let comments = CommentMap::module(text, module);
+      let formatted = Generator::new(options)
+        .with_comments(&comments)
+        .module(module);
+      (formatted, comment_texts(comments.comments()))
+    }
+    None => {
+      let comments = CommentMap::script(text, tree.statements());
+      let formatted = Generator::new(options)
+        .with_comments(&comments)
+        .script(tree.statements());
+      (formatted, comment_texts(comments.comments()))
+    }
+  };
+  if comment_texts(&Source::new(&formatted).comments) != comments {
+    return None;
+  }
+  if formatted == text {
+    return Some(Value::Array(Vec::new()));
+  }
+  let start = Point {
+    line: 0,
+    character: 0,
+  };
+  Some(Value::Array(vec![object(vec![
+    ("range", range(start, document.end())),
+    ("newText", string(formatted)),
+  ])]))
+}
+
+/// Gets the texts of comments, sorted, with the pure annotations as the code generator prints
+/// them.
+fn comment_texts(comments: &[Comment<'_>]) -> Vec<(bool, String)> {
+  let mut texts: Vec<_> = comments
+    .iter()
+    .map(|comment| match comment.text().trim() {
+      "#__PURE__" | "@__PURE__" => (true, "#__PURE__".to_owned()),
+      text => (comment.is_block(), text.to_owned()),
+    })
+    .collect();
+  texts.sort();
+  texts
+}
+
+/// Converts two points to a range of the protocol.
+fn range(start: Point, end: Point) -> Value {
+  object(vec![("start", point(start)), ("end", point(end))])
+}
+
+/// Converts a point to a position of the protocol.
+fn point(point: Point) -> Value {
+  object(vec![
+    ("line", number(point.line)),
+    ("character", number(point.character)),
+  ])
+}
+
+/// Gets the keyword declaring a kind of binding.
+fn keyword(kind: BindingKind) -> &'static str {
+  match kind {
+    BindingKind::Var => Keyword::Var.as_str(),
+    BindingKind::Let => Keyword::Let.as_str(),
+    BindingKind::Const => Keyword::Const.as_str(),
+    BindingKind::Function => Keyword::Function.as_str(),
+    BindingKind::Class => Keyword::Class.as_str(),
+    BindingKind::Import => Keyword::Import.as_str(),
+    BindingKind::Parameter | BindingKind::CatchParameter => "parameter",
+    BindingKind::Arguments => "arguments",
+  }
+}
+
+/// Returns `true` for the punctuators that are operators, rather than brackets and separators.
+fn is_operator(punctuator: Punctuator) -> bool {
+  !matches!(
+    punctuator,
+    Punctuator::OpenBlock
+      | Punctuator::CloseBlock
+      | Punctuator::OpenParen
+      | Punctuator::CloseParen
+      | Punctuator::OpenBracket
+      | Punctuator::CloseBracket
+      | Punctuator::Semicolon
+      | Punctuator::Comma
+      | Punctuator::Colon
+      | Punctuator::Dot
+      | Punctuator::Optional
+  )
+}
+
+/// Returns `true` for the characters ending lines.
+fn is_line_break(ch: char) -> bool {
+  matches!(ch, '\r' | '\n' | '\u{2028}' | '\u{2029}')
+}
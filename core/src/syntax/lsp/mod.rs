@@ -0,0 +1,523 @@
+//! A language server: the features of an editor for JavaScript, over the Language Server
+//! Protocol.
+//!
+//! [`serve`] reads the JSON-RPC messages of the client from an input, each after a
+//! `Content-Length` header, and writes the messages of the [`Server`] to an output the same way,
+//! until the client sends the `exit` notification. The `plastic-lsp` binary serves the standard
+//! input and output.
+//!
+//! The server keeps the text of the documents open in the editor, which the client sends whole
+//! or as the edits of ranges, and their syntax trees, as modules, or as scripts for `.cjs`
+//! files. The edits are parsed [incrementally](crate::syntax::incremental) once a document
+//! parses, and a document with syntax errors is parsed again with error recovery. It provides:
+//!
+//!  - The syntax errors, published as the diagnostics of a document when it is opened and when
+//!    it changes.
+//!  - The top level declarations, as the symbols of a document.
+//!  - The declaration of a name and its references, from the scope analysis.
+//!  - The kind of the declaration of a name, on hover.
+//!  - Semantic tokens, classified by the lexer, with names classified by the kind of their
+//!    binding.
+//!  - Formatting with the code generator, keeping the comments, and refused when the formatted
+//!    text would lose some of them.
+//!
+//! Positions are in UTF-16 code units, the encoding of the protocol. A [`Server`] handles
+//! messages one at a time, so it can be driven without any input or output:
+//!
+//! ```
+//! use plastic::syntax::lsp::Server;
+//!
+//! let mut server = Server::new();
+//! server.handle(r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}"#);
+//! let replies = server.handle(
+//!   r#"{"jsonrpc":"2.0","method":"textDocument/didOpen","params":{"textDocument":
+//!     {"uri":"file:///a.js","languageId":"javascript","version":1,"text":"let a = ;"}}}"#,
+//! );
+//! assert_eq!(
+//!   replies,
+//!   [
+//!     "{\"jsonrpc\":\"2.0\",\"method\":\"textDocument/publishDiagnostics\",\"params\":\
+//!      {\"uri\":\"file:///a.js\",\"version\":1,\"diagnostics\":[{\"range\":{\"start\":\
+//!      {\"line\":0,\"character\":8},\"end\":{\"line\":0,\"character\":9}},\"severity\":1,\
+//!      \"code\":\"E0003\",\"source\":\"plastic\",\"message\":\"unexpected token ';', \
+//!      primary expression\"}]}}"
+//!   ]
+//! );
+//! ```
+
+mod document;
+mod features;
+#[cfg(test)]
+mod tests;
+
+use self::{
+  document::{Document, Point},
+  features::Analysis,
+};
+use crate::syntax::json::{self, Value};
+use std::{
+  collections::HashMap,
+  io::{self, BufRead, Write},
+};
+
+/// The error code of a message that is not JSON.
+const PARSE_ERROR: i32 = -32700;
+/// The error code of a request that can not be handled in the state of the server.
+const INVALID_REQUEST: i32 = -32600;
+/// The error code of a request for a method the server does not have.
+const METHOD_NOT_FOUND: i32 = -32601;
+/// The error code of a request with missing or wrong parameters.
+const INVALID_PARAMS: i32 = -32602;
+/// The error code of a request sent before the `initialize` request.
+const SERVER_NOT_INITIALIZED: i32 = -32002;
+
+/// The state of a server, in the lifecycle of the protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+  /// Waiting for the `initialize` request.
+  Uninitialized,
+  Running,
+  /// After the `shutdown` request, waiting for the `exit` notification.
+  ShutDown,
+  /// After the `exit` notification, with the exit code of the process.
+  Exited(i32),
+}
+
+/// An error replying to a request.
+#[derive(Debug)]
+struct Error {
+  code: i32,
+  message: String,
+}
+
+impl Error {
+  fn new<M>(code: i32, message: M) -> Self
+  where
+    M: Into<String>,
+  {
+    Self {
+      code,
+      message: message.into(),
+    }
+  }
+}
+
+/// A language server, handling the messages of a client.
+#[derive(Debug)]
+pub struct Server {
+  state: State,
+  /// The open documents, by their URI.
+  documents: HashMap<String, Document>,
+}
+
+impl Server {
+  /// Creates a server waiting for the `initialize` request.
+  pub fn new() -> Self {
+    Self {
+      state: State::Uninitialized,
+      documents: HashMap::new(),
+    }
+  }
+
+  /// Handles the JSON text of a message of the client, returning the JSON texts of the
+  /// messages to send back, in order.
+  pub fn handle(&mut self, message: &str) -> Vec<String> {
+    let message = match json::parse(message) {
+      Ok(message) => message,
+      Err((error, _)) => {
+        return vec![reply(
+          &Value::Null,
+          Err(Error::new(PARSE_ERROR, format!("invalid JSON: {}", error))),
+        )]
+      }
+    };
+    let method = match message.get("method") {
+      Some(Value::String(method)) => method.as_str(),
+      // A response, while the server sends no requests, or a request without a method.
+      _ => {
+        return match message.get("id") {
+          Some(id) if message.get("result").is_none() && message.get("error").is_none() => {
+            vec![reply(
+              id,
+              Err(Error::new(INVALID_REQUEST, "missing method")),
+            )]
+          }
+          _ => Vec::new(),
+        }
+      }
+    };
+    let params = message.get("params").unwrap_or(&Value::Null);
+    match message.get("id") {
+      Some(id) => {
+        let result = self.request(method, params);
+        vec![reply(id, result)]
+      }
+      None => self.notification(method, params),
+    }
+  }
+
+  /// Gets the exit code of the process, once the client sent the `exit` notification: `0`
+  /// after a `shutdown` request, and `1` without one.
+  pub fn exit_code(&self) -> Option<i32> {
+    match self.state {
+      State::Exited(code) => Some(code),
+      _ => None,
+    }
+  }
+
+  /// Handles a request, returning its result.
+  fn request(&mut self, method: &str, params: &Value) -> Result<Value, Error> {
+    match (self.state, method) {
+      (State::Uninitialized, "initialize") => {
+        self.state = State::Running;
+        Ok(capabilities())
+      }
+      (State::Uninitialized, _) => Err(Error::new(
+        SERVER_NOT_INITIALIZED,
+        "the server is not initialized",
+      )),
+      (State::Running, "initialize") => Err(Error::new(
+        INVALID_REQUEST,
+        "the server is already initialized",
+      )),
+      (State::Running, "shutdown") => {
+        self.state = State::ShutDown;
+        Ok(Value::Null)
+      }
+      (State::Running, _) => self.feature(method, params),
+      (State::ShutDown, _) | (State::Exited(_), _) => {
+        Err(Error::new(INVALID_REQUEST, "the server is shut down"))
+      }
+    }
+  }
+
+  /// Handles a request for a feature of a document.
+  fn feature(&self, method: &str, params: &Value) -> Result<Value, Error> {
+    match method {
+      "textDocument/documentSymbol" => {
+        let (_, document) = self.document(params)?;
+        Ok(Analysis::new(document).symbols())
+      }
+      "textDocument/definition" => {
+        let (uri, document) = self.document(params)?;
+        Ok(Analysis::new(document).definition(uri, point(params)?))
+      }
+      "textDocument/references" => {
+        let (uri, document) = self.document(params)?;
+        let declaration = matches!(
+          params
+            .get("context")
+            .and_then(|context| context.get("includeDeclaration")),
+          Some(Value::Bool(true))
+        );
+        Ok(Analysis::new(document).references(uri, point(params)?, declaration))
+      }
+      "textDocument/hover" => {
+        let (_, document) = self.document(params)?;
+        Ok(Analysis::new(document).hover(point(params)?))
+      }
+      "textDocument/semanticTokens/full" => {
+        let (_, document) = self.document(params)?;
+        Ok(Analysis::new(document).semantic_tokens())
+      }
+      "textDocument/formatting" => {
+        let (_, document) = self.document(params)?;
+        let options = params.get("options").unwrap_or(&Value::Null);
+        Ok(features::format(document, options).unwrap_or(Value::Null))
+      }
+      _ => Err(Error::new(
+        METHOD_NOT_FOUND,
+        format!("unknown method `{}`", method),
+      )),
+    }
+  }
+
+  /// Handles a notification, returning the notifications to send back.
+  fn notification(&mut self, method: &str, params: &Value) -> Vec<String> {
+    if method == "exit" {
+      let code = if self.state == State::ShutDown { 0 } else { 1 };
+      self.state = State::Exited(code);
+      return Vec::new();
+    }
+    if self.state != State::Running {
+      return Vec::new();
+    }
+    let document = params.get("textDocument");
+    let uri = match document.and_then(|document| document.get("uri")) {
+      Some(Value::String(uri)) => uri,
+      _ => return Vec::new(),
+    };
+    let version = document
+      .and_then(|document| document.get("version"))
+      .cloned()
+      .unwrap_or(Value::Null);
+    match method {
+      "textDocument/didOpen" => {
+        let text = match document.and_then(|document| document.get("text")) {
+          Some(Value::String(text)) => text,
+          _ => return Vec::new(),
+        };
+        let module = !uri.ends_with(".cjs");
+        self
+          .documents
+          .insert(uri.clone(), Document::new(text.clone(), module));
+      }
+      "textDocument/didChange" => {
+        let document = match self.documents.get_mut(uri) {
+          Some(document) => document,
+          None => return Vec::new(),
+        };
+        let changes = match params.get("contentChanges") {
+          Some(Value::Array(changes)) => changes.as_slice(),
+          _ => &[],
+        };
+        for change in changes {
+          let text = match change.get("text") {
+            Some(Value::String(text)) => text,
+            _ => continue,
+          };
+          let range = change
+            .get("range")
+            .and_then(|range| Some((to_point(range.get("start")?)?, to_point(range.get("end")?)?)));
+          document.change(range, text);
+        }
+      }
+      "textDocument/didClose" => {
+        self.documents.remove(uri);
+        return vec![publish_diagnostics(
+          uri,
+          Value::Null,
+          Value::Array(Vec::new()),
+        )];
+      }
+      _ => return Vec::new(),
+    }
+    let diagnostics = Analysis::new(&self.documents[uri]).diagnostics();
+    vec![publish_diagnostics(uri, version, diagnostics)]
+  }
+
+  /// Gets the document a request is for, with its URI.
+  fn document<'s, 'p>(&'s self, params: &'p Value) -> Result<(&'p str, &'s Document), Error> {
+    let uri = match params
+      .get("textDocument")
+      .and_then(|document| document.get("uri"))
+    {
+      Some(Value::String(uri)) => uri,
+      _ => return Err(Error::new(INVALID_PARAMS, "missing text document")),
+    };
+    match self.documents.get(uri) {
+      Some(document) => Ok((uri, document)),
+      None => Err(Error::new(
+        INVALID_PARAMS,
+        format!("the document `{}` is not open", uri),
+      )),
+    }
+  }
+}
+
+impl Default for Server {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Serves a client until it sends the `exit` notification or closes the input, returning the
+/// exit code of the process.
+pub fn serve<R, W>(mut input: R, mut output: W) -> io::Result<i32>
+where
+  R: BufRead,
+  W: Write,
+{
+  let mut server = Server::new();
+  while let Some(message) = read_message(&mut input)? {
+    for reply in server.handle(&message) {
+      write_message(&mut output, &reply)?;
+    }
+    if let Some(code) = server.exit_code() {
+      return Ok(code);
+    }
+  }
+  Ok(if server.state == State::ShutDown {
+    0
+  } else {
+    1
+  })
+}
+
+/// Reads the content of the next message, or `None` at the end of the input.
+fn read_message<R>(input: &mut R) -> io::Result<Option<String>>
+where
+  R: BufRead,
+{
+  let mut length = None;
+  let mut line = String::new();
+  loop {
+    line.clear();
+    if input.read_line(&mut line)? == 0 {
+      return Ok(None);
+    }
+    let header = line.trim_end_matches(['\r', '\n']);
+    if header.is_empty() {
+      break;
+    }
+    if let Some((name, value)) = header.split_once(':') {
+      if name.trim().eq_ignore_ascii_case("Content-Length") {
+        length = value.trim().parse::<usize>().ok();
+      }
+    }
+  }
+  let length = length.ok_or_else(|| {
+    io::Error::new(
+      io::ErrorKind::InvalidData,
+      "a message has no `Content-Length` header",
+    )
+  })?;
+  let mut content = vec![0; length];
+  input.read_exact(&mut content)?;
+  String::from_utf8(content)
+    .map(Some)
+    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Writes a message after its header.
+fn write_message<W>(output: &mut W, content: &str) -> io::Result<()>
+where
+  W: Write,
+{
+  write!(
+    output,
+    "Content-Length: {}\r\n\r\n{}",
+    content.len(),
+    content
+  )?;
+  output.flush()
+}
+
+/// Gets the capabilities of the server, as the result of the `initialize` request.
+fn capabilities() -> Value {
+  let legend = object(vec![
+    (
+      "tokenTypes",
+      Value::Array(
+        features::TOKEN_TYPES
+          .iter()
+          .map(|name| string(*name))
+          .collect(),
+      ),
+    ),
+    ("tokenModifiers", Value::Array(Vec::new())),
+  ]);
+  object(vec![
+    (
+      "capabilities",
+      object(vec![
+        ("positionEncoding", string("utf-16")),
+        (
+          "textDocumentSync",
+          object(vec![
+            ("openClose", Value::Bool(true)),
+            ("change", number(2)),
+          ]),
+        ),
+        ("documentSymbolProvider", Value::Bool(true)),
+        ("definitionProvider", Value::Bool(true)),
+        ("referencesProvider", Value::Bool(true)),
+        ("hoverProvider", Value::Bool(true)),
+        (
+          "semanticTokensProvider",
+          object(vec![("legend", legend), ("full", Value::Bool(true))]),
+        ),
+        ("documentFormattingProvider", Value::Bool(true)),
+      ]),
+    ),
+    (
+      "serverInfo",
+      object(vec![
+        ("name", string("plastic-lsp")),
+        ("version", string(env!("CARGO_PKG_VERSION"))),
+      ]),
+    ),
+  ])
+}
+
+/// Gets the JSON text of the reply to a request.
+fn reply(id: &Value, result: Result<Value, Error>) -> String {
+  let mut members = vec![("jsonrpc", string("2.0")), ("id", id.clone())];
+  match result {
+    Ok(result) => members.push(("result", result)),
+    Err(error) => members.push((
+      "error",
+      object(vec![
+        ("code", number(error.code)),
+        ("message", string(error.message)),
+      ]),
+    )),
+  }
+  let mut out = String::new();
+  json::write(&object(members), &mut out);
+  out
+}
+
+/// Gets the JSON text of the notification publishing the diagnostics of a document.
+fn publish_diagnostics(uri: &str, version: Value, diagnostics: Value) -> String {
+  let mut params = vec![("uri", string(uri))];
+  if version != Value::Null {
+    params.push(("version", version));
+  }
+  params.push(("diagnostics", diagnostics));
+  let notification = object(vec![
+    ("jsonrpc", string("2.0")),
+    ("method", string("textDocument/publishDiagnostics")),
+    ("params", object(params)),
+  ]);
+  let mut out = String::new();
+  json::write(&notification, &mut out);
+  out
+}
+
+/// Gets the position of a request.
+fn point(params: &Value) -> Result<Point, Error> {
+  params
+    .get("position")
+    .and_then(to_point)
+    .ok_or_else(|| Error::new(INVALID_PARAMS, "missing position"))
+}
+
+/// Converts a position of the protocol to a point.
+fn to_point(position: &Value) -> Option<Point> {
+  match (position.get("line")?, position.get("character")?) {
+    (Value::Number(line), Value::Number(character)) if *line >= 0.0 && *character >= 0.0 => {
+      Some(Point {
+        line: *line as u32,
+        character: *character as u32,
+      })
+    }
+    _ => None,
+  }
+}
+
+/// Creates a JSON object.
+fn object(members: Vec<(&str, Value)>) -> Value {
+  Value::Object(
+    members
+      .into_iter()
+      .map(|(name, value)| (name.to_owned(), value))
+      .collect(),
+  )
+}
+
+/// Creates a JSON string.
+fn string<S>(value: S) -> Value
+where
+  S: Into<String>,
+{
+  Value::String(value.into())
+}
+
+/// Creates a JSON number.
+fn number<N>(value: N) -> Value
+where
+  N: Into<f64>,
+{
+  Value::Number(value.into())
+}
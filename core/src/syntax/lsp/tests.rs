@@ -0,0 +1,512 @@
+//! Tests for the language server, as scripted sessions of JSON-RPC messages.
+
+use super::{
+  document::{Document, Point},
+  serve,
+};
+use crate::syntax::{
+  json::{self, Value},
+  parser::Parser,
+};
+
+/// A scripted session: the messages of the client, sent in order.
+#[derive(Debug, Default)]
+struct Session {
+  input: Vec<u8>,
+  next_id: u32,
+}
+
+impl Session {
+  /// Starts a session with the `initialize` request.
+  fn initialized() -> Self {
+    let mut session = Self::default();
+    session.request("initialize", r#"{"processId":null,"capabilities":{}}"#);
+    session.notify("initialized", "{}");
+    session
+  }
+
+  /// Sends a request with the next id.
+  fn request(&mut self, method: &str, params: &str) -> &mut Self {
+    self.next_id += 1;
+    let message = format!(
+      r#"{{"jsonrpc":"2.0","id":{},"method":"{}","params":{}}}"#,
+      self.next_id, method, params
+    );
+    self.send(&message)
+  }
+
+  /// Sends a notification.
+  fn notify(&mut self, method: &str, params: &str) -> &mut Self {
+    let message = format!(
+      r#"{{"jsonrpc":"2.0","method":"{}","params":{}}}"#,
+      method, params
+    );
+    self.send(&message)
+  }
+
+  /// Opens a document.
+  fn open(&mut self, uri: &str, text: &str) -> &mut Self {
+    let mut quoted = String::new();
+    json::write_string(text, &mut quoted);
+    let params = format!(
+      r#"{{"textDocument":{{"uri":"{}","languageId":"javascript","version":1,"text":{}}}}}"#,
+      uri, quoted
+    );
+    self.notify("textDocument/didOpen", &params)
+  }
+
+  /// Sends a message after its header.
+  fn send(&mut self, message: &str) -> &mut Self {
+    self
+      .input
+      .extend(format!("Content-Length: {}\r\n\r\n{}", message.len(), message).bytes());
+    self
+  }
+
+  /// Runs the session, returning the exit code and the messages of the server.
+  #[track_caller]
+  fn run(&self) -> (i32, Vec<Value>) {
+    let mut output = Vec::new();
+    let code = serve(self.input.as_slice(), &mut output).expect("the session failed");
+    let mut output = String::from_utf8(output).expect("the output is not UTF-8");
+    let mut messages = Vec::new();
+    while !output.is_empty() {
+      let (header, rest) = output.split_once("\r\n\r\n").expect("missing header");
+      let length: usize = header
+        .strip_prefix("Content-Length: ")
+        .and_then(|length| length.parse().ok())
+        .expect("invalid header");
+      messages.push(json::parse(&rest[..length]).expect("invalid JSON"));
+      output = rest[length..].to_owned();
+    }
+    (code, messages)
+  }
+
+  /// Runs the session, returning the result of the last request.
+  #[track_caller]
+  fn result(&self) -> Value {
+    let (_, messages) = self.run();
+    let id = Value::Number(self.next_id.into());
+    let reply = messages
+      .into_iter()
+      .find(|message| message.get("id") == Some(&id))
+      .expect("no reply to the last request");
+    match reply.get("result") {
+      Some(result) => result.clone(),
+      None => panic!("the last request failed: {:?}", reply),
+    }
+  }
+}
+
+/// Parses the expected JSON of a test.
+#[track_caller]
+fn parse(text: &str) -> Value {
+  json::parse(text).unwrap_or_else(|(message, offset)| panic!("{} at {}", message, offset))
+}
+
+/// Gets the parameters of a request at a position of a document.
+fn at(uri: &str, line: u32, character: u32) -> String {
+  format!(
+    r#"{{"textDocument":{{"uri":"{}"}},"position":{{"line":{},"character":{}}}}}"#,
+    uri, line, character
+  )
+}
+
+#[test]
+fn lifecycle() {
+  let mut session = Session::default();
+  session
+    .request("textDocument/hover", &at("file:///a.js", 0, 0))
+    .request("initialize", "{}")
+    .request("initialize", "{}")
+    .request("workspace/symbol", "{}")
+    .request("shutdown", "null")
+    .request("textDocument/hover", &at("file:///a.js", 0, 0))
+    .notify("exit", "null");
+  let (code, messages) = session.run();
+  assert_eq!(code, 0);
+  let errors: Vec<_> = messages
+    .iter()
+    .map(
+      |message| match message.get("error").and_then(|error| error.get("code")) {
+        Some(Value::Number(code)) => *code as i32,
+        _ => 0,
+      },
+    )
+    .collect();
+  assert_eq!(errors, [-32002, 0, -32600, -32601, 0, -32600]);
+
+  let capabilities = messages[1]
+    .get("result")
+    .and_then(|result| result.get("capabilities"))
+    .unwrap();
+  for provider in &[
+    "documentSymbolProvider",
+    "definitionProvider",
+    "referencesProvider",
+    "hoverProvider",
+    "documentFormattingProvider",
+  ] {
+    assert_eq!(capabilities.get(provider), Some(&Value::Bool(true)));
+  }
+  assert_eq!(messages[4].get("result"), Some(&Value::Null));
+
+  // Exiting without shutting down, or without exiting at all, is an error.
+  let mut session = Session::initialized();
+  session.notify("exit", "null");
+  assert_eq!(session.run().0, 1);
+  assert_eq!(Session::initialized().run().0, 1);
+}
+
+#[test]
+fn framing() {
+  let mut session = Session::default();
+  let message = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}"#;
+  session.input.extend(
+    format!(
+      "content-length: {}\r\nContent-Type: application/vscode-jsonrpc; charset=utf-8\r\n\r\n{}",
+      message.len(),
+      message
+    )
+    .bytes(),
+  );
+  session
+    .send("{not json")
+    .send(r#"{"jsonrpc":"2.0","id":2}"#);
+  let (_, messages) = session.run();
+  assert_eq!(messages.len(), 3);
+  assert!(messages[0].get("result").is_some());
+  assert_eq!(
+    messages[1].get("error").and_then(|error| error.get("code")),
+    Some(&Value::Number(-32700.0))
+  );
+  assert_eq!(
+    messages[2].get("error").and_then(|error| error.get("code")),
+    Some(&Value::Number(-32600.0))
+  );
+
+  // A message without a length can not be read.
+  let input = b"Content-Type: text/plain\r\n\r\n{}".to_vec();
+  assert!(serve(input.as_slice(), Vec::new()).is_err());
+}
+
+#[test]
+fn diagnostics() {
+  let mut session = Session::initialized();
+  session
+    .open("file:///a.js", "let a = 1;\nlet b = ;\n")
+    .notify(
+      "textDocument/didChange",
+      r#"{"textDocument":{"uri":"file:///a.js","version":2},"contentChanges":[
+        {"range":{"start":{"line":1,"character":8},"end":{"line":1,"character":8}},"text":"a"}
+      ]}"#,
+    )
+    .notify(
+      "textDocument/didChange",
+      r#"{"textDocument":{"uri":"file:///a.js","version":3},"contentChanges":[
+        {"text":"if (a) {\n"}
+      ]}"#,
+    )
+    .notify(
+      "textDocument/didClose",
+      r#"{"textDocument":{"uri":"file:///a.js"}}"#,
+    );
+  let (_, messages) = session.run();
+  let published: Vec<_> = messages
+    .iter()
+    .filter(|message| {
+      message.get("method") == Some(&Value::String("textDocument/publishDiagnostics".into()))
+    })
+    .map(|message| message.get("params").unwrap().clone())
+    .collect();
+  assert_eq!(
+    published,
+    [
+      parse(
+        r#"{"uri":"file:///a.js","version":1,"diagnostics":[{
+          "range":{"start":{"line":1,"character":8},"end":{"line":1,"character":9}},
+          "severity":1,"code":"E0003","source":"plastic",
+          "message":"unexpected token ';', primary expression"
+        }]}"#
+      ),
+      parse(r#"{"uri":"file:///a.js","version":2,"diagnostics":[]}"#),
+      parse(
+        r#"{"uri":"file:///a.js","version":3,"diagnostics":[{
+          "range":{"start":{"line":1,"character":0},"end":{"line":1,"character":0}},
+          "severity":1,"code":"E0006","source":"plastic",
+          "message":"expected token '}', got the end of the input in block"
+        }]}"#
+      ),
+      parse(r#"{"uri":"file:///a.js","diagnostics":[]}"#),
+    ]
+  );
+}
+
+#[test]
+fn changes() {
+  /// Checks that the statements of a document are the ones of a fresh parse of its text.
+  #[track_caller]
+  fn check(document: &Document) {
+    let fresh = Parser::new(document.text().as_bytes())
+      .parse_module()
+      .expect("the text does not parse");
+    assert!(document.tree().is_some());
+    assert!(document.errors().is_empty());
+    assert_eq!(document.statements(), fresh.body());
+  }
+
+  let point = |line, character| Point { line, character };
+  // The emoji takes two UTF-16 code units.
+  let mut document = Document::new(
+    "let a = '😀', c = 1;\nfunction f(b) {\n  return b;\n}\n".to_owned(),
+    true,
+  );
+  check(&document);
+  document.change(Some((point(2, 9), point(2, 10))), "b + a");
+  check(&document);
+  document.change(Some((point(0, 18), point(0, 19))), "2");
+  assert_eq!(
+    document.text(),
+    "let a = '😀', c = 2;\nfunction f(b) {\n  return b + a;\n}\n"
+  );
+  check(&document);
+
+  // The errors are recovered from until a change fixes them.
+  document.change(Some((point(2, 9), point(2, 10))), "(");
+  assert!(document.tree().is_none());
+  assert_eq!(document.errors().len(), 1);
+  document.change(Some((point(2, 9), point(2, 10))), "b");
+  check(&document);
+  document.change(None, "let d = ;\n");
+  assert_eq!(document.errors().len(), 1);
+  document.change(None, "let d = 1;\n");
+  check(&document);
+}
+
+#[test]
+fn symbols() {
+  let mut session = Session::initialized();
+  session
+    .open(
+      "file:///a.js",
+      "import { read } from './io';\nexport const limit = 10;\nfunction check(value) {\n  let seen = 0;\n}\nclass Point {}\nvar count;\n",
+    )
+    .request(
+      "textDocument/documentSymbol",
+      r#"{"textDocument":{"uri":"file:///a.js"}}"#,
+    );
+  let symbols: Vec<_> = match session.result() {
+    Value::Array(symbols) => symbols
+      .iter()
+      .map(|symbol| {
+        let field = |name| match symbol.get(name) {
+          Some(Value::String(value)) => value.clone(),
+          Some(Value::Number(value)) => value.to_string(),
+          _ => String::new(),
+        };
+        let line = match symbol.get("range").and_then(|range| range.get("start")) {
+          Some(start) => start.get("line").cloned(),
+          None => None,
+        };
+        (field("name"), field("detail"), field("kind"), line)
+      })
+      .collect(),
+    result => panic!("unexpected symbols: {:?}", result),
+  };
+  let symbol = |name: &str, detail: &str, kind: &str, line: f64| {
+    (
+      name.to_owned(),
+      detail.to_owned(),
+      kind.to_owned(),
+      Some(Value::Number(line)),
+    )
+  };
+  assert_eq!(
+    symbols,
+    [
+      symbol("read", "import", "13", 0.0),
+      symbol("limit", "const", "14", 1.0),
+      symbol("check", "function", "12", 2.0),
+      symbol("Point", "class", "5", 5.0),
+      symbol("count", "var", "13", 6.0),
+    ]
+  );
+}
+
+#[test]
+fn definition_and_references() {
+  // The emoji takes two UTF-16 code units, and a single character for the parser.
+  let text = "let total = '😀';\nfunction add(n) {\n  total = total + n;\n}\nadd(total);\n";
+  let location = |line, start, end| {
+    parse(&format!(
+      r#"{{"uri":"file:///a.js","range":{{"start":{{"line":{0},"character":{1}}},"end":{{"line":{0},"character":{2}}}}}}}"#,
+      line, start, end
+    ))
+  };
+
+  let mut session = Session::initialized();
+  session
+    .open("file:///a.js", text)
+    .request("textDocument/definition", &at("file:///a.js", 4, 6));
+  assert_eq!(session.result(), location(0, 4, 9));
+  session.request("textDocument/definition", &at("file:///a.js", 2, 18));
+  assert_eq!(session.result(), location(1, 13, 14));
+  session.request("textDocument/definition", &at("file:///a.js", 4, 0));
+  assert_eq!(session.result(), location(1, 9, 12));
+  session.request("textDocument/definition", &at("file:///a.js", 0, 13));
+  assert_eq!(session.result(), Value::Null);
+
+  session.request(
+    "textDocument/references",
+    r#"{"textDocument":{"uri":"file:///a.js"},"position":{"line":0,"character":6},
+      "context":{"includeDeclaration":true}}"#,
+  );
+  assert_eq!(
+    session.result(),
+    Value::Array(vec![
+      location(0, 4, 9),
+      location(2, 2, 7),
+      location(2, 10, 15),
+      location(4, 4, 9),
+    ])
+  );
+  session.request(
+    "textDocument/references",
+    r#"{"textDocument":{"uri":"file:///a.js"},"position":{"line":2,"character":18},
+      "context":{"includeDeclaration":false}}"#,
+  );
+  assert_eq!(session.result(), Value::Array(vec![location(2, 18, 19)]));
+}
+
+#[test]
+fn hover() {
+  let text = "export const limit = 1;\ntry {} catch (error) { error; }\nfunction f(a) { return arguments; }\nimport x from 'x';\nconsole.log(x);\n";
+  let hover = |line, character| {
+    let mut session = Session::initialized();
+    session
+      .open("file:///a.mjs", text)
+      .request("textDocument/hover", &at("file:///a.mjs", line, character));
+    match session.result() {
+      Value::Null => None,
+      result => match result
+        .get("contents")
+        .and_then(|contents| contents.get("value"))
+      {
+        Some(Value::String(value)) => Some(
+          value
+            .trim_start_matches("```js\n")
+            .trim_end_matches("\n```")
+            .to_owned(),
+        ),
+        _ => panic!("unexpected hover: {:?}", result),
+      },
+    }
+  };
+  assert_eq!(hover(0, 15).as_deref(), Some("export const limit"));
+  assert_eq!(hover(1, 24).as_deref(), Some("(catch parameter) error"));
+  assert_eq!(hover(2, 9).as_deref(), Some("function f"));
+  assert_eq!(hover(2, 11).as_deref(), Some("(parameter) a"));
+  assert_eq!(hover(2, 25).as_deref(), Some("(implicit) arguments"));
+  assert_eq!(hover(4, 12).as_deref(), Some("(import) x"));
+  assert_eq!(hover(4, 2), None);
+  assert_eq!(hover(1, 1), None);
+}
+
+#[test]
+fn semantic_tokens() {
+  let mut session = Session::initialized();
+  session
+    .open(
+      "file:///a.js",
+      "// sum\nfunction f(a) {\n  return a.b + `x\n${1}`;\n}\n",
+    )
+    .request(
+      "textDocument/semanticTokens/full",
+      r#"{"textDocument":{"uri":"file:///a.js"}}"#,
+    );
+  let data = match session.result().get("data") {
+    Some(Value::Array(data)) => data
+      .iter()
+      .map(|number| match number {
+        Value::Number(number) => *number as u32,
+        _ => panic!("unexpected number: {:?}", number),
+      })
+      .collect::<Vec<_>>(),
+    result => panic!("unexpected tokens: {:?}", result),
+  };
+  let tokens: Vec<_> = data.chunks(5).map(|token| token.to_vec()).collect();
+  assert_eq!(
+    tokens,
+    [
+      // `// sum`, a comment.
+      vec![0, 0, 6, 9, 0],
+      // `function`, a keyword, `f`, a function and `a`, a parameter.
+      vec![1, 0, 8, 0, 0],
+      vec![0, 9, 1, 3, 0],
+      vec![0, 2, 1, 5, 0],
+      // `return`, `a`, `.b`, a property, `+`, an operator, and a template on two lines.
+      vec![1, 2, 6, 0, 0],
+      vec![0, 7, 1, 5, 0],
+      vec![0, 2, 1, 2, 0],
+      vec![0, 2, 1, 8, 0],
+      vec![0, 2, 2, 6, 0],
+      vec![1, 0, 2, 6, 0],
+      vec![0, 2, 1, 7, 0],
+      vec![0, 1, 2, 6, 0],
+    ]
+  );
+}
+
+#[test]
+fn formatting() {
+  let mut session = Session::initialized();
+  session
+    .open("file:///a.js", "// first\nif(a){b( 1,2 )}\n")
+    .request(
+      "textDocument/formatting",
+      r#"{"textDocument":{"uri":"file:///a.js"},"options":{"tabSize":4,"insertSpaces":true}}"#,
+    );
+  assert_eq!(
+    session.result(),
+    parse(
+      r#"[{"range":{"start":{"line":0,"character":0},"end":{"line":2,"character":0}},
+        "newText":"// first\nif (a) {\n    b(1, 2);\n}\n"}]"#
+    )
+  );
+
+  session.request(
+    "textDocument/formatting",
+    r#"{"textDocument":{"uri":"file:///a.js"},"options":{"tabSize":2,"insertSpaces":false}}"#,
+  );
+  assert_eq!(
+    session.result(),
+    parse(
+      r#"[{"range":{"start":{"line":0,"character":0},"end":{"line":2,"character":0}},
+        "newText":"// first\nif (a) {\n\tb(1, 2);\n}\n"}]"#
+    )
+  );
+
+  // Formatted documents are left as they are, and documents with errors are not formatted.
+  let mut session = Session::initialized();
+  session.open("file:///a.js", "let a = 1;\n").request(
+    "textDocument/formatting",
+    r#"{"textDocument":{"uri":"file:///a.js"},"options":{"tabSize":2,"insertSpaces":true}}"#,
+  );
+  assert_eq!(session.result(), Value::Array(Vec::new()));
+  let mut session = Session::initialized();
+  session.open("file:///a.js", "let a = ;\n").request(
+    "textDocument/formatting",
+    r#"{"textDocument":{"uri":"file:///a.js"},"options":{"tabSize":2,"insertSpaces":true}}"#,
+  );
+  assert_eq!(session.result(), Value::Null);
+
+  // Documents are not formatted when the formatted text would lose a comment.
+  let mut session = Session::initialized();
+  session
+    .open("file:///a.js", "export default /* f */ function () {}\n")
+    .request(
+      "textDocument/formatting",
+      r#"{"textDocument":{"uri":"file:///a.js"},"options":{"tabSize":2,"insertSpaces":true}}"#,
+    );
+  assert_eq!(session.result(), Value::Null);
+}
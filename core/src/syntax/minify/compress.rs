@@ -0,0 +1,278 @@
+//! Rewrites of the syntax tree that shorten the printed code without changing its meaning.
+
+use crate::syntax::ast::{
+  arena::ArenaBox,
+  node::{
+    ArrowFunctionDecl, BinOp, Block, Case, ClassElement, Declaration, DeclarationList, ExportDecl,
+    FunctionDecl, FunctionExpr, Identifier, IterableLoopInitializer, Pattern, StatementList,
+    UnaryOp,
+  },
+  operator as op,
+  visitor::{
+    visit, visit_mut, Control, Flow, KindPath, NodePath, NodeRef, Path, Visitor, VisitorMut,
+  },
+  Const, Node, Sym,
+};
+
+/// Shortens literals and statement lists.
+#[derive(Debug)]
+pub(super) struct Compressor {
+  /// Whether `undefined` can be replaced by `void 0`, no binding shadowing it.
+  void_undefined: bool,
+}
+
+impl Compressor {
+  /// Creates a compressor for a script or a module.
+  pub(super) fn new(statements: &[Node]) -> Self {
+    let mut finder = UndefinedBinding(false);
+    let _ = finder.visit_statements(statements, &mut Path::new());
+    Self {
+      void_undefined: !finder.0,
+    }
+  }
+
+  /// Compresses the statements of a script or a module.
+  pub(super) fn statements(&mut self, statements: &mut StatementList) {
+    let _ = self.visit_statements(statements.items_mut(), &mut Path::new());
+    statements.replace_items(compress_statements);
+  }
+}
+
+impl VisitorMut for Compressor {
+  fn visit_node(&mut self, node: &mut Node, path: &mut KindPath) -> Flow {
+    match node {
+      Node::Const(Const::Bool(value), _) => {
+        *node = UnaryOp::new(op::UnaryOp::Not, Const::from(if *value { 0 } else { 1 })).into();
+        Flow::Continue(())
+      }
+      Node::Identifier(ident) if self.void_undefined && ident.name() == "undefined" => {
+        *node = UnaryOp::new(op::UnaryOp::Void, Const::from(0)).into();
+        Flow::Continue(())
+      }
+      _ => visit_mut::walk_node(self, node, path),
+    }
+  }
+
+  fn visit_unary_op(&mut self, expr: &mut UnaryOp, path: &mut KindPath) -> Flow {
+    // `undefined++` and `delete undefined` have no `void 0` equivalent.
+    let assigns = matches!(
+      expr.op(),
+      op::UnaryOp::IncrementPre
+        | op::UnaryOp::IncrementPost
+        | op::UnaryOp::DecrementPre
+        | op::UnaryOp::DecrementPost
+        | op::UnaryOp::Delete
+    );
+    if assigns && matches!(expr.target(), Node::Identifier(_)) {
+      return Flow::Continue(());
+    }
+    visit_mut::walk_unary_op(self, expr, path)
+  }
+
+  fn visit_bin_op(&mut self, expr: &mut BinOp, path: &mut KindPath) -> Flow {
+    match (expr.op(), expr.lhs()) {
+      (op::BinOp::Assign(_), Node::Identifier(_)) => self.visit_node(expr.rhs_mut(), path),
+      _ => visit_mut::walk_bin_op(self, expr, path),
+    }
+  }
+
+  fn visit_block(&mut self, block: &mut Block, path: &mut KindPath) -> Flow {
+    visit_mut::walk_block(self, block, path)?;
+    block
+      .statement_list_mut()
+      .replace_items(compress_statements);
+    Flow::Continue(())
+  }
+
+  fn visit_function_decl(&mut self, decl: &mut FunctionDecl, path: &mut KindPath) -> Flow {
+    visit_mut::walk_function_decl(self, decl, path)?;
+    decl.body_list_mut().replace_items(compress_statements);
+    Flow::Continue(())
+  }
+
+  fn visit_function_expr(&mut self, expr: &mut FunctionExpr, path: &mut KindPath) -> Flow {
+    visit_mut::walk_function_expr(self, expr, path)?;
+    expr.body_list_mut().replace_items(compress_statements);
+    Flow::Continue(())
+  }
+
+  fn visit_arrow_function_decl(
+    &mut self,
+    arrow: &mut ArrowFunctionDecl,
+    path: &mut KindPath,
+  ) -> Flow {
+    visit_mut::walk_arrow_function_decl(self, arrow, path)?;
+    arrow.body_list_mut().replace_items(compress_statements);
+    Flow::Continue(())
+  }
+
+  fn visit_case(&mut self, case: &mut Case, path: &mut KindPath) -> Flow {
+    visit_mut::walk_case(self, case, path)?;
+    case.body_list_mut().replace_items(compress_statements);
+    Flow::Continue(())
+  }
+
+  fn visit_class_element(&mut self, element: &mut ClassElement, path: &mut KindPath) -> Flow {
+    visit_mut::walk_class_element(self, element, path)?;
+    if let ClassElement::StaticBlock(body, _) = element {
+      body.replace_items(compress_statements);
+    }
+    Flow::Continue(())
+  }
+}
+
+/// Compresses a list of statements.
+///
+/// Consecutive declarations of the same kind are merged and empty statements dropped. The
+/// statements after a `return`, `throw`, `break` or `continue` are dropped too, except for the
+/// function declarations and the names of the variables they hoist: `var` declarations keep
+/// their names without initializers, and `let`, `const` and `class` declarations become `let`
+/// declarations, so that closures still see them uninitialized.
+fn compress_statements(items: Vec<Node>) -> Vec<Node> {
+  let mut statements = Vec::with_capacity(items.len());
+  let mut items = items.into_iter();
+  while let Some(item) = items.next() {
+    let jumps = matches!(
+      item,
+      Node::Return(_) | Node::Throw(_) | Node::Break(_) | Node::Continue(_)
+    );
+    push(&mut statements, item);
+    if jumps {
+      let mut vars = VarNames(Vec::new());
+      let mut lexical = Vec::new();
+      for item in items {
+        match &item {
+          Node::FunctionDecl(_) => push(&mut statements, item),
+          Node::DeclarationList(DeclarationList::Let(list), _)
+          | Node::DeclarationList(DeclarationList::Const(list), _) => lexical.extend(
+            list
+              .iter()
+              .flat_map(|decl| decl.target().bound_names())
+              .map(Box::from),
+          ),
+          Node::ClassDecl(class) => lexical.extend(class.name().map(Box::from)),
+          item => {
+            let _ = vars.visit_node(item, &mut Path::new());
+          }
+        }
+      }
+      push(
+        &mut statements,
+        uninitialized(lexical, DeclarationList::Let),
+      );
+      push(&mut statements, uninitialized(vars.0, DeclarationList::Var));
+      break;
+    }
+  }
+  statements
+}
+
+/// Adds a statement to a list, merging it with the previous declaration of the same kind.
+fn push(statements: &mut Vec<Node>, mut statement: Node) {
+  match (statements.last_mut(), &mut statement) {
+    (_, Node::Empty(_)) => {}
+    (
+      Some(Node::DeclarationList(DeclarationList::Var(first), _)),
+      Node::DeclarationList(DeclarationList::Var(second), _),
+    )
+    | (
+      Some(Node::DeclarationList(DeclarationList::Let(first), _)),
+      Node::DeclarationList(DeclarationList::Let(second), _),
+    )
+    | (
+      Some(Node::DeclarationList(DeclarationList::Const(first), _)),
+      Node::DeclarationList(DeclarationList::Const(second), _),
+    ) => {
+      let mut declarations = std::mem::take(first).into_vec();
+      declarations.extend(std::mem::take(second).into_vec());
+      *first = declarations.into();
+    }
+    _ => statements.push(statement),
+  }
+}
+
+/// Declares names without initializers, or returns an empty statement if there are none.
+fn uninitialized<F>(names: Vec<Box<str>>, kind: F) -> Node
+where
+  F: FnOnce(ArenaBox<[Declaration]>) -> DeclarationList,
+{
+  if names.is_empty() {
+    return Node::empty();
+  }
+  let mut declarations: Vec<Declaration> = Vec::with_capacity(names.len());
+  for name in names {
+    if !declarations
+      .iter()
+      .any(|decl| decl.target().bound_names() == [&*name])
+    {
+      declarations.push(Declaration::new(Identifier::new(name), None));
+    }
+  }
+  kind(declarations.into()).into()
+}
+
+/// Collects the names of the variables declared by `var`, and of the functions declared in
+/// blocks, outside of nested functions.
+struct VarNames(Vec<Box<str>>);
+
+impl<'ast> Visitor<'ast> for VarNames {
+  fn enter(&mut self, node: NodeRef<'ast>, _path: &NodePath<'ast>) -> Control {
+    match node {
+      NodeRef::Node(Node::DeclarationList(DeclarationList::Var(list), _)) => {
+        self.0.extend(
+          list
+            .iter()
+            .flat_map(|decl| decl.target().bound_names())
+            .map(Box::from),
+        );
+        Control::Continue
+      }
+      NodeRef::Node(Node::FunctionDecl(decl)) => {
+        self.0.push(decl.name().into());
+        Control::SkipChildren
+      }
+      NodeRef::Node(Node::FunctionExpr(_))
+      | NodeRef::Node(Node::ArrowFunctionDecl(_))
+      | NodeRef::Node(Node::ClassDecl(_))
+      | NodeRef::Node(Node::ClassExpr(_)) => Control::SkipChildren,
+      _ => Control::Continue,
+    }
+  }
+
+  fn visit_iterable_loop_initializer(
+    &mut self,
+    init: &'ast IterableLoopInitializer,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    if let IterableLoopInitializer::Var(pattern) = init {
+      self
+        .0
+        .extend(pattern.bound_names().into_iter().map(Box::from));
+    }
+    visit::walk_iterable_loop_initializer(self, init, path)
+  }
+}
+
+/// Finds whether a binding or an assignment target is named `undefined`.
+struct UndefinedBinding(bool);
+
+impl<'ast> Visitor<'ast> for UndefinedBinding {
+  fn enter(&mut self, node: NodeRef<'ast>, _path: &NodePath<'ast>) -> Control {
+    let name = match node {
+      NodeRef::Pattern(Pattern::Identifier(ident)) => Some(ident.name()),
+      NodeRef::Node(Node::FunctionDecl(decl)) => Some(decl.name()),
+      NodeRef::Node(Node::FunctionExpr(expr)) => expr.name(),
+      NodeRef::Node(Node::ClassDecl(class)) | NodeRef::Node(Node::ClassExpr(class)) => class.name(),
+      NodeRef::Node(Node::Export(ExportDecl::DefaultFunction(function), _)) => function.name(),
+      NodeRef::Node(Node::Export(ExportDecl::DefaultClass(class), _)) => class.name(),
+      NodeRef::ImportSpecifier(specifier) => Some(specifier.local()),
+      _ => None,
+    };
+    if name.map(Sym::as_str) == Some("undefined") {
+      self.0 = true;
+      Control::Stop
+    } else {
+      Control::Continue
+    }
+  }
+}
@@ -0,0 +1,295 @@
+//! Renaming of local bindings to short names.
+//!
+//! The bindings and the references to them come from the [`ScopeTree`] of the statements. The
+//! bindings are given new names scope by scope, outer scopes first, then a visit of the syntax
+//! tree renames the declarations and the references, found in the scope tree by their names.
+
+use crate::syntax::{
+  ast::{
+    node::{
+      Class, ExportDecl, FunctionDecl, FunctionExpr, Identifier, ImportSpecifier,
+      PropertyDefinition, PropertyName,
+    },
+    visitor::{visit_mut, Flow, KindPath, Path, VisitorMut},
+    Keyword, Node, NodeId,
+  },
+  lexer::STRICT_FORBIDDEN_IDENTIFIERS,
+  scope::{BindingId, BindingKind, ScopeId, ScopeKind, ScopeTree},
+};
+use std::{
+  cmp::Reverse,
+  collections::{BTreeSet, HashMap, HashSet},
+};
+
+/// Renames the local bindings of a script or a module to the shortest names that keep its
+/// meaning.
+///
+/// The top level bindings of a script are global and keep their names, as do the exported
+/// bindings of a module, the `arguments` bindings, the bindings of a scope containing a direct
+/// `eval` call and of its parent scopes, which the evaluated code can reach by name, and the
+/// bindings referenced from the body of a `with` statement, which are looked up by name on its
+/// object first.
+pub(super) fn mangle(statements: &mut [Node], module: bool) {
+  let tree = ScopeTree::statements(statements, module);
+  let names = assign_names(&tree, statements);
+  let _ = Renamer {
+    tree: &tree,
+    names: &names,
+  }
+  .visit_statements(statements, &mut Path::new());
+}
+
+/// Gives new names to the bindings that can be renamed.
+fn assign_names(tree: &ScopeTree, statements: &[Node]) -> HashMap<BindingId, Box<str>> {
+  let fixed = fixed_bindings(tree, statements);
+
+  // The names kept by bindings of each scope or of the scopes inside it.
+  let mut kept: HashMap<ScopeId, BTreeSet<&str>> = HashMap::new();
+  for &binding in &fixed {
+    let binding = tree.binding(binding);
+    let mut scope = Some(binding.scope());
+    while let Some(current) = scope {
+      kept.entry(current).or_default().insert(binding.name());
+      scope = tree.scope(current).parent();
+    }
+  }
+
+  // The references made from each scope or the scopes inside it to bindings of outer scopes,
+  // or to globals.
+  let mut crossing: HashMap<ScopeId, Vec<_>> = HashMap::new();
+  for (_, reference) in tree.references() {
+    let target = reference
+      .binding()
+      .map(|binding| tree.binding(binding).scope());
+    let mut scope = Some(reference.scope());
+    while let Some(current) = scope.filter(|&scope| Some(scope) != target) {
+      crossing.entry(current).or_default().push(reference);
+      scope = tree.scope(current).parent();
+    }
+  }
+
+  let mut names: HashMap<BindingId, Box<str>> = HashMap::new();
+  let name_of = |names: &HashMap<BindingId, Box<str>>, binding: BindingId| -> Box<str> {
+    names
+      .get(&binding)
+      .cloned()
+      .unwrap_or_else(|| tree.binding(binding).name().into())
+  };
+  // Outer scopes come first, so the new names of the bindings referenced from a scope are
+  // known when its own bindings are renamed.
+  for (id, scope) in tree.scopes() {
+    let mut taken: BTreeSet<Box<str>> = kept
+      .get(&id)
+      .into_iter()
+      .flatten()
+      .map(|&name| name.into())
+      .collect();
+    for reference in crossing.get(&id).into_iter().flatten() {
+      taken.insert(match reference.binding() {
+        Some(binding) => name_of(&names, binding),
+        None => reference.name().into(),
+      });
+    }
+    // The body of a `catch` clause can not declare the names of its parameter.
+    if let Some(parent) = scope.parent() {
+      if scope.kind() == ScopeKind::Block && tree.scope(parent).kind() == ScopeKind::Catch {
+        for &binding in tree.scope(parent).bindings() {
+          taken.insert(name_of(&names, binding));
+        }
+      }
+    }
+
+    // The most referenced bindings get the shortest names.
+    let mut bindings: Vec<BindingId> = scope
+      .bindings()
+      .iter()
+      .copied()
+      .filter(|binding| !fixed.contains(binding))
+      .collect();
+    bindings.sort_by_key(|&binding| (Reverse(tree.binding(binding).references().len()), binding));
+
+    let mut candidates = Names(0);
+    for binding in bindings {
+      let name = candidates
+        .find(|name| !taken.contains(name))
+        .expect("there are infinitely many names");
+      taken.insert(name.clone());
+      names.insert(binding, name);
+    }
+  }
+  names
+}
+
+/// Finds the bindings keeping their names.
+fn fixed_bindings(tree: &ScopeTree, statements: &[Node]) -> HashSet<BindingId> {
+  // The names of default exported functions and classes are not seen by the importers.
+  let defaults: HashSet<BindingId> = statements
+    .iter()
+    .filter_map(|statement| match statement {
+      Node::Export(ExportDecl::DefaultFunction(function), _) => Some(function.id()),
+      Node::Export(ExportDecl::DefaultClass(class), _) => Some(class.id()),
+      _ => None,
+    })
+    .filter_map(|id| tree.declaration_at(id))
+    .collect();
+
+  let mut fixed: HashSet<BindingId> = tree
+    .bindings()
+    .filter(|&(id, binding)| {
+      let scope = tree.scope(binding.scope());
+      binding.kind() == BindingKind::Arguments
+        || binding.name() == "arguments"
+        || scope.kind() == ScopeKind::Global
+        || (binding.is_exported() && !defaults.contains(&id))
+        || scope.is_tainted()
+        || binding
+          .references()
+          .iter()
+          .any(|&reference| tree.reference(reference).is_dynamic())
+    })
+    .map(|(id, _)| id)
+    .collect();
+
+  // A `var` declaration in a `catch` clause can redeclare its parameter, and then assigns it.
+  for (_, binding) in tree.bindings() {
+    if binding.kind() != BindingKind::CatchParameter {
+      continue;
+    }
+    let mut scope = binding.scope();
+    while !tree.scope(scope).kind().is_var_scope() {
+      scope = tree
+        .scope(scope)
+        .parent()
+        .expect("the top level is a var scope");
+    }
+    if let Some(var) = tree.scope(scope).binding(binding.name()) {
+      if tree.binding(var).kind() == BindingKind::Var {
+        fixed.insert(var);
+        fixed.extend(tree.scope(binding.scope()).binding(binding.name()));
+      }
+    }
+  }
+  fixed
+}
+
+/// Renames the declarations and the references of the bindings given new names.
+struct Renamer<'a> {
+  tree: &'a ScopeTree,
+  names: &'a HashMap<BindingId, Box<str>>,
+}
+
+impl Renamer<'_> {
+  /// Gets the new name of a name of the syntax tree, by the id of the node naming it.
+  fn rename(&self, id: NodeId) -> Option<Box<str>> {
+    let binding = self.tree.resolve_at(id)?;
+    self.names.get(&binding).cloned()
+  }
+}
+
+impl VisitorMut for Renamer<'_> {
+  fn visit_identifier(&mut self, ident: &mut Identifier, _path: &mut KindPath) -> Flow {
+    if let Some(renamed) = self.rename(ident.id()) {
+      *ident = Identifier::new(renamed);
+    }
+    Flow::Continue(())
+  }
+
+  fn visit_property_definition(
+    &mut self,
+    property: &mut PropertyDefinition,
+    path: &mut KindPath,
+  ) -> Flow {
+    if let PropertyDefinition::IdentifierReference(ident, span) = property {
+      if let Some(renamed) = self.rename(ident.id()) {
+        *property = PropertyDefinition::Property(
+          PropertyName::Literal(ident.name().clone()),
+          Identifier::new(renamed).into(),
+          *span,
+        );
+      }
+      return Flow::Continue(());
+    }
+    visit_mut::walk_property_definition(self, property, path)
+  }
+
+  fn visit_function_decl(&mut self, decl: &mut FunctionDecl, path: &mut KindPath) -> Flow {
+    if let Some(renamed) = self.rename(decl.id()) {
+      *decl.name_mut() = renamed.into();
+    }
+    visit_mut::walk_function_decl(self, decl, path)
+  }
+
+  fn visit_function_expr(&mut self, expr: &mut FunctionExpr, path: &mut KindPath) -> Flow {
+    if let Some(renamed) = expr.name().and(self.rename(expr.id())) {
+      *expr.name_mut().expect("the function has a name") = renamed.into();
+    }
+    visit_mut::walk_function_expr(self, expr, path)
+  }
+
+  fn visit_class(&mut self, class: &mut Class, path: &mut KindPath) -> Flow {
+    if let Some(renamed) = class.name().and(self.rename(class.id())) {
+      *class.name_mut().expect("the class has a name") = renamed.into();
+    }
+    visit_mut::walk_class(self, class, path)
+  }
+
+  fn visit_import_specifier(
+    &mut self,
+    specifier: &mut ImportSpecifier,
+    _path: &mut KindPath,
+  ) -> Flow {
+    let renamed = self.rename(specifier.id());
+    let local = match specifier {
+      ImportSpecifier::Default(local)
+      | ImportSpecifier::Namespace(local)
+      | ImportSpecifier::Named { local, .. } => local,
+    };
+    if let Some(renamed) = renamed {
+      *local = renamed.into();
+    }
+    Flow::Continue(())
+  }
+}
+
+/// The names given to bindings, shortest first: `a` to `_`, then `aa`, `ba` and so on, leaving
+/// out the reserved words.
+struct Names(usize);
+
+impl Names {
+  /// The characters that can start a name.
+  const FIRST: &'static [u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ$_";
+  /// The characters that can continue a name.
+  const REST: &'static [u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ$_0123456789";
+}
+
+impl Iterator for Names {
+  type Item = Box<str>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      let mut index = self.0;
+      self.0 += 1;
+      let mut name = String::new();
+      name.push(Self::FIRST[index % Self::FIRST.len()].into());
+      index /= Self::FIRST.len();
+      while index > 0 {
+        index -= 1;
+        name.push(Self::REST[index % Self::REST.len()].into());
+        index /= Self::REST.len();
+      }
+      if !is_reserved(&name) {
+        return Some(name.into());
+      }
+    }
+  }
+}
+
+/// Returns `true` if a name can not be used for a binding.
+fn is_reserved(name: &str) -> bool {
+  name.parse::<Keyword>().is_ok()
+    || STRICT_FORBIDDEN_IDENTIFIERS.contains(&name)
+    || matches!(
+      name,
+      "null" | "true" | "false" | "undefined" | "NaN" | "Infinity"
+    )
+}
@@ -0,0 +1,209 @@
+//! Minification: printing scripts and modules in as few bytes as possible.
+//!
+//! The [`Minifier`] rewrites the syntax tree before printing it with [`codegen::Options::compact`],
+//! which already leaves out the whitespace, the comments and the optional semicolons:
+//!
+//!  - Constant expressions are folded, see [`fold`].
+//!  - `true` and `false` become `!0` and `!1`, and `undefined` becomes `void 0` unless a binding
+//!    shadows it.
+//!  - Consecutive `var`, `let` or `const` declarations are merged into one.
+//!  - Statements after a `return`, `throw`, `break` or `continue` are dropped, keeping the
+//!    declarations they hoist.
+//!  - Local bindings are renamed to the shortest names available. Global bindings, the exported
+//!    bindings of a module and the bindings of scopes that contain a direct `eval` call keep
+//!    their names.
+//!
+//! The output is the same for the same input, run after run.
+//!
+//! ```
+//! use plastic::syntax::minify::{minify_script, Options};
+//!
+//! let source = "function add(first, second) {\n  return first + second;\n}\n";
+//! let minified = minify_script(source, &Options::default()).unwrap();
+//! assert_eq!(minified.code(), "function add(a,b){return a+b}");
+//! assert_eq!(minified.to_string(), "57 bytes -> 29 bytes (49.1% smaller)");
+//! ```
+//!
+//! [`codegen::Options::compact`]: crate::syntax::codegen::Options::compact
+//! [`fold`]: crate::syntax::fold
+
+mod compress;
+mod mangle;
+#[cfg(test)]
+mod tests;
+
+use self::compress::Compressor;
+use crate::syntax::{
+  ast::{module::Module, node::StatementList},
+  codegen::{self, Generator},
+  fold,
+  parser::{ParseError, Parser},
+};
+use std::fmt;
+
+/// The rewrites done by a [`Minifier`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+  compress: bool,
+  mangle: bool,
+  quotes: codegen::Quotes,
+}
+
+impl Options {
+  /// Sets whether constant expressions are folded, literals and statements rewritten in shorter
+  /// forms, and unreachable code dropped.
+  pub fn with_compress(mut self, compress: bool) -> Self {
+    self.compress = compress;
+    self
+  }
+
+  /// Sets whether local bindings are renamed.
+  pub fn with_mangle(mut self, mangle: bool) -> Self {
+    self.mangle = mangle;
+    self
+  }
+
+  /// Sets the preferred quotes of string literals.
+  pub fn with_quotes(mut self, quotes: codegen::Quotes) -> Self {
+    self.quotes = quotes;
+    self
+  }
+
+  /// Returns `true` if constant expressions are folded, and literals and statements rewritten
+  /// in shorter forms.
+  pub fn compress(&self) -> bool {
+    self.compress
+  }
+
+  /// Returns `true` if local bindings are renamed.
+  pub fn mangle(&self) -> bool {
+    self.mangle
+  }
+
+  /// Gets the preferred quotes of string literals.
+  pub fn quotes(&self) -> codegen::Quotes {
+    self.quotes
+  }
+}
+
+/// Compresses and mangles, with double quotes.
+impl Default for Options {
+  fn default() -> Self {
+    Self {
+      compress: true,
+      mangle: true,
+      quotes: codegen::Quotes::Double,
+    }
+  }
+}
+
+/// Rewrites and prints syntax trees in as few bytes as possible.
+#[derive(Debug, Clone, Default)]
+pub struct Minifier {
+  options: Options,
+}
+
+impl Minifier {
+  /// Creates a minifier with the given options.
+  pub fn new(options: Options) -> Self {
+    Self { options }
+  }
+
+  /// Minifies a script.
+  pub fn script(&self, mut script: StatementList) -> String {
+    self.rewrite(&mut script, false);
+    self.generator().script(&script)
+  }
+
+  /// Minifies a module.
+  pub fn module(&self, module: Module) -> String {
+    let mut body = module.into_body();
+    self.rewrite(&mut body, true);
+    self.generator().module(&Module::new(body))
+  }
+
+  /// Rewrites the statements of a script or a module.
+  fn rewrite(&self, statements: &mut StatementList, module: bool) {
+    if self.options.compress {
+      fold::fold_statements(statements.items_mut());
+      Compressor::new(statements.items()).statements(statements);
+    }
+    if self.options.mangle {
+      mangle::mangle(statements.items_mut(), module);
+    }
+  }
+
+  /// Gets the code generator printing the rewritten trees.
+  fn generator(&self) -> Generator<'static> {
+    Generator::new(codegen::Options::compact().with_quotes(self.options.quotes))
+  }
+}
+
+/// Minified code, with the size of the source it was minified from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Minified {
+  code: String,
+  original_size: usize,
+}
+
+impl Minified {
+  /// Gets the minified code.
+  pub fn code(&self) -> &str {
+    &self.code
+  }
+
+  /// Consumes the result, returning the minified code.
+  pub fn into_code(self) -> String {
+    self.code
+  }
+
+  /// Gets the size of the source in bytes.
+  pub fn original_size(&self) -> usize {
+    self.original_size
+  }
+
+  /// Gets the size of the minified code in bytes.
+  pub fn minified_size(&self) -> usize {
+    self.code.len()
+  }
+}
+
+/// Reports the sizes, as in `1200 bytes -> 450 bytes (62.5% smaller)`.
+impl fmt::Display for Minified {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{} bytes -> {} bytes",
+      self.original_size,
+      self.minified_size()
+    )?;
+    if self.original_size > 0 {
+      let saved = self.original_size as f64 - self.minified_size() as f64;
+      let percent = saved * 100.0 / self.original_size as f64;
+      if percent >= 0.0 {
+        write!(f, " ({:.1}% smaller)", percent)?;
+      } else {
+        write!(f, " ({:.1}% larger)", -percent)?;
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Minifies the source text of a script.
+pub fn minify_script(source: &str, options: &Options) -> Result<Minified, ParseError> {
+  let script = Parser::new(source.as_bytes()).parse_script()?;
+  Ok(Minified {
+    code: Minifier::new(options.clone()).script(script),
+    original_size: source.len(),
+  })
+}
+
+/// Minifies the source text of a module.
+pub fn minify_module(source: &str, options: &Options) -> Result<Minified, ParseError> {
+  let module = Parser::new(source.as_bytes()).parse_module()?;
+  Ok(Minified {
+    code: Minifier::new(options.clone()).module(module),
+    original_size: source.len(),
+  })
+}
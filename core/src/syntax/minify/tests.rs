@@ -0,0 +1,327 @@
+//! Tests for the minifier.
+
+use super::{minify_module, minify_script, Options};
+use crate::syntax::{codegen::Quotes, parser::Parser};
+
+/// Minifies a script with the default options.
+#[track_caller]
+fn script(js: &str) -> String {
+  minify_script(js, &Options::default())
+    .unwrap_or_else(|err| panic!("failed to parse `{}`: {}", js, err))
+    .into_code()
+}
+
+/// Minifies a module with the default options.
+#[track_caller]
+fn module(js: &str) -> String {
+  minify_module(js, &Options::default())
+    .unwrap_or_else(|err| panic!("failed to parse `{}`: {}", js, err))
+    .into_code()
+}
+
+#[test]
+fn mangling() {
+  assert_eq!(
+    script("function f(first, second) { var local = first; return local + second + x; }"),
+    "function f(a,b){var c=a;return c+b+x}"
+  );
+  // The most referenced bindings get the shortest names.
+  assert_eq!(
+    script("function f(rare, often) { return often(often, often, rare); }"),
+    "function f(b,a){return a(a,a,b)}"
+  );
+  // Names used by outer bindings and globals referenced from the inner function are not reused.
+  assert_eq!(
+    script("function f(long) { return function (a) { return a + long + b; }; }"),
+    "function f(a){return function(c){return c+a+b}}"
+  );
+  // Sibling scopes reuse the same names.
+  assert_eq!(
+    script("(function () { { let first = 1; f(first); } { let second = 2; f(second); } })();"),
+    "(function(){{let a=1;f(a)}{let a=2;f(a)}})()"
+  );
+  // Reserved words are skipped.
+  let names: Vec<String> = (0..60).map(|index| format!("v{}", index)).collect();
+  let js = format!(
+    "function f() {{ var {}; return [{}]; }}",
+    names.join(", "),
+    names.join(", ")
+  );
+  let minified = script(&js);
+  assert!(minified.contains("var a,b,"), "{}", minified);
+  assert!(minified.contains(",_,aa,ba,ca,"), "{}", minified);
+  assert!(!minified.contains(",do,"), "{}", minified);
+  assert!(!minified.contains(",in,"), "{}", minified);
+}
+
+#[test]
+fn globals() {
+  assert_eq!(
+    script("var counter = 0; function increment(step) { counter += step; }"),
+    "var counter=0;function increment(a){counter+=a}"
+  );
+  assert_eq!(
+    script("let first = 1; class Second {} { let third = first; }"),
+    "let first=1;class Second{}{let a=first}"
+  );
+  // Functions declared in blocks are visible outside of them.
+  assert_eq!(
+    script("if (a) { function declared() {} }"),
+    "if(a){function declared(){}}"
+  );
+}
+
+#[test]
+fn eval_scopes() {
+  assert_eq!(
+    script("function f(a) { var local = 1; eval('local'); function g(param) { return param; } }"),
+    "function f(a){var local=1;eval(\"local\");function g(a){return a}}"
+  );
+  assert_eq!(
+    script("function outer(x) { return function inner() { return eval('x'); }; }"),
+    "function outer(x){return function inner(){return eval(\"x\")}}"
+  );
+  // An indirect call through a property is not a direct `eval`.
+  assert_eq!(
+    script("function f(local) { window.eval(local); }"),
+    "function f(a){window.eval(a)}"
+  );
+}
+
+#[test]
+fn with_scopes() {
+  // The names read in the body of a `with` statement are looked up on its object first.
+  assert_eq!(
+    script("function f(object, other) { with (object) { other; } let local = 1; return local; }"),
+    "function f(a,other){with(a){other}let b=1;return b}"
+  );
+}
+
+#[test]
+fn modules() {
+  assert_eq!(
+    module(
+      "import def, { named as local, other } from 'x'; \
+       export function exported(param) { return local(param) + other + def; } \
+       function helper(value) { return value; } export { helper }; \
+       export default function named() { return helper; }"
+    ),
+    "import a,{named as b,other as c}from\"x\";\
+     export function exported(d){return b(d)+c+a}\
+     function helper(a){return a}export{helper};\
+     export default function d(){return helper}"
+  );
+  assert_eq!(
+    module("const first = 1; export const second = first; export default class Thing { method() { return Thing; } }"),
+    "const a=1;export const second=a;export default class b{method(){return b}}"
+  );
+  assert_eq!(
+    module("import * as everything from 'x'; export { everything as all };"),
+    "import *as everything from\"x\";export{everything as all}"
+  );
+}
+
+#[test]
+fn shorthand_properties() {
+  assert_eq!(
+    script("function f(point) { var x = point.x; return { x, y: point.y }; }"),
+    "function f(a){var b=a.x;return{x:b,y:a.y}}"
+  );
+  assert_eq!(
+    script("function f(point) { var { x, y = x } = point; return [x, y]; }"),
+    "function f(b){var {x:a,y:c=a}=b;return[a,c]}"
+  );
+  // Global names stay shorthand.
+  assert_eq!(script("var x = { y };"), "var x={y}");
+}
+
+#[test]
+fn scopes() {
+  assert_eq!(
+    script("function f(x) { switch (x) { case 1: let y = 2; return y; } for (let i = 0; i < x; i++) { g(i); } }"),
+    "function f(a){switch(a){case 1:let a=2;return a}for(let b=0;b<a;b++){g(b)}}"
+  );
+  // A `var` redeclaring a `catch` parameter assigns the parameter.
+  assert_eq!(
+    script("function f() { try { a(); } catch (error) { var error = 1; } }"),
+    "function f(){try{a()}catch(error){var error=1}}"
+  );
+  assert_eq!(
+    script("function f() { try { a(); } catch ({ message }) { return message; } }"),
+    "function f(){try{a()}catch({message:a}){return a}}"
+  );
+  // The body of a `catch` clause can not declare the names of its parameter.
+  let minified = script("function f() { try { a(); } catch (e) { let y = 1; return y; } }");
+  assert_eq!(minified, "function f(){try{a()}catch(a){let b=1;return b}}");
+  assert!(Parser::new(minified.as_bytes()).parse_script().is_ok());
+  // `arguments` keeps its name, even when it is declared.
+  assert_eq!(
+    script("function f() { var arguments = 1; return arguments; }"),
+    "function f(){var arguments=1;return arguments}"
+  );
+  assert_eq!(
+    script("var named = (class Name { m() { return Name; } }); var fn = function self() { return self; };"),
+    "var named=class a{m(){return a}},fn=function a(){return a}"
+  );
+  assert_eq!(
+    script("(function () { let a = 1; class C { static { var d = a; } } })();"),
+    "(function(){let a=1;class b{static{var b=a}}})()"
+  );
+}
+
+#[test]
+fn literals() {
+  assert_eq!(
+    script("a = true; b = false; c = undefined; function f() { return undefined; }"),
+    "a=!0;b=!1;c=void 0;function f(){return void 0}"
+  );
+  // `undefined` can be shadowed, and then means something else.
+  assert_eq!(
+    script("function f(undefined) { return undefined; } g(undefined);"),
+    "function f(a){return a}g(undefined)"
+  );
+  assert_eq!(script("undefined = 1;"), "undefined=1");
+  assert_eq!(
+    script("a = undefined; undefined++;"),
+    "a=void 0;undefined++"
+  );
+}
+
+#[test]
+fn folding() {
+  assert_eq!(
+    script("var day = 60 * 60 * 24, label = 'v' + 2, on = 1 < 2; f(typeof 0 == 'number' ? a : b);"),
+    "var day=86400,label=\"v2\",on=!0;f(a)"
+  );
+  // Folding is part of compressing.
+  assert_eq!(
+    minify_script("x = 1 + 2;", &Options::default().with_compress(false))
+      .unwrap()
+      .into_code(),
+    "x=1+2"
+  );
+}
+
+#[test]
+fn declarations() {
+  assert_eq!(
+    script("var a = 1; var b = 2; let c; let d = 3; const e = 4, f = 5; var g;"),
+    "var a=1,b=2;let c,d=3;const e=4,f=5;var g"
+  );
+  assert_eq!(script("var a = 1;; ; var b;"), "var a=1,b");
+}
+
+#[test]
+fn unreachable_code() {
+  // Function declarations and the names of variables are hoisted, and kept.
+  assert_eq!(
+    script(
+      "function f() { return g; a(); var b = 2; function g() {} let d = 3; class E {} \
+       for (var i of j) { var k; } }"
+    ),
+    "function f(){return a;function a(){}let b,c;var d,e,f}"
+  );
+  assert_eq!(
+    script("for (;;) { if (a) { break; b(); } continue; c(); }"),
+    "for(;;){if(a){break}continue}"
+  );
+  assert_eq!(
+    script("function f() { throw a; var b = function () { var c; }; }"),
+    "function f(){throw a;var b}"
+  );
+  assert_eq!(
+    script("switch (a) { case 1: break; b(); case 2: c(); }"),
+    "switch(a){case 1:break;case 2:c()}"
+  );
+}
+
+#[test]
+fn options() {
+  let js = "function f(first) { return first === true ? 'yes' : undefined; }";
+  assert_eq!(
+    minify_script(js, &Options::default()).unwrap().code(),
+    "function f(a){return a===!0?\"yes\":void 0}"
+  );
+  assert_eq!(
+    minify_script(js, &Options::default().with_compress(false))
+      .unwrap()
+      .code(),
+    "function f(a){return a===true?\"yes\":undefined}"
+  );
+  assert_eq!(
+    minify_script(js, &Options::default().with_mangle(false))
+      .unwrap()
+      .code(),
+    "function f(first){return first===!0?\"yes\":void 0}"
+  );
+  assert_eq!(
+    minify_script(js, &Options::default().with_quotes(Quotes::Single))
+      .unwrap()
+      .code(),
+    "function f(a){return a===!0?'yes':void 0}"
+  );
+}
+
+#[test]
+fn sizes() {
+  let minified = minify_script("var  a = true ;\n", &Options::default()).unwrap();
+  assert_eq!(minified.code(), "var a=!0");
+  assert_eq!(minified.original_size(), 16);
+  assert_eq!(minified.minified_size(), 8);
+  assert_eq!(minified.to_string(), "16 bytes -> 8 bytes (50.0% smaller)");
+
+  let minified = minify_script("a=b", &Options::default()).unwrap();
+  assert_eq!(minified.to_string(), "3 bytes -> 3 bytes (0.0% smaller)");
+  let minified = minify_script("a=!0;", &Options::default()).unwrap();
+  assert_eq!(minified.to_string(), "5 bytes -> 4 bytes (20.0% smaller)");
+  let minified = minify_script("a=true", &Options::default()).unwrap();
+  assert_eq!(minified.to_string(), "6 bytes -> 4 bytes (33.3% smaller)");
+  let minified = minify_script("", &Options::default()).unwrap();
+  assert_eq!(minified.to_string(), "0 bytes -> 0 bytes");
+}
+
+/// Scripts exercising the scopes and the rewrites together.
+const SCRIPTS: &[&str] = &[
+  "function outer(alpha, beta) { var gamma = alpha + beta; function inner(delta) { let epsilon = delta * gamma; \
+   return () => epsilon + alpha; } return inner; }",
+  "var list = [1, 2, 3]; for (var index = 0; index < list.length; index++) { let item = list[index]; \
+   if (item === undefined) continue; else { console.log(item, true); } }",
+  "(function (global) { 'use strict'; var cache = {}; function lookup(key, { fallback = null } = {}) { \
+   return key in cache ? cache[key] : fallback; } global.lookup = lookup; })(this);",
+  "class Counter { constructor(start) { this.count = start; } increment(step = 1) { const next = this.count + step; \
+   this.count = next; return next; } static create() { return new Counter(0); } }",
+  "function f(a, b) { try { return a(b); } catch (error) { let message = error.message; return message; } \
+   finally { b = undefined; } }",
+  "async function load(url) { const response = await fetch(url); for await (const chunk of response) \
+   { yield_(chunk); } return response; }",
+  "function* generate(limit) { let index = 0; while (index < limit) { yield index++; } return; index = 0; }",
+  "label: for (const key in object) { switch (key) { case 'a': { let value = object[key]; break label; } \
+   default: continue label; } }",
+];
+
+#[test]
+fn round_trip() {
+  for js in SCRIPTS {
+    let minified = script(js);
+    let reparsed = Parser::new(minified.as_bytes())
+      .parse_script()
+      .unwrap_or_else(|err| {
+        panic!(
+          "failed to parse `{}` minified from `{}`: {}",
+          minified, js, err
+        )
+      });
+    // The minified code is already as small as it gets.
+    let twice = super::Minifier::default().script(reparsed);
+    assert_eq!(twice, minified, "minified twice from `{}`", js);
+  }
+}
+
+#[test]
+fn determinism() {
+  let js = SCRIPTS.join("\n");
+  let first = script(&js);
+  for _ in 0..10 {
+    assert_eq!(script(&js), first);
+  }
+}
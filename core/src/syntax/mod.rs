@@ -1,3 +1,20 @@
 pub mod ast;
+pub mod bundle;
+pub mod codegen;
+pub mod comments;
+pub mod diagnostics;
+pub mod edit;
+pub mod fold;
+pub mod incremental;
+pub mod jsdoc;
+mod json;
 pub mod lexer;
+pub mod lint;
+pub mod lsp;
+mod locate;
+pub mod minify;
 pub mod parser;
+pub mod scope;
+pub mod shake;
+pub mod sourcemap;
+pub mod transpile;
@@ -0,0 +1,578 @@
+//! Class definition parsing, shared by class declarations and class expressions.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Classes
+//! [spec]: https://tc39.es/ecma262/#sec-class-definitions
+
+#[cfg(test)]
+mod tests;
+
+use super::{
+  expression::{
+    check_accessor_parameters, AssignmentExpression, LeftHandSideExpression, PropertyName,
+  },
+  function::FunctionParts,
+  statement::StatementList,
+  typescript, AllowAwait, AllowYield, BindingKind, Cursor, ParseError, ScopeKind, SuperAccess,
+  TokenParser,
+};
+use crate::syntax::{
+  ast::{
+    node::{
+      self, Class, ClassElement, ClassElementName, FunctionExpr, FunctionKind, MethodDefinitionKind,
+    },
+    Keyword, Punctuator, Span, Sym,
+  },
+  lexer::TokenKind,
+};
+use std::io::Read;
+
+/// Parses the part of a class definition following its name: the optional `extends` clause
+/// and the class body.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ClassTail
+#[derive(Debug, Clone)]
+pub(super) struct ClassTail {
+  name: Option<Sym>,
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl ClassTail {
+  /// Creates a new `ClassTail` parser for the class with the given name.
+  pub(super) fn new<N, Y, A>(name: N, allow_yield: Y, allow_await: A) -> Self
+  where
+    N: Into<Option<Sym>>,
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      name: name.into(),
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for ClassTail
+where
+  R: Read,
+{
+  type Output = Class;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    // All parts of a class definition are strict mode code.
+    let strict = cursor.set_strict(true);
+    let typescript = cursor.is_typescript();
+    if typescript {
+      typescript::skip_type_parameters(cursor)?;
+    }
+    let super_ref = if cursor.next_if(Keyword::Extends)?.is_some() {
+      let super_ref =
+        LeftHandSideExpression::new(self.allow_yield, self.allow_await).parse(cursor)?;
+      if typescript {
+        typescript::skip_type_arguments(cursor)?;
+      }
+      Some(super_ref)
+    } else {
+      None
+    };
+    if typescript
+      && cursor
+        .next_if(TokenKind::identifier("implements"))?
+        .is_some()
+    {
+      typescript::skip_type_list(cursor)?;
+    }
+
+    cursor.expect(Punctuator::OpenBlock, "class body")?;
+
+    cursor.push_private_scope();
+    let body =
+      ClassBody::new(self.allow_yield, self.allow_await, super_ref.is_some()).parse(cursor);
+    let resolved = cursor.pop_private_scope();
+    let (constructor, elements) = body?;
+    resolved?;
+    cursor.set_strict(strict);
+
+    let (constructor, constructor_span) = constructor.unzip();
+    Ok(
+      Class::new(self.name, super_ref, constructor, elements)
+        .with_constructor_span(constructor_span),
+    )
+  }
+}
+
+/// Parses the elements of a class body, up to and including the closing brace.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ClassBody
+#[derive(Debug, Clone, Copy)]
+struct ClassBody {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+  derived: bool,
+}
+
+impl ClassBody {
+  /// Creates a new `ClassBody` parser, `derived` if the class has an `extends` clause.
+  fn new<Y, A>(allow_yield: Y, allow_await: A, derived: bool) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+      derived,
+    }
+  }
+}
+
+impl<R> TokenParser<R> for ClassBody
+where
+  R: Read,
+{
+  type Output = (Option<(FunctionExpr, Span)>, Vec<ClassElement>);
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let mut constructor = None;
+    let mut elements = Vec::new();
+
+    loop {
+      if cursor.next_if(Punctuator::CloseBlock)?.is_some() {
+        break;
+      }
+      if cursor.next_if(Punctuator::Semicolon)?.is_some() {
+        continue;
+      }
+
+      let position = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.span().start();
+      match ClassElementParser::new(self.allow_yield, self.allow_await, self.derived)
+        .parse(cursor)?
+      {
+        ParsedElement::Constructor(function, span) => {
+          if constructor.is_some() {
+            return Err(ParseError::general(
+              "a class may only have one constructor",
+              position,
+            ));
+          }
+          constructor = Some((function, span));
+        }
+        ParsedElement::Element(element) => elements.push(element),
+        ParsedElement::Erased => {}
+      }
+    }
+
+    Ok((constructor, elements))
+  }
+}
+
+/// A parsed class element, the constructor is kept apart from the other members.
+#[derive(Debug)]
+enum ParsedElement {
+  /// The constructor, and the span of the source it was parsed from.
+  Constructor(FunctionExpr, Span),
+  Element(ClassElement),
+  /// A TypeScript element without run time behavior, such as an abstract method.
+  Erased,
+}
+
+/// Returns `true` if the token following a `static`, `async`, `get` or `set` identifier means that the
+/// identifier is the name of the element rather than a modifier.
+///
+/// In TypeScript, a name can also be followed by a type annotation or type parameters.
+fn is_element_name_end(kind: Option<&TokenKind>, typescript: bool) -> bool {
+  match kind {
+    Some(TokenKind::Punctuator(Punctuator::OpenParen))
+    | Some(TokenKind::Punctuator(Punctuator::Assign))
+    | Some(TokenKind::Punctuator(Punctuator::Semicolon))
+    | Some(TokenKind::Punctuator(Punctuator::CloseBlock))
+    | None => true,
+    Some(TokenKind::Punctuator(Punctuator::Colon))
+    | Some(TokenKind::Punctuator(Punctuator::Question))
+    | Some(TokenKind::Punctuator(Punctuator::Not))
+    | Some(TokenKind::Punctuator(Punctuator::LessThan)) => typescript,
+    _ => false,
+  }
+}
+
+/// Parses a single class element.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ClassElement
+#[derive(Debug, Clone, Copy)]
+struct ClassElementParser {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+  derived: bool,
+}
+
+impl ClassElementParser {
+  /// Creates a new `ClassElementParser` parser, `derived` if the class has an `extends` clause.
+  fn new<Y, A>(allow_yield: Y, allow_await: A, derived: bool) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+      derived,
+    }
+  }
+}
+
+impl<R> TokenParser<R> for ClassElementParser
+where
+  R: Read,
+{
+  type Output = ParsedElement;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let element_start = cursor.start()?;
+    let typescript = cursor.is_typescript();
+    let mut modifiers = typescript::ClassModifiers::default();
+    if typescript {
+      modifiers.parse(cursor)?;
+    }
+
+    let mut is_static = false;
+    if let Some(TokenKind::Identifier(ident)) = cursor.peek(0)?.map(|tk| tk.kind()) {
+      if ident.as_ref() == "static"
+        && !is_element_name_end(cursor.peek(1)?.map(|tk| tk.kind()), typescript)
+      {
+        let _ = cursor.next()?.expect("static token vanished");
+        if cursor.next_if(Punctuator::OpenBlock)?.is_some() {
+          cursor.push_scope(ScopeKind::Function);
+          let super_access = cursor.set_super_access(SuperAccess::Property);
+          let body = StatementList::new(false, true, false, true).parse(cursor)?;
+          cursor.expect(Punctuator::CloseBlock, "static block")?;
+          cursor.set_super_access(super_access);
+          cursor.pop_scope();
+          let span = cursor.span_from(element_start);
+          return Ok(ParsedElement::Element(ClassElement::StaticBlock(
+            body,
+            span.into(),
+          )));
+        }
+        is_static = true;
+      }
+    }
+    if typescript {
+      modifiers.parse(cursor)?;
+      if typescript::is_index_signature(cursor)? {
+        typescript::skip_index_signature(cursor)?;
+        return Ok(ParsedElement::Erased);
+      }
+    }
+
+    let mut is_async = false;
+    if let Some(TokenKind::Identifier(ident)) = cursor.peek(0)?.map(|tk| tk.kind()) {
+      if ident.as_ref() == "async"
+        && !is_element_name_end(cursor.peek(1)?.map(|tk| tk.kind()), typescript)
+        && !cursor.is_line_terminator_before(1)?
+      {
+        let _ = cursor.next()?.expect("async token vanished");
+        is_async = true;
+      }
+    }
+    let is_generator = cursor.next_if(Punctuator::Mul)?.is_some();
+    let function_kind = FunctionKind::new(is_async, is_generator);
+
+    let mut kind = MethodDefinitionKind::Ordinary;
+    if let (Some(TokenKind::Identifier(ident)), FunctionKind::Ordinary) =
+      (cursor.peek(0)?.map(|tk| tk.kind()), function_kind)
+    {
+      let accessor = match ident.as_ref() {
+        "get" => Some(MethodDefinitionKind::Get),
+        "set" => Some(MethodDefinitionKind::Set),
+        _ => None,
+      };
+      if let Some(accessor) = accessor {
+        if !is_element_name_end(cursor.peek(1)?.map(|tk| tk.kind()), typescript) {
+          let _ = cursor.next()?.expect("get/set token vanished");
+          kind = accessor;
+        }
+      }
+    }
+
+    let name_token = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.clone();
+    let position = name_token.span().start();
+    let name = if let TokenKind::PrivateIdentifier(name) = name_token.kind() {
+      let _ = cursor.next()?.expect("private identifier vanished");
+      if name.as_ref() == "constructor" {
+        return Err(ParseError::general(
+          "classes may not have a private element named '#constructor'",
+          position,
+        ));
+      }
+      ClassElementName::PrivateName(name.clone())
+    } else {
+      ClassElementName::PropertyName(
+        PropertyName::new(self.allow_yield, self.allow_await).parse(cursor)?,
+      )
+    };
+    let literal_name = match &name {
+      ClassElementName::PropertyName(name) => name.literal(),
+      ClassElementName::PrivateName(_) => None,
+    };
+
+    // An optional member, `x?: number`, or a definitely assigned field, `x!: number`.
+    if typescript && cursor.next_if(Punctuator::Question)?.is_none() {
+      let _ = cursor.next_if(Punctuator::Not)?;
+    }
+
+    if kind != MethodDefinitionKind::Ordinary
+      || function_kind != FunctionKind::Ordinary
+      || cursor.next_is(Punctuator::OpenParen)?
+      || (typescript && cursor.next_is(Punctuator::LessThan)?)
+    {
+      let is_constructor = literal_name == Some("constructor") && !is_static;
+      let mark = cursor.parameter_properties_mark();
+      let start = cursor.start()?;
+      let super_access = if is_constructor && self.derived {
+        SuperAccess::Call
+      } else {
+        SuperAccess::Property
+      };
+      let (params, body) = FunctionParts::new(function_kind, "method definition")
+        .with_signature(typescript)
+        .with_parameter_properties(is_constructor)
+        .with_super(super_access)
+        .parse(cursor)?;
+      let properties = cursor.take_parameter_properties(mark);
+      let mut body = match body {
+        Some(body) if !modifiers.is_ambient() => body,
+        _ if !properties.is_empty() => {
+          return Err(ParseError::general(
+            "parameter properties are only allowed in a constructor implementation",
+            position,
+          ))
+        }
+        _ => return Ok(ParsedElement::Erased),
+      };
+      if !properties.is_empty() {
+        body.replace_items(|items| typescript::assign_parameter_properties(items, &properties));
+      }
+      let function = FunctionExpr::new(None, params, body)
+        .with_kind(function_kind)
+        .with_span(cursor.span_from(start));
+      check_accessor_parameters(kind, &function, position)?;
+
+      if let Some(private_name) = name.private_name() {
+        cursor.declare_private_name(private_name, kind, is_static, position)?;
+      }
+      match literal_name {
+        Some("constructor") if !is_static => {
+          if kind != MethodDefinitionKind::Ordinary {
+            return Err(ParseError::general(
+              "class constructor may not be an accessor",
+              position,
+            ));
+          }
+          if function_kind != FunctionKind::Ordinary {
+            return Err(ParseError::general(
+              "class constructor may not be an async method or a generator",
+              position,
+            ));
+          }
+          return Ok(ParsedElement::Constructor(
+            function,
+            cursor.span_from(element_start),
+          ));
+        }
+        Some("prototype") if is_static => {
+          return Err(ParseError::general(
+            "classes may not have a static property named 'prototype'",
+            position,
+          ));
+        }
+        _ => {}
+      }
+
+      return Ok(ParsedElement::Element(ClassElement::Method {
+        name,
+        kind,
+        function,
+        is_static,
+        span: cursor.span_from(element_start).into(),
+      }));
+    }
+
+    match literal_name {
+      Some("constructor") => {
+        return Err(ParseError::general(
+          "classes may not have a field named 'constructor'",
+          position,
+        ))
+      }
+      Some("prototype") if is_static => {
+        return Err(ParseError::general(
+          "classes may not have a static property named 'prototype'",
+          position,
+        ))
+      }
+      _ => {}
+    }
+    if let Some(private_name) = name.private_name() {
+      cursor.declare_private_name(
+        private_name,
+        MethodDefinitionKind::Ordinary,
+        is_static,
+        position,
+      )?;
+    }
+
+    if typescript {
+      typescript::type_annotation(cursor)?;
+    }
+    let init = if cursor.next_if(Punctuator::Assign)?.is_some() {
+      let super_access = cursor.set_super_access(SuperAccess::Property);
+      let init =
+        AssignmentExpression::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
+      cursor.set_super_access(super_access);
+      Some(init)
+    } else {
+      None
+    };
+    cursor.expect_semicolon("class field")?;
+    if modifiers.is_ambient() {
+      return Ok(ParsedElement::Erased);
+    }
+
+    Ok(ParsedElement::Element(ClassElement::Field {
+      name,
+      init,
+      is_static,
+      span: cursor.span_from(element_start).into(),
+    }))
+  }
+}
+
+/// Parses a class declaration, such as `class A extends B {}`.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/class
+/// [spec]: https://tc39.es/ecma262/#prod-ClassDeclaration
+#[derive(Debug, Clone, Copy)]
+pub(super) struct ClassDeclaration {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl ClassDeclaration {
+  /// Creates a new `ClassDeclaration` parser.
+  pub(super) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for ClassDeclaration
+where
+  R: Read,
+{
+  type Output = node::Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    cursor.expect(Keyword::Class, "class declaration")?;
+    let mark = cursor.bindings_mark();
+    let start = cursor.start()?;
+    let name =
+      super::pattern::BindingIdentifier::new(self.allow_yield, self.allow_await).parse(cursor)?;
+    let name_span = cursor.span_from(start);
+    cursor.declare_bindings(mark, BindingKind::Lexical)?;
+    let class = ClassTail::new(name, self.allow_yield, self.allow_await).parse(cursor)?;
+    Ok(node::Node::ClassDecl(class.with_name_span(name_span)))
+  }
+}
+
+/// Parses a class expression, such as `class extends B {}`, whose name is optional.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/class
+/// [spec]: https://tc39.es/ecma262/#prod-ClassExpression
+#[derive(Debug, Clone, Copy)]
+pub(super) struct ClassExpression {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl ClassExpression {
+  /// Creates a new `ClassExpression` parser.
+  pub(super) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for ClassExpression
+where
+  R: Read,
+{
+  type Output = node::Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    cursor.expect(Keyword::Class, "class expression")?;
+    // The name is only bound inside the class itself.
+    let mark = cursor.bindings_mark();
+    let start = cursor.start()?;
+    let name = if is_class_name(cursor)? {
+      Some(
+        super::pattern::BindingIdentifier::new(self.allow_yield, self.allow_await).parse(cursor)?,
+      )
+    } else {
+      None
+    };
+    let name_span = name.as_ref().map(|_| cursor.span_from(start));
+    cursor.discard_bindings(mark);
+    let class = ClassTail::new(name, self.allow_yield, self.allow_await).parse(cursor)?;
+    Ok(node::Node::ClassExpr(class.with_name_span(name_span)))
+  }
+}
+
+/// Returns `true` if the name of a class is next, rather than its heritage or its body: the
+/// name of a class expression or of a default exported class is optional.
+pub(super) fn is_class_name<R>(cursor: &mut Cursor<R>) -> Result<bool, ParseError>
+where
+  R: Read,
+{
+  let typescript = cursor.is_typescript();
+  Ok(match cursor.peek(0)?.map(|tk| tk.kind()) {
+    Some(TokenKind::Keyword(Keyword::Extends))
+    | Some(TokenKind::Punctuator(Punctuator::OpenBlock)) => false,
+    Some(TokenKind::Punctuator(Punctuator::LessThan)) => !typescript,
+    Some(TokenKind::Identifier(name)) => !(typescript && name.as_ref() == "implements"),
+    _ => true,
+  })
+}
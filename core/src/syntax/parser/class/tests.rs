@@ -0,0 +1,362 @@
+use crate::syntax::{
+  ast::{
+    arena::ArenaBox,
+    node::{
+      Assign, BinOp, Call, Class, ClassElement, ClassElementName, Declaration, DeclarationList,
+      FormalParameter, FunctionExpr, GetConstField, GetPrivateField, Identifier,
+      MethodDefinitionKind, Node, OptionalChain, Pattern, PrivateIn, PropertyName, Return,
+    },
+    operator::NumOp,
+    Const, NodeSpan, Sym,
+  },
+  parser::{
+    tests::{check_invalid, check_parser, strict_body},
+    Parser,
+  },
+};
+
+/// Creates an empty function, as used for methods without parameters or body.
+fn empty_function() -> FunctionExpr {
+  FunctionExpr::new(None, vec![], strict_body(vec![]))
+}
+
+#[test]
+fn empty_class_declaration() {
+  check_parser(
+    "class A {}",
+    vec![Node::ClassDecl(Class::new::<_, Node, _>(
+      Sym::from("A"),
+      None,
+      None,
+      vec![],
+    ))],
+  );
+}
+
+#[test]
+fn class_declaration_with_heritage() {
+  check_parser(
+    "class A extends B.C { constructor() { super(); } }",
+    vec![Node::ClassDecl(Class::new(
+      Sym::from("A"),
+      Some(GetConstField::new(Identifier::from("B"), "C")),
+      Some(FunctionExpr::new(
+        None,
+        vec![],
+        strict_body(vec![Call::new(Node::super_ref(), vec![]).into()]),
+      )),
+      vec![],
+    ))],
+  );
+}
+
+#[test]
+fn class_expression() {
+  check_parser(
+    "let A = class {};",
+    vec![DeclarationList::Let(
+      vec![Declaration::new(
+        Identifier::from("A"),
+        Some(Node::ClassExpr(Class::new::<_, Node, _>(
+          None,
+          None,
+          None,
+          vec![],
+        ))),
+      )]
+      .into(),
+    )
+    .into()],
+  );
+}
+
+#[test]
+fn class_elements() {
+  check_parser(
+    "class A {
+      x = 1;
+      static y;
+      #z;
+      get a() {}
+      static set a(v) {}
+      static() {}
+      ['b']() {}
+      static { this; }
+    }",
+    vec![Node::ClassDecl(Class::new::<_, Node, _>(
+      Sym::from("A"),
+      None,
+      None,
+      vec![
+        ClassElement::Field {
+          name: PropertyName::from("x").into(),
+          init: Some(Const::from(1).into()),
+          is_static: false,
+          span: NodeSpan::default(),
+        },
+        ClassElement::Field {
+          name: PropertyName::from("y").into(),
+          init: None,
+          is_static: true,
+          span: NodeSpan::default(),
+        },
+        ClassElement::Field {
+          name: ClassElementName::PrivateName("z".into()),
+          init: None,
+          is_static: false,
+          span: NodeSpan::default(),
+        },
+        ClassElement::Method {
+          name: PropertyName::from("a").into(),
+          kind: MethodDefinitionKind::Get,
+          function: empty_function(),
+          is_static: false,
+          span: NodeSpan::default(),
+        },
+        ClassElement::Method {
+          name: PropertyName::from("a").into(),
+          kind: MethodDefinitionKind::Set,
+          function: FunctionExpr::new(
+            None,
+            vec![FormalParameter::new(
+              Declaration::new(Identifier::from("v"), None),
+              false,
+            )],
+            strict_body(vec![]),
+          ),
+          is_static: true,
+          span: NodeSpan::default(),
+        },
+        ClassElement::Method {
+          name: PropertyName::from("static").into(),
+          kind: MethodDefinitionKind::Ordinary,
+          function: empty_function(),
+          is_static: false,
+          span: NodeSpan::default(),
+        },
+        ClassElement::Method {
+          name: PropertyName::Computed(Const::from("b").into()).into(),
+          kind: MethodDefinitionKind::Ordinary,
+          function: empty_function(),
+          is_static: false,
+          span: NodeSpan::default(),
+        },
+        ClassElement::StaticBlock(vec![Node::this()].into(), NodeSpan::default()),
+      ],
+    ))],
+  );
+}
+
+#[test]
+fn private_member_access() {
+  check_parser(
+    "class A { #x; m(o) { this.#x = o?.#x; return #x in o; } }",
+    vec![Node::ClassDecl(Class::new::<_, Node, _>(
+      Sym::from("A"),
+      None,
+      None,
+      vec![
+        ClassElement::Field {
+          name: ClassElementName::PrivateName("x".into()),
+          init: None,
+          is_static: false,
+          span: NodeSpan::default(),
+        },
+        ClassElement::Method {
+          name: PropertyName::from("m").into(),
+          kind: MethodDefinitionKind::Ordinary,
+          function: FunctionExpr::new(
+            None,
+            vec![FormalParameter::new(
+              Declaration::new(Identifier::from("o"), None),
+              false,
+            )],
+            strict_body(vec![
+              Assign::new(
+                Pattern::Member(ArenaBox::new(
+                  GetPrivateField::new(Node::this(), "x").into(),
+                )),
+                OptionalChain::new(GetPrivateField::new_optional(Identifier::from("o"), "x")),
+              )
+              .into(),
+              Return::new(Some(PrivateIn::new("x", Identifier::from("o")))).into(),
+            ]),
+          ),
+          is_static: false,
+          span: NodeSpan::default(),
+        },
+      ],
+    ))],
+  );
+}
+
+#[test]
+fn private_in_precedence() {
+  check_parser(
+    "class A { #x; static m(o) { #x in o + 1; } }",
+    vec![Node::ClassDecl(Class::new::<_, Node, _>(
+      Sym::from("A"),
+      None,
+      None,
+      vec![
+        ClassElement::Field {
+          name: ClassElementName::PrivateName("x".into()),
+          init: None,
+          is_static: false,
+          span: NodeSpan::default(),
+        },
+        ClassElement::Method {
+          name: PropertyName::from("m").into(),
+          kind: MethodDefinitionKind::Ordinary,
+          function: FunctionExpr::new(
+            None,
+            vec![FormalParameter::new(
+              Declaration::new(Identifier::from("o"), None),
+              false,
+            )],
+            strict_body(vec![PrivateIn::new(
+              "x",
+              BinOp::new(NumOp::Add, Identifier::from("o"), Const::from(1)),
+            )
+            .into()]),
+          ),
+          is_static: true,
+          span: NodeSpan::default(),
+        },
+      ],
+    ))],
+  );
+}
+
+#[test]
+fn nested_class_resolves_outer_private_name() {
+  check_parser(
+    "class A { #x; m() { class B { n(o) { o.#x; } } } }",
+    vec![Node::ClassDecl(Class::new::<_, Node, _>(
+      Sym::from("A"),
+      None,
+      None,
+      vec![
+        ClassElement::Field {
+          name: ClassElementName::PrivateName("x".into()),
+          init: None,
+          is_static: false,
+          span: NodeSpan::default(),
+        },
+        ClassElement::Method {
+          name: PropertyName::from("m").into(),
+          kind: MethodDefinitionKind::Ordinary,
+          function: FunctionExpr::new(
+            None,
+            vec![],
+            strict_body(vec![Node::ClassDecl(Class::new::<_, Node, _>(
+              Sym::from("B"),
+              None,
+              None,
+              vec![ClassElement::Method {
+                name: PropertyName::from("n").into(),
+                kind: MethodDefinitionKind::Ordinary,
+                function: FunctionExpr::new(
+                  None,
+                  vec![FormalParameter::new(
+                    Declaration::new(Identifier::from("o"), None),
+                    false,
+                  )],
+                  strict_body(vec![GetPrivateField::new(Identifier::from("o"), "x").into()]),
+                ),
+                is_static: false,
+                span: NodeSpan::default(),
+              }],
+            ))]),
+          ),
+          is_static: false,
+          span: NodeSpan::default(),
+        },
+      ],
+    ))],
+  );
+}
+
+#[test]
+fn private_name_early_errors() {
+  check_invalid("class A { m() { this.#x; } }");
+  check_invalid("this.#x;");
+  check_invalid("class A { #x; #x; }");
+  check_invalid("class A { #x; get #x() {} }");
+  check_invalid("class A { #constructor; }");
+  check_invalid("class A { m() { #y in this; } }");
+}
+
+#[test]
+fn private_accessor_pair() {
+  check_parser(
+    "class A { get #x() {} set #x(v) {} }",
+    vec![Node::ClassDecl(Class::new::<_, Node, _>(
+      Sym::from("A"),
+      None,
+      None,
+      vec![
+        ClassElement::Method {
+          name: ClassElementName::PrivateName("x".into()),
+          kind: MethodDefinitionKind::Get,
+          function: empty_function(),
+          is_static: false,
+          span: NodeSpan::default(),
+        },
+        ClassElement::Method {
+          name: ClassElementName::PrivateName("x".into()),
+          kind: MethodDefinitionKind::Set,
+          function: FunctionExpr::new(
+            None,
+            vec![FormalParameter::new(
+              Declaration::new(Identifier::from("v"), None),
+              false,
+            )],
+            strict_body(vec![]),
+          ),
+          is_static: false,
+          span: NodeSpan::default(),
+        },
+      ],
+    ))],
+  );
+}
+
+#[test]
+fn constructor_early_errors() {
+  check_invalid("class A { constructor() {} constructor() {} }");
+  check_invalid("class A { get constructor() {} }");
+  check_invalid("class A { constructor = 1; }");
+  check_invalid("class A { static prototype() {} }");
+  check_invalid("class A { static prototype = 1; }");
+  check_invalid("class A { get a(b) {} }");
+}
+
+#[test]
+fn super_early_errors() {
+  check_invalid("class A { constructor() { super(); } }");
+  check_invalid("class A extends B { m() { super(); } }");
+  check_invalid("class A extends B { x = super(); }");
+  check_invalid("class A extends B { constructor() { function f() { super(); } } }");
+  check_invalid("function f() { super.x; }");
+  check_invalid("super.x;");
+  check_invalid("({ m() { function f() { super.x; } } });");
+  check_invalid("({ f: function () { super.x; } });");
+
+  Parser::new(
+    "class A extends B { constructor(a = super.x) { super(); (() => super())(); } }\n\
+     class C { m() { super.m(); } static n() { super[n]; } x = super.x; static { super.y; } }\n\
+     ({ m() { () => super.x; }, get x() { return super.x; }, async *n() { super.n; } });"
+      .as_bytes(),
+  )
+  .parse_script()
+  .expect("super is allowed in methods, and can be called in a derived constructor");
+}
+
+#[test]
+fn invalid_class_syntax() {
+  check_invalid("if (a) class A {}");
+  check_invalid("class {}");
+  check_invalid("super;");
+  check_invalid("class A { x = 1 y = 2 }");
+}
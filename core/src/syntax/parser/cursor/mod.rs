@@ -1,25 +1,560 @@
+//! Cursor implementation for the parser.
 
-use crate::syntax::lexer::Token;
-use std::io::Read;
+mod recovery;
+mod scope;
 
+pub(super) use self::scope::{BindingKind, ScopeKind, SuperAccess};
+use super::{error::expected_tokens, ParseError};
+use crate::syntax::{
+  ast::{node::MethodDefinitionKind, Position, Punctuator, Span},
+  lexer::{Lexer, Token, TokenKind},
+};
+use std::{
+  collections::{HashMap, VecDeque},
+  io::Read,
+};
+
+/// The result of a peek for a semicolon.
 #[derive(Debug)]
 pub(super) enum SemicolonResult<'s> {
+  /// A semicolon was found, or could be inserted automatically.
   Found(Option<&'s Token>),
-  NotFound(&'s Token)
+  /// The next token is neither a semicolon nor a valid automatic insertion point.
+  NotFound(&'s Token),
+}
+
+/// The private names declared and referenced in a class body.
+///
+/// References are only resolved once the whole body has been parsed, since a method can use a
+/// field declared after it.
+#[derive(Debug, Default)]
+struct PrivateScope {
+  declared: HashMap<Box<str>, (MethodDefinitionKind, bool)>,
+  unresolved: Vec<(Box<str>, Position)>,
+}
+
+/// Token cursor.
+///
+/// This internal structure gives basic testable operations to the parser. It buffers the raw
+/// token stream (including line terminators) so the parser can look ahead as far as it needs,
+/// while `peek` and `next` only ever see significant tokens.
+#[derive(Debug)]
+pub(super) struct Cursor<R> {
+  lexer: Lexer<R>,
+  buffer: VecDeque<Token>,
+  eof: bool,
+  /// Errors in object literals, such as shorthand initializers (`{ a = 1 }`), which go away if
+  /// the surrounding literal is later reinterpreted as a pattern.
+  cover_errors: Vec<(Span, &'static str)>,
+  /// Private name scopes of the class bodies being parsed, innermost last.
+  private_scopes: Vec<PrivateScope>,
+  /// Whether the input is parsed with the module goal.
+  module: bool,
+  /// Declaration scopes, labels and strictness, used to report early errors.
+  early_errors: scope::EarlyErrors,
+  /// Whether errors are recorded in `errors` so that parsing can go on.
+  recovering: bool,
+  /// Errors recovered from, in the order they were found.
+  errors: Vec<ParseError>,
+  /// Number of significant tokens consumed so far.
+  consumed: usize,
+  /// End of the last consumed token.
+  last_end: Position,
+  /// Whether the input is TypeScript, whose type syntax is skipped.
+  typescript: bool,
+  /// Names of the TypeScript parameter properties parsed so far, such as `x` in
+  /// `constructor(private x: number)`, taken by the enclosing constructor.
+  parameter_properties: Vec<Box<str>>,
 }
 
-// pub(super) struct Cursor<R> {
-//   buffered_lexer: BufferedLexer<R>
-// }
-
-// impl<R> Cursor<R>
-//   where
-//     R: Read,
-// {
-//   #[inline]
-//   pub(super) fn new(reader: R) -> Self {
-//     Self {
-//       buffered_lexer: Lexer::new(reader).into(),
-//     }
-//   }
-// }
+impl<R> Cursor<R>
+where
+  R: Read,
+{
+  /// Creates a new cursor.
+  #[inline]
+  pub(super) fn new(reader: R) -> Self {
+    Self {
+      lexer: Lexer::new(reader),
+      buffer: VecDeque::new(),
+      eof: false,
+      cover_errors: Vec::new(),
+      private_scopes: Vec::new(),
+      module: false,
+      early_errors: scope::EarlyErrors::default(),
+      recovering: false,
+      errors: Vec::new(),
+      consumed: 0,
+      last_end: Position::new(1, 1),
+      typescript: false,
+      parameter_properties: Vec::new(),
+    }
+  }
+
+  /// Sets whether the input is parsed with the module goal.
+  ///
+  /// Module code is always strict mode code.
+  pub(super) fn set_module(&mut self, module: bool) {
+    self.module = module;
+    if module {
+      self.enter_module_scope();
+      self.set_strict(true);
+    }
+  }
+
+  /// Returns `true` if the input is parsed with the module goal, where `import.meta` is
+  /// available.
+  pub(super) fn is_module(&self) -> bool {
+    self.module
+  }
+
+  /// Sets whether the input is TypeScript.
+  pub(super) fn set_typescript(&mut self, typescript: bool) {
+    self.typescript = typescript;
+  }
+
+  /// Returns `true` if the input is TypeScript, where type annotations and declarations are
+  /// parsed and left out of the syntax tree.
+  pub(super) fn is_typescript(&self) -> bool {
+    self.typescript
+  }
+
+  /// Returns `true` if the token starting at the given position follows a purity annotation.
+  pub(super) fn is_pure_annotated(&self, position: Position) -> bool {
+    self.lexer.is_pure_annotated(position)
+  }
+
+  /// Returns `true` if the string literal starting at the given position contains a legacy
+  /// octal escape sequence.
+  pub(super) fn has_octal_escape(&self, position: Position) -> bool {
+    self.lexer.has_octal_escape(position)
+  }
+
+  /// Gets the source text of the string literal starting at the given position, with its
+  /// quotes and escape sequences.
+  pub(super) fn raw_string(&self, position: Position, value: &str) -> Box<str> {
+    self.lexer.raw_string(position, value)
+  }
+
+  /// Makes sure the raw buffer holds at least `len` tokens, returns `false` if the input ends
+  /// before that.
+  fn fill(&mut self, len: usize) -> Result<bool, ParseError> {
+    while self.buffer.len() < len {
+      if self.eof {
+        return Ok(false);
+      }
+      match self.lex_token()? {
+        Some(tk) => self.buffer.push_back(tk),
+        None => self.eof = true,
+      }
+    }
+    Ok(true)
+  }
+
+  /// Finds the raw buffer index of the significant token `skip_n` positions ahead, together
+  /// with whether a line terminator precedes it.
+  fn lookahead(&mut self, skip_n: usize) -> Result<Option<(usize, bool)>, ParseError> {
+    let mut remaining = skip_n;
+    let mut index = 0;
+    let mut line_terminator = false;
+    loop {
+      if !self.fill(index + 1)? {
+        return Ok(None);
+      }
+      if self.buffer[index].kind == TokenKind::LineTerminator {
+        line_terminator = true;
+      } else if remaining == 0 {
+        return Ok(Some((index, line_terminator)));
+      } else {
+        remaining -= 1;
+        line_terminator = false;
+      }
+      index += 1;
+    }
+  }
+
+  /// Peeks the significant token `skip_n` positions ahead, without consuming anything.
+  pub(super) fn peek(&mut self, skip_n: usize) -> Result<Option<&Token>, ParseError> {
+    Ok(match self.lookahead(skip_n)? {
+      Some((index, _)) => Some(&self.buffer[index]),
+      None => None,
+    })
+  }
+
+  /// Moves the cursor to the next significant token and returns it.
+  pub(super) fn next(&mut self) -> Result<Option<Token>, ParseError> {
+    match self.lookahead(0)? {
+      Some((index, _)) => {
+        self.buffer.drain(..index);
+        let token = self.buffer.pop_front();
+        if let Some(token) = &token {
+          self.consumed += 1;
+          self.last_end = token.span().end();
+        }
+        Ok(token)
+      }
+      None => {
+        self.buffer.clear();
+        Ok(None)
+      }
+    }
+  }
+
+  /// Consumes a single `>` from the start of the next token, which must start with one.
+  ///
+  /// A list of type arguments can end with `>>`, `>=` or `>>=`, where the lexer only sees a
+  /// shift or a comparison: the rest of the token is left for the parser.
+  pub(super) fn next_greater_than(&mut self) -> Result<(), ParseError> {
+    let (index, _) = self.lookahead(0)?.ok_or(ParseError::AbruptEnd)?;
+    let token = &self.buffer[index];
+    let rest = match token.kind() {
+      TokenKind::Punctuator(punctuator) => split_greater_than(*punctuator),
+      _ => None,
+    };
+    let rest = match rest {
+      Some(Some(rest)) => rest,
+      Some(None) => {
+        let _ = self.next()?;
+        return Ok(());
+      }
+      None => {
+        return Err(ParseError::expected(
+          vec![TokenKind::Punctuator(Punctuator::GreaterThan)],
+          token.kind().clone(),
+          token.span(),
+          "type arguments",
+        ))
+      }
+    };
+    let span = token.span();
+    let start = Position::new(span.start().line_number(), span.start().column_number() + 1);
+    self.buffer[index] = Token::new(TokenKind::Punctuator(rest), Span::new(start, span.end()));
+    self.consumed += 1;
+    self.last_end = start;
+    Ok(())
+  }
+
+  /// Gets the position where the next significant token starts, which is where the node parsed
+  /// from it will start.
+  ///
+  /// At the end of the input, this is the end of the last consumed token.
+  pub(super) fn start(&mut self) -> Result<Position, ParseError> {
+    Ok(match self.peek(0)? {
+      Some(token) => token.span().start(),
+      None => self.last_end,
+    })
+  }
+
+  /// Gets the span from `start` to the end of the last consumed token, the span of a node that
+  /// started at `start` and was just parsed.
+  pub(super) fn span_from(&self, start: Position) -> Span {
+    Span::new(start, self.last_end.max(start))
+  }
+
+  /// Returns an error if the next token is not of kind `kind`, otherwise consumes it.
+  ///
+  /// Note: it will consume the next token only if the next token is the expected type.
+  ///
+  /// When recovering from errors, a missing closing bracket is inserted: the error is recorded
+  /// and an empty token is returned.
+  pub(super) fn expect<K>(&mut self, kind: K, context: &'static str) -> Result<Token, ParseError>
+  where
+    K: Into<TokenKind>,
+  {
+    let kind = kind.into();
+    let (error, position) = match self.peek(0)? {
+      Some(next_token) if next_token.kind() == &kind => {
+        return Ok(self.next()?.expect("token disappeared"));
+      }
+      Some(next_token) => (
+        ParseError::expected(
+          vec![kind.clone()],
+          next_token.kind().clone(),
+          next_token.span(),
+          context,
+        ),
+        next_token.span().start(),
+      ),
+      None => {
+        let end = self.lexer.position();
+        let message = format!(
+          "expected {}, got the end of the input in {}",
+          expected_tokens(std::slice::from_ref(&kind)),
+          context
+        );
+        (ParseError::general(message, end), end)
+      }
+    };
+
+    if self.recovering && recovery::is_insertable(&kind) {
+      self.report(error)?;
+      Ok(Token::new(kind, Span::new(position, position)))
+    } else {
+      Err(error)
+    }
+  }
+
+  /// Consumes the next token if it is of kind `kind`.
+  pub(super) fn next_if<K>(&mut self, kind: K) -> Result<Option<Token>, ParseError>
+  where
+    K: Into<TokenKind>,
+  {
+    let kind = kind.into();
+    Ok(match self.peek(0)? {
+      Some(token) if token.kind() == &kind => self.next()?,
+      _ => None,
+    })
+  }
+
+  /// Returns `true` if the next significant token is of kind `kind`.
+  pub(super) fn next_is<K>(&mut self, kind: K) -> Result<bool, ParseError>
+  where
+    K: Into<TokenKind>,
+  {
+    let kind = kind.into();
+    Ok(self.peek(0)?.is_some_and(|tk| tk.kind() == &kind))
+  }
+
+  /// Returns `true` if a line terminator precedes the significant token `skip_n` positions
+  /// ahead.
+  pub(super) fn is_line_terminator_before(&mut self, skip_n: usize) -> Result<bool, ParseError> {
+    Ok(self.lookahead(skip_n)?.is_some_and(|(_, lt)| lt))
+  }
+
+  /// It will check if the next token is a semicolon.
+  ///
+  /// It will automatically insert a semicolon if needed, as specified in the [spec][spec].
+  ///
+  /// [spec]: https://tc39.es/ecma262/#sec-automatic-semicolon-insertion
+  pub(super) fn peek_semicolon(&mut self) -> Result<SemicolonResult<'_>, ParseError> {
+    match self.lookahead(0)? {
+      None => Ok(SemicolonResult::Found(None)),
+      Some((index, line_terminator)) => {
+        let tk = &self.buffer[index];
+        match tk.kind() {
+          TokenKind::Punctuator(Punctuator::Semicolon)
+          | TokenKind::Punctuator(Punctuator::CloseBlock) => Ok(SemicolonResult::Found(Some(tk))),
+          _ if line_terminator => Ok(SemicolonResult::Found(Some(tk))),
+          _ => Ok(SemicolonResult::NotFound(tk)),
+        }
+      }
+    }
+  }
+
+  /// It will check if the next token is a semicolon.
+  ///
+  /// It will automatically insert a semicolon if needed, as specified in the [spec][spec].
+  /// When recovering from errors, a semicolon is inserted anywhere and the error recorded.
+  ///
+  /// [spec]: https://tc39.es/ecma262/#sec-automatic-semicolon-insertion
+  pub(super) fn expect_semicolon(&mut self, context: &'static str) -> Result<(), ParseError> {
+    match self.peek_semicolon()? {
+      SemicolonResult::Found(Some(tk)) => {
+        if tk.kind() == &TokenKind::Punctuator(Punctuator::Semicolon) {
+          let _ = self.next()?;
+        }
+        Ok(())
+      }
+      SemicolonResult::Found(None) => Ok(()),
+      SemicolonResult::NotFound(tk) => {
+        let error = ParseError::expected(
+          vec![TokenKind::Punctuator(Punctuator::Semicolon)],
+          tk.kind().clone(),
+          tk.span(),
+          context,
+        );
+        self.report(error)
+      }
+    }
+  }
+
+  /// It will make sure that the peeked token `skip_n` positions ahead is not preceded by a
+  /// line terminator.
+  ///
+  /// It expects that the token stream does not end here.
+  pub(super) fn peek_expect_no_lineterminator(
+    &mut self,
+    skip_n: usize,
+    context: &'static str,
+  ) -> Result<(), ParseError> {
+    match self.lookahead(skip_n)? {
+      Some((index, true)) => {
+        let tk = &self.buffer[index];
+        Err(ParseError::unexpected(
+          tk.kind().clone(),
+          tk.span(),
+          context,
+        ))
+      }
+      Some((_, false)) => Ok(()),
+      None => Err(ParseError::AbruptEnd),
+    }
+  }
+
+  /// Scans forward from the significant token `skip_n` positions ahead, which must be an
+  /// opening bracket, and returns how many significant tokens ahead its matching closing
+  /// bracket is. Returns `None` if the brackets are unbalanced.
+  pub(super) fn find_matching_bracket(
+    &mut self,
+    skip_n: usize,
+  ) -> Result<Option<usize>, ParseError> {
+    let mut depth = 0usize;
+    let mut n = skip_n;
+    while let Some(tk) = self.peek(n)? {
+      match tk.kind() {
+        TokenKind::Punctuator(Punctuator::OpenParen)
+        | TokenKind::Punctuator(Punctuator::OpenBracket)
+        | TokenKind::Punctuator(Punctuator::OpenBlock) => depth += 1,
+        TokenKind::Punctuator(Punctuator::CloseParen)
+        | TokenKind::Punctuator(Punctuator::CloseBracket)
+        | TokenKind::Punctuator(Punctuator::CloseBlock) => {
+          depth -= 1;
+          if depth == 0 {
+            return Ok(Some(n));
+          }
+        }
+        _ => {}
+      }
+      n += 1;
+    }
+    Ok(None)
+  }
+
+  /// Records the name of a TypeScript parameter property.
+  pub(super) fn record_parameter_property(&mut self, name: &str) {
+    self.parameter_properties.push(name.into())
+  }
+
+  /// Gets a marker for the currently recorded parameter properties.
+  pub(super) fn parameter_properties_mark(&self) -> usize {
+    self.parameter_properties.len()
+  }
+
+  /// Takes the names of the parameter properties recorded after `mark`.
+  pub(super) fn take_parameter_properties(&mut self, mark: usize) -> Vec<Box<str>> {
+    self.parameter_properties.split_off(mark)
+  }
+
+  /// Records an error in an object literal that only goes away inside a pattern.
+  pub(super) fn push_cover_error(&mut self, span: Span, message: &'static str) {
+    self.cover_errors.push((span, message))
+  }
+
+  /// Gets a marker for the currently recorded cover errors.
+  pub(super) fn cover_errors_mark(&self) -> usize {
+    self.cover_errors.len()
+  }
+
+  /// Forgets the cover errors recorded after `mark`, because the literal containing them was
+  /// reinterpreted as a pattern.
+  pub(super) fn discard_cover_errors(&mut self, mark: usize) {
+    self.cover_errors.truncate(mark)
+  }
+
+  /// Gets the first cover error recorded after `mark`, if any.
+  pub(super) fn cover_error_since(&self, mark: usize) -> Option<ParseError> {
+    self
+      .cover_errors
+      .get(mark)
+      .map(|&(span, message)| ParseError::early(message, span))
+  }
+
+  /// Enters a class body, opening a new scope for private names.
+  pub(super) fn push_private_scope(&mut self) {
+    self.private_scopes.push(PrivateScope::default())
+  }
+
+  /// Declares a private name in the innermost class body.
+  ///
+  /// A name can only be declared once, except for a getter and setter pair with the same
+  /// placement.
+  pub(super) fn declare_private_name(
+    &mut self,
+    name: &str,
+    kind: MethodDefinitionKind,
+    is_static: bool,
+    position: Position,
+  ) -> Result<(), ParseError> {
+    let scope = self
+      .private_scopes
+      .last_mut()
+      .expect("private name declared outside of a class body");
+    match scope.declared.get_mut(name) {
+      None => {
+        scope.declared.insert(name.into(), (kind, is_static));
+        Ok(())
+      }
+      Some(entry) => match (entry.0, kind) {
+        (MethodDefinitionKind::Get, MethodDefinitionKind::Set)
+        | (MethodDefinitionKind::Set, MethodDefinitionKind::Get)
+          if entry.1 == is_static =>
+        {
+          // The pair is complete, any further declaration is a duplicate.
+          entry.0 = MethodDefinitionKind::Ordinary;
+          Ok(())
+        }
+        _ => Err(ParseError::general(
+          format!("private name '#{}' has already been declared", name),
+          position,
+        )),
+      },
+    }
+  }
+
+  /// Records a reference to a private name, which must be declared by an enclosing class.
+  pub(super) fn reference_private_name(
+    &mut self,
+    name: &str,
+    position: Position,
+  ) -> Result<(), ParseError> {
+    match self.private_scopes.last_mut() {
+      Some(scope) => {
+        scope.unresolved.push((name.into(), position));
+        Ok(())
+      }
+      None => Err(undeclared_private_name(name, position)),
+    }
+  }
+
+  /// Leaves a class body, resolving the private names referenced in it.
+  ///
+  /// Names that are not declared by this class are left for the enclosing class, if any.
+  pub(super) fn pop_private_scope(&mut self) -> Result<(), ParseError> {
+    let scope = self
+      .private_scopes
+      .pop()
+      .expect("unbalanced private name scopes");
+    for (name, position) in scope.unresolved {
+      if scope.declared.contains_key(&name) {
+        continue;
+      }
+      match self.private_scopes.last_mut() {
+        Some(outer) => outer.unresolved.push((name, position)),
+        None => return Err(undeclared_private_name(&name, position)),
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Splits a punctuator starting with `>` after its first character: `Some(None)` for `>`
+/// itself, `None` if the punctuator does not start with `>`.
+pub(super) fn split_greater_than(punctuator: Punctuator) -> Option<Option<Punctuator>> {
+  match punctuator {
+    Punctuator::GreaterThan => Some(None),
+    Punctuator::GreaterThanOrEq => Some(Some(Punctuator::Assign)),
+    Punctuator::RightSh => Some(Some(Punctuator::GreaterThan)),
+    Punctuator::URightSh => Some(Some(Punctuator::RightSh)),
+    Punctuator::AssignRightSh => Some(Some(Punctuator::GreaterThanOrEq)),
+    Punctuator::AssignURightSh => Some(Some(Punctuator::AssignRightSh)),
+    _ => None,
+  }
+}
+
+/// Creates the error for a private name that no enclosing class declares.
+fn undeclared_private_name(name: &str, position: Position) -> ParseError {
+  ParseError::general(
+    format!("reference to undeclared private name '#{}'", name),
+    position,
+  )
+}
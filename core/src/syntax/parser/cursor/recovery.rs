@@ -0,0 +1,180 @@
+//! Error recovery for the parser.
+//!
+//! When recovering, errors are recorded instead of returned wherever the parser can go on:
+//! missing closing brackets and semicolons are inserted, missing operands become
+//! `Node::Error`, and a statement that can't be parsed is replaced by `Node::Error` after
+//! skipping to the next statement boundary.
+
+use super::{scope::ScopeMark, Cursor};
+use crate::syntax::{
+  ast::{Keyword, Position, Punctuator, Span},
+  lexer::{Error as LexError, Token, TokenKind},
+  parser::ParseError,
+};
+use std::io::Read;
+
+/// The state of the cursor before a statement list item, restored if it can't be parsed.
+#[derive(Debug, Clone, Copy)]
+pub(in crate::syntax::parser) struct RecoveryPoint {
+  start: Position,
+  consumed: usize,
+  scopes: ScopeMark,
+  private_scopes: usize,
+  cover_errors: usize,
+}
+
+impl<R> Cursor<R>
+where
+  R: Read,
+{
+  /// Returns `true` if the parser records errors and goes on instead of stopping.
+  pub(in crate::syntax::parser) fn is_recovering(&self) -> bool {
+    self.recovering
+  }
+
+  /// Sets whether the parser records errors and goes on instead of stopping.
+  pub(in crate::syntax::parser) fn set_recovering(&mut self, recovering: bool) {
+    self.recovering = recovering;
+  }
+
+  /// Reads the next token from the lexer.
+  ///
+  /// When recovering, invalid characters are reported and skipped, since the lexer consumes
+  /// them. Errors reading the input are always returned.
+  pub(super) fn lex_token(&mut self) -> Result<Option<Token>, ParseError> {
+    loop {
+      match self.lexer.next() {
+        Ok(token) => return Ok(token),
+        Err(error @ LexError::IO(_)) => return Err(error.into()),
+        Err(error) => self.report(error.into())?,
+      }
+    }
+  }
+
+  /// Reports an error the parser can go on after.
+  ///
+  /// The error is returned unless recovering, in which case it is recorded. An error starting
+  /// at the same position as the previous one is not recorded again.
+  pub(in crate::syntax::parser) fn report(&mut self, error: ParseError) -> Result<(), ParseError> {
+    if !self.recovering {
+      return Err(error);
+    }
+    let position = error.position();
+    if position.is_none() || self.errors.last().and_then(ParseError::position) != position {
+      self.errors.push(error);
+    }
+    Ok(())
+  }
+
+  /// Takes the errors recorded so far.
+  pub(in crate::syntax::parser) fn take_errors(&mut self) -> Vec<ParseError> {
+    std::mem::take(&mut self.errors)
+  }
+
+  /// Gets the state to restore if the next statement list item can't be parsed.
+  pub(in crate::syntax::parser) fn recovery_point(&mut self) -> Result<RecoveryPoint, ParseError> {
+    Ok(RecoveryPoint {
+      start: self.start()?,
+      consumed: self.consumed,
+      scopes: self.scope_mark(),
+      private_scopes: self.private_scopes.len(),
+      cover_errors: self.cover_errors.len(),
+    })
+  }
+
+  /// Handles the result of parsing a statement list item started at `point`.
+  ///
+  /// When recovering, an error is recorded and the item replaced by `error`, which holds a
+  /// `Node::Error` spanning the skipped tokens, skipping tokens up to the next statement
+  /// boundary. An item always consumes at least one token, so the enclosing list makes
+  /// progress.
+  pub(in crate::syntax::parser) fn recover_item<T, F>(
+    &mut self,
+    point: RecoveryPoint,
+    result: Result<T, ParseError>,
+    error: F,
+  ) -> Result<T, ParseError>
+  where
+    F: FnOnce(Span) -> T,
+  {
+    match result {
+      Ok(item) => {
+        if self.consumed == point.consumed {
+          let _ = self.next()?;
+        }
+        Ok(item)
+      }
+      Err(err) if self.recovering => {
+        self.report(err)?;
+        self.restore_recovery_point(point);
+        self.synchronize(point)?;
+        Ok(error(self.span_from(point.start)))
+      }
+      Err(err) => Err(err),
+    }
+  }
+
+  /// Restores the scopes entered by an item that could not be parsed.
+  fn restore_recovery_point(&mut self, point: RecoveryPoint) {
+    self.restore_scope_mark(point.scopes);
+    self.private_scopes.truncate(point.private_scopes);
+    self.cover_errors.truncate(point.cover_errors);
+  }
+
+  /// Skips tokens up to the end of the current statement: after a `;`, or before a `}` closing
+  /// the enclosing block or a keyword starting a new statement.
+  ///
+  /// Only braces are balanced, an unclosed parenthesis does not hide the end of the statement.
+  fn synchronize(&mut self, point: RecoveryPoint) -> Result<(), ParseError> {
+    if self.consumed == point.consumed {
+      let _ = self.next()?;
+    }
+
+    let mut depth = 0usize;
+    while let Some(token) = self.peek(0)? {
+      match *token.kind() {
+        TokenKind::Punctuator(Punctuator::Semicolon) if depth == 0 => {
+          let _ = self.next()?;
+          break;
+        }
+        TokenKind::Punctuator(Punctuator::CloseBlock) if depth == 0 => break,
+        TokenKind::Keyword(keyword) if depth == 0 && starts_statement(keyword) => break,
+        TokenKind::Punctuator(Punctuator::OpenBlock) => depth += 1,
+        TokenKind::Punctuator(Punctuator::CloseBlock) => depth -= 1,
+        _ => {}
+      }
+      let _ = self.next()?;
+    }
+    Ok(())
+  }
+}
+
+/// Returns `true` if a missing token of this kind is inserted when recovering from errors.
+pub(super) fn is_insertable(kind: &TokenKind) -> bool {
+  matches!(
+    kind,
+    TokenKind::Punctuator(Punctuator::CloseParen)
+      | TokenKind::Punctuator(Punctuator::CloseBracket)
+      | TokenKind::Punctuator(Punctuator::CloseBlock)
+  )
+}
+
+/// Returns `true` if the keyword can only start a statement, where skipped tokens stop.
+fn starts_statement(keyword: Keyword) -> bool {
+  matches!(
+    keyword,
+    Keyword::Break
+      | Keyword::Const
+      | Keyword::Continue
+      | Keyword::Do
+      | Keyword::For
+      | Keyword::If
+      | Keyword::Let
+      | Keyword::Return
+      | Keyword::Switch
+      | Keyword::Throw
+      | Keyword::Try
+      | Keyword::Var
+      | Keyword::While
+  )
+}
@@ -0,0 +1,633 @@
+//! Declaration scopes, label sets and strictness tracking for the parser.
+//!
+//! These are used to report the early errors of the specification while the tokens, and
+//! therefore their spans, are still available.
+//!
+//! More information:
+//!  - [ECMAScript specification][spec]
+//!
+//! [spec]: https://tc39.es/ecma262/#early-error
+
+use super::Cursor;
+use crate::syntax::{
+  ast::Span,
+  lexer::TokenKind,
+  parser::{pattern::check_strict_identifier, ParseError},
+};
+use std::collections::HashSet;
+
+/// The kind of a declaration scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(in crate::syntax::parser) enum ScopeKind {
+  /// The top level of a script or the body of a function, where `var` declarations stop.
+  Function,
+  /// The top level of a module, where function declarations are lexical.
+  Module,
+  /// A block, a `switch` case block or the head of a `for` loop.
+  Block,
+  /// The parameter of a `catch` clause, which conflicts with lexical declarations of the
+  /// clause's block.
+  Catch,
+}
+
+/// The kind of binding created by a declaration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(in crate::syntax::parser) enum BindingKind {
+  /// A `let`, `const`, class or import binding.
+  Lexical,
+  /// A `var` binding, hoisted to the enclosing function.
+  Var,
+  /// A function declaration, which is `var` scoped only at the top level of a function or a
+  /// script.
+  Function,
+  /// A generator or async function declaration, scoped like a function declaration but never
+  /// declared twice in a block.
+  SpecialFunction,
+  /// A formal parameter or a `catch` parameter.
+  Parameter,
+}
+
+/// Where `super` can be used in the code being parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(in crate::syntax::parser) enum SuperAccess {
+  /// Outside of methods, `super` can't be used.
+  None,
+  /// In a method, a class field initializer or a static block, `super.x` reads a property of
+  /// the home object's prototype.
+  Property,
+  /// In the constructor of a class with an `extends` clause, `super()` can also be called.
+  Call,
+}
+
+/// The names declared in a single scope.
+#[derive(Debug)]
+struct Scope {
+  kind: ScopeKind,
+  /// Lexically declared names.
+  lexical: HashSet<Box<str>>,
+  /// Lexically declared names bound only by plain function declarations in sloppy mode code,
+  /// which may be declared again in a block.
+  functions: HashSet<Box<str>>,
+  /// Names declared with `var` in this scope or a nested block, and parameters.
+  var: HashSet<Box<str>>,
+  /// Names of TypeScript types declared in this scope, which are not bindings.
+  types: HashSet<Box<str>>,
+  /// Parameters declared in this scope, checked again if a directive makes them strict.
+  parameters: Vec<(Box<str>, Span)>,
+  /// Whether the parameter list is simple, which allows a `"use strict"` directive.
+  simple_parameters: bool,
+  /// Whether parameter names must be unique in sloppy mode code too, as for arrow functions,
+  /// methods, non-simple parameter lists and `catch` parameters.
+  unique_parameters: bool,
+  /// Length of the recorded bindings when the scope was entered.
+  bindings_mark: usize,
+}
+
+/// The `break` and `continue` targets of a function body.
+#[derive(Debug, Default)]
+struct JumpTargets {
+  /// The labels of the enclosing statements, and whether they label an iteration statement.
+  labels: Vec<(Box<str>, bool)>,
+  /// Number of enclosing iteration statements.
+  loops: usize,
+  /// Number of enclosing `switch` statements.
+  switches: usize,
+}
+
+/// Early error state of the cursor.
+#[derive(Debug)]
+pub(super) struct EarlyErrors {
+  scopes: Vec<Scope>,
+  jump_targets: Vec<JumpTargets>,
+  /// Binding identifiers parsed but not yet declared, with their spans.
+  bindings: Vec<(Box<str>, Span)>,
+  /// Names exported by the module, and local names whose declaration is checked at the end.
+  exports: HashSet<Box<str>>,
+  exported_bindings: Vec<(Box<str>, Span)>,
+  strict: bool,
+  super_access: SuperAccess,
+}
+
+impl Default for EarlyErrors {
+  fn default() -> Self {
+    Self {
+      scopes: vec![Scope::new(ScopeKind::Function, 0)],
+      jump_targets: vec![JumpTargets::default()],
+      bindings: Vec::new(),
+      exports: HashSet::new(),
+      exported_bindings: Vec::new(),
+      strict: false,
+      super_access: SuperAccess::None,
+    }
+  }
+}
+
+/// The depth of the early error state, restored when the parser recovers from an error.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct ScopeMark {
+  scopes: usize,
+  jump_targets: usize,
+  labels: usize,
+  loops: usize,
+  switches: usize,
+  bindings: usize,
+  strict: bool,
+  super_access: SuperAccess,
+}
+
+impl Scope {
+  fn new(kind: ScopeKind, bindings_mark: usize) -> Self {
+    Self {
+      kind,
+      lexical: HashSet::new(),
+      functions: HashSet::new(),
+      var: HashSet::new(),
+      types: HashSet::new(),
+      parameters: Vec::new(),
+      simple_parameters: true,
+      unique_parameters: kind == ScopeKind::Catch,
+      bindings_mark,
+    }
+  }
+}
+
+impl<R> Cursor<R> {
+  /// Returns `true` if the code being parsed is strict mode code.
+  pub(in crate::syntax::parser) fn is_strict(&self) -> bool {
+    self.early_errors.strict
+  }
+
+  /// Sets whether the code being parsed is strict mode code, returning the previous value.
+  ///
+  /// Tokens already buffered by a lookahead are not lexed again, see
+  /// [`check_strict_lookahead`](Self::check_strict_lookahead).
+  pub(in crate::syntax::parser) fn set_strict(&mut self, strict: bool) -> bool {
+    self.lexer.set_strict_mode(strict);
+    std::mem::replace(&mut self.early_errors.strict, strict)
+  }
+
+  /// Reports the literals lexed as sloppy mode code by a lookahead that are not allowed in strict
+  /// mode code, such as `010` right after a `"use strict"` directive without a semicolon.
+  fn check_strict_lookahead(&self) -> Result<(), ParseError> {
+    for token in &self.buffer {
+      let start = token.span().start();
+      match token.kind() {
+        TokenKind::NumericLiteral(_) if self.lexer.has_legacy_octal(start) => {
+          return Err(ParseError::early(
+            "legacy octal literals are not allowed in strict mode code",
+            token.span(),
+          ))
+        }
+        TokenKind::StringLiteral(_) if self.lexer.has_octal_escape(start) => {
+          return Err(ParseError::early(
+            "octal escape sequences are not allowed in strict mode code",
+            token.span(),
+          ))
+        }
+        _ => {}
+      }
+    }
+    Ok(())
+  }
+
+  /// Sets where `super` can be used, returning the previous value.
+  ///
+  /// Arrow functions keep the value of the enclosing code, other functions set it.
+  pub(in crate::syntax::parser) fn set_super_access(&mut self, access: SuperAccess) -> SuperAccess {
+    std::mem::replace(&mut self.early_errors.super_access, access)
+  }
+
+  /// Checks a use of `super` at `span`, a `super()` call if `call` is `true` and a property
+  /// access otherwise.
+  pub(in crate::syntax::parser) fn check_super(
+    &self,
+    call: bool,
+    span: Span,
+  ) -> Result<(), ParseError> {
+    match (self.early_errors.super_access, call) {
+      (SuperAccess::Call, _) | (SuperAccess::Property, false) => Ok(()),
+      (_, true) => Err(ParseError::early(
+        "'super' can only be called in the constructor of a class with an extends clause",
+        span,
+      )),
+      (SuperAccess::None, false) => Err(ParseError::early(
+        "'super' properties can only be used in methods",
+        span,
+      )),
+    }
+  }
+
+  /// Applies a `"use strict"` directive found at `span` to the current function.
+  ///
+  /// The parameters, parsed before the directive, are checked again as strict mode code.
+  pub(in crate::syntax::parser) fn use_strict_directive(
+    &mut self,
+    span: Span,
+  ) -> Result<(), ParseError> {
+    if !self.current_scope().simple_parameters {
+      return Err(ParseError::early(
+        "\"use strict\" not allowed in function with non-simple parameters",
+        span,
+      ));
+    }
+    if self.set_strict(true) {
+      return Ok(());
+    }
+    self.check_strict_lookahead()?;
+
+    let mut names = HashSet::new();
+    for (name, span) in &self.current_scope().parameters {
+      check_strict_identifier(self, name, *span, true)?;
+      if !names.insert(name) {
+        return Err(duplicate_parameter(name, *span));
+      }
+    }
+    Ok(())
+  }
+
+  /// Gets a marker for the current scopes, labels and strictness.
+  pub(super) fn scope_mark(&self) -> ScopeMark {
+    let errors = &self.early_errors;
+    let targets = errors.jump_targets.last().expect("no enclosing function");
+    ScopeMark {
+      scopes: errors.scopes.len(),
+      jump_targets: errors.jump_targets.len(),
+      labels: targets.labels.len(),
+      loops: targets.loops,
+      switches: targets.switches,
+      bindings: errors.bindings.len(),
+      strict: errors.strict,
+      super_access: errors.super_access,
+    }
+  }
+
+  /// Leaves the scopes and statements entered after `mark` was taken.
+  pub(super) fn restore_scope_mark(&mut self, mark: ScopeMark) {
+    let errors = &mut self.early_errors;
+    errors.scopes.truncate(mark.scopes);
+    errors.jump_targets.truncate(mark.jump_targets);
+    errors.bindings.truncate(mark.bindings);
+    let targets = self.jump_targets_mut();
+    targets.labels.truncate(mark.labels);
+    targets.loops = mark.loops;
+    targets.switches = mark.switches;
+    self.early_errors.super_access = mark.super_access;
+    self.set_strict(mark.strict);
+  }
+
+  /// Makes the top level scope a module scope.
+  pub(super) fn enter_module_scope(&mut self) {
+    self.early_errors.scopes[0].kind = ScopeKind::Module;
+  }
+
+  /// Enters a new declaration scope.
+  ///
+  /// Function scopes also get their own `break` and `continue` targets.
+  pub(in crate::syntax::parser) fn push_scope(&mut self, kind: ScopeKind) {
+    let mark = self.early_errors.bindings.len();
+    self.early_errors.scopes.push(Scope::new(kind, mark));
+    if kind == ScopeKind::Function {
+      self.early_errors.jump_targets.push(JumpTargets::default());
+    }
+  }
+
+  /// Leaves the innermost declaration scope.
+  ///
+  /// Bindings recorded inside the scope and never declared, such as the name of a function
+  /// expression, are dropped.
+  pub(in crate::syntax::parser) fn pop_scope(&mut self) {
+    let scope = self
+      .early_errors
+      .scopes
+      .pop()
+      .expect("unbalanced declaration scopes");
+    self.early_errors.bindings.truncate(scope.bindings_mark);
+    if scope.kind == ScopeKind::Function {
+      self.early_errors.jump_targets.pop();
+    }
+  }
+
+  /// Records a parsed binding identifier, declared later by the enclosing declaration.
+  pub(in crate::syntax::parser) fn record_binding(&mut self, name: &str, span: Span) {
+    self.early_errors.bindings.push((name.into(), span));
+  }
+
+  /// Gets a marker for the currently recorded bindings.
+  pub(in crate::syntax::parser) fn bindings_mark(&self) -> usize {
+    self.early_errors.bindings.len()
+  }
+
+  /// Forgets the bindings recorded after `mark`, because they do not declare anything in the
+  /// current scope.
+  pub(in crate::syntax::parser) fn discard_bindings(&mut self, mark: usize) {
+    self.early_errors.bindings.truncate(mark)
+  }
+
+  /// Declares the bindings recorded after `mark` in the current scope.
+  pub(in crate::syntax::parser) fn declare_bindings(
+    &mut self,
+    mark: usize,
+    kind: BindingKind,
+  ) -> Result<(), ParseError> {
+    let bindings = self.early_errors.bindings.split_off(mark);
+    for (name, span) in bindings {
+      self.declare_binding(&name, span, kind)?;
+    }
+    Ok(())
+  }
+
+  /// Declares the formal parameters recorded after `mark` in the current function scope.
+  ///
+  /// Duplicate names are an error in strict mode code, and in sloppy mode code too if the list
+  /// is `unique` or not `simple`.
+  pub(in crate::syntax::parser) fn declare_parameters(
+    &mut self,
+    mark: usize,
+    simple: bool,
+    unique: bool,
+  ) -> Result<(), ParseError> {
+    let scope = self
+      .early_errors
+      .scopes
+      .last_mut()
+      .expect("no enclosing scope");
+    scope.simple_parameters = simple;
+    scope.unique_parameters = unique || !simple;
+    self.declare_bindings(mark, BindingKind::Parameter)
+  }
+
+  /// Declares a single name in the current scope.
+  pub(in crate::syntax::parser) fn declare_binding(
+    &mut self,
+    name: &str,
+    span: Span,
+    kind: BindingKind,
+  ) -> Result<(), ParseError> {
+    let name: Box<str> = name.into();
+    let scopes = &mut self.early_errors.scopes;
+    let current = scopes.len() - 1;
+    let sloppy_function = kind == BindingKind::Function && !self.early_errors.strict;
+    let kind = match (kind, scopes[current].kind) {
+      (BindingKind::Function, ScopeKind::Function)
+      | (BindingKind::SpecialFunction, ScopeKind::Function) => BindingKind::Var,
+      (BindingKind::Function, _) | (BindingKind::SpecialFunction, _) => BindingKind::Lexical,
+      (kind, _) => kind,
+    };
+
+    match kind {
+      BindingKind::Lexical => {
+        let scope = &scopes[current];
+        let parameter = match scopes.get(current.wrapping_sub(1)) {
+          Some(parent) if parent.kind == ScopeKind::Catch => parent.var.contains(&name),
+          _ => false,
+        };
+        // Annex B lets sloppy mode code declare a plain function twice in a block.
+        if scope.lexical.contains(&name) {
+          if sloppy_function && scope.functions.contains(&name) {
+            return Ok(());
+          }
+          return Err(already_declared(&name, span));
+        }
+        if scope.var.contains(&name) || parameter {
+          return Err(var_conflict(&name, span));
+        }
+        if sloppy_function {
+          scopes[current].functions.insert(name.clone());
+        }
+        scopes[current].lexical.insert(name);
+      }
+      BindingKind::Var => {
+        for scope in scopes.iter_mut().rev() {
+          if scope.lexical.contains(&name) {
+            return Err(var_conflict(&name, span));
+          }
+          scope.var.insert(name.clone());
+          if scope.kind == ScopeKind::Function || scope.kind == ScopeKind::Module {
+            break;
+          }
+        }
+      }
+      BindingKind::Parameter => {
+        let scope = &mut scopes[current];
+        let unique = self.early_errors.strict || scope.unique_parameters;
+        if unique && scope.var.contains(&name) {
+          return Err(duplicate_parameter(&name, span));
+        }
+        scope.parameters.push((name.clone(), span));
+        scope.var.insert(name);
+      }
+      BindingKind::Function | BindingKind::SpecialFunction => {
+        unreachable!("function declarations are resolved above")
+      }
+    }
+    Ok(())
+  }
+
+  /// Returns `true` if a binding of the given name is declared in the current scope, or with
+  /// `var` in a nested block.
+  pub(in crate::syntax::parser) fn is_declared(&self, name: &str) -> bool {
+    let scope = self.current_scope();
+    scope.lexical.contains(name) || scope.var.contains(name)
+  }
+
+  /// Declares the name of a TypeScript type, such as an interface, in the current scope.
+  ///
+  /// Types and bindings live apart, an interface can have the name of a class.
+  pub(in crate::syntax::parser) fn declare_type(&mut self, name: &str) {
+    self
+      .early_errors
+      .scopes
+      .last_mut()
+      .expect("no enclosing scope")
+      .types
+      .insert(name.into());
+  }
+
+  /// Declares the bindings recorded after `mark` as types, for the names imported by
+  /// `import type`.
+  pub(in crate::syntax::parser) fn declare_type_bindings(&mut self, mark: usize) {
+    let bindings = self.early_errors.bindings.split_off(mark);
+    for (name, _) in bindings {
+      self.declare_type(&name);
+    }
+  }
+
+  /// Returns `true` if a name declared at the top level only names a type, so that exporting
+  /// it exports nothing at run time.
+  pub(in crate::syntax::parser) fn is_type_only(&self, name: &str) -> bool {
+    let scope = &self.early_errors.scopes[0];
+    scope.types.contains(name) && !scope.lexical.contains(name) && !scope.var.contains(name)
+  }
+
+  /// Records a name exported by the module, which can only be exported once.
+  pub(in crate::syntax::parser) fn declare_export(
+    &mut self,
+    name: &str,
+    span: Span,
+  ) -> Result<(), ParseError> {
+    if !self.early_errors.exports.insert(name.into()) {
+      return Err(ParseError::early(
+        format!("duplicate export of '{}'", name),
+        span,
+      ));
+    }
+    Ok(())
+  }
+
+  /// Records a local binding exported by an export list, which must be declared by the module.
+  pub(in crate::syntax::parser) fn reference_export(&mut self, name: &str, span: Span) {
+    self
+      .early_errors
+      .exported_bindings
+      .push((name.into(), span));
+  }
+
+  /// Checks that every local binding exported by the module is declared at its top level.
+  pub(in crate::syntax::parser) fn check_exported_bindings(&mut self) -> Result<(), ParseError> {
+    let scope = &self.early_errors.scopes[0];
+    for (name, span) in &self.early_errors.exported_bindings {
+      if !scope.lexical.contains(name) && !scope.var.contains(name) && !scope.types.contains(name) {
+        return Err(ParseError::early(
+          format!("export '{}' is not defined in module", name),
+          *span,
+        ));
+      }
+    }
+    Ok(())
+  }
+
+  /// Adds a label to the label set of the current function.
+  pub(in crate::syntax::parser) fn push_label(
+    &mut self,
+    name: &str,
+    span: Span,
+    is_loop: bool,
+  ) -> Result<(), ParseError> {
+    let targets = self.jump_targets_mut();
+    if targets
+      .labels
+      .iter()
+      .any(|(label, _)| label.as_ref() == name)
+    {
+      return Err(ParseError::early(
+        format!("label '{}' has already been declared", name),
+        span,
+      ));
+    }
+    targets.labels.push((name.into(), is_loop));
+    Ok(())
+  }
+
+  /// Removes the innermost label of the current function.
+  pub(in crate::syntax::parser) fn pop_label(&mut self) {
+    self.jump_targets_mut().labels.pop();
+  }
+
+  /// Enters an iteration statement, or a `switch` statement if `is_loop` is `false`.
+  pub(in crate::syntax::parser) fn enter_breakable(&mut self, is_loop: bool) {
+    let targets = self.jump_targets_mut();
+    if is_loop {
+      targets.loops += 1;
+    } else {
+      targets.switches += 1;
+    }
+  }
+
+  /// Leaves the statement entered by the matching `enter_breakable`.
+  pub(in crate::syntax::parser) fn exit_breakable(&mut self, is_loop: bool) {
+    let targets = self.jump_targets_mut();
+    if is_loop {
+      targets.loops -= 1;
+    } else {
+      targets.switches -= 1;
+    }
+  }
+
+  /// Checks that a `break` statement has a target.
+  pub(in crate::syntax::parser) fn check_break(
+    &mut self,
+    label: Option<&str>,
+    span: Span,
+  ) -> Result<(), ParseError> {
+    let targets = self.jump_targets_mut();
+    match label {
+      Some(label) if !targets.labels.iter().any(|(l, _)| l.as_ref() == label) => Err(
+        ParseError::early(format!("undefined label '{}'", label), span),
+      ),
+      None if targets.loops == 0 && targets.switches == 0 => Err(ParseError::early(
+        "break statement outside of a loop or switch",
+        span,
+      )),
+      _ => Ok(()),
+    }
+  }
+
+  /// Checks that a `continue` statement targets an enclosing iteration statement.
+  pub(in crate::syntax::parser) fn check_continue(
+    &mut self,
+    label: Option<&str>,
+    span: Span,
+  ) -> Result<(), ParseError> {
+    let targets = self.jump_targets_mut();
+    if targets.loops == 0 {
+      return Err(ParseError::early(
+        "continue statement outside of a loop",
+        span,
+      ));
+    }
+    match label.map(|label| {
+      (
+        label,
+        targets.labels.iter().find(|(l, _)| l.as_ref() == label),
+      )
+    }) {
+      Some((label, None)) => Err(ParseError::early(
+        format!("undefined label '{}'", label),
+        span,
+      )),
+      Some((label, Some((_, false)))) => Err(ParseError::early(
+        format!("label '{}' does not denote an iteration statement", label),
+        span,
+      )),
+      _ => Ok(()),
+    }
+  }
+
+  /// Gets the innermost declaration scope.
+  fn current_scope(&self) -> &Scope {
+    self.early_errors.scopes.last().expect("no enclosing scope")
+  }
+
+  /// Gets the jump targets of the current function.
+  fn jump_targets_mut(&mut self) -> &mut JumpTargets {
+    self
+      .early_errors
+      .jump_targets
+      .last_mut()
+      .expect("no enclosing function")
+  }
+}
+
+/// Creates the error for a lexical declaration of a name already declared lexically.
+fn already_declared(name: &str, span: Span) -> ParseError {
+  ParseError::early(
+    format!("identifier '{}' has already been declared", name),
+    span,
+  )
+}
+
+/// Creates the error for a parameter name used twice where names must be unique.
+fn duplicate_parameter(name: &str, span: Span) -> ParseError {
+  ParseError::early(format!("duplicate parameter name '{}'", name), span)
+}
+
+/// Creates the error for a name declared both lexically and with `var` or as a parameter.
+fn var_conflict(name: &str, span: Span) -> ParseError {
+  ParseError::early(
+    format!(
+      "identifier '{}' conflicts with a var declaration or a parameter",
+      name
+    ),
+    span,
+  )
+}
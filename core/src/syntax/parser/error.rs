@@ -0,0 +1,240 @@
+//! Error and result implementation for the parser.
+
+use crate::syntax::{
+  ast::{Position, Span},
+  lexer::{Error as LexError, TokenKind},
+};
+use std::{error::Error as StdError, fmt};
+
+/// Result of a parsing operation.
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// `ParseError` is an enum which represents errors encounted during parsing an expression
+#[derive(Debug)]
+pub enum ParseError {
+  /// When it expected a certain kind of token, but got another as part of something
+  Expected {
+    expected: Box<[TokenKind]>,
+    found: TokenKind,
+    span: Span,
+    context: &'static str,
+  },
+  /// When a token is unexpected
+  Unexpected {
+    found: TokenKind,
+    span: Span,
+    message: Option<&'static str>,
+  },
+  /// When there is an abrupt end to the parsing
+  AbruptEnd,
+  /// A lexing error.
+  Lex { err: LexError },
+  /// Catch all General Error
+  General {
+    message: Box<str>,
+    position: Position,
+  },
+  /// An early error of the specification, such as a duplicate declaration
+  Early { message: Box<str>, span: Span },
+}
+
+impl From<LexError> for ParseError {
+  fn from(e: LexError) -> Self {
+    Self::lex(e)
+  }
+}
+
+impl ParseError {
+  /// Creates an `Expected` parsing error.
+  pub(super) fn expected<E>(
+    expected: E,
+    found: TokenKind,
+    span: Span,
+    context: &'static str,
+  ) -> Self
+  where
+    E: Into<Box<[TokenKind]>>,
+  {
+    Self::Expected {
+      expected: expected.into(),
+      found,
+      span,
+      context,
+    }
+  }
+
+  /// Creates an `Unexpected` parsing error.
+  pub(super) fn unexpected<C>(found: TokenKind, span: Span, message: C) -> Self
+  where
+    C: Into<Option<&'static str>>,
+  {
+    Self::Unexpected {
+      found,
+      span,
+      message: message.into(),
+    }
+  }
+
+  /// Creates a "general" parsing error.
+  pub(super) fn general<M>(message: M, position: Position) -> Self
+  where
+    M: Into<Box<str>>,
+  {
+    Self::General {
+      message: message.into(),
+      position,
+    }
+  }
+
+  /// Creates an early error covering the given span.
+  pub(super) fn early<M>(message: M, span: Span) -> Self
+  where
+    M: Into<Box<str>>,
+  {
+    Self::Early {
+      message: message.into(),
+      span,
+    }
+  }
+
+  /// Creates a parsing error from a lexing error.
+  pub(super) fn lex(e: LexError) -> Self {
+    Self::Lex { err: e }
+  }
+
+  /// Gets the description of the error, without its position.
+  pub fn message(&self) -> String {
+    match self {
+      Self::Expected {
+        expected,
+        found,
+        context,
+        ..
+      } => format!(
+        "expected {}, got '{}' in {}",
+        expected_tokens(expected),
+        found,
+        context
+      ),
+      Self::Unexpected { found, message, .. } => match message {
+        Some(m) => format!("unexpected token '{}', {}", found, m),
+        None => format!("unexpected token '{}'", found),
+      },
+      Self::AbruptEnd => "abrupt end".to_owned(),
+      Self::General { message, .. } | Self::Early { message, .. } => message.to_string(),
+      Self::Lex {
+        err: LexError::Syntax(message, _),
+      } => message.to_string(),
+      Self::Lex { err } => err.to_string(),
+    }
+  }
+
+  /// Gets the position where the error starts, if it has one.
+  pub fn position(&self) -> Option<Position> {
+    match self {
+      Self::Expected { span, .. } | Self::Unexpected { span, .. } | Self::Early { span, .. } => {
+        Some(span.start())
+      }
+      Self::General { position, .. } => Some(*position),
+      Self::Lex {
+        err: LexError::Syntax(_, position),
+      } => Some(*position),
+      Self::AbruptEnd | Self::Lex { .. } => None,
+    }
+  }
+}
+
+/// A syntax tree parsed with error recovery, together with every error found.
+///
+/// Parts of the tree that could not be parsed are `Node::Error` nodes.
+#[derive(Debug)]
+pub struct Recovered<T> {
+  ast: T,
+  errors: Box<[ParseError]>,
+}
+
+impl<T> Recovered<T> {
+  /// Creates a new recovered syntax tree.
+  pub(super) fn new<E>(ast: T, errors: E) -> Self
+  where
+    E: Into<Box<[ParseError]>>,
+  {
+    Self {
+      ast,
+      errors: errors.into(),
+    }
+  }
+
+  /// Gets the partial syntax tree.
+  pub fn ast(&self) -> &T {
+    &self.ast
+  }
+
+  /// Gets the errors found while parsing, in the order they were found.
+  pub fn errors(&self) -> &[ParseError] {
+    &self.errors
+  }
+
+  /// Returns `true` if no error was found.
+  pub fn is_ok(&self) -> bool {
+    self.errors.is_empty()
+  }
+
+  /// Splits into the syntax tree and the errors.
+  pub fn into_parts(self) -> (T, Box<[ParseError]>) {
+    (self.ast, self.errors)
+  }
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match (self, self.position()) {
+      (Self::Lex { err }, _) => fmt::Display::fmt(err, f),
+      (_, Some(position)) => write!(
+        f,
+        "{} at line {}, col {}",
+        self.message(),
+        position.line_number(),
+        position.column_number()
+      ),
+      (_, None) => f.write_str(&self.message()),
+    }
+  }
+}
+
+/// Formats a list of expected tokens, such as `token ';'` or `one of ')' or ','`.
+pub(crate) fn expected_tokens(expected: &[TokenKind]) -> String {
+  if expected.len() == 1 {
+    format!("token '{}'", expected[0])
+  } else {
+    format!(
+      "one of {}",
+      expected
+        .iter()
+        .enumerate()
+        .map(|(i, t)| {
+          format!(
+            "{}'{}'",
+            if i == 0 {
+              ""
+            } else if i == expected.len() - 1 {
+              " or "
+            } else {
+              ", "
+            },
+            t
+          )
+        })
+        .collect::<String>()
+    )
+  }
+}
+
+impl StdError for ParseError {
+  fn source(&self) -> Option<&(dyn StdError + 'static)> {
+    match self {
+      Self::Lex { err } => Some(err),
+      _ => None,
+    }
+  }
+}
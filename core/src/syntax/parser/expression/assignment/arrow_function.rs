@@ -0,0 +1,168 @@
+//! Arrow function parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Functions/Arrow_functions
+//! [spec]: https://tc39.es/ecma262/#sec-arrow-function-definitions
+
+use super::AssignmentExpression;
+use crate::syntax::{
+  ast::{
+    arena::ArenaBox,
+    node::{ArrowFunctionDecl, Declaration, FormalParameter, Identifier, Return, StatementList},
+    Punctuator,
+  },
+  lexer::TokenKind,
+  parser::{
+    function::{is_simple_parameter_list, FormalParameters, FunctionBody},
+    pattern::BindingIdentifier,
+    typescript, AllowAwait, AllowIn, AllowYield, Cursor, ParseError, ScopeKind, TokenParser,
+  },
+};
+use std::io::Read;
+
+/// Arrow function parsing, including async arrow functions.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Functions/Arrow_functions
+/// [spec]: https://tc39.es/ecma262/#prod-ArrowFunction
+#[derive(Debug, Clone, Copy)]
+pub(in crate::syntax::parser) struct ArrowFunction {
+  allow_in: AllowIn,
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl ArrowFunction {
+  /// Creates a new `ArrowFunction` parser.
+  pub(in crate::syntax::parser) fn new<I, Y, A>(allow_in: I, allow_yield: Y, allow_await: A) -> Self
+  where
+    I: Into<AllowIn>,
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_in: allow_in.into(),
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for ArrowFunction
+where
+  R: Read,
+{
+  type Output = ArrowFunctionDecl;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    // The caller only gets here for `async` if it is followed by the parameters of an arrow
+    // function, on the same line. `async => {}` is a plain arrow function with an `async`
+    // parameter.
+    let is_async = cursor.next_is(TokenKind::identifier("async"))?
+      && !cursor
+        .peek(1)?
+        .is_some_and(|tk| tk.kind() == &TokenKind::Punctuator(Punctuator::Arrow));
+    if is_async {
+      let _ = cursor.next()?.expect("async token vanished");
+    }
+
+    // The parameters of an async arrow function can't contain `yield` expressions, and `await`
+    // is reserved in them.
+    let (allow_yield, allow_await) = if is_async {
+      (AllowYield(false), AllowAwait(true))
+    } else {
+      (self.allow_yield, self.allow_await)
+    };
+
+    cursor.push_scope(ScopeKind::Function);
+    if cursor.is_typescript() {
+      typescript::skip_type_parameters(cursor)?;
+    }
+    let mark = cursor.bindings_mark();
+    let params = if cursor.next_if(Punctuator::OpenParen)?.is_some() {
+      let params = FormalParameters::new(allow_yield, allow_await).parse(cursor)?;
+      cursor.expect(Punctuator::CloseParen, "arrow function")?;
+      if cursor.is_typescript() {
+        typescript::return_type_annotation(cursor)?;
+      }
+      params
+    } else {
+      let start = cursor.start()?;
+      let param = BindingIdentifier::new(self.allow_yield, allow_await).parse(cursor)?;
+      let span = cursor.span_from(start);
+      ArenaBox::from([FormalParameter::new(
+        Declaration::new(Identifier::new(param).with_span(span), None).with_span(span),
+        false,
+      )
+      .with_span(span)])
+    };
+    cursor.declare_parameters(mark, is_simple_parameter_list(&params), true)?;
+
+    cursor.peek_expect_no_lineterminator(0, "arrow function")?;
+    cursor.expect(Punctuator::Arrow, "arrow function")?;
+
+    let body = ConciseBody::new(self.allow_in, is_async).parse(cursor)?;
+    cursor.pop_scope();
+
+    if is_async {
+      Ok(ArrowFunctionDecl::new_async(params, body))
+    } else {
+      Ok(ArrowFunctionDecl::new(params, body))
+    }
+  }
+}
+
+/// Parses the body of an arrow function, either a function body or a single expression.
+///
+/// `await` is only an operator in the body of async arrow functions, `yield` is never one.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ConciseBody
+#[derive(Debug, Clone, Copy)]
+struct ConciseBody {
+  allow_in: AllowIn,
+  is_async: bool,
+}
+
+impl ConciseBody {
+  /// Creates a new `ConciseBody` parser.
+  fn new<I>(allow_in: I, is_async: bool) -> Self
+  where
+    I: Into<AllowIn>,
+  {
+    Self {
+      allow_in: allow_in.into(),
+      is_async,
+    }
+  }
+}
+
+impl<R> TokenParser<R> for ConciseBody
+where
+  R: Read,
+{
+  type Output = StatementList;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    if cursor.next_if(Punctuator::OpenBlock)?.is_some() {
+      let strict = cursor.is_strict();
+      let body = FunctionBody::new(false, self.is_async).parse(cursor)?;
+      cursor.set_strict(strict);
+      cursor.expect(Punctuator::CloseBlock, "arrow function")?;
+      Ok(body)
+    } else {
+      let start = cursor.start()?;
+      let expr = AssignmentExpression::new(self.allow_in, false, self.is_async).parse(cursor)?;
+      let body = Return::new(Some(expr)).with_span(cursor.span_from(start));
+      Ok(StatementList::from(vec![body.into()]).with_strict(cursor.is_strict()))
+    }
+  }
+}
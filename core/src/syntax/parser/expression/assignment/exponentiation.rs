@@ -0,0 +1,115 @@
+//! Exponentiation operator parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Arithmetic_Operators#Exponentiation
+//! [spec]: https://tc39.es/ecma262/#sec-exp-operator
+
+use crate::syntax::{
+  ast::{
+    node::{BinOp, Node},
+    operator::NumOp,
+    Keyword, Punctuator,
+  },
+  lexer::TokenKind,
+  parser::{
+    expression::{unary::UnaryExpression, update::UpdateExpression},
+    AllowAwait, AllowYield, Cursor, ParseError, TokenParser,
+  },
+};
+use std::io::Read;
+
+/// Parses an exponentiation expression.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Arithmetic_Operators#Exponentiation
+/// [spec]: https://tc39.es/ecma262/#prod-ExponentiationExpression
+#[derive(Debug, Clone, Copy)]
+pub(in crate::syntax::parser::expression) struct ExponentiationExpression {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl ExponentiationExpression {
+  /// Creates a new `ExponentiationExpression` parser.
+  pub(in crate::syntax::parser::expression) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+/// Checks if the next token is the start of a unary expression (other than an update
+/// expression), which cannot be the base of `**` without parentheses.
+fn is_unary_expression<R>(
+  cursor: &mut Cursor<R>,
+  allow_await: AllowAwait,
+) -> Result<bool, ParseError>
+where
+  R: Read,
+{
+  if allow_await.0 && cursor.next_is(Keyword::Await)? {
+    return Ok(true);
+  }
+  // A TypeScript type assertion, `<T>x`.
+  if cursor.is_typescript() && cursor.next_is(Punctuator::LessThan)? {
+    return Ok(true);
+  }
+
+  Ok(matches!(
+    cursor.peek(0)?.map(|tk| tk.kind()),
+    Some(TokenKind::Keyword(Keyword::Delete))
+      | Some(TokenKind::Keyword(Keyword::Void))
+      | Some(TokenKind::Keyword(Keyword::TypeOf))
+      | Some(TokenKind::Punctuator(Punctuator::Add))
+      | Some(TokenKind::Punctuator(Punctuator::Sub))
+      | Some(TokenKind::Punctuator(Punctuator::Not))
+      | Some(TokenKind::Punctuator(Punctuator::Neg))
+  ))
+}
+
+impl<R> TokenParser<R> for ExponentiationExpression
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    if is_unary_expression(cursor, self.allow_await)? {
+      let unary = UnaryExpression::new(self.allow_yield, self.allow_await).parse(cursor)?;
+      if let Some(tok) = cursor.peek(0)? {
+        if tok.kind() == &TokenKind::Punctuator(Punctuator::Exp) {
+          return Err(ParseError::unexpected(
+            tok.kind().clone(),
+            tok.span(),
+            "unary operator used immediately before exponentiation expression",
+          ));
+        }
+      }
+      return Ok(unary);
+    }
+
+    let start = cursor.start()?;
+    let lhs = UpdateExpression::new(self.allow_yield, self.allow_await).parse(cursor)?;
+    if cursor.next_if(Punctuator::Exp)?.is_some() {
+      let rhs = self.parse(cursor)?;
+      return Ok(
+        BinOp::new(NumOp::Exp, lhs, rhs)
+          .with_span(cursor.span_from(start))
+          .into(),
+      );
+    }
+
+    Ok(lhs)
+  }
+}
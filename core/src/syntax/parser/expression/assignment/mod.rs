@@ -0,0 +1,314 @@
+//! Assignment operator parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Assignment_Operators#Assignment
+//! [spec]: https://tc39.es/ecma262/#sec-assignment-operators
+
+mod arrow_function;
+mod conditional;
+mod exponentiation;
+
+pub(super) use self::exponentiation::ExponentiationExpression;
+use self::{arrow_function::ArrowFunction, conditional::ConditionalExpression};
+use crate::syntax::{
+  ast::{
+    node::{Assign, BinOp, Node, Yield},
+    operator, Keyword, Punctuator,
+  },
+  lexer::TokenKind,
+  parser::{
+    pattern::{
+      check_strict_assignment_target, check_strict_pattern, expression_to_pattern,
+      is_simple_assignment_target,
+    },
+    typescript, AllowAwait, AllowIn, AllowYield, Cursor, ParseError, TokenParser,
+  },
+};
+use std::io::Read;
+
+/// Assignment expression parsing.
+///
+/// This can be one of the following:
+///
+///  - `ConditionalExpression`
+///  - `YieldExpression`
+///  - `ArrowFunction`
+///  - `AsyncArrowFunction`
+///  - `LeftHandSideExpression` `=` `AssignmentExpression`
+///  - `LeftHandSideExpression` `AssignmentOperator` `AssignmentExpression`
+///
+/// An object or array literal followed by `=` is reinterpreted as a destructuring assignment
+/// pattern.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Assignment_Operators#Assignment
+/// [spec]: https://tc39.es/ecma262/#prod-AssignmentExpression
+#[derive(Debug, Clone, Copy)]
+pub(in crate::syntax::parser) struct AssignmentExpression {
+  allow_in: AllowIn,
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+  allow_cover_initializers: bool,
+  consequent: bool,
+}
+
+impl AssignmentExpression {
+  /// Creates a new `AssignmentExpression` parser.
+  pub(in crate::syntax::parser) fn new<I, Y, A>(allow_in: I, allow_yield: Y, allow_await: A) -> Self
+  where
+    I: Into<AllowIn>,
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_in: allow_in.into(),
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+      allow_cover_initializers: false,
+      consequent: false,
+    }
+  }
+
+  /// Allows the errors of object literals that go away in patterns, such as shorthand
+  /// initializers (`{ a = 1 }`), to be left unchecked.
+  ///
+  /// This is used for the elements of array and object literals, which could still become
+  /// part of a pattern once the enclosing literal is followed by `=`.
+  pub(in crate::syntax::parser) fn allow_cover_initializers(mut self) -> Self {
+    self.allow_cover_initializers = true;
+    self
+  }
+
+  /// Parses the consequent of a conditional expression, which is followed by `:`.
+  ///
+  /// In TypeScript, a parenthesized arrow function then only has a return type if the `:` of
+  /// the conditional follows it: `a ? (b): c => d : e`, but `a ? (b) : c => d`.
+  pub(in crate::syntax::parser) fn consequent(mut self) -> Self {
+    self.consequent = true;
+    self
+  }
+}
+
+impl<R> TokenParser<R> for AssignmentExpression
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let next_token = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.clone();
+    let start = next_token.span().start();
+
+    if self.allow_yield.0 && next_token.kind() == &TokenKind::Keyword(Keyword::Yield) {
+      let yield_expr = YieldExpression::new(self.allow_in, self.allow_await).parse(cursor)?;
+      return Ok(yield_expr.with_span(cursor.span_from(start)).into());
+    }
+
+    // Arrow functions are detected ahead of time, their parameters are parsed as binding
+    // patterns directly.
+    if self.is_arrow_function(cursor)? {
+      let arrow =
+        ArrowFunction::new(self.allow_in, self.allow_yield, self.allow_await).parse(cursor)?;
+      return Ok(arrow.with_span(cursor.span_from(start)).into());
+    }
+
+    let mark = cursor.cover_errors_mark();
+    let lhs = ConditionalExpression::new(self.allow_in, self.allow_yield, self.allow_await)
+      .parse(cursor)?;
+
+    if let Some(tok) = cursor.peek(0)? {
+      match tok.kind() {
+        TokenKind::Punctuator(Punctuator::Assign) => {
+          let _ = cursor.next()?.expect("= token vanished");
+          check_strict_assignment_target(cursor, &lhs, next_token.span())?;
+          let target = expression_to_pattern(&lhs, start)?;
+          check_strict_pattern(cursor, &target, next_token.span())?;
+          cursor.discard_cover_errors(mark);
+
+          let rhs = self.parse_rhs(cursor)?;
+          return Ok(
+            Assign::new(target, rhs)
+              .with_span(cursor.span_from(start))
+              .into(),
+          );
+        }
+        TokenKind::Punctuator(p) => {
+          if let Some(op @ operator::BinOp::Assign(_)) = p.as_binop() {
+            let _ = cursor.next()?.expect("assignment operator token vanished");
+            if !is_simple_assignment_target(&lhs) {
+              return Err(ParseError::general(
+                "invalid left-hand side in assignment",
+                start,
+              ));
+            }
+            check_strict_assignment_target(cursor, &lhs, next_token.span())?;
+
+            let rhs = self.parse_rhs(cursor)?;
+            return Ok(
+              BinOp::new(op, lhs, rhs)
+                .with_span(cursor.span_from(start))
+                .into(),
+            );
+          }
+        }
+        _ => {}
+      }
+    }
+
+    if !self.allow_cover_initializers {
+      if let Some(err) = cursor.cover_error_since(mark) {
+        return Err(err);
+      }
+    }
+
+    Ok(lhs)
+  }
+}
+
+impl AssignmentExpression {
+  /// Parses the right hand side of an assignment, which is a plain `AssignmentExpression`.
+  fn parse_rhs<R>(self, cursor: &mut Cursor<R>) -> Result<Node, ParseError>
+  where
+    R: Read,
+  {
+    AssignmentExpression::new(self.allow_in, self.allow_yield, self.allow_await).parse(cursor)
+  }
+
+  /// Returns `true` if the upcoming tokens start an arrow function or an async arrow function.
+  fn is_arrow_function<R>(self, cursor: &mut Cursor<R>) -> Result<bool, ParseError>
+  where
+    R: Read,
+  {
+    let typescript = cursor.is_typescript();
+    let is_async = match cursor.peek(0)?.map(|tk| tk.kind()) {
+      Some(TokenKind::Identifier(name)) if name.as_ref() == "async" => {
+        match cursor.peek(1)?.map(|tk| tk.kind()) {
+          Some(TokenKind::Identifier(_))
+          | Some(TokenKind::Keyword(Keyword::Yield))
+          | Some(TokenKind::Keyword(Keyword::Await))
+          | Some(TokenKind::Punctuator(Punctuator::OpenParen)) => {
+            !cursor.is_line_terminator_before(1)?
+          }
+          Some(TokenKind::Punctuator(Punctuator::LessThan)) if typescript => {
+            !cursor.is_line_terminator_before(1)?
+          }
+          _ => false,
+        }
+      }
+      _ => false,
+    };
+    let skip = usize::from(is_async);
+
+    // In TypeScript, the parameters can follow type parameters and be followed by a return
+    // type, as in `<T>(x: T): T => x`.
+    if typescript {
+      if let Some(TokenKind::Punctuator(Punctuator::OpenParen))
+      | Some(TokenKind::Punctuator(Punctuator::LessThan)) =
+        cursor.peek(skip)?.map(|tk| tk.kind())
+      {
+        return match typescript::typed_arrow(cursor, skip)? {
+          Some((arrow, return_type)) => {
+            let ambiguous = return_type && self.consequent;
+            Ok(
+              !cursor.is_line_terminator_before(arrow)?
+                && (!ambiguous || typescript::is_alternate_ahead(cursor, arrow + 1)?),
+            )
+          }
+          None => Ok(false),
+        };
+      }
+    }
+
+    let arrow = match cursor.peek(skip)?.map(|tk| tk.kind()) {
+      Some(TokenKind::Identifier(_))
+      | Some(TokenKind::Keyword(Keyword::Yield))
+      | Some(TokenKind::Keyword(Keyword::Await)) => skip + 1,
+      Some(TokenKind::Punctuator(Punctuator::OpenParen)) => {
+        match cursor.find_matching_bracket(skip)? {
+          Some(close) => close + 1,
+          None => return Ok(false),
+        }
+      }
+      _ => return Ok(false),
+    };
+
+    let is_arrow = cursor
+      .peek(arrow)?
+      .is_some_and(|tk| tk.kind() == &TokenKind::Punctuator(Punctuator::Arrow));
+    Ok(is_arrow && !cursor.is_line_terminator_before(arrow)?)
+  }
+}
+
+/// Yield expression parsing.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/yield
+/// [spec]: https://tc39.es/ecma262/#prod-YieldExpression
+#[derive(Debug, Clone, Copy)]
+struct YieldExpression {
+  allow_in: AllowIn,
+  allow_await: AllowAwait,
+}
+
+impl YieldExpression {
+  /// Creates a new `YieldExpression` parser.
+  fn new<I, A>(allow_in: I, allow_await: A) -> Self
+  where
+    I: Into<AllowIn>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_in: allow_in.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for YieldExpression
+where
+  R: Read,
+{
+  type Output = Yield;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    cursor.expect(Keyword::Yield, "yield expression")?;
+
+    // A line terminator ends the expression, `yield` then has no operand.
+    let delegate =
+      !cursor.is_line_terminator_before(0)? && cursor.next_if(Punctuator::Mul)?.is_some();
+
+    let line_terminator = cursor.is_line_terminator_before(0)?;
+    let has_operand = delegate
+      || match cursor.peek(0)? {
+        None => false,
+        Some(_) if line_terminator => false,
+        Some(tk) => !matches!(
+          tk.kind(),
+          TokenKind::Punctuator(Punctuator::CloseParen)
+            | TokenKind::Punctuator(Punctuator::CloseBracket)
+            | TokenKind::Punctuator(Punctuator::CloseBlock)
+            | TokenKind::Punctuator(Punctuator::Comma)
+            | TokenKind::Punctuator(Punctuator::Semicolon)
+            | TokenKind::Punctuator(Punctuator::Colon)
+            | TokenKind::Punctuator(Punctuator::Question)
+        ),
+      };
+
+    let expr = if has_operand {
+      Some(AssignmentExpression::new(self.allow_in, true, self.allow_await).parse(cursor)?)
+    } else {
+      None
+    };
+
+    Ok(Yield::new(expr, delegate))
+  }
+}
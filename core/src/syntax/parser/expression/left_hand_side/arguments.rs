@@ -0,0 +1,114 @@
+//! Argument parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Glossary/Argument
+//! [spec]: https://tc39.es/ecma262/#prod-Arguments
+
+use crate::syntax::{
+  ast::{
+    arena::ArenaBox,
+    node::{Node, Spread},
+    Punctuator,
+  },
+  lexer::TokenKind,
+  parser::{
+    expression::AssignmentExpression, AllowAwait, AllowYield, Cursor, ParseError, TokenParser,
+  },
+};
+use std::io::Read;
+
+/// Parses a list of arguments, including the surrounding parentheses.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Glossary/Argument
+/// [spec]: https://tc39.es/ecma262/#prod-Arguments
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Arguments {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl Arguments {
+  /// Creates a new `Arguments` parser.
+  pub(super) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for Arguments
+where
+  R: Read,
+{
+  type Output = ArenaBox<[Node]>;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    cursor.expect(Punctuator::OpenParen, "arguments")?;
+    let mut args = Vec::new();
+    loop {
+      // An unclosed list at the end of the input is closed when recovering from errors.
+      let next_token = match cursor.peek(0)? {
+        Some(token) => token,
+        None => {
+          cursor.expect(Punctuator::CloseParen, "argument list")?;
+          break;
+        }
+      };
+      match next_token.kind() {
+        TokenKind::Punctuator(Punctuator::CloseParen) => {
+          let _ = cursor.next()?.expect(") token vanished");
+          break;
+        }
+        TokenKind::Punctuator(Punctuator::Comma) if !args.is_empty() => {
+          let _ = cursor.next()?.expect(", token vanished");
+          // Trailing comma.
+          if cursor.next_if(Punctuator::CloseParen)?.is_some() {
+            break;
+          }
+          if cursor.peek(0)?.is_none() {
+            continue;
+          }
+        }
+        _ if !args.is_empty() => {
+          return Err(ParseError::expected(
+            vec![
+              TokenKind::Punctuator(Punctuator::Comma),
+              TokenKind::Punctuator(Punctuator::CloseParen),
+            ],
+            next_token.kind().clone(),
+            next_token.span(),
+            "argument list",
+          ));
+        }
+        _ => {}
+      }
+
+      if let Some(spread) = cursor.next_if(Punctuator::Spread)? {
+        let val =
+          AssignmentExpression::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
+        args.push(
+          Spread::new(val)
+            .with_span(cursor.span_from(spread.span().start()))
+            .into(),
+        );
+      } else {
+        args
+          .push(AssignmentExpression::new(true, self.allow_yield, self.allow_await).parse(cursor)?);
+      }
+    }
+
+    Ok(args.into())
+  }
+}
@@ -0,0 +1,173 @@
+//! Call expression parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Guide/Functions
+//! [spec]: https://tc39.es/ecma262/#prod-CallExpression
+
+use super::{
+  arguments::Arguments,
+  member::{private_name, IdentifierName},
+};
+use crate::syntax::{
+  ast::{
+    node::{Call, GetConstField, GetField, GetPrivateField, Node, OptionalChain, TaggedTemplate},
+    Position, Punctuator,
+  },
+  lexer::TokenKind,
+  parser::{
+    expression::{primary::TemplateLiteral, Expression},
+    typescript, AllowAwait, AllowYield, Cursor, ParseError, TokenParser,
+  },
+};
+use std::io::Read;
+
+/// Parses the calls, property accesses and optional chains following a member expression.
+///
+/// If any link of the chain is optional (`?.`), the whole chain is wrapped in an
+/// `OptionalChain` node, which is where the short-circuit ends.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Guide/Functions
+/// [spec]: https://tc39.es/ecma262/#prod-CallExpression
+#[derive(Debug)]
+pub(super) struct CallExpression {
+  start: Position,
+  first_member_expr: Node,
+  pure: bool,
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl CallExpression {
+  /// Creates a new `CallExpression` parser.
+  pub(super) fn new<Y, A>(
+    start: Position,
+    first_member_expr: Node,
+    allow_yield: Y,
+    allow_await: A,
+  ) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      start,
+      first_member_expr,
+      pure: false,
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+
+  /// Sets whether the first call of the chain follows a purity annotation.
+  pub(super) fn with_pure(mut self, pure: bool) -> Self {
+    self.pure = pure;
+    self
+  }
+}
+
+impl<R> TokenParser<R> for CallExpression
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let mut lhs = self.first_member_expr;
+    let mut optional = false;
+    let mut pure = self.pure;
+    let typescript = cursor.is_typescript();
+
+    while let Some(tok) = cursor.peek(0)? {
+      match tok.kind() {
+        TokenKind::Punctuator(Punctuator::OpenParen) => {
+          let args = Arguments::new(self.allow_yield, self.allow_await).parse(cursor)?;
+          let mut call = Call::new(lhs, args);
+          call.set_pure(pure);
+          lhs = call.into();
+        }
+        TokenKind::Punctuator(Punctuator::Dot) => {
+          let _ = cursor.next()?.expect(". token vanished");
+          lhs = match private_name(cursor)? {
+            Some(name) => GetPrivateField::new(lhs, name).into(),
+            None => GetConstField::new(lhs, IdentifierName.parse(cursor)?).into(),
+          };
+        }
+        TokenKind::Punctuator(Punctuator::OpenBracket) => {
+          let _ = cursor.next()?.expect("[ token vanished");
+          let idx = Expression::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
+          cursor.expect(Punctuator::CloseBracket, "call expression")?;
+          lhs = GetField::new(lhs, idx).into();
+        }
+        // The template would be evaluated even when the chain short-circuits.
+        TokenKind::TemplateLiteral(template) if template.is_head() && optional => {
+          return Err(ParseError::general(
+            "tagged template cannot be used in optional chain",
+            tok.span().start(),
+          ));
+        }
+        TokenKind::TemplateLiteral(template) if template.is_head() => {
+          let template =
+            TemplateLiteral::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
+          lhs = TaggedTemplate::new(lhs, template).into();
+        }
+        TokenKind::Punctuator(Punctuator::Optional) => {
+          let _ = cursor.next()?.expect("?. token vanished");
+          optional = true;
+          if typescript && cursor.next_is(Punctuator::LessThan)? {
+            typescript::skip_type_arguments(cursor)?;
+          }
+          match cursor.peek(0)?.map(|tk| tk.kind()) {
+            Some(TokenKind::Punctuator(Punctuator::OpenParen)) => {
+              let args = Arguments::new(self.allow_yield, self.allow_await).parse(cursor)?;
+              lhs = Call::new_optional(lhs, args).into();
+            }
+            Some(TokenKind::Punctuator(Punctuator::OpenBracket)) => {
+              let _ = cursor.next()?.expect("[ token vanished");
+              let idx = Expression::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
+              cursor.expect(Punctuator::CloseBracket, "optional chain")?;
+              lhs = GetField::new_optional(lhs, idx).into();
+            }
+            _ => {
+              lhs = match private_name(cursor)? {
+                Some(name) => GetPrivateField::new_optional(lhs, name).into(),
+                None => GetConstField::new_optional(lhs, IdentifierName.parse(cursor)?).into(),
+              };
+            }
+          }
+        }
+        TokenKind::Punctuator(Punctuator::LessThan) if typescript => {
+          match typescript::call_type_arguments(cursor, 0)? {
+            Some(end) => end.advance(cursor)?,
+            None => break,
+          }
+          continue;
+        }
+        // A TypeScript non-null assertion, `x!`, only leaves `x`.
+        TokenKind::Punctuator(Punctuator::Not) if typescript => {
+          if cursor.is_line_terminator_before(0)? {
+            break;
+          }
+          let _ = cursor.next()?.expect("! token vanished");
+          continue;
+        }
+        _ => break,
+      }
+      lhs.set_span(cursor.span_from(self.start));
+      pure = false;
+    }
+
+    if optional {
+      lhs = OptionalChain::new(lhs)
+        .with_span(cursor.span_from(self.start))
+        .into();
+    }
+    Ok(lhs)
+  }
+}
@@ -0,0 +1,234 @@
+//! Member expression parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Property_Accessors
+//! [spec]: https://tc39.es/ecma262/#prod-MemberExpression
+
+use super::arguments::Arguments;
+use crate::syntax::{
+  ast::{
+    arena::ArenaBox,
+    node::{GetConstField, GetField, GetPrivateField, ImportCall, New, Node, TaggedTemplate},
+    Keyword, Punctuator, Sym,
+  },
+  lexer::TokenKind,
+  parser::{
+    expression::{
+      primary::{PrimaryExpression, TemplateLiteral},
+      AssignmentExpression, Expression,
+    },
+    typescript, AllowAwait, AllowYield, Cursor, ParseError, TokenParser,
+  },
+};
+use std::io::Read;
+
+/// Parses a member expression.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Property_Accessors
+/// [spec]: https://tc39.es/ecma262/#prod-MemberExpression
+#[derive(Debug, Clone, Copy)]
+pub(super) struct MemberExpression {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl MemberExpression {
+  /// Creates a new `MemberExpression` parser.
+  pub(super) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for MemberExpression
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let start = cursor.start()?;
+    let mut lhs = if let Some(new) = cursor.next_if(Keyword::New)? {
+      let expr = self.parse(cursor)?;
+      if let Node::ImportCall(_) = expr {
+        return Err(ParseError::general(
+          "cannot use new with import()",
+          new.span().start(),
+        ));
+      }
+      let args = if cursor.next_is(Punctuator::OpenParen)? {
+        Arguments::new(self.allow_yield, self.allow_await).parse(cursor)?
+      } else {
+        ArenaBox::default()
+      };
+      let mut new_expr = New::new(expr, args).with_span(cursor.span_from(start));
+      new_expr.set_pure(cursor.is_pure_annotated(new.span().start()));
+      new_expr.into()
+    } else if let Some(tok) = cursor.next_if(Keyword::Super)? {
+      match cursor.peek(0)?.map(|tk| tk.kind()) {
+        Some(TokenKind::Punctuator(Punctuator::OpenParen)) => {
+          cursor.check_super(true, tok.span())?;
+          Node::super_ref().with_span(tok.span())
+        }
+        Some(TokenKind::Punctuator(Punctuator::Dot))
+        | Some(TokenKind::Punctuator(Punctuator::OpenBracket)) => {
+          cursor.check_super(false, tok.span())?;
+          Node::super_ref().with_span(tok.span())
+        }
+        _ => {
+          return Err(ParseError::unexpected(
+            tok.kind().clone(),
+            tok.span(),
+            "'super' keyword unexpected here",
+          ))
+        }
+      }
+    } else if cursor.next_is(Keyword::Import)? {
+      self.parse_import(cursor)?
+    } else {
+      PrimaryExpression::new(self.allow_yield, self.allow_await).parse(cursor)?
+    };
+
+    let typescript = cursor.is_typescript();
+    loop {
+      match cursor.peek(0)?.map(|tk| tk.kind()) {
+        Some(TokenKind::Punctuator(Punctuator::Dot)) => {
+          let _ = cursor.next()?.expect(". token vanished");
+          lhs = match private_name(cursor)? {
+            Some(name) => GetPrivateField::new(lhs, name).into(),
+            None => GetConstField::new(lhs, IdentifierName.parse(cursor)?).into(),
+          };
+        }
+        Some(TokenKind::Punctuator(Punctuator::OpenBracket)) => {
+          let _ = cursor.next()?.expect("[ token vanished");
+          let idx = Expression::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
+          cursor.expect(Punctuator::CloseBracket, "member expression")?;
+          lhs = GetField::new(lhs, idx).into();
+        }
+        Some(TokenKind::TemplateLiteral(template)) if template.is_head() => {
+          let template =
+            TemplateLiteral::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
+          lhs = TaggedTemplate::new(lhs, template).into();
+        }
+        // TypeScript type arguments, `f<T>(x)`, are skipped before the arguments they apply
+        // to.
+        Some(TokenKind::Punctuator(Punctuator::LessThan)) if typescript => {
+          match typescript::call_type_arguments(cursor, 0)? {
+            Some(end) => end.advance(cursor)?,
+            None => break,
+          }
+          if !cursor.next_is(Punctuator::OpenParen)? {
+            continue;
+          }
+          break;
+        }
+        _ => break,
+      }
+      lhs.set_span(cursor.span_from(start));
+    }
+
+    Ok(lhs)
+  }
+}
+
+impl MemberExpression {
+  /// Parses `import.meta` or a dynamic `import(...)` call.
+  fn parse_import<R>(self, cursor: &mut Cursor<R>) -> Result<Node, ParseError>
+  where
+    R: Read,
+  {
+    let tok = cursor.expect(Keyword::Import, "import expression")?;
+
+    if cursor.next_if(Punctuator::Dot)?.is_some() {
+      cursor.expect(TokenKind::identifier("meta"), "import.meta")?;
+      if !cursor.is_module() {
+        return Err(ParseError::general(
+          "import.meta may only appear in a module",
+          tok.span().start(),
+        ));
+      }
+      return Ok(Node::import_meta().with_span(cursor.span_from(tok.span().start())));
+    }
+
+    cursor.expect(Punctuator::OpenParen, "import call")?;
+    let specifier =
+      AssignmentExpression::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
+    let mut options = None;
+    if cursor.next_if(Punctuator::Comma)?.is_some() && !cursor.next_is(Punctuator::CloseParen)? {
+      options =
+        Some(AssignmentExpression::new(true, self.allow_yield, self.allow_await).parse(cursor)?);
+      let _ = cursor.next_if(Punctuator::Comma)?;
+    }
+    cursor.expect(Punctuator::CloseParen, "import call")?;
+
+    Ok(
+      ImportCall::new(specifier, options)
+        .with_span(cursor.span_from(tok.span().start()))
+        .into(),
+    )
+  }
+}
+
+/// Parses the name following a `.`, which can be any identifier name, including reserved
+/// words.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-IdentifierName
+#[derive(Debug, Clone, Copy)]
+pub(in crate::syntax::parser) struct IdentifierName;
+
+impl<R> TokenParser<R> for IdentifierName
+where
+  R: Read,
+{
+  type Output = Sym;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let tok = cursor.next()?.ok_or(ParseError::AbruptEnd)?;
+    match tok.kind() {
+      TokenKind::Identifier(name) => Ok(name.clone()),
+      TokenKind::Keyword(kw) => Ok(kw.as_str().into()),
+      TokenKind::BooleanLiteral(b) => Ok(b.to_string().into()),
+      TokenKind::NullLiteral => Ok("null".into()),
+      _ => Err(ParseError::expected(
+        vec![TokenKind::identifier("identifier")],
+        tok.kind().clone(),
+        tok.span(),
+        "property access",
+      )),
+    }
+  }
+}
+
+/// Consumes a private name such as `#x` if it is the next token, recording the reference so
+/// that it can be checked against the declarations of the enclosing classes.
+pub(super) fn private_name<R>(cursor: &mut Cursor<R>) -> Result<Option<Sym>, ParseError>
+where
+  R: Read,
+{
+  let (name, position) = match cursor.peek(0)? {
+    Some(tok) => match tok.kind() {
+      TokenKind::PrivateIdentifier(name) => (name.clone(), tok.span().start()),
+      _ => return Ok(None),
+    },
+    None => return Ok(None),
+  };
+  let _ = cursor.next()?.expect("private identifier vanished");
+  cursor.reference_private_name(&name, position)?;
+  Ok(Some(name))
+}
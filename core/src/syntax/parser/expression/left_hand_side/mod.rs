@@ -0,0 +1,72 @@
+//! Left hand side expression parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators#Left-hand-side_expressions
+//! [spec]: https://tc39.es/ecma262/#sec-left-hand-side-expressions
+
+mod arguments;
+mod call;
+mod member;
+
+pub(in crate::syntax::parser) use self::member::IdentifierName;
+use self::{call::CallExpression, member::MemberExpression};
+use crate::syntax::{
+  ast::{Keyword, Node},
+  lexer::TokenKind,
+  parser::{AllowAwait, AllowYield, Cursor, ParseError, TokenParser},
+};
+use std::io::Read;
+
+/// Parses a left hand side expression.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators#Left-hand-side_expressions
+/// [spec]: https://tc39.es/ecma262/#prod-LeftHandSideExpression
+#[derive(Debug, Clone, Copy)]
+pub(in crate::syntax::parser) struct LeftHandSideExpression {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl LeftHandSideExpression {
+  /// Creates a new `LeftHandSideExpression` parser.
+  pub(in crate::syntax::parser) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for LeftHandSideExpression
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let start = cursor.start()?;
+    // The annotation of `/*#__PURE__*/ new C()` belongs to the `new` expression.
+    let pure = match cursor.peek(0)? {
+      Some(tok) if tok.kind() != &TokenKind::Keyword(Keyword::New) => {
+        let start = tok.span().start();
+        cursor.is_pure_annotated(start)
+      }
+      _ => false,
+    };
+    let lhs = MemberExpression::new(self.allow_yield, self.allow_await).parse(cursor)?;
+    CallExpression::new(start, lhs, self.allow_yield, self.allow_await)
+      .with_pure(pure)
+      .parse(cursor)
+  }
+}
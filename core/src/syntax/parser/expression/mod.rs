@@ -0,0 +1,270 @@
+//! Expression parsing.
+//!
+//! More information:
+//!  - [ECMAScript specification][spec]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-ecmascript-language-expressions
+
+mod assignment;
+mod left_hand_side;
+mod primary;
+#[cfg(test)]
+mod tests;
+mod unary;
+mod update;
+
+use self::assignment::ExponentiationExpression;
+pub(super) use self::{
+  assignment::AssignmentExpression,
+  left_hand_side::{IdentifierName, LeftHandSideExpression},
+  primary::{check_accessor_parameters, FunctionExpression, Initializer, PropertyName},
+};
+use super::{typescript, AllowAwait, AllowIn, AllowYield, Cursor, ParseError, TokenParser};
+use crate::syntax::{
+  ast::{
+    node::{self, Node},
+    operator::{BinOp, CompOp, LogOp},
+    Keyword, Punctuator,
+  },
+  lexer::TokenKind,
+};
+use std::io::Read;
+
+/// Expression parsing.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Comma_Operator
+/// [spec]: https://tc39.es/ecma262/#prod-Expression
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Expression {
+  allow_in: AllowIn,
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl Expression {
+  /// Creates a new `Expression` parser.
+  pub(super) fn new<I, Y, A>(allow_in: I, allow_yield: Y, allow_await: A) -> Self
+  where
+    I: Into<AllowIn>,
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_in: allow_in.into(),
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for Expression
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Node, ParseError> {
+    let start = cursor.start()?;
+    let mut lhs =
+      AssignmentExpression::new(self.allow_in, self.allow_yield, self.allow_await).parse(cursor)?;
+    while cursor.next_if(Punctuator::Comma)?.is_some() {
+      let rhs = AssignmentExpression::new(self.allow_in, self.allow_yield, self.allow_await)
+        .parse(cursor)?;
+      lhs = node::BinOp::new(BinOp::Comma, lhs, rhs)
+        .with_span(cursor.span_from(start))
+        .into();
+    }
+    Ok(lhs)
+  }
+}
+
+/// The precedence of relational operators, such as `<`, `instanceof` and `in`.
+const RELATIONAL_PRECEDENCE: u8 = 8;
+
+/// The lowest precedence of the operands of `??`, a `BitwiseORExpression`.
+const COALESCE_OPERAND_PRECEDENCE: u8 = 4;
+
+/// Gets the binding power of a binary operator token, higher binds tighter.
+///
+/// The exponentiation operator is right associative and is handled separately by
+/// `ExponentiationExpression`.
+fn binary_precedence(kind: &TokenKind, allow_in: AllowIn) -> Option<(u8, BinOp)> {
+  let prec = match kind {
+    TokenKind::Punctuator(p) => match p {
+      Punctuator::Coalesce => 1,
+      Punctuator::BoolOr => 2,
+      Punctuator::BoolAnd => 3,
+      Punctuator::Or => 4,
+      Punctuator::Xor => 5,
+      Punctuator::And => 6,
+      Punctuator::Eq | Punctuator::NotEq | Punctuator::StrictEq | Punctuator::StrictNotEq => 7,
+      Punctuator::LessThan
+      | Punctuator::GreaterThan
+      | Punctuator::LessThanOrEq
+      | Punctuator::GreaterThanOrEq => RELATIONAL_PRECEDENCE,
+      Punctuator::LeftSh | Punctuator::RightSh | Punctuator::URightSh => 9,
+      Punctuator::Add | Punctuator::Sub => 10,
+      Punctuator::Mul | Punctuator::Div | Punctuator::Mod => 11,
+      _ => return None,
+    },
+    TokenKind::Keyword(Keyword::InstanceOf) => {
+      return Some((RELATIONAL_PRECEDENCE, BinOp::Comp(CompOp::InstanceOf)));
+    }
+    TokenKind::Keyword(Keyword::In) if allow_in.0 => {
+      return Some((RELATIONAL_PRECEDENCE, BinOp::Comp(CompOp::In)))
+    }
+    _ => return None,
+  };
+
+  match kind {
+    TokenKind::Punctuator(p) => p.as_binop().map(|op| (prec, op)),
+    _ => None,
+  }
+}
+
+/// Parses binary expressions (`ShortCircuitExpression` and everything it contains) with
+/// precedence climbing.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ShortCircuitExpression
+#[derive(Debug, Clone, Copy)]
+pub(super) struct ShortCircuitExpression {
+  allow_in: AllowIn,
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl ShortCircuitExpression {
+  /// Creates a new `ShortCircuitExpression` parser.
+  pub(super) fn new<I, Y, A>(allow_in: I, allow_yield: Y, allow_await: A) -> Self
+  where
+    I: Into<AllowIn>,
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_in: allow_in.into(),
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+
+  fn parse_binary<R>(self, cursor: &mut Cursor<R>, min_prec: u8) -> Result<Node, ParseError>
+  where
+    R: Read,
+  {
+    let start = cursor.start()?;
+    let mut lhs = match self.parse_private_in(cursor, min_prec)? {
+      Some(node) => node,
+      None => ExponentiationExpression::new(self.allow_yield, self.allow_await).parse(cursor)?,
+    };
+
+    // `??` can't be mixed with `||` or `&&` without parentheses, whichever comes first.
+    let mut coalesce = None;
+    loop {
+      // TypeScript's `x as T` and `x satisfies T` bind like relational operators, and only
+      // leave `x`.
+      if cursor.is_typescript()
+        && min_prec <= RELATIONAL_PRECEDENCE
+        && typescript::skip_as_expression(cursor)?
+      {
+        continue;
+      }
+      let tk = match cursor.peek(0)? {
+        Some(tk) => tk,
+        None => break,
+      };
+      let (prec, op) = match binary_precedence(tk.kind(), self.allow_in) {
+        Some((prec, op)) if prec >= min_prec => (prec, op),
+        _ => break,
+      };
+      let span = cursor.next()?.expect("operator token vanished").span();
+
+      let rhs = match op {
+        BinOp::Log(log) => {
+          let is_coalesce = log == LogOp::Coalesce;
+          if *coalesce.get_or_insert(is_coalesce) != is_coalesce {
+            return Err(ParseError::early(
+              "`??` cannot be mixed with `||` or `&&` without parentheses",
+              span,
+            ));
+          }
+          // The operand of `??` stops before `||` and `&&`, so that they are reported above.
+          let min_prec = if is_coalesce {
+            COALESCE_OPERAND_PRECEDENCE
+          } else {
+            prec + 1
+          };
+          self.parse_binary(cursor, min_prec)?
+        }
+        _ => self.parse_binary(cursor, prec + 1)?,
+      };
+
+      lhs = node::BinOp::new(op, lhs, rhs)
+        .with_span(cursor.span_from(start))
+        .into();
+    }
+
+    Ok(lhs)
+  }
+
+  /// Parses an ergonomic brand check such as `#x in obj`, which may only appear where a
+  /// relational expression is allowed.
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-RelationalExpression
+  fn parse_private_in<R>(
+    self,
+    cursor: &mut Cursor<R>,
+    min_prec: u8,
+  ) -> Result<Option<Node>, ParseError>
+  where
+    R: Read,
+  {
+    if !self.allow_in.0 || min_prec > RELATIONAL_PRECEDENCE {
+      return Ok(None);
+    }
+    let (name, position) = match cursor.peek(0)? {
+      Some(tok) => match tok.kind() {
+        TokenKind::PrivateIdentifier(name) => (name.clone(), tok.span().start()),
+        _ => return Ok(None),
+      },
+      None => return Ok(None),
+    };
+    if !cursor
+      .peek(1)?
+      .is_some_and(|tk| tk.kind() == &TokenKind::Keyword(Keyword::In))
+    {
+      return Ok(None);
+    }
+    let _ = cursor.next()?.expect("private identifier vanished");
+    let _ = cursor.next()?.expect("in token vanished");
+    cursor.reference_private_name(&name, position)?;
+
+    let target = self.parse_binary(cursor, RELATIONAL_PRECEDENCE + 1)?;
+    Ok(Some(
+      node::PrivateIn::new(name, target)
+        .with_span(cursor.span_from(position))
+        .into(),
+    ))
+  }
+}
+
+impl<R> TokenParser<R> for ShortCircuitExpression
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Node, ParseError> {
+    self.parse_binary(cursor, 1)
+  }
+}
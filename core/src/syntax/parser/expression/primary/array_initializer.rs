@@ -0,0 +1,100 @@
+//! Array initializer parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array
+//! [spec]: https://tc39.es/ecma262/#sec-array-initializer
+
+use crate::syntax::{
+  ast::{
+    node::{ArrayDecl, Node, Spread},
+    Punctuator, Span,
+  },
+  lexer::TokenKind,
+  parser::{
+    expression::AssignmentExpression, AllowAwait, AllowYield, Cursor, ParseError, TokenParser,
+  },
+};
+use std::io::Read;
+
+/// Parses an array literal.
+///
+/// Holes (`[1, , 2]`) are represented by `Node::Empty`.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array
+/// [spec]: https://tc39.es/ecma262/#prod-ArrayLiteral
+#[derive(Debug, Clone, Copy)]
+pub(super) struct ArrayLiteral {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl ArrayLiteral {
+  /// Creates a new `ArrayLiteral` parser.
+  pub(super) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for ArrayLiteral
+where
+  R: Read,
+{
+  type Output = ArrayDecl;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    cursor.expect(Punctuator::OpenBracket, "array literal")?;
+    let mut elements = Vec::new();
+
+    loop {
+      let start = cursor.start()?;
+      match cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.kind() {
+        TokenKind::Punctuator(Punctuator::CloseBracket) => {
+          let _ = cursor.next()?.expect("] token vanished");
+          break;
+        }
+        TokenKind::Punctuator(Punctuator::Comma) => {
+          let _ = cursor.next()?.expect(", token vanished");
+          elements.push(Node::empty().with_span(Span::from(start)));
+          continue;
+        }
+        _ => {}
+      }
+
+      let element = if cursor.next_if(Punctuator::Spread)?.is_some() {
+        Spread::new(
+          AssignmentExpression::new(true, self.allow_yield, self.allow_await)
+            .allow_cover_initializers()
+            .parse(cursor)?,
+        )
+        .with_span(cursor.span_from(start))
+        .into()
+      } else {
+        AssignmentExpression::new(true, self.allow_yield, self.allow_await)
+          .allow_cover_initializers()
+          .parse(cursor)?
+      };
+      elements.push(element);
+
+      if cursor.next_if(Punctuator::CloseBracket)?.is_some() {
+        break;
+      }
+      cursor.expect(Punctuator::Comma, "array literal")?;
+    }
+
+    Ok(elements.into())
+  }
+}
@@ -0,0 +1,77 @@
+//! Function expression parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/function
+//! [spec]: https://tc39.es/ecma262/#prod-FunctionExpression
+
+use crate::syntax::{
+  ast::{
+    node::{FunctionExpr, FunctionKind},
+    Keyword, Punctuator,
+  },
+  lexer::TokenKind,
+  parser::{
+    function::function_parts,
+    pattern::{check_strict_name, BindingIdentifier},
+    Cursor, ParseError, TokenParser,
+  },
+};
+use std::io::Read;
+
+/// Function expression parsing, including generator and async function expressions.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/function
+/// [spec]: https://tc39.es/ecma262/#prod-FunctionExpression
+#[derive(Debug, Clone, Copy)]
+pub(in crate::syntax::parser) struct FunctionExpression;
+
+impl<R> TokenParser<R> for FunctionExpression
+where
+  R: Read,
+{
+  type Output = FunctionExpr;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let start = cursor.start()?;
+    let is_async = cursor.next_if(TokenKind::identifier("async"))?.is_some();
+    cursor.expect(Keyword::Function, "function expression")?;
+    let is_generator = cursor.next_if(Punctuator::Mul)?.is_some();
+    let kind = FunctionKind::new(is_async, is_generator);
+
+    // The name is only bound inside the function itself, so it follows the function's own
+    // context rather than the enclosing one, and it is not declared in the enclosing scope.
+    let mark = cursor.bindings_mark();
+    let span = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.span();
+    let unnamed = cursor.next_is(Punctuator::OpenParen)?
+      || (cursor.is_typescript() && cursor.next_is(Punctuator::LessThan)?);
+    let name = if unnamed {
+      None
+    } else {
+      Some(BindingIdentifier::new(is_generator, is_async).parse(cursor)?)
+    };
+    cursor.discard_bindings(mark);
+    let (params, body) = function_parts(cursor, kind, "function expression")?;
+
+    let name_span = name.as_ref().map(|_| span);
+    // A directive in the body also applies to the name.
+    if let Some(name) = &name {
+      if body.strict() {
+        check_strict_name(name, span, true)?;
+      }
+    }
+
+    Ok(
+      FunctionExpr::new(name, params, body)
+        .with_kind(kind)
+        .with_span(cursor.span_from(start))
+        .with_name_span(name_span),
+    )
+  }
+}
@@ -0,0 +1,165 @@
+//! Primary expression parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators#Primary_expressions
+//! [spec]: https://tc39.es/ecma262/#prod-PrimaryExpression
+
+mod array_initializer;
+mod function_expression;
+mod object_initializer;
+mod template;
+
+use self::{array_initializer::ArrayLiteral, object_initializer::ObjectLiteral};
+pub(in crate::syntax::parser) use self::{
+  function_expression::FunctionExpression,
+  object_initializer::{check_accessor_parameters, Initializer, PropertyName},
+  template::TemplateLiteral,
+};
+use super::Expression;
+use crate::syntax::{
+  ast::{
+    node::{Identifier, Node},
+    Const, Keyword, Punctuator,
+  },
+  lexer::{token::Numeric, TokenKind},
+  parser::{
+    class::ClassExpression,
+    pattern::{check_strict_identifier, check_yield_await},
+    statement::is_async_function,
+    AllowAwait, AllowYield, Cursor, ParseError, TokenParser,
+  },
+};
+use std::io::Read;
+
+/// Parses a primary expression.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators#Primary_expressions
+/// [spec]: https://tc39.es/ecma262/#prod-PrimaryExpression
+#[derive(Debug, Clone, Copy)]
+pub(in crate::syntax::parser) struct PrimaryExpression {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl PrimaryExpression {
+  /// Creates a new `PrimaryExpression` parser.
+  pub(in crate::syntax::parser) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for PrimaryExpression
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let start = cursor.start()?;
+    if is_async_function(cursor)? {
+      let function = FunctionExpression.parse(cursor)?;
+      return Ok(function.with_span(cursor.span_from(start)).into());
+    }
+
+    let recovering = cursor.is_recovering();
+    let tok = match cursor.peek(0)? {
+      Some(tok) => tok,
+      None => {
+        cursor.report(ParseError::AbruptEnd)?;
+        return Ok(Node::error().with_span(cursor.span_from(start)));
+      }
+    };
+
+    // A parenthesized expression keeps its own span, the parentheses are part of the span of
+    // the enclosing node.
+    let node = match tok.kind() {
+      TokenKind::Keyword(Keyword::Function) => FunctionExpression.parse(cursor).map(Node::from),
+      TokenKind::Keyword(Keyword::Class) => {
+        ClassExpression::new(self.allow_yield, self.allow_await).parse(cursor)
+      }
+      TokenKind::Punctuator(Punctuator::OpenBracket) => {
+        ArrayLiteral::new(self.allow_yield, self.allow_await)
+          .parse(cursor)
+          .map(Node::from)
+      }
+      TokenKind::Punctuator(Punctuator::OpenBlock) => {
+        ObjectLiteral::new(self.allow_yield, self.allow_await)
+          .parse(cursor)
+          .map(Node::from)
+      }
+      TokenKind::TemplateLiteral(_) => {
+        TemplateLiteral::new(false, self.allow_yield, self.allow_await)
+          .parse(cursor)
+          .map(Node::from)
+      }
+      TokenKind::Punctuator(Punctuator::OpenParen) => {
+        let _ = cursor.next()?.expect("( token vanished");
+        let expr = Expression::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
+        cursor.expect(Punctuator::CloseParen, "primary expression")?;
+        return Ok(expr);
+      }
+      TokenKind::Punctuator(Punctuator::Div) | TokenKind::Punctuator(Punctuator::AssignDiv) => {
+        Err(ParseError::unexpected(
+          tok.kind().clone(),
+          tok.span(),
+          "regular expression literals are not supported yet",
+        ))
+      }
+      // When recovering, a missing operand is an error node, leaving the token that ends the
+      // expression to the caller.
+      TokenKind::Punctuator(Punctuator::CloseParen)
+      | TokenKind::Punctuator(Punctuator::CloseBracket)
+      | TokenKind::Punctuator(Punctuator::CloseBlock)
+      | TokenKind::Punctuator(Punctuator::Semicolon)
+      | TokenKind::Punctuator(Punctuator::Comma)
+      | TokenKind::Punctuator(Punctuator::Colon)
+        if recovering =>
+      {
+        let error = ParseError::unexpected(tok.kind().clone(), tok.span(), "primary expression");
+        cursor.report(error)?;
+        Ok(Node::error())
+      }
+      _ => {
+        let tok = cursor.next()?.expect("token vanished");
+        match tok.kind() {
+          TokenKind::Keyword(Keyword::This) => Ok(Node::this()),
+          TokenKind::Identifier(ident) => {
+            check_strict_identifier(cursor, ident, tok.span(), false)?;
+            Ok(Identifier::from(ident.clone()).into())
+          }
+          // `yield` and `await` are plain identifiers outside of generators and async functions.
+          TokenKind::Keyword(kw @ Keyword::Yield) | TokenKind::Keyword(kw @ Keyword::Await) => {
+            check_yield_await(*kw, tok.span(), self.allow_yield, self.allow_await)?;
+            check_strict_identifier(cursor, kw.as_str(), tok.span(), false)?;
+            Ok(Identifier::from(kw.as_str()).into())
+          }
+          TokenKind::BooleanLiteral(b) => Ok(Const::from(*b).into()),
+          TokenKind::NullLiteral => Ok(Const::Null.into()),
+          TokenKind::StringLiteral(s) => Ok(Const::from(s.clone()).into()),
+          TokenKind::NumericLiteral(Numeric::Integer(num)) => Ok(Const::from(*num).into()),
+          TokenKind::NumericLiteral(Numeric::Rational(num)) => Ok(Const::from(*num).into()),
+          _ => Err(ParseError::unexpected(
+            tok.kind().clone(),
+            tok.span(),
+            "primary expression",
+          )),
+        }
+      }
+    }?;
+    Ok(node.with_span(cursor.span_from(start)))
+  }
+}
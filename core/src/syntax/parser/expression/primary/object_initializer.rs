@@ -0,0 +1,418 @@
+//! Object initializer parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Object_initializer
+//! [spec]: https://tc39.es/ecma262/#sec-object-initializer
+
+use crate::syntax::{
+  ast::{
+    node::{
+      self, Assign, FunctionExpr, FunctionKind, Identifier, MethodDefinitionKind, Node, Object,
+      PropertyDefinition,
+    },
+    Keyword, Position, Punctuator, Sym,
+  },
+  lexer::{token::Numeric, TokenKind},
+  parser::{
+    expression::AssignmentExpression, function::FunctionParts, pattern::check_yield_await,
+    AllowAwait, AllowIn, AllowYield, Cursor, ParseError, SuperAccess, TokenParser,
+  },
+};
+use std::io::Read;
+
+/// Parses an object literal.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Object_initializer
+/// [spec]: https://tc39.es/ecma262/#prod-ObjectLiteral
+#[derive(Debug, Clone, Copy)]
+pub(super) struct ObjectLiteral {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl ObjectLiteral {
+  /// Creates a new `ObjectLiteral` parser.
+  pub(super) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for ObjectLiteral
+where
+  R: Read,
+{
+  type Output = Object;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    cursor.expect(Punctuator::OpenBlock, "object literal")?;
+    let mut elements = Vec::new();
+    let mut has_proto = false;
+
+    loop {
+      if cursor.next_if(Punctuator::CloseBlock)?.is_some() {
+        break;
+      }
+
+      let span = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.span();
+      let element =
+        PropertyDefinitionParser::new(self.allow_yield, self.allow_await).parse(cursor)?;
+      if let PropertyDefinition::Property(key, _, _) = &element {
+        if key.literal() == Some("__proto__") {
+          // Like shorthand initializers, this is allowed if the literal is a pattern.
+          if has_proto {
+            cursor.push_cover_error(span, "duplicate __proto__ property in object literal");
+          }
+          has_proto = true;
+        }
+      }
+      elements.push(element.with_span(cursor.span_from(span.start())));
+
+      if cursor.next_if(Punctuator::CloseBlock)?.is_some() {
+        break;
+      }
+      cursor.expect(Punctuator::Comma, "object literal")?;
+    }
+
+    Ok(Object::from(elements))
+  }
+}
+
+/// Parses a property definition.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-PropertyDefinition
+#[derive(Debug, Clone, Copy)]
+struct PropertyDefinitionParser {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl PropertyDefinitionParser {
+  /// Creates a new `PropertyDefinitionParser` parser.
+  fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for PropertyDefinitionParser
+where
+  R: Read,
+{
+  type Output = PropertyDefinition;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    if cursor.next_if(Punctuator::Spread)?.is_some() {
+      let node = AssignmentExpression::new(true, self.allow_yield, self.allow_await)
+        .allow_cover_initializers()
+        .parse(cursor)?;
+      return Ok(PropertyDefinition::spread_object(node));
+    }
+
+    let tok = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.clone();
+
+    if cursor.next_if(Punctuator::Mul)?.is_some() {
+      let name = PropertyName::new(self.allow_yield, self.allow_await).parse(cursor)?;
+      let method = MethodDefinition::new(FunctionKind::Generator).parse(cursor)?;
+      return Ok(PropertyDefinition::method_definition(
+        MethodDefinitionKind::Ordinary,
+        name,
+        method,
+      ));
+    }
+
+    // `async`, `get` and `set` only introduce methods and accessors when followed by a property
+    // name.
+    if let TokenKind::Identifier(ident) = tok.kind() {
+      let kind = match ident.as_ref() {
+        "get" => Some(MethodDefinitionKind::Get),
+        "set" => Some(MethodDefinitionKind::Set),
+        _ => None,
+      };
+      let is_modifier = is_method_modifier(cursor)?;
+
+      if ident.as_ref() == "async" && is_modifier && !cursor.is_line_terminator_before(1)? {
+        let _ = cursor.next()?.expect("async token vanished");
+        let is_generator = cursor.next_if(Punctuator::Mul)?.is_some();
+        let name = PropertyName::new(self.allow_yield, self.allow_await).parse(cursor)?;
+        let method = MethodDefinition::new(FunctionKind::new(true, is_generator)).parse(cursor)?;
+        return Ok(PropertyDefinition::method_definition(
+          MethodDefinitionKind::Ordinary,
+          name,
+          method,
+        ));
+      }
+
+      if let (Some(kind), true) = (kind, is_modifier) {
+        let _ = cursor.next()?.expect("get/set token vanished");
+        let name = PropertyName::new(self.allow_yield, self.allow_await).parse(cursor)?;
+        let method = MethodDefinition::new(FunctionKind::Ordinary).parse(cursor)?;
+        check_accessor_parameters(kind, &method, tok.span().start())?;
+        return Ok(PropertyDefinition::method_definition(kind, name, method));
+      }
+    }
+
+    let name = PropertyName::new(self.allow_yield, self.allow_await).parse(cursor)?;
+
+    let typescript = cursor.is_typescript();
+    match cursor.peek(0)?.map(|tk| tk.kind()) {
+      Some(TokenKind::Punctuator(Punctuator::Colon)) => {
+        let _ = cursor.next()?.expect(": token vanished");
+        let value = AssignmentExpression::new(true, self.allow_yield, self.allow_await)
+          .allow_cover_initializers()
+          .parse(cursor)?;
+        return Ok(PropertyDefinition::property(name, value));
+      }
+      // In TypeScript, the parameters of a generic method follow its type parameters, as in
+      // `{ f<T>(x: T) {} }`.
+      Some(TokenKind::Punctuator(p @ Punctuator::OpenParen))
+      | Some(TokenKind::Punctuator(p @ Punctuator::LessThan))
+        if *p == Punctuator::OpenParen || typescript =>
+      {
+        let method = MethodDefinition::new(FunctionKind::Ordinary).parse(cursor)?;
+        return Ok(PropertyDefinition::method_definition(
+          MethodDefinitionKind::Ordinary,
+          name,
+          method,
+        ));
+      }
+      _ => {}
+    }
+
+    // Only identifiers can be used in shorthand properties.
+    let ident = match tok.kind() {
+      TokenKind::Identifier(ident) => ident.clone(),
+      TokenKind::Keyword(kw @ Keyword::Yield) | TokenKind::Keyword(kw @ Keyword::Await) => {
+        check_yield_await(*kw, tok.span(), self.allow_yield, self.allow_await)?;
+        kw.as_str().into()
+      }
+      _ => {
+        return Err(ParseError::expected(
+          vec![
+            TokenKind::Punctuator(Punctuator::Colon),
+            TokenKind::Punctuator(Punctuator::OpenParen),
+          ],
+          tok.kind().clone(),
+          tok.span(),
+          "object literal",
+        ))
+      }
+    };
+
+    // `{ a = 1 }` is only valid if the literal turns out to be a pattern, it is recorded so that
+    // the enclosing assignment expression can report it otherwise.
+    if let Some(assign) = cursor.next_if(Punctuator::Assign)? {
+      cursor.push_cover_error(assign.span(), "invalid shorthand property initializer");
+      let init =
+        AssignmentExpression::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
+      let target = Identifier::from(ident).with_span(tok.span());
+      let assign = Assign::new(target, init).with_span(cursor.span_from(tok.span().start()));
+      return Ok(PropertyDefinition::property(name, assign));
+    }
+
+    Ok(PropertyDefinition::identifier_reference(
+      Identifier::from(ident).with_span(tok.span()),
+    ))
+  }
+}
+
+/// Returns `true` if the identifier at the cursor is a method modifier (`async`, `get` or `set`)
+/// rather than the name of the property itself.
+fn is_method_modifier<R>(cursor: &mut Cursor<R>) -> Result<bool, ParseError>
+where
+  R: Read,
+{
+  let typescript = cursor.is_typescript();
+  let is_name = match cursor.peek(1)?.map(|tk| tk.kind()) {
+    Some(TokenKind::Punctuator(Punctuator::OpenParen))
+    | Some(TokenKind::Punctuator(Punctuator::Colon))
+    | Some(TokenKind::Punctuator(Punctuator::Comma))
+    | Some(TokenKind::Punctuator(Punctuator::CloseBlock))
+    | Some(TokenKind::Punctuator(Punctuator::Assign))
+    | None => true,
+    // The type parameters of a TypeScript method, `get<T>() {}`.
+    Some(TokenKind::Punctuator(Punctuator::LessThan)) => typescript,
+    _ => false,
+  };
+  Ok(!is_name)
+}
+
+/// Checks that a getter has no parameters and a setter exactly one.
+pub(in crate::syntax::parser) fn check_accessor_parameters(
+  kind: MethodDefinitionKind,
+  method: &FunctionExpr,
+  position: Position,
+) -> Result<(), ParseError> {
+  let params = method.parameters();
+  match kind {
+    MethodDefinitionKind::Get if !params.is_empty() => Err(ParseError::general(
+      "getter functions must have no parameters",
+      position,
+    )),
+    MethodDefinitionKind::Set if params.len() != 1 || params[0].is_rest_param() => Err(
+      ParseError::general("setter functions must have exactly one parameter", position),
+    ),
+    _ => Ok(()),
+  }
+}
+
+/// Parses the parameters and body of a method, getter or setter.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-MethodDefinition
+#[derive(Debug, Clone, Copy)]
+pub(in crate::syntax::parser) struct MethodDefinition {
+  kind: FunctionKind,
+}
+
+impl MethodDefinition {
+  /// Creates a new `MethodDefinition` parser for a method of the given kind.
+  pub(in crate::syntax::parser) fn new(kind: FunctionKind) -> Self {
+    Self { kind }
+  }
+}
+
+impl<R> TokenParser<R> for MethodDefinition
+where
+  R: Read,
+{
+  type Output = FunctionExpr;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let start = cursor.start()?;
+    let (params, body) = FunctionParts::new(self.kind, "method definition")
+      .with_super(SuperAccess::Property)
+      .parse(cursor)?;
+    let body = body.expect("a method body is required");
+    Ok(
+      FunctionExpr::new(None, params, body)
+        .with_kind(self.kind)
+        .with_span(cursor.span_from(start)),
+    )
+  }
+}
+
+/// Parses a property name.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-PropertyName
+#[derive(Debug, Clone, Copy)]
+pub(in crate::syntax::parser) struct PropertyName {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl PropertyName {
+  /// Creates a new `PropertyName` parser.
+  pub(in crate::syntax::parser) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for PropertyName
+where
+  R: Read,
+{
+  type Output = node::PropertyName;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let tok = cursor.next()?.ok_or(ParseError::AbruptEnd)?;
+    let name: Sym = match tok.kind() {
+      TokenKind::Punctuator(Punctuator::OpenBracket) => {
+        let node =
+          AssignmentExpression::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
+        cursor.expect(Punctuator::CloseBracket, "computed property name")?;
+        return Ok(node.into());
+      }
+      TokenKind::Identifier(name) => name.clone(),
+      TokenKind::StringLiteral(name) => name.as_ref().into(),
+      TokenKind::Keyword(kw) => kw.as_str().into(),
+      TokenKind::BooleanLiteral(b) => b.to_string().into(),
+      TokenKind::NullLiteral => "null".into(),
+      TokenKind::NumericLiteral(Numeric::Integer(num)) => num.to_string().into(),
+      TokenKind::NumericLiteral(Numeric::Rational(num)) => num.to_string().into(),
+      _ => {
+        return Err(ParseError::unexpected(
+          tok.kind().clone(),
+          tok.span(),
+          "expected a property name",
+        ))
+      }
+    };
+    Ok(name.into())
+  }
+}
+
+/// Initializer parsing.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-Initializer
+#[derive(Debug, Clone, Copy)]
+pub(in crate::syntax::parser) struct Initializer {
+  allow_in: AllowIn,
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl Initializer {
+  /// Creates a new `Initializer` parser.
+  pub(in crate::syntax::parser) fn new<I, Y, A>(allow_in: I, allow_yield: Y, allow_await: A) -> Self
+  where
+    I: Into<AllowIn>,
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_in: allow_in.into(),
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for Initializer
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    cursor.expect(Punctuator::Assign, "initializer")?;
+    AssignmentExpression::new(self.allow_in, self.allow_yield, self.allow_await).parse(cursor)
+  }
+}
@@ -0,0 +1,89 @@
+//! Template literal parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Template_literals
+//! [spec]: https://tc39.es/ecma262/#sec-template-literals
+
+use crate::syntax::{
+  ast::node::{TemplateElement, TemplateLit},
+  lexer::TokenKind,
+  parser::{expression::Expression, AllowAwait, AllowYield, Cursor, ParseError, TokenParser},
+};
+use std::io::Read;
+
+/// Parses a template literal, the template of a tagged template if `tagged`.
+///
+/// The lexer splits the template at its substitutions, so the parts after the first one start
+/// with the `}` closing the previous substitution. Invalid escape sequences are only allowed in
+/// tagged templates, whose strings then have no cooked value.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Template_literals
+/// [spec]: https://tc39.es/ecma262/#prod-TemplateLiteral
+#[derive(Debug, Clone, Copy)]
+pub(in crate::syntax::parser) struct TemplateLiteral {
+  tagged: bool,
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl TemplateLiteral {
+  /// Creates a new `TemplateLiteral` parser.
+  pub(in crate::syntax::parser) fn new<Y, A>(tagged: bool, allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      tagged,
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for TemplateLiteral
+where
+  R: Read,
+{
+  type Output = TemplateLit;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let start = cursor.start()?;
+    let mut quasis = Vec::new();
+    let mut exprs = Vec::new();
+
+    loop {
+      let tok = cursor.next()?.ok_or(ParseError::AbruptEnd)?;
+      let part = match tok.kind() {
+        TokenKind::TemplateLiteral(part) if part.is_head() == quasis.is_empty() => part,
+        _ => {
+          return Err(ParseError::unexpected(
+            tok.kind().clone(),
+            tok.span(),
+            "template literal",
+          ))
+        }
+      };
+      if part.cooked().is_none() && !self.tagged {
+        cursor.report(ParseError::general(
+          "invalid escape sequence in template literal",
+          tok.span().start(),
+        ))?;
+      }
+      quasis.push(TemplateElement::new(part.cooked(), part.raw()));
+      if part.is_tail() {
+        break;
+      }
+      exprs.push(Expression::new(true, self.allow_yield, self.allow_await).parse(cursor)?);
+    }
+
+    Ok(TemplateLit::new(quasis, exprs).with_span(cursor.span_from(start)))
+  }
+}
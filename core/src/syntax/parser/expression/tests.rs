@@ -0,0 +1,271 @@
+use crate::syntax::{
+  ast::{
+    node::{
+      ArrayDecl, ArrowFunctionDecl, Assign, BinOp, Call, ConditionalOp, Declaration,
+      FormalParameter, GetConstField, GetField, Identifier, New, Node, Object, OptionalChain,
+      PropertyDefinition, Return, Spread, TaggedTemplate, TemplateElement, TemplateLit, UnaryOp,
+    },
+    operator::{
+      AssignOp, BinOp as BinOperator, BitOp, CompOp, LogOp, NumOp, UnaryOp as UnaryOperator,
+    },
+    Const,
+  },
+  parser::tests::{check_invalid, check_parser},
+};
+
+#[test]
+fn binary_precedence() {
+  check_parser(
+    "a + b * c",
+    vec![BinOp::new(
+      NumOp::Add,
+      Identifier::from("a"),
+      BinOp::new(NumOp::Mul, Identifier::from("b"), Identifier::from("c")),
+    )
+    .into()],
+  );
+  check_parser(
+    "a - b - c",
+    vec![BinOp::new(
+      NumOp::Sub,
+      BinOp::new(NumOp::Sub, Identifier::from("a"), Identifier::from("b")),
+      Identifier::from("c"),
+    )
+    .into()],
+  );
+}
+
+#[test]
+fn exponentiation_is_right_associative() {
+  check_parser(
+    "a ** b ** c",
+    vec![BinOp::new(
+      NumOp::Exp,
+      Identifier::from("a"),
+      BinOp::new(NumOp::Exp, Identifier::from("b"), Identifier::from("c")),
+    )
+    .into()],
+  );
+  check_invalid("-a ** b");
+}
+
+#[test]
+fn logical_and_coalesce() {
+  check_parser(
+    "a ?? (b || c < d)",
+    vec![BinOp::new(
+      LogOp::Coalesce,
+      Identifier::from("a"),
+      BinOp::new(
+        LogOp::Or,
+        Identifier::from("b"),
+        BinOp::new(
+          CompOp::LessThan,
+          Identifier::from("c"),
+          Identifier::from("d"),
+        ),
+      ),
+    )
+    .into()],
+  );
+  check_parser(
+    "a ?? b ?? c | d",
+    vec![BinOp::new(
+      LogOp::Coalesce,
+      BinOp::new(
+        LogOp::Coalesce,
+        Identifier::from("a"),
+        Identifier::from("b"),
+      ),
+      BinOp::new(BitOp::Or, Identifier::from("c"), Identifier::from("d")),
+    )
+    .into()],
+  );
+  check_invalid("a ?? b || c");
+  check_invalid("a || b ?? c");
+  check_invalid("a ?? b && c");
+  check_invalid("a && b ?? c");
+  check_invalid("a || b && c ?? d");
+}
+
+#[test]
+fn conditional_and_compound_assignment() {
+  check_parser(
+    "a ? b : c",
+    vec![ConditionalOp::new(
+      Identifier::from("a"),
+      Identifier::from("b"),
+      Identifier::from("c"),
+    )
+    .into()],
+  );
+  check_parser(
+    "a ??= b",
+    vec![BinOp::new(
+      BinOperator::Assign(AssignOp::Coalesce),
+      Identifier::from("a"),
+      Identifier::from("b"),
+    )
+    .into()],
+  );
+  check_invalid("a + b += c");
+}
+
+#[test]
+fn update_expressions() {
+  check_parser(
+    "a++; --b",
+    vec![
+      UnaryOp::new(UnaryOperator::IncrementPost, Identifier::from("a")).into(),
+      UnaryOp::new(UnaryOperator::DecrementPre, Identifier::from("b")).into(),
+    ],
+  );
+  check_invalid("1++");
+}
+
+#[test]
+fn member_and_call() {
+  check_parser(
+    "new a.b(c)[d]",
+    vec![GetField::new(
+      New::new(
+        GetConstField::new(Identifier::from("a"), "b"),
+        vec![Identifier::from("c").into()],
+      ),
+      Identifier::from("d"),
+    )
+    .into()],
+  );
+  check_parser(
+    "f(...a, b,)",
+    vec![Call::new(
+      Identifier::from("f"),
+      vec![
+        Spread::new(Identifier::from("a")).into(),
+        Identifier::from("b").into(),
+      ],
+    )
+    .into()],
+  );
+}
+
+#[test]
+fn optional_chain() {
+  check_parser(
+    "a?.b.c",
+    vec![OptionalChain::new(GetConstField::new(
+      GetConstField::new_optional(Identifier::from("a"), "b"),
+      "c",
+    ))
+    .into()],
+  );
+  check_invalid("a?.b = 1");
+}
+
+#[test]
+fn array_and_object_literals() {
+  check_parser(
+    "[1, , ...a]",
+    vec![ArrayDecl::from(vec![
+      Const::from(1).into(),
+      Node::empty(),
+      Spread::new(Identifier::from("a")).into(),
+    ])
+    .into()],
+  );
+  check_parser(
+    "({ a, b: 1, ...c })",
+    vec![Object::from(vec![
+      PropertyDefinition::identifier_reference("a"),
+      PropertyDefinition::property("b", Const::from(1)),
+      PropertyDefinition::spread_object(Identifier::from("c")),
+    ])
+    .into()],
+  );
+}
+
+#[test]
+fn shorthand_initializer_outside_pattern() {
+  check_invalid("({ a = 1 })");
+  check_invalid("[{ a = 1 }]");
+}
+
+#[test]
+fn arrow_functions() {
+  check_parser(
+    "a => a",
+    vec![ArrowFunctionDecl::new(
+      vec![FormalParameter::new(
+        Declaration::new(Identifier::from("a"), None),
+        false,
+      )],
+      vec![Return::new(Some(Identifier::from("a"))).into()],
+    )
+    .into()],
+  );
+  check_parser(
+    "(a, ...b) => {}",
+    vec![ArrowFunctionDecl::new(
+      vec![
+        FormalParameter::new(Declaration::new(Identifier::from("a"), None), false),
+        FormalParameter::new(Declaration::new(Identifier::from("b"), None), true),
+      ],
+      vec![],
+    )
+    .into()],
+  );
+  check_invalid("(a)\n=> a");
+}
+
+#[test]
+fn plain_assignment() {
+  check_parser(
+    "a = b = 1",
+    vec![Assign::new(
+      Identifier::from("a"),
+      Assign::new(Identifier::from("b"), Const::from(1)),
+    )
+    .into()],
+  );
+}
+
+#[test]
+fn template_literals() {
+  check_parser(
+    "`a${b}c${d + 1}`",
+    vec![TemplateLit::new(
+      vec![
+        TemplateElement::new(Some("a"), "a"),
+        TemplateElement::new(Some("c"), "c"),
+        TemplateElement::new(Some(""), ""),
+      ],
+      vec![
+        Identifier::from("b").into(),
+        BinOp::new(NumOp::Add, Identifier::from("d"), Const::from(1)).into(),
+      ],
+    )
+    .into()],
+  );
+  check_parser(
+    "a.b`\\unicode`",
+    vec![TaggedTemplate::new(
+      GetConstField::new(Identifier::from("a"), "b"),
+      TemplateLit::new(
+        vec![TemplateElement::new(None::<&str>, "\\unicode")],
+        vec![],
+      ),
+    )
+    .into()],
+  );
+  check_parser(
+    "f()`x`",
+    vec![TaggedTemplate::new(
+      Call::new(Identifier::from("f"), vec![]),
+      TemplateLit::new(vec![TemplateElement::new(Some("x"), "x")], vec![]),
+    )
+    .into()],
+  );
+  check_invalid("`\\unicode`");
+  check_invalid("a?.b`x`");
+  check_invalid("`a${b`");
+}
@@ -0,0 +1,99 @@
+//! Unary operator parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Expressions_and_Operators#Unary
+//! [spec]: https://tc39.es/ecma262/#sec-unary-operators
+
+use super::update::UpdateExpression;
+use crate::syntax::{
+  ast::{
+    node::{self, Await, Node},
+    operator::UnaryOp,
+    Keyword, Punctuator,
+  },
+  lexer::TokenKind,
+  parser::{typescript, AllowAwait, AllowYield, Cursor, ParseError, TokenParser},
+};
+use std::io::Read;
+
+/// Parses a unary expression.
+///
+/// Inside async functions and modules this includes `await` expressions.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Expressions_and_Operators#Unary
+/// [spec]: https://tc39.es/ecma262/#prod-UnaryExpression
+#[derive(Debug, Clone, Copy)]
+pub(in crate::syntax::parser) struct UnaryExpression {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl UnaryExpression {
+  /// Creates a new `UnaryExpression` parser.
+  pub(in crate::syntax::parser) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for UnaryExpression
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let start = cursor.start()?;
+    if self.allow_await.0 && cursor.next_if(Keyword::Await)?.is_some() {
+      let target = self.parse(cursor)?;
+      return Ok(Await::new(target).with_span(cursor.span_from(start)).into());
+    }
+
+    // A TypeScript type assertion, `<T>x`, only leaves `x`.
+    if cursor.is_typescript() && cursor.next_if(Punctuator::LessThan)?.is_some() {
+      typescript::skip_type(cursor)?;
+      cursor.next_greater_than()?;
+      return self.parse(cursor);
+    }
+
+    let op = match cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.kind() {
+      TokenKind::Keyword(Keyword::Delete) => UnaryOp::Delete,
+      TokenKind::Keyword(Keyword::Void) => UnaryOp::Void,
+      TokenKind::Keyword(Keyword::TypeOf) => UnaryOp::TypeOf,
+      TokenKind::Punctuator(Punctuator::Add) => UnaryOp::Plus,
+      TokenKind::Punctuator(Punctuator::Sub) => UnaryOp::Minus,
+      TokenKind::Punctuator(Punctuator::Neg) => UnaryOp::Tilde,
+      TokenKind::Punctuator(Punctuator::Not) => UnaryOp::Not,
+      _ => return UpdateExpression::new(self.allow_yield, self.allow_await).parse(cursor),
+    };
+    let tok = cursor.next()?.expect("unary operator token vanished");
+
+    let target = self.parse(cursor)?;
+    if op == UnaryOp::Delete && cursor.is_strict() {
+      if let Node::Identifier(_) = target {
+        return Err(ParseError::early(
+          "delete of an unqualified identifier in strict mode code",
+          tok.span(),
+        ));
+      }
+    }
+    Ok(
+      node::UnaryOp::new(op, target)
+        .with_span(cursor.span_from(start))
+        .into(),
+    )
+  }
+}
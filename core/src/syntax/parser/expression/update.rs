@@ -0,0 +1,116 @@
+//! Update expression parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Arithmetic_Operators#Increment
+//! [spec]: https://tc39.es/ecma262/#sec-update-expressions
+
+use super::{left_hand_side::LeftHandSideExpression, unary::UnaryExpression};
+use crate::syntax::{
+  ast::{
+    node::{self, Node},
+    operator::UnaryOp,
+    Punctuator,
+  },
+  lexer::TokenKind,
+  parser::{
+    pattern::{check_strict_assignment_target, is_simple_assignment_target},
+    AllowAwait, AllowYield, Cursor, ParseError, TokenParser,
+  },
+};
+use std::io::Read;
+
+/// Parses an update expression.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Arithmetic_Operators#Increment
+/// [spec]: https://tc39.es/ecma262/#prod-UpdateExpression
+#[derive(Debug, Clone, Copy)]
+pub(in crate::syntax::parser) struct UpdateExpression {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl UpdateExpression {
+  /// Creates a new `UpdateExpression` parser.
+  pub(in crate::syntax::parser) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for UpdateExpression
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let tok = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?;
+    let span = tok.span();
+    let start = span.start();
+    let prefix = match tok.kind() {
+      TokenKind::Punctuator(Punctuator::Inc) => Some(UnaryOp::IncrementPre),
+      TokenKind::Punctuator(Punctuator::Dec) => Some(UnaryOp::DecrementPre),
+      _ => None,
+    };
+
+    if let Some(op) = prefix {
+      let _ = cursor.next()?.expect("update operator token vanished");
+      let target_span = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.span();
+      let target = UnaryExpression::new(self.allow_yield, self.allow_await).parse(cursor)?;
+      if !is_simple_assignment_target(&target) {
+        return Err(ParseError::general(
+          "invalid left-hand side expression in prefix operation",
+          start,
+        ));
+      }
+      check_strict_assignment_target(cursor, &target, target_span)?;
+      return Ok(
+        node::UnaryOp::new(op, target)
+          .with_span(cursor.span_from(start))
+          .into(),
+      );
+    }
+
+    let lhs = LeftHandSideExpression::new(self.allow_yield, self.allow_await).parse(cursor)?;
+
+    // A line terminator before `++` or `--` ends the expression, the operator then starts the
+    // next statement.
+    if !cursor.is_line_terminator_before(0)? {
+      let postfix = match cursor.peek(0)?.map(|tk| tk.kind()) {
+        Some(TokenKind::Punctuator(Punctuator::Inc)) => Some(UnaryOp::IncrementPost),
+        Some(TokenKind::Punctuator(Punctuator::Dec)) => Some(UnaryOp::DecrementPost),
+        _ => None,
+      };
+      if let Some(op) = postfix {
+        let _ = cursor.next()?.expect("update operator token vanished");
+        if !is_simple_assignment_target(&lhs) {
+          return Err(ParseError::general(
+            "invalid left-hand side expression in postfix operation",
+            start,
+          ));
+        }
+        check_strict_assignment_target(cursor, &lhs, span)?;
+        return Ok(
+          node::UnaryOp::new(op, lhs)
+            .with_span(cursor.span_from(start))
+            .into(),
+        );
+      }
+    }
+
+    Ok(lhs)
+  }
+}
@@ -0,0 +1,364 @@
+//! Function definition parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/function
+//! [spec]: https://tc39.es/ecma262/#sec-function-definitions
+
+#[cfg(test)]
+mod tests;
+
+use crate::syntax::{
+  ast::{
+    arena::ArenaBox,
+    node::{Declaration, FormalParameter, FunctionKind, Pattern, PatternElement, StatementList},
+    Punctuator, Span,
+  },
+  lexer::TokenKind,
+  parser::{
+    expression::Initializer,
+    pattern::{BindingElement, BindingTarget},
+    statement, typescript, AllowAwait, AllowYield, Cursor, ParseError, ScopeKind, SuperAccess,
+    TokenParser,
+  },
+};
+use std::io::Read;
+
+/// Formal parameters parsing.
+///
+/// Parses everything between the parentheses of a parameter list, the closing parenthesis is
+/// left for the caller.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Glossary/Parameter
+/// [spec]: https://tc39.es/ecma262/#prod-FormalParameters
+#[derive(Debug, Clone, Copy)]
+pub(in crate::syntax::parser) struct FormalParameters {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+  allow_parameter_properties: bool,
+}
+
+impl FormalParameters {
+  /// Creates a new `FormalParameters` parser.
+  pub(in crate::syntax::parser) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+      allow_parameter_properties: false,
+    }
+  }
+
+  /// Allows TypeScript parameter properties, such as `private x` in the parameters of a class
+  /// constructor. Their names are recorded on the cursor.
+  pub(in crate::syntax::parser) fn with_parameter_properties(mut self, allow: bool) -> Self {
+    self.allow_parameter_properties = allow;
+    self
+  }
+}
+
+impl<R> TokenParser<R> for FormalParameters
+where
+  R: Read,
+{
+  type Output = ArenaBox<[FormalParameter]>;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let mut params = Vec::new();
+
+    while !cursor.next_is(Punctuator::CloseParen)? {
+      let start = cursor.start()?;
+      if cursor.is_typescript() && typescript::is_this_parameter(cursor)? {
+        let _ = cursor.next()?.expect("this token vanished");
+        typescript::type_annotation(cursor)?;
+        if !cursor.next_is(Punctuator::CloseParen)? {
+          cursor.expect(Punctuator::Comma, "parameter list")?;
+        }
+        continue;
+      }
+      let modifier = if cursor.is_typescript() {
+        typescript::parameter_modifiers(cursor)?
+      } else {
+        None
+      };
+
+      if let Some(spread) = cursor.next_if(Punctuator::Spread)? {
+        if let Some(span) = modifier {
+          return Err(ParseError::general(
+            "a parameter property can not be a rest parameter",
+            span.start(),
+          ));
+        }
+        let element = self.element(cursor)?;
+        if element.default().is_some() {
+          return Err(ParseError::general(
+            "rest parameter may not have a default initializer",
+            spread.span().start(),
+          ));
+        }
+        let span = cursor.span_from(start);
+        params.push(
+          FormalParameter::new(
+            Declaration::new(element.target().clone(), None).with_span(span),
+            true,
+          )
+          .with_span(span),
+        );
+
+        // The rest parameter must be the last one, without a trailing comma.
+        let next = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?;
+        if next.kind() != &TokenKind::Punctuator(Punctuator::CloseParen) {
+          return Err(ParseError::unexpected(
+            next.kind().clone(),
+            next.span(),
+            "rest parameter must be last formal parameter",
+          ));
+        }
+        break;
+      }
+
+      let element = self.element(cursor)?;
+      if let Some(span) = modifier {
+        self.parameter_property(cursor, &element, span)?;
+      }
+      let span = cursor.span_from(start);
+      params.push(
+        FormalParameter::new(
+          Declaration::new(element.target().clone(), element.default().cloned()).with_span(span),
+          false,
+        )
+        .with_span(span),
+      );
+
+      if !cursor.next_is(Punctuator::CloseParen)? {
+        cursor.expect(Punctuator::Comma, "parameter list")?;
+      }
+    }
+
+    Ok(params.into())
+  }
+}
+
+impl FormalParameters {
+  /// Parses a single parameter, which in TypeScript can be optional and have a type, as in
+  /// `x?: number = 1`.
+  fn element<R>(self, cursor: &mut Cursor<R>) -> Result<PatternElement, ParseError>
+  where
+    R: Read,
+  {
+    if !cursor.is_typescript() {
+      return BindingElement::new(self.allow_yield, self.allow_await).parse(cursor);
+    }
+    let target = BindingTarget::new(self.allow_yield, self.allow_await).parse(cursor)?;
+    let _ = cursor.next_if(Punctuator::Question)?;
+    typescript::type_annotation(cursor)?;
+    let default = if cursor.next_is(Punctuator::Assign)? {
+      Some(Initializer::new(true, self.allow_yield, self.allow_await).parse(cursor)?)
+    } else {
+      None
+    };
+    Ok(PatternElement::new(target, default))
+  }
+
+  /// Records a parameter declared with a modifier such as `private` as a parameter property.
+  fn parameter_property<R>(
+    self,
+    cursor: &mut Cursor<R>,
+    element: &PatternElement,
+    span: Span,
+  ) -> Result<(), ParseError>
+  where
+    R: Read,
+  {
+    if !self.allow_parameter_properties {
+      return Err(ParseError::general(
+        "parameter properties are only allowed in a constructor implementation",
+        span.start(),
+      ));
+    }
+    match element.target() {
+      Pattern::Identifier(ident) => {
+        cursor.record_parameter_property(ident.name());
+        Ok(())
+      }
+      _ => Err(ParseError::general(
+        "a parameter property can not be declared with a binding pattern",
+        span.start(),
+      )),
+    }
+  }
+}
+
+/// Function body parsing.
+///
+/// Parses the statements between the braces of a function, the braces themselves are left for
+/// the caller.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/function
+/// [spec]: https://tc39.es/ecma262/#prod-FunctionBody
+#[derive(Debug, Clone, Copy)]
+pub(in crate::syntax::parser) struct FunctionBody {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl FunctionBody {
+  /// Creates a new `FunctionBody` parser.
+  pub(in crate::syntax::parser) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for FunctionBody
+where
+  R: Read,
+{
+  type Output = StatementList;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    statement::StatementList::new(self.allow_yield, self.allow_await, true, true)
+      .with_directives()
+      .parse(cursor)
+  }
+}
+
+/// Returns `true` if the parameters are plain identifiers, without defaults or a rest parameter.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-static-semantics-issimpleparameterlist
+pub(in crate::syntax::parser) fn is_simple_parameter_list(params: &[FormalParameter]) -> bool {
+  params.iter().all(|param| {
+    !param.is_rest_param()
+      && param.init().is_none()
+      && matches!(param.target(), Pattern::Identifier(_))
+  })
+}
+
+/// Parses the parameters and body of a function of the given kind, from the opening parenthesis
+/// of the parameter list to the closing brace of the body.
+///
+/// Generators allow `yield` and async functions allow `await` in both their parameters and
+/// their body, independently of the enclosing context.
+pub(in crate::syntax::parser) fn function_parts<R>(
+  cursor: &mut Cursor<R>,
+  kind: FunctionKind,
+  context: &'static str,
+) -> Result<(ArenaBox<[FormalParameter]>, StatementList), ParseError>
+where
+  R: Read,
+{
+  let (params, body) = FunctionParts::new(kind, context).parse(cursor)?;
+  Ok((params, body.expect("a function body is required")))
+}
+
+/// Parses the parameters and body of a function, like [`function_parts`].
+///
+/// In TypeScript, the parameter list can follow type parameters and be followed by a return
+/// type. A method or an overload can also be a signature without a body, which is `None`.
+#[derive(Debug, Clone, Copy)]
+pub(in crate::syntax::parser) struct FunctionParts {
+  kind: FunctionKind,
+  context: &'static str,
+  allow_signature: bool,
+  allow_parameter_properties: bool,
+  super_access: SuperAccess,
+}
+
+impl FunctionParts {
+  /// Creates a new `FunctionParts` parser for a function of the given kind.
+  pub(in crate::syntax::parser) fn new(kind: FunctionKind, context: &'static str) -> Self {
+    Self {
+      kind,
+      context,
+      allow_signature: false,
+      allow_parameter_properties: false,
+      super_access: SuperAccess::None,
+    }
+  }
+
+  /// Allows `super` in the function, for a method or a constructor.
+  pub(in crate::syntax::parser) fn with_super(mut self, access: SuperAccess) -> Self {
+    self.super_access = access;
+    self
+  }
+
+  /// Allows a TypeScript signature without a body, such as an abstract method.
+  pub(in crate::syntax::parser) fn with_signature(mut self, allow: bool) -> Self {
+    self.allow_signature = allow;
+    self
+  }
+
+  /// Allows TypeScript parameter properties, for a class constructor.
+  pub(in crate::syntax::parser) fn with_parameter_properties(mut self, allow: bool) -> Self {
+    self.allow_parameter_properties = allow;
+    self
+  }
+}
+
+impl<R> TokenParser<R> for FunctionParts
+where
+  R: Read,
+{
+  type Output = (ArenaBox<[FormalParameter]>, Option<StatementList>);
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let (allow_yield, allow_await) = (self.kind.is_generator(), self.kind.is_async());
+    let strict = cursor.is_strict();
+    let typescript = cursor.is_typescript();
+
+    cursor.push_scope(ScopeKind::Function);
+    let super_access = cursor.set_super_access(self.super_access);
+    if typescript {
+      typescript::skip_type_parameters(cursor)?;
+    }
+    cursor.expect(Punctuator::OpenParen, self.context)?;
+    let mark = cursor.bindings_mark();
+    let params = FormalParameters::new(allow_yield, allow_await)
+      .with_parameter_properties(self.allow_parameter_properties)
+      .parse(cursor)?;
+    // Methods, the functions that can use `super`, have unique parameter names.
+    let unique = self.super_access != SuperAccess::None;
+    cursor.declare_parameters(mark, is_simple_parameter_list(&params), unique)?;
+    cursor.expect(Punctuator::CloseParen, self.context)?;
+    if typescript {
+      typescript::return_type_annotation(cursor)?;
+      if self.allow_signature && !cursor.next_is(Punctuator::OpenBlock)? {
+        cursor.expect_semicolon(self.context)?;
+        cursor.set_super_access(super_access);
+        cursor.pop_scope();
+        return Ok((params, None));
+      }
+    }
+    cursor.expect(Punctuator::OpenBlock, self.context)?;
+    let body = FunctionBody::new(allow_yield, allow_await).parse(cursor)?;
+    // A directive only applies to this function, the token after the body is not read yet.
+    cursor.set_strict(strict);
+    cursor.expect(Punctuator::CloseBlock, self.context)?;
+    cursor.set_super_access(super_access);
+    cursor.pop_scope();
+
+    Ok((params, Some(body)))
+  }
+}
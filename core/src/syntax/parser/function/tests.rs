@@ -0,0 +1,284 @@
+use crate::syntax::{
+  ast::{
+    node::{
+      ArrowFunctionDecl, Await, Block, Call, Class, ClassElement, Declaration, DeclarationList,
+      ForOfLoop, FormalParameter, FunctionDecl, FunctionExpr, FunctionKind, Identifier,
+      IterableLoopInitializer, MethodDefinitionKind, Node, Object, PropertyDefinition,
+      PropertyName, Return, Yield,
+    },
+    Const, NodeSpan, Sym,
+  },
+  parser::{
+    tests::{check_invalid, check_parser, strict_body},
+    Parser,
+  },
+};
+
+/// Creates a parameter binding the given name, without a default value.
+fn param(name: &str) -> FormalParameter {
+  FormalParameter::new(Declaration::new(Identifier::from(name), None), false)
+}
+
+/// Creates an empty method of the given kind.
+fn empty_method(kind: FunctionKind) -> FunctionExpr {
+  FunctionExpr::new(None, vec![], vec![]).with_kind(kind)
+}
+
+/// Creates an empty method of the given kind in a class body, which is strict mode code.
+fn empty_class_method(kind: FunctionKind) -> FunctionExpr {
+  FunctionExpr::new(None, vec![], strict_body(vec![])).with_kind(kind)
+}
+
+#[test]
+fn generator_declaration() {
+  check_parser(
+    "function* g() { yield 1; yield* a; yield; }",
+    vec![FunctionDecl::new(
+      Sym::from("g"),
+      vec![],
+      vec![
+        Yield::new(Some(Const::from(1)), false).into(),
+        Yield::new(Some(Identifier::from("a")), true).into(),
+        Yield::new(None::<Node>, false).into(),
+      ],
+    )
+    .with_kind(FunctionKind::Generator)
+    .into()],
+  );
+}
+
+#[test]
+fn yield_without_operand() {
+  check_parser(
+    "function* g() { f(yield); yield\na; }",
+    vec![FunctionDecl::new(
+      Sym::from("g"),
+      vec![],
+      vec![
+        Call::new(
+          Identifier::from("f"),
+          vec![Yield::new(None::<Node>, false).into()],
+        )
+        .into(),
+        Yield::new(None::<Node>, false).into(),
+        Identifier::from("a").into(),
+      ],
+    )
+    .with_kind(FunctionKind::Generator)
+    .into()],
+  );
+}
+
+#[test]
+fn async_function_declaration() {
+  check_parser(
+    "async function f(a) { return await a; }",
+    vec![FunctionDecl::new(
+      Sym::from("f"),
+      vec![param("a")],
+      vec![Return::new(Some(Await::new(Identifier::from("a")))).into()],
+    )
+    .with_kind(FunctionKind::Async)
+    .into()],
+  );
+}
+
+#[test]
+fn async_generator_expression() {
+  check_parser(
+    "(async function* g() { yield await a; });",
+    vec![FunctionExpr::new(
+      Some(Sym::from("g")),
+      vec![],
+      vec![Yield::new(Some(Await::new(Identifier::from("a"))), false).into()],
+    )
+    .with_kind(FunctionKind::AsyncGenerator)
+    .into()],
+  );
+}
+
+#[test]
+fn async_arrow_functions() {
+  check_parser(
+    "async x => await x; async (a, b) => {};",
+    vec![
+      ArrowFunctionDecl::new_async(
+        vec![param("x")],
+        vec![Return::new(Some(Await::new(Identifier::from("x")))).into()],
+      )
+      .into(),
+      ArrowFunctionDecl::new_async(vec![param("a"), param("b")], vec![]).into(),
+    ],
+  );
+}
+
+#[test]
+fn async_as_identifier() {
+  check_parser(
+    "async => async; async(x);",
+    vec![
+      ArrowFunctionDecl::new(
+        vec![param("async")],
+        vec![Return::new(Some(Identifier::from("async"))).into()],
+      )
+      .into(),
+      Call::new(
+        Identifier::from("async"),
+        vec![Identifier::from("x").into()],
+      )
+      .into(),
+    ],
+  );
+}
+
+#[test]
+fn yield_and_await_as_identifiers() {
+  check_parser(
+    "var yield = 1; function f(await) { return yield; }",
+    vec![
+      DeclarationList::Var(
+        vec![Declaration::new(
+          Identifier::from("yield"),
+          Some(Const::from(1).into()),
+        )]
+        .into(),
+      )
+      .into(),
+      FunctionDecl::new(
+        Sym::from("f"),
+        vec![param("await")],
+        vec![Return::new(Some(Identifier::from("yield"))).into()],
+      )
+      .into(),
+    ],
+  );
+}
+
+#[test]
+fn reserved_yield_and_await() {
+  check_invalid("function* g() { var yield; }");
+  check_invalid("function* g(yield) {}");
+  check_invalid("async function f() { var await; }");
+  check_invalid("async function f(await) {}");
+  check_invalid("async (await) => {};");
+  check_invalid("function* g() { function* yield() {} }");
+  check_invalid("(async function await() {});");
+}
+
+#[test]
+fn nested_functions_reset_context() {
+  check_parser(
+    "function* g() { function f() { yield; } }",
+    vec![FunctionDecl::new(
+      Sym::from("g"),
+      vec![],
+      vec![FunctionDecl::new(
+        Sym::from("f"),
+        vec![],
+        vec![Identifier::from("yield").into()],
+      )
+      .into()],
+    )
+    .with_kind(FunctionKind::Generator)
+    .into()],
+  );
+  check_invalid("async function f() { function g() { await x; } }");
+}
+
+#[test]
+fn async_and_generator_methods() {
+  check_parser(
+    "({ *a() {}, async b() {}, async *c() {}, async: 1 });",
+    vec![Object::from(vec![
+      PropertyDefinition::method_definition(
+        MethodDefinitionKind::Ordinary,
+        "a",
+        empty_method(FunctionKind::Generator),
+      ),
+      PropertyDefinition::method_definition(
+        MethodDefinitionKind::Ordinary,
+        "b",
+        empty_method(FunctionKind::Async),
+      ),
+      PropertyDefinition::method_definition(
+        MethodDefinitionKind::Ordinary,
+        "c",
+        empty_method(FunctionKind::AsyncGenerator),
+      ),
+      PropertyDefinition::property("async", Const::from(1)),
+    ])
+    .into()],
+  );
+}
+
+#[test]
+fn async_and_generator_class_methods() {
+  check_parser(
+    "class A { static async a() {} *b() {} async() {} }",
+    vec![Node::ClassDecl(Class::new::<_, Node, _>(
+      Sym::from("A"),
+      None,
+      None,
+      vec![
+        ClassElement::Method {
+          name: PropertyName::from("a").into(),
+          kind: MethodDefinitionKind::Ordinary,
+          function: empty_class_method(FunctionKind::Async),
+          is_static: true,
+          span: NodeSpan::default(),
+        },
+        ClassElement::Method {
+          name: PropertyName::from("b").into(),
+          kind: MethodDefinitionKind::Ordinary,
+          function: empty_class_method(FunctionKind::Generator),
+          is_static: false,
+          span: NodeSpan::default(),
+        },
+        ClassElement::Method {
+          name: PropertyName::from("async").into(),
+          kind: MethodDefinitionKind::Ordinary,
+          function: empty_class_method(FunctionKind::Ordinary),
+          is_static: false,
+          span: NodeSpan::default(),
+        },
+      ],
+    ))],
+  );
+  check_invalid("class A { async constructor() {} }");
+  check_invalid("class A { *constructor() {} }");
+}
+
+#[test]
+fn for_await_of() {
+  check_parser(
+    "async function f() { for await (const x of xs) {} }",
+    vec![FunctionDecl::new(
+      Sym::from("f"),
+      vec![],
+      vec![ForOfLoop::new_await(
+        IterableLoopInitializer::Const(Identifier::from("x").into()),
+        Identifier::from("xs"),
+        Block::from(vec![]),
+      )
+      .into()],
+    )
+    .with_kind(FunctionKind::Async)
+    .into()],
+  );
+  check_invalid("function f() { for await (const x of xs) {} }");
+  check_invalid("async function f() { for await (const x in xs) {} }");
+  check_invalid("async function f() { for await (;;) {} }");
+}
+
+#[test]
+fn top_level_await() {
+  assert_eq!(
+    Parser::new("await x;".as_bytes())
+      .parse_module()
+      .expect("failed to parse")
+      .into_body(),
+    strict_body(vec![Await::new(Identifier::from("x")).into()])
+  );
+  assert!(Parser::new("var await;".as_bytes()).parse_module().is_err());
+  check_invalid("await x;");
+}
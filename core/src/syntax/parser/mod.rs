@@ -1,14 +1,276 @@
+//! Parser implementation, turning the token stream into the AST.
+//!
+//! The parser can also read TypeScript, see [`Parser::with_typescript`].
+
+mod class;
 mod cursor;
-// pub use self::error::{ParserError};
-// use std::io::Read;
+pub mod error;
+mod expression;
+mod function;
+mod module;
+mod pattern;
+mod statement;
+#[cfg(test)]
+mod tests;
+mod typescript;
+
+use self::cursor::{BindingKind, Cursor, ScopeKind, SuperAccess};
+pub use self::error::{ParseError, ParseResult, Recovered};
+use crate::syntax::ast::{self, node::StatementList};
+use std::io::Read;
+
+/// Trait implemented by parsers.
+///
+/// This makes it possible to abstract over the underlying implementation of a parser.
+trait TokenParser<R>: Sized
+where
+  R: Read,
+{
+  /// Output type for the parser.
+  type Output;
+
+  /// Parses the token stream using the current parser.
+  ///
+  /// This method needs to be provided by the implementor type.
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError>;
+}
+
+/// Boolean representing if the parser should allow a `in` keyword.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AllowIn(bool);
+
+impl From<bool> for AllowIn {
+  fn from(allow: bool) -> Self {
+    Self(allow)
+  }
+}
+
+/// Boolean representing if the parser should allow a `return` keyword.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AllowReturn(bool);
+
+impl From<bool> for AllowReturn {
+  fn from(allow: bool) -> Self {
+    Self(allow)
+  }
+}
+
+/// Boolean representing if the parser should allow a `yield` keyword.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AllowYield(bool);
+
+impl From<bool> for AllowYield {
+  fn from(allow: bool) -> Self {
+    Self(allow)
+  }
+}
+
+/// Boolean representing if the parser should allow a `await` keyword.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AllowAwait(bool);
+
+impl From<bool> for AllowAwait {
+  fn from(allow: bool) -> Self {
+    Self(allow)
+  }
+}
+
+/// Parser for JavaScript source code.
+#[derive(Debug)]
+pub struct Parser<R> {
+  /// Cursor of the parser, pointing to the lexer and used to get tokens for the parser.
+  cursor: Cursor<R>,
+}
+
+impl<R> Parser<R> {
+  /// Creates a new parser over the given reader.
+  pub fn new(reader: R) -> Self
+  where
+    R: Read,
+  {
+    Self {
+      cursor: Cursor::new(reader),
+    }
+  }
+
+  /// Sets whether the input is TypeScript.
+  ///
+  /// TypeScript syntax is parsed and stripped, leaving the JavaScript it stands for: type
+  /// annotations, interfaces and other type-only declarations are left out, and enums,
+  /// namespaces and parameter properties become the statements they run as. Types are not
+  /// checked. Constructs whose output depends on type information, such as `export =`, are
+  /// errors.
+  pub fn with_typescript(mut self, typescript: bool) -> Self
+  where
+    R: Read,
+  {
+    self.cursor.set_typescript(typescript);
+    self
+  }
+
+  /// Parses the full input as a [Script][spec], stopping at the first error.
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-Script
+  pub fn parse_script(&mut self) -> Result<StatementList, ParseError>
+  where
+    R: Read,
+  {
+    Script.parse(&mut self.cursor)
+  }
+
+  /// Parses the full input as a [Module][spec], stopping at the first error.
+  ///
+  /// `await` is reserved at the top level of a module, where it can be used as an operator.
+  /// The returned module also summarizes its imports and exports.
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-Module
+  pub fn parse_module(&mut self) -> Result<ast::module::Module, ParseError>
+  where
+    R: Read,
+  {
+    Module.parse(&mut self.cursor)
+  }
+
+  /// Parses the full input as a [Script][spec], going on after errors.
+  ///
+  /// Missing closing brackets and semicolons are inserted, and the expressions and statements
+  /// that can't be parsed are replaced by `Node::Error`. The partial syntax tree is returned
+  /// with every error found.
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-Script
+  pub fn parse_script_with_recovery(&mut self) -> Recovered<StatementList>
+  where
+    R: Read,
+  {
+    self.cursor.set_recovering(true);
+    let result = Script.parse(&mut self.cursor);
+    self.recovered(result, StatementList::from(Vec::new()))
+  }
+
+  /// Parses the full input as a [Module][spec], going on after errors.
+  ///
+  /// See [`parse_script_with_recovery`](Self::parse_script_with_recovery) for the recovery.
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-Module
+  pub fn parse_module_with_recovery(&mut self) -> Recovered<ast::module::Module>
+  where
+    R: Read,
+  {
+    self.cursor.set_recovering(true);
+    let result = Module.parse(&mut self.cursor);
+    let empty = ast::module::Module::new(StatementList::from(Vec::new()).with_strict(true));
+    self.recovered(result, empty)
+  }
+
+  /// Collects the errors recorded while recovering, and the error that stopped the parser if
+  /// reading the input failed.
+  fn recovered<T>(&mut self, result: Result<T, ParseError>, empty: T) -> Recovered<T>
+  where
+    R: Read,
+  {
+    let mut errors = self.cursor.take_errors();
+    let ast = result.unwrap_or_else(|error| {
+      errors.push(error);
+      empty
+    });
+    Recovered::new(ast, errors)
+  }
+}
+
+/// Parses a full script.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-Script
+#[derive(Debug, Clone, Copy)]
+pub struct Script;
+
+impl<R> TokenParser<R> for Script
+where
+  R: Read,
+{
+  type Output = StatementList;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    if cursor.peek(0)?.is_some() {
+      ScriptBody.parse(cursor)
+    } else {
+      Ok(StatementList::from(Vec::new()))
+    }
+  }
+}
+
+/// Parses a script body.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ScriptBody
+#[derive(Debug, Clone, Copy)]
+pub struct ScriptBody;
+
+impl<R> TokenParser<R> for ScriptBody
+where
+  R: Read,
+{
+  type Output = StatementList;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    self::statement::StatementList::new(false, false, false, false)
+      .with_directives()
+      .parse(cursor)
+  }
+}
+
+/// Parses a full module.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-Module
+#[derive(Debug, Clone, Copy)]
+pub struct Module;
+
+impl<R> TokenParser<R> for Module
+where
+  R: Read,
+{
+  type Output = ast::module::Module;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    cursor.set_module(true);
+    let mut body = if cursor.peek(0)?.is_some() {
+      ModuleBody.parse(cursor)?
+    } else {
+      StatementList::from(Vec::new())
+    };
+    if cursor.is_typescript() {
+      typescript::remove_type_exports(cursor, &mut body);
+    }
+    if let Err(error) = cursor.check_exported_bindings() {
+      cursor.report(error)?;
+    }
+    Ok(ast::module::Module::new(body.with_strict(true)))
+  }
+}
 
-// trait TokenParser<R>: Sized
-//   where
-//     R: Read
-// {
-//   type Output;
+/// Parses a module body.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ModuleBody
+#[derive(Debug, Clone, Copy)]
+pub struct ModuleBody;
 
-//   fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParserError> {
+impl<R> TokenParser<R> for ModuleBody
+where
+  R: Read,
+{
+  type Output = StatementList;
 
-//   }
-// }
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    self::module::ModuleItemList.parse(cursor)
+  }
+}
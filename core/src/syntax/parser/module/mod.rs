@@ -0,0 +1,729 @@
+//! Module item parsing: import and export declarations.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Guide/Modules
+//! [spec]: https://tc39.es/ecma262/#sec-modules
+
+#[cfg(test)]
+mod tests;
+
+use super::{
+  class::{is_class_name, ClassTail},
+  expression::{AssignmentExpression, FunctionExpression, IdentifierName},
+  pattern::{check_strict_identifier, BindingIdentifier},
+  statement::{
+    is_async_function, is_import_declaration, Declaration, StatementListItem, VariableStatement,
+  },
+  typescript, BindingKind, Cursor, ParseError, TokenParser,
+};
+use crate::syntax::{
+  ast::{
+    arena::ArenaBox,
+    node::{
+      self, ExportDecl, ExportSpecifier, Identifier, ImportAttribute, ImportDecl, ImportSpecifier,
+      Node,
+    },
+    Const, Keyword, Position, Punctuator, Span, Sym,
+  },
+  lexer::TokenKind,
+};
+use std::io::Read;
+
+/// Parses the items of a module body until the end of the input.
+///
+/// Module code is always strict and can use `await` at the top level, so the items are parsed
+/// with `await` reserved and without `yield` or `return`.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ModuleItemList
+#[derive(Debug, Clone, Copy)]
+pub(super) struct ModuleItemList;
+
+impl<R> TokenParser<R> for ModuleItemList
+where
+  R: Read,
+{
+  type Output = node::StatementList;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let mut items = Vec::new();
+    let mut directives = true;
+    while let Some(token) = cursor.peek(0)? {
+      let directive = match token.kind() {
+        TokenKind::StringLiteral(value) if directives => Some((value.clone(), token.span())),
+        _ => None,
+      };
+      let raw = directive.map(|(value, span)| cursor.raw_string(span.start(), &value));
+
+      let point = cursor.recovery_point()?;
+      let item = ModuleItem.parse(cursor);
+      let mut item =
+        cursor.recover_item(point, item, |span| vec![Node::error().with_span(span)])?;
+      // Module code is strict, so the directive prologue only matters to the syntax tree.
+      match (raw, item.as_slice()) {
+        (Some(raw), [Node::Const(Const::String(value), span)]) => {
+          item = vec![node::Directive::new(value.clone(), raw)
+            .with_span(*span)
+            .into()];
+        }
+        _ => directives = false,
+      }
+      items.extend(item);
+    }
+    Ok(items.into())
+  }
+}
+
+/// Parses a single module item: an import declaration, an export declaration or a statement
+/// list item.
+///
+/// In TypeScript, an item can also be a declaration without run time behavior, such as an
+/// interface, or a declaration standing for several statements, such as an enum. The item is
+/// parsed as the statements it strips to.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ModuleItem
+#[derive(Debug, Clone, Copy)]
+struct ModuleItem;
+
+impl<R> TokenParser<R> for ModuleItem
+where
+  R: Read,
+{
+  type Output = Vec<Node>;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let typescript = cursor.is_typescript();
+    if typescript && typescript::is_declaration(cursor)? {
+      return typescript::Declaration::new(false, true).parse(cursor);
+    }
+    let start = cursor.start()?;
+    if is_import_declaration(cursor)? {
+      // Imported bindings are lexical declarations of the module, the names imported by
+      // `import type` are types.
+      let mark = cursor.bindings_mark();
+      return match ImportDeclaration.parse(cursor)? {
+        Some(decl) => {
+          cursor.declare_bindings(mark, BindingKind::Lexical)?;
+          Ok(vec![decl.with_span(cursor.span_from(start)).into()])
+        }
+        None => {
+          cursor.declare_type_bindings(mark);
+          Ok(Vec::new())
+        }
+      };
+    }
+    if cursor.next_is(Keyword::Export)? {
+      if typescript {
+        if let Some(items) = TypeScriptExport.parse(cursor)? {
+          return Ok(items);
+        }
+      }
+      let decl = ExportDeclaration.parse(cursor)?;
+      return Ok(vec![Node::Export(decl, cursor.span_from(start).into())]);
+    }
+    Ok(vec![
+      StatementListItem::new(false, true, false).parse(cursor)?
+    ])
+  }
+}
+
+/// Parses an import declaration.
+///
+/// A TypeScript `import type` declaration is parsed to `None`. The names imported with a `type`
+/// modifier, as in `import { type A, B } from "mod"`, are left out, and an import left without
+/// names only loads the module.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/import
+/// [spec]: https://tc39.es/ecma262/#prod-ImportDeclaration
+#[derive(Debug, Clone, Copy)]
+struct ImportDeclaration;
+
+impl<R> TokenParser<R> for ImportDeclaration
+where
+  R: Read,
+{
+  type Output = Option<ImportDecl>;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let tok = cursor.expect(Keyword::Import, "import declaration")?;
+    let typescript = cursor.is_typescript();
+    let type_only = typescript && typescript::is_type_modifier(cursor)?;
+    if type_only {
+      let _ = cursor.next()?.expect("type token vanished");
+    }
+    if typescript && typescript::is_import_assignment(cursor)? {
+      return Err(ParseError::general(
+        "import assignments can not be stripped to JavaScript",
+        tok.span().start(),
+      ));
+    }
+
+    let mut specifiers = Vec::new();
+    if let Some(TokenKind::StringLiteral(_)) = cursor.peek(0)?.map(|tk| tk.kind()) {
+      // A side effect import, `import "mod";`.
+      return finish_import(cursor, specifiers, false).map(Some);
+    }
+
+    if let Some(TokenKind::Identifier(_)) = cursor.peek(0)?.map(|tk| tk.kind()) {
+      let start = cursor.start()?;
+      let local = BindingIdentifier::new(false, true).parse(cursor)?;
+      let local = Identifier::new(local).with_span(cursor.span_from(start));
+      specifiers.push(ImportSpecifier::Default(local));
+      if cursor.next_if(Punctuator::Comma)?.is_none() {
+        let decl = finish_import(cursor, specifiers, true)?;
+        return Ok(Some(decl).filter(|_| !type_only));
+      }
+    }
+
+    if cursor.next_if(Punctuator::Mul)?.is_some() {
+      cursor.expect(TokenKind::identifier("as"), "namespace import")?;
+      let start = cursor.start()?;
+      let local = BindingIdentifier::new(false, true).parse(cursor)?;
+      let local = Identifier::new(local).with_span(cursor.span_from(start));
+      specifiers.push(ImportSpecifier::Namespace(local));
+    } else {
+      cursor.expect(Punctuator::OpenBlock, "import declaration")?;
+      while cursor.next_if(Punctuator::CloseBlock)?.is_none() {
+        if typescript && typescript::is_type_modifier(cursor)? {
+          let _ = cursor.next()?.expect("type token vanished");
+          let mark = cursor.bindings_mark();
+          let _ = NamedImport.parse(cursor)?;
+          cursor.declare_type_bindings(mark);
+        } else {
+          specifiers.push(NamedImport.parse(cursor)?);
+        }
+        if cursor.next_if(Punctuator::CloseBlock)?.is_some() {
+          break;
+        }
+        cursor.expect(Punctuator::Comma, "import declaration")?;
+      }
+    }
+
+    let decl = finish_import(cursor, specifiers, true)?;
+    Ok(Some(decl).filter(|_| !type_only))
+  }
+}
+
+/// Parses the end of an import declaration, from the `from` clause, if the declaration imports
+/// names, to the semicolon.
+fn finish_import<R>(
+  cursor: &mut Cursor<R>,
+  specifiers: Vec<ImportSpecifier>,
+  from: bool,
+) -> Result<ImportDecl, ParseError>
+where
+  R: Read,
+{
+  if from {
+    cursor.expect(TokenKind::identifier("from"), "import declaration")?;
+  }
+  let source = module_specifier(cursor)?;
+  let attributes = WithClause.parse(cursor)?;
+  cursor.expect_semicolon("import declaration")?;
+
+  Ok(ImportDecl::new(specifiers, source, attributes))
+}
+
+/// Parses a single named import, such as `a` or `a as b` in `import { a, a as b } from "mod"`.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ImportSpecifier
+#[derive(Debug, Clone, Copy)]
+struct NamedImport;
+
+impl<R> TokenParser<R> for NamedImport
+where
+  R: Read,
+{
+  type Output = ImportSpecifier;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let tok = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.clone();
+    let imported = ModuleExportName.parse(cursor)?;
+
+    if cursor.next_if(TokenKind::identifier("as"))?.is_some() {
+      let start = cursor.start()?;
+      let local = BindingIdentifier::new(false, true).parse(cursor)?;
+      let local = Identifier::new(local).with_span(cursor.span_from(start));
+      return Ok(ImportSpecifier::named(imported, local));
+    }
+
+    // Without `as`, the imported name is also the name of the binding, so it has to be a valid
+    // identifier.
+    match tok.kind() {
+      TokenKind::Identifier(_) => {
+        check_strict_identifier(cursor, &imported, tok.span(), true)?;
+        cursor.record_binding(&imported, tok.span());
+        let local = Identifier::new(imported.clone()).with_span(tok.span());
+        Ok(ImportSpecifier::named(imported, local))
+      }
+      _ => Err(ParseError::expected(
+        vec![TokenKind::identifier("as")],
+        tok.kind().clone(),
+        tok.span(),
+        "import specifier",
+      )),
+    }
+  }
+}
+
+/// Parses an export declaration.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/export
+/// [spec]: https://tc39.es/ecma262/#prod-ExportDeclaration
+#[derive(Debug, Clone, Copy)]
+struct ExportDeclaration;
+
+impl<R> TokenParser<R> for ExportDeclaration
+where
+  R: Read,
+{
+  type Output = ExportDecl;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    cursor.expect(Keyword::Export, "export declaration")?;
+
+    let tok = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.clone();
+    if is_async_function(cursor)? {
+      let decl = ExportDecl::declaration(Declaration::new(false, true).parse(cursor)?);
+      declare_exports(cursor, &decl, tok.span())?;
+      return Ok(decl);
+    }
+
+    let decl = match tok.kind() {
+      TokenKind::Punctuator(Punctuator::Mul) => StarExport.parse(cursor),
+      TokenKind::Punctuator(Punctuator::OpenBlock) => NamedExports.parse(cursor),
+      TokenKind::Keyword(Keyword::Var) => Ok(ExportDecl::declaration(
+        VariableStatement::new(false, true).parse(cursor)?,
+      )),
+      TokenKind::Keyword(Keyword::Function)
+      | TokenKind::Keyword(Keyword::Class)
+      | TokenKind::Keyword(Keyword::Let)
+      | TokenKind::Keyword(Keyword::Const) => Ok(ExportDecl::declaration(
+        Declaration::new(false, true).parse(cursor)?,
+      )),
+      TokenKind::Keyword(Keyword::Default) => {
+        let _ = cursor.next()?.expect("default token vanished");
+        DefaultExport.parse(cursor)
+      }
+      _ => Err(ParseError::unexpected(
+        tok.kind().clone(),
+        tok.span(),
+        "expected a declaration or an export list",
+      )),
+    }?;
+    declare_exports(cursor, &decl, tok.span())?;
+    Ok(decl)
+  }
+}
+
+/// Parses a star re-export, such as `* as ns from "mod"` after `export`.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ExportFromClause
+#[derive(Debug, Clone, Copy)]
+struct StarExport;
+
+impl<R> TokenParser<R> for StarExport
+where
+  R: Read,
+{
+  type Output = ExportDecl;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    cursor.expect(Punctuator::Mul, "export declaration")?;
+    let alias = if cursor.next_if(TokenKind::identifier("as"))?.is_some() {
+      Some(ModuleExportName.parse(cursor)?)
+    } else {
+      None
+    };
+    cursor.expect(TokenKind::identifier("from"), "export declaration")?;
+    let source = module_specifier(cursor)?;
+    let attributes = WithClause.parse(cursor)?;
+    cursor.expect_semicolon("export declaration")?;
+    Ok(ExportDecl::All {
+      alias,
+      source,
+      attributes,
+    })
+  }
+}
+
+/// Parses the TypeScript forms of export declarations, the cursor being at `export`.
+///
+/// Type-only exports, such as `export type { A }` or `export interface A {}`, are parsed to no
+/// statements, and the statements of an exported enum or namespace are exported.
+/// `export =` and `export import` have no JavaScript equivalent. `None` is returned, without
+/// consuming anything, for the forms of JavaScript.
+#[derive(Debug, Clone, Copy)]
+struct TypeScriptExport;
+
+impl<R> TokenParser<R> for TypeScriptExport
+where
+  R: Read,
+{
+  type Output = Option<Vec<Node>>;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let tok = cursor.peek(1)?.ok_or(ParseError::AbruptEnd)?.clone();
+    let span = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.span();
+    match tok.kind() {
+      TokenKind::Punctuator(Punctuator::Assign) | TokenKind::Keyword(Keyword::Import) => {
+        return Err(ParseError::unexpected(
+          tok.kind().clone(),
+          tok.span(),
+          "CommonJS style exports can not be stripped to JavaScript",
+        ));
+      }
+      TokenKind::Identifier(name) if name.as_ref() == "as" => {
+        // `export as namespace A;` only describes a global for scripts.
+        let _ = cursor.next()?.expect("export token vanished");
+        let _ = cursor.next()?.expect("as token vanished");
+        cursor.expect(TokenKind::identifier("namespace"), "export declaration")?;
+        let _ = IdentifierName.parse(cursor)?;
+        cursor.expect_semicolon("export declaration")?;
+        return Ok(Some(Vec::new()));
+      }
+      TokenKind::Identifier(name)
+        if name.as_ref() == "type"
+          && matches!(
+            cursor.peek(2)?.map(|tk| tk.kind()),
+            Some(TokenKind::Punctuator(Punctuator::OpenBlock))
+              | Some(TokenKind::Punctuator(Punctuator::Mul))
+          ) =>
+      {
+        let _ = cursor.next()?.expect("export token vanished");
+        let _ = cursor.next()?.expect("type token vanished");
+        if cursor.next_is(Punctuator::Mul)? {
+          let _ = StarExport.parse(cursor)?;
+        } else {
+          let _ = NamedExports.parse(cursor)?;
+        }
+        return Ok(Some(Vec::new()));
+      }
+      TokenKind::Keyword(Keyword::Default)
+        if typescript::is_keyword_before_name(cursor, 2, "interface")? =>
+      {
+        let _ = cursor.next()?.expect("export token vanished");
+        let _ = cursor.next()?.expect("default token vanished");
+        return typescript::Declaration::new(false, true)
+          .parse(cursor)
+          .map(Some);
+      }
+      _ => {}
+    }
+
+    if !typescript::is_declaration_at(cursor, 1)? {
+      return Ok(None);
+    }
+    let _ = cursor.next()?.expect("export token vanished");
+    let mut items = typescript::Declaration::new(false, true).parse(cursor)?;
+    if let Some(first) = items.first_mut() {
+      if matches!(
+        first,
+        Node::DeclarationList(_, _) | Node::FunctionDecl(_) | Node::ClassDecl(_)
+      ) {
+        let decl = ExportDecl::declaration(first.clone());
+        declare_exports(cursor, &decl, span)?;
+        *first = decl.into();
+      }
+    }
+    Ok(Some(items))
+  }
+}
+
+/// Records the names exported by a declaration, so that duplicate exports are reported.
+///
+/// Export lists record their names as they are parsed, with the span of each name.
+fn declare_exports<R>(
+  cursor: &mut Cursor<R>,
+  decl: &ExportDecl,
+  span: Span,
+) -> Result<(), ParseError>
+where
+  R: Read,
+{
+  match decl {
+    ExportDecl::Named { .. } | ExportDecl::All { alias: None, .. } => Ok(()),
+    ExportDecl::All {
+      alias: Some(alias), ..
+    } => cursor.declare_export(alias, span),
+    ExportDecl::Declaration(node) => match node.as_ref() {
+      Node::DeclarationList(list, _) => {
+        for declaration in list.as_ref() {
+          for name in declaration.target().bound_names() {
+            cursor.declare_export(name, span)?;
+          }
+        }
+        Ok(())
+      }
+      Node::FunctionDecl(function) => cursor.declare_export(function.name(), span),
+      Node::ClassDecl(class) => cursor.declare_export(class.name().map_or("", Sym::as_str), span),
+      _ => Ok(()),
+    },
+    ExportDecl::DefaultFunction(_)
+    | ExportDecl::DefaultClass(_)
+    | ExportDecl::DefaultExpression(_) => cursor.declare_export("default", span),
+  }
+}
+
+/// Parses an export list, such as `{ a, b as c }`, with its optional `from` clause.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-NamedExports
+#[derive(Debug, Clone, Copy)]
+struct NamedExports;
+
+impl<R> TokenParser<R> for NamedExports
+where
+  R: Read,
+{
+  type Output = ExportDecl;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    cursor.expect(Punctuator::OpenBlock, "export declaration")?;
+
+    let mut specifiers = Vec::new();
+    let mut locals = Vec::new();
+    // The first local name which is not a valid identifier reference, only allowed when the
+    // names are re-exported from another module.
+    let mut invalid_local: Option<Position> = None;
+    while cursor.next_if(Punctuator::CloseBlock)?.is_none() {
+      // A TypeScript type-only export, `type A`, exports nothing at run time.
+      if cursor.is_typescript() && typescript::is_type_modifier(cursor)? {
+        let _ = cursor.next()?.expect("type token vanished");
+        let _ = ModuleExportName.parse(cursor)?;
+        if cursor.next_if(TokenKind::identifier("as"))?.is_some() {
+          let _ = ModuleExportName.parse(cursor)?;
+        }
+        if cursor.next_if(Punctuator::CloseBlock)?.is_some() {
+          break;
+        }
+        cursor.expect(Punctuator::Comma, "export declaration")?;
+        continue;
+      }
+
+      let tok = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.clone();
+      let local = ModuleExportName.parse(cursor)?;
+      if !matches!(tok.kind(), TokenKind::Identifier(_)) && invalid_local.is_none() {
+        invalid_local = Some(tok.span().start());
+      }
+
+      let (exported, span) = if cursor.next_if(TokenKind::identifier("as"))?.is_some() {
+        let span = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.span();
+        (ModuleExportName.parse(cursor)?, span)
+      } else {
+        (local.clone(), tok.span())
+      };
+      cursor.declare_export(&exported, span)?;
+      locals.push((local.clone(), tok.span()));
+      specifiers.push(ExportSpecifier::new(local, exported).with_local_span(tok.span()));
+
+      if cursor.next_if(Punctuator::CloseBlock)?.is_some() {
+        break;
+      }
+      cursor.expect(Punctuator::Comma, "export declaration")?;
+    }
+
+    let (source, attributes) = if cursor.next_if(TokenKind::identifier("from"))?.is_some() {
+      let source = module_specifier(cursor)?;
+      (Some(source), WithClause.parse(cursor)?)
+    } else {
+      if let Some(position) = invalid_local {
+        return Err(ParseError::general(
+          "only identifiers can be exported without a 'from' clause",
+          position,
+        ));
+      }
+      // The exported bindings must be declared somewhere in the module.
+      for (local, span) in locals {
+        cursor.reference_export(&local, span);
+      }
+      (None, ArenaBox::default())
+    };
+    cursor.expect_semicolon("export declaration")?;
+
+    Ok(ExportDecl::named(specifiers, source, attributes))
+  }
+}
+
+/// Parses what follows `export default`: a function or class declaration whose name is
+/// optional, or an expression.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ExportDeclaration
+#[derive(Debug, Clone, Copy)]
+struct DefaultExport;
+
+impl<R> TokenParser<R> for DefaultExport
+where
+  R: Read,
+{
+  type Output = ExportDecl;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    if is_async_function(cursor)? || cursor.next_is(Keyword::Function)? {
+      let span = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.span();
+      let function = FunctionExpression.parse(cursor)?;
+      if let Some(name) = function.name() {
+        cursor.declare_binding(name, span, BindingKind::Function)?;
+      }
+      return Ok(ExportDecl::DefaultFunction(function));
+    }
+
+    let start = cursor.start()?;
+    if cursor.is_typescript()
+      && typescript::is_contextual_before(cursor, "abstract", Keyword::Class)?
+    {
+      let _ = cursor.next()?.expect("abstract token vanished");
+    }
+    if cursor.next_if(Keyword::Class)?.is_some() {
+      let mark = cursor.bindings_mark();
+      let name_start = cursor.start()?;
+      let name = if is_class_name(cursor)? {
+        Some(BindingIdentifier::new(false, true).parse(cursor)?)
+      } else {
+        None
+      };
+      let name_span = name.as_ref().map(|_| cursor.span_from(name_start));
+      cursor.declare_bindings(mark, BindingKind::Lexical)?;
+      let class = ClassTail::new(name, false, true).parse(cursor)?;
+      return Ok(ExportDecl::DefaultClass(
+        class
+          .with_span(cursor.span_from(start))
+          .with_name_span(name_span),
+      ));
+    }
+
+    let expr = AssignmentExpression::new(true, false, true).parse(cursor)?;
+    cursor.expect_semicolon("export declaration")?;
+    Ok(ExportDecl::default_expression(expr))
+  }
+}
+
+/// Parses the optional `with` clause of an import or re-export, such as
+/// `with { type: "json" }`.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-WithClause
+#[derive(Debug, Clone, Copy)]
+struct WithClause;
+
+impl<R> TokenParser<R> for WithClause
+where
+  R: Read,
+{
+  type Output = ArenaBox<[ImportAttribute]>;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let mut attributes: Vec<ImportAttribute> = Vec::new();
+    if cursor.next_if(Keyword::With)?.is_none() {
+      return Ok(attributes.into());
+    }
+
+    cursor.expect(Punctuator::OpenBlock, "import attributes")?;
+    while cursor.next_if(Punctuator::CloseBlock)?.is_none() {
+      let tok = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.clone();
+      let key = match tok.kind() {
+        TokenKind::StringLiteral(key) => {
+          let _ = cursor.next()?.expect("string literal vanished");
+          key.clone()
+        }
+        _ => IdentifierName.parse(cursor)?.into(),
+      };
+      if attributes.iter().any(|attr| attr.key() == key.as_ref()) {
+        return Err(ParseError::general(
+          format!("duplicate import attribute '{}'", key),
+          tok.span().start(),
+        ));
+      }
+      cursor.expect(Punctuator::Colon, "import attributes")?;
+      let value = string_literal(cursor, "import attributes")?;
+      attributes.push(ImportAttribute::new(key, value));
+
+      if cursor.next_if(Punctuator::CloseBlock)?.is_some() {
+        break;
+      }
+      cursor.expect(Punctuator::Comma, "import attributes")?;
+    }
+
+    Ok(attributes.into())
+  }
+}
+
+/// Parses a name imported from or exported to another module, which can be any identifier
+/// name or a string literal.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ModuleExportName
+#[derive(Debug, Clone, Copy)]
+struct ModuleExportName;
+
+impl<R> TokenParser<R> for ModuleExportName
+where
+  R: Read,
+{
+  type Output = Sym;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    if let Some(TokenKind::StringLiteral(name)) = cursor.peek(0)?.map(|tk| tk.kind()) {
+      let name = Sym::from(name.as_ref());
+      let _ = cursor.next()?.expect("string literal vanished");
+      return Ok(name);
+    }
+    IdentifierName.parse(cursor)
+  }
+}
+
+/// Parses the string literal naming a module.
+fn module_specifier<R>(cursor: &mut Cursor<R>) -> Result<Box<str>, ParseError>
+where
+  R: Read,
+{
+  string_literal(cursor, "module specifier")
+}
+
+/// Parses a string literal.
+fn string_literal<R>(cursor: &mut Cursor<R>, context: &'static str) -> Result<Box<str>, ParseError>
+where
+  R: Read,
+{
+  let tok = cursor.next()?.ok_or(ParseError::AbruptEnd)?;
+  match tok.kind() {
+    TokenKind::StringLiteral(s) => Ok(s.clone()),
+    _ => Err(ParseError::expected(
+      vec![TokenKind::string_literal("string")],
+      tok.kind().clone(),
+      tok.span(),
+      context,
+    )),
+  }
+}
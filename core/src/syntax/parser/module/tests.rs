@@ -0,0 +1,429 @@
+use crate::syntax::{
+  ast::{
+    arena::ArenaBox,
+    module::{ExportEntry, ImportName, Module, ModuleSummary},
+    node::{
+      BinOp, Call, Class, Declaration, DeclarationList, ExportDecl, ExportSpecifier, FunctionDecl,
+      FunctionExpr, FunctionKind, GetConstField, Identifier, ImportAttribute, ImportCall,
+      ImportDecl, ImportSpecifier, Node, Object, PropertyDefinition,
+    },
+    operator::NumOp,
+    Const, Sym,
+  },
+  parser::{
+    tests::{check_invalid, check_parser, strict_body},
+    Parser,
+  },
+};
+
+/// Parses the given JavaScript string as a module.
+#[track_caller]
+fn parse_module(js: &str) -> Module {
+  Parser::new(js.as_bytes())
+    .parse_module()
+    .expect("failed to parse")
+}
+
+/// Checks that the given JavaScript string, parsed as a module, gives the expected items.
+#[track_caller]
+fn check_module<L>(js: &str, expr: L)
+where
+  L: Into<ArenaBox<[Node]>>,
+{
+  assert_eq!(parse_module(js).body(), &strict_body(expr));
+}
+
+/// Checks that the given JavaScript string creates a parse error when parsed as a module.
+#[track_caller]
+fn check_invalid_module(js: &str) {
+  assert!(Parser::new(js.as_bytes()).parse_module().is_err());
+}
+
+#[test]
+fn import_declarations() {
+  check_module(
+    r#"import a from "m";
+    import * as ns from "m";
+    import { b, c as d, "e-f" as g, } from "m";
+    import h, { i } from "n";
+    import j, * as k from "n";
+    import "side";"#,
+    vec![
+      ImportDecl::new(vec![ImportSpecifier::Default("a".into())], "m", vec![]).into(),
+      ImportDecl::new(vec![ImportSpecifier::Namespace("ns".into())], "m", vec![]).into(),
+      ImportDecl::new(
+        vec![
+          ImportSpecifier::named("b", "b"),
+          ImportSpecifier::named("c", "d"),
+          ImportSpecifier::named("e-f", "g"),
+        ],
+        "m",
+        vec![],
+      )
+      .into(),
+      ImportDecl::new(
+        vec![
+          ImportSpecifier::Default("h".into()),
+          ImportSpecifier::named("i", "i"),
+        ],
+        "n",
+        vec![],
+      )
+      .into(),
+      ImportDecl::new(
+        vec![
+          ImportSpecifier::Default("j".into()),
+          ImportSpecifier::Namespace("k".into()),
+        ],
+        "n",
+        vec![],
+      )
+      .into(),
+      ImportDecl::new(vec![], "side", vec![]).into(),
+    ],
+  );
+}
+
+#[test]
+fn import_attributes() {
+  check_module(
+    r#"import data from "./d.json" with { type: "json", "x-y": "z" };"#,
+    vec![ImportDecl::new(
+      vec![ImportSpecifier::Default("data".into())],
+      "./d.json",
+      vec![
+        ImportAttribute::new("type", "json"),
+        ImportAttribute::new("x-y", "z"),
+      ],
+    )
+    .into()],
+  );
+  check_invalid_module(r#"import a from "m" with { type: "json", type: "json" };"#);
+  check_invalid_module(r#"import a from "m" with { type: json };"#);
+}
+
+#[test]
+fn invalid_imports() {
+  check_invalid_module(r#"import { if } from "m";"#);
+  check_invalid_module(r#"import { "a" } from "m";"#);
+  check_invalid_module(r#"import a, b from "m";"#);
+  check_invalid_module(r#"import * from "m";"#);
+  check_invalid_module(r#"import { a } "m";"#);
+  check_invalid_module(r#"import { a as await } from "m";"#);
+  check_invalid_module(r#"function f() { import a from "m"; }"#);
+  check_invalid(r#"import a from "m";"#);
+}
+
+#[test]
+fn export_lists() {
+  check_module(
+    r#"var a, b;
+    export { a, b as c, };
+    export { d as "e-f", if } from "m";
+    export * from "m";
+    export * as ns from "m" with { type: "json" };"#,
+    vec![
+      DeclarationList::Var(
+        vec![
+          Declaration::new(Identifier::from("a"), None),
+          Declaration::new(Identifier::from("b"), None),
+        ]
+        .into(),
+      )
+      .into(),
+      ExportDecl::named(
+        vec![
+          ExportSpecifier::new("a", "a"),
+          ExportSpecifier::new("b", "c"),
+        ],
+        None,
+        ArenaBox::default(),
+      )
+      .into(),
+      ExportDecl::named(
+        vec![
+          ExportSpecifier::new("d", "e-f"),
+          ExportSpecifier::new("if", "if"),
+        ],
+        Some("m".into()),
+        ArenaBox::default(),
+      )
+      .into(),
+      ExportDecl::All {
+        alias: None,
+        source: "m".into(),
+        attributes: ArenaBox::default(),
+      }
+      .into(),
+      ExportDecl::All {
+        alias: Some("ns".into()),
+        source: "m".into(),
+        attributes: vec![ImportAttribute::new("type", "json")].into(),
+      }
+      .into(),
+    ],
+  );
+}
+
+#[test]
+fn exported_declarations() {
+  check_module(
+    "export var a = 1; export const b = 2; export function f() {} export async function g() {} \
+     export class C {}",
+    vec![
+      ExportDecl::declaration(DeclarationList::Var(
+        vec![Declaration::new(
+          Identifier::from("a"),
+          Some(Const::from(1).into()),
+        )]
+        .into(),
+      ))
+      .into(),
+      ExportDecl::declaration(DeclarationList::Const(
+        vec![Declaration::new(
+          Identifier::from("b"),
+          Some(Const::from(2).into()),
+        )]
+        .into(),
+      ))
+      .into(),
+      ExportDecl::declaration(FunctionDecl::new(
+        Sym::from("f"),
+        vec![],
+        strict_body(vec![]),
+      ))
+      .into(),
+      ExportDecl::declaration(
+        FunctionDecl::new(Sym::from("g"), vec![], strict_body(vec![]))
+          .with_kind(FunctionKind::Async),
+      )
+      .into(),
+      ExportDecl::declaration(Node::ClassDecl(Class::new::<_, Node, _>(
+        Sym::from("C"),
+        None,
+        None,
+        vec![],
+      )))
+      .into(),
+    ],
+  );
+}
+
+#[test]
+fn default_exports() {
+  check_module(
+    "export default function () {}",
+    vec![ExportDecl::DefaultFunction(FunctionExpr::new(None, vec![], strict_body(vec![]))).into()],
+  );
+  check_module(
+    "export default async function* f() {}",
+    vec![ExportDecl::DefaultFunction(
+      FunctionExpr::new(Some(Sym::from("f")), vec![], strict_body(vec![]))
+        .with_kind(FunctionKind::AsyncGenerator),
+    )
+    .into()],
+  );
+  check_module(
+    "export default class A {}",
+    vec![
+      ExportDecl::DefaultClass(Class::new::<_, Node, _>(Sym::from("A"), None, None, vec![])).into(),
+    ],
+  );
+  check_module(
+    "export default a + b;",
+    vec![ExportDecl::default_expression(BinOp::new(
+      NumOp::Add,
+      Identifier::from("a"),
+      Identifier::from("b"),
+    ))
+    .into()],
+  );
+}
+
+#[test]
+fn invalid_exports() {
+  check_invalid_module(r#"export { "a" };"#);
+  check_invalid_module("export { if };");
+  check_invalid_module("export default var a;");
+  check_invalid_module("export a;");
+  check_invalid_module(r#"export * as ns;"#);
+  check_invalid_module("{ export var a; }");
+  check_invalid("export var a;");
+}
+
+#[test]
+fn export_early_errors() {
+  check_invalid_module("export var a; export { a };");
+  check_invalid_module("export default 1; export default function () {}");
+  check_invalid_module("var a, b; export { a as c, b as c };");
+  check_invalid_module("export * as ns from \"m\"; export * as ns from \"n\";");
+  check_invalid_module("export { a };");
+  check_invalid_module("import a from \"m\"; let a;");
+  check_invalid_module("import { a, b as a } from \"m\";");
+  check_invalid_module("import { eval } from \"m\";");
+
+  parse_module("export { a, b, f, C }; import a from \"m\"; var b; function f() {} class C {}");
+  parse_module("export { a } from \"m\"; export * from \"n\"; export * from \"o\";");
+  parse_module("export default function f() {} export { f as g };");
+}
+
+#[test]
+fn module_code_is_strict() {
+  check_invalid_module("delete a;");
+  check_invalid_module("var eval;");
+  check_invalid_module("a = 010;");
+  check_invalid_module("var static;");
+  check_invalid_module("function f() {} let f;");
+
+  check_invalid_module("static: ;");
+
+  parse_module("eval(a); arguments; a = 0o10; eval: ;");
+}
+
+#[test]
+fn dynamic_import() {
+  check_parser(
+    r#"import("./a.js").then(f); import(a, { with: { type: "json" } },);"#,
+    vec![
+      Call::new(
+        GetConstField::new(ImportCall::new(Const::from("./a.js"), None), "then"),
+        vec![Identifier::from("f").into()],
+      )
+      .into(),
+      ImportCall::new(
+        Identifier::from("a"),
+        Some(
+          Object::from(vec![PropertyDefinition::property(
+            "with",
+            Object::from(vec![PropertyDefinition::property(
+              "type",
+              Const::from("json"),
+            )]),
+          )])
+          .into(),
+        ),
+      )
+      .into(),
+    ],
+  );
+  check_invalid("import();");
+  check_invalid(r#"new import("a");"#);
+}
+
+#[test]
+fn import_meta() {
+  check_module(
+    "import.meta.url;",
+    vec![GetConstField::new(Node::import_meta(), "url").into()],
+  );
+  check_invalid("import.meta;");
+  check_invalid_module("import.foo;");
+}
+
+#[test]
+fn module_summary() {
+  let module = parse_module(
+    r#"import a, { b as c } from "m";
+    import * as ns from "n";
+    export { c, ns, d as e };
+    export * from "m";
+    export * as all from "o";
+    export { f as g } from "n";
+    export let d = 1, { h } = {};
+    export default 1;"#,
+  );
+
+  assert_eq!(
+    module.summary(),
+    &ModuleSummary::new(module.body()),
+    "the summary is computed from the body"
+  );
+  assert_eq!(
+    module.summary().requested_modules(),
+    &[Box::from("m"), Box::from("n"), Box::from("o")]
+  );
+
+  let imports: Vec<_> = module
+    .summary()
+    .imports()
+    .iter()
+    .map(|entry| {
+      (
+        entry.module_request(),
+        entry.import_name().clone(),
+        entry.local_name(),
+      )
+    })
+    .collect();
+  assert_eq!(
+    imports,
+    vec![
+      ("m", ImportName::Name("default".into()), "a"),
+      ("m", ImportName::Name("b".into()), "c"),
+      ("n", ImportName::Namespace, "ns"),
+    ]
+  );
+
+  assert_eq!(
+    module.summary().exports(),
+    &[
+      ExportEntry::Indirect {
+        export_name: "c".into(),
+        module_request: "m".into(),
+        import_name: ImportName::Name("b".into()),
+      },
+      ExportEntry::Local {
+        export_name: "ns".into(),
+        local_name: "ns".into(),
+      },
+      ExportEntry::Local {
+        export_name: "e".into(),
+        local_name: "d".into(),
+      },
+      ExportEntry::Star {
+        module_request: "m".into(),
+      },
+      ExportEntry::Indirect {
+        export_name: "all".into(),
+        module_request: "o".into(),
+        import_name: ImportName::Namespace,
+      },
+      ExportEntry::Indirect {
+        export_name: "g".into(),
+        module_request: "n".into(),
+        import_name: ImportName::Name("f".into()),
+      },
+      ExportEntry::Local {
+        export_name: "d".into(),
+        local_name: "d".into(),
+      },
+      ExportEntry::Local {
+        export_name: "h".into(),
+        local_name: "h".into(),
+      },
+      ExportEntry::Local {
+        export_name: "default".into(),
+        local_name: "*default*".into(),
+      },
+    ]
+  );
+  assert_eq!(
+    module.summary().exported_names().collect::<Vec<_>>(),
+    vec!["c", "ns", "e", "all", "g", "d", "h", "default"]
+  );
+}
+
+#[test]
+fn empty_module_summary() {
+  let module = parse_module("");
+  assert!(module.body().items().is_empty());
+  assert_eq!(module.summary(), &ModuleSummary::default());
+}
+
+#[test]
+fn module_recovery() {
+  let recovered = Parser::new("import { a from 'x';\nexport let b = ;\nexport { c };".as_bytes())
+    .parse_module_with_recovery();
+  assert_eq!(recovered.errors().len(), 3, "{:?}", recovered.errors());
+  assert_eq!(recovered.ast().body().items().len(), 3);
+}
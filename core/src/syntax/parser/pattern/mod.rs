@@ -0,0 +1,660 @@
+//! Destructuring pattern parsing.
+//!
+//! Binding patterns (declarations, parameters and catch clauses) are parsed directly, while
+//! assignment patterns are first parsed as object or array literals and then reinterpreted once
+//! the `=` (or the `of`/`in` of a `for` loop) is seen.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Destructuring_assignment
+//! [spec]: https://tc39.es/ecma262/#sec-destructuring-binding-patterns
+
+#[cfg(test)]
+mod tests;
+
+use super::{
+  expression::{Initializer, PropertyName},
+  AllowAwait, AllowYield, Cursor, ParseError, TokenParser,
+};
+use crate::syntax::{
+  ast::{
+    arena::ArenaBox,
+    node::{
+      ArrayPattern, Identifier, Node, ObjectPattern, Pattern, PatternElement, PropertyDefinition,
+      PropertyPattern,
+    },
+    Keyword, Position, Punctuator, Span, Sym,
+  },
+  lexer::{TokenKind, STRICT_FORBIDDEN_IDENTIFIERS},
+};
+use std::io::Read;
+
+/// Binding identifier parsing.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-BindingIdentifier
+#[derive(Debug, Clone, Copy)]
+pub(super) struct BindingIdentifier {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl BindingIdentifier {
+  /// Creates a new `BindingIdentifier` parser.
+  pub(super) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for BindingIdentifier
+where
+  R: Read,
+{
+  type Output = Sym;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let next_token = cursor.next()?.ok_or(ParseError::AbruptEnd)?;
+
+    let name: Sym = match next_token.kind() {
+      TokenKind::Identifier(ref s) => s.clone(),
+      TokenKind::Keyword(k @ Keyword::Yield) | TokenKind::Keyword(k @ Keyword::Await) => {
+        check_yield_await(*k, next_token.span(), self.allow_yield, self.allow_await)?;
+        k.as_str().into()
+      }
+      _ => {
+        return Err(ParseError::expected(
+          vec![TokenKind::identifier("identifier")],
+          next_token.kind().clone(),
+          next_token.span(),
+          "binding identifier",
+        ))
+      }
+    };
+    check_strict_identifier(cursor, &name, next_token.span(), true)?;
+    cursor.record_binding(&name, next_token.span());
+    Ok(name)
+  }
+}
+
+/// Checks that `yield` or `await` can be used as an identifier in the current context.
+///
+/// `yield` is reserved inside generators and `await` inside async functions and modules, where
+/// they are operators instead.
+pub(super) fn check_yield_await(
+  keyword: Keyword,
+  span: Span,
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+) -> Result<(), ParseError> {
+  let reserved = match keyword {
+    Keyword::Yield => allow_yield.0,
+    Keyword::Await => allow_await.0,
+    _ => true,
+  };
+  if reserved {
+    return Err(ParseError::unexpected(
+      TokenKind::Keyword(keyword),
+      span,
+      "reserved word used as an identifier",
+    ));
+  }
+  Ok(())
+}
+
+/// Checks that an identifier can be used in strict mode code.
+///
+/// Binding identifiers can not be `eval` or `arguments` either.
+pub(super) fn check_strict_identifier<R>(
+  cursor: &Cursor<R>,
+  name: &str,
+  span: Span,
+  is_binding: bool,
+) -> Result<(), ParseError> {
+  if cursor.is_strict() {
+    check_strict_name(name, span, is_binding)
+  } else {
+    Ok(())
+  }
+}
+
+/// Checks that a name can be used in strict mode code, whatever the strictness of the cursor.
+///
+/// This is used for names parsed before a directive made the code strict, such as the name
+/// of a function with a `"use strict"` directive.
+pub(super) fn check_strict_name(
+  name: &str,
+  span: Span,
+  is_binding: bool,
+) -> Result<(), ParseError> {
+  if !STRICT_FORBIDDEN_IDENTIFIERS.contains(&name) {
+    return Ok(());
+  }
+  match name {
+    "eval" | "arguments" if is_binding => Err(ParseError::early(
+      format!("'{}' can not be a binding name in strict mode code", name),
+      span,
+    )),
+    "eval" | "arguments" => Ok(()),
+    _ => Err(ParseError::early(
+      format!("'{}' is a reserved word in strict mode code", name),
+      span,
+    )),
+  }
+}
+
+/// Returns `true` if the token can start a binding identifier.
+fn is_binding_identifier(kind: &TokenKind) -> bool {
+  matches!(
+    kind,
+    TokenKind::Identifier(_)
+      | TokenKind::Keyword(Keyword::Yield)
+      | TokenKind::Keyword(Keyword::Await)
+  )
+}
+
+/// Binding pattern parsing, either an object or an array pattern.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-BindingPattern
+#[derive(Debug, Clone, Copy)]
+pub(super) struct BindingPattern {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl BindingPattern {
+  /// Creates a new `BindingPattern` parser.
+  pub(super) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for BindingPattern
+where
+  R: Read,
+{
+  type Output = Pattern;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let next_token = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?;
+    match next_token.kind() {
+      TokenKind::Punctuator(Punctuator::OpenBlock) => {
+        ObjectBindingPattern::new(self.allow_yield, self.allow_await)
+          .parse(cursor)
+          .map(Pattern::from)
+      }
+      TokenKind::Punctuator(Punctuator::OpenBracket) => {
+        ArrayBindingPattern::new(self.allow_yield, self.allow_await)
+          .parse(cursor)
+          .map(Pattern::from)
+      }
+      _ => Err(ParseError::expected(
+        vec![
+          TokenKind::Punctuator(Punctuator::OpenBlock),
+          TokenKind::Punctuator(Punctuator::OpenBracket),
+        ],
+        next_token.kind().clone(),
+        next_token.span(),
+        "binding pattern",
+      )),
+    }
+  }
+}
+
+/// Binding element parsing, a binding identifier or pattern with an optional initializer.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-BindingElement
+#[derive(Debug, Clone, Copy)]
+pub(super) struct BindingElement {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl BindingElement {
+  /// Creates a new `BindingElement` parser.
+  pub(super) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for BindingElement
+where
+  R: Read,
+{
+  type Output = PatternElement;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let target = BindingTarget::new(self.allow_yield, self.allow_await).parse(cursor)?;
+    let default = if cursor.next_is(Punctuator::Assign)? {
+      Some(Initializer::new(true, self.allow_yield, self.allow_await).parse(cursor)?)
+    } else {
+      None
+    };
+    Ok(PatternElement::new(target, default))
+  }
+}
+
+/// Parses either a binding identifier or a binding pattern.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct BindingTarget {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl BindingTarget {
+  /// Creates a new `BindingTarget` parser.
+  pub(super) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for BindingTarget
+where
+  R: Read,
+{
+  type Output = Pattern;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let start = cursor.start()?;
+    match cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.kind() {
+      TokenKind::Punctuator(Punctuator::OpenBlock)
+      | TokenKind::Punctuator(Punctuator::OpenBracket) => {
+        BindingPattern::new(self.allow_yield, self.allow_await).parse(cursor)
+      }
+      _ => {
+        let name = BindingIdentifier::new(self.allow_yield, self.allow_await).parse(cursor)?;
+        Ok(
+          Identifier::new(name)
+            .with_span(cursor.span_from(start))
+            .into(),
+        )
+      }
+    }
+  }
+}
+
+/// Object binding pattern parsing.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ObjectBindingPattern
+#[derive(Debug, Clone, Copy)]
+struct ObjectBindingPattern {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl ObjectBindingPattern {
+  /// Creates a new `ObjectBindingPattern` parser.
+  fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for ObjectBindingPattern
+where
+  R: Read,
+{
+  type Output = ObjectPattern;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let start = cursor.start()?;
+    cursor.expect(Punctuator::OpenBlock, "object binding pattern")?;
+    let mut properties = Vec::new();
+    let mut rest = None;
+
+    loop {
+      if cursor.next_if(Punctuator::CloseBlock)?.is_some() {
+        break;
+      }
+
+      if cursor.next_if(Punctuator::Spread)?.is_some() {
+        let start = cursor.start()?;
+        let name = BindingIdentifier::new(self.allow_yield, self.allow_await).parse(cursor)?;
+        rest = Some(
+          Identifier::new(name)
+            .with_span(cursor.span_from(start))
+            .into(),
+        );
+        cursor.expect(Punctuator::CloseBlock, "object binding pattern")?;
+        break;
+      }
+
+      let is_shorthand = {
+        let tok = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?;
+        is_binding_identifier(tok.kind())
+          && !cursor
+            .peek(1)?
+            .is_some_and(|tk| tk.kind() == &TokenKind::Punctuator(Punctuator::Colon))
+      };
+
+      if is_shorthand {
+        let start = cursor.start()?;
+        let name = BindingIdentifier::new(self.allow_yield, self.allow_await).parse(cursor)?;
+        let name = Identifier::new(name).with_span(cursor.span_from(start));
+        let default = if cursor.next_is(Punctuator::Assign)? {
+          Some(Initializer::new(true, self.allow_yield, self.allow_await).parse(cursor)?)
+        } else {
+          None
+        };
+        properties.push(PropertyPattern::shorthand(name, default));
+      } else {
+        let key = PropertyName::new(self.allow_yield, self.allow_await).parse(cursor)?;
+        cursor.expect(Punctuator::Colon, "object binding pattern")?;
+        let value = BindingElement::new(self.allow_yield, self.allow_await).parse(cursor)?;
+        properties.push(PropertyPattern::new(key, value));
+      }
+
+      if cursor.next_if(Punctuator::CloseBlock)?.is_some() {
+        break;
+      }
+      cursor.expect(Punctuator::Comma, "object binding pattern")?;
+    }
+
+    Ok(ObjectPattern::new(properties, rest).with_span(cursor.span_from(start)))
+  }
+}
+
+/// Array binding pattern parsing.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ArrayBindingPattern
+#[derive(Debug, Clone, Copy)]
+struct ArrayBindingPattern {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl ArrayBindingPattern {
+  /// Creates a new `ArrayBindingPattern` parser.
+  fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for ArrayBindingPattern
+where
+  R: Read,
+{
+  type Output = ArrayPattern;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let start = cursor.start()?;
+    cursor.expect(Punctuator::OpenBracket, "array binding pattern")?;
+    let mut elements = Vec::new();
+    let mut rest = None;
+
+    loop {
+      match cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.kind() {
+        TokenKind::Punctuator(Punctuator::CloseBracket) => {
+          let _ = cursor.next()?.expect("] token vanished");
+          break;
+        }
+        TokenKind::Punctuator(Punctuator::Comma) => {
+          let _ = cursor.next()?.expect(", token vanished");
+          elements.push(None);
+          continue;
+        }
+        TokenKind::Punctuator(Punctuator::Spread) => {
+          let _ = cursor.next()?.expect("... token vanished");
+          rest = Some(BindingTarget::new(self.allow_yield, self.allow_await).parse(cursor)?);
+          cursor.expect(Punctuator::CloseBracket, "array binding pattern")?;
+          break;
+        }
+        _ => {}
+      }
+
+      elements.push(Some(
+        BindingElement::new(self.allow_yield, self.allow_await).parse(cursor)?,
+      ));
+
+      if cursor.next_if(Punctuator::CloseBracket)?.is_some() {
+        break;
+      }
+      cursor.expect(Punctuator::Comma, "array binding pattern")?;
+    }
+
+    Ok(ArrayPattern::new(elements, rest).with_span(cursor.span_from(start)))
+  }
+}
+
+/// Returns `true` if the expression can be the target of a compound assignment or an update
+/// expression.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-static-semantics-assignmenttargettype
+pub(super) fn is_simple_assignment_target(node: &Node) -> bool {
+  match node {
+    Node::Identifier(_) => true,
+    Node::GetConstField(field) => !field.optional(),
+    Node::GetField(field) => !field.optional(),
+    Node::GetPrivateField(field) => !field.optional(),
+    _ => false,
+  }
+}
+
+/// Checks that an assignment target is not `eval` or `arguments` in strict mode code.
+pub(super) fn check_strict_assignment_target<R>(
+  cursor: &Cursor<R>,
+  node: &Node,
+  span: Span,
+) -> Result<(), ParseError> {
+  match node {
+    Node::Identifier(ident)
+      if cursor.is_strict() && matches!(ident.name().as_str(), "eval" | "arguments") =>
+    {
+      Err(ParseError::early(
+        format!(
+          "'{}' can not be assigned to in strict mode code",
+          ident.name()
+        ),
+        span,
+      ))
+    }
+    _ => Ok(()),
+  }
+}
+
+/// Checks that no identifier target of a destructuring assignment pattern is `eval` or
+/// `arguments` in strict mode code, including shorthand properties and nested patterns.
+pub(super) fn check_strict_pattern<R>(
+  cursor: &Cursor<R>,
+  pattern: &Pattern,
+  span: Span,
+) -> Result<(), ParseError> {
+  if !cursor.is_strict() {
+    return Ok(());
+  }
+  match pattern
+    .bound_names()
+    .into_iter()
+    .find(|name| matches!(name.as_str(), "eval" | "arguments"))
+  {
+    Some(name) => Err(ParseError::early(
+      format!("'{}' can not be assigned to in strict mode code", name),
+      span,
+    )),
+    None => Ok(()),
+  }
+}
+
+/// Reinterprets an expression as an assignment pattern.
+///
+/// This is used when an object or array literal (or any other left hand side expression) turns
+/// out to be the target of an assignment, or the left hand side of a `for ... in/of` loop.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-destructuring-assignment
+pub(super) fn expression_to_pattern(
+  node: &Node,
+  position: Position,
+) -> Result<Pattern, ParseError> {
+  match node {
+    Node::Identifier(ident) => Ok(ident.clone().into()),
+    Node::GetConstField(_) | Node::GetField(_) | Node::GetPrivateField(_)
+      if is_simple_assignment_target(node) =>
+    {
+      Ok(Pattern::Member(ArenaBox::new(node.clone())))
+    }
+    Node::Object(obj) => {
+      let mut properties = Vec::new();
+      let mut rest = None;
+      for (i, prop) in obj.properties().iter().enumerate() {
+        match prop {
+          PropertyDefinition::IdentifierReference(ident, _) => {
+            properties.push(PropertyPattern::shorthand(ident.clone(), None));
+          }
+          PropertyDefinition::Property(key, value, _) => {
+            let element = expression_to_element(value, position)?;
+            match (key.literal(), element.target()) {
+              (Some(key), Pattern::Identifier(ident)) if key == ident.name().as_str() => {
+                properties.push(PropertyPattern::shorthand(
+                  ident.clone(),
+                  element.default().cloned(),
+                ));
+              }
+              _ => properties.push(PropertyPattern::new(key.clone(), element)),
+            }
+          }
+          PropertyDefinition::SpreadObject(target, _) => {
+            if i + 1 != obj.properties().len() {
+              return Err(ParseError::general(
+                "rest element must be last element",
+                position,
+              ));
+            }
+            match expression_to_pattern(target, position)? {
+              pattern @ Pattern::Identifier(_) | pattern @ Pattern::Member(_) => {
+                rest = Some(pattern)
+              }
+              _ => {
+                return Err(ParseError::general(
+                  "`...` must be followed by an assignable reference in assignment contexts",
+                  position,
+                ))
+              }
+            }
+          }
+          PropertyDefinition::MethodDefinition(_, _, _, _) => {
+            return Err(ParseError::general(
+              "invalid destructuring assignment target",
+              position,
+            ))
+          }
+        }
+      }
+      Ok(
+        ObjectPattern::new(properties, rest)
+          .with_span(obj.span())
+          .into(),
+      )
+    }
+    Node::ArrayDecl(arr) => {
+      let mut elements = Vec::new();
+      let mut rest = None;
+      for (i, elem) in arr.elements().iter().enumerate() {
+        match elem {
+          Node::Empty(_) => elements.push(None),
+          Node::Spread(spread) => {
+            if i + 1 != arr.elements().len() {
+              return Err(ParseError::general(
+                "rest element must be last element",
+                position,
+              ));
+            }
+            if let Node::Assign(_) = spread.val() {
+              return Err(ParseError::general(
+                "rest element may not have a default initializer",
+                position,
+              ));
+            }
+            rest = Some(expression_to_pattern(spread.val(), position)?);
+          }
+          _ => elements.push(Some(expression_to_element(elem, position)?)),
+        }
+      }
+      Ok(
+        ArrayPattern::new(elements, rest)
+          .with_span(arr.span())
+          .into(),
+      )
+    }
+    _ => Err(ParseError::general(
+      "invalid destructuring assignment target",
+      position,
+    )),
+  }
+}
+
+/// Reinterprets an element of an object or array literal as a pattern element, turning a
+/// plain assignment into a target with a default value.
+fn expression_to_element(node: &Node, position: Position) -> Result<PatternElement, ParseError> {
+  match node {
+    Node::Assign(assign) => Ok(PatternElement::new(
+      assign.lhs().clone(),
+      Some(assign.rhs().clone()),
+    )),
+    _ => Ok(PatternElement::new(
+      expression_to_pattern(node, position)?,
+      None,
+    )),
+  }
+}
@@ -0,0 +1,179 @@
+use crate::syntax::{
+  ast::{
+    arena::ArenaBox,
+    node::{
+      ArrayPattern, Assign, Catch, Declaration, DeclarationList, ForOfLoop, GetConstField,
+      Identifier, IterableLoopInitializer, ObjectPattern, Pattern, PatternElement, PropertyName,
+      PropertyPattern, Try,
+    },
+    Const, Node,
+  },
+  parser::tests::{check_invalid, check_parser},
+};
+
+#[test]
+fn object_binding_pattern() {
+  check_parser(
+    "let { a, b: [c] = d, ...e } = f;",
+    vec![DeclarationList::Let(
+      vec![Declaration::new(
+        ObjectPattern::new(
+          vec![
+            PropertyPattern::shorthand("a", None),
+            PropertyPattern::new(
+              PropertyName::from("b"),
+              PatternElement::new(
+                ArrayPattern::new(
+                  vec![Some(PatternElement::new(Identifier::from("c"), None))],
+                  None,
+                ),
+                Some(Identifier::from("d").into()),
+              ),
+            ),
+          ],
+          Some(Identifier::from("e").into()),
+        ),
+        Some(Identifier::from("f").into()),
+      )]
+      .into(),
+    )
+    .into()],
+  );
+}
+
+#[test]
+fn array_binding_pattern() {
+  check_parser(
+    "var [a, , b = 1, ...[c]] = d;",
+    vec![DeclarationList::Var(
+      vec![Declaration::new(
+        ArrayPattern::new(
+          vec![
+            Some(PatternElement::new(Identifier::from("a"), None)),
+            None,
+            Some(PatternElement::new(
+              Identifier::from("b"),
+              Some(Const::from(1).into()),
+            )),
+          ],
+          Some(
+            ArrayPattern::new(
+              vec![Some(PatternElement::new(Identifier::from("c"), None))],
+              None,
+            )
+            .into(),
+          ),
+        ),
+        Some(Identifier::from("d").into()),
+      )]
+      .into(),
+    )
+    .into()],
+  );
+}
+
+#[test]
+fn invalid_binding_patterns() {
+  check_invalid("let [...a, b] = c;");
+  check_invalid("let { ...a, b } = c;");
+  check_invalid("let [a.b] = c;");
+  check_invalid("function f(...a = 1) {}");
+}
+
+#[test]
+fn destructuring_assignment() {
+  check_parser(
+    "[a, { b = 1, c: d.e }] = f",
+    vec![Assign::new(
+      ArrayPattern::new(
+        vec![
+          Some(PatternElement::new(Identifier::from("a"), None)),
+          Some(PatternElement::new(
+            ObjectPattern::new(
+              vec![
+                PropertyPattern::shorthand("b", Some(Const::from(1).into())),
+                PropertyPattern::new(
+                  PropertyName::from("c"),
+                  PatternElement::new(
+                    Pattern::Member(ArenaBox::new(
+                      GetConstField::new(Identifier::from("d"), "e").into(),
+                    )),
+                    None,
+                  ),
+                ),
+              ],
+              None,
+            ),
+            None,
+          )),
+        ],
+        None,
+      ),
+      Identifier::from("f"),
+    )
+    .into()],
+  );
+}
+
+#[test]
+fn invalid_assignment_targets() {
+  check_invalid("[a + b] = c");
+  check_invalid("({ a() {} } = b)");
+  check_invalid("[...a = 1] = b");
+  check_invalid("[...a, b] = c");
+  check_invalid("({ ...{ a } } = b)");
+  check_invalid("f() = 1");
+}
+
+#[test]
+fn for_of_patterns() {
+  check_parser(
+    "for ({ a = 1 } of b);",
+    vec![ForOfLoop::new(
+      IterableLoopInitializer::Pattern(
+        ObjectPattern::new(
+          vec![PropertyPattern::shorthand("a", Some(Const::from(1).into()))],
+          None,
+        )
+        .into(),
+      ),
+      Identifier::from("b"),
+      Node::empty(),
+    )
+    .into()],
+  );
+  check_parser(
+    "for (const [a] of b);",
+    vec![ForOfLoop::new(
+      IterableLoopInitializer::Const(
+        ArrayPattern::new(
+          vec![Some(PatternElement::new(Identifier::from("a"), None))],
+          None,
+        )
+        .into(),
+      ),
+      Identifier::from("b"),
+      Node::empty(),
+    )
+    .into()],
+  );
+}
+
+#[test]
+fn catch_pattern() {
+  check_parser(
+    "try {} catch ({ message }) {}",
+    vec![Try::new(
+      vec![],
+      Some(Catch::new(
+        Some(ObjectPattern::new(
+          vec![PropertyPattern::shorthand("message", None)],
+          None,
+        )),
+        vec![],
+      )),
+      None,
+    )
+    .into()],
+  );
+}
@@ -0,0 +1,78 @@
+//! Block statement parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/block
+//! [spec]: https://tc39.es/ecma262/#sec-block
+
+use super::StatementList;
+use crate::syntax::{
+  ast::{node, Punctuator},
+  parser::{AllowAwait, AllowReturn, AllowYield, Cursor, ParseError, ScopeKind, TokenParser},
+};
+use std::io::Read;
+
+/// A `BlockStatement` is equivalent to a `Block`.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-BlockStatement
+pub(super) type BlockStatement = Block;
+
+/// A `Block` is a list of statements wrapped in braces.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/block
+/// [spec]: https://tc39.es/ecma262/#prod-Block
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Block {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+  allow_return: AllowReturn,
+}
+
+impl Block {
+  /// Creates a new `Block` parser.
+  pub(super) fn new<Y, A, R>(allow_yield: Y, allow_await: A, allow_return: R) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+    R: Into<AllowReturn>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+      allow_return: allow_return.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for Block
+where
+  R: Read,
+{
+  type Output = node::Block;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let start = cursor.start()?;
+    cursor.expect(Punctuator::OpenBlock, "block")?;
+    if cursor.next_if(Punctuator::CloseBlock)?.is_some() {
+      return Ok(node::Block::from(vec![]).with_span(cursor.span_from(start)));
+    }
+
+    cursor.push_scope(ScopeKind::Block);
+    let statement_list =
+      StatementList::new(self.allow_yield, self.allow_await, self.allow_return, true)
+        .parse(cursor)?;
+    cursor.expect(Punctuator::CloseBlock, "block")?;
+    cursor.pop_scope();
+
+    Ok(node::Block::from(statement_list).with_span(cursor.span_from(start)))
+  }
+}
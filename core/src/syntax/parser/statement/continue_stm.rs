@@ -0,0 +1,65 @@
+//! Continue expression parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/continue
+//! [spec]: https://tc39.es/ecma262/#sec-continue-statement
+
+use super::label_identifier;
+use crate::syntax::{
+  ast::{node::Continue, Keyword, Node, Sym},
+  parser::{AllowAwait, AllowYield, Cursor, ParseError, TokenParser},
+};
+use std::io::Read;
+
+/// Continue statement parsing
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/continue
+/// [spec]: https://tc39.es/ecma262/#prod-ContinueStatement
+#[derive(Debug, Clone, Copy)]
+pub(super) struct ContinueStatement {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl ContinueStatement {
+  /// Creates a new `ContinueStatement` parser.
+  pub(super) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for ContinueStatement
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let tok = cursor.expect(Keyword::Continue, "continue statement")?;
+    let start = cursor.start()?;
+    let label = label_identifier(cursor, self.allow_yield, self.allow_await)?;
+    let label_span = label.as_ref().map(|_| cursor.span_from(start));
+    cursor.check_continue(label.as_deref(), tok.span())?;
+    cursor.expect_semicolon("continue statement")?;
+
+    Ok(
+      Continue::new::<_, Sym>(label)
+        .with_label_span(label_span)
+        .into(),
+    )
+  }
+}
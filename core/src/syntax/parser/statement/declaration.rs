@@ -0,0 +1,313 @@
+//! Declaration parsing.
+//!
+//! More information:
+//!  - [ECMAScript specification][spec]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-declarations-and-the-variable-statement
+
+use crate::syntax::{
+  ast::{
+    arena::ArenaBox,
+    node::{self, DeclarationList, FunctionDecl, FunctionKind, Pattern},
+    Keyword, Node, Position, Punctuator,
+  },
+  lexer::TokenKind,
+  parser::{
+    class::ClassDeclaration,
+    expression::Initializer,
+    function::function_parts,
+    pattern::{check_strict_name, BindingIdentifier, BindingTarget},
+    typescript, AllowAwait, AllowIn, AllowYield, BindingKind, Cursor, ParseError, TokenParser,
+  },
+};
+use std::io::Read;
+
+/// Parses a declaration: a function, class, or `let`/`const` declaration.
+///
+/// An identifier can only start a declaration as the `async` of an async function.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-Declaration
+#[derive(Debug, Clone, Copy)]
+pub(in crate::syntax::parser) struct Declaration {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl Declaration {
+  /// Creates a new `Declaration` parser.
+  pub(in crate::syntax::parser) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for Declaration
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let start = cursor.start()?;
+    let tok = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?;
+
+    let node = match tok.kind() {
+      TokenKind::Keyword(Keyword::Function) | TokenKind::Identifier(_) => {
+        FunctionDeclaration::new(self.allow_yield, self.allow_await)
+          .parse(cursor)
+          .map(Node::from)
+      }
+      TokenKind::Keyword(Keyword::Class) => {
+        ClassDeclaration::new(self.allow_yield, self.allow_await).parse(cursor)
+      }
+      TokenKind::Keyword(Keyword::Let) | TokenKind::Keyword(Keyword::Const) => {
+        let list =
+          LexicalDeclaration::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
+        cursor.expect_semicolon("lexical declaration")?;
+        Ok(list.into())
+      }
+      _ => Err(ParseError::unexpected(
+        tok.kind().clone(),
+        tok.span(),
+        "expected a declaration",
+      )),
+    }?;
+    Ok(node.with_span(cursor.span_from(start)))
+  }
+}
+
+/// Function declaration parsing, including generator and async function declarations.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/function
+/// [spec]: https://tc39.es/ecma262/#prod-FunctionDeclaration
+#[derive(Debug, Clone, Copy)]
+struct FunctionDeclaration {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl FunctionDeclaration {
+  /// Creates a new `FunctionDeclaration` parser.
+  fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for FunctionDeclaration
+where
+  R: Read,
+{
+  type Output = FunctionDecl;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let start = cursor.start()?;
+    let is_async = cursor.next_if(TokenKind::identifier("async"))?.is_some();
+    cursor.expect(Keyword::Function, "function declaration")?;
+    let is_generator = cursor.next_if(Punctuator::Mul)?.is_some();
+    let kind = FunctionKind::new(is_async, is_generator);
+
+    // The name is bound in the enclosing scope, so it follows the enclosing context.
+    let mark = cursor.bindings_mark();
+    let span = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.span();
+    let name = BindingIdentifier::new(self.allow_yield, self.allow_await).parse(cursor)?;
+    let binding = if is_async || is_generator {
+      BindingKind::SpecialFunction
+    } else {
+      BindingKind::Function
+    };
+    cursor.declare_bindings(mark, binding)?;
+    let (params, body) = function_parts(cursor, kind, "function declaration")?;
+
+    // A directive in the body also applies to the name.
+    if body.strict() {
+      check_strict_name(&name, span, true)?;
+    }
+
+    Ok(
+      FunctionDecl::new(name, params, body)
+        .with_kind(kind)
+        .with_span(cursor.span_from(start))
+        .with_name_span(span),
+    )
+  }
+}
+
+/// Parses a `let` or `const` declaration list, without the trailing semicolon.
+///
+/// Missing initializers are checked here, so this must not be used for the head of a
+/// `for ... in/of` loop.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/let
+/// [spec]: https://tc39.es/ecma262/#prod-LexicalDeclaration
+#[derive(Debug, Clone, Copy)]
+pub(super) struct LexicalDeclaration {
+  allow_in: AllowIn,
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl LexicalDeclaration {
+  /// Creates a new `LexicalDeclaration` parser.
+  pub(super) fn new<I, Y, A>(allow_in: I, allow_yield: Y, allow_await: A) -> Self
+  where
+    I: Into<AllowIn>,
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_in: allow_in.into(),
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for LexicalDeclaration
+where
+  R: Read,
+{
+  type Output = DeclarationList;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let tok = cursor.next()?.ok_or(ParseError::AbruptEnd)?;
+    let mark = cursor.bindings_mark();
+    let list = match tok.kind() {
+      TokenKind::Keyword(Keyword::Let) => DeclarationList::Let(
+        BindingList::new(self.allow_in, self.allow_yield, self.allow_await).parse(cursor)?,
+      ),
+      TokenKind::Keyword(Keyword::Const) => DeclarationList::Const(
+        BindingList::new(self.allow_in, self.allow_yield, self.allow_await).parse(cursor)?,
+      ),
+      _ => {
+        return Err(ParseError::expected(
+          vec![Keyword::Let.into(), Keyword::Const.into()],
+          tok.kind().clone(),
+          tok.span(),
+          "lexical declaration",
+        ))
+      }
+    };
+    check_initializers(&list, tok.span().start())?;
+    cursor.declare_bindings(mark, BindingKind::Lexical)?;
+    Ok(list)
+  }
+}
+
+/// Checks that every `const` declaration and every destructuring declaration has an
+/// initializer.
+pub(super) fn check_initializers(
+  list: &DeclarationList,
+  position: Position,
+) -> Result<(), ParseError> {
+  for decl in list.as_ref() {
+    if decl.init().is_some() {
+      continue;
+    }
+    if let DeclarationList::Const(_) = list {
+      return Err(ParseError::general(
+        "missing initializer in const declaration",
+        position,
+      ));
+    }
+    if !matches!(decl.target(), Pattern::Identifier(_)) {
+      return Err(ParseError::general(
+        "missing initializer in destructuring declaration",
+        position,
+      ));
+    }
+  }
+  Ok(())
+}
+
+/// Parses a comma separated list of bindings, each one an identifier or a pattern with an
+/// optional initializer.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-BindingList
+#[derive(Debug, Clone, Copy)]
+pub(super) struct BindingList {
+  allow_in: AllowIn,
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl BindingList {
+  /// Creates a new `BindingList` parser.
+  pub(super) fn new<I, Y, A>(allow_in: I, allow_yield: Y, allow_await: A) -> Self
+  where
+    I: Into<AllowIn>,
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_in: allow_in.into(),
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for BindingList
+where
+  R: Read,
+{
+  type Output = ArenaBox<[node::Declaration]>;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let mut list = Vec::new();
+
+    loop {
+      let start = cursor.start()?;
+      let target = BindingTarget::new(self.allow_yield, self.allow_await).parse(cursor)?;
+      if cursor.is_typescript() {
+        // A definite assignment assertion, `let x!: number`.
+        let definite = cursor.next_is(Punctuator::Not)?
+          && cursor
+            .peek(1)?
+            .is_some_and(|tk| tk.kind() == &TokenKind::Punctuator(Punctuator::Colon));
+        if definite {
+          let _ = cursor.next()?.expect("! token vanished");
+        }
+        typescript::type_annotation(cursor)?;
+      }
+      let init = if cursor.next_is(Punctuator::Assign)? {
+        Some(Initializer::new(self.allow_in, self.allow_yield, self.allow_await).parse(cursor)?)
+      } else {
+        None
+      };
+      list.push(node::Declaration::new(target, init).with_span(cursor.span_from(start)));
+
+      if cursor.next_if(Punctuator::Comma)?.is_none() {
+        break;
+      }
+    }
+
+    Ok(list.into())
+  }
+}
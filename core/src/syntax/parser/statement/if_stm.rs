@@ -0,0 +1,105 @@
+//! If statement parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/if...else
+//! [spec]: https://tc39.es/ecma262/#sec-if-statement
+
+use super::{sloppy_function_declaration, Statement};
+use crate::syntax::{
+  ast::{
+    node::{Block, If},
+    Keyword, Node, Punctuator,
+  },
+  parser::{
+    expression::Expression, AllowAwait, AllowReturn, AllowYield, Cursor, ParseError, ScopeKind,
+    TokenParser,
+  },
+};
+use std::io::Read;
+
+/// If statement parsing.
+///
+/// An `if` statement will have a condition, a block statemet, and an optional `else` statement.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/if...else
+/// [spec]: https://tc39.es/ecma262/#prod-IfStatement
+#[derive(Debug, Clone, Copy)]
+pub(super) struct IfStatement {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+  allow_return: AllowReturn,
+}
+
+impl IfStatement {
+  /// Creates a new `IfStatement` parser.
+  pub(super) fn new<Y, A, R>(allow_yield: Y, allow_await: A, allow_return: R) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+    R: Into<AllowReturn>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+      allow_return: allow_return.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for IfStatement
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    cursor.expect(Keyword::If, "if statement")?;
+    cursor.expect(Punctuator::OpenParen, "if statement")?;
+
+    let cond = Expression::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
+
+    cursor.expect(Punctuator::CloseParen, "if statement")?;
+
+    let then_stm = self.clause(cursor)?;
+
+    let else_stm = if cursor.next_if(Keyword::Else)?.is_some() {
+      Some(self.clause(cursor)?)
+    } else {
+      None
+    };
+
+    Ok(If::new::<_, _, Node, _>(cond, then_stm, else_stm).into())
+  }
+}
+
+impl IfStatement {
+  /// Parses the `then` or `else` clause of the statement.
+  ///
+  /// A function declaration in sloppy mode code is parsed as if it was the only statement of a
+  /// block.
+  fn clause<R>(self, cursor: &mut Cursor<R>) -> Result<Node, ParseError>
+  where
+    R: Read,
+  {
+    if !cursor.next_is(Keyword::Function)? {
+      return Statement::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor);
+    }
+
+    let start = cursor.start()?;
+    cursor.push_scope(ScopeKind::Block);
+    let function = sloppy_function_declaration(cursor, self.allow_yield, self.allow_await)?;
+    cursor.pop_scope();
+    Ok(
+      Block::from(vec![function])
+        .with_span(cursor.span_from(start))
+        .into(),
+    )
+  }
+}
@@ -0,0 +1,356 @@
+//! Iteration statement parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Guide/Loops_and_iteration
+//! [spec]: https://tc39.es/ecma262/#sec-iteration-statements
+
+use super::{
+  declaration::{check_initializers, BindingList},
+  is_contextual, Statement,
+};
+use crate::syntax::{
+  ast::{
+    node::{
+      DeclarationList, DoWhileLoop, ForInLoop, ForLoop, ForOfLoop, IterableLoopInitializer,
+      WhileLoop,
+    },
+    Keyword, Node, Punctuator,
+  },
+  lexer::TokenKind,
+  parser::{
+    expression::{AssignmentExpression, Expression, LeftHandSideExpression},
+    pattern::{check_strict_pattern, expression_to_pattern},
+    AllowAwait, AllowReturn, AllowYield, BindingKind, Cursor, ParseError, ScopeKind, TokenParser,
+  },
+};
+use std::io::Read;
+
+/// While statement parsing
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/while
+/// [spec]: https://tc39.es/ecma262/#sec-while-statement
+#[derive(Debug, Clone, Copy)]
+pub(super) struct WhileStatement {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+  allow_return: AllowReturn,
+}
+
+impl WhileStatement {
+  /// Creates a new `WhileStatement` parser.
+  pub(super) fn new<Y, A, R>(allow_yield: Y, allow_await: A, allow_return: R) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+    R: Into<AllowReturn>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+      allow_return: allow_return.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for WhileStatement
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    cursor.expect(Keyword::While, "while statement")?;
+    cursor.expect(Punctuator::OpenParen, "while statement")?;
+
+    let cond = Expression::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
+
+    cursor.expect(Punctuator::CloseParen, "while statement")?;
+
+    cursor.enter_breakable(true);
+    let body =
+      Statement::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor)?;
+    cursor.exit_breakable(true);
+
+    Ok(WhileLoop::new(cond, body).into())
+  }
+}
+
+/// Do...while statement parsing
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/do...while
+/// [spec]: https://tc39.es/ecma262/#sec-do-while-statement
+#[derive(Debug, Clone, Copy)]
+pub(super) struct DoWhileStatement {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+  allow_return: AllowReturn,
+}
+
+impl DoWhileStatement {
+  /// Creates a new `DoWhileStatement` parser.
+  pub(super) fn new<Y, A, R>(allow_yield: Y, allow_await: A, allow_return: R) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+    R: Into<AllowReturn>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+      allow_return: allow_return.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for DoWhileStatement
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    cursor.expect(Keyword::Do, "do while statement")?;
+
+    cursor.enter_breakable(true);
+    let body =
+      Statement::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor)?;
+    cursor.exit_breakable(true);
+
+    cursor.expect(Keyword::While, "do while statement")?;
+    cursor.expect(Punctuator::OpenParen, "do while statement")?;
+
+    let cond = Expression::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
+
+    cursor.expect(Punctuator::CloseParen, "do while statement")?;
+
+    // A semicolon is always inserted after a do-while statement, even on the same line.
+    let _ = cursor.next_if(Punctuator::Semicolon)?;
+
+    Ok(DoWhileLoop::new(body, cond).into())
+  }
+}
+
+/// For statement parsing, this includes `for ... in`, `for ... of` and `for await ... of` loops.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/for
+/// [spec]: https://tc39.es/ecma262/#sec-for-statement
+#[derive(Debug, Clone, Copy)]
+pub(super) struct ForStatement {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+  allow_return: AllowReturn,
+}
+
+impl ForStatement {
+  /// Creates a new `ForStatement` parser.
+  pub(super) fn new<Y, A, R>(allow_yield: Y, allow_await: A, allow_return: R) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+    R: Into<AllowReturn>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+      allow_return: allow_return.into(),
+    }
+  }
+
+  /// Parses the rest of a `for ... in` or `for ... of` loop, once its left hand side is known.
+  ///
+  /// `for await` loops must be `for ... of` loops.
+  fn parse_in_of<R>(
+    self,
+    cursor: &mut Cursor<R>,
+    init: IterableLoopInitializer,
+    is_await: bool,
+  ) -> Result<Node, ParseError>
+  where
+    R: Read,
+  {
+    if let Some(tok) = cursor.next_if(Keyword::In)? {
+      if is_await {
+        return Err(ParseError::unexpected(
+          tok.kind().clone(),
+          tok.span(),
+          "for await loops must be for...of loops",
+        ));
+      }
+      let expr = Expression::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
+      cursor.expect(Punctuator::CloseParen, "for in statement")?;
+      let body =
+        Statement::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor)?;
+      return Ok(ForInLoop::new(init, expr, body).into());
+    }
+
+    let _ = cursor.next()?.expect("of token vanished");
+    let iterable =
+      AssignmentExpression::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
+    cursor.expect(Punctuator::CloseParen, "for of statement")?;
+    let body =
+      Statement::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor)?;
+    if is_await {
+      Ok(ForOfLoop::new_await(init, iterable, body).into())
+    } else {
+      Ok(ForOfLoop::new(init, iterable, body).into())
+    }
+  }
+
+  /// Parses the loop, from the `for` keyword to the end of its body.
+  fn parse_loop<R>(self, cursor: &mut Cursor<R>) -> Result<Node, ParseError>
+  where
+    R: Read,
+  {
+    cursor.expect(Keyword::For, "for statement")?;
+    let is_await = self.allow_await.0 && cursor.next_if(Keyword::Await)?.is_some();
+    cursor.expect(Punctuator::OpenParen, "for statement")?;
+
+    let tok = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.clone();
+    let start = tok.span().start();
+
+    let init = match tok.kind() {
+      TokenKind::Punctuator(Punctuator::Semicolon) => None,
+      TokenKind::Keyword(kw @ Keyword::Var)
+      | TokenKind::Keyword(kw @ Keyword::Let)
+      | TokenKind::Keyword(kw @ Keyword::Const) => {
+        let _ = cursor.next()?.expect("declaration token vanished");
+        let mark = cursor.bindings_mark();
+        let list = BindingList::new(false, self.allow_yield, self.allow_await).parse(cursor)?;
+        let kind = if *kw == Keyword::Var {
+          BindingKind::Var
+        } else {
+          BindingKind::Lexical
+        };
+        cursor.declare_bindings(mark, kind)?;
+
+        if list.len() == 1 && list[0].init().is_none() && is_in_or_of(cursor, 0)? {
+          let target = list[0].target().clone();
+          let init = match kw {
+            Keyword::Var => IterableLoopInitializer::Var(target),
+            Keyword::Let => IterableLoopInitializer::Let(target),
+            _ => IterableLoopInitializer::Const(target),
+          };
+          return self.parse_in_of(cursor, init, is_await);
+        }
+
+        let list = match kw {
+          Keyword::Var => DeclarationList::Var(list),
+          Keyword::Let => DeclarationList::Let(list),
+          _ => DeclarationList::Const(list),
+        };
+        check_initializers(&list, start)?;
+        Some(Node::from(list).with_span(cursor.span_from(start)))
+      }
+      TokenKind::Punctuator(Punctuator::OpenBracket)
+      | TokenKind::Punctuator(Punctuator::OpenBlock)
+        if cursor
+          .find_matching_bracket(0)?
+          .map_or(Ok(false), |close| is_in_or_of(cursor, close + 1))? =>
+      {
+        // A destructuring assignment target, `for ([a, b] of c)`. The literal may contain
+        // shorthand initializers, which are valid once it is reinterpreted as a pattern.
+        let mark = cursor.cover_errors_mark();
+        let lhs = LeftHandSideExpression::new(self.allow_yield, self.allow_await).parse(cursor)?;
+        let pattern = expression_to_pattern(&lhs, start)?;
+        check_strict_pattern(cursor, &pattern, cursor.span_from(start))?;
+        cursor.discard_cover_errors(mark);
+        return self.parse_in_of(cursor, IterableLoopInitializer::Pattern(pattern), is_await);
+      }
+      _ => {
+        let expr = Expression::new(false, self.allow_yield, self.allow_await).parse(cursor)?;
+        // `for (async of x)` would be ambiguous with an async arrow function, `for (async of =>
+        // {};;)`, so a plain `async` can't be the target of a `for ... of` loop.
+        let is_async = cursor.span_from(start) == tok.span()
+          && matches!(tok.kind(), TokenKind::Identifier(ident) if ident.as_ref() == "async");
+        if !is_await && is_async && is_contextual(cursor, 0, "of")? {
+          return Err(ParseError::early(
+            "the target of a for-of loop can't be `async`",
+            tok.span(),
+          ));
+        }
+        if is_in_or_of(cursor, 0)? {
+          let pattern = expression_to_pattern(&expr, start)?;
+          check_strict_pattern(cursor, &pattern, cursor.span_from(start))?;
+          return self.parse_in_of(cursor, IterableLoopInitializer::Pattern(pattern), is_await);
+        }
+        Some(expr)
+      }
+    };
+
+    if is_await {
+      let tok = cursor.next()?.ok_or(ParseError::AbruptEnd)?;
+      return Err(ParseError::expected(
+        vec![TokenKind::identifier("of")],
+        tok.kind().clone(),
+        tok.span(),
+        "for await statement",
+      ));
+    }
+    cursor.expect(Punctuator::Semicolon, "for statement")?;
+
+    let cond = if cursor.next_is(Punctuator::Semicolon)? {
+      None
+    } else {
+      Some(Expression::new(true, self.allow_yield, self.allow_await).parse(cursor)?)
+    };
+
+    cursor.expect(Punctuator::Semicolon, "for statement")?;
+
+    let step = if cursor.next_is(Punctuator::CloseParen)? {
+      None
+    } else {
+      Some(Expression::new(true, self.allow_yield, self.allow_await).parse(cursor)?)
+    };
+
+    cursor.expect(Punctuator::CloseParen, "for statement")?;
+
+    let body =
+      Statement::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor)?;
+
+    Ok(ForLoop::new(init, cond, step, body).into())
+  }
+}
+
+/// Checks if the significant token `skip_n` positions ahead is `in` or `of`.
+fn is_in_or_of<R>(cursor: &mut Cursor<R>, skip_n: usize) -> Result<bool, ParseError>
+where
+  R: Read,
+{
+  Ok(match cursor.peek(skip_n)?.map(|tk| tk.kind()) {
+    Some(TokenKind::Keyword(Keyword::In)) => true,
+    Some(TokenKind::Identifier(ident)) => ident.as_ref() == "of",
+    _ => false,
+  })
+}
+
+impl<R> TokenParser<R> for ForStatement
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    // Declarations in the head are scoped to the loop.
+    cursor.push_scope(ScopeKind::Block);
+    cursor.enter_breakable(true);
+    let node = self.parse_loop(cursor)?;
+    cursor.exit_breakable(true);
+    cursor.pop_scope();
+    Ok(node)
+  }
+}
@@ -0,0 +1,98 @@
+//! Labelled statement parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/label
+//! [spec]: https://tc39.es/ecma262/#sec-labelled-statements
+
+use super::{sloppy_function_declaration, LabelIdentifier, Statement};
+use crate::syntax::{
+  ast::{node::Labelled, Keyword, Node, Punctuator},
+  lexer::TokenKind,
+  parser::{AllowAwait, AllowReturn, AllowYield, Cursor, ParseError, TokenParser},
+};
+use std::io::Read;
+
+/// Labelled statement parsing.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/label
+/// [spec]: https://tc39.es/ecma262/#prod-LabelledStatement
+#[derive(Debug, Clone, Copy)]
+pub(super) struct LabelledStatement {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+  allow_return: AllowReturn,
+}
+
+impl LabelledStatement {
+  /// Creates a new `LabelledStatement` parser.
+  pub(super) fn new<Y, A, R>(allow_yield: Y, allow_await: A, allow_return: R) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+    R: Into<AllowReturn>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+      allow_return: allow_return.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for LabelledStatement
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let span = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.span();
+    let label = LabelIdentifier::new(self.allow_yield, self.allow_await).parse(cursor)?;
+    cursor.expect(Punctuator::Colon, "labelled statement")?;
+
+    let is_loop = labels_iteration(cursor)?;
+    cursor.push_label(&label, span, is_loop)?;
+    let statement = if cursor.next_is(Keyword::Function)? {
+      sloppy_function_declaration(cursor, self.allow_yield, self.allow_await)?
+    } else {
+      Statement::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor)?
+    };
+    cursor.pop_label();
+
+    Ok(Labelled::new(label, statement).with_label_span(span).into())
+  }
+}
+
+/// Checks if the labelled statement is an iteration statement, possibly with more labels in
+/// between, so that `continue` can target the label.
+fn labels_iteration<R>(cursor: &mut Cursor<R>) -> Result<bool, ParseError>
+where
+  R: Read,
+{
+  let mut skip = 0;
+  loop {
+    let is_label = match cursor.peek(skip)?.map(|tk| tk.kind()) {
+      Some(TokenKind::Keyword(Keyword::For))
+      | Some(TokenKind::Keyword(Keyword::While))
+      | Some(TokenKind::Keyword(Keyword::Do)) => return Ok(true),
+      Some(TokenKind::Identifier(_))
+      | Some(TokenKind::Keyword(Keyword::Yield))
+      | Some(TokenKind::Keyword(Keyword::Await)) => true,
+      _ => false,
+    };
+    let colon = cursor
+      .peek(skip + 1)?
+      .is_some_and(|tk| tk.kind() == &TokenKind::Punctuator(Punctuator::Colon));
+    if !is_label || !colon {
+      return Ok(false);
+    }
+    skip += 2;
+  }
+}
@@ -0,0 +1,621 @@
+//! Statement and declaration parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements
+//! [spec]: https://tc39.es/ecma262/#sec-ecmascript-language-statements-and-declarations
+
+mod block;
+mod break_stm;
+mod continue_stm;
+mod declaration;
+mod if_stm;
+mod iteration;
+mod labelled_stm;
+mod return_stm;
+mod switch;
+mod throw;
+mod try_stm;
+mod variable;
+mod with_stm;
+
+use self::{
+  block::BlockStatement,
+  break_stm::BreakStatement,
+  continue_stm::ContinueStatement,
+  if_stm::IfStatement,
+  iteration::{DoWhileStatement, ForStatement, WhileStatement},
+  labelled_stm::LabelledStatement,
+  return_stm::ReturnStatement,
+  switch::SwitchStatement,
+  throw::ThrowStatement,
+  try_stm::TryStatement,
+  with_stm::WithStatement,
+};
+pub(super) use self::{declaration::Declaration, variable::VariableStatement};
+use super::{
+  expression::Expression,
+  pattern::{check_strict_identifier, check_yield_await},
+  typescript, AllowAwait, AllowReturn, AllowYield, Cursor, ParseError, TokenParser,
+};
+use crate::syntax::{
+  ast::{node, Const, Keyword, Node, NodeSpan, Punctuator, Sym},
+  lexer::TokenKind,
+};
+use std::io::Read;
+
+/// Statement parsing.
+///
+/// This can be one of the following:
+///
+///  - `BlockStatement`
+///  - `VariableStatement`
+///  - `EmptyStatement`
+///  - `ExpressionStatement`
+///  - `IfStatement`
+///  - `BreakableStatement`
+///  - `ContinueStatement`
+///  - `BreakStatement`
+///  - `ReturnStatement`
+///  - `WithStatement`
+///  - `LabelledStatement`
+///  - `ThrowStatement`
+///  - `TryStatement`
+///  - `DebuggerStatement`
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements
+/// [spec]: https://tc39.es/ecma262/#prod-Statement
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Statement {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+  allow_return: AllowReturn,
+}
+
+impl Statement {
+  /// Creates a new `Statement` parser.
+  pub(super) fn new<Y, A, R>(allow_yield: Y, allow_await: A, allow_return: R) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+    R: Into<AllowReturn>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+      allow_return: allow_return.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for Statement
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    if is_async_function(cursor)? {
+      let tok = cursor.peek(0)?.expect("async token vanished");
+      return Err(ParseError::unexpected(
+        tok.kind().clone(),
+        tok.span(),
+        "async function declarations are not allowed in statement position",
+      ));
+    }
+
+    if is_import_declaration(cursor)? {
+      let tok = cursor.next()?.expect("import token vanished");
+      return Err(ParseError::unexpected(
+        tok.kind().clone(),
+        tok.span(),
+        "import declarations may only appear at the top level of a module",
+      ));
+    }
+
+    let start = cursor.start()?;
+    let tok = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?;
+
+    // An expression statement is the expression node itself, whose span leaves out the
+    // semicolon.
+    let node = match tok.kind() {
+      TokenKind::Keyword(Keyword::If) => {
+        IfStatement::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor)
+      }
+      TokenKind::Keyword(Keyword::Var) => {
+        VariableStatement::new(self.allow_yield, self.allow_await).parse(cursor)
+      }
+      TokenKind::Keyword(Keyword::While) => {
+        WhileStatement::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor)
+      }
+      TokenKind::Keyword(Keyword::Do) => {
+        DoWhileStatement::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor)
+      }
+      TokenKind::Keyword(Keyword::For) => {
+        ForStatement::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor)
+      }
+      TokenKind::Keyword(Keyword::Return) => {
+        if self.allow_return.0 {
+          ReturnStatement::new(self.allow_yield, self.allow_await).parse(cursor)
+        } else {
+          Err(ParseError::unexpected(
+            tok.kind().clone(),
+            tok.span(),
+            "return statement outside of a function",
+          ))
+        }
+      }
+      TokenKind::Keyword(Keyword::Break) => {
+        BreakStatement::new(self.allow_yield, self.allow_await).parse(cursor)
+      }
+      TokenKind::Keyword(Keyword::Continue) => {
+        ContinueStatement::new(self.allow_yield, self.allow_await).parse(cursor)
+      }
+      TokenKind::Keyword(Keyword::Try) => {
+        TryStatement::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor)
+      }
+      TokenKind::Keyword(Keyword::Throw) => {
+        ThrowStatement::new(self.allow_yield, self.allow_await).parse(cursor)
+      }
+      TokenKind::Keyword(Keyword::Switch) => {
+        SwitchStatement::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor)
+      }
+      TokenKind::Keyword(Keyword::Debugger) => {
+        let _ = cursor.next()?.expect("debugger token vanished");
+        cursor.expect_semicolon("debugger statement")?;
+        Ok(Node::Debugger(NodeSpan::default()))
+      }
+      TokenKind::Keyword(Keyword::Class) => Err(ParseError::unexpected(
+        tok.kind().clone(),
+        tok.span(),
+        "class declarations are not allowed in statement position",
+      )),
+      TokenKind::Keyword(Keyword::Export) => Err(ParseError::unexpected(
+        tok.kind().clone(),
+        tok.span(),
+        "export declarations may only appear at the top level of a module",
+      )),
+      TokenKind::Keyword(Keyword::With) => {
+        WithStatement::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor)
+      }
+      TokenKind::Keyword(Keyword::Function) => Err(ParseError::unexpected(
+        tok.kind().clone(),
+        tok.span(),
+        "function declarations are not allowed in statement position",
+      )),
+      TokenKind::Punctuator(Punctuator::OpenBlock) => {
+        BlockStatement::new(self.allow_yield, self.allow_await, self.allow_return)
+          .parse(cursor)
+          .map(Node::from)
+      }
+      TokenKind::Punctuator(Punctuator::Semicolon) => {
+        let _ = cursor.next()?.expect("; token vanished");
+        Ok(Node::empty())
+      }
+      TokenKind::Identifier(_)
+      | TokenKind::Keyword(Keyword::Yield)
+      | TokenKind::Keyword(Keyword::Await) => {
+        let is_label = cursor
+          .peek(1)?
+          .is_some_and(|tk| tk.kind() == &TokenKind::Punctuator(Punctuator::Colon));
+        if is_label {
+          LabelledStatement::new(self.allow_yield, self.allow_await, self.allow_return)
+            .parse(cursor)
+        } else {
+          return ExpressionStatement::new(self.allow_yield, self.allow_await).parse(cursor);
+        }
+      }
+      _ => return ExpressionStatement::new(self.allow_yield, self.allow_await).parse(cursor),
+    }?;
+    Ok(node.with_span(cursor.span_from(start)))
+  }
+}
+
+/// Reads a list of statements.
+///
+/// If `break_when_closingbraces` is `true`, it will stop as soon as it finds a `}` character,
+/// leaving it for the caller.
+///
+/// The body of a script or a function starts with a directive prologue, which can make it
+/// strict mode code. The strictness of such a body is recorded on the result.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-StatementList
+#[derive(Debug, Clone, Copy)]
+pub(super) struct StatementList {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+  allow_return: AllowReturn,
+  break_when_closingbraces: bool,
+  directives: bool,
+}
+
+impl StatementList {
+  /// Creates a new `StatementList` parser.
+  pub(super) fn new<Y, A, R>(
+    allow_yield: Y,
+    allow_await: A,
+    allow_return: R,
+    break_when_closingbraces: bool,
+  ) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+    R: Into<AllowReturn>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+      allow_return: allow_return.into(),
+      break_when_closingbraces,
+      directives: false,
+    }
+  }
+
+  /// Parses a directive prologue at the start of the list.
+  pub(super) fn with_directives(mut self) -> Self {
+    self.directives = true;
+    self
+  }
+}
+
+impl<R> TokenParser<R> for StatementList
+where
+  R: Read,
+{
+  type Output = node::StatementList;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let mut items = Vec::new();
+    let mut directives = self.directives;
+    // The first directive with a legacy octal escape, an error once the body turns strict.
+    let mut octal_directive = None;
+
+    loop {
+      let recovering = cursor.is_recovering();
+      match cursor.peek(0)? {
+        Some(token) if token.kind() == &TokenKind::Punctuator(Punctuator::CloseBlock) => {
+          if self.break_when_closingbraces {
+            break;
+          } else {
+            let error = ParseError::unexpected(token.kind().clone(), token.span(), None);
+            cursor.report(error)?;
+            let _ = cursor.next()?;
+            continue;
+          }
+        }
+        // When recovering, the caller inserts the missing closing brace.
+        None if self.break_when_closingbraces && !recovering => {
+          return Err(ParseError::AbruptEnd);
+        }
+        None => break,
+        _ => {}
+      }
+
+      let directive = match cursor.peek(0)? {
+        Some(token) if directives => match token.kind() {
+          TokenKind::StringLiteral(value) => Some((value.clone(), token.span())),
+          _ => None,
+        },
+        _ => None,
+      };
+      let directive =
+        directive.map(|(value, span)| (cursor.raw_string(span.start(), &value), span));
+
+      let point = cursor.recovery_point()?;
+      if cursor.is_typescript() && typescript::is_declaration(cursor)? {
+        let declaration =
+          typescript::Declaration::new(self.allow_yield, self.allow_await).parse(cursor);
+        items.extend(cursor.recover_item(point, declaration, |span| {
+          vec![Node::error().with_span(span)]
+        })?);
+        directives = false;
+        continue;
+      }
+      let item =
+        StatementListItem::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor);
+      let mut item = cursor.recover_item(point, item, |span| Node::error().with_span(span))?;
+
+      // A directive is an expression statement made of a single string literal. Since it is
+      // only known once the statement ends, a token after it read to insert a semicolon is
+      // still lexed with the previous strictness, and checked again by `use_strict_directive`.
+      match (directive, item) {
+        (Some((raw, span)), Node::Const(Const::String(value), node_span)) => {
+          let directive = node::Directive::new(value, raw).with_span(node_span);
+          if directive.is_use_strict() {
+            cursor.use_strict_directive(span)?
+          } else if octal_directive.is_none() && cursor.has_octal_escape(span.start()) {
+            octal_directive = Some(span);
+          }
+          // The directive may precede `"use strict"`, or have been lexed before it took effect.
+          if let Some(span) = octal_directive.filter(|_| cursor.is_strict()) {
+            return Err(ParseError::early(
+              "octal escape sequences are not allowed in strict mode",
+              span,
+            ));
+          }
+          item = directive.into();
+        }
+        (_, statement) => {
+          directives = false;
+          item = statement;
+        }
+      }
+      items.push(item);
+    }
+
+    Ok(node::StatementList::from(items).with_strict(self.directives && cursor.is_strict()))
+  }
+}
+
+/// Statement list item parsing, either a statement or a declaration.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-StatementListItem
+#[derive(Debug, Clone, Copy)]
+pub(super) struct StatementListItem {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+  allow_return: AllowReturn,
+}
+
+impl StatementListItem {
+  /// Creates a new `StatementListItem` parser.
+  pub(super) fn new<Y, A, R>(allow_yield: Y, allow_await: A, allow_return: R) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+    R: Into<AllowReturn>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+      allow_return: allow_return.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for StatementListItem
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    if is_async_function(cursor)? {
+      return Declaration::new(self.allow_yield, self.allow_await).parse(cursor);
+    }
+
+    let tok = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?;
+
+    match tok.kind() {
+      TokenKind::Keyword(Keyword::Function)
+      | TokenKind::Keyword(Keyword::Class)
+      | TokenKind::Keyword(Keyword::Const)
+      | TokenKind::Keyword(Keyword::Let) => {
+        Declaration::new(self.allow_yield, self.allow_await).parse(cursor)
+      }
+      _ => Statement::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor),
+    }
+  }
+}
+
+/// Expression statement parsing.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ExpressionStatement
+#[derive(Debug, Clone, Copy)]
+struct ExpressionStatement {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl ExpressionStatement {
+  /// Creates a new `ExpressionStatement` parser.
+  fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for ExpressionStatement
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let expr = Expression::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
+    cursor.expect_semicolon("expression statement")?;
+    Ok(expr)
+  }
+}
+
+/// Parses a function declaration where a statement is expected, as the clause of an `if`
+/// statement or a labelled item.
+///
+/// Annex B only allows this for plain function declarations in sloppy mode code.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-functiondeclarations-in-ifstatement-statement-clauses
+fn sloppy_function_declaration<R>(
+  cursor: &mut Cursor<R>,
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+) -> Result<Node, ParseError>
+where
+  R: Read,
+{
+  let span = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.span();
+  if cursor.is_strict() {
+    return Err(ParseError::early(
+      "function declarations are not allowed in statement position in strict mode code",
+      span,
+    ));
+  }
+  if cursor
+    .peek(1)?
+    .is_some_and(|tk| tk.kind() == &TokenKind::Punctuator(Punctuator::Mul))
+  {
+    return Err(ParseError::early(
+      "generator declarations are not allowed in statement position",
+      span,
+    ));
+  }
+  Declaration::new(allow_yield, allow_await).parse(cursor)
+}
+
+/// Checks if the next tokens are `async function`, without a line terminator in between.
+pub(in crate::syntax::parser) fn is_async_function<R>(
+  cursor: &mut Cursor<R>,
+) -> Result<bool, ParseError>
+where
+  R: Read,
+{
+  let is_async = cursor.peek(0)?.is_some_and(
+    |tk| matches!(tk.kind(), TokenKind::Identifier(ident) if ident.as_ref() == "async"),
+  );
+  Ok(
+    is_async
+      && cursor
+        .peek(1)?
+        .is_some_and(|tk| tk.kind() == &TokenKind::Keyword(Keyword::Function))
+      && !cursor.is_line_terminator_before(1)?,
+  )
+}
+
+/// Checks if the token `skip` tokens ahead is the identifier `name`, which is only a keyword in
+/// some places, such as `async` or `interface`.
+pub(in crate::syntax::parser) fn is_contextual<R>(
+  cursor: &mut Cursor<R>,
+  skip: usize,
+  name: &str,
+) -> Result<bool, ParseError>
+where
+  R: Read,
+{
+  Ok(
+    cursor.peek(skip)?.is_some_and(
+      |tk| matches!(tk.kind(), TokenKind::Identifier(ident) if ident.as_ref() == name),
+    ),
+  )
+}
+
+/// Checks if the next token starts an import declaration, rather than an `import(...)` call or
+/// `import.meta`.
+pub(in crate::syntax::parser) fn is_import_declaration<R>(
+  cursor: &mut Cursor<R>,
+) -> Result<bool, ParseError>
+where
+  R: Read,
+{
+  Ok(
+    cursor.next_is(Keyword::Import)?
+      && !matches!(
+        cursor.peek(1)?.map(|tk| tk.kind()),
+        Some(TokenKind::Punctuator(Punctuator::OpenParen))
+          | Some(TokenKind::Punctuator(Punctuator::Dot))
+      ),
+  )
+}
+
+/// Label identifier parsing.
+///
+/// Labels are not bindings, so unlike binding identifiers they can be `eval` or `arguments` in
+/// strict mode code.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-LabelIdentifier
+#[derive(Debug, Clone, Copy)]
+struct LabelIdentifier {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl LabelIdentifier {
+  /// Creates a new `LabelIdentifier` parser.
+  fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for LabelIdentifier
+where
+  R: Read,
+{
+  type Output = Sym;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let tok = cursor.next()?.ok_or(ParseError::AbruptEnd)?;
+    match tok.kind() {
+      TokenKind::Identifier(name) => {
+        check_strict_identifier(cursor, name, tok.span(), false)?;
+        Ok(name.clone())
+      }
+      TokenKind::Keyword(k @ Keyword::Yield) | TokenKind::Keyword(k @ Keyword::Await) => {
+        check_yield_await(*k, tok.span(), self.allow_yield, self.allow_await)?;
+        check_strict_identifier(cursor, k.as_str(), tok.span(), false)?;
+        Ok(k.as_str().into())
+      }
+      _ => Err(ParseError::expected(
+        vec![TokenKind::identifier("identifier")],
+        tok.kind().clone(),
+        tok.span(),
+        "label identifier",
+      )),
+    }
+  }
+}
+
+/// Parses the optional label of a `break` or `continue` statement.
+///
+/// The label must be on the same line as the keyword, otherwise a semicolon is inserted.
+fn label_identifier<R>(
+  cursor: &mut Cursor<R>,
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+) -> Result<Option<Sym>, ParseError>
+where
+  R: Read,
+{
+  if cursor.is_line_terminator_before(0)? {
+    return Ok(None);
+  }
+  match cursor.peek(0)?.map(|tk| tk.kind()) {
+    Some(TokenKind::Identifier(_))
+    | Some(TokenKind::Keyword(Keyword::Yield))
+    | Some(TokenKind::Keyword(Keyword::Await)) => Ok(Some(
+      LabelIdentifier::new(allow_yield, allow_await).parse(cursor)?,
+    )),
+    _ => Ok(None),
+  }
+}
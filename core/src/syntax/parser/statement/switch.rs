@@ -0,0 +1,176 @@
+//! Switch statement parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/switch
+//! [spec]: https://tc39.es/ecma262/#sec-switch-statement
+
+use super::StatementListItem;
+use crate::syntax::{
+  ast::{
+    arena::ArenaBox,
+    node::{Case, Switch},
+    Keyword, Node, Punctuator,
+  },
+  lexer::TokenKind,
+  parser::{
+    expression::Expression, AllowAwait, AllowReturn, AllowYield, Cursor, ParseError, ScopeKind,
+    TokenParser,
+  },
+};
+use std::io::Read;
+
+/// Switch statement parsing.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/switch
+/// [spec]: https://tc39.es/ecma262/#prod-SwitchStatement
+#[derive(Debug, Clone, Copy)]
+pub(super) struct SwitchStatement {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+  allow_return: AllowReturn,
+}
+
+impl SwitchStatement {
+  /// Creates a new `SwitchStatement` parser.
+  pub(super) fn new<Y, A, R>(allow_yield: Y, allow_await: A, allow_return: R) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+    R: Into<AllowReturn>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+      allow_return: allow_return.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for SwitchStatement
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    cursor.expect(Keyword::Switch, "switch statement")?;
+    cursor.expect(Punctuator::OpenParen, "switch statement")?;
+
+    let condition = Expression::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
+
+    cursor.expect(Punctuator::CloseParen, "switch statement")?;
+
+    cursor.enter_breakable(false);
+    let cases =
+      CaseBlock::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor)?;
+    cursor.exit_breakable(false);
+
+    Ok(Switch::new(condition, cases).into())
+  }
+}
+
+/// Switch case block parsing.
+///
+/// Clauses are kept in source order, the `default` clause is the one without a condition.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-CaseBlock
+#[derive(Debug, Clone, Copy)]
+struct CaseBlock {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+  allow_return: AllowReturn,
+}
+
+impl CaseBlock {
+  /// Creates a new `CaseBlock` parser.
+  fn new<Y, A, R>(allow_yield: Y, allow_await: A, allow_return: R) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+    R: Into<AllowReturn>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+      allow_return: allow_return.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for CaseBlock
+where
+  R: Read,
+{
+  type Output = ArenaBox<[Case]>;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    cursor.expect(Punctuator::OpenBlock, "switch case block")?;
+    cursor.push_scope(ScopeKind::Block);
+
+    let mut cases = Vec::new();
+    let mut has_default = false;
+
+    loop {
+      let tok = cursor.next()?.ok_or(ParseError::AbruptEnd)?;
+      let start = tok.span().start();
+      let condition = match tok.kind() {
+        TokenKind::Punctuator(Punctuator::CloseBlock) => break,
+        TokenKind::Keyword(Keyword::Case) => {
+          let cond = Expression::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
+          Some(cond)
+        }
+        TokenKind::Keyword(Keyword::Default) => {
+          if has_default {
+            return Err(ParseError::general(
+              "more than one switch default",
+              tok.span().start(),
+            ));
+          }
+          has_default = true;
+          None
+        }
+        _ => {
+          return Err(ParseError::expected(
+            vec![
+              Keyword::Case.into(),
+              Keyword::Default.into(),
+              Punctuator::CloseBlock.into(),
+            ],
+            tok.kind().clone(),
+            tok.span(),
+            "switch case block",
+          ))
+        }
+      };
+      cursor.expect(Punctuator::Colon, "switch case block")?;
+
+      let mut body = Vec::new();
+      loop {
+        match cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.kind() {
+          TokenKind::Keyword(Keyword::Case)
+          | TokenKind::Keyword(Keyword::Default)
+          | TokenKind::Punctuator(Punctuator::CloseBlock) => break,
+          _ => body.push(
+            StatementListItem::new(self.allow_yield, self.allow_await, self.allow_return)
+              .parse(cursor)?,
+          ),
+        }
+      }
+
+      cases.push(Case::new(condition, body).with_span(cursor.span_from(start)));
+    }
+    cursor.pop_scope();
+
+    Ok(cases.into())
+  }
+}
@@ -0,0 +1,59 @@
+//! Throw statement parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/throw
+//! [spec]: https://tc39.es/ecma262/#sec-throw-statement
+
+use crate::syntax::{
+  ast::{node::Throw, Keyword, Node},
+  parser::{expression::Expression, AllowAwait, AllowYield, Cursor, ParseError, TokenParser},
+};
+use std::io::Read;
+
+/// Throw statement parsing
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/throw
+/// [spec]: https://tc39.es/ecma262/#prod-ThrowStatement
+#[derive(Debug, Clone, Copy)]
+pub(super) struct ThrowStatement {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl ThrowStatement {
+  /// Creates a new `ThrowStatement` parser.
+  pub(super) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for ThrowStatement
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    cursor.expect(Keyword::Throw, "throw statement")?;
+    cursor.peek_expect_no_lineterminator(0, "throw statement")?;
+
+    let expr = Expression::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
+    cursor.expect_semicolon("throw statement")?;
+
+    Ok(Throw::new(expr).into())
+  }
+}
@@ -0,0 +1,106 @@
+//! Try statement parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/try...catch
+//! [spec]: https://tc39.es/ecma262/#sec-try-statement
+
+use super::block::Block;
+use crate::syntax::{
+  ast::{
+    node::{Catch, Finally, Pattern, Try},
+    Keyword, Node, Punctuator,
+  },
+  parser::{
+    pattern::BindingTarget, typescript, AllowAwait, AllowReturn, AllowYield, BindingKind, Cursor,
+    ParseError, ScopeKind, TokenParser,
+  },
+};
+use std::io::Read;
+
+/// Try...catch statement parsing
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/try...catch
+/// [spec]: https://tc39.es/ecma262/#prod-TryStatement
+#[derive(Debug, Clone, Copy)]
+pub(super) struct TryStatement {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+  allow_return: AllowReturn,
+}
+
+impl TryStatement {
+  /// Creates a new `TryStatement` parser.
+  pub(super) fn new<Y, A, R>(allow_yield: Y, allow_await: A, allow_return: R) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+    R: Into<AllowReturn>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+      allow_return: allow_return.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for TryStatement
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let try_tok = cursor.expect(Keyword::Try, "try statement")?;
+
+    let try_clause =
+      Block::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor)?;
+
+    let catch = if let Some(catch_tok) = cursor.next_if(Keyword::Catch)? {
+      // The catch binding is optional, `catch { ... }`.
+      cursor.push_scope(ScopeKind::Catch);
+      let param: Option<Pattern> = if cursor.next_if(Punctuator::OpenParen)?.is_some() {
+        let mark = cursor.bindings_mark();
+        let param = BindingTarget::new(self.allow_yield, self.allow_await).parse(cursor)?;
+        if cursor.is_typescript() {
+          typescript::type_annotation(cursor)?;
+        }
+        cursor.declare_bindings(mark, BindingKind::Parameter)?;
+        cursor.expect(Punctuator::CloseParen, "catch in try statement")?;
+        Some(param)
+      } else {
+        None
+      };
+      let block =
+        Block::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor)?;
+      cursor.pop_scope();
+      Some(Catch::new(param, block).with_span(cursor.span_from(catch_tok.span().start())))
+    } else {
+      None
+    };
+
+    let finally = if cursor.next_if(Keyword::Finally)?.is_some() {
+      Some(Finally::from(
+        Block::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor)?,
+      ))
+    } else {
+      None
+    };
+
+    if catch.is_none() && finally.is_none() {
+      return Err(ParseError::general(
+        "missing catch or finally after try",
+        try_tok.span().start(),
+      ));
+    }
+
+    Ok(Try::new(try_clause, catch, finally).into())
+  }
+}
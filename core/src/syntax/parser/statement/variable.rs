@@ -0,0 +1,67 @@
+//! Variable statement parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/var
+//! [spec]: https://tc39.es/ecma262/#sec-variable-statement
+
+use super::declaration::{check_initializers, BindingList};
+use crate::syntax::{
+  ast::{node::DeclarationList, Keyword, Node},
+  parser::{AllowAwait, AllowYield, BindingKind, Cursor, ParseError, TokenParser},
+};
+use std::io::Read;
+
+/// Variable statement parsing.
+///
+/// A varible statement contains the `var` keyword.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/var
+/// [spec]: https://tc39.es/ecma262/#prod-VariableStatement
+#[derive(Debug, Clone, Copy)]
+pub(in crate::syntax::parser) struct VariableStatement {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+}
+
+impl VariableStatement {
+  /// Creates a new `VariableStatement` parser.
+  pub(in crate::syntax::parser) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for VariableStatement
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let tok = cursor.expect(Keyword::Var, "variable statement")?;
+    let mark = cursor.bindings_mark();
+
+    let list = DeclarationList::Var(
+      BindingList::new(true, self.allow_yield, self.allow_await).parse(cursor)?,
+    );
+    check_initializers(&list, tok.span().start())?;
+    cursor.declare_bindings(mark, BindingKind::Var)?;
+
+    cursor.expect_semicolon("variable statement")?;
+
+    Ok(Node::from(list).with_span(cursor.span_from(tok.span().start())))
+  }
+}
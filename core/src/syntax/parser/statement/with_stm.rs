@@ -0,0 +1,77 @@
+//! With statement parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/with
+//! [spec]: https://tc39.es/ecma262/#sec-with-statement
+
+use super::Statement;
+use crate::syntax::{
+  ast::{node::With, Keyword, Node, Punctuator},
+  parser::{
+    expression::Expression, AllowAwait, AllowReturn, AllowYield, Cursor, ParseError, TokenParser,
+  },
+};
+use std::io::Read;
+
+/// With statement parsing.
+///
+/// A `with` statement is an early error in strict mode code.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/with
+/// [spec]: https://tc39.es/ecma262/#prod-WithStatement
+#[derive(Debug, Clone, Copy)]
+pub(super) struct WithStatement {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+  allow_return: AllowReturn,
+}
+
+impl WithStatement {
+  /// Creates a new `WithStatement` parser.
+  pub(super) fn new<Y, A, R>(allow_yield: Y, allow_await: A, allow_return: R) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+    R: Into<AllowReturn>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+      allow_return: allow_return.into(),
+    }
+  }
+}
+
+impl<R> TokenParser<R> for WithStatement
+where
+  R: Read,
+{
+  type Output = Node;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let span = cursor.expect(Keyword::With, "with statement")?.span();
+    if cursor.is_strict() {
+      return Err(ParseError::early(
+        "with statements are not allowed in strict mode code",
+        span,
+      ));
+    }
+    cursor.expect(Punctuator::OpenParen, "with statement")?;
+
+    let object = Expression::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
+
+    cursor.expect(Punctuator::CloseParen, "with statement")?;
+
+    let body =
+      Statement::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor)?;
+
+    Ok(With::new(object, body).into())
+  }
+}
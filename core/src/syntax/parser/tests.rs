@@ -0,0 +1,798 @@
+//! Tests for the parser.
+
+use super::{ParseError, Parser};
+use crate::syntax::ast::{
+  arena::ArenaBox,
+  node::{
+    Block, Break, Call, Case, Catch, Class, Continue, Declaration, DeclarationList, Directive,
+    DoWhileLoop, ForLoop, FormalParameter, FunctionDecl, FunctionExpr, Identifier, If, Labelled,
+    Pattern, Return, StatementList, Switch, Throw, Try, WhileLoop, With,
+  },
+  visitor::{visit, Flow, NodePath, Path, Visitor},
+  Const, Node, Span, Sym,
+};
+
+/// Checks that the given JavaScript string gives the expected expression.
+#[track_caller]
+pub(super) fn check_parser<L>(js: &str, expr: L)
+where
+  L: Into<ArenaBox<[Node]>>,
+{
+  assert_eq!(
+    Parser::new(js.as_bytes())
+      .parse_script()
+      .expect("failed to parse"),
+    StatementList::from(expr)
+  );
+}
+
+/// Creates the body of a function in strict mode code, such as a class method.
+pub(super) fn strict_body<L>(items: L) -> StatementList
+where
+  L: Into<ArenaBox<[Node]>>,
+{
+  StatementList::from(items).with_strict(true)
+}
+
+/// Checks that the given javascript string creates a parse error.
+#[track_caller]
+pub(super) fn check_invalid(js: &str) {
+  assert!(Parser::new(js.as_bytes()).parse_script().is_err());
+}
+
+/// Checks that the given javascript string creates an early error starting at the given line
+/// and column.
+#[track_caller]
+fn check_early_error(js: &str, line: u32, column: u32) {
+  match Parser::new(js.as_bytes()).parse_script() {
+    Err(ParseError::Early { span, .. }) => assert_eq!(
+      (span.start().line_number(), span.start().column_number()),
+      (line, column)
+    ),
+    result => panic!("expected an early error, got {:?}", result),
+  }
+}
+
+#[test]
+fn empty_script() {
+  check_parser("", vec![]);
+  check_parser("\n\n", vec![]);
+}
+
+#[test]
+fn var_declarations() {
+  check_parser(
+    "var a = 1, b;",
+    vec![DeclarationList::Var(
+      vec![
+        Declaration::new(Identifier::from("a"), Some(Const::from(1).into())),
+        Declaration::new(Identifier::from("b"), None),
+      ]
+      .into(),
+    )
+    .into()],
+  );
+}
+
+#[test]
+fn const_requires_initializer() {
+  check_invalid("const a;");
+  check_invalid("let [a];");
+}
+
+#[test]
+fn automatic_semicolon_insertion() {
+  check_parser(
+    "a\nb",
+    vec![Identifier::from("a").into(), Identifier::from("b").into()],
+  );
+  check_invalid("a b");
+}
+
+#[test]
+fn if_else() {
+  check_parser(
+    "if (a) b; else { c }",
+    vec![If::new::<_, _, Node, _>(
+      Identifier::from("a"),
+      Identifier::from("b"),
+      Some(Block::from(vec![Identifier::from("c").into()]).into()),
+    )
+    .into()],
+  );
+}
+
+#[test]
+fn loops() {
+  check_parser(
+    "while (a) break;",
+    vec![WhileLoop::new(Identifier::from("a"), Break::new::<_, Box<str>>(None)).into()],
+  );
+  check_parser(
+    "do continue; while (a)",
+    vec![DoWhileLoop::new(Continue::new::<_, Box<str>>(None), Identifier::from("a")).into()],
+  );
+  check_parser(
+    "for (;;) {}",
+    vec![ForLoop::new(None, None, None, Block::from(vec![])).into()],
+  );
+  check_early_error("for (async of a);", 1, 6);
+  Parser::new("for (async in a); for ((async) of a); for (async of => {};;) break;".as_bytes())
+    .parse_script()
+    .expect("only a plain async can't start a for-of loop");
+  Parser::new("async function f() { for await (async of a); }".as_bytes())
+    .parse_script()
+    .expect("for await loops can assign to async");
+}
+
+#[test]
+fn labelled_break() {
+  check_parser(
+    "outer: while (a) break outer;",
+    vec![Labelled::new(
+      "outer",
+      WhileLoop::new(Identifier::from("a"), Break::new("outer")),
+    )
+    .into()],
+  );
+}
+
+#[test]
+fn break_label_on_next_line() {
+  check_parser(
+    "while (a) break\nouter",
+    vec![
+      WhileLoop::new(Identifier::from("a"), Break::new::<_, Box<str>>(None)).into(),
+      Identifier::from("outer").into(),
+    ],
+  );
+}
+
+#[test]
+fn function_declaration() {
+  check_parser(
+    "function f(a, b = 1) { return a; }",
+    vec![FunctionDecl::new(
+      "f",
+      vec![
+        FormalParameter::new(Declaration::new(Identifier::from("a"), None), false),
+        FormalParameter::new(
+          Declaration::new(Identifier::from("b"), Some(Const::from(1).into())),
+          false,
+        ),
+      ],
+      vec![Return::new(Some(Identifier::from("a"))).into()],
+    )
+    .into()],
+  );
+}
+
+#[test]
+fn return_outside_function() {
+  check_invalid("return 1;");
+}
+
+#[test]
+fn switch_cases() {
+  check_parser(
+    "switch (a) { case 1: b; default: case 2: }",
+    vec![Switch::new(
+      Identifier::from("a"),
+      vec![
+        Case::new(
+          Some(Const::from(1).into()),
+          vec![Identifier::from("b").into()],
+        ),
+        Case::new(None, vec![]),
+        Case::new(Some(Const::from(2).into()), vec![]),
+      ],
+    )
+    .into()],
+  );
+  check_invalid("switch (a) { default: default: }");
+}
+
+#[test]
+fn try_catch_finally() {
+  check_parser(
+    "try { throw a } catch { } finally { }",
+    vec![Try::new(
+      vec![Throw::new(Identifier::from("a")).into()],
+      Some(Catch::new::<Identifier, _>(None, vec![])),
+      Some(vec![].into()),
+    )
+    .into()],
+  );
+  check_invalid("try {}");
+  check_invalid("throw\na");
+}
+
+#[test]
+fn duplicate_lexical_declarations() {
+  check_early_error("let a; let a;", 1, 12);
+  check_early_error("let a;\nconst [b, a] = c;", 2, 11);
+  check_early_error("class A {} class A {}", 1, 18);
+  check_early_error("{ function f() {} let f; }", 1, 23);
+  check_early_error("switch (a) { case 1: let b; default: let b; }", 1, 42);
+  check_early_error("{ function* f() {} function f() {} }", 1, 29);
+  check_early_error("{ function f() {} async function f() {} }", 1, 34);
+  check_early_error("'use strict'; { function f() {} function f() {} }", 1, 42);
+  Parser::new(
+    "{ function f() {} function f() {} } switch (a) { case 1: function g() {} }".as_bytes(),
+  )
+  .parse_script()
+  .expect("sloppy mode code can declare a block function twice");
+  check_parser(
+    "{ let a; } { let a; }",
+    vec![
+      Block::from(vec![DeclarationList::Let(
+        vec![Declaration::new(Identifier::from("a"), None)].into(),
+      )
+      .into()])
+      .into(),
+      Block::from(vec![DeclarationList::Let(
+        vec![Declaration::new(Identifier::from("a"), None)].into(),
+      )
+      .into()])
+      .into(),
+    ],
+  );
+}
+
+#[test]
+fn lexical_var_conflicts() {
+  check_early_error("let a; var a;", 1, 12);
+  check_early_error("var a; let a;", 1, 12);
+  check_early_error("let a; { var a; }", 1, 14);
+  check_early_error("{ var a; } let a;", 1, 16);
+  check_early_error("function f(a) { let a; }", 1, 21);
+  check_early_error("try {} catch ([e]) { let e; }", 1, 26);
+  check_early_error("for (let i;;) { var i; }", 1, 21);
+  check_early_error("let f; function f() {}", 1, 17);
+
+  Parser::new("var a; var a; function a() {}".as_bytes())
+    .parse_script()
+    .expect("var declarations can be repeated");
+  Parser::new("function f(a) { var a; } try {} catch (e) { var e; { let e; } }".as_bytes())
+    .parse_script()
+    .expect("parameters can be redeclared with var");
+  Parser::new("let a; { let a; } for (let a;;) { let a; } let f = function a() {};".as_bytes())
+    .parse_script()
+    .expect("nested scopes can shadow lexical declarations");
+}
+
+#[test]
+fn duplicate_parameters() {
+  check_early_error("(a, a) => 1;", 1, 5);
+  check_early_error("async (a, a) => 1;", 1, 11);
+  check_early_error("function f(a = 1, a) {}", 1, 19);
+  check_early_error("function f({ a, a }) {}", 1, 17);
+  check_early_error("function f(a, ...a) {}", 1, 18);
+  check_early_error("try {} catch ([e, e]) {}", 1, 19);
+  check_early_error("({ m(a, a) {} });", 1, 9);
+  Parser::new("function f(a, a) {} (function (b, b) {});".as_bytes())
+    .parse_script()
+    .expect("simple parameter lists can repeat names in sloppy mode code");
+}
+
+#[test]
+fn jump_targets() {
+  check_early_error("break;", 1, 1);
+  check_early_error("continue;", 1, 1);
+  check_early_error("switch (a) { default: continue; }", 1, 23);
+  check_early_error("a: { continue a; }", 1, 6);
+  check_early_error("while (a) { break b; }", 1, 13);
+  check_early_error("a: while (1) { function f() { break a; } }", 1, 31);
+  check_early_error("a: { a: ; }", 1, 6);
+
+  check_parser(
+    "a: { break a; }",
+    vec![Labelled::new("a", Block::from(vec![Break::new("a").into()])).into()],
+  );
+  check_parser(
+    "a: b: do continue a; while (c)",
+    vec![Labelled::new(
+      "a",
+      Labelled::new(
+        "b",
+        DoWhileLoop::new(Continue::new("a"), Identifier::from("c")),
+      ),
+    )
+    .into()],
+  );
+  check_parser(
+    "a: ; a: ;",
+    vec![
+      Labelled::new("a", Node::empty()).into(),
+      Labelled::new("a", Node::empty()).into(),
+    ],
+  );
+}
+
+#[test]
+fn with_statement() {
+  check_parser(
+    "with (a) b;",
+    vec![With::new(Identifier::from("a"), Identifier::from("b")).into()],
+  );
+  check_early_error("'use strict'; with (a) b;", 1, 15);
+  check_early_error("function f() { 'use strict'; with (a) {} }", 1, 30);
+}
+
+#[test]
+fn function_declaration_in_statement_position() {
+  check_parser(
+    "if (a) function f() {} else function g() {}",
+    vec![If::new::<_, _, Node, _>(
+      Identifier::from("a"),
+      Block::from(vec![
+        FunctionDecl::new(Box::from("f"), vec![], vec![]).into()
+      ]),
+      Some(
+        Block::from(vec![
+          FunctionDecl::new(Box::from("g"), vec![], vec![]).into()
+        ])
+        .into(),
+      ),
+    )
+    .into()],
+  );
+  check_parser(
+    "a: function f() {}",
+    vec![Labelled::new("a", FunctionDecl::new(Box::from("f"), vec![], vec![])).into()],
+  );
+  Parser::new("let f; if (a) function f() {}".as_bytes())
+    .parse_script()
+    .expect("the function is declared in its own block");
+
+  check_early_error("'use strict'; if (a) function f() {}", 1, 22);
+  check_early_error("'use strict'; a: function f() {}", 1, 18);
+  check_early_error("if (a) function* f() {}", 1, 8);
+  check_invalid("if (a) async function f() {}");
+  check_invalid("while (a) function f() {}");
+}
+
+#[test]
+fn duplicate_proto() {
+  check_early_error("({ __proto__: a, \"__proto__\": b });", 1, 18);
+  Parser::new("({ __proto__: a, [\"__proto__\"]: b, __proto__() {}, __proto__ });".as_bytes())
+    .parse_script()
+    .expect("only non-shorthand, non-computed properties count");
+  Parser::new("({ __proto__: a, __proto__: b } = c);".as_bytes())
+    .parse_script()
+    .expect("patterns can repeat __proto__");
+}
+
+#[test]
+fn strict_mode_early_errors() {
+  // Class bodies are strict mode code.
+  check_early_error("class A { m() { delete a; } }", 1, 17);
+  check_early_error("class A { m(eval) {} }", 1, 13);
+  check_early_error("class A { m() { let arguments; } }", 1, 21);
+  check_early_error("class A { m() { arguments = 1; } }", 1, 17);
+  check_early_error("class A { m() { eval++; } }", 1, 17);
+  check_early_error("class A { m() { return implements; } }", 1, 24);
+  check_invalid("class A { m() { return 010; } }");
+
+  // Identifier targets inside destructuring assignments, including shorthand properties.
+  check_early_error("\"use strict\"; ({eval} = {});", 1, 16);
+  check_early_error("\"use strict\"; ({a: arguments} = {});", 1, 16);
+  check_early_error("\"use strict\"; [a, [eval]] = [];", 1, 15);
+  check_early_error("\"use strict\"; ({...arguments} = {});", 1, 16);
+  check_early_error("\"use strict\"; for ({eval} of []);", 1, 20);
+  check_early_error("\"use strict\"; for (arguments in {});", 1, 20);
+
+  Parser::new("delete a; var eval; arguments = 1; var implements = 010;".as_bytes())
+    .parse_script()
+    .expect("sloppy mode code allows these");
+  Parser::new("({eval} = {}); [arguments] = []; for ({eval} of []);".as_bytes())
+    .parse_script()
+    .expect("sloppy mode code allows destructuring into eval and arguments");
+  Parser::new("class A { static m() { eval(a); a: arguments; delete a.b; } }".as_bytes())
+    .parse_script()
+    .expect("eval and arguments can be referenced in strict mode code");
+}
+
+#[test]
+fn use_strict_directives() {
+  let script = Parser::new("'use strict'; function f() {}".as_bytes())
+    .parse_script()
+    .expect("failed to parse");
+  assert!(script.strict());
+  match script.items() {
+    [_, Node::FunctionDecl(f)] => assert!(f.is_strict()),
+    items => panic!("unexpected items: {:?}", items),
+  }
+
+  check_invalid("\"use strict\"; var x = 010;");
+  check_early_error("\"use strict\"\nvar public;", 2, 5);
+  check_early_error("'a'; 'use strict'; var public;", 1, 24);
+  check_early_error("\"\\07\"; \"use strict\";", 1, 1);
+  check_early_error("function f() { 'a'; '\\8'; 'use strict'; }", 1, 21);
+  check_invalid("'use strict'; '\\07';");
+  check_invalid("class A { m() { '\\1'; } }");
+  Parser::new("'\\07'; function f() { '\\9'; }".as_bytes())
+    .parse_script()
+    .expect("sloppy mode code allows legacy octal escapes");
+  check_early_error("function f() { 'use strict'; var eval; }", 1, 34);
+  check_early_error("() => {\n  'use strict'; delete a;\n}", 2, 17);
+
+  // A literal read to end the directive without a semicolon was lexed as sloppy mode code.
+  check_early_error("\"use strict\"\n010", 2, 1);
+  check_early_error("\"use strict\"\n09", 2, 1);
+  check_early_error("function f() { \"use strict\"\n010 }", 2, 1);
+  check_early_error("\"use strict\"\n'\\07'", 2, 1);
+  check_early_error("function f() { 'use strict'\n'\\8' }", 2, 1);
+  Parser::new("\"use strict\"\n0.5; 0o10; '\\0'".as_bytes())
+    .parse_script()
+    .expect("these literals are allowed in strict mode code");
+
+  // The directive applies to the name and parameters parsed before it.
+  check_early_error("function eval() { 'use strict'; }", 1, 10);
+  check_early_error("(function f(a, arguments) { 'use strict'; })", 1, 16);
+  check_early_error("function f(a, a) { 'use strict'; }", 1, 15);
+  check_early_error("function f(a = 1) { 'use strict'; }", 1, 21);
+  check_early_error("([a]) => {\n  'use strict';\n}", 2, 3);
+  check_early_error("'use strict'; function f(a, a) {}", 1, 29);
+
+  // Escaped directives and strings that are not a whole statement are not directives.
+  for js in &[
+    "'use\\x20strict'; var public;",
+    "'use \\\nstrict'; var public;",
+    "('use strict'); var public;",
+    "'use strict'.length; var public;",
+    "a; 'use strict'; var public;",
+    "function f(a, a) {} var x = 010;",
+  ] {
+    let script = Parser::new(js.as_bytes())
+      .parse_script()
+      .expect("not strict mode code");
+    assert!(!script.strict());
+  }
+
+  // Directives keep their source text, a parenthesized string ends the prologue.
+  check_parser(
+    "'use\\x20strict'; \"a\"; ('b'); 'c';",
+    vec![
+      Directive::new("use strict", "'use\\x20strict'").into(),
+      Directive::new("a", "\"a\"").into(),
+      Node::from(Const::from("b")),
+      Node::from(Const::from("c")),
+    ],
+  );
+
+  // A directive only applies to its function.
+  let script = Parser::new("function f() { 'use strict'; } var public = 010;".as_bytes())
+    .parse_script()
+    .expect("failed to parse");
+  assert!(!script.strict());
+}
+
+/// Checks that the given JavaScript string, parsed with error recovery, gives the expected
+/// items and the given number of errors.
+#[track_caller]
+fn check_recovery<L>(js: &str, expr: L, errors: usize)
+where
+  L: Into<ArenaBox<[Node]>>,
+{
+  let recovered = Parser::new(js.as_bytes()).parse_script_with_recovery();
+  assert_eq!(recovered.ast(), &StatementList::from(expr));
+  assert_eq!(recovered.errors().len(), errors, "{:?}", recovered.errors());
+}
+
+#[test]
+fn recover_missing_tokens() {
+  check_recovery(
+    "let a = ;\nlet b = 1;",
+    vec![
+      DeclarationList::Let(
+        vec![Declaration::new(Identifier::from("a"), Some(Node::error()))].into(),
+      )
+      .into(),
+      DeclarationList::Let(
+        vec![Declaration::new(
+          Identifier::from("b"),
+          Some(Const::from(1).into()),
+        )]
+        .into(),
+      )
+      .into(),
+    ],
+    1,
+  );
+  check_recovery(
+    "if (a { b; }",
+    vec![If::new::<_, _, Node, _>(
+      Identifier::from("a"),
+      Block::from(vec![Identifier::from("b").into()]),
+      None,
+    )
+    .into()],
+    1,
+  );
+  check_recovery(
+    "a b",
+    vec![Identifier::from("a").into(), Identifier::from("b").into()],
+    1,
+  );
+  check_recovery(
+    "function f() { a;",
+    vec![FunctionDecl::new(Box::from("f"), vec![], vec![Identifier::from("a").into()]).into()],
+    1,
+  );
+  check_recovery(
+    "foo(1, 2",
+    vec![Call::new(
+      Identifier::from("foo"),
+      vec![Const::from(1).into(), Const::from(2).into()],
+    )
+    .into()],
+    1,
+  );
+  check_recovery(
+    "foo(1,",
+    vec![Call::new(Identifier::from("foo"), vec![Const::from(1).into()]).into()],
+    1,
+  );
+
+  // The missing parenthesis is reported where the input ends.
+  let recovered = Parser::new("foo(1,\n  2".as_bytes()).parse_script_with_recovery();
+  let position = recovered.errors()[0].position().expect("no position");
+  assert_eq!((position.line_number(), position.column_number()), (2, 4));
+
+  assert!(Parser::new("let a = ;".as_bytes()).parse_script().is_err());
+}
+
+#[test]
+fn recover_statements() {
+  check_recovery(
+    "foo(1, ; bar;",
+    vec![Node::error(), Identifier::from("bar").into()],
+    1,
+  );
+  check_recovery(
+    "function f() { let = ; return 1; }",
+    vec![FunctionDecl::new(
+      Box::from("f"),
+      vec![],
+      vec![Node::error(), Return::new(Some(Const::from(1))).into()],
+    )
+    .into()],
+    1,
+  );
+  check_recovery(
+    "a; } b;",
+    vec![Identifier::from("a").into(), Identifier::from("b").into()],
+    1,
+  );
+  check_recovery(
+    "var 1 + (2; if (a) {}",
+    vec![
+      Node::error(),
+      If::new::<_, _, Node, _>(Identifier::from("a"), Block::from(vec![]), None).into(),
+    ],
+    1,
+  );
+  check_recovery(
+    "a @ ; b;",
+    vec![Identifier::from("a").into(), Identifier::from("b").into()],
+    1,
+  );
+
+  // Scopes and labels entered by a broken statement are left again.
+  check_recovery(
+    "a: var = 1;\na: { function f(1) {} break a; }",
+    vec![
+      Node::error(),
+      Labelled::new(
+        "a",
+        Block::from(vec![Node::error(), Break::new("a").into()]),
+      )
+      .into(),
+    ],
+    2,
+  );
+}
+
+#[test]
+fn recovery_terminates() {
+  for js in &[
+    ")",
+    "]]",
+    "{{{",
+    "((",
+    "a(",
+    "[,",
+    "class { x",
+    "for (;;",
+    "} } }",
+    "=>",
+    "...",
+    ":",
+    "import",
+    "a ? b",
+    "({ a: })",
+    "x = [1, 2",
+    "try {",
+    "switch (a) { case",
+    "new",
+    "function",
+    "a.",
+    "`",
+    "let [a, ...] = b;",
+    "'abc",
+  ] {
+    let recovered = Parser::new(js.as_bytes()).parse_script_with_recovery();
+    assert!(!recovered.is_ok(), "no error in {:?}", js);
+  }
+}
+
+/// Checks that every node of a tree has a span, inside the span of the node containing it.
+struct SpanChecker<'a> {
+  source: &'a str,
+  ancestors: Vec<Span>,
+}
+
+impl SpanChecker<'_> {
+  /// Checks the span of a node and makes it the innermost ancestor while the node is walked.
+  #[track_caller]
+  fn enter(&mut self, kind: &str, span: Option<Span>) {
+    let span = span.unwrap_or_else(|| panic!("{} without a span in {:?}", kind, self.source));
+    if let Some(parent) = self.ancestors.last() {
+      assert!(
+        parent.contains(span),
+        "{} at {} outside of its parent at {} in {:?}",
+        kind,
+        span,
+        parent,
+        self.source
+      );
+    }
+    self.ancestors.push(span);
+  }
+
+  /// Checks the span of the name or the label of a node, which must have one if it has a name.
+  #[track_caller]
+  fn name(&mut self, kind: &str, name: Option<&str>, span: Option<Span>) {
+    if name.is_some() {
+      self.enter(kind, span);
+      let _ = self.ancestors.pop();
+    } else {
+      assert_eq!(span, None, "{} without a name in {:?}", kind, self.source);
+    }
+  }
+
+  /// Leaves the innermost ancestor, passing the flow of its walk through.
+  fn leave(&mut self, flow: Flow) -> Flow {
+    let _ = self.ancestors.pop();
+    flow
+  }
+}
+
+impl<'ast> Visitor<'ast> for SpanChecker<'_> {
+  fn visit_node(&mut self, node: &'ast Node, path: &mut NodePath<'ast>) -> Flow {
+    self.enter("node", node.span());
+    match node {
+      Node::FunctionDecl(decl) => self.name("function name", Some(decl.name()), decl.name_span()),
+      Node::Labelled(labelled) => self.name("label", Some(labelled.label()), labelled.label_span()),
+      Node::Break(brk) => self.name("label", brk.label(), brk.label_span()),
+      Node::Continue(cont) => self.name("label", cont.label(), cont.label_span()),
+      _ => {}
+    }
+    let flow = visit::walk_node(self, node, path);
+    self.leave(flow)
+  }
+
+  fn visit_pattern(&mut self, pattern: &'ast Pattern, path: &mut NodePath<'ast>) -> Flow {
+    self.enter("pattern", pattern.span());
+    let flow = visit::walk_pattern(self, pattern, path);
+    self.leave(flow)
+  }
+
+  fn visit_identifier(&mut self, ident: &'ast Identifier, _path: &mut NodePath<'ast>) -> Flow {
+    self.enter("identifier", ident.span());
+    self.leave(Flow::Continue(()))
+  }
+
+  fn visit_declaration(&mut self, decl: &'ast Declaration, path: &mut NodePath<'ast>) -> Flow {
+    self.enter("declaration", decl.span());
+    let flow = visit::walk_declaration(self, decl, path);
+    self.leave(flow)
+  }
+
+  fn visit_formal_parameter(
+    &mut self,
+    param: &'ast FormalParameter,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    self.enter("parameter", param.span());
+    let flow = visit::walk_formal_parameter(self, param, path);
+    self.leave(flow)
+  }
+
+  fn visit_function_expr(&mut self, expr: &'ast FunctionExpr, path: &mut NodePath<'ast>) -> Flow {
+    self.enter("function", expr.span());
+    self.name(
+      "function name",
+      expr.name().map(Sym::as_str),
+      expr.name_span(),
+    );
+    let flow = visit::walk_function_expr(self, expr, path);
+    self.leave(flow)
+  }
+
+  fn visit_class(&mut self, class: &'ast Class, path: &mut NodePath<'ast>) -> Flow {
+    self.enter("class", class.span());
+    self.name(
+      "class name",
+      class.name().map(Sym::as_str),
+      class.name_span(),
+    );
+    let flow = visit::walk_class(self, class, path);
+    self.leave(flow)
+  }
+
+  fn visit_block(&mut self, block: &'ast Block, path: &mut NodePath<'ast>) -> Flow {
+    self.enter("block", block.span());
+    let flow = visit::walk_block(self, block, path);
+    self.leave(flow)
+  }
+
+  fn visit_case(&mut self, case: &'ast Case, path: &mut NodePath<'ast>) -> Flow {
+    self.enter("case", case.span());
+    let flow = visit::walk_case(self, case, path);
+    self.leave(flow)
+  }
+
+  fn visit_catch(&mut self, catch: &'ast Catch, path: &mut NodePath<'ast>) -> Flow {
+    self.enter("catch", catch.span());
+    let flow = visit::walk_catch(self, catch, path);
+    self.leave(flow)
+  }
+}
+
+/// Checks the spans of a tree parsed from a source.
+#[track_caller]
+fn check_spans(source: &str, items: &[Node]) {
+  let mut checker = SpanChecker {
+    source,
+    ancestors: Vec::new(),
+  };
+  let _ = checker.visit_statements(items, &mut Path::new());
+}
+
+#[test]
+fn spans_contain_children() {
+  let script = r#"
+var a = 1, { b, c: [d = 2, ...e] } = f;
+function g(h, { i } = {}, ...j) {
+  if (h > 0 && !i) { return h ** 2; } else return;
+  { let t = new g(h), u = new g; t = u += a, delete t[u]; }
+  k: for (let k = 0; k < 10; k++) continue k;
+  for (const l of j) label: while (l) break label;
+  for (var m in h) do m--; while (m);
+  switch (h) { case 1: throw new Error(`bad ${h}`); default: }
+  try { h?.[i](...j); } catch ({ message }) { debugger; } finally {}
+}
+const n = async (o) => await o, p = x => ({ x, y: [x, , x] });
+let q = class Q extends g { #r = 1; static s() { return #r in this && super.t; } };
+[a, b.c] = [typeof a, void 0 ? a : b];
+(function* r() { yield* a, yield; })();
+tag`x${a?.b.c()}y`, { get r() {}, set r(v) {}, [a]: 1, ...b, *s() {} };
+"#;
+  let script_tree = Parser::new(script.as_bytes())
+    .parse_script()
+    .expect("failed to parse");
+  check_spans(script, script_tree.items());
+
+  let module = r#"
+import u, { v as w } from "mod";
+export const z = import.meta;
+export default class D { m() { return import("mod"); } }
+export { u, w as y };
+"#;
+  let module_tree = Parser::new(module.as_bytes())
+    .parse_module()
+    .expect("failed to parse");
+  check_spans(module, module_tree.body().items());
+}
@@ -0,0 +1,223 @@
+//! Enum declaration parsing.
+//!
+//! An enum becomes an object filled by a function, as TypeScript emits it:
+//!
+//! ```text
+//! enum E { A, B = "b" }
+//! ```
+//!
+//! gives
+//!
+//! ```text
+//! var E;
+//! (function (E) {
+//!   E[E["A"] = 0] = "A";
+//!   E["B"] = "b";
+//! })(E || (E = {}));
+//! ```
+//!
+//! Numeric members are also mapped back from their value to their name. Initializers which
+//! only use literals and earlier members are folded to their value, so that the next member
+//! can count on from it.
+//!
+//! More information:
+//!  - [TypeScript handbook][handbook]
+//!
+//! [handbook]: https://www.typescriptlang.org/docs/handbook/enums.html
+
+use super::{define_object, type_name};
+use crate::syntax::{
+  ast::{
+    arena::ArenaBox,
+    node::{Assign, GetConstField, GetField, Identifier, Node, Pattern},
+    visitor::{visit_mut, Flow, KindPath, VisitorMut},
+    Const, Keyword, Punctuator,
+  },
+  fold::{evaluate, Value},
+  lexer::TokenKind,
+  parser::{expression::AssignmentExpression, Cursor, ParseError, TokenParser},
+};
+use std::{collections::HashMap, io::Read};
+
+/// Parses an enum declaration, following `const` for a `const enum`.
+#[derive(Debug, Clone)]
+pub(super) struct EnumDeclaration {
+  namespace: Option<Box<str>>,
+}
+
+impl EnumDeclaration {
+  /// Creates a new `EnumDeclaration` parser, for an enum exported from `namespace` if any.
+  pub(super) fn new(namespace: Option<Box<str>>) -> Self {
+    Self { namespace }
+  }
+}
+
+impl<R> TokenParser<R> for EnumDeclaration
+where
+  R: Read,
+{
+  type Output = Vec<Node>;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    cursor.expect(Keyword::Enum, "enum declaration")?;
+    let (name, span) = type_name(cursor, "enum declaration")?;
+    cursor.expect(Punctuator::OpenBlock, "enum declaration")?;
+
+    let mut members = Members {
+      enum_name: name.clone(),
+      values: HashMap::new(),
+    };
+    let mut statements = Vec::new();
+    // The value of a member without an initializer, which follows a numeric member.
+    let mut next = Some(0.0);
+    while cursor.next_if(Punctuator::CloseBlock)?.is_none() {
+      let tok = cursor.next()?.ok_or(ParseError::AbruptEnd)?;
+      let member: Box<str> = match tok.kind() {
+        TokenKind::Identifier(name) => name.as_str().into(),
+        TokenKind::StringLiteral(name) => name.clone(),
+        TokenKind::Keyword(keyword) => keyword.as_str().into(),
+        TokenKind::NumericLiteral(_) => {
+          return Err(ParseError::general(
+            "an enum member can not have a numeric name",
+            tok.span().start(),
+          ))
+        }
+        _ => {
+          return Err(ParseError::unexpected(
+            tok.kind().clone(),
+            tok.span(),
+            "expected an enum member",
+          ))
+        }
+      };
+
+      let value = if cursor.next_if(Punctuator::Assign)?.is_some() {
+        let mut init = AssignmentExpression::new(true, false, false).parse(cursor)?;
+        let _ = members.visit_node(&mut init, &mut KindPath::new());
+        match evaluate(&init) {
+          Some(Value::Number(n)) if Value::Number(n).to_const().is_some() => MemberValue::Number(n),
+          Some(Value::String(s)) => MemberValue::String(s),
+          _ => MemberValue::Computed(init),
+        }
+      } else {
+        match next {
+          Some(n) => MemberValue::Number(n),
+          None => {
+            return Err(ParseError::general(
+              "an enum member must have an initializer after a string or computed member",
+              tok.span().start(),
+            ))
+          }
+        }
+      };
+
+      next = match &value {
+        MemberValue::Number(n) => Some(n + 1.0),
+        MemberValue::String(_) | MemberValue::Computed(_) => None,
+      };
+      let constant = value.constant();
+      statements.push(value.assignment(&name, &member));
+      members.values.insert(member, constant);
+
+      if cursor.next_if(Punctuator::Comma)?.is_none() {
+        cursor.expect(Punctuator::CloseBlock, "enum declaration")?;
+        break;
+      }
+    }
+
+    define_object(cursor, &name, span, self.namespace.as_deref(), statements)
+  }
+}
+
+/// The value of an enum member.
+#[derive(Debug)]
+enum MemberValue {
+  Number(f64),
+  String(Box<str>),
+  /// A value only known at run time, which is a number.
+  Computed(Node),
+}
+
+impl MemberValue {
+  /// Gets the constant value, if it is known.
+  fn constant(&self) -> Option<Const> {
+    match self {
+      Self::Number(n) => Value::Number(*n).to_const(),
+      Self::String(s) => Some(Const::from(s.as_ref())),
+      Self::Computed(_) => None,
+    }
+  }
+
+  /// Builds the statement setting the member `member` of the enum `name`.
+  fn assignment(self, name: &str, member: &str) -> Node {
+    let property = |key: Node| {
+      Pattern::Member(ArenaBox::new(
+        GetField::new(Identifier::from(name), key).into(),
+      ))
+    };
+    let member_name = || Node::from(Const::from(member));
+    let value = match self {
+      Self::String(s) => {
+        return Assign::new(property(member_name()), Const::from(s.as_ref())).into()
+      }
+      Self::Number(n) => Value::Number(n)
+        .to_const()
+        .map(Node::from)
+        .expect("enum member values are finite numbers"),
+      Self::Computed(init) => init,
+    };
+    let forward = Assign::new(property(member_name()), value);
+    Assign::new(property(forward.into()), member_name()).into()
+  }
+}
+
+/// The members of an enum declared so far, which an initializer can refer to by name.
+#[derive(Debug)]
+struct Members {
+  enum_name: Box<str>,
+  values: HashMap<Box<str>, Option<Const>>,
+}
+
+impl Members {
+  /// Gets the expression of the member `name`: its value if it is known, the property of the
+  /// enum otherwise.
+  fn member(&self, name: &str) -> Option<Node> {
+    let value = self.values.get(name)?;
+    Some(match value {
+      Some(value) => value.clone().into(),
+      None => GetConstField::new(Identifier::from(self.enum_name.as_ref()), name).into(),
+    })
+  }
+}
+
+impl VisitorMut for Members {
+  fn visit_node(&mut self, node: &mut Node, path: &mut KindPath) -> Flow {
+    let replacement = match node {
+      Node::Identifier(ident) => self.member(ident.name()),
+      Node::GetConstField(field) => match field.obj() {
+        Node::Identifier(obj) if obj.name() == self.enum_name.as_ref() => self
+          .member(field.field())
+          .filter(|member| matches!(member, Node::Const(_, _))),
+        _ => None,
+      },
+      Node::GetField(field) => match (field.obj(), field.field()) {
+        (Node::Identifier(obj), Node::Const(Const::String(key), _))
+          if obj.name() == self.enum_name.as_ref() =>
+        {
+          self
+            .member(key)
+            .filter(|member| matches!(member, Node::Const(_, _)))
+        }
+        _ => None,
+      },
+      _ => None,
+    };
+    match replacement {
+      Some(replacement) => {
+        *node = replacement;
+        Flow::Continue(())
+      }
+      None => visit_mut::walk_node(self, node, path),
+    }
+  }
+}
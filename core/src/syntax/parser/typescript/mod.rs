@@ -0,0 +1,776 @@
+//! TypeScript parsing, in the TypeScript mode of the parser.
+//!
+//! TypeScript is parsed as JavaScript with extra syntax, which is stripped as it is read: only
+//! the JavaScript it stands for ends up in the syntax tree. Types are skipped without being
+//! checked, and type-only declarations such as interfaces and type aliases declare their names
+//! as types, so that exporting them exports nothing. Enums, namespaces and parameter properties
+//! become the statements they run as.
+//!
+//! The source is read file by file, without type information: constructs whose output depends
+//! on other files, such as `export =` or ambient `const enum`s, are errors. A `const enum` is
+//! emitted as a plain enum, since its uses can only be inlined where it is declared.
+//!
+//! More information:
+//!  - [TypeScript handbook][handbook]
+//!
+//! [handbook]: https://www.typescriptlang.org/docs/handbook/intro.html
+
+mod enums;
+mod namespace;
+#[cfg(test)]
+mod tests;
+mod types;
+
+pub(in crate::syntax::parser) use self::types::{
+  call_type_arguments, is_alternate_ahead, is_keyword_before_name, return_type_annotation,
+  skip_type, skip_type_arguments, skip_type_parameters, type_annotation, typed_arrow,
+};
+use self::{enums::EnumDeclaration, namespace::NamespaceDeclaration};
+use crate::syntax::{
+  ast::{
+    arena::ArenaBox,
+    node::{
+      Assign, BinOp, Call, Declaration as VariableDeclaration, DeclarationList, ExportDecl,
+      ExportSpecifier, FormalParameter, FunctionExpr, GetConstField, Identifier, Node, Object,
+      Pattern, StatementList,
+    },
+    operator::LogOp,
+    Keyword, Punctuator, Span,
+  },
+  lexer::TokenKind,
+  parser::{
+    class::{ClassDeclaration, ClassTail},
+    cursor::BindingKind,
+    pattern::{BindingIdentifier, BindingTarget},
+    statement::is_contextual,
+    AllowAwait, AllowYield, Cursor, ParseError, TokenParser,
+  },
+};
+use std::io::Read;
+
+/// Returns `true` if the next tokens start a TypeScript declaration, which is parsed by
+/// [`Declaration`].
+pub(in crate::syntax::parser) fn is_declaration<R>(
+  cursor: &mut Cursor<R>,
+) -> Result<bool, ParseError>
+where
+  R: Read,
+{
+  is_declaration_at(cursor, 0)
+}
+
+/// Returns `true` if the tokens starting `skip` tokens ahead start a TypeScript declaration.
+pub(in crate::syntax::parser) fn is_declaration_at<R>(
+  cursor: &mut Cursor<R>,
+  skip: usize,
+) -> Result<bool, ParseError>
+where
+  R: Read,
+{
+  let name = match cursor.peek(skip)?.map(|tk| tk.kind()) {
+    Some(TokenKind::Keyword(Keyword::Enum)) => return Ok(true),
+    Some(TokenKind::Keyword(Keyword::Const)) => {
+      return Ok(matches!(
+        cursor.peek(skip + 1)?.map(|tk| tk.kind()),
+        Some(TokenKind::Keyword(Keyword::Enum))
+      ))
+    }
+    Some(TokenKind::Keyword(Keyword::Function)) => {
+      return types::is_function_signature(cursor, skip)
+    }
+    Some(TokenKind::Identifier(name)) => name.clone(),
+    _ => return Ok(false),
+  };
+
+  match name.as_ref() {
+    "interface" | "type" | "namespace" | "module" => is_keyword_before_name(cursor, skip, &name),
+    "abstract" => Ok(
+      matches!(
+        cursor.peek(skip + 1)?.map(|tk| tk.kind()),
+        Some(TokenKind::Keyword(Keyword::Class))
+      ) && !cursor.is_line_terminator_before(skip + 1)?,
+    ),
+    "declare" => {
+      let declaration = match cursor.peek(skip + 1)?.map(|tk| tk.kind()) {
+        Some(TokenKind::Keyword(Keyword::Var))
+        | Some(TokenKind::Keyword(Keyword::Let))
+        | Some(TokenKind::Keyword(Keyword::Const))
+        | Some(TokenKind::Keyword(Keyword::Function))
+        | Some(TokenKind::Keyword(Keyword::Class))
+        | Some(TokenKind::Keyword(Keyword::Enum)) => true,
+        Some(TokenKind::Identifier(name)) => matches!(
+          name.as_ref(),
+          "namespace" | "module" | "global" | "abstract"
+        ),
+        _ => false,
+      };
+      Ok(declaration && !cursor.is_line_terminator_before(skip + 1)?)
+    }
+    "async" => Ok(
+      !cursor.is_line_terminator_before(skip + 1)?
+        && types::is_function_signature(cursor, skip + 1)?,
+    ),
+    _ => Ok(false),
+  }
+}
+
+/// Parses a TypeScript declaration.
+///
+/// Type-only declarations give no statement. Enums and namespaces give a variable declaration,
+/// unless the name is already declared, followed by the function call filling the object.
+///
+/// More information:
+///  - [TypeScript handbook][handbook]
+///
+/// [handbook]: https://www.typescriptlang.org/docs/handbook/declaration-files/by-example.html
+#[derive(Debug, Clone)]
+pub(in crate::syntax::parser) struct Declaration {
+  allow_yield: AllowYield,
+  allow_await: AllowAwait,
+  namespace: Option<Box<str>>,
+}
+
+impl Declaration {
+  /// Creates a new `Declaration` parser.
+  pub(in crate::syntax::parser) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+  where
+    Y: Into<AllowYield>,
+    A: Into<AllowAwait>,
+  {
+    Self {
+      allow_yield: allow_yield.into(),
+      allow_await: allow_await.into(),
+      namespace: None,
+    }
+  }
+
+  /// Sets the namespace the declaration is exported from, enums and namespaces then also
+  /// become properties of the namespace object.
+  pub(in crate::syntax::parser) fn exported_from<N>(mut self, namespace: N) -> Self
+  where
+    N: Into<Box<str>>,
+  {
+    self.namespace = Some(namespace.into());
+    self
+  }
+}
+
+impl<R> TokenParser<R> for Declaration
+where
+  R: Read,
+{
+  type Output = Vec<Node>;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let tok = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.clone();
+    match tok.kind() {
+      TokenKind::Keyword(Keyword::Enum) => EnumDeclaration::new(self.namespace).parse(cursor),
+      TokenKind::Keyword(Keyword::Const) => {
+        let _ = cursor.next()?.expect("const token vanished");
+        EnumDeclaration::new(self.namespace).parse(cursor)
+      }
+      TokenKind::Keyword(Keyword::Function) => {
+        skip_function_signature(cursor)?;
+        Ok(Vec::new())
+      }
+      TokenKind::Identifier(name) => match name.as_ref() {
+        "interface" => {
+          interface(cursor)?;
+          Ok(Vec::new())
+        }
+        "type" => {
+          type_alias(cursor)?;
+          Ok(Vec::new())
+        }
+        "namespace" | "module" => NamespaceDeclaration::new(self.namespace).parse(cursor),
+        "abstract" => {
+          let _ = cursor.next()?.expect("abstract token vanished");
+          let class = ClassDeclaration::new(self.allow_yield, self.allow_await).parse(cursor)?;
+          Ok(vec![class])
+        }
+        "declare" => {
+          let _ = cursor.next()?.expect("declare token vanished");
+          self.ambient(cursor)?;
+          Ok(Vec::new())
+        }
+        "async" => {
+          let _ = cursor.next()?.expect("async token vanished");
+          skip_function_signature(cursor)?;
+          Ok(Vec::new())
+        }
+        _ => Err(ParseError::unexpected(
+          tok.kind().clone(),
+          tok.span(),
+          "expected a declaration",
+        )),
+      },
+      _ => Err(ParseError::unexpected(
+        tok.kind().clone(),
+        tok.span(),
+        "expected a declaration",
+      )),
+    }
+  }
+}
+
+impl Declaration {
+  /// Skips an ambient declaration, following `declare`, which describes a value that exists
+  /// at run time without defining it.
+  ///
+  /// The declared names are types: exporting them exports nothing.
+  fn ambient<R>(&self, cursor: &mut Cursor<R>) -> Result<(), ParseError>
+  where
+    R: Read,
+  {
+    let tok = cursor.next()?.ok_or(ParseError::AbruptEnd)?;
+    match tok.kind() {
+      TokenKind::Keyword(Keyword::Const) if cursor.next_is(Keyword::Enum)? => {
+        Err(ParseError::general(
+          "ambient const enums need type information to be inlined",
+          tok.span().start(),
+        ))
+      }
+      TokenKind::Keyword(Keyword::Var)
+      | TokenKind::Keyword(Keyword::Let)
+      | TokenKind::Keyword(Keyword::Const) => {
+        loop {
+          let mark = cursor.bindings_mark();
+          let _ = BindingTarget::new(self.allow_yield, self.allow_await).parse(cursor)?;
+          cursor.declare_type_bindings(mark);
+          type_annotation(cursor)?;
+          if cursor.next_if(Punctuator::Comma)?.is_none() {
+            break;
+          }
+        }
+        cursor.expect_semicolon("ambient variable declaration")
+      }
+      TokenKind::Keyword(Keyword::Function) => {
+        let name = signature_rest(cursor)?;
+        cursor.declare_type(&name);
+        Ok(())
+      }
+      TokenKind::Keyword(Keyword::Class) => self.ambient_class(cursor),
+      TokenKind::Identifier(name) if name.as_ref() == "abstract" => {
+        cursor.expect(Keyword::Class, "ambient class declaration")?;
+        self.ambient_class(cursor)
+      }
+      TokenKind::Keyword(Keyword::Enum) => {
+        let (name, _) = type_name(cursor, "ambient enum declaration")?;
+        skip_block(cursor, "ambient enum declaration")?;
+        cursor.declare_type(&name);
+        Ok(())
+      }
+      TokenKind::Identifier(name) if name.as_ref() == "global" => {
+        skip_block(cursor, "ambient global declaration")
+      }
+      TokenKind::Identifier(_) => {
+        // `declare namespace A.B { ... }`, or `declare module "name";`.
+        let name = match cursor.next()? {
+          Some(tk) => match tk.kind() {
+            TokenKind::Identifier(name) => Some(name.clone()),
+            TokenKind::StringLiteral(_) => None,
+            _ => {
+              return Err(ParseError::unexpected(
+                tk.kind().clone(),
+                tk.span(),
+                "expected a namespace name",
+              ))
+            }
+          },
+          None => return Err(ParseError::AbruptEnd),
+        };
+        while cursor.next_if(Punctuator::Dot)?.is_some() {
+          let _ = type_name(cursor, "ambient namespace declaration")?;
+        }
+        if cursor.next_is(Punctuator::OpenBlock)? {
+          skip_block(cursor, "ambient namespace declaration")?;
+        } else {
+          cursor.expect_semicolon("ambient module declaration")?;
+        }
+        if let Some(name) = name {
+          cursor.declare_type(&name);
+        }
+        Ok(())
+      }
+      _ => Err(ParseError::unexpected(
+        tok.kind().clone(),
+        tok.span(),
+        "expected an ambient declaration",
+      )),
+    }
+  }
+
+  /// Skips an ambient class, following `class`: the members are parsed as usual, and can
+  /// leave out method bodies.
+  fn ambient_class<R>(&self, cursor: &mut Cursor<R>) -> Result<(), ParseError>
+  where
+    R: Read,
+  {
+    let mark = cursor.bindings_mark();
+    let name = BindingIdentifier::new(self.allow_yield, self.allow_await).parse(cursor)?;
+    cursor.discard_bindings(mark);
+    cursor.declare_type(&name);
+    skip_type_parameters(cursor)?;
+    let _ = ClassTail::new(name, self.allow_yield, self.allow_await).parse(cursor)?;
+    Ok(())
+  }
+}
+
+/// Skips an interface declaration, such as `interface Point { x: number; y: number }`.
+fn interface<R>(cursor: &mut Cursor<R>) -> Result<(), ParseError>
+where
+  R: Read,
+{
+  let _ = cursor.next()?.expect("interface token vanished");
+  let (name, _) = type_name(cursor, "interface declaration")?;
+  skip_type_parameters(cursor)?;
+  if cursor.next_if(Keyword::Extends)?.is_some() {
+    skip_type_list(cursor)?;
+  }
+  skip_block(cursor, "interface declaration")?;
+  cursor.declare_type(&name);
+  Ok(())
+}
+
+/// Skips a type alias, such as `type Pair<T> = [T, T];`.
+fn type_alias<R>(cursor: &mut Cursor<R>) -> Result<(), ParseError>
+where
+  R: Read,
+{
+  let _ = cursor.next()?.expect("type token vanished");
+  let (name, _) = type_name(cursor, "type alias")?;
+  skip_type_parameters(cursor)?;
+  cursor.expect(Punctuator::Assign, "type alias")?;
+  skip_type(cursor)?;
+  cursor.expect_semicolon("type alias")?;
+  cursor.declare_type(&name);
+  Ok(())
+}
+
+/// Skips a function overload signature, such as `function f(x: string): string;`.
+fn skip_function_signature<R>(cursor: &mut Cursor<R>) -> Result<(), ParseError>
+where
+  R: Read,
+{
+  cursor.expect(Keyword::Function, "function signature")?;
+  let _ = signature_rest(cursor)?;
+  Ok(())
+}
+
+/// Skips a function signature following `function`, and returns the name of the function.
+fn signature_rest<R>(cursor: &mut Cursor<R>) -> Result<Box<str>, ParseError>
+where
+  R: Read,
+{
+  let _ = cursor.next_if(Punctuator::Mul)?;
+  let (name, _) = type_name(cursor, "function signature")?;
+  skip_type_parameters(cursor)?;
+  if !cursor.next_is(Punctuator::OpenParen)? {
+    cursor.expect(Punctuator::OpenParen, "function signature")?;
+  }
+  skip_brackets(cursor)?;
+  return_type_annotation(cursor)?;
+  cursor.expect_semicolon("function signature")?;
+  Ok(name)
+}
+
+/// Consumes the name of a declaration.
+fn type_name<R>(
+  cursor: &mut Cursor<R>,
+  context: &'static str,
+) -> Result<(Box<str>, Span), ParseError>
+where
+  R: Read,
+{
+  let tok = cursor.next()?.ok_or(ParseError::AbruptEnd)?;
+  match tok.kind() {
+    TokenKind::Identifier(name) => Ok((name.as_str().into(), tok.span())),
+    _ => Err(ParseError::expected(
+      vec![TokenKind::identifier("identifier")],
+      tok.kind().clone(),
+      tok.span(),
+      context,
+    )),
+  }
+}
+
+/// Skips a block of members, such as the body of an interface, which must be next.
+fn skip_block<R>(cursor: &mut Cursor<R>, context: &'static str) -> Result<(), ParseError>
+where
+  R: Read,
+{
+  if !cursor.next_is(Punctuator::OpenBlock)? {
+    cursor.expect(Punctuator::OpenBlock, context)?;
+  }
+  skip_brackets(cursor)
+}
+
+/// Consumes the brackets starting at the next token and everything between them.
+fn skip_brackets<R>(cursor: &mut Cursor<R>) -> Result<(), ParseError>
+where
+  R: Read,
+{
+  let close = cursor
+    .find_matching_bracket(0)?
+    .ok_or(ParseError::AbruptEnd)?;
+  for _ in 0..=close {
+    let _ = cursor.next()?;
+  }
+  Ok(())
+}
+
+/// Consumes a comma separated list of types, such as the interfaces after `implements`.
+pub(in crate::syntax::parser) fn skip_type_list<R>(cursor: &mut Cursor<R>) -> Result<(), ParseError>
+where
+  R: Read,
+{
+  skip_type(cursor)?;
+  while cursor.next_if(Punctuator::Comma)?.is_some() {
+    skip_type(cursor)?;
+  }
+  Ok(())
+}
+
+/// Consumes a type assertion, `as T`, `as const` or `satisfies T`, if one is next, and returns
+/// `true` if it did.
+pub(in crate::syntax::parser) fn skip_as_expression<R>(
+  cursor: &mut Cursor<R>,
+) -> Result<bool, ParseError>
+where
+  R: Read,
+{
+  let is_as = is_contextual(cursor, 0, "as")?;
+  if !(is_as || is_contextual(cursor, 0, "satisfies")?) || cursor.is_line_terminator_before(0)? {
+    return Ok(false);
+  }
+  let _ = cursor.next()?.expect("as token vanished");
+  if !(is_as && cursor.next_if(Keyword::Const)?.is_some()) {
+    skip_type(cursor)?;
+  }
+  Ok(true)
+}
+
+/// Returns `true` if the next parameter is the `this` parameter, which only gives the type of
+/// `this` in the function.
+pub(in crate::syntax::parser) fn is_this_parameter<R>(
+  cursor: &mut Cursor<R>,
+) -> Result<bool, ParseError>
+where
+  R: Read,
+{
+  Ok(
+    matches!(
+      cursor.peek(0)?.map(|tk| tk.kind()),
+      Some(TokenKind::Keyword(Keyword::This))
+    ) && matches!(
+      cursor.peek(1)?.map(|tk| tk.kind()),
+      Some(TokenKind::Punctuator(Punctuator::Colon))
+        | Some(TokenKind::Punctuator(Punctuator::Comma))
+        | Some(TokenKind::Punctuator(Punctuator::CloseParen))
+    ),
+  )
+}
+
+/// Consumes the modifiers of a parameter, such as `private readonly`, and returns the span of
+/// the first one.
+///
+/// A constructor parameter with modifiers is a parameter property, which is also assigned to a
+/// property of the instance.
+pub(in crate::syntax::parser) fn parameter_modifiers<R>(
+  cursor: &mut Cursor<R>,
+) -> Result<Option<Span>, ParseError>
+where
+  R: Read,
+{
+  let mut first = None;
+  loop {
+    let is_modifier = matches!(
+      cursor.peek(0)?.map(|tk| tk.kind()),
+      Some(TokenKind::Identifier(name))
+        if matches!(name.as_ref(), "public" | "private" | "protected" | "readonly" | "override")
+    ) && matches!(
+      cursor.peek(1)?.map(|tk| tk.kind()),
+      Some(TokenKind::Identifier(_))
+        | Some(TokenKind::Keyword(Keyword::Yield))
+        | Some(TokenKind::Keyword(Keyword::Await))
+        | Some(TokenKind::Punctuator(Punctuator::OpenBlock))
+        | Some(TokenKind::Punctuator(Punctuator::OpenBracket))
+    );
+    if !is_modifier {
+      return Ok(first);
+    }
+    let tok = cursor.next()?.expect("modifier token vanished");
+    first.get_or_insert(tok.span());
+  }
+}
+
+/// The modifiers of a class element, such as `private` or `abstract`.
+#[derive(Debug, Clone, Copy, Default)]
+pub(in crate::syntax::parser) struct ClassModifiers {
+  ambient: bool,
+}
+
+impl ClassModifiers {
+  /// Consumes the modifiers ahead.
+  ///
+  /// A modifier is followed by the name of the element, `private` alone is the name of an
+  /// element.
+  pub(in crate::syntax::parser) fn parse<R>(
+    &mut self,
+    cursor: &mut Cursor<R>,
+  ) -> Result<(), ParseError>
+  where
+    R: Read,
+  {
+    loop {
+      let ambient = match cursor.peek(0)?.map(|tk| tk.kind()) {
+        Some(TokenKind::Identifier(name)) => match name.as_ref() {
+          "public" | "private" | "protected" | "readonly" | "override" => false,
+          "abstract" | "declare" => true,
+          _ => return Ok(()),
+        },
+        _ => return Ok(()),
+      };
+      let starts_name = matches!(
+        cursor.peek(1)?.map(|tk| tk.kind()),
+        Some(TokenKind::Identifier(_))
+          | Some(TokenKind::Keyword(_))
+          | Some(TokenKind::StringLiteral(_))
+          | Some(TokenKind::NumericLiteral(_))
+          | Some(TokenKind::BooleanLiteral(_))
+          | Some(TokenKind::NullLiteral)
+          | Some(TokenKind::PrivateIdentifier(_))
+          | Some(TokenKind::Punctuator(Punctuator::OpenBracket))
+          | Some(TokenKind::Punctuator(Punctuator::Mul))
+      );
+      if !starts_name || cursor.is_line_terminator_before(1)? {
+        return Ok(());
+      }
+      let _ = cursor.next()?.expect("modifier token vanished");
+      self.ambient |= ambient;
+    }
+  }
+
+  /// Returns `true` if the element is `abstract` or `declare`, and so does not exist at run
+  /// time.
+  pub(in crate::syntax::parser) fn is_ambient(self) -> bool {
+    self.ambient
+  }
+}
+
+/// Returns `true` if an index signature, such as `[key: string]: number;`, is next.
+pub(in crate::syntax::parser) fn is_index_signature<R>(
+  cursor: &mut Cursor<R>,
+) -> Result<bool, ParseError>
+where
+  R: Read,
+{
+  Ok(
+    cursor.next_is(Punctuator::OpenBracket)?
+      && matches!(
+        cursor.peek(1)?.map(|tk| tk.kind()),
+        Some(TokenKind::Identifier(_))
+      )
+      && matches!(
+        cursor.peek(2)?.map(|tk| tk.kind()),
+        Some(TokenKind::Punctuator(Punctuator::Colon))
+      ),
+  )
+}
+
+/// Skips an index signature.
+pub(in crate::syntax::parser) fn skip_index_signature<R>(
+  cursor: &mut Cursor<R>,
+) -> Result<(), ParseError>
+where
+  R: Read,
+{
+  skip_brackets(cursor)?;
+  type_annotation(cursor)?;
+  cursor.expect_semicolon("index signature")
+}
+
+/// Adds the assignments of the parameter properties `names` to a constructor body: they run
+/// right after the `super(...)` call, or first when there is none.
+pub(in crate::syntax::parser) fn assign_parameter_properties(
+  mut items: Vec<Node>,
+  names: &[Box<str>],
+) -> Vec<Node> {
+  let position = match items
+    .iter()
+    .position(|item| matches!(item, Node::Call(call) if matches!(call.expr(), Node::Super(_))))
+  {
+    Some(call) => call + 1,
+    None => items
+      .iter()
+      .take_while(|item| matches!(item, Node::Directive(_)))
+      .count(),
+  };
+  let assignments = names.iter().map(|name| {
+    let property = GetConstField::new(Node::this(), name.as_ref());
+    Assign::new(
+      Pattern::Member(ArenaBox::new(property.into())),
+      Identifier::from(name.as_ref()),
+    )
+    .into()
+  });
+  let _ = items.splice(position..position, assignments);
+  items
+}
+
+/// Returns `true` if the next token is a `type` modifier, of an import or export, or of one
+/// of their specifiers: `import type { A }`, `import { type A }`.
+pub(in crate::syntax::parser) fn is_type_modifier<R>(
+  cursor: &mut Cursor<R>,
+) -> Result<bool, ParseError>
+where
+  R: Read,
+{
+  if !is_contextual(cursor, 0, "type")? {
+    return Ok(false);
+  }
+  match cursor.peek(1)?.map(|tk| tk.kind()) {
+    Some(TokenKind::Punctuator(Punctuator::OpenBlock))
+    | Some(TokenKind::Punctuator(Punctuator::Mul))
+    | Some(TokenKind::StringLiteral(_))
+    | Some(TokenKind::Keyword(_)) => Ok(true),
+    // `import { type as }` imports `type`, `import type from "m"` imports `type` as a default.
+    Some(TokenKind::Identifier(name)) => match name.as_ref() {
+      "as" => Ok(false),
+      "from" => is_contextual(cursor, 2, "from"),
+      _ => Ok(true),
+    },
+    _ => Ok(false),
+  }
+}
+
+/// Returns `true` if an import assignment, such as `import fs = require("fs")`, follows
+/// `import`.
+pub(in crate::syntax::parser) fn is_import_assignment<R>(
+  cursor: &mut Cursor<R>,
+) -> Result<bool, ParseError>
+where
+  R: Read,
+{
+  Ok(
+    matches!(
+      cursor.peek(0)?.map(|tk| tk.kind()),
+      Some(TokenKind::Identifier(_))
+    ) && matches!(
+      cursor.peek(1)?.map(|tk| tk.kind()),
+      Some(TokenKind::Punctuator(Punctuator::Assign))
+    ),
+  )
+}
+
+/// Returns `true` if the next token is the contextual keyword `name`, followed on the same line
+/// by `keyword`, as in `abstract class`.
+pub(in crate::syntax::parser) fn is_contextual_before<R>(
+  cursor: &mut Cursor<R>,
+  name: &str,
+  keyword: Keyword,
+) -> Result<bool, ParseError>
+where
+  R: Read,
+{
+  Ok(
+    is_contextual(cursor, 0, name)?
+      && cursor
+        .peek(1)?
+        .is_some_and(|tk| tk.kind() == &TokenKind::Keyword(keyword))
+      && !cursor.is_line_terminator_before(1)?,
+  )
+}
+
+/// Removes the type-only names from the local export lists of a module, `export { A }` where
+/// `A` is an interface: a list left empty is removed.
+pub(in crate::syntax::parser) fn remove_type_exports<R>(
+  cursor: &Cursor<R>,
+  body: &mut StatementList,
+) {
+  body.replace_items(|items| {
+    items
+      .into_iter()
+      .filter_map(|item| {
+        let (specifiers, attributes) = match &item {
+          Node::Export(
+            ExportDecl::Named {
+              specifiers,
+              source: None,
+              attributes,
+            },
+            _,
+          ) => (specifiers, attributes),
+          _ => return Some(item),
+        };
+        if !specifiers
+          .iter()
+          .any(|spec| cursor.is_type_only(spec.local()))
+        {
+          return Some(item);
+        }
+        let kept: Vec<ExportSpecifier> = specifiers
+          .iter()
+          .filter(|spec| !cursor.is_type_only(spec.local()))
+          .cloned()
+          .collect();
+        if kept.is_empty() {
+          None
+        } else {
+          Some(ExportDecl::named(kept, None, attributes.clone()).into())
+        }
+      })
+      .collect()
+  });
+}
+
+/// Builds the statements defining the object of the enum or namespace `name` from the
+/// statements filling it: a variable declaration, unless the name is already declared, and a
+/// call of a function getting the object as its parameter.
+///
+/// `enum E { A }` gives `var E; (function (E) { ... })(E || (E = {}));`. In a namespace `N`
+/// which exports it, the argument is `E = N.E || (N.E = {})`.
+fn define_object<R>(
+  cursor: &mut Cursor<R>,
+  name: &str,
+  span: Span,
+  namespace: Option<&str>,
+  body: Vec<Node>,
+) -> Result<Vec<Node>, ParseError>
+where
+  R: Read,
+{
+  let mut items = Vec::with_capacity(2);
+  if !cursor.is_declared(name) {
+    cursor.declare_binding(name, span, BindingKind::Var)?;
+    let declaration = VariableDeclaration::new(Identifier::from(name), None);
+    items.push(DeclarationList::Var(vec![declaration].into()).into());
+  }
+
+  let empty = || Node::from(Object::from(Vec::new()));
+  let argument: Node = match namespace {
+    None => BinOp::new(
+      LogOp::Or,
+      Identifier::from(name),
+      Assign::new(Identifier::from(name), empty()),
+    )
+    .into(),
+    Some(namespace) => {
+      let property = || Node::from(GetConstField::new(Identifier::from(namespace), name));
+      let object = BinOp::new(
+        LogOp::Or,
+        property(),
+        Assign::new(Pattern::Member(ArenaBox::new(property())), empty()),
+      );
+      Assign::new(Identifier::from(name), object).into()
+    }
+  };
+
+  let parameter = FormalParameter::new(
+    VariableDeclaration::new(Identifier::from(name), None),
+    false,
+  );
+  let function = FunctionExpr::new(None, vec![parameter], body);
+  items.push(Call::new(function, vec![argument]).into());
+  Ok(items)
+}
@@ -0,0 +1,192 @@
+//! Namespace declaration parsing.
+//!
+//! A namespace becomes an object filled by a function, whose body is the body of the
+//! namespace, as TypeScript emits it:
+//!
+//! ```text
+//! namespace N { export function f() {} }
+//! ```
+//!
+//! gives
+//!
+//! ```text
+//! var N;
+//! (function (N) {
+//!   function f() {}
+//!   N.f = f;
+//! })(N || (N = {}));
+//! ```
+//!
+//! Exported variables are copied to the namespace object after their declaration: unlike
+//! TypeScript, which rewrites their uses to properties of the object, later assignments to them
+//! are not seen from outside of the namespace. A namespace which only holds types exists only
+//! at compile time, and gives no statement.
+//!
+//! More information:
+//!  - [TypeScript handbook][handbook]
+//!
+//! [handbook]: https://www.typescriptlang.org/docs/handbook/namespaces.html
+
+use super::{define_object, is_declaration, type_name, Declaration};
+use crate::syntax::{
+  ast::{
+    arena::ArenaBox,
+    node::{Assign, GetConstField, Identifier, Node, Pattern},
+    Keyword, Punctuator, Span, Sym,
+  },
+  lexer::TokenKind,
+  parser::{
+    cursor::ScopeKind,
+    statement::{
+      is_async_function, Declaration as StatementDeclaration, StatementListItem, VariableStatement,
+    },
+    Cursor, ParseError, TokenParser,
+  },
+};
+use std::io::Read;
+
+/// Parses a namespace declaration, `namespace N { ... }` or `module N { ... }`.
+#[derive(Debug, Clone)]
+pub(super) struct NamespaceDeclaration {
+  namespace: Option<Box<str>>,
+}
+
+impl NamespaceDeclaration {
+  /// Creates a new `NamespaceDeclaration` parser, for a namespace exported from `namespace` if
+  /// any.
+  pub(super) fn new(namespace: Option<Box<str>>) -> Self {
+    Self { namespace }
+  }
+}
+
+impl<R> TokenParser<R> for NamespaceDeclaration
+where
+  R: Read,
+{
+  type Output = Vec<Node>;
+
+  fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+    let _ = cursor.next()?.expect("namespace token vanished");
+    let (name, span) = type_name(cursor, "namespace declaration")?;
+    namespace(cursor, name, span, self.namespace)
+  }
+}
+
+/// Parses the rest of a namespace declaration following its name `name`: the body, or the
+/// rest of a dotted name, `namespace A.B { ... }` being `A` exporting the namespace `B`.
+fn namespace<R>(
+  cursor: &mut Cursor<R>,
+  name: Box<str>,
+  span: Span,
+  exported_from: Option<Box<str>>,
+) -> Result<Vec<Node>, ParseError>
+where
+  R: Read,
+{
+  cursor.push_scope(ScopeKind::Function);
+  let body = if cursor.next_if(Punctuator::Dot)?.is_some() {
+    type_name(cursor, "namespace declaration")
+      .and_then(|(inner, inner_span)| namespace(cursor, inner, inner_span, Some(name.clone())))
+  } else {
+    namespace_body(cursor, &name)
+  };
+  cursor.pop_scope();
+
+  let body = body?;
+  if body.is_empty() {
+    cursor.declare_type(&name);
+    return Ok(Vec::new());
+  }
+  define_object(cursor, &name, span, exported_from.as_deref(), body)
+}
+
+/// Parses the body of the namespace `name`, between braces.
+fn namespace_body<R>(cursor: &mut Cursor<R>, name: &str) -> Result<Vec<Node>, ParseError>
+where
+  R: Read,
+{
+  cursor.expect(Punctuator::OpenBlock, "namespace declaration")?;
+  let mut items = Vec::new();
+  while cursor.next_if(Punctuator::CloseBlock)?.is_none() {
+    let point = cursor.recovery_point()?;
+    let item = namespace_item(cursor, name);
+    items.extend(cursor.recover_item(point, item, |span| vec![Node::error().with_span(span)])?);
+  }
+  Ok(items)
+}
+
+/// Parses an item of the body of the namespace `name`, which can be exported.
+fn namespace_item<R>(cursor: &mut Cursor<R>, name: &str) -> Result<Vec<Node>, ParseError>
+where
+  R: Read,
+{
+  if cursor.next_if(Keyword::Export)?.is_none() {
+    if is_declaration(cursor)? {
+      return Declaration::new(false, false).parse(cursor);
+    }
+    return StatementListItem::new(false, false, false)
+      .parse(cursor)
+      .map(|item| vec![item]);
+  }
+
+  if is_declaration(cursor)? {
+    let mut items = Declaration::new(false, false)
+      .exported_from(name)
+      .parse(cursor)?;
+    // Enums and namespaces set their property themselves, only an abstract class is left.
+    if let Some(Node::ClassDecl(class)) = items.first() {
+      let exports = export_assignment(name, class.name().map(Sym::as_str));
+      items.extend(exports);
+    }
+    return Ok(items);
+  }
+
+  let tok = cursor.peek(0)?.ok_or(ParseError::AbruptEnd)?.clone();
+  let declaration = match tok.kind() {
+    TokenKind::Keyword(Keyword::Var) => VariableStatement::new(false, false).parse(cursor)?,
+    TokenKind::Keyword(Keyword::Let)
+    | TokenKind::Keyword(Keyword::Const)
+    | TokenKind::Keyword(Keyword::Function)
+    | TokenKind::Keyword(Keyword::Class) => {
+      StatementDeclaration::new(false, false).parse(cursor)?
+    }
+    _ if is_async_function(cursor)? => StatementDeclaration::new(false, false).parse(cursor)?,
+    _ => {
+      return Err(ParseError::unexpected(
+        tok.kind().clone(),
+        tok.span(),
+        "expected a declaration to export from a namespace",
+      ))
+    }
+  };
+
+  let names: Vec<Box<str>> = match &declaration {
+    Node::DeclarationList(list, _) => list
+      .as_ref()
+      .iter()
+      .flat_map(|decl| decl.target().bound_names())
+      .map(Box::from)
+      .collect(),
+    Node::FunctionDecl(function) => vec![function.name().into()],
+    Node::ClassDecl(class) => class.name().map(Box::from).into_iter().collect(),
+    _ => Vec::new(),
+  };
+  let mut items = vec![declaration];
+  for local in &names {
+    items.extend(export_assignment(name, Some(local.as_ref())));
+  }
+  Ok(items)
+}
+
+/// Builds the assignment exporting `local` from the namespace `name`, `N.local = local;`.
+fn export_assignment(name: &str, local: Option<&str>) -> Option<Node> {
+  let local = local?;
+  let property = GetConstField::new(Identifier::from(name), local);
+  Some(
+    Assign::new(
+      Pattern::Member(ArenaBox::new(property.into())),
+      Identifier::from(local),
+    )
+    .into(),
+  )
+}
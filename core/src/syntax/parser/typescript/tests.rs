@@ -0,0 +1,215 @@
+//! Tests for the TypeScript mode of the parser.
+
+use crate::syntax::{
+  codegen::{Generator, Options},
+  parser::Parser,
+};
+
+/// Parses a TypeScript script, and prints the JavaScript it stands for.
+#[track_caller]
+fn strip(ts: &str) -> String {
+  let script = Parser::new(ts.as_bytes())
+    .with_typescript(true)
+    .parse_script()
+    .unwrap_or_else(|err| panic!("failed to parse `{}`: {}", ts, err));
+  Generator::new(Options::compact()).script(&script)
+}
+
+/// Parses a TypeScript module, and prints the JavaScript it stands for.
+#[track_caller]
+fn strip_module(ts: &str) -> String {
+  let module = Parser::new(ts.as_bytes())
+    .with_typescript(true)
+    .parse_module()
+    .unwrap_or_else(|err| panic!("failed to parse `{}`: {}", ts, err));
+  Generator::new(Options::compact()).module(&module)
+}
+
+/// Checks that a TypeScript module can not be stripped.
+#[track_caller]
+fn check_invalid(ts: &str) {
+  assert!(
+    Parser::new(ts.as_bytes())
+      .with_typescript(true)
+      .parse_module()
+      .is_err(),
+    "`{}` was parsed",
+    ts
+  );
+}
+
+#[test]
+fn annotations() {
+  assert_eq!(
+    strip("let x: number = 1, y: Array<string> = [];"),
+    "let x=1,y=[]"
+  );
+  assert_eq!(strip("let x!: number;"), "let x");
+  assert_eq!(
+    strip("function f<T extends object = {}>(a: T, b?: string, ...c: number[]): T | undefined { return a; }"),
+    "function f(a,b,...c){return a}"
+  );
+  assert_eq!(
+    strip("function g(this: Window, { a }: { a: number } = {} as any): asserts a is string {}"),
+    "function g({a}={}){}"
+  );
+  assert_eq!(strip("try {} catch (e: unknown) {}"), "try{}catch(e){}");
+  assert_eq!(
+    strip("let t: [a: string, b?: number, ...c: any[]]; let w: A.B<C>[]; let z: Array<Array<number>>= [];"),
+    "let t;let w;let z=[]"
+  );
+}
+
+#[test]
+fn arrow_functions() {
+  assert_eq!(
+    strip("const f = <T,>(x: T): T => x; const g = async <T>(x: T): Promise<T> => x;"),
+    "const f=x=>x;const g=async x=>x"
+  );
+  assert_eq!(
+    strip("let h = (x: number): x is 1 => true;"),
+    "let h=x=>true"
+  );
+  assert_eq!(strip("let r = a ? (b) : c => d;"), "let r=a?b:c=>d");
+  assert_eq!(strip("let r = a ? (b): c => d : e;"), "let r=a?b=>d:e");
+}
+
+#[test]
+fn expressions() {
+  assert_eq!(
+    strip("a = (x as any) as string; b = y satisfies T; c = [1] as const; d = e!.f!; g!();"),
+    "a=x;b=y;c=[1];d=e.f;g()"
+  );
+  assert_eq!(
+    strip("let v = <any>x; let w = (<Window>y).z;"),
+    "let v=x;let w=y.z"
+  );
+  assert_eq!(
+    strip("a < b; a < b > c; f<string>(x); new Map<string, number>(); f<T>`x`;"),
+    "a<b;a<b>c;f(x);new Map();f`x`"
+  );
+  assert_eq!(strip("let g = a < (b as number) ? c : d;"), "let g=a<b?c:d");
+  assert_eq!(
+    strip("let f = function <T>(x: T): T { return x; };"),
+    "let f=function(x){return x}"
+  );
+}
+
+#[test]
+fn type_declarations() {
+  assert_eq!(
+    strip("interface A<T> extends B, C<T> { x: number; y(): void }"),
+    ""
+  );
+  assert_eq!(
+    strip("type P<T> = { [K in keyof T]?: T[K] } | ((x: number) => void) | `a${string}`;"),
+    ""
+  );
+  assert_eq!(
+    strip("type C<T> = T extends infer U extends number ? U : T extends string ? 'a' : never;"),
+    ""
+  );
+  assert_eq!(
+    strip("function o(a: string): string; function o(a: any) { return a; }"),
+    "function o(a){return a}"
+  );
+  assert_eq!(
+    strip("declare const a: number; declare function f(): void; declare class C { m(): void }"),
+    ""
+  );
+  assert_eq!(
+    strip("let type = 1; type = 2; let declare = 1; declare = 2; module.exports = type;"),
+    "let type=1;type=2;let declare=1;declare=2;module.exports=type"
+  );
+}
+
+#[test]
+fn enums() {
+  assert_eq!(
+    strip("enum E { A, B, C = 10, D, S = \"s\", T = S, U = B | C }"),
+    "var E;(function(E){E[E[\"A\"]=0]=\"A\";E[E[\"B\"]=1]=\"B\";E[E[\"C\"]=10]=\"C\";\
+     E[E[\"D\"]=11]=\"D\";E[\"S\"]=\"s\";E[\"T\"]=\"s\";E[E[\"U\"]=11]=\"U\"})(E||(E={}))"
+  );
+  assert_eq!(
+    strip("const enum K { A = f(), B = 2 }"),
+    "var K;(function(K){K[K[\"A\"]=f()]=\"A\";K[K[\"B\"]=2]=\"B\"})(K||(K={}))"
+  );
+  assert!(Parser::new("enum E { A = 'a', B }".as_bytes())
+    .with_typescript(true)
+    .parse_script()
+    .is_err());
+}
+
+#[test]
+fn namespaces() {
+  assert_eq!(
+    strip("namespace N { export const x = 1; export function f() {} let y = 2; }"),
+    "var N;(function(N){const x=1;N.x=x;function f(){}N.f=f;let y=2})(N||(N={}))"
+  );
+  assert_eq!(
+    strip("namespace N.M { export enum E { A } }"),
+    "var N;(function(N){var M;(function(M){var E;(function(E){E[E[\"A\"]=0]=\"A\"})\
+     (E=M.E||(M.E={}))})(M=N.M||(N.M={}))})(N||(N={}))"
+  );
+  assert_eq!(
+    strip("namespace T { export interface I {} type J = number; }"),
+    ""
+  );
+}
+
+#[test]
+fn classes() {
+  assert_eq!(
+    strip(
+      "class A<T> extends B<T> implements I, J<T> { private x: number = 1; y?: string; \
+       declare d: number; [k: string]: any; static readonly s = 1; \
+       constructor(public a: number, private readonly b = 2) { super(); } \
+       m<U>(u: U): U { return u; } n(): void; n() {} }"
+    ),
+    "class A extends B{constructor(a,b=2){super();this.a=a;this.b=b}x=1;y;static s=1;\
+     m(u){return u}n(){}}"
+  );
+  assert_eq!(
+    strip(
+      "abstract class Q { abstract m(): void; constructor(protected p: string) { 'use strict'; } }"
+    ),
+    "class Q{constructor(p){'use strict';this.p=p}}"
+  );
+  assert_eq!(
+    strip("class C { public; private = 1; declare; }"),
+    "class C{public;private=1;declare;}"
+  );
+  assert!(Parser::new("class C { m(public a) {} }".as_bytes())
+    .with_typescript(true)
+    .parse_script()
+    .is_err());
+}
+
+#[test]
+fn modules() {
+  assert_eq!(
+    strip_module(
+      "import type { A } from 'a'; import { type B, C } from 'b'; export type { A }; \
+       interface I {} const v = 1; export { v, I as J };"
+    ),
+    "import {C}from\"b\";const v=1;export{v}"
+  );
+  assert_eq!(
+    strip_module("export interface I {} export type T = 1; export enum E { A } export declare const d: number;"),
+    "export var E;(function(E){E[E[\"A\"]=0]=\"A\"})(E||(E={}))"
+  );
+  assert_eq!(
+    strip_module(
+      "export default class implements I {} export as namespace Lib; export type * from './t';"
+    ),
+    "export default class{}"
+  );
+}
+
+#[test]
+fn type_information() {
+  check_invalid("export = x;");
+  check_invalid("import x = require('y');");
+  check_invalid("export import A = B.C;");
+  check_invalid("declare const enum E { A }");
+}
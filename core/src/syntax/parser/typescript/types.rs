@@ -0,0 +1,799 @@
+//! TypeScript type syntax, which is skipped since types are erased.
+//!
+//! Types are scanned on the tokens ahead of the cursor, without consuming them. The same scan
+//! consumes a type annotation, and tells whether `<` starts type arguments or is a comparison,
+//! as in `f<T>(x)` and `a < b`.
+//!
+//! Object types, tuples and parameter lists are skipped as balanced brackets: only their end
+//! matters.
+
+use crate::syntax::{
+  ast::{Keyword, Punctuator},
+  lexer::TokenKind,
+  parser::{cursor::split_greater_than, statement::is_contextual, Cursor, ParseError},
+};
+use std::io::Read;
+
+/// A position in the tokens ahead of the cursor: a number of significant tokens, and the number
+/// of `>` characters already read from the next one, for a `>>` closing two type argument lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(in crate::syntax::parser) struct Ahead {
+  tokens: usize,
+  split: usize,
+}
+
+impl Ahead {
+  /// Gets the position of the token `tokens` significant tokens ahead.
+  pub(in crate::syntax::parser) fn new(tokens: usize) -> Self {
+    Self { tokens, split: 0 }
+  }
+
+  /// Consumes the tokens up to this position.
+  pub(in crate::syntax::parser) fn advance<R>(
+    self,
+    cursor: &mut Cursor<R>,
+  ) -> Result<(), ParseError>
+  where
+    R: Read,
+  {
+    for _ in 0..self.tokens {
+      let _ = cursor.next()?;
+    }
+    for _ in 0..self.split {
+      cursor.next_greater_than()?;
+    }
+    Ok(())
+  }
+}
+
+/// Scans types on the tokens ahead of the cursor.
+///
+/// Each scanning method returns `false` if the tokens do not make the syntax it scans, leaving
+/// the scanner at an unspecified position. The furthest position where a scan failed is kept
+/// for error messages.
+#[derive(Debug)]
+pub(in crate::syntax::parser) struct Scanner<'c, R> {
+  cursor: &'c mut Cursor<R>,
+  at: Ahead,
+  failed: Ahead,
+  /// Whether the scanned type follows the `extends` of a conditional type.
+  in_extends: bool,
+}
+
+impl<'c, R> Scanner<'c, R>
+where
+  R: Read,
+{
+  /// Creates a scanner starting `skip` significant tokens ahead of the cursor.
+  pub(in crate::syntax::parser) fn new(cursor: &'c mut Cursor<R>, skip: usize) -> Self {
+    Self {
+      cursor,
+      at: Ahead::new(skip),
+      failed: Ahead::new(skip),
+      in_extends: false,
+    }
+  }
+
+  /// Gets the current position.
+  pub(in crate::syntax::parser) fn position(&self) -> Ahead {
+    self.at
+  }
+
+  /// Gets the current token, without the `>` characters split from it.
+  pub(in crate::syntax::parser) fn kind(&mut self) -> Result<Option<TokenKind>, ParseError> {
+    let mut kind = match self.cursor.peek(self.at.tokens)? {
+      Some(tk) => tk.kind().clone(),
+      None => return Ok(None),
+    };
+    for _ in 0..self.at.split {
+      kind = match kind {
+        TokenKind::Punctuator(p) => match split_greater_than(p) {
+          Some(Some(rest)) => TokenKind::Punctuator(rest),
+          _ => unreachable!("only `>` characters are split from a token"),
+        },
+        _ => unreachable!("only `>` characters are split from a token"),
+      };
+    }
+    Ok(Some(kind))
+  }
+
+  /// Returns `true` if the current token is of kind `kind`.
+  fn is<K>(&mut self, kind: K) -> Result<bool, ParseError>
+  where
+    K: Into<TokenKind>,
+  {
+    Ok(self.kind()? == Some(kind.into()))
+  }
+
+  /// Returns `true` if the token `n` tokens after the current one is of kind `kind`.
+  fn is_at<K>(&mut self, n: usize, kind: K) -> Result<bool, ParseError>
+  where
+    K: Into<TokenKind>,
+  {
+    let kind = kind.into();
+    Ok(
+      self
+        .cursor
+        .peek(self.at.tokens + n)?
+        .is_some_and(|tk| tk.kind() == &kind),
+    )
+  }
+
+  /// Returns `true` if a line terminator precedes the current token.
+  fn is_line_terminator_before(&mut self) -> Result<bool, ParseError> {
+    Ok(self.at.split == 0 && self.cursor.is_line_terminator_before(self.at.tokens)?)
+  }
+
+  /// Moves to the next token.
+  fn bump(&mut self) {
+    self.at = Ahead::new(self.at.tokens + 1);
+  }
+
+  /// Moves past the current token if it is of kind `kind`.
+  fn eat<K>(&mut self, kind: K) -> Result<bool, ParseError>
+  where
+    K: Into<TokenKind>,
+  {
+    let found = self.is(kind)?;
+    if found {
+      self.bump();
+    }
+    Ok(found)
+  }
+
+  /// Moves past a `>`, which may start a longer token.
+  fn eat_greater_than(&mut self) -> Result<bool, ParseError> {
+    let rest = match self.kind()? {
+      Some(TokenKind::Punctuator(p)) => split_greater_than(p),
+      _ => None,
+    };
+    match rest {
+      Some(Some(_)) => self.at.split += 1,
+      Some(None) => self.bump(),
+      None => return Ok(false),
+    }
+    Ok(true)
+  }
+
+  /// Records a failed scan at the current position.
+  fn fail(&mut self) -> Result<bool, ParseError> {
+    self.failed = self.failed.max(self.at);
+    Ok(false)
+  }
+
+  /// Moves past brackets and everything between them, the current token being the opening
+  /// one.
+  fn skip_brackets(&mut self) -> Result<bool, ParseError> {
+    if self.at.split != 0 {
+      return self.fail();
+    }
+    match self.cursor.find_matching_bracket(self.at.tokens)? {
+      Some(close) => {
+        self.at = Ahead::new(close + 1);
+        Ok(true)
+      }
+      None => self.fail(),
+    }
+  }
+
+  /// Scans a type.
+  ///
+  /// More information:
+  ///  - [TypeScript handbook][handbook]
+  ///
+  /// [handbook]: https://www.typescriptlang.org/docs/handbook/2/types-from-types.html
+  pub(in crate::syntax::parser) fn scan_type(&mut self) -> Result<bool, ParseError> {
+    let in_extends = std::mem::replace(&mut self.in_extends, false);
+    let scanned = self.conditional_type(true);
+    self.in_extends = in_extends;
+    scanned
+  }
+
+  /// Scans a type, a conditional type only if `conditional` is `true`: the type following the
+  /// `extends` of a conditional type can not be one itself.
+  fn conditional_type(&mut self, conditional: bool) -> Result<bool, ParseError> {
+    if self.is_function_type()? {
+      return self.function_type();
+    }
+    if !self.union_type()? {
+      return Ok(false);
+    }
+    if !conditional || self.is_line_terminator_before()? || !self.is(Keyword::Extends)? {
+      return Ok(true);
+    }
+    self.bump();
+    let in_extends = std::mem::replace(&mut self.in_extends, true);
+    let extends = self.conditional_type(false);
+    self.in_extends = in_extends;
+    Ok(
+      extends?
+        && self.expect(Punctuator::Question)?
+        && self.scan_type()?
+        && self.expect(Punctuator::Colon)?
+        && self.scan_type()?,
+    )
+  }
+
+  /// Moves past a token of kind `kind`, failing if it is not the current one.
+  fn expect<K>(&mut self, kind: K) -> Result<bool, ParseError>
+  where
+    K: Into<TokenKind>,
+  {
+    if self.eat(kind)? {
+      Ok(true)
+    } else {
+      self.fail()
+    }
+  }
+
+  /// Returns `true` if a function or constructor type starts at the current token, such as
+  /// `(a: string) => void` or `new <T>() => T`.
+  fn is_function_type(&mut self) -> Result<bool, ParseError> {
+    match self.kind()? {
+      Some(TokenKind::Punctuator(Punctuator::LessThan))
+      | Some(TokenKind::Keyword(Keyword::New)) => Ok(true),
+      Some(TokenKind::Identifier(name)) if name.as_ref() == "abstract" => {
+        self.is_at(1, Keyword::New)
+      }
+      Some(TokenKind::Punctuator(Punctuator::OpenParen)) if self.at.split == 0 => {
+        match self.cursor.find_matching_bracket(self.at.tokens)? {
+          Some(close) => Ok(
+            self
+              .cursor
+              .peek(close + 1)?
+              .is_some_and(|tk| tk.kind() == &TokenKind::Punctuator(Punctuator::Arrow)),
+          ),
+          None => Ok(false),
+        }
+      }
+      _ => Ok(false),
+    }
+  }
+
+  /// Scans a function or constructor type.
+  fn function_type(&mut self) -> Result<bool, ParseError> {
+    let _ = self.eat(TokenKind::identifier("abstract"))?;
+    let _ = self.eat(Keyword::New)?;
+    if self.is(Punctuator::LessThan)? && !self.type_parameters()? {
+      return Ok(false);
+    }
+    if !self.is(Punctuator::OpenParen)? {
+      return self.fail();
+    }
+    Ok(self.skip_brackets()? && self.expect(Punctuator::Arrow)? && self.return_type()?)
+  }
+
+  /// Scans the return type of a function, which can be a type predicate such as `x is string`
+  /// or `asserts x`.
+  pub(in crate::syntax::parser) fn return_type(&mut self) -> Result<bool, ParseError> {
+    let is_name = |kind: Option<&TokenKind>| {
+      matches!(
+        kind,
+        Some(TokenKind::Identifier(_)) | Some(TokenKind::Keyword(Keyword::This))
+      )
+    };
+    let n = self.at.tokens;
+    if self.at.split == 0 {
+      let asserts = self.is(TokenKind::identifier("asserts"))?
+        && is_name(self.cursor.peek(n + 1)?.map(|tk| tk.kind()))
+        && !self.cursor.is_line_terminator_before(n + 1)?;
+      if asserts {
+        self.at = Ahead::new(n + 2);
+        if self.is(TokenKind::identifier("is"))? && !self.is_line_terminator_before()? {
+          self.bump();
+          return self.scan_type();
+        }
+        return Ok(true);
+      }
+      let predicate = is_name(self.cursor.peek(n)?.map(|tk| tk.kind()))
+        && self.is_at(1, TokenKind::identifier("is"))?
+        && !self.cursor.is_line_terminator_before(n + 1)?;
+      if predicate {
+        self.at = Ahead::new(n + 2);
+        return self.scan_type();
+      }
+    }
+    self.scan_type()
+  }
+
+  /// Scans a union of types, such as `A | B`, or a single type.
+  fn union_type(&mut self) -> Result<bool, ParseError> {
+    let _ = self.eat(Punctuator::Or)?;
+    if !self.intersection_type()? {
+      return Ok(false);
+    }
+    while self.eat(Punctuator::Or)? {
+      if !self.intersection_type()? {
+        return Ok(false);
+      }
+    }
+    Ok(true)
+  }
+
+  /// Scans an intersection of types, such as `A & B`, or a single type.
+  fn intersection_type(&mut self) -> Result<bool, ParseError> {
+    let _ = self.eat(Punctuator::And)?;
+    if !self.type_operator()? {
+      return Ok(false);
+    }
+    while self.eat(Punctuator::And)? {
+      if !self.type_operator()? {
+        return Ok(false);
+      }
+    }
+    Ok(true)
+  }
+
+  /// Scans a type with its prefix operators, such as `keyof T` or `readonly string[]`.
+  fn type_operator(&mut self) -> Result<bool, ParseError> {
+    match self.kind()? {
+      Some(TokenKind::Identifier(name))
+        if matches!(name.as_ref(), "keyof" | "unique" | "readonly") && self.starts_type(1)? =>
+      {
+        self.bump();
+        self.type_operator()
+      }
+      Some(TokenKind::Identifier(name)) if name.as_ref() == "infer" && self.starts_type(1)? => {
+        self.bump();
+        if !matches!(self.kind()?, Some(TokenKind::Identifier(_))) {
+          return self.fail();
+        }
+        self.bump();
+        // `infer U extends string` has a constraint, unless the `extends` starts a conditional
+        // type, which can not follow the `extends` of another one.
+        if self.is(Keyword::Extends)? {
+          let before = self.at;
+          self.bump();
+          if !self.conditional_type(false)?
+            || (!self.in_extends && self.is(Punctuator::Question)?)
+          {
+            self.at = before;
+          }
+        }
+        Ok(true)
+      }
+      _ => self.postfix_type(),
+    }
+  }
+
+  /// Returns `true` if the token `n` tokens after the current one can start a type, so that a
+  /// name such as `keyof` before it is an operator.
+  fn starts_type(&mut self, n: usize) -> Result<bool, ParseError> {
+    Ok(matches!(
+      self.cursor.peek(self.at.tokens + n)?.map(|tk| tk.kind()),
+      Some(TokenKind::Identifier(_))
+        | Some(TokenKind::Keyword(_))
+        | Some(TokenKind::StringLiteral(_))
+        | Some(TokenKind::NumericLiteral(_))
+        | Some(TokenKind::BooleanLiteral(_))
+        | Some(TokenKind::NullLiteral)
+        | Some(TokenKind::TemplateLiteral(_))
+        | Some(TokenKind::Punctuator(Punctuator::OpenParen))
+        | Some(TokenKind::Punctuator(Punctuator::OpenBracket))
+        | Some(TokenKind::Punctuator(Punctuator::OpenBlock))
+        | Some(TokenKind::Punctuator(Punctuator::LessThan))
+        | Some(TokenKind::Punctuator(Punctuator::Sub))
+    ))
+  }
+
+  /// Scans a type followed by array or indexed access suffixes, such as `T[]` or `T["key"]`.
+  fn postfix_type(&mut self) -> Result<bool, ParseError> {
+    if !self.primary_type()? {
+      return Ok(false);
+    }
+    while !self.is_line_terminator_before()? && self.eat(Punctuator::OpenBracket)? {
+      if self.eat(Punctuator::CloseBracket)? {
+        continue;
+      }
+      if !self.scan_type()? || !self.expect(Punctuator::CloseBracket)? {
+        return Ok(false);
+      }
+    }
+    Ok(true)
+  }
+
+  /// Scans a type without operators.
+  fn primary_type(&mut self) -> Result<bool, ParseError> {
+    let kind = match self.kind()? {
+      Some(kind) => kind,
+      None => return self.fail(),
+    };
+    match kind {
+      TokenKind::Punctuator(Punctuator::OpenParen) => {
+        self.bump();
+        Ok(self.scan_type()? && self.expect(Punctuator::CloseParen)?)
+      }
+      // Object types, mapped types and tuples.
+      TokenKind::Punctuator(Punctuator::OpenBlock)
+      | TokenKind::Punctuator(Punctuator::OpenBracket) => self.skip_brackets(),
+      TokenKind::Punctuator(Punctuator::Sub) => {
+        self.bump();
+        match self.kind()? {
+          Some(TokenKind::NumericLiteral(_)) => {
+            self.bump();
+            Ok(true)
+          }
+          _ => self.fail(),
+        }
+      }
+      TokenKind::StringLiteral(_)
+      | TokenKind::NumericLiteral(_)
+      | TokenKind::BooleanLiteral(_)
+      | TokenKind::NullLiteral
+      | TokenKind::Keyword(Keyword::Void)
+      | TokenKind::Keyword(Keyword::This) => {
+        self.bump();
+        Ok(true)
+      }
+      TokenKind::TemplateLiteral(template) if template.is_head() => {
+        self.bump();
+        let mut tail = template.is_tail();
+        while !tail {
+          if !self.scan_type()? {
+            return Ok(false);
+          }
+          match self.kind()? {
+            Some(TokenKind::TemplateLiteral(part)) if !part.is_head() => {
+              tail = part.is_tail();
+              self.bump();
+            }
+            _ => return self.fail(),
+          }
+        }
+        Ok(true)
+      }
+      TokenKind::Keyword(Keyword::TypeOf) => {
+        self.bump();
+        if self.is(Keyword::Import)? {
+          return self.import_type();
+        }
+        self.type_reference()
+      }
+      TokenKind::Keyword(Keyword::Import) => self.import_type(),
+      TokenKind::Identifier(_) => self.type_reference(),
+      _ => self.fail(),
+    }
+  }
+
+  /// Scans a reference to a named type, such as `Map<K, V>` or `ns.Type`.
+  fn type_reference(&mut self) -> Result<bool, ParseError> {
+    if !matches!(self.kind()?, Some(TokenKind::Identifier(_))) {
+      return self.fail();
+    }
+    self.bump();
+    self.qualified_name_rest()
+  }
+
+  /// Scans the `.name` parts and the type arguments following the start of a type reference.
+  fn qualified_name_rest(&mut self) -> Result<bool, ParseError> {
+    while self.eat(Punctuator::Dot)? {
+      match self.kind()? {
+        Some(TokenKind::Identifier(_))
+        | Some(TokenKind::Keyword(_))
+        | Some(TokenKind::BooleanLiteral(_))
+        | Some(TokenKind::NullLiteral) => self.bump(),
+        _ => return self.fail(),
+      }
+    }
+    if !self.is_line_terminator_before()? && self.is(Punctuator::LessThan)? {
+      return self.type_arguments();
+    }
+    Ok(true)
+  }
+
+  /// Scans an import type, such as `import("./mod").Type`.
+  fn import_type(&mut self) -> Result<bool, ParseError> {
+    self.bump();
+    if !self.is(Punctuator::OpenParen)? {
+      return self.fail();
+    }
+    Ok(self.skip_brackets()? && self.qualified_name_rest()?)
+  }
+
+  /// Scans type arguments, such as `<string, T[]>`.
+  pub(in crate::syntax::parser) fn type_arguments(&mut self) -> Result<bool, ParseError> {
+    if !self.expect(Punctuator::LessThan)? {
+      return Ok(false);
+    }
+    loop {
+      if !self.scan_type()? {
+        return Ok(false);
+      }
+      if !self.eat(Punctuator::Comma)? {
+        break;
+      }
+      // A trailing comma.
+      if self.eat_greater_than()? {
+        return Ok(true);
+      }
+    }
+    if self.eat_greater_than()? {
+      Ok(true)
+    } else {
+      self.fail()
+    }
+  }
+
+  /// Scans type parameters, such as `<T extends object = {}, const U>`.
+  pub(in crate::syntax::parser) fn type_parameters(&mut self) -> Result<bool, ParseError> {
+    if !self.expect(Punctuator::LessThan)? {
+      return Ok(false);
+    }
+    loop {
+      // The `in`, `out` and `const` modifiers, unless the parameter itself is named `out`.
+      loop {
+        let modifier = match self.kind()? {
+          Some(TokenKind::Keyword(Keyword::In)) | Some(TokenKind::Keyword(Keyword::Const)) => true,
+          Some(TokenKind::Identifier(name)) => name.as_ref() == "out",
+          _ => false,
+        };
+        let named = matches!(
+          self.cursor.peek(self.at.tokens + 1)?.map(|tk| tk.kind()),
+          Some(TokenKind::Identifier(_))
+        );
+        if !(modifier && named) {
+          break;
+        }
+        self.bump();
+      }
+      if !matches!(self.kind()?, Some(TokenKind::Identifier(_))) {
+        return self.fail();
+      }
+      self.bump();
+      if self.eat(Keyword::Extends)? && !self.scan_type()? {
+        return Ok(false);
+      }
+      if self.eat(Punctuator::Assign)? && !self.scan_type()? {
+        return Ok(false);
+      }
+      if !self.eat(Punctuator::Comma)? {
+        break;
+      }
+      if self.eat_greater_than()? {
+        return Ok(true);
+      }
+    }
+    if self.eat_greater_than()? {
+      Ok(true)
+    } else {
+      self.fail()
+    }
+  }
+
+  /// Creates the error for the failed scan that went the furthest.
+  pub(in crate::syntax::parser) fn error(&mut self, context: &'static str) -> ParseError {
+    let failed = self.failed;
+    match self.cursor.peek(failed.tokens) {
+      Ok(Some(tk)) => ParseError::unexpected(tk.kind().clone(), tk.span(), context),
+      Ok(None) => ParseError::AbruptEnd,
+      Err(err) => err,
+    }
+  }
+}
+
+/// Consumes a type annotation, such as `: string`, if the next token is a colon.
+pub(in crate::syntax::parser) fn type_annotation<R>(
+  cursor: &mut Cursor<R>,
+) -> Result<(), ParseError>
+where
+  R: Read,
+{
+  if cursor.next_if(Punctuator::Colon)?.is_some() {
+    skip_type(cursor)?;
+  }
+  Ok(())
+}
+
+/// Consumes a type.
+pub(in crate::syntax::parser) fn skip_type<R>(cursor: &mut Cursor<R>) -> Result<(), ParseError>
+where
+  R: Read,
+{
+  skip_with(cursor, "expected a type", |scanner| scanner.scan_type())
+}
+
+/// Consumes the return type of a function, such as `: string` or `: x is string`, if the next
+/// token is a colon.
+pub(in crate::syntax::parser) fn return_type_annotation<R>(
+  cursor: &mut Cursor<R>,
+) -> Result<(), ParseError>
+where
+  R: Read,
+{
+  if cursor.next_if(Punctuator::Colon)?.is_some() {
+    skip_with(cursor, "expected a return type", |scanner| {
+      scanner.return_type()
+    })?;
+  }
+  Ok(())
+}
+
+/// Consumes type parameters, such as `<T>`, if the next token is `<`.
+pub(in crate::syntax::parser) fn skip_type_parameters<R>(
+  cursor: &mut Cursor<R>,
+) -> Result<(), ParseError>
+where
+  R: Read,
+{
+  if cursor.next_is(Punctuator::LessThan)? {
+    skip_with(cursor, "in type parameters", |scanner| {
+      scanner.type_parameters()
+    })?;
+  }
+  Ok(())
+}
+
+/// Consumes type arguments, such as `<string>`, if the next token is `<`.
+pub(in crate::syntax::parser) fn skip_type_arguments<R>(
+  cursor: &mut Cursor<R>,
+) -> Result<(), ParseError>
+where
+  R: Read,
+{
+  if cursor.next_is(Punctuator::LessThan)? {
+    skip_with(cursor, "in type arguments", |scanner| {
+      scanner.type_arguments()
+    })?;
+  }
+  Ok(())
+}
+
+/// Consumes what `scan` scans, or returns the error of the scan.
+fn skip_with<R, F>(cursor: &mut Cursor<R>, context: &'static str, scan: F) -> Result<(), ParseError>
+where
+  R: Read,
+  F: FnOnce(&mut Scanner<'_, R>) -> Result<bool, ParseError>,
+{
+  let mut scanner = Scanner::new(cursor, 0);
+  if !scan(&mut scanner)? {
+    return Err(scanner.error(context));
+  }
+  let end = scanner.position();
+  end.advance(cursor)
+}
+
+/// Returns the position after the type arguments starting `skip` tokens ahead if they are
+/// followed by the arguments of a call or a template, as in `f<T>(x)`: `<` is otherwise a
+/// comparison.
+pub(in crate::syntax::parser) fn call_type_arguments<R>(
+  cursor: &mut Cursor<R>,
+  skip: usize,
+) -> Result<Option<Ahead>, ParseError>
+where
+  R: Read,
+{
+  let mut scanner = Scanner::new(cursor, skip);
+  if !scanner.type_arguments()? {
+    return Ok(None);
+  }
+  let end = scanner.position();
+  if end.split != 0 {
+    return Ok(None);
+  }
+  Ok(match scanner.kind()? {
+    Some(TokenKind::Punctuator(Punctuator::OpenParen)) => Some(end),
+    Some(TokenKind::TemplateLiteral(template)) if template.is_head() => Some(end),
+    _ => None,
+  })
+}
+
+/// Returns the position of the `=>` of an arrow function whose parameter list, with optional
+/// type parameters, starts `skip` tokens ahead and may be followed by a return type, as in
+/// `<T>(x: T): T => x`, and whether it has a return type.
+pub(in crate::syntax::parser) fn typed_arrow<R>(
+  cursor: &mut Cursor<R>,
+  skip: usize,
+) -> Result<Option<(usize, bool)>, ParseError>
+where
+  R: Read,
+{
+  let mut scanner = Scanner::new(cursor, skip);
+  if scanner.is(Punctuator::LessThan)? && !scanner.type_parameters()? {
+    return Ok(None);
+  }
+  if !scanner.is(Punctuator::OpenParen)? || !scanner.skip_brackets()? {
+    return Ok(None);
+  }
+  let return_type = scanner.eat(Punctuator::Colon)?;
+  if return_type && !scanner.return_type()? {
+    return Ok(None);
+  }
+  let end = scanner.position();
+  if end.split != 0 || !scanner.is(Punctuator::Arrow)? {
+    return Ok(None);
+  }
+  Ok(Some((end.tokens, return_type)))
+}
+
+/// Returns `true` if the `:` of an enclosing conditional expression follows the tokens
+/// starting `skip` tokens ahead, before the end of the expression.
+///
+/// In `a ? (b): c => d : e`, the consequent is an arrow function with a return type, while in
+/// `a ? (b) : c => d` the alternate is an arrow function.
+pub(in crate::syntax::parser) fn is_alternate_ahead<R>(
+  cursor: &mut Cursor<R>,
+  mut skip: usize,
+) -> Result<bool, ParseError>
+where
+  R: Read,
+{
+  let mut conditionals = 0_usize;
+  loop {
+    let kind = match cursor.peek(skip)? {
+      Some(tk) => tk.kind().clone(),
+      None => return Ok(false),
+    };
+    match kind {
+      TokenKind::Punctuator(Punctuator::OpenParen)
+      | TokenKind::Punctuator(Punctuator::OpenBracket)
+      | TokenKind::Punctuator(Punctuator::OpenBlock) => {
+        match cursor.find_matching_bracket(skip)? {
+          Some(close) => skip = close,
+          None => return Ok(false),
+        }
+      }
+      TokenKind::Punctuator(Punctuator::Question) => conditionals += 1,
+      TokenKind::Punctuator(Punctuator::Colon) => match conditionals.checked_sub(1) {
+        Some(rest) => conditionals = rest,
+        None => return Ok(true),
+      },
+      TokenKind::Punctuator(Punctuator::CloseParen)
+      | TokenKind::Punctuator(Punctuator::CloseBracket)
+      | TokenKind::Punctuator(Punctuator::CloseBlock)
+      | TokenKind::Punctuator(Punctuator::Semicolon)
+      | TokenKind::Punctuator(Punctuator::Comma) => return Ok(false),
+      _ => {}
+    }
+    skip += 1;
+  }
+}
+
+/// Returns `true` if the next token is the contextual keyword `name`, not followed by a line
+/// terminator but by a name, as in `interface Point`.
+pub(in crate::syntax::parser) fn is_keyword_before_name<R>(
+  cursor: &mut Cursor<R>,
+  skip: usize,
+  name: &str,
+) -> Result<bool, ParseError>
+where
+  R: Read,
+{
+  Ok(
+    is_contextual(cursor, skip, name)?
+      && matches!(
+        cursor.peek(skip + 1)?.map(|tk| tk.kind()),
+        Some(TokenKind::Identifier(_))
+      )
+      && !cursor.is_line_terminator_before(skip + 1)?,
+  )
+}
+
+/// Returns `true` if the function declaration starting `skip` tokens ahead is an overload
+/// signature, which has no body, as in `function f(x: string): string;`.
+pub(in crate::syntax::parser) fn is_function_signature<R>(
+  cursor: &mut Cursor<R>,
+  skip: usize,
+) -> Result<bool, ParseError>
+where
+  R: Read,
+{
+  let mut scanner = Scanner::new(cursor, skip);
+  if !scanner.eat(Keyword::Function)? {
+    return Ok(false);
+  }
+  scanner.eat(Punctuator::Mul)?;
+  if !matches!(scanner.kind()?, Some(TokenKind::Identifier(_))) {
+    return Ok(false);
+  }
+  scanner.bump();
+  if scanner.is(Punctuator::LessThan)? && !scanner.type_parameters()? {
+    return Ok(false);
+  }
+  if !scanner.is(Punctuator::OpenParen)? || !scanner.skip_brackets()? {
+    return Ok(false);
+  }
+  if scanner.eat(Punctuator::Colon)? && !scanner.return_type()? {
+    return Ok(false);
+  }
+  Ok(!scanner.is(Punctuator::OpenBlock)?)
+}
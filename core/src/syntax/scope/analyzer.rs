@@ -0,0 +1,670 @@
+//! The visitor building a [`ScopeTree`].
+//!
+//! Bindings are declared while the tree is visited, and the references are resolved once all
+//! of them are known, since `var` and function declarations are visible before them.
+
+use super::{
+  Access, Binding, BindingId, BindingKind, Reference, ReferenceId, Scope, ScopeId, ScopeKind,
+  ScopeTree,
+};
+use crate::syntax::ast::{
+  node::{
+    ArrowFunctionDecl, Assign, BinOp, Block, Call, Catch, Class, ClassElement, DeclarationList,
+    ExportDecl, ForInLoop, ForLoop, ForOfLoop, FormalParameter, FunctionDecl, FunctionExpr,
+    Identifier, ImportSpecifier, IterableLoopInitializer, Node, Pattern, PatternElement,
+    PropertyDefinition, PropertyName, Switch, UnaryOp, With,
+  },
+  operator,
+  visitor::{visit, Flow, NodeKind, NodePath, NodeRef, Path, Visitor},
+  NodeId, Sym,
+};
+use std::{
+  collections::{BTreeMap, HashMap},
+  mem,
+};
+
+/// What the identifiers being visited do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+  Reference(Access),
+  Declare(BindingKind),
+}
+
+#[derive(Debug)]
+pub(super) struct Analyzer {
+  tree: ScopeTree,
+  current: ScopeId,
+  mode: Mode,
+  /// The evaluation context: the top level, or a function body, a field initializer or a static
+  /// block, which run at another time than the code around them.
+  context: usize,
+  contexts: usize,
+  /// The bindings declared since the start of the current [`bind`](Self::bind).
+  declared: Vec<BindingId>,
+  /// Whether the declarations being visited are exported.
+  exporting: bool,
+  /// The references made by export lists.
+  exports: Vec<ReferenceId>,
+}
+
+impl Analyzer {
+  pub(super) fn new(kind: ScopeKind) -> Self {
+    Self {
+      tree: ScopeTree {
+        scopes: vec![Scope::new(kind, None)],
+        bindings: Vec::new(),
+        references: Vec::new(),
+        declaration_sites: HashMap::new(),
+        reference_sites: HashMap::new(),
+      },
+      current: ScopeId(0),
+      mode: Mode::Reference(Access::Read),
+      context: 0,
+      contexts: 1,
+      declared: Vec::new(),
+      exporting: false,
+      exports: Vec::new(),
+    }
+  }
+
+  /// Analyzes the statements of a script or a module.
+  pub(super) fn analyze(mut self, statements: &[Node]) -> ScopeTree {
+    let _ = self.visit_statements(statements, &mut Path::new());
+    self.resolve();
+    self.tree
+  }
+
+  fn scope(&mut self, id: ScopeId) -> &mut Scope {
+    &mut self.tree.scopes[id.0]
+  }
+
+  /// Visits the inside of a new scope.
+  fn scoped<F>(&mut self, kind: ScopeKind, visit: F) -> Flow
+  where
+    F: FnOnce(&mut Self) -> Flow,
+  {
+    let id = ScopeId(self.tree.scopes.len());
+    let parent = self.current;
+    self.tree.scopes.push(Scope::new(kind, Some(parent)));
+    self.scope(parent).children.push(id);
+    self.current = id;
+    // Only the declarations of the module scope can be exported.
+    let exporting = mem::replace(&mut self.exporting, false);
+    let flow = visit(self);
+    self.exporting = exporting;
+    self.current = parent;
+    flow
+  }
+
+  /// Visits code running in its own evaluation context.
+  fn deferred<F>(&mut self, visit: F) -> Flow
+  where
+    F: FnOnce(&mut Self) -> Flow,
+  {
+    let context = mem::replace(&mut self.context, self.contexts);
+    self.contexts += 1;
+    let flow = self.with_mode(Mode::Reference(Access::Read), visit);
+    self.context = context;
+    flow
+  }
+
+  /// Visits something with the identifiers in it doing what `mode` says.
+  fn with_mode<F>(&mut self, mode: Mode, visit: F) -> Flow
+  where
+    F: FnOnce(&mut Self) -> Flow,
+  {
+    let previous = mem::replace(&mut self.mode, mode);
+    let flow = visit(self);
+    self.mode = previous;
+    flow
+  }
+
+  /// Visits a binding pattern, returning the bindings it declares.
+  fn bind<F>(&mut self, kind: BindingKind, visit: F) -> (Flow, Vec<BindingId>)
+  where
+    F: FnOnce(&mut Self) -> Flow,
+  {
+    let outer = mem::take(&mut self.declared);
+    let flow = self.with_mode(Mode::Declare(kind), visit);
+    (flow, mem::replace(&mut self.declared, outer))
+  }
+
+  /// Marks bindings as initialized by the code visited so far.
+  fn initialize(&mut self, bindings: &[BindingId]) {
+    let count = self.tree.references.len();
+    for binding in bindings {
+      let binding = &mut self.tree.bindings[binding.0];
+      if binding.initialized.is_none() {
+        binding.initialized = Some(count);
+      }
+    }
+  }
+
+  /// Declares a name of the syntax tree in the current scope, or in the closest `var` scope for
+  /// `var` declarations and, in scripts, function declarations.
+  fn declare(&mut self, name: &Sym, site: NodeId, kind: BindingKind) -> BindingId {
+    let module = self.tree.scopes[0].kind == ScopeKind::Module;
+    let mut scope = self.current;
+    if kind == BindingKind::Var || (kind == BindingKind::Function && !module) {
+      while !self.tree.scopes[scope.0].kind.is_var_scope() {
+        scope = self.tree.scopes[scope.0]
+          .parent
+          .expect("the top level is a var scope");
+      }
+    }
+    self.declare_in(scope, name, site, kind)
+  }
+
+  /// Declares a name of the syntax tree in a scope.
+  fn declare_in(
+    &mut self,
+    scope: ScopeId,
+    name: &Sym,
+    site: NodeId,
+    kind: BindingKind,
+  ) -> BindingId {
+    let id = self.binding(scope, name, kind);
+    if self.exporting {
+      self.tree.bindings[id.0].exported = true;
+    }
+    self.tree.declaration_sites.insert(site, id);
+    self.declared.push(id);
+    id
+  }
+
+  /// Gets the binding of a name in a scope, creating it if needed.
+  fn binding(&mut self, scope: ScopeId, name: &str, kind: BindingKind) -> BindingId {
+    if let Some(&id) = self.tree.scopes[scope.0].names.get(name) {
+      return id;
+    }
+    let id = BindingId(self.tree.bindings.len());
+    self.tree.bindings.push(Binding {
+      name: name.into(),
+      kind,
+      scope,
+      references: Vec::new(),
+      captured: false,
+      exported: false,
+      context: self.context,
+      initialized: None,
+    });
+    let scope = self.scope(scope);
+    scope.names.insert(name.into(), id);
+    scope.bindings.push(id);
+    id
+  }
+
+  /// Records a reference made by a name of the syntax tree.
+  fn reference(&mut self, name: &Sym, site: NodeId, access: Access) -> ReferenceId {
+    let id = ReferenceId(self.tree.references.len());
+    self.tree.references.push(Reference {
+      name: name.into(),
+      scope: self.current,
+      access,
+      binding: None,
+      tdz: false,
+      dynamic: false,
+      context: self.context,
+    });
+    self.tree.reference_sites.insert(site, id);
+    id
+  }
+
+  /// Visits the parameters and the body of a function in a new function scope.
+  fn function<F>(&mut self, arrow: bool, visit: F) -> Flow
+  where
+    F: FnOnce(&mut Self) -> Flow,
+  {
+    self.scoped(ScopeKind::Function, |this| {
+      let current = this.current;
+      this.scope(current).arrow = arrow;
+      this.deferred(visit)
+    })
+  }
+
+  /// Visits a function expression, declaring its name in its own scope if `named`.
+  ///
+  /// The name is declared last, so that parameters and declarations of the body with the same
+  /// name shadow it.
+  fn function_expr<'ast>(
+    &mut self,
+    function: &'ast FunctionExpr,
+    path: &mut NodePath<'ast>,
+    named: bool,
+  ) -> Flow {
+    self.function(false, |this| {
+      let flow = visit::walk_function_expr(this, function, path);
+      if let (true, Some(name)) = (named, function.name()) {
+        if this.tree.scopes[this.current.0].binding(name).is_none() {
+          this.declare_in(this.current, name, function.id(), BindingKind::Function);
+        }
+      }
+      flow
+    })
+  }
+
+  /// Visits a class, declaring its name in the class scope if `named`.
+  fn class<'ast>(&mut self, class: &'ast Class, path: &mut NodePath<'ast>, named: bool) -> Flow {
+    self.scoped(ScopeKind::Class, |this| {
+      let mut declared = Vec::new();
+      if let (true, Some(name)) = (named, class.name()) {
+        declared.push(this.declare_in(this.current, name, class.id(), BindingKind::Class));
+      }
+      let flow = this.with_mode(Mode::Reference(Access::Read), |this| {
+        visit::walk_class(this, class, path)
+      });
+      this.initialize(&declared);
+      flow
+    })
+  }
+
+  /// Resolves the references, and flags the captured bindings and the reads in the TDZ.
+  fn resolve(&mut self) {
+    for index in 0..self.tree.references.len() {
+      let reference = &self.tree.references[index];
+      let (name, context) = (reference.name.clone(), reference.context);
+      let mut scope = reference.scope;
+      // The function scopes between the reference and its binding.
+      let mut crossed = Vec::new();
+      let mut dynamic = false;
+      let binding = loop {
+        let current = &self.tree.scopes[scope.0];
+        if let Some(binding) = current.binding(&name) {
+          break Some(binding);
+        }
+        if current.kind == ScopeKind::Function && !current.arrow && &*name == "arguments" {
+          break Some(self.binding(scope, &name, BindingKind::Arguments));
+        }
+        if matches!(current.kind, ScopeKind::Function | ScopeKind::StaticBlock) {
+          crossed.push(scope);
+        }
+        dynamic |= current.kind == ScopeKind::With || current.eval_vars;
+        match current.parent {
+          Some(parent) => scope = parent,
+          None => break None,
+        }
+      };
+      self.tree.references[index].dynamic = dynamic;
+      let binding = match binding {
+        Some(binding) => binding,
+        None => continue,
+      };
+
+      let data = &mut self.tree.bindings[binding.0];
+      data.references.push(ReferenceId(index));
+      data.captured |= !crossed.is_empty();
+      let tdz = data.kind.has_tdz()
+        && data.context == context
+        && matches!(data.initialized, Some(initialized) if index < initialized);
+      let reference = &mut self.tree.references[index];
+      reference.binding = Some(binding);
+      reference.tdz = tdz;
+      for scope in crossed {
+        self.scope(scope).captures.push(binding);
+      }
+    }
+
+    for &export in &self.exports {
+      if let Some(binding) = self.tree.references[export.0].binding {
+        self.tree.bindings[binding.0].exported = true;
+      }
+    }
+    for scope in &mut self.tree.scopes {
+      scope.captures.sort();
+      scope.captures.dedup();
+    }
+    // Scopes come after their parents, so going backwards reaches the children first.
+    for index in (0..self.tree.scopes.len()).rev() {
+      let scope = &mut self.tree.scopes[index];
+      scope.tainted |= scope.direct_eval;
+      if let (true, Some(parent)) = (scope.tainted, scope.parent) {
+        self.scope(parent).tainted = true;
+      }
+    }
+  }
+}
+
+impl<'ast> Visitor<'ast> for Analyzer {
+  fn visit_identifier(&mut self, ident: &'ast Identifier, _path: &mut NodePath<'ast>) -> Flow {
+    match self.mode {
+      Mode::Declare(kind) => {
+        self.declare(ident.name(), ident.id(), kind);
+      }
+      Mode::Reference(access) => {
+        self.reference(ident.name(), ident.id(), access);
+      }
+    }
+    Flow::Continue(())
+  }
+
+  fn visit_pattern(&mut self, pattern: &'ast Pattern, path: &mut NodePath<'ast>) -> Flow {
+    match pattern {
+      // The object of an assigned property is only read.
+      Pattern::Member(_) => self.with_mode(Mode::Reference(Access::Read), |this| {
+        visit::walk_pattern(this, pattern, path)
+      }),
+      _ => visit::walk_pattern(self, pattern, path),
+    }
+  }
+
+  fn visit_pattern_element(
+    &mut self,
+    element: &'ast PatternElement,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    self.visit_pattern(element.target(), path)?;
+    match element.default() {
+      Some(default) => self.with_mode(Mode::Reference(Access::Read), |this| {
+        this.visit_node(default, path)
+      }),
+      None => Flow::Continue(()),
+    }
+  }
+
+  fn visit_property_name(&mut self, name: &'ast PropertyName, path: &mut NodePath<'ast>) -> Flow {
+    self.with_mode(Mode::Reference(Access::Read), |this| {
+      visit::walk_property_name(this, name, path)
+    })
+  }
+
+  fn visit_declaration_list(
+    &mut self,
+    list: &'ast DeclarationList,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    let kind = match list {
+      DeclarationList::Var(_) => BindingKind::Var,
+      DeclarationList::Let(_) => BindingKind::Let,
+      DeclarationList::Const(_) => BindingKind::Const,
+    };
+    for decl in list.as_ref() {
+      let (flow, declared) = self.bind(kind, |this| this.visit_pattern(decl.target(), path));
+      flow?;
+      if let Some(init) = decl.init() {
+        self.visit_node(init, path)?;
+      }
+      self.initialize(&declared);
+    }
+    Flow::Continue(())
+  }
+
+  fn visit_formal_parameter(
+    &mut self,
+    parameter: &'ast FormalParameter,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    let (flow, declared) = self.bind(BindingKind::Parameter, |this| {
+      this.visit_pattern(parameter.target(), path)
+    });
+    flow?;
+    if let Some(init) = parameter.init() {
+      self.visit_node(init, path)?;
+    }
+    self.initialize(&declared);
+    Flow::Continue(())
+  }
+
+  fn visit_assign(&mut self, assign: &'ast Assign, path: &mut NodePath<'ast>) -> Flow {
+    self.with_mode(Mode::Reference(Access::Write), |this| {
+      this.visit_pattern(assign.lhs(), path)
+    })?;
+    self.visit_node(assign.rhs(), path)
+  }
+
+  fn visit_bin_op(&mut self, op: &'ast BinOp, path: &mut NodePath<'ast>) -> Flow {
+    match (op.op(), op.lhs()) {
+      (operator::BinOp::Assign(_), Node::Identifier(ident)) => {
+        self.reference(ident.name(), ident.id(), Access::ReadWrite);
+        self.visit_node(op.rhs(), path)
+      }
+      _ => visit::walk_bin_op(self, op, path),
+    }
+  }
+
+  fn visit_unary_op(&mut self, op: &'ast UnaryOp, path: &mut NodePath<'ast>) -> Flow {
+    let updates = matches!(
+      op.op(),
+      operator::UnaryOp::IncrementPre
+        | operator::UnaryOp::IncrementPost
+        | operator::UnaryOp::DecrementPre
+        | operator::UnaryOp::DecrementPost
+    );
+    match op.target() {
+      Node::Identifier(ident) if updates => {
+        self.reference(ident.name(), ident.id(), Access::ReadWrite);
+        Flow::Continue(())
+      }
+      _ => visit::walk_unary_op(self, op, path),
+    }
+  }
+
+  fn visit_property_definition(
+    &mut self,
+    property: &'ast PropertyDefinition,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    match property {
+      PropertyDefinition::IdentifierReference(ident, _) => {
+        self.reference(ident.name(), ident.id(), Access::Read);
+        Flow::Continue(())
+      }
+      _ => visit::walk_property_definition(self, property, path),
+    }
+  }
+
+  fn visit_call(&mut self, call: &'ast Call, path: &mut NodePath<'ast>) -> Flow {
+    if matches!(call.expr(), Node::Identifier(ident) if ident.name() == "eval") {
+      let mut scope = self.current;
+      self.scope(scope).direct_eval = true;
+      // The `var` declarations of the evaluated code are bound in the closest `var` scope.
+      while !self.tree.scopes[scope.0].kind.is_var_scope() {
+        scope = self.tree.scopes[scope.0]
+          .parent
+          .expect("the top level is a var scope");
+      }
+      self.scope(scope).eval_vars = true;
+    }
+    visit::walk_call(self, call, path)
+  }
+
+  fn visit_block(&mut self, block: &'ast Block, path: &mut NodePath<'ast>) -> Flow {
+    self.scoped(ScopeKind::Block, |this| {
+      visit::walk_block(this, block, path)
+    })
+  }
+
+  fn visit_with(&mut self, stmt: &'ast With, path: &mut NodePath<'ast>) -> Flow {
+    // The object is evaluated outside of the scope it adds.
+    self.visit_node(stmt.object(), path)?;
+    self.scoped(ScopeKind::With, |this| this.visit_node(stmt.body(), path))
+  }
+
+  fn visit_for_loop(&mut self, stmt: &'ast ForLoop, path: &mut NodePath<'ast>) -> Flow {
+    self.scoped(ScopeKind::Block, |this| {
+      visit::walk_for_loop(this, stmt, path)
+    })
+  }
+
+  fn visit_for_in_loop(&mut self, stmt: &'ast ForInLoop, path: &mut NodePath<'ast>) -> Flow {
+    self.scoped(ScopeKind::Block, |this| {
+      let (flow, declared) = this.loop_head(stmt.init(), path);
+      flow?;
+      this.visit_node(stmt.expr(), path)?;
+      this.initialize(&declared);
+      this.visit_node(stmt.body(), path)
+    })
+  }
+
+  fn visit_for_of_loop(&mut self, stmt: &'ast ForOfLoop, path: &mut NodePath<'ast>) -> Flow {
+    self.scoped(ScopeKind::Block, |this| {
+      let (flow, declared) = this.loop_head(stmt.init(), path);
+      flow?;
+      this.visit_node(stmt.iterable(), path)?;
+      this.initialize(&declared);
+      this.visit_node(stmt.body(), path)
+    })
+  }
+
+  fn visit_switch(&mut self, stmt: &'ast Switch, path: &mut NodePath<'ast>) -> Flow {
+    // The discriminant is outside of the scope of the clauses.
+    self.visit_node(stmt.val(), path)?;
+    self.scoped(ScopeKind::Block, |this| {
+      for case in stmt.cases() {
+        this.visit_case(case, path)?;
+      }
+      Flow::Continue(())
+    })
+  }
+
+  fn visit_catch(&mut self, catch: &'ast Catch, path: &mut NodePath<'ast>) -> Flow {
+    self.scoped(ScopeKind::Catch, |this| {
+      if let Some(parameter) = catch.parameter() {
+        let (flow, declared) = this.bind(BindingKind::CatchParameter, |this| {
+          this.visit_pattern(parameter, path)
+        });
+        flow?;
+        this.initialize(&declared);
+      }
+      this.visit_block(catch.block(), path)
+    })
+  }
+
+  fn visit_function_decl(&mut self, decl: &'ast FunctionDecl, path: &mut NodePath<'ast>) -> Flow {
+    self.declare(decl.name(), decl.id(), BindingKind::Function);
+    self.function(false, |this| visit::walk_function_decl(this, decl, path))
+  }
+
+  fn visit_function_expr(&mut self, expr: &'ast FunctionExpr, path: &mut NodePath<'ast>) -> Flow {
+    self.function_expr(expr, path, true)
+  }
+
+  fn visit_arrow_function_decl(
+    &mut self,
+    arrow: &'ast ArrowFunctionDecl,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    self.function(true, |this| {
+      visit::walk_arrow_function_decl(this, arrow, path)
+    })
+  }
+
+  fn visit_class(&mut self, class: &'ast Class, path: &mut NodePath<'ast>) -> Flow {
+    // The path ends with the class node itself.
+    if path.parent().map(NodeRef::kind) == Some(NodeKind::ClassDecl) {
+      let declared = class
+        .name()
+        .map(|name| self.declare(name, class.id(), BindingKind::Class));
+      let flow = self.class(class, path, false);
+      self.initialize(declared.as_slice());
+      flow
+    } else {
+      self.class(class, path, true)
+    }
+  }
+
+  fn visit_class_element(
+    &mut self,
+    element: &'ast ClassElement,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    match element {
+      ClassElement::Field { .. } => {
+        self.deferred(|this| visit::walk_class_element(this, element, path))
+      }
+      ClassElement::StaticBlock(_, _) => self.scoped(ScopeKind::StaticBlock, |this| {
+        this.deferred(|this| visit::walk_class_element(this, element, path))
+      }),
+      ClassElement::Method { .. } => visit::walk_class_element(self, element, path),
+    }
+  }
+
+  fn visit_import_specifier(
+    &mut self,
+    specifier: &'ast ImportSpecifier,
+    _path: &mut NodePath<'ast>,
+  ) -> Flow {
+    self.declare(specifier.local(), specifier.id(), BindingKind::Import);
+    Flow::Continue(())
+  }
+
+  fn visit_export_decl(&mut self, decl: &'ast ExportDecl, path: &mut NodePath<'ast>) -> Flow {
+    match decl {
+      ExportDecl::Named {
+        specifiers,
+        source: None,
+        ..
+      } => {
+        for specifier in specifiers.iter() {
+          let reference = self.reference(specifier.local(), specifier.id(), Access::Read);
+          self.exports.push(reference);
+        }
+        Flow::Continue(())
+      }
+      ExportDecl::Declaration(declaration) => {
+        self.exporting = true;
+        let flow = self.visit_node(declaration, path);
+        self.exporting = false;
+        flow
+      }
+      // The names of default exported functions and classes are bound in the module scope.
+      ExportDecl::DefaultFunction(function) => {
+        if let Some(name) = function.name() {
+          let binding = self.declare(name, function.id(), BindingKind::Function);
+          self.tree.bindings[binding.0].exported = true;
+        }
+        self.function_expr(function, path, false)
+      }
+      ExportDecl::DefaultClass(class) => {
+        let declared = class.name().map(|name| {
+          let binding = self.declare(name, class.id(), BindingKind::Class);
+          self.tree.bindings[binding.0].exported = true;
+          binding
+        });
+        let flow = self.class(class, path, false);
+        self.initialize(declared.as_slice());
+        flow
+      }
+      decl => visit::walk_export_decl(self, decl, path),
+    }
+  }
+}
+
+impl Analyzer {
+  /// Visits the target of a `for ... in` or `for ... of` loop, returning the bindings it
+  /// declares.
+  fn loop_head<'ast>(
+    &mut self,
+    init: &'ast IterableLoopInitializer,
+    path: &mut NodePath<'ast>,
+  ) -> (Flow, Vec<BindingId>) {
+    let kind = match init {
+      IterableLoopInitializer::Var(_) => BindingKind::Var,
+      IterableLoopInitializer::Let(_) => BindingKind::Let,
+      IterableLoopInitializer::Const(_) => BindingKind::Const,
+      IterableLoopInitializer::Pattern(pattern) => {
+        let flow = self.with_mode(Mode::Reference(Access::Write), |this| {
+          this.visit_pattern(pattern, path)
+        });
+        return (flow, Vec::new());
+      }
+    };
+    self.bind(kind, |this| this.visit_pattern(init.pattern(), path))
+  }
+}
+
+impl Scope {
+  fn new(kind: ScopeKind, parent: Option<ScopeId>) -> Self {
+    Self {
+      kind,
+      parent,
+      children: Vec::new(),
+      bindings: Vec::new(),
+      names: BTreeMap::new(),
+      arrow: false,
+      direct_eval: false,
+      eval_vars: false,
+      tainted: false,
+      captures: Vec::new(),
+    }
+  }
+}
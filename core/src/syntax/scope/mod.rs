@@ -0,0 +1,441 @@
+//! Scope analysis: what each identifier of a script or a module refers to.
+//!
+//! A [`ScopeTree`] holds the scopes of a syntax tree, the bindings declared in each of them and
+//! every reference to a name, resolved to the binding it reads or writes, or left unresolved
+//! when it refers to a global:
+//!
+//! ```
+//! use plastic::syntax::{
+//!   parser::Parser,
+//!   scope::{BindingKind, ScopeKind, ScopeTree},
+//! };
+//!
+//! let script = Parser::new(&b"let count = 0; function add(step) { count += step; }"[..])
+//!   .parse_script()
+//!   .unwrap();
+//! let tree = ScopeTree::script(&script);
+//!
+//! let (_, add) = tree.scopes().find(|(_, scope)| scope.kind() == ScopeKind::Function).unwrap();
+//! let count = tree.binding(tree.find(tree.root(), "count").unwrap());
+//! assert_eq!(count.kind(), BindingKind::Let);
+//! assert!(count.is_captured());
+//! assert_eq!(add.captures().len(), 1);
+//! ```
+//!
+//! The analysis follows the scoping rules of strict mode code, with two exceptions for scripts:
+//! functions declared in blocks are also visible in the enclosing function, and `var`
+//! declarations in a `catch` clause may redeclare its parameter. The body of a `with` statement
+//! gets a [`ScopeKind::With`] scope, and the references made from it, or from code where a
+//! direct `eval` call can declare `var` bindings, may resolve to something else at runtime; see
+//! [`Reference::is_dynamic`].
+//!
+//! The declarations and references of the syntax tree are found again by the [`NodeId`] of the
+//! node naming them, which clones of the tree share. See [`ScopeTree::declaration_at`] and
+//! [`ScopeTree::reference_at`].
+
+mod analyzer;
+#[cfg(test)]
+mod tests;
+
+use self::analyzer::Analyzer;
+use crate::syntax::ast::{module::Module, node::StatementList, Node, NodeId};
+use std::collections::{BTreeMap, HashMap};
+
+/// The index of a scope in a [`ScopeTree`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ScopeId(usize);
+
+/// The index of a binding in a [`ScopeTree`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BindingId(usize);
+
+/// The index of a reference in a [`ScopeTree`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ReferenceId(usize);
+
+/// The kind of a scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScopeKind {
+  /// The top level of a script.
+  Global,
+  /// The top level of a module.
+  Module,
+  /// The parameters and the body of a function, an arrow function or a method.
+  Function,
+  /// A class static block, such as `static { init(); }`.
+  StaticBlock,
+  /// A block, a `switch` statement, or the head and the body of a `for` loop.
+  Block,
+  /// The parameter of a `catch` clause.
+  Catch,
+  /// The body of a class, with the name of a class expression.
+  Class,
+  /// The body of a `with` statement, where the properties of the object shadow the bindings of
+  /// enclosing scopes.
+  With,
+}
+
+impl ScopeKind {
+  /// Returns `true` if `var` declarations in this scope are bound in it.
+  pub fn is_var_scope(self) -> bool {
+    matches!(
+      self,
+      Self::Global | Self::Module | Self::Function | Self::StaticBlock
+    )
+  }
+}
+
+/// The kind of a binding, after the declaration that created it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BindingKind {
+  Var,
+  Let,
+  Const,
+  /// A function declaration, or the name of a function expression.
+  Function,
+  /// A class declaration, or the name of a class expression.
+  Class,
+  /// A parameter of a function.
+  Parameter,
+  /// The parameter of a `catch` clause.
+  CatchParameter,
+  Import,
+  /// The implicit `arguments` object of a function.
+  Arguments,
+}
+
+impl BindingKind {
+  /// Returns `true` if the binding can not be read before its declaration runs.
+  pub fn has_tdz(self) -> bool {
+    matches!(
+      self,
+      Self::Let | Self::Const | Self::Class | Self::Parameter
+    )
+  }
+}
+
+/// How a reference uses the binding it refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Access {
+  /// The value is read, as in `a + 1`.
+  Read,
+  /// The value is replaced, as in `a = 1` or `[a] = b`.
+  Write,
+  /// The value is read and replaced, as in `a += 1` or `a++`.
+  ReadWrite,
+}
+
+impl Access {
+  /// Returns `true` if the value is read.
+  pub fn is_read(self) -> bool {
+    self != Self::Write
+  }
+
+  /// Returns `true` if the value is replaced.
+  pub fn is_write(self) -> bool {
+    self != Self::Read
+  }
+}
+
+/// A scope, with the bindings declared in it.
+#[derive(Debug, Clone)]
+pub struct Scope {
+  kind: ScopeKind,
+  parent: Option<ScopeId>,
+  children: Vec<ScopeId>,
+  /// The bindings, in declaration order.
+  bindings: Vec<BindingId>,
+  names: BTreeMap<Box<str>, BindingId>,
+  arrow: bool,
+  direct_eval: bool,
+  /// Whether a direct `eval` call may declare `var` bindings in the scope.
+  eval_vars: bool,
+  tainted: bool,
+  captures: Vec<BindingId>,
+}
+
+impl Scope {
+  /// Gets the kind of the scope.
+  pub fn kind(&self) -> ScopeKind {
+    self.kind
+  }
+
+  /// Gets the scope containing this one, `None` for the top level.
+  pub fn parent(&self) -> Option<ScopeId> {
+    self.parent
+  }
+
+  /// Gets the scopes directly inside this one, in source order.
+  pub fn children(&self) -> &[ScopeId] {
+    &self.children
+  }
+
+  /// Gets the bindings declared in the scope, in declaration order.
+  pub fn bindings(&self) -> &[BindingId] {
+    &self.bindings
+  }
+
+  /// Gets the binding of a name declared in this scope.
+  pub fn binding(&self, name: &str) -> Option<BindingId> {
+    self.names.get(name).copied()
+  }
+
+  /// Returns `true` if the scope is the scope of an arrow function.
+  pub fn is_arrow(&self) -> bool {
+    self.arrow
+  }
+
+  /// Returns `true` if the scope itself contains a direct `eval` call.
+  pub fn contains_direct_eval(&self) -> bool {
+    self.direct_eval
+  }
+
+  /// Returns `true` if the scope or a scope inside it contains a direct `eval` call.
+  ///
+  /// The evaluated code can then read and write the bindings of the scope, and declare `var`
+  /// bindings shadowing them.
+  pub fn is_tainted(&self) -> bool {
+    self.tainted
+  }
+
+  /// Gets the bindings of enclosing scopes referenced from inside a function scope.
+  ///
+  /// These are the variables a closure created from the function keeps alive. The list is
+  /// sorted, and empty for other scopes.
+  pub fn captures(&self) -> &[BindingId] {
+    &self.captures
+  }
+}
+
+/// A name declared in a scope.
+#[derive(Debug, Clone)]
+pub struct Binding {
+  name: Box<str>,
+  kind: BindingKind,
+  scope: ScopeId,
+  references: Vec<ReferenceId>,
+  captured: bool,
+  exported: bool,
+  /// The evaluation context of the declaration, see [`Reference::is_tdz`].
+  context: usize,
+  /// The number of references recorded when the binding was initialized.
+  initialized: Option<usize>,
+}
+
+impl Binding {
+  /// Gets the declared name.
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Gets the kind of declaration that created the binding.
+  pub fn kind(&self) -> BindingKind {
+    self.kind
+  }
+
+  /// Gets the scope the binding is declared in.
+  pub fn scope(&self) -> ScopeId {
+    self.scope
+  }
+
+  /// Gets the references resolved to the binding, in source order.
+  pub fn references(&self) -> &[ReferenceId] {
+    &self.references
+  }
+
+  /// Returns `true` if the binding is referenced from a function nested in its scope.
+  pub fn is_captured(&self) -> bool {
+    self.captured
+  }
+
+  /// Returns `true` if the binding is exported by its module.
+  pub fn is_exported(&self) -> bool {
+    self.exported
+  }
+}
+
+/// A use of a name.
+#[derive(Debug, Clone)]
+pub struct Reference {
+  name: Box<str>,
+  scope: ScopeId,
+  access: Access,
+  binding: Option<BindingId>,
+  tdz: bool,
+  dynamic: bool,
+  context: usize,
+}
+
+impl Reference {
+  /// Gets the referenced name.
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Gets the scope the reference is made from.
+  pub fn scope(&self) -> ScopeId {
+    self.scope
+  }
+
+  /// Gets how the reference uses the binding.
+  pub fn access(&self) -> Access {
+    self.access
+  }
+
+  /// Gets the binding the reference resolves to, `None` if it refers to a global.
+  pub fn binding(&self) -> Option<BindingId> {
+    self.binding
+  }
+
+  /// Returns `true` if the reference resolves to no declaration, and so refers to a property of
+  /// the global object.
+  pub fn is_global(&self) -> bool {
+    self.binding.is_none()
+  }
+
+  /// Returns `true` if the reference always reads a `let`, `const`, `class` or parameter
+  /// binding before it is initialized, throwing a `ReferenceError`.
+  ///
+  /// Only references evaluated along with the declaration are flagged, such as `x` in
+  /// `x; let x;` or `let x = x;`. A reference from a function can not be known to run too
+  /// early.
+  pub fn is_tdz(&self) -> bool {
+    self.tdz
+  }
+
+  /// Returns `true` if the reference may resolve to something else than its binding at runtime.
+  ///
+  /// This is the case when the name is looked up in the body of a `with` statement, where a
+  /// property of the object can shadow it, or in a function where a direct `eval` call can
+  /// declare a `var` binding shadowing it, such as `a` in `let a; function f() { eval(s); a; }`.
+  pub fn is_dynamic(&self) -> bool {
+    self.dynamic
+  }
+}
+
+/// The scopes, the bindings and the references of a script or a module.
+#[derive(Debug, Clone)]
+pub struct ScopeTree {
+  scopes: Vec<Scope>,
+  bindings: Vec<Binding>,
+  references: Vec<Reference>,
+  /// The bindings of declarations, by the id of the node declaring them.
+  declaration_sites: HashMap<NodeId, BindingId>,
+  /// The references, by the id of the node making them.
+  reference_sites: HashMap<NodeId, ReferenceId>,
+}
+
+impl ScopeTree {
+  /// Analyzes a script.
+  pub fn script(script: &StatementList) -> Self {
+    Analyzer::new(ScopeKind::Global).analyze(script.items())
+  }
+
+  /// Analyzes a module.
+  pub fn module(module: &Module) -> Self {
+    Analyzer::new(ScopeKind::Module).analyze(module.body().items())
+  }
+
+  /// Analyzes the statements of a module if `module` is `true`, or else of a script.
+  pub(crate) fn statements(statements: &[Node], module: bool) -> Self {
+    let kind = if module {
+      ScopeKind::Module
+    } else {
+      ScopeKind::Global
+    };
+    Analyzer::new(kind).analyze(statements)
+  }
+
+  /// Gets the top level scope.
+  pub fn root(&self) -> ScopeId {
+    ScopeId(0)
+  }
+
+  /// Gets a scope.
+  pub fn scope(&self, id: ScopeId) -> &Scope {
+    &self.scopes[id.0]
+  }
+
+  /// Gets a binding.
+  pub fn binding(&self, id: BindingId) -> &Binding {
+    &self.bindings[id.0]
+  }
+
+  /// Gets a reference.
+  pub fn reference(&self, id: ReferenceId) -> &Reference {
+    &self.references[id.0]
+  }
+
+  /// Iterates over the scopes, outer scopes before the scopes inside them.
+  pub fn scopes(&self) -> impl Iterator<Item = (ScopeId, &Scope)> {
+    self
+      .scopes
+      .iter()
+      .enumerate()
+      .map(|(index, scope)| (ScopeId(index), scope))
+  }
+
+  /// Iterates over the bindings, in declaration order.
+  pub fn bindings(&self) -> impl Iterator<Item = (BindingId, &Binding)> {
+    self
+      .bindings
+      .iter()
+      .enumerate()
+      .map(|(index, binding)| (BindingId(index), binding))
+  }
+
+  /// Iterates over the references, in source order.
+  pub fn references(&self) -> impl Iterator<Item = (ReferenceId, &Reference)> {
+    self
+      .references
+      .iter()
+      .enumerate()
+      .map(|(index, reference)| (ReferenceId(index), reference))
+  }
+
+  /// Iterates over the references to globals.
+  pub fn globals(&self) -> impl Iterator<Item = (ReferenceId, &Reference)> {
+    self
+      .references()
+      .filter(|(_, reference)| reference.is_global())
+  }
+
+  /// Finds the binding a name resolves to in a scope.
+  pub fn find(&self, mut scope: ScopeId, name: &str) -> Option<BindingId> {
+    loop {
+      let current = self.scope(scope);
+      if let Some(binding) = current.binding(name) {
+        return Some(binding);
+      }
+      scope = current.parent?;
+    }
+  }
+
+  /// Gets the binding declared by a node of the syntax tree.
+  ///
+  /// The id is the one of the node naming the binding, such as [`Identifier::id`] or
+  /// [`FunctionDecl::id`], in the analyzed tree or in one of its clones.
+  ///
+  /// [`Identifier::id`]: crate::syntax::ast::node::Identifier::id
+  /// [`FunctionDecl::id`]: crate::syntax::ast::node::FunctionDecl::id
+  pub fn declaration_at(&self, id: NodeId) -> Option<BindingId> {
+    self.declaration_sites.get(&id).copied()
+  }
+
+  /// Gets the reference made by a node of the syntax tree.
+  ///
+  /// As for [`declaration_at`](Self::declaration_at), the id is the one of the node naming the
+  /// binding.
+  pub fn reference_at(&self, id: NodeId) -> Option<ReferenceId> {
+    self.reference_sites.get(&id).copied()
+  }
+
+  /// Gets the binding a node of the syntax tree declares or refers to.
+  pub fn resolve_at(&self, id: NodeId) -> Option<BindingId> {
+    self.declaration_at(id).or_else(|| {
+      self
+        .reference_at(id)
+        .and_then(|id| self.reference(id).binding)
+    })
+  }
+}
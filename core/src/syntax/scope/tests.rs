@@ -0,0 +1,405 @@
+//! Tests for the scope analysis.
+
+use super::{Access, BindingKind, ScopeKind, ScopeTree};
+use crate::syntax::{
+  ast::node::{FunctionDecl, Identifier, Node},
+  parser::Parser,
+};
+
+/// Analyzes a script.
+#[track_caller]
+fn script(js: &str) -> ScopeTree {
+  let script = Parser::new(js.as_bytes())
+    .parse_script()
+    .unwrap_or_else(|err| panic!("failed to parse `{}`: {}", js, err));
+  ScopeTree::script(&script)
+}
+
+/// Analyzes a module.
+#[track_caller]
+fn module(js: &str) -> ScopeTree {
+  let module = Parser::new(js.as_bytes())
+    .parse_module()
+    .unwrap_or_else(|err| panic!("failed to parse `{}`: {}", js, err));
+  ScopeTree::module(&module)
+}
+
+/// Gets the kinds of the scopes, in tree order.
+fn scope_kinds(tree: &ScopeTree) -> Vec<ScopeKind> {
+  tree.scopes().map(|(_, scope)| scope.kind()).collect()
+}
+
+/// Gets the names and the kinds of the bindings, in declaration order.
+fn bindings(tree: &ScopeTree) -> Vec<(&str, BindingKind)> {
+  tree
+    .bindings()
+    .map(|(_, binding)| (binding.name(), binding.kind()))
+    .collect()
+}
+
+/// Gets the names of the references to globals.
+fn globals(tree: &ScopeTree) -> Vec<&str> {
+  tree
+    .globals()
+    .map(|(_, reference)| reference.name())
+    .collect()
+}
+
+/// Gets the names of the references in the TDZ.
+fn tdz(tree: &ScopeTree) -> Vec<&str> {
+  tree
+    .references()
+    .filter(|(_, reference)| reference.is_tdz())
+    .map(|(_, reference)| reference.name())
+    .collect()
+}
+
+/// Gets the names of the dynamic references, in source order.
+fn dynamic(tree: &ScopeTree) -> Vec<&str> {
+  tree
+    .references()
+    .filter(|(_, reference)| reference.is_dynamic())
+    .map(|(_, reference)| reference.name())
+    .collect()
+}
+
+#[test]
+fn scope_kinds_and_nesting() {
+  let tree = script(
+    "{ let a; } for (let i of x) {} try {} catch (e) {} switch (x) {} \
+     class C { static {} } () => {};",
+  );
+  use ScopeKind::*;
+  assert_eq!(
+    scope_kinds(&tree),
+    [
+      Global,
+      Block,
+      Block,
+      Block,
+      Block,
+      Catch,
+      Block,
+      Block,
+      Class,
+      StaticBlock,
+      Function
+    ]
+  );
+  let root = tree.scope(tree.root());
+  assert_eq!(root.parent(), None);
+  assert_eq!(root.children().len(), 7);
+  for &child in root.children() {
+    assert_eq!(tree.scope(child).parent(), Some(tree.root()));
+  }
+  let (arrow, _) = tree.scopes().last().unwrap();
+  assert!(tree.scope(arrow).is_arrow());
+
+  assert_eq!(scope_kinds(&module("let a;")), [Module]);
+}
+
+#[test]
+fn binding_kinds() {
+  let tree = script(
+    "var a; let b; const c = 1; function d(e, { f = 1 }, ...g) { arguments; } class H {} \
+     try {} catch ([i]) {}",
+  );
+  use BindingKind::*;
+  assert_eq!(
+    bindings(&tree),
+    [
+      ("a", Var),
+      ("b", Let),
+      ("c", Const),
+      ("d", Function),
+      ("e", Parameter),
+      ("f", Parameter),
+      ("g", Parameter),
+      ("H", Class),
+      ("i", CatchParameter),
+      ("arguments", Arguments)
+    ]
+  );
+
+  let tree = module("import a, { b as c } from 'm'; import * as d from 'n';");
+  assert_eq!(
+    bindings(&tree),
+    [("a", Import), ("c", Import), ("d", Import)]
+  );
+
+  // Arrow functions use the `arguments` of the enclosing function.
+  let tree = script("() => arguments;");
+  assert_eq!(globals(&tree), ["arguments"]);
+}
+
+#[test]
+fn hoisting() {
+  // `var` and function declarations are visible before them, in the whole function.
+  let tree = script("f(a); { var a = 1; function f() {} } function g() { { var b; } b; }");
+  assert!(globals(&tree).is_empty());
+  let root = tree.scope(tree.root());
+  assert!(root.binding("a").is_some());
+  assert!(root.binding("f").is_some());
+  assert!(root.binding("b").is_none());
+
+  // Functions declared in blocks of modules are bound in the blocks.
+  let tree = module("{ function f() {} } f();");
+  assert_eq!(globals(&tree), ["f"]);
+  assert!(tree.scope(tree.root()).binding("f").is_none());
+
+  // `let` declarations are bound in their block.
+  let tree = script("{ let a; } a;");
+  assert_eq!(globals(&tree), ["a"]);
+}
+
+#[test]
+fn resolution() {
+  let tree = script(
+    "let a = 1; function f(b) { let a = b; return a + c; } \
+     (function g() { return g; })(); g;",
+  );
+  assert_eq!(globals(&tree), ["c", "g"]);
+
+  let outer = tree.scope(tree.root()).binding("a").unwrap();
+  let (function, _) = tree
+    .scopes()
+    .find(|(_, scope)| scope.kind() == ScopeKind::Function)
+    .unwrap();
+  let inner = tree.find(function, "a").unwrap();
+  assert_ne!(inner, outer);
+  assert!(tree.binding(outer).references().is_empty());
+  assert_eq!(tree.binding(inner).references().len(), 1);
+  assert_eq!(
+    tree.find(function, "f"),
+    tree.scope(tree.root()).binding("f")
+  );
+  assert_eq!(tree.find(function, "c"), None);
+
+  // The name of a function expression is bound in the function, parameters shadow it.
+  let tree = script("(function f() { f; }); (function f(f) { f; });");
+  let kinds: Vec<_> = tree
+    .references()
+    .map(|(_, reference)| tree.binding(reference.binding().unwrap()).kind())
+    .collect();
+  assert_eq!(kinds, [BindingKind::Function, BindingKind::Parameter]);
+
+  // Shorthand properties, computed keys and values are references, other keys are not.
+  let tree = script("({ a, [b]: c, d: e, f() {} });");
+  assert_eq!(globals(&tree), ["a", "b", "c", "e"]);
+
+  // Class expressions see their own name, class declarations are bound outside.
+  let tree = script("(class A { m() { A; } }); class B { m() { B; } } A; B;");
+  assert_eq!(globals(&tree), ["A"]);
+}
+
+#[test]
+fn access() {
+  let tree = script("let a, b, c, d, e; a = 1; b += 1; c++; [d, e.f] = g; for (e in a) {}");
+  let accesses: Vec<_> = tree
+    .references()
+    .map(|(_, reference)| (reference.name(), reference.access()))
+    .collect();
+  assert_eq!(
+    accesses,
+    [
+      ("a", Access::Write),
+      ("b", Access::ReadWrite),
+      ("c", Access::ReadWrite),
+      ("d", Access::Write),
+      ("e", Access::Read),
+      ("g", Access::Read),
+      ("e", Access::Write),
+      ("a", Access::Read),
+    ]
+  );
+  assert!(Access::ReadWrite.is_read() && Access::ReadWrite.is_write());
+  assert!(!Access::Write.is_read() && !Access::Read.is_write());
+}
+
+#[test]
+fn captures() {
+  let tree = script(
+    "let a = 1, b = 2; function f() { let c = a; return () => c + b + d; } a + b; \
+     class C { static { a; } }",
+  );
+  let captured: Vec<_> = tree
+    .bindings()
+    .filter(|(_, binding)| binding.is_captured())
+    .map(|(_, binding)| binding.name())
+    .collect();
+  assert_eq!(captured, ["a", "b", "c"]);
+
+  let names = |scope: &super::Scope| -> Vec<&str> {
+    scope
+      .captures()
+      .iter()
+      .map(|&binding| tree.binding(binding).name())
+      .collect()
+  };
+  let captures: Vec<_> = tree
+    .scopes()
+    .filter(|(_, scope)| !scope.captures().is_empty())
+    .map(|(_, scope)| (scope.kind(), names(scope)))
+    .collect();
+  assert_eq!(
+    captures,
+    [
+      (ScopeKind::Function, vec!["a", "b"]),
+      (ScopeKind::Function, vec!["b", "c"]),
+      (ScopeKind::StaticBlock, vec!["a"]),
+    ]
+  );
+}
+
+#[test]
+fn temporal_dead_zone() {
+  assert_eq!(tdz(&script("a; let a;")), ["a"]);
+  assert_eq!(tdz(&script("const a = a;")), ["a"]);
+  assert_eq!(tdz(&script("let [a = b, b] = [];")), ["b"]);
+  assert_eq!(tdz(&script("function f(a = b, b) {}")), ["b"]);
+  assert_eq!(tdz(&script("class C extends C {}")), ["C"]);
+  assert_eq!(tdz(&script("(class C extends C {});")), ["C"]);
+  assert_eq!(tdz(&script("for (let a of a) {}")), ["a"]);
+  assert_eq!(tdz(&script("{ a; } let a;")), ["a"]);
+
+  // Functions may run after the declaration.
+  assert!(tdz(&script("function f() { return a; } let a = 1; f();")).is_empty());
+  assert!(tdz(&script("let f = () => f;")).is_empty());
+  assert!(tdz(&script("class C { a = C; static { C; } m() { C; } }")).is_empty());
+  // Bindings without a TDZ.
+  assert!(tdz(&script("a; var a; f(); function f() {}")).is_empty());
+  assert!(tdz(&script("let a; a; let b = 1, c = b;")).is_empty());
+  // An inner declaration does not initialize the outer one early.
+  assert_eq!(tdz(&script("let a = [function (b) {}, a];")), ["a"]);
+}
+
+#[test]
+fn direct_eval() {
+  let tree = script("function f() { { eval('a'); } } function g() { (0, eval)('b'); } h();");
+  let flags: Vec<_> = tree
+    .scopes()
+    .map(|(_, scope)| {
+      (
+        scope.kind(),
+        scope.contains_direct_eval(),
+        scope.is_tainted(),
+      )
+    })
+    .collect();
+  assert_eq!(
+    flags,
+    [
+      (ScopeKind::Global, false, true),
+      (ScopeKind::Function, false, true),
+      (ScopeKind::Block, true, true),
+      (ScopeKind::Function, false, false),
+    ]
+  );
+}
+
+#[test]
+fn dynamic_references() {
+  let tree = script("let a, b; with (a) { b; { let c; c; } }");
+  assert_eq!(
+    scope_kinds(&tree),
+    [
+      ScopeKind::Global,
+      ScopeKind::With,
+      ScopeKind::Block,
+      ScopeKind::Block
+    ]
+  );
+  assert_eq!(dynamic(&tree), ["b"]);
+  assert_eq!(dynamic(&script("with (a) b; c;")), ["b"]);
+
+  // A direct `eval` call can declare `var` bindings in the closest `var` scope.
+  let tree = script("let a; function f(b) { { eval(s); } a; b; g; } a;");
+  assert_eq!(dynamic(&tree), ["eval", "s", "a", "g"]);
+  assert!(dynamic(&script("let a; function f() { (0, eval)(s); a; }")).is_empty());
+}
+
+#[test]
+fn exports() {
+  let tree = module(
+    "export let a = 1; export function b(x) { let y; } export default class C {} \
+     let d; var e; export { d as f }; export * from 'm'; export { g } from 'n';",
+  );
+  let exported: Vec<_> = tree
+    .bindings()
+    .filter(|(_, binding)| binding.is_exported())
+    .map(|(_, binding)| binding.name())
+    .collect();
+  assert_eq!(exported, ["a", "b", "C", "d"]);
+  assert!(globals(&tree).is_empty());
+
+  let tree = module("export default function () {}");
+  assert!(tree.bindings().next().is_none());
+  let tree = module("export default (function f() {});");
+  assert!(tree.bindings().all(|(_, binding)| !binding.is_exported()));
+}
+
+#[test]
+fn sites() {
+  let js = "function f(a) { return a; } f(1);";
+  let script = Parser::new(js.as_bytes()).parse_script().unwrap();
+  let tree = ScopeTree::script(&script);
+
+  let decl: &FunctionDecl = match &script.items()[0] {
+    Node::FunctionDecl(decl) => decl,
+    node => panic!("unexpected node {:?}", node),
+  };
+  let f = tree.declaration_at(decl.id()).unwrap();
+  assert_eq!(tree.binding(f).name(), "f");
+  assert_eq!(tree.resolve_at(decl.id()), Some(f));
+
+  let call = match &script.items()[1] {
+    Node::Call(call) => call,
+    node => panic!("unexpected node {:?}", node),
+  };
+  let callee = match call.expr() {
+    Node::Identifier(ident) => ident,
+    node => panic!("unexpected node {:?}", node),
+  };
+  let reference = tree.reference_at(callee.id()).unwrap();
+  assert_eq!(tree.reference(reference).binding(), Some(f));
+  assert_eq!(tree.resolve_at(callee.id()), Some(f));
+  assert_eq!(tree.binding(f).references(), [reference]);
+
+  // The declaration and the reference share the interned name, but are different sites.
+  assert!(decl.name().ptr_eq(callee.name()));
+  assert_eq!(tree.declaration_at(callee.id()), None);
+  assert_eq!(tree.reference_at(decl.id()), None);
+
+  // Equal names outside of the tree are not found.
+  assert_eq!(tree.resolve_at(Identifier::new("f").id()), None);
+}
+
+#[test]
+fn moved_sites() {
+  let js = "let a = 1; { let a = 2; a; } a;";
+  let script = Parser::new(js.as_bytes()).parse_script().unwrap();
+  let tree = ScopeTree::script(&script);
+
+  // The sites are found in clones of the tree, and once the tree is moved.
+  let clone = script.clone();
+  let moved = Box::new(script);
+  for items in [clone.items(), moved.items()] {
+    let inner = match &items[1] {
+      Node::Block(block) => match block.statements() {
+        [_, Node::Identifier(ident)] => ident,
+        nodes => panic!("unexpected nodes {:?}", nodes),
+      },
+      node => panic!("unexpected node {:?}", node),
+    };
+    let outer = match &items[2] {
+      Node::Identifier(ident) => ident,
+      node => panic!("unexpected node {:?}", node),
+    };
+    let (inner, outer) = (
+      tree.resolve_at(inner.id()).unwrap(),
+      tree.resolve_at(outer.id()).unwrap(),
+    );
+    assert_ne!(inner, outer);
+    assert_eq!(tree.binding(outer).scope(), tree.root());
+  }
+}
@@ -0,0 +1,401 @@
+//! What the top level statements of a module declare, use, import and export.
+
+use super::effects;
+use crate::syntax::{
+  ast::{
+    arena::ArenaBox,
+    module::Module,
+    node::{
+      Class, DeclarationList, ExportDecl, ExportSpecifier, FunctionDecl, FunctionExpr, Identifier,
+      ImportCall, ImportSpecifier, Node, PropertyDefinition, StatementList,
+    },
+    visitor::{visit, Flow, NodePath, Path, Visitor},
+    Const, NodeId,
+  },
+  scope::{BindingId, ScopeTree},
+};
+use std::{collections::HashMap, mem};
+
+/// A top level statement of a module.
+#[derive(Debug)]
+pub(super) struct Statement {
+  /// The module bindings the statement reads or writes, in source order.
+  pub(super) references: Vec<BindingId>,
+  /// The specifiers of the modules imported with `import()`.
+  pub(super) dynamic_imports: Vec<Box<str>>,
+  /// Whether running the statement may have an effect outside of the bindings it declares.
+  pub(super) side_effects: bool,
+  /// The declaration list the statement was split from, see [`split_declarations`].
+  pub(super) group: Option<usize>,
+}
+
+/// What an exported name refers to.
+#[derive(Debug)]
+pub(super) enum Target {
+  /// A binding of the module, `None` for anonymous default exports.
+  Local(Option<BindingId>),
+  /// A name of another module.
+  Indirect { source: Box<str>, name: Box<str> },
+  /// The namespace object of another module.
+  Namespace { source: Box<str> },
+}
+
+/// A name exported by a module.
+#[derive(Debug)]
+pub(super) struct Export {
+  /// The statement exporting the name.
+  pub(super) statement: usize,
+  pub(super) target: Target,
+}
+
+/// A binding imported from another module.
+#[derive(Debug)]
+pub(super) struct Import {
+  pub(super) statement: usize,
+  pub(super) source: Box<str>,
+  /// The imported name, `None` for namespace imports.
+  pub(super) name: Option<Box<str>>,
+}
+
+/// The analysis of a module.
+#[derive(Debug)]
+pub(super) struct Analysis {
+  pub(super) tree: ScopeTree,
+  pub(super) statements: Vec<Statement>,
+  /// The exported names, in source order.
+  pub(super) exports: Vec<(Box<str>, Export)>,
+  /// The statements and the sources of `export * from` re-exports.
+  pub(super) stars: Vec<(usize, Box<str>)>,
+  pub(super) imports: HashMap<BindingId, Import>,
+  /// The statements declaring each module binding.
+  pub(super) declarations: HashMap<BindingId, Vec<usize>>,
+  /// The modules imported by `import` and `export ... from` declarations, in source order.
+  pub(super) dependencies: Vec<(usize, Box<str>)>,
+  /// Whether the module calls `eval` directly, which may use any of its bindings.
+  pub(super) eval: bool,
+}
+
+impl Analysis {
+  /// Analyzes a module, whose declaration lists are already split.
+  pub(super) fn new(module: &Module, groups: Vec<Option<usize>>) -> Self {
+    let body = module.body();
+    let tree = ScopeTree::module(module);
+    let root = tree.root();
+    let binding = |name: &str| tree.scope(root).binding(name);
+
+    let mut statements = Vec::with_capacity(body.items().len());
+    let mut exports = Vec::new();
+    let mut stars = Vec::new();
+    let mut imports = HashMap::new();
+    let mut declarations: HashMap<_, Vec<_>> = HashMap::new();
+    let mut dependencies = Vec::new();
+
+    for ((index, item), group) in body.items().iter().enumerate().zip(groups) {
+      let mut sites = Sites::default();
+      let _ = sites.visit_node(item, &mut Path::new());
+
+      let mut declares = Vec::new();
+      let mut references = Vec::new();
+      for site in sites.names {
+        if let Some(id) = tree.declaration_at(site) {
+          if tree.binding(id).scope() == root && !declares.contains(&id) {
+            declares.push(id);
+            declarations.entry(id).or_default().push(index);
+          }
+        } else if let Some(id) = tree.resolve_at(site) {
+          if tree.binding(id).scope() == root {
+            references.push(id);
+          }
+        }
+      }
+
+      match item {
+        Node::Import(decl) => {
+          dependencies.push((index, decl.source().into()));
+          for specifier in decl.specifiers() {
+            if let Some(id) = binding(specifier.local()) {
+              let name = match specifier {
+                ImportSpecifier::Default(_) => Some("default".into()),
+                ImportSpecifier::Namespace(_) => None,
+                ImportSpecifier::Named { imported, .. } => Some(imported.as_str().into()),
+              };
+              imports.insert(
+                id,
+                Import {
+                  statement: index,
+                  source: decl.source().into(),
+                  name,
+                },
+              );
+            }
+          }
+        }
+        Node::Export(decl, _) => {
+          if let Some(source) = decl.source() {
+            dependencies.push((index, source.into()));
+          }
+          let mut export = |name: &str, target| {
+            exports.push((
+              name.into(),
+              Export {
+                statement: index,
+                target,
+              },
+            ));
+          };
+          match decl {
+            ExportDecl::Named {
+              specifiers,
+              source: None,
+              ..
+            } => {
+              for specifier in specifiers.iter() {
+                export(
+                  specifier.exported(),
+                  Target::Local(binding(specifier.local())),
+                );
+              }
+            }
+            ExportDecl::Named {
+              specifiers,
+              source: Some(source),
+              ..
+            } => {
+              for specifier in specifiers.iter() {
+                export(
+                  specifier.exported(),
+                  Target::Indirect {
+                    source: source.clone(),
+                    name: specifier.local().into(),
+                  },
+                );
+              }
+            }
+            ExportDecl::All {
+              alias: Some(alias),
+              source,
+              ..
+            } => export(
+              alias,
+              Target::Namespace {
+                source: source.clone(),
+              },
+            ),
+            ExportDecl::All {
+              alias: None,
+              source,
+              ..
+            } => stars.push((index, source.clone())),
+            ExportDecl::Declaration(_) => {
+              for &id in &declares {
+                export(tree.binding(id).name(), Target::Local(Some(id)));
+              }
+            }
+            ExportDecl::DefaultFunction(_) | ExportDecl::DefaultClass(_) => {
+              export("default", Target::Local(declares.first().copied()))
+            }
+            ExportDecl::DefaultExpression(_) => export("default", Target::Local(None)),
+          }
+        }
+        _ => {}
+      }
+
+      statements.push(Statement {
+        references,
+        dynamic_imports: sites.dynamic_imports,
+        side_effects: effects::statement(item, &tree),
+        group,
+      });
+    }
+
+    let eval = tree.scope(root).is_tainted();
+    Self {
+      tree,
+      statements,
+      exports,
+      stars,
+      imports,
+      declarations,
+      dependencies,
+      eval,
+    }
+  }
+
+  /// Finds an exported name.
+  pub(super) fn export(&self, name: &str) -> Option<&Export> {
+    self
+      .exports
+      .iter()
+      .find(|(exported, _)| **exported == *name)
+      .map(|(_, export)| export)
+  }
+}
+
+/// Splits the top level declaration lists declaring several names, such as `const a = 1, b = 2;`,
+/// into a statement per declaration, so that each one can be removed on its own.
+///
+/// Returns the index of the original list of each statement, `None` for the other statements.
+pub(super) fn split_declarations(body: &mut StatementList) -> Vec<Option<usize>> {
+  let mut groups = Vec::new();
+  let mut group = 0;
+  body.replace_items(|items| {
+    let mut split = Vec::with_capacity(items.len());
+    for mut item in items {
+      let exported = matches!(item, Node::Export(_, _));
+      let list = match &mut item {
+        Node::DeclarationList(list, _) if list.as_ref().len() > 1 => list,
+        Node::Export(ExportDecl::Declaration(decl), _) => match decl.as_mut() {
+          Node::DeclarationList(list, _) if list.as_ref().len() > 1 => list,
+          _ => {
+            split.push(item);
+            groups.push(None);
+            continue;
+          }
+        },
+        _ => {
+          split.push(item);
+          groups.push(None);
+          continue;
+        }
+      };
+      let (declarations, rebuild): (_, fn(ArenaBox<[_]>) -> DeclarationList) = match list {
+        DeclarationList::Const(declarations) => (mem::take(declarations), DeclarationList::Const),
+        DeclarationList::Let(declarations) => (mem::take(declarations), DeclarationList::Let),
+        DeclarationList::Var(declarations) => (mem::take(declarations), DeclarationList::Var),
+      };
+      for declaration in declarations.into_vec() {
+        let list = Node::from(rebuild(ArenaBox::from([declaration])));
+        split.push(if exported {
+          ExportDecl::declaration(list).into()
+        } else {
+          list
+        });
+        groups.push(Some(group));
+      }
+      group += 1;
+    }
+    split
+  });
+  groups
+}
+
+/// Merges back the declarations split by [`split_declarations`] which are next to each other.
+///
+/// Returns the number of statements each remaining statement was merged from.
+pub(super) fn merge_declarations(items: Vec<(Node, Option<usize>)>) -> Vec<(Node, usize)> {
+  let mut merged: Vec<(Node, usize)> = Vec::with_capacity(items.len());
+  let mut last_group = None;
+  for (mut item, group) in items {
+    if let (Some(group), Some(last)) = (group, last_group) {
+      let previous_exported = matches!(merged.last(), Some((Node::Export(_, _), _)));
+      if group == last && previous_exported == matches!(item, Node::Export(_, _)) {
+        let (previous, count) = merged
+          .last_mut()
+          .expect("a statement of the group was kept");
+        if let (Some(previous), Some(list)) =
+          (declaration_list(previous), declaration_list(&mut item))
+        {
+          let mut declarations = mem::take(declarations_mut(previous)).into_vec();
+          declarations.extend(mem::take(declarations_mut(list)).into_vec());
+          *declarations_mut(previous) = declarations.into();
+          *count += 1;
+          continue;
+        }
+      }
+    }
+    last_group = group;
+    merged.push((item, 1));
+  }
+  merged
+}
+
+/// Gets the declaration list of a statement, exported or not.
+fn declaration_list(item: &mut Node) -> Option<&mut DeclarationList> {
+  match item {
+    Node::DeclarationList(list, _) => Some(list),
+    Node::Export(ExportDecl::Declaration(decl), _) => match decl.as_mut() {
+      Node::DeclarationList(list, _) => Some(list),
+      _ => None,
+    },
+    _ => None,
+  }
+}
+
+/// Gets the declarations of a list.
+fn declarations_mut(
+  list: &mut DeclarationList,
+) -> &mut ArenaBox<[crate::syntax::ast::node::Declaration]> {
+  match list {
+    DeclarationList::Const(declarations)
+    | DeclarationList::Let(declarations)
+    | DeclarationList::Var(declarations) => declarations,
+  }
+}
+
+/// Collects the ids of the names of a statement which may declare or refer to a binding, and
+/// the modules it imports dynamically.
+///
+/// Names which are neither are left out by the scope tree, so collecting too many is harmless.
+#[derive(Debug, Default)]
+struct Sites {
+  names: Vec<NodeId>,
+  dynamic_imports: Vec<Box<str>>,
+}
+
+impl<'ast> Visitor<'ast> for Sites {
+  fn visit_identifier(&mut self, ident: &'ast Identifier, _path: &mut NodePath<'ast>) -> Flow {
+    self.names.push(ident.id());
+    Flow::Continue(())
+  }
+
+  fn visit_property_definition(
+    &mut self,
+    property: &'ast PropertyDefinition,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    if let PropertyDefinition::IdentifierReference(ident, _) = property {
+      self.names.push(ident.id());
+    }
+    visit::walk_property_definition(self, property, path)
+  }
+
+  fn visit_function_decl(&mut self, decl: &'ast FunctionDecl, path: &mut NodePath<'ast>) -> Flow {
+    self.names.push(decl.id());
+    visit::walk_function_decl(self, decl, path)
+  }
+
+  fn visit_function_expr(&mut self, expr: &'ast FunctionExpr, path: &mut NodePath<'ast>) -> Flow {
+    self.names.extend(expr.name().map(|_| expr.id()));
+    visit::walk_function_expr(self, expr, path)
+  }
+
+  fn visit_class(&mut self, class: &'ast Class, path: &mut NodePath<'ast>) -> Flow {
+    self.names.extend(class.name().map(|_| class.id()));
+    visit::walk_class(self, class, path)
+  }
+
+  fn visit_import_specifier(
+    &mut self,
+    specifier: &'ast ImportSpecifier,
+    _path: &mut NodePath<'ast>,
+  ) -> Flow {
+    self.names.push(specifier.id());
+    Flow::Continue(())
+  }
+
+  fn visit_export_specifier(
+    &mut self,
+    specifier: &'ast ExportSpecifier,
+    _path: &mut NodePath<'ast>,
+  ) -> Flow {
+    self.names.push(specifier.id());
+    Flow::Continue(())
+  }
+
+  fn visit_import_call(&mut self, call: &'ast ImportCall, path: &mut NodePath<'ast>) -> Flow {
+    if let Node::Const(Const::String(specifier), _) = call.specifier() {
+      self.dynamic_imports.push(specifier.clone());
+    }
+    visit::walk_import_call(self, call, path)
+  }
+}
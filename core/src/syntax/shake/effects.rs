@@ -0,0 +1,223 @@
+//! Side effect analysis: whether removing a statement or an expression changes what a program
+//! does.
+//!
+//! The analysis is conservative: anything that may run code from elsewhere, such as a call, a
+//! property access which may run a getter or an operator which may convert an object, has side
+//! effects unless it is annotated as pure.
+
+use crate::syntax::{
+  ast::{
+    node::{
+      Class, ClassElement, ClassElementName, DeclarationList, ExportDecl, Identifier, Pattern,
+      PropertyDefinition, PropertyName,
+    },
+    operator::{self, BinOp, CompOp},
+    Node,
+  },
+  fold,
+  scope::ScopeTree,
+};
+
+/// The globals which can be read without side effects.
+const KNOWN_GLOBALS: &[&str] = &[
+  "Array",
+  "ArrayBuffer",
+  "Boolean",
+  "DataView",
+  "Date",
+  "Error",
+  "Function",
+  "Infinity",
+  "JSON",
+  "Map",
+  "Math",
+  "NaN",
+  "Number",
+  "Object",
+  "Promise",
+  "Proxy",
+  "RangeError",
+  "Reflect",
+  "RegExp",
+  "Set",
+  "String",
+  "Symbol",
+  "TypeError",
+  "WeakMap",
+  "WeakSet",
+  "globalThis",
+  "undefined",
+];
+
+/// Returns `true` if running a top level statement may have an effect outside of the bindings
+/// it declares.
+pub(super) fn statement(node: &Node, tree: &ScopeTree) -> bool {
+  !Checker { tree }.statement(node)
+}
+
+/// Checks whether nodes are free of side effects.
+#[derive(Debug, Clone, Copy)]
+struct Checker<'t> {
+  tree: &'t ScopeTree,
+}
+
+impl Checker<'_> {
+  /// Returns `true` if a statement is free of side effects.
+  fn statement(self, node: &Node) -> bool {
+    match node {
+      Node::Empty(_) | Node::FunctionDecl(_) | Node::Import(_) => true,
+      Node::ClassDecl(class) => self.class(class),
+      Node::DeclarationList(list, _) => self.declarations(list),
+      Node::Export(decl, _) => match decl {
+        ExportDecl::Named { .. } | ExportDecl::All { .. } | ExportDecl::DefaultFunction(_) => true,
+        ExportDecl::Declaration(node) => self.statement(node),
+        ExportDecl::DefaultClass(class) => self.class(class),
+        ExportDecl::DefaultExpression(expr) => self.expr(expr),
+      },
+      Node::Block(block) => block.statements().iter().all(|node| self.statement(node)),
+      Node::If(stmt) => {
+        self.expr(stmt.cond())
+          && self.statement(stmt.body())
+          && stmt.else_node().is_none_or(|node| self.statement(node))
+      }
+      node => self.expr(node),
+    }
+  }
+
+  /// Returns `true` if the declarations of a list are free of side effects.
+  ///
+  /// Destructuring may run getters and iterators, so only declarations of single names are.
+  fn declarations(self, list: &DeclarationList) -> bool {
+    list.as_ref().iter().all(|declaration| {
+      matches!(declaration.target(), Pattern::Identifier(_))
+        && declaration.init().is_none_or(|init| self.expr(init))
+    })
+  }
+
+  /// Returns `true` if evaluating an expression is free of side effects.
+  fn expr(self, node: &Node) -> bool {
+    match node {
+      Node::Const(_, _)
+      | Node::FunctionExpr(_)
+      | Node::ArrowFunctionDecl(_)
+      | Node::This(_)
+      | Node::ImportMeta(_) => true,
+      Node::Identifier(ident) => self.read(ident),
+      Node::ClassExpr(class) => self.class(class),
+      Node::ArrayDecl(array) => array
+        .as_ref()
+        .iter()
+        .all(|element| !matches!(element, Node::Spread(_)) && self.expr(element)),
+      Node::Object(object) => object.properties().iter().all(|property| match property {
+        PropertyDefinition::IdentifierReference(ident, _) => self.read(ident),
+        PropertyDefinition::Property(name, value, _) => self.property_name(name) && self.expr(value),
+        PropertyDefinition::MethodDefinition(_, name, _, _) => self.property_name(name),
+        PropertyDefinition::SpreadObject(_, _) => false,
+      }),
+      Node::UnaryOp(expr) => match expr.op() {
+        operator::UnaryOp::TypeOf => match expr.target() {
+          // Reading an undeclared global throws, except with `typeof`.
+          Node::Identifier(_) => true,
+          target => self.expr(target),
+        },
+        operator::UnaryOp::Not | operator::UnaryOp::Void => self.expr(expr.target()),
+        // Converting an object to a number may call its methods.
+        operator::UnaryOp::Minus | operator::UnaryOp::Plus | operator::UnaryOp::Tilde => {
+          fold::evaluate(node).is_some()
+        }
+        _ => false,
+      },
+      Node::BinOp(expr) => match expr.op() {
+        BinOp::Log(_) | BinOp::Comma => self.expr(expr.lhs()) && self.expr(expr.rhs()),
+        BinOp::Comp(CompOp::StrictEqual) | BinOp::Comp(CompOp::StrictNotEqual) => {
+          self.expr(expr.lhs()) && self.expr(expr.rhs())
+        }
+        BinOp::Assign(_) => false,
+        _ => fold::evaluate(node).is_some(),
+      },
+      Node::ConditionalOp(expr) => {
+        self.expr(expr.cond()) && self.expr(expr.if_true()) && self.expr(expr.if_false())
+      }
+      // Converting an object to a string may call its methods.
+      Node::TemplateLit(template) => template
+        .exprs()
+        .iter()
+        .all(|expr| fold::evaluate(expr).is_some()),
+      Node::Call(call) => {
+        call.pure() && self.callee(call.expr()) && call.args().iter().all(|arg| self.argument(arg))
+      }
+      Node::New(new) => {
+        new.pure() && self.callee(new.expr()) && new.args().iter().all(|arg| self.argument(arg))
+      }
+      _ => false,
+    }
+  }
+
+  /// Returns `true` if reading a name is free of side effects: it refers to a binding that is
+  /// initialized, or to a known global, and is not dynamic, since the object of a `with`
+  /// statement can have a getter for it.
+  fn read(self, ident: &Identifier) -> bool {
+    match self.tree.reference_at(ident.id()) {
+      Some(id) => {
+        let reference = self.tree.reference(id);
+        if reference.is_dynamic() {
+          false
+        } else if reference.is_global() {
+          KNOWN_GLOBALS.contains(&ident.name().as_str())
+        } else {
+          !reference.is_tdz()
+        }
+      }
+      None => false,
+    }
+  }
+
+  /// Returns `true` if the callee of an annotated call is free of side effects. The property
+  /// accesses leading to the called function are part of the annotated call.
+  fn callee(self, node: &Node) -> bool {
+    match node {
+      Node::Identifier(_) => true,
+      Node::GetConstField(get) => self.callee(get.obj()),
+      Node::GetField(get) => self.callee(get.obj()) && self.expr(get.field()),
+      node => self.expr(node),
+    }
+  }
+
+  /// Returns `true` if an argument of a call is free of side effects.
+  fn argument(self, node: &Node) -> bool {
+    !matches!(node, Node::Spread(_)) && self.expr(node)
+  }
+
+  /// Returns `true` if computing the name of a property is free of side effects.
+  fn property_name(self, name: &PropertyName) -> bool {
+    match name {
+      PropertyName::Literal(_) => true,
+      // Converting a key to a string may call its methods.
+      PropertyName::Computed(key) => {
+        matches!(key, Node::Const(_, _)) || fold::evaluate(key).is_some()
+      }
+    }
+  }
+
+  /// Returns `true` if defining a class is free of side effects: the superclass, the computed
+  /// names and the static initializers are.
+  fn class(self, class: &Class) -> bool {
+    class.super_ref().is_none_or(|node| self.expr(node))
+      && class.elements().iter().all(|element| {
+        let name = match element.name() {
+          Some(ClassElementName::PropertyName(name)) => self.property_name(name),
+          _ => true,
+        };
+        name
+          && match element {
+            ClassElement::Field {
+              init: Some(init),
+              is_static: true,
+              ..
+            } => self.expr(init),
+            ClassElement::StaticBlock(body, _) => body.items().is_empty(),
+            _ => true,
+          }
+      })
+  }
+}
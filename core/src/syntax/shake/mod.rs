@@ -0,0 +1,510 @@
+//! Tree shaking: removing the code of a module graph that nothing uses.
+//!
+//! A [`ModuleGraph`] holds parsed modules by name, some of them entries whose exports are all
+//! used. [`ModuleGraph::shake`] follows the imports from the entries and keeps the top level
+//! statements which are needed:
+//!
+//!  - statements with side effects, in the modules which run,
+//!  - declarations of the bindings that kept statements use, following imports and re-exports
+//!    into other modules,
+//!  - imports of the modules which still have statements, so that they run in the same order.
+//!
+//! Everything else is removed: unused exports, functions, classes and variables nothing refers
+//! to, and statements which are free of side effects, such as unused calls annotated with
+//! `/*#__PURE__*/`. Modules which are not reached, or whose statements are all removed,
+//! disappear from the output. Each kept statement comes with the [`Reason`] it was kept for:
+//!
+//! ```
+//! use plastic::syntax::{
+//!   codegen::{Generator, Options as CodegenOptions},
+//!   parser::Parser,
+//!   shake::{ModuleGraph, Options},
+//! };
+//!
+//! let parse = |js: &str| Parser::new(js.as_bytes()).parse_module().unwrap();
+//! let mut graph = ModuleGraph::new();
+//! graph.add_entry("main.js", parse("import { add } from './math.js'; console.log(add(1, 2));"));
+//! graph.add(
+//!   "math.js",
+//!   parse("export function add(a, b) { return a + b; } export const zero = /*#__PURE__*/ make();"),
+//! );
+//!
+//! let shaken = graph.shake(&Options::default());
+//! let math = shaken.module("math.js").unwrap();
+//! assert_eq!(
+//!   Generator::new(CodegenOptions::compact()).module(math.module().unwrap()),
+//!   "export function add(a,b){return a+b}"
+//! );
+//! assert_eq!(
+//!   shaken.to_string(),
+//!   "main.js\n  \
+//!      0: import \"./math.js\": imports `add`, which kept code uses\n  \
+//!      1: expression: has side effects\n\
+//!    math.js\n  \
+//!      0: export function add: `add` is imported by main.js\n"
+//! );
+//! ```
+//!
+//! Whether a statement has side effects is decided conservatively: calls, `new`, assignments,
+//! property accesses and operators which may convert objects have, unless the call is
+//! annotated as pure. Destructuring declarations have, since they may run getters. A module
+//! calling `eval` directly keeps all of its statements.
+//!
+//! Import specifiers are resolved against the names of the modules in the graph, see
+//! [`ModuleGraph::resolve`]. Modules which are not found are external: their imports are kept,
+//! and the names they export are assumed to be used.
+
+mod analysis;
+mod effects;
+mod shaker;
+#[cfg(test)]
+mod tests;
+
+use crate::syntax::ast::{
+  module::Module,
+  node::{DeclarationList, ExportDecl, Node},
+  Sym,
+};
+use std::fmt;
+
+/// The index of a module in a [`ModuleGraph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ModuleId(usize);
+
+/// A module of a [`ModuleGraph`].
+#[derive(Debug, Clone)]
+struct GraphModule {
+  name: Box<str>,
+  module: Module,
+  entry: bool,
+}
+
+/// Parsed modules by name, and the entries of the program they make.
+#[derive(Debug, Clone, Default)]
+pub struct ModuleGraph {
+  modules: Vec<GraphModule>,
+}
+
+impl ModuleGraph {
+  /// Creates an empty graph.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Adds a module, replacing the module of the same name if there is one.
+  pub fn add<N>(&mut self, name: N, module: Module) -> ModuleId
+  where
+    N: Into<Box<str>>,
+  {
+    self.insert(name.into(), module, false)
+  }
+
+  /// Adds an entry module, whose exports are all used.
+  pub fn add_entry<N>(&mut self, name: N, module: Module) -> ModuleId
+  where
+    N: Into<Box<str>>,
+  {
+    self.insert(name.into(), module, true)
+  }
+
+  fn insert(&mut self, name: Box<str>, module: Module, entry: bool) -> ModuleId {
+    let graph_module = GraphModule {
+      name,
+      module,
+      entry,
+    };
+    match self.find(&graph_module.name) {
+      Some(id) => {
+        self.modules[id.0] = graph_module;
+        id
+      }
+      None => {
+        self.modules.push(graph_module);
+        ModuleId(self.modules.len() - 1)
+      }
+    }
+  }
+
+  /// Finds a module by name.
+  pub fn find(&self, name: &str) -> Option<ModuleId> {
+    self
+      .modules
+      .iter()
+      .position(|module| *module.name == *name)
+      .map(ModuleId)
+  }
+
+  /// Gets the name of a module.
+  pub fn name(&self, id: ModuleId) -> &str {
+    &self.modules[id.0].name
+  }
+
+  /// Gets a module.
+  pub fn module(&self, id: ModuleId) -> &Module {
+    &self.modules[id.0].module
+  }
+
+  /// Returns `true` if a module is an entry.
+  pub fn is_entry(&self, id: ModuleId) -> bool {
+    self.modules[id.0].entry
+  }
+
+  /// Iterates over the modules, in the order they were added.
+  pub fn modules(&self) -> impl Iterator<Item = (ModuleId, &str)> {
+    self
+      .modules
+      .iter()
+      .enumerate()
+      .map(|(index, module)| (ModuleId(index), &*module.name))
+  }
+
+  /// Resolves an import specifier of a module to a module of the graph.
+  ///
+  /// Relative specifiers, starting with `./` or `../`, are joined to the directory of the
+  /// importing module, then looked up as they are, with a `.js` or `.mjs` extension, or as a
+  /// directory with an `index.js` file. Other specifiers are looked up as they are.
+  pub fn resolve(&self, from: ModuleId, specifier: &str) -> Option<ModuleId> {
+    resolve(self.name(from), specifier, |name| self.find(name))
+  }
+
+  /// Removes the code that nothing uses.
+  pub fn shake(self, options: &Options) -> Shaken {
+    shaker::shake(self, options)
+  }
+}
+
+/// Resolves an import specifier of the module `from`, finding modules with `find`.
+fn resolve<F>(from: &str, specifier: &str, find: F) -> Option<ModuleId>
+where
+  F: Fn(&str) -> Option<ModuleId>,
+{
+  if !(specifier.starts_with("./") || specifier.starts_with("../")) {
+    return find(specifier);
+  }
+  let path = match from.rfind('/') {
+    Some(end) => normalize(&format!("{}/{}", &from[..end], specifier))?,
+    None => normalize(specifier)?,
+  };
+  ["", ".js", ".mjs", "/index.js"]
+    .iter()
+    .find_map(|suffix| find(&format!("{}{}", path, suffix)))
+}
+
+/// Removes the `.` and `..` segments of a path, `None` if it leaves the root.
+fn normalize(path: &str) -> Option<String> {
+  let mut segments: Vec<&str> = Vec::new();
+  for segment in path.split('/') {
+    match segment {
+      "" | "." => {}
+      ".." => {
+        segments.pop()?;
+      }
+      segment => segments.push(segment),
+    }
+  }
+  let normalized = segments.join("/");
+  Some(if path.starts_with('/') {
+    format!("/{}", normalized)
+  } else {
+    normalized
+  })
+}
+
+/// Which modules run their side effects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SideEffects {
+  /// Every module may have side effects.
+  All,
+  /// Only the modules whose names match one of the patterns may have side effects, like the
+  /// `sideEffects` field of a `package.json` file.
+  ///
+  /// The statements with side effects of other modules only run if something they export is
+  /// used, and are removed with the module otherwise. Entries always run.
+  ///
+  /// In patterns, `*` matches any characters except `/`, `**` any characters and `?` one
+  /// character. Patterns without a `/` are matched against the last segment of module names,
+  /// so `*.css` matches every module ending with `.css`.
+  Only(Vec<Box<str>>),
+}
+
+impl SideEffects {
+  /// Creates an allow-list of patterns.
+  pub fn only<I, P>(patterns: I) -> Self
+  where
+    I: IntoIterator<Item = P>,
+    P: Into<Box<str>>,
+  {
+    Self::Only(patterns.into_iter().map(Into::into).collect())
+  }
+
+  /// Returns `true` if the module of the given name may have side effects.
+  pub fn matches(&self, name: &str) -> bool {
+    match self {
+      Self::All => true,
+      Self::Only(patterns) => patterns.iter().any(|pattern| {
+        let name = if pattern.contains('/') {
+          name.trim_start_matches("./")
+        } else {
+          name.rsplit('/').next().unwrap_or(name)
+        };
+        glob(pattern.trim_start_matches("./").as_bytes(), name.as_bytes())
+      }),
+    }
+  }
+}
+
+/// Every module may have side effects.
+impl Default for SideEffects {
+  fn default() -> Self {
+    Self::All
+  }
+}
+
+/// Matches a name against a glob pattern.
+fn glob(pattern: &[u8], name: &[u8]) -> bool {
+  match pattern {
+    [] => name.is_empty(),
+    [b'*', b'*', rest @ ..] => (0..=name.len()).any(|skip| glob(rest, &name[skip..])),
+    [b'*', rest @ ..] => (0..=name.len())
+      .take_while(|&skip| skip == 0 || name[skip - 1] != b'/')
+      .any(|skip| glob(rest, &name[skip..])),
+    [b'?', rest @ ..] => !name.is_empty() && glob(rest, &name[1..]),
+    [byte, rest @ ..] => name.first() == Some(byte) && glob(rest, &name[1..]),
+  }
+}
+
+/// The options of [`ModuleGraph::shake`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Options {
+  side_effects: SideEffects,
+}
+
+impl Options {
+  /// Sets which modules run their side effects.
+  pub fn with_side_effects(mut self, side_effects: SideEffects) -> Self {
+    self.side_effects = side_effects;
+    self
+  }
+
+  /// Gets which modules run their side effects.
+  pub fn side_effects(&self) -> &SideEffects {
+    &self.side_effects
+  }
+}
+
+/// Why a top level statement was kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reason {
+  /// The statement has side effects, and its module runs.
+  SideEffects,
+  /// The module calls `eval` directly, which may use any of its bindings.
+  Eval,
+  /// The statement declares or exports a name that an entry module exports.
+  Entry {
+    /// The exported name.
+    name: Box<str>,
+  },
+  /// The statement declares or exports a name that another module imports.
+  Imported {
+    /// The exported name.
+    name: Box<str>,
+    /// The module importing it.
+    by: Box<str>,
+  },
+  /// The statement declares a binding that another kept statement of the module uses.
+  Referenced {
+    /// The name of the binding.
+    name: Box<str>,
+    /// The index of the statement using it, in the shaken module.
+    by: usize,
+  },
+  /// The statement imports a binding that kept statements use.
+  Import {
+    /// The local name of the binding.
+    name: Box<str>,
+  },
+  /// The statement imports a module which still has statements to run.
+  Dependency {
+    /// The imported module.
+    module: Box<str>,
+  },
+}
+
+impl fmt::Display for Reason {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::SideEffects => f.write_str("has side effects"),
+      Self::Eval => f.write_str("the module calls `eval`"),
+      Self::Entry { name } => write!(f, "`{}` is exported by the entry", name),
+      Self::Imported { name, by } => write!(f, "`{}` is imported by {}", name, by),
+      Self::Referenced { name, by } => write!(f, "declares `{}`, used by statement {}", name, by),
+      Self::Import { name } => write!(f, "imports `{}`, which kept code uses", name),
+      Self::Dependency { module } => write!(f, "runs {} first", module),
+    }
+  }
+}
+
+/// A kept top level statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Kept {
+  statement: usize,
+  reason: Reason,
+}
+
+impl Kept {
+  /// Gets the index of the statement in the shaken module.
+  pub fn statement(&self) -> usize {
+    self.statement
+  }
+
+  /// Gets why the statement was kept.
+  pub fn reason(&self) -> &Reason {
+    &self.reason
+  }
+}
+
+/// A module after tree shaking.
+#[derive(Debug, Clone)]
+pub struct ShakenModule {
+  name: Box<str>,
+  module: Option<Module>,
+  kept: Vec<Kept>,
+}
+
+impl ShakenModule {
+  /// Gets the name of the module.
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Gets the shaken module, `None` if all of its statements were removed.
+  pub fn module(&self) -> Option<&Module> {
+    self.module.as_ref()
+  }
+
+  /// Consumes the result, returning the shaken module.
+  pub fn into_module(self) -> Option<Module> {
+    self.module
+  }
+
+  /// Gets the kept statements and why they were kept, in source order.
+  pub fn kept(&self) -> &[Kept] {
+    &self.kept
+  }
+}
+
+/// Lists the kept statements of the module, with why they were kept.
+impl fmt::Display for ShakenModule {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let module = match &self.module {
+      Some(module) => module,
+      None => return writeln!(f, "{}: removed", self.name),
+    };
+    writeln!(f, "{}", self.name)?;
+    for kept in &self.kept {
+      writeln!(
+        f,
+        "  {}: {}: {}",
+        kept.statement,
+        describe(&module.body().items()[kept.statement]),
+        kept.reason
+      )?;
+    }
+    Ok(())
+  }
+}
+
+/// The result of [`ModuleGraph::shake`].
+#[derive(Debug, Clone)]
+pub struct Shaken {
+  modules: Vec<ShakenModule>,
+}
+
+impl Shaken {
+  /// Gets the modules, in the order they were added to the graph. Modules which are not
+  /// reached from an entry are removed.
+  pub fn modules(&self) -> &[ShakenModule] {
+    &self.modules
+  }
+
+  /// Finds a module by name.
+  pub fn module(&self, name: &str) -> Option<&ShakenModule> {
+    self.modules.iter().find(|module| *module.name == *name)
+  }
+
+  /// Consumes the result, returning the modules.
+  pub fn into_modules(self) -> Vec<ShakenModule> {
+    self.modules
+  }
+}
+
+/// Lists the kept statements of every module, with why they were kept.
+impl fmt::Display for Shaken {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for module in &self.modules {
+      write!(f, "{}", module)?;
+    }
+    Ok(())
+  }
+}
+
+/// Describes a top level statement in a few words, such as `function add` or `const a, b`.
+fn describe(node: &Node) -> String {
+  let declarations = |list: &DeclarationList| {
+    let names: Vec<_> = list
+      .as_ref()
+      .iter()
+      .flat_map(|declaration| declaration.target().bound_names())
+      .collect();
+    let names: Vec<_> = names.iter().map(|name| name.as_str()).collect();
+    format!("{} {}", list.kind(), names.join(", "))
+  };
+  match node {
+    Node::FunctionDecl(function) => format!("function {}", function.name()),
+    Node::ClassDecl(class) => format!("class {}", class.name().map_or("", Sym::as_str)),
+    Node::DeclarationList(list, _) => declarations(list),
+    Node::Import(decl) => format!("import {:?}", decl.source()),
+    Node::Export(decl, _) => match decl {
+      ExportDecl::Named {
+        specifiers, source, ..
+      } => {
+        let names: Vec<_> = specifiers
+          .iter()
+          .map(|specifier| specifier.exported())
+          .collect();
+        match source {
+          Some(source) => format!("export {{ {} }} from {:?}", names.join(", "), source),
+          None => format!("export {{ {} }}", names.join(", ")),
+        }
+      }
+      ExportDecl::All {
+        alias: Some(alias),
+        source,
+        ..
+      } => format!("export * as {} from {:?}", alias, source),
+      ExportDecl::All { source, .. } => format!("export * from {:?}", source),
+      ExportDecl::Declaration(decl) => format!("export {}", describe(decl)),
+      ExportDecl::DefaultFunction(_)
+      | ExportDecl::DefaultClass(_)
+      | ExportDecl::DefaultExpression(_) => "export default".to_owned(),
+    },
+    Node::Block(_)
+    | Node::Break(_)
+    | Node::Continue(_)
+    | Node::Debugger(_)
+    | Node::DoWhileLoop(_)
+    | Node::Empty(_)
+    | Node::ForInLoop(_)
+    | Node::ForLoop(_)
+    | Node::ForOfLoop(_)
+    | Node::If(_)
+    | Node::Labelled(_)
+    | Node::Return(_)
+    | Node::Switch(_)
+    | Node::Throw(_)
+    | Node::Try(_)
+    | Node::WhileLoop(_)
+    | Node::With(_) => "statement".to_owned(),
+    _ => "expression".to_owned(),
+  }
+}
@@ -0,0 +1,527 @@
+//! Marking the statements to keep, then rewriting the modules without the others.
+
+use super::{
+  analysis::{self, Analysis, Target},
+  resolve, Kept, ModuleGraph, ModuleId, Options, Reason, Shaken, ShakenModule,
+};
+use crate::syntax::{
+  ast::{
+    module::Module,
+    node::{ExportDecl, ImportDecl, Node},
+  },
+  scope::BindingId,
+};
+use std::{
+  collections::{HashMap, HashSet, VecDeque},
+  mem,
+};
+
+/// Why the exports of a module are used.
+#[derive(Debug, Clone, Copy)]
+enum Cause {
+  /// The module is an entry.
+  Entry,
+  /// Another module imports them.
+  Imported(usize),
+}
+
+/// The state of the marking of the statements to keep.
+#[derive(Debug)]
+struct Shaker<'o> {
+  options: &'o Options,
+  names: Vec<Box<str>>,
+  entries: Vec<bool>,
+  modules: Vec<Module>,
+  analyses: Vec<Analysis>,
+  /// Why each statement is kept, `None` for the statements to remove. The statements using a
+  /// binding are given by their index in the original module until the modules are rewritten.
+  reasons: Vec<Vec<Option<Reason>>>,
+  used_bindings: Vec<HashSet<BindingId>>,
+  used_exports: Vec<HashSet<Box<str>>>,
+  /// Whether the module is reached from an entry by static imports.
+  reached: Vec<bool>,
+  /// Whether the statements with side effects of the module run.
+  included: Vec<bool>,
+  /// The kept statements whose references are not followed yet.
+  queue: VecDeque<(usize, usize)>,
+}
+
+/// Shakes the modules of a graph.
+pub(super) fn shake(graph: ModuleGraph, options: &Options) -> Shaken {
+  let mut shaker = Shaker {
+    options,
+    names: Vec::new(),
+    entries: Vec::new(),
+    modules: Vec::new(),
+    analyses: Vec::new(),
+    reasons: Vec::new(),
+    used_bindings: Vec::new(),
+    used_exports: Vec::new(),
+    reached: Vec::new(),
+    included: Vec::new(),
+    queue: VecDeque::new(),
+  };
+  for graph_module in graph.modules {
+    let mut body = graph_module.module.into_body();
+    let groups = analysis::split_declarations(&mut body);
+    let module = Module::new(body);
+    let analysis = Analysis::new(&module, groups);
+    shaker.reasons.push(vec![None; analysis.statements.len()]);
+    shaker.names.push(graph_module.name);
+    shaker.entries.push(graph_module.entry);
+    shaker.modules.push(module);
+    shaker.analyses.push(analysis);
+    shaker.used_bindings.push(HashSet::new());
+    shaker.used_exports.push(HashSet::new());
+    shaker.reached.push(false);
+    shaker.included.push(false);
+  }
+
+  let entries: Vec<_> = (0..shaker.names.len())
+    .filter(|&module| shaker.entries[module])
+    .collect();
+  for &entry in &entries {
+    shaker.reach(entry);
+  }
+  for &entry in &entries {
+    shaker.use_all(entry, Cause::Entry);
+  }
+  while let Some((module, statement)) = shaker.queue.pop_front() {
+    shaker.follow(module, statement);
+  }
+  shaker.rewrite()
+}
+
+impl Shaker<'_> {
+  /// Resolves an import specifier of a module.
+  fn resolve(&self, module: usize, specifier: &str) -> Option<usize> {
+    resolve(&self.names[module], specifier, |name| {
+      self
+        .names
+        .iter()
+        .position(|module| **module == *name)
+        .map(ModuleId)
+    })
+    .map(|ModuleId(index)| index)
+  }
+
+  /// Marks a module as reached, with the modules it imports statically.
+  fn reach(&mut self, module: usize) {
+    if mem::replace(&mut self.reached[module], true) {
+      return;
+    }
+    if self.entries[module] || self.options.side_effects().matches(&self.names[module]) {
+      self.include(module);
+    }
+    let dependencies: Vec<_> = self.analyses[module]
+      .dependencies
+      .iter()
+      .filter_map(|(_, source)| self.resolve(module, source))
+      .collect();
+    for dependency in dependencies {
+      self.reach(dependency);
+    }
+  }
+
+  /// Keeps the statements with side effects of a module, or all of its statements if it calls
+  /// `eval`.
+  fn include(&mut self, module: usize) {
+    if mem::replace(&mut self.included[module], true) {
+      return;
+    }
+    let analysis = &self.analyses[module];
+    if analysis.eval {
+      let mut imports: Vec<_> = analysis.imports.keys().copied().collect();
+      imports.sort();
+      for statement in 0..analysis.statements.len() {
+        self.keep(module, statement, Reason::Eval);
+      }
+      for binding in imports {
+        self.use_binding(module, binding, Reason::Eval);
+      }
+    } else {
+      let effects: Vec<_> = (0..analysis.statements.len())
+        .filter(|&statement| analysis.statements[statement].side_effects)
+        .collect();
+      for statement in effects {
+        self.keep(module, statement, Reason::SideEffects);
+      }
+    }
+  }
+
+  /// Keeps a statement, unless it is already kept.
+  fn keep(&mut self, module: usize, statement: usize, reason: Reason) {
+    let kept = &mut self.reasons[module][statement];
+    if kept.is_none() {
+      *kept = Some(reason);
+      self.queue.push_back((module, statement));
+    }
+  }
+
+  /// Uses the bindings and the modules a kept statement refers to.
+  fn follow(&mut self, module: usize, statement: usize) {
+    let analysis = &self.analyses[module];
+    let references: Vec<_> = analysis.statements[statement]
+      .references
+      .iter()
+      .map(|&binding| (binding, Box::from(analysis.tree.binding(binding).name())))
+      .collect();
+    let dynamic_imports: Vec<_> = analysis.statements[statement]
+      .dynamic_imports
+      .iter()
+      .filter_map(|specifier| self.resolve(module, specifier))
+      .collect();
+
+    for (binding, name) in references {
+      self.use_binding(
+        module,
+        binding,
+        Reason::Referenced {
+          name,
+          by: statement,
+        },
+      );
+    }
+    for target in dynamic_imports {
+      self.reach(target);
+      self.use_all(target, Cause::Imported(module));
+    }
+  }
+
+  /// Keeps the declarations of a binding, or the import creating it.
+  fn use_binding(&mut self, module: usize, binding: BindingId, reason: Reason) {
+    if !self.used_bindings[module].insert(binding) {
+      return;
+    }
+    let analysis = &self.analyses[module];
+    if let Some(import) = analysis.imports.get(&binding) {
+      let statement = import.statement;
+      let name = import.name.clone();
+      let target = self.resolve(module, &import.source);
+      let local = analysis.tree.binding(binding).name().into();
+      self.keep(module, statement, Reason::Import { name: local });
+      match (target, name) {
+        (Some(target), Some(name)) => self.use_export(target, &name, Cause::Imported(module)),
+        (Some(target), None) => self.use_all(target, Cause::Imported(module)),
+        (None, _) => {}
+      }
+    } else if let Some(declarations) = analysis.declarations.get(&binding) {
+      for statement in declarations.clone() {
+        self.keep(module, statement, reason.clone());
+      }
+    }
+  }
+
+  /// Gets the reason to keep the statements declaring or exporting an exported name.
+  fn export_reason(&self, name: &str, cause: Cause) -> Reason {
+    match cause {
+      Cause::Entry => Reason::Entry { name: name.into() },
+      Cause::Imported(by) => Reason::Imported {
+        name: name.into(),
+        by: self.names[by].clone(),
+      },
+    }
+  }
+
+  /// Keeps the statements exporting a name of a module, and its declarations.
+  fn use_export(&mut self, module: usize, name: &str, cause: Cause) {
+    if !self.used_exports[module].insert(name.into()) {
+      return;
+    }
+    self.include(module);
+    let reason = self.export_reason(name, cause);
+
+    let analysis = &self.analyses[module];
+    if let Some(export) = analysis.export(name) {
+      let statement = export.statement;
+      let next = match &export.target {
+        Target::Local(binding) => Err(*binding),
+        Target::Indirect { source, name } => Ok((self.resolve(module, source), Some(name.clone()))),
+        Target::Namespace { source } => Ok((self.resolve(module, source), None)),
+      };
+      self.keep(module, statement, reason.clone());
+      match next {
+        Err(Some(binding)) => self.use_binding(module, binding, reason),
+        Err(None) | Ok((None, _)) => {}
+        Ok((Some(target), Some(name))) => self.use_export(target, &name, Cause::Imported(module)),
+        Ok((Some(target), None)) => self.use_all(target, Cause::Imported(module)),
+      }
+    } else if name != "default" {
+      // The first star re-export providing the name.
+      let stars = analysis.stars.clone();
+      for (statement, source) in stars {
+        match self.resolve(module, &source) {
+          Some(target) if self.exports(target, name, &mut HashSet::new()) => {
+            self.keep(module, statement, reason);
+            self.use_export(target, name, Cause::Imported(module));
+            break;
+          }
+          Some(_) => {}
+          // External modules may export anything.
+          None => self.keep(module, statement, reason.clone()),
+        }
+      }
+    }
+  }
+
+  /// Uses all the names a module exports.
+  fn use_all(&mut self, module: usize, cause: Cause) {
+    self.include(module);
+    let mut names = Vec::new();
+    self.export_names(module, &mut names, &mut HashSet::new());
+    for name in names {
+      self.use_export(module, &name, cause);
+    }
+    // The names of external modules are not known.
+    let stars = self.analyses[module].stars.clone();
+    for (statement, source) in stars {
+      if self.resolve(module, &source).is_none() {
+        let reason = self.export_reason("*", cause);
+        self.keep(module, statement, reason);
+      }
+    }
+  }
+
+  /// Returns `true` if a module exports a name, directly or through star re-exports.
+  fn exports(&self, module: usize, name: &str, visited: &mut HashSet<usize>) -> bool {
+    if !visited.insert(module) {
+      return false;
+    }
+    let analysis = &self.analyses[module];
+    analysis.export(name).is_some()
+      || (name != "default"
+        && analysis.stars.iter().any(|(_, source)| {
+          self
+            .resolve(module, source)
+            .is_some_and(|target| self.exports(target, name, visited))
+        }))
+  }
+
+  /// Collects the names a module exports, in source order, star re-exports last.
+  fn export_names(&self, module: usize, names: &mut Vec<Box<str>>, visited: &mut HashSet<usize>) {
+    if !visited.insert(module) {
+      return;
+    }
+    let analysis = &self.analyses[module];
+    for (name, _) in &analysis.exports {
+      if !names.contains(name) {
+        names.push(name.clone());
+      }
+    }
+    for (_, source) in &analysis.stars {
+      if let Some(target) = self.resolve(module, source) {
+        let mut star_names = Vec::new();
+        self.export_names(target, &mut star_names, visited);
+        for name in star_names {
+          if &*name != "default" && !names.contains(&name) {
+            names.push(name);
+          }
+        }
+      }
+    }
+  }
+
+  /// Computes which reached modules still have statements to run: kept statements other than
+  /// imports, or imports of such modules.
+  fn contents(&self) -> Vec<bool> {
+    let mut contents: Vec<_> = (0..self.names.len())
+      .map(|module| {
+        self.reached[module]
+          && self.modules[module]
+            .body()
+            .items()
+            .iter()
+            .zip(&self.reasons[module])
+            .any(|(item, reason)| reason.is_some() && !matches!(item, Node::Import(_)))
+      })
+      .collect();
+    loop {
+      let mut changed = false;
+      for module in 0..self.names.len() {
+        if contents[module] || !self.reached[module] {
+          continue;
+        }
+        let runs = self.analyses[module]
+          .dependencies
+          .iter()
+          .any(|(_, source)| self.dependency_runs(module, source, &contents));
+        if runs {
+          contents[module] = true;
+          changed = true;
+        }
+      }
+      if !changed {
+        return contents;
+      }
+    }
+  }
+
+  /// Returns `true` if a module imported by another one has statements to run. External
+  /// modules are assumed to have some, unless they are free of side effects.
+  fn dependency_runs(&self, module: usize, source: &str, contents: &[bool]) -> bool {
+    match self.resolve(module, source) {
+      Some(target) => contents[target],
+      None => self.options.side_effects().matches(source),
+    }
+  }
+
+  /// Rewrites the modules without the statements to remove.
+  fn rewrite(mut self) -> Shaken {
+    let contents = self.contents();
+    let modules = mem::take(&mut self.modules);
+    let mut shaken = Vec::with_capacity(modules.len());
+    for (index, module) in modules.into_iter().enumerate() {
+      let name = self.names[index].clone();
+      if !contents[index] {
+        shaken.push(ShakenModule {
+          name,
+          module: None,
+          kept: Vec::new(),
+        });
+        continue;
+      }
+      let (module, kept) = self.rewrite_module(index, module, &contents);
+      shaken.push(ShakenModule {
+        name,
+        module: Some(module),
+        kept,
+      });
+    }
+    Shaken { modules: shaken }
+  }
+
+  /// Rewrites a module which still has statements.
+  fn rewrite_module(
+    &mut self,
+    index: usize,
+    module: Module,
+    contents: &[bool],
+  ) -> (Module, Vec<Kept>) {
+    let entry = self.entries[index];
+    let reasons = mem::take(&mut self.reasons[index]);
+    let analysis = &self.analyses[index];
+    let root = analysis.tree.root();
+    let used_exports = &self.used_exports[index];
+    let is_used = |local: &str| {
+      analysis
+        .tree
+        .scope(root)
+        .binding(local)
+        .is_some_and(|binding| self.used_bindings[index].contains(&binding))
+    };
+
+    let mut items = Vec::new();
+    let mut origins = Vec::new();
+    let mut body = module.into_body();
+    for (statement, (item, reason)) in body.items_mut().iter_mut().zip(reasons).enumerate() {
+      let group = analysis.statements[statement].group;
+      let rewritten = match (item, reason) {
+        (Node::Import(decl), _) => {
+          let specifiers: Vec<_> = decl
+            .specifiers()
+            .iter()
+            .filter(|specifier| is_used(specifier.local()))
+            .cloned()
+            .collect();
+          let reason = match specifiers.first() {
+            Some(specifier) => Some(Reason::Import {
+              name: specifier.local().into(),
+            }),
+            None if self.dependency_runs(index, decl.source(), contents) => {
+              Some(self.dependency_reason(index, decl.source()))
+            }
+            None => None,
+          };
+          reason.map(|reason| {
+            let decl = ImportDecl::new(specifiers, decl.source(), decl.attributes().to_vec());
+            (Node::from(decl), reason)
+          })
+        }
+        (Node::Export(decl, _), None) => match decl.source() {
+          // Re-exports still run the module they re-export from.
+          Some(source) if self.dependency_runs(index, source, contents) => {
+            let reason = self.dependency_reason(index, source);
+            Some((
+              ImportDecl::new(Vec::new(), source, Vec::new()).into(),
+              reason,
+            ))
+          }
+          _ => None,
+        },
+        (_, None) => None,
+        (
+          Node::Export(
+            ExportDecl::Named {
+              specifiers,
+              source,
+              attributes,
+            },
+            _,
+          ),
+          Some(reason),
+        ) => {
+          let specifiers: Vec<_> = specifiers
+            .iter()
+            .filter(|specifier| entry || used_exports.contains(specifier.exported()))
+            .cloned()
+            .collect();
+          let decl = ExportDecl::named(specifiers, source.take(), mem::take(attributes));
+          Some((decl.into(), reason))
+        }
+        // Exported declarations nothing imports are not exported anymore.
+        (Node::Export(ExportDecl::Declaration(decl), _), Some(reason))
+          if !entry
+            && !analysis.exports.iter().any(|(name, export)| {
+              export.statement == statement && used_exports.contains(name)
+            }) =>
+        {
+          Some((mem::replace(decl.as_mut(), Node::empty()), reason))
+        }
+        (Node::Export(ExportDecl::DefaultExpression(expr), _), Some(reason))
+          if !entry && !used_exports.contains("default") =>
+        {
+          Some((mem::replace(expr.as_mut(), Node::empty()), reason))
+        }
+        (item, Some(reason)) => Some((mem::replace(item, Node::empty()), reason)),
+      };
+      if let Some((item, reason)) = rewritten {
+        items.push((item, group));
+        origins.push((statement, reason));
+      }
+    }
+
+    // Merge the declaration lists split for the analysis back, and number the statements.
+    let merged = analysis::merge_declarations(items);
+    let mut positions = HashMap::new();
+    let mut kept = Vec::with_capacity(merged.len());
+    let mut origins = origins.into_iter();
+    let mut statements = Vec::with_capacity(merged.len());
+    for (position, (item, count)) in merged.into_iter().enumerate() {
+      let (statement, reason) = origins.next().expect("a merged statement has an origin");
+      positions.insert(statement, position);
+      for (statement, _) in origins.by_ref().take(count - 1) {
+        positions.insert(statement, position);
+      }
+      statements.push(item);
+      kept.push(Kept {
+        statement: position,
+        reason,
+      });
+    }
+    for kept in &mut kept {
+      if let Reason::Referenced { by, .. } = &mut kept.reason {
+        *by = positions[by];
+      }
+    }
+    (Module::new(statements), kept)
+  }
+
+  /// Gets the reason to keep the import of a module which has statements to run.
+  fn dependency_reason(&self, module: usize, source: &str) -> Reason {
+    let module = match self.resolve(module, source) {
+      Some(target) => self.names[target].clone(),
+      None => source.into(),
+    };
+    Reason::Dependency { module }
+  }
+}
@@ -0,0 +1,584 @@
+//! Tests for tree shaking.
+
+use super::{ModuleGraph, Options, Reason, Shaken, SideEffects};
+use crate::syntax::{
+  ast::module::Module,
+  codegen::{self, Generator},
+  parser::Parser,
+};
+
+/// Parses a module.
+#[track_caller]
+fn parse(js: &str) -> Module {
+  Parser::new(js.as_bytes())
+    .parse_module()
+    .unwrap_or_else(|err| panic!("failed to parse `{}`: {}", js, err))
+}
+
+/// Builds a graph whose first module is the entry.
+fn graph(modules: &[(&str, &str)]) -> ModuleGraph {
+  let mut graph = ModuleGraph::new();
+  for (index, (name, js)) in modules.iter().enumerate() {
+    if index == 0 {
+      graph.add_entry(*name, parse(js));
+    } else {
+      graph.add(*name, parse(js));
+    }
+  }
+  graph
+}
+
+/// Shakes a graph whose first module is the entry.
+fn shake_with(modules: &[(&str, &str)], options: &Options) -> Shaken {
+  graph(modules).shake(options)
+}
+
+/// Prints the shaken modules in compact form, `None` for removed ones.
+fn code(shaken: &Shaken) -> Vec<(&str, Option<String>)> {
+  let generator = Generator::new(codegen::Options::compact());
+  shaken
+    .modules()
+    .iter()
+    .map(|module| {
+      (
+        module.name(),
+        module.module().map(|module| generator.module(module)),
+      )
+    })
+    .collect()
+}
+
+/// Shakes a graph and prints the shaken modules.
+fn shake<'a>(modules: &[(&'a str, &str)]) -> Vec<(&'a str, Option<String>)> {
+  let shaken = shake_with(modules, &Options::default());
+  modules
+    .iter()
+    .map(|(name, _)| *name)
+    .zip(code(&shaken).into_iter().map(|(_, code)| code))
+    .collect()
+}
+
+/// Shakes a single entry module and prints it.
+fn shake_entry(js: &str) -> String {
+  shake(&[("main.js", js)])
+    .pop()
+    .and_then(|(_, code)| code)
+    .unwrap_or_default()
+}
+
+#[test]
+fn unused_exports() {
+  let shaken = shake(&[
+    (
+      "main.js",
+      "import { a, c as d } from './lib.js'; use(a, d);",
+    ),
+    (
+      "lib.js",
+      "export const a = 1; export function b() {} export class c {} export let e = b;",
+    ),
+  ]);
+  assert_eq!(
+    shaken,
+    [
+      (
+        "main.js",
+        Some("import {a,c as d}from\"./lib.js\";use(a,d)".into())
+      ),
+      ("lib.js", Some("export const a=1;export class c{}".into())),
+    ]
+  );
+
+  // Exported bindings used inside their module only are not exported anymore.
+  let shaken = shake(&[
+    ("main.js", "import { a } from './lib.js'; use(a);"),
+    (
+      "lib.js",
+      "const b = 2; export const a = b + c; export function c() { return b; }",
+    ),
+  ]);
+  assert_eq!(
+    shaken[1],
+    (
+      "lib.js",
+      Some("const b=2;export const a=b+c;function c(){return b}".into())
+    )
+  );
+
+  // The exports of entries are all kept.
+  assert_eq!(
+    shake_entry("export const a = 1; const b = 2; export { b as c }; const d = 3;"),
+    "export const a=1;const b=2;export{b as c}"
+  );
+}
+
+#[test]
+fn unreferenced_functions() {
+  assert_eq!(
+    shake_entry(
+      "function used() { return helper(); } function helper() { return 1; } \
+       function unused() { return helper(); } function recursive() { return recursive(); } \
+       used();"
+    ),
+    "function used(){return helper()}function helper(){return 1}used()"
+  );
+  // Functions used by other functions are kept only if those are.
+  assert_eq!(
+    shake_entry("function a() { b(); } function b() { a(); } class C { m() { a(); } }"),
+    ""
+  );
+  // Inner bindings with the name of a top level one do not keep it.
+  assert_eq!(
+    shake_entry("function f() {} function g(f) { return f; } export { g };"),
+    "function g(f){return f}export{g}"
+  );
+}
+
+#[test]
+fn side_effect_free_statements() {
+  assert_eq!(
+    shake_entry(
+      "const a = 1, b = 'b', c = [a, { b }], d = () => a; let e; var f = typeof g; \
+       class H { static i = 1; m() {} } 1 + 2; a === b ? c : d; if (a) { let j = 1; } ;"
+    ),
+    ""
+  );
+  assert_eq!(
+    shake_entry(
+      "console.log(1); a.b = 2; const c = d.e; const { f } = g; let h = i; new J(); \
+       class K extends L {} class M { static { run(); } } const n = -o;"
+    ),
+    "console.log(1);a.b=2;const c=d.e;const {f}=g;let h=i;new J();class K extends L{}\
+     class M{static{run()}}const n=-o"
+  );
+  // Reading a binding before its declaration throws.
+  assert_eq!(
+    shake_entry("const a = b; const b = 1;"),
+    "const a=b;const b=1"
+  );
+  // Known globals can be read, others may not exist.
+  assert_eq!(
+    shake_entry("const a = Math, b = undefined, c = unknown;"),
+    "const c=unknown"
+  );
+}
+
+#[test]
+fn pure_annotations() {
+  assert_eq!(
+    shake_entry(
+      "const a = /*#__PURE__*/ create(); const b = /*@__PURE__*/ new Map([[1, 2]]); \
+       /*#__PURE__*/ lib.factory.make(a);"
+    ),
+    ""
+  );
+  assert_eq!(
+    shake_entry(
+      "const a = create(); const b = /*#__PURE__*/ create(side()); \
+       const c = /*#__PURE__*/ create(...d); const e = /*#__PURE__*/ make()();"
+    ),
+    "const a=create();const b=/*#__PURE__*/create(side());\
+     const c=/*#__PURE__*/create(...d);const e=/*#__PURE__*/make()()"
+  );
+  // Used annotated calls are kept.
+  assert_eq!(
+    shake_entry("const a = /*#__PURE__*/ create(); export { a };"),
+    "const a=/*#__PURE__*/create();export{a}"
+  );
+}
+
+#[test]
+fn side_effects_allow_list() {
+  let modules = [
+    (
+      "src/main.js",
+      "import './setup.js'; import './util.js'; import { used } from './lib.js'; used();",
+    ),
+    ("src/setup.js", "window.ready = true;"),
+    (
+      "src/util.js",
+      "console.log('loaded'); export const helper = 1;",
+    ),
+    ("src/lib.js", "log('lib'); export function used() {}"),
+  ];
+  let options = Options::default().with_side_effects(SideEffects::only(vec!["setup.js"]));
+  assert_eq!(
+    code(&shake_with(&modules, &options)),
+    [
+      (
+        "src/main.js",
+        Some("import\"./setup.js\";import {used}from\"./lib.js\";used()".into())
+      ),
+      ("src/setup.js", Some("window.ready=true".into())),
+      ("src/util.js", None),
+      // Modules run their side effects when something they export is used.
+      (
+        "src/lib.js",
+        Some("log(\"lib\");export function used(){}".into())
+      ),
+    ]
+  );
+
+  // Every module may have side effects by default.
+  assert_eq!(
+    shake(&modules)[2],
+    ("src/util.js", Some("console.log(\"loaded\")".into()))
+  );
+  // Entries always run.
+  let options = Options::default().with_side_effects(SideEffects::only(Vec::<&str>::new()));
+  assert_eq!(
+    code(&shake_with(&[("main.js", "start();")], &options)),
+    [("main.js", Some("start()".into()))]
+  );
+}
+
+#[test]
+fn side_effects_patterns() {
+  let patterns = SideEffects::only(vec!["*.css", "./src/polyfills/**", "lib/?.js"]);
+  assert!(patterns.matches("styles/app.css"));
+  assert!(patterns.matches("app.css"));
+  assert!(patterns.matches("src/polyfills/array/flat.js"));
+  assert!(patterns.matches("lib/a.js"));
+  assert!(!patterns.matches("lib/ab.js"));
+  assert!(!patterns.matches("src/app.js"));
+  assert!(!patterns.matches("app.css.js"));
+  assert!(SideEffects::All.matches("anything.js"));
+  assert!(!SideEffects::only(vec!["src/*.js"]).matches("src/a/b.js"));
+}
+
+#[test]
+fn imports() {
+  // Modules which are not reached are removed, and imports of modules without statements.
+  assert_eq!(
+    shake(&[
+      (
+        "main.js",
+        "import { a } from './a.js'; import b from './b.js'; import './c.js';"
+      ),
+      ("a.js", "export const a = 1;"),
+      ("b.js", "export default function () {}"),
+      ("c.js", "import './d.js';"),
+      ("d.js", "run();"),
+      ("e.js", "run();"),
+    ]),
+    [
+      ("main.js", Some("import\"./c.js\"".into())),
+      ("a.js", None),
+      ("b.js", None),
+      ("c.js", Some("import\"./d.js\"".into())),
+      ("d.js", Some("run()".into())),
+      ("e.js", None),
+    ]
+  );
+
+  // Namespace imports use every export.
+  assert_eq!(
+    shake(&[
+      ("main.js", "import * as ns from './lib.js'; use(ns);"),
+      (
+        "lib.js",
+        "export const a = 1; export function b() {} const c = 2;"
+      ),
+    ])[1],
+    (
+      "lib.js",
+      Some("export const a=1;export function b(){}".into())
+    )
+  );
+
+  // External modules are kept, they may have side effects.
+  assert_eq!(
+    shake_entry("import a from 'react'; import { b } from 'lodash'; import 'polyfill'; b();"),
+    "import\"react\";import {b}from\"lodash\";import\"polyfill\";b()"
+  );
+}
+
+#[test]
+fn reexports() {
+  let shaken = shake(&[
+    (
+      "main.js",
+      "import { a, renamed, c, ns } from './index.js'; use(a, renamed, c, ns);",
+    ),
+    (
+      "index.js",
+      "export { a, unused } from './a.js'; export { b as renamed } from './b.js'; \
+       export * from './c.js'; export * as ns from './d.js'; export * from './e.js';",
+    ),
+    ("a.js", "export const a = 1; export const unused = 2;"),
+    ("b.js", "export const b = 3;"),
+    ("c.js", "export const c = 4; export const other = 5;"),
+    ("d.js", "export const d = 6;"),
+    ("e.js", "export const e = 7;"),
+  ]);
+  assert_eq!(
+    shaken,
+    [
+      (
+        "main.js",
+        Some("import {a,renamed,c,ns}from\"./index.js\";use(a,renamed,c,ns)".into())
+      ),
+      (
+        "index.js",
+        Some(
+          "export{a}from\"./a.js\";export{b as renamed}from\"./b.js\";export*from\"./c.js\";\
+           export*as ns from\"./d.js\""
+            .into()
+        )
+      ),
+      ("a.js", Some("export const a=1".into())),
+      ("b.js", Some("export const b=3".into())),
+      ("c.js", Some("export const c=4".into())),
+      ("d.js", Some("export const d=6".into())),
+      ("e.js", None),
+    ]
+  );
+
+  // Imported bindings which are exported again.
+  assert_eq!(
+    shake(&[
+      ("main.js", "import { b } from './b.js'; use(b);"),
+      (
+        "b.js",
+        "import { a } from './a.js'; export { a as b }; export const c = a;"
+      ),
+      ("a.js", "export const a = 1;"),
+    ]),
+    [
+      ("main.js", Some("import {b}from\"./b.js\";use(b)".into())),
+      (
+        "b.js",
+        Some("import {a}from\"./a.js\";export{a as b}".into())
+      ),
+      ("a.js", Some("export const a=1".into())),
+    ]
+  );
+}
+
+#[test]
+fn default_exports() {
+  assert_eq!(
+    shake(&[
+      ("main.js", "import f, { g } from './lib.js'; f(); g();"),
+      (
+        "lib.js",
+        "export default function f() {} export function g() {}"
+      ),
+    ])[1],
+    (
+      "lib.js",
+      Some("export default function f(){}export function g(){}".into())
+    )
+  );
+  // Unused default exported expressions are kept for their side effects only.
+  assert_eq!(
+    shake(&[
+      ("main.js", "import './a.js'; import './b.js';"),
+      ("a.js", "export default create();"),
+      ("b.js", "export default 1 + 2;"),
+    ]),
+    [
+      ("main.js", Some("import\"./a.js\"".into())),
+      ("a.js", Some("create()".into())),
+      ("b.js", None),
+    ]
+  );
+}
+
+#[test]
+fn declaration_lists() {
+  assert_eq!(
+    shake(&[
+      ("main.js", "import { a, d } from './lib.js'; use(a, d);"),
+      (
+        "lib.js",
+        "export const a = 1, b = 2, c = f(), d = 3; let e = 4, g = 5;"
+      ),
+    ])[1],
+    (
+      "lib.js",
+      Some("export const a=1;const c=f();export const d=3".into())
+    )
+  );
+  assert_eq!(
+    shake_entry("var a = 1, b = f(), c = 2, d = g(); use(b, d);"),
+    "var b=f(),d=g();use(b,d)"
+  );
+}
+
+#[test]
+fn dynamic_imports_and_eval() {
+  assert_eq!(
+    shake(&[
+      (
+        "main.js",
+        "function load() { return import('./lazy.js'); } load();"
+      ),
+      ("lazy.js", "export const a = 1; export function b() {}"),
+      ("other.js", "export const c = 1;"),
+    ]),
+    [
+      (
+        "main.js",
+        Some("function load(){return import(\"./lazy.js\")}load()".into())
+      ),
+      (
+        "lazy.js",
+        Some("export const a=1;export function b(){}".into())
+      ),
+      ("other.js", None),
+    ]
+  );
+  // Dynamic imports in removed code do not keep modules.
+  assert_eq!(
+    shake(&[
+      ("main.js", "function load() { return import('./lazy.js'); }"),
+      ("lazy.js", "run();"),
+    ])[1],
+    ("lazy.js", None)
+  );
+
+  // A module calling `eval` keeps everything.
+  assert_eq!(
+    shake(&[
+      (
+        "main.js",
+        "import { a } from './lib.js'; const b = 1; function c() {} eval(code);"
+      ),
+      ("lib.js", "export const a = 1; export const unused = 2;"),
+    ]),
+    [
+      (
+        "main.js",
+        Some("import {a}from\"./lib.js\";const b=1;function c(){}eval(code)".into())
+      ),
+      ("lib.js", Some("export const a=1".into())),
+    ]
+  );
+}
+
+#[test]
+fn reasons() {
+  let shaken = shake_with(
+    &[
+      (
+        "main.js",
+        "import { add } from './math.js'; import './setup.js'; export const total = sum(); \
+         function sum() { return add(1, 2); }",
+      ),
+      (
+        "math.js",
+        "const base = 0; export function add(a, b) { return base + a + b; }",
+      ),
+      ("setup.js", "init();"),
+    ],
+    &Options::default(),
+  );
+  let reasons: Vec<_> = shaken
+    .modules()
+    .iter()
+    .map(|module| {
+      let reasons: Vec<_> = module
+        .kept()
+        .iter()
+        .map(|kept| (kept.statement(), kept.reason().clone()))
+        .collect();
+      (module.name(), reasons)
+    })
+    .collect();
+  assert_eq!(
+    reasons,
+    [
+      (
+        "main.js",
+        vec![
+          (0, Reason::Import { name: "add".into() }),
+          (
+            1,
+            Reason::Dependency {
+              module: "setup.js".into()
+            }
+          ),
+          (2, Reason::SideEffects),
+          (
+            3,
+            Reason::Referenced {
+              name: "sum".into(),
+              by: 2
+            }
+          ),
+        ]
+      ),
+      (
+        "math.js",
+        vec![
+          (
+            0,
+            Reason::Referenced {
+              name: "base".into(),
+              by: 1
+            }
+          ),
+          (
+            1,
+            Reason::Imported {
+              name: "add".into(),
+              by: "main.js".into()
+            }
+          ),
+        ]
+      ),
+      ("setup.js", vec![(0, Reason::SideEffects)]),
+    ]
+  );
+  assert_eq!(
+    shaken.to_string(),
+    "main.js\n  \
+       0: import \"./math.js\": imports `add`, which kept code uses\n  \
+       1: import \"./setup.js\": runs setup.js first\n  \
+       2: export const total: has side effects\n  \
+       3: function sum: declares `sum`, used by statement 2\n\
+     math.js\n  \
+       0: const base: declares `base`, used by statement 1\n  \
+       1: export function add: `add` is imported by main.js\n\
+     setup.js\n  \
+       0: expression: has side effects\n"
+  );
+
+  let shaken = shake_with(
+    &[("main.js", "export const a = 1;"), ("unused.js", "run();")],
+    &Options::default(),
+  );
+  assert_eq!(
+    shaken.to_string(),
+    "main.js\n  0: export const a: `a` is exported by the entry\nunused.js: removed\n"
+  );
+}
+
+#[test]
+fn resolution() {
+  let mut graph = ModuleGraph::new();
+  let main = graph.add_entry("src/main.js", parse(""));
+  let util = graph.add("src/util.js", parse(""));
+  let index = graph.add("src/lib/index.js", parse(""));
+  let module = graph.add("src/esm.mjs", parse(""));
+  let shared = graph.add("shared.js", parse(""));
+  let package = graph.add("package", parse(""));
+
+  assert_eq!(graph.resolve(main, "./util.js"), Some(util));
+  assert_eq!(graph.resolve(main, "./util"), Some(util));
+  assert_eq!(graph.resolve(main, "./lib"), Some(index));
+  assert_eq!(graph.resolve(main, "./esm"), Some(module));
+  assert_eq!(graph.resolve(index, "../util"), Some(util));
+  assert_eq!(graph.resolve(index, "./../../shared"), Some(shared));
+  assert_eq!(graph.resolve(main, "package"), Some(package));
+  assert_eq!(graph.resolve(main, "./package"), None);
+  assert_eq!(graph.resolve(main, "../../shared.js"), None);
+  assert_eq!(graph.resolve(shared, "./src/util.js"), Some(util));
+
+  // Adding a module again replaces it.
+  assert_eq!(graph.add("shared.js", parse("a;")), shared);
+  assert_eq!(graph.module(shared).body().items().len(), 1);
+  assert!(graph.is_entry(main) && !graph.is_entry(shared));
+  assert_eq!(graph.modules().count(), 6);
+}
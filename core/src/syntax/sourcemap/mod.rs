@@ -0,0 +1,682 @@
+//! Source maps, in the [version 3][spec] format.
+//!
+//! A source map links the positions of generated code to the positions of the source it was
+//! generated from. A [`SourceMapBuilder`] records the mappings while the code is written, and
+//! builds the [`SourceMap`], which can be written as JSON with [`SourceMap::to_json`] and read
+//! back with [`SourceMap::from_json`].
+//!
+//! When the code goes through several passes, each pass maps its output to its input. The map
+//! of a pass [composes](SourceMap::compose) with the map of the previous one, giving a map of
+//! the last output to the first input:
+//!
+//! ```
+//! use plastic::syntax::{
+//!   ast::{Position, Span},
+//!   sourcemap::SourceMapBuilder,
+//! };
+//!
+//! // `let answer = 42;` was transpiled to `var answer = 42;`...
+//! let mut builder = SourceMapBuilder::new().with_file("app.es5.js");
+//! let source = builder.add_source("app.js", Some("let answer = 42;"));
+//! let span = |start, end| Span::new(Position::new(1, start), Position::new(1, end));
+//! builder.add_span(span(5, 11), source, span(5, 11), Some("answer"));
+//! let transpiled = builder.build();
+//!
+//! // ... then minified to `var a=42;`.
+//! let mut builder = SourceMapBuilder::new().with_file("app.min.js");
+//! let source = builder.add_source("app.es5.js", None);
+//! builder.add_span(span(5, 6), source, span(5, 11), Some("answer"));
+//! let minified = builder.build();
+//!
+//! let composed = minified.compose(&transpiled);
+//! let original = composed.lookup(Position::new(1, 5)).unwrap();
+//! assert_eq!(original.source(), "app.js");
+//! assert_eq!(original.position(), Position::new(1, 5));
+//! assert_eq!(original.name(), Some("answer"));
+//! assert_eq!(original.source_content(), Some("let answer = 42;"));
+//! ```
+//!
+//! Positions start at line 1 and column 1, as everywhere in the syntax tree, and are written
+//! from 0 in the JSON. Columns are counted in the same units as the positions given to the
+//! builder.
+//!
+//! [spec]: https://tc39.es/source-map/
+
+#[cfg(test)]
+mod tests;
+pub(crate) mod tokens;
+mod vlq;
+
+use super::json::{self, Value};
+use crate::syntax::ast::{Position, Span};
+use std::{collections::HashMap, error::Error as StdError, fmt};
+
+/// A link from a position of the generated code to a position of a source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mapping {
+  generated: Position,
+  original: Option<Original>,
+}
+
+impl Mapping {
+  /// Gets the position in the generated code.
+  pub fn generated(&self) -> Position {
+    self.generated
+  }
+
+  /// Gets the position in the source, or `None` if the generated code from this position on
+  /// comes from no source.
+  pub fn original(&self) -> Option<&Original> {
+    self.original.as_ref()
+  }
+}
+
+/// The source position of a [`Mapping`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Original {
+  source: u32,
+  position: Position,
+  name: Option<u32>,
+}
+
+impl Original {
+  /// Gets the index of the source in [`SourceMap::sources`].
+  pub fn source(&self) -> u32 {
+    self.source
+  }
+
+  /// Gets the position in the source.
+  pub fn position(&self) -> Position {
+    self.position
+  }
+
+  /// Gets the index of the original name of the identifier in [`SourceMap::names`], if any.
+  pub fn name(&self) -> Option<u32> {
+    self.name
+  }
+}
+
+/// A source position found by [`SourceMap::lookup`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location<'a> {
+  source: &'a str,
+  source_content: Option<&'a str>,
+  position: Position,
+  name: Option<&'a str>,
+}
+
+impl<'a> Location<'a> {
+  /// Gets the name of the source.
+  pub fn source(&self) -> &'a str {
+    self.source
+  }
+
+  /// Gets the text of the source, if the map contains it.
+  pub fn source_content(&self) -> Option<&'a str> {
+    self.source_content
+  }
+
+  /// Gets the position in the source.
+  pub fn position(&self) -> Position {
+    self.position
+  }
+
+  /// Gets the original name of the identifier at this position, if any.
+  pub fn name(&self) -> Option<&'a str> {
+    self.name
+  }
+}
+
+/// Records mappings, to build a [`SourceMap`].
+///
+/// The mappings can be added in any order.
+#[derive(Debug, Clone, Default)]
+pub struct SourceMapBuilder {
+  file: Option<Box<str>>,
+  source_root: Option<Box<str>>,
+  sources: Vec<Box<str>>,
+  sources_content: Vec<Option<Box<str>>>,
+  names: Vec<Box<str>>,
+  name_indices: HashMap<Box<str>, u32>,
+  mappings: Vec<Mapping>,
+}
+
+impl SourceMapBuilder {
+  /// Creates an empty builder.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets the name of the generated file.
+  pub fn with_file<F>(mut self, file: F) -> Self
+  where
+    F: Into<Box<str>>,
+  {
+    self.file = Some(file.into());
+    self
+  }
+
+  /// Sets the path the source names are relative to.
+  pub fn with_source_root<R>(mut self, source_root: R) -> Self
+  where
+    R: Into<Box<str>>,
+  {
+    self.source_root = Some(source_root.into());
+    self
+  }
+
+  /// Adds a source, returning its index.
+  ///
+  /// Adding a source twice returns the same index, and keeps its text if it is not given the
+  /// second time.
+  pub fn add_source(&mut self, name: &str, content: Option<&str>) -> u32 {
+    let index = match self.sources.iter().position(|source| &**source == name) {
+      Some(index) => index,
+      None => {
+        self.sources.push(name.into());
+        self.sources_content.push(None);
+        self.sources.len() - 1
+      }
+    };
+    if let Some(content) = content {
+      self.sources_content[index] = Some(content.into());
+    }
+    index as u32
+  }
+
+  /// Adds an original name, returning its index.
+  pub fn add_name(&mut self, name: &str) -> u32 {
+    if let Some(&index) = self.name_indices.get(name) {
+      return index;
+    }
+    let index = self.names.len() as u32;
+    self.names.push(name.into());
+    self.name_indices.insert(name.into(), index);
+    index
+  }
+
+  /// Maps a position of the generated code to a position of a source, with the original name
+  /// of the identifier found there, if any.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the source was not added to this builder.
+  pub fn add_mapping(
+    &mut self,
+    generated: Position,
+    source: u32,
+    original: Position,
+    name: Option<&str>,
+  ) {
+    assert!(
+      (source as usize) < self.sources.len(),
+      "the source must be added to the builder"
+    );
+    let name = name.map(|name| self.add_name(name));
+    self.mappings.push(Mapping {
+      generated,
+      original: Some(Original {
+        source,
+        position: original,
+        name,
+      }),
+    });
+  }
+
+  /// Maps the span of generated code to the span of source code it was generated from.
+  ///
+  /// Both starts are mapped, with the original name if any, and so are both ends unless the
+  /// generated span is empty.
+  pub fn add_span(&mut self, generated: Span, source: u32, original: Span, name: Option<&str>) {
+    self.add_mapping(generated.start(), source, original.start(), name);
+    if generated.end() != generated.start() {
+      self.add_mapping(generated.end(), source, original.end(), None);
+    }
+  }
+
+  /// Marks the generated code from a position on as coming from no source.
+  pub fn add_unmapped(&mut self, generated: Position) {
+    self.mappings.push(Mapping {
+      generated,
+      original: None,
+    });
+  }
+
+  /// Builds the source map.
+  ///
+  /// The mappings are sorted by generated position, and where several map the same position,
+  /// the first one added is kept.
+  pub fn build(mut self) -> SourceMap {
+    self.mappings.sort_by_key(|mapping| mapping.generated);
+    self.mappings.dedup_by_key(|mapping| mapping.generated);
+    SourceMap {
+      file: self.file,
+      source_root: self.source_root,
+      sources: self.sources,
+      sources_content: self.sources_content,
+      names: self.names,
+      mappings: self.mappings,
+    }
+  }
+}
+
+/// A version 3 source map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceMap {
+  file: Option<Box<str>>,
+  source_root: Option<Box<str>>,
+  sources: Vec<Box<str>>,
+  sources_content: Vec<Option<Box<str>>>,
+  names: Vec<Box<str>>,
+  /// The mappings, sorted by generated position.
+  mappings: Vec<Mapping>,
+}
+
+impl SourceMap {
+  /// Gets the name of the generated file, if known.
+  pub fn file(&self) -> Option<&str> {
+    self.file.as_deref()
+  }
+
+  /// Gets the path the source names are relative to, if any.
+  pub fn source_root(&self) -> Option<&str> {
+    self.source_root.as_deref()
+  }
+
+  /// Gets the names of the sources.
+  pub fn sources(&self) -> &[Box<str>] {
+    &self.sources
+  }
+
+  /// Gets the text of a source, if the map contains it.
+  pub fn source_content(&self, source: u32) -> Option<&str> {
+    self
+      .sources_content
+      .get(source as usize)
+      .and_then(Option::as_deref)
+  }
+
+  /// Gets the original names of identifiers.
+  pub fn names(&self) -> &[Box<str>] {
+    &self.names
+  }
+
+  /// Gets the mappings, sorted by generated position.
+  pub fn mappings(&self) -> &[Mapping] {
+    &self.mappings
+  }
+
+  /// Finds the source position of a position of the generated code.
+  ///
+  /// This is the source position of the closest mapping at or before the position on the same
+  /// generated line.
+  pub fn lookup(&self, generated: Position) -> Option<Location<'_>> {
+    let index = self
+      .mappings
+      .partition_point(|mapping| mapping.generated <= generated);
+    let mapping = self.mappings[..index].last()?;
+    if mapping.generated.line_number() != generated.line_number() {
+      return None;
+    }
+    let original = mapping.original?;
+    Some(Location {
+      source: &self.sources[original.source as usize],
+      source_content: self.source_content(original.source),
+      position: original.position,
+      name: original.name.map(|name| &*self.names[name as usize]),
+    })
+  }
+
+  /// Composes this map with the map of the pass that generated its sources.
+  ///
+  /// The result maps the code generated by this pass to the sources of `input`. Positions that
+  /// `input` does not map become unmapped.
+  pub fn compose(&self, input: &SourceMap) -> SourceMap {
+    self.compose_sources(input, |_| true)
+  }
+
+  /// Composes this map with the map of the pass that generated one of its sources.
+  ///
+  /// The mappings to other sources are kept as they are. This is how the input maps of the
+  /// files of a bundle are composed with the map of the bundle.
+  pub fn compose_source(&self, source: &str, input: &SourceMap) -> SourceMap {
+    self.compose_sources(input, |name| name == source)
+  }
+
+  fn compose_sources<F>(&self, input: &SourceMap, composed: F) -> SourceMap
+  where
+    F: Fn(&str) -> bool,
+  {
+    let mut builder = SourceMapBuilder {
+      file: self.file.clone(),
+      source_root: self.source_root.clone(),
+      ..SourceMapBuilder::default()
+    };
+    for mapping in &self.mappings {
+      let original = match mapping.original {
+        Some(original) => original,
+        None => {
+          builder.add_unmapped(mapping.generated);
+          continue;
+        }
+      };
+      let source = &self.sources[original.source as usize];
+      let name = original.name.map(|name| &*self.names[name as usize]);
+      if !composed(source) {
+        let index = builder.add_source(source, self.source_content(original.source));
+        builder.add_mapping(mapping.generated, index, original.position, name);
+        continue;
+      }
+      match input.lookup(original.position) {
+        // The name in the input is older than the name in the generated code, which may have
+        // been given by the input's pass.
+        Some(location) => {
+          let index = builder.add_source(location.source, location.source_content);
+          builder.add_mapping(
+            mapping.generated,
+            index,
+            location.position,
+            location.name.or(name),
+          );
+        }
+        None => builder.add_unmapped(mapping.generated),
+      }
+    }
+    builder.build()
+  }
+
+  /// Writes the map as JSON.
+  pub fn to_json(&self) -> String {
+    let mut out = String::from("{\"version\":3");
+    if let Some(file) = &self.file {
+      out.push_str(",\"file\":");
+      json::write_string(file, &mut out);
+    }
+    if let Some(source_root) = &self.source_root {
+      out.push_str(",\"sourceRoot\":");
+      json::write_string(source_root, &mut out);
+    }
+    out.push_str(",\"sources\":[");
+    for (index, source) in self.sources.iter().enumerate() {
+      if index > 0 {
+        out.push(',');
+      }
+      json::write_string(source, &mut out);
+    }
+    out.push(']');
+    if self.sources_content.iter().any(Option::is_some) {
+      out.push_str(",\"sourcesContent\":[");
+      for (index, content) in self.sources_content.iter().enumerate() {
+        if index > 0 {
+          out.push(',');
+        }
+        match content {
+          Some(content) => json::write_string(content, &mut out),
+          None => out.push_str("null"),
+        }
+      }
+      out.push(']');
+    }
+    out.push_str(",\"names\":[");
+    for (index, name) in self.names.iter().enumerate() {
+      if index > 0 {
+        out.push(',');
+      }
+      json::write_string(name, &mut out);
+    }
+    out.push_str("],\"mappings\":\"");
+    self.write_mappings(&mut out);
+    out.push_str("\"}");
+    out
+  }
+
+  /// Writes the `mappings` field: lines separated by `;`, made of segments separated by `,`.
+  ///
+  /// Each segment holds the generated column, and if mapped, the source index, the source line
+  /// and column and the name index. They are all relative to the previous segment, except that
+  /// the generated column starts again from 0 on each line.
+  fn write_mappings(&self, out: &mut String) {
+    let mut line = 1;
+    let mut column = 0;
+    let (mut source, mut original_line, mut original_column, mut name) = (0, 0, 0, 0);
+    let mut first = true;
+    for mapping in &self.mappings {
+      let generated = mapping.generated;
+      if generated.line_number() != line {
+        for _ in line..generated.line_number() {
+          out.push(';');
+        }
+        line = generated.line_number();
+        column = 0;
+      } else if !first {
+        out.push(',');
+      }
+      first = false;
+      let generated_column = i64::from(generated.column_number() - 1);
+      vlq::encode(generated_column - column, out);
+      column = generated_column;
+      if let Some(original) = mapping.original {
+        let position = original.position;
+        vlq::encode(i64::from(original.source) - source, out);
+        source = original.source.into();
+        vlq::encode(i64::from(position.line_number() - 1) - original_line, out);
+        original_line = i64::from(position.line_number() - 1);
+        vlq::encode(
+          i64::from(position.column_number() - 1) - original_column,
+          out,
+        );
+        original_column = i64::from(position.column_number() - 1);
+        if let Some(index) = original.name {
+          vlq::encode(i64::from(index) - name, out);
+          name = index.into();
+        }
+      }
+    }
+  }
+
+  /// Reads a map from JSON.
+  ///
+  /// Index maps, made of `sections`, are not supported.
+  pub fn from_json(text: &str) -> Result<Self, SourceMapError> {
+    let value =
+      json::parse(text).map_err(|(message, offset)| SourceMapError::Json { message, offset })?;
+    if !matches!(value, Value::Object(_)) {
+      return Err(SourceMapError::field("", "an object"));
+    }
+    match value.get("version") {
+      Some(Value::Number(version)) if *version == 3.0 => {}
+      Some(Value::Number(version)) => return Err(SourceMapError::Version(*version)),
+      _ => return Err(SourceMapError::field("version", "a number")),
+    }
+    if value.get("sections").is_some() {
+      return Err(SourceMapError::Sections);
+    }
+
+    let file = optional_string(&value, "file")?;
+    let source_root = optional_string(&value, "sourceRoot")?;
+    let sources = strings(&value, "sources")?
+      .into_iter()
+      .map(|source| source.unwrap_or_default())
+      .collect::<Vec<_>>();
+    let mut sources_content = match value.get("sourcesContent") {
+      None | Some(Value::Null) => Vec::new(),
+      Some(_) => strings(&value, "sourcesContent")?,
+    };
+    sources_content.resize(sources.len(), None);
+    let names = match value.get("names") {
+      None => Vec::new(),
+      Some(_) => strings(&value, "names")?
+        .into_iter()
+        .map(|name| name.unwrap_or_default())
+        .collect(),
+    };
+    let mappings = match value.get("mappings") {
+      Some(Value::String(mappings)) => read_mappings(mappings, sources.len(), names.len())?,
+      _ => return Err(SourceMapError::field("mappings", "a string")),
+    };
+
+    let mut map = Self {
+      file,
+      source_root,
+      sources,
+      sources_content,
+      names,
+      mappings,
+    };
+    map.mappings.sort_by_key(|mapping| mapping.generated);
+    Ok(map)
+  }
+}
+
+/// Reads an optional string field.
+fn optional_string(value: &Value, field: &'static str) -> Result<Option<Box<str>>, SourceMapError> {
+  match value.get(field) {
+    None | Some(Value::Null) => Ok(None),
+    Some(Value::String(string)) => Ok(Some(string.as_str().into())),
+    Some(_) => Err(SourceMapError::field(field, "a string")),
+  }
+}
+
+/// Reads an array of strings or `null`s.
+fn strings(value: &Value, field: &'static str) -> Result<Vec<Option<Box<str>>>, SourceMapError> {
+  match value.get(field) {
+    Some(Value::Array(elements)) => elements
+      .iter()
+      .map(|element| match element {
+        Value::String(string) => Ok(Some(string.as_str().into())),
+        Value::Null => Ok(None),
+        _ => Err(SourceMapError::field(field, "an array of strings")),
+      })
+      .collect(),
+    _ => Err(SourceMapError::field(field, "an array of strings")),
+  }
+}
+
+/// Reads the `mappings` field.
+fn read_mappings(text: &str, sources: usize, names: usize) -> Result<Vec<Mapping>, SourceMapError> {
+  let bytes = text.as_bytes();
+  let error = |message, offset| SourceMapError::Mappings { message, offset };
+  let mut mappings = Vec::new();
+  let mut line = 1;
+  let mut column = 0;
+  let (mut source, mut original_line, mut original_column, mut name) = (0, 0, 0, 0);
+  let mut offset = 0;
+  while offset < bytes.len() {
+    match bytes[offset] {
+      b';' => {
+        line += 1;
+        column = 0;
+        offset += 1;
+        continue;
+      }
+      b',' => {
+        offset += 1;
+        continue;
+      }
+      _ => {}
+    }
+    let start = offset;
+    let mut fields = Vec::with_capacity(5);
+    while offset < bytes.len() && bytes[offset] != b',' && bytes[offset] != b';' {
+      let (value, read) =
+        vlq::decode(&bytes[offset..]).map_err(|message| error(message, offset))?;
+      fields.push(value);
+      offset += read;
+    }
+    column += fields[0];
+    let generated = position(line, column).ok_or_else(|| error("negative column", start))?;
+    let original = match fields.len() {
+      1 => None,
+      4 | 5 => {
+        source += fields[1];
+        original_line += fields[2];
+        original_column += fields[3];
+        if source < 0 || source as usize >= sources {
+          return Err(error("source index out of range", start));
+        }
+        let position = position(original_line + 1, original_column)
+          .ok_or_else(|| error("negative source position", start))?;
+        let name = if fields.len() == 5 {
+          name += fields[4];
+          if name < 0 || name as usize >= names {
+            return Err(error("name index out of range", start));
+          }
+          Some(name as u32)
+        } else {
+          None
+        };
+        Some(Original {
+          source: source as u32,
+          position,
+          name,
+        })
+      }
+      _ => return Err(error("a segment has 1, 4 or 5 fields", start)),
+    };
+    mappings.push(Mapping {
+      generated,
+      original,
+    });
+  }
+  Ok(mappings)
+}
+
+/// Converts a line starting from 1 and a column starting from 0 to a position.
+fn position(line: i64, column: i64) -> Option<Position> {
+  if line < 1 || column < 0 || line >= i64::from(u32::MAX) || column >= i64::from(u32::MAX) {
+    return None;
+  }
+  Some(Position::new(line as u32, column as u32 + 1))
+}
+
+/// An error reading a source map.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SourceMapError {
+  /// The text is not valid JSON.
+  Json {
+    message: &'static str,
+    /// The byte offset of the error in the text.
+    offset: usize,
+  },
+  /// A field is missing or has the wrong type.
+  Field {
+    field: &'static str,
+    expected: &'static str,
+  },
+  /// The map is of another version than 3.
+  Version(f64),
+  /// The map is an index map, made of sections.
+  Sections,
+  /// The `mappings` field is malformed.
+  Mappings {
+    message: &'static str,
+    /// The byte offset of the error in the field.
+    offset: usize,
+  },
+}
+
+impl SourceMapError {
+  fn field(field: &'static str, expected: &'static str) -> Self {
+    Self::Field { field, expected }
+  }
+}
+
+impl fmt::Display for SourceMapError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Json { message, offset } => write!(f, "invalid JSON at byte {}: {}", offset, message),
+      Self::Field {
+        field: "",
+        expected,
+      } => write!(f, "a source map must be {}", expected),
+      Self::Field { field, expected } => write!(f, "the `{}` field must be {}", field, expected),
+      Self::Version(version) => write!(f, "unsupported source map version {}", version),
+      Self::Sections => f.write_str("index maps with sections are not supported"),
+      Self::Mappings { message, offset } => {
+        write!(f, "invalid mappings at byte {}: {}", offset, message)
+      }
+    }
+  }
+}
+
+impl StdError for SourceMapError {}
@@ -0,0 +1,318 @@
+//! Tests for source maps.
+
+use super::{vlq, SourceMap, SourceMapBuilder, SourceMapError};
+use crate::syntax::ast::{Position, Span};
+
+fn position(line: u32, column: u32) -> Position {
+  Position::new(line, column)
+}
+
+fn span(line: u32, start: u32, end: u32) -> Span {
+  Span::new(position(line, start), position(line, end))
+}
+
+#[test]
+fn vlq_round_trip() {
+  let cases: &[(i64, &str)] = &[
+    (0, "A"),
+    (1, "C"),
+    (-1, "D"),
+    (15, "e"),
+    (16, "gB"),
+    (-16, "hB"),
+    (123, "2H"),
+    (1_000_000, "gkh9B"),
+  ];
+  for &(value, encoded) in cases {
+    let mut out = String::new();
+    vlq::encode(value, &mut out);
+    assert_eq!(out, encoded, "encoding {}", value);
+    assert_eq!(
+      vlq::decode(encoded.as_bytes()),
+      Ok((value, encoded.len())),
+      "decoding {}",
+      encoded
+    );
+  }
+  assert_eq!(vlq::decode(b"gB,A"), Ok((16, 2)));
+  assert!(vlq::decode(b"g").is_err());
+  assert!(vlq::decode(b"!").is_err());
+  assert!(vlq::decode(b"gggggggggggggA").is_err());
+}
+
+#[test]
+fn json_output() {
+  let mut builder = SourceMapBuilder::new().with_file("out.js");
+  let first = builder.add_source("a.js", Some("let first = 1;\n"));
+  let second = builder.add_source("b.js", None);
+  builder.add_mapping(position(1, 1), first, position(1, 1), None);
+  builder.add_mapping(position(1, 5), first, position(1, 5), Some("first"));
+  builder.add_mapping(position(3, 3), second, position(10, 1), Some("second"));
+  builder.add_unmapped(position(3, 9));
+  let map = builder.build();
+  assert_eq!(
+    map.to_json(),
+    "{\"version\":3,\"file\":\"out.js\",\"sources\":[\"a.js\",\"b.js\"],\
+     \"sourcesContent\":[\"let first = 1;\\n\",null],\"names\":[\"first\",\"second\"],\
+     \"mappings\":\"AAAA,IAAIA;;ECSJC,M\"}"
+  );
+
+  let empty = SourceMapBuilder::new().build();
+  assert_eq!(
+    empty.to_json(),
+    "{\"version\":3,\"sources\":[],\"names\":[],\"mappings\":\"\"}"
+  );
+}
+
+#[test]
+fn builder() {
+  let mut builder = SourceMapBuilder::new().with_source_root("src/");
+  let source = builder.add_source("a.js", None);
+  assert_eq!(builder.add_source("b.js", None), 1);
+  assert_eq!(builder.add_source("a.js", Some("a")), source);
+  assert_eq!(builder.add_name("x"), 0);
+  assert_eq!(builder.add_name("y"), 1);
+  assert_eq!(builder.add_name("x"), 0);
+
+  // Mappings are sorted, and the first one added for a position wins.
+  builder.add_span(span(2, 1, 4), source, span(5, 3, 6), Some("z"));
+  builder.add_mapping(position(1, 1), source, position(1, 1), None);
+  builder.add_mapping(position(2, 1), source, position(9, 9), None);
+  builder.add_span(span(2, 7, 7), source, span(6, 1, 2), None);
+  let map = builder.build();
+
+  assert_eq!(map.source_root(), Some("src/"));
+  assert_eq!(map.source_content(source), Some("a"));
+  assert_eq!(map.source_content(1), None);
+  assert_eq!(&*map.names()[2], "z");
+  let generated: Vec<_> = map.mappings().iter().map(|m| m.generated()).collect();
+  assert_eq!(
+    generated,
+    [
+      position(1, 1),
+      position(2, 1),
+      position(2, 4),
+      position(2, 7)
+    ]
+  );
+  let original = map.mappings()[1].original().unwrap();
+  assert_eq!(original.position(), position(5, 3));
+  assert_eq!(original.name(), Some(2));
+  assert_eq!(
+    map.mappings()[2].original().unwrap().position(),
+    position(5, 6)
+  );
+}
+
+#[test]
+fn lookup() {
+  let mut builder = SourceMapBuilder::new();
+  let source = builder.add_source("a.js", Some("source"));
+  builder.add_mapping(position(1, 5), source, position(3, 1), Some("name"));
+  builder.add_mapping(position(1, 10), source, position(3, 8), None);
+  builder.add_unmapped(position(1, 20));
+  builder.add_mapping(position(3, 1), source, position(4, 2), None);
+  let map = builder.build();
+
+  assert_eq!(map.lookup(position(1, 1)), None);
+  let location = map.lookup(position(1, 5)).unwrap();
+  assert_eq!(location.source(), "a.js");
+  assert_eq!(location.source_content(), Some("source"));
+  assert_eq!(location.position(), position(3, 1));
+  assert_eq!(location.name(), Some("name"));
+  assert_eq!(
+    map.lookup(position(1, 9)).unwrap().position(),
+    position(3, 1)
+  );
+  let location = map.lookup(position(1, 15)).unwrap();
+  assert_eq!(location.position(), position(3, 8));
+  assert_eq!(location.name(), None);
+  assert_eq!(map.lookup(position(1, 25)), None);
+  // Mappings do not carry over to the next lines.
+  assert_eq!(map.lookup(position(2, 1)), None);
+  assert_eq!(
+    map.lookup(position(3, 100)).unwrap().position(),
+    position(4, 2)
+  );
+  assert_eq!(map.lookup(position(4, 1)), None);
+}
+
+#[test]
+fn json_round_trip() {
+  let mut builder = SourceMapBuilder::new().with_file("bundle \"min\".js");
+  let first = builder.add_source("src/a.js", Some("const \u{1F600} = '\\u2028';\n\t"));
+  let second = builder.add_source("src/b.js", None);
+  for line in 1..20 {
+    builder.add_mapping(
+      position(line, line * 3),
+      if line % 2 == 0 { first } else { second },
+      position(100 - line, 50 - line),
+      if line % 3 == 0 { Some("three") } else { None },
+    );
+    if line % 4 == 0 {
+      builder.add_unmapped(position(line, 200));
+    }
+  }
+  let map = builder.build();
+  let json = map.to_json();
+  assert_eq!(SourceMap::from_json(&json), Ok(map));
+}
+
+#[test]
+fn json_input() {
+  let map = SourceMap::from_json(
+    r#"{
+      "version": 3,
+      "file": "out.js",
+      "sourceRoot": "",
+      "sources": ["foo.js", "bar.js"],
+      "sourcesContent": [null, "bar \u00e9\ud83d\ude00"],
+      "names": ["src", "maps", "are", "fun"],
+      "mappings": "A,AAAA;;ACCCC;"
+    }"#,
+  )
+  .unwrap();
+  assert_eq!(map.file(), Some("out.js"));
+  assert_eq!(map.source_root(), Some(""));
+  assert_eq!(map.sources().len(), 2);
+  assert_eq!(map.source_content(0), None);
+  assert_eq!(map.source_content(1), Some("bar \u{e9}\u{1F600}"));
+  assert_eq!(map.names().len(), 4);
+  assert_eq!(map.mappings().len(), 3);
+  assert_eq!(map.mappings()[0].original(), None);
+  let location = map.lookup(position(1, 1)).unwrap();
+  assert_eq!(location.source(), "foo.js");
+  assert_eq!(location.position(), position(1, 1));
+  assert_eq!(location.name(), None);
+  let location = map.lookup(position(3, 1)).unwrap();
+  assert_eq!(location.source(), "bar.js");
+  assert_eq!(location.position(), position(2, 2));
+  assert_eq!(location.name(), Some("maps"));
+
+  // Missing optional fields.
+  let map = SourceMap::from_json(r#"{"version":3,"sources":["a.js"],"mappings":"AAAA"}"#).unwrap();
+  assert_eq!(map.file(), None);
+  assert_eq!(map.source_content(0), None);
+  assert!(map.names().is_empty());
+}
+
+#[test]
+fn json_errors() {
+  let error = |json: &str| SourceMap::from_json(json).unwrap_err();
+  assert_eq!(
+    error("{\"version\":3,"),
+    SourceMapError::Json {
+      message: "expected a member name",
+      offset: 13
+    }
+  );
+  assert_eq!(
+    error("[]"),
+    SourceMapError::Field {
+      field: "",
+      expected: "an object"
+    }
+  );
+  assert_eq!(
+    error(r#"{"version":2,"sources":[],"mappings":""}"#),
+    SourceMapError::Version(2.0)
+  );
+  assert_eq!(
+    error(r#"{"version":3,"sections":[]}"#),
+    SourceMapError::Sections
+  );
+  assert_eq!(
+    error(r#"{"version":3,"sources":"a.js","mappings":""}"#),
+    SourceMapError::Field {
+      field: "sources",
+      expected: "an array of strings"
+    }
+  );
+  assert_eq!(
+    error(r#"{"version":3,"sources":[]}"#),
+    SourceMapError::Field {
+      field: "mappings",
+      expected: "a string"
+    }
+  );
+  assert_eq!(
+    error(r#"{"version":3,"sources":["a.js"],"mappings":"AAAA,AA"}"#),
+    SourceMapError::Mappings {
+      message: "a segment has 1, 4 or 5 fields",
+      offset: 5
+    }
+  );
+  assert_eq!(
+    error(r#"{"version":3,"sources":["a.js"],"mappings":"ACAA"}"#),
+    SourceMapError::Mappings {
+      message: "source index out of range",
+      offset: 0
+    }
+  );
+  assert_eq!(
+    error(r#"{"version":3,"sources":["a.js"],"mappings":"AAAA,D"}"#),
+    SourceMapError::Mappings {
+      message: "negative column",
+      offset: 5
+    }
+  );
+  assert_eq!(
+    error(r#"{"version":3,"sources":["a.js"],"mappings":"AA*A"}"#).to_string(),
+    "invalid mappings at byte 2: invalid base 64 digit"
+  );
+}
+
+#[test]
+fn compose() {
+  // `a.ts` was compiled to `a.js`, where `x` is on line 2...
+  let mut builder = SourceMapBuilder::new().with_file("a.js");
+  let source = builder.add_source("a.ts", Some("let x: number = 1;"));
+  builder.add_span(span(2, 5, 6), source, span(1, 5, 6), Some("x"));
+  builder.add_mapping(position(2, 9), source, position(1, 17), None);
+  let compiled = builder.build();
+
+  // ... then `a.js` and `b.js` were bundled, with `a.js` starting on line 11...
+  let mut builder = SourceMapBuilder::new().with_file("bundle.js");
+  let a = builder.add_source("a.js", None);
+  let b = builder.add_source("b.js", Some("b()"));
+  builder.add_mapping(position(1, 1), b, position(1, 1), None);
+  builder.add_span(span(12, 5, 6), a, span(2, 5, 6), Some("x"));
+  builder.add_mapping(position(12, 9), a, position(2, 9), None);
+  builder.add_mapping(position(13, 1), a, position(3, 1), None);
+  let bundled = builder.build();
+
+  // ... then minified, with `x` renamed to `a`.
+  let mut builder = SourceMapBuilder::new().with_file("bundle.min.js");
+  let bundle = builder.add_source("bundle.js", None);
+  builder.add_mapping(position(1, 1), bundle, position(1, 1), None);
+  builder.add_mapping(position(1, 10), bundle, position(12, 5), Some("x"));
+  builder.add_mapping(position(1, 12), bundle, position(12, 9), None);
+  builder.add_mapping(position(1, 14), bundle, position(13, 1), None);
+  builder.add_mapping(position(1, 16), bundle, position(14, 1), None);
+  let minified = builder.build();
+
+  let composed = minified.compose(&bundled.compose_source("a.js", &compiled));
+  assert_eq!(composed.file(), Some("bundle.min.js"));
+  assert_eq!(composed.sources(), ["b.js".into(), "a.ts".into()]);
+
+  let location = composed.lookup(position(1, 1)).unwrap();
+  assert_eq!(location.source(), "b.js");
+  assert_eq!(location.source_content(), Some("b()"));
+  let location = composed.lookup(position(1, 10)).unwrap();
+  assert_eq!(location.source(), "a.ts");
+  assert_eq!(location.position(), position(1, 5));
+  assert_eq!(location.name(), Some("x"));
+  assert_eq!(location.source_content(), Some("let x: number = 1;"));
+  assert_eq!(
+    composed.lookup(position(1, 12)).unwrap().position(),
+    position(1, 17)
+  );
+  // Line 3 of `a.js` has no mapping to `a.ts`, and line 14 of the bundle none at all.
+  assert_eq!(composed.lookup(position(1, 14)), None);
+  assert_eq!(composed.lookup(position(1, 16)), None);
+
+  // Composition through the JSON forms gives the same map.
+  let from_json = |map: &SourceMap| SourceMap::from_json(&map.to_json()).unwrap();
+  let bundled = from_json(&bundled).compose_source("a.js", &from_json(&compiled));
+  assert_eq!(from_json(&minified).compose(&bundled), composed);
+}
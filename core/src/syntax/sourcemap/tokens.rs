@@ -0,0 +1,89 @@
+//! Mapping printed code back to its source by its tokens.
+//!
+//! The printer does not record where it writes the nodes, so the map of code printed from a
+//! rewritten syntax tree is rebuilt from the tokens: the names and the literals of the output
+//! are matched in order with the ones of the source. Code which is not in the source matches
+//! nothing and is left unmapped.
+
+use crate::syntax::lexer::{
+  token::{Token, TokenKind},
+  Lexer,
+};
+
+/// How far around the last match in the source a name of the output is looked for. Rewrites
+/// move code around a little, within a statement or a function.
+const WINDOW: usize = 64;
+
+/// How far around the last match in the source a literal is looked for. Literals such as `0`
+/// are also created by rewrites, and should not skip the source code in between.
+const LITERAL_WINDOW: usize = 8;
+
+/// The tokens of a source, matched in order with the tokens of the code printed from it.
+#[derive(Debug)]
+pub(crate) struct TokenMatcher {
+  original: Vec<Token>,
+  used: Vec<bool>,
+  cursor: usize,
+}
+
+impl TokenMatcher {
+  /// Creates a matcher for the tokens of a source.
+  pub(crate) fn new(source: &str) -> Self {
+    Self::from_tokens(tokens(source))
+  }
+
+  /// Creates a matcher for some of the tokens of a source, the ones the printed code can come
+  /// from.
+  pub(crate) fn from_tokens(original: Vec<Token>) -> Self {
+    Self {
+      used: vec![false; original.len()],
+      original,
+      cursor: 0,
+    }
+  }
+
+  /// Finds the token of the source a token of the output comes from.
+  ///
+  /// Tokens are matched in order, except for the ones moved after the tokens that followed
+  /// them, which are found behind the cursor.
+  pub(crate) fn find(&mut self, kind: &TokenKind) -> Option<&Token> {
+    let window = match kind {
+      TokenKind::Identifier(_) => WINDOW,
+      _ => LITERAL_WINDOW,
+    };
+    let start = self.cursor.saturating_sub(window);
+    let end = self.original.len().min(self.cursor + window);
+    let index =
+      (start..end).find(|&index| !self.used[index] && self.original[index].kind() == kind)?;
+    self.used[index] = true;
+    self.cursor = self.cursor.max(index + 1);
+    Some(&self.original[index])
+  }
+}
+
+/// Lexes the names and the literals of some code, stopping at the first error.
+pub(crate) fn tokens(code: &str) -> Vec<Token> {
+  let mut lexer = Lexer::new(code.as_bytes());
+  let mut tokens = Vec::new();
+  while let Ok(Some(token)) = lexer.next() {
+    match token.kind() {
+      TokenKind::EOF => break,
+      kind if is_mapped(kind) => tokens.push(token),
+      _ => {}
+    }
+  }
+  tokens
+}
+
+/// Returns `true` for the kinds of tokens which are mapped: names and literals.
+pub(crate) fn is_mapped(kind: &TokenKind) -> bool {
+  matches!(
+    kind,
+    TokenKind::Identifier(_)
+      | TokenKind::BooleanLiteral(_)
+      | TokenKind::NullLiteral
+      | TokenKind::NumericLiteral(_)
+      | TokenKind::StringLiteral(_)
+      | TokenKind::TemplateLiteral(_)
+  )
+}
@@ -0,0 +1,67 @@
+//! Base 64 variable length quantities, the encoding of the numbers of the `mappings` field.
+
+/// The digits of the base 64 encoding.
+const DIGITS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// The bit set on the digits followed by more digits of the same number.
+const CONTINUATION: u32 = 0b10_0000;
+
+/// Appends a number to a string.
+///
+/// The sign is stored in the lowest bit, and the magnitude in groups of five bits, the lowest
+/// group first.
+pub(super) fn encode(value: i64, out: &mut String) {
+  let mut rest = if value < 0 {
+    ((-value) as u64) << 1 | 1
+  } else {
+    (value as u64) << 1
+  };
+  loop {
+    let mut digit = (rest & 0b1_1111) as u32;
+    rest >>= 5;
+    if rest > 0 {
+      digit |= CONTINUATION;
+    }
+    out.push(DIGITS[digit as usize].into());
+    if rest == 0 {
+      break;
+    }
+  }
+}
+
+/// Reads a number from the start of a string, returning it with the number of bytes read.
+pub(super) fn decode(text: &[u8]) -> Result<(i64, usize), &'static str> {
+  let mut value: u64 = 0;
+  let mut shift = 0;
+  for (index, &byte) in text.iter().enumerate() {
+    let digit = digit(byte).ok_or("invalid base 64 digit")?;
+    if shift > 60 {
+      return Err("number too large");
+    }
+    value |= u64::from(digit & !CONTINUATION) << shift;
+    shift += 5;
+    if digit & CONTINUATION == 0 {
+      let magnitude = (value >> 1) as i64;
+      let value = if value & 1 == 1 {
+        -magnitude
+      } else {
+        magnitude
+      };
+      return Ok((value, index + 1));
+    }
+  }
+  Err("unterminated number")
+}
+
+/// Gets the value of a base 64 digit.
+fn digit(byte: u8) -> Option<u32> {
+  let value = match byte {
+    b'A'..=b'Z' => byte - b'A',
+    b'a'..=b'z' => byte - b'a' + 26,
+    b'0'..=b'9' => byte - b'0' + 52,
+    b'+' => 62,
+    b'/' => 63,
+    _ => return None,
+  };
+  Some(value.into())
+}
@@ -0,0 +1,176 @@
+//! Arrow functions.
+//!
+//! An arrow function becomes a function expression. Its `this` and `arguments` are the ones of
+//! the enclosing function, which keeps them in variables: `function f() { return () => this; }`
+//! becomes `function f() { var _this = this; return function () { return _this; }; }`.
+
+use super::{util, Context};
+use crate::syntax::ast::{
+  node::{
+    ArrowFunctionDecl, ClassElement, FunctionDecl, FunctionExpr, FunctionKind, StatementList,
+  },
+  visitor::{visit_mut, Flow, KindPath, Path, VisitorMut},
+  Node,
+};
+
+/// Lowers the arrow functions of a program.
+pub(super) fn run(body: &mut StatementList, cx: &mut Context) {
+  let mut lowering = Lowering {
+    cx,
+    frames: vec![Frame::new(false)],
+  };
+  let _ = lowering.visit_statements(body.items_mut(), &mut Path::new());
+  // `arguments` is not bound outside of functions, arrows referring to it are left as they are.
+  let frame = lowering.frames.pop().expect("the program frame");
+  util::prepend(body, frame.declarations());
+}
+
+/// The variables holding the `this` and `arguments` of a function for its arrow functions.
+struct Frame {
+  this: Option<Box<str>>,
+  arguments: Option<Box<str>>,
+  /// Whether the frame is a class field or static block, whose `this` can not be kept in a
+  /// variable.
+  barrier: bool,
+}
+
+impl Frame {
+  fn new(barrier: bool) -> Self {
+    Self {
+      this: None,
+      arguments: None,
+      barrier,
+    }
+  }
+
+  /// Declares the variables used by the arrow functions of the frame.
+  fn declarations(self) -> Vec<Node> {
+    let mut declarations = Vec::new();
+    if let Some(this) = self.this {
+      declarations.push((this, Some(Node::this())));
+    }
+    if let Some(arguments) = self.arguments {
+      declarations.push((arguments, Some(util::ident("arguments"))));
+    }
+    if declarations.is_empty() {
+      Vec::new()
+    } else {
+      vec![util::var(declarations)]
+    }
+  }
+}
+
+/// Rewrites the arrow functions from the leaves up.
+struct Lowering<'cx> {
+  cx: &'cx mut Context,
+  frames: Vec<Frame>,
+}
+
+impl Lowering<'_> {
+  /// Lowers an arrow function, or returns `None` if it has to be kept.
+  fn arrow(&mut self, arrow: &mut ArrowFunctionDecl) -> Option<Node> {
+    let params = arrow.params();
+    let body = arrow.body();
+    if util::uses_super(params, body) {
+      return None;
+    }
+    let uses_this = util::contains(params, body, |node| matches!(node, Node::This(_)));
+    let uses_arguments = util::contains(
+      params,
+      body,
+      |node| matches!(node, Node::Identifier(ident) if ident.name() == "arguments"),
+    );
+    let program = self.frames.len() == 1;
+    let frame = self.frames.last_mut().expect("a frame");
+    if (uses_this || uses_arguments) && frame.barrier {
+      return None;
+    }
+    let this = if uses_this {
+      let cx = &mut *self.cx;
+      Some(frame.this.get_or_insert_with(|| cx.fresh("this")).clone())
+    } else {
+      None
+    };
+    let arguments = if uses_arguments && !program {
+      let cx = &mut *self.cx;
+      Some(
+        frame
+          .arguments
+          .get_or_insert_with(|| cx.fresh("arguments"))
+          .clone(),
+      )
+    } else {
+      None
+    };
+
+    for param in arrow.params_mut() {
+      if let Some(init) = param.init_mut() {
+        util::rebind_node(init, this.as_deref(), arguments.as_deref());
+      }
+    }
+    util::rebind(arrow.body_mut(), this.as_deref(), arguments.as_deref());
+    let kind = if arrow.is_async() {
+      FunctionKind::Async
+    } else {
+      FunctionKind::Ordinary
+    };
+    let body = util::take_list(arrow.body_list_mut());
+    Some(
+      FunctionExpr::new(None, arrow.params().to_vec(), body)
+        .with_kind(kind)
+        .into(),
+    )
+  }
+
+  /// Visits the body of a function in a frame of its own.
+  fn frame<F>(&mut self, barrier: bool, visit: F) -> (Flow, Vec<Node>)
+  where
+    F: FnOnce(&mut Self) -> Flow,
+  {
+    self.frames.push(Frame::new(barrier));
+    let flow = visit(self);
+    let frame = self.frames.pop().expect("the frame was pushed");
+    (flow, frame.declarations())
+  }
+}
+
+impl VisitorMut for Lowering<'_> {
+  fn visit_node(&mut self, node: &mut Node, path: &mut KindPath) -> Flow {
+    visit_mut::walk_node(self, node, path)?;
+    if let Node::ArrowFunctionDecl(arrow) = node {
+      if let Some(function) = self.arrow(arrow) {
+        *node = function;
+      }
+    }
+    Flow::Continue(())
+  }
+
+  fn visit_function_decl(&mut self, decl: &mut FunctionDecl, path: &mut KindPath) -> Flow {
+    let (flow, declarations) = self.frame(false, |this| {
+      visit_mut::walk_function_decl(this, decl, path)
+    });
+    util::prepend(decl.body_list_mut(), declarations);
+    flow
+  }
+
+  fn visit_function_expr(&mut self, expr: &mut FunctionExpr, path: &mut KindPath) -> Flow {
+    let (flow, declarations) = self.frame(false, |this| {
+      visit_mut::walk_function_expr(this, expr, path)
+    });
+    util::prepend(expr.body_list_mut(), declarations);
+    flow
+  }
+
+  fn visit_class_element(&mut self, element: &mut ClassElement, path: &mut KindPath) -> Flow {
+    match element {
+      ClassElement::Method { .. } => visit_mut::walk_class_element(self, element, path),
+      _ => {
+        self
+          .frame(true, |this| {
+            visit_mut::walk_class_element(this, element, path)
+          })
+          .0
+      }
+    }
+  }
+}
@@ -0,0 +1,683 @@
+//! `let` and `const` declarations.
+//!
+//! The declarations become `var` declarations, renamed when the name would clash with another
+//! binding of the function once hoisted to it: `let a = 1; { let a = 2; }` becomes `var a = 1;
+//! { var _a = 2; }`.
+//!
+//! A loop whose `let` and `const` bindings are captured by closures runs its body in a function
+//! called each iteration, so that each closure sees the binding of its iteration:
+//!
+//! ```text
+//! for (let i = 0; i < 3; i++) { fns.push(function () { return i; }); }
+//! ```
+//!
+//! becomes
+//!
+//! ```text
+//! var _loop = function (i) { fns.push(function () { return i; }); };
+//! for (var i = 0; i < 3; i++) { _loop(i); }
+//! ```
+//!
+//! The body function returns how the loop goes on: `"break"`, `"break|label"` and
+//! `"continue|label"` for the jumps out of it, and `{ v: value }` for a `return` statement.
+
+use super::{util, Context};
+use crate::syntax::{
+  ast::{
+    node::{
+      ArrowFunctionDecl, Assign, Block, Break, Continue, Declaration, DeclarationList, Finally,
+      ForLoop, FunctionDecl, FunctionExpr, Identifier, If, IterableLoopInitializer, Object,
+      Pattern, PropertyDefinition, PropertyName, Return, StatementList, Try, UnaryOp,
+    },
+    operator::{self, CompOp},
+    visitor::{visit, visit_mut, Flow, KindPath, NodePath, Path, Visitor, VisitorMut},
+    Node,
+  },
+  scope::{BindingId, BindingKind, ScopeId, ScopeTree},
+};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Lowers the `let` and `const` declarations of a program.
+pub(super) fn run(body: &mut StatementList, cx: &mut Context) {
+  let tree = ScopeTree::script(body);
+  let renames = renames(&tree, cx);
+  let mut lowering = Lowering {
+    cx,
+    tree: &tree,
+    renames,
+    frames: vec![Frame::new(false)],
+    loops: 0,
+  };
+  let _ = lowering.visit_statements(body.items_mut(), &mut Path::new());
+  let frame = lowering.frames.pop().expect("the program frame");
+  frame.declare(body);
+}
+
+/// Gives new names to the block scoped bindings that would clash with another name of their
+/// function once declared with `var`.
+fn renames(tree: &ScopeTree, cx: &mut Context) -> HashMap<BindingId, Box<str>> {
+  let function = |mut scope: ScopeId| loop {
+    let current = tree.scope(scope);
+    if current.kind().is_var_scope() {
+      return scope;
+    }
+    scope = current.parent().expect("the top level is a var scope");
+  };
+
+  // The names a function can not give to a hoisted binding: its own bindings, and the names
+  // its code refers to outside of it.
+  let mut taken: HashMap<ScopeId, HashSet<Box<str>>> = HashMap::new();
+  for (_, binding) in tree.bindings() {
+    if tree.scope(binding.scope()).kind().is_var_scope() {
+      taken
+        .entry(binding.scope())
+        .or_default()
+        .insert(binding.name().into());
+    }
+  }
+  for (_, reference) in tree.references() {
+    let declared = reference
+      .binding()
+      .map(|binding| tree.binding(binding).scope());
+    let mut scope = reference.scope();
+    loop {
+      if tree.scope(scope).kind().is_var_scope() {
+        taken
+          .entry(scope)
+          .or_default()
+          .insert(reference.name().into());
+      }
+      match tree.scope(scope).parent() {
+        Some(parent) if Some(scope) != declared => scope = parent,
+        _ => break,
+      }
+    }
+  }
+
+  let mut renames = HashMap::new();
+  for (id, binding) in tree.bindings() {
+    let lexical = matches!(binding.kind(), BindingKind::Let | BindingKind::Const);
+    if !lexical || tree.scope(binding.scope()).kind().is_var_scope() {
+      continue;
+    }
+    let names = taken.entry(function(binding.scope())).or_default();
+    if names.contains(binding.name()) {
+      let name = cx.fresh(binding.name());
+      names.insert(name.clone());
+      renames.insert(id, name);
+    } else {
+      names.insert(binding.name().into());
+    }
+  }
+  renames
+}
+
+/// The variables declared at the start of a function, or of the body function of a loop.
+struct Frame {
+  variables: Vec<(Box<str>, Option<Node>)>,
+  /// Whether the frame is the body function of a loop, whose `var` declarations are hoisted out
+  /// of it.
+  closure: bool,
+}
+
+impl Frame {
+  fn new(closure: bool) -> Self {
+    Self {
+      variables: Vec::new(),
+      closure,
+    }
+  }
+
+  /// Declares the variables of the frame at the start of a body.
+  fn declare(self, body: &mut StatementList) {
+    if !self.variables.is_empty() {
+      util::prepend(body, vec![util::var(self.variables)]);
+    }
+  }
+}
+
+/// How the body of a loop run in a function has to be lowered.
+struct Plan {
+  /// The bindings declared in the head of the loop, passed to each call.
+  head: Vec<BindingId>,
+  /// Whether the body assigns the bindings of the head, which are then copied back.
+  writes: bool,
+}
+
+/// Rewrites the declarations and the loops from the leaves up.
+struct Lowering<'a> {
+  cx: &'a mut Context,
+  tree: &'a ScopeTree,
+  renames: HashMap<BindingId, Box<str>>,
+  frames: Vec<Frame>,
+  /// The number of loops around the code being visited, in its function.
+  loops: usize,
+}
+
+impl Lowering<'_> {
+  /// Declares a variable in the innermost frame.
+  fn declare(&mut self, name: Box<str>, init: Option<Node>) {
+    let frame = self.frames.last_mut().expect("a frame");
+    frame.variables.push((name, init));
+  }
+
+  /// Gets the name a binding is declared with after the renaming.
+  fn name(&self, binding: BindingId) -> Box<str> {
+    match self.renames.get(&binding) {
+      Some(name) => name.clone(),
+      None => self.tree.binding(binding).name().into(),
+    }
+  }
+
+  /// Decides whether a loop runs its body in a function, before the loop is renamed.
+  fn plan(&self, node: &Node) -> Option<Plan> {
+    let (head, body) = match node {
+      Node::ForLoop(stmt) => {
+        let head = match stmt.init() {
+          Some(Node::DeclarationList(
+            list @ (DeclarationList::Let(_) | DeclarationList::Const(_)),
+            _,
+          )) => list
+            .as_ref()
+            .iter()
+            .flat_map(|declaration| declaration.target().bound_identifiers())
+            .collect(),
+          _ => Vec::new(),
+        };
+        (head, stmt.body())
+      }
+      Node::ForInLoop(stmt) => (lexical_head(stmt.init()), stmt.body()),
+      Node::ForOfLoop(stmt) => (lexical_head(stmt.init()), stmt.body()),
+      Node::WhileLoop(stmt) => (Vec::new(), stmt.body()),
+      Node::DoWhileLoop(stmt) => (Vec::new(), stmt.body()),
+      _ => return None,
+    };
+    let mut lexical = Lexical { names: Vec::new() };
+    let _ = lexical.visit_node(body, &mut Path::new());
+    let captured = head
+      .iter()
+      .chain(&lexical.names)
+      .filter_map(|ident| self.tree.declaration_at(ident.id()))
+      .any(|binding| self.tree.binding(binding).is_captured());
+    // Generators and `async` functions can not yield from a nested function, and `super` is
+    // only valid in methods.
+    let suspends = util::contains(&[], std::slice::from_ref(body), |node| {
+      matches!(node, Node::Yield(_) | Node::Await(_) | Node::Super(_))
+    });
+    if !captured || suspends {
+      return None;
+    }
+    let head: Vec<_> = head
+      .iter()
+      .filter_map(|ident| self.tree.declaration_at(ident.id()))
+      .collect();
+    // The update of a `for` loop is in the scope of the head, the body in scopes inside it.
+    let writes = head.iter().any(|&id| {
+      let binding = self.tree.binding(id);
+      binding.references().iter().any(|&reference| {
+        let reference = self.tree.reference(reference);
+        reference.access().is_write() && reference.scope() != binding.scope()
+      })
+    });
+    Some(Plan { head, writes })
+  }
+
+  /// Replaces the body of a loop with a call of a function running it.
+  fn closure(&mut self, body: &mut Node, plan: Plan, frame: Frame) {
+    let head: Vec<Box<str>> = plan.head.iter().map(|&id| self.name(id)).collect();
+    let mut statements = util::take(body);
+    let mut statements = match &mut statements {
+      Node::Block(block) => util::take_list(block.statement_list_mut()),
+      _ => StatementList::from(vec![statements]),
+    };
+    frame.declare(&mut statements);
+    let mut statements = statements.items().to_vec();
+
+    // The `var` declarations of the body are bindings of the function containing the loop.
+    let mut hoisting = Hoisting {
+      tree: self.tree,
+      names: Vec::new(),
+    };
+    let _ = hoisting.visit_statements(&mut statements, &mut Path::new());
+    for name in hoisting.names {
+      let frame = self
+        .frames
+        .iter_mut()
+        .rev()
+        .find(|frame| !frame.closure)
+        .expect("a function frame");
+      frame.variables.push((name, None));
+    }
+
+    let mut jumps = Jumps::default();
+    let _ = jumps.visit_statements(&mut statements, &mut Path::new());
+
+    let uses_this = util::contains(&[], &statements, |node| matches!(node, Node::This(_)));
+    let in_function = self.frames.iter().filter(|frame| !frame.closure).count() > 1;
+    let uses_arguments = in_function
+      && util::contains(
+        &[],
+        &statements,
+        |node| matches!(node, Node::Identifier(ident) if ident.name() == "arguments"),
+      );
+    let this = uses_this.then(|| self.cx.fresh("this"));
+    let arguments = uses_arguments.then(|| self.cx.fresh("arguments"));
+    util::rebind(&mut statements, this.as_deref(), arguments.as_deref());
+    if let Some(this) = this {
+      self.declare(this, Some(Node::this()));
+    }
+    if let Some(arguments) = arguments {
+      self.declare(arguments, Some(util::ident("arguments")));
+    }
+
+    // The assignments of the head bindings are copied back to the loop, however the body ends.
+    let mut copies = Vec::new();
+    if plan.writes {
+      let mut finalizer = Vec::new();
+      for name in &head {
+        let out = self.cx.fresh(name);
+        self.declare(out.clone(), None);
+        finalizer.push(util::assign(util::ident(&out), util::ident(name)));
+        copies.push(util::assign(util::ident(name), util::ident(&out)));
+      }
+      let finalizer = Finally::from(Block::from(finalizer));
+      statements = vec![Try::new(Block::from(statements), None, Some(finalizer)).into()];
+    }
+
+    let params: Vec<_> = head.iter().map(|name| util::param(name)).collect();
+    let name = self.cx.fresh("loop");
+    self.declare(name.clone(), Some(util::function(params, statements)));
+    let call = util::call(
+      util::ident(&name),
+      head.iter().map(|name| util::ident(name)).collect(),
+    );
+
+    let mut result = Vec::new();
+    if !jumps.breaks && !jumps.returns && jumps.labels.is_empty() {
+      result.push(call);
+      result.extend(copies);
+      *body = Block::from(result).into();
+      return;
+    }
+    let ret = self.cx.fresh("ret");
+    result.push(util::var(vec![(ret.clone(), Some(call))]));
+    result.extend(copies);
+    let is =
+      |value: &str| util::binary(CompOp::StrictEqual, util::ident(&ret), util::string(value));
+    if jumps.breaks {
+      result.push(
+        If::new::<_, _, Node, _>(
+          is("break"),
+          Node::Break(Break::new::<_, Box<str>>(None)),
+          None,
+        )
+        .into(),
+      );
+    }
+    for (is_continue, label) in &jumps.labels {
+      let (value, jump) = if *is_continue {
+        (
+          format!("continue|{}", label),
+          Node::Continue(Continue::new::<_, Box<str>>(Some(label.clone()))),
+        )
+      } else {
+        (
+          format!("break|{}", label),
+          Node::Break(Break::new::<_, Box<str>>(Some(label.clone()))),
+        )
+      };
+      result.push(If::new::<_, _, Node, _>(is(&value), jump, None).into());
+    }
+    if jumps.returns {
+      let object = util::binary(
+        CompOp::StrictEqual,
+        UnaryOp::new(operator::UnaryOp::TypeOf, util::ident(&ret)).into(),
+        util::string("object"),
+      );
+      let value = util::member(util::ident(&ret), "v");
+      result.push(If::new::<_, _, Node, _>(object, Return::new(Some(value)), None).into());
+    }
+    *body = Block::from(result).into();
+  }
+
+  /// Visits a function in a frame of its own.
+  fn function<F>(&mut self, visit: F) -> (Flow, Frame)
+  where
+    F: FnOnce(&mut Self) -> Flow,
+  {
+    self.frames.push(Frame::new(false));
+    let loops = std::mem::replace(&mut self.loops, 0);
+    let flow = visit(self);
+    self.loops = loops;
+    (flow, self.frames.pop().expect("the frame was pushed"))
+  }
+}
+
+impl VisitorMut for Lowering<'_> {
+  fn visit_node(&mut self, node: &mut Node, path: &mut KindPath) -> Flow {
+    let looping = matches!(
+      node,
+      Node::ForLoop(_)
+        | Node::ForInLoop(_)
+        | Node::ForOfLoop(_)
+        | Node::WhileLoop(_)
+        | Node::DoWhileLoop(_)
+    );
+    if !looping {
+      visit_mut::walk_node(self, node, path)?;
+      if let Node::DeclarationList(list, _) = node {
+        if let Some(list) = self.var(list) {
+          *node = list.into();
+        }
+      }
+      return Flow::Continue(());
+    }
+
+    let plan = self.plan(node);
+    if plan.is_some() {
+      self.frames.push(Frame::new(true));
+    }
+    self.loops += 1;
+    let flow = visit_mut::walk_node(self, node, path);
+    self.loops -= 1;
+    let frame = plan
+      .as_ref()
+      .map(|_| self.frames.pop().expect("the frame was pushed"));
+    flow?;
+    if let (Some(plan), Some(frame)) = (plan, frame) {
+      let body = match node {
+        Node::ForLoop(stmt) => stmt.body_mut(),
+        Node::ForInLoop(stmt) => stmt.body_mut(),
+        Node::ForOfLoop(stmt) => stmt.body_mut(),
+        Node::WhileLoop(stmt) => stmt.body_mut(),
+        Node::DoWhileLoop(stmt) => stmt.body_mut(),
+        _ => unreachable!("the node is a loop"),
+      };
+      self.closure(body, plan, frame);
+    }
+    let init = match node {
+      Node::ForInLoop(stmt) => stmt.init_mut(),
+      Node::ForOfLoop(stmt) => stmt.init_mut(),
+      _ => return Flow::Continue(()),
+    };
+    if let IterableLoopInitializer::Let(pattern) | IterableLoopInitializer::Const(pattern) = init {
+      *init = IterableLoopInitializer::Var(pattern.clone());
+    }
+    Flow::Continue(())
+  }
+
+  fn visit_identifier(&mut self, ident: &mut Identifier, _path: &mut KindPath) -> Flow {
+    if let Some(name) = self
+      .tree
+      .resolve_at(ident.id())
+      .and_then(|binding| self.renames.get(&binding))
+    {
+      *ident = Identifier::from(name.clone());
+    }
+    Flow::Continue(())
+  }
+
+  fn visit_property_definition(
+    &mut self,
+    property: &mut PropertyDefinition,
+    path: &mut KindPath,
+  ) -> Flow {
+    if let PropertyDefinition::IdentifierReference(ident, span) = property {
+      if let Some(renamed) = self
+        .tree
+        .resolve_at(ident.id())
+        .and_then(|binding| self.renames.get(&binding))
+      {
+        *property = PropertyDefinition::Property(
+          PropertyName::Literal(ident.name().clone()),
+          util::ident(renamed),
+          *span,
+        );
+      }
+      return Flow::Continue(());
+    }
+    visit_mut::walk_property_definition(self, property, path)
+  }
+
+  fn visit_function_decl(&mut self, decl: &mut FunctionDecl, path: &mut KindPath) -> Flow {
+    let (flow, frame) = self.function(|this| visit_mut::walk_function_decl(this, decl, path));
+    frame.declare(decl.body_list_mut());
+    flow
+  }
+
+  fn visit_function_expr(&mut self, expr: &mut FunctionExpr, path: &mut KindPath) -> Flow {
+    let (flow, frame) = self.function(|this| visit_mut::walk_function_expr(this, expr, path));
+    frame.declare(expr.body_list_mut());
+    flow
+  }
+
+  fn visit_arrow_function_decl(
+    &mut self,
+    arrow: &mut ArrowFunctionDecl,
+    path: &mut KindPath,
+  ) -> Flow {
+    let (flow, frame) =
+      self.function(|this| visit_mut::walk_arrow_function_decl(this, arrow, path));
+    frame.declare(arrow.body_list_mut());
+    flow
+  }
+}
+
+impl Lowering<'_> {
+  /// Turns a `let` or `const` declaration into a `var` declaration. In a loop, a `let`
+  /// declaration without value is set to `undefined`, which a `var` declaration keeps from the
+  /// previous iteration.
+  fn var(&self, list: &DeclarationList) -> Option<DeclarationList> {
+    let declarations = match list {
+      DeclarationList::Let(declarations) | DeclarationList::Const(declarations) => declarations,
+      DeclarationList::Var(_) => return None,
+    };
+    let declarations = declarations
+      .iter()
+      .map(|declaration| match declaration.init() {
+        None if self.loops > 0 => {
+          Declaration::new(declaration.target().clone(), Some(util::void0()))
+        }
+        _ => declaration.clone(),
+      })
+      .collect::<Vec<_>>();
+    Some(DeclarationList::Var(declarations.into()))
+  }
+}
+
+/// Gets the names a `for ... in` or `for ... of` loop declares with `let` or `const`.
+fn lexical_head(init: &IterableLoopInitializer) -> Vec<&Identifier> {
+  match init {
+    IterableLoopInitializer::Let(pattern) | IterableLoopInitializer::Const(pattern) => {
+      pattern.bound_identifiers()
+    }
+    _ => Vec::new(),
+  }
+}
+
+/// Collects the names declared with `let` or `const` in the body of a loop, outside of the
+/// functions and the loops it contains, which have their own bindings.
+struct Lexical<'ast> {
+  names: Vec<&'ast Identifier>,
+}
+
+impl<'ast> Visitor<'ast> for Lexical<'ast> {
+  fn visit_node(&mut self, node: &'ast Node, path: &mut NodePath<'ast>) -> Flow {
+    match node {
+      Node::FunctionDecl(_)
+      | Node::FunctionExpr(_)
+      | Node::ArrowFunctionDecl(_)
+      | Node::ClassDecl(_)
+      | Node::ClassExpr(_)
+      | Node::ForLoop(_)
+      | Node::ForInLoop(_)
+      | Node::ForOfLoop(_)
+      | Node::WhileLoop(_)
+      | Node::DoWhileLoop(_) => Flow::Continue(()),
+      Node::DeclarationList(list @ (DeclarationList::Let(_) | DeclarationList::Const(_)), _) => {
+        for declaration in list.as_ref() {
+          self.names.extend(declaration.target().bound_identifiers());
+        }
+        Flow::Continue(())
+      }
+      _ => visit::walk_node(self, node, path),
+    }
+  }
+}
+
+/// Turns the `var` declarations of the body of a loop into assignments, collecting their names.
+struct Hoisting<'a> {
+  tree: &'a ScopeTree,
+  names: Vec<Box<str>>,
+}
+
+impl Hoisting<'_> {
+  /// Returns `true` if a pattern declares `var` bindings of the source. The `let` and `const`
+  /// declarations turned into `var` declarations stay in the body.
+  fn hoists(&self, pattern: &Pattern) -> bool {
+    pattern.bound_identifiers().first().is_some_and(|ident| {
+      self
+        .tree
+        .declaration_at(ident.id())
+        .is_some_and(|binding| self.tree.binding(binding).kind() == BindingKind::Var)
+    })
+  }
+}
+
+impl VisitorMut for Hoisting<'_> {
+  fn visit_node(&mut self, node: &mut Node, path: &mut KindPath) -> Flow {
+    match node {
+      Node::FunctionDecl(_)
+      | Node::FunctionExpr(_)
+      | Node::ArrowFunctionDecl(_)
+      | Node::ClassDecl(_)
+      | Node::ClassExpr(_) => return Flow::Continue(()),
+      _ => visit_mut::walk_node(self, node, path)?,
+    }
+    match node {
+      Node::DeclarationList(DeclarationList::Var(declarations), _)
+        if declarations
+          .iter()
+          .any(|declaration| self.hoists(declaration.target())) =>
+      {
+        let mut assignments = Vec::new();
+        for declaration in declarations.iter() {
+          self.names.extend(
+            declaration
+              .target()
+              .bound_names()
+              .into_iter()
+              .map(Box::from),
+          );
+          if let Some(init) = declaration.init() {
+            assignments.push(Assign::new(declaration.target().clone(), init.clone()).into());
+          }
+        }
+        *node = if assignments.is_empty() {
+          Node::empty()
+        } else {
+          util::sequence(assignments)
+        };
+      }
+      Node::ForLoop(stmt) if matches!(stmt.init(), Some(Node::Empty(_))) => {
+        let condition = stmt.condition_mut().map(util::take);
+        let update = stmt.final_expr_mut().map(util::take);
+        let body = util::take(stmt.body_mut());
+        *node = ForLoop::new(None, condition, update, body).into();
+      }
+      Node::ForInLoop(stmt) => hoist_head(self, stmt.init_mut()),
+      Node::ForOfLoop(stmt) => hoist_head(self, stmt.init_mut()),
+      _ => {}
+    }
+    Flow::Continue(())
+  }
+}
+
+/// Turns the `var` declaration of the head of a `for ... in` or `for ... of` loop into an
+/// assignment target.
+fn hoist_head(hoisting: &mut Hoisting<'_>, init: &mut IterableLoopInitializer) {
+  if let IterableLoopInitializer::Var(pattern) = init {
+    if hoisting.hoists(pattern) {
+      hoisting
+        .names
+        .extend(pattern.bound_names().into_iter().map(Box::from));
+      *init = IterableLoopInitializer::Pattern(pattern.clone());
+    }
+  }
+}
+
+/// Rewrites the jumps out of the body of a loop into `return` statements of its function.
+#[derive(Default)]
+struct Jumps {
+  /// The loops around the code being visited, inside the body.
+  loops: usize,
+  /// The `switch` statements around the code being visited, inside the body.
+  switches: usize,
+  /// The labels defined inside the body.
+  inner: Vec<Box<str>>,
+  breaks: bool,
+  returns: bool,
+  /// The labelled jumps out of the body, with `true` for a `continue` statement.
+  labels: BTreeSet<(bool, Box<str>)>,
+}
+
+impl VisitorMut for Jumps {
+  fn visit_node(&mut self, node: &mut Node, path: &mut KindPath) -> Flow {
+    let encoded = match node {
+      Node::FunctionDecl(_)
+      | Node::FunctionExpr(_)
+      | Node::ArrowFunctionDecl(_)
+      | Node::ClassDecl(_)
+      | Node::ClassExpr(_) => return Flow::Continue(()),
+      Node::ForLoop(_)
+      | Node::ForInLoop(_)
+      | Node::ForOfLoop(_)
+      | Node::WhileLoop(_)
+      | Node::DoWhileLoop(_) => {
+        self.loops += 1;
+        let flow = visit_mut::walk_node(self, node, path);
+        self.loops -= 1;
+        return flow;
+      }
+      Node::Switch(_) => {
+        self.switches += 1;
+        let flow = visit_mut::walk_node(self, node, path);
+        self.switches -= 1;
+        return flow;
+      }
+      Node::Labelled(labelled) => {
+        self.inner.push(labelled.label().into());
+        let flow = visit_mut::walk_node(self, node, path);
+        self.inner.pop();
+        return flow;
+      }
+      Node::Break(stmt) => match stmt.label() {
+        None if self.loops == 0 && self.switches == 0 => {
+          self.breaks = true;
+          Some(util::string("break"))
+        }
+        Some(label) if !self.inner.iter().any(|inner| &**inner == label) => {
+          self.labels.insert((false, label.into()));
+          Some(util::string(&format!("break|{}", label)))
+        }
+        _ => return Flow::Continue(()),
+      },
+      Node::Continue(stmt) => match stmt.label() {
+        None if self.loops == 0 => None,
+        Some(label) if !self.inner.iter().any(|inner| &**inner == label) => {
+          self.labels.insert((true, label.into()));
+          Some(util::string(&format!("continue|{}", label)))
+        }
+        _ => return Flow::Continue(()),
+      },
+      Node::Return(stmt) => {
+        self.returns = true;
+        let value = stmt.expr().cloned().unwrap_or_else(util::void0);
+        Some(Object::from(vec![PropertyDefinition::property("v", value)]).into())
+      }
+      _ => return visit_mut::walk_node(self, node, path),
+    };
+    *node = Return::new(encoded).into();
+    Flow::Continue(())
+  }
+}
@@ -0,0 +1,187 @@
+//! The checks run before lowering a program.
+//!
+//! Generators, `async` functions and private class members have no transform, so a program
+//! using them can not be lowered to an edition without them. Once `let` and `const` become
+//! `var`, assigning a constant and reading a binding before its declaration no longer throw, so
+//! the cases the scope analysis finds are reported instead.
+
+use super::{class, Options, Target, Transform, TranspileError};
+use crate::syntax::{
+  ast::{
+    node::{
+      ArrowFunctionDecl, Await, Class, ForOfLoop, FunctionDecl, FunctionExpr, FunctionKind,
+      Identifier, StatementList,
+    },
+    visitor::{visit, Flow, NodePath, Path, Visitor},
+    Span,
+  },
+  scope::{BindingKind, ScopeTree},
+};
+use std::mem;
+
+/// Checks that a program can be lowered with the given options.
+pub(super) fn run(
+  body: &StatementList,
+  module: bool,
+  options: &Options,
+) -> Result<(), TranspileError> {
+  let block_scoping = options.is_enabled(Transform::BlockScoping);
+  let parameters = options.is_enabled(Transform::Parameters);
+  let mut checker = Checker {
+    options,
+    scopes: (block_scoping || parameters).then(|| ScopeTree::statements(body.items(), module)),
+    block_scoping,
+    parameters,
+    is_async: false,
+    error: None,
+  };
+  let _ = checker.visit_statements(body.items(), &mut Path::new());
+  checker.error.map_or(Ok(()), Err)
+}
+
+/// Finds the first error of a program, in source order.
+struct Checker<'a> {
+  options: &'a Options,
+  /// The scopes of the program, when `let`, `const` or default parameters are lowered.
+  scopes: Option<ScopeTree>,
+  block_scoping: bool,
+  parameters: bool,
+  /// Whether the code being visited is in an `async` function.
+  is_async: bool,
+  error: Option<TranspileError>,
+}
+
+impl Checker<'_> {
+  /// Reports a construct if the target edition predates it.
+  fn edition(&mut self, construct: &'static str, edition: Target, span: Option<Span>) -> Flow {
+    if self.options.target() < edition {
+      return self.fail(TranspileError::Unsupported {
+        construct,
+        target: self.options.target(),
+        span,
+      });
+    }
+    Flow::Continue(())
+  }
+
+  /// Reports a function of a kind the target edition lacks.
+  fn function_kind(&mut self, kind: FunctionKind, span: Option<Span>) -> Flow {
+    match kind {
+      FunctionKind::Ordinary => Flow::Continue(()),
+      FunctionKind::Generator => self.edition("generator functions", Target::Es2015, span),
+      FunctionKind::Async => self.edition("async functions", Target::Es2017, span),
+      FunctionKind::AsyncGenerator => self.edition("async generators", Target::Es2018, span),
+    }
+  }
+
+  /// Visits the inside of a function.
+  fn function<F>(&mut self, is_async: bool, visit: F) -> Flow
+  where
+    F: FnOnce(&mut Self) -> Flow,
+  {
+    let outer = mem::replace(&mut self.is_async, is_async);
+    let flow = visit(self);
+    self.is_async = outer;
+    flow
+  }
+
+  /// Records the error and stops the search.
+  fn fail(&mut self, error: TranspileError) -> Flow {
+    self.error = Some(error);
+    Flow::Break(())
+  }
+}
+
+impl<'ast> Visitor<'ast> for Checker<'_> {
+  fn visit_identifier(&mut self, ident: &'ast Identifier, _path: &mut NodePath<'ast>) -> Flow {
+    let scopes = match &self.scopes {
+      Some(scopes) => scopes,
+      None => return Flow::Continue(()),
+    };
+    let reference = match scopes.reference_at(ident.id()) {
+      Some(reference) => scopes.reference(reference),
+      None => return Flow::Continue(()),
+    };
+    let kind = match reference.binding() {
+      Some(binding) => scopes.binding(binding).kind(),
+      None => return Flow::Continue(()),
+    };
+    let lowered = match kind {
+      BindingKind::Parameter => self.parameters,
+      _ => self.block_scoping,
+    };
+    let name = ident.name().as_str().into();
+    if self.block_scoping && kind == BindingKind::Const && reference.access().is_write() {
+      self.fail(TranspileError::ConstAssignment {
+        name,
+        span: ident.span(),
+      })
+    } else if lowered && reference.is_tdz() {
+      self.fail(TranspileError::UninitializedRead {
+        name,
+        span: ident.span(),
+      })
+    } else {
+      Flow::Continue(())
+    }
+  }
+
+  fn visit_function_decl(&mut self, decl: &'ast FunctionDecl, path: &mut NodePath<'ast>) -> Flow {
+    self.function_kind(decl.kind(), decl.span())?;
+    self.function(decl.kind().is_async(), |this| {
+      visit::walk_function_decl(this, decl, path)
+    })
+  }
+
+  fn visit_function_expr(&mut self, expr: &'ast FunctionExpr, path: &mut NodePath<'ast>) -> Flow {
+    self.function_kind(expr.kind(), expr.span())?;
+    self.function(expr.kind().is_async(), |this| {
+      visit::walk_function_expr(this, expr, path)
+    })
+  }
+
+  fn visit_arrow_function_decl(
+    &mut self,
+    arrow: &'ast ArrowFunctionDecl,
+    path: &mut NodePath<'ast>,
+  ) -> Flow {
+    if arrow.is_async() {
+      self.edition("async functions", Target::Es2017, arrow.span())?;
+    }
+    self.function(arrow.is_async(), |this| {
+      visit::walk_arrow_function_decl(this, arrow, path)
+    })
+  }
+
+  fn visit_await(&mut self, expr: &'ast Await, path: &mut NodePath<'ast>) -> Flow {
+    if !self.is_async {
+      self.edition("top-level await", Target::Es2022, expr.span())?;
+    }
+    visit::walk_await(self, expr, path)
+  }
+
+  fn visit_for_of_loop(&mut self, stmt: &'ast ForOfLoop, path: &mut NodePath<'ast>) -> Flow {
+    if stmt.is_await() {
+      if self.is_async {
+        self.edition("async iteration", Target::Es2018, stmt.span())?;
+      } else {
+        self.edition("top-level await", Target::Es2022, stmt.span())?;
+      }
+    }
+    visit::walk_for_of_loop(self, stmt, path)
+  }
+
+  fn visit_class(&mut self, class: &'ast Class, path: &mut NodePath<'ast>) -> Flow {
+    if class::has_private(class)
+      && (self.options.is_enabled(Transform::Classes)
+        || self.options.is_enabled(Transform::ClassFields))
+    {
+      return self.fail(TranspileError::Unsupported {
+        construct: "private class members",
+        target: self.options.target(),
+        span: class.span(),
+      });
+    }
+    visit::walk_class(self, class, path)
+  }
+}
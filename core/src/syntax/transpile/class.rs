@@ -0,0 +1,733 @@
+//! Classes, and their fields and static blocks.
+//!
+//! A class becomes a function called once, which declares the constructor and defines the
+//! methods and static members on it:
+//!
+//! ```text
+//! class A extends B { x = 1; m() { return super.m(); } }
+//!
+//! let A = (function (_Super) {
+//!   _inherits(A, _Super);
+//!   function A() {
+//!     var _this;
+//!     _classCallCheck(this, A);
+//!     _this = _Super.apply(this, arguments) || this;
+//!     _defineProperty(_this, "x", 1);
+//!     return _this;
+//!   }
+//!   _createClass(A, [{ key: "m", value: function () { return _Super.prototype.m.call(this); } }]);
+//!   return A;
+//! })(B);
+//! ```
+//!
+//! When only fields are lowered, the class is kept: the instance fields are initialized in the
+//! constructor, and the static ones are defined once the class is created. Classes with private
+//! members can not be lowered, the transpiler reports them before running the transforms.
+
+use super::{
+  helpers::Helper,
+  util::{self, Scopes},
+  Context, Options, Transform,
+};
+use crate::syntax::ast::{
+  arena::ArenaBox,
+  node::{
+    ArrayDecl, Call, Class, ClassElement, ClassElementName, Declaration, DeclarationList,
+    ExportDecl, FormalParameter, FunctionDecl, FunctionExpr, Identifier, MethodDefinitionKind,
+    Object, PropertyDefinition, PropertyName, Return, Spread, StatementList,
+  },
+  operator::LogOp,
+  visitor::{visit_mut, Flow, KindPath, Path, VisitorMut},
+  Node, Sym,
+};
+
+/// Lowers the classes of a program, or only their fields, as enabled by the options.
+pub(super) fn run(body: &mut StatementList, cx: &mut Context, options: &Options) {
+  let mut lowering = Lowering {
+    cx,
+    scopes: Scopes::default(),
+    classes: options.is_enabled(Transform::Classes),
+  };
+  lowering.scopes.enter();
+  let _ = lowering.visit_statements(body.items_mut(), &mut Path::new());
+  lowering.scopes.exit(body);
+}
+
+/// A static member initialized once the class is created.
+enum Static {
+  Field(Box<Node>, Option<Node>),
+  Block(StatementList),
+}
+
+/// The descriptor of a method, getter or setter passed to `_createClass`.
+struct Descriptor {
+  /// The name of the member, if it is not computed.
+  literal: Option<Box<str>>,
+  key: Node,
+  value: Option<Node>,
+  get: Option<Node>,
+  set: Option<Node>,
+}
+
+impl Descriptor {
+  fn into_node(self) -> Node {
+    let mut properties = vec![PropertyDefinition::property("key", self.key)];
+    for (name, function) in [("value", self.value), ("get", self.get), ("set", self.set)] {
+      if let Some(function) = function {
+        properties.push(PropertyDefinition::property(name, function));
+      }
+    }
+    Object::from(properties).into()
+  }
+}
+
+/// The members of a class, sorted by how they are defined.
+#[derive(Default)]
+struct Members {
+  methods: Vec<Descriptor>,
+  static_methods: Vec<Descriptor>,
+  fields: Vec<(Node, Option<Node>)>,
+  statics: Vec<Static>,
+  /// The computed field names, evaluated in order into variables when the class is created.
+  keys: Vec<(Box<str>, Node)>,
+}
+
+impl Members {
+  /// Adds a method, getter or setter, merging a getter and a setter of the same name.
+  fn add_method(&mut self, name: &PropertyName, kind: MethodKind, function: Node, is_static: bool) {
+    let descriptors = if is_static {
+      &mut self.static_methods
+    } else {
+      &mut self.methods
+    };
+    let literal = name.literal().map(Box::from);
+    if let (Some(literal), MethodKind::Get | MethodKind::Set) = (&literal, kind) {
+      let accessor = descriptors.iter_mut().rev().find(|descriptor| {
+        descriptor.literal.as_ref() == Some(literal) && descriptor.value.is_none()
+      });
+      if let Some(descriptor) = accessor {
+        let slot = match kind {
+          MethodKind::Get => &mut descriptor.get,
+          _ => &mut descriptor.set,
+        };
+        if slot.is_none() {
+          *slot = Some(function);
+          return;
+        }
+      }
+    }
+    let mut descriptor = Descriptor {
+      literal,
+      key: key(name),
+      value: None,
+      get: None,
+      set: None,
+    };
+    match kind {
+      MethodKind::Get => descriptor.get = Some(function),
+      MethodKind::Set => descriptor.set = Some(function),
+      MethodKind::Value => descriptor.value = Some(function),
+    }
+    descriptors.push(descriptor);
+  }
+}
+
+/// How a method is defined on its object.
+#[derive(Debug, Clone, Copy)]
+enum MethodKind {
+  Value,
+  Get,
+  Set,
+}
+
+/// Rewrites the classes from the leaves up.
+struct Lowering<'cx> {
+  cx: &'cx mut Context,
+  scopes: Scopes,
+  /// Whether whole classes are lowered, or only their fields.
+  classes: bool,
+}
+
+impl Lowering<'_> {
+  /// Lowers a class declaration into a `let` declaration.
+  fn declaration(&mut self, class: &Class) -> Option<Node> {
+    let name = class.name()?.to_owned();
+    if self.classes {
+      let init = self.lower(class, &name);
+      return Some(let_declaration(&name, init));
+    }
+    let (class, prefix, statics) = self.fields(class)?;
+    if prefix.is_empty() && statics.is_empty() {
+      return Some(Node::ClassDecl(class));
+    }
+    let init = self.assemble(class, Some(&name), prefix, statics);
+    Some(let_declaration(&name, init))
+  }
+
+  /// Lowers a class expression.
+  fn expression(&mut self, class: &Class) -> Option<Node> {
+    if self.classes {
+      let name = match class.name() {
+        Some(name) => name.into(),
+        None => self.cx.fresh("class"),
+      };
+      return Some(self.lower(class, &name));
+    }
+    let (kept, prefix, statics) = self.fields(class)?;
+    if prefix.is_empty() && statics.is_empty() {
+      return Some(Node::ClassExpr(kept));
+    }
+    let name = class.name().map(Sym::to_string);
+    Some(self.assemble(kept, name.as_deref(), prefix, statics))
+  }
+
+  /// Lowers `export default class`, assigning the class to a variable if it has a name.
+  fn default_export(&mut self, class: &Class) -> Option<Node> {
+    let name = class.name().map(Sym::to_string);
+    let value = if self.classes {
+      let binding = match &name {
+        Some(name) => name.as_str().into(),
+        None => self.cx.fresh("class"),
+      };
+      self.lower(class, &binding)
+    } else {
+      let (kept, prefix, statics) = self.fields(class)?;
+      if prefix.is_empty() && statics.is_empty() {
+        return Some(ExportDecl::DefaultClass(kept).into());
+      }
+      self.assemble(kept, name.as_deref(), prefix, statics)
+    };
+    let value = match name {
+      Some(name) => {
+        self.scopes.declare(name.as_str().into(), None);
+        util::assign(util::ident(&name), value)
+      }
+      None => value,
+    };
+    Some(ExportDecl::DefaultExpression(ArenaBox::new(value)).into())
+  }
+
+  /// Sorts the members of a class, rewriting `super` in them.
+  fn members(&mut self, class: &Class, parent: Option<&str>) -> Members {
+    let mut members = Members::default();
+    let instance_base = home(parent, false);
+    let static_base = home(parent, true);
+    for element in class.elements() {
+      match element {
+        ClassElement::Method {
+          name: ClassElementName::PropertyName(name),
+          kind,
+          function,
+          is_static,
+          ..
+        } => {
+          let (mut function, is_static) = (function.clone(), *is_static);
+          let base = if is_static {
+            &static_base
+          } else {
+            &instance_base
+          };
+          let _ = visit_mut::walk_function_expr(
+            &mut SuperMembers { base },
+            &mut function,
+            &mut Path::new(),
+          );
+          let kind = match kind {
+            MethodDefinitionKind::Get => MethodKind::Get,
+            MethodDefinitionKind::Set => MethodKind::Set,
+            MethodDefinitionKind::Ordinary => MethodKind::Value,
+          };
+          members.add_method(name, kind, function.into(), is_static);
+        }
+        ClassElement::Field {
+          name: ClassElementName::PropertyName(name),
+          init,
+          is_static,
+          ..
+        } => {
+          let (mut init, is_static) = (init.clone(), *is_static);
+          let key = self.field_key(name, &mut members.keys);
+          if let Some(init) = &mut init {
+            let base = if is_static {
+              &static_base
+            } else {
+              &instance_base
+            };
+            let _ = SuperMembers { base }.visit_node(init, &mut Path::new());
+          }
+          if is_static {
+            members.statics.push(Static::Field(Box::new(key), init));
+          } else {
+            members.fields.push((key, init));
+          }
+        }
+        ClassElement::StaticBlock(body, _) => {
+          let mut body = body.clone();
+          let _ = SuperMembers { base: &static_base }
+            .visit_statements(body.items_mut(), &mut Path::new());
+          members.statics.push(Static::Block(body));
+        }
+        ClassElement::Method { .. } | ClassElement::Field { .. } => {
+          unreachable!("classes with private members are kept")
+        }
+      }
+    }
+    members
+  }
+
+  /// Gets the key of a field, moving a computed one into a new variable, since the name is
+  /// computed once when the class is created.
+  fn field_key(&mut self, name: &PropertyName, keys: &mut Vec<(Box<str>, Node)>) -> Node {
+    match name {
+      PropertyName::Literal(name) => util::string(name),
+      PropertyName::Computed(key @ Node::Const(_, _)) => key.clone(),
+      PropertyName::Computed(key) => {
+        let temp = self.cx.fresh("key");
+        keys.push((temp.clone(), key.clone()));
+        util::ident(&temp)
+      }
+    }
+  }
+
+  /// Lowers a whole class into a function called once, `name` being the name of the
+  /// constructor.
+  fn lower(&mut self, class: &Class, name: &str) -> Node {
+    let parent = class.super_ref().map(|_| self.cx.fresh("Super"));
+    let members = self.members(class, parent.as_deref());
+    let pure = members.keys.is_empty()
+      && members.statics.is_empty()
+      && members
+        .methods
+        .iter()
+        .chain(&members.static_methods)
+        .all(|descriptor| descriptor.literal.is_some());
+
+    let mut body = Vec::new();
+    if let Some(parent) = &parent {
+      body.push(util::call(
+        self.cx.helper(Helper::Inherits),
+        vec![util::ident(name), util::ident(parent)],
+      ));
+    }
+    for (temp, key) in members.keys {
+      body.push(util::var(vec![(temp, Some(key))]));
+    }
+    body.push(
+      self
+        .constructor(class, name, parent.as_deref(), members.fields)
+        .into(),
+    );
+
+    if !members.methods.is_empty() || !members.static_methods.is_empty() {
+      let mut args = vec![util::ident(name), descriptors(members.methods)];
+      if !members.static_methods.is_empty() {
+        args.push(descriptors(members.static_methods));
+      }
+      body.push(util::call(self.cx.helper(Helper::CreateClass), args));
+    }
+    body.extend(self.statics(members.statics, name));
+    body.push(Return::new(Some(util::ident(name))).into());
+
+    let params = parent
+      .iter()
+      .map(|parent| util::param(parent))
+      .collect::<Vec<_>>();
+    let args = class.super_ref().cloned().into_iter().collect::<Vec<_>>();
+    let mut call = Call::new(util::function(params, body), args);
+    call.set_pure(pure);
+    call.into()
+  }
+
+  /// Creates the constructor function of a lowered class, initializing the instance fields.
+  fn constructor(
+    &mut self,
+    class: &Class,
+    name: &str,
+    parent: Option<&str>,
+    fields: Vec<(Node, Option<Node>)>,
+  ) -> FunctionDecl {
+    let (params, mut body) = match class.constructor() {
+      Some(constructor) => (
+        constructor.parameters().to_vec(),
+        constructor.body().to_vec(),
+      ),
+      None => (Vec::new(), Vec::new()),
+    };
+    let base = home(parent, false);
+    let _ = SuperMembers { base: &base }.visit_statements(&mut body, &mut Path::new());
+    let check = util::call(
+      self.cx.helper(Helper::ClassCallCheck),
+      vec![Node::this(), util::ident(name)],
+    );
+
+    let statements = match parent {
+      None => {
+        let mut statements = vec![check];
+        statements.extend(self.initializers(Node::this(), fields));
+        statements.extend(body);
+        statements
+      }
+      // The instance is the one the parent constructor returns, `_this`.
+      Some(parent) => {
+        let this = self.cx.fresh("this");
+        util::rebind(&mut body, Some(&this), None);
+        let inits = self.initializers(util::ident(&this), fields);
+        let mut statements = vec![util::var(vec![(this.clone(), None)]), check];
+        if class.constructor().is_some() {
+          let _ = SuperCalls {
+            lowered: Some((parent, &this)),
+            inits: &inits,
+            arrows: 0,
+          }
+          .visit_statements(&mut body, &mut Path::new());
+          statements.extend(body);
+        } else {
+          let apply = util::call(
+            util::member(util::ident(parent), "apply"),
+            vec![Node::this(), util::ident("arguments")],
+          );
+          statements.push(util::assign(
+            util::ident(&this),
+            util::binary(LogOp::Or, apply, Node::this()),
+          ));
+          statements.extend(inits);
+        }
+        statements.push(Return::new(Some(util::ident(&this))).into());
+        statements
+      }
+    };
+    FunctionDecl::new(name, params, statements)
+  }
+
+  /// Creates the definitions of the fields of an instance, `this` standing for the instance in
+  /// their initializers.
+  fn initializers(&mut self, this: Node, fields: Vec<(Node, Option<Node>)>) -> Vec<Node> {
+    fields
+      .into_iter()
+      .map(|(key, init)| {
+        let mut init = init.unwrap_or_else(util::void0);
+        if let Node::Identifier(this) = &this {
+          util::rebind_node(&mut init, Some(this.name()), None);
+        }
+        util::call(
+          self.cx.helper(Helper::DefineProperty),
+          vec![this.clone(), key, init],
+        )
+      })
+      .collect()
+  }
+
+  /// Creates the definitions of the static fields and the calls of the static blocks of the
+  /// class held by `name`.
+  fn statics(&mut self, statics: Vec<Static>, name: &str) -> Vec<Node> {
+    statics
+      .into_iter()
+      .map(|member| match member {
+        Static::Field(key, init) => {
+          let mut init = init.unwrap_or_else(util::void0);
+          util::rebind_node(&mut init, Some(name), None);
+          util::call(
+            self.cx.helper(Helper::DefineProperty),
+            vec![util::ident(name), *key, init],
+          )
+        }
+        Static::Block(body) => util::call(
+          util::member(util::function(Vec::new(), body), "call"),
+          vec![util::ident(name)],
+        ),
+      })
+      .collect()
+  }
+
+  /// Moves the fields and the static blocks out of a class that is kept, returning the class,
+  /// the expressions to evaluate before it and the static members, or `None` if it has none.
+  fn fields(&mut self, class: &Class) -> Option<(Class, Vec<Node>, Vec<Static>)> {
+    if !class
+      .elements()
+      .iter()
+      .any(|element| !matches!(element, ClassElement::Method { .. }))
+    {
+      return None;
+    }
+    let mut keys = Vec::new();
+    let mut fields = Vec::new();
+    let mut statics = Vec::new();
+    let mut methods = Vec::new();
+    for element in class.elements() {
+      match element {
+        ClassElement::Field {
+          name: ClassElementName::PropertyName(name),
+          init,
+          is_static,
+          ..
+        } => {
+          let key = self.field_key(name, &mut keys);
+          if *is_static {
+            statics.push(Static::Field(Box::new(key), init.clone()));
+          } else {
+            fields.push((key, init.clone()));
+          }
+        }
+        ClassElement::StaticBlock(body, _) => statics.push(Static::Block(body.clone())),
+        method => methods.push(method.clone()),
+      }
+    }
+    let prefix = keys
+      .into_iter()
+      .map(|(temp, key)| {
+        self.scopes.declare(temp.clone(), None);
+        util::assign(util::ident(&temp), key)
+      })
+      .collect();
+
+    let inits = self.initializers(Node::this(), fields);
+    let constructor = match class.constructor().cloned() {
+      constructor if inits.is_empty() => constructor,
+      Some(mut constructor) => {
+        if class.super_ref().is_some() {
+          let _ = SuperCalls {
+            lowered: None,
+            inits: &inits,
+            arrows: 0,
+          }
+          .visit_statements(constructor.body_mut(), &mut Path::new());
+        } else {
+          util::prepend(constructor.body_list_mut(), inits);
+        }
+        Some(constructor)
+      }
+      // `constructor(...args) { super(...args); }`, initializing the fields after the call.
+      None if class.super_ref().is_some() => {
+        let args = self.cx.fresh("args");
+        let rest = FormalParameter::new(Declaration::new(Identifier::from(&*args), None), true);
+        let mut body = vec![Call::new(
+          Node::super_ref(),
+          vec![Spread::new(util::ident(&args)).into()],
+        )
+        .into()];
+        body.extend(inits);
+        Some(FunctionExpr::new(None, vec![rest], body))
+      }
+      None => Some(FunctionExpr::new(None, Vec::new(), inits)),
+    };
+    let kept = Class::new(
+      class.name().map(Sym::from),
+      class.super_ref().cloned(),
+      constructor,
+      methods,
+    );
+    Some((kept, prefix, statics))
+  }
+
+  /// Creates the expression defining a kept class and its static members:
+  /// `(_A = class A {}, _defineProperty(_A, "b", 1), _A)`.
+  fn assemble(
+    &mut self,
+    class: Class,
+    name: Option<&str>,
+    mut exprs: Vec<Node>,
+    statics: Vec<Static>,
+  ) -> Node {
+    if statics.is_empty() {
+      exprs.push(Node::ClassExpr(class));
+      return util::sequence(exprs);
+    }
+    let temp = self.scopes.temp(self.cx, name.unwrap_or("class"));
+    exprs.push(util::assign(util::ident(&temp), Node::ClassExpr(class)));
+    let mut statics = self.statics(statics, &temp);
+    // The static members are outside of the class, where its name is not bound yet.
+    if let Some(name) = name {
+      for node in &mut statics {
+        let _ = Renamer {
+          from: name,
+          to: &temp,
+        }
+        .visit_node(node, &mut Path::new());
+      }
+    }
+    exprs.extend(statics);
+    exprs.push(util::ident(&temp));
+    util::sequence(exprs)
+  }
+}
+
+impl VisitorMut for Lowering<'_> {
+  util::function_scopes!();
+
+  fn visit_node(&mut self, node: &mut Node, path: &mut KindPath) -> Flow {
+    visit_mut::walk_node(self, node, path)?;
+    let lowered = match node {
+      Node::ClassDecl(class) if !has_private(class) => self.declaration(class),
+      Node::ClassExpr(class) if !has_private(class) => self.expression(class),
+      Node::Export(ExportDecl::DefaultClass(class), _) if !has_private(class) => {
+        self.default_export(class)
+      }
+      _ => None,
+    };
+    if let Some(lowered) = lowered {
+      *node = lowered;
+    }
+    Flow::Continue(())
+  }
+}
+
+/// Returns `true` if a class has private members, which are not lowered.
+pub(super) fn has_private(class: &Class) -> bool {
+  class
+    .elements()
+    .iter()
+    .any(|element| matches!(element.name(), Some(ClassElementName::PrivateName(_))))
+}
+
+/// Creates `let name = init;`.
+fn let_declaration(name: &str, init: Node) -> Node {
+  DeclarationList::Let(vec![Declaration::new(Identifier::from(name), Some(init))].into()).into()
+}
+
+/// Gets the object `super` properties are read from: the prototype of the parent class for
+/// instance members, the parent class for static ones.
+fn home(parent: Option<&str>, is_static: bool) -> Node {
+  match (parent, is_static) {
+    (Some(parent), false) => util::member(util::ident(parent), "prototype"),
+    (Some(parent), true) => util::ident(parent),
+    (None, false) => util::member(util::ident("Object"), "prototype"),
+    (None, true) => util::member(util::ident("Function"), "prototype"),
+  }
+}
+
+/// Creates the array of descriptors passed to `_createClass`.
+fn descriptors(descriptors: Vec<Descriptor>) -> Node {
+  ArrayDecl::from(
+    descriptors
+      .into_iter()
+      .map(Descriptor::into_node)
+      .collect::<Vec<_>>(),
+  )
+  .into()
+}
+
+/// Gets the expression of the key of a method.
+fn key(name: &PropertyName) -> Node {
+  match name {
+    PropertyName::Literal(name) => util::string(name),
+    PropertyName::Computed(key) => key.clone(),
+  }
+}
+
+/// Rewrites the `super` property accesses of a member, reading them from its home object and
+/// calling them with `this`.
+struct SuperMembers<'a> {
+  base: &'a Node,
+}
+
+impl VisitorMut for SuperMembers<'_> {
+  fn visit_node(&mut self, node: &mut Node, path: &mut KindPath) -> Flow {
+    match node {
+      // Functions and classes have their own `super`.
+      Node::FunctionDecl(_) | Node::FunctionExpr(_) | Node::ClassDecl(_) | Node::ClassExpr(_) => {
+        return Flow::Continue(())
+      }
+      Node::GetConstField(get) if matches!(get.obj(), Node::Super(_)) => {
+        *get.obj_mut() = self.base.clone();
+        return Flow::Continue(());
+      }
+      Node::GetField(get) if matches!(get.obj(), Node::Super(_)) => {
+        *get.obj_mut() = self.base.clone();
+        return self.visit_node(get.field_mut(), path);
+      }
+      _ => {}
+    }
+    let method = matches!(
+      node,
+      Node::Call(call) if matches!(
+        call.expr(),
+        Node::GetConstField(get) if matches!(get.obj(), Node::Super(_))
+      ) || matches!(
+        call.expr(),
+        Node::GetField(get) if matches!(get.obj(), Node::Super(_))
+      )
+    );
+    visit_mut::walk_node(self, node, path)?;
+    if let (true, Node::Call(call)) = (method, &mut *node) {
+      let mut args = vec![Node::this()];
+      args.extend(util::take_all(call.args_mut()));
+      *node = util::call(util::member(util::take(call.expr_mut()), "call"), args);
+    }
+    Flow::Continue(())
+  }
+}
+
+/// Initializes the fields of an instance after each `super(...)` call of a constructor, and
+/// calls the parent constructor as a function when the class is lowered.
+struct SuperCalls<'a> {
+  /// The variable holding the parent class and the one holding the instance, for lowered
+  /// classes.
+  lowered: Option<(&'a str, &'a str)>,
+  inits: &'a [Node],
+  arrows: usize,
+}
+
+impl VisitorMut for SuperCalls<'_> {
+  fn visit_node(&mut self, node: &mut Node, path: &mut KindPath) -> Flow {
+    match node {
+      Node::FunctionDecl(_) | Node::FunctionExpr(_) | Node::ClassDecl(_) | Node::ClassExpr(_) => {
+        Flow::Continue(())
+      }
+      Node::ArrowFunctionDecl(_) => {
+        self.arrows += 1;
+        let flow = visit_mut::walk_node(self, node, path);
+        self.arrows -= 1;
+        flow
+      }
+      Node::Return(stmt) if stmt.expr().is_none() && self.arrows == 0 => {
+        if let Some((_, this)) = self.lowered {
+          *node = Return::new(Some(util::ident(this))).into();
+        }
+        Flow::Continue(())
+      }
+      Node::Call(call) if matches!(call.expr(), Node::Super(_)) => {
+        for arg in call.args_mut() {
+          self.visit_node(arg, path)?;
+        }
+        let args = util::take_all(call.args_mut());
+        let mut exprs = Vec::new();
+        match self.lowered {
+          Some((parent, this)) => {
+            let mut args = args;
+            args.insert(0, Node::this());
+            let call = util::call(util::member(util::ident(parent), "call"), args);
+            exprs.push(util::assign(
+              util::ident(this),
+              util::binary(LogOp::Or, call, Node::this()),
+            ));
+            exprs.extend(self.inits.iter().cloned());
+            exprs.push(util::ident(this));
+          }
+          None => {
+            exprs.push(Call::new(Node::super_ref(), args).into());
+            exprs.extend(self.inits.iter().cloned());
+            exprs.push(Node::this());
+          }
+        }
+        *node = util::sequence(exprs);
+        Flow::Continue(())
+      }
+      _ => visit_mut::walk_node(self, node, path),
+    }
+  }
+}
+
+/// Renames the references to a name.
+struct Renamer<'a> {
+  from: &'a str,
+  to: &'a str,
+}
+
+impl VisitorMut for Renamer<'_> {
+  fn visit_identifier(&mut self, ident: &mut Identifier, _path: &mut KindPath) -> Flow {
+    if ident.name() == self.from {
+      *ident = Identifier::from(self.to);
+    }
+    Flow::Continue(())
+  }
+}
@@ -0,0 +1,408 @@
+//! Destructuring declarations, assignments and parameters.
+//!
+//! A pattern is flattened into bindings of its names, reading the value through a temporary
+//! variable: `var { a, b: [c] } = f();` becomes `var _f = f(), a = _f.a, c = _slicedToArray(_f.b,
+//! 1)[0];`. An assignment becomes a comma expression giving the assigned value, and the
+//! patterns of parameters, `catch` clauses and loop heads are bound in the body instead.
+//!
+//! When only [`Transform::ObjectRestSpread`] runs, the patterns with rest properties are the only
+//! ones lowered.
+
+use super::{
+  helpers::Helper,
+  util::{self, Scopes},
+  Context, Options, Transform,
+};
+use crate::syntax::{
+  ast::{
+    node::{
+      ArrayDecl, ArrayPattern, ArrowFunctionDecl, Assign, Catch, Declaration, DeclarationList,
+      FormalParameter, FunctionDecl, FunctionExpr, Identifier, IterableLoopInitializer,
+      ObjectPattern, Pattern, PatternElement, PropertyName, StatementList,
+    },
+    visitor::{visit_mut, Flow, KindPath, Path, VisitorMut},
+    Node,
+  },
+  lexer::is_identifier_name,
+};
+use std::mem;
+
+/// Lowers the destructuring patterns of a program.
+pub(super) fn run(body: &mut StatementList, cx: &mut Context, options: &Options) {
+  let mut lowering = Lowering {
+    cx,
+    scopes: Scopes::default(),
+    all: options.is_enabled(Transform::Destructuring),
+  };
+  lowering.scopes.enter();
+  let _ = lowering.visit_statements(body.items_mut(), &mut Path::new());
+  lowering.scopes.exit(body);
+}
+
+/// The bindings a pattern is flattened into, in order.
+struct Steps {
+  /// Whether the bindings are declarations, whose temporary variables are declared with them,
+  /// rather than assignments.
+  declare: bool,
+  bindings: Vec<Declaration>,
+}
+
+impl Steps {
+  fn new(declare: bool) -> Self {
+    Self {
+      declare,
+      bindings: Vec::new(),
+    }
+  }
+}
+
+/// Rewrites the patterns from the leaves up.
+struct Lowering<'cx> {
+  cx: &'cx mut Context,
+  scopes: Scopes,
+  /// Whether all patterns are lowered, and not only the ones with rest properties.
+  all: bool,
+}
+
+impl Lowering<'_> {
+  /// Returns `true` if a pattern is lowered.
+  fn lowers(&self, pattern: &Pattern) -> bool {
+    match pattern {
+      Pattern::Identifier(_) | Pattern::Member(_) => false,
+      _ if self.all => true,
+      Pattern::Object(obj) => {
+        obj.rest().is_some()
+          || obj
+            .properties()
+            .iter()
+            .any(|property| self.lowers(property.value().target()))
+      }
+      Pattern::Array(arr) => {
+        arr
+          .elements()
+          .iter()
+          .flatten()
+          .any(|element| self.lowers(element.target()))
+          || arr.rest().is_some_and(|rest| self.lowers(rest))
+      }
+    }
+  }
+
+  /// Stores a value in a new temporary variable, returning a read of the variable.
+  fn temp(&mut self, steps: &mut Steps, base: &str, value: Node) -> Node {
+    let name = if steps.declare {
+      self.cx.fresh(base)
+    } else {
+      self.scopes.temp(self.cx, base)
+    };
+    steps.bindings.push(Declaration::new(
+      Identifier::from(name.clone()),
+      Some(value),
+    ));
+    util::ident(&name)
+  }
+
+  /// Gets an expression reading a value several times, storing it in a temporary variable
+  /// unless it is a name the pattern does not assign.
+  fn source(&mut self, pattern: &Pattern, value: Node, steps: &mut Steps) -> Node {
+    let reusable = match &value {
+      Node::Identifier(ident) => !pattern.bound_names().contains(&ident.name()),
+      Node::This(_) => true,
+      _ => false,
+    };
+    if reusable {
+      value
+    } else {
+      let base = util::base_name(&value);
+      self.temp(steps, &base, value)
+    }
+  }
+
+  /// Binds the names of a pattern to the parts of a value.
+  fn destructure(&mut self, pattern: &Pattern, value: Node, steps: &mut Steps) {
+    if !self.lowers(pattern) {
+      steps
+        .bindings
+        .push(Declaration::new(pattern.clone(), Some(value)));
+      return;
+    }
+    match pattern {
+      Pattern::Object(obj) => self.object(pattern, obj, value, steps),
+      Pattern::Array(arr) => self.array(arr, value, steps),
+      Pattern::Identifier(_) | Pattern::Member(_) => unreachable!("names are not lowered"),
+    }
+  }
+
+  /// Binds the properties of an object pattern.
+  fn object(&mut self, pattern: &Pattern, obj: &ObjectPattern, value: Node, steps: &mut Steps) {
+    if obj.properties().is_empty() && obj.rest().is_none() {
+      let check = util::call(
+        self.cx.helper(Helper::ObjectDestructuringEmpty),
+        vec![value],
+      );
+      self.temp(steps, "ref", check);
+      return;
+    }
+    // A single property is read from the value itself.
+    let source = if obj.properties().len() == 1 && obj.rest().is_none() {
+      value
+    } else {
+      self.source(pattern, value, steps)
+    };
+    let mut excluded = Vec::new();
+    for property in obj.properties() {
+      let access = match property.key() {
+        PropertyName::Literal(name) => {
+          excluded.push(util::string(name));
+          if is_identifier_name(name) {
+            util::member(source.clone(), name)
+          } else {
+            util::index(source.clone(), util::string(name))
+          }
+        }
+        PropertyName::Computed(key) => {
+          // The key is computed once, for the access and the rest properties.
+          let key = match key {
+            Node::Const(_, _) => key.clone(),
+            _ if obj.rest().is_some() => self.temp(steps, "key", key.clone()),
+            _ => key.clone(),
+          };
+          excluded.push(key.clone());
+          util::index(source.clone(), key)
+        }
+      };
+      self.element(property.value(), access, steps);
+    }
+    if let Some(rest) = obj.rest() {
+      let helper = self.cx.helper(Helper::ObjectWithoutProperties);
+      let value = util::call(helper, vec![source, ArrayDecl::from(excluded).into()]);
+      self.destructure(rest, value, steps);
+    }
+  }
+
+  /// Binds the elements of an array pattern, after collecting the items of the iterable.
+  fn array(&mut self, arr: &ArrayPattern, value: Node, steps: &mut Steps) {
+    let count = arr.elements().len();
+    let base = util::base_name(&value);
+    let items = match arr.rest() {
+      Some(_) => util::call(self.cx.helper(Helper::ToArray), vec![value]),
+      None => util::call(
+        self.cx.helper(Helper::SlicedToArray),
+        vec![value, util::number(count)],
+      ),
+    };
+    // A single element is read from the items themselves.
+    let source = if count == 1 && arr.rest().is_none() && arr.elements()[0].is_some() {
+      items
+    } else {
+      self.temp(steps, &base, items)
+    };
+    for (index, element) in arr.elements().iter().enumerate() {
+      if let Some(element) = element {
+        let access = util::index(source.clone(), util::number(index));
+        self.element(element, access, steps);
+      }
+    }
+    if let Some(rest) = arr.rest() {
+      let value = util::call(util::member(source, "slice"), vec![util::number(count)]);
+      self.destructure(rest, value, steps);
+    }
+  }
+
+  /// Binds an element of a pattern to a part of the value, or to its default value when the
+  /// part is `undefined`.
+  fn element(&mut self, element: &PatternElement, access: Node, steps: &mut Steps) {
+    let value = match element.default() {
+      Some(default) => {
+        let base = util::base_name(&access);
+        let part = self.temp(steps, &base, access);
+        util::conditional(util::is_undefined(part.clone()), default.clone(), part)
+      }
+      None => access,
+    };
+    self.destructure(element.target(), value, steps);
+  }
+
+  /// Lowers the patterns of a list of declarations, keeping its kind.
+  fn declarations(&mut self, list: &DeclarationList) -> DeclarationList {
+    let mut steps = Steps::new(true);
+    for declaration in list.as_ref() {
+      match declaration.init() {
+        Some(init) if self.lowers(declaration.target()) => {
+          self.destructure(declaration.target(), init.clone(), &mut steps)
+        }
+        _ => steps.bindings.push(declaration.clone()),
+      }
+    }
+    let bindings = steps.bindings.into();
+    match list {
+      DeclarationList::Var(_) => DeclarationList::Var(bindings),
+      DeclarationList::Let(_) => DeclarationList::Let(bindings),
+      DeclarationList::Const(_) => DeclarationList::Const(bindings),
+    }
+  }
+
+  /// Lowers a destructuring assignment. As an expression, it gives the assigned value.
+  fn assignment(&mut self, pattern: &Pattern, value: Node, statement: bool) -> Node {
+    let mut steps = Steps::new(false);
+    let result = if statement {
+      self.destructure(pattern, value, &mut steps);
+      None
+    } else {
+      let value = self.source(pattern, value, &mut steps);
+      self.destructure(pattern, value.clone(), &mut steps);
+      Some(value)
+    };
+    let mut exprs = steps
+      .bindings
+      .iter()
+      .map(|binding| {
+        let value = binding.init().cloned().expect("bindings have a value");
+        Assign::new(binding.target().clone(), value).into()
+      })
+      .collect::<Vec<Node>>();
+    exprs.extend(result);
+    util::sequence(exprs)
+  }
+
+  /// Lowers a declaration or an assignment statement.
+  fn statement(&mut self, node: Node) -> Node {
+    if let Node::DeclarationList(list, _) = &node {
+      self.declarations(list).into()
+    } else if let Node::Assign(assign) = &node {
+      self.assignment(assign.lhs(), assign.rhs().clone(), true)
+    } else {
+      node
+    }
+  }
+
+  /// Binds the pattern of a `for ... in` or `for ... of` loop at the start of its body.
+  fn head(&mut self, init: &mut IterableLoopInitializer, body: &mut Node) {
+    let name = match init {
+      IterableLoopInitializer::Pattern(_) => self.scopes.temp(self.cx, "ref"),
+      _ => self.cx.fresh("ref"),
+    };
+    let target = Pattern::from(Identifier::from(name.clone()));
+    let replacement = match init {
+      IterableLoopInitializer::Var(_) => IterableLoopInitializer::Var(target),
+      IterableLoopInitializer::Let(_) => IterableLoopInitializer::Let(target),
+      IterableLoopInitializer::Const(_) => IterableLoopInitializer::Const(target),
+      IterableLoopInitializer::Pattern(_) => IterableLoopInitializer::Pattern(target),
+    };
+    let original = mem::replace(init, replacement);
+    let first = self.statement(util::bind(&original, util::ident(&name)));
+    *body = util::with_first(util::take(body), first);
+  }
+
+  /// Replaces the patterns of parameters with names, returning the declarations binding the
+  /// patterns at the start of the body.
+  fn params(&mut self, params: &mut [FormalParameter]) -> Vec<Node> {
+    let mut prologue = Vec::new();
+    for param in params {
+      if !self.lowers(param.target()) {
+        continue;
+      }
+      let name = self.cx.fresh("ref");
+      let pattern = mem::replace(param.target_mut(), Identifier::from(name.clone()).into());
+      let declaration = Declaration::new(pattern, Some(util::ident(&name)));
+      let list = DeclarationList::Var(vec![declaration].into());
+      prologue.push(self.declarations(&list).into());
+    }
+    prologue
+  }
+}
+
+impl VisitorMut for Lowering<'_> {
+  fn visit_statements(&mut self, statements: &mut [Node], path: &mut KindPath) -> Flow {
+    for statement in statements {
+      match statement {
+        // The value of an assignment statement is not used.
+        Node::Assign(assign) if self.lowers(assign.lhs()) => {
+          visit_mut::walk_node(self, statement, path)?;
+          *statement = self.statement(util::take(statement));
+        }
+        _ => self.visit_node(statement, path)?,
+      }
+    }
+    Flow::Continue(())
+  }
+
+  fn visit_node(&mut self, node: &mut Node, path: &mut KindPath) -> Flow {
+    visit_mut::walk_node(self, node, path)?;
+    let lowered = match node {
+      Node::DeclarationList(list, _) if list.as_ref().iter().any(|d| self.lowers(d.target())) => {
+        self.declarations(list).into()
+      }
+      Node::Assign(assign) if self.lowers(assign.lhs()) => {
+        let value = util::take(assign.rhs_mut());
+        self.assignment(assign.lhs(), value, false)
+      }
+      Node::ForInLoop(stmt) if self.lowers(stmt.init().pattern()) => {
+        let mut body = util::take(stmt.body_mut());
+        self.head(stmt.init_mut(), &mut body);
+        *stmt.body_mut() = body;
+        return Flow::Continue(());
+      }
+      Node::ForOfLoop(stmt) if self.lowers(stmt.init().pattern()) => {
+        let mut body = util::take(stmt.body_mut());
+        self.head(stmt.init_mut(), &mut body);
+        *stmt.body_mut() = body;
+        return Flow::Continue(());
+      }
+      _ => return Flow::Continue(()),
+    };
+    *node = lowered;
+    Flow::Continue(())
+  }
+
+  fn visit_catch(&mut self, catch: &mut Catch, path: &mut KindPath) -> Flow {
+    visit_mut::walk_catch(self, catch, path)?;
+    let parameter = match catch.parameter_mut() {
+      Some(parameter) if self.lowers(parameter) => parameter,
+      _ => return Flow::Continue(()),
+    };
+    let name = self.cx.fresh("ref");
+    let pattern = mem::replace(parameter, Identifier::from(name.clone()).into());
+    let declaration = Declaration::new(pattern, Some(util::ident(&name)));
+    let binding = self.declarations(&DeclarationList::Let(vec![declaration].into()));
+    catch
+      .block_mut()
+      .statement_list_mut()
+      .replace_items(|mut items| {
+        items.insert(0, binding.into());
+        items
+      });
+    Flow::Continue(())
+  }
+
+  fn visit_function_decl(&mut self, decl: &mut FunctionDecl, path: &mut KindPath) -> Flow {
+    self.scopes.enter();
+    let flow = visit_mut::walk_function_decl(self, decl, path);
+    let prologue = self.params(decl.parameters_mut());
+    util::prepend(decl.body_list_mut(), prologue);
+    self.scopes.exit(decl.body_list_mut());
+    flow
+  }
+
+  fn visit_function_expr(&mut self, expr: &mut FunctionExpr, path: &mut KindPath) -> Flow {
+    self.scopes.enter();
+    let flow = visit_mut::walk_function_expr(self, expr, path);
+    let prologue = self.params(expr.parameters_mut());
+    util::prepend(expr.body_list_mut(), prologue);
+    self.scopes.exit(expr.body_list_mut());
+    flow
+  }
+
+  fn visit_arrow_function_decl(
+    &mut self,
+    arrow: &mut ArrowFunctionDecl,
+    path: &mut KindPath,
+  ) -> Flow {
+    self.scopes.enter();
+    let flow = visit_mut::walk_arrow_function_decl(self, arrow, path);
+    let prologue = self.params(arrow.params_mut());
+    util::prepend(arrow.body_list_mut(), prologue);
+    self.scopes.exit(arrow.body_list_mut());
+    flow
+  }
+}
@@ -0,0 +1,58 @@
+//! The exponentiation operator: `a ** b` becomes `Math.pow(a, b)`.
+
+use super::{
+  util::{self, Scopes},
+  Context,
+};
+use crate::syntax::ast::{
+  node::StatementList,
+  operator::{self, AssignOp, NumOp},
+  visitor::{visit_mut, Flow, KindPath, Path, VisitorMut},
+  Node,
+};
+
+/// Lowers the exponentiations of a program.
+pub(super) fn run(body: &mut StatementList, cx: &mut Context) {
+  let mut lowering = Lowering {
+    cx,
+    scopes: Scopes::default(),
+  };
+  lowering.scopes.enter();
+  let _ = lowering.visit_statements(body.items_mut(), &mut Path::new());
+  lowering.scopes.exit(body);
+}
+
+/// Rewrites the exponentiations from the leaves up.
+struct Lowering<'cx> {
+  cx: &'cx mut Context,
+  scopes: Scopes,
+}
+
+impl VisitorMut for Lowering<'_> {
+  util::function_scopes!();
+
+  fn visit_node(&mut self, node: &mut Node, path: &mut KindPath) -> Flow {
+    visit_mut::walk_node(self, node, path)?;
+    if let Node::BinOp(op) = node {
+      let lowered = match op.op() {
+        operator::BinOp::Num(NumOp::Exp) => pow(util::take(op.lhs_mut()), util::take(op.rhs_mut())),
+        // `a.b **= c` reads `a` once: `(_a = a).b = Math.pow(_a.b, c)`.
+        operator::BinOp::Assign(AssignOp::Exp) => {
+          let (first, second) = self.scopes.reference(self.cx, util::take(op.lhs_mut()));
+          util::assign(first, pow(second, util::take(op.rhs_mut())))
+        }
+        _ => return Flow::Continue(()),
+      };
+      *node = lowered;
+    }
+    Flow::Continue(())
+  }
+}
+
+/// Creates `Math.pow(base, exponent)`.
+fn pow(base: Node, exponent: Node) -> Node {
+  util::call(
+    util::member(util::ident("Math"), "pow"),
+    vec![base, exponent],
+  )
+}
@@ -0,0 +1,86 @@
+//! `for ... of` loops.
+//!
+//! The loop steps through the iterator of the iterable, which is closed with its `return`
+//! method when the loop exits early, on `break`, `return` or an exception: `for (const x of xs)
+//! {}` becomes `{ var _iterator = _createForOfIterator(xs), _step; try { for (; !(_step =
+//! _iterator.n()).done; ) { const x = _step.value; } } catch (_err) { _iterator.e(_err); }
+//! finally { _iterator.f(); } }`. The labels of the loop stay on the `for` loop. Without
+//! `Symbol.iterator`, the helper steps through arrays and array-like objects.
+
+use super::{helpers::Helper, util, Context};
+use crate::syntax::ast::{
+  node::{Block, Catch, ForLoop, Identifier, StatementList, Try, UnaryOp},
+  operator,
+  visitor::{visit_mut, Flow, KindPath, Path, VisitorMut},
+  Node,
+};
+
+/// Lowers the `for ... of` loops of a program.
+pub(super) fn run(body: &mut StatementList, cx: &mut Context) {
+  let _ = Lowering { cx }.visit_statements(body.items_mut(), &mut Path::new());
+}
+
+/// Rewrites the loops from the leaves up.
+struct Lowering<'cx> {
+  cx: &'cx mut Context,
+}
+
+impl VisitorMut for Lowering<'_> {
+  fn visit_node(&mut self, node: &mut Node, path: &mut KindPath) -> Flow {
+    let target = match labelled_loop(node) {
+      Some(target) => target,
+      None => return visit_mut::walk_node(self, node, path),
+    };
+    let stmt = match target {
+      Node::ForOfLoop(stmt) => stmt,
+      _ => unreachable!("the target is a `for ... of` loop"),
+    };
+    visit_mut::walk_for_of_loop(self, stmt, path)?;
+
+    let iterator = self.cx.fresh("iterator");
+    let step = self.cx.fresh("step");
+    let error = self.cx.fresh("err");
+    let helper = self.cx.helper(Helper::CreateForOfIterator);
+    let declaration = util::var(vec![
+      (
+        iterator.clone(),
+        Some(util::call(helper, vec![util::take(stmt.iterable_mut())])),
+      ),
+      (step.clone(), None),
+    ]);
+    let next = util::assign(
+      util::ident(&step),
+      util::call(util::member(util::ident(&iterator), "n"), Vec::new()),
+    );
+    let condition = UnaryOp::new(operator::UnaryOp::Not, util::member(next, "done")).into();
+    let item = util::member(util::ident(&step), "value");
+    let body = util::with_first(util::take(stmt.body_mut()), util::bind(stmt.init(), item));
+    *target = ForLoop::new(None, Some(condition), None, body).into();
+
+    let method = |name: &str, args| util::call(util::member(util::ident(&iterator), name), args);
+    let catch = Catch::new(
+      Some(Identifier::from(error.clone())),
+      Block::from(vec![method("e", vec![util::ident(&error)])]),
+    );
+    let finally = Block::from(vec![method("f", Vec::new())]);
+    let guarded = Try::new(
+      Block::from(vec![util::take(node)]),
+      Some(catch),
+      Some(finally.into()),
+    );
+    *node = Block::from(vec![declaration, guarded.into()]).into();
+    Flow::Continue(())
+  }
+}
+
+/// Finds the `for ... of` loop a statement is, behind its labels.
+fn labelled_loop(node: &mut Node) -> Option<&mut Node> {
+  // Async iteration needs the generator transforms.
+  if matches!(node, Node::ForOfLoop(stmt) if !stmt.is_await()) {
+    return Some(node);
+  }
+  match node {
+    Node::Labelled(labelled) => labelled_loop(labelled.statement_mut()),
+    _ => None,
+  }
+}
@@ -0,0 +1,314 @@
+//! The runtime functions the lowered code calls, added to the programs using them.
+//!
+//! Each helper is written in ES5, and declared under a name that clashes with no other name of
+//! the program: its source names the helpers it calls by their usual name, such as
+//! `_defineProperty`, which is replaced when it is declared.
+
+use crate::syntax::{
+  ast::{
+    node::{FunctionDecl, Identifier},
+    visitor::{visit_mut, Flow, KindPath, Path, VisitorMut},
+    Node,
+  },
+  parser::Parser,
+};
+use std::collections::BTreeMap;
+
+/// A runtime helper.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(super) enum Helper {
+  ClassCallCheck,
+  CreateClass,
+  CreateForOfIterator,
+  DefineProperties,
+  DefineProperty,
+  Inherits,
+  ObjectDestructuringEmpty,
+  ObjectSpread,
+  ObjectWithoutProperties,
+  SetPrototypeOf,
+  SlicedToArray,
+  TaggedTemplateLiteral,
+  ToArray,
+  ToConsumableArray,
+}
+
+impl Helper {
+  /// All the helpers.
+  const ALL: [Self; 14] = [
+    Self::ClassCallCheck,
+    Self::CreateClass,
+    Self::CreateForOfIterator,
+    Self::DefineProperties,
+    Self::DefineProperty,
+    Self::Inherits,
+    Self::ObjectDestructuringEmpty,
+    Self::ObjectSpread,
+    Self::ObjectWithoutProperties,
+    Self::SetPrototypeOf,
+    Self::SlicedToArray,
+    Self::TaggedTemplateLiteral,
+    Self::ToArray,
+    Self::ToConsumableArray,
+  ];
+
+  /// Gets the usual name of the helper, without its leading underscore.
+  pub(super) fn name(self) -> &'static str {
+    match self {
+      Self::ClassCallCheck => "classCallCheck",
+      Self::CreateClass => "createClass",
+      Self::CreateForOfIterator => "createForOfIterator",
+      Self::DefineProperties => "defineProperties",
+      Self::DefineProperty => "defineProperty",
+      Self::Inherits => "inherits",
+      Self::ObjectDestructuringEmpty => "objectDestructuringEmpty",
+      Self::ObjectSpread => "objectSpread",
+      Self::ObjectWithoutProperties => "objectWithoutProperties",
+      Self::SetPrototypeOf => "setPrototypeOf",
+      Self::SlicedToArray => "slicedToArray",
+      Self::TaggedTemplateLiteral => "taggedTemplateLiteral",
+      Self::ToArray => "toArray",
+      Self::ToConsumableArray => "toConsumableArray",
+    }
+  }
+
+  /// Gets the helpers called by this one.
+  pub(super) fn dependencies(self) -> &'static [Self] {
+    match self {
+      Self::CreateClass => &[Self::DefineProperties],
+      Self::Inherits => &[Self::SetPrototypeOf],
+      Self::ObjectSpread => &[Self::DefineProperty],
+      Self::ToArray => &[Self::ToConsumableArray],
+      _ => &[],
+    }
+  }
+
+  /// Gets the source of the helper, a function declaration.
+  fn source(self) -> &'static str {
+    match self {
+      Self::ClassCallCheck => {
+        r#"function _classCallCheck(instance, Constructor) {
+          if (!(instance instanceof Constructor)) {
+            throw new TypeError("Cannot call a class as a function");
+          }
+        }"#
+      }
+      Self::CreateClass => {
+        r#"function _createClass(Constructor, protoProps, staticProps) {
+          if (protoProps) _defineProperties(Constructor.prototype, protoProps);
+          if (staticProps) _defineProperties(Constructor, staticProps);
+          return Constructor;
+        }"#
+      }
+      Self::CreateForOfIterator => {
+        r#"function _createForOfIterator(o) {
+          var it;
+          if (typeof Symbol !== "undefined" && o != null && o[Symbol.iterator] != null) {
+            it = o[Symbol.iterator]();
+          } else if (o != null && typeof o.length === "number") {
+            var i = 0;
+            it = {
+              next: function () {
+                if (i < o.length) return { done: false, value: o[i++] };
+                return { done: true, value: void 0 };
+              }
+            };
+          } else {
+            throw new TypeError("Invalid attempt to iterate non-iterable instance");
+          }
+          var normalCompletion = true, didErr = false, err;
+          return {
+            n: function () {
+              normalCompletion = true;
+              var step = it.next();
+              normalCompletion = step.done;
+              return step;
+            },
+            e: function (e) {
+              didErr = true;
+              err = e;
+            },
+            f: function () {
+              try {
+                if (!normalCompletion && it["return"] != null) it["return"]();
+              } finally {
+                if (didErr) throw err;
+              }
+            }
+          };
+        }"#
+      }
+      Self::DefineProperties => {
+        r#"function _defineProperties(target, props) {
+          for (var i = 0; i < props.length; i++) {
+            var descriptor = props[i];
+            descriptor.enumerable = false;
+            descriptor.configurable = true;
+            if ("value" in descriptor) descriptor.writable = true;
+            Object.defineProperty(target, descriptor.key, descriptor);
+          }
+        }"#
+      }
+      Self::DefineProperty => {
+        r#"function _defineProperty(obj, key, value) {
+          if (key in obj) {
+            Object.defineProperty(obj, key, {
+              value: value,
+              enumerable: true,
+              configurable: true,
+              writable: true
+            });
+          } else {
+            obj[key] = value;
+          }
+          return obj;
+        }"#
+      }
+      Self::Inherits => {
+        r#"function _inherits(subClass, superClass) {
+          if (typeof superClass !== "function" && superClass !== null) {
+            throw new TypeError("Super expression must either be null or a function");
+          }
+          subClass.prototype = Object.create(superClass && superClass.prototype, {
+            constructor: { value: subClass, writable: true, configurable: true }
+          });
+          if (superClass) _setPrototypeOf(subClass, superClass);
+        }"#
+      }
+      Self::ObjectDestructuringEmpty => {
+        r#"function _objectDestructuringEmpty(obj) {
+          if (obj == null) throw new TypeError("Cannot destructure " + obj);
+          return obj;
+        }"#
+      }
+      Self::ObjectSpread => {
+        r#"function _objectSpread(target) {
+          for (var i = 1; i < arguments.length; i++) {
+            var source = arguments[i] != null ? Object(arguments[i]) : {};
+            var keys = Object.keys(source);
+            if (typeof Object.getOwnPropertySymbols === "function") {
+              keys = keys.concat(Object.getOwnPropertySymbols(source).filter(function (symbol) {
+                return Object.getOwnPropertyDescriptor(source, symbol).enumerable;
+              }));
+            }
+            for (var j = 0; j < keys.length; j++) {
+              _defineProperty(target, keys[j], source[keys[j]]);
+            }
+          }
+          return target;
+        }"#
+      }
+      Self::ObjectWithoutProperties => {
+        r#"function _objectWithoutProperties(source, excluded) {
+          if (source == null) throw new TypeError("Cannot destructure " + source);
+          source = Object(source);
+          var target = {};
+          var keys = Object.keys(source);
+          if (typeof Object.getOwnPropertySymbols === "function") {
+            keys = keys.concat(Object.getOwnPropertySymbols(source));
+          }
+          for (var i = 0; i < excluded.length; i++) {
+            if (typeof excluded[i] !== "symbol") excluded[i] = String(excluded[i]);
+          }
+          for (var j = 0; j < keys.length; j++) {
+            var key = keys[j];
+            if (excluded.indexOf(key) >= 0) continue;
+            if (!Object.prototype.propertyIsEnumerable.call(source, key)) continue;
+            target[key] = source[key];
+          }
+          return target;
+        }"#
+      }
+      Self::SetPrototypeOf => {
+        r#"function _setPrototypeOf(o, p) {
+          _setPrototypeOf = Object.setPrototypeOf || function (o, p) {
+            o.__proto__ = p;
+            return o;
+          };
+          return _setPrototypeOf(o, p);
+        }"#
+      }
+      Self::SlicedToArray => {
+        r#"function _slicedToArray(arr, i) {
+          if (Array.isArray(arr)) return arr;
+          if (typeof Symbol !== "undefined" && arr != null && arr[Symbol.iterator] != null) {
+            var items = [];
+            for (var iterator = arr[Symbol.iterator](), step; items.length < i && !(step = iterator.next()).done; ) {
+              items.push(step.value);
+            }
+            return items;
+          }
+          if (arr != null && typeof arr.length === "number") return arr;
+          throw new TypeError("Invalid attempt to destructure non-iterable instance");
+        }"#
+      }
+      Self::TaggedTemplateLiteral => {
+        r#"function _taggedTemplateLiteral(strings, raw) {
+          if (!raw) raw = strings.slice(0);
+          return Object.freeze(Object.defineProperties(strings, {
+            raw: { value: Object.freeze(raw) }
+          }));
+        }"#
+      }
+      Self::ToArray => {
+        r#"function _toArray(arr) {
+          return Array.isArray(arr) ? arr : _toConsumableArray(arr);
+        }"#
+      }
+      Self::ToConsumableArray => {
+        r#"function _toConsumableArray(arr) {
+          if (Array.isArray(arr)) return arr.slice();
+          if (typeof Symbol !== "undefined" && arr != null && arr[Symbol.iterator] != null) {
+            var items = [];
+            for (var iterator = arr[Symbol.iterator](), step; !(step = iterator.next()).done; ) {
+              items.push(step.value);
+            }
+            return items;
+          }
+          if (arr != null && typeof arr.length === "number") return Array.prototype.slice.call(arr);
+          throw new TypeError("Invalid attempt to spread non-iterable instance");
+        }"#
+      }
+    }
+  }
+}
+
+/// Declares the helpers used by a program under the names they were given.
+pub(super) fn declarations(helpers: &BTreeMap<Helper, Box<str>>) -> Vec<Node> {
+  let mut renamer = Renamer {
+    names: Helper::ALL
+      .iter()
+      .filter_map(|helper| Some((format!("_{}", helper.name()), helpers.get(helper)?.clone())))
+      .collect(),
+  };
+  let mut declarations = Vec::new();
+  for &helper in helpers.keys() {
+    let mut script = Parser::new(helper.source().as_bytes())
+      .parse_script()
+      .expect("the source of a helper is valid");
+    let _ = renamer.visit_statements(script.items_mut(), &mut Path::new());
+    declarations.extend(script.items().iter().cloned());
+  }
+  declarations
+}
+
+/// Gives the helpers their names in the program.
+struct Renamer {
+  names: BTreeMap<String, Box<str>>,
+}
+
+impl VisitorMut for Renamer {
+  fn visit_identifier(&mut self, ident: &mut Identifier, _path: &mut KindPath) -> Flow {
+    if let Some(name) = self.names.get(ident.name().as_str()) {
+      *ident = Identifier::from(name.clone());
+    }
+    Flow::Continue(())
+  }
+
+  fn visit_function_decl(&mut self, decl: &mut FunctionDecl, path: &mut KindPath) -> Flow {
+    if let Some(name) = self.names.get(decl.name().as_str()) {
+      *decl.name_mut() = name.as_ref().into();
+    }
+    visit_mut::walk_function_decl(self, decl, path)
+  }
+}
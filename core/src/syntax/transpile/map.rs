@@ -0,0 +1,50 @@
+//! The source map of lowered code.
+//!
+//! The nodes created by the transforms have no spans, and the printer does not record where it
+//! writes the others, so the map is rebuilt from the tokens, see [`TokenMatcher`]. The code
+//! created by the transforms, such as the helpers, matches nothing and is left unmapped.
+
+use crate::syntax::{
+  lexer::token::TokenKind,
+  sourcemap::{
+    tokens::{tokens, TokenMatcher},
+    SourceMap, SourceMapBuilder,
+  },
+};
+
+/// The code printed at the start of the output, as it is printed alone.
+pub(super) struct Skipped {
+  /// The directives of the program, which come from the source.
+  pub(super) before: String,
+  /// The helpers and the variables added after the directives, which do not.
+  pub(super) skipped: String,
+}
+
+/// Maps lowered code to its source.
+pub(super) fn source_map(name: &str, source: &str, code: &str, skipped: &Skipped) -> SourceMap {
+  let mut builder = SourceMapBuilder::new();
+  let source_index = builder.add_source(name, Some(source));
+  let mut matcher = TokenMatcher::new(source);
+  let before = tokens(&skipped.before).len();
+  let prelude = tokens(&skipped.skipped).len();
+
+  for (position, token) in tokens(code).into_iter().enumerate() {
+    if (before..before + prelude).contains(&position) {
+      if position == before {
+        builder.add_unmapped(token.span().start());
+      }
+      continue;
+    }
+    match matcher.find(token.kind()) {
+      Some(matched) => {
+        let name = match matched.kind() {
+          TokenKind::Identifier(name) => Some(&**name),
+          _ => None,
+        };
+        builder.add_span(token.span(), source_index, matched.span(), name);
+      }
+      None => builder.add_unmapped(token.span().start()),
+    }
+  }
+  builder.build()
+}
@@ -0,0 +1,642 @@
+//! Downleveling: rewriting modern JavaScript into code older engines run, down to ES5.
+//!
+//! The [`Transpiler`] lowers the syntax an edition introduced into the constructs of the
+//! editions before it. Each [`Transform`] covers one feature and is enabled when the [`Target`]
+//! edition predates it, or by hand with [`Options::with_transform`]:
+//!
+//! ```
+//! use plastic::syntax::transpile::{transpile_script, Options, Target};
+//!
+//! let source = "const greet = (name = 'world') => `hello ${name}`;\n";
+//! let transpiled = transpile_script("greet.js", source, &Options::default()).unwrap();
+//! assert_eq!(
+//!   transpiled.code(),
+//!   "var greet = function (name) {\n  if (name === void 0) {\n    name = \"world\";\n  }\n  \
+//!    return \"hello \".concat(name);\n};\n"
+//! );
+//!
+//! let options = Options::default().with_target(Target::Es2015);
+//! let transpiled = transpile_script("pow.js", "x = y ** 2 ?? z;", &options).unwrap();
+//! assert_eq!(
+//!   transpiled.code(),
+//!   "var _ref;\nx = (_ref = Math.pow(y, 2)) !== null && _ref !== void 0 ? _ref : z;\n"
+//! );
+//! ```
+//!
+//! Some constructs are rewritten with the help of small runtime functions, such as
+//! `_classCallCheck` or `_slicedToArray`, which are added at the top of the output when they are
+//! used. They stand in for the iteration protocol with arrays and array-like objects when
+//! `Symbol.iterator` is missing. Names created by the transforms never clash with the names of
+//! the program.
+//!
+//! The lowering follows the language closely, with a few documented shortcuts:
+//!
+//!  - `let` and `const` become `var`, renamed where they would clash with another binding of
+//!    the function. Assigning a constant, and reading a binding before its declaration where the
+//!    scope analysis can tell, are reported as errors; reads from functions that may run later no
+//!    longer throw.
+//!  - Loops whose bindings are captured by closures run their body in a function, which gets
+//!    its own binding each iteration. Loops containing `yield` or `await` keep their body.
+//!  - Modules are left as they are: their imports and exports are kept for a bundler to
+//!    resolve.
+//!
+//! Generators, `async` functions and private class members have no transform: lowering them to
+//! an edition without them fails with [`TranspileError::Unsupported`], naming the construct.
+//!
+//! The [`Transpiled`] output comes with a [source map](crate::syntax::sourcemap) tracing the
+//! names and literals of the generated code back to the source.
+
+mod arrow;
+mod block_scoping;
+mod check;
+mod class;
+mod destructure;
+mod exponent;
+mod for_of;
+mod helpers;
+mod map;
+mod names;
+mod object;
+mod optional;
+mod params;
+mod spread;
+mod template;
+#[cfg(test)]
+mod tests;
+mod util;
+
+use self::{helpers::Helper, names::Names};
+use crate::syntax::{
+  ast::{module::Module, node::StatementList, Node, Span},
+  codegen::{self, Generator},
+  parser::{ParseError, Parser},
+  sourcemap::SourceMap,
+};
+use std::{collections::BTreeMap, error::Error as StdError, fmt, mem};
+
+/// An edition of the language, the oldest one the output of a [`Transpiler`] runs on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Target {
+  /// ECMAScript 5, from 2009.
+  Es5,
+  Es2015,
+  Es2016,
+  Es2017,
+  Es2018,
+  Es2019,
+  Es2020,
+  Es2021,
+  Es2022,
+}
+
+impl Target {
+  /// All the editions, from the oldest.
+  pub const ALL: [Self; 9] = [
+    Self::Es5,
+    Self::Es2015,
+    Self::Es2016,
+    Self::Es2017,
+    Self::Es2018,
+    Self::Es2019,
+    Self::Es2020,
+    Self::Es2021,
+    Self::Es2022,
+  ];
+
+  /// Gets the name of the edition, such as `es2015`.
+  pub fn name(self) -> &'static str {
+    match self {
+      Self::Es5 => "es5",
+      Self::Es2015 => "es2015",
+      Self::Es2016 => "es2016",
+      Self::Es2017 => "es2017",
+      Self::Es2018 => "es2018",
+      Self::Es2019 => "es2019",
+      Self::Es2020 => "es2020",
+      Self::Es2021 => "es2021",
+      Self::Es2022 => "es2022",
+    }
+  }
+
+  /// Finds an edition by its name, `es6` standing for `es2015`.
+  pub fn from_name(name: &str) -> Option<Self> {
+    let name = name.to_ascii_lowercase();
+    if name == "es6" {
+      return Some(Self::Es2015);
+    }
+    Self::ALL
+      .iter()
+      .copied()
+      .find(|target| target.name() == name)
+  }
+}
+
+impl fmt::Display for Target {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.name())
+  }
+}
+
+/// A rewrite lowering one feature of the language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Transform {
+  /// Arrow functions become function expressions, with `this` and `arguments` taken from the
+  /// enclosing function.
+  ArrowFunctions,
+  /// `let` and `const` become `var`, with a binding per iteration for the loops whose bindings
+  /// are captured.
+  BlockScoping,
+  /// Classes become constructor functions with their methods on the prototype.
+  Classes,
+  /// Template literals become string concatenations, tagged templates calls of the tag.
+  TemplateLiterals,
+  /// `{ a, b() {} }` becomes `{ a: a, b: function () {} }`.
+  ShorthandProperties,
+  /// Object literals with computed keys, such as `{ [key]: value }`, are built property by
+  /// property.
+  ComputedProperties,
+  /// Spread elements of array literals, calls and `new` expressions.
+  Spread,
+  /// Destructuring declarations, assignments and parameters.
+  Destructuring,
+  /// Default and rest parameters.
+  Parameters,
+  /// `for ... of` loops.
+  ForOf,
+  /// `a ** b` becomes `Math.pow(a, b)`.
+  Exponentiation,
+  /// Object spread `{ ...a }` and rest properties `{ ...rest } = a`.
+  ObjectRestSpread,
+  /// Optional chains, such as `a?.b()`.
+  OptionalChaining,
+  /// `a ?? b`.
+  NullishCoalescing,
+  /// `a ||= b`, `a &&= b` and `a ??= b`.
+  LogicalAssignment,
+  /// Class fields and static blocks, for classes that are kept.
+  ClassFields,
+}
+
+impl Transform {
+  /// All the transforms.
+  pub const ALL: [Self; 16] = [
+    Self::OptionalChaining,
+    Self::LogicalAssignment,
+    Self::NullishCoalescing,
+    Self::ObjectRestSpread,
+    Self::Exponentiation,
+    Self::TemplateLiterals,
+    Self::ShorthandProperties,
+    Self::ComputedProperties,
+    Self::ClassFields,
+    Self::Classes,
+    Self::Spread,
+    Self::ArrowFunctions,
+    Self::Parameters,
+    Self::ForOf,
+    Self::Destructuring,
+    Self::BlockScoping,
+  ];
+
+  /// Gets the edition that introduced the feature.
+  pub fn edition(self) -> Target {
+    match self {
+      Self::ArrowFunctions
+      | Self::BlockScoping
+      | Self::Classes
+      | Self::TemplateLiterals
+      | Self::ShorthandProperties
+      | Self::ComputedProperties
+      | Self::Spread
+      | Self::Destructuring
+      | Self::Parameters
+      | Self::ForOf => Target::Es2015,
+      Self::Exponentiation => Target::Es2016,
+      Self::ObjectRestSpread => Target::Es2018,
+      Self::OptionalChaining | Self::NullishCoalescing => Target::Es2020,
+      Self::LogicalAssignment => Target::Es2021,
+      Self::ClassFields => Target::Es2022,
+    }
+  }
+
+  /// Gets the name of the transform, such as `arrow-functions`.
+  pub fn name(self) -> &'static str {
+    match self {
+      Self::ArrowFunctions => "arrow-functions",
+      Self::BlockScoping => "block-scoping",
+      Self::Classes => "classes",
+      Self::TemplateLiterals => "template-literals",
+      Self::ShorthandProperties => "shorthand-properties",
+      Self::ComputedProperties => "computed-properties",
+      Self::Spread => "spread",
+      Self::Destructuring => "destructuring",
+      Self::Parameters => "parameters",
+      Self::ForOf => "for-of",
+      Self::Exponentiation => "exponentiation",
+      Self::ObjectRestSpread => "object-rest-spread",
+      Self::OptionalChaining => "optional-chaining",
+      Self::NullishCoalescing => "nullish-coalescing",
+      Self::LogicalAssignment => "logical-assignment",
+      Self::ClassFields => "class-fields",
+    }
+  }
+
+  /// Finds a transform by its name.
+  pub fn from_name(name: &str) -> Option<Self> {
+    Self::ALL
+      .iter()
+      .copied()
+      .find(|transform| transform.name() == name)
+  }
+}
+
+impl fmt::Display for Transform {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.name())
+  }
+}
+
+/// The transforms run by a [`Transpiler`], and how it prints the result.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+  target: Target,
+  /// The transforms enabled or disabled by hand, whatever the target.
+  overrides: BTreeMap<Transform, bool>,
+  output: codegen::Options,
+}
+
+impl Options {
+  /// Sets the edition the output runs on, enabling the transforms of the later editions.
+  pub fn with_target(mut self, target: Target) -> Self {
+    self.target = target;
+    self
+  }
+
+  /// Enables or disables a transform, whatever the target.
+  ///
+  /// Transforms lean on the ones that run after them: lowering a class produces a `let`
+  /// declaration, which only becomes a `var` with [`Transform::BlockScoping`].
+  pub fn with_transform(mut self, transform: Transform, enabled: bool) -> Self {
+    self.overrides.insert(transform, enabled);
+    self
+  }
+
+  /// Sets how the output is printed.
+  pub fn with_output(mut self, output: codegen::Options) -> Self {
+    self.output = output;
+    self
+  }
+
+  /// Gets the edition the output runs on.
+  pub fn target(&self) -> Target {
+    self.target
+  }
+
+  /// Returns `true` if a transform runs.
+  pub fn is_enabled(&self, transform: Transform) -> bool {
+    match self.overrides.get(&transform) {
+      Some(&enabled) => enabled,
+      None => transform.edition() > self.target,
+    }
+  }
+
+  /// Gets the transforms that run.
+  pub fn transforms(&self) -> impl Iterator<Item = Transform> + '_ {
+    Transform::ALL
+      .iter()
+      .copied()
+      .filter(move |&transform| self.is_enabled(transform))
+  }
+
+  /// Gets how the output is printed.
+  pub fn output(&self) -> &codegen::Options {
+    &self.output
+  }
+}
+
+/// Targets ES5, printing with [`codegen::Options::pretty`].
+impl Default for Options {
+  fn default() -> Self {
+    Self {
+      target: Target::Es5,
+      overrides: BTreeMap::new(),
+      output: codegen::Options::pretty(),
+    }
+  }
+}
+
+/// The state shared by the transforms of a program.
+#[derive(Debug)]
+struct Context {
+  names: Names,
+  /// The runtime helpers used so far, with the names they are declared with.
+  helpers: BTreeMap<Helper, Box<str>>,
+  /// The variables declared at the top of the program, such as the cached template objects.
+  globals: Vec<Box<str>>,
+}
+
+impl Context {
+  fn new(body: &StatementList) -> Self {
+    Self {
+      names: Names::new(body.items()),
+      helpers: BTreeMap::new(),
+      globals: Vec::new(),
+    }
+  }
+
+  /// Creates a name used nowhere in the program, after `base`.
+  fn fresh(&mut self, base: &str) -> Box<str> {
+    self.names.fresh(base)
+  }
+
+  /// Gets a reference to a runtime helper, which is added to the program with the helpers it
+  /// calls.
+  fn helper(&mut self, helper: Helper) -> Node {
+    for &dependency in helper.dependencies() {
+      self.helper(dependency);
+    }
+    if !self.helpers.contains_key(&helper) {
+      let name = self.names.fresh(helper.name());
+      self.helpers.insert(helper, name);
+    }
+    util::ident(&self.helpers[&helper])
+  }
+
+  /// Declares a variable at the top of the program.
+  fn global(&mut self, base: &str) -> Box<str> {
+    let name = self.fresh(base);
+    self.globals.push(name.clone());
+    name
+  }
+}
+
+/// Lowers syntax trees to an older edition of the language.
+#[derive(Debug, Clone, Default)]
+pub struct Transpiler {
+  options: Options,
+}
+
+impl Transpiler {
+  /// Creates a transpiler with the given options.
+  pub fn new(options: Options) -> Self {
+    Self { options }
+  }
+
+  /// Lowers a script.
+  pub fn script(&self, mut script: StatementList) -> Result<StatementList, TranspileError> {
+    self.rewrite(&mut script, false)?;
+    Ok(script)
+  }
+
+  /// Lowers a module, keeping its imports and exports.
+  pub fn module(&self, module: Module) -> Result<Module, TranspileError> {
+    let mut body = module.into_body();
+    self.rewrite(&mut body, true)?;
+    Ok(Module::new(body))
+  }
+
+  /// Runs the enabled transforms on the statements of a program, then adds the helpers and the
+  /// variables they use.
+  fn rewrite(&self, body: &mut StatementList, module: bool) -> Result<(), TranspileError> {
+    let prelude = self.lower(body, module)?;
+    body.replace_items(|items| {
+      let directives = util::directives(&items);
+      let mut items = items.into_iter();
+      let mut result: Vec<Node> = items.by_ref().take(directives).collect();
+      result.extend(prelude);
+      result.extend(items);
+      result
+    });
+    Ok(())
+  }
+
+  /// Runs the enabled transforms, returning the statements to add after the directives of the
+  /// program.
+  fn lower(&self, body: &mut StatementList, module: bool) -> Result<Vec<Node>, TranspileError> {
+    let options = &self.options;
+    check::run(body, module, options)?;
+    let enabled = |transform| options.is_enabled(transform);
+    let mut cx = Context::new(body);
+    if enabled(Transform::OptionalChaining)
+      || enabled(Transform::LogicalAssignment)
+      || enabled(Transform::NullishCoalescing)
+    {
+      optional::run(body, &mut cx, options);
+    }
+    if enabled(Transform::ObjectRestSpread)
+      || enabled(Transform::ShorthandProperties)
+      || enabled(Transform::ComputedProperties)
+    {
+      object::run(body, &mut cx, options);
+    }
+    if enabled(Transform::Exponentiation) {
+      exponent::run(body, &mut cx);
+    }
+    if enabled(Transform::TemplateLiterals) {
+      template::run(body, &mut cx);
+    }
+    if enabled(Transform::Classes) || enabled(Transform::ClassFields) {
+      class::run(body, &mut cx, options);
+    }
+    if enabled(Transform::Spread) {
+      spread::run(body, &mut cx);
+    }
+    if enabled(Transform::ArrowFunctions) {
+      arrow::run(body, &mut cx);
+    }
+    if enabled(Transform::Parameters) {
+      params::run(body, &mut cx);
+    }
+    if enabled(Transform::ForOf) {
+      for_of::run(body, &mut cx);
+    }
+    // Rest properties are lowered with the pattern holding them.
+    if enabled(Transform::Destructuring) || enabled(Transform::ObjectRestSpread) {
+      destructure::run(body, &mut cx, options);
+    }
+    if enabled(Transform::BlockScoping) {
+      block_scoping::run(body, &mut cx);
+    }
+
+    let mut prelude = helpers::declarations(&cx.helpers);
+    if !cx.globals.is_empty() {
+      let globals = mem::take(&mut cx.globals);
+      prelude.push(util::var(
+        globals.into_iter().map(|name| (name, None)).collect(),
+      ));
+    }
+    Ok(prelude)
+  }
+}
+
+/// An error preventing a program from being lowered.
+#[derive(Debug)]
+pub enum TranspileError {
+  /// The source could not be parsed.
+  Parse(ParseError),
+  /// The program uses a construct that no transform lowers, and that the target edition lacks.
+  Unsupported {
+    /// What the construct is, such as `generator functions`.
+    construct: &'static str,
+    /// The edition the output runs on.
+    target: Target,
+    /// Where the construct is, `None` if it was not parsed.
+    span: Option<Span>,
+  },
+  /// A constant is assigned.
+  ConstAssignment {
+    /// The name of the constant.
+    name: Box<str>,
+    /// Where it is assigned, `None` if it was not parsed.
+    span: Option<Span>,
+  },
+  /// A binding is read before its declaration runs.
+  UninitializedRead {
+    /// The name of the binding.
+    name: Box<str>,
+    /// Where it is read, `None` if it was not parsed.
+    span: Option<Span>,
+  },
+}
+
+impl fmt::Display for TranspileError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Parse(err) => write!(f, "could not parse the source: {}", err),
+      Self::Unsupported {
+        construct,
+        target,
+        span,
+      } => {
+        location(f, *span)?;
+        write!(f, "{} can not be lowered to {}", construct, target)
+      }
+      Self::ConstAssignment { name, span } => {
+        location(f, *span)?;
+        write!(f, "assignment to the constant `{}`", name)
+      }
+      Self::UninitializedRead { name, span } => {
+        location(f, *span)?;
+        write!(f, "`{}` is read before its declaration runs", name)
+      }
+    }
+  }
+}
+
+/// Writes where an error is, ahead of its message.
+fn location(f: &mut fmt::Formatter<'_>, span: Option<Span>) -> fmt::Result {
+  match span {
+    Some(span) => write!(f, "{}: ", span.start()),
+    None => Ok(()),
+  }
+}
+
+impl StdError for TranspileError {
+  fn source(&self) -> Option<&(dyn StdError + 'static)> {
+    match self {
+      Self::Parse(err) => Some(err),
+      _ => None,
+    }
+  }
+}
+
+impl From<ParseError> for TranspileError {
+  fn from(err: ParseError) -> Self {
+    Self::Parse(err)
+  }
+}
+
+/// Code lowered by a [`Transpiler`], with its source map.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transpiled {
+  code: String,
+  map: SourceMap,
+}
+
+impl Transpiled {
+  /// Gets the lowered code.
+  pub fn code(&self) -> &str {
+    &self.code
+  }
+
+  /// Consumes the result, returning the lowered code.
+  pub fn into_code(self) -> String {
+    self.code
+  }
+
+  /// Gets the source map from the lowered code to the source.
+  pub fn source_map(&self) -> &SourceMap {
+    &self.map
+  }
+}
+
+/// Lowers the source text of a script, `name` being the name of the source in the source map.
+pub fn transpile_script(
+  name: &str,
+  source: &str,
+  options: &Options,
+) -> Result<Transpiled, TranspileError> {
+  let script = Parser::new(source.as_bytes()).parse_script()?;
+  let transpiler = Transpiler::new(options.clone());
+  let mut body = script;
+  let prelude = transpiler.lower(&mut body, false)?;
+  Ok(print(
+    name,
+    source,
+    options,
+    body,
+    prelude,
+    |generator, body| generator.script(&body),
+  ))
+}
+
+/// Lowers the source text of a module, `name` being the name of the source in the source map.
+pub fn transpile_module(
+  name: &str,
+  source: &str,
+  options: &Options,
+) -> Result<Transpiled, TranspileError> {
+  let module = Parser::new(source.as_bytes()).parse_module()?;
+  let transpiler = Transpiler::new(options.clone());
+  let mut body = module.into_body();
+  let prelude = transpiler.lower(&mut body, true)?;
+  Ok(print(
+    name,
+    source,
+    options,
+    body,
+    prelude,
+    |generator, body| generator.module(&Module::new(body)),
+  ))
+}
+
+/// Prints a lowered program with its prelude, and maps it to the source.
+fn print<F>(
+  name: &str,
+  source: &str,
+  options: &Options,
+  mut body: StatementList,
+  prelude: Vec<Node>,
+  generate: F,
+) -> Transpiled
+where
+  F: FnOnce(&Generator<'_>, StatementList) -> String,
+{
+  let generator = Generator::new(options.output.clone());
+  // The prelude is not in the source, the tokens it is printed with are left unmapped.
+  let directives = util::directives(body.items());
+  let skipped = map::Skipped {
+    before: generator.script(&StatementList::from(body.items()[..directives].to_vec())),
+    skipped: generator.script(&StatementList::from(prelude.clone())),
+  };
+  body.replace_items(|items| {
+    let mut items = items.into_iter();
+    let mut result: Vec<Node> = items.by_ref().take(directives).collect();
+    result.extend(prelude);
+    result.extend(items);
+    result
+  });
+  let code = generate(&generator, body);
+  let map = map::source_map(name, source, &code, &skipped);
+  Transpiled { code, map }
+}
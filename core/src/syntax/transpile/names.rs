@@ -0,0 +1,101 @@
+//! The names of a program, to create new ones that clash with none of them.
+
+use crate::syntax::ast::{
+  node::{Class, FunctionDecl, FunctionExpr, Identifier, PropertyDefinition},
+  visitor::{visit, Control, Flow, NodePath, NodeRef, Path, Visitor},
+  Node,
+};
+use std::collections::HashSet;
+
+/// The names used in a program and the ones created since.
+#[derive(Debug, Default)]
+pub(super) struct Names {
+  used: HashSet<Box<str>>,
+}
+
+impl Names {
+  /// Collects the names of the bindings and the references of a program.
+  pub(super) fn new(statements: &[Node]) -> Self {
+    let mut names = Self::default();
+    let _ = Collector {
+      names: &mut names.used,
+    }
+    .visit_statements(statements, &mut Path::new());
+    names
+  }
+
+  /// Creates a name used nowhere else: `_base`, or `_base2`, `_base3` and so on if it is taken.
+  ///
+  /// Characters that can not be part of a name are left out of the base.
+  pub(super) fn fresh(&mut self, base: &str) -> Box<str> {
+    let base: String = base
+      .trim_start_matches('_')
+      .chars()
+      .filter(|&ch| ch == '$' || ch == '_' || ch.is_alphanumeric())
+      .collect();
+    let base = if base.is_empty() {
+      "ref".to_owned()
+    } else {
+      base
+    };
+    let mut name = format!("_{}", base);
+    let mut counter = 1;
+    while self.used.contains(name.as_str()) {
+      counter += 1;
+      name = format!("_{}{}", base, counter);
+    }
+    let name = name.into_boxed_str();
+    self.used.insert(name.clone());
+    name
+  }
+}
+
+/// Collects the names of a program.
+struct Collector<'n> {
+  names: &'n mut HashSet<Box<str>>,
+}
+
+impl Collector<'_> {
+  fn add(&mut self, name: &str) {
+    if !self.names.contains(name) {
+      self.names.insert(name.into());
+    }
+  }
+}
+
+impl<'ast> Visitor<'ast> for Collector<'_> {
+  fn enter(&mut self, node: NodeRef<'ast>, _path: &NodePath<'ast>) -> Control {
+    match node {
+      NodeRef::ImportSpecifier(specifier) => self.add(specifier.local()),
+      NodeRef::PropertyDefinition(PropertyDefinition::IdentifierReference(ident, _)) => {
+        self.add(ident.name())
+      }
+      _ => {}
+    }
+    Control::Continue
+  }
+
+  fn visit_identifier(&mut self, ident: &'ast Identifier, _path: &mut NodePath<'ast>) -> Flow {
+    self.add(ident.name());
+    Flow::Continue(())
+  }
+
+  fn visit_function_decl(&mut self, decl: &'ast FunctionDecl, path: &mut NodePath<'ast>) -> Flow {
+    self.add(decl.name());
+    visit::walk_function_decl(self, decl, path)
+  }
+
+  fn visit_function_expr(&mut self, expr: &'ast FunctionExpr, path: &mut NodePath<'ast>) -> Flow {
+    if let Some(name) = expr.name() {
+      self.add(name);
+    }
+    visit::walk_function_expr(self, expr, path)
+  }
+
+  fn visit_class(&mut self, class: &'ast Class, path: &mut NodePath<'ast>) -> Flow {
+    if let Some(name) = class.name() {
+      self.add(name);
+    }
+    visit::walk_class(self, class, path)
+  }
+}
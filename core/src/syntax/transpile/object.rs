@@ -0,0 +1,191 @@
+//! Object literals: spread properties, shorthand properties and methods, and computed keys.
+//!
+//! `{ a, ...b, c: 1 }` becomes `_objectSpread({ a: a }, b, { c: 1 })`. An object with computed
+//! keys is created with the properties before the first computed one, and gets the others one
+//! by one, in order: `{ a: 1, [b]: 2 }` becomes `(_obj = { a: 1 }, _defineProperty(_obj, b, 2),
+//! _obj)`.
+
+use super::{
+  helpers::Helper,
+  util::{self, Scopes},
+  Context, Options, Transform,
+};
+use crate::syntax::ast::{
+  node::{MethodDefinitionKind, Object, PropertyDefinition, PropertyName, StatementList},
+  visitor::{visit_mut, Flow, KindPath, Path, VisitorMut},
+  Const, Node,
+};
+
+/// Lowers the object literals of a program, as enabled by the options.
+pub(super) fn run(body: &mut StatementList, cx: &mut Context, options: &Options) {
+  let mut lowering = Lowering {
+    cx,
+    scopes: Scopes::default(),
+    spread: options.is_enabled(Transform::ObjectRestSpread),
+    shorthand: options.is_enabled(Transform::ShorthandProperties),
+    computed: options.is_enabled(Transform::ComputedProperties),
+  };
+  lowering.scopes.enter();
+  let _ = lowering.visit_statements(body.items_mut(), &mut Path::new());
+  lowering.scopes.exit(body);
+}
+
+/// Rewrites the object literals from the leaves up.
+struct Lowering<'cx> {
+  cx: &'cx mut Context,
+  scopes: Scopes,
+  spread: bool,
+  shorthand: bool,
+  computed: bool,
+}
+
+impl Lowering<'_> {
+  /// Lowers an object literal.
+  fn object(&mut self, properties: Vec<PropertyDefinition>) -> Node {
+    // Methods using `super` need the object literal as their home object.
+    let home = properties.iter().any(|property| match property {
+      PropertyDefinition::MethodDefinition(_, _, function, _) => {
+        util::uses_super(function.parameters(), function.body())
+      }
+      _ => false,
+    });
+    let properties = if self.shorthand && !home {
+      properties.into_iter().map(expand).collect()
+    } else {
+      properties
+    };
+    let has_spread = properties
+      .iter()
+      .any(|property| matches!(property, PropertyDefinition::SpreadObject(_, _)));
+    if self.spread && has_spread {
+      return self.spread(properties, home);
+    }
+    if !has_spread && !home {
+      return self.computed(properties);
+    }
+    Object::from(properties).into()
+  }
+
+  /// Lowers an object literal with spread properties into a call of `_objectSpread`.
+  fn spread(&mut self, properties: Vec<PropertyDefinition>, home: bool) -> Node {
+    let mut args = Vec::new();
+    let mut group = Vec::new();
+    for property in properties {
+      if let PropertyDefinition::SpreadObject(value, _) = &property {
+        if !group.is_empty() || args.is_empty() {
+          args.push(self.group(std::mem::take(&mut group), home));
+        }
+        args.push(value.clone());
+      } else {
+        group.push(property);
+      }
+    }
+    if !group.is_empty() {
+      args.push(self.group(group, home));
+    }
+    util::call(self.cx.helper(Helper::ObjectSpread), args)
+  }
+
+  /// Creates the object literal holding the properties between two spread properties.
+  fn group(&mut self, properties: Vec<PropertyDefinition>, home: bool) -> Node {
+    if home {
+      Object::from(properties).into()
+    } else {
+      self.computed(properties)
+    }
+  }
+
+  /// Lowers an object literal with computed keys, if enabled.
+  fn computed(&mut self, mut properties: Vec<PropertyDefinition>) -> Node {
+    let first = properties.iter().position(|property| {
+      matches!(
+        property,
+        PropertyDefinition::Property(PropertyName::Computed(_), _, _)
+          | PropertyDefinition::MethodDefinition(_, PropertyName::Computed(_), _, _)
+      )
+    });
+    let first = match first {
+      Some(first) if self.computed => first,
+      _ => return Object::from(properties).into(),
+    };
+    let rest = properties.split_off(first);
+    let obj = self.scopes.temp(self.cx, "obj");
+    let mut exprs = vec![util::assign(
+      util::ident(&obj),
+      Object::from(properties).into(),
+    )];
+    for property in rest {
+      let (key, value, kind) = match &property {
+        PropertyDefinition::IdentifierReference(ident, _) => {
+          (util::string(ident.name()), ident.clone().into(), None)
+        }
+        PropertyDefinition::Property(name, value, _) => (key(name), value.clone(), None),
+        PropertyDefinition::MethodDefinition(kind, name, function, _) => {
+          let function = function.clone().into();
+          match kind {
+            MethodDefinitionKind::Get => (key(name), function, Some("get")),
+            MethodDefinitionKind::Set => (key(name), function, Some("set")),
+            MethodDefinitionKind::Ordinary => (key(name), function, None),
+          }
+        }
+        PropertyDefinition::SpreadObject(_, _) => unreachable!("the object has no spread"),
+      };
+      exprs.push(match kind {
+        None => util::call(
+          self.cx.helper(Helper::DefineProperty),
+          vec![util::ident(&obj), key, value],
+        ),
+        Some(kind) => util::call(
+          util::member(util::ident("Object"), "defineProperty"),
+          vec![
+            util::ident(&obj),
+            key,
+            Object::from(vec![
+              PropertyDefinition::property(kind, value),
+              PropertyDefinition::property("configurable", Const::Bool(true)),
+              PropertyDefinition::property("enumerable", Const::Bool(true)),
+            ])
+            .into(),
+          ],
+        ),
+      });
+    }
+    exprs.push(util::ident(&obj));
+    util::sequence(exprs)
+  }
+}
+
+impl VisitorMut for Lowering<'_> {
+  util::function_scopes!();
+
+  fn visit_node(&mut self, node: &mut Node, path: &mut KindPath) -> Flow {
+    visit_mut::walk_node(self, node, path)?;
+    if let Node::Object(object) = node {
+      let properties = object.properties().to_vec();
+      *node = self.object(properties);
+    }
+    Flow::Continue(())
+  }
+}
+
+/// Expands a shorthand property or method: `a` becomes `a: a`, `b() {}` becomes
+/// `b: function () {}`.
+fn expand(property: PropertyDefinition) -> PropertyDefinition {
+  match &property {
+    PropertyDefinition::IdentifierReference(ident, _) => {
+      PropertyDefinition::property(ident.name().clone(), ident.clone())
+    }
+    PropertyDefinition::MethodDefinition(MethodDefinitionKind::Ordinary, name, function, _) => {
+      PropertyDefinition::property(name.clone(), function.clone())
+    }
+    _ => property,
+  }
+}
+
+/// Gets the expression of the key of a property.
+fn key(name: &PropertyName) -> Node {
+  match name {
+    PropertyName::Literal(name) => util::string(name),
+    PropertyName::Computed(key) => key.clone(),
+  }
+}
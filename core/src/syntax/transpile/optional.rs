@@ -0,0 +1,224 @@
+//! Optional chains, `??` and logical assignments.
+//!
+//! Each optional link tests the value it reads once, in a temporary variable when reading it
+//! again could differ: `a.b?.c` becomes `(_a$b = a.b) === null || _a$b === void 0 ? void 0 :
+//! _a$b.c`. The tests of the links of a chain are joined with `||`, so that the first link
+//! finding `null` or `undefined` skips the rest of the chain.
+
+use super::{
+  util::{self, Scopes},
+  Context, Options, Transform,
+};
+use crate::syntax::ast::{
+  node::{GetPrivateField, StatementList, UnaryOp},
+  operator::{self, AssignOp, CompOp, LogOp},
+  visitor::{visit_mut, Flow, KindPath, Path, VisitorMut},
+  Const, Node,
+};
+
+/// Lowers the optional chains, `??` and logical assignments of a program, as enabled by the
+/// options.
+pub(super) fn run(body: &mut StatementList, cx: &mut Context, options: &Options) {
+  let mut lowering = Lowering {
+    cx,
+    scopes: Scopes::default(),
+    chains: options.is_enabled(Transform::OptionalChaining),
+    nullish: options.is_enabled(Transform::NullishCoalescing),
+    logical: options.is_enabled(Transform::LogicalAssignment),
+  };
+  lowering.scopes.enter();
+  let _ = lowering.visit_statements(body.items_mut(), &mut Path::new());
+  lowering.scopes.exit(body);
+}
+
+/// A link of an optional chain, with whether it is optional.
+enum Link {
+  Field(Box<str>, bool),
+  Index(Node, bool),
+  Private(Box<str>, bool),
+  Call(Vec<Node>, bool),
+}
+
+impl Link {
+  fn is_optional(&self) -> bool {
+    match self {
+      Self::Field(_, optional)
+      | Self::Index(_, optional)
+      | Self::Private(_, optional)
+      | Self::Call(_, optional) => *optional,
+    }
+  }
+}
+
+/// Rewrites the expressions from the leaves up.
+struct Lowering<'cx> {
+  cx: &'cx mut Context,
+  scopes: Scopes,
+  chains: bool,
+  nullish: bool,
+  logical: bool,
+}
+
+impl Lowering<'_> {
+  /// Lowers the expression of an optional chain, deleting the property it ends with if
+  /// `delete` is `true`.
+  fn chain(&mut self, expr: Node, delete: bool) -> Node {
+    let mut links = Vec::new();
+    let mut base = expr;
+    loop {
+      let (link, next) = match &mut base {
+        Node::GetConstField(get) => (
+          Link::Field(get.field().into(), get.optional()),
+          util::take(get.obj_mut()),
+        ),
+        Node::GetField(get) => (
+          Link::Index(util::take(get.field_mut()), get.optional()),
+          util::take(get.obj_mut()),
+        ),
+        Node::GetPrivateField(get) => (
+          Link::Private(get.field().into(), get.optional()),
+          util::take(get.obj_mut()),
+        ),
+        Node::Call(call) => (
+          Link::Call(util::take_all(call.args_mut()), call.optional()),
+          util::take(call.expr_mut()),
+        ),
+        _ => break,
+      };
+      links.push(link);
+      base = next;
+    }
+    links.reverse();
+
+    let mut checks = Vec::new();
+    let mut current = base;
+    for link in links {
+      let optional = link.is_optional();
+      current = match link {
+        Link::Call(args, true) if is_member(&current) => {
+          // The called function gets the object it was read from as `this`.
+          let (callee, this) = self.method(current);
+          let (first, second) = self.scopes.memoize(self.cx, callee);
+          checks.push(util::is_nullish(first, second.clone()));
+          let mut args = args;
+          args.insert(0, this);
+          util::call(util::member(second, "call"), args)
+        }
+        link => {
+          if optional {
+            let (first, second) = self.scopes.memoize(self.cx, current);
+            checks.push(util::is_nullish(first, second.clone()));
+            current = second;
+          }
+          match link {
+            Link::Field(name, _) => util::member(current, &name),
+            Link::Index(field, _) => util::index(current, field),
+            Link::Private(name, _) => GetPrivateField::new(current, name).into(),
+            Link::Call(args, _) => util::call(current, args),
+          }
+        }
+      };
+    }
+
+    let (skipped, current) = if delete {
+      (
+        Const::Bool(true).into(),
+        UnaryOp::new(operator::UnaryOp::Delete, current).into(),
+      )
+    } else {
+      (util::void0(), current)
+    };
+    match checks
+      .into_iter()
+      .reduce(|lhs, rhs| util::binary(LogOp::Or, lhs, rhs))
+    {
+      Some(test) => util::conditional(test, skipped, current),
+      None => current,
+    }
+  }
+
+  /// Splits a property access about to be called into the access, reading the object once,
+  /// and the object.
+  fn method(&mut self, mut callee: Node) -> (Node, Node) {
+    let obj = match &mut callee {
+      Node::GetConstField(get) => get.obj_mut(),
+      Node::GetField(get) => get.obj_mut(),
+      Node::GetPrivateField(get) => get.obj_mut(),
+      _ => unreachable!("the callee is a property access"),
+    };
+    let (first, second) = match util::take(obj) {
+      // `super.a` reads from the prototype of the home object, but calls with `this`.
+      Node::Super(span) => (Node::Super(span), Node::this()),
+      obj => self.scopes.memoize(self.cx, obj),
+    };
+    *obj = first;
+    (callee, second)
+  }
+
+  /// Lowers `lhs ?? rhs`.
+  fn coalesce(&mut self, lhs: Node, rhs: Node) -> Node {
+    let (first, second) = self.scopes.memoize(self.cx, lhs);
+    let test = util::binary(
+      LogOp::And,
+      util::binary(CompOp::StrictNotEqual, first, Const::Null.into()),
+      util::binary(CompOp::StrictNotEqual, second.clone(), util::void0()),
+    );
+    util::conditional(test, second, rhs)
+  }
+
+  /// Lowers `lhs &&= rhs`, `lhs ||= rhs` or `lhs ??= rhs`: the assignment only happens when
+  /// the operator would evaluate its right hand side.
+  fn logical_assign(&mut self, op: AssignOp, lhs: Node, rhs: Node) -> Node {
+    let (first, second) = self.scopes.reference(self.cx, lhs);
+    let assignment = util::assign(second, rhs);
+    match op {
+      AssignOp::BoolAnd => util::binary(LogOp::And, first, assignment),
+      AssignOp::BoolOr => util::binary(LogOp::Or, first, assignment),
+      _ if self.nullish => self.coalesce(first, assignment),
+      _ => util::binary(LogOp::Coalesce, first, assignment),
+    }
+  }
+}
+
+impl VisitorMut for Lowering<'_> {
+  util::function_scopes!();
+
+  fn visit_node(&mut self, node: &mut Node, path: &mut KindPath) -> Flow {
+    // `delete a?.b` deletes nothing when `a` is nullish.
+    if let Node::UnaryOp(op) = node {
+      if let (operator::UnaryOp::Delete, true) = (op.op(), self.chains) {
+        if let Node::OptionalChain(chain) = op.target_mut() {
+          let flow = self.visit_node(chain.expr_mut(), path);
+          *node = self.chain(util::take(chain.expr_mut()), true);
+          return flow;
+        }
+      }
+    }
+    visit_mut::walk_node(self, node, path)?;
+    let lowered = match node {
+      Node::OptionalChain(chain) if self.chains => self.chain(util::take(chain.expr_mut()), false),
+      Node::BinOp(op) => match op.op() {
+        operator::BinOp::Log(LogOp::Coalesce) if self.nullish => {
+          self.coalesce(util::take(op.lhs_mut()), util::take(op.rhs_mut()))
+        }
+        operator::BinOp::Assign(
+          assign @ (AssignOp::BoolAnd | AssignOp::BoolOr | AssignOp::Coalesce),
+        ) if self.logical => {
+          self.logical_assign(assign, util::take(op.lhs_mut()), util::take(op.rhs_mut()))
+        }
+        _ => return Flow::Continue(()),
+      },
+      _ => return Flow::Continue(()),
+    };
+    *node = lowered;
+    Flow::Continue(())
+  }
+}
+
+/// Returns `true` if a node is a property access.
+fn is_member(node: &Node) -> bool {
+  matches!(
+    node,
+    Node::GetConstField(_) | Node::GetField(_) | Node::GetPrivateField(_)
+  )
+}
@@ -0,0 +1,111 @@
+//! Default and rest parameters.
+//!
+//! The defaults are assigned at the start of the body when the argument is `undefined`, and
+//! the rest parameter is sliced from `arguments`: `function f(a = 1, ...b) {}` becomes
+//! `function f(a) { if (a === void 0) { a = 1; } var b = Array.prototype.slice.call(arguments,
+//! 1); }`. Destructured parameters of such functions are declared in the body too.
+
+use super::{util, Context};
+use crate::syntax::ast::{
+  node::{
+    Block, Declaration, DeclarationList, FormalParameter, FunctionDecl, FunctionExpr, If, Pattern,
+    StatementList,
+  },
+  visitor::{visit_mut, Flow, KindPath, Path, VisitorMut},
+  Node, Sym,
+};
+
+/// Lowers the default and rest parameters of the functions of a program.
+pub(super) fn run(body: &mut StatementList, cx: &mut Context) {
+  let _ = Lowering { cx }.visit_statements(body.items_mut(), &mut Path::new());
+}
+
+/// Rewrites the parameters of functions.
+struct Lowering<'cx> {
+  cx: &'cx mut Context,
+}
+
+impl Lowering<'_> {
+  /// Lowers a list of parameters, returning the parameters that are kept and the statements
+  /// initializing the others, or `None` if the parameters are all kept.
+  fn params(&mut self, params: &[FormalParameter]) -> Option<(Vec<FormalParameter>, Vec<Node>)> {
+    if params
+      .iter()
+      .all(|param| param.init().is_none() && !param.is_rest_param())
+    {
+      return None;
+    }
+    let mut kept = Vec::new();
+    let mut prologue = Vec::new();
+    for (index, param) in params.iter().enumerate() {
+      if param.is_rest_param() {
+        let rest = util::call(
+          util::member(
+            util::member(util::member(util::ident("Array"), "prototype"), "slice"),
+            "call",
+          ),
+          vec![util::ident("arguments"), util::number(index)],
+        );
+        prologue.push(declare(param.target().clone(), rest));
+        continue;
+      }
+      match (param.target(), param.init()) {
+        (Pattern::Identifier(ident), None) => kept.push(util::param(ident.name())),
+        (Pattern::Identifier(ident), Some(init)) => {
+          kept.push(util::param(ident.name()));
+          let assign = util::assign(util::ident(ident.name()), init.clone());
+          prologue.push(
+            If::new::<_, _, Node, _>(
+              util::is_undefined(util::ident(ident.name())),
+              Block::from(vec![assign]),
+              None,
+            )
+            .into(),
+          );
+        }
+        (pattern, init) => {
+          let name = self.cx.fresh("ref");
+          kept.push(util::param(&name));
+          let value = match init {
+            Some(init) => util::conditional(
+              util::is_undefined(util::ident(&name)),
+              init.clone(),
+              util::ident(&name),
+            ),
+            None => util::ident(&name),
+          };
+          prologue.push(declare(pattern.clone(), value));
+        }
+      }
+    }
+    Some((kept, prologue))
+  }
+}
+
+impl VisitorMut for Lowering<'_> {
+  fn visit_function_decl(&mut self, decl: &mut FunctionDecl, path: &mut KindPath) -> Flow {
+    visit_mut::walk_function_decl(self, decl, path)?;
+    if let Some((params, prologue)) = self.params(decl.parameters()) {
+      let mut body = util::take_list(decl.body_list_mut());
+      util::prepend(&mut body, prologue);
+      *decl = FunctionDecl::new(decl.name(), params, body).with_kind(decl.kind());
+    }
+    Flow::Continue(())
+  }
+
+  fn visit_function_expr(&mut self, expr: &mut FunctionExpr, path: &mut KindPath) -> Flow {
+    visit_mut::walk_function_expr(self, expr, path)?;
+    if let Some((params, prologue)) = self.params(expr.parameters()) {
+      let mut body = util::take_list(expr.body_list_mut());
+      util::prepend(&mut body, prologue);
+      let name = expr.name().map(Sym::from);
+      *expr = FunctionExpr::new(name, params, body).with_kind(expr.kind());
+    }
+    Flow::Continue(())
+  }
+}
+
+/// Creates `var target = value;`.
+fn declare(target: Pattern, value: Node) -> Node {
+  DeclarationList::Var(vec![Declaration::new(target, Some(value))].into()).into()
+}
@@ -0,0 +1,138 @@
+//! Spread elements of array literals, calls and `new` expressions.
+//!
+//! The items are collected into an array with `concat`: `[a, ...b]` becomes
+//! `[a].concat(_toConsumableArray(b))`, and a call passes the array with `apply`, so that
+//! `o.f(...args)` becomes `(_o = o).f.apply(_o, _toConsumableArray(args))`.
+
+use super::{
+  helpers::Helper,
+  util::{self, Scopes},
+  Context,
+};
+use crate::syntax::ast::{
+  node::{ArrayDecl, New, StatementList},
+  visitor::{visit_mut, Flow, KindPath, Path, VisitorMut},
+  Const, Node,
+};
+
+/// Lowers the spread elements of a program.
+pub(super) fn run(body: &mut StatementList, cx: &mut Context) {
+  let mut lowering = Lowering {
+    cx,
+    scopes: Scopes::default(),
+  };
+  lowering.scopes.enter();
+  let _ = lowering.visit_statements(body.items_mut(), &mut Path::new());
+  lowering.scopes.exit(body);
+}
+
+/// Rewrites the spread elements from the leaves up.
+struct Lowering<'cx> {
+  cx: &'cx mut Context,
+  scopes: Scopes,
+}
+
+impl Lowering<'_> {
+  /// Creates an array of items, some of them spread.
+  fn array(&mut self, items: Vec<Node>) -> Node {
+    let mut parts = Vec::new();
+    let mut group = Vec::new();
+    for mut item in items {
+      if let Node::Spread(spread) = &mut item {
+        if !group.is_empty() {
+          parts.push(ArrayDecl::from(std::mem::take(&mut group)).into());
+        }
+        let value = util::take(spread.val_mut());
+        parts.push(util::call(
+          self.cx.helper(Helper::ToConsumableArray),
+          vec![value],
+        ));
+      } else {
+        group.push(item);
+      }
+    }
+    if !group.is_empty() {
+      parts.push(ArrayDecl::from(group).into());
+    }
+    let mut parts = parts.into_iter();
+    let first = parts
+      .next()
+      .unwrap_or_else(|| ArrayDecl::from(Vec::new()).into());
+    let rest = parts.collect::<Vec<_>>();
+    if rest.is_empty() {
+      first
+    } else {
+      util::call(util::member(first, "concat"), rest)
+    }
+  }
+
+  /// Lowers a call with spread arguments into a call of `apply`.
+  fn call(&mut self, mut callee: Node, args: Vec<Node>) -> Node {
+    let obj = match &mut callee {
+      Node::GetConstField(get) => Some(get.obj_mut()),
+      Node::GetField(get) => Some(get.obj_mut()),
+      Node::GetPrivateField(get) => Some(get.obj_mut()),
+      _ => None,
+    };
+    let this = match obj {
+      Some(obj) => {
+        let (first, second) = match util::take(obj) {
+          Node::Super(span) => (Node::Super(span), Node::this()),
+          node => self.scopes.memoize(self.cx, node),
+        };
+        *obj = first;
+        second
+      }
+      None => util::void0(),
+    };
+    let args = self.array(args);
+    util::call(util::member(callee, "apply"), vec![this, args])
+  }
+}
+
+impl VisitorMut for Lowering<'_> {
+  util::function_scopes!();
+
+  fn visit_node(&mut self, node: &mut Node, path: &mut KindPath) -> Flow {
+    visit_mut::walk_node(self, node, path)?;
+    let lowered = match node {
+      Node::ArrayDecl(array) if has_spread(array.elements()) => {
+        let items = util::take_all(array.elements_mut());
+        self.array(items)
+      }
+      // `super(...args)` is lowered with its class.
+      Node::Call(call)
+        if has_spread(call.args())
+          && !call.optional()
+          && !matches!(call.expr(), Node::Super(_)) =>
+      {
+        let callee = util::take(call.expr_mut());
+        let args = util::take_all(call.args_mut());
+        self.call(callee, args)
+      }
+      // `new C(...args)` creates the instance from a bound function.
+      Node::New(new) if has_spread(new.args()) => {
+        let constructor = util::take(new.expr_mut());
+        let mut args = vec![Const::Null.into()];
+        args.extend(util::take_all(new.args_mut()));
+        let args = self.array(args);
+        let bound = util::call(
+          util::member(
+            util::member(util::member(util::ident("Function"), "prototype"), "bind"),
+            "apply",
+          ),
+          vec![constructor, args],
+        );
+        New::new(bound, Vec::new()).into()
+      }
+      _ => return Flow::Continue(()),
+    };
+    *node = lowered;
+    Flow::Continue(())
+  }
+}
+
+/// Returns `true` if a list of items has spread elements.
+fn has_spread(items: &[Node]) -> bool {
+  items.iter().any(|item| matches!(item, Node::Spread(_)))
+}
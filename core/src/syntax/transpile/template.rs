@@ -0,0 +1,102 @@
+//! Template literals.
+//!
+//! `` `a${b}c` `` becomes `"a".concat(b, "c")`, which converts the substitutions to strings
+//! the way the template does, unlike `+`. A tagged template passes the tag its strings in an
+//! array created once, as in `` tag`a${b}` `` becoming `tag(_templateObject || (_templateObject
+//! = _taggedTemplateLiteral(["a", ""])), b)`.
+
+use super::{helpers::Helper, util, Context};
+use crate::syntax::ast::{
+  node::{ArrayDecl, StatementList, TemplateLit},
+  operator::LogOp,
+  visitor::{visit_mut, Flow, KindPath, Path, VisitorMut},
+  Node,
+};
+
+/// Lowers the template literals of a program.
+pub(super) fn run(body: &mut StatementList, cx: &mut Context) {
+  let _ = Lowering { cx }.visit_statements(body.items_mut(), &mut Path::new());
+}
+
+/// Rewrites the template literals from the leaves up.
+struct Lowering<'cx> {
+  cx: &'cx mut Context,
+}
+
+impl Lowering<'_> {
+  /// Lowers a tagged template into a call of the tag.
+  fn tagged(&mut self, tag: Node, template: &mut TemplateLit) -> Node {
+    let cooked = template
+      .quasis()
+      .iter()
+      .map(|quasi| quasi.cooked().map_or_else(util::void0, util::string))
+      .collect::<Vec<_>>();
+    let mut args = vec![ArrayDecl::from(cooked).into()];
+    if template
+      .quasis()
+      .iter()
+      .any(|quasi| quasi.cooked() != Some(quasi.raw()))
+    {
+      let raw = template
+        .quasis()
+        .iter()
+        .map(|quasi| util::string(quasi.raw()))
+        .collect::<Vec<_>>();
+      args.push(ArrayDecl::from(raw).into());
+    }
+    let strings = util::call(self.cx.helper(Helper::TaggedTemplateLiteral), args);
+
+    // The strings are the same array each time the template is evaluated.
+    let cache = self.cx.global("templateObject");
+    let strings = util::binary(
+      LogOp::Or,
+      util::ident(&cache),
+      util::assign(util::ident(&cache), strings),
+    );
+    let mut args = vec![strings];
+    args.extend(util::take_all(template.exprs_mut()));
+    util::call(tag, args)
+  }
+}
+
+impl VisitorMut for Lowering<'_> {
+  fn visit_node(&mut self, node: &mut Node, path: &mut KindPath) -> Flow {
+    visit_mut::walk_node(self, node, path)?;
+    let lowered = match node {
+      Node::TemplateLit(template) => concat(template),
+      Node::TaggedTemplate(tagged) => {
+        let tag = util::take(tagged.tag_mut());
+        self.tagged(tag, tagged.template_mut())
+      }
+      _ => return Flow::Continue(()),
+    };
+    *node = lowered;
+    Flow::Continue(())
+  }
+}
+
+/// Lowers an untagged template literal into a string concatenation.
+fn concat(template: &mut TemplateLit) -> Node {
+  let mut strings = template
+    .quasis()
+    .iter()
+    .map(|quasi| quasi.cooked().unwrap_or_default().to_owned())
+    .collect::<Vec<_>>()
+    .into_iter();
+  let head = util::string(&strings.next().unwrap_or_default());
+  let mut args = Vec::new();
+  for (expr, string) in util::take_all(template.exprs_mut())
+    .into_iter()
+    .zip(strings)
+  {
+    args.push(expr);
+    if !string.is_empty() {
+      args.push(util::string(&string));
+    }
+  }
+  if args.is_empty() {
+    head
+  } else {
+    util::call(util::member(head, "concat"), args)
+  }
+}
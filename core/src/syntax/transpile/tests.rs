@@ -0,0 +1,389 @@
+//! Tests for the transpiler.
+
+use super::{transpile_module, transpile_script, Options, Target, Transform};
+use crate::syntax::{ast::Position, codegen};
+
+/// Lowers a script to ES5, printing compact code.
+#[track_caller]
+fn script(js: &str) -> String {
+  lower(js, Options::default())
+}
+
+/// Lowers a script with the given options, printing compact code.
+#[track_caller]
+fn lower(js: &str, options: Options) -> String {
+  let options = options.with_output(codegen::Options::compact());
+  transpile_script("input.js", js, &options)
+    .unwrap_or_else(|err| panic!("failed to parse `{}`: {}", js, err))
+    .into_code()
+}
+
+/// Checks that the lowered code ends with `expected`, after the helpers it uses.
+#[track_caller]
+fn assert_lowered(js: &str, expected: &str, helpers: &[&str]) {
+  let code = script(js);
+  assert!(code.ends_with(expected), "{}", code);
+  for helper in helpers {
+    assert!(
+      code.contains(&format!("function {}(", helper)),
+      "missing `{}` in {}",
+      helper,
+      code
+    );
+  }
+}
+
+#[test]
+fn arrow_functions() {
+  assert_eq!(
+    script("var f = (a, b) => a + b; var g = () => { return 1; };"),
+    "var f=function(a,b){return a+b};var g=function(){return 1}"
+  );
+  assert_eq!(
+    script("function f() { return () => this.x + arguments[0]; }"),
+    "function f(){var _this=this,_arguments=arguments;return function(){return _this.x+_arguments[0]}}"
+  );
+}
+
+#[test]
+fn block_scoping() {
+  assert_eq!(
+    script("let a = 1; { let a = 2; f(a); } f(a);"),
+    "var a=1;{var _a=2;f(_a)}f(a)"
+  );
+  // Captured loop bindings get a function called each iteration.
+  assert_eq!(
+    script("for (let i = 0; i < 3; i++) { fns.push(() => i); }"),
+    "var _loop=function(i){fns.push(function(){return i})};for(var i=0;i<3;i++){_loop(i)}"
+  );
+  // Loops whose bindings are not captured keep their body.
+  assert_eq!(
+    script("for (let i = 0; i < 3; i++) { f(i); }"),
+    "for(var i=0;i<3;i++){f(i)}"
+  );
+  // Jumps out of the body are returned, and the writes to the binding copied back.
+  assert_eq!(
+    script(
+      "function g() { for (let i = 0; i < 3; i++) { var x = i; if (i) break; if (x) continue; \
+       fns.push(() => i); if (x > 1) return x; } return x; }"
+    ),
+    "function g(){var x,_loop=function(i){x=i;if(i)return\"break\";if(x)return;\
+     fns.push(function(){return i});if(x>1)return{v:x};};for(var i=0;i<3;i++){\
+     var _ret=_loop(i);if(_ret===\"break\")break;if(typeof _ret===\"object\")return _ret.v;}return x}"
+  );
+  assert_eq!(
+    script("for (let i = 0; i < 3; i++) { setTimeout(() => i); i += 1; }"),
+    "var _i,_loop=function(i){try{setTimeout(function(){return i});i+=1}finally{_i=i}};\
+     for(var i=0;i<3;i++){_loop(i);i=_i}"
+  );
+}
+
+#[test]
+fn classes() {
+  assert_lowered(
+    "class A extends B { constructor(x) { super(x); this.y = 1; } get z() { return super.z; } \
+     static s() {} }",
+    "var A=/*#__PURE__*/function(_Super){_inherits(A,_Super);function A(x){var _this;\
+     _classCallCheck(this,A);_this=_Super.call(this,x)||this,_this;_this.y=1;return _this}\
+     _createClass(A,[{key:\"z\",get:function(){return _Super.prototype.z}}],\
+     [{key:\"s\",value:function(){}}]);return A}(B)",
+    &[
+      "_classCallCheck",
+      "_createClass",
+      "_inherits",
+      "_setPrototypeOf",
+    ],
+  );
+  assert_lowered(
+    "class C { x = 1; static y = 2; m() {} }",
+    "var C=function(){function C(){_classCallCheck(this,C);_defineProperty(this,\"x\",1)}\
+     _createClass(C,[{key:\"m\",value:function(){}}]);_defineProperty(C,\"y\",2);return C}()",
+    &["_defineProperty"],
+  );
+}
+
+#[test]
+fn template_literals() {
+  assert_eq!(
+    script("var x = `a${b}c${d}`;"),
+    "var x=\"a\".concat(b,\"c\",d)"
+  );
+  assert_lowered(
+    "t = tag`a${b}c`;",
+    "var _templateObject;t=tag(_templateObject||\
+     (_templateObject=_taggedTemplateLiteral([\"a\",\"c\"])),b)",
+    &["_taggedTemplateLiteral"],
+  );
+}
+
+#[test]
+fn destructuring() {
+  assert_lowered(
+    "var { a, b: [c, d = 1], ...rest } = obj;",
+    "var a=obj.a,_obj$b=_slicedToArray(obj.b,2),c=_obj$b[0],_obj$b2=_obj$b[1],\
+     d=_obj$b2===void 0?1:_obj$b2,rest=_objectWithoutProperties(obj,[\"a\",\"b\"])",
+    &["_slicedToArray", "_objectWithoutProperties"],
+  );
+  assert_lowered(
+    "[a, b] = [b, a];",
+    "var _ref;_ref=_slicedToArray([b,a],2),a=_ref[0],b=_ref[1]",
+    &[],
+  );
+  // Assignments used as expressions evaluate to their right-hand side.
+  assert_eq!(script("x = ({ a } = f());"), "var _f;x=(_f=f(),a=_f.a,_f)");
+  assert_eq!(
+    script("try { f(); } catch ({ message }) { g(message); }"),
+    "try{f()}catch(_ref){var message=_ref.message;g(message)}"
+  );
+}
+
+#[test]
+fn parameters() {
+  assert_eq!(
+    script("const greet = (name = 'world') => `hello ${name}`;"),
+    "var greet=function(name){if(name===void 0){name=\"world\"}return\"hello \".concat(name)}"
+  );
+  assert_lowered(
+    "function h({ a, b }, [c], ...r) { return a + b + c + r.length; }",
+    "function h(_ref,_ref2){var a=_ref.a,b=_ref.b;var c=_slicedToArray(_ref2,1)[0];\
+     var r=Array.prototype.slice.call(arguments,2);return a+b+c+r.length}",
+    &["_slicedToArray"],
+  );
+}
+
+#[test]
+fn for_of() {
+  // The iterator is closed when the loop exits early.
+  assert_lowered(
+    "for (const [k, v] of entries) { f(k, v); }",
+    "{var _iterator=_createForOfIterator(entries),_step;\
+     try{for(;!(_step=_iterator.n()).done;){\
+     var _step$value=_slicedToArray(_step.value,2),k=_step$value[0],v=_step$value[1];f(k,v)}}\
+     catch(_err){_iterator.e(_err)}finally{_iterator.f()}}",
+    &["_createForOfIterator", "_slicedToArray"],
+  );
+  assert!(script("for (x of xs) if (x) break;").contains("it[\"return\"]()"));
+  // Labelled jumps out of nested loop functions are forwarded to the loop they target, which
+  // keeps its label.
+  assert_lowered(
+    "label: for (let i of a) { for (let j of b) { if (j) continue label; fns.push(() => i + j); } }",
+    "var _iterator,_step,_loop2=function(){var _loop=function(){var j=_step.value;\
+     if(j)return\"continue|label\";fns.push(function(){return i+j})};var i=_step2.value;\
+     {_iterator=_createForOfIterator(b);try{for(;!(_step=_iterator.n()).done;){\
+     var _ret=_loop();if(_ret===\"continue|label\")return\"continue|label\";}}\
+     catch(_err){_iterator.e(_err)}finally{_iterator.f()}}};\
+     {var _iterator2=_createForOfIterator(a),_step2;try{\
+     label:for(;!(_step2=_iterator2.n()).done;){var _ret2=_loop2();\
+     if(_ret2===\"continue|label\")continue label;}}\
+     catch(_err2){_iterator2.e(_err2)}finally{_iterator2.f()}}",
+    &[],
+  );
+}
+
+#[test]
+fn unsupported() {
+  let error = |js: &str, target: Target| {
+    let options = Options::default().with_target(target);
+    match transpile_script("input.js", js, &options) {
+      Ok(transpiled) => panic!("lowered `{}` to `{}`", js, transpiled.code()),
+      Err(err) => err.to_string(),
+    }
+  };
+  assert_eq!(
+    error("function f() {}\nfunction* g() {}", Target::Es5),
+    "2:1: generator functions can not be lowered to es5"
+  );
+  assert_eq!(
+    error("var o = { async m() {} };", Target::Es2015),
+    "1:18: async functions can not be lowered to es2015"
+  );
+  assert_eq!(
+    error("f(async () => 1);", Target::Es2016),
+    "1:3: async functions can not be lowered to es2016"
+  );
+  assert_eq!(
+    error("async function* f() {}", Target::Es2017),
+    "1:1: async generators can not be lowered to es2017"
+  );
+  assert_eq!(
+    error("class A { #x = 1; m() { return this.#x; } }", Target::Es5),
+    "1:1: private class members can not be lowered to es5"
+  );
+  assert_eq!(
+    error(
+      "async function f() { for await (x of xs); }",
+      Target::Es2017
+    ),
+    "1:22: async iteration can not be lowered to es2017"
+  );
+  let options = Options::default().with_target(Target::Es2017);
+  assert!(transpile_script("input.js", "async function f() { await g(); }", &options).is_ok());
+  let options = Options::default().with_target(Target::Es2021);
+  assert!(transpile_module("input.js", "await f();", &options).is_err());
+  let options = Options::default().with_target(Target::Es2022);
+  assert!(transpile_script("input.js", "class A { #x; }", &options).is_ok());
+
+  // Errors lowering would hide.
+  assert_eq!(
+    error("const a = 1;\nfunction f() { a++; }", Target::Es5),
+    "2:16: assignment to the constant `a`"
+  );
+  assert_eq!(
+    error("const [a] = b; ({ a } = c);", Target::Es5),
+    "1:19: assignment to the constant `a`"
+  );
+  assert_eq!(
+    error("f(a); let a = 1;", Target::Es5),
+    "1:3: `a` is read before its declaration runs"
+  );
+  assert_eq!(
+    error("function f(a = b, b) {}", Target::Es5),
+    "1:16: `b` is read before its declaration runs"
+  );
+  assert!(transpile_script(
+    "input.js",
+    "function f() { return a; } let a = 1;",
+    &Options::default()
+  )
+  .is_ok());
+  let options = Options::default().with_target(Target::Es2015);
+  assert!(transpile_script("input.js", "const a = 1; a = 2;", &options).is_ok());
+}
+
+#[test]
+fn spread() {
+  assert_lowered(
+    "f(...args); o.m(a, ...b); new C(...d); x = [1, ...e];",
+    "f.apply(void 0,_toConsumableArray(args));o.m.apply(o,[a].concat(_toConsumableArray(b)));\
+     new (Function.prototype.bind.apply(C,[null].concat(_toConsumableArray(d))))();\
+     x=[1].concat(_toConsumableArray(e))",
+    &["_toConsumableArray"],
+  );
+}
+
+#[test]
+fn object_literals() {
+  assert_lowered(
+    "o = { a, b() { return 1; }, [k]: v, ...s };",
+    "var _obj;o=_objectSpread((_obj={a:a,b:function(){return 1}},_defineProperty(_obj,k,v),_obj),s)",
+    &["_objectSpread", "_defineProperty"],
+  );
+}
+
+#[test]
+fn operators() {
+  assert_eq!(
+    script("x = y ** 2 ?? z;"),
+    "var _ref;x=(_ref=Math.pow(y,2))!==null&&_ref!==void 0?_ref:z"
+  );
+  assert_eq!(
+    script("y = a?.b.c(); z = a?.[0]?.(1);"),
+    "var _a;y=a===null||a===void 0?void 0:a.b.c();\
+     z=a===null||a===void 0||((_a=a[0])===null||_a===void 0)?void 0:_a.call(a,1)"
+  );
+  assert_eq!(
+    script("a ??= b; a ||= c; o.p &&= d;"),
+    "a!==null&&a!==void 0?a:a=b;a||(a=c);o.p&&(o.p=d)"
+  );
+}
+
+#[test]
+fn targets() {
+  let js = "const f = (x) => x ** 2 ?? [...x];";
+  assert_eq!(
+    lower(js, Options::default().with_target(Target::Es2022)),
+    "const f=x=>x**2??[...x]"
+  );
+  assert_eq!(
+    lower(js, Options::default().with_target(Target::Es2016)),
+    "const f=x=>{var _ref;return(_ref=x**2)!==null&&_ref!==void 0?_ref:[...x]}"
+  );
+  assert_eq!(
+    lower(js, Options::default().with_target(Target::Es2015)),
+    "const f=x=>{var _ref;return(_ref=Math.pow(x,2))!==null&&_ref!==void 0?_ref:[...x]}"
+  );
+  // Overrides win over the target.
+  let options = Options::default()
+    .with_transform(Transform::ArrowFunctions, false)
+    .with_transform(Transform::Exponentiation, false);
+  assert!(!options.is_enabled(Transform::ArrowFunctions));
+  assert!(options.is_enabled(Transform::BlockScoping));
+  assert!(lower(js, options).ends_with(
+    "var f=x=>{var _ref;return(_ref=x**2)!==null&&_ref!==void 0?_ref:_toConsumableArray(x)}"
+  ));
+  let options = Options::default()
+    .with_target(Target::Es2022)
+    .with_transform(Transform::ArrowFunctions, true);
+  assert_eq!(
+    options.transforms().collect::<Vec<_>>(),
+    [Transform::ArrowFunctions]
+  );
+
+  assert_eq!(Target::from_name("ES6"), Some(Target::Es2015));
+  assert_eq!(Target::from_name("es2020"), Some(Target::Es2020));
+  assert_eq!(Target::from_name("es3"), None);
+  for &transform in Transform::ALL.iter() {
+    assert_eq!(Transform::from_name(transform.name()), Some(transform));
+  }
+}
+
+#[test]
+fn modules() {
+  let options = Options::default().with_output(codegen::Options::compact());
+  let transpiled = transpile_module(
+    "input.js",
+    "import a from 'a'; export const b = () => a;",
+    &options,
+  )
+  .unwrap();
+  assert_eq!(
+    transpiled.code(),
+    "import a from\"a\";export var b=function(){return a}"
+  );
+}
+
+#[test]
+fn directives() {
+  // The helpers come after the directives of the program.
+  let code = script("'use strict'; f(...a);");
+  assert!(
+    code.starts_with("'use strict';function _toConsumableArray("),
+    "{}",
+    code
+  );
+}
+
+#[test]
+fn source_map() {
+  let source = "let total = 0;\nfor (const item of items) {\n  total = total + item;\n}\n";
+  let transpiled = transpile_script("input.js", source, &Options::default()).unwrap();
+  let map = transpiled.source_map();
+  assert_eq!(&*map.sources()[0], "input.js");
+  assert_eq!(map.source_content(0), Some(source));
+
+  // Looks up the first occurrence of some code.
+  let find = |needle: &str| {
+    let offset = transpiled.code().find(needle).unwrap();
+    let before = &transpiled.code()[..offset];
+    let line = before.matches('\n').count() as u32 + 1;
+    let column = (offset - before.rfind('\n').map_or(0, |line| line + 1)) as u32 + 1;
+    map.lookup(Position::new(line, column))
+  };
+  // The names of the source are traced back to their declarations and uses, even when the
+  // transforms move them.
+  let total = find("total = 0").unwrap();
+  assert_eq!(total.position(), Position::new(1, 5));
+  assert_eq!(total.name(), Some("total"));
+  assert_eq!(find("items)").unwrap().position(), Position::new(2, 20));
+  let item = find("item = ").unwrap();
+  assert_eq!(item.position(), Position::new(2, 12));
+  assert_eq!(item.name(), Some("item"));
+  assert_eq!(
+    find("total + item").unwrap().position(),
+    Position::new(3, 11)
+  );
+  assert_eq!(find("item;").unwrap().position(), Position::new(3, 19));
+  // The helpers and the variables of the loop have no source.
+  assert_eq!(find("function _createForOfIterator"), None);
+  assert_eq!(find("_step = _iterator"), None);
+}
@@ -0,0 +1,443 @@
+//! Building blocks of the transforms: node constructors, temporary variables and searches.
+
+use super::Context;
+use crate::syntax::ast::{
+  arena::ArenaBox,
+  node::{
+    Assign, BinOp, Block, Call, ConditionalOp, Declaration, DeclarationList, FormalParameter,
+    FunctionDecl, FunctionExpr, GetConstField, GetField, Identifier, IterableLoopInitializer,
+    Pattern, StatementList, UnaryOp,
+  },
+  operator::{self, CompOp, LogOp},
+  visitor::{visit, visit_mut, Flow, KindPath, NodePath, Path, Visitor, VisitorMut},
+  Const, Node,
+};
+use std::mem;
+
+/// Creates a reference to a name.
+pub(super) fn ident(name: &str) -> Node {
+  Identifier::from(name).into()
+}
+
+/// Takes a node out of the tree, leaving an empty node in its place.
+pub(super) fn take(node: &mut Node) -> Node {
+  mem::replace(node, Node::empty())
+}
+
+/// Takes the nodes of a list out of the tree.
+pub(super) fn take_all(nodes: &mut [Node]) -> Vec<Node> {
+  nodes.iter_mut().map(take).collect()
+}
+
+/// Takes a statement list out of the tree, leaving an empty list in its place.
+pub(super) fn take_list(list: &mut StatementList) -> StatementList {
+  let strict = list.strict();
+  mem::replace(list, StatementList::from(Vec::new())).with_strict(strict)
+}
+
+/// Creates a static property access, `obj.name`.
+pub(super) fn member(obj: Node, name: &str) -> Node {
+  GetConstField::new(obj, name).into()
+}
+
+/// Creates a computed property access, `obj[field]`.
+pub(super) fn index(obj: Node, field: Node) -> Node {
+  GetField::new(obj, field).into()
+}
+
+/// Creates a call.
+pub(super) fn call(callee: Node, args: Vec<Node>) -> Node {
+  Call::new(callee, args).into()
+}
+
+/// Creates a string literal.
+pub(super) fn string(value: &str) -> Node {
+  Const::from(value).into()
+}
+
+/// Creates a number literal.
+pub(super) fn number(value: usize) -> Node {
+  Const::Int(value as i32).into()
+}
+
+/// Creates `void 0`, which is `undefined` whatever the bindings in scope.
+pub(super) fn void0() -> Node {
+  UnaryOp::new(operator::UnaryOp::Void, Const::Int(0)).into()
+}
+
+/// Creates a binary operation.
+pub(super) fn binary<O>(op: O, lhs: Node, rhs: Node) -> Node
+where
+  O: Into<operator::BinOp>,
+{
+  BinOp::new(op, lhs, rhs).into()
+}
+
+/// Creates a conditional expression.
+pub(super) fn conditional(cond: Node, if_true: Node, if_false: Node) -> Node {
+  ConditionalOp::new(cond, if_true, if_false).into()
+}
+
+/// Creates a test of whether a value is `null` or `undefined`: `first === null || second ===
+/// void 0`, where `first` computes the value and `second` reads it again.
+pub(super) fn is_nullish(first: Node, second: Node) -> Node {
+  binary(
+    LogOp::Or,
+    binary(CompOp::StrictEqual, first, Const::Null.into()),
+    binary(CompOp::StrictEqual, second, void0()),
+  )
+}
+
+/// Creates a test of whether a value is `undefined`.
+pub(super) fn is_undefined(value: Node) -> Node {
+  binary(CompOp::StrictEqual, value, void0())
+}
+
+/// Turns an expression into an assignment target: a name or a property access.
+pub(super) fn pattern(target: Node) -> Pattern {
+  if let Node::Identifier(ident) = &target {
+    return ident.clone().into();
+  }
+  Pattern::Member(ArenaBox::new(target))
+}
+
+/// Creates an assignment to a name or a property access.
+pub(super) fn assign(target: Node, value: Node) -> Node {
+  Assign::new(pattern(target), value).into()
+}
+
+/// Creates a comma expression evaluating the expressions in order, or the only one.
+pub(super) fn sequence(exprs: Vec<Node>) -> Node {
+  exprs
+    .into_iter()
+    .reduce(|lhs, rhs| binary(operator::BinOp::Comma, lhs, rhs))
+    .unwrap_or_else(void0)
+}
+
+/// Creates a `var` statement.
+pub(super) fn var(declarations: Vec<(Box<str>, Option<Node>)>) -> Node {
+  DeclarationList::Var(
+    declarations
+      .into_iter()
+      .map(|(name, init)| Declaration::new(Identifier::from(name), init))
+      .collect::<Vec<_>>()
+      .into(),
+  )
+  .into()
+}
+
+/// Creates the statement binding the target of a `for ... in` or `for ... of` loop to the value
+/// of an iteration.
+pub(super) fn bind(init: &IterableLoopInitializer, value: Node) -> Node {
+  let declaration =
+    |pattern: &Pattern| vec![Declaration::new(pattern.clone(), Some(value.clone()))];
+  match init {
+    IterableLoopInitializer::Var(pattern) => {
+      DeclarationList::Var(declaration(pattern).into()).into()
+    }
+    IterableLoopInitializer::Let(pattern) => {
+      DeclarationList::Let(declaration(pattern).into()).into()
+    }
+    IterableLoopInitializer::Const(pattern) => {
+      DeclarationList::Const(declaration(pattern).into()).into()
+    }
+    IterableLoopInitializer::Pattern(pattern) => Assign::new(pattern.clone(), value).into(),
+  }
+}
+
+/// Adds a statement at the start of the body of a loop, making it a block if needed.
+pub(super) fn with_first(mut body: Node, first: Node) -> Node {
+  let mut statements = vec![first];
+  if let Node::Block(block) = &mut body {
+    statements.extend(
+      take_list(block.statement_list_mut())
+        .items()
+        .iter()
+        .cloned(),
+    );
+  } else {
+    statements.push(body);
+  }
+  Block::from(statements).into()
+}
+
+/// Creates a parameter with a name and no default value.
+pub(super) fn param(name: &str) -> FormalParameter {
+  FormalParameter::new(Declaration::new(Identifier::from(name), None), false)
+}
+
+/// Creates an anonymous function expression.
+pub(super) fn function<P, B>(params: P, body: B) -> Node
+where
+  P: Into<ArenaBox<[FormalParameter]>>,
+  B: Into<StatementList>,
+{
+  FunctionExpr::new(None, params, body).into()
+}
+
+/// Returns `true` if evaluating an expression again gives the same value, without side
+/// effects, as long as no code runs in between.
+pub(super) fn is_simple(node: &Node) -> bool {
+  match node {
+    Node::Identifier(_) | Node::This(_) => true,
+    // A regular expression literal creates a new object each time.
+    Node::Const(value, _) => !matches!(value, Const::RegExp(..)),
+    _ => false,
+  }
+}
+
+/// Gets a name describing an expression, the base of the name of a variable holding its value:
+/// `a$b` for `a.b`.
+pub(super) fn base_name(node: &Node) -> String {
+  match node {
+    Node::Identifier(ident) => ident.name().to_string(),
+    Node::This(_) => "this".to_owned(),
+    Node::GetConstField(get) => format!("{}${}", base_name(get.obj()), get.field()),
+    Node::GetField(get) => base_name(get.obj()),
+    Node::Call(call) => base_name(call.expr()),
+    _ => "ref".to_owned(),
+  }
+}
+
+/// Counts the directives at the start of a list of statements.
+pub(super) fn directives(items: &[Node]) -> usize {
+  items
+    .iter()
+    .take_while(|item| matches!(item, Node::Directive(_)))
+    .count()
+}
+
+/// Inserts statements at the start of a function body or a program, after its directives and
+/// the declarations of temporary variables.
+pub(super) fn prepend(body: &mut StatementList, nodes: Vec<Node>) {
+  if nodes.is_empty() {
+    return;
+  }
+  body.replace_items(|mut items| {
+    let mut at = directives(&items);
+    while items.get(at).is_some_and(is_temporary) {
+      at += 1;
+    }
+    items.splice(at..at, nodes);
+    items
+  });
+}
+
+/// Returns `true` if a statement only declares variables, set to nothing, `this` or
+/// `arguments`, as the transforms do at the start of functions.
+fn is_temporary(node: &Node) -> bool {
+  match node {
+    Node::DeclarationList(DeclarationList::Var(declarations), _) => {
+      declarations.iter().all(|declaration| {
+        matches!(declaration.target(), Pattern::Identifier(_))
+          && match declaration.init() {
+            None | Some(Node::This(_)) => true,
+            Some(Node::Identifier(ident)) => ident.name() == "arguments",
+            Some(_) => false,
+          }
+      })
+    }
+    _ => false,
+  }
+}
+
+/// The temporary variables of the functions being visited, declared at the start of their
+/// bodies.
+#[derive(Debug, Default)]
+pub(super) struct Scopes {
+  stack: Vec<Vec<(Box<str>, Option<Node>)>>,
+}
+
+impl Scopes {
+  /// Enters a function or a program.
+  pub(super) fn enter(&mut self) {
+    self.stack.push(Vec::new());
+  }
+
+  /// Leaves a function or a program, declaring its variables at the start of its body.
+  pub(super) fn exit(&mut self, body: &mut StatementList) {
+    let variables = self.stack.pop().expect("no scope was entered");
+    if !variables.is_empty() {
+      prepend(body, vec![var(variables)]);
+    }
+  }
+
+  /// Declares a variable of the current function.
+  pub(super) fn declare(&mut self, name: Box<str>, init: Option<Node>) {
+    self
+      .stack
+      .last_mut()
+      .expect("no scope was entered")
+      .push((name, init));
+  }
+
+  /// Declares a temporary variable with a new name.
+  pub(super) fn temp(&mut self, cx: &mut Context, base: &str) -> Box<str> {
+    let name = cx.fresh(base);
+    self.declare(name.clone(), None);
+    name
+  }
+
+  /// Evaluates an expression once for two uses, returning the expression for the first one,
+  /// which stores the value in a temporary variable if needed, and a read of the value for the
+  /// second.
+  pub(super) fn memoize(&mut self, cx: &mut Context, node: Node) -> (Node, Node) {
+    if is_simple(&node) {
+      return (node.clone(), node);
+    }
+    let name = self.temp(cx, &base_name(&node));
+    (assign(ident(&name), node), ident(&name))
+  }
+
+  /// Evaluates the object and the key of an assignment target once for two uses, as in
+  /// `(_a = a).b = _a.b + 1`.
+  pub(super) fn reference(&mut self, cx: &mut Context, mut target: Node) -> (Node, Node) {
+    match &mut target {
+      Node::GetConstField(get) => {
+        let (first, second) = self.memoize(cx, take(get.obj_mut()));
+        (member(first, get.field()), member(second, get.field()))
+      }
+      Node::GetField(get) => {
+        let (obj, obj_again) = self.memoize(cx, take(get.obj_mut()));
+        let (field, field_again) = self.memoize(cx, take(get.field_mut()));
+        (index(obj, field), index(obj_again, field_again))
+      }
+      _ => (target.clone(), target),
+    }
+  }
+}
+
+/// Implements the `visit_*` methods of the functions for a [`VisitorMut`] with a `scopes` field,
+/// so that the temporary variables of each function are declared in its body.
+macro_rules! function_scopes {
+  () => {
+    fn visit_function_decl(
+      &mut self,
+      decl: &mut $crate::syntax::ast::node::FunctionDecl,
+      path: &mut $crate::syntax::ast::visitor::KindPath,
+    ) -> $crate::syntax::ast::visitor::Flow {
+      self.scopes.enter();
+      let flow = $crate::syntax::ast::visitor::visit_mut::walk_function_decl(self, decl, path);
+      self.scopes.exit(decl.body_list_mut());
+      flow
+    }
+
+    fn visit_function_expr(
+      &mut self,
+      expr: &mut $crate::syntax::ast::node::FunctionExpr,
+      path: &mut $crate::syntax::ast::visitor::KindPath,
+    ) -> $crate::syntax::ast::visitor::Flow {
+      self.scopes.enter();
+      let flow = $crate::syntax::ast::visitor::visit_mut::walk_function_expr(self, expr, path);
+      self.scopes.exit(expr.body_list_mut());
+      flow
+    }
+
+    fn visit_arrow_function_decl(
+      &mut self,
+      arrow: &mut $crate::syntax::ast::node::ArrowFunctionDecl,
+      path: &mut $crate::syntax::ast::visitor::KindPath,
+    ) -> $crate::syntax::ast::visitor::Flow {
+      self.scopes.enter();
+      let flow =
+        $crate::syntax::ast::visitor::visit_mut::walk_arrow_function_decl(self, arrow, path);
+      self.scopes.exit(arrow.body_list_mut());
+      flow
+    }
+  };
+}
+
+pub(super) use function_scopes;
+
+/// Returns `true` if the code of a function has a node passing `test`, outside of the functions
+/// and classes it contains. Arrow functions are part of the function containing them.
+pub(super) fn contains<F>(params: &[FormalParameter], body: &[Node], test: F) -> bool
+where
+  F: Fn(&Node) -> bool,
+{
+  let mut finder = Finder { test };
+  let path = &mut Path::new();
+  params
+    .iter()
+    .any(|param| finder.visit_formal_parameter(param, path).is_break())
+    || finder.visit_statements(body, path).is_break()
+}
+
+/// Returns `true` if the code of a function refers to `super`, and so needs its home object.
+pub(super) fn uses_super(params: &[FormalParameter], body: &[Node]) -> bool {
+  contains(params, body, |node| matches!(node, Node::Super(_)))
+}
+
+/// Looks for a node of a function, see [`contains`].
+struct Finder<F> {
+  test: F,
+}
+
+impl<'ast, F> Visitor<'ast> for Finder<F>
+where
+  F: Fn(&Node) -> bool,
+{
+  fn visit_node(&mut self, node: &'ast Node, path: &mut NodePath<'ast>) -> Flow {
+    if (self.test)(node) {
+      return Flow::Break(());
+    }
+    match node {
+      Node::FunctionDecl(_) | Node::FunctionExpr(_) | Node::ClassDecl(_) | Node::ClassExpr(_) => {
+        Flow::Continue(())
+      }
+      _ => visit::walk_node(self, node, path),
+    }
+  }
+
+  fn visit_function_expr(&mut self, _expr: &'ast FunctionExpr, _path: &mut NodePath<'ast>) -> Flow {
+    Flow::Continue(())
+  }
+}
+
+/// Replaces `this` with a name, and `arguments` too if `arguments` is given, in code of a
+/// function outside of the functions it contains.
+pub(super) fn rebind(statements: &mut [Node], this: Option<&str>, arguments: Option<&str>) {
+  let _ = Rebinder { this, arguments }.visit_statements(statements, &mut Path::new());
+}
+
+/// Replaces `this` and `arguments`, see [`rebind`].
+pub(super) fn rebind_node(node: &mut Node, this: Option<&str>, arguments: Option<&str>) {
+  let _ = Rebinder { this, arguments }.visit_node(node, &mut Path::new());
+}
+
+/// Replaces `this` and `arguments`, see [`rebind`].
+struct Rebinder<'a> {
+  this: Option<&'a str>,
+  arguments: Option<&'a str>,
+}
+
+impl VisitorMut for Rebinder<'_> {
+  fn visit_node(&mut self, node: &mut Node, path: &mut KindPath) -> Flow {
+    match node {
+      Node::This(_) => {
+        if let Some(this) = self.this {
+          *node = ident(this);
+        }
+        Flow::Continue(())
+      }
+      Node::Identifier(ident) if ident.name() == "arguments" => {
+        if let Some(arguments) = self.arguments {
+          *ident = Identifier::from(arguments);
+        }
+        Flow::Continue(())
+      }
+      Node::FunctionDecl(_) | Node::FunctionExpr(_) | Node::ClassDecl(_) | Node::ClassExpr(_) => {
+        Flow::Continue(())
+      }
+      _ => visit_mut::walk_node(self, node, path),
+    }
+  }
+
+  fn visit_function_expr(&mut self, _expr: &mut FunctionExpr, _path: &mut KindPath) -> Flow {
+    Flow::Continue(())
+  }
+
+  fn visit_function_decl(&mut self, _decl: &mut FunctionDecl, _path: &mut KindPath) -> Flow {
+    Flow::Continue(())
+  }
+}